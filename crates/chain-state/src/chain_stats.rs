@@ -0,0 +1,547 @@
+//! Aggregated statistics of the canonical chain per day and per epoch.
+
+use crate::CanonStateNotification;
+use alloy_consensus::BlockHeader;
+use alloy_eips::merge::{EPOCH_SLOTS, SLOT_DURATION};
+use alloy_primitives::BlockNumber;
+use parking_lot::RwLock;
+use reth_execution_types::Chain;
+use reth_primitives_traits::{BlockBody, NodePrimitives};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+    time::Duration,
+};
+
+/// Number of seconds in a day.
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// The default number of days whose statistics are kept.
+pub const DEFAULT_CHAIN_STATS_DAYS: usize = 90;
+
+/// The default number of epochs whose statistics are kept, a bit more than 4 days.
+pub const DEFAULT_CHAIN_STATS_EPOCHS: usize = 1_024;
+
+/// A type that provides access to the [`ChainStats`] of the node.
+pub trait ChainStatsProvider: Send + Sync {
+    /// Returns the aggregated statistics of the canonical chain.
+    fn chain_stats(&self) -> ChainStats;
+}
+
+/// The period statistics are aggregated over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChainStatsPeriod {
+    /// A UTC day.
+    Day,
+    /// A beacon chain epoch.
+    Epoch,
+}
+
+/// The statistics of a single block that are aggregated into [`ChainStatsEntry`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BlockStats {
+    /// The block number.
+    pub number: BlockNumber,
+    /// The block timestamp.
+    pub timestamp: u64,
+    /// The number of transactions.
+    pub transactions: u64,
+    /// The gas used by all transactions.
+    pub gas_used: u64,
+    /// The gas limit.
+    pub gas_limit: u64,
+    /// The base fee per gas, zero before London.
+    pub base_fee_per_gas: u64,
+    /// The blob gas used, zero before Cancun.
+    pub blob_gas_used: u64,
+}
+
+impl BlockStats {
+    /// Returns the statistics of the block with the given header and number of transactions.
+    pub fn new(header: &impl BlockHeader, transactions: usize) -> Self {
+        Self {
+            number: header.number(),
+            timestamp: header.timestamp(),
+            transactions: transactions as u64,
+            gas_used: header.gas_used(),
+            gas_limit: header.gas_limit(),
+            base_fee_per_gas: header.base_fee_per_gas().unwrap_or_default(),
+            blob_gas_used: header.blob_gas_used().unwrap_or_default(),
+        }
+    }
+}
+
+/// The net number of accounts and storage slots created by a chain segment.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StateGrowth {
+    /// Created accounts minus destroyed accounts.
+    pub accounts: i64,
+    /// Storage slots set to a non-zero value minus storage slots cleared.
+    pub storage_slots: i64,
+}
+
+impl StateGrowth {
+    /// Returns the state growth of the chain segment.
+    ///
+    /// Storage wiped by self-destructs is not accounted for.
+    pub fn from_chain<N: NodePrimitives>(chain: &Chain<N>) -> Self {
+        let mut growth = Self::default();
+        for account in chain.execution_outcome().bundle.state.values() {
+            growth.accounts +=
+                account.info.is_some() as i64 - account.original_info.is_some() as i64;
+            for slot in account.storage.values() {
+                growth.storage_slots += !slot.present_value().is_zero() as i64 -
+                    !slot.original_value().is_zero() as i64;
+            }
+        }
+        growth
+    }
+}
+
+/// The aggregated statistics of a day or an epoch.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ChainStatsEntry {
+    /// The number of canonical blocks.
+    pub blocks: u64,
+    /// The number of transactions.
+    pub transactions: u64,
+    /// The gas used by all transactions.
+    pub gas_used: u64,
+    /// The sum of the gas limits.
+    pub gas_limit: u64,
+    /// The sum of the base fees per gas, see [`ChainStatsEntry::avg_base_fee_per_gas`].
+    pub base_fee_per_gas_total: u128,
+    /// The blob gas used.
+    pub blob_gas_used: u64,
+    /// The state growth. Attributed to the period of the last block of each chain segment.
+    pub state_growth: StateGrowth,
+}
+
+impl ChainStatsEntry {
+    /// Returns the average base fee per gas of the blocks.
+    pub const fn avg_base_fee_per_gas(&self) -> u64 {
+        if self.blocks == 0 {
+            return 0
+        }
+        (self.base_fee_per_gas_total / self.blocks as u128) as u64
+    }
+
+    fn add(&mut self, block: &BlockStats) {
+        self.blocks += 1;
+        self.transactions += block.transactions;
+        self.gas_used += block.gas_used;
+        self.gas_limit += block.gas_limit;
+        self.base_fee_per_gas_total += block.base_fee_per_gas as u128;
+        self.blob_gas_used += block.blob_gas_used;
+    }
+
+    fn remove(&mut self, block: &BlockStats) {
+        self.blocks = self.blocks.saturating_sub(1);
+        self.transactions = self.transactions.saturating_sub(block.transactions);
+        self.gas_used = self.gas_used.saturating_sub(block.gas_used);
+        self.gas_limit = self.gas_limit.saturating_sub(block.gas_limit);
+        self.base_fee_per_gas_total =
+            self.base_fee_per_gas_total.saturating_sub(block.base_fee_per_gas as u128);
+        self.blob_gas_used = self.blob_gas_used.saturating_sub(block.blob_gas_used);
+    }
+}
+
+/// The days and epochs whose statistics were changed by an update of [`ChainStats`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChainStatsChanges {
+    /// The changed days since the unix epoch.
+    pub days: BTreeSet<u64>,
+    /// The changed beacon chain epochs.
+    pub epochs: BTreeSet<u64>,
+}
+
+impl ChainStatsChanges {
+    /// Returns `true` if no statistics were changed.
+    pub fn is_empty(&self) -> bool {
+        self.days.is_empty() && self.epochs.is_empty()
+    }
+
+    /// Returns the changed periods of the given kind.
+    pub const fn periods(&self, period: ChainStatsPeriod) -> &BTreeSet<u64> {
+        match period {
+            ChainStatsPeriod::Day => &self.days,
+            ChainStatsPeriod::Epoch => &self.epochs,
+        }
+    }
+}
+
+/// Aggregates the statistics of canonical blocks per day and per epoch.
+///
+/// The statistics are updated incrementally from canonical state notifications, blocks of reorged
+/// chain segments are subtracted again, see [`ChainStats::on_canon_state`]. Only blocks that
+/// became canonical through the engine are included, blocks synced by the pipeline are not.
+///
+/// The statistics are kept in memory, persisting them is up to the caller: the periods changed by
+/// an update are returned, and previously persisted statistics can be loaded with
+/// [`ChainStats::restore`]. The number of the last recorded block is tracked, so blocks that
+/// become canonical again after a restart aren't counted twice.
+///
+/// This is cheap to clone, all clones share the same statistics.
+#[derive(Debug, Clone, Default)]
+pub struct ChainStats {
+    inner: Arc<RwLock<ChainStatsInner>>,
+}
+
+impl ChainStats {
+    /// Creates a new instance that keeps the statistics of at most `days` days and `epochs`
+    /// epochs.
+    pub fn new(days: usize, epochs: usize) -> Self {
+        let inner = ChainStatsInner { max_days: days, max_epochs: epochs, ..Default::default() };
+        Self { inner: Arc::new(RwLock::new(inner)) }
+    }
+
+    /// Sets the beacon chain genesis time that epochs are counted from.
+    pub fn set_genesis_time(&self, genesis_time: u64) {
+        self.inner.write().genesis_time = genesis_time;
+    }
+
+    /// Sets the duration of a slot and the number of slots per epoch, which determine the length
+    /// of an epoch.
+    pub fn set_slot_timing(&self, slot_duration: Duration, epoch_slots: u64) {
+        let mut inner = self.inner.write();
+        inner.slot_duration = slot_duration;
+        inner.epoch_slots = epoch_slots;
+    }
+
+    /// Returns the maximum number of periods of the given kind whose statistics are kept.
+    pub fn max_periods(&self, period: ChainStatsPeriod) -> usize {
+        let inner = self.inner.read();
+        match period {
+            ChainStatsPeriod::Day => inner.max_days,
+            ChainStatsPeriod::Epoch => inner.max_epochs,
+        }
+    }
+
+    /// Returns the number of the last recorded block.
+    pub fn head(&self) -> Option<BlockNumber> {
+        self.inner.read().head
+    }
+
+    /// Loads previously persisted statistics, replacing the current ones.
+    ///
+    /// `head` is the number of the last block included in the statistics.
+    pub fn restore(
+        &self,
+        head: Option<BlockNumber>,
+        days: impl IntoIterator<Item = (u64, ChainStatsEntry)>,
+        epochs: impl IntoIterator<Item = (u64, ChainStatsEntry)>,
+    ) {
+        let mut inner = self.inner.write();
+        inner.head = head;
+        inner.days = days.into_iter().collect();
+        inner.epochs = epochs.into_iter().collect();
+        inner.truncate();
+    }
+
+    /// Updates the statistics with the blocks of the canonical state notification, and returns the
+    /// changed periods.
+    pub fn on_canon_state<N: NodePrimitives>(
+        &self,
+        notification: &CanonStateNotification<N>,
+    ) -> ChainStatsChanges {
+        let mut changes = ChainStatsChanges::default();
+        if let Some(reverted) = notification.reverted() {
+            let blocks = reverted
+                .blocks_iter()
+                .map(|block| BlockStats::new(block.header(), block.body().transaction_count()))
+                .collect::<Vec<_>>();
+            self.revert_into(&blocks, StateGrowth::from_chain(&reverted), &mut changes);
+        }
+
+        let committed = notification.committed();
+        let blocks = committed
+            .blocks_iter()
+            .map(|block| BlockStats::new(block.header(), block.body().transaction_count()))
+            .collect::<Vec<_>>();
+        self.record_into(&blocks, StateGrowth::from_chain(&committed), &mut changes);
+        changes
+    }
+
+    /// Adds the blocks of a chain segment that became canonical, and returns the changed periods.
+    ///
+    /// Blocks at or below the last recorded block are skipped, they're already included.
+    pub fn record(&self, blocks: &[BlockStats], growth: StateGrowth) -> ChainStatsChanges {
+        let mut changes = ChainStatsChanges::default();
+        self.record_into(blocks, growth, &mut changes);
+        changes
+    }
+
+    /// Subtracts the blocks of a chain segment that is no longer canonical, and returns the
+    /// changed periods.
+    ///
+    /// Blocks above the last recorded block are skipped, they were never included.
+    pub fn revert(&self, blocks: &[BlockStats], growth: StateGrowth) -> ChainStatsChanges {
+        let mut changes = ChainStatsChanges::default();
+        self.revert_into(blocks, growth, &mut changes);
+        changes
+    }
+
+    fn record_into(
+        &self,
+        blocks: &[BlockStats],
+        growth: StateGrowth,
+        changes: &mut ChainStatsChanges,
+    ) {
+        let mut inner = self.inner.write();
+        let head = inner.head;
+        let blocks = match head {
+            Some(head) => &blocks[blocks.partition_point(|block| block.number <= head)..],
+            None => blocks,
+        };
+        let Some(last) = blocks.last() else { return };
+
+        for block in blocks {
+            let (day, epoch) = inner.periods(block.timestamp);
+            inner.days.entry(day).or_default().add(block);
+            inner.epochs.entry(epoch).or_default().add(block);
+            changes.days.insert(day);
+            changes.epochs.insert(epoch);
+        }
+        inner.apply_growth(blocks, growth, 1);
+        inner.head = Some(last.number);
+        inner.truncate();
+    }
+
+    fn revert_into(
+        &self,
+        blocks: &[BlockStats],
+        growth: StateGrowth,
+        changes: &mut ChainStatsChanges,
+    ) {
+        let mut inner = self.inner.write();
+        let Some(head) = inner.head else { return };
+        let blocks = &blocks[..blocks.partition_point(|block| block.number <= head)];
+        let Some(first) = blocks.first() else { return };
+
+        for block in blocks {
+            let (day, epoch) = inner.periods(block.timestamp);
+            if let Some(entry) = inner.days.get_mut(&day) {
+                entry.remove(block);
+            }
+            if let Some(entry) = inner.epochs.get_mut(&epoch) {
+                entry.remove(block);
+            }
+            changes.days.insert(day);
+            changes.epochs.insert(epoch);
+        }
+        inner.apply_growth(blocks, growth, -1);
+        inner.head = first.number.checked_sub(1);
+
+        inner.days.retain(|_, entry| entry.blocks > 0);
+        inner.epochs.retain(|_, entry| entry.blocks > 0);
+    }
+
+    /// Returns the statistics of the given day or epoch.
+    pub fn entry(&self, period: ChainStatsPeriod, number: u64) -> Option<ChainStatsEntry> {
+        let inner = self.inner.read();
+        match period {
+            ChainStatsPeriod::Day => inner.days.get(&number).copied(),
+            ChainStatsPeriod::Epoch => inner.epochs.get(&number).copied(),
+        }
+    }
+
+    /// Returns the statistics of at most `limit` of the most recent periods, newest first.
+    ///
+    /// Each entry is paired with the number of its day since the unix epoch or the number of its
+    /// beacon chain epoch.
+    pub fn entries(&self, period: ChainStatsPeriod, limit: usize) -> Vec<(u64, ChainStatsEntry)> {
+        let inner = self.inner.read();
+        let entries = match period {
+            ChainStatsPeriod::Day => &inner.days,
+            ChainStatsPeriod::Epoch => &inner.epochs,
+        };
+        entries.iter().rev().take(limit).map(|(period, entry)| (*period, *entry)).collect()
+    }
+
+    /// Returns the timestamp at which the given day or epoch starts.
+    pub fn period_start(&self, period: ChainStatsPeriod, number: u64) -> u64 {
+        match period {
+            ChainStatsPeriod::Day => number * SECONDS_PER_DAY,
+            ChainStatsPeriod::Epoch => {
+                let inner = self.inner.read();
+                inner.genesis_time + number * inner.epoch_duration()
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct ChainStatsInner {
+    /// The beacon chain genesis time in seconds.
+    genesis_time: u64,
+    /// The duration of a slot.
+    slot_duration: Duration,
+    /// The number of slots per epoch.
+    epoch_slots: u64,
+    /// The maximum number of days to keep.
+    max_days: usize,
+    /// The maximum number of epochs to keep.
+    max_epochs: usize,
+    /// The number of the last recorded block.
+    head: Option<BlockNumber>,
+    /// The statistics per day since the unix epoch.
+    days: BTreeMap<u64, ChainStatsEntry>,
+    /// The statistics per beacon chain epoch.
+    epochs: BTreeMap<u64, ChainStatsEntry>,
+}
+
+impl ChainStatsInner {
+    /// Returns the duration of an epoch in seconds.
+    fn epoch_duration(&self) -> u64 {
+        self.slot_duration.as_secs().max(1) * self.epoch_slots.max(1)
+    }
+
+    /// Returns the day and the epoch of the timestamp.
+    fn periods(&self, timestamp: u64) -> (u64, u64) {
+        (
+            timestamp / SECONDS_PER_DAY,
+            timestamp.saturating_sub(self.genesis_time) / self.epoch_duration(),
+        )
+    }
+
+    /// Attributes the state growth to the periods of the last block.
+    fn apply_growth(&mut self, blocks: &[BlockStats], growth: StateGrowth, sign: i64) {
+        let Some(last) = blocks.last() else { return };
+        let (day, epoch) = self.periods(last.timestamp);
+        for entry in [self.days.get_mut(&day), self.epochs.get_mut(&epoch)].into_iter().flatten() {
+            entry.state_growth.accounts += sign * growth.accounts;
+            entry.state_growth.storage_slots += sign * growth.storage_slots;
+        }
+    }
+
+    /// Drops the oldest periods beyond the maximum number of periods to keep.
+    fn truncate(&mut self) {
+        while self.days.len() > self.max_days {
+            self.days.pop_first();
+        }
+        while self.epochs.len() > self.max_epochs {
+            self.epochs.pop_first();
+        }
+    }
+}
+
+impl Default for ChainStatsInner {
+    fn default() -> Self {
+        Self {
+            genesis_time: 0,
+            slot_duration: SLOT_DURATION,
+            epoch_slots: EPOCH_SLOTS,
+            max_days: DEFAULT_CHAIN_STATS_DAYS,
+            max_epochs: DEFAULT_CHAIN_STATS_EPOCHS,
+            head: None,
+            days: BTreeMap::new(),
+            epochs: BTreeMap::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(timestamp: u64, transactions: u64, base_fee_per_gas: u64) -> BlockStats {
+        BlockStats {
+            // one block per slot
+            number: timestamp / SLOT_DURATION.as_secs(),
+            timestamp,
+            transactions,
+            gas_used: transactions * 21_000,
+            gas_limit: 30_000_000,
+            base_fee_per_gas,
+            blob_gas_used: 0,
+        }
+    }
+
+    #[test]
+    fn aggregates_per_day_and_epoch() {
+        let stats = ChainStats::default();
+        let epoch_duration = SLOT_DURATION.as_secs() * EPOCH_SLOTS;
+        stats.record(&[block(0, 2, 10), block(12, 4, 20)], StateGrowth::default());
+        stats
+            .record(&[block(epoch_duration, 1, 30)], StateGrowth { accounts: 2, storage_slots: 5 });
+        stats.record(&[block(SECONDS_PER_DAY, 3, 40)], StateGrowth::default());
+
+        let days = stats.entries(ChainStatsPeriod::Day, 10);
+        assert_eq!(days.len(), 2);
+        assert_eq!(days[0].0, 1);
+        assert_eq!(days[1].0, 0);
+        assert_eq!(days[1].1.blocks, 3);
+        assert_eq!(days[1].1.transactions, 7);
+        assert_eq!(days[1].1.avg_base_fee_per_gas(), 20);
+        assert_eq!(days[1].1.state_growth, StateGrowth { accounts: 2, storage_slots: 5 });
+
+        let epochs = stats.entries(ChainStatsPeriod::Epoch, 2);
+        assert_eq!(epochs.len(), 2);
+        assert_eq!(epochs[1].0, 1);
+        assert_eq!(epochs[1].1.blocks, 1);
+        assert_eq!(stats.period_start(ChainStatsPeriod::Epoch, 1), epoch_duration);
+    }
+
+    #[test]
+    fn revert_subtracts_blocks() {
+        let stats = ChainStats::default();
+        stats.record(&[block(0, 2, 10)], StateGrowth::default());
+        stats.record(&[block(12, 4, 20)], StateGrowth { accounts: 1, storage_slots: 3 });
+        stats.revert(&[block(12, 4, 20)], StateGrowth { accounts: 1, storage_slots: 3 });
+
+        let days = stats.entries(ChainStatsPeriod::Day, 10);
+        assert_eq!(days.len(), 1);
+        assert_eq!(days[0].1.blocks, 1);
+        assert_eq!(days[0].1.transactions, 2);
+        assert_eq!(days[0].1.state_growth, StateGrowth::default());
+
+        stats.revert(&[block(0, 2, 10)], StateGrowth::default());
+        assert!(stats.entries(ChainStatsPeriod::Day, 10).is_empty());
+    }
+
+    #[test]
+    fn keeps_most_recent_periods() {
+        let stats = ChainStats::new(2, 2);
+        for day in 0..4 {
+            stats.record(&[block(day * SECONDS_PER_DAY, 1, 0)], StateGrowth::default());
+        }
+        let days = stats.entries(ChainStatsPeriod::Day, 10);
+        assert_eq!(days.iter().map(|(day, _)| *day).collect::<Vec<_>>(), vec![3, 2]);
+    }
+
+    #[test]
+    fn skips_recorded_blocks() {
+        let stats = ChainStats::default();
+        let changes = stats.record(&[block(0, 2, 10), block(12, 4, 20)], StateGrowth::default());
+        assert_eq!(changes.days, BTreeSet::from([0]));
+        assert_eq!(stats.head(), Some(1));
+
+        // blocks at or below the head are already included
+        let changes = stats.record(&[block(12, 4, 20), block(24, 1, 30)], StateGrowth::default());
+        assert_eq!(changes.epochs, BTreeSet::from([0]));
+        assert_eq!(stats.entry(ChainStatsPeriod::Day, 0).unwrap().blocks, 3);
+        assert_eq!(stats.head(), Some(2));
+
+        // blocks above the head were never included
+        stats.revert(&[block(24, 1, 30), block(36, 1, 30)], StateGrowth::default());
+        assert_eq!(stats.entry(ChainStatsPeriod::Day, 0).unwrap().blocks, 2);
+        assert_eq!(stats.head(), Some(1));
+
+        let restored = ChainStats::default();
+        restored.restore(stats.head(), stats.entries(ChainStatsPeriod::Day, 10), []);
+        assert!(restored.record(&[block(12, 4, 20)], StateGrowth::default()).is_empty());
+        assert_eq!(restored.entry(ChainStatsPeriod::Day, 0).unwrap().blocks, 2);
+    }
+
+    #[test]
+    fn epochs_use_slot_timing() {
+        let stats = ChainStats::default();
+        stats.set_genesis_time(100);
+        stats.set_slot_timing(Duration::from_secs(2), 8);
+        stats.record(&[block(100, 1, 0), block(116, 1, 0)], StateGrowth::default());
+
+        let epochs = stats.entries(ChainStatsPeriod::Epoch, 10);
+        assert_eq!(epochs.iter().map(|(epoch, _)| *epoch).collect::<Vec<_>>(), vec![1, 0]);
+        assert_eq!(stats.period_start(ChainStatsPeriod::Epoch, 1), 116);
+    }
+}
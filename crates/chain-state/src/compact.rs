@@ -0,0 +1,355 @@
+//! Compaction of the execution outputs of in-memory blocks.
+
+use alloy_primitives::map::HashMap;
+use reth_execution_types::BlockExecutionOutput;
+use reth_primitives_traits::Account;
+use revm_database::{
+    states::reverts::{AccountInfoRevert, AccountRevert, RevertToSlot, Reverts},
+    AccountStatus, BundleAccount, BundleState,
+};
+use std::borrow::Cow;
+
+/// Compacts the given execution output in place.
+///
+/// The compacted output still serves state reads from the in-memory overlay, but its reverts are
+/// dropped. They're the largest part of the output after the state itself, and only needed to
+/// write or read the changesets of the block, so they're rebuilt lazily from the original account
+/// infos and storage values of the bundle, see [`execution_output_reverts`]. The original values
+/// are stored inline in the accounts and storage slots of the bundle, so they're kept.
+///
+/// Besides the reverts, compaction drops:
+///  - bytecodes of account infos that are also stored in the contracts of the bundle, from where
+///    they're read by [`BundleState::bytecode`],
+///  - spare capacity of the hash maps and vectors, which are grown during execution.
+///
+/// The output is expected to be the output of a single block.
+pub fn compact_execution_output<R>(output: &mut BlockExecutionOutput<R>) {
+    let BundleState { state, contracts, reverts, reverts_size, .. } = &mut output.state;
+
+    *reverts = Reverts::default();
+    *reverts_size = 0;
+
+    contracts.shrink_to_fit();
+    state.shrink_to_fit();
+    for account in state.values_mut() {
+        for info in [&mut account.info, &mut account.original_info].into_iter().flatten() {
+            if contracts.contains_key(&info.code_hash) {
+                info.code = None;
+            }
+        }
+        account.storage.shrink_to_fit();
+    }
+}
+
+/// Returns `true` if the reverts of the given execution output were dropped by
+/// [`compact_execution_output`].
+pub fn is_compacted<R>(output: &BlockExecutionOutput<R>) -> bool {
+    output.state.reverts.is_empty() && !output.state.state.is_empty()
+}
+
+/// Returns the reverts of the given execution output, rebuilding them if they were dropped by
+/// [`compact_execution_output`].
+pub fn execution_output_reverts<R>(output: &BlockExecutionOutput<R>) -> Cow<'_, Reverts> {
+    if is_compacted(output) {
+        Cow::Owned(rebuild_reverts(&output.state))
+    } else {
+        Cow::Borrowed(&output.state.reverts)
+    }
+}
+
+/// Returns the given execution output with its reverts, rehydrating it if it was compacted by
+/// [`compact_execution_output`].
+pub fn rehydrate_execution_output<R: Clone>(
+    output: &BlockExecutionOutput<R>,
+) -> Cow<'_, BlockExecutionOutput<R>> {
+    if !is_compacted(output) {
+        return Cow::Borrowed(output)
+    }
+
+    let mut rehydrated = output.clone();
+    rehydrated.state.reverts = rebuild_reverts(&output.state);
+    rehydrated.state.reverts_size = rehydrated.state.reverts.iter().map(Vec::len).sum();
+    Cow::Owned(rehydrated)
+}
+
+/// Rebuilds the reverts of a single block bundle from the original account infos and storage
+/// values of its accounts.
+fn rebuild_reverts(bundle: &BundleState) -> Reverts {
+    let mut reverts = bundle
+        .state
+        .iter()
+        .filter_map(|(address, account)| Some((*address, account_revert(account)?)))
+        .collect::<Vec<_>>();
+    reverts.sort_unstable_by_key(|(address, _)| *address);
+    Reverts::new(vec![reverts])
+}
+
+/// Returns the revert of the given account, or `None` if the account wasn't changed.
+///
+/// Storage of destroyed accounts is wiped, so their changed slots are reverted to the wiped
+/// storage, like revm does.
+fn account_revert(account: &BundleAccount) -> Option<AccountRevert> {
+    let info_changed = account.info.as_ref().map(Account::from) !=
+        account.original_info.as_ref().map(Account::from);
+    let info = if info_changed {
+        account
+            .original_info
+            .clone()
+            .map_or(AccountInfoRevert::DeleteIt, AccountInfoRevert::RevertTo)
+    } else {
+        AccountInfoRevert::DoNothing
+    };
+
+    let wipe_storage = account.status.was_destroyed();
+    let storage = account
+        .storage
+        .iter()
+        .filter(|(_, slot)| slot.is_changed())
+        .map(|(key, slot)| {
+            let revert = if wipe_storage {
+                RevertToSlot::Destroyed
+            } else {
+                RevertToSlot::Some(slot.previous_or_original_value)
+            };
+            (*key, revert)
+        })
+        .collect::<HashMap<_, _>>();
+
+    if !info_changed && storage.is_empty() && !wipe_storage {
+        return None
+    }
+
+    let previous_status = if account.original_info.is_some() {
+        AccountStatus::Loaded
+    } else {
+        AccountStatus::LoadedNotExisting
+    };
+    Some(AccountRevert { account: info, storage, previous_status, wipe_storage })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{keccak256, Address, Bytes, U256};
+    use revm_database::{states::StorageSlot, OriginalValuesKnown};
+    use revm_state::{AccountInfo, Bytecode};
+
+    fn bundle_state() -> BundleState {
+        let changed = Address::with_last_byte(1);
+        let destroyed = Address::with_last_byte(2);
+        let created = Address::with_last_byte(3);
+        let info = AccountInfo { balance: U256::from(10), nonce: 1, ..Default::default() };
+        let code = Bytecode::new_raw(Bytes::from_static(&[0x60, 0x00]));
+        let code_hash = keccak256(code.original_bytes());
+        let contract =
+            AccountInfo { code_hash, code: Some(code.clone()), ..AccountInfo::default() };
+
+        let mut bundle = BundleState::default();
+        bundle.state.insert(
+            changed,
+            BundleAccount::new(
+                Some(AccountInfo::default()),
+                Some(info.clone()),
+                [
+                    (U256::from(2), StorageSlot::new_changed(U256::ZERO, U256::from(20))),
+                    (U256::from(1), StorageSlot::new_changed(U256::from(5), U256::from(10))),
+                ]
+                .into_iter()
+                .collect(),
+                AccountStatus::Changed,
+            ),
+        );
+        bundle.state.insert(
+            destroyed,
+            BundleAccount::new(
+                Some(info.clone()),
+                None,
+                Default::default(),
+                AccountStatus::Destroyed,
+            ),
+        );
+        bundle.state.insert(
+            created,
+            BundleAccount::new(
+                None,
+                Some(contract),
+                Default::default(),
+                AccountStatus::InMemoryChange,
+            ),
+        );
+        bundle.contracts.insert(code_hash, code);
+        bundle.reverts = Reverts::new(vec![vec![
+            (
+                changed,
+                AccountRevert {
+                    account: AccountInfoRevert::RevertTo(AccountInfo::default()),
+                    storage: [
+                        (U256::from(1), RevertToSlot::Some(U256::from(5))),
+                        (U256::from(2), RevertToSlot::Some(U256::ZERO)),
+                    ]
+                    .into_iter()
+                    .collect(),
+                    previous_status: AccountStatus::Loaded,
+                    wipe_storage: false,
+                },
+            ),
+            (
+                destroyed,
+                AccountRevert {
+                    account: AccountInfoRevert::RevertTo(info),
+                    storage: Default::default(),
+                    previous_status: AccountStatus::Loaded,
+                    wipe_storage: true,
+                },
+            ),
+            (
+                created,
+                AccountRevert {
+                    account: AccountInfoRevert::DeleteIt,
+                    storage: Default::default(),
+                    previous_status: AccountStatus::LoadedNotExisting,
+                    wipe_storage: false,
+                },
+            ),
+        ]]);
+        bundle.reverts_size = 3;
+        bundle
+    }
+
+    fn compacted<R: Clone>(output: &BlockExecutionOutput<R>) -> BlockExecutionOutput<R> {
+        let mut compact = output.clone();
+        compact_execution_output(&mut compact);
+        compact
+    }
+
+    /// Estimates the heap memory held by the given bundle.
+    fn heap_size(bundle: &BundleState) -> usize {
+        let state = bundle.state.capacity() * size_of::<(Address, BundleAccount)>() +
+            bundle
+                .state
+                .values()
+                .map(|account| account.storage.capacity() * size_of::<(U256, StorageSlot)>())
+                .sum::<usize>();
+        let reverts = bundle.reverts.capacity() * size_of::<Vec<(Address, AccountRevert)>>() +
+            bundle
+                .reverts
+                .iter()
+                .map(|reverts| {
+                    reverts.capacity() * size_of::<(Address, AccountRevert)>() +
+                        reverts
+                            .iter()
+                            .map(|(_, revert)| {
+                                revert.storage.capacity() * size_of::<(U256, RevertToSlot)>()
+                            })
+                            .sum::<usize>()
+                })
+                .sum::<usize>();
+        state + reverts
+    }
+
+    #[test]
+    fn compact_reads() {
+        let output = BlockExecutionOutput::<()> { state: bundle_state(), ..Default::default() };
+        let compact = compacted(&output);
+        assert!(is_compacted(&compact));
+        assert!(compact.state.reverts.is_empty());
+
+        for address in (0..4).map(Address::with_last_byte) {
+            assert_eq!(compact.account(&address), output.account(&address));
+            for slot in [U256::from(1), U256::from(2), U256::from(3)] {
+                assert_eq!(compact.storage(&address, slot), output.storage(&address, slot));
+            }
+        }
+        for hash in output.state.contracts.keys() {
+            assert_eq!(compact.bytecode(hash), output.bytecode(hash));
+        }
+
+        // the duplicated bytecode of the created contract is dropped
+        let created = &compact.state.state[&Address::with_last_byte(3)];
+        assert!(created.info.as_ref().is_some_and(|info| info.code.is_none()));
+    }
+
+    #[test]
+    fn compact_rehydrates_reverts() {
+        let output = BlockExecutionOutput::<()> { state: bundle_state(), ..Default::default() };
+        assert!(!is_compacted(&output));
+        assert!(matches!(execution_output_reverts(&output), Cow::Borrowed(_)));
+
+        let compact = compacted(&output);
+        assert_eq!(*execution_output_reverts(&compact), output.state.reverts);
+
+        let rehydrated = rehydrate_execution_output(&compact);
+        assert!(!is_compacted(&rehydrated));
+        assert_eq!(rehydrated.state.reverts, output.state.reverts);
+        assert_eq!(rehydrated.state.reverts_size, output.state.reverts_size);
+
+        for (address, account) in &output.state.state {
+            let compacted = &rehydrated.state.state[address];
+            assert_eq!(compacted.original_info, account.original_info);
+            assert_eq!(compacted.storage, account.storage);
+            assert_eq!(compacted.status, account.status);
+        }
+
+        let plain_state = |bundle: BundleState| {
+            let (changeset, reverts) = bundle.to_plain_state_and_reverts(OriginalValuesKnown::No);
+            let mut accounts = changeset.accounts;
+            accounts.sort_unstable_by_key(|(address, _)| *address);
+            let mut storage = changeset
+                .storage
+                .into_iter()
+                .map(|mut changeset| {
+                    changeset.storage.sort_unstable();
+                    (changeset.address, changeset.wipe_storage, changeset.storage)
+                })
+                .collect::<Vec<_>>();
+            storage.sort_unstable_by_key(|(address, _, _)| *address);
+            let mut contracts = changeset.contracts;
+            contracts.sort_unstable_by_key(|(hash, _)| *hash);
+            (accounts, storage, contracts, reverts)
+        };
+        assert_eq!(plain_state(rehydrated.into_owned().state), plain_state(output.state));
+    }
+
+    #[test]
+    fn compact_reduces_size() {
+        let mut bundle = BundleState::default();
+        let mut reverts = Vec::new();
+        for address in (0..100).map(Address::with_last_byte) {
+            let storage = (0..16u64)
+                .map(|slot| {
+                    (U256::from(slot), StorageSlot::new_changed(U256::from(slot), U256::MAX))
+                })
+                .collect();
+            let info = AccountInfo { nonce: 1, ..Default::default() };
+            bundle.state.insert(
+                address,
+                BundleAccount::new(
+                    Some(AccountInfo::default()),
+                    Some(info),
+                    storage,
+                    AccountStatus::Changed,
+                ),
+            );
+        }
+        for (address, account) in &bundle.state {
+            reverts.push((*address, account_revert(account).unwrap()));
+        }
+        bundle.reverts = Reverts::new(vec![reverts]);
+        let output = BlockExecutionOutput::<()> { state: bundle, ..Default::default() };
+
+        let compact = compacted(&output);
+        let full_size = heap_size(&output.state);
+        let compact_size = heap_size(&compact.state);
+
+        // the reverts are about as large as the state, so compaction frees roughly half
+        assert!(
+            compact_size * 3 < full_size * 2,
+            "compacted {compact_size} bytes, full {full_size} bytes"
+        );
+        assert_eq!(*execution_output_reverts(&compact), {
+            let mut reverts = output.state.reverts.clone();
+            reverts.sort();
+            reverts
+        });
+    }
+}
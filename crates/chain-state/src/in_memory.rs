@@ -1,8 +1,9 @@
 //! Types for tracking the canonical chain state in memory.
 
 use crate::{
-    CanonStateNotification, CanonStateNotificationSender, CanonStateNotifications,
-    ChainInfoTracker, ComputedTrieData, DeferredTrieData, MemoryOverlayStateProvider,
+    execution_output_reverts, rehydrate_execution_output, BlockTimings, CanonStateNotification,
+    CanonStateNotificationSender, CanonStateNotifications, ChainInfoTracker, ChainStats,
+    ComputedTrieData, DeferredTrieData, MemoryOverlayStateProvider, SyncProgress,
 };
 use alloy_consensus::{transaction::TransactionMeta, BlockHeader};
 use alloy_eips::{BlockHashOrNumber, BlockNumHash};
@@ -21,7 +22,8 @@ use reth_trie::{
     updates::TrieUpdatesSorted, HashedPostStateSorted, LazyTrieData, SortedTrieData,
     TrieInputSorted,
 };
-use std::{collections::BTreeMap, sync::Arc, time::Instant};
+use revm_database::states::reverts::Reverts;
+use std::{borrow::Cow, collections::BTreeMap, sync::Arc, time::Instant};
 use tokio::sync::{broadcast, watch};
 
 /// Size of the broadcast channel used to notify canonical state events.
@@ -150,6 +152,12 @@ pub(crate) struct CanonicalInMemoryStateInner<N: NodePrimitives> {
     pub(crate) in_memory_state: InMemoryState<N>,
     /// A broadcast stream that emits events when the canonical chain is updated.
     pub(crate) canon_state_notification_sender: CanonStateNotificationSender<N>,
+    /// Tracks when recent canonical blocks were received and executed.
+    pub(crate) block_timings: BlockTimings,
+    /// Aggregated statistics of the canonical chain.
+    pub(crate) chain_stats: ChainStats,
+    /// The progress of the pipeline stages.
+    pub(crate) sync_progress: SyncProgress,
 }
 
 impl<N: NodePrimitives> CanonicalInMemoryStateInner<N> {
@@ -203,6 +211,9 @@ impl<N: NodePrimitives> CanonicalInMemoryState<N> {
                 chain_info_tracker,
                 in_memory_state,
                 canon_state_notification_sender,
+                block_timings: BlockTimings::default(),
+                chain_stats: ChainStats::default(),
+                sync_progress: SyncProgress::default(),
             }),
         }
     }
@@ -227,6 +238,9 @@ impl<N: NodePrimitives> CanonicalInMemoryState<N> {
             chain_info_tracker,
             in_memory_state,
             canon_state_notification_sender,
+            block_timings: BlockTimings::default(),
+            chain_stats: ChainStats::default(),
+            sync_progress: SyncProgress::default(),
         };
 
         Self { inner: Arc::new(inner) }
@@ -375,6 +389,42 @@ impl<N: NodePrimitives> CanonicalInMemoryState<N> {
         self.inner.in_memory_state.update_metrics();
     }
 
+    /// Replaces the execution outputs of the given in-memory blocks, e.g. with compacted copies
+    /// of them, see [`compact_execution_output`](crate::compact_execution_output).
+    ///
+    /// Blocks that are no longer in memory are skipped.
+    pub fn replace_execution_outputs(
+        &self,
+        outputs: &HashMap<B256, Arc<BlockExecutionOutput<N::Receipt>>>,
+    ) {
+        // block numbers and hashes don't change, so only the blocks lock is needed
+        let mut blocks = self.inner.in_memory_state.blocks.write();
+        if !outputs.keys().any(|hash| blocks.contains_key(hash)) {
+            return
+        }
+
+        // sort the blocks by number so we can insert them back in natural order (low -> high)
+        let mut old_blocks = blocks.drain().map(|(_, b)| b.block.clone()).collect::<Vec<_>>();
+        old_blocks.sort_unstable_by_key(|block| block.recovered_block().number());
+
+        // re-insert the blocks with their new outputs and connect them to their parent blocks
+        for mut block in old_blocks {
+            if let Some(output) = outputs.get(&block.recovered_block().hash()) {
+                block.execution_output = output.clone();
+            }
+            let parent = blocks.get(&block.recovered_block().parent_hash()).cloned();
+            let block_state = BlockState::with_parent(block, parent);
+            blocks.insert(block_state.hash(), Arc::new(block_state));
+        }
+
+        // also reconnect the pending state if it exists
+        self.inner.in_memory_state.pending.send_modify(|p| {
+            if let Some(p) = p.as_mut() {
+                p.parent = blocks.get(&p.block_ref().recovered_block().parent_hash()).cloned();
+            }
+        });
+    }
+
     /// Returns in memory state corresponding the given hash.
     pub fn state_by_hash(&self, hash: B256) -> Option<Arc<BlockState<N>>> {
         self.inner.in_memory_state.state_by_hash(hash)
@@ -530,6 +580,21 @@ impl<N: NodePrimitives> CanonicalInMemoryState<N> {
         self.inner.chain_info_tracker.subscribe_persisted_block()
     }
 
+    /// Returns the timings of recently received canonical blocks.
+    pub fn block_timings(&self) -> &BlockTimings {
+        &self.inner.block_timings
+    }
+
+    /// Returns the aggregated statistics of the canonical chain.
+    pub fn chain_stats(&self) -> &ChainStats {
+        &self.inner.chain_stats
+    }
+
+    /// Returns the progress of the pipeline stages.
+    pub fn sync_progress(&self) -> &SyncProgress {
+        &self.inner.sync_progress
+    }
+
     /// Attempts to send a new [`CanonStateNotification`] to all active Receiver handles.
     pub fn notify_canon_state(&self, event: CanonStateNotification<N>) {
         self.inner.canon_state_notification_sender.send(event).ok();
@@ -841,6 +906,29 @@ impl<N: NodePrimitives> ExecutedBlock<N> {
         &self.execution_output
     }
 
+    /// Returns the block's execution outcome, with the reverts rehydrated if they were dropped by
+    /// [`compact_execution_output`](crate::compact_execution_output).
+    #[inline]
+    pub fn rehydrated_execution_outcome(&self) -> Cow<'_, BlockExecutionOutput<N::Receipt>> {
+        rehydrate_execution_output(&self.execution_output)
+    }
+
+    /// Returns the block's reverts, rebuilding them if they were dropped by
+    /// [`compact_execution_output`](crate::compact_execution_output).
+    #[inline]
+    pub fn execution_reverts(&self) -> Cow<'_, Reverts> {
+        execution_output_reverts(&self.execution_output)
+    }
+
+    /// Rehydrates the reverts of the block's execution outcome, if they were dropped by
+    /// [`compact_execution_output`](crate::compact_execution_output).
+    pub fn into_rehydrated(mut self) -> Self {
+        if let Cow::Owned(output) = self.rehydrated_execution_outcome() {
+            self.execution_output = Arc::new(output);
+        }
+        self
+    }
+
     /// Returns the trie data, computing it synchronously if not already cached.
     ///
     /// Uses `OnceLock::get_or_init` internally:
@@ -955,7 +1043,7 @@ impl<N: NodePrimitives<SignedTx: SignedTransaction>> NewCanonicalChain<N> {
                 let mut chain = Chain::from_block(
                     first.recovered_block().clone(),
                     ExecutionOutcome::from((
-                        first.execution_outcome().clone(),
+                        first.rehydrated_execution_outcome().into_owned(),
                         first.block_number(),
                     )),
                     LazyTrieData::deferred(move || {
@@ -971,7 +1059,7 @@ impl<N: NodePrimitives<SignedTx: SignedTransaction>> NewCanonicalChain<N> {
                     chain.append_block(
                         exec.recovered_block().clone(),
                         ExecutionOutcome::from((
-                            exec.execution_outcome().clone(),
+                            exec.rehydrated_execution_outcome().into_owned(),
                             exec.block_number(),
                         )),
                         LazyTrieData::deferred(move || {
@@ -1004,7 +1092,7 @@ impl<N: NodePrimitives<SignedTx: SignedTransaction>> NewCanonicalChain<N> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::test_utils::TestBlockBuilder;
+    use crate::{compact_execution_output, test_utils::TestBlockBuilder};
     use alloy_eips::eip7685::Requests;
     use alloy_primitives::{Address, BlockNumber, Bytes, StorageKey, StorageValue};
     use rand::Rng;
@@ -1461,6 +1549,49 @@ mod tests {
         assert_eq!(chain[2].number(), 1);
     }
 
+    #[test]
+    fn test_canonical_in_memory_state_replace_execution_outputs() {
+        let mut parent_hash = B256::random();
+        let mut block_builder = TestBlockBuilder::eth();
+        let state: CanonicalInMemoryState = CanonicalInMemoryState::empty();
+
+        let mut blocks = Vec::new();
+        for i in 1..=3 {
+            let block = block_builder.get_executed_block_with_number(i, parent_hash);
+            parent_hash = block.recovered_block().hash();
+            state.update_blocks(Some(block.clone()), None);
+            blocks.push(block);
+        }
+
+        let outputs: HashMap<_, _> = blocks[..2]
+            .iter()
+            .map(|block| {
+                let mut output = block.execution_output.clone();
+                compact_execution_output(Arc::make_mut(&mut output));
+                (block.recovered_block().hash(), output)
+            })
+            .collect();
+        state.replace_execution_outputs(&outputs);
+
+        let chain: Vec<_> = state.canonical_chain().collect();
+        assert_eq!(chain.len(), 3);
+        for (block_state, block) in chain.iter().zip(blocks.iter().rev()) {
+            let replaced = block_state.block_ref();
+            let hash = block.recovered_block().hash();
+            let expected = outputs.get(&hash).unwrap_or(&block.execution_output);
+            assert!(Arc::ptr_eq(&replaced.execution_output, expected));
+            assert_eq!(replaced.recovered_block(), block.recovered_block());
+            assert_eq!(replaced.execution_output.result, block.execution_output.result);
+            assert_eq!(replaced.execution_reverts(), block.execution_reverts());
+        }
+        // the chain is reconnected to the replaced parents
+        let parent = chain[0].parent.as_ref().unwrap().block_ref();
+        assert!(Arc::ptr_eq(
+            &parent.execution_output,
+            &outputs[&blocks[1].recovered_block().hash()]
+        ));
+    }
+
     // ensures the pending block is not part of the canonical chain
     #[test]
     fn test_canonical_in_memory_state_canonical_chain_with_pending_block() {
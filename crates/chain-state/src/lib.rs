@@ -11,6 +11,9 @@
 mod in_memory;
 pub use in_memory::*;
 
+mod compact;
+pub use compact::*;
+
 mod deferred_trie;
 pub use deferred_trie::*;
 
@@ -30,6 +33,21 @@ pub use notifications::{
     WatchValueStream,
 };
 
+mod chain_stats;
+pub use chain_stats::{
+    BlockStats, ChainStats, ChainStatsChanges, ChainStatsEntry, ChainStatsPeriod,
+    ChainStatsProvider, StateGrowth, DEFAULT_CHAIN_STATS_DAYS, DEFAULT_CHAIN_STATS_EPOCHS,
+};
+
+mod sync_progress;
+pub use sync_progress::{StageProgress, SyncMode, SyncProgress, SyncProgressProvider};
+
+mod timings;
+pub use timings::{
+    attestation_deadline_ms, beacon_genesis_time, BlockTiming, BlockTimings, BlockTimingsProvider,
+    EpochTimingSummary, DEFAULT_BLOCK_TIMINGS_CAPACITY,
+};
+
 mod memory_overlay;
 pub use memory_overlay::{MemoryOverlayStateProvider, MemoryOverlayStateProviderRef};
 
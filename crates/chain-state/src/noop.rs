@@ -1,8 +1,9 @@
 //! Noop impls for testing.
 
 use crate::{
-    CanonStateNotifications, CanonStateSubscriptions, ForkChoiceNotifications,
-    ForkChoiceSubscriptions, PersistedBlockNotifications, PersistedBlockSubscriptions,
+    BlockTimings, BlockTimingsProvider, CanonStateNotifications, CanonStateSubscriptions,
+    ChainStats, ChainStatsProvider, ForkChoiceNotifications, ForkChoiceSubscriptions,
+    PersistedBlockNotifications, PersistedBlockSubscriptions, SyncProgress, SyncProgressProvider,
 };
 use reth_primitives_traits::NodePrimitives;
 use reth_storage_api::noop::NoopProvider;
@@ -34,3 +35,21 @@ impl<C: Send + Sync, N: NodePrimitives> PersistedBlockSubscriptions for NoopProv
         PersistedBlockNotifications(rx)
     }
 }
+
+impl<C: Send + Sync, N: NodePrimitives> BlockTimingsProvider for NoopProvider<C, N> {
+    fn block_timings(&self) -> BlockTimings {
+        BlockTimings::default()
+    }
+}
+
+impl<C: Send + Sync, N: NodePrimitives> ChainStatsProvider for NoopProvider<C, N> {
+    fn chain_stats(&self) -> ChainStats {
+        ChainStats::default()
+    }
+}
+
+impl<C: Send + Sync, N: NodePrimitives> SyncProgressProvider for NoopProvider<C, N> {
+    fn sync_progress(&self) -> SyncProgress {
+        SyncProgress::default()
+    }
+}
@@ -157,16 +157,21 @@ impl<N: NodePrimitives> CanonStateNotification<N> {
     ///
     /// The boolean in the tuple (2nd element) denotes whether the receipt was from the reverted
     /// chain segment.
+    ///
+    /// Receipts of the reverted chain segment come first, ordered from the old tip down to the
+    /// fork point, followed by the receipts of the new chain segment in ascending order. This is
+    /// the order in which the changes have to be undone and applied by consumers.
     pub fn block_receipts(&self) -> Vec<(BlockReceipts<N::Receipt>, bool)>
     where
         N::SignedTx: Encodable2718,
     {
         let mut receipts = Vec::new();
 
-        // get old receipts
+        // get old receipts, newest first
         if let Some(old) = self.reverted() {
-            receipts
-                .extend(old.receipts_with_attachment().into_iter().map(|receipt| (receipt, true)));
+            receipts.extend(
+                old.receipts_with_attachment().into_iter().rev().map(|receipt| (receipt, true)),
+            );
         }
         // get new receipts
         receipts.extend(
@@ -519,4 +524,48 @@ mod tests {
         // Confirm this is from the committed segment.
         assert!(!block_receipts[1].1);
     }
+
+    #[test]
+    fn test_block_receipts_reorg_reverted_order() {
+        let recovered_block = |number: u64| {
+            let mut body = BlockBody::<TransactionSigned>::default();
+            body.transactions
+                .push(TxLegacy::default().into_signed(Signature::test_signature()).into());
+            let mut block =
+                SealedBlock::<alloy_consensus::Block<TransactionSigned>>::from_sealed_parts(
+                    SealedHeader::seal_slow(alloy_consensus::Header::default()),
+                    body,
+                )
+                .try_recover()
+                .unwrap();
+            block.set_block_number(number);
+            block.set_hash(B256::with_last_byte(number as u8));
+            block
+        };
+        let receipts = |count: usize| ExecutionOutcome {
+            receipts: vec![vec![Receipt::default()]; count],
+            ..Default::default()
+        };
+
+        let old_chain = Arc::new(Chain::new(
+            vec![recovered_block(1), recovered_block(2)],
+            receipts(2),
+            BTreeMap::new(),
+        ));
+        let new_chain = Arc::new(Chain::new(
+            vec![recovered_block(1), recovered_block(2), recovered_block(3)],
+            receipts(3),
+            BTreeMap::new(),
+        ));
+        let notification = CanonStateNotification::Reorg { old: old_chain, new: new_chain };
+
+        let blocks = notification
+            .block_receipts()
+            .into_iter()
+            .map(|(receipts, removed)| (receipts.block.number, removed))
+            .collect::<Vec<_>>();
+
+        // Reverted blocks are returned from the old tip down, committed blocks in ascending order.
+        assert_eq!(blocks, vec![(2, true), (1, true), (1, false), (2, false), (3, false)]);
+    }
 }
@@ -0,0 +1,219 @@
+//! Tracks the progress of the pipeline stages while the node is syncing.
+
+use alloy_primitives::BlockNumber;
+use parking_lot::RwLock;
+use reth_stages_types::{EntitiesCheckpoint, StageCheckpoint, StageId};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// A type that provides access to the [`SyncProgress`] of the node.
+pub trait SyncProgressProvider: Send + Sync {
+    /// Returns the sync progress reported by the pipeline.
+    fn sync_progress(&self) -> SyncProgress;
+}
+
+/// How the node advances its chain.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SyncMode {
+    /// The engine follows the chain by executing the payloads it receives from the consensus
+    /// layer.
+    #[default]
+    Live,
+    /// The pipeline backfills a range of blocks stage by stage.
+    Backfill,
+}
+
+/// The progress of a single pipeline stage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StageProgress {
+    /// The stage.
+    pub stage_id: StageId,
+    /// The last checkpoint reported by the stage.
+    pub checkpoint: StageCheckpoint,
+    /// The block the stage is running or unwinding to, if known.
+    pub target: Option<BlockNumber>,
+    /// The estimated time until the stage reaches its target, if it reports entity progress.
+    pub eta: Option<Duration>,
+}
+
+impl StageProgress {
+    /// Returns the entity progress of the stage, if the stage reports it.
+    pub fn entities(&self) -> Option<EntitiesCheckpoint> {
+        self.checkpoint.entities()
+    }
+}
+
+/// The progress of the pipeline stages, shared between the pipeline event listener and its
+/// readers.
+#[derive(Debug, Clone, Default)]
+pub struct SyncProgress {
+    inner: Arc<RwLock<SyncProgressInner>>,
+}
+
+impl SyncProgress {
+    /// Returns how the node currently advances its chain.
+    pub fn mode(&self) -> SyncMode {
+        self.inner.read().mode
+    }
+
+    /// Sets how the node currently advances its chain.
+    pub fn set_mode(&self, mode: SyncMode) {
+        self.inner.write().mode = mode;
+    }
+
+    /// Records a checkpoint reported by a stage.
+    ///
+    /// The target is kept from previous reports of the stage if it's `None`.
+    pub fn update_stage(
+        &self,
+        stage_id: StageId,
+        checkpoint: StageCheckpoint,
+        target: Option<BlockNumber>,
+    ) {
+        let mut inner = self.inner.write();
+        inner.current_stage = Some(stage_id);
+
+        let index = match inner.stages.iter().position(|stage| stage.stage_id == stage_id) {
+            Some(index) => index,
+            None => {
+                inner.stages.push(StageState::new(stage_id));
+                inner.stages.len() - 1
+            }
+        };
+        inner.stages[index].update(checkpoint, target);
+    }
+
+    /// Returns the stage that reported its progress last.
+    pub fn current_stage(&self) -> Option<StageId> {
+        self.inner.read().current_stage
+    }
+
+    /// Returns the progress of all stages that reported it, in the order they first reported it.
+    pub fn stages(&self) -> Vec<StageProgress> {
+        self.inner.read().stages.iter().map(StageState::progress).collect()
+    }
+
+    /// Returns the highest target any stage is running to.
+    pub fn target(&self) -> Option<BlockNumber> {
+        self.inner.read().stages.iter().filter_map(|stage| stage.target).max()
+    }
+}
+
+#[derive(Debug, Default)]
+struct SyncProgressInner {
+    mode: SyncMode,
+    current_stage: Option<StageId>,
+    stages: Vec<StageState>,
+}
+
+/// The progress of a stage and the data needed to estimate its remaining time.
+#[derive(Debug)]
+struct StageState {
+    stage_id: StageId,
+    checkpoint: StageCheckpoint,
+    target: Option<BlockNumber>,
+    /// The processed entities of the last checkpoint and when it was reported.
+    last_entities: Option<(u64, Instant)>,
+    /// The estimated remaining time and when it was estimated.
+    eta: Option<(Duration, Instant)>,
+}
+
+impl StageState {
+    fn new(stage_id: StageId) -> Self {
+        Self {
+            stage_id,
+            checkpoint: StageCheckpoint::default(),
+            target: None,
+            last_entities: None,
+            eta: None,
+        }
+    }
+
+    fn update(&mut self, checkpoint: StageCheckpoint, target: Option<BlockNumber>) {
+        self.checkpoint = checkpoint;
+        if target.is_some() {
+            self.target = target;
+        }
+
+        let Some(entities) = checkpoint.entities() else {
+            self.last_entities = None;
+            self.eta = None;
+            return
+        };
+
+        let now = Instant::now();
+        if let Some((last_processed, last_time)) = self.last_entities {
+            let elapsed = now.duration_since(last_time).as_secs_f64();
+            self.eta = entities
+                .processed
+                .checked_sub(last_processed)
+                .filter(|processed| *processed > 0 && elapsed > 0.0)
+                .and_then(|processed| {
+                    let remaining = entities.total.saturating_sub(entities.processed);
+                    Duration::try_from_secs_f64(remaining as f64 * elapsed / processed as f64).ok()
+                })
+                .map(|eta| (eta, now));
+        }
+        self.last_entities = Some((entities.processed, now));
+    }
+
+    fn progress(&self) -> StageProgress {
+        StageProgress {
+            stage_id: self.stage_id,
+            checkpoint: self.checkpoint,
+            target: self.target,
+            eta: self.eta.map(|(eta, estimated_at)| eta.saturating_sub(estimated_at.elapsed())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_stages_types::ExecutionCheckpoint;
+
+    fn execution_checkpoint(block_number: BlockNumber, processed: u64) -> StageCheckpoint {
+        StageCheckpoint::new(block_number).with_execution_stage_checkpoint(ExecutionCheckpoint {
+            block_range: Default::default(),
+            progress: EntitiesCheckpoint { processed, total: 1_000 },
+        })
+    }
+
+    #[test]
+    fn tracks_stages_in_order() {
+        let progress = SyncProgress::default();
+        assert_eq!(progress.mode(), SyncMode::Live);
+
+        progress.set_mode(SyncMode::Backfill);
+        progress.update_stage(StageId::Headers, StageCheckpoint::new(10), Some(100));
+        progress.update_stage(StageId::Bodies, StageCheckpoint::new(5), Some(100));
+        progress.update_stage(StageId::Headers, StageCheckpoint::new(100), None);
+
+        assert_eq!(progress.mode(), SyncMode::Backfill);
+        assert_eq!(progress.current_stage(), Some(StageId::Headers));
+        assert_eq!(progress.target(), Some(100));
+
+        let stages = progress.stages();
+        assert_eq!(stages.len(), 2);
+        assert_eq!(stages[0].stage_id, StageId::Headers);
+        assert_eq!(stages[0].checkpoint.block_number, 100);
+        assert_eq!(stages[0].target, Some(100));
+        assert_eq!(stages[1].stage_id, StageId::Bodies);
+    }
+
+    #[test]
+    fn estimates_remaining_time() {
+        let progress = SyncProgress::default();
+
+        progress.update_stage(StageId::Execution, execution_checkpoint(0, 0), Some(1_000));
+        assert_eq!(progress.stages()[0].eta, None);
+
+        std::thread::sleep(Duration::from_millis(10));
+        progress.update_stage(StageId::Execution, execution_checkpoint(500, 500), None);
+        let stage = progress.stages()[0];
+        assert!(stage.eta.is_some());
+        assert_eq!(stage.entities(), Some(EntitiesCheckpoint { processed: 500, total: 1_000 }));
+    }
+}
@@ -0,0 +1,446 @@
+//! Tracks when canonical blocks were received and executed relative to their slot.
+
+use alloy_eips::merge::{EPOCH_SLOTS, SLOT_DURATION};
+use alloy_primitives::{map::B256Map, BlockNumber, B256};
+use parking_lot::RwLock;
+use reth_chainspec::{Chain, NamedChain};
+use reth_metrics::{
+    metrics::{Counter, Gauge, Histogram},
+    Metrics,
+};
+use std::{collections::BTreeMap, sync::Arc, time::Duration};
+
+/// Returns the time into a slot of the given duration at which attesters vote for the head of the
+/// chain, in milliseconds.
+///
+/// Attestations are due a third into the slot, 4 seconds on mainnet.
+pub const fn attestation_deadline_ms(slot_duration: Duration) -> u64 {
+    slot_duration.as_millis() as u64 / 3
+}
+
+/// The default number of blocks whose timings are kept, 16 epochs.
+pub const DEFAULT_BLOCK_TIMINGS_CAPACITY: usize = EPOCH_SLOTS as usize * 16;
+
+/// Returns the beacon chain genesis time of well-known networks.
+///
+/// Epochs are counted from the beacon chain genesis, which differs from the timestamp of the
+/// execution layer genesis on networks that launched before the merge.
+pub fn beacon_genesis_time(chain: Chain) -> Option<u64> {
+    match chain.named()? {
+        NamedChain::Mainnet => Some(1_606_824_023),
+        NamedChain::Sepolia => Some(1_655_733_600),
+        NamedChain::Holesky => Some(1_695_902_400),
+        NamedChain::Hoodi => Some(1_742_213_400),
+        _ => None,
+    }
+}
+
+/// A type that provides access to the [`BlockTimings`] of the node.
+pub trait BlockTimingsProvider: Send + Sync {
+    /// Returns the timings of recently received canonical blocks.
+    fn block_timings(&self) -> BlockTimings;
+}
+
+/// When a block was received and executed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockTiming {
+    /// The block number.
+    pub number: BlockNumber,
+    /// The block hash.
+    pub hash: B256,
+    /// The block timestamp, which is the start of its slot, in seconds.
+    pub timestamp: u64,
+    /// Unix time in milliseconds at which the payload was received.
+    pub received_at_ms: u64,
+    /// Unix time in milliseconds at which the payload finished executing.
+    pub executed_at_ms: u64,
+}
+
+impl BlockTiming {
+    /// Returns the time between the start of the slot and receiving the payload.
+    pub const fn received_delay_ms(&self) -> u64 {
+        self.received_at_ms.saturating_sub(self.timestamp.saturating_mul(1_000))
+    }
+
+    /// Returns the time between the start of the slot and the end of execution.
+    pub const fn executed_delay_ms(&self) -> u64 {
+        self.executed_at_ms.saturating_sub(self.timestamp.saturating_mul(1_000))
+    }
+
+    /// Returns the time left until the given attestation deadline once the payload was executed.
+    ///
+    /// This is negative if the deadline was missed.
+    pub const fn deadline_margin_ms(&self, deadline_ms: u64) -> i64 {
+        deadline_ms as i64 - self.executed_delay_ms() as i64
+    }
+
+    /// Returns `true` if the payload finished executing after the given attestation deadline.
+    pub const fn missed_deadline(&self, deadline_ms: u64) -> bool {
+        self.executed_delay_ms() > deadline_ms
+    }
+}
+
+/// The aggregated block timings of an epoch.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EpochTimingSummary {
+    /// The epoch number.
+    pub epoch: u64,
+    /// The number of blocks with recorded timings.
+    pub blocks: u64,
+    /// The average time between the start of the slot and receiving the payload.
+    pub avg_received_delay_ms: u64,
+    /// The maximum time between the start of the slot and receiving the payload.
+    pub max_received_delay_ms: u64,
+    /// The average time between the start of the slot and the end of execution.
+    pub avg_executed_delay_ms: u64,
+    /// The maximum time between the start of the slot and the end of execution.
+    pub max_executed_delay_ms: u64,
+    /// The smallest time left until the attestation deadline, negative if it was missed.
+    pub min_deadline_margin_ms: i64,
+    /// The number of blocks that finished executing after the attestation deadline.
+    pub missed_deadlines: u64,
+}
+
+impl EpochTimingSummary {
+    /// Aggregates the timings of blocks of the given epoch.
+    fn new<'a>(
+        epoch: u64,
+        deadline_ms: u64,
+        timings: impl IntoIterator<Item = &'a BlockTiming>,
+    ) -> Self {
+        let mut summary = Self { epoch, min_deadline_margin_ms: i64::MAX, ..Default::default() };
+        let (mut received_total, mut executed_total) = (0u64, 0u64);
+        for timing in timings {
+            summary.blocks += 1;
+            received_total += timing.received_delay_ms();
+            executed_total += timing.executed_delay_ms();
+            summary.max_received_delay_ms =
+                summary.max_received_delay_ms.max(timing.received_delay_ms());
+            summary.max_executed_delay_ms =
+                summary.max_executed_delay_ms.max(timing.executed_delay_ms());
+            summary.min_deadline_margin_ms =
+                summary.min_deadline_margin_ms.min(timing.deadline_margin_ms(deadline_ms));
+            summary.missed_deadlines += timing.missed_deadline(deadline_ms) as u64;
+        }
+        if summary.blocks > 0 {
+            summary.avg_received_delay_ms = received_total / summary.blocks;
+            summary.avg_executed_delay_ms = executed_total / summary.blocks;
+        }
+        summary
+    }
+}
+
+/// Keeps the timings of the most recent canonical blocks.
+///
+/// The timings of executed blocks are held back until the blocks become canonical, so that only
+/// the blocks that were attested to are included.
+///
+/// This is cheap to clone, all clones share the same timings.
+#[derive(Debug, Clone, Default)]
+pub struct BlockTimings {
+    inner: Arc<RwLock<BlockTimingsInner>>,
+    metrics: BlockTimingMetrics,
+}
+
+impl BlockTimings {
+    /// Creates a new instance that keeps the timings of at most `capacity` blocks.
+    pub fn new(capacity: usize) -> Self {
+        let inner = BlockTimingsInner { capacity, ..Default::default() };
+        Self { inner: Arc::new(RwLock::new(inner)), metrics: Default::default() }
+    }
+
+    /// Sets the beacon chain genesis time that epochs are counted from.
+    pub fn set_genesis_time(&self, genesis_time: u64) {
+        self.inner.write().genesis_time = genesis_time;
+    }
+
+    /// Sets the duration of a slot, which determines the attestation deadline and the length of
+    /// an epoch.
+    pub fn set_slot_duration(&self, slot_duration: Duration) {
+        self.inner.write().slot_duration = slot_duration;
+    }
+
+    /// Returns the attestation deadline of the configured slot duration, in milliseconds.
+    pub fn deadline_ms(&self) -> u64 {
+        attestation_deadline_ms(self.inner.read().slot_duration)
+    }
+
+    /// Keeps the timing of an executed block until it becomes canonical, see
+    /// [`Self::on_canonical`].
+    ///
+    /// The first timing of a block is kept, so blocks whose payload is received again keep the
+    /// timing of their original execution.
+    pub fn on_executed(&self, timing: BlockTiming) {
+        let mut inner = self.inner.write();
+        if inner.blocks.get(&timing.number).is_some_and(|recorded| recorded.hash == timing.hash) {
+            return
+        }
+        inner.executed.entry(timing.hash).or_insert(timing);
+    }
+
+    /// Records the timings of the blocks that became canonical, in ascending order.
+    ///
+    /// Executed blocks at or below the last of them that didn't become canonical are dropped.
+    pub fn on_canonical(&self, blocks: impl IntoIterator<Item = B256>) {
+        let mut tip = None;
+        for hash in blocks {
+            let timing = self.inner.write().executed.remove(&hash);
+            if let Some(timing) = timing {
+                tip = Some(timing.number);
+                self.record(timing);
+            }
+        }
+
+        if let Some(tip) = tip {
+            self.inner.write().executed.retain(|_, timing| timing.number > tip);
+        }
+    }
+
+    /// Records the timing of a canonical block.
+    ///
+    /// This replaces the timings of blocks at the same or a higher height, which were reorged
+    /// out.
+    pub fn record(&self, timing: BlockTiming) {
+        let mut inner = self.inner.write();
+        let deadline_ms = attestation_deadline_ms(inner.slot_duration);
+        self.metrics.received_delay.record(timing.received_delay_ms() as f64 / 1_000.0);
+        self.metrics.executed_delay.record(timing.executed_delay_ms() as f64 / 1_000.0);
+        if timing.missed_deadline(deadline_ms) {
+            self.metrics.missed_deadlines.increment(1);
+        }
+
+        inner.blocks.retain(|number, _| *number < timing.number);
+        let previous_epoch = inner.blocks.last_key_value().map(|(_, last)| inner.epoch(last));
+        inner.blocks.insert(timing.number, timing);
+        while inner.blocks.len() > inner.capacity {
+            inner.blocks.pop_first();
+        }
+
+        // the previous epoch is complete once the first block of a later epoch arrives
+        if let Some(previous_epoch) = previous_epoch &&
+            previous_epoch < inner.epoch(&timing)
+        {
+            let summary = inner.summary(previous_epoch);
+            self.metrics
+                .last_epoch_avg_executed_delay
+                .set(summary.avg_executed_delay_ms as f64 / 1_000.0);
+            self.metrics
+                .last_epoch_max_executed_delay
+                .set(summary.max_executed_delay_ms as f64 / 1_000.0);
+            self.metrics
+                .last_epoch_min_deadline_margin
+                .set(summary.min_deadline_margin_ms as f64 / 1_000.0);
+            self.metrics.last_epoch_missed_deadlines.set(summary.missed_deadlines as f64);
+        }
+    }
+
+    /// Returns the timing of the block with the given number.
+    pub fn block(&self, number: BlockNumber) -> Option<BlockTiming> {
+        self.inner.read().blocks.get(&number).copied()
+    }
+
+    /// Returns the summaries of at most `limit` of the most recent epochs, newest first.
+    pub fn epoch_summaries(&self, limit: usize) -> Vec<EpochTimingSummary> {
+        let inner = self.inner.read();
+        let mut epochs = Vec::new();
+        for timing in inner.blocks.values().rev() {
+            let epoch = inner.epoch(timing);
+            if epochs.last() != Some(&epoch) {
+                if epochs.len() == limit {
+                    break
+                }
+                epochs.push(epoch);
+            }
+        }
+        epochs.into_iter().map(|epoch| inner.summary(epoch)).collect()
+    }
+}
+
+#[derive(Debug)]
+struct BlockTimingsInner {
+    /// The beacon chain genesis time in seconds.
+    genesis_time: u64,
+    /// The duration of a slot.
+    slot_duration: Duration,
+    /// The maximum number of blocks to keep.
+    capacity: usize,
+    /// The timings of the most recent canonical blocks.
+    blocks: BTreeMap<BlockNumber, BlockTiming>,
+    /// The timings of executed blocks that didn't become canonical yet.
+    executed: B256Map<BlockTiming>,
+}
+
+impl BlockTimingsInner {
+    /// Returns the epoch of the block.
+    fn epoch(&self, timing: &BlockTiming) -> u64 {
+        let epoch_duration = self.slot_duration.as_secs().max(1) * EPOCH_SLOTS;
+        timing.timestamp.saturating_sub(self.genesis_time) / epoch_duration
+    }
+
+    /// Aggregates the timings of all kept blocks of the epoch.
+    fn summary(&self, epoch: u64) -> EpochTimingSummary {
+        EpochTimingSummary::new(
+            epoch,
+            attestation_deadline_ms(self.slot_duration),
+            self.blocks.values().filter(|timing| self.epoch(timing) == epoch),
+        )
+    }
+}
+
+impl Default for BlockTimingsInner {
+    fn default() -> Self {
+        Self {
+            genesis_time: 0,
+            slot_duration: SLOT_DURATION,
+            capacity: DEFAULT_BLOCK_TIMINGS_CAPACITY,
+            blocks: BTreeMap::new(),
+            executed: B256Map::default(),
+        }
+    }
+}
+
+/// Metrics for the timings of canonical blocks.
+#[derive(Metrics, Clone)]
+#[metrics(scope = "engine.block_timing")]
+struct BlockTimingMetrics {
+    /// The time between the start of the slot and receiving the payload, in seconds.
+    received_delay: Histogram,
+    /// The time between the start of the slot and the end of execution, in seconds.
+    executed_delay: Histogram,
+    /// The number of blocks that finished executing after the attestation deadline.
+    missed_deadlines: Counter,
+    /// The average execution delay of the last complete epoch, in seconds.
+    last_epoch_avg_executed_delay: Gauge,
+    /// The maximum execution delay of the last complete epoch, in seconds.
+    last_epoch_max_executed_delay: Gauge,
+    /// The smallest attestation deadline margin of the last complete epoch, in seconds.
+    last_epoch_min_deadline_margin: Gauge,
+    /// The number of missed attestation deadlines in the last complete epoch.
+    last_epoch_missed_deadlines: Gauge,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timing(number: BlockNumber, received_delay_ms: u64, executed_delay_ms: u64) -> BlockTiming {
+        let timestamp = 1_000 + number * SLOT_DURATION.as_secs();
+        BlockTiming {
+            number,
+            hash: B256::with_last_byte(number as u8),
+            timestamp,
+            received_at_ms: timestamp * 1_000 + received_delay_ms,
+            executed_at_ms: timestamp * 1_000 + executed_delay_ms,
+        }
+    }
+
+    #[test]
+    fn deadline_margin() {
+        let deadline_ms = attestation_deadline_ms(SLOT_DURATION);
+        assert_eq!(deadline_ms, 4_000);
+
+        let on_time = timing(1, 500, 1_500);
+        assert_eq!(on_time.received_delay_ms(), 500);
+        assert_eq!(on_time.deadline_margin_ms(deadline_ms), 2_500);
+        assert!(!on_time.missed_deadline(deadline_ms));
+
+        let late = timing(2, 3_000, 4_500);
+        assert_eq!(late.deadline_margin_ms(deadline_ms), -500);
+        assert!(late.missed_deadline(deadline_ms));
+    }
+
+    #[test]
+    fn slot_duration() {
+        let timings = BlockTimings::default();
+        timings.set_genesis_time(1_000);
+        timings.set_slot_duration(Duration::from_secs(6));
+        assert_eq!(timings.deadline_ms(), 2_000);
+
+        // with 6 second slots, an epoch lasts 192 seconds
+        let block = BlockTiming {
+            number: 1,
+            hash: B256::with_last_byte(1),
+            timestamp: 1_000 + EPOCH_SLOTS * 6,
+            received_at_ms: (1_000 + EPOCH_SLOTS * 6) * 1_000,
+            executed_at_ms: (1_000 + EPOCH_SLOTS * 6) * 1_000 + 3_000,
+        };
+        timings.record(block);
+
+        let summaries = timings.epoch_summaries(1);
+        assert_eq!(summaries[0].epoch, 1);
+        assert_eq!(summaries[0].min_deadline_margin_ms, -1_000);
+        assert_eq!(summaries[0].missed_deadlines, 1);
+    }
+
+    #[test]
+    fn record_on_canonical() {
+        let timings = BlockTimings::default();
+        let canonical = timing(1, 100, 1_000);
+        let fork = BlockTiming { hash: B256::with_last_byte(0xff), ..timing(1, 200, 2_000) };
+        let next = timing(2, 100, 1_000);
+        timings.on_executed(canonical);
+        timings.on_executed(fork);
+        timings.on_executed(next);
+
+        // executed blocks are only recorded once they're canonical
+        assert!(timings.block(1).is_none());
+        timings.on_canonical([canonical.hash]);
+        assert_eq!(timings.block(1), Some(canonical));
+
+        // the timing of the original execution is kept
+        timings.on_executed(BlockTiming { executed_at_ms: u64::MAX, ..next });
+        timings.on_canonical([next.hash]);
+        assert_eq!(timings.block(2), Some(next));
+
+        // blocks at or below the canonical tip that weren't canonicalized are dropped
+        timings.on_canonical([fork.hash]);
+        assert_eq!(timings.block(1), Some(canonical));
+        assert!(timings.inner.read().executed.is_empty());
+    }
+
+    #[test]
+    fn epoch_summaries() {
+        let timings = BlockTimings::default();
+        timings.set_genesis_time(1_000);
+        for number in 0..EPOCH_SLOTS {
+            timings.record(timing(number, 100, 1_000));
+        }
+        timings.record(timing(EPOCH_SLOTS, 200, 5_000));
+        timings.record(timing(EPOCH_SLOTS + 1, 400, 3_000));
+
+        let summaries = timings.epoch_summaries(10);
+        assert_eq!(summaries.len(), 2);
+        assert_eq!(
+            summaries[0],
+            EpochTimingSummary {
+                epoch: 1,
+                blocks: 2,
+                avg_received_delay_ms: 300,
+                max_received_delay_ms: 400,
+                avg_executed_delay_ms: 4_000,
+                max_executed_delay_ms: 5_000,
+                min_deadline_margin_ms: -1_000,
+                missed_deadlines: 1,
+            }
+        );
+        assert_eq!(summaries[1].epoch, 0);
+        assert_eq!(summaries[1].blocks, EPOCH_SLOTS);
+        assert_eq!(summaries[1].min_deadline_margin_ms, 3_000);
+
+        assert_eq!(timings.epoch_summaries(1).len(), 1);
+    }
+
+    #[test]
+    fn record_replaces_reorged_blocks() {
+        let timings = BlockTimings::new(4);
+        for number in 0..6 {
+            timings.record(timing(number, 100, 1_000));
+        }
+        assert!(timings.block(1).is_none());
+        assert!(timings.block(5).is_some());
+
+        timings.record(timing(4, 100, 2_000));
+        assert_eq!(timings.block(4).unwrap().executed_delay_ms(), 2_000);
+        assert!(timings.block(5).is_none());
+    }
+}
@@ -1,10 +1,18 @@
-use crate::{ChainSpec, DepositContract};
+use crate::{ChainSpec, DepositContract, ScheduledSystemTransaction};
 use alloc::{boxed::Box, vec::Vec};
 use alloy_chains::Chain;
-use alloy_eips::{calc_next_block_base_fee, eip1559::BaseFeeParams, eip7840::BlobParams};
+use alloy_eips::{
+    calc_next_block_base_fee,
+    eip1559::BaseFeeParams,
+    eip7840::BlobParams,
+    merge::{EPOCH_SLOTS, SLOT_DURATION},
+};
 use alloy_genesis::Genesis;
 use alloy_primitives::{B256, U256};
-use core::fmt::{Debug, Display};
+use core::{
+    fmt::{Debug, Display},
+    time::Duration,
+};
 use reth_ethereum_forks::EthereumHardforks;
 use reth_network_peers::NodeRecord;
 use reth_primitives_traits::{AlloyBlockHeader, BlockHeader};
@@ -63,6 +71,22 @@ pub trait EthChainSpec: Send + Sync + Unpin + Debug {
     /// Returns the final total difficulty if the Paris hardfork is known.
     fn final_paris_total_difficulty(&self) -> Option<U256>;
 
+    /// Returns the duration of a slot of the consensus layer, in which at most one block is
+    /// proposed.
+    fn slot_duration(&self) -> Duration {
+        SLOT_DURATION
+    }
+
+    /// Returns the number of slots per epoch of the consensus layer.
+    fn epoch_slots(&self) -> u64 {
+        EPOCH_SLOTS
+    }
+
+    /// Returns the system transactions that are executed at the block boundaries.
+    fn system_transactions(&self) -> &[ScheduledSystemTransaction] {
+        &[]
+    }
+
     /// See [`calc_next_block_base_fee`].
     fn next_block_base_fee(&self, parent: &Self::Header, target_timestamp: u64) -> Option<u64> {
         Some(calc_next_block_base_fee(
@@ -134,4 +158,8 @@ impl<H: BlockHeader> EthChainSpec for ChainSpec<H> {
     fn final_paris_total_difficulty(&self) -> Option<U256> {
         self.paris_block_and_final_difficulty.map(|(_, final_difficulty)| final_difficulty)
     }
+
+    fn system_transactions(&self) -> &[ScheduledSystemTransaction] {
+        &self.system_transactions
+    }
 }
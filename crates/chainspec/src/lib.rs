@@ -20,6 +20,8 @@ mod api;
 mod info;
 /// The chain spec module.
 mod spec;
+/// Scheduled system transactions.
+mod system_tx;
 
 pub use alloy_chains::{Chain, ChainKind, NamedChain};
 /// Re-export for convenience
@@ -35,6 +37,9 @@ pub use spec::{
     BaseFeeParams, BaseFeeParamsKind, ChainSpec, ChainSpecBuilder, ChainSpecProvider,
     DepositContract, ForkBaseFeeParams, DEV, HOLESKY, HOODI, MAINNET, SEPOLIA,
 };
+pub use system_tx::{
+    ScheduledSystemTransaction, SystemTransactionPosition, SYSTEM_TRANSACTIONS_KEY,
+};
 
 use reth_primitives_traits::sync::OnceLock;
 
@@ -8,7 +8,7 @@ use crate::{
     mainnet::{MAINNET_PARIS_BLOCK, MAINNET_PARIS_TTD},
     sepolia,
     sepolia::SEPOLIA_PARIS_BLOCK,
-    EthChainSpec,
+    EthChainSpec, ScheduledSystemTransaction,
 };
 use alloc::{
     boxed::Box,
@@ -126,6 +126,7 @@ pub static MAINNET: LazyLock<Arc<ChainSpec>> = LazyLock::new(|| {
             (mainnet::MAINNET_BPO1_TIMESTAMP, BlobParams::bpo1()),
             (mainnet::MAINNET_BPO2_TIMESTAMP, BlobParams::bpo2()),
         ]),
+        system_transactions: Vec::new(),
     };
     spec.genesis.config.dao_fork_support = true;
     spec.into()
@@ -161,6 +162,7 @@ pub static SEPOLIA: LazyLock<Arc<ChainSpec>> = LazyLock::new(|| {
             (sepolia::SEPOLIA_BPO1_TIMESTAMP, BlobParams::bpo1()),
             (sepolia::SEPOLIA_BPO2_TIMESTAMP, BlobParams::bpo2()),
         ]),
+        system_transactions: Vec::new(),
     };
     spec.genesis.config.dao_fork_support = true;
     spec.into()
@@ -191,6 +193,7 @@ pub static HOLESKY: LazyLock<Arc<ChainSpec>> = LazyLock::new(|| {
             (holesky::HOLESKY_BPO1_TIMESTAMP, BlobParams::bpo1()),
             (holesky::HOLESKY_BPO2_TIMESTAMP, BlobParams::bpo2()),
         ]),
+        system_transactions: Vec::new(),
     };
     spec.genesis.config.dao_fork_support = true;
     spec.into()
@@ -223,6 +226,7 @@ pub static HOODI: LazyLock<Arc<ChainSpec>> = LazyLock::new(|| {
             (hoodi::HOODI_BPO1_TIMESTAMP, BlobParams::bpo1()),
             (hoodi::HOODI_BPO2_TIMESTAMP, BlobParams::bpo2()),
         ]),
+        system_transactions: Vec::new(),
     };
     spec.genesis.config.dao_fork_support = true;
     spec.into()
@@ -434,6 +438,10 @@ pub struct ChainSpec<H: BlockHeader = Header> {
 
     /// The settings passed for blob configurations for specific hardforks.
     pub blob_params: BlobScheduleBlobParams,
+
+    /// The system transactions that are executed at the block boundaries, see
+    /// [`ScheduledSystemTransaction`].
+    pub system_transactions: Vec<ScheduledSystemTransaction>,
 }
 
 impl<H: BlockHeader> Default for ChainSpec<H> {
@@ -448,6 +456,7 @@ impl<H: BlockHeader> Default for ChainSpec<H> {
             base_fee_params: BaseFeeParamsKind::Constant(BaseFeeParams::ethereum()),
             prune_delete_limit: MAINNET_PRUNE_DELETE_LIMIT,
             blob_params: Default::default(),
+            system_transactions: Default::default(),
         }
     }
 }
@@ -805,6 +814,7 @@ impl<H: BlockHeader> ChainSpec<H> {
             base_fee_params,
             prune_delete_limit,
             blob_params,
+            system_transactions,
         } = self;
         ChainSpec {
             chain,
@@ -816,12 +826,46 @@ impl<H: BlockHeader> ChainSpec<H> {
             base_fee_params,
             prune_delete_limit,
             blob_params,
+            system_transactions,
         }
     }
 }
 
 impl From<Genesis> for ChainSpec {
+    /// Converts the genesis into a chain spec.
+    ///
+    /// Invalid scheduled system transactions in the chain config are logged and ignored, see
+    /// [`ChainSpec::try_from_genesis`] for a conversion that rejects them.
     fn from(genesis: Genesis) -> Self {
+        let system_transactions = ScheduledSystemTransaction::from_genesis(&genesis)
+            .unwrap_or_else(|err| {
+                tracing::warn!(
+                    target: "reth::chainspec",
+                    %err,
+                    "Ignoring invalid scheduled system transactions"
+                );
+                Vec::new()
+            });
+        Self::from_genesis_with_system_transactions(genesis, system_transactions)
+    }
+}
+
+impl ChainSpec {
+    /// Converts the genesis into a chain spec.
+    ///
+    /// Returns an error if the chain config schedules invalid system transactions, see
+    /// [`ScheduledSystemTransaction::from_genesis`].
+    pub fn try_from_genesis(genesis: Genesis) -> Result<Self, serde_json::Error> {
+        let system_transactions = ScheduledSystemTransaction::from_genesis(&genesis)?;
+        Ok(Self::from_genesis_with_system_transactions(genesis, system_transactions))
+    }
+
+    /// Converts the genesis into a chain spec with the given, already parsed, scheduled system
+    /// transactions.
+    fn from_genesis_with_system_transactions(
+        genesis: Genesis,
+        system_transactions: Vec<ScheduledSystemTransaction>,
+    ) -> Self {
         // Block-based hardforks
         let hardfork_opts = [
             (EthereumHardfork::Frontier.boxed(), Some(0)),
@@ -949,6 +993,7 @@ impl From<Genesis> for ChainSpec {
             paris_block_and_final_difficulty,
             deposit_contract,
             blob_params,
+            system_transactions,
             ..Default::default()
         }
     }
@@ -2558,6 +2603,21 @@ Post-merge hard forks (timestamp based):
         assert_eq!(genesis.base_fee_per_gas, Some(0x1337));
     }
 
+    #[test]
+    fn invalid_system_transactions_in_genesis() {
+        let genesis: Genesis = serde_json::from_str(
+            r#"{"config":{"chainId":1337,"systemTransactions":[{"position":"midBlock"}]}}"#,
+        )
+        .unwrap();
+
+        assert!(ChainSpec::try_from_genesis(genesis.clone()).is_err());
+
+        // the infallible conversion ignores the invalid system transactions
+        let spec = ChainSpec::from(genesis);
+        assert_eq!(spec.chain, Chain::from_id(1337));
+        assert!(spec.system_transactions.is_empty());
+    }
+
     #[test]
     fn test_parse_cancun_genesis_json() {
         let s = r#"{"config":{"ethash":{},"chainId":1337,"homesteadBlock":0,"eip150Block":0,"eip155Block":0,"eip158Block":0,"byzantiumBlock":0,"constantinopleBlock":0,"petersburgBlock":0,"istanbulBlock":0,"berlinBlock":0,"londonBlock":0,"terminalTotalDifficulty":0,"terminalTotalDifficultyPassed":true,"shanghaiTime":0,"cancunTime":4661},"nonce":"0x0","timestamp":"0x0","extraData":"0x","gasLimit":"0x4c4b40","difficulty":"0x1","mixHash":"0x0000000000000000000000000000000000000000000000000000000000000000","coinbase":"0x0000000000000000000000000000000000000000","alloc":{"658bdf435d810c91414ec09147daa6db62406379":{"balance":"0x487a9a304539440000"},"aa00000000000000000000000000000000000000":{"code":"0x6042","storage":{"0x0000000000000000000000000000000000000000000000000000000000000000":"0x0000000000000000000000000000000000000000000000000000000000000000","0x0100000000000000000000000000000000000000000000000000000000000000":"0x0100000000000000000000000000000000000000000000000000000000000000","0x0200000000000000000000000000000000000000000000000000000000000000":"0x0200000000000000000000000000000000000000000000000000000000000000","0x0300000000000000000000000000000000000000000000000000000000000000":"0x0000000000000000000000000000000000000000000000000000000000000303"},"balance":"0x1","nonce":"0x1"},"bb00000000000000000000000000000000000000":{"code":"0x600154600354","storage":{"0x0000000000000000000000000000000000000000000000000000000000000000":"0x0000000000000000000000000000000000000000000000000000000000000000","0x0100000000000000000000000000000000000000000000000000000000000000":"0x0100000000000000000000000000000000000000000000000000000000000000","0x0200000000000000000000000000000000000000000000000000000000000000":"0x0200000000000000000000000000000000000000000000000000000000000000","0x0300000000000000000000000000000000000000000000000000000000000000":"0x0000000000000000000000000000000000000000000000000000000000000303"},"balance":"0x2","nonce":"0x1"}},"number":"0x0","gasUsed":"0x0","parentHash":"0x0000000000000000000000000000000000000000000000000000000000000000","baseFeePerGas":"0x3b9aca00"}"#;
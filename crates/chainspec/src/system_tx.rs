@@ -0,0 +1,138 @@
+//! System transactions that are scheduled by the chain specification.
+
+use alloc::vec::Vec;
+use alloy_eips::eip4788::SYSTEM_ADDRESS;
+use alloy_genesis::Genesis;
+use alloy_primitives::{Address, Bytes};
+
+/// The key of the scheduled system transactions in the chain config of the genesis file.
+pub const SYSTEM_TRANSACTIONS_KEY: &str = "systemTransactions";
+
+/// Where in the block a [`ScheduledSystemTransaction`] is executed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SystemTransactionPosition {
+    /// Executed after the pre-execution changes of the block, before the first transaction.
+    PreBlock,
+    /// Executed after the last transaction of the block, before the post-execution changes.
+    PostBlock,
+}
+
+/// A system call that is executed at a block boundary of every block it is due for, e.g. an
+/// oracle update or a fee distribution of an appchain.
+///
+/// Like the system calls of [EIP-4788](https://eips.ethereum.org/EIPS/eip-4788), scheduled system
+/// transactions are not part of the block body: they don't consume block gas, don't produce a
+/// receipt and only their state changes are committed. This makes them deterministic for both
+/// block import and payload building.
+///
+/// Scheduled system transactions are configured in the chain config of the genesis file under
+/// the [`SYSTEM_TRANSACTIONS_KEY`]:
+///
+/// ```json
+/// "systemTransactions": [
+///     { "position": "postBlock", "to": "0x...", "input": "0x...", "interval": 100 }
+/// ]
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduledSystemTransaction {
+    /// Where in the block the system transaction is executed.
+    pub position: SystemTransactionPosition,
+    /// The caller of the system transaction, the [`SYSTEM_ADDRESS`] by default.
+    #[serde(default = "default_system_caller")]
+    pub from: Address,
+    /// The called contract.
+    pub to: Address,
+    /// The calldata of the system transaction.
+    #[serde(default)]
+    pub input: Bytes,
+    /// The first block the system transaction is executed in.
+    #[serde(default)]
+    pub start_block: u64,
+    /// The last block the system transaction is executed in, if any.
+    #[serde(default)]
+    pub end_block: Option<u64>,
+    /// The system transaction is executed in every `interval`-th block, counted from the
+    /// `start_block`.
+    #[serde(default = "default_interval")]
+    pub interval: u64,
+}
+
+impl ScheduledSystemTransaction {
+    /// Parses the scheduled system transactions of the chain config of the genesis file.
+    ///
+    /// Returns an empty list if the chain config doesn't schedule any system transactions.
+    pub fn from_genesis(genesis: &Genesis) -> Result<Vec<Self>, serde_json::Error> {
+        genesis
+            .config
+            .extra_fields
+            .get_deserialized::<Vec<Self>>(SYSTEM_TRANSACTIONS_KEY)
+            .transpose()
+            .map(Option::unwrap_or_default)
+    }
+
+    /// Returns `true` if the system transaction is executed in the given block.
+    pub fn is_due(&self, block_number: u64) -> bool {
+        if block_number < self.start_block || self.end_block.is_some_and(|end| block_number > end) {
+            return false
+        }
+        // an interval of zero is treated as executing the system transaction in every block
+        (block_number - self.start_block) % self.interval.max(1) == 0
+    }
+}
+
+const fn default_system_caller() -> Address {
+    SYSTEM_ADDRESS
+}
+
+const fn default_interval() -> u64 {
+    1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    #[test]
+    fn parse_scheduled_system_transactions() {
+        let genesis: Genesis = serde_json::from_str(
+            r#"{
+                "config": {
+                    "chainId": 1337,
+                    "systemTransactions": [
+                        {
+                            "position": "preBlock",
+                            "to": "0x000000000000000000000000000000000000beef",
+                            "input": "0x01"
+                        },
+                        {
+                            "position": "postBlock",
+                            "from": "0x0000000000000000000000000000000000001234",
+                            "to": "0x000000000000000000000000000000000000cafe",
+                            "startBlock": 10,
+                            "endBlock": 30,
+                            "interval": 10
+                        }
+                    ]
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let txs = ScheduledSystemTransaction::from_genesis(&genesis).unwrap();
+        assert_eq!(txs.len(), 2);
+        assert_eq!(txs[0].position, SystemTransactionPosition::PreBlock);
+        assert_eq!(txs[0].from, SYSTEM_ADDRESS);
+        assert_eq!(txs[0].to, address!("0x000000000000000000000000000000000000beef"));
+        assert!((0..5).all(|block| txs[0].is_due(block)));
+
+        assert_eq!(txs[1].position, SystemTransactionPosition::PostBlock);
+        assert_eq!(txs[1].from, address!("0x0000000000000000000000000000000000001234"));
+        let due = (0..50).filter(|block| txs[1].is_due(*block)).collect::<Vec<_>>();
+        assert_eq!(due, [10, 20, 30]);
+
+        assert!(ScheduledSystemTransaction::from_genesis(&Genesis::default()).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,449 @@
+//! Command that backfills receipts of a block range from the network.
+
+use crate::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
+use alloy_consensus::{proofs::calculate_receipt_root, BlockHeader, TxReceipt};
+use alloy_primitives::{BlockNumber, Bloom, B256};
+use clap::Parser;
+use reth_chainspec::{EthChainSpec, EthereumHardforks, Hardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_cli_util::get_secret_key;
+use reth_db_api::{tables, transaction::DbTxMut};
+use reth_eth_wire::{EthVersion, GetReceipts, GetReceipts70};
+use reth_network::{types::ReputationChangeKind, NetworkHandle, PeerRequest, Peers};
+use reth_network_peers::PeerId;
+use reth_node_api::{HeaderTy, ReceiptTy};
+use reth_node_core::{args::NetworkArgs, version::version_metadata};
+use reth_primitives_traits::{Receipt, SealedHeader};
+use reth_provider::{
+    providers::ProviderNodeTypes, BlockBodyIndicesProvider, BlockNumReader, ChainSpecProvider,
+    DBProvider, DatabaseProviderFactory, EitherWriter, HeaderProvider, ProviderFactory,
+    PruneCheckpointReader, PruneCheckpointWriter, StaticFileProviderFactory,
+};
+use reth_prune::{PruneCheckpoint, PruneSegment};
+use reth_static_file_types::StaticFileSegment;
+use std::{sync::Arc, time::Duration};
+use tokio::sync::oneshot;
+use tracing::{debug, info, warn};
+
+/// `reth backfill receipts` command
+///
+/// Downloads the receipts of a block range from peers via `GetReceipts`, checks them against the
+/// stored headers and writes them to storage. Headers and transactions of the range must already
+/// be present.
+///
+/// Receipts from Byzantium on are checked against the receipts roots of the headers. Older receipts
+/// committed to intermediate state roots instead of a status, which can't be checked without
+/// executing the blocks, so only their logs blooms and gas used are checked.
+///
+/// If receipts are stored in static files, the range must either directly follow the highest
+/// receipts static file, or end below the lowest one. Receipts below the static files are written
+/// to the database, which serves them from then on. If the range closes the gap left by pruning,
+/// the receipts prune checkpoints are lowered to the block before the range.
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    #[command(flatten)]
+    network: NetworkArgs,
+
+    /// The first block to backfill receipts for
+    #[arg(long)]
+    from: BlockNumber,
+
+    /// The last block to backfill receipts for
+    #[arg(long)]
+    to: BlockNumber,
+
+    /// The maximum number of blocks to request receipts for at once
+    #[arg(long, default_value = "128")]
+    batch_size: u64,
+
+    /// The number of peers to try before giving up on a batch
+    #[arg(long, default_value = "5")]
+    retries: usize,
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>> Command<C> {
+    /// Execute `backfill receipts` command
+    pub async fn execute<N: CliNodeTypes<ChainSpec = C::ChainSpec>>(self) -> eyre::Result<()> {
+        info!(target: "reth::cli", "reth {} starting", version_metadata().short_version);
+
+        if self.from > self.to {
+            eyre::bail!("--from ({}) must not be greater than --to ({})", self.from, self.to)
+        }
+
+        let Environment { provider_factory, mut config, data_dir } =
+            self.env.init::<N>(AccessRights::RW)?;
+
+        let best_block = provider_factory.best_block_number()?;
+        if self.to > best_block {
+            eyre::bail!(
+                "Blocks up to {} must be synced, but the best block is {best_block}",
+                self.to
+            )
+        }
+
+        // Static files can only be extended, so older receipts are written to the database
+        let mut to_static_files = false;
+        if EitherWriter::receipts_destination(&provider_factory.provider()?).is_static_file() {
+            let static_file_provider = provider_factory.static_file_provider();
+            let next_block = static_file_provider
+                .get_highest_static_file_block(StaticFileSegment::Receipts)
+                .map_or(0, |block| block + 1);
+            let lowest_block = static_file_provider
+                .get_lowest_range_start(StaticFileSegment::Receipts)
+                .unwrap_or(next_block);
+            if self.from == next_block {
+                to_static_files = true;
+            } else if self.to >= lowest_block {
+                eyre::bail!(
+                    "Receipts are stored in static files from block {lowest_block}, which can only be extended, expected --from {next_block} or --to below {lowest_block}"
+                )
+            }
+        }
+
+        config.peers.trusted_nodes_only = self.network.trusted_only;
+        config.peers.trusted_nodes.extend(self.network.trusted_peers.clone());
+
+        let network_secret_path =
+            self.network.p2p_secret_key.clone().unwrap_or_else(|| data_dir.p2p_secret());
+        let p2p_secret_key = get_secret_key(&network_secret_path)?;
+
+        let network = self
+            .network
+            .network_config::<N::NetworkPrimitives>(
+                &config,
+                provider_factory.chain_spec(),
+                p2p_secret_key,
+                data_dir.known_peers(),
+            )
+            .build(provider_factory.clone())
+            .start_network()
+            .await?;
+
+        let mut block = self.from;
+        while block <= self.to {
+            let end = self.to.min(block.saturating_add(self.batch_size.max(1) - 1));
+            let headers = provider_factory.sealed_headers_range(block..=end)?;
+            let receipts = self
+                .download_receipts::<N>(&network, &provider_factory.chain_spec(), &headers)
+                .await?;
+
+            let downloaded = receipts.len() as u64;
+            write_receipts(&provider_factory, block, receipts, to_static_files)?;
+            info!(target: "reth::cli", from = block, to = block + downloaded - 1, "Backfilled receipts");
+
+            block += downloaded;
+        }
+
+        lower_prune_checkpoints(&provider_factory, self.from, self.to)?;
+
+        info!(target: "reth::cli", from = self.from, to = self.to, "Receipts backfill finished");
+
+        Ok(())
+    }
+
+    /// Downloads and validates the receipts of the given headers, trying another peer if a request
+    /// fails.
+    ///
+    /// Peers may respond with the receipts of fewer blocks than requested, so only the receipts of
+    /// a non-empty prefix of the headers are returned.
+    async fn download_receipts<N: CliNodeTypes>(
+        &self,
+        network: &NetworkHandle<N::NetworkPrimitives>,
+        chain_spec: &C::ChainSpec,
+        headers: &[SealedHeader<HeaderTy<N>>],
+    ) -> eyre::Result<Vec<Vec<ReceiptTy<N>>>> {
+        let block_hashes = headers.iter().map(|header| header.hash()).collect::<Vec<_>>();
+
+        let mut attempts = 0;
+        loop {
+            let peers = network.get_all_peers().await?;
+            let Some(peer) = peers.get(attempts % peers.len().max(1)) else {
+                debug!(target: "reth::cli", "Waiting for peers");
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                continue
+            };
+            attempts += 1;
+
+            match request_receipts::<N>(
+                network,
+                peer.remote_id,
+                peer.eth_version,
+                block_hashes.clone(),
+            )
+            .await
+            {
+                Ok(receipts) if receipts.is_empty() => {
+                    debug!(target: "reth::cli", peer_id = %peer.remote_id, "Peer returned no receipts");
+                }
+                Ok(receipts) => match validate_receipts(chain_spec, headers, &receipts) {
+                    Ok(()) => return Ok(receipts),
+                    Err(err) => {
+                        warn!(target: "reth::cli", peer_id = %peer.remote_id, %err, "Peer returned invalid receipts");
+                        network.reputation_change(peer.remote_id, ReputationChangeKind::BadMessage);
+                    }
+                },
+                Err(err) => {
+                    debug!(target: "reth::cli", peer_id = %peer.remote_id, %err, "Failed to request receipts");
+                }
+            }
+
+            if attempts >= self.retries.max(1) {
+                eyre::bail!(
+                    "Failed to download receipts of block {} after {attempts} attempts",
+                    headers[0].number()
+                )
+            }
+        }
+    }
+}
+
+impl<C: ChainSpecParser> Command<C> {
+    /// Returns the underlying chain being used to run this command
+    pub const fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        Some(&self.env.chain)
+    }
+}
+
+/// Requests the receipts of the given blocks from the peer, using the request of the negotiated
+/// `eth` version.
+async fn request_receipts<N: CliNodeTypes>(
+    network: &NetworkHandle<N::NetworkPrimitives>,
+    peer_id: PeerId,
+    version: EthVersion,
+    block_hashes: Vec<B256>,
+) -> eyre::Result<Vec<Vec<ReceiptTy<N>>>> {
+    let receipts = if version >= EthVersion::Eth70 {
+        let (response, rx) = oneshot::channel();
+        let request = GetReceipts70 { first_block_receipt_index: 0, block_hashes };
+        network.send_request(peer_id, PeerRequest::GetReceipts70 { request, response });
+        let mut receipts = rx.await??;
+        // The receipts of the last block are truncated if the response exceeded the size limit
+        if receipts.last_block_incomplete {
+            receipts.receipts.pop();
+        }
+        receipts.receipts
+    } else if version >= EthVersion::Eth69 {
+        let (response, rx) = oneshot::channel();
+        let request = GetReceipts(block_hashes);
+        network.send_request(peer_id, PeerRequest::GetReceipts69 { request, response });
+        rx.await??.0
+    } else {
+        let (response, rx) = oneshot::channel();
+        let request = GetReceipts(block_hashes);
+        network.send_request(peer_id, PeerRequest::GetReceipts { request, response });
+        rx.await??
+            .0
+            .into_iter()
+            .map(|receipts| receipts.into_iter().map(|receipt| receipt.receipt).collect())
+            .collect()
+    };
+
+    Ok(receipts)
+}
+
+/// Checks the receipts against the headers they were requested for.
+///
+/// From Byzantium on, the receipts root is compared. Receipts of earlier blocks committed to
+/// intermediate state roots that are not part of the receipts, so only their logs bloom and gas
+/// used are compared.
+fn validate_receipts<H: BlockHeader, R: Receipt>(
+    chain_spec: impl EthereumHardforks,
+    headers: &[SealedHeader<H>],
+    receipts: &[Vec<R>],
+) -> eyre::Result<()> {
+    if receipts.len() > headers.len() {
+        eyre::bail!(
+            "Received receipts of {} blocks, but requested {}",
+            receipts.len(),
+            headers.len()
+        )
+    }
+
+    for (header, receipts) in headers.iter().zip(receipts) {
+        if !chain_spec.is_byzantium_active_at_block(header.number()) {
+            let gas_used = receipts.last().map_or(0, |receipt| receipt.cumulative_gas_used());
+            if gas_used != header.gas_used() {
+                eyre::bail!(
+                    "Gas used mismatch for block {}: expected {}, got {gas_used}",
+                    header.number(),
+                    header.gas_used()
+                )
+            }
+
+            let mut logs_bloom = Bloom::ZERO;
+            for receipt in receipts {
+                logs_bloom.accrue_bloom(&receipt.bloom());
+            }
+            if logs_bloom != header.logs_bloom() {
+                eyre::bail!(
+                    "Logs bloom mismatch for block {}: expected {}, got {logs_bloom}",
+                    header.number(),
+                    header.logs_bloom()
+                )
+            }
+            continue
+        }
+
+        let receipts_with_bloom =
+            receipts.iter().map(TxReceipt::with_bloom_ref).collect::<Vec<_>>();
+        let receipts_root = calculate_receipt_root(&receipts_with_bloom);
+        if receipts_root != header.receipts_root() {
+            eyre::bail!(
+                "Receipts root mismatch for block {}: expected {}, got {receipts_root}",
+                header.number(),
+                header.receipts_root()
+            )
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes the receipts of consecutive blocks starting at `first_block`, either appending them to
+/// the receipts static files or writing them to the database.
+fn write_receipts<N: ProviderNodeTypes>(
+    provider_factory: &ProviderFactory<N>,
+    first_block: BlockNumber,
+    receipts: Vec<Vec<ReceiptTy<N>>>,
+    to_static_files: bool,
+) -> eyre::Result<()> {
+    let provider = provider_factory.database_provider_rw()?;
+    let last_block = first_block + receipts.len() as u64 - 1;
+    let block_indices = provider.block_body_indices_range(first_block..=last_block)?;
+    if block_indices.len() != receipts.len() {
+        eyre::bail!("Missing block bodies in range {first_block}..={last_block}")
+    }
+
+    let mut static_file_writer = to_static_files
+        .then(|| provider.get_static_file_writer(first_block, StaticFileSegment::Receipts))
+        .transpose()?;
+
+    for ((block_number, receipts), indices) in (first_block..).zip(receipts).zip(block_indices) {
+        if let Some(writer) = &mut static_file_writer {
+            writer.increment_block(block_number)?;
+        }
+
+        for (tx_num, receipt) in indices.tx_num_range().zip(receipts) {
+            if let Some(writer) = &mut static_file_writer {
+                writer.append_receipt(tx_num, &receipt)?;
+            } else {
+                // Backfilled receipts precede the stored ones, so they can't be appended
+                provider.tx_ref().put::<tables::Receipts<ReceiptTy<N>>>(tx_num, receipt)?;
+            }
+        }
+    }
+
+    drop(static_file_writer);
+    provider.commit()?;
+
+    Ok(())
+}
+
+/// Lowers the receipts prune checkpoints to the block before `from`, if the backfilled range
+/// reaches the highest pruned block.
+///
+/// A checkpoint above `to` is left as is, since the blocks in between are still pruned.
+fn lower_prune_checkpoints<N: ProviderNodeTypes>(
+    provider_factory: &ProviderFactory<N>,
+    from: BlockNumber,
+    to: BlockNumber,
+) -> eyre::Result<()> {
+    let provider = provider_factory.database_provider_rw()?;
+    for segment in [PruneSegment::Receipts, PruneSegment::ContractLogs] {
+        let Some(checkpoint) = provider.get_prune_checkpoint(segment)? else { continue };
+        let Some(pruned_block) = checkpoint.block_number else { continue };
+        if pruned_block < from {
+            continue
+        }
+        if pruned_block > to {
+            warn!(target: "reth::cli", ?segment, pruned_block, "Receipts above the backfilled range are still pruned, prune checkpoint left unchanged");
+            continue
+        }
+
+        let tx_number = if from == 0 {
+            None
+        } else {
+            provider
+                .block_body_indices(from)?
+                .ok_or_else(|| eyre::eyre!("Missing block body indices of block {from}"))?
+                .first_tx_num()
+                .checked_sub(1)
+        };
+        let checkpoint =
+            PruneCheckpoint { block_number: from.checked_sub(1), tx_number, ..checkpoint };
+        provider.save_prune_checkpoint(segment, checkpoint)?;
+        info!(target: "reth::cli", ?segment, ?checkpoint, "Lowered prune checkpoint");
+    }
+    provider.commit()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Header;
+    use alloy_primitives::{Address, Log, LogData};
+    use reth_chainspec::{ChainSpecBuilder, EthereumHardfork, ForkCondition};
+    use reth_ethereum_primitives::{Receipt, TxType};
+
+    fn receipts(logs: Vec<Log>) -> Vec<Receipt> {
+        vec![
+            Receipt { tx_type: TxType::Legacy, success: true, cumulative_gas_used: 21_000, logs },
+            Receipt {
+                tx_type: TxType::Legacy,
+                success: false,
+                cumulative_gas_used: 42_000,
+                logs: Vec::new(),
+            },
+        ]
+    }
+
+    fn header(number: BlockNumber, receipts: &[Receipt]) -> SealedHeader {
+        let receipts_with_bloom =
+            receipts.iter().map(TxReceipt::with_bloom_ref).collect::<Vec<_>>();
+        let mut logs_bloom = Bloom::ZERO;
+        for receipt in receipts {
+            logs_bloom.accrue_bloom(&receipt.bloom());
+        }
+        SealedHeader::seal_slow(Header {
+            number,
+            receipts_root: calculate_receipt_root(&receipts_with_bloom),
+            logs_bloom,
+            gas_used: 42_000,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn validate_receipts_by_fork() {
+        let chain_spec = ChainSpecBuilder::mainnet()
+            .with_fork(EthereumHardfork::Byzantium, ForkCondition::Block(10))
+            .build();
+        let log = Log { address: Address::with_last_byte(1), data: LogData::default() };
+        let valid = receipts(vec![log]);
+
+        for number in [9, 10] {
+            let headers = [header(number, &valid)];
+            validate_receipts(&chain_spec, &headers, std::slice::from_ref(&valid)).unwrap();
+
+            // Missing logs change the bloom before Byzantium and the root after it
+            let invalid = receipts(Vec::new());
+            assert!(validate_receipts(&chain_spec, &headers, &[invalid]).is_err());
+
+            let mut invalid = valid.clone();
+            invalid[1].cumulative_gas_used += 1;
+            assert!(validate_receipts(&chain_spec, &headers, &[invalid]).is_err());
+        }
+
+        // The status of pre-Byzantium receipts is not committed to by the header
+        let mut status = valid.clone();
+        status[0].success = false;
+        validate_receipts(&chain_spec, &[header(9, &valid)], std::slice::from_ref(&status))
+            .unwrap();
+        assert!(validate_receipts(&chain_spec, &[header(10, &valid)], &[status]).is_err());
+    }
+}
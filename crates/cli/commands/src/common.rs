@@ -77,6 +77,7 @@ impl<C: ChainSpecParser> EnvironmentArgs<C> {
     pub fn storage_settings(&self) -> StorageSettings {
         StorageSettings::base()
             .with_receipts_in_static_files(self.static_files.receipts)
+            .with_receipts_hot_blocks(self.static_files.receipts_hot_blocks)
             .with_transaction_senders_in_static_files(self.static_files.transaction_senders)
             .with_account_changesets_in_static_files(self.static_files.account_changesets)
             .with_transaction_hash_numbers_in_rocksdb(self.rocksdb.all || self.rocksdb.tx_hash)
@@ -116,19 +117,26 @@ impl<C: ChainSpecParser> EnvironmentArgs<C> {
         if config.stages.era.folder.is_none() {
             config.stages.era = config.stages.era.with_datadir(data_dir.data_dir());
         }
+        if config.stages.headers.download_checkpoint_dir.is_none() {
+            config.stages.headers = config.stages.headers.with_datadir(data_dir.data_dir());
+        }
 
         info!(target: "reth::cli", ?db_path, ?sf_path, "Opening storage");
         let genesis_block_number = self.chain.genesis().number.unwrap_or_default();
+        let segment_dirs = config.static_files.as_segment_directories_map(&sf_path);
         let (db, sfp) = match access {
             AccessRights::RW => (
                 Arc::new(init_db(db_path, self.db.database_args())?),
                 StaticFileProviderBuilder::read_write(sf_path)
+                    .with_shared_dir(config.static_files.shared_dir.clone())
+                    .with_segment_dirs(&segment_dirs)
                     .with_genesis_block_number(genesis_block_number)
                     .build()?,
             ),
             AccessRights::RO | AccessRights::RoInconsistent => {
                 (Arc::new(open_db_read_only(&db_path, self.db.database_args())?), {
                     let provider = StaticFileProviderBuilder::read_only(sf_path)
+                        .with_segment_dirs(&segment_dirs)
                         .with_genesis_block_number(genesis_block_number)
                         .build()?;
                     provider.watch_directory();
@@ -0,0 +1,231 @@
+//! Checksum manifest of all tables and static file segments.
+
+use super::{hash_static_file_segment, ChecksumViewer};
+use crate::common::CliNodeTypes;
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_db::{lockfile::StorageLock, DatabaseEnv};
+use reth_db_api::{TableViewer, Tables};
+use reth_db_common::DbTool;
+use reth_node_builder::NodeTypesWithDBAdapter;
+use reth_provider::{DBProvider, StageCheckpointReader, StaticFileProviderFactory};
+use reth_stages::StageId;
+use reth_static_file_types::StaticFileSegment;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::PathBuf,
+    sync::Arc,
+};
+use tracing::{info, warn};
+
+/// Tables with data that is local to the node, e.g. its pruning or migration progress, which
+/// differs between nodes with identical chain data.
+const NODE_LOCAL_TABLES: [Tables; 5] = [
+    Tables::VersionHistory,
+    Tables::StageCheckpointProgresses,
+    Tables::PruneCheckpoints,
+    Tables::Metadata,
+    Tables::ChainState,
+];
+
+/// Checksums of the data of a node, in a canonical order.
+///
+/// Two nodes at the same [`block`](Self::block) have byte-identical data if their manifests are
+/// equal.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct ChecksumManifest {
+    /// The chain id of the node.
+    chain_id: u64,
+    /// The highest fully synced block, i.e. the checkpoint of the `Finish` stage.
+    block: u64,
+    /// Checksums of the MDBX tables by table name.
+    tables: BTreeMap<String, ChecksumEntry>,
+    /// Checksums of the static file segments by segment name.
+    static_files: BTreeMap<String, ChecksumEntry>,
+    /// Checksums of the RocksDB tables by table name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    rocksdb: BTreeMap<String, ChecksumEntry>,
+}
+
+/// Checksum of a table or static file segment.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct ChecksumEntry {
+    /// The checksum of the raw keys and values, as hex string.
+    checksum: String,
+    /// The number of hashed entries.
+    entries: usize,
+}
+
+impl ChecksumEntry {
+    fn new(checksum: u64, entries: usize) -> Self {
+        Self { checksum: format!("{checksum:#018x}"), entries }
+    }
+}
+
+impl ChecksumManifest {
+    /// Returns the names of the tables and segments whose checksums differ from the ones of the
+    /// other manifest, including the ones that are missing in either manifest.
+    fn mismatches(&self, other: &Self) -> Vec<String> {
+        let mut mismatches = Vec::new();
+        for (kind, ours, theirs) in [
+            ("table", &self.tables, &other.tables),
+            ("static file segment", &self.static_files, &other.static_files),
+            ("rocksdb table", &self.rocksdb, &other.rocksdb),
+        ] {
+            let names = ours.keys().chain(theirs.keys()).collect::<BTreeSet<_>>();
+            for name in names {
+                match (ours.get(name), theirs.get(name)) {
+                    (Some(ours), Some(theirs)) if ours == theirs => {}
+                    (Some(ours), Some(theirs)) => mismatches.push(format!(
+                        "{kind} `{name}`: {} ({} entries) != {} ({} entries)",
+                        ours.checksum, ours.entries, theirs.checksum, theirs.entries
+                    )),
+                    (Some(_), None) => {
+                        mismatches.push(format!("{kind} `{name}`: missing in other"))
+                    }
+                    (None, _) => mismatches.push(format!("{kind} `{name}`: missing locally")),
+                }
+            }
+        }
+        mismatches
+    }
+}
+
+/// Computes the checksum manifest of the node, writes it to the output file or stdout and
+/// optionally compares it with the manifest of another node.
+pub(crate) fn checksum_manifest<N: CliNodeTypes<ChainSpec: EthereumHardforks>>(
+    tool: &DbTool<NodeTypesWithDBAdapter<N, Arc<DatabaseEnv>>>,
+    output: Option<PathBuf>,
+    compare: Option<PathBuf>,
+) -> eyre::Result<()> {
+    // load the other manifest first, so an invalid path fails before the expensive hashing
+    let other: Option<ChecksumManifest> = compare
+        .map(|path| -> eyre::Result<_> {
+            Ok(serde_json::from_str(&reth_fs_util::read_to_string(path)?)?)
+        })
+        .transpose()?;
+
+    let manifest = compute_manifest(tool)?;
+
+    let json = serde_json::to_string_pretty(&manifest)?;
+    match output {
+        Some(path) => {
+            reth_fs_util::write(&path, json)?;
+            info!("Wrote checksum manifest to {}", path.display());
+        }
+        None => println!("{json}"),
+    }
+
+    let Some(other) = other else { return Ok(()) };
+
+    if manifest.chain_id != other.chain_id {
+        eyre::bail!("Chain ids differ: {} != {}", manifest.chain_id, other.chain_id);
+    }
+    if manifest.block != other.block {
+        warn!(
+            "Manifests were computed at different heights: {} != {}",
+            manifest.block, other.block
+        );
+    }
+
+    let mismatches = manifest.mismatches(&other);
+    if mismatches.is_empty() {
+        info!("Checksums match the other manifest");
+        return Ok(())
+    }
+    for mismatch in &mismatches {
+        warn!("Checksum mismatch, {mismatch}");
+    }
+    eyre::bail!("{} checksums differ from the other manifest", mismatches.len())
+}
+
+/// Computes the checksums of all tables, except the [`NODE_LOCAL_TABLES`], and static file
+/// segments.
+///
+/// Static files are not covered by database transactions, so the manifest can't be computed while
+/// the node is running.
+fn compute_manifest<N: CliNodeTypes<ChainSpec: EthereumHardforks>>(
+    tool: &DbTool<NodeTypesWithDBAdapter<N, Arc<DatabaseEnv>>>,
+) -> eyre::Result<ChecksumManifest> {
+    let static_file_provider = tool.provider_factory.static_file_provider();
+    // held until the manifest is computed, so the node can't start in the meantime
+    let _lock = StorageLock::try_acquire(static_file_provider.directory()).map_err(|err| {
+        eyre::eyre!("Checksum manifest can't be computed while the node is running: {err}")
+    })?;
+
+    // all tables are read in one transaction, so their checksums match the same block
+    let provider = tool.provider_factory.provider()?.disable_long_read_transaction_safety();
+    let block = provider.get_stage_checkpoint(StageId::Finish)?.unwrap_or_default().block_number;
+
+    let mut tables = BTreeMap::new();
+    for table in Tables::ALL.iter().filter(|table| !NODE_LOCAL_TABLES.contains(table)) {
+        info!("Computing checksum for table `{}`", table.name());
+        let (checksum, entries, _) =
+            table.view(&ChecksumViewer::new(tool).with_provider(&provider))?;
+        tables.insert(table.name().to_string(), ChecksumEntry::new(checksum, entries));
+    }
+
+    if let Err(err) = static_file_provider.check_consistency(&provider) {
+        warn!("Error checking consistency of static files: {err}");
+    }
+    let segments = static_file_provider.static_files()?;
+
+    let mut static_files = BTreeMap::new();
+    for segment in StaticFileSegment::iter().filter(|segment| segments.get(*segment).is_some()) {
+        let (checksum, entries) = hash_static_file_segment(tool, segment, None, None, None)?;
+        static_files.insert(segment.as_str().to_string(), ChecksumEntry::new(checksum, entries));
+    }
+
+    #[cfg_attr(not(all(unix, feature = "edge")), expect(unused_mut))]
+    let mut rocksdb = BTreeMap::new();
+    #[cfg(all(unix, feature = "edge"))]
+    {
+        use super::rocksdb::{hash_rocksdb_table, RocksDbTable};
+        use clap::ValueEnum;
+        use reth_provider::RocksDBProviderFactory;
+
+        let provider = tool.provider_factory.rocksdb_provider();
+        for table in RocksDbTable::value_variants() {
+            info!("Computing checksum for RocksDB table `{}`", table.name());
+            let (checksum, entries) = hash_rocksdb_table(&provider, *table, usize::MAX)?;
+            rocksdb.insert(table.name().to_string(), ChecksumEntry::new(checksum, entries));
+        }
+    }
+
+    Ok(ChecksumManifest { chain_id: tool.chain().chain_id(), block, tables, static_files, rocksdb })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manifest_mismatches() {
+        let manifest = ChecksumManifest {
+            chain_id: 1,
+            block: 10,
+            tables: BTreeMap::from([
+                ("Headers".to_string(), ChecksumEntry::new(1, 1)),
+                ("Bodies".to_string(), ChecksumEntry::new(2, 1)),
+            ]),
+            static_files: BTreeMap::from([("headers".to_string(), ChecksumEntry::new(3, 10))]),
+            rocksdb: BTreeMap::new(),
+        };
+
+        let decoded: ChecksumManifest =
+            serde_json::from_str(&serde_json::to_string(&manifest).unwrap()).unwrap();
+        assert_eq!(decoded, manifest);
+        assert!(manifest.mismatches(&decoded).is_empty());
+
+        let mut other = manifest.clone();
+        other.tables.insert("Bodies".to_string(), ChecksumEntry::new(4, 1));
+        other.static_files.clear();
+        assert_eq!(
+            manifest.mismatches(&other),
+            [
+                "table `Bodies`: 0x0000000000000002 (1 entries) != 0x0000000000000004 (1 entries)",
+                "static file segment `headers`: missing in other",
+            ]
+        );
+    }
+}
@@ -6,22 +6,26 @@ use alloy_primitives::map::foldhash::fast::FixedState;
 use clap::Parser;
 use itertools::Itertools;
 use reth_chainspec::EthereumHardforks;
-use reth_db::{static_file::iter_static_files, DatabaseEnv};
+use reth_db::DatabaseEnv;
 use reth_db_api::{
     cursor::DbCursorRO, table::Table, transaction::DbTx, RawKey, RawTable, RawValue, TableViewer,
     Tables,
 };
 use reth_db_common::DbTool;
 use reth_node_builder::{NodeTypesWithDB, NodeTypesWithDBAdapter};
-use reth_provider::{providers::ProviderNodeTypes, DBProvider, StaticFileProviderFactory};
+use reth_provider::{
+    providers::ProviderNodeTypes, DBProvider, DatabaseProviderRO, StaticFileProviderFactory,
+};
 use reth_static_file_types::StaticFileSegment;
 use std::{
     hash::{BuildHasher, Hasher},
+    path::PathBuf,
     sync::Arc,
     time::{Duration, Instant},
 };
 use tracing::{info, warn};
 
+mod manifest;
 #[cfg(all(unix, feature = "edge"))]
 mod rocksdb;
 
@@ -84,6 +88,21 @@ enum Subcommand {
         #[arg(long)]
         limit: Option<usize>,
     },
+    /// Calculates the checksums of all tables and static file segments and prints them as a
+    /// manifest in a canonical order.
+    ///
+    /// Two nodes at the same height have byte-identical data if their manifests are equal. Tables
+    /// with node-local data, like prune checkpoints and migration history, are skipped. The node
+    /// must not be running.
+    Manifest {
+        /// Write the manifest to the given file instead of printing it.
+        #[arg(long, value_name = "PATH")]
+        output: Option<PathBuf>,
+
+        /// Compare the checksums with the manifest of another node and fail if they differ.
+        #[arg(long, value_name = "PATH")]
+        compare: Option<PathBuf>,
+    },
 }
 
 impl Command {
@@ -96,7 +115,7 @@ impl Command {
 
         match self.subcommand {
             Subcommand::Mdbx { table, start_key, end_key, limit } => {
-                table.view(&ChecksumViewer { tool, start_key, end_key, limit })?;
+                table.view(&ChecksumViewer { tool, provider: None, start_key, end_key, limit })?;
             }
             Subcommand::StaticFile { segment, start_block, end_block, limit } => {
                 checksum_static_file(tool, segment, start_block, end_block, limit)?;
@@ -105,6 +124,9 @@ impl Command {
             Subcommand::Rocksdb { table, limit } => {
                 rocksdb::checksum_rocksdb(tool, table, limit)?;
             }
+            Subcommand::Manifest { output, compare } => {
+                manifest::checksum_manifest(tool, output, compare)?;
+            }
         }
 
         Ok(())
@@ -128,13 +150,34 @@ fn checksum_static_file<N: CliNodeTypes<ChainSpec: EthereumHardforks>>(
         warn!("Error checking consistency of static files: {err}");
     }
 
-    let static_files = iter_static_files(static_file_provider.directory())?;
+    let start_time = Instant::now();
+    let (checksum, total) = hash_static_file_segment(tool, segment, start_block, end_block, limit)?;
+    let elapsed = start_time.elapsed();
+
+    info!(
+        "Checksum for static file segment `{}`: {:#x} ({} entries, elapsed: {:?})",
+        segment, checksum, total, elapsed
+    );
+
+    Ok(())
+}
+
+/// Hashes the rows of the static files of a segment in the given block range, returns the
+/// checksum and the number of hashed rows.
+fn hash_static_file_segment<N: CliNodeTypes<ChainSpec: EthereumHardforks>>(
+    tool: &DbTool<NodeTypesWithDBAdapter<N, Arc<DatabaseEnv>>>,
+    segment: StaticFileSegment,
+    start_block: Option<u64>,
+    end_block: Option<u64>,
+    limit: Option<usize>,
+) -> eyre::Result<(u64, usize)> {
+    let static_file_provider = tool.provider_factory.static_file_provider();
+    let static_files = static_file_provider.static_files()?;
 
     let ranges = static_files
         .get(segment)
         .ok_or_else(|| eyre::eyre!("No static files found for segment: {}", segment))?;
 
-    let start_time = Instant::now();
     let mut hasher = checksum_hasher();
     let mut total = 0usize;
     let limit = limit.unwrap_or(usize::MAX);
@@ -189,36 +232,48 @@ fn checksum_static_file<N: CliNodeTypes<ChainSpec: EthereumHardforks>>(
         static_file_provider.remove_cached_provider(segment, fixed_block_range.end());
     }
 
-    let checksum = hasher.finish();
-    let elapsed = start_time.elapsed();
-
-    info!(
-        "Checksum for static file segment `{}`: {:#x} ({} entries, elapsed: {:?})",
-        segment, checksum, total, elapsed
-    );
-
-    Ok(())
+    Ok((hasher.finish(), total))
 }
 
 pub(crate) struct ChecksumViewer<'a, N: NodeTypesWithDB> {
     tool: &'a DbTool<N>,
+    /// The provider to read the tables with, a new one is opened per table if unset.
+    provider: Option<&'a DatabaseProviderRO<N::DB, N>>,
     start_key: Option<String>,
     end_key: Option<String>,
     limit: Option<usize>,
 }
 
-impl<N: NodeTypesWithDB> ChecksumViewer<'_, N> {
+impl<'a, N: NodeTypesWithDB> ChecksumViewer<'a, N> {
     pub(crate) const fn new(tool: &'_ DbTool<N>) -> ChecksumViewer<'_, N> {
-        ChecksumViewer { tool, start_key: None, end_key: None, limit: None }
+        ChecksumViewer { tool, provider: None, start_key: None, end_key: None, limit: None }
+    }
+
+    /// Reads all tables with the given provider, so their checksums are computed on the same
+    /// snapshot of the database.
+    pub(crate) const fn with_provider(
+        mut self,
+        provider: &'a DatabaseProviderRO<N::DB, N>,
+    ) -> Self {
+        self.provider = Some(provider);
+        self
     }
 }
 
-impl<N: ProviderNodeTypes> TableViewer<(u64, Duration)> for ChecksumViewer<'_, N> {
+impl<N: ProviderNodeTypes> TableViewer<(u64, usize, Duration)> for ChecksumViewer<'_, N> {
     type Error = eyre::Report;
 
-    fn view<T: Table>(&self) -> Result<(u64, Duration), Self::Error> {
-        let provider =
-            self.tool.provider_factory.provider()?.disable_long_read_transaction_safety();
+    /// Returns the checksum, the number of hashed entries and the elapsed time.
+    fn view<T: Table>(&self) -> Result<(u64, usize, Duration), Self::Error> {
+        let owned_provider;
+        let provider = match self.provider {
+            Some(provider) => provider,
+            None => {
+                owned_provider =
+                    self.tool.provider_factory.provider()?.disable_long_read_transaction_safety();
+                &owned_provider
+            }
+        };
         let tx = provider.tx_ref();
         info!(
             "Start computing checksum, start={:?}, end={:?}, limit={:?}",
@@ -283,6 +338,6 @@ impl<N: ProviderNodeTypes> TableViewer<(u64, Duration)> for ChecksumViewer<'_, N
 
         info!("Checksum for table `{}`: {:#x} (elapsed: {:?})", T::NAME, checksum, elapsed);
 
-        Ok((checksum, elapsed))
+        Ok((checksum, total, elapsed))
     }
 }
@@ -25,7 +25,7 @@ pub enum RocksDbTable {
 
 impl RocksDbTable {
     /// Returns the table name as a string
-    const fn name(&self) -> &'static str {
+    pub(super) const fn name(&self) -> &'static str {
         match self {
             Self::TransactionHashNumbers => tables::TransactionHashNumbers::NAME,
             Self::AccountsHistory => tables::AccountsHistory::NAME,
@@ -51,17 +51,7 @@ pub fn checksum_rocksdb<N: CliNodeTypes<ChainSpec: EthereumHardforks>>(
         if limit == usize::MAX { None } else { Some(limit) }
     );
 
-    let (checksum, total) = match table {
-        RocksDbTable::TransactionHashNumbers => {
-            checksum_rocksdb_table::<tables::TransactionHashNumbers>(&rocksdb, limit)?
-        }
-        RocksDbTable::AccountsHistory => {
-            checksum_rocksdb_table::<tables::AccountsHistory>(&rocksdb, limit)?
-        }
-        RocksDbTable::StoragesHistory => {
-            checksum_rocksdb_table::<tables::StoragesHistory>(&rocksdb, limit)?
-        }
-    };
+    let (checksum, total) = hash_rocksdb_table(&rocksdb, table, limit)?;
 
     let elapsed = start_time.elapsed();
 
@@ -76,6 +66,25 @@ pub fn checksum_rocksdb<N: CliNodeTypes<ChainSpec: EthereumHardforks>>(
     Ok(())
 }
 
+/// Computes the checksum of a RocksDB table, returns the checksum and the number of hashed rows.
+pub(super) fn hash_rocksdb_table(
+    rocksdb: &reth_provider::providers::RocksDBProvider,
+    table: RocksDbTable,
+    limit: usize,
+) -> eyre::Result<(u64, usize)> {
+    match table {
+        RocksDbTable::TransactionHashNumbers => {
+            checksum_rocksdb_table::<tables::TransactionHashNumbers>(rocksdb, limit)
+        }
+        RocksDbTable::AccountsHistory => {
+            checksum_rocksdb_table::<tables::AccountsHistory>(rocksdb, limit)
+        }
+        RocksDbTable::StoragesHistory => {
+            checksum_rocksdb_table::<tables::StoragesHistory>(rocksdb, limit)
+        }
+    }
+}
+
 /// Computes checksum for a specific RocksDB table by iterating over rows.
 fn checksum_rocksdb_table<T: Table>(
     rocksdb: &reth_provider::providers::RocksDBProvider,
@@ -1,5 +1,4 @@
 use clap::{Parser, Subcommand};
-use reth_db::static_file::iter_static_files;
 use reth_db_api::{
     database::Database,
     table::Table,
@@ -27,7 +26,7 @@ impl Command {
             }
             Subcommands::StaticFile { segment } => {
                 let static_file_provider = tool.provider_factory.static_file_provider();
-                let static_files = iter_static_files(static_file_provider.directory())?;
+                let static_files = static_file_provider.static_files()?;
 
                 if let Some(segment_static_files) = static_files.get(segment) {
                     for (block_range, _) in segment_static_files {
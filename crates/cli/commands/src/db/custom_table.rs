@@ -0,0 +1,111 @@
+//! Access to the custom tables of downstream crates.
+//!
+//! Custom tables are declared with [`custom_tables!`](reth_db_api::custom_tables) outside of reth,
+//! so their key and value types are unknown here and they're read as raw bytes.
+
+use alloy_primitives::Bytes;
+use eyre::WrapErr;
+use reth_db::{transaction::DbTx, DatabaseEnv};
+use reth_db_api::{custom_table_namespace, database::Database, Tables};
+use reth_db_common::ListFilter;
+use std::str::FromStr;
+
+/// A table in the node's database.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DbTable {
+    /// One of the [`Tables`] of reth.
+    Reth(Tables),
+    /// A custom table, identified by its namespaced name.
+    Custom(String),
+}
+
+impl FromStr for DbTable {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match Tables::from_str(s) {
+            Ok(table) => Ok(Self::Reth(table)),
+            Err(_) if custom_table_namespace(s).is_some() => Ok(Self::Custom(s.to_string())),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+/// Returns the raw value of the given raw key in a custom table.
+pub(crate) fn get_custom_table(
+    db: &DatabaseEnv,
+    table: &str,
+    key: &[u8],
+) -> eyre::Result<Option<Bytes>> {
+    db.view(|tx| {
+        let table_db = tx
+            .inner()
+            .open_db(Some(table))
+            .wrap_err_with(|| format!("Could not open table: {table}"))?;
+        Ok(tx.inner().get::<Vec<u8>>(table_db.dbi(), key)?.map(Bytes::from))
+    })?
+}
+
+/// Lists the raw entries of a custom table matching the given filter.
+///
+/// Returns the entries and the number of matching entries.
+pub(crate) fn list_custom_table(
+    db: &DatabaseEnv,
+    table: &str,
+    filter: &ListFilter,
+) -> eyre::Result<(Vec<(Bytes, Bytes)>, usize)> {
+    let matches = |key: &[u8], value: &[u8]| {
+        key.len() + value.len() >= filter.min_row_size &&
+            key.len() >= filter.min_key_size &&
+            value.len() >= filter.min_value_size &&
+            (!filter.has_search() ||
+                [key, value].iter().any(|data| {
+                    data.windows(filter.search.len()).any(|window| window == filter.search)
+                }))
+    };
+
+    db.view(|tx| {
+        // We may be listing a large table
+        tx.disable_long_read_transaction_safety();
+
+        let table_db = tx
+            .inner()
+            .open_db(Some(table))
+            .wrap_err_with(|| format!("Could not open table: {table}"))?;
+        let mut cursor = tx.inner().cursor_with_dbi(table_db.dbi())?;
+
+        let mut entries = Vec::new();
+        let mut hits = 0;
+        let mut entry = if filter.reverse { cursor.last()? } else { cursor.first()? };
+        let mut position = 0;
+        while let Some((key, value)) = entry {
+            let (key, value): (Vec<u8>, Vec<u8>) = (key, value);
+            if position >= filter.skip && matches(&key, &value) {
+                hits += 1;
+                if !filter.only_count {
+                    entries.push((key.into(), value.into()));
+                    if entries.len() >= filter.len {
+                        break
+                    }
+                }
+            }
+
+            position += 1;
+            entry = if filter.reverse { cursor.prev()? } else { cursor.next()? };
+        }
+
+        Ok((entries, hits))
+    })?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_db_table() {
+        assert_eq!("Headers".parse(), Ok(DbTable::Reth(Tables::Headers)));
+        assert_eq!("my_exex.Counters".parse(), Ok(DbTable::Custom("my_exex.Counters".to_string())));
+        assert!("Unknown".parse::<DbTable>().is_err());
+    }
+}
@@ -0,0 +1,319 @@
+use alloy_primitives::Bytes;
+use arrow_array::{ArrayRef, BinaryArray, RecordBatch, StringArray};
+use arrow_schema::{DataType, Field, Schema, SchemaRef};
+use clap::{Parser, ValueEnum};
+use eyre::WrapErr;
+use parquet::{
+    arrow::ArrowWriter,
+    basic::{Compression, ZstdLevel},
+    file::properties::WriterProperties,
+};
+use reth_db::DatabaseEnv;
+use reth_db_api::{
+    cursor::DbCursorRO, database::Database, table::Table, transaction::DbTx, RawKey, RawTable,
+    RawValue, TableViewer, Tables,
+};
+use reth_db_common::DbTool;
+use reth_node_builder::NodeTypesWithDB;
+use reth_node_core::version::version_metadata;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tracing::info;
+
+/// Key of the [`TableSchema`] in the metadata of a Parquet export.
+const PARQUET_SCHEMA_METADATA_KEY: &str = "reth.table_schema";
+
+/// Number of rows written to a Parquet export per record batch.
+const PARQUET_BATCH_SIZE: usize = 8_192;
+
+/// The arguments for the `reth db export` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The table name
+    pub(crate) table: Tables,
+    /// The output file. The table schema is written next to it, with a `.schema.json` suffix.
+    #[arg(long, short, value_name = "FILE")]
+    pub(crate) output: PathBuf,
+    /// The output format
+    #[arg(long, value_enum, default_value_t = ExportFormat::Csv)]
+    pub(crate) format: ExportFormat,
+    /// Skip first N entries
+    #[arg(long, default_value_t = 0)]
+    pub(crate) skip: usize,
+    /// Maximum number of entries to export
+    #[arg(long)]
+    pub(crate) limit: Option<usize>,
+}
+
+impl Command {
+    /// Execute `db export` command
+    pub fn execute<N: NodeTypesWithDB<DB = Arc<DatabaseEnv>>>(
+        self,
+        tool: &DbTool<N>,
+    ) -> eyre::Result<()> {
+        self.export(tool.provider_factory.db_ref())
+    }
+
+    /// Exports the table from the given database.
+    pub(crate) fn export<DB: Database>(&self, db: &DB) -> eyre::Result<()> {
+        self.table.view(&ExportViewer { db, args: self })
+    }
+}
+
+/// Portable formats a table can be exported to and imported from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    /// Comma-separated values with a header row. Decoded keys and values are JSON-encoded.
+    Csv,
+    /// One JSON object per line.
+    Jsonl,
+    /// Apache Parquet, with JSON-encoded decoded keys and values as string columns and the raw
+    /// ones as binary columns. The table schema is also stored in the file metadata.
+    Parquet,
+}
+
+/// Schema of an exported table, stored next to the exported rows.
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct TableSchema {
+    /// The table name.
+    pub(crate) table: String,
+    /// Whether the table is a `DUPSORT` table.
+    pub(crate) dupsort: bool,
+    /// Rust type of the decoded keys.
+    pub(crate) key_type: String,
+    /// Rust type of the decoded values.
+    pub(crate) value_type: String,
+    /// Format of the exported rows.
+    pub(crate) format: ExportFormat,
+    /// Version of the client that exported the table.
+    pub(crate) client_version: String,
+}
+
+impl TableSchema {
+    pub(crate) fn new<T: Table>(format: ExportFormat) -> Self {
+        Self {
+            table: T::NAME.to_string(),
+            dupsort: T::DUPSORT,
+            key_type: std::any::type_name::<T::Key>().to_string(),
+            value_type: std::any::type_name::<T::Value>().to_string(),
+            format,
+            client_version: version_metadata().short_version.to_string(),
+        }
+    }
+
+    /// Returns the path of the schema file for the given exported file.
+    pub(crate) fn path(file: &Path) -> PathBuf {
+        let mut path = file.as_os_str().to_owned();
+        path.push(".schema.json");
+        path.into()
+    }
+}
+
+/// Row of a CSV export.
+#[derive(Debug, Serialize)]
+struct CsvRow {
+    key: String,
+    value: String,
+    raw_key: Bytes,
+    raw_value: Bytes,
+}
+
+/// Row of a JSON Lines export.
+#[derive(Debug, Serialize)]
+struct JsonRow<'a, K, V> {
+    key: &'a K,
+    value: &'a V,
+    raw_key: Bytes,
+    raw_value: Bytes,
+}
+
+/// Writes exported rows in the chosen [`ExportFormat`].
+enum RowWriter<W: Write + Send> {
+    Csv(csv::Writer<W>),
+    Jsonl(W),
+    Parquet(ParquetRowWriter<W>),
+}
+
+impl<W: Write + Send> RowWriter<W> {
+    fn new(schema: &TableSchema, writer: W) -> eyre::Result<Self> {
+        Ok(match schema.format {
+            ExportFormat::Csv => Self::Csv(csv::Writer::from_writer(writer)),
+            ExportFormat::Jsonl => Self::Jsonl(writer),
+            ExportFormat::Parquet => Self::Parquet(ParquetRowWriter::new(schema, writer)?),
+        })
+    }
+
+    fn write<K: Serialize, V: Serialize>(
+        &mut self,
+        key: &K,
+        value: &V,
+        raw_key: Bytes,
+        raw_value: Bytes,
+    ) -> eyre::Result<()> {
+        match self {
+            Self::Csv(writer) => writer.serialize(CsvRow {
+                key: serde_json::to_string(key)?,
+                value: serde_json::to_string(value)?,
+                raw_key,
+                raw_value,
+            })?,
+            Self::Jsonl(writer) => {
+                serde_json::to_writer(&mut *writer, &JsonRow { key, value, raw_key, raw_value })?;
+                writer.write_all(b"\n")?;
+            }
+            Self::Parquet(writer) => writer.write(
+                serde_json::to_string(key)?,
+                serde_json::to_string(value)?,
+                raw_key,
+                raw_value,
+            )?,
+        }
+        Ok(())
+    }
+
+    /// Writes the buffered rows and finishes the file.
+    fn finish(self) -> eyre::Result<()> {
+        match self {
+            Self::Csv(mut writer) => writer.flush()?,
+            Self::Jsonl(mut writer) => writer.flush()?,
+            Self::Parquet(writer) => writer.finish()?,
+        }
+        Ok(())
+    }
+}
+
+/// Writes exported rows to a Parquet file in record batches of [`PARQUET_BATCH_SIZE`] rows.
+struct ParquetRowWriter<W: Write + Send> {
+    writer: ArrowWriter<W>,
+    schema: SchemaRef,
+    keys: Vec<String>,
+    values: Vec<String>,
+    raw_keys: Vec<Bytes>,
+    raw_values: Vec<Bytes>,
+}
+
+impl<W: Write + Send> ParquetRowWriter<W> {
+    fn new(table_schema: &TableSchema, writer: W) -> eyre::Result<Self> {
+        let schema = Arc::new(
+            Schema::new(vec![
+                Field::new("key", DataType::Utf8, false),
+                Field::new("value", DataType::Utf8, false),
+                Field::new("raw_key", DataType::Binary, false),
+                Field::new("raw_value", DataType::Binary, false),
+            ])
+            .with_metadata(HashMap::from([(
+                PARQUET_SCHEMA_METADATA_KEY.to_string(),
+                serde_json::to_string(table_schema)?,
+            )])),
+        );
+        let properties = WriterProperties::builder()
+            .set_compression(Compression::ZSTD(ZstdLevel::default()))
+            .build();
+
+        Ok(Self {
+            writer: ArrowWriter::try_new(writer, schema.clone(), Some(properties))?,
+            schema,
+            keys: Vec::with_capacity(PARQUET_BATCH_SIZE),
+            values: Vec::with_capacity(PARQUET_BATCH_SIZE),
+            raw_keys: Vec::with_capacity(PARQUET_BATCH_SIZE),
+            raw_values: Vec::with_capacity(PARQUET_BATCH_SIZE),
+        })
+    }
+
+    fn write(
+        &mut self,
+        key: String,
+        value: String,
+        raw_key: Bytes,
+        raw_value: Bytes,
+    ) -> eyre::Result<()> {
+        self.keys.push(key);
+        self.values.push(value);
+        self.raw_keys.push(raw_key);
+        self.raw_values.push(raw_value);
+        if self.keys.len() >= PARQUET_BATCH_SIZE {
+            self.write_batch()?;
+        }
+        Ok(())
+    }
+
+    fn write_batch(&mut self) -> eyre::Result<()> {
+        if self.keys.is_empty() {
+            return Ok(())
+        }
+
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(StringArray::from(std::mem::take(&mut self.keys))),
+            Arc::new(StringArray::from(std::mem::take(&mut self.values))),
+            Arc::new(BinaryArray::from_iter_values(self.raw_keys.drain(..))),
+            Arc::new(BinaryArray::from_iter_values(self.raw_values.drain(..))),
+        ];
+        self.writer.write(&RecordBatch::try_new(self.schema.clone(), columns)?)?;
+        Ok(())
+    }
+
+    fn finish(mut self) -> eyre::Result<()> {
+        self.write_batch()?;
+        self.writer.close()?;
+        Ok(())
+    }
+}
+
+/// Raw part of an exported row, which is all that's needed to import it back.
+#[derive(Debug, Deserialize)]
+pub(crate) struct RawRow {
+    pub(crate) raw_key: Bytes,
+    pub(crate) raw_value: Bytes,
+}
+
+struct ExportViewer<'a, DB> {
+    db: &'a DB,
+    args: &'a Command,
+}
+
+impl<DB: Database> TableViewer<()> for ExportViewer<'_, DB> {
+    type Error = eyre::Report;
+
+    fn view<T: Table>(&self) -> Result<(), Self::Error> {
+        let schema = TableSchema::new::<T>(self.args.format);
+        let schema_path = TableSchema::path(&self.args.output);
+        std::fs::write(&schema_path, serde_json::to_vec_pretty(&schema)?)
+            .wrap_err_with(|| format!("Could not write schema to {}", schema_path.display()))?;
+
+        let file = File::create(&self.args.output)
+            .wrap_err_with(|| format!("Could not create {}", self.args.output.display()))?;
+        let mut writer = RowWriter::new(&schema, BufWriter::new(file))?;
+
+        let tx = self.db.tx()?;
+        // Exporting a large table can take a long time
+        tx.disable_long_read_transaction_safety();
+
+        let mut cursor = tx.cursor_read::<RawTable<T>>()?;
+        let walker =
+            cursor.walk(None)?.skip(self.args.skip).take(self.args.limit.unwrap_or(usize::MAX));
+
+        let mut exported = 0;
+        for entry in walker {
+            let (raw_key, raw_value): (RawKey<T::Key>, RawValue<T::Value>) = entry?;
+            let key = raw_key.key()?;
+            let value = raw_value.value()?;
+            let raw_key = Bytes::from(raw_key.into_key());
+            let raw_value = Bytes::from(raw_value.into_value());
+
+            writer.write(&key, &value, raw_key, raw_value)?;
+            exported += 1;
+        }
+        writer.finish()?;
+
+        info!(target: "reth::cli", table = T::NAME, exported, output = %self.args.output.display(), "Exported table");
+
+        Ok(())
+    }
+}
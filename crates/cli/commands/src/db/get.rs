@@ -1,11 +1,13 @@
-use alloy_primitives::{hex, BlockHash};
+use super::custom_table::{get_custom_table, DbTable};
+use alloy_primitives::{hex, BlockHash, Bytes};
 use clap::Parser;
+use eyre::WrapErr;
 use reth_db::{
     static_file::{
         AccountChangesetMask, ColumnSelectorOne, ColumnSelectorTwo, HeaderWithHashMask,
         ReceiptMask, TransactionMask, TransactionSenderMask,
     },
-    RawDupSort,
+    DatabaseEnv, RawDupSort,
 };
 use reth_db_api::{
     cursor::{DbCursorRO, DbDupCursorRO},
@@ -22,6 +24,7 @@ use reth_primitives_traits::ValueWithSubKey;
 use reth_provider::{providers::ProviderNodeTypes, ChangeSetReader, StaticFileProviderFactory};
 use reth_static_file_types::StaticFileSegment;
 use reth_storage_api::StorageChangeSetReader;
+use std::sync::Arc;
 use tracing::error;
 
 /// The arguments for the `reth db get` command
@@ -33,9 +36,12 @@ pub struct Command {
 
 #[derive(clap::Subcommand, Debug)]
 enum Subcommand {
-    /// Gets the content of a database table for the given key
+    /// Gets the content of a database table for the given key.
+    ///
+    /// Custom tables of downstream crates are read by their namespaced name, with the key and the
+    /// returned value as hex encoded raw bytes.
     Mdbx {
-        table: tables::Tables,
+        table: DbTable,
 
         /// The key to get content for
         #[arg(value_parser = maybe_json_value_parser)]
@@ -77,10 +83,26 @@ enum Subcommand {
 
 impl Command {
     /// Execute `db get` command
-    pub fn execute<N: ProviderNodeTypes>(self, tool: &DbTool<N>) -> eyre::Result<()> {
+    pub fn execute<N: ProviderNodeTypes<DB = Arc<DatabaseEnv>>>(
+        self,
+        tool: &DbTool<N>,
+    ) -> eyre::Result<()> {
         match self.subcommand {
-            Subcommand::Mdbx { table, key, subkey, end_key, end_subkey, raw } => {
-                table.view(&GetValueViewer { tool, key, subkey, end_key, end_subkey, raw })?
+            Subcommand::Mdbx {
+                table: DbTable::Reth(table),
+                key,
+                subkey,
+                end_key,
+                end_subkey,
+                raw,
+            } => table.view(&GetValueViewer { tool, key, subkey, end_key, end_subkey, raw })?,
+            Subcommand::Mdbx { table: DbTable::Custom(table), key, .. } => {
+                let key = serde_json::from_str::<Bytes>(&key)
+                    .wrap_err("Keys of custom tables must be hex encoded")?;
+                match get_custom_table(tool.provider_factory.db_ref(), &table, &key)? {
+                    Some(value) => println!("{value}"),
+                    None => error!(target: "reth::cli", "No content for the given table key."),
+                }
             }
             Subcommand::StaticFile { segment, key, subkey, raw } => {
                 if let StaticFileSegment::StorageChangeSets = segment {
@@ -0,0 +1,276 @@
+use super::export::{ExportFormat, RawRow, TableSchema};
+use alloy_primitives::Bytes;
+use arrow_array::{Array, BinaryArray, RecordBatch};
+use clap::Parser;
+use eyre::WrapErr;
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use reth_db::DatabaseEnv;
+use reth_db_api::{
+    cursor::DbCursorRO,
+    database::Database,
+    table::Table,
+    transaction::{DbTx, DbTxMut},
+    RawKey, RawTable, RawValue, TableViewer, Tables,
+};
+use reth_db_common::DbTool;
+use reth_node_builder::NodeTypesWithDB;
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+    path::PathBuf,
+    sync::Arc,
+};
+use tracing::info;
+
+/// Number of imported rows after which the progress is logged.
+const LOG_INTERVAL: usize = 100_000;
+
+/// The arguments for the `reth db import` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The table name. The table must be empty, see `reth db clear`.
+    pub(crate) table: Tables,
+    /// The file previously written by `reth db export`. Its schema is read from the file with the
+    /// `.schema.json` suffix next to it.
+    ///
+    /// All rows are imported in a single database transaction, which is only committed if every
+    /// row can be decoded into the types of the table.
+    #[arg(long, short, value_name = "FILE")]
+    pub(crate) input: PathBuf,
+}
+
+impl Command {
+    /// Execute `db import` command
+    pub fn execute<N: NodeTypesWithDB<DB = Arc<DatabaseEnv>>>(
+        self,
+        tool: &DbTool<N>,
+    ) -> eyre::Result<()> {
+        self.import(tool.provider_factory.db_ref())
+    }
+
+    /// Imports the table into the given database.
+    pub(crate) fn import<DB: Database>(&self, db: &DB) -> eyre::Result<()> {
+        let schema_path = TableSchema::path(&self.input);
+        let schema: TableSchema = serde_json::from_slice(
+            &std::fs::read(&schema_path)
+                .wrap_err_with(|| format!("Could not read schema {}", schema_path.display()))?,
+        )?;
+        eyre::ensure!(
+            schema.table == self.table.name(),
+            "Exported table {} does not match the table {}",
+            schema.table,
+            self.table.name()
+        );
+
+        self.table.view(&ImportViewer { db, input: &self.input, schema })
+    }
+}
+
+struct ImportViewer<'a, DB> {
+    db: &'a DB,
+    input: &'a PathBuf,
+    schema: TableSchema,
+}
+
+impl<DB> ImportViewer<'_, DB> {
+    /// Returns an iterator over the raw rows of the input file.
+    fn rows(&self) -> eyre::Result<Box<dyn Iterator<Item = eyre::Result<RawRow>>>> {
+        let file = File::open(self.input)
+            .wrap_err_with(|| format!("Could not open {}", self.input.display()))?;
+
+        Ok(match self.schema.format {
+            ExportFormat::Csv => Box::new(
+                csv::Reader::from_reader(BufReader::new(file))
+                    .into_deserialize::<RawRow>()
+                    .map(|row| row.map_err(Into::into)),
+            ),
+            ExportFormat::Jsonl => Box::new(
+                BufReader::new(file)
+                    .lines()
+                    .filter(|line| !matches!(line, Ok(line) if line.is_empty()))
+                    .map(|line| Ok(serde_json::from_str(&line?)?)),
+            ),
+            ExportFormat::Parquet => Box::new(
+                ParquetRecordBatchReaderBuilder::try_new(file)?.build()?.flat_map(|batch| {
+                    match batch.map_err(eyre::Report::from).and_then(|batch| parquet_rows(&batch)) {
+                        Ok(rows) => rows.into_iter().map(Ok).collect::<Vec<_>>(),
+                        Err(err) => vec![Err(err)],
+                    }
+                }),
+            ),
+        })
+    }
+}
+
+impl<DB: Database> TableViewer<()> for ImportViewer<'_, DB> {
+    type Error = eyre::Report;
+
+    fn view<T: Table>(&self) -> Result<(), Self::Error> {
+        let expected = TableSchema::new::<T>(self.schema.format);
+        eyre::ensure!(
+            self.schema.dupsort == expected.dupsort &&
+                self.schema.key_type == expected.key_type &&
+                self.schema.value_type == expected.value_type,
+            "Schema of the table {} exported by {} does not match: expected keys {} and values {}, found keys {} and values {}",
+            T::NAME,
+            self.schema.client_version,
+            expected.key_type,
+            expected.value_type,
+            self.schema.key_type,
+            self.schema.value_type,
+        );
+
+        let tx = self.db.tx_mut()?;
+        eyre::ensure!(
+            tx.cursor_read::<RawTable<T>>()?.first()?.is_none(),
+            "Table {} is not empty, clear it with `reth db clear mdbx {}` first",
+            T::NAME,
+            T::NAME
+        );
+
+        let mut imported = 0;
+        for row in self.rows()? {
+            let RawRow { raw_key, raw_value } = row?;
+            let key = RawKey::<T::Key>::from_vec(raw_key.to_vec());
+            let value = RawValue::<T::Value>::from_vec(raw_value.to_vec());
+            key.key()
+                .and(value.value())
+                .wrap_err_with(|| format!("Row {imported} can't be decoded as {}", T::NAME))?;
+            tx.put::<RawTable<T>>(key, value)?;
+
+            imported += 1;
+            if imported % LOG_INTERVAL == 0 {
+                info!(target: "reth::cli", table = T::NAME, imported, "Importing table");
+            }
+        }
+        tx.commit()?;
+
+        info!(target: "reth::cli", table = T::NAME, imported, input = %self.input.display(), "Imported table");
+
+        Ok(())
+    }
+}
+
+/// Returns the raw rows of a record batch of a Parquet export.
+fn parquet_rows(batch: &RecordBatch) -> eyre::Result<Vec<RawRow>> {
+    let column = |name: &str| {
+        batch
+            .column_by_name(name)
+            .and_then(|column| column.as_any().downcast_ref::<BinaryArray>())
+            .ok_or_else(|| eyre::eyre!("Missing binary column {name}"))
+    };
+    let (raw_keys, raw_values) = (column("raw_key")?, column("raw_value")?);
+    eyre::ensure!(
+        raw_keys.null_count() == 0 && raw_values.null_count() == 0,
+        "Raw keys and values must not be null"
+    );
+
+    Ok((0..batch.num_rows())
+        .map(|row| RawRow {
+            raw_key: Bytes::copy_from_slice(raw_keys.value(row)),
+            raw_value: Bytes::copy_from_slice(raw_values.value(row)),
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::export;
+    use alloy_primitives::{Address, B256, U256};
+    use reth_db_api::tables;
+    use reth_primitives_traits::StorageEntry;
+    use reth_provider::test_utils::create_test_provider_factory;
+
+    fn export_command(table: Tables, output: PathBuf, format: ExportFormat) -> export::Command {
+        export::Command { table, output, format, skip: 0, limit: None }
+    }
+
+    fn raw_entries<T: Table, DB: Database>(db: &DB) -> Vec<(Vec<u8>, Vec<u8>)> {
+        let tx = db.tx().unwrap();
+        let mut cursor = tx.cursor_read::<RawTable<T>>().unwrap();
+        cursor
+            .walk(None)
+            .unwrap()
+            .map(|entry| {
+                let (key, value) = entry.unwrap();
+                (key.into_key(), value.into_value())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn export_import_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = create_test_provider_factory();
+        let tx = source.db_ref().tx_mut().unwrap();
+        for number in 0..10u64 {
+            tx.put::<tables::CanonicalHeaders>(number, B256::with_last_byte(number as u8)).unwrap();
+        }
+        for slot in 0..3u8 {
+            tx.put::<tables::PlainStorageState>(
+                Address::with_last_byte(1),
+                StorageEntry { key: B256::with_last_byte(slot), value: U256::from(slot + 1) },
+            )
+            .unwrap();
+        }
+        tx.commit().unwrap();
+
+        for format in [ExportFormat::Csv, ExportFormat::Jsonl, ExportFormat::Parquet] {
+            let target = create_test_provider_factory();
+            for table in [Tables::CanonicalHeaders, Tables::PlainStorageState] {
+                let output = dir.path().join(format!("{}.{format:?}", table.name()));
+                export_command(table, output.clone(), format).export(source.db_ref()).unwrap();
+                Command { table, input: output }.import(target.db_ref()).unwrap();
+            }
+
+            assert_eq!(
+                raw_entries::<tables::CanonicalHeaders, _>(target.db_ref()),
+                raw_entries::<tables::CanonicalHeaders, _>(source.db_ref()),
+            );
+            assert_eq!(
+                raw_entries::<tables::PlainStorageState, _>(target.db_ref()),
+                raw_entries::<tables::PlainStorageState, _>(source.db_ref()),
+            );
+        }
+    }
+
+    #[test]
+    fn import_checks() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = create_test_provider_factory();
+        let tx = source.db_ref().tx_mut().unwrap();
+        tx.put::<tables::CanonicalHeaders>(1, B256::with_last_byte(1)).unwrap();
+        tx.commit().unwrap();
+
+        let output = dir.path().join("headers.jsonl");
+        export_command(Tables::CanonicalHeaders, output.clone(), ExportFormat::Jsonl)
+            .export(source.db_ref())
+            .unwrap();
+
+        // exported table must match the table to import into
+        let target = create_test_provider_factory();
+        assert!(Command { table: Tables::HeaderNumbers, input: output.clone() }
+            .import(target.db_ref())
+            .is_err());
+
+        // table to import into must be empty
+        assert!(Command { table: Tables::CanonicalHeaders, input: output.clone() }
+            .import(source.db_ref())
+            .is_err());
+
+        // rows that can't be decoded abort the import without writing anything
+        let value = B256::with_last_byte(1);
+        std::fs::write(
+            &output,
+            format!(
+                "{{\"raw_key\":\"0x0000000000000002\",\"raw_value\":\"{value}\"}}\n{{\"raw_key\":\"0x02\",\"raw_value\":\"{value}\"}}\n"
+            ),
+        )
+        .unwrap();
+        assert!(Command { table: Tables::CanonicalHeaders, input: output }
+            .import(target.db_ref())
+            .is_err());
+        assert!(raw_entries::<tables::CanonicalHeaders, _>(target.db_ref()).is_empty());
+    }
+}
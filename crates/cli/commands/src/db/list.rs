@@ -1,10 +1,13 @@
-use super::tui::DbListTUI;
+use super::{
+    custom_table::{list_custom_table, DbTable},
+    tui::DbListTUI,
+};
 use alloy_primitives::hex;
 use clap::Parser;
 use eyre::WrapErr;
 use reth_chainspec::EthereumHardforks;
 use reth_db::{transaction::DbTx, DatabaseEnv};
-use reth_db_api::{database::Database, table::Table, RawValue, TableViewer, Tables};
+use reth_db_api::{database::Database, table::Table, RawValue, TableViewer};
 use reth_db_common::{DbTool, ListFilter};
 use reth_node_builder::{NodeTypes, NodeTypesWithDBAdapter};
 use std::{cell::RefCell, sync::Arc};
@@ -13,8 +16,10 @@ use tracing::error;
 #[derive(Parser, Debug)]
 /// The arguments for the `reth db list` command
 pub struct Command {
-    /// The table name
-    table: Tables,
+    /// The table name.
+    ///
+    /// Custom tables of downstream crates are listed by their namespaced name, as raw bytes.
+    table: DbTable,
     /// Skip first N entries
     #[arg(long, short, default_value_t = 0)]
     skip: usize,
@@ -57,7 +62,20 @@ impl Command {
         self,
         tool: &DbTool<NodeTypesWithDBAdapter<N, Arc<DatabaseEnv>>>,
     ) -> eyre::Result<()> {
-        self.table.view(&ListTableViewer { tool, args: &self })
+        match &self.table {
+            DbTable::Reth(table) => table.view(&ListTableViewer { tool, args: &self }),
+            DbTable::Custom(table) => {
+                let (list, count) =
+                    list_custom_table(tool.provider_factory.db_ref(), table, &self.list_filter()?)?;
+
+                if self.count {
+                    println!("{count} entries found.")
+                } else {
+                    println!("{}", serde_json::to_string_pretty(&list)?);
+                }
+                Ok(())
+            }
+        }
     }
 
     /// Generate [`ListFilter`] from command.
@@ -97,12 +115,13 @@ impl<N: NodeTypes> TableViewer<()> for ListTableViewer<'_, N> {
     type Error = eyre::Report;
 
     fn view<T: Table>(&self) -> Result<(), Self::Error> {
+        let table_name = T::NAME;
         self.tool.provider_factory.db_ref().view(|tx| {
             // We may be using the tui for a long time
             tx.disable_long_read_transaction_safety();
 
-            let table_db = tx.inner().open_db(Some(self.args.table.name())).wrap_err("Could not open db.")?;
-                    let stats = tx.inner().db_stat(table_db.dbi()).wrap_err(format!("Could not find table: {}", self.args.table.name()))?;
+            let table_db = tx.inner().open_db(Some(table_name)).wrap_err("Could not open db.")?;
+                    let stats = tx.inner().db_stat(table_db.dbi()).wrap_err(format!("Could not find table: {}", table_name))?;
             let total_entries = stats.entries();
             let final_entry_idx = total_entries.saturating_sub(1);
             if self.args.skip > final_entry_idx {
@@ -111,7 +130,7 @@ impl<N: NodeTypes> TableViewer<()> for ListTableViewer<'_, N> {
                     "Start index {start} is greater than the final entry index ({final_entry_idx}) in the table {table}",
                     start = self.args.skip,
                     final_entry_idx = final_entry_idx,
-                    table = self.args.table.name()
+                    table = table_name
                 );
                 return Ok(())
             }
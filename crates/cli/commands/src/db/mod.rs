@@ -12,12 +12,17 @@ use std::{
 mod account_storage;
 mod checksum;
 mod clear;
+mod custom_table;
 mod diff;
+mod export;
 mod get;
+mod import;
 mod list;
 mod repair_trie;
+mod serve;
 mod settings;
 mod static_file_header;
+mod static_file_producer;
 mod stats;
 /// DB List TUI
 mod tui;
@@ -45,6 +50,12 @@ pub enum Subcommands {
     Diff(diff::Command),
     /// Gets the content of a table for the given key
     Get(get::Command),
+    /// Exports the decoded and raw entries of a table to a CSV, JSON Lines or Parquet file
+    Export(export::Command),
+    /// Imports the entries of a table from a file written by `db export`
+    Import(import::Command),
+    /// Serves the database read-only over the network
+    Serve(serve::Command),
     /// Deletes all database entries
     Drop {
         /// Bypasses the interactive confirmation and drops the database directly
@@ -57,6 +68,9 @@ pub enum Subcommands {
     RepairTrie(repair_trie::Command),
     /// Reads and displays the static file segment header
     StaticFileHeader(static_file_header::Command),
+    /// Reports and resolves interrupted static file producer runs and static file <> database
+    /// range inconsistencies
+    StaticFileProducer(static_file_producer::Command),
     /// Lists current and local database versions
     Version,
     /// Returns the full database path
@@ -108,7 +122,7 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
                     AccessRights::RO
                 };
                 db_exec!(self.env, tool, N, access_rights, {
-                    command.execute(data_dir, &tool)?;
+                    command.execute(&tool)?;
                 });
             }
             Subcommands::List(command) => {
@@ -131,6 +145,21 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
                     command.execute(&tool)?;
                 });
             }
+            Subcommands::Export(command) => {
+                db_exec!(self.env, tool, N, AccessRights::RO, {
+                    command.execute(&tool)?;
+                });
+            }
+            Subcommands::Import(command) => {
+                db_exec!(self.env, tool, N, AccessRights::RW, {
+                    command.execute(&tool)?;
+                });
+            }
+            Subcommands::Serve(command) => {
+                db_exec!(self.env, tool, N, AccessRights::RO, {
+                    command.execute(&tool, &data_dir)?;
+                });
+            }
             Subcommands::Drop { force } => {
                 if !force {
                     // Ask for confirmation
@@ -170,6 +199,21 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
                     command.execute(&tool)?;
                 });
             }
+            Subcommands::StaticFileProducer(command) => {
+                let inconsistent = {
+                    let Environment { provider_factory, .. } =
+                        self.env.init::<N>(AccessRights::RoInconsistent)?;
+                    command.report(&DbTool::new(provider_factory)?)?
+                };
+
+                if inconsistent && !command.dry_run {
+                    // Opening the storage with read-write access heals the static files and
+                    // unwinds the database if their ranges are inconsistent
+                    db_exec!(self.env, tool, N, AccessRights::RW, {
+                        command.resolve(&tool)?;
+                    });
+                }
+            }
             Subcommands::Version => {
                 let local_db_version = match get_db_version(&db_path) {
                     Ok(version) => Some(version),
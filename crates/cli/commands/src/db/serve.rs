@@ -0,0 +1,87 @@
+use clap::Parser;
+use eyre::WrapErr;
+use reth_db::DatabaseEnv;
+use reth_db_common::DbTool;
+use reth_db_remote::{tls, RemoteDatabaseServer, DEFAULT_MAX_BATCH_SIZE, DEFAULT_MAX_CONNECTIONS};
+use reth_node_builder::NodeTypesWithDB;
+use reth_node_core::{
+    dirs::{ChainPath, DataDirPath},
+    utils::get_or_create_jwt_secret_from_path,
+};
+use std::{
+    net::{Ipv4Addr, SocketAddr, TcpListener},
+    path::PathBuf,
+    sync::Arc,
+};
+use tracing::{info, warn};
+
+/// The default port of the remote database server.
+const DEFAULT_REMOTE_DB_PORT: u16 = 9400;
+
+/// The arguments for the `reth db serve` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The address to accept remote database connections on
+    ///
+    /// Connections are only encrypted if `--tls-cert` and `--tls-key` are set, otherwise the
+    /// address should be a loopback address.
+    #[arg(long, default_value_t = SocketAddr::from((Ipv4Addr::LOCALHOST, DEFAULT_REMOTE_DB_PORT)))]
+    addr: SocketAddr,
+    /// Path to a hex-encoded 32 byte secret clients have to authenticate with
+    ///
+    /// If no path is provided, a secret is read from or created at `<DATADIR>/db-serve.hex`.
+    #[arg(long, value_name = "PATH")]
+    secret: Option<PathBuf>,
+    /// The maximum number of connections served at the same time
+    #[arg(long, default_value_t = DEFAULT_MAX_CONNECTIONS)]
+    max_connections: usize,
+    /// The maximum number of entries returned by a single cursor request
+    #[arg(long, default_value_t = DEFAULT_MAX_BATCH_SIZE)]
+    max_batch_size: usize,
+    /// Path to a PEM file with the TLS certificate chain of the server
+    ///
+    /// If set, all connections are encrypted with TLS.
+    #[arg(long, value_name = "PATH", requires = "tls_key")]
+    tls_cert: Option<PathBuf>,
+    /// Path to a PEM file with the private key of the TLS certificate
+    #[arg(long, value_name = "PATH", requires = "tls_cert")]
+    tls_key: Option<PathBuf>,
+}
+
+impl Command {
+    /// Execute `db serve` command
+    pub fn execute<N: NodeTypesWithDB<DB = Arc<DatabaseEnv>>>(
+        self,
+        tool: &DbTool<N>,
+        data_dir: &ChainPath<DataDirPath>,
+    ) -> eyre::Result<()> {
+        let secret_path = self.secret.unwrap_or_else(|| data_dir.remote_db_secret());
+        let secret = get_or_create_jwt_secret_from_path(&secret_path)
+            .wrap_err_with(|| format!("Could not load secret from {}", secret_path.display()))?;
+
+        let mut server =
+            RemoteDatabaseServer::new(tool.provider_factory.db_ref().clone(), secret.as_bytes())
+                .with_max_batch_size(self.max_batch_size)
+                .with_max_connections(self.max_connections);
+        if let (Some(cert), Some(key)) = (&self.tls_cert, &self.tls_key) {
+            let config = tls::server_config(cert, key).wrap_err_with(|| {
+                format!(
+                    "Could not load TLS certificate {} and key {}",
+                    cert.display(),
+                    key.display()
+                )
+            })?;
+            server = server.with_tls(config);
+        } else if !self.addr.ip().is_loopback() {
+            warn!(target: "reth::cli", addr = %self.addr, "Serving the database without TLS on a non-loopback address, connections are not encrypted");
+        }
+
+        let listener = TcpListener::bind(self.addr)
+            .wrap_err_with(|| format!("Could not listen on {}", self.addr))?;
+        info!(target: "reth::cli", addr = %self.addr, secret = %secret_path.display(), tls = self.tls_cert.is_some(), "Serving read-only database");
+
+        server.serve(listener)?;
+
+        Ok(())
+    }
+}
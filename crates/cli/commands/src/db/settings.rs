@@ -74,6 +74,9 @@ pub enum SetCommand {
         #[clap(action(ArgAction::Set))]
         value: bool,
     },
+    /// Keep receipts of the given number of most recent blocks in the database when receipts are
+    /// stored in static files
+    ReceiptsHotBlocks { value: u64 },
 }
 
 impl Command {
@@ -121,6 +124,7 @@ impl Command {
             account_history_in_rocksdb: _,
             account_changesets_in_static_files: _,
             storage_changesets_in_static_files: _,
+            receipts_hot_blocks: _,
         } = settings.unwrap_or_else(StorageSettings::legacy);
 
         // Update the setting based on the key
@@ -181,6 +185,14 @@ impl Command {
                 settings.storage_changesets_in_static_files = value;
                 println!("Set storage_changesets_in_static_files = {}", value);
             }
+            SetCommand::ReceiptsHotBlocks { value } => {
+                if settings.receipts_hot_blocks == Some(value) {
+                    println!("receipts_hot_blocks is already set to {}", value);
+                    return Ok(());
+                }
+                settings.receipts_hot_blocks = Some(value);
+                println!("Set receipts_hot_blocks = {}", value);
+            }
         }
 
         // Write updated settings
@@ -0,0 +1,96 @@
+use crate::common::CliNodeTypes;
+use clap::Parser;
+use reth_db::DatabaseEnv;
+use reth_db_common::DbTool;
+use reth_node_builder::NodeTypesWithDBAdapter;
+use reth_provider::{providers::ProviderNodeTypes, DBProvider, StaticFileProviderFactory};
+use reth_static_file::{ProducerJournal, StaticFileProducer, StaticFileSegment};
+use std::sync::Arc;
+use tracing::info;
+
+/// The arguments for the `reth db static-file-producer` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// Only show inconsistencies without resolving them
+    #[arg(long)]
+    pub(crate) dry_run: bool,
+}
+
+impl Command {
+    /// Reports interrupted static file producer runs and static file <> database range
+    /// inconsistencies.
+    ///
+    /// Returns `true` if anything needs to be resolved.
+    pub fn report<N: ProviderNodeTypes>(&self, tool: &DbTool<N>) -> eyre::Result<bool> {
+        let static_file_provider = tool.provider_factory.static_file_provider();
+        let mut inconsistent = false;
+
+        let journal = ProducerJournal::new(static_file_provider.directory());
+        match journal.read()? {
+            Some(progress) => {
+                println!("Interrupted producer run found in {}", journal.path().display());
+                for segment in progress.iter().filter(|segment| !segment.is_finished()) {
+                    println!(
+                        "Segment {}: target {:?}, produced up to {}, remaining {:?}",
+                        segment.segment,
+                        segment.target,
+                        segment.produced.map_or_else(|| "none".to_string(), |b| b.to_string()),
+                        segment.remaining(),
+                    );
+                }
+                inconsistent = true;
+            }
+            None => println!("No interrupted producer run"),
+        }
+
+        for segment in StaticFileSegment::iter() {
+            if let Some(highest_block) = static_file_provider.get_highest_static_file_block(segment)
+            {
+                println!("Highest static file block of {segment}: {highest_block}");
+            }
+        }
+
+        match static_file_provider.check_consistency(&tool.provider_factory.provider()?) {
+            Ok(None) => println!("Static file and database ranges are consistent"),
+            Ok(Some(target)) => {
+                println!("Static file and database ranges are inconsistent, requires {target}");
+                inconsistent = true;
+            }
+            Err(err) => {
+                println!("Static files are inconsistent: {err}");
+                inconsistent = true;
+            }
+        }
+
+        Ok(inconsistent)
+    }
+
+    /// Resumes the interrupted static file producer run.
+    ///
+    /// Range inconsistencies are already resolved when opening the storage with read-write access,
+    /// by healing the static files and unwinding the database if needed.
+    pub fn resolve<N: CliNodeTypes>(
+        &self,
+        tool: &DbTool<NodeTypesWithDBAdapter<N, Arc<DatabaseEnv>>>,
+    ) -> eyre::Result<()> {
+        let prune_modes = tool.provider_factory.provider()?.prune_modes_ref().clone();
+        let static_file_producer =
+            StaticFileProducer::new(tool.provider_factory.clone(), prune_modes);
+
+        match static_file_producer.lock().resume()? {
+            Some(targets) => info!(target: "reth::cli", ?targets, "Resumed interrupted run"),
+            None => info!(target: "reth::cli", "No interrupted run to resume"),
+        }
+
+        if let Some(target) = tool
+            .provider_factory
+            .static_file_provider()
+            .check_consistency(&tool.provider_factory.provider()?)?
+        {
+            eyre::bail!("Static file and database ranges are still inconsistent, requires {target}")
+        }
+        info!(target: "reth::cli", "Static file and database ranges are consistent");
+
+        Ok(())
+    }
+}
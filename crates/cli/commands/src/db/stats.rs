@@ -5,18 +5,18 @@ use eyre::WrapErr;
 use human_bytes::human_bytes;
 use itertools::Itertools;
 use reth_chainspec::EthereumHardforks;
-use reth_db::{mdbx, static_file::iter_static_files, DatabaseEnv};
+use reth_db::{mdbx, DatabaseEnv};
 use reth_db_api::{database::Database, TableViewer, Tables};
 use reth_db_common::DbTool;
 use reth_fs_util as fs;
 use reth_node_builder::{NodePrimitives, NodeTypesWithDB, NodeTypesWithDBAdapter};
-use reth_node_core::dirs::{ChainPath, DataDirPath};
 use reth_provider::{
     providers::{ProviderNodeTypes, StaticFileProvider},
-    RocksDBProviderFactory,
+    RocksDBProviderFactory, StaticFileProviderFactory,
 };
 use reth_static_file_types::SegmentRangeInclusive;
 use std::{sync::Arc, time::Duration};
+use tracing::warn;
 
 #[derive(Parser, Debug)]
 /// The arguments for the `reth db stats` command
@@ -33,6 +33,18 @@ pub struct Command {
     #[arg(long, default_value_t = false)]
     detailed_segments: bool,
 
+    /// Show page utilization per database table and freelist details.
+    #[arg(long, default_value_t = false)]
+    detailed: bool,
+
+    /// Estimate how full the leaf and overflow pages of each database table are and recommend
+    /// tables that waste space. Implies `--detailed`.
+    ///
+    /// WARNING: this option will take a long time to run, as it needs to traverse the entire
+    /// database.
+    #[arg(long, default_value_t = false)]
+    page_fill: bool,
+
     /// Show a checksum of each table in the database.
     ///
     /// WARNING: this option will take a long time to run, as it needs to traverse and hash the
@@ -47,7 +59,6 @@ impl Command {
     /// Execute `db stats` command
     pub fn execute<N: CliNodeTypes<ChainSpec: EthereumHardforks>>(
         self,
-        data_dir: ChainPath<DataDirPath>,
         tool: &DbTool<NodeTypesWithDBAdapter<N, Arc<DatabaseEnv>>>,
     ) -> eyre::Result<()> {
         if self.checksum {
@@ -56,7 +67,9 @@ impl Command {
             println!("\n");
         }
 
-        let static_files_stats_table = self.static_files_stats_table::<N::Primitives>(data_dir)?;
+        let static_files_stats_table = self.static_files_stats_table::<N::Primitives>(
+            tool.provider_factory.static_file_provider(),
+        )?;
         println!("{static_files_stats_table}");
 
         println!("\n");
@@ -66,6 +79,13 @@ impl Command {
 
         println!("\n");
 
+        if self.detailed || self.page_fill {
+            let db_utilization_table = self.db_utilization_table(tool)?;
+            println!("{db_utilization_table}");
+
+            println!("\n");
+        }
+
         let rocksdb_stats_table = self.rocksdb_stats_table(tool);
         println!("{rocksdb_stats_table}");
 
@@ -87,12 +107,13 @@ impl Command {
             "Total Size",
         ]);
 
+        // Includes the custom tables created by downstream crates, namespaced by their crate.
+        let db_tables = tool.provider_factory.db_ref().table_names()?;
         tool.provider_factory.db_ref().view(|tx| {
-            let mut db_tables = Tables::ALL.iter().map(|table| table.name()).collect::<Vec<_>>();
-            db_tables.sort();
             let mut total_size = 0;
-            for db_table in db_tables {
-                let table_db = tx.inner().open_db(Some(db_table)).wrap_err("Could not open db.")?;
+            for db_table in &db_tables {
+                let table_db =
+                    tx.inner().open_db(Some(db_table.as_str())).wrap_err("Could not open db.")?;
 
                 let stats = tx
                     .inner()
@@ -156,6 +177,80 @@ impl Command {
         Ok(table)
     }
 
+    fn db_utilization_table<N: NodeTypesWithDB<DB = Arc<DatabaseEnv>>>(
+        &self,
+        tool: &DbTool<N>,
+    ) -> eyre::Result<ComfyTable> {
+        let db = tool.provider_factory.db_ref();
+        let utilization = db.utilization()?;
+        let tables_pages = utilization.tables.iter().map(|table| table.pages()).sum::<usize>();
+
+        let mut table = ComfyTable::new();
+        table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+        let mut header =
+            vec!["Table Name", "Depth", "Entries / Leaf Page", "Overflow Share", "Share of Tables"];
+        if self.page_fill {
+            header.extend(["Leaf Fill", "Overflow Fill", "Recommendation"]);
+        }
+        table.set_header(header);
+
+        let mut tables = utilization.tables.iter().collect::<Vec<_>>();
+        tables.sort_by_key(|table| table.table);
+        for db_table in tables {
+            let pages = db_table.pages();
+            let mut row = Row::new();
+            row.add_cell(Cell::new(db_table.table))
+                .add_cell(Cell::new(db_table.depth))
+                .add_cell(Cell::new(format!("{:.2}", db_table.entries_per_leaf_page())))
+                .add_cell(Cell::new(percentage(db_table.overflow_pages, pages)))
+                .add_cell(Cell::new(percentage(pages, tables_pages)));
+            if self.page_fill {
+                let payload = db.table_payload(db_table.table)?;
+                let leaf_fill = db_table.leaf_fill_factor(&payload) * 100.0;
+                let overflow_fill = db_table.overflow_fill_factor(&payload) * 100.0;
+                row.add_cell(Cell::new(format!("{leaf_fill:.2}%")))
+                    .add_cell(Cell::new(format!("{overflow_fill:.2}%")))
+                    .add_cell(Cell::new(db_table.recommendation(&payload).unwrap_or_default()));
+            }
+            table.add_row(row);
+        }
+
+        let max_widths = table.column_max_content_widths();
+        let mut separator = Row::new();
+        for width in max_widths {
+            separator.add_cell(Cell::new("-".repeat(width as usize)));
+        }
+        table.add_row(separator);
+
+        for (name, value) in [
+            ("File Size", human_bytes(utilization.file_size as f64)),
+            ("Max Size", human_bytes(utilization.max_size as f64)),
+            ("Used Size", human_bytes(utilization.used_size() as f64)),
+            ("Freelist Size", human_bytes(utilization.freelist_size() as f64)),
+            ("Freelist Share", format!("{:.2}%", utilization.freelist_ratio() * 100.0)),
+        ] {
+            let mut row = Row::new();
+            row.add_cell(Cell::new(name))
+                .add_cell(Cell::new(""))
+                .add_cell(Cell::new(""))
+                .add_cell(Cell::new(""))
+                .add_cell(Cell::new(value));
+            if self.page_fill {
+                row.add_cell(Cell::new("")).add_cell(Cell::new("")).add_cell(Cell::new(""));
+            }
+            table.add_row(row);
+        }
+
+        if utilization.is_freelist_pathological() {
+            warn!(
+                target: "reth::cli",
+                "Database freelist is unusually large, consider compacting the database"
+            );
+        }
+
+        Ok(table)
+    }
+
     fn rocksdb_stats_table<N: NodeTypesWithDB>(&self, tool: &DbTool<N>) -> ComfyTable {
         let mut table = ComfyTable::new();
         table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
@@ -222,7 +317,7 @@ impl Command {
 
     fn static_files_stats_table<N: NodePrimitives>(
         &self,
-        data_dir: ChainPath<DataDirPath>,
+        static_file_provider: StaticFileProvider<N>,
     ) -> eyre::Result<ComfyTable> {
         let mut table = ComfyTable::new();
         table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
@@ -249,9 +344,7 @@ impl Command {
             ]);
         }
 
-        let static_files = iter_static_files(&data_dir.static_files())?;
-        let static_file_provider =
-            StaticFileProvider::<N>::read_only(data_dir.static_files(), false)?;
+        let static_files = static_file_provider.static_files()?;
 
         let mut total_data_size = 0;
         let mut total_index_size = 0;
@@ -415,7 +508,7 @@ impl Command {
         let mut total_elapsed = Duration::default();
 
         for &db_table in db_tables {
-            let (checksum, elapsed) = ChecksumViewer::new(tool).view_rt(db_table).unwrap();
+            let (checksum, _, elapsed) = ChecksumViewer::new(tool).view_rt(db_table).unwrap();
 
             // increment duration for final report
             total_elapsed += elapsed;
@@ -446,3 +539,11 @@ impl Command {
         Ok(table)
     }
 }
+
+/// Formats `part` as a percentage of `total`.
+fn percentage(part: usize, total: usize) -> String {
+    if total == 0 {
+        return "0.00%".to_string()
+    }
+    format!("{:.2}%", part as f64 / total as f64 * 100.0)
+}
@@ -0,0 +1,312 @@
+//! Command that checks the readiness of the node for an upcoming fork.
+
+use crate::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
+use alloy_consensus::BlockHeader;
+use alloy_eips::{eip2935, eip4788, eip7002, eip7251};
+use alloy_primitives::Address;
+use clap::{Parser, ValueEnum};
+use reth_chainspec::{
+    ChainSpecProvider, EthChainSpec, EthereumHardfork, EthereumHardforks, ForkCondition,
+};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_provider::{BlockNumReader, HeaderProvider, StateProviderFactory};
+use reth_rpc_engine_api::capabilities::CAPABILITIES;
+use std::{fmt, sync::Arc, time::Duration};
+
+/// `reth debug fork-readiness` command
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    /// The fork to check, defaults to the next fork that is not active at the head of the node.
+    #[arg(long, value_enum)]
+    fork: Option<Fork>,
+}
+
+/// A fork that the readiness can be checked for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Fork {
+    /// The Cancun fork.
+    Cancun,
+    /// The Prague fork.
+    Prague,
+    /// The Osaka fork.
+    Osaka,
+}
+
+impl Fork {
+    /// All forks that can be checked, in activation order.
+    const ALL: [Self; 3] = [Self::Cancun, Self::Prague, Self::Osaka];
+
+    /// Returns the corresponding [`EthereumHardfork`].
+    const fn hardfork(self) -> EthereumHardfork {
+        match self {
+            Self::Cancun => EthereumHardfork::Cancun,
+            Self::Prague => EthereumHardfork::Prague,
+            Self::Osaka => EthereumHardfork::Osaka,
+        }
+    }
+
+    /// Returns the fork that must be active before this fork.
+    const fn parent(self) -> EthereumHardfork {
+        match self {
+            Self::Cancun => EthereumHardfork::Shanghai,
+            Self::Prague => EthereumHardfork::Cancun,
+            Self::Osaka => EthereumHardfork::Prague,
+        }
+    }
+
+    /// Returns the engine API methods that the consensus client calls after this fork.
+    const fn engine_methods(self) -> &'static [&'static str] {
+        match self {
+            Self::Cancun => &[
+                "engine_forkchoiceUpdatedV3",
+                "engine_getPayloadV3",
+                "engine_newPayloadV3",
+                "engine_getBlobsV1",
+            ],
+            Self::Prague => &["engine_getPayloadV4", "engine_newPayloadV4"],
+            Self::Osaka => &["engine_getPayloadV5", "engine_getBlobsV2"],
+        }
+    }
+
+    /// Returns the system contracts that must be deployed before this fork.
+    const fn system_contracts(self) -> &'static [(&'static str, Address)] {
+        match self {
+            Self::Cancun => &[("EIP-4788 beacon roots", eip4788::BEACON_ROOTS_ADDRESS)],
+            Self::Prague => &[
+                ("EIP-2935 history storage", eip2935::HISTORY_STORAGE_ADDRESS),
+                ("EIP-7002 withdrawal requests", eip7002::WITHDRAWAL_REQUEST_PREDEPLOY_ADDRESS),
+                (
+                    "EIP-7251 consolidation requests",
+                    eip7251::CONSOLIDATION_REQUEST_PREDEPLOY_ADDRESS,
+                ),
+            ],
+            Self::Osaka => &[],
+        }
+    }
+}
+
+impl fmt::Display for Fork {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.hardfork())
+    }
+}
+
+/// Outcome of a single readiness check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    Ok,
+    Warn,
+    Fail,
+}
+
+/// A single line of the readiness report.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Check {
+    status: Status,
+    message: String,
+}
+
+impl Check {
+    fn ok(message: impl Into<String>) -> Self {
+        Self { status: Status::Ok, message: message.into() }
+    }
+
+    fn warn(message: impl Into<String>) -> Self {
+        Self { status: Status::Warn, message: message.into() }
+    }
+
+    fn fail(message: impl Into<String>) -> Self {
+        Self { status: Status::Fail, message: message.into() }
+    }
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let status = match self.status {
+            Status::Ok => "ok",
+            Status::Warn => "warn",
+            Status::Fail => "FAIL",
+        };
+        write!(f, "[{status:>4}] {}", self.message)
+    }
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C> {
+    /// Execute `debug fork-readiness` command
+    pub async fn execute<N: CliNodeTypes<ChainSpec = C::ChainSpec>>(self) -> eyre::Result<()> {
+        let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RO)?;
+        let chain_spec = provider_factory.chain_spec();
+
+        let head = provider_factory.best_block_number()?;
+        let head_timestamp = provider_factory
+            .header_by_number(head)?
+            .ok_or_else(|| eyre::eyre!("Header of the head block {head} not found"))?
+            .timestamp();
+
+        let fork = match self.fork {
+            Some(fork) => fork,
+            None => Fork::ALL
+                .into_iter()
+                .find(|fork| {
+                    !chain_spec
+                        .ethereum_fork_activation(fork.hardfork())
+                        .active_at_timestamp(head_timestamp)
+                })
+                .ok_or_else(|| {
+                    eyre::eyre!("All supported forks are already active at block {head}")
+                })?,
+        };
+
+        println!("Checking readiness for {fork} at head block {head} (timestamp {head_timestamp})");
+
+        let mut checks = check_chain_spec(&*chain_spec, fork, head_timestamp);
+        checks.extend(check_engine_capabilities(fork, CAPABILITIES));
+
+        let state = provider_factory.latest()?;
+        let mut contracts = fork
+            .system_contracts()
+            .iter()
+            .map(|(name, address)| (name.to_string(), *address))
+            .collect::<Vec<_>>();
+        if fork == Fork::Prague {
+            match chain_spec.deposit_contract() {
+                Some(deposit_contract) => {
+                    contracts.push(("EIP-6110 deposit".to_string(), deposit_contract.address))
+                }
+                None => checks.push(Check::warn(
+                    "no deposit contract configured, deposit requests are not processed",
+                )),
+            }
+        }
+        for (name, address) in contracts {
+            let has_code = state.account_code(&address)?.is_some_and(|code| !code.is_empty());
+            if has_code {
+                checks.push(Check::ok(format!("{name} contract deployed at {address}")));
+            } else {
+                checks.push(Check::fail(format!("{name} contract has no code at {address}")));
+            }
+        }
+
+        for check in &checks {
+            println!("{check}");
+        }
+
+        let failed = checks.iter().filter(|check| check.status == Status::Fail).count();
+        if failed > 0 {
+            eyre::bail!("{failed} readiness checks for {fork} failed");
+        }
+        println!("Node is ready for {fork}");
+
+        Ok(())
+    }
+}
+
+impl<C: ChainSpecParser> Command<C> {
+    /// Returns the underlying chain being used to run this command
+    pub const fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        Some(&self.env.chain)
+    }
+}
+
+/// Checks the activation and blob parameters of the fork in the chain spec.
+fn check_chain_spec<Spec>(chain_spec: &Spec, fork: Fork, head_timestamp: u64) -> Vec<Check>
+where
+    Spec: EthChainSpec + EthereumHardforks,
+{
+    let mut checks = Vec::new();
+
+    let timestamp = match chain_spec.ethereum_fork_activation(fork.hardfork()) {
+        ForkCondition::Timestamp(timestamp) => timestamp,
+        ForkCondition::Never => {
+            checks.push(Check::fail(format!("{fork} is not scheduled")));
+            return checks
+        }
+        condition => {
+            checks.push(Check::fail(format!(
+                "{fork} must be activated by timestamp, but is activated by {condition:?}"
+            )));
+            return checks
+        }
+    };
+
+    if timestamp <= head_timestamp {
+        checks.push(Check::ok(format!("{fork} is active since timestamp {timestamp}")));
+    } else {
+        let remaining = humantime::format_duration(Duration::from_secs(timestamp - head_timestamp));
+        checks.push(Check::ok(format!(
+            "{fork} is scheduled at timestamp {timestamp}, {remaining} after the head"
+        )));
+    }
+
+    let parent = fork.parent();
+    if chain_spec.ethereum_fork_activation(parent).active_at_timestamp(timestamp) {
+        checks.push(Check::ok(format!("{parent} is active before {fork}")));
+    } else {
+        checks.push(Check::fail(format!("{parent} is not active at the activation of {fork}")));
+    }
+
+    match chain_spec.blob_params_at_timestamp(timestamp) {
+        Some(params) if params.target_blob_count > params.max_blob_count => {
+            checks.push(Check::fail(format!(
+                "blob target {} exceeds the blob max {} at {fork}",
+                params.target_blob_count, params.max_blob_count
+            )))
+        }
+        Some(params) if params.update_fraction == 0 => {
+            checks.push(Check::fail(format!("blob base fee update fraction is zero at {fork}")))
+        }
+        Some(params) => checks.push(Check::ok(format!(
+            "blob params at {fork}: target {}, max {}, update fraction {}",
+            params.target_blob_count, params.max_blob_count, params.update_fraction
+        ))),
+        None => checks.push(Check::fail(format!("no blob params configured for {fork}"))),
+    }
+
+    checks
+}
+
+/// Checks that the engine API serves the methods that are required after the fork.
+fn check_engine_capabilities(fork: Fork, capabilities: &[&str]) -> Vec<Check> {
+    fork.engine_methods()
+        .iter()
+        .map(|method| {
+            if capabilities.contains(method) {
+                Check::ok(format!("engine API serves {method}"))
+            } else {
+                Check::fail(format!("engine API doesn't serve {method}"))
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_chainspec::{ChainSpecBuilder, MAINNET};
+
+    #[test]
+    fn chain_spec_checks() {
+        let checks = check_chain_spec(&*MAINNET, Fork::Prague, 0);
+        assert!(checks.iter().all(|check| check.status == Status::Ok), "{checks:?}");
+        assert!(check_engine_capabilities(Fork::Osaka, CAPABILITIES)
+            .iter()
+            .all(|check| check.status == Status::Ok));
+
+        // Prague is scheduled without Cancun
+        let chain_spec = ChainSpecBuilder::mainnet()
+            .reset()
+            .shanghai_activated()
+            .with_fork(EthereumHardfork::Prague, ForkCondition::Timestamp(100))
+            .build();
+        let checks = check_chain_spec(&chain_spec, Fork::Prague, 0);
+        assert!(checks.iter().any(|check| {
+            check.status == Status::Fail && check.message.contains("Cancun is not active")
+        }));
+
+        let checks = check_chain_spec(&chain_spec, Fork::Osaka, 0);
+        assert_eq!(checks, [Check::fail("Osaka is not scheduled")]);
+    }
+}
@@ -0,0 +1,59 @@
+//! `reth debug` command
+
+use std::sync::Arc;
+
+use crate::common::{CliNodeComponents, CliNodeTypes};
+use clap::{Parser, Subcommand};
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+
+pub mod fork_readiness;
+pub mod replay_pipeline;
+
+/// `reth debug` command
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(subcommand)]
+    pub command: Subcommands<C>,
+}
+
+/// `reth debug` subcommands
+#[derive(Subcommand, Debug)]
+pub enum Subcommands<C: ChainSpecParser> {
+    /// Re-run the pipeline from inputs recorded with `--debug.record-pipeline`.
+    ///
+    /// The recorded sync targets and downloaded batches are fed to the stages instead of the
+    /// network, so the datadir must be in the same state as when the recording started.
+    #[command(name = "replay-pipeline")]
+    ReplayPipeline(replay_pipeline::Command<C>),
+    /// Check the chain spec, engine API and state of the node against an upcoming fork.
+    #[command(name = "fork-readiness")]
+    ForkReadiness(fork_readiness::Command<C>),
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C> {
+    /// Execute `debug` command
+    pub async fn execute<N, Comp>(
+        self,
+        components: impl FnOnce(Arc<C::ChainSpec>) -> Comp,
+    ) -> eyre::Result<()>
+    where
+        N: CliNodeTypes<ChainSpec = C::ChainSpec>,
+        Comp: CliNodeComponents<N>,
+    {
+        match self.command {
+            Subcommands::ReplayPipeline(command) => command.execute::<N, _>(components).await,
+            Subcommands::ForkReadiness(command) => command.execute::<N>().await,
+        }
+    }
+}
+
+impl<C: ChainSpecParser> Command<C> {
+    /// Returns the underlying chain being used to run this command
+    pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        match self.command {
+            Subcommands::ReplayPipeline(ref command) => command.chain_spec(),
+            Subcommands::ForkReadiness(ref command) => command.chain_spec(),
+        }
+    }
+}
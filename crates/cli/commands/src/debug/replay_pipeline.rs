@@ -0,0 +1,103 @@
+//! Command that re-runs the pipeline from recorded inputs.
+
+use crate::common::{AccessRights, CliNodeComponents, CliNodeTypes, Environment, EnvironmentArgs};
+use alloy_primitives::B256;
+use clap::Parser;
+use reth_chainspec::{ChainSpecProvider, EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_downloaders::pipeline_log::PipelineInputLog;
+use reth_exex::ExExManagerHandle;
+use reth_node_api::BlockTy;
+use reth_node_core::version::version_metadata;
+use reth_stages::{prelude::DefaultStages, stages::ExecutionStage, Pipeline, StageSet};
+use reth_static_file::StaticFileProducer;
+use std::{path::PathBuf, sync::Arc};
+use tokio::sync::watch;
+use tracing::info;
+
+/// `reth debug replay-pipeline` command
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    /// The path to a pipeline input log written with `--debug.record-pipeline`.
+    #[arg(value_name = "LOG_PATH", verbatim_doc_comment)]
+    path: PathBuf,
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C> {
+    /// Execute `debug replay-pipeline` command
+    pub async fn execute<N, Comp>(
+        self,
+        components: impl FnOnce(Arc<N::ChainSpec>) -> Comp,
+    ) -> eyre::Result<()>
+    where
+        N: CliNodeTypes<ChainSpec = C::ChainSpec>,
+        Comp: CliNodeComponents<N>,
+    {
+        info!(target: "reth::cli", "reth {} starting", version_metadata().short_version);
+
+        let Environment { provider_factory, config, .. } = self.env.init::<N>(AccessRights::RW)?;
+
+        let components = components(provider_factory.chain_spec());
+
+        let log = PipelineInputLog::<BlockTy<N>>::open(&self.path)?;
+        let tips = log.tips().to_vec();
+        if tips.is_empty() {
+            eyre::bail!("No sync targets recorded in {}", self.path.display());
+        }
+        info!(target: "reth::cli", runs = tips.len(), max_block = ?log.max_block(), "Replaying pipeline inputs");
+
+        let (header_downloader, body_downloader) = log.into_downloaders()?;
+        let consensus = Arc::new(components.consensus().clone());
+        let evm_config = components.evm_config().clone();
+        let prune_modes = config.prune.segments.clone();
+
+        let (tip_tx, tip_rx) = watch::channel(B256::ZERO);
+        let mut pipeline = Pipeline::<N>::builder()
+            .with_tip_sender(tip_tx)
+            .add_stages(
+                DefaultStages::new(
+                    provider_factory.clone(),
+                    tip_rx,
+                    consensus.clone(),
+                    header_downloader,
+                    body_downloader,
+                    evm_config.clone(),
+                    config.stages.clone(),
+                    prune_modes.clone(),
+                    None,
+                )
+                .set(ExecutionStage::new(
+                    evm_config,
+                    consensus,
+                    config.stages.execution.into(),
+                    config.stages.execution_external_clean_threshold(),
+                    ExExManagerHandle::empty(),
+                )),
+            )
+            .build(
+                provider_factory.clone(),
+                StaticFileProducer::new(provider_factory, prune_modes),
+            );
+
+        pipeline.move_to_static_files()?;
+
+        // Every recorded sync target started a new pipeline run, replay them in the same order.
+        for tip in tips {
+            pipeline.set_tip(tip);
+            let control_flow = pipeline.run_loop().await?;
+            info!(target: "reth::cli", %tip, ?control_flow, "Replayed pipeline run");
+        }
+
+        Ok(())
+    }
+}
+
+impl<C: ChainSpecParser> Command<C> {
+    /// Returns the underlying chain being used to run this command
+    pub const fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        Some(&self.env.chain)
+    }
+}
@@ -281,7 +281,7 @@ where
         .sealed_header(last_block_number)?
         .ok_or_else(|| ProviderError::HeaderNotFound(last_block_number.into()))?;
 
-    let mut header_downloader = ReverseHeadersDownloaderBuilder::new(config.stages.headers)
+    let mut header_downloader = ReverseHeadersDownloaderBuilder::new(config.stages.headers.clone())
         .build(file_client.clone(), consensus.clone())
         .into_task();
     // TODO: The pipeline should correctly configure the downloader on its own.
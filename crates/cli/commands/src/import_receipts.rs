@@ -0,0 +1,388 @@
+//! Command that imports receipts from a flat file into the receipt static files.
+
+use crate::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
+use alloy_consensus::{proofs::calculate_receipt_root, BlockHeader, ReceiptWithBloom, TxReceipt};
+use clap::{Parser, ValueEnum};
+use futures::{stream, Stream, StreamExt};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_db_api::tables;
+use reth_downloaders::{
+    file_client::{ChunkedFileReader, FileClientError, DEFAULT_BYTE_LEN_CHUNK_CHAIN_FILE},
+    receipt_file_client::{ReceiptDecoder, ReceiptFileClient, RlpReceiptFileCodec},
+};
+use reth_era::common::file_ops::StreamReader;
+use reth_era_downloader::{read_dir, EraMeta};
+use reth_era_utils as era;
+use reth_node_builder::ReceiptTy;
+use reth_node_core::version::version_metadata;
+use reth_provider::{
+    providers::ProviderNodeTypes, DBProvider, DatabaseProviderFactory, ExecutionOutcome,
+    HeaderProvider, OriginalValuesKnown, ProviderError, ProviderFactory, StageCheckpointReader,
+    StageCheckpointWriter, StateWriteConfig, StateWriter, StaticFileProviderFactory, StatsReader,
+};
+use reth_stages::{StageCheckpoint, StageId};
+use reth_static_file_types::StaticFileSegment;
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tracing::{debug, info, trace, warn};
+
+/// Imports receipts from a file into the receipt static files.
+///
+/// Headers and transactions of the imported range must already be present, e.g. after an
+/// `import --no-state` or an `import-era`, and no receipts may have been written yet. Receipts that
+/// were pruned can't be restored, because receipt static files can only be appended to. The
+/// receipts root of every block is checked against the stored header before anything is written.
+#[derive(Debug, Parser)]
+pub struct ImportReceiptsCommand<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    /// Chunk byte length to read from file.
+    #[arg(long, value_name = "CHUNK_LEN", verbatim_doc_comment)]
+    chunk_len: Option<u64>,
+
+    /// The format of the receipts at `IMPORT_PATH`.
+    #[arg(long, value_enum, default_value_t = ReceiptsFileFormat::Rlp)]
+    format: ReceiptsFileFormat,
+
+    /// The path to a receipts file for import, or a directory of ERA1 files with `--format era`.
+    ///
+    /// Every entry in an RLP file is an RLP list of `[block_number, receipt]`, or an empty list
+    /// for a block without receipts. ERA1 files are read in order starting with the genesis file,
+    /// and are checked against the `checksums.txt` of the directory.
+    #[arg(value_name = "IMPORT_PATH", verbatim_doc_comment)]
+    path: PathBuf,
+}
+
+/// The format of the receipts imported by [`ImportReceiptsCommand`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum ReceiptsFileFormat {
+    /// A file of RLP encoded `[block_number, receipt]` entries.
+    #[default]
+    Rlp,
+    /// A directory of ERA1 files.
+    Era,
+}
+
+impl<C: ChainSpecParser> ImportReceiptsCommand<C> {
+    /// Execute `import-receipts` command
+    pub async fn execute<N: CliNodeTypes<ChainSpec = C::ChainSpec>>(self) -> eyre::Result<()> {
+        info!(target: "reth::cli", "reth {} starting", version_metadata().short_version);
+
+        debug!(target: "reth::cli",
+            chunk_byte_len=self.chunk_len.unwrap_or(DEFAULT_BYTE_LEN_CHUNK_CHAIN_FILE),
+            "Chunking receipts import"
+        );
+
+        let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RW)?;
+
+        let ImportReceiptsResult { total_decoded_receipts, .. } = match self.format {
+            ReceiptsFileFormat::Rlp => {
+                import_receipts_from_file::<_, RlpReceiptFileCodec<ReceiptTy<N>>, _, _>(
+                    provider_factory,
+                    self.path,
+                    self.chunk_len,
+                    true,
+                    |_, _| 0,
+                )
+                .await?
+            }
+            ReceiptsFileFormat::Era => {
+                import_receipts_from_era_dir(&provider_factory, self.path, true).await?
+            }
+        };
+
+        info!(target: "reth::cli", total_decoded_receipts, "Receipt file imported");
+
+        Ok(())
+    }
+
+    /// Returns the underlying chain being used to run this command
+    pub const fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        Some(&self.env.chain)
+    }
+}
+
+/// Imports receipts to static files from file in chunks. See [`import_receipts_from_reader`].
+pub async fn import_receipts_from_file<N, D, P, F>(
+    provider_factory: ProviderFactory<N>,
+    path: P,
+    chunk_len: Option<u64>,
+    verify_receipts_root: bool,
+    filter: F,
+) -> eyre::Result<ImportReceiptsResult>
+where
+    N: ProviderNodeTypes,
+    D: ReceiptDecoder<Receipt = ReceiptTy<N>, Error = FileClientError> + fmt::Debug + Default,
+    P: AsRef<Path>,
+    F: FnMut(u64, &mut Vec<Vec<ReceiptTy<N>>>) -> usize,
+{
+    for stage in StageId::ALL {
+        let checkpoint = provider_factory.database_provider_ro()?.get_stage_checkpoint(stage)?;
+        trace!(target: "reth::cli",
+            ?stage,
+            ?checkpoint,
+            "Read stage checkpoints from db"
+        );
+    }
+
+    // open file
+    let reader = ChunkedFileReader::new(&path, chunk_len).await?;
+
+    // import receipts
+    import_receipts_from_reader::<N, D, F>(&provider_factory, reader, verify_receipts_root, filter)
+        .await
+}
+
+/// Imports receipts to static files, decoding the file with `D`. Takes a filter callback as
+/// parameter, that returns the total number of filtered out receipts.
+///
+/// If `verify_receipts_root` is set, the receipts root of every imported block is compared with
+/// the receipts root of the stored header, and the import fails on the first mismatch.
+///
+/// Caution! Filter callback must replace completely filtered out receipts for a block, with empty
+/// vectors, rather than `vec!(None)`. This is since the code for writing to static files, expects
+/// indices in the receipts list, to map to sequential block numbers.
+pub async fn import_receipts_from_reader<N, D, F>(
+    provider_factory: &ProviderFactory<N>,
+    reader: ChunkedFileReader,
+    verify_receipts_root: bool,
+    filter: F,
+) -> eyre::Result<ImportReceiptsResult>
+where
+    N: ProviderNodeTypes,
+    D: ReceiptDecoder<Receipt = ReceiptTy<N>, Error = FileClientError> + fmt::Debug + Default,
+    F: FnMut(u64, &mut Vec<Vec<ReceiptTy<N>>>) -> usize,
+{
+    let chunks = stream::try_unfold(reader, |mut reader| async move {
+        let Some(file_client) = reader.next_receipts_chunk::<ReceiptFileClient<D>>().await? else {
+            return Ok::<_, eyre::Report>(None)
+        };
+        let ReceiptFileClient { receipts, first_block, total_receipts, .. } = file_client;
+        Ok(Some((ReceiptsChunk { receipts, first_block, total_receipts }, reader)))
+    });
+
+    import_receipt_chunks(provider_factory, chunks, verify_receipts_root, filter).await
+}
+
+/// Imports the receipts of a directory of ERA1 files to static files.
+///
+/// The files are read in order starting with the genesis file, after their checksums were checked
+/// against the `checksums.txt` of the directory. Every file is imported as a single chunk, see
+/// [`import_receipts_from_reader`].
+pub async fn import_receipts_from_era_dir<N>(
+    provider_factory: &ProviderFactory<N>,
+    dir: PathBuf,
+    verify_receipts_root: bool,
+) -> eyre::Result<ImportReceiptsResult>
+where
+    N: ProviderNodeTypes,
+{
+    let chunks = read_dir(dir, 0)?.map(|meta| {
+        let meta = meta?;
+        let mut chunk = ReceiptsChunk::<ReceiptTy<N>>::default();
+        for block in era::open(&meta)?.iter() {
+            let block = block?;
+            let number = block.header.decode_header()?.number;
+            if chunk.receipts.is_empty() {
+                chunk.first_block = number;
+            }
+            eyre::ensure!(
+                number == chunk.first_block + chunk.receipts.len() as u64,
+                "ERA1 file {} is not ordered by block number at block {number}",
+                meta.path().display()
+            );
+
+            let receipts = block.receipts.decode::<Vec<ReceiptWithBloom<ReceiptTy<N>>>>()?;
+            chunk.total_receipts += receipts.len();
+            chunk.receipts.push(receipts.into_iter().map(|receipt| receipt.receipt).collect());
+        }
+        eyre::ensure!(
+            !chunk.receipts.is_empty(),
+            "ERA1 file {} has no blocks",
+            meta.path().display()
+        );
+        Ok(chunk)
+    });
+
+    import_receipt_chunks(provider_factory, chunks, verify_receipts_root, |_, _| 0).await
+}
+
+/// Receipts of consecutive blocks, read from a receipts file.
+#[derive(Debug)]
+struct ReceiptsChunk<R> {
+    /// The receipts, one list per block.
+    receipts: Vec<Vec<R>>,
+    /// The number of the first block.
+    first_block: u64,
+    /// The total number of receipts.
+    total_receipts: usize,
+}
+
+impl<R> Default for ReceiptsChunk<R> {
+    fn default() -> Self {
+        Self { receipts: Vec::new(), first_block: 0, total_receipts: 0 }
+    }
+}
+
+/// Imports the receipts of the chunks to static files, see [`import_receipts_from_reader`].
+async fn import_receipt_chunks<N, S, F>(
+    provider_factory: &ProviderFactory<N>,
+    chunks: S,
+    verify_receipts_root: bool,
+    mut filter: F,
+) -> eyre::Result<ImportReceiptsResult>
+where
+    N: ProviderNodeTypes,
+    S: Stream<Item = eyre::Result<ReceiptsChunk<ReceiptTy<N>>>>,
+    F: FnMut(u64, &mut Vec<Vec<ReceiptTy<N>>>) -> usize,
+{
+    let mut chunks = std::pin::pin!(chunks);
+    let static_file_provider = provider_factory.static_file_provider();
+
+    // Ensure that receipts hasn't been initialized apart from `init_genesis`.
+    if let Some(num_receipts) =
+        static_file_provider.get_highest_static_file_tx(StaticFileSegment::Receipts) &&
+        num_receipts > 0
+    {
+        eyre::bail!("Expected no receipts in storage, but found {num_receipts}.");
+    }
+    match static_file_provider.get_highest_static_file_block(StaticFileSegment::Receipts) {
+        Some(receipts_block) => {
+            if receipts_block > 0 {
+                eyre::bail!("Expected highest receipt block to be 0, but found {receipts_block}.");
+            }
+        }
+        None => {
+            eyre::bail!(
+                "Receipts was not initialized. Please import blocks and transactions before calling this command."
+            );
+        }
+    }
+
+    let provider = provider_factory.database_provider_rw()?;
+    let mut total_decoded_receipts = 0;
+    let mut total_receipts = 0;
+    let mut total_filtered_out_dup_txns = 0;
+    let mut highest_block_receipts = 0;
+
+    let highest_block_transactions = static_file_provider
+        .get_highest_static_file_block(StaticFileSegment::Transactions)
+        .expect("transaction static files must exist before importing receipts");
+
+    while let Some(chunk) = chunks.next().await.transpose()? {
+        if highest_block_receipts == highest_block_transactions {
+            warn!(target: "reth::cli",  highest_block_receipts, highest_block_transactions, "Ignoring all other blocks in the file since we have reached the desired height");
+            break
+        }
+
+        let ReceiptsChunk { mut receipts, mut first_block, total_receipts: total_receipts_chunk } =
+            chunk;
+
+        // mark these as decoded
+        total_decoded_receipts += total_receipts_chunk;
+
+        total_filtered_out_dup_txns += filter(first_block, &mut receipts);
+
+        info!(target: "reth::cli",
+            first_receipts_block=?first_block,
+            total_receipts_chunk,
+            "Importing receipt file chunk"
+        );
+
+        // It is possible for the first receipt returned by the file client to be the genesis
+        // block. In this case, we just prepend empty receipts to the current list of receipts.
+        // When initially writing to static files, the provider expects the first block to be block
+        // one. So, if the first block returned by the file client is the genesis block, we remove
+        // those receipts.
+        if first_block == 0 {
+            // remove the first empty receipts
+            let genesis_receipts = receipts.remove(0);
+            debug_assert!(genesis_receipts.is_empty());
+            // this ensures the execution outcome and static file producer start at block 1
+            first_block = 1;
+        }
+        highest_block_receipts = first_block + receipts.len() as u64 - 1;
+
+        // RLP file may have too many blocks. We ignore the excess, but warn the user.
+        if highest_block_receipts > highest_block_transactions {
+            let excess = highest_block_receipts - highest_block_transactions;
+            highest_block_receipts -= excess;
+
+            // Remove the last `excess` blocks
+            receipts.truncate(receipts.len() - excess as usize);
+
+            warn!(target: "reth::cli", highest_block_receipts, "Too many decoded blocks, ignoring the last {excess}.");
+        }
+
+        if verify_receipts_root {
+            for (index, block_receipts) in receipts.iter().enumerate() {
+                let block_number = first_block + index as u64;
+                let header = provider
+                    .header_by_number(block_number)?
+                    .ok_or(ProviderError::HeaderNotFound(block_number.into()))?;
+
+                let receipts_with_bloom =
+                    block_receipts.iter().map(TxReceipt::with_bloom_ref).collect::<Vec<_>>();
+                let receipts_root = calculate_receipt_root(&receipts_with_bloom);
+                if receipts_root != header.receipts_root() {
+                    eyre::bail!(
+                        "Receipts root mismatch for block {block_number}: expected {}, got {receipts_root}",
+                        header.receipts_root()
+                    )
+                }
+            }
+        }
+
+        // Update total_receipts after all filtering
+        total_receipts += receipts.iter().map(|v| v.len()).sum::<usize>();
+
+        let execution_outcome =
+            ExecutionOutcome::new(Default::default(), receipts, first_block, Default::default());
+
+        // finally, write the receipts
+        provider.write_state(
+            &execution_outcome,
+            OriginalValuesKnown::Yes,
+            StateWriteConfig::default(),
+        )?;
+    }
+
+    // Only commit if we have imported as many receipts as the number of transactions.
+    let total_imported_txns = static_file_provider
+        .count_entries::<tables::Transactions>()
+        .expect("transaction static files must exist before importing receipts");
+
+    if total_receipts != total_imported_txns {
+        eyre::bail!(
+            "Number of receipts ({total_receipts}) inconsistent with transactions {total_imported_txns}"
+        )
+    }
+
+    // Only commit if the receipt block height matches the one from transactions.
+    if highest_block_receipts != highest_block_transactions {
+        eyre::bail!(
+            "Receipt block height ({highest_block_receipts}) inconsistent with transactions' {highest_block_transactions}"
+        )
+    }
+
+    // Required or any access-write provider factory will attempt to unwind to 0.
+    provider
+        .save_stage_checkpoint(StageId::Execution, StageCheckpoint::new(highest_block_receipts))?;
+
+    provider.commit()?;
+
+    Ok(ImportReceiptsResult { total_decoded_receipts, total_filtered_out_dup_txns })
+}
+
+/// Result of importing receipts in chunks.
+#[derive(Debug)]
+pub struct ImportReceiptsResult {
+    /// Total decoded receipts.
+    pub total_decoded_receipts: usize,
+    /// Total filtered out receipts.
+    pub total_filtered_out_dup_txns: usize,
+}
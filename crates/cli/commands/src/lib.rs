@@ -8,15 +8,18 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
+pub mod backfill;
 pub mod common;
 pub mod config_cmd;
 pub mod db;
+pub mod debug;
 pub mod download;
 pub mod dump_genesis;
 pub mod export_era;
 pub mod import;
 pub mod import_core;
 pub mod import_era;
+pub mod import_receipts;
 pub mod init_cmd;
 pub mod init_state;
 pub mod launcher;
@@ -25,6 +28,7 @@ pub mod p2p;
 pub mod prune;
 pub mod re_execute;
 pub mod stage;
+pub mod static_files;
 #[cfg(feature = "arbitrary")]
 pub mod test_vectors;
 
@@ -9,7 +9,7 @@ use reth_db::init_db;
 use reth_node_builder::NodeBuilder;
 use reth_node_core::{
     args::{
-        DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, EngineArgs, EraArgs, MetricArgs,
+        DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, EngineArgs, EraArgs, ExExArgs, MetricArgs,
         NetworkArgs, PayloadBuilderArgs, PruningArgs, RocksDbArgs, RpcServerArgs, StaticFilesArgs,
         TxPoolArgs,
     },
@@ -119,6 +119,10 @@ pub struct NodeCommand<C: ChainSpecParser, Ext: clap::Args + fmt::Debug = NoArgs
     #[command(flatten, next_help_heading = "Static Files")]
     pub static_files: StaticFilesArgs,
 
+    /// All `ExEx` related arguments with --exex prefix
+    #[command(flatten, next_help_heading = "ExEx")]
+    pub exex: ExExArgs,
+
     /// Additional cli arguments
     #[command(flatten, next_help_heading = "Extension")]
     pub ext: Ext,
@@ -175,6 +179,7 @@ where
             engine,
             era,
             static_files,
+            exex,
             ext,
         } = self;
 
@@ -199,6 +204,7 @@ where
             rocksdb,
             engine,
             era,
+            exex,
             static_files,
         };
 
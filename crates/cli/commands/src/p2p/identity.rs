@@ -0,0 +1,260 @@
+//! Node identity command
+
+use alloy_primitives::{hex, Bytes, B256};
+use clap::{Args, Parser, Subcommand};
+use reth_chainspec::EthChainSpec;
+use reth_cli::chainspec::ChainSpecParser;
+use reth_cli_util::{get_secret_key, load_secret_key::rng_secret_key};
+use reth_discv4::DEFAULT_DISCOVERY_PORT;
+use reth_discv5::{
+    discv5::{
+        self,
+        enr::{Enr, EnrKey},
+    },
+    enr_to_discv4_id,
+};
+use reth_fs_util as fs;
+use reth_network_peers::{pk2id, NodeRecord};
+use reth_node_core::args::DatadirArgs;
+use secp256k1::{SecretKey, SECP256K1};
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// Manage the node key, from which the peer id of the node is derived, and its ENR.
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(flatten)]
+    datadir: DatadirArgs,
+
+    /// The chain this node is running.
+    ///
+    /// Possible values are either a built-in chain or the path to a chain specification file.
+    #[arg(
+        long,
+        value_name = "CHAIN_OR_PATH",
+        long_help = C::help_message(),
+        default_value = C::default_value(),
+        value_parser = C::parser(),
+        global = true
+    )]
+    chain: Arc<C::ChainSpec>,
+
+    /// Path to the node key file.
+    ///
+    /// Defaults to the `discovery-secret` file of the datadir.
+    #[arg(long, value_name = "PATH", global = true)]
+    p2p_secret_key: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: Subcommands,
+}
+
+/// `reth p2p identity` subcommands
+#[derive(Debug, Subcommand)]
+pub enum Subcommands {
+    /// Print the peer id, enode URL and ENR of the node key.
+    Show(EnrArgs),
+    /// Replace the node key with a new random key.
+    ///
+    /// This changes the peer id of the node.
+    Regenerate {
+        /// Overwrite an existing node key.
+        #[arg(long)]
+        force: bool,
+    },
+    /// Replace the node key with the given key.
+    Import {
+        /// The hex encoded secret key.
+        secret_key: B256,
+
+        /// Overwrite an existing node key.
+        #[arg(long)]
+        force: bool,
+    },
+    /// Print an ENR signed with the node key.
+    Sign(EnrArgs),
+    /// Verify the signature of an ENR and print its content.
+    Verify {
+        /// The base64 encoded ENR, starting with `enr:`.
+        enr: String,
+    },
+}
+
+/// The content of an ENR signed with the node key.
+#[derive(Debug, Args)]
+pub struct EnrArgs {
+    /// IP address to include in the ENR.
+    ///
+    /// If not provided, the ENR doesn't include an address.
+    #[arg(long)]
+    ip: Option<IpAddr>,
+
+    /// TCP port of `RLPx` connections to include in the ENR.
+    #[arg(long, default_value_t = DEFAULT_DISCOVERY_PORT)]
+    tcp_port: u16,
+
+    /// UDP port of discovery to include in the ENR.
+    #[arg(long, default_value_t = DEFAULT_DISCOVERY_PORT)]
+    udp_port: u16,
+
+    /// Sequence number of the ENR.
+    #[arg(long, default_value_t = 1)]
+    seq: u64,
+
+    /// Additional kv-pair to include in the ENR, the value is the hex encoded rlp value.
+    ///
+    /// Can be passed multiple times, e.g. `--field eth=0xc7c68488...`.
+    #[arg(long = "field", value_name = "KEY=RLP", value_parser = parse_enr_field)]
+    fields: Vec<(String, Bytes)>,
+}
+
+impl EnrArgs {
+    /// Builds the ENR and signs it with the given key.
+    fn sign(&self, sk: &SecretKey) -> eyre::Result<Enr<SecretKey>> {
+        let mut builder = Enr::builder();
+        builder.seq(self.seq);
+        match self.ip {
+            Some(ip @ IpAddr::V4(_)) => {
+                builder.ip(ip).tcp4(self.tcp_port).udp4(self.udp_port);
+            }
+            Some(ip @ IpAddr::V6(_)) => {
+                builder.ip(ip).tcp6(self.tcp_port).udp6(self.udp_port);
+            }
+            None => {
+                builder.tcp4(self.tcp_port).udp4(self.udp_port);
+            }
+        }
+        for (key, rlp) in &self.fields {
+            builder.add_value_rlp(key, rlp.0.clone());
+        }
+        builder.build(sk).map_err(|err| eyre::eyre!("failed to build ENR: {err:?}"))
+    }
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec>> Command<C> {
+    /// Execute `p2p identity` command
+    pub fn execute(self) -> eyre::Result<()> {
+        let path = self.p2p_secret_key.clone().unwrap_or_else(|| {
+            self.datadir.clone().resolve_datadir(self.chain.chain()).p2p_secret()
+        });
+
+        match self.command {
+            Subcommands::Show(args) => {
+                let sk = load_secret_key(&path)?;
+                let ip = args.ip.unwrap_or(IpAddr::V4(Ipv4Addr::UNSPECIFIED));
+                let mut enode =
+                    NodeRecord::from_secret_key(SocketAddr::new(ip, args.tcp_port), &sk);
+                enode.udp_port = args.udp_port;
+
+                println!("Node key: {}", path.display());
+                println!("Peer ID:  {}", pk2id(&sk.public_key(SECP256K1)));
+                println!("Enode:    {enode}");
+                print_enr(&args.sign(&sk)?);
+            }
+            Subcommands::Regenerate { force } => {
+                let sk = rng_secret_key();
+                store_secret_key(&path, &sk, force)?;
+                println!("Generated node key {}", path.display());
+                println!("Peer ID: {}", pk2id(&sk.public_key(SECP256K1)));
+            }
+            Subcommands::Import { secret_key, force } => {
+                let sk = SecretKey::from_slice(secret_key.as_slice())?;
+                store_secret_key(&path, &sk, force)?;
+                println!("Imported node key {}", path.display());
+                println!("Peer ID: {}", pk2id(&sk.public_key(SECP256K1)));
+            }
+            Subcommands::Sign(args) => {
+                let sk = load_secret_key(&path)?;
+                println!("{}", args.sign(&sk)?);
+            }
+            Subcommands::Verify { enr } => {
+                let enr = enr.parse::<discv5::Enr>().map_err(|err| eyre::eyre!("{err}"))?;
+                eyre::ensure!(enr.verify(), "Invalid ENR signature");
+                if let Some(peer_id) = enr_to_discv4_id(&enr) {
+                    println!("Peer ID:  {peer_id}");
+                }
+                print_enr(&enr);
+                println!("Signature is valid");
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<C: ChainSpecParser> Command<C> {
+    /// Returns the underlying chain being used to run this command
+    pub const fn chain_spec(&self) -> &Arc<C::ChainSpec> {
+        &self.chain
+    }
+}
+
+/// Loads the node key, without generating a new one if there's none.
+fn load_secret_key(path: &Path) -> eyre::Result<SecretKey> {
+    eyre::ensure!(
+        path.exists(),
+        "No node key at {}, it is generated on the first start of the node or with `reth p2p identity regenerate`",
+        path.display()
+    );
+    Ok(get_secret_key(path)?)
+}
+
+/// Writes the node key to the given path, in the format read by [`get_secret_key`].
+fn store_secret_key(path: &Path, sk: &SecretKey, force: bool) -> eyre::Result<()> {
+    eyre::ensure!(
+        force || !path.exists(),
+        "Node key {} already exists, use `--force` to overwrite it",
+        path.display()
+    );
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, hex::encode(sk.as_ref()))?;
+    Ok(())
+}
+
+/// Prints the ENR and its kv-pairs.
+fn print_enr<K: EnrKey>(enr: &Enr<K>) {
+    println!("Node ID:  {}", enr.node_id());
+    println!("ENR:      {}", enr.to_base64());
+    println!("Sequence: {}", enr.seq());
+    for (key, value) in enr.iter() {
+        println!("  {}: 0x{}", String::from_utf8_lossy(key), hex::encode(value));
+    }
+}
+
+/// Parses a `KEY=RLP` pair of an ENR.
+fn parse_enr_field(value: &str) -> eyre::Result<(String, Bytes)> {
+    let (key, rlp) =
+        value.split_once('=').ok_or_else(|| eyre::eyre!("expected KEY=RLP, got {value}"))?;
+    eyre::ensure!(!key.is_empty(), "ENR key must not be empty");
+    Ok((key.to_string(), rlp.parse()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sign_and_verify_enr() {
+        let sk = rng_secret_key();
+        let args = EnrArgs {
+            ip: Some(IpAddr::V4(Ipv4Addr::LOCALHOST)),
+            tcp_port: 30303,
+            udp_port: 30304,
+            seq: 3,
+            fields: vec![parse_enr_field("feature=0x07").unwrap()],
+        };
+
+        let enr = args.sign(&sk).unwrap().to_base64().parse::<discv5::Enr>().unwrap();
+        assert!(enr.verify());
+        assert_eq!(enr_to_discv4_id(&enr), Some(pk2id(&sk.public_key(SECP256K1))));
+        assert_eq!(enr.seq(), 3);
+        assert_eq!(enr.udp4(), Some(30304));
+        assert_eq!(enr.get_decodable::<u64>(b"feature"), Some(Ok(7)));
+    }
+}
@@ -19,6 +19,7 @@ use reth_node_core::{
 
 pub mod bootnode;
 pub mod enode;
+pub mod identity;
 pub mod rlpx;
 
 /// `reth p2p` command
@@ -89,6 +90,9 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
             Subcommands::Enode(command) => {
                 command.execute()?;
             }
+            Subcommands::Identity(command) => {
+                command.execute()?;
+            }
         }
 
         Ok(())
@@ -104,6 +108,7 @@ impl<C: ChainSpecParser> Command<C> {
             Subcommands::Rlpx(_) => None,
             Subcommands::Bootnode(_) => None,
             Subcommands::Enode(_) => None,
+            Subcommands::Identity(command) => Some(command.chain_spec()),
         }
     }
 }
@@ -133,6 +138,8 @@ pub enum Subcommands<C: ChainSpecParser> {
     Bootnode(bootnode::Command),
     /// Print enode identifier
     Enode(enode::Command),
+    /// Manage the node key and ENR
+    Identity(identity::Command<C>),
 }
 
 #[derive(Debug, Clone, Parser)]
@@ -244,4 +251,17 @@ mod tests {
         let _args: Command<EthereumChainSpecParser> =
             Command::parse_from(["reth", "enode", "/tmp/secret", "--ip", "192.168.1.1"]);
     }
+
+    #[test]
+    fn parse_identity_cmd() {
+        let _args: Command<EthereumChainSpecParser> = Command::parse_from([
+            "reth",
+            "identity",
+            "sign",
+            "--p2p-secret-key",
+            "/tmp/secret",
+            "--field",
+            "eth=0xc7c6849fc3a6b880",
+        ]);
+    }
 }
@@ -0,0 +1,279 @@
+use super::setup;
+use alloy_primitives::{Address, BlockNumber, B256};
+use eyre::Result;
+use reth_db::DatabaseEnv;
+use reth_db_api::{tables, transaction::DbTxMut};
+use reth_db_common::DbTool;
+use reth_node_core::dirs::{ChainPath, DataDirPath};
+use reth_provider::{
+    providers::{ProviderNodeTypes, RocksDBProvider, StaticFileProvider},
+    ChangeSetReader, DBProvider, DatabaseProviderFactory, HistoryReader, HistoryWriter,
+    ProviderFactory, ProviderResult, PruneCheckpointReader, StorageChangeSetReader,
+};
+use reth_prune::PruneSegment;
+use reth_stages::{
+    stages::{IndexAccountHistoryStage, IndexStorageHistoryStage},
+    ExecInput, Stage, StageCheckpoint,
+};
+use std::{collections::BTreeSet, ops::RangeInclusive, sync::Arc};
+use tracing::info;
+
+pub(crate) async fn dump_account_history_stage<N: ProviderNodeTypes<DB = Arc<DatabaseEnv>>>(
+    db_tool: &DbTool<N>,
+    from: BlockNumber,
+    to: BlockNumber,
+    output_datadir: ChainPath<DataDirPath>,
+    should_run: bool,
+) -> Result<()> {
+    let (output_db, _) = setup(from, to, &output_datadir.db(), db_tool)?;
+    let provider_factory = output_provider_factory(db_tool, output_db, &output_datadir)?;
+
+    let provider_rw = provider_factory.database_provider_rw()?;
+    copy_account_history(&db_tool.provider_factory.provider()?, &provider_rw, from..=to)?;
+    provider_rw.commit()?;
+
+    if should_run {
+        dry_run(provider_factory, IndexAccountHistoryStage::default(), to, from)?;
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn dump_storage_history_stage<N: ProviderNodeTypes<DB = Arc<DatabaseEnv>>>(
+    db_tool: &DbTool<N>,
+    from: BlockNumber,
+    to: BlockNumber,
+    output_datadir: ChainPath<DataDirPath>,
+    should_run: bool,
+) -> Result<()> {
+    let (output_db, _) = setup(from, to, &output_datadir.db(), db_tool)?;
+    let provider_factory = output_provider_factory(db_tool, output_db, &output_datadir)?;
+
+    let provider_rw = provider_factory.database_provider_rw()?;
+    copy_storage_history(&db_tool.provider_factory.provider()?, &provider_rw, from..=to)?;
+    provider_rw.commit()?;
+
+    if should_run {
+        dry_run(provider_factory, IndexStorageHistoryStage::default(), to, from)?;
+    }
+
+    Ok(())
+}
+
+/// Copies the input of the account history stage for the range into the output.
+///
+/// The account changesets of the range are read wherever the source keeps them, database or
+/// static files, and written to the database of the output. The history indices of the changed
+/// accounts from before the range are copied as well, wherever the source keeps them, so that
+/// the stage appends to the same shards as it does on the source. Indices of pruned blocks aren't
+/// available and are skipped.
+fn copy_account_history<Source, Output>(
+    source: &Source,
+    output: &Output,
+    range: RangeInclusive<BlockNumber>,
+) -> ProviderResult<()>
+where
+    Source: ChangeSetReader + HistoryReader + PruneCheckpointReader,
+    Output: DBProvider<Tx: DbTxMut> + HistoryWriter,
+{
+    let changesets = source.account_changesets_range(range.clone())?;
+    let addresses =
+        changesets.iter().map(|(_, changeset)| changeset.address).collect::<BTreeSet<_>>();
+    for (block_number, changeset) in changesets {
+        output.tx_ref().put::<tables::AccountChangeSets>(block_number, changeset)?;
+    }
+
+    let Some(before) = unpruned_blocks_before(source, PruneSegment::AccountHistory, &range)? else {
+        return Ok(())
+    };
+    let mut indices = Vec::new();
+    for address in addresses {
+        let blocks = source
+            .account_history_range(address, before.clone())?
+            .into_iter()
+            .map(|(block_number, _)| block_number)
+            .collect::<Vec<_>>();
+        if !blocks.is_empty() {
+            indices.push((address, blocks));
+        }
+    }
+    output.insert_account_history_index(indices)
+}
+
+/// Copies the input of the storage history stage for the range into the output.
+///
+/// Same as [`copy_account_history`], for the storage changesets and the history indices of the
+/// changed storage slots.
+fn copy_storage_history<Source, Output>(
+    source: &Source,
+    output: &Output,
+    range: RangeInclusive<BlockNumber>,
+) -> ProviderResult<()>
+where
+    Source: StorageChangeSetReader + HistoryReader + PruneCheckpointReader,
+    Output: DBProvider<Tx: DbTxMut> + HistoryWriter,
+{
+    let changesets = source.storage_changesets_range(range.clone())?;
+    let slots = changesets
+        .iter()
+        .map(|(block_address, entry)| (block_address.address(), entry.key))
+        .collect::<BTreeSet<(Address, B256)>>();
+    for (block_address, entry) in changesets {
+        output.tx_ref().put::<tables::StorageChangeSets>(block_address, entry)?;
+    }
+
+    let Some(before) = unpruned_blocks_before(source, PruneSegment::StorageHistory, &range)? else {
+        return Ok(())
+    };
+    let mut indices = Vec::new();
+    for (address, storage_key) in slots {
+        let blocks = source
+            .storage_history_range(address, storage_key, before.clone())?
+            .into_iter()
+            .map(|(block_number, _)| block_number)
+            .collect::<Vec<_>>();
+        if !blocks.is_empty() {
+            indices.push(((address, storage_key), blocks));
+        }
+    }
+    output.insert_storage_history_index(indices)
+}
+
+/// Returns the blocks before `range` whose history of the segment wasn't pruned, if any.
+fn unpruned_blocks_before(
+    source: &impl PruneCheckpointReader,
+    segment: PruneSegment,
+    range: &RangeInclusive<BlockNumber>,
+) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+    let Some(last_block) = range.start().checked_sub(1) else { return Ok(None) };
+    let first_block = source
+        .get_prune_checkpoint(segment)?
+        .and_then(|checkpoint| checkpoint.block_number)
+        .map_or(0, |pruned| pruned + 1);
+    Ok((first_block <= last_block).then_some(first_block..=last_block))
+}
+
+/// Creates a provider factory for the dumped database.
+fn output_provider_factory<N: ProviderNodeTypes<DB = Arc<DatabaseEnv>>>(
+    db_tool: &DbTool<N>,
+    output_db: DatabaseEnv,
+    output_datadir: &ChainPath<DataDirPath>,
+) -> Result<ProviderFactory<N>> {
+    Ok(ProviderFactory::<N>::new(
+        Arc::new(output_db),
+        db_tool.chain(),
+        StaticFileProvider::read_write(output_datadir.static_files())?,
+        RocksDBProvider::builder(output_datadir.rocksdb()).build()?,
+    )?)
+}
+
+/// Try to re-execute the stage straight away
+fn dry_run<N, S>(
+    output_provider_factory: ProviderFactory<N>,
+    mut stage: S,
+    to: u64,
+    from: u64,
+) -> eyre::Result<()>
+where
+    N: ProviderNodeTypes,
+    S: Stage<<ProviderFactory<N> as DatabaseProviderFactory>::ProviderRW>,
+{
+    info!(target: "reth::cli", "Executing stage.");
+
+    let provider = output_provider_factory.database_provider_rw()?;
+    let mut checkpoint = StageCheckpoint::new(from);
+    loop {
+        let input = ExecInput { target: Some(to), checkpoint: Some(checkpoint) };
+        let output = stage.execute(&provider, input)?;
+        checkpoint = output.checkpoint;
+        if output.done {
+            break
+        }
+    }
+
+    info!(target: "reth::cli", "Success.");
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::U256;
+    use reth_db_api::{
+        models::{AccountBeforeTx, BlockNumberAddress, ShardedKey, StorageShardedKey},
+        transaction::DbTx,
+    };
+    use reth_primitives_traits::StorageEntry;
+    use reth_provider::test_utils::create_test_provider_factory;
+
+    #[test]
+    fn copy_history_input() {
+        let address = Address::with_last_byte(1);
+        let storage_key = B256::with_last_byte(2);
+
+        let source = create_test_provider_factory();
+        let provider_rw = source.database_provider_rw().unwrap();
+        for block_number in 1..=3 {
+            provider_rw
+                .tx_ref()
+                .put::<tables::AccountChangeSets>(
+                    block_number,
+                    AccountBeforeTx { address, info: None },
+                )
+                .unwrap();
+            provider_rw
+                .tx_ref()
+                .put::<tables::StorageChangeSets>(
+                    BlockNumberAddress((block_number, address)),
+                    StorageEntry { key: storage_key, value: U256::from(block_number) },
+                )
+                .unwrap();
+        }
+        provider_rw.insert_account_history_index([(address, [1, 2, 3])]).unwrap();
+        provider_rw.insert_storage_history_index([((address, storage_key), [1, 2, 3])]).unwrap();
+        provider_rw.commit().unwrap();
+
+        let output = create_test_provider_factory();
+        let provider_rw = output.database_provider_rw().unwrap();
+        let provider = source.provider().unwrap();
+        copy_account_history(&provider, &provider_rw, 2..=3).unwrap();
+        copy_storage_history(&provider, &provider_rw, 2..=3).unwrap();
+        provider_rw.commit().unwrap();
+
+        // only the changesets of the range are copied
+        let provider = output.provider().unwrap();
+        assert_eq!(
+            provider
+                .account_changesets_range(0..=3)
+                .unwrap()
+                .into_iter()
+                .map(|(block_number, _)| block_number)
+                .collect::<Vec<_>>(),
+            [2, 3]
+        );
+        assert_eq!(
+            provider
+                .storage_changesets_range(0..=3)
+                .unwrap()
+                .into_iter()
+                .map(|(block_address, _)| block_address.block_number())
+                .collect::<Vec<_>>(),
+            [2, 3]
+        );
+
+        // the indices from before the range are copied, the stage adds the ones of the range
+        let accounts = provider
+            .tx_ref()
+            .get::<tables::AccountsHistory>(ShardedKey::new(address, u64::MAX))
+            .unwrap()
+            .unwrap();
+        assert_eq!(accounts.iter().collect::<Vec<_>>(), [1]);
+        let storages = provider
+            .tx_ref()
+            .get::<tables::StoragesHistory>(StorageShardedKey::new(address, storage_key, u64::MAX))
+            .unwrap()
+            .unwrap();
+        assert_eq!(storages.iter().collect::<Vec<_>>(), [1]);
+    }
+}
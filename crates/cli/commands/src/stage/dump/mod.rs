@@ -29,6 +29,9 @@ use execution::dump_execution_stage;
 mod merkle;
 use merkle::dump_merkle_stage;
 
+mod history;
+use history::{dump_account_history_stage, dump_storage_history_stage};
+
 /// `reth dump-stage` command
 #[derive(Debug, Parser)]
 pub struct Command<C: ChainSpecParser> {
@@ -50,6 +53,10 @@ pub enum Stages {
     AccountHashing(StageCommand),
     /// Merkle stage.
     Merkle(StageCommand),
+    /// `AccountHistory` stage.
+    AccountHistory(StageCommand),
+    /// `StorageHistory` stage.
+    StorageHistory(StageCommand),
 }
 
 /// Stage command that takes a range
@@ -110,6 +117,8 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
             Stages::Merkle(cmd) => {
                 handle_stage!(dump_merkle_stage, &tool, cmd, evm_config, consensus)
             }
+            Stages::AccountHistory(cmd) => handle_stage!(dump_account_history_stage, &tool, cmd),
+            Stages::StorageHistory(cmd) => handle_stage!(dump_storage_history_stage, &tool, cmd),
         }
 
         Ok(())
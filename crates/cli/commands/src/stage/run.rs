@@ -34,9 +34,9 @@ use reth_provider::{
 };
 use reth_stages::{
     stages::{
-        AccountHashingStage, BodyStage, ExecutionStage, HeaderStage, IndexAccountHistoryStage,
-        IndexStorageHistoryStage, MerkleStage, SenderRecoveryStage, StorageHashingStage,
-        TransactionLookupStage,
+        AccountHashingStage, BodyStage, ContractsIndexer, ExecutionStage, HeaderStage,
+        IndexAccountHistoryStage, IndexStorageHistoryStage, MerkleStage, SenderRecoveryStage,
+        StorageHashingStage, TransactionLookupStage,
     },
     ExecInput, ExecOutput, ExecutionStageThresholds, Stage, StageExt, UnwindInput, UnwindOutput,
 };
@@ -195,7 +195,7 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
                     (
                         Box::new(HeaderStage::new(
                             provider_factory.clone(),
-                            ReverseHeadersDownloaderBuilder::new(config.stages.headers)
+                            ReverseHeadersDownloaderBuilder::new(config.stages.headers.clone())
                                 .build(fetch_client, consensus.clone()),
                             rx,
                             etl_config,
@@ -254,18 +254,26 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
                     None,
                 ),
                 StageEnum::Execution => (
-                    Box::new(ExecutionStage::new(
-                        components.evm_config().clone(),
-                        Arc::new(components.consensus().clone()),
-                        ExecutionStageThresholds {
-                            max_blocks: Some(batch_size),
-                            max_changes: None,
-                            max_cumulative_gas: None,
-                            max_duration: None,
-                        },
-                        config.stages.merkle.incremental_threshold,
-                        ExExManagerHandle::empty(),
-                    )),
+                    Box::new(
+                        ExecutionStage::new(
+                            components.evm_config().clone(),
+                            Arc::new(components.consensus().clone()),
+                            ExecutionStageThresholds {
+                                max_blocks: Some(batch_size),
+                                max_changes: None,
+                                max_cumulative_gas: None,
+                                max_duration: None,
+                            },
+                            config.stages.merkle.incremental_threshold,
+                            ExExManagerHandle::empty(),
+                        )
+                        .with_contracts_indexer(
+                            ContractsIndexer::from_config(
+                                components.evm_config().clone(),
+                                &config.stages,
+                            ),
+                        ),
+                    ),
                     None,
                 ),
                 StageEnum::TxLookup => (
@@ -0,0 +1,196 @@
+//! Command that rewrites static files with a different compression algorithm.
+
+use crate::common::{AccessRights, CliNodeTypes, EnvironmentArgs};
+use clap::Parser;
+use comfy_table::{Cell, Row, Table as ComfyTable};
+use eyre::WrapErr;
+use human_bytes::human_bytes;
+use itertools::Itertools;
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_fs_util as fs;
+use reth_nippy_jar::{NippyJar, NippyJarCursor, NippyJarWriter};
+use reth_provider::{
+    providers::{replace_static_file, replacement_path, REPLACEMENTS_DIR},
+    StaticFileProviderFactory,
+};
+use reth_static_file_types::{Compression, SegmentHeader, StaticFileSegment};
+use std::{path::Path, sync::Arc};
+use tracing::info;
+
+/// Maximum size of a trained zstd dictionary, per column.
+const ZSTD_MAX_DICT_SIZE: usize = 100_000;
+
+/// Maximum number of rows sampled from a static file to train its zstd dictionaries.
+const ZSTD_DICT_SAMPLE_ROWS: usize = 100_000;
+
+/// `reth static-files compact` command
+///
+/// Only static files that have been fully written are rewritten. The static file currently being
+/// appended to by the node is left untouched.
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    /// Static file segments to compact. If not provided, all segments are compacted.
+    #[arg(long, value_delimiter = ',')]
+    segments: Vec<StaticFileSegment>,
+
+    /// Compression algorithm to rewrite the static files with.
+    #[arg(long, value_enum, default_value_t = Compression::ZstdWithDictionary)]
+    compression: Compression,
+
+    /// Zstd compression level. Only used with `zstd` and `zstd-with-dictionary` compression.
+    ///
+    /// Higher levels produce smaller files at the cost of more CPU time when compacting. Reading
+    /// is not meaningfully affected.
+    #[arg(long, default_value_t = 3, value_parser = clap::value_parser!(i32).range(1..=22))]
+    zstd_level: i32,
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C> {
+    /// Execute `static-files compact` command
+    pub async fn execute<N: CliNodeTypes<ChainSpec = C::ChainSpec>>(self) -> eyre::Result<()> {
+        // Holding the environment with write access makes sure no node is running on the datadir
+        // while static files are being rewritten.
+        let env = self.env.init::<N>(AccessRights::RW)?;
+        let static_file_provider = env.provider_factory.static_file_provider();
+
+        let mut table = ComfyTable::new();
+        table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+        table.set_header(["Segment", "Block Range", "Rows", "Before", "After"]);
+
+        let (mut total_before, mut total_after) = (0, 0);
+
+        let static_files = static_file_provider.static_files()?;
+        for (segment, ranges) in static_files.into_iter().sorted_by_key(|(segment, _)| *segment) {
+            if !self.segments.is_empty() && !self.segments.contains(&segment) {
+                continue
+            }
+
+            let segment_dir = static_file_provider.segment_directory(segment);
+
+            for (block_range, header) in ranges {
+                let expected_block_range = header.expected_block_range();
+                if header.block_end() != Some(expected_block_range.end()) {
+                    info!(target: "reth::cli", %segment, %block_range, "Skipping static file that is still being written");
+                    continue
+                }
+
+                let path = segment_dir.join(segment.filename(&expected_block_range));
+                let jar = NippyJar::<SegmentHeader>::load(&path)?;
+                let rows = jar.rows();
+                let before = jar_size(&jar);
+
+                info!(target: "reth::cli", %segment, %block_range, rows, compression = self.compression.as_ref(), "Compacting static file");
+                // Compacted files are written next to the originals, so they can be moved into
+                // place even if the segment is stored on a different volume. An interrupted
+                // replacement is completed the next time the static files are opened.
+                self.compact_jar(jar, &replacement_path(&path))
+                    .wrap_err_with(|| format!("Failed to compact {}", path.display()))?;
+                replace_static_file(&path)?;
+
+                let after = jar_size(&NippyJar::<SegmentHeader>::load(&path)?);
+                info!(target: "reth::cli", %segment, %block_range, before = human_bytes(before as f64), after = human_bytes(after as f64), "Compacted static file");
+
+                let mut row = Row::new();
+                row.add_cell(Cell::new(segment))
+                    .add_cell(Cell::new(block_range))
+                    .add_cell(Cell::new(rows))
+                    .add_cell(Cell::new(human_bytes(before as f64)))
+                    .add_cell(Cell::new(human_bytes(after as f64)));
+                table.add_row(row);
+
+                total_before += before;
+                total_after += after;
+            }
+        }
+
+        for dir in static_file_provider.directories() {
+            let compact_dir = dir.join(REPLACEMENTS_DIR);
+            if compact_dir.exists() {
+                fs::remove_dir_all(&compact_dir)?;
+            }
+        }
+
+        let mut row = Row::new();
+        row.add_cell(Cell::new("Total"))
+            .add_cell(Cell::new(""))
+            .add_cell(Cell::new(""))
+            .add_cell(Cell::new(human_bytes(total_before as f64)))
+            .add_cell(Cell::new(human_bytes(total_after as f64)));
+        table.add_row(row);
+
+        println!("{table}");
+
+        Ok(())
+    }
+
+    /// Writes all rows of `jar` into a new jar at `path`, using the configured compression.
+    fn compact_jar(&self, jar: NippyJar<SegmentHeader>, path: &Path) -> eyre::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        // Remove leftovers from a previously interrupted run.
+        NippyJar::new_without_header(jar.columns(), path).delete()?;
+
+        let mut new_jar = NippyJar::new(jar.columns(), path, jar.user_header().clone());
+        new_jar = match self.compression {
+            Compression::Lz4 => new_jar.with_lz4(),
+            Compression::Zstd => new_jar.with_zstd(false, 0).with_zstd_level(self.zstd_level),
+            Compression::ZstdWithDictionary => {
+                new_jar.with_zstd(true, ZSTD_MAX_DICT_SIZE).with_zstd_level(self.zstd_level)
+            }
+            Compression::Uncompressed => new_jar,
+        };
+
+        if matches!(self.compression, Compression::ZstdWithDictionary) {
+            new_jar.prepare_compression(dictionary_samples(&jar)?)?;
+        }
+
+        let mut writer = NippyJarWriter::new(new_jar)?;
+        let mut cursor = NippyJarCursor::new(&jar)?;
+        while let Some(row) = cursor.next_row()? {
+            for column in row {
+                writer.append_column(Some(Ok(column)))?;
+            }
+        }
+        writer.commit()?;
+
+        Ok(())
+    }
+}
+
+impl<C: ChainSpecParser> Command<C> {
+    /// Returns the underlying chain being used to run this command
+    pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        Some(&self.env.chain)
+    }
+}
+
+/// Samples up to [`ZSTD_DICT_SAMPLE_ROWS`] rows evenly spread across `jar`, returning the values
+/// of each column.
+fn dictionary_samples(jar: &NippyJar<SegmentHeader>) -> eyre::Result<Vec<Vec<Vec<u8>>>> {
+    let step = jar.rows().div_ceil(ZSTD_DICT_SAMPLE_ROWS).max(1);
+    let mut columns = vec![Vec::new(); jar.columns()];
+
+    let mut cursor = NippyJarCursor::new(jar)?;
+    for row_number in (0..jar.rows()).step_by(step) {
+        if let Some(row) = cursor.row_by_number(row_number)? {
+            for (column, value) in columns.iter_mut().zip(row) {
+                column.push(value.to_vec());
+            }
+        }
+    }
+
+    Ok(columns)
+}
+
+/// Returns the total size on disk of all files belonging to `jar`.
+fn jar_size(jar: &NippyJar<SegmentHeader>) -> u64 {
+    [jar.data_path().to_path_buf(), jar.index_path(), jar.offsets_path(), jar.config_path()]
+        .into_iter()
+        .map(|path| fs::metadata(path).map(|metadata| metadata.len()).unwrap_or_default())
+        .sum()
+}
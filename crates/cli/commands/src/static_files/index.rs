@@ -0,0 +1,60 @@
+//! Command that writes the receipts index of existing receipts static files.
+
+use crate::common::{AccessRights, CliNodeTypes, EnvironmentArgs};
+use clap::Parser;
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_provider::StaticFileProviderFactory;
+use std::sync::Arc;
+use tracing::info;
+
+/// `reth static-files index` command
+///
+/// Writes the index of the offsets of every receipt within its block for the finished receipts
+/// static files, which lets single receipts be served without reading all receipts of their block.
+/// The node writes the index for new static files on its own, so this is only needed for static
+/// files that were produced before.
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    /// Rewrite the index of static files that are already indexed.
+    #[arg(long)]
+    force: bool,
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C> {
+    /// Execute `static-files index` command
+    pub async fn execute<N: CliNodeTypes<ChainSpec = C::ChainSpec>>(self) -> eyre::Result<()> {
+        // Holding the environment with write access makes sure no node is modifying the static
+        // files while they are being indexed.
+        let env = self.env.init::<N>(AccessRights::RW)?;
+        let provider = env.provider_factory.provider()?;
+        let static_file_provider = env.provider_factory.static_file_provider();
+
+        let mut indexed_files = 0;
+        for (block_range, indexed) in static_file_provider.finished_receipts_static_files()? {
+            if indexed && !self.force {
+                info!(target: "reth::cli", %block_range, "Skipping static file that is already indexed");
+                continue
+            }
+
+            info!(target: "reth::cli", %block_range, "Indexing receipts static file");
+            if static_file_provider.write_receipts_index(&provider, block_range)? {
+                indexed_files += 1;
+            }
+        }
+
+        info!(target: "reth::cli", indexed_files, "Indexed receipts static files");
+
+        Ok(())
+    }
+}
+
+impl<C: ChainSpecParser> Command<C> {
+    /// Returns the underlying chain being used to run this command
+    pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        Some(&self.env.chain)
+    }
+}
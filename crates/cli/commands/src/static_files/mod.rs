@@ -0,0 +1,46 @@
+//! Static file utilities
+
+use crate::common::CliNodeTypes;
+use clap::{Parser, Subcommand};
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use std::sync::Arc;
+
+pub mod compact;
+pub mod index;
+
+/// `reth static-files` command
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(subcommand)]
+    command: Subcommands<C>,
+}
+
+/// `reth static-files` subcommands
+#[derive(Subcommand, Debug)]
+pub enum Subcommands<C: ChainSpecParser> {
+    /// Rewrites existing static files with a different compression algorithm.
+    Compact(compact::Command<C>),
+    /// Writes the receipts index of finished receipts static files.
+    Index(index::Command<C>),
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C> {
+    /// Execute `static-files` command
+    pub async fn execute<N: CliNodeTypes<ChainSpec = C::ChainSpec>>(self) -> eyre::Result<()> {
+        match self.command {
+            Subcommands::Compact(command) => command.execute::<N>().await,
+            Subcommands::Index(command) => command.execute::<N>().await,
+        }
+    }
+}
+
+impl<C: ChainSpecParser> Command<C> {
+    /// Returns the underlying chain being used to run this command
+    pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        match &self.command {
+            Subcommands::Compact(command) => command.chain_spec(),
+            Subcommands::Index(command) => command.chain_spec(),
+        }
+    }
+}
@@ -0,0 +1,21 @@
+//! Randomized testing of the engine API tree handler.
+
+use eyre::Result;
+use reth_engine_tree::tree::fuzz::{EngineFuzzConfig, EngineFuzzer};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Sends `steps` random `newPayload` and `forkchoiceUpdated` messages to the engine API tree
+/// handler and checks its invariants after every step.
+///
+/// If no seed is given, a random one is used. The seed is printed so failures can be reproduced.
+pub fn fuzz_engine_tree(seed: Option<u64>, steps: usize) -> Result<()> {
+    let seed = seed.unwrap_or_else(|| {
+        SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_nanos() as u64
+    });
+    println!("Seed for engine test vectors: {seed}");
+
+    let report = EngineFuzzer::new(EngineFuzzConfig { seed, steps }).run()?;
+    println!("{report}");
+
+    Ok(())
+}
@@ -3,6 +3,7 @@
 use clap::{Parser, Subcommand};
 
 pub mod compact;
+pub mod engine;
 pub mod tables;
 
 /// Generate test-vectors for different data types.
@@ -36,6 +37,20 @@ pub enum Subcommands {
         #[arg(long)]
         read: bool,
     },
+    /// Sends random sequences of `newPayload` and `forkchoiceUpdated` messages to the engine API
+    /// tree handler and checks its invariants after every step.
+    ///
+    /// The sequences include valid chains, conflicting forks, invalid blocks and their
+    /// descendants, and payloads that arrive before their parents.
+    Engine {
+        /// The seed the messages are generated from. Defaults to a random seed.
+        #[arg(long)]
+        seed: Option<u64>,
+
+        /// The number of messages to send.
+        #[arg(long, default_value_t = 10_000)]
+        steps: usize,
+    },
 }
 
 impl Command {
@@ -52,6 +67,9 @@ impl Command {
                     compact::read_vectors()?;
                 }
             }
+            Subcommands::Engine { seed, steps } => {
+                engine::fuzz_engine_tree(seed, steps)?;
+            }
         }
         Ok(())
     }
@@ -4,6 +4,8 @@ use reth_prune_types::PruneModes;
 use reth_stages_types::ExecutionStageThresholds;
 use reth_static_file_types::{StaticFileMap, StaticFileSegment};
 use std::{
+    collections::BTreeMap,
+    net::IpAddr,
     path::{Path, PathBuf},
     time::Duration,
 };
@@ -32,6 +34,9 @@ pub struct Config {
     /// Configuration for static files.
     #[cfg_attr(feature = "serde", serde(default))]
     pub static_files: StaticFilesConfig,
+    /// Configuration for the RPC servers.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub rpc: RpcConfig,
 }
 
 impl Config {
@@ -128,6 +133,14 @@ pub struct StageConfig {
     pub index_account_history: IndexHistoryConfig,
     /// Index Storage History stage configuration.
     pub index_storage_history: IndexHistoryConfig,
+    /// Index Transaction Addresses stage configuration.
+    pub index_transaction_addresses: IndexTransactionAddressesConfig,
+    /// Contract creations index configuration.
+    pub index_contract_creations: IndexContractCreationsConfig,
+    /// Account destructions index configuration.
+    pub index_account_destructions: IndexAccountDestructionsConfig,
+    /// Verify Receipts stage configuration.
+    pub verify_receipts: VerifyReceiptsConfig,
     /// Common ETL related configuration.
     pub etl: EtlConfig,
 }
@@ -173,7 +186,7 @@ impl EraConfig {
 }
 
 /// Header stage configuration.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(default))]
 pub struct HeadersConfig {
@@ -192,6 +205,9 @@ pub struct HeadersConfig {
     pub downloader_request_limit: u64,
     /// The maximum number of headers to download before committing progress to the database.
     pub commit_threshold: u64,
+    /// Path to a directory where downloaded headers are persisted until they are written, so an
+    /// interrupted download can be resumed after a restart.
+    pub download_checkpoint_dir: Option<PathBuf>,
 }
 
 impl Default for HeadersConfig {
@@ -202,10 +218,19 @@ impl Default for HeadersConfig {
             downloader_max_concurrent_requests: 100,
             downloader_min_concurrent_requests: 5,
             downloader_max_buffered_responses: 100,
+            download_checkpoint_dir: None,
         }
     }
 }
 
+impl HeadersConfig {
+    /// Sets `download_checkpoint_dir` to a directory called "headers-download" inside `dir`.
+    pub fn with_datadir(mut self, dir: impl AsRef<Path>) -> Self {
+        self.download_checkpoint_dir = Some(dir.as_ref().join("headers-download"));
+        self
+    }
+}
+
 /// Body stage configuration.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -232,6 +257,11 @@ pub struct BodiesConfig {
     ///
     /// Default: 100
     pub downloader_max_concurrent_requests: usize,
+    /// How long to wait for a response before re-dispatching a request to another peer.
+    ///
+    /// Default: 15s
+    #[cfg_attr(feature = "serde", serde(with = "humantime_serde"))]
+    pub downloader_request_timeout: Duration,
 }
 
 impl Default for BodiesConfig {
@@ -242,6 +272,7 @@ impl Default for BodiesConfig {
             downloader_max_buffered_blocks_size_bytes: 2 * 1024 * 1024 * 1024, // ~2GB
             downloader_min_concurrent_requests: 5,
             downloader_max_concurrent_requests: 100,
+            downloader_request_timeout: Duration::from_secs(15),
         }
     }
 }
@@ -415,12 +446,21 @@ impl EtlConfig {
 }
 
 /// Static files configuration.
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(default))]
 pub struct StaticFilesConfig {
     /// Number of blocks per file for each segment.
     pub blocks_per_file: BlocksPerFileConfig,
+    /// Path to a directory where finished static files are stored content-addressed, so they can
+    /// be shared with other nodes of the same chain on this host.
+    ///
+    /// Must be on the same filesystem as the datadir, because the static files of the datadir are
+    /// hard links to the shared files.
+    pub shared_dir: Option<PathBuf>,
+    /// Directories of the segments that are not stored in the static files directory of the
+    /// datadir, e.g. to keep receipts on a cheaper volume than headers.
+    pub directories: SegmentDirectoriesConfig,
 }
 
 /// Configuration for the number of blocks per file for each segment.
@@ -442,6 +482,28 @@ pub struct BlocksPerFileConfig {
     pub storage_change_sets: Option<u64>,
 }
 
+/// Configuration for the directories of the static files of each segment.
+///
+/// Relative paths are resolved against the static files directory of the datadir. Segments
+/// without a directory are stored in the static files directory itself.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct SegmentDirectoriesConfig {
+    /// Directory of the headers segment.
+    pub headers: Option<PathBuf>,
+    /// Directory of the transactions segment.
+    pub transactions: Option<PathBuf>,
+    /// Directory of the receipts segment.
+    pub receipts: Option<PathBuf>,
+    /// Directory of the transaction senders segment.
+    pub transaction_senders: Option<PathBuf>,
+    /// Directory of the account changesets segment.
+    pub account_change_sets: Option<PathBuf>,
+    /// Directory of the storage changesets segment.
+    pub storage_change_sets: Option<PathBuf>,
+}
+
 impl StaticFilesConfig {
     /// Validates the static files configuration.
     ///
@@ -509,6 +571,88 @@ impl StaticFilesConfig {
         }
         map
     }
+
+    /// Converts the segment directories configuration into a [`StaticFileMap`], resolving
+    /// relative paths against the given static files directory.
+    pub fn as_segment_directories_map(&self, static_files_dir: &Path) -> StaticFileMap<PathBuf> {
+        let SegmentDirectoriesConfig {
+            headers,
+            transactions,
+            receipts,
+            transaction_senders,
+            account_change_sets,
+            storage_change_sets,
+        } = &self.directories;
+
+        let mut map = StaticFileMap::default();
+        for segment in StaticFileSegment::iter() {
+            let directory = match segment {
+                StaticFileSegment::Headers => headers,
+                StaticFileSegment::Transactions => transactions,
+                StaticFileSegment::Receipts => receipts,
+                StaticFileSegment::TransactionSenders => transaction_senders,
+                StaticFileSegment::AccountChangeSets => account_change_sets,
+                StaticFileSegment::StorageChangeSets => storage_change_sets,
+            };
+
+            if let Some(directory) = directory {
+                map.insert(segment, static_files_dir.join(directory));
+            }
+        }
+        map
+    }
+}
+
+/// Configuration for the RPC servers.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct RpcConfig {
+    /// Rate limits and method filters of the http and ws servers.
+    pub rate_limit: RpcRateLimitConfig,
+}
+
+/// Rate limits and method filters of the http and ws RPC servers.
+///
+/// Method names can end with `*` to match every method with the given prefix, e.g. `debug_*`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct RpcRateLimitConfig {
+    /// Methods that can be called. If empty, all methods that are not denied can be called.
+    pub allow_methods: Vec<String>,
+    /// Methods that are rejected, even if they are allowed.
+    pub deny_methods: Vec<String>,
+    /// Token buckets limiting the calls of methods, shared by all clients.
+    ///
+    /// If multiple entries match a method, the exact name takes precedence over the longest
+    /// matching prefix.
+    pub methods: BTreeMap<String, TokenBucketConfig>,
+    /// Token bucket limiting the calls of every client IP address.
+    ///
+    /// Clients are identified by the address of their connection, IPv6 clients by its /64
+    /// prefix.
+    pub per_ip: Option<TokenBucketConfig>,
+    /// Addresses of reverse proxies whose `X-Forwarded-For` and `X-Real-IP` headers identify the
+    /// client of a request.
+    pub trusted_proxies: Vec<IpAddr>,
+}
+
+impl RpcRateLimitConfig {
+    /// Returns `true` if no limit or filter is configured.
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+/// Configuration of a token bucket.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TokenBucketConfig {
+    /// Number of tokens added to the bucket per second.
+    pub per_second: u32,
+    /// Maximum number of tokens in the bucket, i.e. the largest allowed burst.
+    pub burst: u32,
 }
 
 /// History stage configuration.
@@ -526,6 +670,66 @@ impl Default for IndexHistoryConfig {
     }
 }
 
+/// Transaction addresses indexing stage configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct IndexTransactionAddressesConfig {
+    /// Whether to index the transactions sent from or to each address. Disabled by default.
+    pub enabled: bool,
+    /// The maximum number of blocks to process before committing progress to the database.
+    pub commit_threshold: u64,
+}
+
+impl Default for IndexTransactionAddressesConfig {
+    fn default() -> Self {
+        Self { enabled: false, commit_threshold: 100_000 }
+    }
+}
+
+/// Contract creations index configuration.
+///
+/// The index is recorded while the blocks are executed, by the execution stage and when the
+/// engine persists blocks.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct IndexContractCreationsConfig {
+    /// Whether to index the creator, creation transaction, code hash and salt of every created
+    /// contract. Disabled by default.
+    pub enabled: bool,
+}
+
+/// Account destructions index configuration.
+///
+/// The index is recorded while the blocks are executed, like the contract creations index.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct IndexAccountDestructionsConfig {
+    /// Whether to index the accounts removed by `SELFDESTRUCT` or state clearing, with the
+    /// removing transaction and the beneficiary. Disabled by default.
+    pub enabled: bool,
+}
+
+/// Receipts verification stage configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct VerifyReceiptsConfig {
+    /// Whether to verify the stored receipts against the receipts roots and logs blooms of the
+    /// headers. Disabled by default.
+    pub enabled: bool,
+    /// The maximum number of blocks to process before committing progress to the database.
+    pub commit_threshold: u64,
+}
+
+impl Default for VerifyReceiptsConfig {
+    fn default() -> Self {
+        Self { enabled: false, commit_threshold: 100_000 }
+    }
+}
+
 /// Pruning configuration.
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -615,12 +819,18 @@ where
 
 #[cfg(all(test, feature = "serde"))]
 mod tests {
-    use super::{Config, EXTENSION};
+    use super::{Config, TokenBucketConfig, EXTENSION};
     use crate::PruneConfig;
     use alloy_primitives::Address;
     use reth_network_peers::TrustedPeer;
     use reth_prune_types::{PruneMode, PruneModes, ReceiptsLogPruneConfig};
-    use std::{collections::BTreeMap, path::Path, str::FromStr, time::Duration};
+    use reth_static_file_types::StaticFileSegment;
+    use std::{
+        collections::BTreeMap,
+        path::{Path, PathBuf},
+        str::FromStr,
+        time::Duration,
+    };
 
     fn with_tempdir(filename: &str, proc: fn(&std::path::Path)) {
         let temp_dir = tempfile::tempdir().unwrap();
@@ -1133,6 +1343,55 @@ receipts = { distance = 16384 }
         assert_eq!(config1.segments.receipts_log_filter, original_filter);
     }
 
+    #[test]
+    fn test_rpc_rate_limit_config() {
+        let reth_toml = r#"
+[rpc.rate_limit]
+deny_methods = ["admin_*"]
+per_ip = { per_second = 10, burst = 20 }
+trusted_proxies = ["127.0.0.1", "::1"]
+
+[rpc.rate_limit.methods]
+"debug_*" = { per_second = 1, burst = 2 }
+eth_call = { per_second = 100, burst = 100 }
+"#;
+
+        let conf: Config = toml::from_str(reth_toml).unwrap();
+        let rate_limit = &conf.rpc.rate_limit;
+        assert!(rate_limit.allow_methods.is_empty());
+        assert_eq!(rate_limit.deny_methods, vec!["admin_*".to_string()]);
+        assert_eq!(rate_limit.per_ip, Some(TokenBucketConfig { per_second: 10, burst: 20 }));
+        assert_eq!(rate_limit.trusted_proxies.len(), 2);
+        assert_eq!(
+            rate_limit.methods.get("debug_*"),
+            Some(&TokenBucketConfig { per_second: 1, burst: 2 })
+        );
+        assert_eq!(rate_limit.methods.len(), 2);
+        assert!(Config::default().rpc.rate_limit.is_empty());
+    }
+
+    #[test]
+    fn test_static_files_segment_directories() {
+        let reth_toml = r#"
+[static_files.directories]
+receipts = "/mnt/hdd/receipts"
+transaction_senders = "senders"
+"#;
+
+        let conf: Config = toml::from_str(reth_toml).unwrap();
+        let directories =
+            conf.static_files.as_segment_directories_map(Path::new("/data/static_files"));
+        assert_eq!(
+            directories.get(StaticFileSegment::Receipts),
+            Some(&PathBuf::from("/mnt/hdd/receipts"))
+        );
+        assert_eq!(
+            directories.get(StaticFileSegment::TransactionSenders),
+            Some(&PathBuf::from("/data/static_files/senders"))
+        );
+        assert_eq!(directories.get(StaticFileSegment::Headers), None);
+    }
+
     #[test]
     fn test_conf_trust_nodes_only() {
         let trusted_nodes_only = r"#
@@ -9,4 +9,6 @@
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
 pub mod config;
-pub use config::{BodiesConfig, Config, PruneConfig};
+pub use config::{
+    BodiesConfig, Config, PruneConfig, RpcConfig, RpcRateLimitConfig, TokenBucketConfig,
+};
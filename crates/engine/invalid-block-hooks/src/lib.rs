@@ -1,5 +1,7 @@
 //! Invalid block hook implementations.
 
+mod webhook;
 mod witness;
 
+pub use webhook::{InvalidBlockNotification, InvalidBlockWebhookHook};
 pub use witness::InvalidBlockWitnessHook;
@@ -0,0 +1,111 @@
+use alloy_consensus::BlockHeader;
+use alloy_primitives::{BlockNumber, B256};
+use reth_engine_primitives::InvalidBlockHook;
+use reth_primitives_traits::{NodePrimitives, RecoveredBlock, SealedHeader};
+use reth_provider::BlockExecutionOutput;
+use reth_tracing::tracing::{debug, warn};
+use reth_trie::updates::TrieUpdates;
+use serde::Serialize;
+use std::time::Duration;
+use tokio::{runtime::Handle, sync::mpsc};
+
+/// The timeout of a single webhook request.
+const WEBHOOK_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The maximum number of notifications waiting to be sent. Notifications of further invalid
+/// blocks are dropped until the webhook caught up.
+const MAX_PENDING_NOTIFICATIONS: usize = 64;
+
+/// The payload posted to the webhook for every invalid block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InvalidBlockNotification {
+    /// The number of the invalid block.
+    pub number: BlockNumber,
+    /// The hash of the invalid block.
+    pub hash: B256,
+    /// The hash of the parent block.
+    pub parent_hash: B256,
+    /// The gas used reported by the block header.
+    pub gas_used: u64,
+    /// The gas used by the local execution of the block.
+    pub executed_gas_used: u64,
+    /// The number of receipts produced by the local execution of the block.
+    pub receipts: usize,
+    /// The state root reported by the block header.
+    pub state_root: B256,
+    /// The state root computed by the local execution of the block, if the state root was
+    /// computed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub computed_state_root: Option<B256>,
+}
+
+/// Hook that posts an [`InvalidBlockNotification`] to a remote webhook.
+///
+/// Notifications are posted one after another by a single background task, so the engine is never
+/// blocked by a slow or unreachable endpoint.
+#[derive(Debug)]
+pub struct InvalidBlockWebhookHook {
+    /// Sender of the notifications to the task that posts them.
+    notifications: mpsc::Sender<InvalidBlockNotification>,
+}
+
+impl InvalidBlockWebhookHook {
+    /// Creates a new webhook hook that posts notifications to the given URL from a task spawned on
+    /// the given runtime.
+    pub fn new(url: impl Into<String>, handle: &Handle) -> reqwest::Result<Self> {
+        let client = reqwest::Client::builder().timeout(WEBHOOK_REQUEST_TIMEOUT).build()?;
+        let (notifications, rx) = mpsc::channel(MAX_PENDING_NOTIFICATIONS);
+        handle.spawn(post_notifications(client, url.into(), rx));
+        Ok(Self { notifications })
+    }
+}
+
+impl<N: NodePrimitives> InvalidBlockHook<N> for InvalidBlockWebhookHook {
+    fn on_invalid_block(
+        &self,
+        parent_header: &SealedHeader<N::BlockHeader>,
+        block: &RecoveredBlock<N::Block>,
+        output: &BlockExecutionOutput<N::Receipt>,
+        trie_updates: Option<(&TrieUpdates, B256)>,
+    ) {
+        let notification = InvalidBlockNotification {
+            number: block.number(),
+            hash: block.hash(),
+            parent_hash: parent_header.hash(),
+            gas_used: block.gas_used(),
+            executed_gas_used: output.gas_used,
+            receipts: output.receipts.len(),
+            state_root: block.state_root(),
+            computed_state_root: trie_updates.map(|(_, root)| root),
+        };
+
+        if let Err(err) = self.notifications.try_send(notification) {
+            warn!(target: "engine::invalid_block_hooks::webhook", %err, number = block.number(), hash = ?block.hash(), "Dropped invalid block notification")
+        }
+    }
+}
+
+/// Posts the received notifications to the webhook until the hook is dropped.
+async fn post_notifications(
+    client: reqwest::Client,
+    url: String,
+    mut notifications: mpsc::Receiver<InvalidBlockNotification>,
+) {
+    while let Some(notification) = notifications.recv().await {
+        match client
+            .post(&url)
+            .json(&notification)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+        {
+            Ok(_) => {
+                debug!(target: "engine::invalid_block_hooks::webhook", number = notification.number, hash = ?notification.hash, "Sent invalid block notification")
+            }
+            Err(err) => {
+                warn!(target: "engine::invalid_block_hooks::webhook", %err, number = notification.number, hash = ?notification.hash, "Failed to send invalid block notification")
+            }
+        }
+    }
+}
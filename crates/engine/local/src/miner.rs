@@ -4,6 +4,7 @@ use alloy_primitives::{TxHash, B256};
 use alloy_rpc_types_engine::ForkchoiceState;
 use eyre::OptionExt;
 use futures_util::{stream::Fuse, StreamExt};
+use parking_lot::Mutex;
 use reth_engine_primitives::ConsensusEngineHandle;
 use reth_payload_builder::PayloadBuilderHandle;
 use reth_payload_primitives::{
@@ -16,10 +17,14 @@ use std::{
     collections::VecDeque,
     future::Future,
     pin::Pin,
+    sync::Arc,
     task::{Context, Poll},
     time::Duration,
 };
-use tokio::time::Interval;
+use tokio::{
+    sync::{mpsc, oneshot},
+    time::{Interval, Sleep},
+};
 use tokio_stream::wrappers::ReceiverStream;
 use tracing::error;
 
@@ -40,16 +45,30 @@ pub enum MiningMode<Pool: TransactionPool + Unpin> {
         max_transactions: Option<usize>,
         /// Counter for accumulated transactions (only used when `max_transactions` is set).
         accumulated: usize,
+        /// Maximum time to wait for `max_transactions` after the first transaction accumulated,
+        /// before the accumulated transactions are mined anyway.
+        max_wait: Option<Duration>,
+        /// Fires when the accumulated transactions waited for `max_wait`.
+        deadline: Option<Pin<Box<Sleep>>>,
     },
     /// In this mode a block is built at a fixed interval.
     Interval(Interval),
+    /// In this mode blocks are only built on request, e.g. with the `reth_mine` RPC method.
+    Manual,
 }
 
 impl<Pool: TransactionPool + Unpin> MiningMode<Pool> {
     /// Constructor for a [`MiningMode::Instant`]
     pub fn instant(pool: Pool, max_transactions: Option<usize>) -> Self {
         let rx = pool.pending_transactions_listener();
-        Self::Instant { pool, rx: ReceiverStream::new(rx).fuse(), max_transactions, accumulated: 0 }
+        Self::Instant {
+            pool,
+            rx: ReceiverStream::new(rx).fuse(),
+            max_transactions,
+            accumulated: 0,
+            max_wait: None,
+            deadline: None,
+        }
     }
 
     /// Constructor for a [`MiningMode::Interval`]
@@ -57,6 +76,35 @@ impl<Pool: TransactionPool + Unpin> MiningMode<Pool> {
         let start = tokio::time::Instant::now() + duration;
         Self::Interval(tokio::time::interval_at(start, duration))
     }
+
+    /// Constructor for a [`MiningMode::Manual`]
+    pub const fn manual() -> Self {
+        Self::Manual
+    }
+
+    /// Sets the maximum time a [`MiningMode::Instant`] with `max_transactions` waits for more
+    /// transactions after the first transaction accumulated.
+    ///
+    /// This has no effect on the other modes.
+    pub fn with_max_wait(mut self, max_wait: Duration) -> Self {
+        if let Self::Instant { max_wait: wait, .. } = &mut self {
+            *wait = Some(max_wait);
+        }
+        self
+    }
+
+    /// Resets the mode after a block was mined on request, so the next block is mined as if it
+    /// was mined by the mode itself.
+    fn reset(&mut self) {
+        match self {
+            Self::Instant { accumulated, deadline, .. } => {
+                *accumulated = 0;
+                *deadline = None;
+            }
+            Self::Interval(interval) => interval.reset(),
+            Self::Manual => {}
+        }
+    }
 }
 
 impl<Pool: TransactionPool + Unpin> Future for MiningMode<Pool> {
@@ -65,7 +113,7 @@ impl<Pool: TransactionPool + Unpin> Future for MiningMode<Pool> {
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         let this = self.get_mut();
         match this {
-            Self::Instant { pool, rx, max_transactions, accumulated } => {
+            Self::Instant { pool, rx, max_transactions, accumulated, max_wait, deadline } => {
                 // Poll for new transaction notifications
                 while let Poll::Ready(Some(_)) = rx.poll_next_unpin(cx) {
                     if pool.pending_and_queued_txn_count().0 == 0 {
@@ -76,13 +124,28 @@ impl<Pool: TransactionPool + Unpin> Future for MiningMode<Pool> {
                         // If we've reached the max transactions threshold, mine a block
                         if *accumulated >= *max_tx {
                             *accumulated = 0; // Reset counter for next block
+                            *deadline = None;
                             return Poll::Ready(());
                         }
+                        // Start waiting for more transactions, at most for `max_wait`
+                        if deadline.is_none() &&
+                            let Some(max_wait) = max_wait
+                        {
+                            *deadline = Some(Box::pin(tokio::time::sleep(*max_wait)));
+                        }
                     } else {
                         // If no max_transactions is set, mine immediately
                         return Poll::Ready(());
                     }
                 }
+                // Mine the accumulated transactions once they waited for `max_wait`
+                if let Some(sleep) = deadline &&
+                    sleep.as_mut().poll(cx).is_ready()
+                {
+                    *accumulated = 0;
+                    *deadline = None;
+                    return Poll::Ready(());
+                }
                 Poll::Pending
             }
             Self::Interval(interval) => {
@@ -91,10 +154,60 @@ impl<Pool: TransactionPool + Unpin> Future for MiningMode<Pool> {
                 }
                 Poll::Pending
             }
+            Self::Manual => Poll::Pending,
         }
     }
 }
 
+/// The maximum number of blocks that can be mined with a single [`LocalMinerHandle::mine`] request.
+///
+/// The miner doesn't advance the chain on its own while it serves a request, so requests are
+/// capped to keep it responsive.
+pub const MAX_MINE_BLOCKS: u64 = 1024;
+
+/// A request to the [`LocalMiner`] to mine blocks.
+#[derive(Debug)]
+struct MineRequest {
+    /// The number of blocks to mine.
+    blocks: u64,
+    /// Sender for the hashes of the mined blocks.
+    tx: oneshot::Sender<eyre::Result<Vec<B256>>>,
+}
+
+/// Handle to mine blocks on request with a [`LocalMiner`], e.g. by the `reth_mine` RPC method.
+///
+/// The handle can be created before the miner is spawned, requests fail until a miner is attached
+/// with [`LocalMiner::with_handle`].
+#[derive(Debug, Clone, Default)]
+pub struct LocalMinerHandle {
+    /// Channel to the attached miner.
+    to_miner: Arc<Mutex<Option<mpsc::UnboundedSender<MineRequest>>>>,
+}
+
+impl LocalMinerHandle {
+    /// Returns `true` if a miner is attached to the handle.
+    pub fn is_attached(&self) -> bool {
+        self.to_miner.lock().as_ref().is_some_and(|tx| !tx.is_closed())
+    }
+
+    /// Mines the given number of blocks and returns their hashes.
+    ///
+    /// The blocks are mined regardless of the [`MiningMode`] of the miner. Fails if more than
+    /// [`MAX_MINE_BLOCKS`] blocks are requested.
+    pub async fn mine(&self, blocks: u64) -> eyre::Result<Vec<B256>> {
+        eyre::ensure!(
+            blocks <= MAX_MINE_BLOCKS,
+            "Can't mine more than {MAX_MINE_BLOCKS} blocks at once, requested {blocks}"
+        );
+        let to_miner = self.to_miner.lock().clone().ok_or_eyre("No local miner is running")?;
+        let (tx, rx) = oneshot::channel();
+        to_miner
+            .send(MineRequest { blocks, tx })
+            .map_err(|_| eyre::eyre!("Local miner stopped"))?;
+        rx.await?
+    }
+}
+
 /// Local miner advancing the chain
 #[derive(Debug)]
 pub struct LocalMiner<T: PayloadTypes, B, Pool: TransactionPool + Unpin> {
@@ -110,6 +223,8 @@ pub struct LocalMiner<T: PayloadTypes, B, Pool: TransactionPool + Unpin> {
     last_header: SealedHeaderFor<<T::BuiltPayload as BuiltPayload>::Primitives>,
     /// Stores latest mined blocks.
     last_block_hashes: VecDeque<B256>,
+    /// Requests to mine blocks, if a [`LocalMinerHandle`] is attached.
+    mine_requests: Option<mpsc::UnboundedReceiver<MineRequest>>,
 }
 
 impl<T, B, Pool> LocalMiner<T, B, Pool>
@@ -139,9 +254,19 @@ where
             payload_builder,
             last_block_hashes: VecDeque::from([last_header.hash()]),
             last_header,
+            mine_requests: None,
         }
     }
 
+    /// Attaches the given handle to the miner, so it can request blocks regardless of the
+    /// [`MiningMode`].
+    pub fn with_handle(mut self, handle: &LocalMinerHandle) -> Self {
+        let (tx, rx) = mpsc::unbounded_channel();
+        *handle.to_miner.lock() = Some(tx);
+        self.mine_requests = Some(rx);
+        self
+    }
+
     /// Runs the [`LocalMiner`] in a loop, polling the miner and building payloads.
     pub async fn run(mut self) {
         let mut fcu_interval = tokio::time::interval(Duration::from_secs(1));
@@ -153,6 +278,15 @@ where
                         error!(target: "engine::local", "Error advancing the chain: {:?}", e);
                     }
                 }
+                // Mine blocks on request
+                Some(request) = next_mine_request(&mut self.mine_requests) => {
+                    let result = self.mine(request.blocks).await;
+                    if let Err(e) = &result {
+                        error!(target: "engine::local", "Error mining requested blocks: {:?}", e);
+                    }
+                    self.mode.reset();
+                    let _ = request.tx.send(result);
+                }
                 // send FCU once in a while
                 _ = fcu_interval.tick() => {
                     if let Err(e) = self.update_forkchoice_state().await {
@@ -163,6 +297,16 @@ where
         }
     }
 
+    /// Mines the given number of blocks and returns their hashes.
+    async fn mine(&mut self, blocks: u64) -> eyre::Result<Vec<B256>> {
+        let mut hashes = Vec::new();
+        for _ in 0..blocks {
+            self.advance().await?;
+            hashes.push(self.last_header.hash());
+        }
+        Ok(hashes)
+    }
+
     /// Returns current forkchoice state.
     fn forkchoice_state(&self) -> ForkchoiceState {
         ForkchoiceState {
@@ -235,3 +379,13 @@ where
         Ok(())
     }
 }
+
+/// Returns the next request to mine blocks, or never resolves if there's no attached handle.
+async fn next_mine_request(
+    requests: &mut Option<mpsc::UnboundedReceiver<MineRequest>>,
+) -> Option<MineRequest> {
+    match requests {
+        Some(requests) => requests.recv().await,
+        None => std::future::pending().await,
+    }
+}
@@ -1,6 +1,7 @@
 //! Engine tree configuration.
 
 use alloy_eips::merge::EPOCH_SLOTS;
+use core::time::Duration;
 
 /// Triggers persistence when the number of canonical blocks in memory exceeds this threshold.
 pub const DEFAULT_PERSISTENCE_THRESHOLD: u64 = 2;
@@ -8,6 +9,9 @@ pub const DEFAULT_PERSISTENCE_THRESHOLD: u64 = 2;
 /// How close to the canonical head we persist blocks.
 pub const DEFAULT_MEMORY_BLOCK_BUFFER_TARGET: u64 = 0;
 
+/// How far below the canonical head the execution outputs of in-memory blocks are compacted.
+pub const DEFAULT_EXECUTION_OUTPUT_COMPACTION_DEPTH: u64 = 64;
+
 /// Minimum number of workers we allow configuring explicitly.
 pub const MIN_WORKER_COUNT: usize = 32;
 
@@ -47,7 +51,9 @@ pub const DEFAULT_RESERVED_CPU_CORES: usize = 1;
 /// Default maximum concurrency for prewarm task.
 pub const DEFAULT_PREWARM_MAX_CONCURRENCY: usize = 16;
 
-const DEFAULT_BLOCK_BUFFER_LIMIT: u32 = EPOCH_SLOTS as u32 * 2;
+/// Default maximum number of blocks in the block buffer.
+pub const DEFAULT_BLOCK_BUFFER_LIMIT: u32 = EPOCH_SLOTS as u32 * 2;
+
 const DEFAULT_MAX_INVALID_HEADER_CACHE_LENGTH: u32 = 256;
 const DEFAULT_MAX_EXECUTE_BLOCK_BATCH_SIZE: usize = 4;
 const DEFAULT_CROSS_BLOCK_CACHE_SIZE: usize = default_cross_block_cache_size();
@@ -110,6 +116,8 @@ pub struct TreeConfig {
     disable_state_cache: bool,
     /// Whether to disable parallel prewarming.
     disable_prewarming: bool,
+    /// Whether to disable prefetching the state touched by the access lists of transactions.
+    disable_access_list_prefetch: bool,
     /// Whether to enable state provider metrics.
     state_provider_metrics: bool,
     /// Cross-block cache size in bytes.
@@ -152,6 +160,11 @@ pub struct TreeConfig {
     disable_proof_v2: bool,
     /// Whether to disable cache metrics recording (can be expensive with large cached state).
     disable_cache_metrics: bool,
+    /// Number of blocks below the canonical head after which the execution outputs of in-memory
+    /// blocks are compacted.
+    ///
+    /// This reduces the memory used by the in-memory blocks during long periods of non-finality.
+    execution_output_compaction_depth: u64,
 }
 
 impl Default for TreeConfig {
@@ -166,6 +179,7 @@ impl Default for TreeConfig {
             always_compare_trie_updates: false,
             disable_state_cache: false,
             disable_prewarming: false,
+            disable_access_list_prefetch: false,
             state_provider_metrics: false,
             cross_block_cache_size: DEFAULT_CROSS_BLOCK_CACHE_SIZE,
             has_enough_parallelism: has_enough_parallelism(),
@@ -181,6 +195,7 @@ impl Default for TreeConfig {
             account_worker_count: default_account_worker_count(),
             disable_proof_v2: false,
             disable_cache_metrics: false,
+            execution_output_compaction_depth: DEFAULT_EXECUTION_OUTPUT_COMPACTION_DEPTH,
         }
     }
 }
@@ -198,6 +213,7 @@ impl TreeConfig {
         always_compare_trie_updates: bool,
         disable_state_cache: bool,
         disable_prewarming: bool,
+        disable_access_list_prefetch: bool,
         state_provider_metrics: bool,
         cross_block_cache_size: usize,
         has_enough_parallelism: bool,
@@ -213,6 +229,7 @@ impl TreeConfig {
         account_worker_count: usize,
         disable_proof_v2: bool,
         disable_cache_metrics: bool,
+        execution_output_compaction_depth: u64,
     ) -> Self {
         Self {
             persistence_threshold,
@@ -224,6 +241,7 @@ impl TreeConfig {
             always_compare_trie_updates,
             disable_state_cache,
             disable_prewarming,
+            disable_access_list_prefetch,
             state_provider_metrics,
             cross_block_cache_size,
             has_enough_parallelism,
@@ -239,6 +257,7 @@ impl TreeConfig {
             account_worker_count,
             disable_proof_v2,
             disable_cache_metrics,
+            execution_output_compaction_depth,
         }
     }
 
@@ -315,6 +334,12 @@ impl TreeConfig {
         self.disable_prewarming
     }
 
+    /// Returns whether or not prefetching the state touched by transaction access lists is
+    /// disabled.
+    pub const fn disable_access_list_prefetch(&self) -> bool {
+        self.disable_access_list_prefetch
+    }
+
     /// Returns whether to always compare trie updates from the state root task to the trie updates
     /// from the regular state root calculation.
     pub const fn always_compare_trie_updates(&self) -> bool {
@@ -414,6 +439,15 @@ impl TreeConfig {
         self
     }
 
+    /// Setter for whether to disable prefetching the state touched by transaction access lists.
+    pub const fn without_access_list_prefetch(
+        mut self,
+        disable_access_list_prefetch: bool,
+    ) -> Self {
+        self.disable_access_list_prefetch = disable_access_list_prefetch;
+        self
+    }
+
     /// Setter for whether to always compare trie updates from the state root task to the trie
     /// updates from the regular state root calculation.
     pub const fn with_always_compare_trie_updates(
@@ -540,4 +574,72 @@ impl TreeConfig {
         self.disable_cache_metrics = disable_cache_metrics;
         self
     }
+
+    /// Return the execution output compaction depth.
+    pub const fn execution_output_compaction_depth(&self) -> u64 {
+        self.execution_output_compaction_depth
+    }
+
+    /// Setter for the execution output compaction depth.
+    pub const fn with_execution_output_compaction_depth(
+        mut self,
+        execution_output_compaction_depth: u64,
+    ) -> Self {
+        self.execution_output_compaction_depth = execution_output_compaction_depth;
+        self
+    }
+}
+
+/// Default duration without canonical head progress after which the head is considered stalled.
+pub const DEFAULT_HEAD_WATCHDOG_STALL_THRESHOLD: Duration = Duration::from_secs(180);
+
+/// Default duration between escalating recovery actions of a stalled head.
+pub const DEFAULT_HEAD_WATCHDOG_ESCALATION_INTERVAL: Duration = Duration::from_secs(120);
+
+/// Default number of blocks peers must be ahead of the canonical head for it to be stalled.
+pub const DEFAULT_HEAD_WATCHDOG_MIN_PEER_LEAD: u64 = 2;
+
+/// The configuration of the watchdog that recovers a stalled canonical head.
+///
+/// The canonical head is stalled if it didn't advance for the
+/// [`stall_threshold`](Self::stall_threshold) while connected peers advertise blocks that are at
+/// least [`min_peer_lead`](Self::min_peer_lead) blocks ahead of it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeadWatchdogConfig {
+    /// Duration without head progress after which the head is considered stalled.
+    pub stall_threshold: Duration,
+    /// Duration between escalating recovery actions while the head remains stalled.
+    pub escalation_interval: Duration,
+    /// Number of blocks the best peer must be ahead of the canonical head.
+    pub min_peer_lead: u64,
+}
+
+impl Default for HeadWatchdogConfig {
+    fn default() -> Self {
+        Self {
+            stall_threshold: DEFAULT_HEAD_WATCHDOG_STALL_THRESHOLD,
+            escalation_interval: DEFAULT_HEAD_WATCHDOG_ESCALATION_INTERVAL,
+            min_peer_lead: DEFAULT_HEAD_WATCHDOG_MIN_PEER_LEAD,
+        }
+    }
+}
+
+impl HeadWatchdogConfig {
+    /// Setter for the duration without head progress after which the head is stalled.
+    pub const fn with_stall_threshold(mut self, stall_threshold: Duration) -> Self {
+        self.stall_threshold = stall_threshold;
+        self
+    }
+
+    /// Setter for the duration between escalating recovery actions.
+    pub const fn with_escalation_interval(mut self, escalation_interval: Duration) -> Self {
+        self.escalation_interval = escalation_interval;
+        self
+    }
+
+    /// Setter for the number of blocks the best peer must be ahead of the canonical head.
+    pub const fn with_min_peer_lead(mut self, min_peer_lead: u64) -> Self {
+        self.min_peer_lead = min_peer_lead;
+        self
+    }
 }
@@ -7,7 +7,7 @@ use reth_engine_tree::{
     backfill::PipelineSync,
     download::BasicBlockDownloader,
     engine::{EngineApiKind, EngineApiRequest, EngineApiRequestHandler, EngineHandler},
-    persistence::PersistenceHandle,
+    persistence::{PersistenceBlockIndexer, PersistenceHandle},
     tree::{EngineApiTreeHandler, EngineValidator, TreeConfig},
 };
 pub use reth_engine_tree::{
@@ -80,6 +80,7 @@ where
         provider: ProviderFactory<N>,
         blockchain_db: BlockchainProvider<N>,
         pruner: PrunerWithFactory<ProviderFactory<N>>,
+        block_indexer: Option<PersistenceBlockIndexer<N>>,
         payload_builder: PayloadBuilderHandle<N::Payload>,
         payload_validator: V,
         tree_config: TreeConfig,
@@ -96,8 +97,12 @@ where
 
         let downloader = BasicBlockDownloader::new(client, consensus.clone());
 
-        let persistence_handle =
-            PersistenceHandle::<EthPrimitives>::spawn_service(provider, pruner, sync_metrics_tx);
+        let persistence_handle = PersistenceHandle::<EthPrimitives>::spawn_service_with_indexer(
+            provider,
+            pruner,
+            sync_metrics_tx,
+            block_indexer,
+        );
 
         let canonical_in_memory_state = blockchain_db.canonical_in_memory_state();
 
@@ -150,7 +155,7 @@ mod tests {
     use reth_ethereum_consensus::EthBeaconConsensus;
     use reth_ethereum_engine_primitives::EthEngineTypes;
     use reth_evm_ethereum::EthEvmConfig;
-    use reth_exex_types::FinishedExExHeight;
+    use reth_exex_types::FinishedExExHeightReceiver;
     use reth_network_p2p::test_utils::TestFullBlockClient;
     use reth_node_ethereum::EthereumEngineValidator;
     use reth_primitives_traits::SealedHeader;
@@ -161,7 +166,7 @@ mod tests {
     use reth_tasks::TokioTaskExecutor;
     use reth_trie_db::ChangesetCache;
     use std::sync::Arc;
-    use tokio::sync::{mpsc::unbounded_channel, watch};
+    use tokio::sync::mpsc::unbounded_channel;
     use tokio_stream::wrappers::UnboundedReceiverStream;
 
     #[test]
@@ -188,8 +193,14 @@ mod tests {
             BlockchainProvider::with_latest(provider_factory.clone(), SealedHeader::default())
                 .unwrap();
         let engine_payload_validator = EthereumEngineValidator::new(chain_spec.clone());
-        let (_tx, rx) = watch::channel(FinishedExExHeight::NoExExs);
-        let pruner = Pruner::new_with_factory(provider_factory.clone(), vec![], 0, 0, None, rx);
+        let pruner = Pruner::new_with_factory(
+            provider_factory.clone(),
+            vec![],
+            0,
+            0,
+            None,
+            FinishedExExHeightReceiver::no_exexs(),
+        );
         let evm_config = EthEvmConfig::new(chain_spec.clone());
 
         let changeset_cache = ChangesetCache::new();
@@ -216,6 +227,7 @@ mod tests {
             provider_factory,
             blockchain_db,
             pruner,
+            None,
             PayloadBuilderHandle::new(tx),
             engine_validator,
             TreeConfig::default(),
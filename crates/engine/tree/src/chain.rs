@@ -1,4 +1,7 @@
-use crate::backfill::{BackfillAction, BackfillEvent, BackfillSync};
+use crate::{
+    backfill::{BackfillAction, BackfillEvent, BackfillSync},
+    watchdog::RecoveryAction,
+};
 use futures::Stream;
 use reth_stages_api::{ControlFlow, PipelineTarget};
 use std::{
@@ -68,6 +71,13 @@ where
         self.backfill_sync.on_action(BackfillAction::Start(target.into()));
     }
 
+    /// Asks the handler to take the given action to recover a stalled canonical head.
+    ///
+    /// See also [`HeadWatchdog`](crate::watchdog::HeadWatchdog).
+    pub fn recover_head(&mut self, action: RecoveryAction) {
+        self.handler.on_event(FromOrchestrator::RecoverHead(action));
+    }
+
     /// Internal function used to advance the chain.
     ///
     /// Polls the `ChainOrchestrator` for the next event.
@@ -225,6 +235,8 @@ pub enum FromOrchestrator {
     BackfillSyncFinished(ControlFlow),
     /// Invoked when backfill sync started
     BackfillSyncStarted,
+    /// Attempt to recover a stalled canonical head with the given action.
+    RecoverHead(RecoveryAction),
     /// Gracefully terminate the engine service.
     ///
     /// When this variant is received, the engine will persist all remaining in-memory blocks
@@ -4,6 +4,7 @@ use crate::{
     backfill::BackfillAction,
     chain::{ChainHandler, FromOrchestrator, HandlerEvent},
     download::{BlockDownloader, DownloadAction, DownloadOutcome},
+    watchdog::RecoveryAction,
 };
 use alloy_primitives::B256;
 use crossbeam_channel::Sender;
@@ -75,6 +76,11 @@ where
     type Event = T::Event;
 
     fn on_event(&mut self, event: FromOrchestrator) {
+        if matches!(event, FromOrchestrator::RecoverHead(RecoveryAction::RestartEngine)) {
+            // drop in-flight downloads, the handler requests the missing blocks again
+            self.downloader.on_action(DownloadAction::Clear);
+        }
+
         // delegate event to the handler
         self.handler.on_event(event.into());
     }
@@ -106,6 +106,8 @@ pub mod metrics;
 pub mod persistence;
 /// Support for interacting with the blockchain tree.
 pub mod tree;
+/// Watchdog that recovers a stalled canonical head.
+pub mod watchdog;
 
 /// Test utilities.
 #[cfg(any(test, feature = "test-utils"))]
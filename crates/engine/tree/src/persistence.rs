@@ -6,17 +6,25 @@ use reth_errors::ProviderError;
 use reth_ethereum_primitives::EthPrimitives;
 use reth_primitives_traits::NodePrimitives;
 use reth_provider::{
-    providers::ProviderNodeTypes, BlockExecutionWriter, BlockHashReader, ChainStateBlockWriter,
-    DBProvider, DatabaseProviderFactory, ProviderFactory, SaveBlocksMode,
+    providers::{BlockWriteBatch, ProviderNodeTypes},
+    BlockExecutionWriter, BlockHashReader, ChainStateBlockWriter, DBProvider,
+    DatabaseProviderFactory, ProviderFactory, SaveBlocksMode,
 };
 use reth_prune::{PrunerError, PrunerOutput, PrunerWithFactory};
-use reth_stages_api::{MetricEvent, MetricEventsSender};
+use reth_stages_api::{BoxedBlockIndexer, MetricEvent, MetricEventsSender};
 use std::{
     sync::mpsc::{Receiver, SendError, Sender},
     time::Instant,
 };
 use thiserror::Error;
-use tracing::{debug, error};
+use tracing::{debug, error, warn};
+
+/// The optional index that is recorded when blocks are persisted, see
+/// [`BlockIndexer`](reth_stages_api::BlockIndexer).
+pub type PersistenceBlockIndexer<N> = BoxedBlockIndexer<
+    <ProviderFactory<N> as DatabaseProviderFactory>::ProviderRW,
+    <<N as ProviderNodeTypes>::Primitives as NodePrimitives>::Block,
+>;
 
 /// Writes parts of reth's in memory tree state to the database and static files.
 ///
@@ -40,6 +48,10 @@ where
     metrics: PersistenceMetrics,
     /// Sender for sync metrics - we only submit sync metrics for persisted blocks
     sync_metrics_tx: MetricEventsSender,
+    /// Reusable buffers for the database writes of persisted blocks
+    write_batch: BlockWriteBatch<<N::Primitives as NodePrimitives>::Receipt>,
+    /// Records the optional index of the persisted blocks, if enabled
+    block_indexer: Option<PersistenceBlockIndexer<N>>,
 }
 
 impl<N> PersistenceService<N>
@@ -53,7 +65,21 @@ where
         pruner: PrunerWithFactory<ProviderFactory<N>>,
         sync_metrics_tx: MetricEventsSender,
     ) -> Self {
-        Self { provider, incoming, pruner, metrics: PersistenceMetrics::default(), sync_metrics_tx }
+        Self {
+            provider,
+            incoming,
+            pruner,
+            metrics: PersistenceMetrics::default(),
+            sync_metrics_tx,
+            write_batch: BlockWriteBatch::default(),
+            block_indexer: None,
+        }
+    }
+
+    /// Set the indexer recording the optional index of the persisted blocks.
+    pub fn with_block_indexer(mut self, block_indexer: Option<PersistenceBlockIndexer<N>>) -> Self {
+        self.block_indexer = block_indexer;
+        self
     }
 
     /// Prunes block data before the given block number according to the configured prune
@@ -138,7 +164,7 @@ where
     }
 
     fn on_save_blocks(
-        &self,
+        &mut self,
         blocks: Vec<ExecutedBlock<N::Primitives>>,
     ) -> Result<Option<BlockNumHash>, PersistenceError> {
         let first_block = blocks.first().map(|b| b.recovered_block.num_hash());
@@ -148,10 +174,25 @@ where
 
         let start_time = Instant::now();
 
-        if last_block.is_some() {
+        if let Some(last_block) = last_block {
             let provider_rw = self.provider.database_provider_rw()?;
 
-            provider_rw.save_blocks(blocks, SaveBlocksMode::Full)?;
+            // The blocks are indexed on top of the state before them, so before they're written.
+            // The index is optional, so a failure only stops indexing instead of persisting.
+            if let Some(indexer) = &self.block_indexer {
+                let recovered_blocks =
+                    blocks.iter().map(|block| &*block.recovered_block).collect::<Vec<_>>();
+                if let Err(err) = indexer.index_blocks(&provider_rw, &recovered_blocks) {
+                    warn!(target: "engine::persistence", %err, "Failed to index persisted blocks");
+                }
+            }
+
+            provider_rw.save_blocks_with_batch(
+                blocks,
+                SaveBlocksMode::Full,
+                &mut self.write_batch,
+            )?;
+            provider_rw.move_cold_receipts_to_static_files(last_block.number)?;
             provider_rw.commit()?;
         }
 
@@ -218,6 +259,20 @@ impl<T: NodePrimitives> PersistenceHandle<T> {
         pruner: PrunerWithFactory<ProviderFactory<N>>,
         sync_metrics_tx: MetricEventsSender,
     ) -> PersistenceHandle<N::Primitives>
+    where
+        N: ProviderNodeTypes,
+    {
+        Self::spawn_service_with_indexer(provider_factory, pruner, sync_metrics_tx, None)
+    }
+
+    /// Create a new [`PersistenceHandle`], and spawn the persistence service recording the
+    /// optional index of the persisted blocks with the given indexer.
+    pub fn spawn_service_with_indexer<N>(
+        provider_factory: ProviderFactory<N>,
+        pruner: PrunerWithFactory<ProviderFactory<N>>,
+        sync_metrics_tx: MetricEventsSender,
+        block_indexer: Option<PersistenceBlockIndexer<N>>,
+    ) -> PersistenceHandle<N::Primitives>
     where
         N: ProviderNodeTypes,
     {
@@ -229,7 +284,8 @@ impl<T: NodePrimitives> PersistenceHandle<T> {
 
         // spawn the persistence service
         let db_service =
-            PersistenceService::new(provider_factory, db_service_rx, pruner, sync_metrics_tx);
+            PersistenceService::new(provider_factory, db_service_rx, pruner, sync_metrics_tx)
+                .with_block_indexer(block_indexer);
         std::thread::Builder::new()
             .name("Persistence Service".to_string())
             .spawn(|| {
@@ -302,7 +358,7 @@ mod tests {
     use super::*;
     use alloy_primitives::B256;
     use reth_chain_state::test_utils::TestBlockBuilder;
-    use reth_exex_types::FinishedExExHeight;
+    use reth_exex_types::FinishedExExHeightReceiver;
     use reth_provider::test_utils::create_test_provider_factory;
     use reth_prune::Pruner;
     use tokio::sync::mpsc::unbounded_channel;
@@ -310,11 +366,14 @@ mod tests {
     fn default_persistence_handle() -> PersistenceHandle<EthPrimitives> {
         let provider = create_test_provider_factory();
 
-        let (_finished_exex_height_tx, finished_exex_height_rx) =
-            tokio::sync::watch::channel(FinishedExExHeight::NoExExs);
-
-        let pruner =
-            Pruner::new_with_factory(provider.clone(), vec![], 5, 0, None, finished_exex_height_rx);
+        let pruner = Pruner::new_with_factory(
+            provider.clone(),
+            vec![],
+            5,
+            0,
+            None,
+            FinishedExExHeightReceiver::no_exexs(),
+        );
 
         let (sync_metrics_tx, _sync_metrics_rx) = unbounded_channel();
         PersistenceHandle::<EthPrimitives>::spawn_service(provider, pruner, sync_metrics_tx)
@@ -1,8 +1,8 @@
 use crate::tree::metrics::BlockBufferMetrics;
 use alloy_consensus::BlockHeader;
 use alloy_primitives::{BlockHash, BlockNumber};
-use reth_primitives_traits::{Block, SealedBlock};
-use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use reth_primitives_traits::{Block, InMemorySize, SealedBlock};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 /// Contains the tree of pending blocks that cannot be executed due to missing parent.
 /// It allows to store unconnected blocks for potential future inclusion.
@@ -14,7 +14,8 @@ use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 /// * [`BlockBuffer::remove_old_blocks`] to remove old blocks that precede the finalized number.
 ///
 /// Note: Buffer is limited by number of blocks that it can contain and eviction of the block
-/// is done in FIFO order (oldest inserted block is evicted first).
+/// is done by block number (the block with the lowest number is evicted first). The head of the
+/// latest forkchoice update is never evicted, see [`BlockBuffer::set_fcu_head`].
 #[derive(Debug)]
 pub struct BlockBuffer<B: Block> {
     /// All blocks in the buffer stored by their block hash.
@@ -24,13 +25,15 @@ pub struct BlockBuffer<B: Block> {
     /// Allows connecting buffered blocks by parent.
     pub(crate) parent_to_child: HashMap<BlockHash, HashSet<BlockHash>>,
     /// `BTreeMap` tracking the earliest blocks by block number.
-    /// Used for removal of old blocks that precede finalization.
+    /// Used for removal of old blocks that precede finalization, and for eviction of the block
+    /// with the lowest number when the buffer reaches its capacity limit.
     pub(crate) earliest_blocks: BTreeMap<BlockNumber, HashSet<BlockHash>>,
-    /// FIFO queue tracking block insertion order for eviction.
-    /// When the buffer reaches its capacity limit, the oldest block is evicted first.
-    pub(crate) block_queue: VecDeque<BlockHash>,
+    /// Head block hash of the latest forkchoice update, which is not evicted.
+    pub(crate) fcu_head: Option<BlockHash>,
     /// Maximum number of blocks that can be stored in the buffer
     pub(crate) max_blocks: usize,
+    /// Total in-memory size of the buffered blocks in bytes.
+    pub(crate) size: usize,
     /// Various metrics for the block buffer.
     pub(crate) metrics: BlockBufferMetrics,
 }
@@ -42,8 +45,9 @@ impl<B: Block> BlockBuffer<B> {
             blocks: Default::default(),
             parent_to_child: Default::default(),
             earliest_blocks: Default::default(),
-            block_queue: VecDeque::default(),
+            fcu_head: None,
             max_blocks: limit as usize,
+            size: 0,
             metrics: Default::default(),
         }
     }
@@ -62,28 +66,47 @@ impl<B: Block> BlockBuffer<B> {
         Some(current_block)
     }
 
+    /// Sets the head block hash of the latest forkchoice update.
+    ///
+    /// The consensus layer doesn't send the head again, so it's kept in the buffer until its
+    /// missing ancestors are downloaded, even if it has the lowest number of the buffered blocks.
+    pub fn set_fcu_head(&mut self, hash: BlockHash) {
+        self.fcu_head = Some(hash);
+    }
+
     /// Insert a correct block inside the buffer.
     pub fn insert_block(&mut self, block: SealedBlock<B>) {
         let hash = block.hash();
 
-        match self.blocks.entry(hash) {
-            std::collections::hash_map::Entry::Occupied(_) => return,
-            std::collections::hash_map::Entry::Vacant(entry) => {
-                self.parent_to_child.entry(block.parent_hash()).or_default().insert(hash);
-                self.earliest_blocks.entry(block.number()).or_default().insert(hash);
-                entry.insert(block);
-            }
-        };
+        if self.blocks.contains_key(&hash) {
+            return
+        }
 
-        // Add block to FIFO queue and handle eviction if needed
-        if self.block_queue.len() >= self.max_blocks {
-            // Evict oldest block if limit is hit
-            if let Some(evicted_hash) = self.block_queue.pop_front() {
+        // Evict the block with the lowest number if limit is hit, skipping the forkchoice head
+        if self.blocks.len() >= self.max_blocks {
+            let lowest = self
+                .earliest_blocks
+                .values()
+                .flatten()
+                .find(|hash| Some(*hash) != self.fcu_head.as_ref())
+                .copied();
+            if let Some(evicted_hash) = lowest {
                 self.remove_block(&evicted_hash);
+                self.metrics.evicted_blocks.increment(1);
             }
         }
-        self.block_queue.push_back(hash);
+
+        self.parent_to_child.entry(block.parent_hash()).or_default().insert(hash);
+        self.earliest_blocks.entry(block.number()).or_default().insert(hash);
+        self.size += block.size();
+        self.blocks.insert(hash, block);
+        self.update_metrics();
+    }
+
+    /// Updates the gauges of the buffer.
+    fn update_metrics(&self) {
         self.metrics.blocks.set(self.blocks.len() as f64);
+        self.metrics.size_bytes.set(self.size as f64);
     }
 
     /// Removes the given block from the buffer and also all the children of the block.
@@ -98,10 +121,20 @@ impl<B: Block> BlockBuffer<B> {
             .into_iter()
             .chain(self.remove_children(vec![*parent_hash]))
             .collect();
-        self.metrics.blocks.set(self.blocks.len() as f64);
+        self.update_metrics();
         removed
     }
 
+    /// Discards all blocks of the buffer.
+    pub fn clear(&mut self) {
+        self.blocks.clear();
+        self.parent_to_child.clear();
+        self.earliest_blocks.clear();
+        self.fcu_head = None;
+        self.size = 0;
+        self.update_metrics();
+    }
+
     /// Discard all blocks that precede block number from the buffer.
     pub fn remove_old_blocks(&mut self, block_number: BlockNumber) {
         let mut block_hashes_to_remove = Vec::new();
@@ -122,7 +155,7 @@ impl<B: Block> BlockBuffer<B> {
         }
 
         self.remove_children(block_hashes_to_remove);
-        self.metrics.blocks.set(self.blocks.len() as f64);
+        self.update_metrics();
     }
 
     /// Remove block entry
@@ -155,7 +188,7 @@ impl<B: Block> BlockBuffer<B> {
         let block = self.blocks.remove(hash)?;
         self.remove_from_earliest_blocks(block.number(), hash);
         self.remove_from_parent(block.parent_hash(), hash);
-        self.block_queue.retain(|h| h != hash);
+        self.size -= block.size();
         Some(block)
     }
 
@@ -201,7 +234,7 @@ mod tests {
     /// Assert that all buffer collections have the same data length.
     fn assert_buffer_lengths<B: Block>(buffer: &BlockBuffer<B>, expected: usize) {
         assert_eq!(buffer.blocks.len(), expected);
-        assert_eq!(buffer.block_queue.len(), expected);
+        assert_eq!(buffer.size, buffer.blocks.values().map(|block| block.size()).sum::<usize>());
         assert_eq!(
             buffer.parent_to_child.iter().fold(0, |acc, (_, hashes)| acc + hashes.len()),
             expected
@@ -487,7 +520,7 @@ mod tests {
         buffer.insert_block(block3);
         buffer.insert_block(block4);
 
-        // block3 gets evicted
+        // block1 gets evicted
         assert_block_removal(&buffer, &block1);
 
         assert_buffer_lengths(&buffer, 3);
@@ -504,7 +537,7 @@ mod tests {
         let unrelated_parent = rng.random();
         let unrelated_block = create_block(&mut rng, 12, unrelated_parent);
 
-        // Capacity 2 so third insert evicts the oldest (block1)
+        // Capacity 2 so third insert evicts the lowest (block1)
         let mut buffer = BlockBuffer::new(2);
 
         buffer.insert_block(block1.clone());
@@ -545,4 +578,60 @@ mod tests {
         // And lowest ancestor for block2 becomes itself after its parent is evicted
         assert_eq!(buffer.lowest_ancestor(&block2.hash()), Some(&block2));
     }
+
+    #[test]
+    fn fcu_head_not_evicted() {
+        let mut rng = generators::rng();
+
+        let parent1 = rng.random();
+        let block1 = create_block(&mut rng, 10, parent1);
+        let parent2 = rng.random();
+        let block2 = create_block(&mut rng, 11, parent2);
+        let parent3 = rng.random();
+        let block3 = create_block(&mut rng, 12, parent3);
+
+        let mut buffer = BlockBuffer::new(2);
+
+        buffer.insert_block(block1.clone());
+        buffer.set_fcu_head(block1.hash());
+        buffer.insert_block(block2.clone());
+        buffer.insert_block(block3.clone());
+
+        // block1 is the lowest block, but it's the forkchoice head, so block2 gets evicted
+        assert_block_removal(&buffer, &block2);
+        assert_eq!(buffer.block(&block1.hash()), Some(&block1));
+        assert_eq!(buffer.block(&block3.hash()), Some(&block3));
+
+        assert_buffer_lengths(&buffer, 2);
+
+        // the forkchoice head is reset when the buffer is cleared
+        buffer.clear();
+        assert_eq!(buffer.fcu_head, None);
+        assert_buffer_lengths(&buffer, 0);
+    }
+
+    #[test]
+    fn lowest_block_evicted() {
+        let mut rng = generators::rng();
+
+        let parent1 = rng.random();
+        let block1 = create_block(&mut rng, 20, parent1);
+        let parent2 = rng.random();
+        let block2 = create_block(&mut rng, 10, parent2);
+        let parent3 = rng.random();
+        let block3 = create_block(&mut rng, 15, parent3);
+
+        let mut buffer = BlockBuffer::new(2);
+
+        buffer.insert_block(block1.clone());
+        buffer.insert_block(block2.clone());
+        buffer.insert_block(block3.clone());
+
+        // block1 was inserted first, but block2 has the lowest number
+        assert_block_removal(&buffer, &block2);
+        assert_eq!(buffer.block(&block1.hash()), Some(&block1));
+        assert_eq!(buffer.block(&block3.hash()), Some(&block3));
+
+        assert_buffer_lengths(&buffer, 2);
+    }
 }
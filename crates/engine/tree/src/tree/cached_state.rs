@@ -153,6 +153,9 @@ pub(crate) struct CachedStateMetrics {
     /// Code cache collisions (hash collisions causing eviction)
     code_cache_collisions: Gauge,
 
+    /// Code cache hit rate of all lookups, including prewarming, since the last update
+    code_cache_hit_rate: Gauge,
+
     /// Storage cache hits
     storage_cache_hits: Gauge,
 
@@ -168,6 +171,9 @@ pub(crate) struct CachedStateMetrics {
     /// Storage cache collisions (hash collisions causing eviction)
     storage_cache_collisions: Gauge,
 
+    /// Storage cache hit rate of all lookups, including prewarming, since the last update
+    storage_cache_hit_rate: Gauge,
+
     /// Account cache hits
     account_cache_hits: Gauge,
 
@@ -182,6 +188,9 @@ pub(crate) struct CachedStateMetrics {
 
     /// Account cache collisions (hash collisions causing eviction)
     account_cache_collisions: Gauge,
+
+    /// Account cache hit rate of all lookups, including prewarming, since the last update
+    account_cache_hit_rate: Gauge,
 }
 
 impl CachedStateMetrics {
@@ -217,12 +226,13 @@ impl CachedStateMetrics {
     }
 }
 
-/// A stats handler for fixed-cache that tracks collisions and size.
+/// A stats handler for fixed-cache that tracks collisions, size and hit rate.
 ///
-/// Note: Hits and misses are tracked directly by the [`CachedStateProvider`] via
-/// [`CachedStateMetrics`], not here. The stats handler is used for:
+/// Note: Hits and misses of block execution are tracked directly by the [`CachedStateProvider`]
+/// via [`CachedStateMetrics`]. The stats handler is used for:
 /// - Collision detection (hash collisions causing eviction of a different key)
 /// - Size tracking
+/// - Hit rate tracking of all cache lookups, including the ones of prewarming
 ///
 /// ## Size Tracking
 ///
@@ -234,6 +244,8 @@ impl CachedStateMetrics {
 #[derive(Debug)]
 pub(crate) struct CacheStatsHandler {
     collisions: AtomicU64,
+    hits: AtomicU64,
+    misses: AtomicU64,
     size: AtomicUsize,
     capacity: usize,
 }
@@ -241,7 +253,13 @@ pub(crate) struct CacheStatsHandler {
 impl CacheStatsHandler {
     /// Creates a new stats handler with all counters initialized to zero.
     pub(crate) const fn new(capacity: usize) -> Self {
-        Self { collisions: AtomicU64::new(0), size: AtomicUsize::new(0), capacity }
+        Self {
+            collisions: AtomicU64::new(0),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            size: AtomicUsize::new(0),
+            capacity,
+        }
     }
 
     /// Returns the number of cache collisions.
@@ -249,6 +267,13 @@ impl CacheStatsHandler {
         self.collisions.load(Ordering::Relaxed)
     }
 
+    /// Returns the share of lookups that were hits, or [`None`] if there were no lookups.
+    pub(crate) fn hit_rate(&self) -> Option<f64> {
+        let hits = self.hits.load(Ordering::Relaxed);
+        let lookups = hits + self.misses.load(Ordering::Relaxed);
+        (lookups > 0).then(|| hits as f64 / lookups as f64)
+    }
+
     /// Returns the current size (number of entries).
     pub(crate) fn size(&self) -> usize {
         self.size.load(Ordering::Relaxed)
@@ -274,16 +299,22 @@ impl CacheStatsHandler {
         self.size.store(0, Ordering::Relaxed);
     }
 
-    /// Resets collision counter to zero (but not size).
+    /// Resets collision, hit and miss counters to zero (but not size).
     pub(crate) fn reset_stats(&self) {
         self.collisions.store(0, Ordering::Relaxed);
+        self.hits.store(0, Ordering::Relaxed);
+        self.misses.store(0, Ordering::Relaxed);
     }
 }
 
 impl<K: PartialEq, V> StatsHandler<K, V> for CacheStatsHandler {
-    fn on_hit(&self, _key: &K, _value: &V) {}
+    fn on_hit(&self, _key: &K, _value: &V) {
+        self.hits.fetch_add(1, Ordering::Relaxed);
+    }
 
-    fn on_miss(&self, _key: AnyRef<'_>) {}
+    fn on_miss(&self, _key: AnyRef<'_>) {
+        self.misses.fetch_add(1, Ordering::Relaxed);
+    }
 
     fn on_insert(&self, key: &K, _value: &V, evicted: Option<(&K, &V)>) {
         match evicted {
@@ -754,16 +785,25 @@ impl ExecutionCache {
         metrics.code_cache_size.set(self.code_stats.size() as f64);
         metrics.code_cache_capacity.set(self.code_stats.capacity() as f64);
         metrics.code_cache_collisions.set(self.code_stats.collisions() as f64);
+        if let Some(hit_rate) = self.code_stats.hit_rate() {
+            metrics.code_cache_hit_rate.set(hit_rate);
+        }
         self.code_stats.reset_stats();
 
         metrics.storage_cache_size.set(self.storage_stats.size() as f64);
         metrics.storage_cache_capacity.set(self.storage_stats.capacity() as f64);
         metrics.storage_cache_collisions.set(self.storage_stats.collisions() as f64);
+        if let Some(hit_rate) = self.storage_stats.hit_rate() {
+            metrics.storage_cache_hit_rate.set(hit_rate);
+        }
         self.storage_stats.reset_stats();
 
         metrics.account_cache_size.set(self.account_stats.size() as f64);
         metrics.account_cache_capacity.set(self.account_stats.capacity() as f64);
         metrics.account_cache_collisions.set(self.account_stats.collisions() as f64);
+        if let Some(hit_rate) = self.account_stats.hit_rate() {
+            metrics.account_cache_hit_rate.set(hit_rate);
+        }
         self.account_stats.reset_stats();
     }
 }
@@ -940,6 +980,21 @@ mod tests {
         assert!(!cache.is_available(), "Cache should not be available with active guard");
     }
 
+    #[test]
+    fn test_cache_stats_hit_rate() {
+        let stats = CacheStatsHandler::new(10);
+        assert_eq!(stats.hit_rate(), None);
+
+        StatsHandler::<u64, u64>::on_hit(&stats, &1, &1);
+        StatsHandler::<u64, u64>::on_hit(&stats, &1, &1);
+        StatsHandler::<u64, u64>::on_hit(&stats, &1, &1);
+        stats.misses.fetch_add(1, Ordering::Relaxed);
+        assert_eq!(stats.hit_rate(), Some(0.75));
+
+        stats.reset_stats();
+        assert_eq!(stats.hit_rate(), None);
+    }
+
     #[test]
     fn test_saved_cache_multiple_references() {
         let execution_cache = ExecutionCache::new(1000);
@@ -0,0 +1,90 @@
+//! Compaction of the execution outputs of in-memory blocks.
+
+use crate::tree::executor::WorkloadExecutor;
+use alloy_primitives::{map::HashMap, BlockNumber, B256};
+use crossbeam_channel::{Receiver, Sender};
+use reth_chain_state::{compact_execution_output, CanonicalInMemoryState};
+use reth_execution_types::BlockExecutionOutput;
+use reth_primitives_traits::NodePrimitives;
+use std::sync::Arc;
+use tracing::debug;
+
+/// Compacted execution outputs by block hash.
+pub(crate) type CompactedOutputs<R> = HashMap<B256, Arc<BlockExecutionOutput<R>>>;
+
+/// Compacts the execution outputs of canonical in-memory blocks off the engine loop.
+///
+/// Compaction runs on a blocking task, which replaces the outputs of the blocks in the
+/// [`CanonicalInMemoryState`] once done. The compacted outputs are also sent back, so that the
+/// engine can replace the outputs of the same blocks in the tree state, see [`Self::compacted`].
+#[derive(Debug)]
+pub(crate) struct ExecutionOutputCompaction<N: NodePrimitives> {
+    /// Executor for the compaction tasks.
+    executor: WorkloadExecutor,
+    /// Height up to which the compaction of canonical blocks was started.
+    compacted_height: BlockNumber,
+    /// Sender half handed to the compaction tasks.
+    tx: Sender<CompactedOutputs<N::Receipt>>,
+    /// Receives the outputs of finished compaction tasks.
+    rx: Receiver<CompactedOutputs<N::Receipt>>,
+}
+
+impl<N: NodePrimitives> Default for ExecutionOutputCompaction<N> {
+    fn default() -> Self {
+        let (tx, rx) = crossbeam_channel::unbounded();
+        Self { executor: WorkloadExecutor::default(), compacted_height: 0, tx, rx }
+    }
+}
+
+impl<N: NodePrimitives> ExecutionOutputCompaction<N> {
+    /// Invoked when a reorg made the chain canonical, starting at `first_block`.
+    ///
+    /// The blocks of the new chain need to be compacted again.
+    pub(crate) fn on_reorg(&mut self, first_block: BlockNumber) {
+        self.compacted_height = self.compacted_height.min(first_block.saturating_sub(1));
+    }
+
+    /// Spawns a task that compacts the execution outputs of all canonical in-memory blocks at or
+    /// below `compact_height` that weren't compacted yet.
+    pub(crate) fn compact(
+        &mut self,
+        canonical_in_memory_state: &CanonicalInMemoryState<N>,
+        compact_height: BlockNumber,
+    ) {
+        if compact_height <= self.compacted_height {
+            return
+        }
+
+        let blocks = canonical_in_memory_state
+            .canonical_chain()
+            .take_while(|block| block.number() > self.compacted_height)
+            .filter(|block| block.number() <= compact_height)
+            .map(|block| block.block())
+            .collect::<Vec<_>>();
+        self.compacted_height = compact_height;
+        if blocks.is_empty() {
+            return
+        }
+
+        debug!(target: "engine::tree", count = blocks.len(), compact_height, "Compacting execution outputs");
+        let canonical_in_memory_state = canonical_in_memory_state.clone();
+        let tx = self.tx.clone();
+        self.executor.spawn_blocking(move || {
+            let outputs = blocks
+                .iter()
+                .map(|block| {
+                    let mut output = block.execution_output.clone();
+                    compact_execution_output(Arc::make_mut(&mut output));
+                    (block.recovered_block().hash(), output)
+                })
+                .collect::<CompactedOutputs<_>>();
+            canonical_in_memory_state.replace_execution_outputs(&outputs);
+            let _ = tx.send(outputs);
+        });
+    }
+
+    /// Returns the outputs of the compaction tasks that finished since the last call.
+    pub(crate) fn compacted(&self) -> impl Iterator<Item = CompactedOutputs<N::Receipt>> + '_ {
+        self.rx.try_iter()
+    }
+}
@@ -0,0 +1,787 @@
+//! Randomized testing of the [`EngineApiTreeHandler`] state machine.
+//!
+//! The [`EngineFuzzer`] drives a tree handler with random sequences of `newPayload` and
+//! `forkchoiceUpdated` messages and checks after every step that the responses and the state of
+//! the tree are consistent with the blocks it was sent.
+//!
+//! The sequences are generated from a seed, so every failure can be reproduced. They cover:
+//!  - valid chains, including long chains that are persisted to disk
+//!  - conflicting forks and reorgs between them, including reorgs below the persisted block
+//!  - invalid blocks and their descendants
+//!  - payloads that arrive before their parents, which are then downloaded on request
+//!
+//! Blocks are not executed. The validator of the tree returns a pre-built [`ExecutedBlock`] for
+//! valid blocks and a consensus error for invalid ones, and persistence only updates the headers
+//! of a [`MockEthProvider`], so the harness only exercises the tree state machine itself.
+
+use super::{EngineApiTreeHandler, EngineApiTreeState, PersistenceState, TreeConfig};
+use crate::{
+    engine::{DownloadRequest, EngineApiEvent, EngineApiKind, EngineApiRequest, FromEngine},
+    persistence::{PersistenceAction, PersistenceHandle},
+    tree::{
+        error::InsertBlockError,
+        payload_validator::{EngineValidator, TreeCtx, ValidationOutcome},
+    },
+};
+use alloy_consensus::{BlockHeader, Header};
+use alloy_eips::BlockNumHash;
+use alloy_primitives::{
+    map::{HashMap, HashSet},
+    B256,
+};
+use alloy_rpc_types_engine::{
+    ExecutionData, ExecutionPayloadSidecar, ExecutionPayloadV1, ForkchoiceState,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use reth_chain_state::{CanonicalInMemoryState, ComputedTrieData, ExecutedBlock};
+use reth_consensus::noop::NoopConsensus;
+use reth_engine_primitives::{BeaconEngineMessage, ForkchoiceStatus};
+use reth_errors::ConsensusError;
+use reth_ethereum_engine_primitives::EthEngineTypes;
+use reth_ethereum_primitives::{Block, BlockBody, EthPrimitives};
+use reth_evm_ethereum::EthEvmConfig;
+use reth_payload_builder::PayloadBuilderHandle;
+use reth_payload_primitives::{
+    EngineApiMessageVersion, InvalidPayloadAttributesError, NewPayloadError, PayloadTypes,
+};
+use reth_primitives_traits::{Block as _, RecoveredBlock, SealedBlock, SealedHeader};
+use reth_provider::{
+    test_utils::MockEthProvider, BlockExecutionOutput, BlockExecutionResult, BlockHashReader,
+};
+use reth_trie_db::ChangesetCache;
+use std::{
+    fmt,
+    sync::{mpsc, Arc},
+};
+use tokio::sync::{
+    mpsc::{unbounded_channel, UnboundedReceiver},
+    oneshot,
+};
+
+/// How far new blocks may be ahead of the canonical head.
+///
+/// This keeps every block within
+/// [`MIN_BLOCKS_FOR_PIPELINE_RUN`](super::MIN_BLOCKS_FOR_PIPELINE_RUN) of the canonical head, so
+/// the tree never has to backfill.
+const MAX_LEAD: u64 = 16;
+
+/// How far below the highest block new blocks may fork off.
+const MAX_FORK_DEPTH: u64 = 8;
+
+/// Number of the oldest unsent payloads that the next payload is picked from.
+const OUT_OF_ORDER_WINDOW: usize = 4;
+
+/// Probability that a new block is invalid.
+const INVALID_BLOCK_PROBABILITY: f64 = 0.1;
+
+/// Probability that a new block extends the previously generated block.
+const EXTEND_LATEST_PROBABILITY: f64 = 0.5;
+
+/// Probability that a `forkchoiceUpdated` targets a block that doesn't exist.
+const UNKNOWN_HEAD_PROBABILITY: f64 = 0.05;
+
+/// The tree handler that is driven by the [`EngineFuzzer`].
+type FuzzTree = EngineApiTreeHandler<
+    EthPrimitives,
+    MockEthProvider,
+    EthEngineTypes,
+    FuzzValidator,
+    EthEvmConfig,
+>;
+
+/// Configuration of an [`EngineFuzzer`] run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EngineFuzzConfig {
+    /// The seed the sequence of messages is generated from.
+    pub seed: u64,
+    /// The number of steps to run.
+    pub steps: usize,
+}
+
+/// Number of responses with each status.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StatusCounts {
+    /// Number of `VALID` responses.
+    pub valid: usize,
+    /// Number of `INVALID` responses.
+    pub invalid: usize,
+    /// Number of `SYNCING` or `ACCEPTED` responses.
+    pub syncing: usize,
+}
+
+impl fmt::Display for StatusCounts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} valid, {} invalid, {} syncing", self.valid, self.invalid, self.syncing)
+    }
+}
+
+/// Summary of a successful [`EngineFuzzer`] run.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EngineFuzzReport {
+    /// The seed of the run.
+    pub seed: u64,
+    /// The number of steps that were run.
+    pub steps: usize,
+    /// The number of generated blocks.
+    pub blocks: usize,
+    /// The number of generated blocks that are invalid.
+    pub invalid_blocks: usize,
+    /// Responses to `newPayload`.
+    pub new_payload: StatusCounts,
+    /// Responses to `forkchoiceUpdated`.
+    pub forkchoice_updated: StatusCounts,
+    /// The number of blocks that were delivered to the tree after it requested them.
+    pub downloaded_blocks: usize,
+    /// The canonical head at the end of the run.
+    pub canonical_head: BlockNumHash,
+    /// The last persisted block at the end of the run.
+    pub last_persisted_block: BlockNumHash,
+}
+
+impl fmt::Display for EngineFuzzReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "seed {}: {} steps, no invariant violations", self.seed, self.steps)?;
+        writeln!(f, "blocks: {} generated, {} invalid", self.blocks, self.invalid_blocks)?;
+        writeln!(f, "newPayload: {}", self.new_payload)?;
+        writeln!(f, "forkchoiceUpdated: {}", self.forkchoice_updated)?;
+        writeln!(f, "downloaded blocks: {}", self.downloaded_blocks)?;
+        write!(
+            f,
+            "canonical head: {}, last persisted block: {}",
+            self.canonical_head.number, self.last_persisted_block.number
+        )
+    }
+}
+
+/// An invariant of the tree that was violated during an [`EngineFuzzer`] run.
+#[derive(Debug, thiserror::Error)]
+#[error("invariant violated at step {step} of seed {seed}: {message}")]
+pub struct InvariantViolation {
+    /// The seed of the run.
+    pub seed: u64,
+    /// The step at which the violation was detected.
+    pub step: usize,
+    /// Description of the violation.
+    pub message: String,
+}
+
+/// Returns an error with the formatted message if the condition doesn't hold.
+macro_rules! ensure {
+    ($cond:expr, $($arg:tt)+) => {
+        if !$cond {
+            return Err(format!($($arg)+))
+        }
+    };
+}
+
+/// A block that was generated by the [`EngineFuzzer`].
+#[derive(Debug)]
+struct FuzzBlock {
+    block: SealedBlock<Block>,
+    /// Whether the block or one of its ancestors is invalid.
+    tainted: bool,
+}
+
+/// Validator that returns the outcome the [`EngineFuzzer`] decided on for a block, instead of
+/// executing it.
+#[derive(Debug, Default)]
+struct FuzzValidator {
+    /// All generated blocks.
+    blocks: HashMap<B256, SealedBlock<Block>>,
+    /// The blocks that fail validation.
+    invalid: HashSet<B256>,
+}
+
+impl FuzzValidator {
+    fn execute(&self, block: SealedBlock<Block>) -> ValidationOutcome<EthPrimitives> {
+        if self.invalid.contains(&block.hash()) {
+            let error = ConsensusError::Other("block is invalid".to_string());
+            return Err(InsertBlockError::consensus_error(error, block).into())
+        }
+
+        Ok(ExecutedBlock::new(
+            Arc::new(RecoveredBlock::new_sealed(block, Vec::new())),
+            Arc::new(BlockExecutionOutput {
+                result: BlockExecutionResult {
+                    receipts: Vec::new(),
+                    requests: Default::default(),
+                    gas_used: 0,
+                    blob_gas_used: 0,
+                },
+                state: Default::default(),
+            }),
+            ComputedTrieData::default(),
+        ))
+    }
+}
+
+impl EngineValidator<EthEngineTypes> for FuzzValidator {
+    fn validate_payload_attributes_against_header(
+        &self,
+        _attr: &<EthEngineTypes as PayloadTypes>::PayloadAttributes,
+        _header: &Header,
+    ) -> Result<(), InvalidPayloadAttributesError> {
+        Ok(())
+    }
+
+    fn convert_payload_to_block(
+        &self,
+        payload: ExecutionData,
+    ) -> Result<SealedBlock<Block>, NewPayloadError> {
+        let hash = payload.payload.block_hash();
+        self.blocks
+            .get(&hash)
+            .cloned()
+            .ok_or_else(|| NewPayloadError::Other(format!("unknown payload {hash}").into()))
+    }
+
+    fn validate_payload(
+        &mut self,
+        payload: ExecutionData,
+        _ctx: TreeCtx<'_, EthPrimitives>,
+    ) -> ValidationOutcome<EthPrimitives> {
+        let block = self.convert_payload_to_block(payload)?;
+        self.execute(block)
+    }
+
+    fn validate_block(
+        &mut self,
+        block: SealedBlock<Block>,
+        _ctx: TreeCtx<'_, EthPrimitives>,
+    ) -> ValidationOutcome<EthPrimitives> {
+        self.execute(block)
+    }
+
+    fn on_inserted_executed_block(&self, _block: ExecutedBlock<EthPrimitives>) {}
+}
+
+/// Drives an [`EngineApiTreeHandler`] with random engine API messages and checks its invariants.
+///
+/// The harness plays the roles of the consensus client, the network and the persistence service:
+/// it sends `newPayload` and `forkchoiceUpdated` messages, answers download requests with the
+/// generated blocks and writes persisted blocks to the provider.
+///
+/// After every step, it checks that:
+///  - valid blocks are never reported `INVALID`, and invalid blocks and their descendants are never
+///    reported `VALID`
+///  - payloads whose parent is known are validated right away, and payloads whose parent is unknown
+///    are reported `SYNCING`
+///  - the head of a `VALID` forkchoice update is canonical
+///  - the blocks of the tree are consistently indexed and never include invalid blocks
+///  - the canonical chain leads from the canonical head back to a block on disk, and the canonical
+///    in-memory state has the same head
+///  - persisted blocks extend the chain on disk, and the tree never requests a backfill
+#[derive(Debug)]
+pub struct EngineFuzzer {
+    config: EngineFuzzConfig,
+    rng: StdRng,
+    tree: FuzzTree,
+    provider: MockEthProvider,
+    from_tree: UnboundedReceiver<EngineApiEvent>,
+    persistence: mpsc::Receiver<PersistenceAction>,
+    /// All generated blocks, including genesis.
+    blocks: HashMap<B256, FuzzBlock>,
+    /// The most recently generated block.
+    latest: B256,
+    /// The highest block number of all generated blocks.
+    max_number: u64,
+    /// Generated blocks that weren't sent yet, oldest first.
+    unsent: Vec<B256>,
+    /// Blocks that were sent at least once.
+    sent: Vec<B256>,
+    /// Blocks the tree requested, which are delivered on a later step.
+    downloads: Vec<B256>,
+    report: EngineFuzzReport,
+}
+
+impl EngineFuzzer {
+    /// Creates a new fuzzer with a tree handler whose canonical head is the mainnet genesis block.
+    pub fn new(config: EngineFuzzConfig) -> Self {
+        let provider = MockEthProvider::default().with_genesis_block();
+        let genesis = SealedHeader::seal_slow(provider.chain_spec.genesis_header().clone());
+
+        let (persistence_tx, persistence) = mpsc::channel();
+        let (to_payload_service, _) = unbounded_channel();
+        let (outgoing, from_tree) = unbounded_channel();
+        let tree_config = TreeConfig::default();
+
+        let tree = EngineApiTreeHandler::new(
+            provider.clone(),
+            NoopConsensus::arc(),
+            FuzzValidator::default(),
+            outgoing,
+            EngineApiTreeState::new(
+                tree_config.block_buffer_limit(),
+                tree_config.max_invalid_header_cache_length(),
+                genesis.num_hash(),
+                EngineApiKind::Ethereum,
+            ),
+            CanonicalInMemoryState::with_head(genesis.clone(), None, None),
+            PersistenceHandle::new(persistence_tx),
+            PersistenceState { last_persisted_block: genesis.num_hash(), rx: None },
+            PayloadBuilderHandle::new(to_payload_service),
+            tree_config,
+            EngineApiKind::Ethereum,
+            EthEvmConfig::new(provider.chain_spec.clone()),
+            ChangesetCache::new(),
+        );
+
+        let genesis_block = SealedBlock::from_sealed_parts(genesis.clone(), BlockBody::default());
+        let mut blocks = HashMap::default();
+        blocks.insert(genesis.hash(), FuzzBlock { block: genesis_block, tainted: false });
+
+        Self {
+            config,
+            rng: StdRng::seed_from_u64(config.seed),
+            tree,
+            provider,
+            from_tree,
+            persistence,
+            blocks,
+            latest: genesis.hash(),
+            max_number: genesis.number(),
+            unsent: Vec::new(),
+            sent: Vec::new(),
+            downloads: Vec::new(),
+            report: EngineFuzzReport {
+                seed: config.seed,
+                canonical_head: genesis.num_hash(),
+                last_persisted_block: genesis.num_hash(),
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Runs the configured number of steps.
+    ///
+    /// Returns the first invariant violation, if any.
+    pub fn run(mut self) -> Result<EngineFuzzReport, InvariantViolation> {
+        for step in 0..self.config.steps {
+            self.step().map_err(|message| InvariantViolation {
+                seed: self.config.seed,
+                step,
+                message,
+            })?;
+            self.report.steps += 1;
+        }
+
+        self.report.canonical_head = self.tree.state.tree_state.current_canonical_head;
+        self.report.last_persisted_block = self.tree.persistence_state.last_persisted_block;
+        Ok(self.report)
+    }
+
+    /// Sends a random message to the tree, lets it process all follow-up work and checks the
+    /// invariants of the tree.
+    fn step(&mut self) -> Result<(), String> {
+        match self.rng.random_range(0..100) {
+            0..30 => {
+                self.new_block();
+            }
+            30..65 => self.new_payload()?,
+            65..85 => self.forkchoice_updated()?,
+            _ => self.deliver_downloads()?,
+        }
+
+        self.process()?;
+        self.check_tree()
+    }
+
+    /// Generates a new block on top of a random recent block.
+    fn new_block(&mut self) -> B256 {
+        let head = self.tree.state.tree_state.canonical_block_number();
+        let lowest = self.max_number.saturating_sub(MAX_FORK_DEPTH).min(head);
+        let highest = head + MAX_LEAD - 1;
+
+        let latest = &self.blocks[&self.latest].block;
+        let parent = if (lowest..=highest).contains(&latest.number()) &&
+            self.rng.random_bool(EXTEND_LATEST_PROBABILITY)
+        {
+            self.latest
+        } else {
+            let mut candidates = self
+                .blocks
+                .iter()
+                .filter(|(_, block)| (lowest..=highest).contains(&block.block.number()))
+                .map(|(hash, _)| *hash)
+                .collect::<Vec<_>>();
+            // sort for determinism, the iteration order of the map is random
+            candidates.sort_unstable();
+            candidates[self.rng.random_range(0..candidates.len())]
+        };
+        let parent = &self.blocks[&parent];
+
+        let header = Header {
+            parent_hash: parent.block.hash(),
+            number: parent.block.number() + 1,
+            timestamp: parent.block.timestamp() + 12,
+            gas_limit: parent.block.gas_limit(),
+            mix_hash: B256::from(self.rng.random::<[u8; 32]>()),
+            ..Default::default()
+        };
+        let block = Block::new(header, BlockBody::default()).seal_slow();
+        let hash = block.hash();
+        let invalid = self.rng.random_bool(INVALID_BLOCK_PROBABILITY);
+        let tainted = invalid || parent.tainted;
+
+        self.report.blocks += 1;
+        if invalid {
+            self.report.invalid_blocks += 1;
+            self.tree.payload_validator.invalid.insert(hash);
+        }
+        self.tree.payload_validator.blocks.insert(hash, block.clone());
+        self.max_number = self.max_number.max(block.number());
+        self.blocks.insert(hash, FuzzBlock { block, tainted });
+        self.latest = hash;
+        self.unsent.push(hash);
+
+        hash
+    }
+
+    /// Sends a `newPayload` for one of the oldest unsent blocks, or resends a block.
+    fn new_payload(&mut self) -> Result<(), String> {
+        let hash = if !self.unsent.is_empty() && (self.sent.is_empty() || self.rng.random_bool(0.9))
+        {
+            let index = self.rng.random_range(0..self.unsent.len().min(OUT_OF_ORDER_WINDOW));
+            self.unsent.remove(index)
+        } else if !self.sent.is_empty() {
+            self.sent[self.rng.random_range(0..self.sent.len())]
+        } else {
+            let hash = self.new_block();
+            self.unsent.retain(|unsent| *unsent != hash);
+            hash
+        };
+        self.sent.push(hash);
+
+        let FuzzBlock { block, tainted } = &self.blocks[&hash];
+        let (number, tainted) = (block.number(), *tainted);
+        let invalid = self.tree.payload_validator.invalid.contains(&hash);
+        let known = self.is_known(hash);
+        let parent_known = self.is_known(block.parent_hash());
+        let payload = ExecutionData {
+            payload: ExecutionPayloadV1::from_block_unchecked(hash, &block.clone_block()).into(),
+            sidecar: ExecutionPayloadSidecar::none(),
+        };
+
+        let (tx, mut rx) = oneshot::channel();
+        self.on_engine_message(FromEngine::Request(
+            BeaconEngineMessage::NewPayload { payload, tx }.into(),
+        ))?;
+        let status = rx
+            .try_recv()
+            .map_err(|_| format!("no newPayload response for block {hash}"))?
+            .map_err(|err| format!("newPayload of block {hash} failed: {err}"))?
+            .status;
+
+        if status.is_valid() {
+            self.report.new_payload.valid += 1;
+        } else if status.is_invalid() {
+            self.report.new_payload.invalid += 1;
+        } else {
+            self.report.new_payload.syncing += 1;
+        }
+
+        if tainted {
+            ensure!(!status.is_valid(), "invalid block {number} {hash} was reported VALID");
+            if invalid && !known && parent_known {
+                ensure!(
+                    status.is_invalid(),
+                    "invalid block {number} {hash} with a known parent was reported {status:?}"
+                );
+            }
+        } else {
+            ensure!(!status.is_invalid(), "valid block {number} {hash} was reported INVALID");
+            if known || parent_known {
+                ensure!(
+                    status.is_valid(),
+                    "valid block {number} {hash} with a known parent was reported {status:?}"
+                );
+            } else {
+                ensure!(
+                    status.is_syncing(),
+                    "valid block {number} {hash} with an unknown parent was reported {status:?}"
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Sends a `forkchoiceUpdated` to a random recent block.
+    fn forkchoice_updated(&mut self) -> Result<(), String> {
+        let head = if self.rng.random_bool(UNKNOWN_HEAD_PROBABILITY) {
+            B256::from(self.rng.random::<[u8; 32]>())
+        } else {
+            let lowest = self.max_number.saturating_sub(MAX_LEAD);
+            let mut candidates = self
+                .blocks
+                .iter()
+                .filter(|(_, block)| block.block.number() >= lowest)
+                .map(|(hash, _)| *hash)
+                .collect::<Vec<_>>();
+            candidates.sort_unstable();
+            candidates[self.rng.random_range(0..candidates.len())]
+        };
+        let tainted = self.blocks.get(&head).is_some_and(|block| block.tainted);
+
+        let state = ForkchoiceState {
+            head_block_hash: head,
+            safe_block_hash: B256::ZERO,
+            finalized_block_hash: B256::ZERO,
+        };
+        let (tx, mut rx) = oneshot::channel();
+        self.on_engine_message(FromEngine::Request(
+            BeaconEngineMessage::ForkchoiceUpdated {
+                state,
+                payload_attrs: None,
+                tx,
+                version: EngineApiMessageVersion::default(),
+            }
+            .into(),
+        ))?;
+        let status = rx
+            .try_recv()
+            .map_err(|_| format!("no forkchoiceUpdated response for head {head}"))?
+            .map_err(|err| format!("forkchoiceUpdated to head {head} failed: {err}"))?
+            .forkchoice_status();
+
+        match status {
+            ForkchoiceStatus::Valid => {
+                self.report.forkchoice_updated.valid += 1;
+                ensure!(!tainted, "forkchoiceUpdated to invalid head {head} was VALID");
+                ensure!(
+                    self.tree.state.tree_state.is_canonical(head) || self.is_persisted(head),
+                    "head {head} of VALID forkchoiceUpdated isn't canonical"
+                );
+            }
+            ForkchoiceStatus::Invalid => {
+                self.report.forkchoice_updated.invalid += 1;
+                ensure!(tainted, "forkchoiceUpdated to valid head {head} was INVALID");
+            }
+            ForkchoiceStatus::Syncing => self.report.forkchoice_updated.syncing += 1,
+        }
+
+        Ok(())
+    }
+
+    /// Delivers the blocks the tree requested.
+    fn deliver_downloads(&mut self) -> Result<(), String> {
+        let blocks = std::mem::take(&mut self.downloads)
+            .into_iter()
+            .filter_map(|hash| self.blocks.get(&hash))
+            .map(|block| block.block.clone())
+            .collect::<Vec<_>>();
+        if blocks.is_empty() {
+            return Ok(())
+        }
+
+        self.report.downloaded_blocks += blocks.len();
+        self.on_engine_message(FromEngine::DownloadedBlocks(blocks))
+    }
+
+    fn on_engine_message(
+        &mut self,
+        msg: FromEngine<EngineApiRequest<EthEngineTypes, EthPrimitives>, Block>,
+    ) -> Result<(), String> {
+        self.tree
+            .on_engine_message(msg)
+            .map(drop)
+            .map_err(|err| format!("engine returned a fatal error: {err}"))
+    }
+
+    /// Processes the messages the tree queued to itself, the persistence actions and the events of
+    /// the tree, until there is no more work.
+    fn process(&mut self) -> Result<(), String> {
+        loop {
+            while let Ok(msg) = self.tree.incoming.try_recv() {
+                self.on_engine_message(msg)?;
+            }
+
+            self.tree
+                .advance_persistence()
+                .map_err(|err| format!("advancing persistence failed: {err}"))?;
+            let persisted = self.on_persistence_action()?;
+
+            while let Ok(event) = self.from_tree.try_recv() {
+                match event {
+                    EngineApiEvent::BeaconConsensus(_) => {}
+                    EngineApiEvent::BackfillAction(action) => {
+                        return Err(format!("unexpected backfill request {action:?}"))
+                    }
+                    EngineApiEvent::Download(DownloadRequest::BlockSet(hashes)) => {
+                        let mut hashes = hashes.into_iter().collect::<Vec<_>>();
+                        // sort for determinism, the iteration order of the set is random
+                        hashes.sort_unstable();
+                        self.downloads.extend(hashes);
+                    }
+                    EngineApiEvent::Download(DownloadRequest::BlockRange(hash, count)) => {
+                        let mut hash = hash;
+                        for _ in 0..count {
+                            let Some(block) = self.blocks.get(&hash) else { break };
+                            self.downloads.push(hash);
+                            hash = block.block.parent_hash();
+                        }
+                    }
+                }
+            }
+
+            if !persisted && self.tree.incoming.is_empty() {
+                return Ok(())
+            }
+        }
+    }
+
+    /// Handles the next persistence action of the tree, if any, and notifies the tree about its
+    /// completion.
+    ///
+    /// Returns `true` if an action was handled.
+    fn on_persistence_action(&mut self) -> Result<bool, String> {
+        let Ok(action) = self.persistence.try_recv() else { return Ok(false) };
+
+        match action {
+            PersistenceAction::SaveBlocks(blocks, tx) => {
+                for executed in &blocks {
+                    let block = executed.recovered_block();
+                    let (number, hash) = (block.number(), block.hash());
+                    ensure!(
+                        !self.blocks.get(&hash).is_some_and(|block| block.tainted),
+                        "invalid block {number} {hash} was persisted"
+                    );
+                    ensure!(
+                        self.disk_hash(number).is_none(),
+                        "block {number} {hash} overwrites a block on disk"
+                    );
+                    ensure!(
+                        number == 0 || self.disk_hash(number - 1) == Some(block.parent_hash()),
+                        "persisted block {number} {hash} doesn't extend the chain on disk"
+                    );
+                    self.provider.add_block(hash, block.clone_block());
+                }
+                let _ = tx.send(blocks.last().map(|block| block.recovered_block().num_hash()));
+            }
+            PersistenceAction::RemoveBlocksAbove(number, tx) => {
+                self.provider.headers.lock().retain(|_, header| header.number <= number);
+                self.provider.blocks.lock().retain(|_, block| block.header.number <= number);
+                let new_tip = self.disk_hash(number).map(|hash| BlockNumHash::new(number, hash));
+                let _ = tx.send(new_tip);
+            }
+            PersistenceAction::SaveFinalizedBlock(_) | PersistenceAction::SaveSafeBlock(_) => {}
+        }
+
+        if let Some((rx, start_time, _)) = self.tree.persistence_state.rx.take() {
+            let result = rx.try_recv().map_err(|err| format!("no persistence result: {err}"))?;
+            self.tree
+                .on_persistence_complete(result, start_time)
+                .map_err(|err| format!("completing persistence failed: {err}"))?;
+        }
+
+        Ok(true)
+    }
+
+    /// Checks the invariants of the tree state.
+    fn check_tree(&self) -> Result<(), String> {
+        let tree_state = &self.tree.state.tree_state;
+
+        for (hash, executed) in &tree_state.blocks_by_hash {
+            let block = executed.recovered_block();
+            let number = block.number();
+            ensure!(block.hash() == *hash, "block {number} {hash} is stored under the wrong hash");
+            let Some(generated) = self.blocks.get(hash) else {
+                return Err(format!("tree contains unknown block {number} {hash}"))
+            };
+            ensure!(!generated.tainted, "tree contains invalid block {number} {hash}");
+            ensure!(
+                tree_state.blocks_by_number.get(&number).is_some_and(|blocks| blocks
+                    .iter()
+                    .any(|b| b.recovered_block().hash() == *hash)),
+                "block {number} {hash} is missing from the blocks by number"
+            );
+            ensure!(
+                !tree_state.blocks_by_hash.contains_key(&block.parent_hash()) ||
+                    tree_state
+                        .parent_to_child
+                        .get(&block.parent_hash())
+                        .is_some_and(|children| children.contains(hash)),
+                "block {number} {hash} is missing from the children of its parent"
+            );
+        }
+
+        let indexed = tree_state.blocks_by_number.values().map(Vec::len).sum::<usize>();
+        ensure!(
+            indexed == tree_state.blocks_by_hash.len(),
+            "{indexed} blocks by number, but {} blocks by hash",
+            tree_state.blocks_by_hash.len()
+        );
+
+        for (parent, children) in &tree_state.parent_to_child {
+            for child in children {
+                ensure!(
+                    tree_state
+                        .blocks_by_hash
+                        .get(child)
+                        .is_some_and(|block| block.recovered_block().parent_hash() == *parent),
+                    "{child} is tracked as a child of {parent}, but isn't in the tree"
+                );
+            }
+        }
+
+        // the canonical chain must lead from the head back to a block on disk
+        let head = tree_state.current_canonical_head;
+        let (mut hash, mut number) = (head.hash, head.number);
+        while let Some(executed) = tree_state.blocks_by_hash.get(&hash) {
+            let block = executed.recovered_block();
+            ensure!(
+                block.number() == number,
+                "canonical block {hash} has number {}, expected {number}",
+                block.number()
+            );
+            hash = block.parent_hash();
+            number = number.checked_sub(1).ok_or("canonical chain goes below genesis")?;
+        }
+        ensure!(
+            self.disk_hash(number) == Some(hash),
+            "canonical chain of head {} {} ends at {number} {hash}, which isn't on disk",
+            head.number,
+            head.hash
+        );
+
+        let in_memory_head = self.tree.canonical_in_memory_state.get_canonical_head().num_hash();
+        ensure!(
+            in_memory_head == head,
+            "canonical in-memory head {in_memory_head:?} doesn't match the tree head {head:?}"
+        );
+
+        Ok(())
+    }
+
+    /// Returns `true` if the block is in the tree or on disk.
+    fn is_known(&self, hash: B256) -> bool {
+        self.tree.state.tree_state.blocks_by_hash.contains_key(&hash) || self.is_persisted(hash)
+    }
+
+    /// Returns `true` if the block is on disk.
+    fn is_persisted(&self, hash: B256) -> bool {
+        self.provider.headers.lock().contains_key(&hash)
+    }
+
+    /// Returns the hash of the block with the given number on disk.
+    fn disk_hash(&self, number: u64) -> Option<B256> {
+        self.provider.block_hash(number).ok().flatten()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fuzz_engine_tree() {
+        for seed in 0..4 {
+            let report = EngineFuzzer::new(EngineFuzzConfig { seed, steps: 300 })
+                .run()
+                .unwrap_or_else(|err| panic!("{err}"));
+            assert_eq!(report.steps, 300);
+            assert!(report.new_payload.valid > 0, "{report}");
+            assert!(report.forkchoice_updated.valid > 0, "{report}");
+        }
+    }
+}
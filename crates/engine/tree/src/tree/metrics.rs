@@ -22,9 +22,14 @@ pub(crate) struct EngineApiMetrics {
     pub(crate) block_validation: BlockValidationMetrics,
     /// Canonical chain and reorg related metrics
     pub tree: TreeMetrics,
+    /// Block insertion, in-memory block and reorg metrics of the engine tree.
+    pub(crate) engine_tree: EngineTreeMetrics,
     /// Metrics for EIP-7928 Block-Level Access Lists (BAL).
     #[allow(dead_code)]
     pub(crate) bal: BalMetrics,
+    /// Opcode usage metrics of executed blocks, created on first use of an opcode.
+    #[cfg(feature = "execution-stats")]
+    pub(crate) opcodes: alloy_primitives::map::HashMap<u8, OpcodeMetrics>,
 }
 
 impl EngineApiMetrics {
@@ -82,6 +87,24 @@ impl EngineApiMetrics {
     pub(crate) fn record_transaction_execution(&self, elapsed: Duration) {
         self.executor.transaction_execution_histogram.record(elapsed);
     }
+
+    /// Records the opcode usage of an executed block.
+    #[cfg(feature = "execution-stats")]
+    pub(crate) fn record_execution_stats(
+        &mut self,
+        stats: &reth_revm::execution_stats::ExecutionStats,
+    ) {
+        for (op, usage) in stats.opcodes() {
+            let metrics = self.opcodes.entry(op).or_insert_with(|| {
+                OpcodeMetrics::new_with_labels(&[(
+                    "opcode",
+                    reth_revm::execution_stats::opcode_name(op),
+                )])
+            });
+            metrics.count.increment(usage.count);
+            metrics.gas_used.increment(usage.gas_used);
+        }
+    }
 }
 
 /// Metrics for the entire blockchain tree
@@ -100,6 +123,19 @@ pub(crate) struct TreeMetrics {
     pub finalized_block_height: Gauge,
 }
 
+/// Metrics for block insertion into the engine tree, the blocks it keeps in memory and reorgs.
+#[derive(Metrics)]
+#[metrics(scope = "engine.tree")]
+pub(crate) struct EngineTreeMetrics {
+    /// Histogram of the time it takes to insert a block into the tree, including execution and
+    /// state root computation
+    pub(crate) insert_block_duration: Histogram,
+    /// The number of executed blocks kept in memory
+    pub(crate) in_memory_blocks: Gauge,
+    /// Histogram of reorg depths, i.e. the number of blocks removed from the canonical chain
+    pub(crate) reorg_depth: Histogram,
+}
+
 /// Metrics for the `EngineApi`.
 #[derive(Metrics)]
 #[metrics(scope = "consensus.engine.beacon")]
@@ -386,12 +422,27 @@ impl BlockValidationMetrics {
     }
 }
 
+/// Opcode usage of all executed blocks, labeled by opcode.
+#[cfg(feature = "execution-stats")]
+#[derive(Metrics)]
+#[metrics(scope = "sync.execution_stats")]
+pub(crate) struct OpcodeMetrics {
+    /// The number of times the opcode was executed.
+    count: Counter,
+    /// The gas spent by the opcode.
+    gas_used: Counter,
+}
+
 /// Metrics for the blockchain tree block buffer
 #[derive(Metrics)]
 #[metrics(scope = "blockchain_tree.block_buffer")]
 pub(crate) struct BlockBufferMetrics {
     /// Total blocks in the block buffer
     pub blocks: Gauge,
+    /// Total in-memory size of the blocks in the block buffer in bytes
+    pub size_bytes: Gauge,
+    /// Number of blocks evicted because the block buffer was full
+    pub evicted_blocks: Counter,
 }
 
 #[cfg(test)]
@@ -4,18 +4,20 @@ use crate::{
     engine::{DownloadRequest, EngineApiEvent, EngineApiKind, EngineApiRequest, FromEngine},
     persistence::PersistenceHandle,
     tree::{error::InsertPayloadError, metrics::EngineApiMetrics, payload_validator::TreeCtx},
+    watchdog::RecoveryAction,
 };
 use alloy_consensus::BlockHeader;
 use alloy_eips::{eip1898::BlockWithParent, merge::EPOCH_SLOTS, BlockNumHash, NumHash};
 use alloy_evm::block::StateChangeSource;
-use alloy_primitives::B256;
+use alloy_primitives::{BlockNumber, B256};
 use alloy_rpc_types_engine::{
     ForkchoiceState, PayloadStatus, PayloadStatusEnum, PayloadValidationError,
 };
+use compaction::ExecutionOutputCompaction;
 use error::{InsertBlockError, InsertBlockFatalError};
 use reth_chain_state::{
-    CanonicalInMemoryState, ComputedTrieData, ExecutedBlock, MemoryOverlayStateProvider,
-    NewCanonicalChain,
+    BlockTiming, CanonicalInMemoryState, ComputedTrieData, ExecutedBlock,
+    MemoryOverlayStateProvider, NewCanonicalChain,
 };
 use reth_consensus::{Consensus, FullConsensus};
 use reth_engine_primitives::{
@@ -40,7 +42,12 @@ use reth_stages_api::ControlFlow;
 use reth_trie_db::ChangesetCache;
 use revm::state::EvmState;
 use state::TreeState;
-use std::{fmt::Debug, ops, sync::Arc, time::Instant};
+use std::{
+    fmt::Debug,
+    ops,
+    sync::Arc,
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
 
 use crossbeam_channel::{Receiver, Sender};
 use tokio::sync::{
@@ -51,7 +58,10 @@ use tracing::*;
 
 mod block_buffer;
 mod cached_state;
+mod compaction;
 pub mod error;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod fuzz;
 pub mod instrumented_state;
 mod invalid_headers;
 mod metrics;
@@ -282,6 +292,8 @@ where
     evm_config: C,
     /// Changeset cache for in-memory trie changesets
     changeset_cache: ChangesetCache,
+    /// Compacts the execution outputs of older in-memory blocks.
+    compaction: ExecutionOutputCompaction<N>,
 }
 
 impl<N, P: Debug, T: PayloadTypes + Debug, V: Debug, C> std::fmt::Debug
@@ -307,6 +319,7 @@ where
             .field("engine_kind", &self.engine_kind)
             .field("evm_config", &self.evm_config)
             .field("changeset_cache", &self.changeset_cache)
+            .field("compaction", &self.compaction)
             .finish()
     }
 }
@@ -367,6 +380,7 @@ where
             engine_kind,
             evm_config,
             changeset_cache,
+            compaction: Default::default(),
         }
     }
 
@@ -574,6 +588,7 @@ where
 
         // start timing for the new payload process
         let start = Instant::now();
+        let received_at = SystemTime::now();
 
         // Ensures that the given payload does not violate any consensus rules that concern the
         // block's layout, like:
@@ -602,6 +617,7 @@ where
         // This validation **MUST** be instantly run in all cases even during active sync process.
 
         let num_hash = payload.num_hash();
+        let timestamp = payload.timestamp();
         let engine_event = ConsensusEngineEvent::BlockReceived(num_hash);
         self.emit_event(EngineApiEvent::BeaconConsensus(engine_event));
 
@@ -617,7 +633,11 @@ where
         self.metrics.block_validation.record_payload_validation(start.elapsed().as_secs_f64());
 
         let status = if self.backfill_sync_state.is_idle() {
-            self.try_insert_payload(payload)?
+            let status = self.try_insert_payload(payload)?;
+            if status.status.is_valid() {
+                self.record_block_timing(num_hash, timestamp, received_at);
+            }
+            status
         } else {
             self.try_buffer_payload(payload)?
         };
@@ -639,6 +659,23 @@ where
         Ok(outcome)
     }
 
+    /// Keeps when the payload of a valid block was received and finished executing, the timing is
+    /// recorded once the block becomes canonical.
+    ///
+    /// Payloads of blocks that were already seen keep their original timing.
+    fn record_block_timing(&self, num_hash: BlockNumHash, timestamp: u64, received_at: SystemTime) {
+        let unix_ms = |time: SystemTime| {
+            time.duration_since(UNIX_EPOCH).unwrap_or_default().as_millis() as u64
+        };
+        self.canonical_in_memory_state.block_timings().on_executed(BlockTiming {
+            number: num_hash.number,
+            hash: num_hash.hash,
+            timestamp,
+            received_at_ms: unix_ms(received_at),
+            executed_at_ms: unix_ms(SystemTime::now()),
+        });
+    }
+
     /// Processes a payload during normal sync operation.
     ///
     /// Returns:
@@ -1016,6 +1053,9 @@ where
             return Ok(TreeOutcome::new(early_result));
         }
 
+        // Keep the head buffered until its missing ancestors are downloaded
+        self.state.buffer.set_fcu_head(state.head_block_hash);
+
         // Return early if we are on the correct fork
         if let Some(result) = self.handle_canonical_head(state, &attrs, version)? {
             return Ok(result);
@@ -1287,12 +1327,16 @@ where
 
     /// Triggers new persistence actions if no persistence task is currently in progress.
     ///
-    /// This checks if we need to remove blocks (disk reorg) or save new blocks to disk.
+    /// This checks if we need to remove blocks (disk reorg) or save new blocks to disk. Disk reorgs
+    /// to a sibling chain are deferred to the next flush, see
+    /// [`Self::should_reconcile_disk_reorg`].
     /// Persistence completion is handled separately via the `wait_for_event` method.
     fn advance_persistence(&mut self) -> Result<(), AdvancePersistenceError> {
         if !self.persistence_state.in_progress() {
             if let Some(new_tip_num) = self.find_disk_reorg()? {
-                self.remove_blocks(new_tip_num)
+                if self.should_reconcile_disk_reorg() {
+                    self.remove_blocks(new_tip_num)
+                }
             } else if self.should_persist() {
                 let blocks_to_persist =
                     self.get_canonical_blocks_to_persist(PersistTarget::Threshold)?;
@@ -1326,6 +1370,15 @@ where
                 self.on_persistence_complete(result, start_time)?;
             }
 
+            // persisted blocks that were reorged out must be removed before the canonical blocks
+            // can be saved
+            if let Some(new_tip_num) = self.find_disk_reorg()? {
+                self.remove_blocks(new_tip_num);
+                if self.persistence_state.in_progress() {
+                    continue
+                }
+            }
+
             let blocks_to_persist = self.get_canonical_blocks_to_persist(PersistTarget::Head)?;
 
             if blocks_to_persist.is_empty() {
@@ -1426,6 +1479,9 @@ where
                 FromOrchestrator::BackfillSyncFinished(ctrl) => {
                     self.on_backfill_sync_finished(ctrl)?;
                 }
+                FromOrchestrator::RecoverHead(action) => {
+                    self.on_recover_head(action)?;
+                }
                 FromOrchestrator::Terminate { tx } => {
                     debug!(target: "engine::tree", "received terminate request");
                     if let Err(err) = self.finish_termination(tx) {
@@ -1456,6 +1512,7 @@ where
                         }
 
                         self.state.tree_state.insert_executed(block.clone());
+                        self.update_in_memory_block_metrics();
                         self.payload_validator.on_inserted_executed_block(block.clone());
                         self.metrics.engine.inserted_already_executed_blocks.increment(1);
                         self.emit_event(EngineApiEvent::BeaconConsensus(
@@ -1620,6 +1677,7 @@ where
         }
 
         self.metrics.engine.executed_blocks.set(self.state.tree_state.block_count() as f64);
+        self.update_in_memory_block_metrics();
         self.metrics.tree.canonical_chain_height.set(backfill_height as f64);
 
         // remove all buffered blocks below the backfill height
@@ -1675,45 +1733,111 @@ where
         };
 
         // Check if there are more blocks to sync between current head and FCU target
-        if let Some(lowest_buffered) =
-            self.state.buffer.lowest_ancestor(&sync_target_state.head_block_hash)
-        {
-            let current_head_num = self.state.tree_state.current_canonical_head.number;
-            let target_head_num = lowest_buffered.number();
+        if self.download_sync_target(sync_target_state.head_block_hash) {
+            return Ok(())
+        }
 
-            if let Some(distance) = self.distance_from_local_tip(current_head_num, target_head_num)
-            {
-                // There are blocks between current head and FCU target, download them
-                debug!(
-                    target: "engine::tree",
-                    %current_head_num,
-                    %target_head_num,
-                    %distance,
-                    "Backfill complete, downloading remaining blocks to reach FCU target"
-                );
+        // try to close the gap by executing buffered blocks that are child blocks of the new head
+        self.try_connect_buffered_blocks(self.state.tree_state.current_canonical_head)
+    }
 
-                self.emit_event(EngineApiEvent::Download(DownloadRequest::BlockRange(
-                    lowest_buffered.parent_hash(),
-                    distance,
-                )));
-                return Ok(());
-            }
-        } else {
+    /// Requests the download of the blocks between the canonical head and the given head of the
+    /// sync target.
+    ///
+    /// Returns `true` if a download was requested.
+    fn download_sync_target(&mut self, sync_target_head: B256) -> bool {
+        let Some(lowest_buffered) = self.state.buffer.lowest_ancestor(&sync_target_head) else {
             // We don't have the head block or any of its ancestors buffered. Request
             // a download for the head block which will then trigger further sync.
             debug!(
                 target: "engine::tree",
-                head_hash = %sync_target_state.head_block_hash,
-                "Backfill complete but head block not buffered, requesting download"
+                head_hash = %sync_target_head,
+                "Head block not buffered, requesting download"
             );
             self.emit_event(EngineApiEvent::Download(DownloadRequest::single_block(
-                sync_target_state.head_block_hash,
+                sync_target_head,
             )));
-            return Ok(());
+            return true
+        };
+
+        let current_head_num = self.state.tree_state.current_canonical_head.number;
+        let target_head_num = lowest_buffered.number();
+        let Some(distance) = self.distance_from_local_tip(current_head_num, target_head_num) else {
+            return false
+        };
+
+        // There are blocks between current head and FCU target, download them
+        debug!(
+            target: "engine::tree",
+            %current_head_num,
+            %target_head_num,
+            %distance,
+            "Downloading remaining blocks to reach FCU target"
+        );
+        self.emit_event(EngineApiEvent::Download(DownloadRequest::BlockRange(
+            lowest_buffered.parent_hash(),
+            distance,
+        )));
+        true
+    }
+
+    /// Attempts to recover a stalled canonical head with the given action.
+    ///
+    /// This is a no-op if there's no sync target, the head of the sync target is already
+    /// canonical, or a backfill sync is running.
+    fn on_recover_head(&mut self, action: RecoveryAction) -> Result<(), InsertBlockFatalError> {
+        let Some(sync_target_state) = self.state.forkchoice_state_tracker.sync_target_state()
+        else {
+            debug!(target: "engine::tree", %action, "No sync target to recover the head");
+            return Ok(())
+        };
+        let sync_target_head = sync_target_state.head_block_hash;
+        if self.state.tree_state.canonical_block_hash() == sync_target_head {
+            return Ok(())
         }
 
-        // try to close the gap by executing buffered blocks that are child blocks of the new head
-        self.try_connect_buffered_blocks(self.state.tree_state.current_canonical_head)
+        if action == RecoveryAction::RestartEngine &&
+            self.backfill_sync_state == BackfillSyncState::Pending
+        {
+            // the requested backfill sync never started, so nothing keeps it from being requested
+            // again
+            warn!(target: "engine::tree", "Resetting pending backfill sync");
+            self.backfill_sync_state = BackfillSyncState::Idle;
+        }
+
+        if !self.backfill_sync_state.is_idle() {
+            debug!(target: "engine::tree", %action, "Backfill sync in progress, skipping head recovery");
+            return Ok(())
+        }
+
+        warn!(target: "engine::tree", %action, %sync_target_head, head=?self.state.tree_state.current_canonical_head, "Recovering stalled canonical head");
+        match action {
+            RecoveryAction::RequestSyncTarget => {
+                if !self.download_sync_target(sync_target_head) {
+                    self.try_connect_buffered_blocks(self.state.tree_state.current_canonical_head)?;
+                }
+            }
+            RecoveryAction::RestartEngine => {
+                self.state.buffer.clear();
+                self.download_sync_target(sync_target_head);
+            }
+            RecoveryAction::Backfill => {
+                // sync to the finalized block if it's not on disk yet, otherwise to the head
+                let finalized = sync_target_state.finalized_block_hash;
+                let target = if !finalized.is_zero() &&
+                    self.provider.header_by_hash_or_number(finalized.into())?.is_none()
+                {
+                    finalized
+                } else {
+                    sync_target_head
+                };
+                self.emit_event(EngineApiEvent::BackfillAction(BackfillAction::Start(
+                    target.into(),
+                )));
+            }
+        }
+
+        Ok(())
     }
 
     /// Attempts to make the given target canonical.
@@ -1722,11 +1846,29 @@ where
     fn make_canonical(&mut self, target: B256) -> ProviderResult<()> {
         if let Some(chain_update) = self.on_new_head(target)? {
             self.on_canonical_chain_update(chain_update);
+            self.update_safe_and_finalized_from_forkchoice();
         }
 
         Ok(())
     }
 
+    /// Updates the safe and finalized blocks to the ones of the latest forkchoice state.
+    ///
+    /// A forkchoice update that arrives before its blocks are known is answered with `SYNCING`
+    /// and can't update the safe and finalized blocks. Once the blocks are downloaded and made
+    /// canonical, this applies them, so the `safe` and `finalized` block tags served over RPC
+    /// match the consensus layer without waiting for the next forkchoice update.
+    fn update_safe_and_finalized_from_forkchoice(&self) {
+        if self.state.forkchoice_state_tracker.is_latest_invalid() {
+            return
+        }
+        let Some(state) = self.state.forkchoice_state_tracker.latest_state() else { return };
+
+        // blocks that aren't canonical yet are applied on a later canonical update
+        let _ = self.update_finalized_block(state.finalized_block_hash);
+        let _ = self.update_safe_block(state.safe_block_hash);
+    }
+
     /// Convenience function to handle an optional tree event.
     fn on_maybe_tree_event(&mut self, event: Option<TreeEvent>) -> ProviderResult<()> {
         if let Some(event) = event {
@@ -1799,6 +1941,23 @@ where
             self.config.persistence_threshold()
     }
 
+    /// Returns true if persisted blocks that are no longer canonical should be removed from the
+    /// database now.
+    ///
+    /// A reorg to a sibling chain that is at least as high as the last persisted block is handled
+    /// entirely in memory: blocks on top of the new chain are executed against the persisted fork
+    /// point, so the reorged out blocks are only removed from the database once the next batch of
+    /// blocks is flushed. This avoids database unwinds for shallow reorgs, and skips the unwind
+    /// entirely if the chain reorgs back before the next flush.
+    ///
+    /// If the canonical head moved below the last persisted block, the database is unwound right
+    /// away.
+    const fn should_reconcile_disk_reorg(&self) -> bool {
+        self.should_persist() ||
+            self.persistence_state.last_persisted_block.number >
+                self.state.tree_state.canonical_block_number()
+    }
+
     /// Returns a batch of consecutive canonical blocks to persist in the range
     /// `(last_persisted_number .. target]`. The expected order is oldest -> newest.
     fn get_canonical_blocks_to_persist(
@@ -1856,9 +2015,12 @@ where
     /// Assumes that `finish` has been called on the `persistence_state` at least once
     fn on_new_persisted_block(&mut self) -> ProviderResult<()> {
         // If we have an on-disk reorg, we need to handle it first before touching the in-memory
-        // state.
+        // state. Until the database is reconciled, the canonical blocks that replaced the
+        // persisted ones are only held in memory.
         if let Some(remove_above) = self.find_disk_reorg()? {
-            self.remove_blocks(remove_above);
+            if self.should_reconcile_disk_reorg() {
+                self.remove_blocks(remove_above);
+            }
             return Ok(())
         }
 
@@ -2313,7 +2475,8 @@ where
     }
 
     /// This method tries to detect whether on-disk and in-memory states have diverged. It might
-    /// happen if a reorg is happening while we are persisting a block.
+    /// happen if a reorg is happening while we are persisting a block, or if a reorg replaced
+    /// persisted blocks and the database has not been reconciled yet.
     fn find_disk_reorg(&self) -> ProviderResult<Option<u64>> {
         let mut canonical = self.state.tree_state.current_canonical_head;
         let mut persisted = self.persistence_state.last_persisted_block;
@@ -2340,9 +2503,21 @@ where
         // chain back. In this case we need to truncate it to the first canonical block it connects
         // to.
 
+        // The provider doesn't serve persisted blocks that were reorged out, so they are read from
+        // the database directly.
+        let persisted_parent_num_hash = |num_hash: NumHash| -> ProviderResult<NumHash> {
+            let header = match self.sealed_header_by_hash(num_hash.hash)? {
+                Some(header) => Some(header),
+                None => {
+                    self.provider.database_provider_ro()?.sealed_header_by_hash(num_hash.hash)?
+                }
+            };
+            Ok(header.ok_or(ProviderError::BlockHashNotFound(num_hash.hash))?.parent_num_hash())
+        };
+
         // Firstly, walk back until we reach the same height as `canonical`.
         while persisted.number > canonical.number {
-            persisted = parent_num_hash(persisted)?;
+            persisted = persisted_parent_num_hash(persisted)?;
         }
 
         debug_assert_eq!(persisted.number, canonical.number);
@@ -2350,7 +2525,7 @@ where
         // Now walk both chains back until we find a common ancestor.
         while persisted.hash != canonical.hash {
             canonical = parent_num_hash(canonical)?;
-            persisted = parent_num_hash(persisted)?;
+            persisted = persisted_parent_num_hash(persisted)?;
         }
 
         debug!(target: "engine::tree", remove_above=persisted.number, "on-disk reorg detected");
@@ -2377,15 +2552,29 @@ where
             let old_first = old.first().map(|first| first.recovered_block().num_hash());
             trace!(target: "engine::tree", ?new_first, ?old_first, "Reorg detected, new and old first blocks");
 
+            if let Some(new_first) = new_first {
+                self.compaction.on_reorg(new_first.number);
+            }
             self.update_reorg_metrics(old.len());
             self.reinsert_reorged_blocks(new.clone());
             self.reinsert_reorged_blocks(old.clone());
+            self.update_in_memory_block_metrics();
         }
 
+        // record the timings of the blocks that became canonical
+        let (NewCanonicalChain::Commit { new } | NewCanonicalChain::Reorg { new, .. }) =
+            &chain_update;
+        self.canonical_in_memory_state
+            .block_timings()
+            .on_canonical(new.iter().map(|block| block.recovered_block().hash()));
+
         // update the tracked in-memory state with the new chain
         self.canonical_in_memory_state.update_chain(chain_update);
         self.canonical_in_memory_state.set_canonical_head(tip.clone());
 
+        // compact the execution outputs of blocks that are unlikely to be reorged
+        self.compact_execution_outputs(tip.number());
+
         // Update metrics based on new tip
         self.metrics.tree.canonical_chain_height.set(tip.number() as f64);
 
@@ -2399,10 +2588,34 @@ where
         ));
     }
 
+    /// Compacts the execution outputs of in-memory blocks that are more than the configured
+    /// compaction depth below the canonical head.
+    ///
+    /// Compaction runs off the engine loop, the outputs compacted in the meantime are shared with
+    /// the tree state here.
+    fn compact_execution_outputs(&mut self, canonical_head: BlockNumber) {
+        for outputs in self.compaction.compacted() {
+            self.state.tree_state.replace_execution_outputs(&outputs);
+        }
+
+        let Some(compact_height) =
+            canonical_head.checked_sub(self.config.execution_output_compaction_depth())
+        else {
+            return
+        };
+        self.compaction.compact(&self.canonical_in_memory_state, compact_height);
+    }
+
     /// This updates metrics based on the given reorg length.
     fn update_reorg_metrics(&self, old_chain_length: usize) {
         self.metrics.tree.reorgs.increment(1);
         self.metrics.tree.latest_reorg_depth.set(old_chain_length as f64);
+        self.metrics.engine_tree.reorg_depth.record(old_chain_length as f64);
+    }
+
+    /// Updates the metric for the number of executed blocks kept in memory.
+    fn update_in_memory_block_metrics(&self) {
+        self.metrics.engine_tree.in_memory_blocks.set(self.state.tree_state.block_count() as f64);
     }
 
     /// This reinserts any blocks in the new chain that do not already exist in the tree
@@ -2652,6 +2865,7 @@ where
 
         self.state.tree_state.insert_executed(executed.clone());
         self.metrics.engine.executed_blocks.set(self.state.tree_state.block_count() as f64);
+        self.update_in_memory_block_metrics();
 
         // emit insert event
         let elapsed = start.elapsed();
@@ -2662,10 +2876,9 @@ where
         };
         self.emit_event(EngineApiEvent::BeaconConsensus(engine_event));
 
-        self.metrics
-            .engine
-            .block_insert_total_duration
-            .record(block_insert_start.elapsed().as_secs_f64());
+        let block_insert_elapsed = block_insert_start.elapsed();
+        self.metrics.engine.block_insert_total_duration.record(block_insert_elapsed.as_secs_f64());
+        self.metrics.engine_tree.insert_block_duration.record(block_insert_elapsed);
         debug!(target: "engine::tree", block=?block_num_hash, "Finished inserting block");
         Ok(InsertPayloadOk::Inserted(BlockStatus::Valid))
     }
@@ -2915,6 +3128,7 @@ where
             self.persistence_state.last_persisted_block.hash,
             num,
         );
+        self.update_in_memory_block_metrics();
         Ok(())
     }
 
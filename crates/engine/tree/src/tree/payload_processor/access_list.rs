@@ -0,0 +1,249 @@
+//! Access list (EIP-2930) based prefetching.
+//!
+//! The sender, the recipient and the access list of a transaction are known before the transaction
+//! is executed. Loading these accounts and storage slots into the execution cache, and fetching the
+//! proofs of their trie nodes, while the preceding transactions are still executing hides the
+//! latency of the database reads from the block execution and the state root computation.
+//!
+//! The code of the recipient is additionally scanned for hardcoded addresses and storage slots,
+//! which are speculatively loaded into the cache.
+
+use crate::tree::payload_processor::multiproof::VersionedMultiProofTargets;
+use alloy_consensus::{constants::KECCAK_EMPTY, Transaction};
+use alloy_primitives::{
+    keccak256,
+    map::{AddressMap, B256Set},
+    Address, B256,
+};
+use reth_primitives_traits::Bytecode;
+use reth_provider::{ProviderResult, StateProvider};
+use reth_trie::MultiProofTargets;
+use revm::bytecode::opcode;
+
+/// Maximum number of hardcoded addresses that are prefetched from the code of a recipient.
+const MAX_CODE_ADDRESSES: usize = 16;
+
+/// Maximum number of hardcoded storage slots that are prefetched from the code of a recipient.
+const MAX_CODE_SLOTS: usize = 64;
+
+/// The accounts and storage slots that a transaction is expected to touch.
+#[derive(Debug)]
+pub(super) struct AccessListTargets {
+    /// The recipient of the transaction, if it's a call.
+    recipient: Option<Address>,
+    /// The sender, the recipient and the access list entries of the transaction.
+    ///
+    /// These are touched by the transaction, so their proofs are prefetched as well.
+    declared: AddressMap<B256Set>,
+    /// The hardcoded addresses and storage slots found in the code of the recipient.
+    ///
+    /// These may not be touched by the transaction, so they are only loaded into the cache.
+    speculative: AddressMap<B256Set>,
+}
+
+impl AccessListTargets {
+    /// Collects the sender, the recipient and the access list entries of the transaction.
+    pub(super) fn new<T: Transaction>(tx: &T, sender: Address) -> Self {
+        let recipient = tx.to();
+
+        let mut declared = AddressMap::default();
+        declared.entry(sender).or_insert_with(B256Set::default);
+        if let Some(recipient) = recipient {
+            declared.entry(recipient).or_insert_with(B256Set::default);
+        }
+        for item in tx.access_list().into_iter().flatten() {
+            declared
+                .entry(item.address)
+                .or_insert_with(B256Set::default)
+                .extend(&item.storage_keys);
+        }
+
+        Self { recipient, declared, speculative: AddressMap::default() }
+    }
+
+    /// Returns the number of declared storage slots.
+    pub(super) fn storage_targets_len(&self) -> usize {
+        self.declared.values().map(|slots| slots.len()).sum()
+    }
+
+    /// Loads the targets into the cache of the given state provider.
+    ///
+    /// The code of the recipient is analyzed first, and the hardcoded addresses and storage slots
+    /// it references are loaded as well.
+    pub(super) fn prefetch<P: StateProvider>(&mut self, provider: &P) -> ProviderResult<()> {
+        if let Some(recipient) = self.recipient &&
+            let Some(code) = load_account(provider, recipient)?
+        {
+            let (addresses, slots) = analyze_code(code.original_byte_slice());
+            for address in addresses {
+                if !self.declared.contains_key(&address) {
+                    self.speculative.entry(address).or_insert_with(B256Set::default);
+                }
+            }
+            let declared = &self.declared[&recipient];
+            self.speculative
+                .entry(recipient)
+                .or_insert_with(B256Set::default)
+                .extend(slots.into_iter().filter(|slot| !declared.contains(slot)));
+        }
+
+        for (address, slots) in self.declared.iter().chain(&self.speculative) {
+            // the code of the recipient was already loaded above
+            if Some(*address) != self.recipient {
+                load_account(provider, *address)?;
+            }
+            for slot in slots {
+                provider.storage(*address, *slot)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the proof targets of the declared accounts and storage slots.
+    pub(super) fn into_multiproof_targets(self, v2_enabled: bool) -> VersionedMultiProofTargets {
+        if v2_enabled {
+            use reth_trie::proof_v2;
+            use reth_trie_parallel::targets_v2::MultiProofTargetsV2;
+
+            let mut targets = MultiProofTargetsV2::default();
+            for (address, slots) in self.declared {
+                let hashed_address = keccak256(address);
+                targets.account_targets.push(hashed_address.into());
+                if !slots.is_empty() {
+                    targets.storage_targets.insert(
+                        hashed_address,
+                        slots
+                            .into_iter()
+                            .map(|slot| proof_v2::Target::from(keccak256(slot)))
+                            .collect(),
+                    );
+                }
+            }
+            VersionedMultiProofTargets::V2(targets)
+        } else {
+            let mut targets = MultiProofTargets::with_capacity(self.declared.len());
+            for (address, slots) in self.declared {
+                targets.insert(keccak256(address), slots.into_iter().map(keccak256).collect());
+            }
+            VersionedMultiProofTargets::Legacy(targets)
+        }
+    }
+}
+
+/// Loads the account and its code into the cache of the state provider, returning the code if the
+/// account has any.
+fn load_account<P: StateProvider>(
+    provider: &P,
+    address: Address,
+) -> ProviderResult<Option<Bytecode>> {
+    let Some(code_hash) = provider
+        .basic_account(&address)?
+        .and_then(|account| account.bytecode_hash)
+        .filter(|code_hash| *code_hash != KECCAK_EMPTY)
+    else {
+        return Ok(None)
+    };
+    provider.bytecode_by_hash(&code_hash)
+}
+
+/// Scans the code for addresses that are pushed with `PUSH20`, and for storage slots that are
+/// pushed right before an `SLOAD`.
+///
+/// This is a linear scan without any control flow analysis, so the results are only a guess of the
+/// state that is accessed by the code.
+fn analyze_code(code: &[u8]) -> (Vec<Address>, Vec<B256>) {
+    let mut addresses = Vec::new();
+    let mut slots = Vec::new();
+
+    let mut pc = 0;
+    while pc < code.len() {
+        let op = code[pc];
+        pc += 1;
+        if !(opcode::PUSH0..=opcode::PUSH32).contains(&op) {
+            continue
+        }
+
+        let immediate = &code[pc..(pc + (op - opcode::PUSH0) as usize).min(code.len())];
+        pc += immediate.len();
+
+        if code.get(pc) == Some(&opcode::SLOAD) {
+            let slot = B256::left_padding_from(immediate);
+            if slots.len() < MAX_CODE_SLOTS && !slots.contains(&slot) {
+                slots.push(slot);
+            }
+        } else if op == opcode::PUSH20 && immediate.len() == 20 {
+            let address = Address::from_slice(immediate);
+            // masks and precompiles are pushed with `PUSH20` as well
+            if address.0[..18] != [0; 18] &&
+                address != Address::repeat_byte(0xff) &&
+                addresses.len() < MAX_CODE_ADDRESSES &&
+                !addresses.contains(&address)
+            {
+                addresses.push(address);
+            }
+        }
+    }
+
+    (addresses, slots)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::TxEip2930;
+    use alloy_eips::eip2930::{AccessList, AccessListItem};
+    use alloy_primitives::{address, b256, hex, TxKind};
+
+    #[test]
+    fn access_list_targets() {
+        let sender = address!("0x0000000000000000000000000000000000001111");
+        let recipient = address!("0x0000000000000000000000000000000000002222");
+        let token = address!("0x0000000000000000000000000000000000003333");
+        let slot = b256!("0x0000000000000000000000000000000000000000000000000000000000000007");
+
+        let tx = TxEip2930 {
+            to: TxKind::Call(recipient),
+            access_list: AccessList(vec![
+                AccessListItem { address: token, storage_keys: vec![slot, slot] },
+                AccessListItem { address: recipient, storage_keys: vec![] },
+            ]),
+            ..Default::default()
+        };
+        let targets = AccessListTargets::new(&tx, sender);
+        assert_eq!(targets.recipient, Some(recipient));
+        assert_eq!(targets.declared.len(), 3);
+        assert_eq!(targets.storage_targets_len(), 1);
+
+        let VersionedMultiProofTargets::Legacy(proof_targets) =
+            targets.into_multiproof_targets(false)
+        else {
+            panic!("expected legacy targets")
+        };
+        assert_eq!(proof_targets.len(), 3);
+        assert_eq!(proof_targets[&keccak256(token)], B256Set::from_iter([keccak256(slot)]));
+        assert!(proof_targets[&keccak256(sender)].is_empty());
+    }
+
+    #[test]
+    fn analyze_code_targets() {
+        // PUSH1 0x02 SLOAD
+        // PUSH0 SLOAD
+        // PUSH20 0xdac17f958d2ee523a2206206994597c13d831ec7 EXTCODESIZE
+        // PUSH20 0xff..ff AND
+        // PUSH20 0x00..01 STATICCALL
+        // PUSH32 <truncated>
+        let code = [
+            &hex!("600254")[..],
+            &hex!("5f54"),
+            &hex!("73dac17f958d2ee523a2206206994597c13d831ec73b"),
+            &hex!("73ffffffffffffffffffffffffffffffffffffffff16"),
+            &hex!("730000000000000000000000000000000000000001fa"),
+            &hex!("7f0102"),
+        ]
+        .concat();
+        let (addresses, slots) = analyze_code(&code);
+        assert_eq!(addresses, [address!("0xdac17f958d2ee523a2206206994597c13d831ec7")]);
+        assert_eq!(slots, [B256::with_last_byte(2), B256::ZERO]);
+    }
+}
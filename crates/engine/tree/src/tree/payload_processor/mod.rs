@@ -56,6 +56,7 @@ use std::{
 };
 use tracing::{debug, debug_span, instrument, warn, Span};
 
+mod access_list;
 pub mod bal;
 pub mod executor;
 pub mod multiproof;
@@ -117,6 +118,8 @@ where
     cross_block_cache_size: usize,
     /// Whether transactions should not be executed on prewarming task.
     disable_transaction_prewarming: bool,
+    /// Whether the state touched by the access lists of transactions should not be prefetched.
+    disable_access_list_prefetch: bool,
     /// Whether state cache should be disable
     disable_state_cache: bool,
     /// Determines how to configure the evm for execution.
@@ -159,6 +162,7 @@ where
             trie_metrics: Default::default(),
             cross_block_cache_size: config.cross_block_cache_size(),
             disable_transaction_prewarming: config.disable_prewarming(),
+            disable_access_list_prefetch: config.disable_access_list_prefetch(),
             evm_config,
             disable_state_cache: config.disable_state_cache(),
             precompile_cache_disabled: config.precompile_cache_disabled(),
@@ -444,6 +448,7 @@ where
             precompile_cache_disabled: self.precompile_cache_disabled,
             precompile_cache_map: self.precompile_cache_map.clone(),
             v2_proofs_enabled,
+            access_list_prefetch_disabled: self.disable_access_list_prefetch,
         };
 
         let (prewarm_task, to_prewarm_task) = PrewarmCacheTask::new(
@@ -10,10 +10,14 @@
 //!    and one for actual execution (executed sequentially)
 //! 2. Prewarming tasks execute transactions in parallel using shared caches
 //! 3. When actual block execution happens, it benefits from the warmed cache
+//!
+//! Alongside the prewarming tasks, the state declared by the access lists of the transactions is
+//! prefetched without executing them, see the `access_list` module.
 
 use crate::tree::{
     cached_state::{CachedStateProvider, SavedCache},
     payload_processor::{
+        access_list::AccessListTargets,
         bal::{total_slots, BALSlotIter},
         executor::WorkloadExecutor,
         multiproof::{MultiProofMessage, VersionedMultiProofTargets},
@@ -166,6 +170,11 @@ where
             // Spawn workers
             let tx_sender = ctx.clone().spawn_workers(workers_needed, &executor, actions_tx.clone(), done_tx.clone());
 
+            // Spawn the access list prefetcher, which runs ahead of the workers
+            let prefetch_sender = (!ctx.access_list_prefetch_disabled).then(|| {
+                ctx.clone().spawn_access_list_prefetcher(&executor, actions_tx.clone(), done_tx.clone())
+            });
+
             // Distribute transactions to workers
             let mut tx_index = 0usize;
             while let Ok(tx) = pending.recv() {
@@ -178,6 +187,10 @@ where
                     break;
                 }
 
+                if let Some(prefetch_sender) = &prefetch_sender {
+                    let _ = prefetch_sender.send(tx.clone());
+                }
+
                 let indexed_tx = IndexedTransaction { index: tx_index, tx };
 
                 // Send transaction to the workers
@@ -191,6 +204,7 @@ where
             // drop sender and wait for all tasks to finish
             drop(done_tx);
             drop(tx_sender);
+            drop(prefetch_sender);
             while done_rx.recv().is_ok() {}
 
             let _ = actions_tx
@@ -453,6 +467,8 @@ where
     pub(super) precompile_cache_map: PrecompileCacheMap<SpecFor<Evm>>,
     /// Whether V2 proof calculation is enabled.
     pub(super) v2_proofs_enabled: bool,
+    /// Whether prefetching the state touched by the access lists of transactions is disabled.
+    pub(super) access_list_prefetch_disabled: bool,
 }
 
 impl<N, P, Evm> PrewarmContext<N, P, Evm>
@@ -477,6 +493,7 @@ where
             precompile_cache_disabled,
             precompile_cache_map,
             v2_proofs_enabled,
+            access_list_prefetch_disabled: _,
         } = self;
 
         let mut state_provider = match provider.build() {
@@ -665,6 +682,97 @@ where
         tx_sender
     }
 
+    /// Spawns a worker task that prefetches the state touched by the access lists of the
+    /// transactions, ahead of their execution by the prewarm workers.
+    ///
+    /// Returns the sender for feeding transactions to the worker.
+    fn spawn_access_list_prefetcher<Tx>(
+        self,
+        task_executor: &WorkloadExecutor,
+        actions_tx: Sender<PrewarmTaskEvent<N::Receipt>>,
+        done_tx: Sender<()>,
+    ) -> Sender<Tx>
+    where
+        Tx: ExecutableTxFor<Evm> + Send + 'static,
+    {
+        let (tx_sender, tx_receiver) = mpsc::channel();
+
+        let span = debug_span!(target: "engine::tree::payload_processor::prewarm", "access list prefetcher");
+        task_executor.spawn_blocking(move || {
+            let _enter = span.entered();
+            self.prefetch_access_lists(tx_receiver, actions_tx, done_tx);
+        });
+
+        tx_sender
+    }
+
+    /// Loads the accounts and storage slots that the transactions declare into the cache, and
+    /// streams [`PrewarmTaskEvent::Outcome`] messages with their proof targets.
+    ///
+    /// See [`AccessListTargets`] for the prefetched state.
+    #[instrument(level = "debug", target = "engine::tree::payload_processor::prewarm", skip_all)]
+    fn prefetch_access_lists<Tx>(
+        self,
+        txs: Receiver<Tx>,
+        sender: Sender<PrewarmTaskEvent<N::Receipt>>,
+        done_tx: Sender<()>,
+    ) where
+        Tx: ExecutableTxFor<Evm>,
+    {
+        let Self { saved_cache, provider, metrics, terminate_execution, v2_proofs_enabled, .. } =
+            self;
+
+        // Without a cache the loaded state can't be reused by the execution, so only the proof
+        // targets are sent
+        let state_provider = saved_cache.and_then(|saved_cache| match provider.build() {
+            Ok(state_provider) => Some(
+                CachedStateProvider::new(
+                    state_provider,
+                    saved_cache.cache().clone(),
+                    saved_cache.metrics().clone(),
+                )
+                .prewarm(),
+            ),
+            Err(err) => {
+                trace!(
+                    target: "engine::tree::payload_processor::prewarm",
+                    %err,
+                    "Failed to build state provider in access list prefetch thread"
+                );
+                None
+            }
+        });
+
+        while let Ok(tx) = txs.recv() {
+            if terminate_execution.load(Ordering::Relaxed) {
+                break
+            }
+
+            let start = Instant::now();
+
+            let mut targets = AccessListTargets::new(tx.tx(), *tx.signer());
+            if let Some(state_provider) = &state_provider &&
+                let Err(err) = targets.prefetch(state_provider)
+            {
+                trace!(
+                    target: "engine::tree::payload_processor::prewarm",
+                    %err,
+                    tx_hash=%tx.tx().tx_hash(),
+                    "Failed to prefetch access list"
+                );
+            }
+
+            metrics.access_list_storage_targets.record(targets.storage_targets_len() as f64);
+            let proof_targets = targets.into_multiproof_targets(v2_proofs_enabled);
+            let _ = sender.send(PrewarmTaskEvent::Outcome { proof_targets: Some(proof_targets) });
+
+            metrics.access_list_prefetch_duration.record(start.elapsed());
+        }
+
+        // send a message to the main task to flag that we're done
+        let _ = done_tx.send(());
+    }
+
     /// Spawns a worker task for BAL slot prefetching.
     ///
     /// The worker iterates over the specified range of slots in the BAL and ensures
@@ -899,4 +1007,8 @@ pub(crate) struct PrewarmMetrics {
     pub(crate) transaction_errors: Counter,
     /// A histogram of BAL slot iteration duration during prefetching
     pub(crate) bal_slot_iteration_duration: Histogram,
+    /// A histogram of duration per transaction access list prefetching
+    pub(crate) access_list_prefetch_duration: Histogram,
+    /// A histogram for declared storage slots per transaction access list
+    pub(crate) access_list_storage_targets: Histogram,
 }
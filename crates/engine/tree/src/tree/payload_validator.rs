@@ -690,7 +690,15 @@ where
             .build();
 
         let spec_id = *env.evm_env.spec_id();
+        #[cfg(not(feature = "execution-stats"))]
         let evm = self.evm_config.evm_with_env(&mut db, env.evm_env);
+        // Collect the opcode and precompile usage of the block while executing it
+        #[cfg(feature = "execution-stats")]
+        let evm = self.evm_config.evm_with_env_and_inspector(
+            &mut db,
+            env.evm_env,
+            reth_revm::execution_stats::ExecutionStatsInspector::default(),
+        );
         let ctx =
             self.execution_ctx_for(input).map_err(|e| InsertBlockErrorKind::Other(Box::new(e)))?;
         let mut executor = self.evm_config.create_executor(evm, ctx);
@@ -743,7 +751,16 @@ where
         let post_exec_start = Instant::now();
         let (_evm, result) = debug_span!(target: "engine::tree", "finish")
             .in_scope(|| executor.finish())
-            .map(|(evm, result)| (evm.into_db(), result))?;
+            .map(|(evm, result)| {
+                #[cfg(feature = "execution-stats")]
+                {
+                    let stats = evm.inspector().stats();
+                    self.metrics.record_execution_stats(stats);
+                    reth_revm::execution_stats::ExecutionStatsStore::global()
+                        .insert(input.hash(), stats.clone());
+                }
+                (evm.into_db(), result)
+            })?;
         self.metrics.record_post_execution(post_exec_start.elapsed());
 
         // Merge transitions into bundle state
@@ -7,10 +7,12 @@ use alloy_primitives::{
     BlockNumber, B256,
 };
 use reth_chain_state::{EthPrimitives, ExecutedBlock};
+use reth_execution_types::BlockExecutionOutput;
 use reth_primitives_traits::{AlloyBlockHeader, NodePrimitives, SealedHeader};
 use std::{
     collections::{btree_map, hash_map, BTreeMap, VecDeque},
     ops::Bound,
+    sync::Arc,
 };
 use tracing::debug;
 
@@ -290,6 +292,30 @@ impl<N: NodePrimitives> TreeState<N> {
         }
     }
 
+    /// Replaces the execution outputs of the given blocks, e.g. with compacted copies of them.
+    ///
+    /// Blocks that are no longer tracked are skipped.
+    pub(crate) fn replace_execution_outputs(
+        &mut self,
+        outputs: &HashMap<B256, Arc<BlockExecutionOutput<N::Receipt>>>,
+    ) {
+        for (hash, output) in outputs {
+            let Some(block) = self.blocks_by_hash.get_mut(hash) else { continue };
+            block.execution_output = output.clone();
+
+            let number = block.recovered_block().number();
+            for block in self
+                .blocks_by_number
+                .get_mut(&number)
+                .into_iter()
+                .flatten()
+                .filter(|block| block.recovered_block().hash() == *hash)
+            {
+                block.execution_output = output.clone();
+            }
+        }
+    }
+
     /// Updates the canonical head to the given block.
     pub(crate) const fn set_canonical_head(&mut self, new_head: BlockNumHash) {
         self.current_canonical_head = new_head;
@@ -358,7 +384,7 @@ impl<N: NodePrimitives> TreeState<N> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use reth_chain_state::test_utils::TestBlockBuilder;
+    use reth_chain_state::{compact_execution_output, test_utils::TestBlockBuilder};
 
     #[test]
     fn test_tree_state_normal_descendant() {
@@ -448,6 +474,36 @@ mod tests {
         assert_eq!(tree_state.blocks_by_number[&5].len(), 2);
     }
 
+    #[tokio::test]
+    async fn test_tree_state_replace_execution_outputs() {
+        let start_num_hash = BlockNumHash::default();
+        let mut tree_state = TreeState::new(start_num_hash, EngineApiKind::Ethereum);
+        let blocks: Vec<_> = TestBlockBuilder::eth().get_executed_blocks(1..6).collect();
+
+        for block in &blocks {
+            tree_state.insert_executed(block.clone());
+        }
+
+        let outputs: HashMap<_, _> = blocks[..2]
+            .iter()
+            .map(|block| {
+                let mut output = block.execution_output.clone();
+                compact_execution_output(Arc::make_mut(&mut output));
+                (block.recovered_block().hash(), output)
+            })
+            .collect();
+        tree_state.replace_execution_outputs(&outputs);
+
+        for block in &blocks {
+            let hash = block.recovered_block().hash();
+            let expected = outputs.get(&hash).unwrap_or(&block.execution_output);
+            assert!(Arc::ptr_eq(&tree_state.blocks_by_hash[&hash].execution_output, expected));
+            assert!(tree_state.blocks_by_number[&block.recovered_block().number()]
+                .iter()
+                .all(|b| Arc::ptr_eq(&b.execution_output, expected)));
+        }
+    }
+
     #[tokio::test]
     async fn test_tree_state_remove_before() {
         let start_num_hash = BlockNumHash::default();
@@ -691,6 +691,38 @@ async fn test_holesky_payload() {
     assert!(resp.is_syncing());
 }
 
+#[tokio::test]
+async fn test_make_canonical_applies_forkchoice_safe_and_finalized() {
+    reth_tracing::init_test_tracing();
+    let chain_spec = MAINNET.clone();
+    let mut test_harness = TestHarness::new(chain_spec);
+    let blocks: Vec<_> = test_harness.block_builder.get_executed_blocks(0..4).collect();
+    test_harness = test_harness.with_blocks(blocks[..2].to_vec());
+    for block in &blocks[2..] {
+        test_harness.tree.state.tree_state.insert_executed(block.clone());
+    }
+
+    // the forkchoice update arrived before the blocks were known
+    let state = ForkchoiceState {
+        head_block_hash: blocks[3].recovered_block().hash(),
+        safe_block_hash: blocks[2].recovered_block().hash(),
+        finalized_block_hash: blocks[1].recovered_block().hash(),
+    };
+    test_harness.tree.state.forkchoice_state_tracker.set_latest(state, ForkchoiceStatus::Syncing);
+    assert_eq!(test_harness.tree.canonical_in_memory_state.get_safe_num_hash(), None);
+
+    test_harness.tree.make_canonical(blocks[3].recovered_block().hash()).unwrap();
+
+    assert_eq!(
+        test_harness.tree.canonical_in_memory_state.get_safe_num_hash(),
+        Some(blocks[2].recovered_block().num_hash())
+    );
+    assert_eq!(
+        test_harness.tree.canonical_in_memory_state.get_finalized_num_hash(),
+        Some(blocks[1].recovered_block().num_hash())
+    );
+}
+
 #[tokio::test]
 async fn test_tree_state_on_new_head_reorg() {
     reth_tracing::init_test_tracing();
@@ -877,6 +909,53 @@ fn test_tree_state_on_new_head_deep_fork() {
     }
 }
 
+#[tokio::test]
+async fn test_sibling_reorg_defers_disk_reorg_until_flush() {
+    reth_tracing::init_test_tracing();
+    let chain_spec = MAINNET.clone();
+    let mut test_block_builder = TestBlockBuilder::eth();
+    let blocks: Vec<_> = test_block_builder.get_executed_blocks(0..4).collect();
+    let mut test_harness = TestHarness::new(chain_spec).with_blocks(blocks.clone());
+    test_harness.tree.config =
+        TreeConfig::default().with_persistence_threshold(2).with_memory_block_buffer_target(0);
+
+    // blocks up to the canonical head are persisted
+    test_harness.tree.persistence_state.last_persisted_block =
+        blocks[3].recovered_block().num_hash();
+
+    // reorg to a sibling of the persisted head
+    let fork_block_3 =
+        test_block_builder.get_executed_block_with_number(3, blocks[2].recovered_block().hash());
+    test_harness.tree.state.tree_state.insert_executed(fork_block_3.clone());
+    test_harness.tree.make_canonical(fork_block_3.recovered_block().hash()).unwrap();
+    assert_eq!(test_harness.tree.find_disk_reorg().unwrap(), Some(2));
+
+    // the database is not unwound until the next flush
+    test_harness.tree.advance_persistence().unwrap();
+    assert_eq!(test_harness.tree.persistence_state.current_action(), None);
+
+    // reorging back doesn't require any database change
+    test_harness.tree.make_canonical(blocks[3].recovered_block().hash()).unwrap();
+    assert_eq!(test_harness.tree.find_disk_reorg().unwrap(), None);
+
+    // extend the sibling chain until blocks need to be flushed
+    test_harness.tree.make_canonical(fork_block_3.recovered_block().hash()).unwrap();
+    let mut parent = fork_block_3.recovered_block().hash();
+    for number in 4..7 {
+        let block = test_block_builder.get_executed_block_with_number(number, parent);
+        parent = block.recovered_block().hash();
+        test_harness.tree.state.tree_state.insert_executed(block);
+    }
+    test_harness.tree.make_canonical(parent).unwrap();
+
+    // the reorged out block is removed before the new chain is saved
+    test_harness.tree.advance_persistence().unwrap();
+    assert_eq!(
+        test_harness.tree.persistence_state.current_action().cloned(),
+        Some(CurrentPersistenceAction::RemovingBlocks { new_tip_num: 2 })
+    );
+}
+
 #[tokio::test]
 async fn test_get_canonical_blocks_to_persist() {
     let chain_spec = MAINNET.clone();
@@ -965,6 +1044,49 @@ async fn test_engine_tree_fcu_missing_head() {
     }
 }
 
+#[tokio::test]
+async fn test_engine_tree_recover_head() {
+    let chain_spec = MAINNET.clone();
+    let mut test_harness = TestHarness::new(chain_spec.clone());
+
+    let mut test_block_builder = TestBlockBuilder::eth().with_chain_spec((*chain_spec).clone());
+
+    let blocks: Vec<_> = test_block_builder.get_executed_blocks(0..5).collect();
+    test_harness = test_harness.with_blocks(blocks);
+
+    let missing_block = test_block_builder
+        .generate_random_block(6, test_harness.blocks.last().unwrap().recovered_block().hash());
+
+    test_harness.fcu_to(missing_block.hash(), PayloadStatusEnum::Syncing).await;
+    let _download = test_harness.from_tree_rx.recv().await.unwrap();
+
+    // a backfill sync was requested but never started
+    test_harness.tree.backfill_sync_state = BackfillSyncState::Pending;
+    test_harness.tree.on_recover_head(RecoveryAction::RequestSyncTarget).unwrap();
+    assert!(test_harness.from_tree_rx.try_recv().is_err());
+
+    // restarting resets the pending backfill sync and requests the sync target again
+    test_harness.tree.on_recover_head(RecoveryAction::RestartEngine).unwrap();
+    assert!(test_harness.tree.backfill_sync_state.is_idle());
+    let event = test_harness.from_tree_rx.recv().await.unwrap();
+    match event {
+        EngineApiEvent::Download(DownloadRequest::BlockSet(actual_block_set)) => {
+            assert_eq!(actual_block_set, HashSet::from_iter([missing_block.hash()]));
+        }
+        _ => panic!("Unexpected event: {event:#?}"),
+    }
+
+    // the finalized block of the sync target is not on disk yet
+    test_harness.tree.on_recover_head(RecoveryAction::Backfill).unwrap();
+    let event = test_harness.from_tree_rx.recv().await.unwrap();
+    match event {
+        EngineApiEvent::BackfillAction(BackfillAction::Start(target)) => {
+            assert_eq!(target.sync_target(), Some(missing_block.hash()));
+        }
+        _ => panic!("Unexpected event: {event:#?}"),
+    }
+}
+
 #[tokio::test]
 async fn test_engine_tree_live_sync_transition_required_blocks_requested() {
     reth_tracing::init_test_tracing();
@@ -0,0 +1,238 @@
+//! Watchdog that detects a stalled canonical head and escalates recovery actions.
+
+use alloy_eips::BlockNumHash;
+use reth_engine_primitives::HeadWatchdogConfig;
+use std::{
+    fmt::{Display, Formatter, Result},
+    time::{Duration, Instant},
+};
+
+/// The interval at which the [`HeadWatchdog`] should be checked.
+pub const HEAD_WATCHDOG_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// An action to recover a stalled canonical head.
+///
+/// The actions are ordered by how disruptive they are, the [`HeadWatchdog`] escalates to the next
+/// action if the head remains stalled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RecoveryAction {
+    /// Request the missing blocks up to the head of the forkchoice sync target from the network.
+    RequestSyncTarget,
+    /// Restart the sync of the engine: in-flight downloads are dropped, the buffered blocks are
+    /// discarded and a pending backfill sync that never started is reset, before the sync target
+    /// is requested again.
+    RestartEngine,
+    /// Run a backfill sync to the forkchoice sync target.
+    Backfill,
+}
+
+impl RecoveryAction {
+    /// Returns the action to escalate to if this action didn't recover the head.
+    pub const fn escalate(self) -> Self {
+        match self {
+            Self::RequestSyncTarget => Self::RestartEngine,
+            Self::RestartEngine | Self::Backfill => Self::Backfill,
+        }
+    }
+}
+
+impl Display for RecoveryAction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Self::RequestSyncTarget => write!(f, "request sync target"),
+            Self::RestartEngine => write!(f, "restart engine"),
+            Self::Backfill => write!(f, "backfill"),
+        }
+    }
+}
+
+/// Event emitted by the [`HeadWatchdog`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WatchdogEvent {
+    /// The canonical head is stalled and a recovery action is taken.
+    Stalled {
+        /// The stalled canonical head.
+        head: BlockNumHash,
+        /// The highest block advertised by a connected peer.
+        best_peer_block: u64,
+        /// For how long the head didn't advance.
+        stalled_for: Duration,
+        /// The recovery action that is taken.
+        action: RecoveryAction,
+    },
+    /// The canonical head advanced again after recovery actions were taken.
+    Recovered {
+        /// The new canonical head.
+        head: BlockNumHash,
+        /// For how long the head didn't advance.
+        stalled_for: Duration,
+    },
+}
+
+impl Display for WatchdogEvent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Self::Stalled { head, best_peer_block, stalled_for, action } => write!(
+                f,
+                "Canonical head {} stalled for {stalled_for:?} while peers advertise block {best_peer_block}, attempting to {action}",
+                head.number
+            ),
+            Self::Recovered { head, stalled_for } => write!(
+                f,
+                "Canonical head advanced to {} after being stalled for {stalled_for:?}",
+                head.number
+            ),
+        }
+    }
+}
+
+/// Detects a stalled canonical head and determines the recovery actions to take.
+///
+/// The head is stalled if it didn't advance for the
+/// [`stall_threshold`](HeadWatchdogConfig::stall_threshold) while peers advertise higher blocks.
+/// The first recovery action is [`RecoveryAction::RequestSyncTarget`], every
+/// [`escalation_interval`](HeadWatchdogConfig::escalation_interval) the head remains stalled, the
+/// watchdog escalates to the next action.
+///
+/// The watchdog only keeps track of time, it is advanced by the caller via
+/// [`HeadWatchdog::on_canonical_head`] and [`HeadWatchdog::check`], which makes it deterministic.
+#[derive(Debug)]
+pub struct HeadWatchdog {
+    /// The configured thresholds.
+    config: HeadWatchdogConfig,
+    /// The latest canonical head.
+    head: BlockNumHash,
+    /// When the head last advanced, or when the head was last considered not stalled.
+    last_progress: Instant,
+    /// The latest recovery action and when it was taken, if the head is stalled.
+    last_action: Option<(RecoveryAction, Instant)>,
+}
+
+impl HeadWatchdog {
+    /// Creates a new watchdog for the given canonical head.
+    pub const fn new(config: HeadWatchdogConfig, head: BlockNumHash, now: Instant) -> Self {
+        Self { config, head, last_progress: now, last_action: None }
+    }
+
+    /// Returns the configured thresholds.
+    pub const fn config(&self) -> &HeadWatchdogConfig {
+        &self.config
+    }
+
+    /// Informs the watchdog about a new canonical head.
+    ///
+    /// Returns [`WatchdogEvent::Recovered`] if recovery actions were taken for the previous head.
+    pub fn on_canonical_head(&mut self, head: BlockNumHash, now: Instant) -> Option<WatchdogEvent> {
+        if head == self.head {
+            return None
+        }
+
+        let stalled_for = now.saturating_duration_since(self.last_progress);
+        self.head = head;
+        self.last_progress = now;
+        self.last_action.take().map(|_| WatchdogEvent::Recovered { head, stalled_for })
+    }
+
+    /// Considers the head not stalled as of `now`, e.g. while a backfill sync advances the chain.
+    pub const fn reset(&mut self, now: Instant) {
+        self.last_progress = now;
+        self.last_action = None;
+    }
+
+    /// Checks whether the canonical head is stalled, given the highest block advertised by the
+    /// connected peers.
+    ///
+    /// Returns [`WatchdogEvent::Stalled`] with the recovery action to take, if any is due.
+    pub fn check(&mut self, best_peer_block: Option<u64>, now: Instant) -> Option<WatchdogEvent> {
+        let Some(best_peer_block) = best_peer_block.filter(|best_peer_block| {
+            best_peer_block.saturating_sub(self.head.number) >= self.config.min_peer_lead
+        }) else {
+            // without peers ahead of the head there's nothing to sync to
+            self.reset(now);
+            return None
+        };
+
+        let stalled_for = now.saturating_duration_since(self.last_progress);
+        if stalled_for < self.config.stall_threshold {
+            return None
+        }
+
+        let action = match self.last_action {
+            None => RecoveryAction::RequestSyncTarget,
+            Some((action, at))
+                if now.saturating_duration_since(at) >= self.config.escalation_interval =>
+            {
+                action.escalate()
+            }
+            Some(_) => return None,
+        };
+        self.last_action = Some((action, now));
+
+        Some(WatchdogEvent::Stalled { head: self.head, best_peer_block, stalled_for, action })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+
+    fn stalled_action(event: Option<WatchdogEvent>) -> Option<RecoveryAction> {
+        match event? {
+            WatchdogEvent::Stalled { action, .. } => Some(action),
+            WatchdogEvent::Recovered { .. } => None,
+        }
+    }
+
+    #[test]
+    fn escalates_recovery_actions() {
+        let config = HeadWatchdogConfig::default()
+            .with_stall_threshold(Duration::from_secs(60))
+            .with_escalation_interval(Duration::from_secs(30))
+            .with_min_peer_lead(2);
+        let start = Instant::now();
+        let head = BlockNumHash::new(100, B256::with_last_byte(1));
+        let mut watchdog = HeadWatchdog::new(config, head, start);
+        let at = |secs| start + Duration::from_secs(secs);
+
+        // not stalled yet
+        assert_eq!(watchdog.check(Some(110), at(59)), None);
+        // peers are not far enough ahead
+        assert_eq!(watchdog.check(Some(101), at(60)), None);
+
+        // the stall threshold restarts once peers are ahead
+        assert_eq!(watchdog.check(Some(110), at(119)), None);
+        assert_eq!(
+            stalled_action(watchdog.check(Some(110), at(120))),
+            Some(RecoveryAction::RequestSyncTarget)
+        );
+        assert_eq!(watchdog.check(Some(110), at(149)), None);
+        assert_eq!(
+            stalled_action(watchdog.check(Some(110), at(150))),
+            Some(RecoveryAction::RestartEngine)
+        );
+        assert_eq!(
+            stalled_action(watchdog.check(Some(110), at(180))),
+            Some(RecoveryAction::Backfill)
+        );
+        assert_eq!(
+            stalled_action(watchdog.check(Some(110), at(210))),
+            Some(RecoveryAction::Backfill)
+        );
+
+        // the head advances again
+        let new_head = BlockNumHash::new(101, B256::with_last_byte(2));
+        assert_eq!(
+            watchdog.on_canonical_head(new_head, at(220)),
+            Some(WatchdogEvent::Recovered {
+                head: new_head,
+                stalled_for: Duration::from_secs(160)
+            })
+        );
+        assert_eq!(watchdog.check(Some(110), at(279)), None);
+        assert_eq!(
+            stalled_action(watchdog.check(Some(110), at(280))),
+            Some(RecoveryAction::RequestSyncTarget)
+        );
+    }
+}
@@ -64,7 +64,7 @@ where
     ///
     /// This accepts a closure that is used to launch the node via the
     /// [`NodeCommand`](reth_cli_commands::node::NodeCommand).
-    pub fn run(self, launcher: impl Launcher<C, Ext>) -> Result<()>
+    pub fn run(mut self, launcher: impl Launcher<C, Ext>) -> Result<()>
     where
         C: ChainSpecParser<ChainSpec = ChainSpec>,
     {
@@ -72,6 +72,15 @@ where
             (EthEvmConfig::ethereum(spec.clone()), Arc::new(EthBeaconConsensus::new(spec)))
         };
 
+        // `rpc-serve` builds the ethereum RPC stack and can't be dispatched for arbitrary node
+        // types, so it's handled here rather than in `run_commands_with`.
+        if matches!(self.cli.command, Commands::RpcServe(_)) {
+            let runner = self.init_runner()?;
+            if let Commands::RpcServe(command) = self.cli.command {
+                return runner.run_command_until_exit(|ctx| command.execute(ctx))
+            }
+        }
+
         self.run_with_components::<EthereumNode>(components, |builder, ext| async move {
             launcher.entrypoint(builder, ext).await
         })
@@ -94,6 +103,12 @@ where
         N: CliNodeTypes<Primitives: NodePrimitives<BlockHeader: HeaderMut>, ChainSpec: Hardforks>,
         C: ChainSpecParser<ChainSpec = N::ChainSpec>,
     {
+        let runner = self.init_runner()?;
+        run_commands_with::<C, Ext, Rpc, N, SubCmd>(self.cli, runner, components, launcher)
+    }
+
+    /// Returns the configured runner, or a default one, after initializing tracing and metrics.
+    fn init_runner(&mut self) -> Result<CliRunner> {
         let runner = match self.runner.take() {
             Some(runner) => runner,
             None => CliRunner::try_default_runtime()?,
@@ -110,7 +125,7 @@ where
         // Install the prometheus recorder to be sure to record all metrics
         install_prometheus_recorder();
 
-        run_commands_with::<C, Ext, Rpc, N, SubCmd>(self.cli, runner, components, launcher)
+        Ok(runner)
     }
 
     /// Initializes tracing with the configured options.
@@ -163,6 +178,9 @@ where
             runner.run_blocking_until_ctrl_c(command.execute::<N, _>(components))
         }
         Commands::ImportEra(command) => runner.run_blocking_until_ctrl_c(command.execute::<N>()),
+        Commands::ImportReceipts(command) => {
+            runner.run_blocking_until_ctrl_c(command.execute::<N>())
+        }
         Commands::ExportEra(command) => runner.run_blocking_until_ctrl_c(command.execute::<N>()),
         Commands::DumpGenesis(command) => runner.run_blocking_until_ctrl_c(command.execute()),
         Commands::Db(command) => {
@@ -172,12 +190,18 @@ where
         Commands::Stage(command) => {
             runner.run_command_until_exit(|ctx| command.execute::<N, _>(ctx, components))
         }
+        Commands::Debug(command) => {
+            runner.run_blocking_until_ctrl_c(command.execute::<N, _>(components))
+        }
         Commands::P2P(command) => runner.run_until_ctrl_c(command.execute::<N>()),
+        Commands::Backfill(command) => runner.run_until_ctrl_c(command.execute::<N>()),
         Commands::Config(command) => runner.run_until_ctrl_c(command.execute()),
         Commands::Prune(command) => runner.run_until_ctrl_c(command.execute::<N>()),
         #[cfg(feature = "dev")]
         Commands::TestVectors(command) => runner.run_until_ctrl_c(command.execute()),
         Commands::ReExecute(command) => runner.run_until_ctrl_c(command.execute::<N>(components)),
+        Commands::StaticFiles(command) => runner.run_blocking_until_ctrl_c(command.execute::<N>()),
+        Commands::RpcServe(_) => Err(eyre!("`rpc-serve` is only supported for ethereum nodes")),
         Commands::Ext(command) => command.execute(runner),
     }
 }
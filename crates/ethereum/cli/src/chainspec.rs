@@ -16,7 +16,10 @@ pub fn chain_value_parser(s: &str) -> eyre::Result<Arc<ChainSpec>, eyre::Error>
         "holesky" => HOLESKY.clone(),
         "hoodi" => HOODI.clone(),
         "dev" => DEV.clone(),
-        _ => Arc::new(parse_genesis(s)?.into()),
+        _ => Arc::new(
+            ChainSpec::try_from_genesis(parse_genesis(s)?)
+                .map_err(|err| eyre::eyre!("invalid scheduled system transactions: {err}"))?,
+        ),
     })
 }
 
@@ -3,16 +3,19 @@
 use crate::{
     app::{run_commands_with, CliApp},
     chainspec::EthereumChainSpecParser,
+    rpc_serve::RpcServeCommand,
 };
 use clap::{Parser, Subcommand};
 use reth_chainspec::{ChainSpec, EthChainSpec, Hardforks};
 use reth_cli::chainspec::ChainSpecParser;
 use reth_cli_commands::{
+    backfill,
     common::{CliComponentsBuilder, CliNodeTypes, HeaderMut},
-    config_cmd, db, download, dump_genesis, export_era, import, import_era, init_cmd, init_state,
+    config_cmd, db, debug, download, dump_genesis, export_era, import, import_era, import_receipts,
+    init_cmd, init_state,
     launcher::FnLauncher,
     node::{self, NoArgs},
-    p2p, prune, re_execute, stage,
+    p2p, prune, re_execute, stage, static_files,
 };
 use reth_cli_runner::CliRunner;
 use reth_db::DatabaseEnv;
@@ -282,6 +285,9 @@ pub enum Commands<
     /// This syncs ERA encoded blocks from a directory.
     #[command(name = "import-era")]
     ImportEra(import_era::ImportEraCommand<C>),
+    /// Imports receipts from a file into the receipt static files.
+    #[command(name = "import-receipts")]
+    ImportReceipts(import_receipts::ImportReceiptsCommand<C>),
     /// Exports block to era1 files in a specified directory.
     #[command(name = "export-era")]
     ExportEra(export_era::ExportEraCommand<C>),
@@ -296,9 +302,15 @@ pub enum Commands<
     /// Manipulate individual stages.
     #[command(name = "stage")]
     Stage(stage::Command<C>),
+    /// Debugging utilities for pipeline runs
+    #[command(name = "debug")]
+    Debug(debug::Command<C>),
     /// P2P Debugging utilities
     #[command(name = "p2p")]
     P2P(Box<p2p::Command<C>>),
+    /// Backfill data, like pruned receipts, from the network
+    #[command(name = "backfill")]
+    Backfill(Box<backfill::Command<C>>),
     /// Generate Test Vectors
     #[cfg(feature = "dev")]
     #[command(name = "test-vectors")]
@@ -312,6 +324,12 @@ pub enum Commands<
     /// Re-execute blocks in parallel to verify historical sync correctness.
     #[command(name = "re-execute")]
     ReExecute(re_execute::Command<C>),
+    /// Static file utilities
+    #[command(name = "static-files")]
+    StaticFiles(static_files::Command<C>),
+    /// Serve RPC from a read-only datadir without running the node
+    #[command(name = "rpc-serve")]
+    RpcServe(Box<RpcServeCommand<C>>),
     /// Extension subcommands provided by consumers.
     #[command(flatten)]
     Ext(SubCmd),
@@ -342,16 +360,21 @@ impl<C: ChainSpecParser, Ext: clap::Args + fmt::Debug, SubCmd: Subcommand + fmt:
             Self::Import(cmd) => cmd.chain_spec(),
             Self::ExportEra(cmd) => cmd.chain_spec(),
             Self::ImportEra(cmd) => cmd.chain_spec(),
+            Self::ImportReceipts(cmd) => cmd.chain_spec(),
             Self::DumpGenesis(cmd) => cmd.chain_spec(),
             Self::Db(cmd) => cmd.chain_spec(),
             Self::Download(cmd) => cmd.chain_spec(),
             Self::Stage(cmd) => cmd.chain_spec(),
+            Self::Debug(cmd) => cmd.chain_spec(),
             Self::P2P(cmd) => cmd.chain_spec(),
+            Self::Backfill(cmd) => cmd.chain_spec(),
             #[cfg(feature = "dev")]
             Self::TestVectors(_) => None,
             Self::Config(_) => None,
             Self::Prune(cmd) => cmd.chain_spec(),
             Self::ReExecute(cmd) => cmd.chain_spec(),
+            Self::StaticFiles(cmd) => cmd.chain_spec(),
+            Self::RpcServe(cmd) => cmd.chain_spec(),
             Self::Ext(_) => None,
         }
     }
@@ -0,0 +1,215 @@
+//! `reth rpc-serve` command.
+
+use alloy_primitives::B256;
+use clap::Parser;
+use reth_chainspec::{ChainSpec, EthChainSpec};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_cli_commands::common::{AccessRights, Environment, EnvironmentArgs};
+use reth_cli_runner::CliContext;
+use reth_node_ethereum::{consensus::EthBeaconConsensus, EthEvmConfig, EthereumNode};
+use reth_primitives_traits::SealedHeader;
+use reth_provider::{
+    providers::BlockchainProvider, BlockNumReader, CanonChainTracker, HeaderProvider,
+    ProviderResult,
+};
+use reth_rpc_builder::{RpcModuleBuilder, RpcServerConfig, TransportRpcModuleConfig};
+use reth_rpc_server_types::{constants, RpcModuleSelection};
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    sync::Arc,
+    time::Duration,
+};
+use tracing::{debug, info, warn};
+
+/// `reth rpc-serve` command
+///
+/// Opens the datadir read-only and serves the configured RPC namespaces without running the
+/// pipeline, engine or p2p stack.
+///
+/// The database and static files can be shared with a syncing node. New blocks written by that
+/// node are picked up by polling the database for its canonical head, which also picks up reorgs
+/// of that node, see [`RpcServeCommand::head_poll_interval`].
+#[derive(Debug, Parser)]
+pub struct RpcServeCommand<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    /// Http server address to listen on
+    #[arg(long = "http.addr", default_value_t = IpAddr::V4(Ipv4Addr::LOCALHOST))]
+    http_addr: IpAddr,
+
+    /// Http server port to listen on
+    #[arg(long = "http.port", default_value_t = constants::DEFAULT_HTTP_RPC_PORT)]
+    http_port: u16,
+
+    /// Rpc Modules to be configured for the HTTP server
+    #[arg(long = "http.api", default_value = "eth,debug,trace")]
+    http_api: RpcModuleSelection,
+
+    /// Http Corsdomain to allow request from
+    #[arg(long = "http.corsdomain")]
+    http_corsdomain: Option<String>,
+
+    /// Enable the GraphQL server on the `/graphql` path of the HTTP-RPC server
+    #[arg(long)]
+    graphql: bool,
+
+    /// Enable the WS-RPC server
+    #[arg(long)]
+    ws: bool,
+
+    /// Ws server address to listen on
+    #[arg(long = "ws.addr", default_value_t = IpAddr::V4(Ipv4Addr::LOCALHOST))]
+    ws_addr: IpAddr,
+
+    /// Ws server port to listen on
+    #[arg(long = "ws.port", default_value_t = constants::DEFAULT_WS_RPC_PORT)]
+    ws_port: u16,
+
+    /// Origins from which to accept `WebSocket` requests
+    #[arg(long = "ws.origins")]
+    ws_allowed_origins: Option<String>,
+
+    /// Rpc Modules to be configured for the WS server
+    #[arg(long = "ws.api", default_value = "eth,debug,trace")]
+    ws_api: RpcModuleSelection,
+
+    /// How often the database is polled for blocks written by another process.
+    #[arg(long = "head-poll-interval", value_parser = humantime::parse_duration, default_value = "1s")]
+    head_poll_interval: Duration,
+}
+
+impl<C: ChainSpecParser> RpcServeCommand<C> {
+    /// Returns the underlying chain being used to run this command
+    pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        Some(&self.env.chain)
+    }
+}
+
+impl<C: ChainSpecParser<ChainSpec = ChainSpec>> RpcServeCommand<C> {
+    /// Execute `rpc-serve` command
+    pub async fn execute(self, ctx: CliContext) -> eyre::Result<()> {
+        info!(target: "reth::cli", chain=%self.env.chain.chain(), "Starting read-only RPC server");
+
+        let Environment { provider_factory, .. } =
+            self.env.init::<EthereumNode>(AccessRights::RO)?;
+        let chain_spec = self.env.chain.clone();
+
+        let provider = BlockchainProvider::new(provider_factory.clone())?;
+
+        let rpc_builder = RpcModuleBuilder::default()
+            .with_provider(provider.clone())
+            .with_noop_pool()
+            .with_noop_network()
+            .with_executor(Box::new(ctx.task_executor.clone()))
+            .with_evm_config(EthEvmConfig::ethereum(chain_spec.clone()))
+            .with_consensus(EthBeaconConsensus::new(chain_spec));
+        let eth_api = rpc_builder.bootstrap_eth_api();
+
+        let mut module_config = TransportRpcModuleConfig::default().with_http(self.http_api);
+        let mut server_config = RpcServerConfig::http(Default::default())
+            .with_http_address(SocketAddr::new(self.http_addr, self.http_port))
+            .with_http_cors(self.http_corsdomain.clone())
+            .with_http_graphql(self.graphql);
+        if self.ws {
+            module_config = module_config.with_ws(self.ws_api);
+            server_config = server_config
+                .with_ws(Default::default())
+                .with_ws_address(SocketAddr::new(self.ws_addr, self.ws_port))
+                .with_ws_cors(self.ws_allowed_origins);
+        }
+
+        let modules = rpc_builder.build(module_config, eth_api, Default::default());
+        let handle = server_config.start(&modules).await?;
+
+        if let Some(url) = handle.http_url() {
+            info!(target: "reth::cli", url, "RPC HTTP server started");
+        }
+        if let Some(url) = handle.ws_url() {
+            info!(target: "reth::cli", url, "RPC WS server started");
+        }
+
+        // The database is written by another process, so the canonical head tracked by the
+        // provider has to be advanced manually.
+        let mut interval = tokio::time::interval(self.head_poll_interval);
+        loop {
+            interval.tick().await;
+
+            let head = provider.chain_info()?.best_hash;
+            match new_canonical_head(&provider_factory, head) {
+                Ok(Some(header)) => {
+                    debug!(target: "reth::cli", number = header.number, hash = ?header.hash(), "Updating canonical head");
+                    provider.set_canonical_head(header);
+                }
+                Ok(None) => {}
+                Err(err) => {
+                    warn!(target: "reth::cli", %err, "Failed to read canonical head")
+                }
+            }
+        }
+    }
+}
+
+/// Returns the canonical head of the database if it's not the given head.
+///
+/// Heads are compared by hash, so that a reorg to a block of the same height is detected as well.
+fn new_canonical_head<P>(
+    database: &P,
+    head: B256,
+) -> ProviderResult<Option<SealedHeader<P::Header>>>
+where
+    P: BlockNumReader + HeaderProvider,
+{
+    let latest = database.best_block_number()?;
+    Ok(database.sealed_header(latest)?.filter(|header| header.hash() != head))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interface::{Cli, Commands};
+    use alloy_consensus::Header;
+    use reth_provider::test_utils::MockEthProvider;
+
+    #[test]
+    fn parse_cors_domains() {
+        let cli = Cli::try_parse_args_from([
+            "reth",
+            "rpc-serve",
+            "--http.corsdomain",
+            "http://a",
+            "--ws",
+            "--ws.origins",
+            "http://b",
+        ])
+        .unwrap();
+        let Commands::RpcServe(command) = cli.command else { panic!("expected rpc-serve") };
+        assert_eq!(command.http_corsdomain.as_deref(), Some("http://a"));
+        assert_eq!(command.ws_allowed_origins.as_deref(), Some("http://b"));
+    }
+
+    #[test]
+    fn detect_new_canonical_head() {
+        let header = Header { number: 1, ..Default::default() };
+        let database = MockEthProvider::default();
+        database.add_header(header.hash_slow(), header.clone());
+        assert_eq!(new_canonical_head(&database, header.hash_slow()).unwrap(), None);
+
+        // a new block on top of the head
+        let child = Header { number: 2, parent_hash: header.hash_slow(), ..Default::default() };
+        database.add_header(child.hash_slow(), child.clone());
+        assert_eq!(
+            new_canonical_head(&database, header.hash_slow()).unwrap().map(|head| head.hash()),
+            Some(child.hash_slow())
+        );
+
+        // a reorg to another block of the same height
+        let reorged = MockEthProvider::default();
+        let sibling = Header { number: 1, gas_limit: 1, ..Default::default() };
+        reorged.add_header(sibling.hash_slow(), sibling.clone());
+        assert_eq!(
+            new_canonical_head(&reorged, header.hash_slow()).unwrap().map(|head| head.hash()),
+            Some(sibling.hash_slow())
+        );
+    }
+}
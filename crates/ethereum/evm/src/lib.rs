@@ -20,6 +20,7 @@ extern crate alloc;
 use alloc::{borrow::Cow, sync::Arc};
 use alloy_consensus::Header;
 use alloy_evm::{
+    block::{BlockExecutorFactory, BlockExecutorFor},
     eth::{EthBlockExecutionCtx, EthBlockExecutorFactory},
     EthEvmFactory, FromRecoveredTx, FromTxWithEncoded,
 };
@@ -27,11 +28,12 @@ use core::{convert::Infallible, fmt::Debug};
 use reth_chainspec::{ChainSpec, EthChainSpec, MAINNET};
 use reth_ethereum_primitives::{Block, EthPrimitives, TransactionSigned};
 use reth_evm::{
-    eth::NextEvmEnvAttributes, precompiles::PrecompilesMap, ConfigureEvm, EvmEnv, EvmFactory,
-    NextBlockEnvAttributes, TransactionEnv,
+    eth::NextEvmEnvAttributes, precompiles::PrecompilesMap, ConfigureEvm, ConfigureEvmPrecompiles,
+    Database, EvmEnv, EvmFactory, EvmFor, InspectorFor, NextBlockEnvAttributes, PrecompilesHook,
+    SystemTxBlockExecutor, TransactionEnv, WithPrecompiles,
 };
 use reth_primitives_traits::{SealedBlock, SealedHeader};
-use revm::{context::BlockEnv, primitives::hardfork::SpecId};
+use revm::{context::BlockEnv, database::State, primitives::hardfork::SpecId};
 
 #[cfg(feature = "std")]
 use reth_evm::{ConfigureEngineEvm, ExecutableTxIterator};
@@ -154,6 +156,23 @@ where
         &self.block_assembler
     }
 
+    /// Creates an executor that also executes the system transactions scheduled by the chain spec,
+    /// see [`SystemTxBlockExecutor`].
+    fn create_executor<'a, DB, I>(
+        &'a self,
+        evm: EvmFor<Self, &'a mut State<DB>, I>,
+        ctx: EthBlockExecutionCtx<'a>,
+    ) -> impl BlockExecutorFor<'a, Self::BlockExecutorFactory, DB, I>
+    where
+        DB: Database,
+        I: InspectorFor<Self, &'a mut State<DB>> + 'a,
+    {
+        SystemTxBlockExecutor::new(
+            self.executor_factory.create_executor(evm, ctx),
+            self.chain_spec().system_transactions(),
+        )
+    }
+
     fn evm_env(&self, header: &Header) -> Result<EvmEnv<SpecId>, Self::Error> {
         Ok(EvmEnv::for_eth_block(
             header,
@@ -213,6 +232,38 @@ where
     }
 }
 
+impl<ChainSpec, EvmF> ConfigureEvmPrecompiles for EthEvmConfig<ChainSpec, EvmF>
+where
+    ChainSpec: EthExecutorSpec + EthChainSpec<Header = Header> + Hardforks + 'static,
+    EvmF: EvmFactory<
+            Tx: TransactionEnv
+                    + FromRecoveredTx<TransactionSigned>
+                    + FromTxWithEncoded<TransactionSigned>,
+            Spec = SpecId,
+            BlockEnv = BlockEnv,
+            Precompiles = PrecompilesMap,
+        > + Clone
+        + Debug
+        + Send
+        + Sync
+        + Unpin
+        + 'static,
+{
+    type WithPrecompiles = EthEvmConfig<ChainSpec, WithPrecompiles<EvmF>>;
+
+    fn with_precompiles(self, hook: PrecompilesHook<SpecId>) -> Self::WithPrecompiles {
+        let Self { executor_factory, block_assembler } = self;
+        EthEvmConfig {
+            executor_factory: EthBlockExecutorFactory::new(
+                *executor_factory.receipt_builder(),
+                executor_factory.spec().clone(),
+                WithPrecompiles::new(executor_factory.evm_factory().clone(), hook),
+            ),
+            block_assembler,
+        }
+    }
+}
+
 #[cfg(feature = "std")]
 impl<ChainSpec, EvmF> ConfigureEngineEvm<ExecutionData> for EthEvmConfig<ChainSpec, EvmF>
 where
@@ -10,7 +10,10 @@ use alloy_eips::{
 };
 use alloy_evm::block::BlockValidationError;
 use alloy_primitives::{b256, fixed_bytes, keccak256, Bytes, TxKind, B256, U256};
-use reth_chainspec::{ChainSpecBuilder, EthereumHardfork, ForkCondition, MAINNET};
+use reth_chainspec::{
+    ChainSpecBuilder, EthereumHardfork, ForkCondition, ScheduledSystemTransaction,
+    SystemTransactionPosition, MAINNET,
+};
 use reth_ethereum_primitives::{Block, BlockBody, Transaction};
 use reth_evm::{
     execute::{BasicBlockExecutor, Executor},
@@ -829,3 +832,57 @@ fn test_balance_increment_not_duplicated() {
         );
     }
 }
+
+#[test]
+fn scheduled_system_transactions() {
+    // stores the first word of the calldata at the slot of the block number
+    let code = Bytes::from_static(&[0x60, 0x00, 0x35, 0x43, 0x55, 0x00]);
+    let contract = address!("0x000000000000000000000000000000000000beef");
+
+    let mut db = CacheDB::new(EmptyDB::default());
+    db.insert_account_info(
+        contract,
+        AccountInfo {
+            code_hash: keccak256(&code),
+            code: Some(Bytecode::new_raw(code)),
+            ..Default::default()
+        },
+    );
+
+    let mut chain_spec = ChainSpecBuilder::from(&*MAINNET).shanghai_activated().build();
+    chain_spec.system_transactions = vec![ScheduledSystemTransaction {
+        position: SystemTransactionPosition::PreBlock,
+        from: SYSTEM_ADDRESS,
+        to: contract,
+        input: B256::with_last_byte(0x2a).into(),
+        start_block: 2,
+        end_block: None,
+        interval: 2,
+    }];
+
+    let provider = EthEvmConfig::new(Arc::new(chain_spec));
+    let mut executor = BasicBlockExecutor::new(provider, db);
+
+    for number in 1..=3 {
+        let header = Header { timestamp: number, number, ..Header::default() };
+        let result = executor
+            .execute_one(&RecoveredBlock::new_unhashed(
+                Block { header, body: Default::default() },
+                vec![],
+            ))
+            .unwrap();
+
+        // system transactions neither produce receipts nor consume block gas
+        assert!(result.receipts.is_empty());
+        assert_eq!(result.gas_used, 0);
+    }
+
+    // the system transaction is only executed in block 2
+    executor.with_state_mut(|state| state.basic(contract).unwrap());
+    let mut storage = |slot: u64| {
+        executor.with_state_mut(|state| state.storage(contract, U256::from(slot)).unwrap())
+    };
+    assert_eq!(storage(1), U256::ZERO);
+    assert_eq!(storage(2), U256::from(0x2a));
+    assert_eq!(storage(3), U256::ZERO);
+}
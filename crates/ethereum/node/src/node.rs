@@ -53,7 +53,9 @@ use reth_rpc_eth_types::{error::FromEvmError, EthApiError};
 use reth_rpc_server_types::RethRpcModule;
 use reth_tracing::tracing::{debug, info};
 use reth_transaction_pool::{
-    blobstore::DiskFileBlobStore, EthTransactionPool, PoolPooledTx, PoolTransaction,
+    blobstore::DiskFileBlobStore,
+    validate::{EthTransactionValidator, TransactionAdmissionRule, ValidatorChain},
+    CoinbaseTipOrdering, EthPooledTransaction, Pool, PoolPooledTx, PoolTransaction,
     TransactionPool, TransactionValidationTaskExecutor,
 };
 use revm::context::TxEnv;
@@ -450,12 +452,44 @@ where
 ///
 /// This contains various settings that can be configured and take precedence over the node's
 /// config.
-#[derive(Debug, Default, Clone, Copy)]
+#[derive(Default, Clone)]
 #[non_exhaustive]
 pub struct EthereumPoolBuilder {
-    // TODO add options for txpool args
+    /// Additional admission rules that transactions must pass before they are validated.
+    admission_rules: Vec<Arc<dyn TransactionAdmissionRule<EthPooledTransaction>>>,
+}
+
+impl EthereumPoolBuilder {
+    /// Appends a rule that transactions must pass to be admitted to the pool.
+    ///
+    /// The rules are checked in the order they were added, before the transaction is validated by
+    /// the [`EthTransactionValidator`].
+    pub fn with_admission_rule<R>(mut self, rule: R) -> Self
+    where
+        R: TransactionAdmissionRule<EthPooledTransaction> + 'static,
+    {
+        self.admission_rules.push(Arc::new(rule));
+        self
+    }
+}
+
+impl std::fmt::Debug for EthereumPoolBuilder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EthereumPoolBuilder")
+            .field("admission_rules", &self.admission_rules.len())
+            .finish()
+    }
 }
 
+/// The transaction pool built by the [`EthereumPoolBuilder`].
+pub type EthereumTransactionPool<Client, S, Evm> = Pool<
+    TransactionValidationTaskExecutor<
+        ValidatorChain<EthTransactionValidator<Client, EthPooledTransaction, Evm>>,
+    >,
+    CoinbaseTipOrdering<EthPooledTransaction>,
+    S,
+>;
+
 impl<Types, Node, Evm> PoolBuilder<Node, Evm> for EthereumPoolBuilder
 where
     Types: NodeTypes<
@@ -465,7 +499,7 @@ where
     Node: FullNodeTypes<Types = Types>,
     Evm: ConfigureEvm<Primitives = PrimitivesTy<Types>> + Clone + 'static,
 {
-    type Pool = EthTransactionPool<Node::Provider, DiskFileBlobStore, Evm>;
+    type Pool = EthereumTransactionPool<Node::Provider, DiskFileBlobStore, Evm>;
 
     async fn build_pool(
         self,
@@ -506,14 +540,16 @@ where
                 .set_tx_fee_cap(ctx.config().rpc.rpc_tx_fee_cap)
                 .with_max_tx_gas_limit(ctx.config().txpool.max_tx_gas_limit)
                 .with_minimum_priority_fee(ctx.config().txpool.minimum_priority_fee)
+                .with_request_fee_validation(ctx.config().txpool.validate_request_fees)
                 .with_additional_tasks(ctx.config().txpool.additional_validation_tasks)
-                .build_with_tasks(ctx.task_executor().clone(), blob_store.clone());
+                .build_with_tasks(ctx.task_executor().clone(), blob_store.clone())
+                .map(|validator| ValidatorChain::new(validator).with_rules(self.admission_rules));
 
-        if validator.validator().eip4844() {
+        if validator.validator().inner().eip4844() {
             // initializing the KZG settings can be expensive, this should be done upfront so that
             // it doesn't impact the first block or the first gossiped blob transaction, so we
             // initialize this in the background
-            let kzg_settings = validator.validator().kzg_settings().clone();
+            let kzg_settings = validator.validator().inner().kzg_settings().clone();
             ctx.task_executor().spawn_blocking(async move {
                 let _ = kzg_settings.get();
                 debug!(target: "reth::cli", "Initialized KZG settings");
@@ -4,21 +4,37 @@ use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_ethereum_engine_primitives::{
     EthBuiltPayload, EthPayloadAttributes, EthPayloadBuilderAttributes,
 };
-use reth_ethereum_payload_builder::EthereumBuilderConfig;
-use reth_ethereum_primitives::EthPrimitives;
+use reth_ethereum_payload_builder::{EthereumBuilderConfig, FeeRecipientOverrides};
+use reth_ethereum_primitives::{EthPrimitives, TransactionSigned};
 use reth_evm::ConfigureEvm;
 use reth_node_api::{FullNodeTypes, NodeTypes, PrimitivesTy, TxTy};
 use reth_node_builder::{
     components::PayloadBuilderBuilder, BuilderContext, PayloadBuilderConfig, PayloadTypes,
 };
+use reth_payload_builder::{NoopBundleSource, PayloadBundleSource};
+use reth_primitives_traits::Recovered;
+use reth_tracing::tracing::info;
 use reth_transaction_pool::{PoolTransaction, TransactionPool};
 
 /// A basic ethereum payload service.
 #[derive(Clone, Default, Debug)]
 #[non_exhaustive]
-pub struct EthereumPayloadBuilder;
+pub struct EthereumPayloadBuilder<Bundles = NoopBundleSource> {
+    /// Source of external bundles merged into the top of built payloads.
+    bundles: Bundles,
+}
 
-impl<Types, Node, Pool, Evm> PayloadBuilderBuilder<Node, Pool, Evm> for EthereumPayloadBuilder
+impl<Bundles> EthereumPayloadBuilder<Bundles> {
+    /// Configures the source of external bundles that are merged into built payloads, e.g. a
+    /// [`PayloadBundlePool`](reth_payload_builder::PayloadBundlePool) that a local searcher
+    /// submits bundles to.
+    pub fn with_bundle_source<B>(self, bundles: B) -> EthereumPayloadBuilder<B> {
+        EthereumPayloadBuilder { bundles }
+    }
+}
+
+impl<Types, Node, Pool, Evm, Bundles> PayloadBuilderBuilder<Node, Pool, Evm>
+    for EthereumPayloadBuilder<Bundles>
 where
     Types: NodeTypes<ChainSpec: EthereumHardforks, Primitives = EthPrimitives>,
     Node: FullNodeTypes<Types = Types>,
@@ -34,9 +50,10 @@ where
         PayloadAttributes = EthPayloadAttributes,
         PayloadBuilderAttributes = EthPayloadBuilderAttributes,
     >,
+    Bundles: PayloadBundleSource<Recovered<TransactionSigned>> + Clone + Unpin + 'static,
 {
     type PayloadBuilder =
-        reth_ethereum_payload_builder::EthereumPayloadBuilder<Pool, Node::Provider, Evm>;
+        reth_ethereum_payload_builder::EthereumPayloadBuilder<Pool, Node::Provider, Evm, Bundles>;
 
     async fn build_payload_builder(
         self,
@@ -48,6 +65,19 @@ where
         let chain = ctx.chain_spec().chain();
         let gas_limit = conf.gas_limit_for(chain);
 
+        let mut fee_recipient_overrides = FeeRecipientOverrides::new();
+        for (suggested, recipient) in conf.fee_recipient_overrides() {
+            fee_recipient_overrides =
+                fee_recipient_overrides.with_override(*suggested, *recipient)?;
+        }
+        for (window, recipient) in conf.fee_recipient_windows() {
+            fee_recipient_overrides =
+                fee_recipient_overrides.with_window(window.clone(), *recipient)?;
+        }
+        if !fee_recipient_overrides.is_empty() {
+            info!(target: "reth::cli", ?fee_recipient_overrides, "Fee recipient overrides configured");
+        }
+
         Ok(reth_ethereum_payload_builder::EthereumPayloadBuilder::new(
             ctx.provider().clone(),
             pool,
@@ -55,7 +85,9 @@ where
             EthereumBuilderConfig::new()
                 .with_gas_limit(gas_limit)
                 .with_max_blobs_per_block(conf.max_blobs_per_block())
-                .with_extra_data(conf.extra_data_bytes()),
-        ))
+                .with_extra_data(conf.extra_data_bytes())
+                .with_fee_recipient_overrides(fee_recipient_overrides),
+        )
+        .with_bundle_source(self.bundles))
     }
 }
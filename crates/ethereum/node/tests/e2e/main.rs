@@ -6,6 +6,7 @@ mod dev;
 mod eth;
 mod invalid_payload;
 mod p2p;
+mod payload;
 mod pool;
 mod prestate;
 mod rpc;
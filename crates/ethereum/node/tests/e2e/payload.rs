@@ -0,0 +1,155 @@
+use crate::utils::eth_payload_attributes;
+use alloy_consensus::transaction::TxHashRef;
+use alloy_eips::Decodable2718;
+use alloy_primitives::Bytes;
+use reth_basic_payload_builder::{BuildArguments, PayloadBuilder, PayloadConfig};
+use reth_chainspec::{ChainSpecBuilder, MAINNET};
+use reth_e2e_test_utils::{setup, transaction::TransactionTestContext, wallet::Wallet};
+use reth_ethereum_payload_builder::{EthereumBuilderConfig, EthereumPayloadBuilder};
+use reth_ethereum_primitives::TransactionSigned;
+use reth_node_ethereum::EthereumNode;
+use reth_payload_builder::{PayloadBundle, PayloadBundlePool};
+use reth_primitives_traits::Recovered;
+use reth_provider::HeaderProvider;
+use reth_revm::cancelled::ManualCancel;
+use std::sync::Arc;
+
+#[tokio::test]
+async fn payload_build_stops_adding_transactions_on_interrupt() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+
+    let (mut nodes, _tasks, wallet) = setup::<EthereumNode>(
+        1,
+        Arc::new(
+            ChainSpecBuilder::default()
+                .chain(MAINNET.chain)
+                .genesis(serde_json::from_str(include_str!("../assets/genesis.json")).unwrap())
+                .cancun_activated()
+                .build(),
+        ),
+        false,
+        eth_payload_attributes,
+    )
+    .await?;
+    let node = nodes.pop().unwrap();
+
+    let raw_tx = TransactionTestContext::transfer_tx_bytes(1, wallet.inner).await;
+    node.rpc.inject_tx(raw_tx).await?;
+
+    let parent = Arc::new(node.inner.provider.sealed_header(0)?.expect("genesis header"));
+    let builder = EthereumPayloadBuilder::new(
+        node.inner.provider.clone(),
+        node.inner.pool.clone(),
+        node.inner.evm_config.clone(),
+        EthereumBuilderConfig::new(),
+    );
+    let build = |interrupt: ManualCancel| {
+        let config =
+            PayloadConfig::new(parent.clone(), eth_payload_attributes(parent.timestamp + 12));
+        let args = BuildArguments::new(Default::default(), config, Default::default(), None)
+            .with_interrupt(interrupt);
+        builder.try_build(args).map(|outcome| outcome.into_payload().expect("built payload"))
+    };
+
+    // the pool transaction is included by a regular build
+    let payload = build(ManualCancel::default())?;
+    assert_eq!(payload.block().body().transactions.len(), 1);
+
+    // an interrupted build seals the payload without adding more transactions
+    let interrupt = ManualCancel::default();
+    interrupt.clone().cancel();
+    let payload = build(interrupt)?;
+    assert!(payload.block().body().transactions.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn payload_merges_valid_independent_bundles() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+
+    let (mut nodes, _tasks, _wallet) = setup::<EthereumNode>(
+        1,
+        Arc::new(
+            ChainSpecBuilder::default()
+                .chain(MAINNET.chain)
+                .genesis(serde_json::from_str(include_str!("../assets/genesis.json")).unwrap())
+                .cancun_activated()
+                .build(),
+        ),
+        false,
+        eth_payload_attributes,
+    )
+    .await?;
+    let node = nodes.pop().unwrap();
+
+    let signers = Wallet::new(2).wallet_gen();
+    let senders = signers.iter().map(alloy_signer::Signer::address).collect::<Vec<_>>();
+
+    let transfer = Recovered::new_unchecked(
+        TransactionSigned::from(TransactionTestContext::transfer_tx(1, signers[0].clone()).await),
+        senders[0],
+    );
+    let next_transfer = Recovered::new_unchecked(
+        TransactionSigned::decode_2718(
+            &mut TransactionTestContext::transfer_tx_bytes_with_nonce(1, signers[0].clone(), 1)
+                .await
+                .as_ref(),
+        )?,
+        senders[0],
+    );
+    let replayed_transfer = Recovered::new_unchecked(
+        TransactionSigned::from(TransactionTestContext::transfer_tx(1, signers[0].clone()).await),
+        senders[0],
+    );
+    // init code that always reverts
+    let reverting = Recovered::new_unchecked(
+        TransactionSigned::from(
+            TransactionTestContext::deploy_tx(
+                1,
+                100_000,
+                Bytes::from_static(&[0x60, 0x00, 0x60, 0x00, 0xfd]),
+                signers[1].clone(),
+            )
+            .await,
+        ),
+        senders[1],
+    );
+
+    let bundles = PayloadBundlePool::default();
+    // merged
+    bundles.add_bundle(PayloadBundle::new(vec![transfer.clone()]));
+    // conflicts with the merged transfer from the same sender
+    bundles.add_bundle(PayloadBundle::new(vec![next_transfer]));
+    // invalid on top of the merged transfer
+    bundles.add_bundle(PayloadBundle::new(vec![replayed_transfer]));
+    // reverts without being allowed to
+    bundles.add_bundle(PayloadBundle::new(vec![reverting.clone()]));
+    // targets another block
+    bundles.add_bundle(PayloadBundle::new(vec![reverting.clone()]).with_block_number(2));
+    // merged, because it's allowed to revert
+    bundles.add_bundle(
+        PayloadBundle::new(vec![reverting.clone()])
+            .with_reverting_tx_hashes([*reverting.tx_hash()]),
+    );
+
+    let parent = Arc::new(node.inner.provider.sealed_header(0)?.expect("genesis header"));
+    let builder = EthereumPayloadBuilder::new(
+        node.inner.provider.clone(),
+        node.inner.pool.clone(),
+        node.inner.evm_config.clone(),
+        EthereumBuilderConfig::new(),
+    )
+    .with_bundle_source(bundles);
+    let config = PayloadConfig::new(parent.clone(), eth_payload_attributes(parent.timestamp + 12));
+    let payload = builder
+        .try_build(BuildArguments::new(Default::default(), config, Default::default(), None))?
+        .into_payload()
+        .expect("built payload");
+
+    let hashes =
+        payload.block().body().transactions.iter().map(|tx| *tx.tx_hash()).collect::<Vec<_>>();
+    assert_eq!(hashes, vec![*transfer.tx_hash(), *reverting.tx_hash()]);
+
+    Ok(())
+}
@@ -2,16 +2,22 @@
 
 use std::sync::Arc;
 
+use alloy_primitives::{Address, Bytes};
 use reth_db::{
     test_utils::{create_test_rw_db, TempDatabase},
     DatabaseEnv,
 };
+use reth_evm::precompiles::{DynPrecompile, PrecompileInput};
 use reth_node_api::NodeTypesWithDBAdapter;
 use reth_node_builder::{EngineNodeLauncher, FullNodeComponents, NodeBuilder, NodeConfig};
 use reth_node_ethereum::node::{EthereumAddOns, EthereumNode};
 use reth_provider::providers::BlockchainProvider;
 use reth_rpc_builder::Identity;
 use reth_tasks::TaskManager;
+use revm::{
+    precompile::{PrecompileId, PrecompileOutput},
+    primitives::hardfork::SpecId,
+};
 
 #[test]
 fn test_basic_setup() {
@@ -117,3 +123,25 @@ fn test_node_setup() {
     let _builder =
         NodeBuilder::new(config).with_database(db).node(EthereumNode::default()).check_launch();
 }
+
+#[test]
+fn test_node_setup_with_precompiles() {
+    let config = NodeConfig::test();
+    let db = create_test_rw_db();
+    let _builder = NodeBuilder::new(config)
+        .with_database(db)
+        .with_types::<EthereumNode>()
+        .with_components(EthereumNode::components())
+        .with_precompiles(|spec, precompiles| {
+            if *spec >= SpecId::PRAGUE {
+                precompiles.apply_precompile(&Address::repeat_byte(0x42), |_| {
+                    Some(DynPrecompile::from((
+                        PrecompileId::custom("custom"),
+                        |_: PrecompileInput<'_>| Ok(PrecompileOutput::new(0, Bytes::new())),
+                    )))
+                });
+            }
+        })
+        .with_add_ons(EthereumAddOns::default())
+        .check_launch();
+}
@@ -0,0 +1,92 @@
+//! Simulation of external bundles merged into Ethereum payloads.
+
+use alloy_consensus::transaction::{Recovered, TxHashRef};
+use alloy_primitives::{Address, TxHash};
+use reth_ethereum_primitives::{EthPrimitives, TransactionSigned};
+use reth_evm::{ConfigureEvm, Evm, EvmEnvFor, EvmError};
+use reth_payload_builder::{PayloadBundle, StateAccess};
+use reth_revm::db::State;
+use revm::{context_interface::result::ResultAndState, Database, DatabaseCommit};
+
+/// The reason a bundle was not merged into the payload.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum BundleRejection {
+    /// The transaction is invalid on top of the payload being built.
+    InvalidTransaction(TxHash),
+    /// The transaction reverted, but isn't allowed to revert.
+    Reverted(TxHash),
+    /// The transaction couldn't be simulated.
+    SimulationFailed(TxHash, String),
+}
+
+/// Simulates the bundle on top of the given state without modifying it.
+///
+/// Returns the state accessed by the bundle if all of its transactions are valid and none of
+/// them reverts unless allowed to. The `beneficiary` is excluded from the access, because its
+/// balance is changed by every transaction.
+///
+/// Errors of the simulation only reject the bundle, the payload is built without it.
+pub(crate) fn simulate_bundle<EvmConfig, DB>(
+    evm_config: &EvmConfig,
+    evm_env: EvmEnvFor<EvmConfig>,
+    db: &mut State<DB>,
+    bundle: &PayloadBundle<Recovered<TransactionSigned>>,
+    beneficiary: Address,
+) -> Result<StateAccess, BundleRejection>
+where
+    EvmConfig: ConfigureEvm<Primitives = EthPrimitives>,
+    DB: Database<Error: core::error::Error + Send + Sync + 'static>,
+{
+    // changes are committed to a separate layer that reads through to the payload state
+    let mut db = State::builder().with_database(db).build();
+    let mut evm = evm_config.evm_with_env(&mut db, evm_env);
+    let mut access = StateAccess::default();
+
+    for tx in &bundle.transactions {
+        let tx_hash = *tx.tx_hash();
+        let ResultAndState { result, state } = match evm.transact(evm_config.tx_env(tx)) {
+            Ok(result) => result,
+            Err(err) if err.is_invalid_tx_err() => {
+                return Err(BundleRejection::InvalidTransaction(tx_hash))
+            }
+            Err(err) => return Err(BundleRejection::SimulationFailed(tx_hash, err.to_string())),
+        };
+
+        if !result.is_success() && !bundle.can_revert(&tx_hash) {
+            return Err(BundleRejection::Reverted(tx_hash))
+        }
+
+        for (address, account) in &state {
+            if *address == beneficiary {
+                continue
+            }
+
+            access.read_account(*address);
+            if account.is_touched() {
+                // the layer still holds the account info from before the transaction
+                let original = evm
+                    .db_mut()
+                    .basic(*address)
+                    .map_err(|err| BundleRejection::SimulationFailed(tx_hash, err.to_string()))?;
+                if account.is_created() ||
+                    account.is_selfdestructed() ||
+                    original.unwrap_or_default() != account.info
+                {
+                    access.write_account(*address);
+                }
+            }
+
+            for (slot, value) in &account.storage {
+                if value.is_changed() {
+                    access.write_slot(*address, *slot);
+                } else {
+                    access.read_slot(*address, *slot);
+                }
+            }
+        }
+
+        evm.db_mut().commit(state);
+    }
+
+    Ok(access)
+}
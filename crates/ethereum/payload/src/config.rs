@@ -1,6 +1,7 @@
 use alloy_eips::eip1559::ETHEREUM_BLOCK_GAS_LIMIT_30M;
-use alloy_primitives::Bytes;
+use alloy_primitives::{Address, Bytes};
 use reth_primitives_traits::constants::GAS_LIMIT_BOUND_DIVISOR;
+use std::{collections::BTreeMap, ops::Range};
 
 /// Settings for the Ethereum builder.
 #[derive(PartialEq, Eq, Clone, Debug)]
@@ -16,6 +17,8 @@ pub struct EthereumBuilderConfig {
     pub max_blobs_per_block: Option<u64>,
     /// Extra data for built blocks.
     pub extra_data: Bytes,
+    /// Overrides of the fee recipient suggested in the payload attributes.
+    pub fee_recipient_overrides: FeeRecipientOverrides,
 }
 
 impl Default for EthereumBuilderConfig {
@@ -32,6 +35,7 @@ impl EthereumBuilderConfig {
             await_payload_on_missing: true,
             max_blobs_per_block: None,
             extra_data: Bytes::new(),
+            fee_recipient_overrides: FeeRecipientOverrides::new(),
         }
     }
 
@@ -59,6 +63,15 @@ impl EthereumBuilderConfig {
         self.extra_data = extra_data;
         self
     }
+
+    /// Set the overrides of the fee recipient suggested in the payload attributes.
+    pub fn with_fee_recipient_overrides(
+        mut self,
+        fee_recipient_overrides: FeeRecipientOverrides,
+    ) -> Self {
+        self.fee_recipient_overrides = fee_recipient_overrides;
+        self
+    }
 }
 
 impl EthereumBuilderConfig {
@@ -69,7 +82,114 @@ impl EthereumBuilderConfig {
     }
 }
 
+/// Overrides of the fee recipient suggested by the CL in the payload attributes.
+///
+/// The fee recipient of a block is, in order of precedence:
+/// 1. the recipient of the time window containing the timestamp of the block,
+/// 2. the override of the suggested fee recipient, e.g. of a proposer,
+/// 3. the suggested fee recipient.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FeeRecipientOverrides {
+    /// The fee recipient overrides by suggested fee recipient.
+    by_suggested: BTreeMap<Address, Address>,
+    /// The fee recipients of time windows of block timestamps, ordered by start.
+    windows: Vec<(Range<u64>, Address)>,
+}
+
+impl FeeRecipientOverrides {
+    /// Creates an empty set of overrides.
+    pub const fn new() -> Self {
+        Self { by_suggested: BTreeMap::new(), windows: Vec::new() }
+    }
+
+    /// Adds an override of the given suggested fee recipient.
+    ///
+    /// Fails if the recipient is the zero address, or if the suggested fee recipient already has a
+    /// different override.
+    pub fn with_override(
+        mut self,
+        suggested: Address,
+        recipient: Address,
+    ) -> Result<Self, FeeRecipientOverrideError> {
+        if recipient.is_zero() {
+            return Err(FeeRecipientOverrideError::ZeroRecipient)
+        }
+        match self.by_suggested.insert(suggested, recipient) {
+            Some(previous) if previous != recipient => {
+                Err(FeeRecipientOverrideError::ConflictingOverride(suggested))
+            }
+            _ => Ok(self),
+        }
+    }
+
+    /// Adds a fee recipient for the blocks with a timestamp in the given window.
+    ///
+    /// Fails if the recipient is the zero address, if the window is empty, or if it overlaps with
+    /// another window.
+    pub fn with_window(
+        mut self,
+        window: Range<u64>,
+        recipient: Address,
+    ) -> Result<Self, FeeRecipientOverrideError> {
+        if recipient.is_zero() {
+            return Err(FeeRecipientOverrideError::ZeroRecipient)
+        }
+        if window.is_empty() {
+            return Err(FeeRecipientOverrideError::EmptyWindow(window))
+        }
+        if let Some((other, _)) = self
+            .windows
+            .iter()
+            .find(|(other, _)| window.start < other.end && other.start < window.end)
+        {
+            return Err(FeeRecipientOverrideError::OverlappingWindows(window, other.clone()))
+        }
+
+        let index = self.windows.partition_point(|(other, _)| other.start < window.start);
+        self.windows.insert(index, (window, recipient));
+        Ok(self)
+    }
+
+    /// Returns `true` if there are no overrides.
+    pub fn is_empty(&self) -> bool {
+        self.by_suggested.is_empty() && self.windows.is_empty()
+    }
+
+    /// Returns the fee recipient of the block with the given timestamp and suggested fee
+    /// recipient.
+    pub fn fee_recipient(&self, timestamp: u64, suggested: Address) -> Address {
+        let index = self.windows.partition_point(|(window, _)| window.end <= timestamp);
+        if let Some((window, recipient)) = self.windows.get(index) &&
+            window.contains(&timestamp)
+        {
+            return *recipient
+        }
+
+        self.by_suggested.get(&suggested).copied().unwrap_or(suggested)
+    }
+}
+
+/// Error of an invalid fee recipient override.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum FeeRecipientOverrideError {
+    /// The fee recipient is the zero address.
+    #[error("fee recipient override is the zero address")]
+    ZeroRecipient,
+    /// The suggested fee recipient has conflicting overrides.
+    #[error("conflicting fee recipient overrides for {0}")]
+    ConflictingOverride(Address),
+    /// The time window is empty.
+    #[error("empty fee recipient window {0:?}")]
+    EmptyWindow(Range<u64>),
+    /// The time window overlaps with another one.
+    #[error("fee recipient window {0:?} overlaps with {1:?}")]
+    OverlappingWindows(Range<u64>, Range<u64>),
+}
+
 /// Calculate the gas limit for the next block based on parent and desired gas limits.
+///
+/// The gas limit moves towards the desired gas limit by at most `parent_gas_limit / 1024 - 1` per
+/// block, the maximum change allowed by the protocol.
 /// Ref: <https://github.com/ethereum/go-ethereum/blob/88cbfab332c96edfbe99d161d9df6a40721bd786/core/block_validator.go#L166>
 pub fn calculate_block_gas_limit(parent_gas_limit: u64, desired_gas_limit: u64) -> u64 {
     let delta = (parent_gas_limit / GAS_LIMIT_BOUND_DIVISOR).saturating_sub(1);
@@ -77,3 +197,70 @@ pub fn calculate_block_gas_limit(parent_gas_limit: u64, desired_gas_limit: u64)
     let max_gas_limit = parent_gas_limit + delta;
     desired_gas_limit.clamp(min_gas_limit, max_gas_limit)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    #[test]
+    fn fee_recipient_overrides() {
+        let proposer = address!("0x0000000000000000000000000000000000000001");
+        let override_recipient = address!("0x0000000000000000000000000000000000000002");
+        let window_recipient = address!("0x0000000000000000000000000000000000000003");
+        let other = address!("0x0000000000000000000000000000000000000004");
+
+        let overrides = FeeRecipientOverrides::new()
+            .with_override(proposer, override_recipient)
+            .unwrap()
+            .with_window(200..300, window_recipient)
+            .unwrap()
+            .with_window(100..200, other)
+            .unwrap();
+
+        assert_eq!(overrides.fee_recipient(50, proposer), override_recipient);
+        assert_eq!(overrides.fee_recipient(50, other), other);
+        assert_eq!(overrides.fee_recipient(100, proposer), other);
+        assert_eq!(overrides.fee_recipient(200, proposer), window_recipient);
+        assert_eq!(overrides.fee_recipient(299, other), window_recipient);
+        assert_eq!(overrides.fee_recipient(300, proposer), override_recipient);
+
+        assert_eq!(
+            overrides.clone().with_window(250..350, other),
+            Err(FeeRecipientOverrideError::OverlappingWindows(250..350, 200..300))
+        );
+        assert_eq!(
+            overrides.clone().with_window(300..300, other),
+            Err(FeeRecipientOverrideError::EmptyWindow(300..300))
+        );
+        assert_eq!(
+            overrides.clone().with_override(proposer, other),
+            Err(FeeRecipientOverrideError::ConflictingOverride(proposer))
+        );
+        assert_eq!(
+            overrides.with_override(other, Address::ZERO),
+            Err(FeeRecipientOverrideError::ZeroRecipient)
+        );
+    }
+
+    #[test]
+    fn gas_limit_moves_towards_desired() {
+        let desired = 36_000_000;
+
+        // increases by the maximum delta until the desired gas limit is reached
+        let mut gas_limit = 30_000_000;
+        let mut blocks = 0;
+        while gas_limit != desired {
+            let next = calculate_block_gas_limit(gas_limit, desired);
+            assert_eq!(next, (gas_limit + gas_limit / GAS_LIMIT_BOUND_DIVISOR - 1).min(desired));
+            gas_limit = next;
+            blocks += 1;
+        }
+        assert_eq!(blocks, 187);
+
+        // decreases by the maximum delta
+        assert_eq!(calculate_block_gas_limit(60_000_000, desired), 60_000_000 - 58_592);
+        // stays at the desired gas limit
+        assert_eq!(calculate_block_gas_limit(desired, desired), desired);
+    }
+}
@@ -9,7 +9,7 @@
 #![cfg_attr(docsrs, feature(doc_cfg))]
 #![allow(clippy::useless_let_if_seq)]
 
-use alloy_consensus::Transaction;
+use alloy_consensus::{transaction::Recovered, Transaction};
 use alloy_primitives::U256;
 use alloy_rlp::Encodable;
 use reth_basic_payload_builder::{
@@ -25,7 +25,10 @@ use reth_evm::{
     ConfigureEvm, Evm, NextBlockEnvAttributes,
 };
 use reth_evm_ethereum::EthEvmConfig;
-use reth_payload_builder::{BlobSidecars, EthBuiltPayload, EthPayloadBuilderAttributes};
+use reth_payload_builder::{
+    BlobSidecars, EthBuiltPayload, EthPayloadBuilderAttributes, NoopBundleSource, PayloadBundle,
+    PayloadBundleSource, StateAccess,
+};
 use reth_payload_builder_primitives::PayloadBuilderError;
 use reth_payload_primitives::PayloadBuilderAttributes;
 use reth_primitives_traits::transaction::error::InvalidTransactionError;
@@ -38,11 +41,17 @@ use reth_transaction_pool::{
 };
 use revm::context_interface::Block as _;
 use std::sync::Arc;
-use tracing::{debug, trace, warn};
+use tracing::{debug, info, trace, warn};
+
+mod bundle;
+use bundle::{simulate_bundle, BundleRejection};
 
 mod config;
 pub use config::*;
 
+mod metrics;
+use metrics::GasLimitMetrics;
+
 pub mod validator;
 pub use validator::EthereumExecutionPayloadValidator;
 
@@ -52,7 +61,12 @@ type BestTransactionsIter<Pool> = Box<
 
 /// Ethereum payload builder
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct EthereumPayloadBuilder<Pool, Client, EvmConfig = EthEvmConfig> {
+pub struct EthereumPayloadBuilder<
+    Pool,
+    Client,
+    EvmConfig = EthEvmConfig,
+    Bundles = NoopBundleSource,
+> {
     /// Client providing access to node state.
     client: Client,
     /// Transaction pool.
@@ -61,6 +75,8 @@ pub struct EthereumPayloadBuilder<Pool, Client, EvmConfig = EthEvmConfig> {
     evm_config: EvmConfig,
     /// Payload builder configuration.
     builder_config: EthereumBuilderConfig,
+    /// Source of external bundles merged into the top of built payloads.
+    bundles: Bundles,
 }
 
 impl<Pool, Client, EvmConfig> EthereumPayloadBuilder<Pool, Client, EvmConfig> {
@@ -71,16 +87,48 @@ impl<Pool, Client, EvmConfig> EthereumPayloadBuilder<Pool, Client, EvmConfig> {
         evm_config: EvmConfig,
         builder_config: EthereumBuilderConfig,
     ) -> Self {
-        Self { client, pool, evm_config, builder_config }
+        Self { client, pool, evm_config, builder_config, bundles: NoopBundleSource }
+    }
+}
+
+impl<Pool, Client, EvmConfig, Bundles> EthereumPayloadBuilder<Pool, Client, EvmConfig, Bundles> {
+    /// Returns the payload builder configuration.
+    pub const fn builder_config(&self) -> &EthereumBuilderConfig {
+        &self.builder_config
+    }
+
+    /// Replaces the payload builder configuration.
+    ///
+    /// This can be used by builders that wrap this type to derive the configuration of a single
+    /// payload job from custom payload attributes.
+    pub fn with_builder_config(mut self, builder_config: EthereumBuilderConfig) -> Self {
+        self.builder_config = builder_config;
+        self
+    }
+
+    /// Configures the source of external bundles that are merged into built payloads.
+    ///
+    /// See [`default_ethereum_payload_with_bundles`] for how bundles are merged.
+    pub fn with_bundle_source<B>(
+        self,
+        bundles: B,
+    ) -> EthereumPayloadBuilder<Pool, Client, EvmConfig, B>
+    where
+        B: PayloadBundleSource<Recovered<TransactionSigned>>,
+    {
+        let Self { client, pool, evm_config, builder_config, .. } = self;
+        EthereumPayloadBuilder { client, pool, evm_config, builder_config, bundles }
     }
 }
 
 // Default implementation of [PayloadBuilder] for unit type
-impl<Pool, Client, EvmConfig> PayloadBuilder for EthereumPayloadBuilder<Pool, Client, EvmConfig>
+impl<Pool, Client, EvmConfig, Bundles> PayloadBuilder
+    for EthereumPayloadBuilder<Pool, Client, EvmConfig, Bundles>
 where
     EvmConfig: ConfigureEvm<Primitives = EthPrimitives, NextBlockEnvCtx = NextBlockEnvAttributes>,
     Client: StateProviderFactory + ChainSpecProvider<ChainSpec: EthereumHardforks> + Clone,
     Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TransactionSigned>>,
+    Bundles: PayloadBundleSource<Recovered<TransactionSigned>>,
 {
     type Attributes = EthPayloadBuilderAttributes;
     type BuiltPayload = EthBuiltPayload;
@@ -89,12 +137,14 @@ where
         &self,
         args: BuildArguments<EthPayloadBuilderAttributes, EthBuiltPayload>,
     ) -> Result<BuildOutcome<EthBuiltPayload>, PayloadBuilderError> {
-        default_ethereum_payload(
+        let bundles = self.bundles.bundles(args.config.parent_header.number + 1);
+        default_ethereum_payload_with_bundles(
             self.evm_config.clone(),
             self.client.clone(),
             self.pool.clone(),
             self.builder_config.clone(),
             args,
+            bundles,
             |attributes| self.pool.best_transactions_with_attributes(attributes),
         )
     }
@@ -149,7 +199,44 @@ where
     Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TransactionSigned>>,
     F: FnOnce(BestTransactionsAttributes) -> BestTransactionsIter<Pool>,
 {
-    let BuildArguments { mut cached_reads, config, cancel, best_payload } = args;
+    default_ethereum_payload_with_bundles(
+        evm_config,
+        client,
+        pool,
+        builder_config,
+        args,
+        Vec::new(),
+        best_txs,
+    )
+}
+
+/// Constructs an Ethereum transaction payload like [`default_ethereum_payload`], with the given
+/// external bundles merged into its top.
+///
+/// The bundles are merged in order, before any pool transactions. Each bundle is simulated on top
+/// of the payload first and is skipped unless
+/// - all of its transactions are valid and none of them reverts, unless allowed to, and
+/// - it doesn't access state written by an already merged bundle, or write state accessed by one,
+///   so that merged bundles are independent of each other.
+///
+/// A bundle that can't be simulated is skipped as well, so it never fails the build.
+#[inline]
+pub fn default_ethereum_payload_with_bundles<EvmConfig, Client, Pool, F>(
+    evm_config: EvmConfig,
+    client: Client,
+    pool: Pool,
+    builder_config: EthereumBuilderConfig,
+    args: BuildArguments<EthPayloadBuilderAttributes, EthBuiltPayload>,
+    bundles: Vec<PayloadBundle<Recovered<TransactionSigned>>>,
+    best_txs: F,
+) -> Result<BuildOutcome<EthBuiltPayload>, PayloadBuilderError>
+where
+    EvmConfig: ConfigureEvm<Primitives = EthPrimitives, NextBlockEnvCtx = NextBlockEnvAttributes>,
+    Client: StateProviderFactory + ChainSpecProvider<ChainSpec: EthereumHardforks>,
+    Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TransactionSigned>>,
+    F: FnOnce(BestTransactionsAttributes) -> BestTransactionsIter<Pool>,
+{
+    let BuildArguments { mut cached_reads, config, cancel, interrupt, best_payload } = args;
     let PayloadConfig { parent_header, attributes } = config;
 
     let state_provider = client.state_by_block_hash(parent_header.hash())?;
@@ -157,21 +244,48 @@ where
     let mut db =
         State::builder().with_database(cached_reads.as_db_mut(state)).with_bundle_update().build();
 
-    let mut builder = evm_config
-        .builder_for_next_block(
-            &mut db,
-            &parent_header,
-            NextBlockEnvAttributes {
-                timestamp: attributes.timestamp(),
-                suggested_fee_recipient: attributes.suggested_fee_recipient(),
-                prev_randao: attributes.prev_randao(),
-                gas_limit: builder_config.gas_limit(parent_header.gas_limit),
-                parent_beacon_block_root: attributes.parent_beacon_block_root(),
-                withdrawals: Some(attributes.withdrawals().clone()),
-                extra_data: builder_config.extra_data,
-            },
-        )
+    // the gas limit moves towards the desired gas limit within the protocol bounds
+    let gas_limit = builder_config.gas_limit(parent_header.gas_limit);
+    GasLimitMetrics::default().set_gas_limits(
+        builder_config.desired_gas_limit,
+        parent_header.gas_limit,
+        gas_limit,
+    );
+
+    let suggested_fee_recipient = attributes.suggested_fee_recipient();
+    let fee_recipient = builder_config
+        .fee_recipient_overrides
+        .fee_recipient(attributes.timestamp(), suggested_fee_recipient);
+    if fee_recipient != suggested_fee_recipient {
+        // logged once per payload, on its first build
+        if best_payload.is_none() {
+            info!(target: "payload_builder", id=%attributes.id, timestamp = attributes.timestamp(), %suggested_fee_recipient, %fee_recipient, "Overriding suggested fee recipient");
+        } else {
+            trace!(target: "payload_builder", id=%attributes.id, %suggested_fee_recipient, %fee_recipient, "Overriding suggested fee recipient");
+        }
+    }
+
+    let next_block_attributes = NextBlockEnvAttributes {
+        timestamp: attributes.timestamp(),
+        suggested_fee_recipient: fee_recipient,
+        prev_randao: attributes.prev_randao(),
+        gas_limit,
+        parent_beacon_block_root: attributes.parent_beacon_block_root(),
+        withdrawals: Some(attributes.withdrawals().clone()),
+        extra_data: builder_config.extra_data,
+    };
+    // bundles are simulated in the environment of the payload
+    let evm_env = evm_config
+        .next_evm_env(&parent_header, &next_block_attributes)
+        .map_err(PayloadBuilderError::other)?;
+    let ctx = evm_config
+        .context_for_next_block(&parent_header, next_block_attributes)
         .map_err(PayloadBuilderError::other)?;
+    let mut builder = evm_config.create_block_builder(
+        evm_config.evm_with_env(&mut db, evm_env.clone()),
+        &parent_header,
+        ctx,
+    );
 
     let chain_spec = client.chain_spec();
 
@@ -213,6 +327,86 @@ where
 
     let withdrawals_rlp_length = attributes.withdrawals().length();
 
+    let block_number = parent_header.number + 1;
+    let beneficiary = builder.evm_mut().block().beneficiary();
+    let mut merged_access = StateAccess::default();
+    for bundle in bundles {
+        // check if the job was cancelled, if so we can exit early
+        if cancel.is_cancelled() {
+            return Ok(BuildOutcome::Cancelled)
+        }
+
+        // check if the payload was requested, if so it's sealed with the transactions so far
+        if interrupt.is_cancelled() {
+            debug!(target: "payload_builder", id=%attributes.id, "payload build interrupted");
+            break
+        }
+
+        if !bundle.is_valid_for(block_number) {
+            continue
+        }
+
+        // blob sidecars aren't provided with bundles
+        if bundle.transactions.iter().any(|tx| tx.is_eip4844()) {
+            trace!(target: "payload_builder", "skipping bundle with blob transactions");
+            continue
+        }
+
+        let bundle_gas_limit = bundle.transactions.iter().map(|tx| tx.gas_limit()).sum::<u64>();
+        if cumulative_gas_used + bundle_gas_limit > block_gas_limit {
+            trace!(target: "payload_builder", bundle_gas_limit, "skipping bundle exceeding the block gas limit");
+            continue
+        }
+
+        let bundle_rlp_length =
+            bundle.transactions.iter().map(|tx| tx.inner().length()).sum::<usize>();
+        let estimated_block_size_with_bundle =
+            block_transactions_rlp_length + bundle_rlp_length + withdrawals_rlp_length + 1024;
+        if is_osaka && estimated_block_size_with_bundle > MAX_RLP_BLOCK_SIZE {
+            trace!(target: "payload_builder", estimated_block_size_with_bundle, "skipping bundle exceeding the max block size");
+            continue
+        }
+
+        let access = match simulate_bundle(
+            &evm_config,
+            evm_env.clone(),
+            &mut **builder.evm_mut().db_mut(),
+            &bundle,
+            beneficiary,
+        ) {
+            Ok(access) => access,
+            Err(rejection) => {
+                trace!(target: "payload_builder", ?rejection, "skipping rejected bundle");
+                continue
+            }
+        };
+
+        if access.conflicts_with(&merged_access) {
+            trace!(target: "payload_builder", "skipping bundle conflicting with merged bundles");
+            continue
+        }
+
+        // the simulation ran on top of the same state, so the bundle executes identically
+        for tx in bundle.transactions {
+            let tx_rlp_len = tx.inner().length();
+            let gas_used = match builder.execute_transaction(tx.clone()) {
+                Ok(gas_used) => gas_used,
+                // this is an error that we should treat as fatal for this attempt
+                Err(err) => return Err(PayloadBuilderError::evm(err)),
+            };
+
+            block_transactions_rlp_length += tx_rlp_len;
+
+            // update and add to total fees
+            let miner_fee = tx
+                .effective_tip_per_gas(base_fee)
+                .expect("fee is always valid; execution succeeded");
+            total_fees += U256::from(miner_fee) * U256::from(gas_used);
+            cumulative_gas_used += gas_used;
+        }
+        merged_access.extend(access);
+    }
+
     while let Some(pool_tx) = best_txs.next() {
         // ensure we still have capacity for this transaction
         if cumulative_gas_used + pool_tx.gas_limit() > block_gas_limit {
@@ -231,6 +425,12 @@ where
             return Ok(BuildOutcome::Cancelled)
         }
 
+        // check if the payload was requested, if so it's sealed with the transactions so far
+        if interrupt.is_cancelled() {
+            debug!(target: "payload_builder", id=%attributes.id, "payload build interrupted");
+            break
+        }
+
         // convert tx to a signed transaction
         let tx = pool_tx.to_consensus();
 
@@ -0,0 +1,23 @@
+//! Ethereum payload builder metrics.
+
+use reth_metrics::{metrics::Gauge, Metrics};
+
+/// Gas limit metrics of the Ethereum payload builder.
+#[derive(Metrics, Clone)]
+#[metrics(scope = "payloads.gas_limit")]
+pub(crate) struct GasLimitMetrics {
+    /// The configured target gas limit of built blocks
+    pub(crate) target: Gauge,
+    /// The gas limit of the parent of the last built block
+    pub(crate) parent: Gauge,
+    /// The gas limit of the last built block
+    pub(crate) built: Gauge,
+}
+
+impl GasLimitMetrics {
+    pub(crate) fn set_gas_limits(&self, target: u64, parent: u64, built: u64) {
+        self.target.set(target as f64);
+        self.parent.set(parent as f64);
+        self.built.set(built as f64);
+    }
+}
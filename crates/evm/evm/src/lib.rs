@@ -52,6 +52,14 @@ pub use engine::{ConfigureEngineEvm, ExecutableTxIterator, ExecutableTxTuple};
 #[cfg(feature = "metrics")]
 pub mod metrics;
 pub mod noop;
+
+mod precompiles_hook;
+pub use precompiles_hook::{ConfigureEvmPrecompiles, PrecompilesHook, WithPrecompiles};
+
+pub mod stateless;
+
+mod system_tx;
+pub use system_tx::SystemTxBlockExecutor;
 #[cfg(any(test, feature = "test-utils"))]
 /// test helpers for mocking executor
 pub mod test_utils;
@@ -0,0 +1,136 @@
+//! Customization of the precompiles of the configured EVM.
+
+use crate::{ConfigureEvm, SpecFor};
+use alloc::sync::Arc;
+use alloy_evm::{precompiles::PrecompilesMap, Database, Evm, EvmEnv, EvmFactory};
+use core::{error::Error, fmt};
+use revm::{inspector::NoOpInspector, Inspector};
+
+/// A hook that customizes the precompiles of an EVM created for the given spec.
+///
+/// The hook can register additional precompiles or override existing ones, e.g. via
+/// [`PrecompilesMap::apply_precompile`].
+pub type PrecompilesHook<Spec> = Arc<dyn Fn(&Spec, &mut PrecompilesMap) + Send + Sync>;
+
+/// An [`EvmFactory`] that applies a [`PrecompilesHook`] to every EVM created by the inner
+/// factory.
+#[derive(Clone)]
+pub struct WithPrecompiles<F: EvmFactory> {
+    /// The wrapped factory.
+    inner: F,
+    /// The hook applied to the precompiles of every created EVM.
+    hook: PrecompilesHook<F::Spec>,
+}
+
+impl<F: EvmFactory> WithPrecompiles<F> {
+    /// Creates a new factory that applies the given hook to the EVMs created by `inner`.
+    pub fn new(inner: F, hook: PrecompilesHook<F::Spec>) -> Self {
+        Self { inner, hook }
+    }
+
+    /// Returns the wrapped factory.
+    pub const fn inner(&self) -> &F {
+        &self.inner
+    }
+}
+
+impl<F: EvmFactory + fmt::Debug> fmt::Debug for WithPrecompiles<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WithPrecompiles").field("inner", &self.inner).finish_non_exhaustive()
+    }
+}
+
+impl<F> EvmFactory for WithPrecompiles<F>
+where
+    F: EvmFactory<Precompiles = PrecompilesMap>,
+{
+    type Evm<DB: Database, I: Inspector<Self::Context<DB>>> = F::Evm<DB, I>;
+    type Context<DB: Database> = F::Context<DB>;
+    type Tx = F::Tx;
+    type Error<DBError: Error + Send + Sync + 'static> = F::Error<DBError>;
+    type HaltReason = F::HaltReason;
+    type Spec = F::Spec;
+    type BlockEnv = F::BlockEnv;
+    type Precompiles = PrecompilesMap;
+
+    fn create_evm<DB: Database>(
+        &self,
+        db: DB,
+        input: EvmEnv<Self::Spec, Self::BlockEnv>,
+    ) -> Self::Evm<DB, NoOpInspector> {
+        let spec = input.cfg_env.spec;
+        let mut evm = self.inner.create_evm(db, input);
+        (self.hook)(&spec, evm.precompiles_mut());
+        evm
+    }
+
+    fn create_evm_with_inspector<DB: Database, I: Inspector<Self::Context<DB>>>(
+        &self,
+        db: DB,
+        input: EvmEnv<Self::Spec, Self::BlockEnv>,
+        inspector: I,
+    ) -> Self::Evm<DB, I> {
+        let spec = input.cfg_env.spec;
+        let mut evm = self.inner.create_evm_with_inspector(db, input, inspector);
+        (self.hook)(&spec, evm.precompiles_mut());
+        evm
+    }
+}
+
+/// A [`ConfigureEvm`] whose EVM precompiles can be customized with a [`PrecompilesHook`].
+///
+/// This allows registering additional precompiles or overriding existing ones without
+/// implementing a custom [`EvmFactory`].
+pub trait ConfigureEvmPrecompiles: ConfigureEvm {
+    /// The EVM configuration that applies the hook.
+    type WithPrecompiles: ConfigureEvm<
+        Primitives = Self::Primitives,
+        NextBlockEnvCtx = Self::NextBlockEnvCtx,
+    >;
+
+    /// Returns the EVM configuration that applies the given hook to the precompiles of every EVM
+    /// it creates.
+    fn with_precompiles(self, hook: PrecompilesHook<SpecFor<Self>>) -> Self::WithPrecompiles;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_evm::{
+        precompiles::{DynPrecompile, PrecompileInput},
+        EthEvmFactory,
+    };
+    use alloy_primitives::{Address, Bytes};
+    use revm::{
+        database::EmptyDB,
+        precompile::{PrecompileId, PrecompileOutput},
+        primitives::hardfork::SpecId,
+    };
+
+    #[test]
+    fn applies_hook_to_created_evms() {
+        let address = Address::repeat_byte(0x42);
+        let factory = WithPrecompiles::new(
+            EthEvmFactory::default(),
+            Arc::new(move |spec: &SpecId, precompiles: &mut PrecompilesMap| {
+                if *spec >= SpecId::PRAGUE {
+                    precompiles.apply_precompile(&address, |_| {
+                        Some(DynPrecompile::from((
+                            PrecompileId::custom("custom"),
+                            |_: PrecompileInput<'_>| Ok(PrecompileOutput::new(0, Bytes::new())),
+                        )))
+                    });
+                }
+            }),
+        );
+
+        let mut env = EvmEnv::default();
+        env.cfg_env.spec = SpecId::PRAGUE;
+        let mut evm = factory.create_evm(EmptyDB::default(), env.clone());
+        assert!(evm.precompiles_mut().get(&address).is_some());
+
+        env.cfg_env.spec = SpecId::CANCUN;
+        let mut evm = factory.create_evm_with_inspector(EmptyDB::default(), env, NoOpInspector {});
+        assert!(evm.precompiles_mut().get(&address).is_none());
+    }
+}
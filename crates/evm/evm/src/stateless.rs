@@ -0,0 +1,184 @@
+//! Stateless block execution against witness data.
+//!
+//! [`StatelessBlockExecutor`] executes a block without access to a live database. All state reads
+//! are served by a [`StatelessState`], which is populated from an execution witness (trie nodes
+//! and bytecodes) and verified against the parent state root. After execution, the post-state
+//! root is computed from the execution output and checked against the block header.
+
+use crate::{execute::Executor, ConfigureEvm};
+use alloc::{collections::BTreeMap, format};
+use alloy_consensus::BlockHeader;
+use alloy_primitives::{map::B256Map, Address, B256, U256};
+use core::fmt::Debug;
+use reth_execution_errors::BlockExecutionError;
+use reth_execution_types::BlockExecutionOutput;
+use reth_primitives_traits::{BlockTy, ReceiptTy, RecoveredBlock};
+use reth_storage_errors::provider::ProviderError;
+use reth_trie_common::{HashedPostState, KeccakKeyHasher, TrieAccount};
+use revm::{bytecode::Bytecode, state::AccountInfo, Database};
+
+/// State backed by witness data, used by the [`StatelessBlockExecutor`].
+///
+/// Implementations are expected to be populated from an execution witness and verified against
+/// the state root of the parent block before being used for execution.
+pub trait StatelessState: Debug {
+    /// The error returned if the post-state root can't be computed.
+    type Error: core::error::Error;
+
+    /// Returns the [`TrieAccount`] of the given address.
+    ///
+    /// Returns an error if the witness can not prove whether the account exists.
+    fn account(&self, address: Address) -> Result<Option<TrieAccount>, ProviderError>;
+
+    /// Returns the value of the given storage slot of the given address.
+    ///
+    /// Returns an error if the witness can not prove the value of the slot.
+    fn storage(&self, address: Address, slot: U256) -> Result<U256, ProviderError>;
+
+    /// Applies the given state changes and returns the new state root.
+    fn state_root(&mut self, state: HashedPostState) -> Result<B256, Self::Error>;
+}
+
+/// Errors that can occur during stateless block execution.
+#[derive(Debug, thiserror::Error)]
+pub enum StatelessExecutionError<E> {
+    /// Error during block execution.
+    #[error(transparent)]
+    Execution(#[from] BlockExecutionError),
+    /// Error while computing the post-state root.
+    #[error("failed to compute post-state root: {0}")]
+    StateRoot(#[source] E),
+    /// The computed post-state root does not match the one in the block header.
+    #[error("mismatched post-state root: got {got}, expected {expected}")]
+    StateRootMismatch {
+        /// The computed post-state root.
+        got: B256,
+        /// The post-state root in the block header.
+        expected: B256,
+    },
+}
+
+/// Executes blocks against witness data instead of a live database, validating the resulting
+/// state root.
+///
+/// This is useful for environments without access to a full node's database, such as zk
+/// coprocessors or tools cross-validating block execution.
+#[derive(Debug)]
+pub struct StatelessBlockExecutor<E, S> {
+    /// The EVM configuration used to execute the block.
+    evm_config: E,
+    /// The pre-state of the block.
+    state: S,
+    /// Bytecodes of all contracts accessed during execution, by code hash.
+    bytecodes: B256Map<Bytecode>,
+    /// Hashes of the ancestor blocks, by block number. Used to serve the `BLOCKHASH` opcode.
+    block_hashes: BTreeMap<u64, B256>,
+}
+
+impl<E, S> StatelessBlockExecutor<E, S>
+where
+    E: ConfigureEvm,
+    S: StatelessState,
+{
+    /// Creates a new [`StatelessBlockExecutor`].
+    ///
+    /// The `state` must already be verified against the state root of the parent block. The
+    /// `block_hashes` are expected to belong to a contiguous chain of ancestors of the executed
+    /// block.
+    pub const fn new(
+        evm_config: E,
+        state: S,
+        bytecodes: B256Map<Bytecode>,
+        block_hashes: BTreeMap<u64, B256>,
+    ) -> Self {
+        Self { evm_config, state, bytecodes, block_hashes }
+    }
+
+    /// Executes the block and validates that the resulting state root matches the one in the
+    /// block header.
+    ///
+    /// Returns the execution output on success.
+    pub fn execute(
+        mut self,
+        block: &RecoveredBlock<BlockTy<E::Primitives>>,
+    ) -> Result<BlockExecutionOutput<ReceiptTy<E::Primitives>>, StatelessExecutionError<S::Error>>
+    {
+        let output = self.execute_without_state_root(block)?;
+        self.validate_state_root(block, &output)?;
+        Ok(output)
+    }
+
+    /// Executes the block without validating the resulting state root.
+    ///
+    /// This allows running cheaper post-execution checks before the state root is validated with
+    /// [`Self::validate_state_root`].
+    pub fn execute_without_state_root(
+        &self,
+        block: &RecoveredBlock<BlockTy<E::Primitives>>,
+    ) -> Result<BlockExecutionOutput<ReceiptTy<E::Primitives>>, StatelessExecutionError<S::Error>>
+    {
+        let db = StatelessDatabase {
+            state: &self.state,
+            bytecodes: &self.bytecodes,
+            block_hashes: &self.block_hashes,
+        };
+        Ok(self.evm_config.executor(db).execute(block)?)
+    }
+
+    /// Validates that the state root after applying the execution output matches the one in the
+    /// block header.
+    pub fn validate_state_root(
+        &mut self,
+        block: &RecoveredBlock<BlockTy<E::Primitives>>,
+        output: &BlockExecutionOutput<ReceiptTy<E::Primitives>>,
+    ) -> Result<(), StatelessExecutionError<S::Error>> {
+        let hashed_state =
+            HashedPostState::from_bundle_state::<KeccakKeyHasher>(&output.state.state);
+        let state_root =
+            self.state.state_root(hashed_state).map_err(StatelessExecutionError::StateRoot)?;
+        if state_root != block.header().state_root() {
+            return Err(StatelessExecutionError::StateRootMismatch {
+                got: state_root,
+                expected: block.header().state_root(),
+            })
+        }
+
+        Ok(())
+    }
+}
+
+/// A [`Database`] serving reads from a [`StatelessState`], bytecodes and ancestor block hashes.
+#[derive(Debug)]
+struct StatelessDatabase<'a, S> {
+    state: &'a S,
+    bytecodes: &'a B256Map<Bytecode>,
+    block_hashes: &'a BTreeMap<u64, B256>,
+}
+
+impl<S: StatelessState> Database for StatelessDatabase<'_, S> {
+    type Error = ProviderError;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        Ok(self.state.account(address)?.map(|account| AccountInfo {
+            balance: account.balance,
+            nonce: account.nonce,
+            code_hash: account.code_hash,
+            code: None,
+            account_id: None,
+        }))
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        self.bytecodes.get(&code_hash).cloned().ok_or_else(|| {
+            ProviderError::TrieWitnessError(format!("bytecode for {code_hash} not found"))
+        })
+    }
+
+    fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        self.state.storage(address, index)
+    }
+
+    fn block_hash(&mut self, number: u64) -> Result<B256, Self::Error> {
+        self.block_hashes.get(&number).copied().ok_or(ProviderError::StateForNumberNotFound(number))
+    }
+}
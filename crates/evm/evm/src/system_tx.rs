@@ -0,0 +1,269 @@
+//! Block executor that injects the system transactions scheduled by the chain specification.
+
+use crate::{
+    execute::{BlockExecutionError, BlockExecutor},
+    Evm, OnStateHook,
+};
+use alloc::{boxed::Box, format};
+use alloy_evm::block::{ExecutableTx, StateChangeSource};
+use alloy_primitives::Address;
+use reth_chainspec::{ScheduledSystemTransaction, SystemTransactionPosition};
+use reth_execution_types::BlockExecutionResult;
+use revm::{context::Block as _, state::EvmState, DatabaseCommit};
+#[cfg(feature = "std")]
+use std::sync::{Arc, Mutex};
+
+/// A [`BlockExecutor`] that executes the due [`ScheduledSystemTransaction`]s around the block
+/// executed by the wrapped executor.
+///
+/// The [`SystemTransactionPosition::PreBlock`] system transactions are executed after the
+/// pre-execution changes of the wrapped executor, the [`SystemTransactionPosition::PostBlock`]
+/// system transactions before its post-execution changes, in the order they are scheduled.
+///
+/// System transactions are executed as system calls: they don't consume block gas and don't
+/// produce receipts, so the receipts and the gas used of the block only reflect its transactions.
+/// They are not part of the block body, and receipts are stored by transaction number, so a
+/// receipt of a system transaction would shift the receipts of all following transactions.
+/// Because both block import and payload building go through the executor, the system
+/// transactions are applied identically for both.
+///
+/// The full state diff of a system transaction is committed, only the nonce increment of the
+/// caller and the untouched caller and beneficiary accounts are dropped, so that value transfers
+/// to either of them are kept.
+///
+/// The state changes of the system transactions are reported to the state hook as
+/// [`StateChangeSource::Transaction`] with the index of the next transaction of the block. Without
+/// the `std` feature the hook is passed to the wrapped executor as is and only observes the
+/// transactions of the block.
+pub struct SystemTxBlockExecutor<'a, E> {
+    /// The wrapped executor.
+    inner: E,
+    /// The system transactions scheduled by the chain.
+    system_transactions: &'a [ScheduledSystemTransaction],
+    /// The state hook, shared with the wrapped executor.
+    #[cfg(feature = "std")]
+    state_hook: Arc<Mutex<Option<Box<dyn OnStateHook>>>>,
+}
+
+impl<'a, E> SystemTxBlockExecutor<'a, E> {
+    /// Creates a new executor that executes the scheduled system transactions around the blocks
+    /// executed by the given executor.
+    pub fn new(inner: E, system_transactions: &'a [ScheduledSystemTransaction]) -> Self {
+        Self {
+            inner,
+            system_transactions,
+            #[cfg(feature = "std")]
+            state_hook: Default::default(),
+        }
+    }
+
+    /// Returns the wrapped executor.
+    pub const fn inner(&self) -> &E {
+        &self.inner
+    }
+}
+
+impl<E> SystemTxBlockExecutor<'_, E>
+where
+    E: BlockExecutor<Evm: Evm<DB: DatabaseCommit>>,
+{
+    /// Executes the due system transactions at the given position and commits their state.
+    fn apply_system_transactions(
+        &mut self,
+        position: SystemTransactionPosition,
+    ) -> Result<(), BlockExecutionError> {
+        let block_number = self.inner.evm().block().number().saturating_to();
+        #[cfg(feature = "std")]
+        let source = StateChangeSource::Transaction(self.inner.receipts().len());
+        let system_transactions = self.system_transactions;
+
+        for tx in system_transactions
+            .iter()
+            .filter(|tx| tx.position == position && tx.is_due(block_number))
+        {
+            let evm = self.inner.evm_mut();
+            let mut state = evm
+                .transact_system_call(tx.from, tx.to, tx.input.clone())
+                .map_err(|err| {
+                    BlockExecutionError::msg(format!(
+                        "scheduled system transaction to {} failed: {err}",
+                        tx.to
+                    ))
+                })?
+                .state;
+
+            strip_system_call_accounting(&mut state, tx.from, evm.block().beneficiary());
+
+            #[cfg(feature = "std")]
+            if let Some(hook) = self.state_hook.lock().unwrap().as_mut() {
+                hook.on_state(source, &state);
+            }
+            evm.db_mut().commit(state);
+        }
+
+        Ok(())
+    }
+}
+
+impl<E> BlockExecutor for SystemTxBlockExecutor<'_, E>
+where
+    E: BlockExecutor<Evm: Evm<DB: DatabaseCommit>>,
+{
+    type Transaction = E::Transaction;
+    type Receipt = E::Receipt;
+    type Evm = E::Evm;
+    type Result = E::Result;
+
+    fn apply_pre_execution_changes(&mut self) -> Result<(), BlockExecutionError> {
+        self.inner.apply_pre_execution_changes()?;
+        self.apply_system_transactions(SystemTransactionPosition::PreBlock)
+    }
+
+    fn receipts(&self) -> &[Self::Receipt] {
+        self.inner.receipts()
+    }
+
+    fn execute_transaction_without_commit(
+        &mut self,
+        tx: impl ExecutableTx<Self>,
+    ) -> Result<Self::Result, BlockExecutionError> {
+        self.inner.execute_transaction_without_commit(tx)
+    }
+
+    fn commit_transaction(&mut self, output: Self::Result) -> Result<u64, BlockExecutionError> {
+        self.inner.commit_transaction(output)
+    }
+
+    fn finish(
+        mut self,
+    ) -> Result<(Self::Evm, BlockExecutionResult<Self::Receipt>), BlockExecutionError> {
+        self.apply_system_transactions(SystemTransactionPosition::PostBlock)?;
+        self.inner.finish()
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn set_state_hook(&mut self, hook: Option<Box<dyn OnStateHook>>) {
+        self.inner.set_state_hook(hook);
+    }
+
+    #[cfg(feature = "std")]
+    fn set_state_hook(&mut self, hook: Option<Box<dyn OnStateHook>>) {
+        if self.system_transactions.is_empty() {
+            // nothing to report, the hook can be used by the wrapped executor directly
+            self.inner.set_state_hook(hook);
+            return
+        }
+
+        let forward = hook.is_some().then(|| {
+            let hook = self.state_hook.clone();
+            Box::new(move |source: StateChangeSource, state: &EvmState| {
+                if let Some(hook) = hook.lock().unwrap().as_mut() {
+                    hook.on_state(source, state);
+                }
+            }) as Box<dyn OnStateHook>
+        });
+        *self.state_hook.lock().unwrap() = hook;
+        self.inner.set_state_hook(forward);
+    }
+
+    fn evm_mut(&mut self) -> &mut Self::Evm {
+        self.inner.evm_mut()
+    }
+
+    fn evm(&self) -> &Self::Evm {
+        self.inner.evm()
+    }
+}
+
+/// Removes the accounting of a system call from its state diff.
+///
+/// Like other system calls, a system transaction is executed without gas price, so the
+/// beneficiary is only touched and the caller only has its nonce incremented. The nonce is
+/// restored and both accounts are dropped from the diff if nothing else changed them, while value
+/// transfers to or storage changes of either account are kept.
+fn strip_system_call_accounting(state: &mut EvmState, caller: Address, beneficiary: Address) {
+    if let Some(account) = state.get_mut(&caller) {
+        account.info.nonce = account.original_info.nonce;
+    }
+
+    for address in [caller, beneficiary] {
+        let unchanged = state.get(&address).is_some_and(|account| {
+            account.info == *account.original_info &&
+                !account.is_selfdestructed() &&
+                !account.is_created() &&
+                !account.storage.values().any(|slot| slot.is_changed())
+        });
+        if unchanged {
+            state.remove(&address);
+        }
+    }
+}
+
+impl<E: core::fmt::Debug> core::fmt::Debug for SystemTxBlockExecutor<'_, E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SystemTxBlockExecutor")
+            .field("inner", &self.inner)
+            .field("system_transactions", &self.system_transactions)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, U256};
+    use revm::state::{Account, AccountInfo};
+
+    fn account(original: AccountInfo, info: AccountInfo) -> Account {
+        let mut account = Account::from(original.clone());
+        account.original_info = Box::new(original);
+        account.info = info;
+        account.mark_touch();
+        account
+    }
+
+    #[test]
+    fn strips_only_system_call_accounting() {
+        let caller = address!("0x000000000000000000000000000000000000c411");
+        let beneficiary = address!("0x000000000000000000000000000000000000c014");
+        let contract = address!("0x000000000000000000000000000000000000beef");
+
+        // the caller only has its nonce incremented and the beneficiary is only touched
+        let mut state = EvmState::default();
+        state.insert(
+            caller,
+            account(AccountInfo::default(), AccountInfo { nonce: 1, ..Default::default() }),
+        );
+        state.insert(beneficiary, account(AccountInfo::default(), AccountInfo::default()));
+        state.insert(
+            contract,
+            account(
+                AccountInfo { balance: U256::from(10), ..Default::default() },
+                AccountInfo { balance: U256::from(3), ..Default::default() },
+            ),
+        );
+        strip_system_call_accounting(&mut state, caller, beneficiary);
+        assert_eq!(state.keys().collect::<Vec<_>>(), vec![&contract]);
+
+        // value transfers to the caller and the beneficiary are kept
+        let mut state = EvmState::default();
+        state.insert(
+            caller,
+            account(
+                AccountInfo::default(),
+                AccountInfo { nonce: 1, balance: U256::from(2), ..Default::default() },
+            ),
+        );
+        state.insert(
+            beneficiary,
+            account(
+                AccountInfo::default(),
+                AccountInfo { balance: U256::from(5), ..Default::default() },
+            ),
+        );
+        strip_system_call_accounting(&mut state, caller, beneficiary);
+        assert_eq!(state[&caller].info.nonce, 0);
+        assert_eq!(state[&caller].info.balance, U256::from(2));
+        assert_eq!(state[&beneficiary].info.balance, U256::from(5));
+    }
+}
@@ -7,7 +7,10 @@ use reth_payload_builder::PayloadBuilderHandle;
 use reth_provider::BlockReader;
 use reth_tasks::TaskExecutor;
 use std::fmt::Debug;
-use tokio::sync::mpsc::{error::SendError, UnboundedSender};
+use tokio::sync::{
+    mpsc::{error::SendError, UnboundedSender},
+    watch,
+};
 
 /// Captures the context that an `ExEx` has access to.
 ///
@@ -34,6 +37,11 @@ pub struct ExExContext<Node: FullNodeComponents> {
     /// Once an [`ExExNotification`](crate::ExExNotification) is sent over the channel, it is
     /// considered delivered by the node.
     pub notifications: ExExNotifications<Node::Provider, Node::Evm>,
+    /// Channel to receive the latest finalized block.
+    ///
+    /// Finalized blocks are never reverted, so an `ExEx` that maintains its own store can safely
+    /// prune data at or below this block.
+    pub finalized: watch::Receiver<Option<BlockNumHash>>,
 
     /// Node components
     pub components: Node,
@@ -51,6 +59,7 @@ where
             .field("reth_config", &self.reth_config)
             .field("events", &self.events)
             .field("notifications", &self.notifications)
+            .field("finalized", &self.finalized)
             .field("components", &"...")
             .finish()
     }
@@ -119,6 +128,11 @@ where
         self.notifications.set_with_head(head);
     }
 
+    /// Returns the latest finalized block, if any has been observed yet.
+    pub fn finalized_block(&self) -> Option<BlockNumHash> {
+        *self.finalized.borrow()
+    }
+
     /// Sends an [`ExExEvent::FinishedHeight`] to the ExEx task manager letting it know that this
     /// ExEx has processed the corresponding block.
     ///
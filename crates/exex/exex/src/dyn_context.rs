@@ -8,7 +8,7 @@ use reth_node_api::{FullNodeComponents, HeaderTy, NodePrimitives, NodeTypes, Pri
 use reth_node_core::node_config::NodeConfig;
 use reth_provider::BlockReader;
 use std::fmt::Debug;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, watch};
 
 use crate::{ExExContext, ExExEvent, ExExNotificationsStream};
 
@@ -36,6 +36,11 @@ pub struct ExExContextDyn<N: NodePrimitives = EthPrimitives> {
     /// Once an [`ExExNotification`](crate::ExExNotification) is sent over the channel, it is
     /// considered delivered by the node.
     pub notifications: Box<dyn ExExNotificationsStream<N>>,
+    /// Channel to receive the latest finalized block.
+    ///
+    /// Finalized blocks are never reverted, so an `ExEx` that maintains its own store can safely
+    /// prune data at or below this block.
+    pub finalized: watch::Receiver<Option<BlockNumHash>>,
 }
 
 impl<N: NodePrimitives> Debug for ExExContextDyn<N> {
@@ -46,6 +51,7 @@ impl<N: NodePrimitives> Debug for ExExContextDyn<N> {
             .field("reth_config", &self.reth_config)
             .field("events", &self.events)
             .field("notifications", &"...")
+            .field("finalized", &self.finalized)
             .finish()
     }
 }
@@ -67,6 +73,7 @@ where
             reth_config: ctx.reth_config,
             events: ctx.events,
             notifications,
+            finalized: ctx.finalized,
         }
     }
 }
@@ -109,6 +109,11 @@ pub use notifications::*;
 mod wal;
 pub use wal::*;
 
+#[cfg(feature = "wasm")]
+mod wasm;
+#[cfg(feature = "wasm")]
+pub use wasm::*;
+
 // Re-export exex types
 #[doc(inline)]
 pub use reth_exex_types::*;
@@ -1,5 +1,6 @@
 use crate::{
-    wal::Wal, ExExEvent, ExExNotification, ExExNotifications, FinishedExExHeight, WalHandle,
+    wal::Wal, ExExEvent, ExExNotification, ExExNotifications, FinishedExExHeight,
+    FinishedExExHeightReceiver, WalHandle,
 };
 use alloy_consensus::BlockHeader;
 use alloy_eips::BlockNumHash;
@@ -90,6 +91,8 @@ pub struct ExExHandle<N: NodePrimitives = EthPrimitives> {
     ///
     /// If this is `None`, the `ExEx` has not emitted a `FinishedHeight` event.
     finished_height: Option<BlockNumHash>,
+    /// Channel to notify the `ExEx` about the latest finalized block.
+    finalized: watch::Sender<Option<BlockNumHash>>,
 }
 
 impl<N: NodePrimitives> ExExHandle<N> {
@@ -117,12 +120,21 @@ impl<N: NodePrimitives> ExExHandle<N> {
                 receiver: event_rx,
                 next_notification_id: 0,
                 finished_height: None,
+                finalized: watch::channel(None).0,
             },
             event_tx,
             notifications,
         )
     }
 
+    /// Returns a receiver of the latest finalized block, as observed by the [`ExExManager`].
+    ///
+    /// The receiver should be given to the `ExEx`, so it can safely prune its own data at or below
+    /// the finalized block, which will never be reverted.
+    pub fn subscribe_finalized(&self) -> watch::Receiver<Option<BlockNumHash>> {
+        self.finalized.subscribe()
+    }
+
     /// Reserves a slot in the `PollSender` channel and sends the notification if the slot was
     /// successfully reserved.
     ///
@@ -319,7 +331,7 @@ where
                 is_ready_receiver: is_ready_rx.clone(),
                 is_ready: ReusableBoxFuture::new(make_wait_future(is_ready_rx)),
                 current_capacity,
-                finished_height: finished_height_rx,
+                finished_height: finished_height_rx.into(),
             },
             metrics,
         }
@@ -447,7 +459,7 @@ where
     /// Main loop of the [`ExExManager`]. The order of operations is as follows:
     /// 1. Handle incoming ExEx events. We do it before finalizing the WAL, because it depends on
     ///    the latest state of [`ExExEvent::FinishedHeight`] events.
-    /// 2. Finalize the WAL with the finalized header, if necessary.
+    /// 2. Notify ExExes about the finalized header and finalize the WAL with it, if necessary.
     /// 3. Drain [`ExExManagerHandle`] notifications, push them to the internal buffer and update
     ///    the internal buffer capacity.
     /// 5. Send notifications from the internal buffer to those ExExes that are ready to receive new
@@ -475,6 +487,14 @@ where
             last_finalized_header = finalized_header;
         }
         if let Some(header) = last_finalized_header {
+            // Notify ExExes about the new finalized block, so they can prune their own data
+            let finalized = header.num_hash();
+            for exex in &this.exex_handles {
+                exex.finalized.send_if_modified(|current| {
+                    current.replace(finalized).is_none_or(|previous| previous != finalized)
+                });
+            }
+
             this.finalize_wal(header)?;
         }
 
@@ -574,7 +594,7 @@ pub struct ExExManagerHandle<N: NodePrimitives = EthPrimitives> {
     /// The current capacity of the manager's internal notification buffer.
     current_capacity: Arc<AtomicUsize>,
     /// The finished height of all `ExEx`'s.
-    finished_height: watch::Receiver<FinishedExExHeight>,
+    finished_height: FinishedExExHeightReceiver,
 }
 
 impl<N: NodePrimitives> ExExManagerHandle<N> {
@@ -586,7 +606,6 @@ impl<N: NodePrimitives> ExExManagerHandle<N> {
     pub fn empty() -> Self {
         let (exex_tx, _) = mpsc::unbounded_channel();
         let (_, is_ready_rx) = watch::channel(true);
-        let (_, finished_height_rx) = watch::channel(FinishedExExHeight::NoExExs);
 
         Self {
             exex_tx,
@@ -594,7 +613,7 @@ impl<N: NodePrimitives> ExExManagerHandle<N> {
             is_ready_receiver: is_ready_rx.clone(),
             is_ready: ReusableBoxFuture::new(make_wait_future(is_ready_rx)),
             current_capacity: Arc::new(AtomicUsize::new(0)),
-            finished_height: finished_height_rx,
+            finished_height: FinishedExExHeightReceiver::no_exexs(),
         }
     }
 
@@ -641,7 +660,9 @@ impl<N: NodePrimitives> ExExManagerHandle<N> {
     }
 
     /// The finished height of all `ExEx`'s.
-    pub fn finished_height(&self) -> watch::Receiver<FinishedExExHeight> {
+    ///
+    /// This should be given to the pruner, so it doesn't prune data that `ExEx`'s still need.
+    pub fn finished_height(&self) -> FinishedExExHeightReceiver {
         self.finished_height.clone()
     }
 
@@ -921,10 +942,7 @@ mod tests {
         let mut receiver = pinned_manager.handle.finished_height();
 
         // Wait for a new value to be sent
-        receiver.changed().await.unwrap();
-
-        // Get the latest value
-        let finished_height = *receiver.borrow();
+        let finished_height = receiver.changed().await.unwrap();
 
         // The finished height should be updated to the lower block height
         assert_eq!(finished_height, FinishedExExHeight::Height(42));
@@ -978,10 +996,7 @@ mod tests {
         let mut receiver = pinned_manager.handle.finished_height();
 
         // Wait for a new value to be sent
-        receiver.changed().await.unwrap();
-
-        // Get the latest value
-        let finished_height = *receiver.borrow();
+        let finished_height = receiver.changed().await.unwrap();
 
         // The finished height should be updated to the lower block height
         assert_eq!(finished_height, FinishedExExHeight::Height(10));
@@ -1038,10 +1053,7 @@ mod tests {
         let mut receiver = pinned_manager.handle.finished_height();
 
         // Wait for a new value to be sent
-        receiver.changed().await.unwrap();
-
-        // Get the latest value
-        let finished_height = *receiver.borrow();
+        let finished_height = receiver.changed().await.unwrap();
 
         // The finished height should be updated to the lower block height
         assert_eq!(finished_height, FinishedExExHeight::Height(42));
@@ -1422,6 +1434,52 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_notifies_finalized_block() -> eyre::Result<()> {
+        let provider_factory = create_test_provider_factory();
+        init_genesis(&provider_factory).unwrap();
+        let provider = BlockchainProvider::new(provider_factory).unwrap();
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let wal = Wal::new(temp_dir.path()).unwrap();
+
+        let (exex_handle, _, _) = ExExHandle::new(
+            "test_exex".to_string(),
+            Default::default(),
+            provider.clone(),
+            EthEvmConfig::mainnet(),
+            wal.handle(),
+        );
+        let mut finalized = exex_handle.subscribe_finalized();
+        assert_eq!(*finalized.borrow(), None);
+
+        let (finalized_headers_tx, rx) = watch::channel(None);
+        let mut exex_manager = std::pin::pin!(ExExManager::new(
+            provider,
+            vec![exex_handle],
+            2,
+            wal,
+            ForkChoiceStream::new(rx)
+        ));
+
+        let mut cx = Context::from_waker(futures::task::noop_waker_ref());
+
+        let header =
+            SealedHeader::seal_slow(alloy_consensus::Header { number: 1, ..Default::default() });
+        finalized_headers_tx.send(Some(header.clone()))?;
+        assert!(exex_manager.as_mut().poll(&mut cx)?.is_pending());
+
+        assert!(finalized.has_changed()?);
+        assert_eq!(*finalized.borrow_and_update(), Some(header.num_hash()));
+
+        // The same finalized block is not notified twice
+        finalized_headers_tx.send(Some(header))?;
+        assert!(exex_manager.as_mut().poll(&mut cx)?.is_pending());
+        assert!(!finalized.has_changed()?);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_deadlock_manager_wakes_after_buffer_clears() {
         // This test simulates the scenario where the buffer fills up, ingestion pauses,
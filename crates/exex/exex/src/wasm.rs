@@ -0,0 +1,490 @@
+//! Host for `ExEx`'s that are compiled to WASM and loaded at runtime.
+//!
+//! A WASM `ExEx` is a sandboxed WASM module that is loaded from a file when the node starts, so
+//! extensions like indexers can be distributed and installed without recompiling reth. The module
+//! has no access to the host besides the imports listed below, and every notification is processed
+//! with a bounded amount of fuel and memory.
+//!
+//! # ABI
+//!
+//! The module must export:
+//!
+//! - `memory`: the linear memory of the module.
+//! - `alloc(len: u32) -> u32`: allocates `len` bytes and returns a pointer to them. The host writes
+//!   the encoded notification to the returned buffer, which is owned by the module afterwards.
+//! - `on_notification(ptr: u32, len: u32) -> u32`: processes the notification of `len` bytes at
+//!   `ptr`, returns zero on success and an error code otherwise.
+//!
+//! Notifications are encoded as `MessagePack` with the
+//! [`serde_bincode_compat`](reth_exex_types::serde_bincode_compat) representation of the
+//! [`ExExNotification`](crate::ExExNotification), the same format that is used by the
+//! [`Wal`](crate::Wal).
+//!
+//! The module may import `reth.log(ptr: u32, len: u32)` to log a UTF-8 message of its memory.
+//! Messages are truncated to [`MAX_LOG_LEN`] bytes, and at most [`MAX_LOGS_PER_NOTIFICATION`]
+//! messages are logged per notification, further messages are dropped.
+//!
+//! Once a notification with a committed chain was processed successfully, the host emits an
+//! [`ExExEvent::FinishedHeight`] with the tip of the committed chain on behalf of the module.
+
+use crate::{ExExContextDyn, ExExEvent};
+use futures::TryStreamExt;
+use reth_exex_types::serde_bincode_compat;
+use reth_node_api::NodePrimitives;
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+};
+use tracing::{debug, info, warn};
+use wasmtime::{
+    Caller, Engine, Linker, Memory, Module, Store, StoreLimits, StoreLimitsBuilder, TypedFunc,
+};
+
+/// The file extension of the modules that are loaded by [`WasmExExHost::load_dir`].
+pub const WASM_EXEX_EXTENSION: &str = "wasm";
+
+/// The maximum number of messages a module can log with `reth.log` per notification.
+pub const MAX_LOGS_PER_NOTIFICATION: usize = 64;
+
+/// The maximum length in bytes of a message that is logged with `reth.log`.
+pub const MAX_LOG_LEN: usize = 1024;
+
+/// Errors of the WASM `ExEx` host.
+#[derive(Debug, thiserror::Error)]
+pub enum WasmExExError {
+    /// Failed to create the engine that compiles and runs the modules.
+    #[error("failed to create wasm engine: {0:#}")]
+    Engine(wasmtime::Error),
+    /// Failed to read a module or the directory of the modules.
+    #[error(transparent)]
+    Io(#[from] reth_fs_util::FsPathError),
+    /// Failed to compile a module.
+    #[error("failed to compile wasm exex {}: {err:#}", path.display())]
+    Compile {
+        /// The path of the module.
+        path: PathBuf,
+        /// The compilation error.
+        err: wasmtime::Error,
+    },
+    /// The module doesn't export an item that is required by the ABI.
+    #[error("wasm exex {id} doesn't export `{export}`")]
+    MissingExport {
+        /// The id of the `ExEx`.
+        id: String,
+        /// The name of the missing export.
+        export: &'static str,
+    },
+    /// The module trapped, e.g. because it ran out of fuel or memory.
+    #[error("wasm exex {id} failed: {err:#}")]
+    Runtime {
+        /// The id of the `ExEx`.
+        id: String,
+        /// The runtime error.
+        err: wasmtime::Error,
+    },
+    /// The module returned an error code for a notification.
+    #[error("wasm exex {id} returned error code {code}")]
+    Guest {
+        /// The id of the `ExEx`.
+        id: String,
+        /// The error code returned by `on_notification`.
+        code: u32,
+    },
+    /// Failed to encode a notification.
+    #[error(transparent)]
+    Encode(#[from] rmp_serde::encode::Error),
+}
+
+/// Resource limits of the WASM `ExEx`'s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WasmExExConfig {
+    /// The maximum size of the linear memory of a module in bytes.
+    pub max_memory: usize,
+    /// The amount of fuel, roughly the number of executed instructions, that a module can consume
+    /// per notification, unlimited if `None`.
+    pub fuel_per_notification: Option<u64>,
+}
+
+impl Default for WasmExExConfig {
+    fn default() -> Self {
+        Self { max_memory: 512 * 1024 * 1024, fuel_per_notification: Some(10_000_000_000) }
+    }
+}
+
+/// Loads WASM `ExEx`'s from files.
+///
+/// The node installs the modules of the directory that is set with `--exex.wasm-dir`. The loaded
+/// [`WasmExEx`]s can also be installed like any other `ExEx`:
+///
+/// ```ignore
+/// let host = WasmExExHost::new(WasmExExConfig::default())?;
+/// for exex in host.load_dir(datadir.join("exex"))? {
+///     builder = builder.install_exex(exex.id().to_string(), |ctx| async move {
+///         Ok(exex.run(ctx.into_dyn()))
+///     });
+/// }
+/// ```
+#[derive(Clone)]
+pub struct WasmExExHost {
+    engine: Engine,
+    config: WasmExExConfig,
+}
+
+impl WasmExExHost {
+    /// Creates a new host that runs the modules with the given limits.
+    pub fn new(config: WasmExExConfig) -> Result<Self, WasmExExError> {
+        let mut engine_config = wasmtime::Config::new();
+        engine_config.consume_fuel(config.fuel_per_notification.is_some());
+        let engine = Engine::new(&engine_config).map_err(WasmExExError::Engine)?;
+        Ok(Self { engine, config })
+    }
+
+    /// Returns the limits of the modules.
+    pub const fn config(&self) -> &WasmExExConfig {
+        &self.config
+    }
+
+    /// Loads all modules with the [`WASM_EXEX_EXTENSION`] in the directory, sorted by file name.
+    ///
+    /// Returns an empty list if the directory doesn't exist.
+    pub fn load_dir(&self, dir: impl AsRef<Path>) -> Result<Vec<WasmExEx>, WasmExExError> {
+        let dir = dir.as_ref();
+        if !dir.exists() {
+            return Ok(Vec::new())
+        }
+
+        let mut paths = reth_fs_util::read_dir(dir)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.is_file() &&
+                    path.extension().is_some_and(|extension| extension == WASM_EXEX_EXTENSION)
+            })
+            .collect::<Vec<_>>();
+        paths.sort();
+
+        paths.iter().map(|path| self.load_file(path)).collect()
+    }
+
+    /// Loads and compiles the module at the given path, the file stem is used as the id of the
+    /// `ExEx`.
+    pub fn load_file(&self, path: impl AsRef<Path>) -> Result<WasmExEx, WasmExExError> {
+        let path = path.as_ref();
+        let id = path.file_stem().unwrap_or_default().to_string_lossy().into_owned();
+        let bytes = reth_fs_util::read(path)?;
+        let module = Module::new(&self.engine, bytes)
+            .map_err(|err| WasmExExError::Compile { path: path.to_path_buf(), err })?;
+
+        // fail on startup instead of on the first notification
+        for export in ["memory", "alloc", "on_notification"] {
+            if module.get_export(export).is_none() {
+                return Err(WasmExExError::MissingExport { id, export })
+            }
+        }
+
+        info!(target: "exex::wasm", %id, ?path, "Loaded wasm exex");
+        Ok(WasmExEx { id, engine: self.engine.clone(), module, config: self.config })
+    }
+}
+
+impl fmt::Debug for WasmExExHost {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WasmExExHost").field("config", &self.config).finish_non_exhaustive()
+    }
+}
+
+/// A compiled WASM `ExEx`, created by [`WasmExExHost`].
+#[derive(Clone)]
+pub struct WasmExEx {
+    id: String,
+    engine: Engine,
+    module: Module,
+    config: WasmExExConfig,
+}
+
+impl WasmExEx {
+    /// Returns the id of the `ExEx`.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Runs the `ExEx`, passing every notification of the context to the module.
+    ///
+    /// Returns an error if the module fails to process a notification.
+    pub async fn run<N: NodePrimitives>(self, mut ctx: ExExContextDyn<N>) -> eyre::Result<()> {
+        let mut instance = WasmInstance::new(&self)?;
+
+        while let Some(notification) = ctx.notifications.try_next().await? {
+            let encoded = rmp_serde::encode::to_vec(
+                &serde_bincode_compat::ExExNotification::<N>::from(&notification),
+            )
+            .map_err(WasmExExError::from)?;
+
+            // executing the module is blocking, so it must not run on the async runtime
+            instance = tokio::task::spawn_blocking(move || {
+                instance.on_notification(&encoded).map(|()| instance)
+            })
+            .await??;
+
+            if let Some(committed) = notification.committed_chain() {
+                ctx.events.send(ExExEvent::FinishedHeight(committed.tip().num_hash()))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Debug for WasmExEx {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WasmExEx")
+            .field("id", &self.id)
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The state of the host that is accessible from the imports of a module.
+struct HostState {
+    id: String,
+    limits: StoreLimits,
+    /// The number of messages logged while processing the current notification.
+    logged: usize,
+    /// The number of messages dropped while processing the current notification.
+    dropped: usize,
+}
+
+/// An instantiated [`WasmExEx`].
+struct WasmInstance {
+    id: String,
+    store: Store<HostState>,
+    memory: Memory,
+    alloc: TypedFunc<u32, u32>,
+    on_notification: TypedFunc<(u32, u32), u32>,
+    fuel: Option<u64>,
+}
+
+impl WasmInstance {
+    fn new(exex: &WasmExEx) -> Result<Self, WasmExExError> {
+        let id = exex.id.clone();
+        let runtime = |err| WasmExExError::Runtime { id: exex.id.clone(), err };
+
+        let limits =
+            StoreLimitsBuilder::new().memory_size(exex.config.max_memory).instances(1).build();
+        let mut store =
+            Store::new(&exex.engine, HostState { id: id.clone(), limits, logged: 0, dropped: 0 });
+        store.limiter(|state| &mut state.limits);
+
+        let mut linker = Linker::new(&exex.engine);
+        linker
+            .func_wrap("reth", "log", |mut caller: Caller<'_, HostState>, ptr: u32, len: u32| {
+                let state = caller.data_mut();
+                if state.logged >= MAX_LOGS_PER_NOTIFICATION {
+                    if state.dropped == 0 {
+                        warn!(target: "exex::wasm", id = %state.id, "Too many log messages, dropping the rest of the notification");
+                    }
+                    state.dropped += 1;
+                    return
+                }
+                state.logged += 1;
+
+                let Some(memory) = caller.get_export("memory").and_then(|e| e.into_memory()) else {
+                    return
+                };
+                let (ptr, len) = (ptr as usize, (len as usize).min(MAX_LOG_LEN));
+                let message = memory
+                    .data(&caller)
+                    .get(ptr..ptr.saturating_add(len))
+                    .map(|bytes| String::from_utf8_lossy(bytes).into_owned());
+                if let Some(message) = message {
+                    info!(target: "exex::wasm", id = %caller.data().id, "{message}");
+                }
+            })
+            .map_err(runtime)?;
+
+        if let Some(fuel) = exex.config.fuel_per_notification {
+            store.set_fuel(fuel).map_err(runtime)?;
+        }
+        let instance = linker.instantiate(&mut store, &exex.module).map_err(runtime)?;
+
+        let memory = instance
+            .get_memory(&mut store, "memory")
+            .ok_or(WasmExExError::MissingExport { id: id.clone(), export: "memory" })?;
+        let alloc = instance.get_typed_func(&mut store, "alloc").map_err(runtime)?;
+        let on_notification =
+            instance.get_typed_func(&mut store, "on_notification").map_err(runtime)?;
+
+        Ok(Self {
+            id,
+            store,
+            memory,
+            alloc,
+            on_notification,
+            fuel: exex.config.fuel_per_notification,
+        })
+    }
+
+    /// Passes the encoded notification to the module.
+    fn on_notification(&mut self, notification: &[u8]) -> Result<(), WasmExExError> {
+        let runtime = |err| WasmExExError::Runtime { id: self.id.clone(), err };
+
+        if let Some(fuel) = self.fuel {
+            self.store.set_fuel(fuel).map_err(runtime)?;
+        }
+        let state = self.store.data_mut();
+        state.logged = 0;
+        state.dropped = 0;
+
+        let len = u32::try_from(notification.len()).map_err(|_| {
+            runtime(wasmtime::Error::msg("notification exceeds the 32-bit address space"))
+        })?;
+        let ptr = self.alloc.call(&mut self.store, len).map_err(runtime)?;
+        self.memory
+            .write(&mut self.store, ptr as usize, notification)
+            .map_err(|err| runtime(err.into()))?;
+
+        let code = self.on_notification.call(&mut self.store, (ptr, len)).map_err(runtime)?;
+        if code != 0 {
+            return Err(WasmExExError::Guest { id: self.id.clone(), code })
+        }
+
+        debug!(target: "exex::wasm", id = %self.id, len, "Processed notification");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ExExNotification, ExExNotifications, Wal};
+    use alloy_consensus::Header;
+    use reth_chainspec::EthChainSpec;
+    use reth_ethereum_primitives::{Block, EthPrimitives};
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_node_core::node_config::NodeConfig;
+    use reth_primitives_traits::Block as _;
+    use reth_provider::{
+        providers::BlockchainProvider, test_utils::create_test_provider_factory, Chain,
+    };
+    use std::{collections::BTreeMap, sync::Arc};
+    use tokio::sync::{mpsc, watch};
+
+    /// A module that logs a message for every byte of a notification, and returns error code 7
+    /// for notifications that start with `0xff`.
+    const LOGGER: &str = r#"
+        (module
+          (import "reth" "log" (func $log (param i32 i32)))
+          (memory (export "memory") 2)
+          (data (i32.const 0) "processing notification")
+
+          ;; every notification is written to the second page
+          (func (export "alloc") (param $len i32) (result i32)
+            (if (i32.gt_u (local.get $len) (i32.const 65536)) (then (unreachable)))
+            (i32.const 65536))
+
+          (func (export "on_notification") (param $ptr i32) (param $len i32) (result i32)
+            (local $i i32)
+            (if (i32.eq (i32.load8_u (local.get $ptr)) (i32.const 0xff))
+              (then (return (i32.const 7))))
+            (block $done
+              (loop $next
+                (br_if $done (i32.ge_u (local.get $i) (local.get $len)))
+                (call $log (i32.const 0) (i32.const 23))
+                (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                (br $next)))
+            (i32.const 0)))
+    "#;
+
+    /// Loads the [`LOGGER`] module, which is compiled from its text format with the `wat` feature
+    /// of wasmtime.
+    fn load_logger(dir: &Path) -> WasmExEx {
+        let path = dir.join("logger.wasm");
+        std::fs::write(&path, LOGGER).unwrap();
+        WasmExExHost::new(WasmExExConfig::default()).unwrap().load_file(path).unwrap()
+    }
+
+    #[tokio::test]
+    async fn run_module() -> eyre::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let exex = load_logger(dir.path());
+        assert_eq!(exex.id(), "logger");
+
+        let wal = Wal::new(dir.path().join("wal"))?;
+        let provider = BlockchainProvider::new(create_test_provider_factory())?;
+        let (notifications_tx, notifications_rx) = mpsc::channel(1);
+        let (events_tx, mut events_rx) = mpsc::unbounded_channel();
+        let ctx = ExExContextDyn::<EthPrimitives> {
+            head: Default::default(),
+            config: NodeConfig::test().map_chainspec(|chainspec| {
+                Box::new(chainspec) as Box<dyn EthChainSpec<Header = Header>>
+            }),
+            reth_config: Default::default(),
+            events: events_tx,
+            notifications: Box::new(ExExNotifications::new(
+                Default::default(),
+                provider,
+                EthEvmConfig::mainnet(),
+                notifications_rx,
+                wal.handle(),
+            )),
+            finalized: watch::channel(None).1,
+        };
+
+        let block =
+            Block { header: Header { number: 1, ..Default::default() }, ..Default::default() }
+                .seal_slow()
+                .try_recover()?;
+        let tip = block.num_hash();
+        notifications_tx
+            .send(ExExNotification::ChainCommitted {
+                new: Arc::new(Chain::new(vec![block], Default::default(), BTreeMap::new())),
+            })
+            .await?;
+        drop(notifications_tx);
+
+        // the module processes the notification and stops once the channel is closed
+        exex.run(ctx).await?;
+        assert_eq!(events_rx.try_recv()?, ExExEvent::FinishedHeight(tip));
+        assert!(events_rx.try_recv().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn log_is_throttled_per_notification() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut instance = WasmInstance::new(&load_logger(dir.path())).unwrap();
+
+        instance.on_notification(&[0; 3]).unwrap();
+        assert_eq!(instance.store.data().logged, 3);
+        assert_eq!(instance.store.data().dropped, 0);
+
+        instance.on_notification(&[0; MAX_LOGS_PER_NOTIFICATION + 10]).unwrap();
+        assert_eq!(instance.store.data().logged, MAX_LOGS_PER_NOTIFICATION);
+        assert_eq!(instance.store.data().dropped, 10);
+
+        // the budget is reset for every notification
+        assert!(matches!(
+            instance.on_notification(&[0xff]),
+            Err(WasmExExError::Guest { code: 7, .. })
+        ));
+        assert_eq!(instance.store.data().logged, 0);
+        assert_eq!(instance.store.data().dropped, 0);
+    }
+
+    #[test]
+    fn load_dir_skips_other_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("README.md"), "not a module").unwrap();
+
+        let host = WasmExExHost::new(WasmExExConfig::default()).unwrap();
+        assert!(host.load_dir(dir.path()).unwrap().is_empty());
+        assert!(host.load_dir(dir.path().join("missing")).unwrap().is_empty());
+
+        // a module without the required exports is rejected on load
+        std::fs::write(dir.path().join("empty.wasm"), b"\0asm\x01\0\0\0").unwrap();
+        assert!(matches!(
+            host.load_dir(dir.path()),
+            Err(WasmExExError::MissingExport { id, export: "memory" }) if id == "empty"
+        ));
+    }
+}
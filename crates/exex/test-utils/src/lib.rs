@@ -59,7 +59,10 @@ use reth_tasks::TaskManager;
 use reth_transaction_pool::test_utils::{testing_pool, TestPool};
 use tempfile::TempDir;
 use thiserror::Error;
-use tokio::sync::mpsc::{Sender, UnboundedReceiver};
+use tokio::sync::{
+    mpsc::{Sender, UnboundedReceiver},
+    watch,
+};
 
 /// A test [`PoolBuilder`] that builds a [`TestPool`].
 #[derive(Debug, Default, Clone, Copy)]
@@ -175,6 +178,8 @@ pub struct TestExExHandle {
     pub events_rx: UnboundedReceiver<ExExEvent>,
     /// Channel for sending notifications to the Execution Extension
     pub notifications_tx: Sender<ExExNotification>,
+    /// Channel for notifying the Execution Extension about the latest finalized block
+    pub finalized_tx: watch::Sender<Option<BlockNumHash>>,
     /// Node task manager
     pub tasks: TaskManager,
     /// WAL temp directory handle
@@ -210,6 +215,11 @@ impl TestExExHandle {
         Ok(())
     }
 
+    /// Notify the Execution Extension that the given block has been finalized
+    pub fn send_finalized(&self, finalized: BlockNumHash) {
+        self.finalized_tx.send_replace(Some(finalized));
+    }
+
     /// Asserts that the Execution Extension did not emit any events.
     #[track_caller]
     pub fn assert_events_empty(&self) {
@@ -296,6 +306,7 @@ pub async fn test_exex_context_with_chain_spec(
 
     let (events_tx, events_rx) = tokio::sync::mpsc::unbounded_channel();
     let (notifications_tx, notifications_rx) = tokio::sync::mpsc::channel(1);
+    let (finalized_tx, finalized_rx) = watch::channel(None);
     let notifications = ExExNotifications::new(
         head,
         components.provider.clone(),
@@ -310,6 +321,7 @@ pub async fn test_exex_context_with_chain_spec(
         reth_config: reth_config::Config::default(),
         events: events_tx,
         notifications,
+        finalized: finalized_rx,
         components,
     };
 
@@ -320,6 +332,7 @@ pub async fn test_exex_context_with_chain_spec(
             provider_factory,
             events_rx,
             notifications_tx,
+            finalized_tx,
             tasks,
             _wal_directory: wal_directory,
         },
@@ -1,4 +1,5 @@
 use alloy_primitives::BlockNumber;
+use tokio::sync::watch;
 
 /// The finished height of all `ExEx`'s.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -23,3 +24,62 @@ impl FinishedExExHeight {
         matches!(self, Self::NotReady)
     }
 }
+
+/// Receiving half of the channel over which the `ExExManager` reports the
+/// [`FinishedExExHeight`] of all `ExEx`'s.
+///
+/// This is the coordination point between `ExEx`'s and the pruner: the pruner must never prune
+/// data above the reported height, because some `ExEx` may still need it. In turn, an `ExEx`
+/// promises not to require any block at or below the height it reported via
+/// `ExExEvent::FinishedHeight`.
+///
+/// The receiver always holds the latest reported value, intermediate values may be skipped.
+#[derive(Debug, Clone)]
+pub struct FinishedExExHeightReceiver {
+    inner: watch::Receiver<FinishedExExHeight>,
+}
+
+impl FinishedExExHeightReceiver {
+    /// Creates a new [`FinishedExExHeightReceiver`] from the given watch receiver.
+    pub const fn new(inner: watch::Receiver<FinishedExExHeight>) -> Self {
+        Self { inner }
+    }
+
+    /// Creates a [`FinishedExExHeightReceiver`] that always reports
+    /// [`FinishedExExHeight::NoExExs`].
+    ///
+    /// Use this when no `ExEx`'s are installed.
+    pub fn no_exexs() -> Self {
+        Self::new(watch::channel(FinishedExExHeight::NoExExs).1)
+    }
+
+    /// Returns the latest reported [`FinishedExExHeight`].
+    pub fn current(&self) -> FinishedExExHeight {
+        *self.inner.borrow()
+    }
+
+    /// Waits until a new [`FinishedExExHeight`] is reported and returns it.
+    ///
+    /// Returns an error if the `ExExManager` has been dropped.
+    pub async fn changed(&mut self) -> Result<FinishedExExHeight, watch::error::RecvError> {
+        self.inner.changed().await?;
+        Ok(*self.inner.borrow_and_update())
+    }
+
+    /// Consumes the type and returns the underlying watch receiver.
+    pub fn into_inner(self) -> watch::Receiver<FinishedExExHeight> {
+        self.inner
+    }
+}
+
+impl Default for FinishedExExHeightReceiver {
+    fn default() -> Self {
+        Self::no_exexs()
+    }
+}
+
+impl From<watch::Receiver<FinishedExExHeight>> for FinishedExExHeightReceiver {
+    fn from(inner: watch::Receiver<FinishedExExHeight>) -> Self {
+        Self::new(inner)
+    }
+}
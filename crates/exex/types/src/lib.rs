@@ -12,7 +12,7 @@ mod finished_height;
 mod head;
 mod notification;
 
-pub use finished_height::FinishedExExHeight;
+pub use finished_height::{FinishedExExHeight, FinishedExExHeightReceiver};
 pub use head::ExExHead;
 pub use notification::ExExNotification;
 
@@ -112,6 +112,13 @@ impl BanList {
         self.banned_peers.contains_key(peer_id)
     }
 
+    /// Returns an iterator over all banned peers and the timestamp until which they are banned.
+    ///
+    /// A `None` timestamp means the peer is banned indefinitely.
+    pub fn banned_peers(&self) -> impl Iterator<Item = (PeerId, Option<Instant>)> + '_ {
+        self.banned_peers.iter().map(|(peer_id, until)| (*peer_id, *until))
+    }
+
     /// Unbans the ip address
     pub fn unban_ip(&mut self, ip: &IpAddr) {
         self.banned_ips.remove(ip);
@@ -16,7 +16,9 @@ use reth_ethereum_forks::{EnrForkIdEntry, ForkId};
 use reth_network_peers::NodeRecord;
 use tracing::debug;
 
-use crate::{enr::discv4_id_to_multiaddr_id, filter::MustNotIncludeKeys, NetworkStackId};
+use crate::{
+    enr::discv4_id_to_multiaddr_id, filter::MustNotIncludeKeys, topic::Topic, NetworkStackId,
+};
 
 /// The default address for discv5 via UDP is IPv4.
 ///
@@ -85,6 +87,9 @@ pub struct ConfigBuilder {
     /// Custom filter rules to apply to a discovered peer in order to determine if it should be
     /// passed up to rlpx or dropped.
     discovered_peer_filter: Option<MustNotIncludeKeys>,
+    /// Names of the topics to advertise in local node record. Discovered peers must advertise at
+    /// least one of them.
+    topics: Vec<String>,
 }
 
 impl ConfigBuilder {
@@ -100,6 +105,7 @@ impl ConfigBuilder {
             bootstrap_lookup_interval,
             bootstrap_lookup_countdown,
             discovered_peer_filter,
+            topics,
         } = discv5_config;
 
         Self {
@@ -112,6 +118,7 @@ impl ConfigBuilder {
             bootstrap_lookup_interval: Some(bootstrap_lookup_interval),
             bootstrap_lookup_countdown: Some(bootstrap_lookup_countdown),
             discovered_peer_filter: Some(discovered_peer_filter),
+            topics,
         }
     }
 
@@ -214,6 +221,14 @@ impl ConfigBuilder {
         self
     }
 
+    /// Adds topics to advertise in the local [`Enr`](discv5::enr::Enr). Topics are scoped to the
+    /// configured fork. If any topics are set, discovered peers that don't advertise at least one
+    /// of them on the same fork are dropped, and lookup queries only look for such peers.
+    pub fn topics(mut self, topics: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.topics.extend(topics.into_iter().map(Into::into));
+        self
+    }
+
     /// Returns a new [`Config`].
     pub fn build(self) -> Config {
         let Self {
@@ -226,6 +241,7 @@ impl ConfigBuilder {
             bootstrap_lookup_interval,
             bootstrap_lookup_countdown,
             discovered_peer_filter,
+            topics,
         } = self;
 
         let mut discv5_config = discv5_config.unwrap_or_else(|| {
@@ -256,6 +272,7 @@ impl ConfigBuilder {
             bootstrap_lookup_interval,
             bootstrap_lookup_countdown,
             discovered_peer_filter,
+            topics,
         }
     }
 }
@@ -289,6 +306,8 @@ pub struct Config {
     /// Custom filter rules to apply to a discovered peer in order to determine if it should be
     /// passed up to rlpx or dropped.
     pub(super) discovered_peer_filter: MustNotIncludeKeys,
+    /// Names of the topics to advertise in local node record.
+    pub(super) topics: Vec<String>,
 }
 
 impl Config {
@@ -305,6 +324,7 @@ impl Config {
             bootstrap_lookup_interval: None,
             bootstrap_lookup_countdown: None,
             discovered_peer_filter: None,
+            topics: Vec::new(),
         }
     }
 
@@ -345,6 +365,17 @@ impl Config {
     pub const fn rlpx_socket(&self) -> &SocketAddr {
         &self.tcp_socket
     }
+
+    /// Returns the names of the configured topics.
+    pub fn topic_names(&self) -> &[String] {
+        &self.topics
+    }
+
+    /// Returns the configured topics, scoped to the configured fork.
+    pub fn topics(&self) -> Vec<Topic> {
+        let fork_hash = self.fork.as_ref().map(|(_, entry)| entry.fork_id.hash).unwrap_or_default();
+        self.topics.iter().map(|name| Topic::new(name, fork_hash)).collect()
+    }
 }
 
 /// Returns the IPv4 discovery socket if one is configured.
@@ -2,9 +2,27 @@
 
 use std::collections::HashSet;
 
+use alloy_primitives::Bytes;
 use derive_more::Constructor;
 use itertools::Itertools;
 
+use crate::topic::{advertises_any, Topic};
+
+/// Predicate on the node record of a discovered peer, see
+/// [`Discv5::subscribe_peers`](crate::Discv5::subscribe_peers).
+pub type EnrPredicate = Box<dyn Fn(&discv5::Enr) -> bool + Send + Sync>;
+
+/// Returns a predicate matching node records that advertise the given kv-pair. The value must be
+/// rlp encoded.
+pub fn kv_predicate(key: &'static [u8], value: Bytes) -> EnrPredicate {
+    Box::new(move |enr| enr.get_raw_rlp(key) == Some(value.as_ref()))
+}
+
+/// Returns a predicate matching node records that advertise any of the given topics.
+pub fn topics_predicate(topics: Vec<Topic>) -> EnrPredicate {
+    Box::new(move |enr| advertises_any(enr, &topics))
+}
+
 /// Outcome of applying filtering rules on node record.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum FilterOutcome {
@@ -119,4 +137,21 @@ mod tests {
         assert!(matches!(filter.filter(&enr_1), FilterOutcome::Ignore { .. }));
         assert!(matches!(filter.filter(&enr_2), FilterOutcome::Ignore { .. }));
     }
+
+    #[test]
+    fn kv_predicate_matches_value() {
+        let sk = CombinedKey::generate_secp256k1();
+        let enr = Enr::builder()
+            .add_value_rlp(NetworkStackId::ETH, alloy_rlp::encode("devnet").into())
+            .build(&sk)
+            .unwrap();
+
+        let devnet = kv_predicate(NetworkStackId::ETH, alloy_rlp::encode("devnet").into());
+        let other = kv_predicate(NetworkStackId::ETH, alloy_rlp::encode("other").into());
+        let missing = kv_predicate(NetworkStackId::ETH2, alloy_rlp::encode("devnet").into());
+
+        assert!(devnet(&enr));
+        assert!(!other(&enr));
+        assert!(!missing(&enr));
+    }
 }
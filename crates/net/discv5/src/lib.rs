@@ -22,11 +22,13 @@ use discv5::ListenConfig;
 use enr::{discv4_id_to_discv5_id, EnrCombinedKeyWrapper};
 use futures::future::join_all;
 use itertools::Itertools;
+use parking_lot::{Mutex, RwLock};
 use rand::{Rng, RngCore};
 use reth_ethereum_forks::{EnrForkIdEntry, ForkId};
 use reth_network_peers::{NodeRecord, PeerId};
 use secp256k1::SecretKey;
 use tokio::{sync::mpsc, task};
+use topic::{advertises_any, encode_topics, TOPICS_ENR_KEY};
 use tracing::{debug, error, trace};
 
 pub mod config;
@@ -35,6 +37,7 @@ pub mod error;
 pub mod filter;
 pub mod metrics;
 pub mod network_stack_id;
+pub mod topic;
 
 pub use discv5::{self, IpMode};
 
@@ -45,8 +48,9 @@ pub use config::{
 };
 pub use enr::enr_to_discv4_id;
 pub use error::Error;
-pub use filter::{FilterOutcome, MustNotIncludeKeys};
+pub use filter::{EnrPredicate, FilterOutcome, MustNotIncludeKeys};
 pub use network_stack_id::NetworkStackId;
+pub use topic::Topic;
 
 use metrics::{DiscoveredPeersMetrics, Discv5Metrics};
 
@@ -62,6 +66,16 @@ pub const MAX_KBUCKET_INDEX: usize = 255;
 /// Default is 0th index.
 pub const DEFAULT_MIN_TARGET_KBUCKET_INDEX: usize = 0;
 
+/// Number of peers advertising a configured topic to look for in a lookup query.
+///
+/// Default is 16, the size of a kbucket.
+pub const DEFAULT_TOPIC_LOOKUP_PEERS: usize = 16;
+
+/// Capacity of the channel of a subscription to discovered peers, see [`Discv5::subscribe_peers`].
+///
+/// Discovered peers are dropped for a subscription, while its channel is full.
+pub const PEER_SUBSCRIPTION_CHANNEL_SIZE: usize = 256;
+
 /// Transparent wrapper around [`discv5::Discv5`].
 #[derive(Clone)]
 pub struct Discv5 {
@@ -73,6 +87,13 @@ pub struct Discv5 {
     fork_key: Option<&'static [u8]>,
     /// Filter applied to a discovered peers before passing it up to app.
     discovered_peer_filter: MustNotIncludeKeys,
+    /// Names of the topics advertised in the local node record.
+    topic_names: Arc<[String]>,
+    /// Advertised topics, scoped to the current fork. If not empty, discovered peers must
+    /// advertise at least one of them.
+    topics: Arc<RwLock<Vec<Topic>>>,
+    /// Subscriptions to discovered peers matching a predicate.
+    peer_subscriptions: Arc<Mutex<Vec<PeerSubscription>>>,
     /// Metrics for underlying [`discv5::Discv5`] node and filtered discovered peers.
     metrics: Discv5Metrics,
     /// Returns the _local_ [`NodeRecord`] this service was started with.
@@ -127,6 +148,19 @@ impl Discv5 {
         self.set_eip868_in_local_enr(key, buf.into())
     }
 
+    /// Scopes the advertised topics to the given fork and updates them in the local [`Enr`].
+    ///
+    /// Does nothing if no topics are configured.
+    pub fn update_topics_fork(&self, fork_id: ForkId) {
+        if self.topic_names.is_empty() {
+            return
+        }
+        let topics =
+            self.topic_names.iter().map(|name| Topic::new(name, fork_id.hash)).collect::<Vec<_>>();
+        self.set_eip868_in_local_enr(TOPICS_ENR_KEY.to_vec(), encode_topics(&topics));
+        *self.topics.write() = topics;
+    }
+
     /// Adds the peer and id to the ban list.
     ///
     /// This will prevent any future inclusion in the table
@@ -184,6 +218,8 @@ impl Discv5 {
 
         trace!(target: "net::discv5", ?enr, "local ENR");
 
+        let topics = Arc::new(RwLock::new(discv5_config.topics()));
+
         //
         // 2. start discv5
         //
@@ -194,6 +230,7 @@ impl Discv5 {
             bootstrap_lookup_interval,
             bootstrap_lookup_countdown,
             discovered_peer_filter,
+            topics: topic_names,
             ..
         } = discv5_config;
 
@@ -226,6 +263,7 @@ impl Discv5 {
             bootstrap_lookup_countdown,
             metrics.clone(),
             discv5.clone(),
+            topics.clone(),
         );
 
         Ok((
@@ -234,6 +272,9 @@ impl Discv5 {
                 rlpx_ip_mode,
                 fork_key,
                 discovered_peer_filter,
+                topic_names: topic_names.into(),
+                topics,
+                peer_subscriptions: Default::default(),
                 metrics,
                 local_node_record,
             },
@@ -345,7 +386,10 @@ impl Discv5 {
             "discovered peer"
         );
 
-        Some(DiscoveredPeer { node_record, fork_id })
+        let peer = DiscoveredPeer { node_record, fork_id };
+        self.notify_peer_subscriptions(enr, &peer);
+
+        Some(peer)
     }
 
     /// Tries to recover an unreachable [`Enr`](discv5::Enr) received via
@@ -388,7 +432,66 @@ impl Discv5 {
     /// Applies filtering rules on an ENR. Returns [`Ok`](FilterOutcome::Ok) if peer should be
     /// passed up to app, and [`Ignore`](FilterOutcome::Ignore) if peer should instead be dropped.
     pub fn filter_discovered_peer(&self, enr: &discv5::Enr) -> FilterOutcome {
-        self.discovered_peer_filter.filter(enr)
+        let outcome = self.discovered_peer_filter.filter(enr);
+        if !outcome.is_ok() {
+            return outcome
+        }
+
+        let topics = self.topics.read();
+        if !topics.is_empty() && !advertises_any(enr, &topics) {
+            return FilterOutcome::Ignore { reason: "no shared topic advertised".to_string() }
+        }
+
+        FilterOutcome::Ok
+    }
+
+    /// Returns a receiver of all discovered peers, that pass the filter rules and match the given
+    /// predicate on their node record.
+    ///
+    /// The channel is bounded by [`PEER_SUBSCRIPTION_CHANNEL_SIZE`], peers discovered while it's
+    /// full are dropped for the subscription. The subscription is dropped once the receiver is
+    /// dropped.
+    pub fn subscribe_peers(&self, predicate: EnrPredicate) -> mpsc::Receiver<DiscoveredPeer> {
+        let (tx, rx) = mpsc::channel(PEER_SUBSCRIPTION_CHANNEL_SIZE);
+        self.peer_subscriptions.lock().push(PeerSubscription { predicate, tx });
+        rx
+    }
+
+    /// Sends the discovered peer to all subscriptions with a matching predicate.
+    fn notify_peer_subscriptions(&self, enr: &discv5::Enr, peer: &DiscoveredPeer) {
+        self.peer_subscriptions.lock().retain(|subscription| {
+            if !(subscription.predicate)(enr) {
+                return !subscription.tx.is_closed()
+            }
+            match subscription.tx.try_send(peer.clone()) {
+                Ok(()) => true,
+                Err(mpsc::error::TrySendError::Full(_)) => {
+                    trace!(target: "net::discv5",
+                        peer_id=?peer.node_record.id,
+                        "peer subscription channel full, dropping discovered peer"
+                    );
+                    true
+                }
+                Err(mpsc::error::TrySendError::Closed(_)) => false,
+            }
+        });
+    }
+
+    /// Runs a lookup query for peers advertising any of the given topics, and returns their node
+    /// records.
+    pub async fn lookup_topics(
+        &self,
+        topics: Vec<Topic>,
+        target_peers: usize,
+    ) -> Result<Vec<discv5::Enr>, Error> {
+        self.discv5
+            .find_node_predicate(
+                discv5::enr::NodeId::random(),
+                Box::new(move |enr| advertises_any(enr, &topics)),
+                target_peers,
+            )
+            .await
+            .map_err(Error::LookupFailed)
     }
 
     /// Returns the [`ForkId`] of the given [`Enr`](discv5::Enr) w.r.t. the local node's network
@@ -446,6 +549,11 @@ impl Discv5 {
     pub const fn fork_key(&self) -> Option<&[u8]> {
         self.fork_key
     }
+
+    /// Returns the advertised topics, scoped to the current fork.
+    pub fn topics(&self) -> Vec<Topic> {
+        self.topics.read().clone()
+    }
 }
 
 impl fmt::Debug for Discv5 {
@@ -455,7 +563,7 @@ impl fmt::Debug for Discv5 {
 }
 
 /// Result of successfully processing a peer discovered by [`discv5::Discv5`].
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct DiscoveredPeer {
     /// A discovery v4 backwards compatible ENR.
     pub node_record: NodeRecord,
@@ -463,6 +571,12 @@ pub struct DiscoveredPeer {
     pub fork_id: Option<ForkId>,
 }
 
+/// A subscription to discovered peers, see [`Discv5::subscribe_peers`].
+struct PeerSubscription {
+    predicate: EnrPredicate,
+    tx: mpsc::Sender<DiscoveredPeer>,
+}
+
 /// Builds the local ENR with the supplied key.
 pub fn build_local_enr(
     sk: &SecretKey,
@@ -520,6 +634,12 @@ pub fn build_local_enr(
         builder.add_value_rlp(key, value.clone().into());
     }
 
+    // advertise topics, scoped to the fork
+    let topics = config.topics();
+    if !topics.is_empty() {
+        builder.add_value_rlp(TOPICS_ENR_KEY, encode_topics(&topics).into());
+    }
+
     // enr v4 not to get confused with discv4, independent versioning enr and
     // discovery
     let enr = builder.build(sk).expect("should build enr v4");
@@ -574,6 +694,7 @@ pub fn spawn_populate_kbuckets_bg(
     bootstrap_lookup_countdown: u64,
     metrics: Discv5Metrics,
     discv5: Arc<discv5::Discv5>,
+    topics: Arc<RwLock<Vec<Topic>>>,
 ) {
     let local_node_id = discv5.local_enr().node_id();
     let lookup_interval = Duration::from_secs(lookup_interval);
@@ -593,7 +714,7 @@ pub fn spawn_populate_kbuckets_bg(
                 "starting bootstrap boost lookup query"
             );
 
-            lookup(target, &discv5, &metrics).await;
+            lookup_with_topics(target, &discv5, &topics, &metrics).await;
 
             tokio::time::sleep(pulse_lookup_interval).await;
         }
@@ -610,7 +731,7 @@ pub fn spawn_populate_kbuckets_bg(
                 "starting periodic lookup query"
             );
 
-            lookup(target, &discv5, &metrics).await;
+            lookup_with_topics(target, &discv5, &topics, &metrics).await;
 
             if kbucket_index > DEFAULT_MIN_TARGET_KBUCKET_INDEX {
                 // try to populate bucket one step closer
@@ -691,6 +812,39 @@ pub async fn lookup(
     );
 }
 
+/// Runs a [`discv5::Discv5`] lookup query. If topics are configured, the query looks for peers
+/// advertising any of them.
+async fn lookup_with_topics(
+    target: discv5::enr::NodeId,
+    discv5: &discv5::Discv5,
+    topics: &RwLock<Vec<Topic>>,
+    metrics: &DiscoveredPeersMetrics,
+) {
+    let topics = topics.read().clone();
+    if topics.is_empty() {
+        return lookup(target, discv5, metrics).await
+    }
+
+    match discv5
+        .find_node_predicate(
+            target,
+            Box::new(move |enr| advertises_any(enr, &topics)),
+            DEFAULT_TOPIC_LOOKUP_PEERS,
+        )
+        .await
+    {
+        Err(err) => trace!(target: "net::discv5",
+            %err,
+            "topic lookup query failed"
+        ),
+        Ok(peers) => trace!(target: "net::discv5",
+            target=format!("{:#?}", target),
+            peers_count=peers.len(),
+            "peers advertising topics returned by lookup query"
+        ),
+    }
+}
+
 #[cfg(test)]
 mod test {
     #![allow(deprecated)]
@@ -698,6 +852,7 @@ mod test {
     use ::enr::{CombinedKey, EnrKey};
     use rand_08::thread_rng;
     use reth_chainspec::MAINNET;
+    use reth_ethereum_forks::ForkHash;
     use reth_tracing::init_test_tracing;
     use std::env;
     use tracing::trace;
@@ -716,6 +871,9 @@ mod test {
             rlpx_ip_mode: IpMode::Ip4,
             fork_key: None,
             discovered_peer_filter: MustNotIncludeKeys::default(),
+            topic_names: Arc::new([]),
+            topics: Default::default(),
+            peer_subscriptions: Default::default(),
             metrics: Discv5Metrics::default(),
             local_node_record: NodeRecord::new(
                 (Ipv4Addr::LOCALHOST, 30303).into(),
@@ -815,6 +973,76 @@ mod test {
         )
     }
 
+    #[test]
+    fn discovered_peer_topics() {
+        // rig test
+        let topic = Topic::new("devnet", MAINNET.latest_fork_id().hash);
+        let mut discv5 = discv5_noop();
+        discv5.topics = Arc::new(RwLock::new(vec![topic]));
+        let mut subscription = discv5.subscribe_peers(filter::topics_predicate(vec![topic]));
+
+        let remote_socket = "104.28.44.25:9000".parse().unwrap();
+
+        // test
+
+        // peer without topics is filtered out
+        let remote_key = CombinedKey::generate_secp256k1();
+        let remote_enr = Enr::builder().tcp4(30303).build(&remote_key).unwrap();
+        assert!(discv5.on_discovered_peer(&remote_enr, remote_socket).is_none());
+        assert!(subscription.try_recv().is_err());
+
+        // peer advertising the topic passes and is sent to subscription
+        let remote_key = CombinedKey::generate_secp256k1();
+        let remote_enr = Enr::builder()
+            .tcp4(30303)
+            .add_value_rlp(TOPICS_ENR_KEY, encode_topics(&[topic]).into())
+            .build(&remote_key)
+            .unwrap();
+        let peer = discv5.on_discovered_peer(&remote_enr, remote_socket).unwrap();
+        assert_eq!(subscription.try_recv().unwrap().node_record, peer.node_record);
+
+        // subscription is removed once its receiver is dropped
+        drop(subscription);
+        assert!(discv5.on_discovered_peer(&remote_enr, remote_socket).is_some());
+        assert!(discv5.peer_subscriptions.lock().is_empty());
+    }
+
+    #[test]
+    fn discovered_peer_topics_scoped_to_fork() {
+        // rig test
+        let fork = ForkId { hash: ForkHash([0x01; 4]), next: 0 };
+        let next_fork = ForkId { hash: ForkHash([0x02; 4]), next: 0 };
+        let mut discv5 = discv5_noop();
+        discv5.topic_names = Arc::new(["devnet".to_string()]);
+        discv5.update_topics_fork(fork);
+
+        let remote_socket = "104.28.44.25:9000".parse().unwrap();
+        let remote_key = CombinedKey::generate_secp256k1();
+        let remote_enr = Enr::builder()
+            .tcp4(30303)
+            .add_value_rlp(
+                TOPICS_ENR_KEY,
+                encode_topics(&[Topic::new("devnet", next_fork.hash)]).into(),
+            )
+            .build(&remote_key)
+            .unwrap();
+
+        // test
+
+        // same topic name on a different fork doesn't match
+        assert_eq!(
+            topic::advertised_topics(&discv5.local_enr()),
+            vec![Topic::new("devnet", fork.hash)]
+        );
+        assert!(discv5.on_discovered_peer(&remote_enr, remote_socket).is_none());
+
+        // topics are re-advertised once the fork changes
+        discv5.update_topics_fork(next_fork);
+        assert_eq!(discv5.topics(), vec![Topic::new("devnet", next_fork.hash)]);
+        assert_eq!(topic::advertised_topics(&discv5.local_enr()), discv5.topics());
+        assert!(discv5.on_discovered_peer(&remote_enr, remote_socket).is_some());
+    }
+
     // Copied from sigp/discv5 with slight modification (U256 type)
     // <https://github.com/sigp/discv5/blob/master/src/kbucket/key.rs#L89-L101>
     #[expect(unreachable_pub)]
@@ -0,0 +1,72 @@
+//! Topics advertised in the local node record.
+//!
+//! [`discv5`] doesn't implement topic advertisement, so topics are advertised as a list of hashes
+//! under the [`TOPICS_ENR_KEY`] kv-pair. A topic is scoped to the fork of the node advertising it,
+//! which keeps nodes of different forks of the same testnet apart.
+
+use alloy_primitives::{keccak256, Bytes, B256};
+use derive_more::Display;
+use reth_ethereum_forks::ForkHash;
+
+/// Key of the kv-pair that topics are advertised under in a node record.
+pub const TOPICS_ENR_KEY: &[u8] = b"topics";
+
+/// A discovery topic, identified by the hash of its name and the fork it's scoped to.
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Topic(B256);
+
+impl Topic {
+    /// Returns the topic with the given name, scoped to the given fork.
+    pub fn new(name: &str, fork_hash: ForkHash) -> Self {
+        Self(keccak256([fork_hash.0.as_slice(), name.as_bytes()].concat()))
+    }
+
+    /// Returns the hash that's advertised for this topic.
+    pub const fn hash(&self) -> B256 {
+        self.0
+    }
+}
+
+/// Returns the topics advertised in the given node record.
+pub fn advertised_topics(enr: &discv5::Enr) -> Vec<Topic> {
+    enr.get_decodable::<Vec<B256>>(TOPICS_ENR_KEY)
+        .and_then(Result::ok)
+        .map(|hashes| hashes.into_iter().map(Topic).collect())
+        .unwrap_or_default()
+}
+
+/// Returns `true` if the given node record advertises any of the given topics.
+pub fn advertises_any(enr: &discv5::Enr, topics: &[Topic]) -> bool {
+    advertised_topics(enr).iter().any(|topic| topics.contains(topic))
+}
+
+/// Returns the rlp encoded value of the [`TOPICS_ENR_KEY`] kv-pair for the given topics.
+pub fn encode_topics(topics: &[Topic]) -> Bytes {
+    alloy_rlp::encode(topics.iter().map(Topic::hash).collect::<Vec<_>>()).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use discv5::enr::{CombinedKey, Enr};
+
+    #[test]
+    fn topics_roundtrip() {
+        let fork_hash = ForkHash([0xfc, 0x64, 0xec, 0x04]);
+        let devnet = Topic::new("devnet", fork_hash);
+        let other = Topic::new("other", fork_hash);
+
+        let sk = CombinedKey::generate_secp256k1();
+        let enr = Enr::builder()
+            .add_value_rlp(TOPICS_ENR_KEY, encode_topics(&[devnet]).into())
+            .build(&sk)
+            .unwrap();
+
+        assert_eq!(advertised_topics(&enr), vec![devnet]);
+        assert!(advertises_any(&enr, &[other, devnet]));
+        assert!(!advertises_any(&enr, &[other]));
+
+        // same topic name on a different fork doesn't match
+        assert!(!advertises_any(&enr, &[Topic::new("devnet", ForkHash([0; 4]))]));
+    }
+}
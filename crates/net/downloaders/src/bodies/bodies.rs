@@ -25,6 +25,7 @@ use std::{
     pin::Pin,
     sync::Arc,
     task::{Context, Poll},
+    time::Duration,
 };
 use tracing::info;
 
@@ -516,6 +517,8 @@ pub struct BodiesDownloaderBuilder {
     pub max_buffered_blocks_size_bytes: usize,
     /// The maximum number of requests to send concurrently.
     pub concurrent_requests_range: RangeInclusive<usize>,
+    /// How long to wait for a response before re-dispatching a request to another peer.
+    pub request_timeout: Duration,
 }
 
 impl BodiesDownloaderBuilder {
@@ -530,6 +533,7 @@ impl BodiesDownloaderBuilder {
                 config.downloader_min_concurrent_requests..=
                     config.downloader_max_concurrent_requests,
             )
+            .with_request_timeout(config.downloader_request_timeout)
     }
 }
 
@@ -540,6 +544,7 @@ impl Default for BodiesDownloaderBuilder {
             stream_batch_size: 1_000,
             max_buffered_blocks_size_bytes: 2 * 1024 * 1024 * 1024, // ~2GB
             concurrent_requests_range: 5..=100,
+            request_timeout: Duration::from_secs(15),
         }
     }
 }
@@ -575,6 +580,12 @@ impl BodiesDownloaderBuilder {
         self
     }
 
+    /// Set how long to wait for a response before re-dispatching a request to another peer.
+    pub const fn with_request_timeout(mut self, request_timeout: Duration) -> Self {
+        self.request_timeout = request_timeout;
+        self
+    }
+
     /// Consume self and return the concurrent downloader.
     pub fn build<B, C, Provider>(
         self,
@@ -592,9 +603,10 @@ impl BodiesDownloaderBuilder {
             stream_batch_size,
             concurrent_requests_range,
             max_buffered_blocks_size_bytes,
+            request_timeout,
         } = self;
         let metrics = BodyDownloaderMetrics::default();
-        let in_progress_queue = BodiesRequestQueue::new(metrics.clone());
+        let in_progress_queue = BodiesRequestQueue::new(metrics.clone(), request_timeout);
         BodiesDownloader {
             client: Arc::new(client),
             consensus,
@@ -14,8 +14,10 @@ use std::{
     collections::VecDeque,
     pin::Pin,
     sync::Arc,
-    task::{ready, Context, Poll},
+    task::{Context, Poll},
+    time::Duration,
 };
+use tokio::time::{Instant, Sleep};
 
 /// Body request implemented as a [Future].
 ///
@@ -26,6 +28,10 @@ use std::{
 /// It then proceeds to verify the downloaded bodies. In case of a validation error,
 /// the future will start over.
 ///
+/// If a request timeout is configured and a request is not answered in time, the request is
+/// dropped and the remaining range is re-dispatched. Since the slow peer is still busy with the
+/// dropped request, the range will be served by another peer.
+///
 /// The future will filter out any empty headers (see [`alloy_consensus::Header::is_empty`]) from
 /// the request. If [`BodiesRequestFuture`] was initialized with all empty headers, no request will
 /// be dispatched and they will be immediately returned upon polling.
@@ -50,6 +56,12 @@ pub(crate) struct BodiesRequestFuture<B: Block, C: BodiesClient<Body = B::Body>>
     fut: Option<C::Output>,
     /// Tracks how many bodies we requested in the last request.
     last_request_len: Option<usize>,
+    /// How long to wait for a response before re-dispatching the request, if limited.
+    request_timeout: Option<Duration>,
+    /// When the last request was submitted.
+    last_request_at: Option<Instant>,
+    /// Fires when the last request timed out.
+    timeout: Option<Pin<Box<Sleep>>>,
 }
 
 impl<B, C> BodiesRequestFuture<B, C>
@@ -72,9 +84,18 @@ where
             buffer: Default::default(),
             last_request_len: None,
             fut: None,
+            request_timeout: None,
+            last_request_at: None,
+            timeout: None,
         }
     }
 
+    /// Sets how long to wait for a response before re-dispatching the request.
+    pub(crate) const fn with_request_timeout(mut self, request_timeout: Duration) -> Self {
+        self.request_timeout = Some(request_timeout);
+        self
+    }
+
     pub(crate) fn with_headers(mut self, headers: Vec<SealedHeader<B::Header>>) -> Self {
         self.buffer.reserve_exact(headers.len());
         self.pending_headers = VecDeque::from(headers);
@@ -98,6 +119,24 @@ where
         );
     }
 
+    /// Re-dispatches the pending range after the last request timed out.
+    ///
+    /// The peer serving the timed out request is unknown, so it's not penalized here. The network
+    /// penalizes it once the underlying request times out.
+    fn on_timeout(&mut self) {
+        self.metrics.increment_errors(&DownloadError::Timeout);
+        tracing::debug!(
+            target: "downloaders::bodies",
+            request_len = ?self.last_request_len,
+            timeout = ?self.request_timeout,
+            "Bodies request timed out, re-dispatching"
+        );
+        self.submit_request(
+            self.next_request().expect("existing hashes to resubmit"),
+            Priority::High,
+        );
+    }
+
     /// Retrieve header hashes for the next request.
     fn next_request(&self) -> Option<Vec<B256>> {
         let mut hashes =
@@ -110,6 +149,8 @@ where
         tracing::trace!(target: "downloaders::bodies", request_len = req.len(), "Requesting bodies");
         let client = Arc::clone(&self.client);
         self.last_request_len = Some(req.len());
+        self.last_request_at = Some(Instant::now());
+        self.timeout = None;
         self.fut = Some(client.get_block_bodies_with_priority(req, priority));
     }
 
@@ -144,7 +185,13 @@ where
         }
 
         // Buffer block responses
-        self.try_buffer_blocks(bodies)?;
+        let response_size = self.try_buffer_blocks(bodies)?;
+
+        if let Some(elapsed) = self.last_request_at.map(|at| at.elapsed()) {
+            let throughput = response_size as f64 / elapsed.as_secs_f64().max(f64::EPSILON);
+            self.response_metrics.response_throughput_bytes_per_second.record(throughput);
+            tracing::trace!(target: "downloaders::bodies", ?peer_id, response_size, ?elapsed, throughput, "Bodies response throughput");
+        }
 
         // Submit next request if any
         if let Some(req) = self.next_request() {
@@ -161,7 +208,9 @@ where
     ///
     /// This method removes headers from the internal collection.
     /// If the response fails validation, then the header will be put back.
-    fn try_buffer_blocks(&mut self, bodies: Vec<C::Body>) -> DownloadResult<()>
+    ///
+    /// Returns the total size of the buffered bodies.
+    fn try_buffer_blocks(&mut self, bodies: Vec<C::Body>) -> DownloadResult<usize>
     where
         C::Body: InMemorySize,
     {
@@ -172,7 +221,7 @@ where
         while bodies.peek().is_some() {
             let next_header = match self.pending_headers.pop_front() {
                 Some(header) => header,
-                None => return Ok(()), // no more headers
+                None => return Ok(total_size), // no more headers
             };
 
             if next_header.is_empty() {
@@ -205,7 +254,7 @@ where
         self.response_metrics.response_size_bytes.set(total_size as f64);
         self.response_metrics.response_length.set(bodies_len as f64);
 
-        Ok(())
+        Ok(total_size)
     }
 }
 
@@ -227,20 +276,33 @@ where
             // Check if there is a pending requests. It might not exist if all
             // headers are empty and there is nothing to download.
             if let Some(fut) = this.fut.as_mut() {
-                match ready!(fut.poll_unpin(cx)) {
-                    Ok(response) => {
+                match fut.poll_unpin(cx) {
+                    Poll::Ready(Ok(response)) => {
                         let peer_id = response.peer_id();
                         if let Err(error) = this.on_block_response(response) {
                             this.on_error(error, Some(peer_id));
                         }
                     }
-                    Err(error) => {
+                    Poll::Ready(Err(error)) => {
                         if error.is_channel_closed() {
                             return Poll::Ready(Err(error.into()))
                         }
 
                         this.on_error(error.into(), None);
                     }
+                    Poll::Pending => {
+                        if let Some((timeout, at)) = this.request_timeout.zip(this.last_request_at)
+                        {
+                            let sleep = this.timeout.get_or_insert_with(|| {
+                                Box::pin(tokio::time::sleep_until(at + timeout))
+                            });
+                            if sleep.poll_unpin(cx).is_ready() {
+                                this.on_timeout();
+                                continue
+                            }
+                        }
+                        return Poll::Pending
+                    }
                 }
             }
 
@@ -285,6 +347,26 @@ mod tests {
         assert_eq!(client.times_requested(), 0);
     }
 
+    /// Check that a request that is not answered in time is re-dispatched.
+    #[tokio::test]
+    async fn request_redispatches_after_timeout() {
+        let (headers, mut bodies) = generate_bodies(0..=19);
+
+        let client = Arc::new(
+            TestBodiesClient::default().with_bodies(bodies.clone()).with_stalled_requests(1),
+        );
+        let fut = BodiesRequestFuture::<Block, _>::new(
+            client.clone(),
+            Arc::new(TestConsensus::default()),
+            BodyDownloaderMetrics::default(),
+        )
+        .with_request_timeout(Duration::from_millis(50))
+        .with_headers(headers.clone());
+
+        assert_eq!(fut.await.unwrap(), zip_blocks(headers.iter(), &mut bodies));
+        assert_eq!(client.times_requested(), 2);
+    }
+
     /// Check that the request future
     #[tokio::test]
     async fn request_submits_until_fulfilled() {
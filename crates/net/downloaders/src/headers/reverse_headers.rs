@@ -683,6 +683,27 @@ where
 {
     type Header = H::Header;
 
+    /// Starts over at the parent of `lowest`, which is used to validate the next downloaded
+    /// header, instead of requesting the sync target first.
+    fn resume_sync_gap(&mut self, head: SealedHeader<H::Header>, lowest: SealedHeader<H::Header>) {
+        let next_block = lowest.number().saturating_sub(1);
+        trace!(
+            target: "downloaders::headers",
+            head=?head.num_hash(),
+            lowest=?lowest.num_hash(),
+            "Resuming sync gap"
+        );
+
+        self.clear();
+        self.sync_target_request.take();
+        self.sync_target =
+            Some(SyncTargetBlock::HashAndNumber { hash: lowest.parent_hash(), number: next_block });
+        self.next_request_block_number = next_block;
+        self.next_chain_tip_block_number = next_block;
+        self.lowest_validated_header = Some(lowest);
+        self.local_head = Some(head);
+    }
+
     fn update_local_head(&mut self, head: SealedHeader<H::Header>) {
         // ensure we're only yielding headers that are in range and follow the current local head.
         while self
@@ -1470,6 +1491,32 @@ mod tests {
         assert!(downloader.next().await.is_none());
     }
 
+    #[tokio::test]
+    async fn download_resumed_gap() {
+        reth_tracing::init_test_tracing();
+
+        let client = Arc::new(TestHeadersClient::default());
+
+        let p4 = SealedHeader::default();
+        let p3 = child_header(&p4);
+        let p2 = child_header(&p3);
+        let p1 = child_header(&p2);
+        let p0 = child_header(&p1);
+
+        let mut downloader = ReverseHeadersDownloaderBuilder::default()
+            .stream_batch_size(3)
+            .request_limit(3)
+            .build(Arc::clone(&client), Arc::new(TestConsensus::default()));
+        // `p0` and `p1` were downloaded before the download was interrupted
+        downloader.resume_sync_gap(p4.clone(), p1.clone());
+
+        client.extend(vec![p2.as_ref().clone(), p3.as_ref().clone()]).await;
+
+        let headers = downloader.next().await.unwrap();
+        assert_eq!(headers.unwrap(), vec![p2, p3]);
+        assert!(downloader.next().await.is_none());
+    }
+
     #[tokio::test]
     async fn download_one_by_one() {
         reth_tracing::init_test_tracing();
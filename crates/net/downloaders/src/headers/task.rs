@@ -6,7 +6,7 @@ use reth_network_p2p::headers::{
     downloader::{HeaderDownloader, SyncTarget},
     error::HeadersDownloaderResult,
 };
-use reth_primitives_traits::SealedHeader;
+use reth_primitives_traits::{BlockHeader, SealedHeader};
 use reth_tasks::{TaskSpawner, TokioTaskExecutor};
 use std::{
     fmt::Debug,
@@ -32,7 +32,7 @@ pub struct TaskDownloader<H: Sealable> {
 
 // === impl TaskDownloader ===
 
-impl<H: Sealable + Send + Sync + Unpin + 'static> TaskDownloader<H> {
+impl<H: BlockHeader> TaskDownloader<H> {
     /// Spawns the given `downloader` via [`tokio::task::spawn`] and returns a [`TaskDownloader`]
     /// that's connected to that task.
     ///
@@ -91,6 +91,13 @@ impl<H: Sealable + Debug + Send + Sync + Unpin + 'static> HeaderDownloader for T
         let _ = self.to_downloader.send(DownloaderUpdates::UpdateSyncGap(head, target));
     }
 
+    fn resume_sync_gap(&mut self, head: SealedHeader<H>, lowest: SealedHeader<H>)
+    where
+        H: BlockHeader,
+    {
+        let _ = self.to_downloader.send(DownloaderUpdates::ResumeSyncGap(head, lowest));
+    }
+
     fn update_local_head(&mut self, head: SealedHeader<H>) {
         let _ = self.to_downloader.send(DownloaderUpdates::UpdateLocalHead(head));
     }
@@ -120,7 +127,7 @@ struct SpawnedDownloader<T: HeaderDownloader> {
     downloader: T,
 }
 
-impl<T: HeaderDownloader> Future for SpawnedDownloader<T> {
+impl<T: HeaderDownloader<Header: BlockHeader>> Future for SpawnedDownloader<T> {
     type Output = ();
 
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
@@ -139,6 +146,9 @@ impl<T: HeaderDownloader> Future for SpawnedDownloader<T> {
                         DownloaderUpdates::UpdateSyncGap(head, target) => {
                             this.downloader.update_sync_gap(head, target);
                         }
+                        DownloaderUpdates::ResumeSyncGap(head, lowest) => {
+                            this.downloader.resume_sync_gap(head, lowest);
+                        }
                         DownloaderUpdates::UpdateLocalHead(head) => {
                             this.downloader.update_local_head(head);
                         }
@@ -179,6 +189,7 @@ impl<T: HeaderDownloader> Future for SpawnedDownloader<T> {
 #[derive(Debug)]
 enum DownloaderUpdates<H> {
     UpdateSyncGap(SealedHeader<H>, SyncTarget),
+    ResumeSyncGap(SealedHeader<H>, SealedHeader<H>),
     UpdateLocalHead(SealedHeader<H>),
     UpdateSyncTarget(SyncTarget),
     SetBatchSize(usize),
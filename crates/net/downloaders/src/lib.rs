@@ -45,6 +45,12 @@ pub mod receipt_file_client;
 #[cfg(any(test, feature = "file-client"))]
 pub mod file_codec;
 
+/// Records the external inputs of pipeline runs and replays them from the recorded log.
+///
+/// Contains [`PipelineInputRecorder`](pipeline_log::PipelineInputRecorder) and the downloader
+/// wrappers that feed it, as well as the replay downloaders built from a recorded log.
+pub mod pipeline_log;
+
 #[cfg(any(test, feature = "test-utils"))]
 pub mod test_utils;
 
@@ -1,5 +1,5 @@
 use reth_metrics::{
-    metrics::{Counter, Gauge},
+    metrics::{Counter, Gauge, Histogram},
     Metrics,
 };
 use reth_network_p2p::error::DownloadError;
@@ -73,6 +73,9 @@ pub struct ResponseMetrics {
     pub response_size_bytes: Gauge,
     /// The number of bodies in an individual bodies response received by the downloader.
     pub response_length: Gauge,
+    /// The throughput (in bytes per second) of individual bodies responses, measured from
+    /// dispatching the request until receiving the response.
+    pub response_throughput_bytes_per_second: Histogram,
 }
 
 /// Common header downloader metrics.
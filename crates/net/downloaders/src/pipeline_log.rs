@@ -0,0 +1,785 @@
+//! Records the external inputs of pipeline runs and replays them.
+//!
+//! The only inputs of the online stages that don't come from the database are the sync targets
+//! handed to the header downloader and the batches yielded by the header and body downloaders.
+//! [`RecordingHeaderDownloader`] and [`RecordingBodyDownloader`] append these to a
+//! [`PipelineInputRecorder`] log, which can be read back with [`PipelineInputLog::read`] and fed
+//! to the stages again through [`ReplayHeaderDownloader`] and [`ReplayBodyDownloader`].
+//!
+//! The log is a sequence of RLP lists, one per [`PipelineInput`]. Header and body batches carry
+//! the keccak256 digest of their encoded payload, which is checked when the log is read. The log
+//! is read entry by entry, so it's never held in memory as a whole.
+
+use alloy_primitives::{keccak256, BlockNumber, B256};
+use alloy_rlp::{Decodable, Encodable};
+use futures::{Stream, StreamExt};
+use reth_consensus::ConsensusError;
+use reth_network_p2p::{
+    bodies::{
+        downloader::{BodyDownloader, BodyDownloaderResult},
+        response::BlockResponse,
+    },
+    error::DownloadResult,
+    headers::{
+        downloader::{HeaderDownloader, SyncTarget},
+        error::{HeadersDownloaderError, HeadersDownloaderResult},
+    },
+};
+use reth_primitives_traits::{Block, BlockHeader, GotExpected, SealedBlock, SealedHeader};
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, BufWriter, Read, Write},
+    marker::PhantomData,
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+    pin::Pin,
+    sync::mpsc,
+    task::{Context, Poll},
+    thread,
+};
+use tracing::{error, trace};
+
+/// Entry tag of [`PipelineInput::Tip`].
+const TIP: u8 = 0;
+/// Entry tag of [`PipelineInput::Headers`].
+const HEADERS: u8 = 1;
+/// Entry tag of [`PipelineInput::Bodies`].
+const BODIES: u8 = 2;
+
+/// Tag of an encoded [`BlockResponse::Empty`].
+const EMPTY_RESPONSE: u8 = 0;
+/// Tag of an encoded [`BlockResponse::Full`].
+const FULL_RESPONSE: u8 = 1;
+
+/// Errors that can occur when reading a pipeline input log.
+#[derive(Debug, thiserror::Error)]
+pub enum PipelineLogError {
+    /// An error occurred when reading the log file.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    /// An entry of the log could not be decoded.
+    #[error(transparent)]
+    Rlp(#[from] alloy_rlp::Error),
+    /// An entry has an unknown tag.
+    #[error("unknown pipeline log entry {0}")]
+    UnknownEntry(u8),
+    /// The payload of an entry doesn't match its recorded digest.
+    #[error("pipeline log entry digest mismatch: expected {expected}, got {got}")]
+    DigestMismatch {
+        /// The digest stored in the log.
+        expected: B256,
+        /// The digest of the stored payload.
+        got: B256,
+    },
+}
+
+/// An external input of a pipeline run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PipelineInput<B: Block> {
+    /// The header downloader was asked to sync to the given tip.
+    Tip(B256),
+    /// A batch of headers yielded by the header downloader.
+    Headers(Vec<SealedHeader<B::Header>>),
+    /// A batch of blocks yielded by the body downloader.
+    Bodies(Vec<BlockResponse<B>>),
+}
+
+impl<B: Block> PipelineInput<B> {
+    /// Encodes the input as a log entry.
+    pub fn encode(&self, out: &mut Vec<u8>) {
+        match self {
+            Self::Tip(tip) => encode_tip(*tip, out),
+            Self::Headers(headers) => encode_headers(headers, out),
+            Self::Bodies(bodies) => encode_bodies(bodies, out),
+        }
+    }
+
+    /// Decodes a log entry, advancing the buffer past it.
+    pub fn decode(buf: &mut &[u8]) -> Result<Self, PipelineLogError> {
+        Self::decode_payload(take_list(buf)?)
+    }
+
+    /// Decodes the payload of a log entry.
+    fn decode_payload(mut payload: &[u8]) -> Result<Self, PipelineLogError> {
+        match u8::decode(&mut payload)? {
+            TIP => Ok(Self::Tip(B256::decode(&mut payload)?)),
+            HEADERS => {
+                verify_digest(&mut payload)?;
+                let headers = Vec::<B::Header>::decode(&mut payload)?;
+                Ok(Self::Headers(headers.into_iter().map(SealedHeader::seal_slow).collect()))
+            }
+            BODIES => {
+                verify_digest(&mut payload)?;
+                let mut list = take_list(&mut payload)?;
+                let mut bodies = Vec::new();
+                while !list.is_empty() {
+                    bodies.push(decode_block_response(&mut list)?);
+                }
+                Ok(Self::Bodies(bodies))
+            }
+            tag => Err(PipelineLogError::UnknownEntry(tag)),
+        }
+    }
+}
+
+/// Appends the external inputs of pipeline runs to a log file.
+///
+/// Entries are written by a background thread, so that recording doesn't block the downloaders.
+/// The thread flushes the log whenever it has written all entries observed so far, so the log is
+/// complete up to shortly before the node stopped or crashed.
+#[derive(Debug, Clone)]
+pub struct PipelineInputRecorder {
+    tx: mpsc::Sender<RecorderMessage>,
+}
+
+/// A message to the background thread of a [`PipelineInputRecorder`].
+#[derive(Debug)]
+enum RecorderMessage {
+    /// An encoded entry to append to the log.
+    Entry(Vec<u8>),
+    /// Notifies the sender once all previous entries are flushed.
+    Flush(mpsc::SyncSender<()>),
+}
+
+impl PipelineInputRecorder {
+    /// Creates the log file at the given path, truncating any previous recording, and spawns the
+    /// thread that writes to it.
+    pub fn new(path: impl AsRef<Path>) -> io::Result<Self> {
+        if let Some(parent) = path.as_ref().parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = File::create(path)?;
+        let (tx, rx) = mpsc::channel();
+        thread::Builder::new()
+            .name("pipeline-recorder".to_string())
+            .spawn(move || write_entries(BufWriter::new(file), rx))?;
+        Ok(Self { tx })
+    }
+
+    /// Records a new sync target of the header downloader.
+    pub fn record_tip(&self, tip: B256) {
+        let mut out = Vec::new();
+        encode_tip(tip, &mut out);
+        self.send(RecorderMessage::Entry(out));
+    }
+
+    /// Records a batch of headers yielded by the header downloader.
+    pub fn record_headers<H: BlockHeader>(&self, headers: &[SealedHeader<H>]) {
+        let mut out = Vec::new();
+        encode_headers(headers, &mut out);
+        self.send(RecorderMessage::Entry(out));
+    }
+
+    /// Records a batch of blocks yielded by the body downloader.
+    pub fn record_bodies<B: Block>(&self, bodies: &[BlockResponse<B>]) {
+        let mut out = Vec::new();
+        encode_bodies(bodies, &mut out);
+        self.send(RecorderMessage::Entry(out));
+    }
+
+    /// Blocks until all inputs recorded so far are written to the log.
+    pub fn flush(&self) {
+        let (tx, rx) = mpsc::sync_channel(1);
+        self.send(RecorderMessage::Flush(tx));
+        let _ = rx.recv();
+    }
+
+    fn send(&self, message: RecorderMessage) {
+        if self.tx.send(message).is_err() {
+            error!(target: "downloaders::pipeline_log", "Pipeline input recorder stopped, failed to record pipeline input");
+        }
+    }
+}
+
+/// Writes the entries received from a [`PipelineInputRecorder`] until all recorders are dropped.
+///
+/// Stops on the first write error, so that the log is never written with gaps.
+fn write_entries(mut writer: BufWriter<File>, rx: mpsc::Receiver<RecorderMessage>) {
+    let mut flushed = Vec::new();
+    while let Ok(message) = rx.recv() {
+        let mut result = Ok(());
+        // write everything that's queued before flushing
+        for message in std::iter::once(message).chain(rx.try_iter()) {
+            match message {
+                RecorderMessage::Entry(entry) => {
+                    result = result.and_then(|_| writer.write_all(&entry));
+                }
+                RecorderMessage::Flush(tx) => flushed.push(tx),
+            }
+        }
+        if let Err(err) = result.and_then(|_| writer.flush()) {
+            error!(target: "downloaders::pipeline_log", %err, "Failed to record pipeline input, stopping the recording");
+            return
+        }
+        for tx in flushed.drain(..) {
+            let _ = tx.send(());
+        }
+    }
+}
+
+/// A [`HeaderDownloader`] that records the sync targets it receives and the batches it yields.
+#[derive(Debug)]
+pub struct RecordingHeaderDownloader<D> {
+    inner: D,
+    recorder: PipelineInputRecorder,
+}
+
+impl<D> RecordingHeaderDownloader<D> {
+    /// Wraps the given downloader.
+    pub const fn new(inner: D, recorder: PipelineInputRecorder) -> Self {
+        Self { inner, recorder }
+    }
+}
+
+impl<D: HeaderDownloader<Header: BlockHeader>> HeaderDownloader for RecordingHeaderDownloader<D> {
+    type Header = D::Header;
+
+    fn resume_sync_gap(
+        &mut self,
+        head: SealedHeader<Self::Header>,
+        lowest: SealedHeader<Self::Header>,
+    ) {
+        self.inner.resume_sync_gap(head, lowest)
+    }
+
+    fn update_local_head(&mut self, head: SealedHeader<Self::Header>) {
+        self.inner.update_local_head(head)
+    }
+
+    fn update_sync_target(&mut self, target: SyncTarget) {
+        if let SyncTarget::Tip(tip) = target {
+            self.recorder.record_tip(tip);
+        }
+        self.inner.update_sync_target(target)
+    }
+
+    fn set_batch_size(&mut self, limit: usize) {
+        self.inner.set_batch_size(limit)
+    }
+}
+
+impl<D: HeaderDownloader<Header: BlockHeader>> Stream for RecordingHeaderDownloader<D> {
+    type Item = HeadersDownloaderResult<Vec<SealedHeader<D::Header>>, D::Header>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let next = self.inner.poll_next_unpin(cx);
+        if let Poll::Ready(Some(Ok(headers))) = &next {
+            self.recorder.record_headers(headers);
+        }
+        next
+    }
+}
+
+/// A [`BodyDownloader`] that records the batches it yields.
+#[derive(Debug)]
+pub struct RecordingBodyDownloader<D> {
+    inner: D,
+    recorder: PipelineInputRecorder,
+}
+
+impl<D> RecordingBodyDownloader<D> {
+    /// Wraps the given downloader.
+    pub const fn new(inner: D, recorder: PipelineInputRecorder) -> Self {
+        Self { inner, recorder }
+    }
+}
+
+impl<D: BodyDownloader> BodyDownloader for RecordingBodyDownloader<D> {
+    type Block = D::Block;
+
+    fn set_download_range(&mut self, range: RangeInclusive<BlockNumber>) -> DownloadResult<()> {
+        self.inner.set_download_range(range)
+    }
+}
+
+impl<D: BodyDownloader> Stream for RecordingBodyDownloader<D> {
+    type Item = BodyDownloaderResult<D::Block>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let next = self.inner.poll_next_unpin(cx);
+        if let Poll::Ready(Some(Ok(bodies))) = &next {
+            self.recorder.record_bodies(bodies);
+        }
+        next
+    }
+}
+
+/// A log written by a [`PipelineInputRecorder`].
+///
+/// The whole log is checked when it's opened, but inputs are only read when they're replayed.
+#[derive(Debug)]
+pub struct PipelineInputLog<B: Block> {
+    path: PathBuf,
+    tips: Vec<B256>,
+    max_block: Option<BlockNumber>,
+    _block: PhantomData<B>,
+}
+
+impl<B: Block> PipelineInputLog<B> {
+    /// Opens the log at the given path and checks that all of its entries can be decoded.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, PipelineLogError> {
+        let path = path.as_ref().to_path_buf();
+        let mut tips = Vec::new();
+        let mut max_block = None;
+        for input in Self::read_inputs(&path)? {
+            match input? {
+                PipelineInput::Tip(tip) => {
+                    // consecutive duplicates don't start new pipeline runs
+                    if tips.last() != Some(&tip) {
+                        tips.push(tip);
+                    }
+                }
+                PipelineInput::Headers(headers) => {
+                    max_block = max_block.max(headers.iter().map(|h| h.number()).max());
+                }
+                PipelineInput::Bodies(_) => {}
+            }
+        }
+        Ok(Self { path, tips, max_block, _block: PhantomData })
+    }
+
+    /// Returns an iterator over the inputs of the log at the given path, in the order they were
+    /// observed.
+    pub fn read_inputs(
+        path: impl AsRef<Path>,
+    ) -> io::Result<impl Iterator<Item = Result<PipelineInput<B>, PipelineLogError>>> {
+        let mut entries = EntryReader::open(path.as_ref())?;
+        Ok(std::iter::from_fn(move || {
+            entries.next_entry().transpose().map(|entry| PipelineInput::decode_payload(&entry?))
+        }))
+    }
+
+    /// Returns the recorded sync targets in order, without consecutive duplicates.
+    pub fn tips(&self) -> &[B256] {
+        &self.tips
+    }
+
+    /// Returns the highest block number of all recorded headers.
+    pub const fn max_block(&self) -> Option<BlockNumber> {
+        self.max_block
+    }
+
+    /// Returns downloaders that yield the recorded batches in order.
+    pub fn into_downloaders(
+        self,
+    ) -> io::Result<(ReplayHeaderDownloader<B>, ReplayBodyDownloader<B>)> {
+        Ok((
+            ReplayHeaderDownloader {
+                entries: EntryReader::open(&self.path)?,
+                local_head: None,
+                next_hash: None,
+                _block: PhantomData,
+            },
+            ReplayBodyDownloader { entries: EntryReader::open(&self.path)?, _block: PhantomData },
+        ))
+    }
+}
+
+/// Reads the entries of a pipeline input log one by one.
+#[derive(Debug)]
+struct EntryReader {
+    reader: BufReader<File>,
+}
+
+impl EntryReader {
+    fn open(path: &Path) -> io::Result<Self> {
+        Ok(Self { reader: BufReader::new(File::open(path)?) })
+    }
+
+    /// Reads the payload of the next entry, or returns `None` at the end of the log.
+    fn next_entry(&mut self) -> Result<Option<Vec<u8>>, PipelineLogError> {
+        if self.reader.fill_buf()?.is_empty() {
+            return Ok(None)
+        }
+
+        // entries are RLP lists, the header is at most 9 bytes long
+        let mut header = [0u8; 9];
+        self.reader.read_exact(&mut header[..1])?;
+        let header_len = match header[0] {
+            0xc0..=0xf7 => 1,
+            prefix @ 0xf8..=0xff => 1 + (prefix - 0xf7) as usize,
+            _ => return Err(alloy_rlp::Error::UnexpectedString.into()),
+        };
+        self.reader.read_exact(&mut header[1..header_len])?;
+        let payload_length = alloy_rlp::Header::decode(&mut &header[..header_len])?.payload_length;
+
+        // the payload isn't allocated upfront, the length may be corrupted
+        let mut payload = Vec::new();
+        (&mut self.reader).take(payload_length as u64).read_to_end(&mut payload)?;
+        if payload.len() != payload_length {
+            return Err(alloy_rlp::Error::InputTooShort.into())
+        }
+        Ok(Some(payload))
+    }
+
+    /// Reads and decodes the next entry with the given tag, skipping all others.
+    fn next_input<B: Block>(
+        &mut self,
+        tag: u8,
+    ) -> Result<Option<PipelineInput<B>>, PipelineLogError> {
+        while let Some(entry) = self.next_entry()? {
+            if u8::decode(&mut entry.as_slice())? == tag {
+                return PipelineInput::decode_payload(&entry).map(Some)
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// A [`HeaderDownloader`] that yields recorded header batches.
+///
+/// Each batch is checked against the sync gap: the first batch after a sync target update must
+/// start at the target, every batch must continue with the parent of the previous one, and the
+/// batch reaching the local head must attach to it. The stream ends once all recorded batches have
+/// been yielded, or if the recorded batches don't match the sync gap anymore.
+#[derive(Debug)]
+pub struct ReplayHeaderDownloader<B: Block> {
+    entries: EntryReader,
+    /// The local head of the sync gap.
+    local_head: Option<SealedHeader<B::Header>>,
+    /// Hash of the header the next batch has to start with, if known.
+    next_hash: Option<B256>,
+    _block: PhantomData<B>,
+}
+
+impl<B: Block> ReplayHeaderDownloader<B> {
+    /// Returns the next recorded batch, or `None` if it can't be replayed.
+    fn next_batch(
+        &mut self,
+    ) -> Option<HeadersDownloaderResult<Vec<SealedHeader<B::Header>>, B::Header>> {
+        let headers = match self.entries.next_input::<B>(HEADERS) {
+            Ok(Some(PipelineInput::Headers(headers))) => headers,
+            Ok(_) => return None,
+            Err(err) => {
+                error!(target: "downloaders::pipeline_log", %err, "Failed to read recorded headers");
+                return None
+            }
+        };
+        let (Some(highest), Some(lowest)) = (headers.first(), headers.last()) else {
+            return Some(Ok(headers))
+        };
+
+        if let Some(next_hash) = self.next_hash &&
+            highest.hash() != next_hash
+        {
+            error!(target: "downloaders::pipeline_log", expected = %next_hash, got = %highest.hash(), "Recorded headers don't match the sync gap");
+            return None
+        }
+        self.next_hash = Some(lowest.parent_hash());
+
+        if let Some(local_head) = &self.local_head {
+            if lowest.number() <= local_head.number() {
+                error!(target: "downloaders::pipeline_log", local_head = local_head.number(), lowest = lowest.number(), "Recorded headers are below the local head");
+                return None
+            }
+            if lowest.number() == local_head.number() + 1 &&
+                lowest.parent_hash() != local_head.hash()
+            {
+                return Some(Err(HeadersDownloaderError::DetachedHead {
+                    local_head: Box::new(local_head.clone()),
+                    header: Box::new(lowest.clone()),
+                    error: Box::new(ConsensusError::ParentHashMismatch(
+                        GotExpected { got: lowest.parent_hash(), expected: local_head.hash() }
+                            .into(),
+                    )),
+                }))
+            }
+        }
+
+        Some(Ok(headers))
+    }
+}
+
+impl<B: Block + 'static> HeaderDownloader for ReplayHeaderDownloader<B> {
+    type Header = B::Header;
+
+    fn update_local_head(&mut self, head: SealedHeader<B::Header>) {
+        trace!(target: "downloaders::pipeline_log", head = head.number(), "Replaying local head update");
+        self.local_head = Some(head);
+    }
+
+    fn update_sync_target(&mut self, target: SyncTarget) {
+        trace!(target: "downloaders::pipeline_log", ?target, "Replaying sync target update");
+        self.next_hash = match target {
+            SyncTarget::Tip(tip) => Some(tip),
+            SyncTarget::Gap(gap) => Some(gap.parent),
+            SyncTarget::TipNum(_) => None,
+        };
+    }
+
+    fn set_batch_size(&mut self, _: usize) {}
+}
+
+impl<B: Block> Stream for ReplayHeaderDownloader<B> {
+    type Item = HeadersDownloaderResult<Vec<SealedHeader<B::Header>>, B::Header>;
+
+    fn poll_next(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Poll::Ready(self.next_batch())
+    }
+}
+
+/// A [`BodyDownloader`] that yields recorded block batches, regardless of the download range.
+///
+/// The stream ends once all recorded batches have been yielded.
+#[derive(Debug)]
+pub struct ReplayBodyDownloader<B: Block> {
+    entries: EntryReader,
+    _block: PhantomData<B>,
+}
+
+impl<B: Block + 'static> BodyDownloader for ReplayBodyDownloader<B> {
+    type Block = B;
+
+    fn set_download_range(&mut self, range: RangeInclusive<BlockNumber>) -> DownloadResult<()> {
+        trace!(target: "downloaders::pipeline_log", ?range, "Replaying download range update");
+        Ok(())
+    }
+}
+
+impl<B: Block> Stream for ReplayBodyDownloader<B> {
+    type Item = BodyDownloaderResult<B>;
+
+    fn poll_next(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Poll::Ready(match self.entries.next_input::<B>(BODIES) {
+            Ok(Some(PipelineInput::Bodies(bodies))) => Some(Ok(bodies)),
+            Ok(_) => None,
+            Err(err) => {
+                error!(target: "downloaders::pipeline_log", %err, "Failed to read recorded bodies");
+                None
+            }
+        })
+    }
+}
+
+fn encode_tip(tip: B256, out: &mut Vec<u8>) {
+    let mut payload = Vec::new();
+    TIP.encode(&mut payload);
+    tip.encode(&mut payload);
+    encode_list(&payload, out);
+}
+
+fn encode_headers<H: BlockHeader>(headers: &[SealedHeader<H>], out: &mut Vec<u8>) {
+    let mut items = Vec::new();
+    for header in headers {
+        header.header().encode(&mut items);
+    }
+    encode_with_digest(HEADERS, &items, out);
+}
+
+fn encode_bodies<B: Block>(bodies: &[BlockResponse<B>], out: &mut Vec<u8>) {
+    let mut items = Vec::new();
+    for response in bodies {
+        let mut item = Vec::new();
+        match response {
+            BlockResponse::Full(block) => {
+                FULL_RESPONSE.encode(&mut item);
+                block.encode(&mut item);
+            }
+            BlockResponse::Empty(header) => {
+                EMPTY_RESPONSE.encode(&mut item);
+                header.header().encode(&mut item);
+            }
+        }
+        encode_list(&item, &mut items);
+    }
+    encode_with_digest(BODIES, &items, out);
+}
+
+/// Encodes `[tag, keccak256(list), list]`, where `list` is the RLP list of the given items.
+fn encode_with_digest(tag: u8, items: &[u8], out: &mut Vec<u8>) {
+    let mut list = Vec::new();
+    encode_list(items, &mut list);
+
+    let mut payload = Vec::new();
+    tag.encode(&mut payload);
+    keccak256(&list).encode(&mut payload);
+    payload.extend_from_slice(&list);
+    encode_list(&payload, out);
+}
+
+fn encode_list(payload: &[u8], out: &mut Vec<u8>) {
+    alloy_rlp::Header { list: true, payload_length: payload.len() }.encode(out);
+    out.extend_from_slice(payload);
+}
+
+/// Returns the payload of the RLP list at the start of the buffer and advances the buffer past it.
+fn take_list<'a>(buf: &mut &'a [u8]) -> alloy_rlp::Result<&'a [u8]> {
+    let header = alloy_rlp::Header::decode(buf)?;
+    if !header.list {
+        return Err(alloy_rlp::Error::UnexpectedString)
+    }
+    if buf.len() < header.payload_length {
+        return Err(alloy_rlp::Error::InputTooShort)
+    }
+    let (payload, rest) = buf.split_at(header.payload_length);
+    *buf = rest;
+    Ok(payload)
+}
+
+/// Decodes the digest at the start of the buffer and checks it against the remaining payload.
+fn verify_digest(buf: &mut &[u8]) -> Result<(), PipelineLogError> {
+    let expected = B256::decode(buf)?;
+    let got = keccak256(*buf);
+    if expected != got {
+        return Err(PipelineLogError::DigestMismatch { expected, got })
+    }
+    Ok(())
+}
+
+fn decode_block_response<B: Block>(buf: &mut &[u8]) -> Result<BlockResponse<B>, PipelineLogError> {
+    let mut item = take_list(buf)?;
+    match u8::decode(&mut item)? {
+        EMPTY_RESPONSE => {
+            Ok(BlockResponse::Empty(SealedHeader::seal_slow(B::Header::decode(&mut item)?)))
+        }
+        FULL_RESPONSE => Ok(BlockResponse::Full(SealedBlock::decode(&mut item)?)),
+        tag => Err(PipelineLogError::UnknownEntry(tag)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_ethereum_primitives::Block;
+    use reth_testing_utils::generators::{self, random_block_range, BlockRangeParams};
+
+    fn random_inputs() -> Vec<PipelineInput<Block>> {
+        let mut rng = generators::rng();
+        let blocks = random_block_range(
+            &mut rng,
+            0..=9,
+            BlockRangeParams { tx_count: 0..3, ..Default::default() },
+        );
+        let tip = blocks.last().unwrap().hash();
+        let headers = blocks.iter().rev().map(|block| block.clone_sealed_header()).collect();
+        let bodies = blocks
+            .into_iter()
+            .map(|block| {
+                if block.body().transactions.is_empty() {
+                    BlockResponse::Empty(block.clone_sealed_header())
+                } else {
+                    BlockResponse::Full(block)
+                }
+            })
+            .collect();
+
+        vec![
+            PipelineInput::Tip(tip),
+            PipelineInput::Headers(headers),
+            PipelineInput::Bodies(bodies),
+        ]
+    }
+
+    fn write_log(path: &Path, inputs: &[PipelineInput<Block>]) {
+        let mut encoded = Vec::new();
+        for input in inputs {
+            input.encode(&mut encoded);
+        }
+        std::fs::write(path, encoded).unwrap();
+    }
+
+    #[test]
+    fn pipeline_input_log_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("pipeline.log");
+        let inputs = random_inputs();
+        write_log(&path, &inputs);
+
+        let read = PipelineInputLog::<Block>::read_inputs(&path)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(read, inputs);
+
+        let log = PipelineInputLog::<Block>::open(&path).unwrap();
+        assert_eq!(log.max_block(), Some(9));
+    }
+
+    #[test]
+    fn pipeline_input_log_digest_mismatch() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("pipeline.log");
+        let inputs = random_inputs();
+        let mut encoded = Vec::new();
+        inputs[1].encode(&mut encoded);
+
+        // flip a bit of the last encoded header
+        *encoded.last_mut().unwrap() ^= 1;
+        assert!(matches!(
+            PipelineInput::<Block>::decode(&mut encoded.as_slice()),
+            Err(PipelineLogError::DigestMismatch { .. })
+        ));
+
+        std::fs::write(&path, &encoded).unwrap();
+        assert!(matches!(
+            PipelineInputLog::<Block>::open(&path),
+            Err(PipelineLogError::DigestMismatch { .. })
+        ));
+
+        // truncated logs are rejected as well
+        std::fs::write(&path, &encoded[..encoded.len() - 1]).unwrap();
+        assert!(PipelineInputLog::<Block>::open(&path).is_err());
+    }
+
+    #[tokio::test]
+    async fn recorder_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("pipeline.log");
+        let recorder = PipelineInputRecorder::new(&path).unwrap();
+
+        let inputs = random_inputs();
+        for input in &inputs {
+            match input {
+                PipelineInput::Tip(tip) => recorder.record_tip(*tip),
+                PipelineInput::Headers(headers) => recorder.record_headers(headers),
+                PipelineInput::Bodies(bodies) => recorder.record_bodies(bodies),
+            }
+        }
+        recorder.flush();
+
+        let PipelineInput::Tip(tip) = inputs[0] else { unreachable!() };
+        let log = PipelineInputLog::<Block>::open(&path).unwrap();
+        assert_eq!(log.tips(), &[tip]);
+
+        let (mut headers, mut bodies) = log.into_downloaders().unwrap();
+        headers.update_sync_target(SyncTarget::Tip(tip));
+        assert!(matches!(headers.next().await, Some(Ok(batch)) if batch.len() == 10));
+        assert!(headers.next().await.is_none());
+        assert!(matches!(bodies.next().await, Some(Ok(batch)) if batch.len() == 10));
+        assert!(bodies.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn replay_checks_sync_gap() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("pipeline.log");
+        let inputs = random_inputs();
+        let PipelineInput::Headers(headers) = &inputs[1] else { unreachable!() };
+        let (genesis, headers) = headers.split_last().unwrap();
+        let tip = headers[0].hash();
+        write_log(&path, &[PipelineInput::Tip(tip), PipelineInput::Headers(headers.to_vec())]);
+
+        let replay = |local_head: SealedHeader<_>, tip| {
+            let (mut downloader, _) =
+                PipelineInputLog::<Block>::open(&path).unwrap().into_downloaders().unwrap();
+            downloader.update_sync_gap(local_head, SyncTarget::Tip(tip));
+            downloader
+        };
+
+        // the recorded headers close the gap
+        let mut downloader = replay(genesis.clone(), tip);
+        assert!(matches!(downloader.next().await, Some(Ok(batch)) if batch.len() == 9));
+
+        // the recorded headers start at a different tip
+        let mut downloader = replay(genesis.clone(), B256::random());
+        assert!(downloader.next().await.is_none());
+
+        // the recorded headers don't attach to the local head
+        let other_genesis = SealedHeader::seal_slow(alloy_consensus::Header::default());
+        let mut downloader = replay(other_genesis, tip);
+        assert!(matches!(
+            downloader.next().await,
+            Some(Err(HeadersDownloaderError::DetachedHead { .. }))
+        ));
+    }
+}
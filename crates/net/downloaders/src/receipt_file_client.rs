@@ -1,5 +1,7 @@
-use std::{fmt, io};
+use std::{fmt, io, marker::PhantomData};
 
+use alloy_primitives::bytes::{Buf, BytesMut};
+use alloy_rlp::{Decodable, Header};
 use futures::Future;
 use tokio::io::AsyncReadExt;
 use tokio_stream::StreamExt;
@@ -211,6 +213,70 @@ pub struct ReceiptWithBlockNumber<R> {
     pub number: u64,
 }
 
+/// Codec for reading RLP encoded receipts from a flat file.
+///
+/// Every entry in the file is either an RLP list of `[block_number, receipt]`, using the RLP
+/// encoding of the receipt type, or an empty list (`0xc0`) marking a block without receipts.
+/// Receipts of the same block are written as consecutive entries with the same block number.
+///
+/// Same as with any receipt codec, the framed reader must have capacity for the entire chunk, see
+/// [`ReceiptFileClient`].
+#[derive(Debug)]
+pub struct RlpReceiptFileCodec<R>(PhantomData<R>);
+
+impl<R> Default for RlpReceiptFileCodec<R> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<R: Decodable> Decoder for RlpReceiptFileCodec<R> {
+    type Item = Option<ReceiptWithBlockNumber<R>>;
+    type Error = FileClientError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        if src.is_empty() {
+            return Ok(None)
+        }
+
+        let buf_slice = &mut src.as_ref();
+        let receipt = decode_receipt_entry(buf_slice)
+            .map_err(|err| FileClientError::Rlp(err, src.to_vec()))?;
+        src.advance(src.len() - buf_slice.len());
+
+        Ok(Some(receipt))
+    }
+}
+
+/// Decodes a single entry of the [`RlpReceiptFileCodec`] format.
+fn decode_receipt_entry<R: Decodable>(
+    buf: &mut &[u8],
+) -> alloy_rlp::Result<Option<ReceiptWithBlockNumber<R>>> {
+    let header = Header::decode(buf)?;
+    if !header.list {
+        return Err(alloy_rlp::Error::UnexpectedString)
+    }
+    if header.payload_length == 0 {
+        return Ok(None)
+    }
+    if buf.len() < header.payload_length {
+        return Err(alloy_rlp::Error::InputTooShort)
+    }
+
+    let started_len = buf.len();
+    let number = u64::decode(buf)?;
+    let receipt = R::decode(buf)?;
+    let consumed = started_len - buf.len();
+    if consumed != header.payload_length {
+        return Err(alloy_rlp::Error::ListLengthMismatch {
+            expected: header.payload_length,
+            got: consumed,
+        })
+    }
+
+    Ok(Some(ReceiptWithBlockNumber { receipt, number }))
+}
+
 #[cfg(test)]
 mod test {
     use alloy_primitives::{
@@ -218,12 +284,14 @@ mod test {
         bytes::{Buf, BytesMut},
         hex, Bytes, Log, LogData,
     };
-    use alloy_rlp::{Decodable, RlpDecodable};
+    use alloy_rlp::{Decodable, Encodable, Header, RlpDecodable};
     use reth_ethereum_primitives::{Receipt, TxType};
     use reth_tracing::init_test_tracing;
     use tokio_util::codec::Decoder;
 
-    use super::{FromReceiptReader, ReceiptFileClient, ReceiptWithBlockNumber};
+    use super::{
+        FromReceiptReader, ReceiptFileClient, ReceiptWithBlockNumber, RlpReceiptFileCodec,
+    };
     use crate::{DecodedFileChunk, FileClientError};
 
     #[derive(Debug, PartialEq, Eq, RlpDecodable)]
@@ -619,4 +687,58 @@ mod test {
         assert_eq!(receipt_block_2().receipt, receipts[2][1].clone());
         assert_eq!(receipt_block_3().receipt, receipts[3][0].clone());
     }
+
+    fn encode_rlp_receipt_entry(entry: &ReceiptWithBlockNumber<Receipt>) -> Vec<u8> {
+        let mut buf = Vec::new();
+        Header { list: true, payload_length: entry.number.length() + entry.receipt.length() }
+            .encode(&mut buf);
+        entry.number.encode(&mut buf);
+        entry.receipt.encode(&mut buf);
+        buf
+    }
+
+    #[tokio::test]
+    async fn receipt_file_client_rlp_codec() {
+        init_test_tracing();
+
+        // genesis block has no receipts
+        let mut encoded_receipts = MOCK_RECEIPT_BLOCK_NO_TRANSACTIONS.to_vec();
+        // one receipt for block 1, two receipts for block 2
+        encoded_receipts.extend(encode_rlp_receipt_entry(&receipt_block_1()));
+        encoded_receipts.extend(encode_rlp_receipt_entry(&receipt_block_2()));
+        encoded_receipts.extend(encode_rlp_receipt_entry(&receipt_block_2()));
+        // no receipt for block 3
+        encoded_receipts.extend_from_slice(MOCK_RECEIPT_BLOCK_NO_TRANSACTIONS);
+
+        let encoded_byte_len = encoded_receipts.len() as u64;
+        let reader = &mut &encoded_receipts[..];
+
+        let DecodedFileChunk {
+            file_client: ReceiptFileClient { receipts, first_block, total_receipts, .. },
+            ..
+        } = ReceiptFileClient::<RlpReceiptFileCodec<Receipt>>::from_receipt_reader(
+            reader,
+            encoded_byte_len,
+            None,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(3, total_receipts);
+        assert_eq!(0, first_block);
+        assert!(receipts[0].is_empty());
+        assert_eq!(receipt_block_1().receipt, receipts[1][0]);
+        assert_eq!(receipt_block_2().receipt, receipts[2][0]);
+        assert_eq!(receipt_block_2().receipt, receipts[2][1]);
+        assert!(receipts[3].is_empty());
+    }
+
+    #[test]
+    fn rlp_codec_partial_entry() {
+        let encoded = encode_rlp_receipt_entry(&receipt_block_1());
+        let mut buf = BytesMut::from(&encoded[..encoded.len() - 1]);
+
+        let err = RlpReceiptFileCodec::<Receipt>::default().decode(&mut buf).unwrap_err();
+        assert!(matches!(err, FileClientError::Rlp(alloy_rlp::Error::InputTooShort, _)));
+    }
 }
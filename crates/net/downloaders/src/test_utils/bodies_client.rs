@@ -26,6 +26,7 @@ pub struct TestBodiesClient {
     max_batch_size: Option<usize>,
     times_requested: AtomicU64,
     empty_response_mod: Option<u64>,
+    stalled_requests: u64,
 }
 
 impl TestBodiesClient {
@@ -46,6 +47,13 @@ impl TestBodiesClient {
         self
     }
 
+    /// Instructs the client to never respond to the first `stalled_requests` requests, like a
+    /// peer that holds on to a request.
+    pub(crate) const fn with_stalled_requests(mut self, stalled_requests: u64) -> Self {
+        self.stalled_requests = stalled_requests;
+        self
+    }
+
     pub(crate) const fn with_max_batch_size(mut self, max_batch_size: usize) -> Self {
         self.max_batch_size = Some(max_batch_size);
         self
@@ -92,10 +100,15 @@ impl BodiesClient for TestBodiesClient {
         let bodies = self.bodies.clone();
         let max_batch_size = self.max_batch_size;
 
-        self.times_requested.fetch_add(1, Ordering::Relaxed);
+        let should_stall =
+            self.times_requested.fetch_add(1, Ordering::Relaxed) < self.stalled_requests;
         let should_respond_empty = self.should_respond_empty();
 
         Box::pin(async move {
+            if should_stall {
+                std::future::pending::<()>().await;
+            }
+
             if should_respond_empty {
                 return Ok((PeerId::default(), vec![]).into())
             }
@@ -179,6 +179,10 @@ pub struct EthStream<S, N = EthNetworkPrimitives> {
     eth: EthStreamInner<N>,
     #[pin]
     inner: S,
+    /// The size of the last received message, including its message id.
+    last_received_size: usize,
+    /// The size of the last sent message, including its message id.
+    last_sent_size: usize,
 }
 
 impl<S, N: NetworkPrimitives> EthStream<S, N> {
@@ -186,7 +190,7 @@ impl<S, N: NetworkPrimitives> EthStream<S, N> {
     /// to manually handshake a peer.
     #[inline]
     pub const fn new(version: EthVersion, inner: S) -> Self {
-        Self { eth: EthStreamInner::new(version), inner }
+        Self { eth: EthStreamInner::new(version), inner, last_received_size: 0, last_sent_size: 0 }
     }
 
     /// Returns the eth version.
@@ -195,6 +199,22 @@ impl<S, N: NetworkPrimitives> EthStream<S, N> {
         self.eth.version()
     }
 
+    /// Returns the size of the last message received from the stream, including its message id.
+    ///
+    /// This is the size of the frame the message was decoded from, before `snappy` decompression.
+    #[inline]
+    pub const fn last_received_size(&self) -> usize {
+        self.last_received_size
+    }
+
+    /// Returns the size of the last message sent to the stream, including its message id.
+    ///
+    /// This is the size of the frame the message was encoded to, before `snappy` compression.
+    #[inline]
+    pub const fn last_sent_size(&self) -> usize {
+        self.last_sent_size
+    }
+
     /// Returns the underlying stream.
     #[inline]
     pub const fn inner(&self) -> &S {
@@ -225,9 +245,9 @@ where
         &mut self,
         item: EthBroadcastMessage<N>,
     ) -> Result<(), EthStreamError> {
-        self.inner.start_send_unpin(Bytes::from(alloy_rlp::encode(
-            ProtocolBroadcastMessage::from(item),
-        )))?;
+        let bytes = Bytes::from(alloy_rlp::encode(ProtocolBroadcastMessage::from(item)));
+        self.last_sent_size = bytes.len();
+        self.inner.start_send_unpin(bytes)?;
 
         Ok(())
     }
@@ -238,6 +258,7 @@ where
         msg.id.encode(&mut bytes);
         bytes.extend_from_slice(&msg.payload);
 
+        self.last_sent_size = bytes.len();
         self.inner.start_send_unpin(bytes.into())?;
         Ok(())
     }
@@ -256,7 +277,10 @@ where
         let res = ready!(this.inner.poll_next(cx));
 
         match res {
-            Some(Ok(bytes)) => Poll::Ready(Some(this.eth.decode_message(bytes))),
+            Some(Ok(bytes)) => {
+                *this.last_received_size = bytes.len();
+                Poll::Ready(Some(this.eth.decode_message(bytes)))
+            }
             Some(Err(err)) => Poll::Ready(Some(Err(err.into()))),
             None => Poll::Ready(None),
         }
@@ -287,9 +311,10 @@ where
             return Err(EthStreamError::EthHandshakeError(EthHandshakeError::StatusNotInHandshake))
         }
 
-        self.project()
-            .inner
-            .start_send(Bytes::from(alloy_rlp::encode(ProtocolMessage::from(item))))?;
+        let this = self.project();
+        let bytes = Bytes::from(alloy_rlp::encode(ProtocolMessage::from(item)));
+        *this.last_sent_size = bytes.len();
+        this.inner.start_send(bytes)?;
 
         Ok(())
     }
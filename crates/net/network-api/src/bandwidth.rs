@@ -0,0 +1,62 @@
+//! Types for the accounting of the traffic exchanged with connected peers by message type.
+
+use reth_eth_wire_types::EthMessageID;
+use std::collections::BTreeMap;
+
+/// Returns the name of the message type of an `eth` message id, e.g. `BlockBodies`.
+///
+/// All message ids that are not part of the `eth` protocol are named `Other`.
+pub const fn message_type_name(id: EthMessageID) -> &'static str {
+    match id {
+        EthMessageID::Status => "Status",
+        EthMessageID::NewBlockHashes => "NewBlockHashes",
+        EthMessageID::Transactions => "Transactions",
+        EthMessageID::GetBlockHeaders => "GetBlockHeaders",
+        EthMessageID::BlockHeaders => "BlockHeaders",
+        EthMessageID::GetBlockBodies => "GetBlockBodies",
+        EthMessageID::BlockBodies => "BlockBodies",
+        EthMessageID::NewBlock => "NewBlock",
+        EthMessageID::NewPooledTransactionHashes => "NewPooledTransactionHashes",
+        EthMessageID::GetPooledTransactions => "GetPooledTransactions",
+        EthMessageID::PooledTransactions => "PooledTransactions",
+        EthMessageID::GetNodeData => "GetNodeData",
+        EthMessageID::NodeData => "NodeData",
+        EthMessageID::GetReceipts => "GetReceipts",
+        EthMessageID::Receipts => "Receipts",
+        EthMessageID::BlockRangeUpdate => "BlockRangeUpdate",
+        EthMessageID::Other(_) => "Other",
+    }
+}
+
+/// The number of messages of a message type and their total size.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MessageTraffic {
+    /// The number of messages.
+    pub messages: u64,
+    /// The total size of the messages in bytes.
+    pub bytes: u64,
+}
+
+/// The traffic exchanged with a peer since its session was established, by message type.
+///
+/// The size of a message is the size of its message id and its RLP encoded payload, before
+/// `snappy` compression.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PeerBandwidth {
+    /// The messages received from the peer, by [`message_type_name`].
+    pub ingress: BTreeMap<&'static str, MessageTraffic>,
+    /// The messages sent to the peer, by [`message_type_name`].
+    pub egress: BTreeMap<&'static str, MessageTraffic>,
+}
+
+impl PeerBandwidth {
+    /// Returns the total size of the messages received from the peer in bytes.
+    pub fn ingress_bytes(&self) -> u64 {
+        self.ingress.values().map(|traffic| traffic.bytes).sum()
+    }
+
+    /// Returns the total size of the messages sent to the peer in bytes.
+    pub fn egress_bytes(&self) -> u64 {
+        self.egress.values().map(|traffic| traffic.bytes).sum()
+    }
+}
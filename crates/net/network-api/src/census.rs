@@ -0,0 +1,54 @@
+//! Helpers for aggregating connected peers by client, eth version and fork id.
+
+use alloy_primitives::hex;
+use reth_ethereum_forks::ForkId;
+
+/// Names of the clients that are counted separately, other clients are counted as
+/// [`OTHER_CLIENT`].
+pub const KNOWN_CLIENTS: &[&str] =
+    &["Geth", "Nethermind", "erigon", "besu", "reth", "EthereumJS", "Nimbus", "bor"];
+
+/// Name that clients not in [`KNOWN_CLIENTS`] are counted as.
+pub const OTHER_CLIENT: &str = "other";
+
+/// Returns the name of the client from the client version a peer sent in its `Hello` message.
+///
+/// For example `Geth` for `Geth/v1.14.11-stable/linux-amd64/go1.23.1`. The client version is
+/// chosen by the peer, so names are bucketed into [`KNOWN_CLIENTS`], ignoring case, and
+/// [`OTHER_CLIENT`].
+pub fn client_name(client_version: &str) -> &'static str {
+    let name = client_version.split('/').next().unwrap_or(client_version);
+    KNOWN_CLIENTS
+        .iter()
+        .find(|known| known.eq_ignore_ascii_case(name))
+        .copied()
+        .unwrap_or(OTHER_CLIENT)
+}
+
+/// Returns the label of a [`ForkId`] in the form of `<hash>/<next>`.
+///
+/// For example `0x9f3d2254/0` for a peer on the latest mainnet fork.
+pub fn fork_id_label(fork_id: ForkId) -> String {
+    format!("{}/{}", hex::encode_prefixed(fork_id.hash.0), fork_id.next)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_ethereum_forks::ForkHash;
+
+    #[test]
+    fn parse_client_name() {
+        assert_eq!(client_name("Geth/v1.14.11-stable/linux-amd64/go1.23.1"), "Geth");
+        assert_eq!(client_name("reth/v1.1.0-1a2b3c4/x86_64-unknown-linux-gnu"), "reth");
+        assert_eq!(client_name("nethermind"), "Nethermind");
+        assert_eq!(client_name("Gethx/v1.0.0"), OTHER_CLIENT);
+        assert_eq!(client_name(""), OTHER_CLIENT);
+    }
+
+    #[test]
+    fn format_fork_id_label() {
+        let fork_id = ForkId { hash: ForkHash([0x9f, 0x3d, 0x22, 0x54]), next: 1_746_612_311 };
+        assert_eq!(fork_id_label(fork_id), "0x9f3d2254/1746612311");
+    }
+}
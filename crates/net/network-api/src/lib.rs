@@ -13,6 +13,8 @@
 )]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
+pub mod bandwidth;
+pub mod census;
 pub mod downloaders;
 /// Network Error
 pub mod error;
@@ -24,6 +26,7 @@ pub mod test_utils;
 use test_utils::PeersHandleProvider;
 
 pub use alloy_rpc_types_admin::EthProtocolInfo;
+pub use bandwidth::PeerBandwidth;
 pub use reth_network_p2p::{BlockClient, HeadersClient};
 pub use reth_network_types::{PeerKind, Reputation, ReputationChangeKind};
 
@@ -250,6 +253,8 @@ pub struct PeerInfo {
     pub session_established: Instant,
     /// The peer's connection kind
     pub kind: PeerKind,
+    /// The traffic exchanged with the peer since the session was established.
+    pub bandwidth: PeerBandwidth,
 }
 
 /// The direction of the connection.
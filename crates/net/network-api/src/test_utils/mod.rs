@@ -1,5 +1,7 @@
 //! API for integration testing network components.
 
 pub mod peers_manager;
+pub mod scripted;
 
 pub use peers_manager::{PeerCommand, PeersHandle, PeersHandleProvider};
+pub use scripted::{ScriptedNetwork, ScriptedNetworkAction};
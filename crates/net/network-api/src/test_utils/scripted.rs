@@ -0,0 +1,363 @@
+//! A network handle with programmable state.
+//!
+//! Unlike [`NoopNetwork`](crate::noop::NoopNetwork), the [`ScriptedNetwork`] can be driven by a
+//! test: connected peers and sync status can be changed at any time, [`NetworkEvent`]s can be
+//! emitted to all listeners, and all interactions of the component under test with the network are
+//! recorded so they can be asserted on.
+
+use core::fmt;
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    sync::Arc,
+};
+
+use crate::{
+    events::{NetworkPeersEvents, PeerEventStream},
+    test_utils::{PeersHandle, PeersHandleProvider},
+    BlockDownloaderProvider, DiscoveryEvent, NetworkError, NetworkEvent,
+    NetworkEventListenerProvider, NetworkInfo, NetworkStatus, PeerId, PeerInfo, PeerRequest, Peers,
+    PeersInfo,
+};
+use alloy_rpc_types_admin::EthProtocolInfo;
+use enr::{secp256k1::SecretKey, Enr};
+use parking_lot::Mutex;
+use reth_eth_wire_types::{
+    BlockRangeUpdate, DisconnectReason, EthNetworkPrimitives, NetworkPrimitives, ProtocolVersion,
+};
+use reth_ethereum_forks::Head;
+use reth_network_p2p::{
+    sync::{NetworkSyncUpdater, SyncState},
+    NoopFullBlockClient,
+};
+use reth_network_peers::NodeRecord;
+use reth_network_types::{PeerKind, Reputation, ReputationChangeKind};
+use reth_tokio_util::{EventSender, EventStream};
+use tokio::sync::{mpsc, oneshot};
+use tokio_stream::wrappers::UnboundedReceiverStream;
+
+/// Default capacity of the event channel of the [`ScriptedNetwork`].
+const DEFAULT_EVENT_CHANNEL_SIZE: usize = 256;
+
+/// An interaction of a component with the [`ScriptedNetwork`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScriptedNetworkAction {
+    /// A peer was added to the peer set.
+    AddPeer(PeerId, PeerKind, SocketAddr),
+    /// A peer was removed from the peer set.
+    RemovePeer(PeerId, PeerKind),
+    /// A connection to a peer was requested.
+    ConnectPeer(PeerId, PeerKind, SocketAddr),
+    /// A peer was disconnected, with an optional reason.
+    DisconnectPeer(PeerId, Option<DisconnectReason>),
+    /// A peer was marked as trusted.
+    AddTrustedPeerId(PeerId),
+    /// The reputation of a peer was changed.
+    ReputationChange(PeerId, ReputationChangeKind),
+    /// The sync state was updated.
+    UpdateSyncState(SyncState),
+    /// The status of the node was updated.
+    UpdateStatus(Head),
+    /// The advertised block range was updated.
+    UpdateBlockRange(BlockRangeUpdate),
+}
+
+/// The programmable state of a [`ScriptedNetwork`].
+#[derive(Debug, Default)]
+struct ScriptedNetworkState {
+    /// Whether the network reports that it is syncing.
+    syncing: bool,
+    /// Whether the network reports that it is initially syncing.
+    initially_syncing: bool,
+    /// The peers the network reports as connected.
+    peers: Vec<PeerInfo>,
+    /// Reputations reported by the network.
+    reputations: Vec<(PeerId, Reputation)>,
+    /// All recorded interactions.
+    actions: Vec<ScriptedNetworkAction>,
+}
+
+/// A network implementation with programmable state that records all interactions.
+///
+/// Clones share the same state, so a test can keep a handle to the network while the component
+/// under test owns another one.
+///
+/// Intended for testing purposes.
+#[derive(Clone)]
+pub struct ScriptedNetwork<Net: NetworkPrimitives = EthNetworkPrimitives> {
+    chain_id: u64,
+    local_addr: SocketAddr,
+    peers_handle: PeersHandle,
+    state: Arc<Mutex<ScriptedNetworkState>>,
+    events: EventSender<NetworkEvent<PeerRequest<Net>>>,
+}
+
+impl<Net: NetworkPrimitives> ScriptedNetwork<Net> {
+    /// Creates a new [`ScriptedNetwork`] with no connected peers that is not syncing.
+    pub fn new() -> Self {
+        let (tx, _) = mpsc::unbounded_channel();
+
+        Self {
+            chain_id: 1, // mainnet
+            local_addr: (IpAddr::from(Ipv4Addr::UNSPECIFIED), 30303).into(),
+            peers_handle: PeersHandle::new(tx),
+            state: Default::default(),
+            events: EventSender::new(DEFAULT_EVENT_CHANNEL_SIZE),
+        }
+    }
+
+    /// Sets the chain id reported by the network.
+    pub const fn with_chain_id(mut self, chain_id: u64) -> Self {
+        self.chain_id = chain_id;
+        self
+    }
+
+    /// Sets the local address reported by the network.
+    pub const fn with_local_addr(mut self, local_addr: SocketAddr) -> Self {
+        self.local_addr = local_addr;
+        self
+    }
+
+    /// Sets whether the network reports that it is syncing.
+    pub fn set_syncing(&self, syncing: bool) {
+        self.state.lock().syncing = syncing;
+    }
+
+    /// Sets whether the network reports that it is initially syncing.
+    pub fn set_initially_syncing(&self, initially_syncing: bool) {
+        self.state.lock().initially_syncing = initially_syncing;
+    }
+
+    /// Adds a peer that the network reports as connected.
+    pub fn add_connected_peer(&self, peer: PeerInfo) {
+        let mut state = self.state.lock();
+        state.peers.retain(|p| p.remote_id != peer.remote_id);
+        state.peers.push(peer);
+    }
+
+    /// Removes a peer that the network reports as connected.
+    pub fn remove_connected_peer(&self, peer_id: &PeerId) {
+        self.state.lock().peers.retain(|p| &p.remote_id != peer_id);
+    }
+
+    /// Sets the reputation the network reports for the given peer.
+    pub fn set_reputation(&self, peer_id: PeerId, reputation: Reputation) {
+        let mut state = self.state.lock();
+        state.reputations.retain(|(id, _)| id != &peer_id);
+        state.reputations.push((peer_id, reputation));
+    }
+
+    /// Emits the given event to all network event listeners.
+    pub fn emit(&self, event: NetworkEvent<PeerRequest<Net>>) {
+        self.events.notify(event);
+    }
+
+    /// Returns all interactions with the network in the order they happened.
+    pub fn actions(&self) -> Vec<ScriptedNetworkAction> {
+        self.state.lock().actions.clone()
+    }
+
+    /// Returns all interactions with the network and clears the recorded interactions.
+    pub fn take_actions(&self) -> Vec<ScriptedNetworkAction> {
+        core::mem::take(&mut self.state.lock().actions)
+    }
+
+    /// Returns all recorded reputation changes.
+    pub fn reputation_changes(&self) -> Vec<(PeerId, ReputationChangeKind)> {
+        self.state
+            .lock()
+            .actions
+            .iter()
+            .filter_map(|action| match action {
+                ScriptedNetworkAction::ReputationChange(peer_id, kind) => Some((*peer_id, *kind)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn record(&self, action: ScriptedNetworkAction) {
+        self.state.lock().actions.push(action);
+    }
+
+    fn peers_matching(&self, f: impl Fn(&PeerInfo) -> bool) -> Vec<PeerInfo> {
+        self.state.lock().peers.iter().filter(|peer| f(peer)).cloned().collect()
+    }
+}
+
+impl<Net: NetworkPrimitives> Default for ScriptedNetwork<Net> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Net: NetworkPrimitives> fmt::Debug for ScriptedNetwork<Net> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScriptedNetwork")
+            .field("chain_id", &self.chain_id)
+            .field("local_addr", &self.local_addr)
+            .field("state", &self.state)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<Net: NetworkPrimitives> NetworkInfo for ScriptedNetwork<Net> {
+    fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    async fn network_status(&self) -> Result<NetworkStatus, NetworkError> {
+        #[expect(deprecated)]
+        Ok(NetworkStatus {
+            client_version: "reth-test".to_string(),
+            protocol_version: ProtocolVersion::V5 as u64,
+            eth_protocol_info: EthProtocolInfo {
+                network: self.chain_id,
+                difficulty: None,
+                genesis: Default::default(),
+                config: Default::default(),
+                head: Default::default(),
+            },
+            capabilities: vec![],
+        })
+    }
+
+    fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    fn is_syncing(&self) -> bool {
+        self.state.lock().syncing
+    }
+
+    fn is_initially_syncing(&self) -> bool {
+        self.state.lock().initially_syncing
+    }
+}
+
+impl<Net: NetworkPrimitives> PeersInfo for ScriptedNetwork<Net> {
+    fn num_connected_peers(&self) -> usize {
+        self.state.lock().peers.len()
+    }
+
+    fn local_node_record(&self) -> NodeRecord {
+        NodeRecord::new(self.local_addr(), PeerId::ZERO)
+    }
+
+    fn local_enr(&self) -> Enr<SecretKey> {
+        let sk = SecretKey::from_slice(&[0xcd; 32]).unwrap();
+        Enr::builder().build(&sk).unwrap()
+    }
+}
+
+impl<Net: NetworkPrimitives> Peers for ScriptedNetwork<Net> {
+    fn add_trusted_peer_id(&self, peer: PeerId) {
+        self.record(ScriptedNetworkAction::AddTrustedPeerId(peer));
+    }
+
+    fn add_peer_kind(
+        &self,
+        peer: PeerId,
+        kind: PeerKind,
+        tcp_addr: SocketAddr,
+        _udp_addr: Option<SocketAddr>,
+    ) {
+        self.record(ScriptedNetworkAction::AddPeer(peer, kind, tcp_addr));
+    }
+
+    async fn get_peers_by_kind(&self, kind: PeerKind) -> Result<Vec<PeerInfo>, NetworkError> {
+        Ok(self.peers_matching(|peer| peer.kind == kind))
+    }
+
+    async fn get_all_peers(&self) -> Result<Vec<PeerInfo>, NetworkError> {
+        Ok(self.peers_matching(|_| true))
+    }
+
+    async fn get_peer_by_id(&self, peer_id: PeerId) -> Result<Option<PeerInfo>, NetworkError> {
+        Ok(self.peers_matching(|peer| peer.remote_id == peer_id).pop())
+    }
+
+    async fn get_peers_by_id(&self, peer_ids: Vec<PeerId>) -> Result<Vec<PeerInfo>, NetworkError> {
+        Ok(self.peers_matching(|peer| peer_ids.contains(&peer.remote_id)))
+    }
+
+    fn remove_peer(&self, peer: PeerId, kind: PeerKind) {
+        self.record(ScriptedNetworkAction::RemovePeer(peer, kind));
+    }
+
+    fn disconnect_peer(&self, peer: PeerId) {
+        self.record(ScriptedNetworkAction::DisconnectPeer(peer, None));
+    }
+
+    fn disconnect_peer_with_reason(&self, peer: PeerId, reason: DisconnectReason) {
+        self.record(ScriptedNetworkAction::DisconnectPeer(peer, Some(reason)));
+    }
+
+    fn connect_peer_kind(
+        &self,
+        peer: PeerId,
+        kind: PeerKind,
+        tcp_addr: SocketAddr,
+        _udp_addr: Option<SocketAddr>,
+    ) {
+        self.record(ScriptedNetworkAction::ConnectPeer(peer, kind, tcp_addr));
+    }
+
+    fn reputation_change(&self, peer_id: PeerId, kind: ReputationChangeKind) {
+        self.record(ScriptedNetworkAction::ReputationChange(peer_id, kind));
+    }
+
+    async fn reputation_by_id(&self, peer_id: PeerId) -> Result<Option<Reputation>, NetworkError> {
+        Ok(self
+            .state
+            .lock()
+            .reputations
+            .iter()
+            .find_map(|(id, reputation)| (id == &peer_id).then_some(*reputation)))
+    }
+}
+
+impl<Net: NetworkPrimitives> BlockDownloaderProvider for ScriptedNetwork<Net> {
+    type Client = NoopFullBlockClient<Net>;
+
+    async fn fetch_client(&self) -> Result<Self::Client, oneshot::error::RecvError> {
+        Ok(NoopFullBlockClient::<Net>::default())
+    }
+}
+
+impl<Net: NetworkPrimitives> NetworkSyncUpdater for ScriptedNetwork<Net> {
+    fn update_sync_state(&self, state: SyncState) {
+        self.record(ScriptedNetworkAction::UpdateSyncState(state));
+    }
+
+    fn update_status(&self, head: Head) {
+        self.record(ScriptedNetworkAction::UpdateStatus(head));
+    }
+
+    fn update_block_range(&self, update: BlockRangeUpdate) {
+        self.record(ScriptedNetworkAction::UpdateBlockRange(update));
+    }
+}
+
+impl<Net: NetworkPrimitives> NetworkEventListenerProvider for ScriptedNetwork<Net> {
+    type Primitives = Net;
+
+    fn event_listener(&self) -> EventStream<NetworkEvent<PeerRequest<Self::Primitives>>> {
+        self.events.new_listener()
+    }
+
+    fn discovery_listener(&self) -> UnboundedReceiverStream<DiscoveryEvent> {
+        let (_, rx) = mpsc::unbounded_channel();
+        UnboundedReceiverStream::new(rx)
+    }
+}
+
+impl<Net: NetworkPrimitives> NetworkPeersEvents for ScriptedNetwork<Net> {
+    fn peer_events(&self) -> PeerEventStream {
+        PeerEventStream::new(self.events.new_listener())
+    }
+}
+
+impl<Net: NetworkPrimitives> PeersHandleProvider for ScriptedNetwork<Net> {
+    fn peers_handle(&self) -> &PeersHandle {
+        &self.peers_handle
+    }
+}
@@ -11,7 +11,7 @@ use reth_net_banlist::{BanList, IpFilter};
 use reth_network_peers::{NodeRecord, TrustedPeer};
 use tracing::info;
 
-use crate::{BackoffKind, ReputationChangeWeights};
+use crate::{BackoffKind, PersistedPeerReputation, ReputationChangeWeights};
 
 /// Maximum number of available slots for outbound sessions.
 pub const DEFAULT_MAX_COUNT_PEERS_OUTBOUND: u32 = 100;
@@ -172,6 +172,12 @@ pub struct PeersConfig {
     /// IPs within the specified CIDR ranges will be allowed.
     #[cfg_attr(feature = "serde", serde(skip))]
     pub ip_filter: IpFilter,
+    /// Peer reputations and bans restored from a previous run.
+    ///
+    /// Reputations are applied to known peers and bans that haven't expired yet are added to the
+    /// ban list.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub persisted_reputations: Vec<PersistedPeerReputation>,
 }
 
 impl Default for PeersConfig {
@@ -191,6 +197,7 @@ impl Default for PeersConfig {
             max_backoff_count: 5,
             incoming_ip_throttle_duration: INBOUND_IP_THROTTLE_DURATION,
             ip_filter: IpFilter::default(),
+            persisted_reputations: Default::default(),
         }
     }
 }
@@ -308,6 +315,35 @@ impl PeersConfig {
         Ok(self.with_basic_nodes(nodes))
     }
 
+    /// Peer reputations and bans restored from a previous run.
+    pub fn with_persisted_reputations(
+        mut self,
+        reputations: impl IntoIterator<Item = PersistedPeerReputation>,
+    ) -> Self {
+        self.persisted_reputations.extend(reputations);
+        self
+    }
+
+    /// Read persisted peer reputations and bans from file. Ignored if None.
+    ///
+    /// Entries are added to already configured reputations, so this can be used to import
+    /// additional ban lists.
+    #[cfg(feature = "serde")]
+    pub fn with_persisted_reputations_from_file(
+        self,
+        optional_file: Option<impl AsRef<Path>>,
+    ) -> Result<Self, io::Error> {
+        let Some(file_path) = optional_file else { return Ok(self) };
+        let reader = match std::fs::File::open(file_path.as_ref()) {
+            Ok(file) => io::BufReader::new(file),
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(self),
+            Err(e) => Err(e)?,
+        };
+        info!(target: "net::peers", file = %file_path.as_ref().display(), "Loading saved peer reputations");
+        let reputations: Vec<PersistedPeerReputation> = serde_json::from_reader(reader)?;
+        Ok(self.with_persisted_reputations(reputations))
+    }
+
     /// Configure the IP filter for restricting network connections to specific IP ranges.
     pub fn with_ip_filter(mut self, ip_filter: IpFilter) -> Self {
         self.ip_filter = ip_filter;
@@ -5,7 +5,10 @@ pub mod reputation;
 pub mod state;
 
 pub use config::{ConnectionsConfig, PeersConfig};
-pub use reputation::{Reputation, ReputationChange, ReputationChangeKind, ReputationChangeWeights};
+pub use reputation::{
+    PersistedPeerReputation, Reputation, ReputationChange, ReputationChangeKind,
+    ReputationChangeWeights, ReputationPolicy,
+};
 
 use alloy_eip2124::ForkId;
 use tracing::trace;
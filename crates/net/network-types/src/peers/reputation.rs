@@ -1,5 +1,8 @@
 //! Peer reputation management
 
+use core::fmt;
+use reth_network_peers::PeerId;
+
 /// The default reputation of a peer
 pub const DEFAULT_REPUTATION: Reputation = 0;
 
@@ -186,6 +189,34 @@ impl Default for ReputationChangeWeights {
     }
 }
 
+/// Determines how reported [`ReputationChangeKind`]s affect the reputation of a peer.
+///
+/// The peers manager consults the configured policy for every reputation change that is not a
+/// [`ReputationChangeKind::Reset`], which allows for custom scoring, e.g. penalizing certain peers
+/// more aggressively based on their current reputation.
+///
+/// The default policy is [`ReputationChangeWeights`].
+pub trait ReputationPolicy: fmt::Debug + Send + Sync + 'static {
+    /// Returns the [`ReputationChange`] to apply to the given peer with the `current` reputation.
+    fn reputation_change(
+        &self,
+        peer_id: &PeerId,
+        current: Reputation,
+        kind: ReputationChangeKind,
+    ) -> ReputationChange;
+}
+
+impl ReputationPolicy for ReputationChangeWeights {
+    fn reputation_change(
+        &self,
+        _peer_id: &PeerId,
+        _current: Reputation,
+        kind: ReputationChangeKind,
+    ) -> ReputationChange {
+        self.change(kind)
+    }
+}
+
 /// Represents a change in a peer's reputation.
 #[derive(Debug, Copy, Clone, Default)]
 pub struct ReputationChange(Reputation);
@@ -224,3 +255,34 @@ pub enum ReputationChangeOutcome {
     /// Unban the peer
     Unban,
 }
+
+/// The reputation of a peer as it is persisted across restarts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PersistedPeerReputation {
+    /// The id of the peer.
+    pub peer_id: PeerId,
+    /// The reputation of the peer.
+    pub reputation: Reputation,
+    /// Whether the peer is banned.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub banned: bool,
+    /// Unix timestamp in seconds until which the peer is banned.
+    ///
+    /// If the peer is banned and this is `None`, the peer is banned indefinitely.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub banned_until: Option<u64>,
+}
+
+impl PersistedPeerReputation {
+    /// Returns `true` if the peer is still banned at the given unix timestamp in seconds.
+    pub const fn is_banned_at(&self, timestamp: u64) -> bool {
+        if !self.banned {
+            return false
+        }
+        match self.banned_until {
+            Some(until) => until > timestamp,
+            None => true,
+        }
+    }
+}
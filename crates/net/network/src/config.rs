@@ -7,6 +7,8 @@ use crate::{
     NetworkHandle, NetworkManager,
 };
 use alloy_eips::BlockNumHash;
+use alloy_primitives::Bytes;
+use alloy_rlp::Encodable;
 use reth_chainspec::{ChainSpecProvider, EthChainSpec, Hardforks};
 use reth_discv4::{Discv4Config, Discv4ConfigBuilder, NatResolver, DEFAULT_DISCOVERY_ADDRESS};
 use reth_discv5::NetworkStackId;
@@ -18,7 +20,7 @@ use reth_eth_wire::{
 };
 use reth_ethereum_forks::{ForkFilter, Head};
 use reth_network_peers::{mainnet_nodes, pk2id, sepolia_nodes, PeerId, TrustedPeer};
-use reth_network_types::{PeersConfig, SessionsConfig};
+use reth_network_types::{PeersConfig, ReputationPolicy, SessionsConfig};
 use reth_storage_api::{noop::NoopProvider, BlockNumReader, BlockReader, HeaderProvider};
 use reth_tasks::{TaskSpawner, TokioTaskExecutor};
 use secp256k1::SECP256K1;
@@ -60,6 +62,10 @@ pub struct NetworkConfig<C, N: NetworkPrimitives = EthNetworkPrimitives> {
     pub listener_addr: SocketAddr,
     /// How to instantiate peer manager.
     pub peers_config: PeersConfig,
+    /// Custom [`ReputationPolicy`] for the peer manager.
+    ///
+    /// If `None`, the reputation weights of the [`PeersConfig`] are used.
+    pub reputation_policy: Option<Arc<dyn ReputationPolicy>>,
     /// How to configure the [`SessionManager`](crate::session::SessionManager).
     pub sessions_config: SessionsConfig,
     /// The chain id
@@ -97,6 +103,11 @@ pub struct NetworkConfig<C, N: NetworkPrimitives = EthNetworkPrimitives> {
     /// List of block number-hash pairs to check for required blocks.
     /// If non-empty, peers that don't have these blocks will be filtered out.
     pub required_block_hashes: Vec<BlockNumHash>,
+    /// Additional kv-pairs of the local ENR, with rlp encoded values.
+    ///
+    /// These are already added to the discovery configs, and are included in the ENR returned by
+    /// the [`NetworkHandle`].
+    pub enr_kv_pairs: Vec<(&'static [u8], Bytes)>,
 }
 
 // === impl NetworkConfig ===
@@ -201,6 +212,8 @@ pub struct NetworkConfigBuilder<N: NetworkPrimitives = EthNetworkPrimitives> {
     listener_addr: Option<SocketAddr>,
     /// How to instantiate peer manager.
     peers_config: Option<PeersConfig>,
+    /// Custom reputation policy for the peer manager.
+    reputation_policy: Option<Arc<dyn ReputationPolicy>>,
     /// How to configure the sessions manager
     sessions_config: Option<SessionsConfig>,
     /// The default mode of the network.
@@ -228,6 +241,8 @@ pub struct NetworkConfigBuilder<N: NetworkPrimitives = EthNetworkPrimitives> {
     required_block_hashes: Vec<BlockNumHash>,
     /// Optional network id
     network_id: Option<u64>,
+    /// Additional kv-pairs of the local ENR, with rlp encoded values.
+    enr_kv_pairs: Vec<(&'static [u8], Bytes)>,
 }
 
 impl NetworkConfigBuilder<EthNetworkPrimitives> {
@@ -257,6 +272,7 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
             discovery_addr: None,
             listener_addr: None,
             peers_config: None,
+            reputation_policy: None,
             sessions_config: None,
             network_mode: Default::default(),
             executor: None,
@@ -270,6 +286,7 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
             handshake: Arc::new(EthHandshake::default()),
             required_block_hashes: Vec::new(),
             network_id: None,
+            enr_kv_pairs: Vec::new(),
         }
     }
 
@@ -339,6 +356,13 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
         self
     }
 
+    /// Sets a custom [`ReputationPolicy`] that determines how reputation changes of peers are
+    /// weighed.
+    pub fn reputation_policy<P: ReputationPolicy>(mut self, policy: P) -> Self {
+        self.reputation_policy = Some(Arc::new(policy));
+        self
+    }
+
     /// Sets the executor to use for spawning tasks.
     ///
     /// If `None`, then [`tokio::spawn`] is used for spawning tasks.
@@ -596,6 +620,22 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
         self
     }
 
+    /// Adds an additional kv-pair to the local ENR, e.g. to advertise features to peers of
+    /// networks that use ENR based feature discovery.
+    ///
+    /// The pair is advertised by both discv4, as EIP-868 entry, and discv5.
+    pub fn add_enr_kv_pair(self, key: &'static [u8], value: impl Encodable) -> Self {
+        self.add_enr_rlp_pair(key, alloy_rlp::encode(value).into())
+    }
+
+    /// Adds an additional kv-pair with an rlp encoded value to the local ENR.
+    ///
+    /// See also [`Self::add_enr_kv_pair`].
+    pub fn add_enr_rlp_pair(mut self, key: &'static [u8], rlp: Bytes) -> Self {
+        self.enr_kv_pairs.push((key, rlp));
+        self
+    }
+
     /// Consumes the type and creates the actual [`NetworkConfig`]
     /// for the given client type that can interact with the chain.
     ///
@@ -611,12 +651,13 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
         let Self {
             secret_key,
             mut dns_discovery_config,
-            discovery_v4_builder,
+            mut discovery_v4_builder,
             mut discovery_v5_builder,
             boot_nodes,
             discovery_addr,
             listener_addr,
             peers_config,
+            reputation_policy,
             sessions_config,
             network_mode,
             executor,
@@ -630,6 +671,7 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
             handshake,
             required_block_hashes,
             network_id,
+            enr_kv_pairs,
         } = self;
 
         let head = head.unwrap_or_else(|| Head {
@@ -646,9 +688,19 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
                 builder = builder.fork(network_stack_id, fork_id)
             }
 
+            for (key, rlp) in &enr_kv_pairs {
+                builder = builder.add_enr_kv_pair(*key, rlp.clone());
+            }
+
             builder
         });
 
+        if let Some(builder) = discovery_v4_builder.as_mut() {
+            for (key, rlp) in &enr_kv_pairs {
+                builder.add_eip868_rlp_pair(*key, rlp.0.clone());
+            }
+        }
+
         let listener_addr = listener_addr.unwrap_or(DEFAULT_DISCOVERY_ADDRESS);
 
         let mut hello_message =
@@ -687,6 +739,7 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
             discovery_v4_addr: discovery_addr.unwrap_or(DEFAULT_DISCOVERY_ADDRESS),
             listener_addr,
             peers_config: peers_config.unwrap_or_default(),
+            reputation_policy,
             sessions_config: sessions_config.unwrap_or_default(),
             chain_id,
             block_import: block_import.unwrap_or_else(|| Box::<ProofOfStakeBlockImport>::default()),
@@ -701,6 +754,7 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
             nat,
             handshake,
             required_block_hashes,
+            enr_kv_pairs,
         }
     }
 }
@@ -844,4 +898,24 @@ mod tests {
 
         assert_eq!(advertised_fork_id, fork_id);
     }
+
+    #[test]
+    fn test_enr_kv_pairs() {
+        let config = builder()
+            .discovery_v5(reth_discv5::Config::builder((Ipv4Addr::LOCALHOST, 30303).into()))
+            .add_enr_kv_pair(b"feature", 7u64)
+            .build(NoopProvider::default());
+
+        let discv4_config = config.discovery_v4_config.expect("should build config");
+        assert_eq!(
+            discv4_config.additional_eip868_rlp_pairs.get(b"feature".as_slice()),
+            Some(&alloy_rlp::encode(7u64).into())
+        );
+
+        let (local_enr, _, _, _) = build_local_enr(
+            &config.secret_key,
+            &config.discovery_v5_config.expect("should build config"),
+        );
+        assert_eq!(local_enr.get_decodable::<u64>(b"feature"), Some(Ok(7)));
+    }
 }
@@ -151,7 +151,8 @@ impl Discovery {
         self.discovery_listeners.retain_mut(|listener| listener.send(event.clone()).is_ok());
     }
 
-    /// Updates the `eth:ForkId` field in discv4/discv5.
+    /// Updates the `eth:ForkId` field in discv4/discv5, and scopes the discv5 topics to the new
+    /// fork.
     pub(crate) fn update_fork_id(&self, fork_id: ForkId) {
         if let Some(discv4) = &self.discv4 {
             // use forward-compatible forkid entry
@@ -159,7 +160,8 @@ impl Discovery {
         }
         if let Some(discv5) = &self.discv5 {
             discv5
-                .encode_and_set_eip868_in_local_enr(b"eth".to_vec(), EnrForkIdEntry::from(fork_id))
+                .encode_and_set_eip868_in_local_enr(b"eth".to_vec(), EnrForkIdEntry::from(fork_id));
+            discv5.update_topics_fork(fork_id)
         }
     }
 
@@ -24,7 +24,7 @@ use crate::{
     import::{BlockImport, BlockImportEvent, BlockImportOutcome, BlockValidation, NewBlockEvent},
     listener::ConnectionListener,
     message::{NewBlockMessage, PeerMessage},
-    metrics::{DisconnectMetrics, NetworkMetrics, NETWORK_POOL_TRANSACTIONS_SCOPE},
+    metrics::{DisconnectMetrics, NetworkMetrics, PeerCensus, NETWORK_POOL_TRANSACTIONS_SCOPE},
     network::{NetworkHandle, NetworkHandleMessage},
     peers::PeersManager,
     poll_nested_stream_with_budget,
@@ -139,6 +139,8 @@ pub struct NetworkManager<N: NetworkPrimitives = EthNetworkPrimitives> {
     metrics: NetworkMetrics,
     /// Disconnect metrics for the Network
     disconnect_metrics: DisconnectMetrics,
+    /// Connected peers by client, eth version and fork id
+    peer_census: PeerCensus,
 }
 
 impl NetworkManager {
@@ -236,6 +238,7 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             mut discovery_v5_config,
             listener_addr,
             peers_config,
+            reputation_policy,
             sessions_config,
             chain_id,
             block_import,
@@ -252,9 +255,13 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             nat,
             handshake,
             required_block_hashes,
+            enr_kv_pairs,
         } = config;
 
-        let peers_manager = PeersManager::new(peers_config);
+        let mut peers_manager = PeersManager::new(peers_config);
+        if let Some(policy) = reputation_policy {
+            peers_manager = peers_manager.with_reputation_policy(policy);
+        }
         let peers_handle = peers_manager.handle();
 
         let incoming = ConnectionListener::bind(listener_addr).await.map_err(|err| {
@@ -335,6 +342,7 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             discv5,
             event_sender.clone(),
             nat,
+            enr_kv_pairs,
         );
 
         // Spawn required block peer filter if configured
@@ -354,6 +362,7 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             num_active_peers,
             metrics: Default::default(),
             disconnect_metrics: Default::default(),
+            peer_census: Default::default(),
         })
     }
 
@@ -441,6 +450,42 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
         Ok(())
     }
 
+    /// Collect the reputations and bans of all peers from the [`NetworkManager`] and write them to
+    /// the given `persistent_reputations_file`.
+    ///
+    /// The file can be loaded on startup with
+    /// `PeersConfig::with_persisted_reputations_from_file`.
+    #[cfg(feature = "serde")]
+    pub fn write_reputations_to_file(
+        &self,
+        persistent_reputations_file: &Path,
+    ) -> Result<(), FsPathError> {
+        let reputations = self.swarm.state().peers().persisted_reputations();
+        persistent_reputations_file.parent().map(fs::create_dir_all).transpose()?;
+        reth_fs_util::write_json_file(persistent_reputations_file, &reputations)?;
+        Ok(())
+    }
+
+    /// Collect all currently banned peers from the [`NetworkManager`] and write them to the given
+    /// `ban_list_file`.
+    ///
+    /// The written file uses the same format as
+    /// [`write_reputations_to_file`](Self::write_reputations_to_file).
+    #[cfg(feature = "serde")]
+    pub fn write_banned_peers_to_file(&self, ban_list_file: &Path) -> Result<(), FsPathError> {
+        let banned = self
+            .swarm
+            .state()
+            .peers()
+            .persisted_reputations()
+            .into_iter()
+            .filter(|entry| entry.banned)
+            .collect::<Vec<_>>();
+        ban_list_file.parent().map(fs::create_dir_all).transpose()?;
+        reth_fs_util::write_json_file(ban_list_file, &banned)?;
+        Ok(())
+    }
+
     /// Returns a new [`FetchClient`] that can be cloned and shared.
     ///
     /// The [`FetchClient`] is the entrypoint for sending requests to the network.
@@ -809,6 +854,7 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
                 }
 
                 self.update_active_connection_metrics();
+                self.peer_census.on_session_established(peer_id, &client_version, version, &status);
 
                 let peer_kind = self
                     .swarm
@@ -868,6 +914,7 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
                 };
                 self.metrics.closed_sessions.increment(1);
                 self.update_active_connection_metrics();
+                self.peer_census.on_session_closed(&peer_id);
 
                 if let Some(reason) = reason {
                     self.disconnect_metrics.increment(reason);
@@ -1,10 +1,13 @@
 use metrics::Histogram;
-use reth_eth_wire::DisconnectReason;
+use reth_eth_wire::{DisconnectReason, EthVersion, UnifiedStatus};
 use reth_ethereum_primitives::TxType;
 use reth_metrics::{
     metrics::{Counter, Gauge},
     Metrics,
 };
+use reth_network_api::census::{client_name, fork_id_label};
+use reth_network_peers::PeerId;
+use std::collections::HashMap;
 
 /// Scope for monitoring transactions sent from the manager to the tx manager
 pub(crate) const NETWORK_POOL_TRANSACTIONS_SCOPE: &str = "network.pool.transactions";
@@ -89,6 +92,20 @@ pub struct SessionManagerMetrics {
     pub(crate) queued_outgoing_messages: Gauge,
 }
 
+/// Traffic of a message type in one direction, summed over all peers.
+///
+/// Labeled by the message type and the direction, `ingress` or `egress`. The metrics are not
+/// labeled by peer, because peers come and go and every peer would add a new series, the traffic
+/// of every connected peer is returned by `admin_peerStats` instead.
+#[derive(Metrics)]
+#[metrics(scope = "network.bandwidth")]
+pub struct MessageBandwidthMetrics {
+    /// Number of messages sent or received
+    pub(crate) messages: Counter,
+    /// Total size of the messages sent or received in bytes, before compression
+    pub(crate) bytes: Counter,
+}
+
 /// Metrics for the [`TransactionsManager`](crate::transactions::TransactionsManager).
 #[derive(Metrics)]
 #[metrics(scope = "network")]
@@ -410,3 +427,69 @@ impl AnnouncedTxTypesMetrics {
         self.eip7702.record(tx_types_counter.eip7702 as f64);
     }
 }
+
+/// Number of connected peers sharing a value of a census dimension, such as the client name.
+#[derive(Metrics)]
+#[metrics(scope = "network.census")]
+pub struct PeerCensusMetrics {
+    /// Number of connected peers with the labeled dimension value
+    pub(crate) connected_peers: Gauge,
+}
+
+/// Aggregates connected peers by client name, eth version and fork id.
+///
+/// Every combination of dimension and value gets its own labeled [`PeerCensusMetrics`].
+#[derive(Debug, Default)]
+pub struct PeerCensus {
+    /// Census labels of every active session.
+    peers: HashMap<PeerId, [(&'static str, String); 3]>,
+    /// Gauges by `(dimension, value)`.
+    metrics: HashMap<(&'static str, String), PeerCensusMetrics>,
+}
+
+impl PeerCensus {
+    /// Counts a newly established session.
+    pub(crate) fn on_session_established(
+        &mut self,
+        peer_id: PeerId,
+        client_version: &str,
+        version: EthVersion,
+        status: &UnifiedStatus,
+    ) {
+        let labels = [
+            ("client", client_name(client_version).to_string()),
+            ("eth_version", (version as u8).to_string()),
+            ("fork_id", fork_id_label(status.forkid)),
+        ];
+        for label in &labels {
+            self.gauge(label).increment(1.0);
+        }
+        if let Some(previous) = self.peers.insert(peer_id, labels) {
+            for label in &previous {
+                self.gauge(label).decrement(1.0);
+            }
+        }
+    }
+
+    /// Removes a closed session from the census.
+    pub(crate) fn on_session_closed(&mut self, peer_id: &PeerId) {
+        if let Some(labels) = self.peers.remove(peer_id) {
+            for label in &labels {
+                self.gauge(label).decrement(1.0);
+            }
+        }
+    }
+
+    fn gauge(&mut self, (dimension, value): &(&'static str, String)) -> &Gauge {
+        &self
+            .metrics
+            .entry((dimension, value.clone()))
+            .or_insert_with(|| {
+                PeerCensusMetrics::new_with_labels(&[
+                    ("dimension", dimension.to_string()),
+                    ("value", value.clone()),
+                ])
+            })
+            .connected_peers
+    }
+}
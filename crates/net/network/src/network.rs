@@ -2,12 +2,12 @@ use crate::{
     config::NetworkMode, message::PeerMessage, protocol::RlpxSubProtocol,
     swarm::NetworkConnectionState, transactions::TransactionsHandle, FetchClient,
 };
-use alloy_primitives::B256;
+use alloy_primitives::{Bytes, B256};
 use enr::Enr;
 use futures::StreamExt;
 use parking_lot::Mutex;
 use reth_discv4::{Discv4, NatResolver};
-use reth_discv5::Discv5;
+use reth_discv5::{DiscoveredPeer, Discv5, EnrPredicate};
 use reth_eth_wire::{
     BlockRangeUpdate, DisconnectReason, EthNetworkPrimitives, NetworkPrimitives,
     NewPooledTransactionHashes, SharedTransactions,
@@ -66,6 +66,7 @@ impl<N: NetworkPrimitives> NetworkHandle<N> {
         discv5: Option<Discv5>,
         event_sender: EventSender<NetworkEvent<PeerRequest<N>>>,
         nat: Option<NatResolver>,
+        enr_kv_pairs: Vec<(&'static [u8], Bytes)>,
     ) -> Self {
         let inner = NetworkInner {
             num_active_peers,
@@ -83,6 +84,7 @@ impl<N: NetworkPrimitives> NetworkHandle<N> {
             discv5,
             event_sender,
             nat,
+            enr_kv_pairs,
         };
         Self { inner: Arc::new(inner) }
     }
@@ -190,6 +192,17 @@ impl<N: NetworkPrimitives> NetworkHandle<N> {
     pub fn secret_key(&self) -> &SecretKey {
         &self.inner.secret_key
     }
+
+    /// Returns a receiver of the peers discovered by discv5, whose node record matches the given
+    /// predicate, e.g. [`kv_predicate`](reth_discv5::filter::kv_predicate).
+    ///
+    /// Returns `None` if discv5 is disabled.
+    pub fn subscribe_discv5_peers(
+        &self,
+        predicate: EnrPredicate,
+    ) -> Option<mpsc::Receiver<DiscoveredPeer>> {
+        self.inner.discv5.as_ref().map(|discv5| discv5.subscribe_peers(predicate))
+    }
 }
 
 // === API Implementations ===
@@ -286,6 +299,9 @@ impl<N: NetworkPrimitives> PeersInfo for NetworkHandle<N> {
             builder.udp6(local_node_record.udp_port);
             builder.tcp6(local_node_record.tcp_port);
         }
+        for (key, rlp) in &self.inner.enr_kv_pairs {
+            builder.add_value_rlp(*key, rlp.0.clone());
+        }
         builder.build(&self.inner.secret_key).expect("valid enr")
     }
 }
@@ -487,6 +503,8 @@ struct NetworkInner<N: NetworkPrimitives = EthNetworkPrimitives> {
     event_sender: EventSender<NetworkEvent<PeerRequest<N>>>,
     /// The NAT resolver
     nat: Option<NatResolver>,
+    /// Additional kv-pairs of the local ENR, with rlp encoded values.
+    enr_kv_pairs: Vec<(&'static [u8], Bytes)>,
 }
 
 /// Provides access to modify the network's additional protocol handlers.
@@ -20,15 +20,16 @@ use reth_network_types::{
         reputation::{DEFAULT_REPUTATION, MAX_TRUSTED_PEER_REPUTATION_CHANGE},
     },
     ConnectionsConfig, Peer, PeerAddr, PeerConnectionState, PeerKind, PeersConfig,
-    ReputationChangeKind, ReputationChangeOutcome, ReputationChangeWeights,
+    PersistedPeerReputation, ReputationChangeKind, ReputationChangeOutcome, ReputationPolicy,
 };
 use std::{
     collections::{hash_map::Entry, HashMap, HashSet, VecDeque},
     fmt::Display,
     io::{self},
     net::{IpAddr, SocketAddr},
+    sync::Arc,
     task::{Context, Poll},
-    time::Duration,
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 use thiserror::Error;
 use tokio::{
@@ -65,7 +66,7 @@ pub struct PeersManager {
     /// Interval for triggering connections if there are free slots.
     refill_slots_interval: Interval,
     /// How to weigh reputation changes
-    reputation_weights: ReputationChangeWeights,
+    reputation_policy: Arc<dyn ReputationPolicy>,
     /// Tracks current slot stats.
     connection_info: ConnectionInfo,
     /// Tracks unwanted ips/peer ids.
@@ -111,6 +112,7 @@ impl PeersManager {
             max_backoff_count,
             incoming_ip_throttle_duration,
             ip_filter,
+            persisted_reputations,
         } = config;
         let (manager_tx, handle_rx) = mpsc::unbounded_channel();
         let now = Instant::now();
@@ -141,6 +143,9 @@ impl PeersManager {
             });
         }
 
+        let mut ban_list = ban_list;
+        restore_persisted_reputations(&mut peers, &mut ban_list, persisted_reputations);
+
         trace!(target: "net::peers", trusted_peers=?trusted_peer_ids, "Initialized peers manager");
 
         Self {
@@ -153,7 +158,7 @@ impl PeersManager {
             manager_tx,
             handle_rx: UnboundedReceiverStream::new(handle_rx),
             queued_actions: Default::default(),
-            reputation_weights,
+            reputation_policy: Arc::new(reputation_weights),
             refill_slots_interval: tokio::time::interval(refill_slots_interval),
             release_interval: tokio::time::interval_at(now + unban_interval, unban_interval),
             connection_info: ConnectionInfo::new(connection_info),
@@ -170,6 +175,15 @@ impl PeersManager {
         }
     }
 
+    /// Replaces the [`ReputationPolicy`] that determines how reputation changes are weighed.
+    ///
+    /// By default, the configured
+    /// [`ReputationChangeWeights`](reth_network_types::ReputationChangeWeights) are used.
+    pub fn with_reputation_policy(mut self, policy: Arc<dyn ReputationPolicy>) -> Self {
+        self.reputation_policy = policy;
+        self
+    }
+
     /// Returns a new [`PeersHandle`] that can send commands to this type.
     pub(crate) fn handle(&self) -> PeersHandle {
         PeersHandle::new(self.manager_tx.clone())
@@ -193,6 +207,44 @@ impl PeersManager {
         })
     }
 
+    /// Returns the reputations of all peers with a non-default reputation and all banned peers,
+    /// so they can be restored after a restart.
+    pub fn persisted_reputations(&self) -> Vec<PersistedPeerReputation> {
+        let now = std::time::Instant::now();
+        let now_unix = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+
+        let mut reputations = self
+            .peers
+            .iter()
+            .filter(|(_, peer)| peer.reputation != DEFAULT_REPUTATION)
+            .map(|(peer_id, peer)| {
+                (
+                    *peer_id,
+                    PersistedPeerReputation {
+                        peer_id: *peer_id,
+                        reputation: peer.reputation,
+                        banned: false,
+                        banned_until: None,
+                    },
+                )
+            })
+            .collect::<HashMap<_, _>>();
+
+        for (peer_id, until) in self.ban_list.banned_peers() {
+            let entry = reputations.entry(peer_id).or_insert(PersistedPeerReputation {
+                peer_id,
+                reputation: DEFAULT_REPUTATION,
+                banned: false,
+                banned_until: None,
+            });
+            entry.banned = true;
+            entry.banned_until =
+                until.map(|until| (now_unix + until.saturating_duration_since(now)).as_secs());
+        }
+
+        reputations.into_values().collect()
+    }
+
     /// Returns the `NodeRecord` and `PeerKind` for the given peer id
     pub(crate) fn peer_by_id(&self, peer_id: PeerId) -> Option<(NodeRecord, PeerKind)> {
         self.peers.get(&peer_id).map(|v| {
@@ -476,7 +528,10 @@ impl PeersManager {
             if rep.is_reset() {
                 peer.reset_reputation()
             } else {
-                let mut reputation_change = self.reputation_weights.change(rep).as_i32();
+                let mut reputation_change = self
+                    .reputation_policy
+                    .reputation_change(peer_id, peer.reputation, rep)
+                    .as_i32();
                 if peer.is_trusted() || peer.is_static() {
                     // exempt trusted and static peers from reputation slashing for
                     if matches!(
@@ -689,7 +744,11 @@ impl PeersManager {
                     }
                 } else {
                     // If the error was not a backoff error, we reduce the peer's reputation
-                    let reputation_change = self.reputation_weights.change(reputation_change);
+                    let reputation_change = self.reputation_policy.reputation_change(
+                        peer_id,
+                        peer.reputation,
+                        reputation_change,
+                    );
                     peer.reputation = peer.reputation.saturating_add(reputation_change.as_i32());
                 };
 
@@ -1108,6 +1167,36 @@ impl Default for PeersManager {
     }
 }
 
+/// Applies reputations persisted by a previous run to the known peers and re-bans peers whose ban
+/// hasn't expired yet.
+fn restore_persisted_reputations(
+    peers: &mut HashMap<PeerId, Peer>,
+    ban_list: &mut BanList,
+    persisted: Vec<PersistedPeerReputation>,
+) {
+    if persisted.is_empty() {
+        return
+    }
+
+    let now = std::time::Instant::now();
+    let now_unix = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+
+    for entry in persisted {
+        if let Some(peer) = peers.get_mut(&entry.peer_id) {
+            peer.reputation = entry.reputation;
+        }
+
+        if entry.is_banned_at(now_unix) {
+            let until = entry
+                .banned_until
+                .map(|until| now + Duration::from_secs(until.saturating_sub(now_unix)));
+            ban_list.ban_peer_with(entry.peer_id, until);
+        }
+    }
+
+    trace!(target: "net::peers", banned=?ban_list.banned_peers().count(), "Restored persisted peer reputations");
+}
+
 /// Tracks stats about connected nodes
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub struct ConnectionInfo {
@@ -1269,15 +1358,18 @@ mod tests {
     };
     use reth_net_banlist::BanList;
     use reth_network_api::Direction;
-    use reth_network_peers::{PeerId, TrustedPeer};
+    use reth_network_peers::{NodeRecord, PeerId, TrustedPeer};
     use reth_network_types::{
-        peers::reputation::DEFAULT_REPUTATION, BackoffKind, Peer, ReputationChangeKind,
+        peers::reputation::{ReputationChange, DEFAULT_REPUTATION},
+        BackoffKind, Peer, PersistedPeerReputation, ReputationChangeKind, ReputationPolicy,
     };
     use std::{
+        collections::HashSet,
         future::{poll_fn, Future},
         io,
         net::{IpAddr, Ipv4Addr, SocketAddr},
         pin::Pin,
+        sync::Arc,
         task::{Context, Poll},
         time::Duration,
     };
@@ -2372,6 +2464,61 @@ mod tests {
         assert_eq!(peer_id, given_peer_id)
     }
 
+    #[tokio::test]
+    async fn test_restore_persisted_reputations() {
+        let socket_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        let banned = PeerId::random();
+        let expired = PeerId::random();
+
+        let mut peers = PeersManager::new(PeersConfig::test());
+        peers.add_peer(banned, PeerAddr::from_tcp(socket_addr), None);
+        peers.apply_reputation_change(&banned, ReputationChangeKind::BadProtocol);
+        assert!(peers.ban_list.is_banned_peer(&banned));
+
+        let mut persisted = peers.persisted_reputations();
+        assert_eq!(persisted.len(), 1);
+        assert!(persisted[0].banned);
+        persisted.push(PersistedPeerReputation {
+            peer_id: expired,
+            reputation: DEFAULT_REPUTATION,
+            banned: true,
+            banned_until: Some(0),
+        });
+
+        let config = PeersConfig::test()
+            .with_basic_nodes(HashSet::from([NodeRecord::new(socket_addr, banned)]))
+            .with_persisted_reputations(persisted);
+        let peers = PeersManager::new(config);
+        assert!(peers.ban_list.is_banned_peer(&banned));
+        assert!(!peers.ban_list.is_banned_peer(&expired));
+        assert!(peers.peers.get(&banned).unwrap().is_banned());
+    }
+
+    #[tokio::test]
+    async fn test_custom_reputation_policy() {
+        #[derive(Debug)]
+        struct StrictPolicy;
+
+        impl ReputationPolicy for StrictPolicy {
+            fn reputation_change(
+                &self,
+                _peer_id: &PeerId,
+                _current: i32,
+                _kind: ReputationChangeKind,
+            ) -> ReputationChange {
+                i32::MIN.into()
+            }
+        }
+
+        let socket_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        let peer = PeerId::random();
+        let mut peers =
+            PeersManager::new(PeersConfig::test()).with_reputation_policy(Arc::new(StrictPolicy));
+        peers.add_peer(peer, PeerAddr::from_tcp(socket_addr), None);
+        peers.apply_reputation_change(&peer, ReputationChangeKind::Timeout);
+        assert!(peers.ban_list.is_banned_peer(&peer));
+    }
+
     #[test]
     fn test_connection_limits() {
         let mut info = ConnectionInfo::default();
@@ -14,6 +14,7 @@ use std::{
 use crate::{
     message::{NewBlockMessage, PeerMessage, PeerResponse, PeerResponseResult},
     session::{
+        bandwidth::SessionBandwidth,
         conn::EthRlpxConnection,
         handle::{ActiveSessionMessage, SessionCommand},
         BlockRangeInfo, EthVersion, SessionId,
@@ -26,7 +27,8 @@ use metrics::Gauge;
 use reth_eth_wire::{
     errors::{EthHandshakeError, EthStreamError},
     message::{EthBroadcastMessage, MessageError},
-    Capabilities, DisconnectP2P, DisconnectReason, EthMessage, NetworkPrimitives, NewBlockPayload,
+    Capabilities, DisconnectP2P, DisconnectReason, EthMessage, EthMessageID, NetworkPrimitives,
+    NewBlockPayload,
 };
 use reth_eth_wire_types::{message::RequestPair, RawCapabilityMessage};
 use reth_metrics::common::mpsc::MeteredPollSender;
@@ -135,6 +137,8 @@ pub(crate) struct ActiveSession<N: NetworkPrimitives> {
     /// The last latest block number we sent in a range update
     /// Used to avoid sending unnecessary updates when block height hasn't changed significantly
     pub(crate) last_sent_latest_block: Option<u64>,
+    /// The traffic exchanged with the remote peer, shared with the session's handle.
+    pub(crate) bandwidth: Arc<SessionBandwidth>,
 }
 
 impl<N: NetworkPrimitives> ActiveSession<N> {
@@ -632,6 +636,7 @@ impl<N: NetworkPrimitives> Future for ActiveSession<N> {
             while this.conn.poll_ready_unpin(cx).is_ready() {
                 if let Some(msg) = this.queued_outgoing.pop_front() {
                     progress = true;
+                    let id = msg.message_id();
                     let res = match msg {
                         OutgoingMessage::Eth(msg) => this.conn.start_send_unpin(msg),
                         OutgoingMessage::Broadcast(msg) => this.conn.start_send_broadcast(msg),
@@ -642,6 +647,7 @@ impl<N: NetworkPrimitives> Future for ActiveSession<N> {
                         // notify the manager
                         return this.close_on_error(err, cx)
                     }
+                    this.bandwidth.record_egress(id, this.conn.last_sent_size());
                 } else {
                     // no more messages to send over the wire
                     break
@@ -710,6 +716,10 @@ impl<N: NetworkPrimitives> Future for ActiveSession<N> {
                         match res {
                             Ok(msg) => {
                                 trace!(target: "net::session", msg_id=?msg.message_id(), remote_peer_id=?this.remote_peer_id, "received eth message");
+                                this.bandwidth.record_ingress(
+                                    msg.message_id(),
+                                    this.conn.last_received_size(),
+                                );
                                 // decode and handle message
                                 match this.on_incoming_message(msg) {
                                     OnIncomingMessageOutcome::Ok => {
@@ -870,6 +880,17 @@ impl<N: NetworkPrimitives> OutgoingMessage<N> {
             _ => false,
         }
     }
+
+    /// Returns the message id of the message.
+    fn message_id(&self) -> EthMessageID {
+        match self {
+            Self::Eth(msg) => msg.message_id(),
+            Self::Broadcast(msg) => msg.message_id(),
+            Self::Raw(msg) => {
+                EthMessageID::try_from(msg.id).unwrap_or(EthMessageID::Other(msg.id as u8))
+            }
+        }
+    }
 }
 
 impl<N: NetworkPrimitives> From<EthMessage<N>> for OutgoingMessage<N> {
@@ -1079,6 +1100,7 @@ mod tests {
                         ),
                         range_update_interval: None,
                         last_sent_latest_block: None,
+                        bandwidth: Default::default(),
                     }
                 }
                 ev => {
@@ -0,0 +1,165 @@
+//! Accounting of the traffic exchanged with a peer by message type.
+
+use crate::metrics::MessageBandwidthMetrics;
+use reth_eth_wire::EthMessageID;
+use reth_network_api::bandwidth::{message_type_name, MessageTraffic, PeerBandwidth};
+use std::{
+    array,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+/// The number of message types the traffic is accounted for: every `eth` message id up to
+/// [`EthMessageID::BlockRangeUpdate`], and [`OTHER_SLOT`] for all other messages.
+const MESSAGE_TYPES: usize = EthMessageID::BlockRangeUpdate.to_u8() as usize + 2;
+
+/// The slot of the messages that are not part of the `eth` protocol.
+const OTHER_SLOT: usize = MESSAGE_TYPES - 1;
+
+/// Returns the slot the traffic of the given message id is accounted in.
+const fn slot(id: EthMessageID) -> usize {
+    match id {
+        EthMessageID::Other(_) => OTHER_SLOT,
+        id => id.to_u8() as usize,
+    }
+}
+
+/// Returns the message id of the given slot.
+fn slot_message_id(slot: usize) -> EthMessageID {
+    EthMessageID::try_from(slot).unwrap_or(EthMessageID::Other(slot as u8))
+}
+
+/// The metrics of the traffic of all sessions, by direction and message type.
+#[derive(Debug)]
+pub(crate) struct BandwidthMetrics {
+    /// Metrics of the received messages, by slot.
+    ingress: [MessageBandwidthMetrics; MESSAGE_TYPES],
+    /// Metrics of the sent messages, by slot.
+    egress: [MessageBandwidthMetrics; MESSAGE_TYPES],
+}
+
+impl Default for BandwidthMetrics {
+    fn default() -> Self {
+        let metrics = |direction: &'static str| {
+            array::from_fn(|slot| {
+                MessageBandwidthMetrics::new_with_labels(&[
+                    ("message", message_type_name(slot_message_id(slot))),
+                    ("direction", direction),
+                ])
+            })
+        };
+        Self { ingress: metrics("ingress"), egress: metrics("egress") }
+    }
+}
+
+/// Counters of the messages of a message type and their total size.
+#[derive(Debug, Default)]
+struct TrafficCounter {
+    messages: AtomicU64,
+    bytes: AtomicU64,
+}
+
+impl TrafficCounter {
+    fn record(&self, bytes: usize) {
+        self.messages.fetch_add(1, Ordering::Relaxed);
+        self.bytes.fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    fn load(&self) -> MessageTraffic {
+        MessageTraffic {
+            messages: self.messages.load(Ordering::Relaxed),
+            bytes: self.bytes.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// The traffic of a session by message type.
+///
+/// It's recorded by the [`ActiveSession`](super::ActiveSession) and shared with its
+/// [`ActiveSessionHandle`](super::ActiveSessionHandle), which reports it in the
+/// [`PeerInfo`](super::PeerInfo) of the peer.
+#[derive(Debug)]
+pub(crate) struct SessionBandwidth {
+    /// The received messages, by slot.
+    ingress: [TrafficCounter; MESSAGE_TYPES],
+    /// The sent messages, by slot.
+    egress: [TrafficCounter; MESSAGE_TYPES],
+    /// The metrics of all sessions, the traffic of this session is added to.
+    metrics: Arc<BandwidthMetrics>,
+}
+
+impl SessionBandwidth {
+    /// Creates a new instance that also records the traffic in the given metrics.
+    pub(crate) fn new(metrics: Arc<BandwidthMetrics>) -> Self {
+        Self { ingress: Default::default(), egress: Default::default(), metrics }
+    }
+
+    /// Records a message of `bytes` bytes received from the peer.
+    pub(crate) fn record_ingress(&self, id: EthMessageID, bytes: usize) {
+        let slot = slot(id);
+        self.ingress[slot].record(bytes);
+        self.metrics.ingress[slot].messages.increment(1);
+        self.metrics.ingress[slot].bytes.increment(bytes as u64);
+    }
+
+    /// Records a message of `bytes` bytes sent to the peer.
+    pub(crate) fn record_egress(&self, id: EthMessageID, bytes: usize) {
+        let slot = slot(id);
+        self.egress[slot].record(bytes);
+        self.metrics.egress[slot].messages.increment(1);
+        self.metrics.egress[slot].bytes.increment(bytes as u64);
+    }
+
+    /// Returns the traffic of all message types that were received or sent at least once.
+    pub(crate) fn snapshot(&self) -> PeerBandwidth {
+        let traffic = |counters: &[TrafficCounter; MESSAGE_TYPES]| {
+            counters
+                .iter()
+                .enumerate()
+                .map(|(slot, counter)| (message_type_name(slot_message_id(slot)), counter.load()))
+                .filter(|(_, traffic)| traffic.messages > 0)
+                .collect()
+        };
+        PeerBandwidth { ingress: traffic(&self.ingress), egress: traffic(&self.egress) }
+    }
+}
+
+impl Default for SessionBandwidth {
+    fn default() -> Self {
+        Self::new(Default::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshot_by_message_type() {
+        let bandwidth = SessionBandwidth::default();
+        bandwidth.record_ingress(EthMessageID::BlockBodies, 1000);
+        bandwidth.record_ingress(EthMessageID::BlockBodies, 500);
+        bandwidth.record_ingress(EthMessageID::Other(0x20), 10);
+        bandwidth.record_ingress(EthMessageID::Other(0x0b), 5);
+        bandwidth.record_egress(EthMessageID::GetBlockBodies, 100);
+        bandwidth.record_egress(EthMessageID::BlockRangeUpdate, 40);
+
+        let snapshot = bandwidth.snapshot();
+        assert_eq!(
+            snapshot.ingress.into_iter().collect::<Vec<_>>(),
+            vec![
+                ("BlockBodies", MessageTraffic { messages: 2, bytes: 1500 }),
+                ("Other", MessageTraffic { messages: 2, bytes: 15 }),
+            ]
+        );
+        assert_eq!(
+            snapshot.egress.into_iter().collect::<Vec<_>>(),
+            vec![
+                ("BlockRangeUpdate", MessageTraffic { messages: 1, bytes: 40 }),
+                ("GetBlockBodies", MessageTraffic { messages: 1, bytes: 100 }),
+            ]
+        );
+    }
+}
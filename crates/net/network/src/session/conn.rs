@@ -74,6 +74,25 @@ impl<N: NetworkPrimitives> EthRlpxConnection<N> {
         }
     }
 
+    /// Returns the size of the last message received from the connection, including its message
+    /// id.
+    #[inline]
+    pub(crate) const fn last_received_size(&self) -> usize {
+        match self {
+            Self::EthOnly(conn) => conn.last_received_size(),
+            Self::Satellite(conn) => conn.primary().last_received_size(),
+        }
+    }
+
+    /// Returns the size of the last message sent to the connection, including its message id.
+    #[inline]
+    pub(crate) const fn last_sent_size(&self) -> usize {
+        match self {
+            Self::EthOnly(conn) => conn.last_sent_size(),
+            Self::Satellite(conn) => conn.primary().last_sent_size(),
+        }
+    }
+
     /// Same as [`Sink::start_send`] but accepts a [`EthBroadcastMessage`] instead.
     #[inline]
     pub fn start_send_broadcast(
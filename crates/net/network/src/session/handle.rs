@@ -2,7 +2,7 @@
 
 use crate::{
     message::PeerMessage,
-    session::{conn::EthRlpxConnection, Direction, SessionId},
+    session::{bandwidth::SessionBandwidth, conn::EthRlpxConnection, Direction, SessionId},
     PendingSessionHandshakeError,
 };
 use reth_ecies::ECIESError;
@@ -75,6 +75,8 @@ pub struct ActiveSessionHandle<N: NetworkPrimitives> {
     pub(crate) local_addr: Option<SocketAddr>,
     /// The Status message the peer sent for the `eth` handshake
     pub(crate) status: Arc<UnifiedStatus>,
+    /// The traffic exchanged with the peer, recorded by the session.
+    pub(crate) bandwidth: Arc<SessionBandwidth>,
 }
 
 // === impl ActiveSessionHandle ===
@@ -150,6 +152,7 @@ impl<N: NetworkPrimitives> ActiveSessionHandle<N> {
             status: self.status.clone(),
             session_established: self.established,
             kind,
+            bandwidth: self.bandwidth.snapshot(),
         }
     }
 }
@@ -1,6 +1,7 @@
 //! Support for handling peer sessions.
 
 mod active;
+mod bandwidth;
 mod conn;
 mod counter;
 mod handle;
@@ -14,6 +15,7 @@ use crate::{
     session::active::ActiveSession,
 };
 use active::QueuedOutgoingMessages;
+use bandwidth::{BandwidthMetrics, SessionBandwidth};
 use counter::SessionCounter;
 use futures::{future::Either, io, FutureExt, StreamExt};
 use reth_ecies::{stream::ECIESStream, ECIESError};
@@ -115,6 +117,8 @@ pub struct SessionManager<N: NetworkPrimitives> {
     disconnections_counter: DisconnectionsCounter,
     /// Metrics for the session manager.
     metrics: SessionManagerMetrics,
+    /// Metrics of the traffic of all active sessions.
+    bandwidth_metrics: Arc<BandwidthMetrics>,
     /// The [`EthRlpxHandshake`] is used to perform the initial handshake with the peer.
     handshake: Arc<dyn EthRlpxHandshake>,
     /// Shared local range information that gets propagated to active sessions.
@@ -169,6 +173,7 @@ impl<N: NetworkPrimitives> SessionManager<N> {
             extra_protocols,
             disconnections_counter: Default::default(),
             metrics: Default::default(),
+            bandwidth_metrics: Default::default(),
             handshake,
             local_range_info,
         }
@@ -549,6 +554,9 @@ impl<N: NetworkPrimitives> SessionManager<N> {
                     interval
                 });
 
+                let bandwidth =
+                    Arc::new(SessionBandwidth::new(Arc::clone(&self.bandwidth_metrics)));
+
                 let session = ActiveSession {
                     next_id: 0,
                     remote_peer_id: peer_id,
@@ -575,6 +583,7 @@ impl<N: NetworkPrimitives> SessionManager<N> {
                     local_range_info: self.local_range_info.clone(),
                     range_update_interval,
                     last_sent_latest_block: None,
+                    bandwidth: Arc::clone(&bandwidth),
                 };
 
                 self.spawn(session);
@@ -592,6 +601,7 @@ impl<N: NetworkPrimitives> SessionManager<N> {
                     client_version: Arc::clone(&client_version),
                     remote_addr,
                     local_addr,
+                    bandwidth,
                 };
 
                 self.active_sessions.insert(peer_id, handle);
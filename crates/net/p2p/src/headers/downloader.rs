@@ -32,6 +32,22 @@ pub trait HeaderDownloader:
         self.update_sync_target(target);
     }
 
+    /// Resumes an interrupted download of the gap from the local head to the sync target.
+    ///
+    /// `lowest` is the lowest header that was already downloaded and validated, the downloader
+    /// continues with its parent instead of starting over at the sync target.
+    ///
+    /// See also [`HeaderDownloader::update_sync_gap`]
+    fn resume_sync_gap(
+        &mut self,
+        head: SealedHeader<Self::Header>,
+        lowest: SealedHeader<Self::Header>,
+    ) where
+        Self::Header: BlockHeader,
+    {
+        self.update_sync_gap(head, SyncTarget::Gap(lowest.block_with_parent()));
+    }
+
     /// Updates the block number of the local database
     fn update_local_head(&mut self, head: SealedHeader<Self::Header>);
 
@@ -4,15 +4,19 @@
 
 use crate::{
     common::WithConfigs,
-    components::NodeComponentsBuilder,
+    components::{
+        ComponentsBuilder, ExecutorBuilder, NodeComponentsBuilder, PrecompilesExecutorBuilder,
+    },
     node::FullNode,
     rpc::{RethRpcAddOns, RethRpcServerHandles, RpcContext},
     BlockReaderFor, DebugNode, DebugNodeLauncher, EngineNodeLauncher, LaunchNode, Node,
 };
 use alloy_eips::eip4844::env_settings::EnvKzgSettings;
+use alloy_rlp::Encodable;
 use futures::Future;
 use reth_chainspec::{EthChainSpec, EthereumHardforks, Hardforks};
 use reth_db_api::{database::Database, database_metrics::DatabaseMetrics};
+use reth_evm::{precompiles::PrecompilesMap, ConfigureEvmPrecompiles, SpecFor};
 use reth_exex::ExExContext;
 use reth_network::{
     transactions::{
@@ -448,6 +452,72 @@ where
     }
 }
 
+impl<T, PoolB, PayloadB, NetworkB, ExecB, ConsB>
+    WithLaunchContext<
+        NodeBuilderWithComponents<
+            T,
+            ComponentsBuilder<T, PoolB, PayloadB, NetworkB, ExecB, ConsB>,
+            (),
+        >,
+    >
+where
+    T: FullNodeTypes,
+    ExecB: ExecutorBuilder<T, EVM: ConfigureEvmPrecompiles>,
+    ComponentsBuilder<T, PoolB, PayloadB, NetworkB, ExecB, ConsB>: NodeComponentsBuilder<T>,
+{
+    /// Registers a hook that customizes the precompiles of the configured EVM.
+    ///
+    /// The hook is called with the spec of every EVM created by the node and can register
+    /// additional precompiles or override existing ones. This must be configured before any
+    /// hooks or `ExEx`es are installed.
+    ///
+    /// ```ignore
+    /// let handle = NodeBuilder::new(config)
+    ///     .with_launch_context(executor)
+    ///     .with_types::<EthereumNode>()
+    ///     .with_components(EthereumNode::components())
+    ///     .with_precompiles(|_spec, precompiles| {
+    ///         precompiles.apply_precompile(&address, |_| Some(my_precompile()));
+    ///     })
+    ///     .with_add_ons(EthereumAddOns::default())
+    ///     .launch()
+    ///     .await?;
+    /// ```
+    pub fn with_precompiles<F>(
+        self,
+        hook: F,
+    ) -> WithLaunchContext<
+        NodeBuilderWithComponents<
+            T,
+            ComponentsBuilder<
+                T,
+                PoolB,
+                PayloadB,
+                NetworkB,
+                PrecompilesExecutorBuilder<ExecB, SpecFor<ExecB::EVM>>,
+                ConsB,
+            >,
+            (),
+        >,
+    >
+    where
+        F: Fn(&SpecFor<ExecB::EVM>, &mut PrecompilesMap) + Send + Sync + 'static,
+        ComponentsBuilder<
+            T,
+            PoolB,
+            PayloadB,
+            NetworkB,
+            PrecompilesExecutorBuilder<ExecB, SpecFor<ExecB::EVM>>,
+            ConsB,
+        >: NodeComponentsBuilder<T>,
+    {
+        WithLaunchContext {
+            builder: self.builder.with_precompiles(hook),
+            task_executor: self.task_executor,
+        }
+    }
+}
+
 impl<T, CB, AO> WithLaunchContext<NodeBuilderWithComponents<T, CB, AO>>
 where
     T: FullNodeTypes,
@@ -514,6 +584,16 @@ where
         }
     }
 
+    /// Adds an additional kv-pair to the local ENR of the node.
+    ///
+    /// Networks that use ENR based feature discovery can use this to advertise the features of
+    /// the node. The pair is included in the ENR of both discv4 and discv5, the value is rlp
+    /// encoded.
+    pub fn with_enr_kv_pair(mut self, key: &'static [u8], value: impl Encodable) -> Self {
+        self.config_mut().network.add_enr_kv_pair(key, value);
+        self
+    }
+
     /// Sets the hook that is run once the node's components are initialized.
     pub fn on_component_initialized<F>(self, hook: F) -> Self
     where
@@ -905,6 +985,10 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
         self.executor.spawn_critical_blocking("p2p eth request handler", Box::pin(eth));
 
         let default_peers_path = self.config().datadir().known_peers();
+        let reputations_file = self.config().network.persistent_reputations_file(
+            self.config().network.peers_file.as_deref().unwrap_or(&default_peers_path),
+        );
+        let banlist_export = self.config().network.banlist_export.clone();
         let known_peers_file = self.config().network.persistent_peers_file(default_peers_path);
         self.executor.spawn_critical_with_graceful_shutdown_signal(
             "p2p network task",
@@ -922,6 +1006,26 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
                             }
                         }
                     }
+                    if let Some(reputations_file) = reputations_file {
+                        match network.write_reputations_to_file(reputations_file.as_path()) {
+                            Ok(_) => {
+                                info!(target: "reth::cli", ?reputations_file, "Wrote peer reputations to file");
+                            }
+                            Err(err) => {
+                                warn!(target: "reth::cli", %err, "Failed to write peer reputations to file");
+                            }
+                        }
+                    }
+                    if let Some(banlist_file) = banlist_export {
+                        match network.write_banned_peers_to_file(banlist_file.as_path()) {
+                            Ok(_) => {
+                                info!(target: "reth::cli", ?banlist_file, "Wrote banned peers to file");
+                            }
+                            Err(err) => {
+                                warn!(target: "reth::cli", %err, "Failed to write banned peers to file");
+                            }
+                        }
+                    }
                 }))
             },
         );
@@ -6,13 +6,17 @@
 //! before the node can be launched.
 
 use crate::{
-    components::{NodeComponents, NodeComponentsBuilder},
+    components::{
+        ComponentsBuilder, ExecutorBuilder, NodeComponents, NodeComponentsBuilder,
+        PrecompilesExecutorBuilder,
+    },
     hooks::NodeHooks,
     launch::LaunchNode,
     rpc::{RethRpcAddOns, RethRpcServerHandles, RpcContext},
     AddOns, ComponentsFor, FullNode,
 };
 
+use reth_evm::{precompiles::PrecompilesMap, ConfigureEvmPrecompiles, SpecFor};
 use reth_exex::ExExContext;
 use reth_node_api::{FullNodeComponents, FullNodeTypes, NodeAddOns, NodeTypes};
 use reth_node_core::node_config::NodeConfig;
@@ -178,6 +182,52 @@ where
     }
 }
 
+impl<T, PoolB, PayloadB, NetworkB, ExecB, ConsB>
+    NodeBuilderWithComponents<T, ComponentsBuilder<T, PoolB, PayloadB, NetworkB, ExecB, ConsB>, ()>
+where
+    T: FullNodeTypes,
+    ExecB: ExecutorBuilder<T, EVM: ConfigureEvmPrecompiles>,
+    ComponentsBuilder<T, PoolB, PayloadB, NetworkB, ExecB, ConsB>: NodeComponentsBuilder<T>,
+{
+    /// Registers a hook that customizes the precompiles of the configured EVM.
+    ///
+    /// This changes the type of the node's components, so it must be configured before any hooks
+    /// or `ExEx`es are installed, which are discarded otherwise.
+    ///
+    /// See also [`ComponentsBuilder::with_precompiles`].
+    pub fn with_precompiles<F>(
+        self,
+        hook: F,
+    ) -> NodeBuilderWithComponents<
+        T,
+        ComponentsBuilder<
+            T,
+            PoolB,
+            PayloadB,
+            NetworkB,
+            PrecompilesExecutorBuilder<ExecB, SpecFor<ExecB::EVM>>,
+            ConsB,
+        >,
+        (),
+    >
+    where
+        F: Fn(&SpecFor<ExecB::EVM>, &mut PrecompilesMap) + Send + Sync + 'static,
+        ComponentsBuilder<
+            T,
+            PoolB,
+            PayloadB,
+            NetworkB,
+            PrecompilesExecutorBuilder<ExecB, SpecFor<ExecB::EVM>>,
+            ConsB,
+        >: NodeComponentsBuilder<T>,
+    {
+        let Self { config, adapter, components_builder, .. } = self;
+
+        NodeBuilderWithTypes { config, adapter }
+            .with_components(components_builder.with_precompiles(hook))
+    }
+}
+
 impl<T, CB, AO> NodeBuilderWithComponents<T, CB, AO>
 where
     T: FullNodeTypes,
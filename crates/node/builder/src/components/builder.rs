@@ -3,21 +3,24 @@
 use crate::{
     components::{
         Components, ConsensusBuilder, ExecutorBuilder, NetworkBuilder, NodeComponents,
-        PayloadServiceBuilder, PoolBuilder,
+        PayloadServiceBuilder, PoolBuilder, PrecompilesExecutorBuilder,
     },
     BuilderContext, ConfigureEvm, FullNodeTypes,
 };
 use reth_chainspec::EthChainSpec;
 use reth_consensus::{noop::NoopConsensus, FullConsensus};
+use reth_evm::{precompiles::PrecompilesMap, ConfigureEvmPrecompiles, SpecFor};
 use reth_network::{types::NetPrimitivesFor, EthNetworkPrimitives, NetworkPrimitives};
-use reth_network_api::{noop::NoopNetwork, FullNetwork};
+use reth_network_api::{noop::NoopNetwork, test_utils::ScriptedNetwork, FullNetwork};
 use reth_node_api::{BlockTy, BodyTy, HeaderTy, NodeTypes, PrimitivesTy, ReceiptTy, TxTy};
 use reth_payload_builder::PayloadBuilderHandle;
 use reth_transaction_pool::{
-    noop::NoopTransactionPool, EthPoolTransaction, EthPooledTransaction, PoolPooledTx,
-    PoolTransaction, TransactionPool,
+    blobstore::InMemoryBlobStore,
+    noop::{MockTransactionValidator, NoopTransactionPool},
+    CoinbaseTipOrdering, EthPoolTransaction, EthPooledTransaction, PoolPooledTx, PoolTransaction,
+    TransactionPool,
 };
-use std::{future::Future, marker::PhantomData};
+use std::{future::Future, marker::PhantomData, sync::Arc};
 
 /// A generic, general purpose and customizable [`NodeComponentsBuilder`] implementation.
 ///
@@ -183,6 +186,20 @@ where
         }
     }
 
+    /// Sets [`TestingPoolBuilder`].
+    pub fn testing_pool<Tx>(
+        self,
+    ) -> ComponentsBuilder<Node, TestingPoolBuilder<Tx>, PayloadB, NetworkB, ExecB, ConsB> {
+        ComponentsBuilder {
+            pool_builder: TestingPoolBuilder::<Tx>::default(),
+            payload_builder: self.payload_builder,
+            network_builder: self.network_builder,
+            executor_builder: self.executor_builder,
+            consensus_builder: self.consensus_builder,
+            _marker: self._marker,
+        }
+    }
+
     /// Configures the executor builder.
     ///
     /// This accepts a [`ExecutorBuilder`] instance that will be used to create the node's
@@ -212,6 +229,44 @@ where
         }
     }
 
+    /// Registers a hook that customizes the precompiles of the configured EVM.
+    ///
+    /// The hook is called with the spec of every EVM created by the node and can register
+    /// additional precompiles or override existing ones, e.g. via
+    /// [`PrecompilesMap::apply_precompile`].
+    pub fn with_precompiles<F>(
+        self,
+        hook: F,
+    ) -> ComponentsBuilder<
+        Node,
+        PoolB,
+        PayloadB,
+        NetworkB,
+        PrecompilesExecutorBuilder<ExecB, SpecFor<ExecB::EVM>>,
+        ConsB,
+    >
+    where
+        ExecB: ExecutorBuilder<Node, EVM: ConfigureEvmPrecompiles>,
+        F: Fn(&SpecFor<ExecB::EVM>, &mut PrecompilesMap) + Send + Sync + 'static,
+    {
+        let Self {
+            pool_builder,
+            payload_builder,
+            network_builder,
+            executor_builder,
+            consensus_builder,
+            _marker,
+        } = self;
+        ComponentsBuilder {
+            pool_builder,
+            payload_builder,
+            network_builder,
+            executor_builder: PrecompilesExecutorBuilder::new(executor_builder, Arc::new(hook)),
+            consensus_builder,
+            _marker,
+        }
+    }
+
     /// Configures the consensus builder.
     ///
     /// This accepts a [`ConsensusBuilder`] instance that will be used to create the node's
@@ -321,6 +376,24 @@ where
         }
     }
 
+    /// Sets [`ScriptedNetworkBuilder`] that launches the given [`ScriptedNetwork`].
+    ///
+    /// The caller can keep a clone of the network to drive it and to inspect how the node's
+    /// components interact with it.
+    pub fn scripted_network<Net: NetworkPrimitives>(
+        self,
+        network: ScriptedNetwork<Net>,
+    ) -> ComponentsBuilder<Node, PoolB, PayloadB, ScriptedNetworkBuilder<Net>, ExecB, ConsB> {
+        ComponentsBuilder {
+            pool_builder: self.pool_builder,
+            payload_builder: self.payload_builder,
+            network_builder: ScriptedNetworkBuilder::new(network),
+            executor_builder: self.executor_builder,
+            consensus_builder: self.consensus_builder,
+            _marker: self._marker,
+        }
+    }
+
     /// Sets [`NoopPayloadBuilder`].
     pub fn noop_payload(
         self,
@@ -529,6 +602,98 @@ impl<Net> Default for NoopNetworkBuilder<Net> {
     }
 }
 
+/// The transaction pool built by [`TestingPoolBuilder`].
+pub type TestingPool<Tx = EthPooledTransaction> = reth_transaction_pool::Pool<
+    MockTransactionValidator<Tx>,
+    CoinbaseTipOrdering<Tx>,
+    InMemoryBlobStore,
+>;
+
+/// Builds a deterministic [`TestingPool`].
+///
+/// The pool accepts all transactions without validating them against the state and doesn't
+/// spawn any maintenance tasks, so its content only changes through the interactions of the test.
+#[derive(Debug, Clone)]
+pub struct TestingPoolBuilder<Tx = EthPooledTransaction> {
+    validator: MockTransactionValidator<Tx>,
+}
+
+impl<Tx> TestingPoolBuilder<Tx> {
+    /// Creates a pool builder that validates transactions with the given validator.
+    pub const fn with_validator(validator: MockTransactionValidator<Tx>) -> Self {
+        Self { validator }
+    }
+}
+
+impl<N, Tx, Evm> PoolBuilder<N, Evm> for TestingPoolBuilder<Tx>
+where
+    N: FullNodeTypes,
+    Tx: EthPoolTransaction<Consensus = TxTy<N::Types>> + Unpin,
+    Evm: Send,
+{
+    type Pool = TestingPool<Tx>;
+
+    async fn build_pool(
+        self,
+        ctx: &BuilderContext<N>,
+        _evm_config: Evm,
+    ) -> eyre::Result<Self::Pool> {
+        Ok(reth_transaction_pool::Pool::new(
+            self.validator,
+            CoinbaseTipOrdering::default(),
+            InMemoryBlobStore::default(),
+            ctx.pool_config(),
+        ))
+    }
+}
+
+impl<Tx> Default for TestingPoolBuilder<Tx> {
+    fn default() -> Self {
+        Self { validator: Default::default() }
+    }
+}
+
+/// Builds a [`ScriptedNetwork`].
+#[derive(Debug, Clone)]
+pub struct ScriptedNetworkBuilder<Net: NetworkPrimitives = EthNetworkPrimitives> {
+    network: ScriptedNetwork<Net>,
+}
+
+impl<Net: NetworkPrimitives> ScriptedNetworkBuilder<Net> {
+    /// Creates a builder that launches the given [`ScriptedNetwork`].
+    pub const fn new(network: ScriptedNetwork<Net>) -> Self {
+        Self { network }
+    }
+}
+
+impl<N, Pool, Net> NetworkBuilder<N, Pool> for ScriptedNetworkBuilder<Net>
+where
+    N: FullNodeTypes,
+    Pool: TransactionPool,
+    Net: NetworkPrimitives<
+        BlockHeader = HeaderTy<N::Types>,
+        BlockBody = BodyTy<N::Types>,
+        Block = BlockTy<N::Types>,
+        Receipt = ReceiptTy<N::Types>,
+    >,
+{
+    type Network = ScriptedNetwork<Net>;
+
+    async fn build_network(
+        self,
+        ctx: &BuilderContext<N>,
+        _pool: Pool,
+    ) -> eyre::Result<Self::Network> {
+        Ok(self.network.with_chain_id(ctx.chain_spec().chain_id()))
+    }
+}
+
+impl<Net: NetworkPrimitives> Default for ScriptedNetworkBuilder<Net> {
+    fn default() -> Self {
+        Self::new(ScriptedNetwork::default())
+    }
+}
+
 /// Builds [`NoopConsensus`].
 #[derive(Debug, Clone, Default)]
 pub struct NoopConsensusBuilder;
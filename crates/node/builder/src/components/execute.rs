@@ -1,7 +1,8 @@
 //! EVM component for the node builder.
 use crate::{BuilderContext, ConfigureEvm, FullNodeTypes};
+use reth_evm::{ConfigureEvmPrecompiles, PrecompilesHook, SpecFor};
 use reth_node_api::PrimitivesTy;
-use std::future::Future;
+use std::{fmt, future::Future};
 
 /// A type that knows how to build the executor types.
 pub trait ExecutorBuilder<Node: FullNodeTypes>: Send {
@@ -33,3 +34,42 @@ where
         self(ctx)
     }
 }
+
+/// An [`ExecutorBuilder`] that applies a [`PrecompilesHook`] to the EVM config built by the inner
+/// builder.
+///
+/// See also [`ComponentsBuilder::with_precompiles`](crate::components::ComponentsBuilder::with_precompiles).
+pub struct PrecompilesExecutorBuilder<EB, Spec> {
+    /// The wrapped executor builder.
+    inner: EB,
+    /// The hook applied to the precompiles of the EVM.
+    hook: PrecompilesHook<Spec>,
+}
+
+impl<EB, Spec> PrecompilesExecutorBuilder<EB, Spec> {
+    /// Creates a new builder that applies the given hook to the EVM config built by `inner`.
+    pub const fn new(inner: EB, hook: PrecompilesHook<Spec>) -> Self {
+        Self { inner, hook }
+    }
+}
+
+impl<EB: fmt::Debug, Spec> fmt::Debug for PrecompilesExecutorBuilder<EB, Spec> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PrecompilesExecutorBuilder")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<Node, EB> ExecutorBuilder<Node> for PrecompilesExecutorBuilder<EB, SpecFor<EB::EVM>>
+where
+    Node: FullNodeTypes,
+    EB: ExecutorBuilder<Node, EVM: ConfigureEvmPrecompiles>,
+    <EB::EVM as ConfigureEvmPrecompiles>::WithPrecompiles: 'static,
+{
+    type EVM = <EB::EVM as ConfigureEvmPrecompiles>::WithPrecompiles;
+
+    async fn build_evm(self, ctx: &BuilderContext<Node>) -> eyre::Result<Self::EVM> {
+        Ok(self.inner.build_evm(ctx).await?.with_precompiles(self.hook))
+    }
+}
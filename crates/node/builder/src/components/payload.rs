@@ -96,6 +96,7 @@ where
         let payload_job_config = BasicPayloadJobGeneratorConfig::default()
             .interval(conf.interval)
             .deadline(conf.deadline)
+            .interrupt_timeout(conf.interrupt_timeout)
             .max_payload_tasks(conf.max_payload_tasks);
 
         let payload_generator = BasicPayloadJobGenerator::with_builder(
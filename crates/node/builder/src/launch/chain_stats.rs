@@ -0,0 +1,146 @@
+//! Persistence of the aggregated chain statistics.
+
+use reth_chain_state::{ChainStats, ChainStatsEntry, ChainStatsPeriod, StateGrowth};
+use reth_db_api::models::ChainStatsRecord;
+use reth_provider::{
+    providers::ProviderNodeTypes, CanonStateNotifications, ChainStatsStorageReader,
+    ChainStatsStorageWriter, ChainStatsTable, DBProvider, ProviderFactory, ProviderResult,
+};
+use reth_tracing::tracing::{error, warn};
+use tokio::sync::broadcast::error::RecvError;
+
+/// Both periods, paired with the table their statistics are persisted in.
+const PERIODS: [(ChainStatsPeriod, ChainStatsTable); 2] = [
+    (ChainStatsPeriod::Day, ChainStatsTable::Days),
+    (ChainStatsPeriod::Epoch, ChainStatsTable::Epochs),
+];
+
+/// Loads the persisted chain statistics into `stats`.
+pub(crate) fn load_chain_stats<N: ProviderNodeTypes>(
+    provider_factory: &ProviderFactory<N>,
+    stats: &ChainStats,
+) -> ProviderResult<()> {
+    let provider = provider_factory.provider()?;
+    let load = |(period, table): (ChainStatsPeriod, ChainStatsTable)| -> ProviderResult<Vec<_>> {
+        Ok(provider
+            .chain_stats(table, stats.max_periods(period))?
+            .into_iter()
+            .map(|(number, record)| (number, entry(record)))
+            .collect())
+    };
+    let [days, epochs] = PERIODS;
+    stats.restore(provider.chain_stats_head()?, load(days)?, load(epochs)?);
+    Ok(())
+}
+
+/// Updates the chain statistics from the canonical state notifications and persists the changed
+/// periods, until the channel is closed.
+///
+/// Aggregating a notification walks the whole bundle state of its chain segments, so this should
+/// run on its own task. The statistics are written on a blocking task, so that the database write
+/// doesn't block the runtime.
+pub(crate) async fn persist_chain_stats<N: ProviderNodeTypes>(
+    provider_factory: ProviderFactory<N>,
+    stats: ChainStats,
+    mut notifications: CanonStateNotifications<N::Primitives>,
+) {
+    loop {
+        let notification = match notifications.recv().await {
+            Ok(notification) => notification,
+            Err(RecvError::Lagged(skipped)) => {
+                warn!(target: "reth::cli", skipped, "Skipped canonical state notifications, their blocks are missing from the chain statistics");
+                continue
+            }
+            Err(RecvError::Closed) => break,
+        };
+
+        let changes = stats.on_canon_state(&notification);
+        if changes.is_empty() {
+            continue
+        }
+        let updates = PERIODS.map(|(period, table)| {
+            let records = changes
+                .periods(period)
+                .iter()
+                .map(|number| (*number, stats.entry(period, *number).map(record)))
+                .collect::<Vec<_>>();
+            (table, records, stats.max_periods(period))
+        });
+        let head = stats.head();
+
+        let provider_factory = provider_factory.clone();
+        match tokio::task::spawn_blocking(move || {
+            write_chain_stats(&provider_factory, head, updates)
+        })
+        .await
+        {
+            Ok(Ok(())) => {}
+            Ok(Err(err)) => error!(target: "reth::cli", %err, "Failed to persist chain statistics"),
+            Err(err) => {
+                error!(target: "reth::cli", %err, "Chain statistics persistence task failed")
+            }
+        }
+    }
+}
+
+fn write_chain_stats<N: ProviderNodeTypes>(
+    provider_factory: &ProviderFactory<N>,
+    head: Option<u64>,
+    updates: [(ChainStatsTable, Vec<(u64, Option<ChainStatsRecord>)>, usize); 2],
+) -> ProviderResult<()> {
+    let provider = provider_factory.provider_rw()?;
+    for (table, records, keep) in updates {
+        for (number, record) in records {
+            provider.save_chain_stats(table, number, record)?;
+        }
+        provider.prune_chain_stats(table, keep)?;
+    }
+    provider.save_chain_stats_head(head)?;
+    provider.commit()
+}
+
+/// Converts the statistics of a period into their database representation.
+const fn record(entry: ChainStatsEntry) -> ChainStatsRecord {
+    ChainStatsRecord {
+        blocks: entry.blocks,
+        transactions: entry.transactions,
+        gas_used: entry.gas_used,
+        gas_limit: entry.gas_limit,
+        base_fee_per_gas_total: entry.base_fee_per_gas_total,
+        blob_gas_used: entry.blob_gas_used,
+        accounts_growth: entry.state_growth.accounts as u64,
+        storage_slots_growth: entry.state_growth.storage_slots as u64,
+    }
+}
+
+/// Converts the database representation of the statistics of a period back.
+const fn entry(record: ChainStatsRecord) -> ChainStatsEntry {
+    ChainStatsEntry {
+        blocks: record.blocks,
+        transactions: record.transactions,
+        gas_used: record.gas_used,
+        gas_limit: record.gas_limit,
+        base_fee_per_gas_total: record.base_fee_per_gas_total,
+        blob_gas_used: record.blob_gas_used,
+        state_growth: StateGrowth {
+            accounts: record.accounts_growth as i64,
+            storage_slots: record.storage_slots_growth as i64,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_roundtrip() {
+        let entry_with_shrinking_state = ChainStatsEntry {
+            blocks: 3,
+            base_fee_per_gas_total: u64::MAX as u128 * 3,
+            state_growth: StateGrowth { accounts: -2, storage_slots: 7 },
+            ..Default::default()
+        };
+        assert_eq!(entry(record(entry_with_shrinking_state)), entry_with_shrinking_state);
+    }
+}
@@ -65,7 +65,9 @@ use reth_node_metrics::{
     version::VersionInfo,
 };
 use reth_provider::{
-    providers::{NodeTypesForProvider, ProviderNodeTypes, RocksDBProvider, StaticFileProvider},
+    providers::{
+        HealPlan, NodeTypesForProvider, ProviderNodeTypes, RocksDBProvider, StaticFileProvider,
+    },
     BlockHashReader, BlockNumReader, DatabaseProviderFactory, ProviderError, ProviderFactory,
     ProviderResult, RocksDBProviderFactory, StageCheckpointReader, StaticFileProviderBuilder,
     StaticFileProviderFactory,
@@ -138,12 +140,16 @@ impl LaunchContext {
     /// Attaches both the `NodeConfig` and the loaded `reth.toml` config to the launch context.
     pub fn with_loaded_toml_config<ChainSpec>(
         self,
-        config: NodeConfig<ChainSpec>,
+        mut config: NodeConfig<ChainSpec>,
     ) -> eyre::Result<LaunchContextWith<WithConfigs<ChainSpec>>>
     where
         ChainSpec: EthChainSpec + reth_chainspec::EthereumHardforks,
     {
         let toml_config = self.load_toml_config(&config)?;
+
+        // RPC rate limits are only configurable in the config file
+        config.rpc.rate_limit = toml_config.rpc.rate_limit.clone();
+
         Ok(self.with(WithConfigs { config, toml_config }))
     }
 
@@ -342,9 +348,10 @@ impl<R, ChainSpec: EthChainSpec> LaunchContextWith<Attached<WithConfigs<ChainSpe
     ///
     /// This includes:
     /// - Making sure the ETL dir is set to the datadir
+    /// - Making sure the headers download checkpoint dir is set to the datadir
     /// - RPC settings are adjusted to the correct port
     pub fn with_adjusted_configs(self) -> Self {
-        self.ensure_etl_datadir().with_adjusted_instance_ports()
+        self.ensure_etl_datadir().ensure_headers_download_datadir().with_adjusted_instance_ports()
     }
 
     /// Make sure ETL doesn't default to /tmp/, but to whatever datadir is set to
@@ -363,6 +370,18 @@ impl<R, ChainSpec: EthChainSpec> LaunchContextWith<Attached<WithConfigs<ChainSpe
         self
     }
 
+    /// Make sure downloaded headers are persisted in the datadir, so header downloads resume after
+    /// a restart
+    pub fn ensure_headers_download_datadir(mut self) -> Self {
+        if self.toml_config_mut().stages.headers.download_checkpoint_dir.is_none() {
+            let headers =
+                self.toml_config().stages.headers.clone().with_datadir(self.data_dir().data_dir());
+            self.toml_config_mut().stages.headers = headers;
+        }
+
+        self
+    }
+
     /// Change rpc port numbers based on the instance number.
     pub fn with_adjusted_instance_ports(mut self) -> Self {
         self.node_config_mut().adjust_instance_ports();
@@ -483,13 +502,16 @@ where
         let static_files_config = &self.toml_config().static_files;
         static_files_config.validate()?;
 
-        // Apply per-segment blocks_per_file configuration
-        let static_file_provider =
-            StaticFileProviderBuilder::read_write(self.data_dir().static_files())
-                .with_metrics()
-                .with_blocks_per_file_for_segments(&static_files_config.as_blocks_per_file_map())
-                .with_genesis_block_number(self.chain_spec().genesis().number.unwrap_or_default())
-                .build()?;
+        // Apply per-segment blocks_per_file and directory configuration
+        let static_files_dir = self.data_dir().static_files();
+        let segment_dirs = static_files_config.as_segment_directories_map(&static_files_dir);
+        let static_file_provider = StaticFileProviderBuilder::read_write(static_files_dir)
+            .with_metrics()
+            .with_blocks_per_file_for_segments(&static_files_config.as_blocks_per_file_map())
+            .with_segment_dirs(&segment_dirs)
+            .with_shared_dir(static_files_config.shared_dir.clone())
+            .with_genesis_block_number(self.chain_spec().genesis().number.unwrap_or_default())
+            .build()?;
 
         // Initialize RocksDB provider with metrics, statistics, and default tables
         let rocksdb_provider = RocksDBProvider::builder(self.data_dir().rocksdb())
@@ -515,27 +537,38 @@ where
         // 2) check RocksDB (needs static-file tx data)
         // 3) check static-file checkpoints vs MDBX (may prune)
         //
-        // Compute one unwind target and run a single unwind.
+        // Compute one unwind target and run a single unwind. With `--no-heal`, nothing is
+        // modified and the node exits with the computed plan instead.
 
+        let heal = !self.node_config().debug.no_heal;
         let provider_ro = factory.database_provider_ro()?;
+        let mut plan = HealPlan::default();
 
-        // Step 1: heal file-level inconsistencies (no pruning)
-        factory.static_file_provider().check_file_consistency(&provider_ro)?;
+        // Step 1: heal file-level inconsistencies (no pruning). Without healing, these are
+        // reported by step 3.
+        if heal {
+            plan.extend(factory.static_file_provider().file_consistency_plan(&provider_ro, true)?);
+        }
 
         // Step 2: RocksDB consistency check (needs static files tx data)
-        let rocksdb_unwind = factory.rocksdb_provider().check_consistency(&provider_ro)?;
+        let rocksdb_plan = factory.rocksdb_provider().consistency_plan(&provider_ro, heal)?;
+        let rocksdb_unwind = rocksdb_plan.unwind_target();
+        plan.extend(rocksdb_plan);
 
         // Step 3: Static file checkpoint consistency (may prune)
-        let static_file_unwind = factory
-            .static_file_provider()
-            .check_consistency(&provider_ro)?
-            .map(|target| match target {
-                PipelineTarget::Unwind(block) => block,
-                PipelineTarget::Sync(_) => unreachable!("check_consistency returns Unwind"),
-            });
+        let static_file_plan =
+            factory.static_file_provider().consistency_plan(&provider_ro, heal)?;
+        let static_file_unwind = static_file_plan.unwind_target();
+        plan.extend(static_file_plan);
+
+        if !heal && !plan.is_empty() {
+            error!(target: "reth::cli", %plan, "Storage is inconsistent and healing is disabled.");
+            eyre::bail!("storage is inconsistent, required healing actions: {plan}");
+        }
 
-        // Take the minimum block number to ensure all storage layers are consistent.
-        let unwind_target = [rocksdb_unwind, static_file_unwind].into_iter().flatten().min();
+        // The plan keeps the minimum unwind block number to ensure all storage layers are
+        // consistent.
+        let unwind_target = plan.unwind_target();
 
         if let Some(unwind_block) = unwind_target {
             // Highly unlikely to happen, and given its destructive nature, it's better to panic
@@ -592,6 +625,10 @@ where
             })?;
         }
 
+        if !plan.is_empty() {
+            info!(target: "reth::cli", %plan, "Healed storage inconsistencies.");
+        }
+
         Ok(factory)
     }
 
@@ -1328,6 +1365,8 @@ mod tests {
                     bodies_distance: None,
                     receipts_log_filter: None,
                     bodies_before: None,
+                    bodies_older_than: None,
+                    reorg_history_limit: None,
                 },
                 ..NodeConfig::test()
             };
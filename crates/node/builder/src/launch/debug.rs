@@ -289,6 +289,7 @@ where
             };
 
             let dev_mining_mode = handle.node.config.dev_mining_mode(pool);
+            let local_miner = handle.node.add_ons_handle.local_miner.clone();
             handle.node.task_executor.spawn_critical("local engine", async move {
                 LocalMiner::new(
                     blockchain_db,
@@ -297,6 +298,7 @@ where
                     dev_mining_mode,
                     payload_builder_handle,
                 )
+                .with_handle(&local_miner)
                 .run()
                 .await
             });
@@ -3,24 +3,29 @@
 use crate::{
     common::{Attached, LaunchContextWith, WithConfigs},
     hooks::NodeHooks,
+    launch::{chain_stats, reorg_history, sync_progress::track_pipeline_progress},
     rpc::{EngineShutdown, EngineValidatorAddOn, EngineValidatorBuilder, RethRpcAddOns, RpcHandle},
     setup::build_networked_pipeline,
     AddOns, AddOnsContext, FullNode, LaunchContext, LaunchNode, NodeAdapter,
     NodeBuilderWithComponents, NodeComponents, NodeComponentsBuilder, NodeHandle, NodeTypesAdapter,
 };
 use alloy_consensus::BlockHeader;
+use alloy_eips::BlockNumHash;
 use futures::{stream_select, FutureExt, StreamExt};
+use reth_chain_state::{ChainStatsProvider, SyncMode};
 use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_downloaders::pipeline_log::PipelineInputRecorder;
 use reth_engine_service::service::{ChainEvent, EngineService};
 use reth_engine_tree::{
     chain::FromOrchestrator,
     engine::{EngineApiRequest, EngineRequestHandler},
     tree::TreeConfig,
+    watchdog::{HeadWatchdog, WatchdogEvent, HEAD_WATCHDOG_CHECK_INTERVAL},
 };
 use reth_engine_util::EngineMessageStreamExt;
 use reth_exex::ExExManagerHandle;
 use reth_network::{types::BlockRangeUpdate, NetworkSyncUpdater, SyncState};
-use reth_network_api::BlockDownloaderProvider;
+use reth_network_api::{BlockDownloaderProvider, Peers};
 use reth_node_api::{
     BuiltPayload, ConsensusEngineHandle, FullNodeTypes, NodeTypes, NodeTypesWithDBAdapter,
 };
@@ -29,16 +34,17 @@ use reth_node_core::{
     exit::NodeExitFuture,
     primitives::Head,
 };
-use reth_node_events::node;
+use reth_node_events::node::{self, NodeEvent};
 use reth_provider::{
     providers::{BlockchainProvider, NodeTypesForProvider},
-    BlockNumReader, StorageSettingsCache,
+    BlockNumReader, CanonStateSubscriptions, StorageSettingsCache,
 };
+use reth_stages::stages::ContractsIndexer;
 use reth_tasks::TaskExecutor;
 use reth_tokio_util::EventSender;
 use reth_tracing::tracing::{debug, error, info};
 use reth_trie_db::ChangesetCache;
-use std::{future::Future, pin::Pin, sync::Arc};
+use std::{future::Future, pin::Pin, sync::Arc, time::Instant};
 use tokio::sync::{mpsc::unbounded_channel, oneshot};
 use tokio_stream::wrappers::UnboundedReceiverStream;
 
@@ -145,7 +151,7 @@ impl EngineNodeLauncher {
 
         let consensus = Arc::new(ctx.components().consensus().clone());
 
-        let pipeline = build_networked_pipeline(
+        let mut pipeline = build_networked_pipeline(
             &ctx.toml_config().stages,
             network_client.clone(),
             consensus.clone(),
@@ -154,27 +160,69 @@ impl EngineNodeLauncher {
             ctx.sync_metrics_tx(),
             ctx.prune_config(),
             max_block,
-            static_file_producer,
+            static_file_producer.clone(),
             ctx.components().evm_config().clone(),
             maybe_exex_manager_handle.clone().unwrap_or_else(ExExManagerHandle::empty),
             ctx.era_import_source(),
+            node_config
+                .debug
+                .record_pipeline
+                .as_ref()
+                .map(PipelineInputRecorder::new)
+                .transpose()?,
         )?;
 
         // The new engine writes directly to static files. This ensures that they're up to the tip.
         pipeline.move_to_static_files()?;
 
         let pipeline_events = pipeline.events();
+        let sync_progress = ctx.blockchain_db().canonical_in_memory_state().sync_progress().clone();
+        ctx.task_executor()
+            .spawn(track_pipeline_progress(pipeline.events(), sync_progress.clone()));
 
         let mut pruner_builder = ctx.pruner_builder();
         if let Some(exex_manager_handle) = &maybe_exex_manager_handle {
             pruner_builder =
                 pruner_builder.finished_exex_height(exex_manager_handle.finished_height());
         }
+        // the pruning configuration is reloaded from the config file on SIGHUP
+        #[cfg(unix)]
+        {
+            let (prune_config_tx, prune_config_rx) =
+                tokio::sync::watch::channel(ctx.prune_config());
+            pruner_builder = pruner_builder.config_updates(prune_config_rx);
+            let config_path =
+                ctx.node_config().config.clone().unwrap_or_else(|| ctx.data_dir().config());
+            ctx.task_executor().spawn(crate::launch::prune_config::reload_on_sighup(
+                config_path,
+                ctx.node_config().prune_config(),
+                ctx.provider_factory().clone(),
+                static_file_producer,
+                prune_config_tx,
+            ));
+        }
         let pruner = pruner_builder.build_with_provider_factory(ctx.provider_factory().clone());
         let pruner_events = pruner.events();
         info!(target: "reth::cli", prune_config=?ctx.prune_config(), "Pruner initialized");
 
+        let chain_stats = ctx.blockchain_db().chain_stats();
+        chain_stats::load_chain_stats(ctx.provider_factory(), &chain_stats)?;
+        ctx.task_executor().spawn(chain_stats::persist_chain_stats(
+            ctx.provider_factory().clone(),
+            chain_stats,
+            ctx.blockchain_db().subscribe_to_canonical_state(),
+        ));
+
+        if let Some(limit) = ctx.node_config().pruning.reorg_history_limit() {
+            ctx.task_executor().spawn(reorg_history::record_reorgs(
+                ctx.provider_factory().clone(),
+                ctx.blockchain_db().subscribe_to_canonical_state(),
+                limit,
+            ));
+        }
+
         let event_sender = EventSender::default();
+        let watchdog_events = EventSender::<WatchdogEvent>::default();
 
         let beacon_engine_handle = ConsensusEngineHandle::new(consensus_engine_tx.clone());
 
@@ -219,6 +267,13 @@ impl EngineNodeLauncher {
             // during this run.
             .maybe_store_messages(node_config.debug.engine_api_store.clone());
 
+        // The optional contract indices are recorded when the engine persists blocks
+        let block_indexer = ContractsIndexer::from_config(
+            ctx.components().evm_config().clone(),
+            &ctx.toml_config().stages,
+        )
+        .map(|indexer| Box::new(indexer) as _);
+
         let mut engine_service = EngineService::new(
             consensus.clone(),
             ctx.chain_spec(),
@@ -229,6 +284,7 @@ impl EngineNodeLauncher {
             ctx.provider_factory().clone(),
             ctx.blockchain_db().clone(),
             pruner,
+            block_indexer,
             ctx.components().payload_builder_handle().clone(),
             engine_validator,
             engine_tree_config,
@@ -246,6 +302,7 @@ impl EngineNodeLauncher {
             ctx.consensus_layer_events(),
             pruner_events.map(Into::into),
             static_file_producer_events.map(Into::into),
+            watchdog_events.new_listener().map(|event| NodeEvent::Other(event.to_string())),
         );
 
         ctx.task_executor().spawn_critical(
@@ -263,6 +320,7 @@ impl EngineNodeLauncher {
             engine_events,
             beacon_engine_handle,
             engine_shutdown: _,
+            local_miner,
         } = add_ons.launch_add_ons(add_ons_ctx).await?;
 
         // Create engine shutdown handle
@@ -284,11 +342,19 @@ impl EngineNodeLauncher {
         let (exit, rx) = oneshot::channel();
         let terminate_after_backfill = ctx.terminate_after_initial_backfill();
         let startup_sync_state_idle = ctx.node_config().debug.startup_sync_state_idle;
+        let mut head_watchdog = ctx.node_config().engine.head_watchdog_config().map(|config| {
+            HeadWatchdog::new(
+                config,
+                BlockNumHash::new(ctx.head().number, ctx.head().hash),
+                Instant::now(),
+            )
+        });
 
         info!(target: "reth::cli", "Starting consensus engine");
         let consensus_engine = async move {
             if let Some(initial_target) = initial_target {
                 debug!(target: "reth::cli", %initial_target,  "start backfill sync");
+                sync_progress.set_mode(SyncMode::Backfill);
                 // network_handle's sync state is already initialized at Syncing
                 engine_service.orchestrator_mut().start_backfill_sync(initial_target);
             } else if startup_sync_state_idle {
@@ -297,6 +363,7 @@ impl EngineNodeLauncher {
 
             let mut res = Ok(());
             let mut shutdown_rx = shutdown_rx.fuse();
+            let mut head_watchdog_interval = tokio::time::interval(HEAD_WATCHDOG_CHECK_INTERVAL);
 
             // advance the chain and await payloads built locally to add into the engine api
             // tree handler to prevent re-execution if that block is received as payload from
@@ -311,6 +378,26 @@ impl EngineNodeLauncher {
                             );
                         }
                     }
+                    _ = head_watchdog_interval.tick(), if head_watchdog.is_some() => {
+                        let Some(watchdog) = head_watchdog.as_mut() else { continue };
+                        if sync_progress.mode() == SyncMode::Backfill {
+                            // the backfill sync advances the chain
+                            watchdog.reset(Instant::now());
+                            continue
+                        }
+
+                        let best_peer_block = network_handle
+                            .get_all_peers()
+                            .await
+                            .ok()
+                            .and_then(|peers| peers.iter().filter_map(|peer| peer.status.latest_block).max());
+                        if let Some(event) = watchdog.check(best_peer_block, Instant::now()) {
+                            if let WatchdogEvent::Stalled { action, .. } = event {
+                                engine_service.orchestrator_mut().recover_head(action);
+                            }
+                            watchdog_events.notify(event);
+                        }
+                    }
                     payload = built_payloads.select_next_some() => {
                         if let Some(executed_block) = payload.executed_block() {
                             debug!(target: "reth::cli", block=?executed_block.recovered_block.num_hash(),  "inserting built payload");
@@ -322,6 +409,7 @@ impl EngineNodeLauncher {
                         debug!(target: "reth::cli", "Event: {event}");
                         match event {
                             ChainEvent::BackfillSyncFinished => {
+                                sync_progress.set_mode(SyncMode::Live);
                                 if terminate_after_backfill {
                                     debug!(target: "reth::cli", "Terminating after initial backfill");
                                     break
@@ -331,6 +419,7 @@ impl EngineNodeLauncher {
                                 }
                             }
                             ChainEvent::BackfillSyncStarted => {
+                                sync_progress.set_mode(SyncMode::Backfill);
                                 network_handle.update_sync_state(SyncState::Syncing);
                             }
                             ChainEvent::FatalError => {
@@ -340,6 +429,12 @@ impl EngineNodeLauncher {
                             }
                             ChainEvent::Handler(ev) => {
                                 if let Some(head) = ev.canonical_header() {
+                                    if let Some(event) = head_watchdog.as_mut().and_then(|watchdog| {
+                                        watchdog.on_canonical_head(head.num_hash(), Instant::now())
+                                    }) {
+                                        watchdog_events.notify(event);
+                                    }
+
                                     // Once we're progressing via live sync, we can consider the node is not syncing anymore
                                     network_handle.update_sync_state(SyncState::Idle);
                                     let head_block = Head {
@@ -388,6 +483,7 @@ impl EngineNodeLauncher {
                 engine_events,
                 beacon_engine_handle,
                 engine_shutdown,
+                local_miner,
             },
         };
         // Notify on node started
@@ -11,7 +11,7 @@ use reth_exex::{
 use reth_node_api::{FullNodeComponents, NodeTypes, PrimitivesTy};
 use reth_provider::CanonStateSubscriptions;
 use reth_tracing::tracing::{debug, info};
-use std::{fmt, fmt::Debug};
+use std::{fmt, fmt::Debug, path::Path};
 use tracing::Instrument;
 
 use crate::{common::WithConfigs, exex::BoxedLaunchExEx};
@@ -60,9 +60,18 @@ impl<Node: FullNodeComponents + Clone> ExExLauncher<Node> {
     pub async fn launch(
         self,
     ) -> eyre::Result<Option<ExExManagerHandle<PrimitivesTy<Node::Types>>>> {
-        let Self { head, extensions, components, config_container, wal_blocks_warning } = self;
+        let Self { head, mut extensions, components, config_container, wal_blocks_warning } = self;
         let head = BlockNumHash::new(head.number, head.hash);
 
+        if let Some(dir) = &config_container.config.exex.wasm_dir {
+            for (id, exex) in load_wasm_exexes::<Node>(dir)? {
+                if extensions.iter().any(|(installed, _)| *installed == id) {
+                    eyre::bail!("wasm exex {id} conflicts with an installed exex of the same id")
+                }
+                extensions.push((id, exex));
+            }
+        }
+
         if extensions.is_empty() {
             // nothing to launch
             return Ok(None)
@@ -90,6 +99,7 @@ impl<Node: FullNodeComponents + Clone> ExExLauncher<Node> {
                 components.evm_config().clone(),
                 exex_wal.handle(),
             );
+            let finalized = handle.subscribe_finalized();
             exex_handles.push(handle);
 
             // create the launch context for the exex
@@ -100,6 +110,7 @@ impl<Node: FullNodeComponents + Clone> ExExLauncher<Node> {
                 components: components.clone(),
                 events,
                 notifications,
+                finalized,
             };
 
             let executor = components.task_executor().clone();
@@ -165,6 +176,35 @@ impl<Node: FullNodeComponents + Clone> ExExLauncher<Node> {
     }
 }
 
+/// Loads the WASM `ExEx`'s in the given directory, see [`reth_exex::WasmExExHost::load_dir`].
+#[cfg(feature = "wasm-exex")]
+fn load_wasm_exexes<Node: FullNodeComponents>(
+    dir: &Path,
+) -> eyre::Result<Vec<(String, Box<dyn BoxedLaunchExEx<Node>>)>> {
+    let host = reth_exex::WasmExExHost::new(reth_exex::WasmExExConfig::default())?;
+    let exexes = host
+        .load_dir(dir)?
+        .into_iter()
+        .map(|exex| {
+            let id = exex.id().to_string();
+            let launch = move |ctx: ExExContext<Node>| async move { Ok(exex.run(ctx.into_dyn())) };
+            (id, Box::new(launch) as Box<dyn BoxedLaunchExEx<Node>>)
+        })
+        .collect();
+    Ok(exexes)
+}
+
+/// Fails, since WASM `ExEx`'s can only be loaded if the `wasm-exex` feature is enabled.
+#[cfg(not(feature = "wasm-exex"))]
+fn load_wasm_exexes<Node: FullNodeComponents>(
+    dir: &Path,
+) -> eyre::Result<Vec<(String, Box<dyn BoxedLaunchExEx<Node>>)>> {
+    eyre::bail!(
+        "can't load wasm exexes from {}: reth was built without the `wasm-exex` feature",
+        dir.display()
+    )
+}
+
 impl<Node: FullNodeComponents> Debug for ExExLauncher<Node> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("ExExLauncher")
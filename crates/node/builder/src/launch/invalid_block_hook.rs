@@ -56,6 +56,7 @@ where
 /// configuration in the node config. It supports:
 /// - Witness hooks for capturing block witness data
 /// - Healthy node verification via RPC
+/// - Webhook notifications posted to a remote endpoint
 ///
 /// # Arguments
 /// * `config` - The node configuration containing debug settings
@@ -81,7 +82,7 @@ where
     E: reth_evm::ConfigureEvm<Primitives = N> + Clone + 'static,
 {
     use reth_engine_primitives::{InvalidBlockHooks, NoopInvalidBlockHook};
-    use reth_invalid_block_hooks::InvalidBlockWitnessHook;
+    use reth_invalid_block_hooks::{InvalidBlockWebhookHook, InvalidBlockWitnessHook};
 
     let Some(ref hook) = config.debug.invalid_block_hook else {
         return Ok(Box::new(NoopInvalidBlockHook::default()))
@@ -94,16 +95,25 @@ where
         .iter()
         .copied()
         .map(|hook| {
-            let output_directory = output_directory.join(hook.to_string());
-            std::fs::create_dir_all(&output_directory)?;
-
             Ok(match hook {
-                InvalidBlockHookType::Witness => Box::new(InvalidBlockWitnessHook::new(
-                    provider.clone(),
-                    evm_config.clone(),
-                    output_directory,
-                    healthy_node_rpc_client.clone(),
-                )),
+                InvalidBlockHookType::Witness => {
+                    let output_directory = output_directory.join(hook.to_string());
+                    std::fs::create_dir_all(&output_directory)?;
+
+                    Box::new(InvalidBlockWitnessHook::new(
+                        provider.clone(),
+                        evm_config.clone(),
+                        output_directory,
+                        healthy_node_rpc_client.clone(),
+                    ))
+                }
+                InvalidBlockHookType::Webhook => {
+                    let url =
+                        config.debug.invalid_block_webhook_url.clone().ok_or_eyre(
+                            "webhook hook requires `--debug.invalid-block-webhook-url`",
+                        )?;
+                    Box::new(InvalidBlockWebhookHook::new(url, &tokio::runtime::Handle::current())?)
+                }
                 InvalidBlockHookType::PreState | InvalidBlockHookType::Opcode => {
                     eyre::bail!("invalid block hook {hook:?} is not implemented yet")
                 }
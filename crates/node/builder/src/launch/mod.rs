@@ -1,8 +1,13 @@
 //! Abstraction for launching a node.
 
+mod chain_stats;
 pub mod common;
 mod exex;
 pub mod invalid_block_hook;
+#[cfg(unix)]
+mod prune_config;
+mod reorg_history;
+mod sync_progress;
 
 pub(crate) mod debug;
 pub(crate) mod engine;
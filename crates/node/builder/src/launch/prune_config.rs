@@ -0,0 +1,210 @@
+//! Reloading of the pruning configuration from the config file.
+
+use alloy_consensus::BlockHeader;
+use reth_config::{Config, PruneConfig};
+use reth_provider::{
+    providers::ProviderNodeTypes, BlockNumReader, HeaderProvider, ProviderFactory,
+    PruneCheckpointReader,
+};
+use reth_prune::{PruneModes, PrunePurpose, PruneSegment};
+use reth_static_file::StaticFileProducer;
+use reth_tracing::tracing::{error, info};
+use std::path::{Path, PathBuf};
+use tokio::{
+    signal::unix::{signal, SignalKind},
+    sync::watch,
+};
+
+/// Reloads the pruning configuration whenever the node receives `SIGHUP`.
+///
+/// The `[prune]` section of the config file is merged with the pruning configuration of the CLI
+/// in the same way as on startup. If the result changed and doesn't keep history that was already
+/// pruned, it's applied to the providers of the `provider_factory`, which decide what's written
+/// to static files, to the `static_file_producer`, and sent to the pruner.
+pub(crate) async fn reload_on_sighup<N: ProviderNodeTypes>(
+    config_path: PathBuf,
+    cli_config: Option<PruneConfig>,
+    provider_factory: ProviderFactory<N>,
+    static_file_producer: StaticFileProducer<ProviderFactory<N>>,
+    config_tx: watch::Sender<PruneConfig>,
+) {
+    let mut hangup = match signal(SignalKind::hangup()) {
+        Ok(hangup) => hangup,
+        Err(err) => {
+            error!(target: "reth::cli", %err, "Failed to listen for SIGHUP, pruning configuration won't be reloaded");
+            return
+        }
+    };
+
+    while hangup.recv().await.is_some() {
+        let config = load_prune_config(&config_path, cli_config.clone()).and_then(|config| {
+            validate_prune_config(&provider_factory.provider()?, &config)?;
+            Ok(config)
+        });
+        let config = match config {
+            Ok(config) => config,
+            Err(err) => {
+                error!(target: "reth::cli", path = ?config_path, %err, "Failed to reload pruning configuration");
+                continue
+            }
+        };
+
+        let changed = *config_tx.borrow() != config;
+        if changed {
+            provider_factory.set_prune_modes(config.segments.clone());
+            static_file_producer.lock().set_prune_modes(config.segments.clone());
+            config_tx.send_replace(config);
+        }
+        info!(target: "reth::cli", path = ?config_path, changed, "Pruning configuration reloaded");
+    }
+}
+
+/// Loads the pruning configuration from the config file and merges it into the pruning
+/// configuration of the CLI, if any.
+fn load_prune_config(
+    config_path: &Path,
+    cli_config: Option<PruneConfig>,
+) -> eyre::Result<PruneConfig> {
+    let mut config = Config::from_path(config_path)?.prune;
+    config.segments.migrate();
+
+    Ok(match cli_config {
+        Some(mut cli_config) => {
+            cli_config.merge(config);
+            cli_config
+        }
+        None => config,
+    })
+}
+
+/// Checks that the pruning configuration can be applied to a running node.
+///
+/// Pruned history can't be restored, so every segment that was pruned must still be pruned at
+/// least up to its prune checkpoint at the current tip. Time based modes whose block can't be
+/// resolved yet are accepted.
+fn validate_prune_config<Provider>(provider: &Provider, config: &PruneConfig) -> eyre::Result<()>
+where
+    Provider: BlockNumReader + HeaderProvider + PruneCheckpointReader,
+{
+    let tip = provider.best_block_number()?;
+    let PruneModes {
+        sender_recovery,
+        transaction_lookup,
+        receipts,
+        account_history,
+        storage_history,
+        bodies_history,
+        receipts_log_filter: _,
+    } = &config.segments;
+
+    for (segment, mode) in [
+        (PruneSegment::SenderRecovery, sender_recovery),
+        (PruneSegment::TransactionLookup, transaction_lookup),
+        (PruneSegment::Receipts, receipts),
+        (PruneSegment::AccountHistory, account_history),
+        (PruneSegment::StorageHistory, storage_history),
+        (PruneSegment::Bodies, bodies_history),
+    ] {
+        let Some(pruned) =
+            provider.get_prune_checkpoint(segment)?.and_then(|checkpoint| checkpoint.block_number)
+        else {
+            continue
+        };
+
+        let target = match mode {
+            Some(mode) => {
+                let mode = mode.resolve_time_based(tip, segment, |number| {
+                    provider.header_by_number(number).map(|header| header.map(|h| h.timestamp()))
+                })?;
+                if mode.is_time_based() {
+                    continue
+                }
+                mode.prune_target_block(tip, segment, PrunePurpose::User)?.map(|(block, _)| block)
+            }
+            None => None,
+        };
+        if target.is_none_or(|target| target < pruned) {
+            eyre::bail!(
+                "{segment} is already pruned up to block {pruned}, prune mode {mode:?} would keep pruned history"
+            )
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_provider::{
+        test_utils::create_test_provider_factory, DatabaseProviderFactory, PruneCheckpointWriter,
+        StageCheckpointWriter,
+    };
+    use reth_prune::{PruneCheckpoint, PruneMode};
+    use reth_stages::{StageCheckpoint, StageId};
+
+    #[test]
+    fn validate_against_prune_checkpoints() {
+        let factory = create_test_provider_factory();
+        let provider = factory.database_provider_rw().unwrap();
+        provider.save_stage_checkpoint(StageId::Finish, StageCheckpoint::new(20_000)).unwrap();
+        provider
+            .save_prune_checkpoint(
+                PruneSegment::SenderRecovery,
+                PruneCheckpoint {
+                    block_number: Some(15_000),
+                    tx_number: None,
+                    prune_mode: PruneMode::Before(15_001),
+                },
+            )
+            .unwrap();
+
+        let config = |sender_recovery| PruneConfig {
+            segments: PruneModes { sender_recovery, ..Default::default() },
+            ..Default::default()
+        };
+
+        // pruning at least up to the checkpoint is fine
+        for mode in [PruneMode::Full, PruneMode::Distance(5_000), PruneMode::Before(15_001)] {
+            validate_prune_config(&provider, &config(Some(mode))).unwrap();
+        }
+
+        // keeping pruned history isn't
+        for mode in [None, Some(PruneMode::Distance(10_000)), Some(PruneMode::Before(100))] {
+            assert!(validate_prune_config(&provider, &config(mode)).is_err());
+        }
+
+        // segments without a checkpoint can be changed freely
+        let config = PruneConfig {
+            segments: PruneModes {
+                sender_recovery: Some(PruneMode::Full),
+                receipts: Some(PruneMode::Distance(100_000)),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        validate_prune_config(&provider, &config).unwrap();
+    }
+
+    #[test]
+    fn load_merges_cli_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = dir.path().join("reth.toml");
+        let mut config = Config::default();
+        config.prune.block_interval = 10;
+        config.prune.segments.receipts = Some(PruneMode::Distance(100_000));
+        config.prune.segments.sender_recovery = Some(PruneMode::Distance(100_000));
+        config.save(&config_path).unwrap();
+
+        let cli_config = PruneConfig {
+            segments: PruneModes { sender_recovery: Some(PruneMode::Full), ..Default::default() },
+            ..Default::default()
+        };
+        let loaded = load_prune_config(&config_path, Some(cli_config)).unwrap();
+        assert_eq!(loaded.block_interval, 10);
+        assert_eq!(loaded.segments.sender_recovery, Some(PruneMode::Full));
+        assert_eq!(loaded.segments.receipts, Some(PruneMode::Distance(100_000)));
+
+        assert_eq!(load_prune_config(&config_path, None).unwrap(), config.prune);
+    }
+}
@@ -0,0 +1,80 @@
+//! Recording of canonical chain reorgs into the reorg history.
+
+use alloy_consensus::BlockHeader;
+use reth_db_api::models::ReorgRecord;
+use reth_primitives_traits::{BlockBody, NodePrimitives};
+use reth_provider::{
+    providers::ProviderNodeTypes, CanonStateNotification, CanonStateNotifications, Chain,
+    DBProvider, ProviderFactory, ProviderResult, ReorgHistoryWriter,
+};
+use reth_tracing::tracing::{debug, error, warn};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::sync::broadcast::error::RecvError;
+
+/// Records every reorg of the canonical chain into the reorg history.
+///
+/// After each insert the history is pruned to at most `limit` entries. Records are written on a
+/// blocking task, so that the database write doesn't block the runtime.
+pub(crate) async fn record_reorgs<N: ProviderNodeTypes>(
+    provider_factory: ProviderFactory<N>,
+    mut notifications: CanonStateNotifications<N::Primitives>,
+    limit: usize,
+) {
+    loop {
+        let notification = match notifications.recv().await {
+            Ok(notification) => notification,
+            Err(RecvError::Lagged(skipped)) => {
+                warn!(target: "reth::cli", skipped, "Skipped canonical state notifications, reorgs among them are missing from the reorg history");
+                continue
+            }
+            Err(RecvError::Closed) => break,
+        };
+        let CanonStateNotification::Reorg { old, new } = notification else { continue };
+
+        let record = reorg_record(&old, &new);
+        debug!(target: "reth::cli", ?record, "Recording reorg");
+
+        let provider_factory = provider_factory.clone();
+        match tokio::task::spawn_blocking(move || {
+            write_reorg_record(&provider_factory, record, limit)
+        })
+        .await
+        {
+            Ok(Ok(())) => {}
+            Ok(Err(err)) => error!(target: "reth::cli", %err, "Failed to record reorg"),
+            Err(err) => error!(target: "reth::cli", %err, "Reorg recording task failed"),
+        }
+    }
+}
+
+fn write_reorg_record<N: ProviderNodeTypes>(
+    provider_factory: &ProviderFactory<N>,
+    record: ReorgRecord,
+    limit: usize,
+) -> ProviderResult<()> {
+    let provider = provider_factory.provider_rw()?;
+    provider.insert_reorg_record(record)?;
+    provider.prune_reorg_history(limit)?;
+    provider.commit()
+}
+
+/// Creates the [`ReorgRecord`] of a reorg from the reverted `old` and the applied `new` chain.
+fn reorg_record<N: NodePrimitives>(old: &Chain<N>, new: &Chain<N>) -> ReorgRecord {
+    let transactions = |chain: &Chain<N>| {
+        chain.blocks_iter().map(|b| b.body().transaction_count() as u64).sum::<u64>()
+    };
+    let (old_head, new_head) = (old.tip(), new.tip());
+
+    ReorgRecord {
+        timestamp: SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs(),
+        old_head: old_head.hash(),
+        old_head_number: old_head.number(),
+        old_head_timestamp: old_head.timestamp(),
+        new_head: new_head.hash(),
+        new_head_number: new_head.number(),
+        new_head_timestamp: new_head.timestamp(),
+        depth: old.len() as u64,
+        reverted_transactions: transactions(old),
+        applied_transactions: transactions(new),
+    }
+}
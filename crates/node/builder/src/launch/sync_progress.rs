@@ -0,0 +1,28 @@
+//! Tracking of the pipeline stage progress reported by `eth_syncing` and `reth_syncStatus`.
+
+use futures::{Stream, StreamExt};
+use reth_chain_state::SyncProgress;
+use reth_stages::{ExecOutput, PipelineEvent, UnwindOutput};
+
+/// Records the checkpoints reported by the pipeline stages into the [`SyncProgress`].
+pub(crate) async fn track_pipeline_progress<St>(mut events: St, sync_progress: SyncProgress)
+where
+    St: Stream<Item = PipelineEvent> + Unpin,
+{
+    while let Some(event) = events.next().await {
+        match event {
+            PipelineEvent::Prepare { stage_id, checkpoint, target, .. } |
+            PipelineEvent::Run { stage_id, checkpoint, target, .. } => {
+                sync_progress.update_stage(stage_id, checkpoint.unwrap_or_default(), target);
+            }
+            PipelineEvent::Ran { stage_id, result: ExecOutput { checkpoint, .. }, .. } |
+            PipelineEvent::Unwound { stage_id, result: UnwindOutput { checkpoint } } => {
+                sync_progress.update_stage(stage_id, checkpoint, None);
+            }
+            PipelineEvent::Unwind { stage_id, input } => {
+                sync_progress.update_stage(stage_id, input.checkpoint, Some(input.unwind_to));
+            }
+            PipelineEvent::Error { .. } | PipelineEvent::Skipped { .. } => {}
+        }
+    }
+}
@@ -15,6 +15,7 @@ use jsonrpsee::{core::middleware::layer::Either, RpcModule};
 use parking_lot::Mutex;
 use reth_chain_state::CanonStateSubscriptions;
 use reth_chainspec::{ChainSpecProvider, EthChainSpec, EthereumHardforks, Hardforks};
+use reth_engine_local::LocalMinerHandle;
 use reth_node_api::{
     AddOnsContext, BlockTy, EngineApiValidator, EngineTypes, FullNodeComponents, FullNodeTypes,
     NodeAddOns, NodeTypes, PayloadTypes, PayloadValidator, PrimitivesTy, TreeConfig,
@@ -27,19 +28,21 @@ use reth_node_core::{
 use reth_payload_builder::{PayloadBuilderHandle, PayloadStore};
 use reth_rpc::{
     eth::{core::EthRpcConverterFor, DevSigner, EthApiTypes, FullEthApiServer},
-    AdminApi,
+    AdminApi, RethMiner,
 };
-use reth_rpc_api::{eth::helpers::EthTransactions, IntoEngineApiRpcModule};
+use reth_rpc_api::{eth::helpers::EthTransactions, IntoEngineApiRpcModule, RethMinerApiServer};
 use reth_rpc_builder::{
     auth::{AuthRpcModule, AuthServerHandle},
     config::RethRpcServerConfig,
-    RpcModuleBuilder, RpcRegistryInner, RpcServerConfig, RpcServerHandle, TransportRpcModules,
+    RethRpcModule, RpcConsistencyLayer, RpcModuleBuilder, RpcRegistryInner, RpcServerConfig,
+    RpcServerHandle, TransportRpcModules,
 };
 use reth_rpc_engine_api::{capabilities::EngineCapabilities, EngineApi};
 use reth_rpc_eth_types::{cache::cache_new_blocks_task, EthConfig, EthStateCache};
 use reth_tokio_util::EventSender;
 use reth_tracing::tracing::{debug, info};
 use std::{
+    collections::HashSet,
     fmt::{self, Debug},
     future::Future,
     ops::{Deref, DerefMut},
@@ -338,6 +341,8 @@ pub struct RpcHandle<Node: FullNodeComponents, EthApi: EthApiTypes> {
     pub beacon_engine_handle: ConsensusEngineHandle<<Node::Types as NodeTypes>::Payload>,
     /// Handle to trigger engine shutdown.
     pub engine_shutdown: EngineShutdown,
+    /// Handle to the local miner that serves `reth_mine` in dev mode.
+    pub local_miner: LocalMinerHandle,
 }
 
 impl<Node: FullNodeComponents, EthApi: EthApiTypes> Clone for RpcHandle<Node, EthApi> {
@@ -348,6 +353,7 @@ impl<Node: FullNodeComponents, EthApi: EthApiTypes> Clone for RpcHandle<Node, Et
             engine_events: self.engine_events.clone(),
             beacon_engine_handle: self.beacon_engine_handle.clone(),
             engine_shutdown: self.engine_shutdown.clone(),
+            local_miner: self.local_miner.clone(),
         }
     }
 }
@@ -369,6 +375,7 @@ where
             .field("rpc_server_handles", &self.rpc_server_handles)
             .field("rpc_registry", &self.rpc_registry)
             .field("engine_shutdown", &self.engine_shutdown)
+            .field("local_miner", &self.local_miner)
             .finish()
     }
 }
@@ -497,6 +504,7 @@ struct RpcSetupContext<'a, Node: FullNodeComponents, EthApi: EthApiTypes> {
     on_rpc_started: Box<dyn OnRpcStarted<Node, EthApi>>,
     engine_events: EventSender<ConsensusEngineEvent<<Node::Types as NodeTypes>::Primitives>>,
     engine_handle: ConsensusEngineHandle<<Node::Types as NodeTypes>::Payload>,
+    local_miner: LocalMinerHandle,
 }
 
 /// Node add-ons containing RPC server configuration, with customizable eth API handler.
@@ -853,11 +861,18 @@ where
             on_rpc_started,
             engine_events,
             engine_handle,
+            local_miner: _,
         } = setup_ctx;
 
         let server_config = config
             .rpc
             .rpc_server_config()
+            .with_consistency(
+                config
+                    .rpc
+                    .rpc_served_block
+                    .then(|| RpcConsistencyLayer::new(node.provider().clone())),
+            )
             .set_rpc_middleware(rpc_middleware)
             .with_tokio_runtime(tokio_runtime);
         let rpc_server_handle = Self::launch_rpc_server_internal(server_config, &modules).await?;
@@ -924,11 +939,18 @@ where
             on_rpc_started,
             engine_events,
             engine_handle,
+            local_miner,
         } = setup_ctx;
 
         let server_config = config
             .rpc
             .rpc_server_config()
+            .with_consistency(
+                config
+                    .rpc
+                    .rpc_served_block
+                    .then(|| RpcConsistencyLayer::new(node.provider().clone())),
+            )
             .set_rpc_middleware(rpc_middleware)
             .with_tokio_runtime(tokio_runtime);
 
@@ -965,6 +987,7 @@ where
             engine_events,
             beacon_engine_handle: engine_handle,
             engine_shutdown: EngineShutdown::default(),
+            local_miner,
         })
     }
 
@@ -979,6 +1002,7 @@ where
     {
         let Self { eth_api_builder, engine_api_builder, hooks, .. } = self;
 
+        let capabilities = engine_api_builder.capabilities();
         let engine_api = engine_api_builder.build_engine_api(&ctx).await?;
         let AddOnsContext { node, config, beacon_engine_handle, jwt_secret, engine_events } = ctx;
 
@@ -1021,12 +1045,21 @@ where
             .with_consensus(node.consensus().clone())
             .build_with_auth_server(module_config, engine_api, eth_api, engine_events.clone());
 
-        // in dev mode we generate 20 random dev-signer accounts
+        // in dev mode we generate 20 random dev-signer accounts and serve `reth_mine`, the local
+        // miner is attached to the handle once the dev mode is launched
+        let local_miner = LocalMinerHandle::default();
         if config.dev.dev {
             let signers = DevSigner::from_mnemonic(config.dev.dev_mnemonic.as_str(), 20);
             registry.eth_api().signers().write().extend(signers);
+            modules.merge_if_module_configured(
+                RethRpcModule::Reth,
+                RethMiner::new(local_miner.clone()).into_rpc(),
+            )?;
         }
 
+        // methods of the engine API itself are advertised by its own capabilities
+        let base_methods: HashSet<_> = auth_module.method_names().collect();
+
         let mut registry = RpcRegistry { registry };
         let ctx = RpcContext {
             node: node.clone(),
@@ -1045,6 +1078,16 @@ where
         })?;
         extend_rpc_modules.extend_rpc_modules(ctx)?;
 
+        // advertise engine methods added by the hooks and drop the ones they removed
+        if let Some(capabilities) = capabilities {
+            let methods: HashSet<_> = auth_module.method_names().collect();
+            capabilities.register_engine_methods(methods.difference(&base_methods).copied());
+            for removed in base_methods.difference(&methods) {
+                capabilities.unregister(removed);
+            }
+            debug!(target: "reth::cli", capabilities=?capabilities.list(), "Engine API capabilities");
+        }
+
         Ok(RpcSetupContext {
             node,
             config,
@@ -1055,6 +1098,7 @@ where
             on_rpc_started,
             engine_events,
             engine_handle: beacon_engine_handle,
+            local_miner,
         })
     }
 
@@ -1251,6 +1295,15 @@ pub trait EngineApiBuilder<Node: FullNodeComponents>: Send + Sync {
         self,
         ctx: &AddOnsContext<'_, Node>,
     ) -> impl Future<Output = eyre::Result<Self::EngineApi>> + Send;
+
+    /// Returns the capabilities registry advertised by `engine_exchangeCapabilities`.
+    ///
+    /// If set, `engine_` methods that are added to the authenticated RPC server by the
+    /// [`ExtendRpcModules`] hooks are registered once the RPC modules are configured, and removed
+    /// methods are unregistered.
+    fn capabilities(&self) -> Option<EngineCapabilities> {
+        None
+    }
 }
 
 /// Builder trait for creating payload validators specifically for the Engine API.
@@ -1363,6 +1416,7 @@ where
 #[derive(Debug, Default)]
 pub struct BasicEngineApiBuilder<PVB> {
     payload_validator_builder: PVB,
+    capabilities: EngineCapabilities,
 }
 
 impl<N, PVB> EngineApiBuilder<N> for BasicEngineApiBuilder<PVB>
@@ -1385,7 +1439,7 @@ where
     >;
 
     async fn build_engine_api(self, ctx: &AddOnsContext<'_, N>) -> eyre::Result<Self::EngineApi> {
-        let Self { payload_validator_builder } = self;
+        let Self { payload_validator_builder, capabilities } = self;
 
         let engine_validator = payload_validator_builder.build(ctx).await?;
         let client = ClientVersionV1 {
@@ -1403,12 +1457,16 @@ where
             ctx.node.pool().clone(),
             Box::new(ctx.node.task_executor().clone()),
             client,
-            EngineCapabilities::default(),
+            capabilities,
             engine_validator,
             ctx.config.engine.accept_execution_requests_hash,
             ctx.node.network().clone(),
         ))
     }
+
+    fn capabilities(&self) -> Option<EngineCapabilities> {
+        Some(self.capabilities.clone())
+    }
 }
 
 /// A noop Builder that satisfies the [`EngineApiBuilder`] trait without actually configuring an
@@ -9,6 +9,7 @@ use reth_consensus::FullConsensus;
 use reth_downloaders::{
     bodies::bodies::BodiesDownloaderBuilder,
     headers::reverse_headers::ReverseHeadersDownloaderBuilder,
+    pipeline_log::{PipelineInputRecorder, RecordingBodyDownloader, RecordingHeaderDownloader},
 };
 use reth_evm::ConfigureEvm;
 use reth_exex::ExExManagerHandle;
@@ -19,7 +20,7 @@ use reth_node_api::HeaderTy;
 use reth_provider::{providers::ProviderNodeTypes, ProviderFactory};
 use reth_stages::{
     prelude::DefaultStages,
-    stages::{EraImportSource, ExecutionStage},
+    stages::{ContractsIndexer, EraImportSource, ExecutionStage},
     Pipeline, StageSet,
 };
 use reth_static_file::StaticFileProducer;
@@ -28,6 +29,9 @@ use reth_tracing::tracing::debug;
 use tokio::sync::watch;
 
 /// Constructs a [Pipeline] that's wired to the network
+///
+/// If a [`PipelineInputRecorder`] is given, all sync targets and downloaded batches are recorded
+/// so the pipeline runs can be replayed later.
 #[expect(clippy::too_many_arguments)]
 pub fn build_networked_pipeline<N, Client, Evm>(
     config: &StageConfig,
@@ -42,6 +46,7 @@ pub fn build_networked_pipeline<N, Client, Evm>(
     evm_config: Evm,
     exex_manager_handle: ExExManagerHandle<N::Primitives>,
     era_import_source: Option<EraImportSource>,
+    pipeline_recorder: Option<PipelineInputRecorder>,
 ) -> eyre::Result<Pipeline<N>>
 where
     N: ProviderNodeTypes,
@@ -49,7 +54,7 @@ where
     Evm: ConfigureEvm<Primitives = N::Primitives> + 'static,
 {
     // building network downloaders using the fetch client
-    let header_downloader = ReverseHeadersDownloaderBuilder::new(config.headers)
+    let header_downloader = ReverseHeadersDownloaderBuilder::new(config.headers.clone())
         .build(client.clone(), consensus.clone())
         .into_task_with(task_executor);
 
@@ -57,20 +62,38 @@ where
         .build(client, consensus.clone(), provider_factory.clone())
         .into_task_with(task_executor);
 
-    let pipeline = build_pipeline(
-        provider_factory,
-        config,
-        header_downloader,
-        body_downloader,
-        consensus,
-        max_block,
-        metrics_tx,
-        prune_config,
-        static_file_producer,
-        evm_config,
-        exex_manager_handle,
-        era_import_source,
-    )?;
+    let pipeline = if let Some(recorder) = pipeline_recorder {
+        debug!(target: "reth::cli", "Recording pipeline inputs");
+        build_pipeline(
+            provider_factory,
+            config,
+            RecordingHeaderDownloader::new(header_downloader, recorder.clone()),
+            RecordingBodyDownloader::new(body_downloader, recorder),
+            consensus,
+            max_block,
+            metrics_tx,
+            prune_config,
+            static_file_producer,
+            evm_config,
+            exex_manager_handle,
+            era_import_source,
+        )?
+    } else {
+        build_pipeline(
+            provider_factory,
+            config,
+            header_downloader,
+            body_downloader,
+            consensus,
+            max_block,
+            metrics_tx,
+            prune_config,
+            static_file_producer,
+            evm_config,
+            exex_manager_handle,
+            era_import_source,
+        )?
+    };
 
     Ok(pipeline)
 }
@@ -121,13 +144,16 @@ where
                 prune_config.segments,
                 era_import_source,
             )
-            .set(ExecutionStage::new(
-                evm_config,
-                consensus,
-                stage_config.execution.into(),
-                stage_config.execution_external_clean_threshold(),
-                exex_manager_handle,
-            )),
+            .set(
+                ExecutionStage::new(
+                    evm_config.clone(),
+                    consensus,
+                    stage_config.execution.into(),
+                    stage_config.execution_external_clean_threshold(),
+                    exex_manager_handle,
+                )
+                .with_contracts_indexer(ContractsIndexer::from_config(evm_config, stage_config)),
+            ),
         )
         .build(provider_factory, static_file_producer);
 
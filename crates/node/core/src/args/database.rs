@@ -1,6 +1,6 @@
 //! clap [Args](clap::Args) for database configuration
 
-use std::{fmt, str::FromStr, time::Duration};
+use std::{fmt, str::FromStr, sync::Arc, time::Duration};
 
 use crate::version::default_client_version;
 use clap::{
@@ -9,7 +9,10 @@ use clap::{
     value_parser, Arg, Args, Command, Error,
 };
 use reth_db::{
-    mdbx::{MaxReadTransactionDuration, SyncMode},
+    mdbx::{
+        MaxReadTransactionDuration, ReadTransactionTimeoutAction, ReadTransactionTimeoutPolicy,
+        SyncMode,
+    },
     ClientVersion,
 };
 use reth_storage_errors::db::LogLevel;
@@ -51,6 +54,17 @@ pub struct DatabaseArgs {
     /// Read transaction timeout in seconds, 0 means no timeout.
     #[arg(long = "db.read-transaction-timeout")]
     pub read_transaction_timeout: Option<u64>,
+    /// Action taken for read transactions open for longer than the read transaction timeout:
+    /// `abort` times them out, `keep` keeps them open, and `deny` keeps them open but denies their
+    /// new reads, so that only their open cursors can still be used. Other read transactions are
+    /// never affected.
+    ///
+    /// The default is `abort`.
+    #[arg(
+        long = "db.read-transaction-timeout-action",
+        value_parser = value_parser!(ReadTransactionTimeoutAction),
+    )]
+    pub read_transaction_timeout_action: Option<ReadTransactionTimeoutAction>,
     /// Maximum number of readers allowed to access the database concurrently.
     #[arg(long = "db.max-readers")]
     pub max_readers: Option<u64>,
@@ -84,6 +98,10 @@ impl DatabaseArgs {
             .with_log_level(self.log_level)
             .with_exclusive(self.exclusive)
             .with_max_read_transaction_duration(max_read_transaction_duration)
+            .with_read_transaction_timeout_policy(
+                self.read_transaction_timeout_action
+                    .map(|action| Arc::new(action) as Arc<dyn ReadTransactionTimeoutPolicy>),
+            )
             .with_geometry_max_size(self.max_size)
             .with_geometry_page_size(self.page_size)
             .with_growth_step(self.growth_step)
@@ -435,4 +453,28 @@ mod tests {
             CommandParser::<DatabaseArgs>::try_parse_from(["reth", "--db.sync-mode", "ultra-fast"]);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_command_parser_with_read_transaction_timeout_action() {
+        let cmd = CommandParser::<DatabaseArgs>::try_parse_from(["reth"]).unwrap();
+        assert!(cmd.args.read_transaction_timeout_action.is_none());
+
+        let cmd = CommandParser::<DatabaseArgs>::try_parse_from([
+            "reth",
+            "--db.read-transaction-timeout-action",
+            "deny",
+        ])
+        .unwrap();
+        assert_eq!(
+            cmd.args.read_transaction_timeout_action,
+            Some(ReadTransactionTimeoutAction::Deny)
+        );
+
+        let result = CommandParser::<DatabaseArgs>::try_parse_from([
+            "reth",
+            "--db.read-transaction-timeout-action",
+            "drop",
+        ]);
+        assert!(result.is_err());
+    }
 }
@@ -72,6 +72,13 @@ pub struct DebugArgs {
     #[arg(long = "debug.engine-api-store", help_heading = "Debug", value_name = "PATH")]
     pub engine_api_store: Option<PathBuf>,
 
+    /// The path to record the external inputs of pipeline runs at.
+    ///
+    /// Sync targets and downloaded header and body batches are appended to this file, which can
+    /// be replayed against a copy of the initial datadir with `reth debug replay-pipeline`.
+    #[arg(long = "debug.record-pipeline", help_heading = "Debug", value_name = "PATH")]
+    pub record_pipeline: Option<PathBuf>,
+
     /// Determines which type of invalid block hook to install
     ///
     /// Example: `witness,prestate`
@@ -97,6 +104,10 @@ pub struct DebugArgs {
     )]
     pub healthy_node_rpc_url: Option<String>,
 
+    /// The URL invalid block notifications are posted to by the `webhook` invalid block hook.
+    #[arg(long = "debug.invalid-block-webhook-url", help_heading = "Debug", value_name = "URL")]
+    pub invalid_block_webhook_url: Option<String>,
+
     /// The URL of the ethstats server to connect to.
     /// Example: `nodename:secret@host:port`
     #[arg(long = "ethstats", help_heading = "Debug")]
@@ -108,6 +119,14 @@ pub struct DebugArgs {
     /// the backfill, but did not yet receive any new blocks.
     #[arg(long = "debug.startup-sync-state-idle", help_heading = "Debug")]
     pub startup_sync_state_idle: bool,
+
+    /// Disables healing of storage inconsistencies at startup.
+    ///
+    /// The startup consistency checks still run, but instead of truncating static files,
+    /// rebuilding indices or unwinding the pipeline, the node reports the actions healing would
+    /// take and exits.
+    #[arg(long = "no-heal", help_heading = "Debug")]
+    pub no_heal: bool,
 }
 
 impl Default for DebugArgs {
@@ -123,10 +142,13 @@ impl Default for DebugArgs {
             reorg_frequency: None,
             reorg_depth: None,
             engine_api_store: None,
+            record_pipeline: None,
             invalid_block_hook: Some(InvalidBlockSelection::default()),
             healthy_node_rpc_url: None,
+            invalid_block_webhook_url: None,
             ethstats: None,
             startup_sync_state_idle: false,
+            no_heal: false,
         }
     }
 }
@@ -303,6 +325,8 @@ pub enum InvalidBlockHookType {
     PreState,
     /// An opcode trace value enum
     Opcode,
+    /// A webhook notification value enum
+    Webhook,
 }
 
 impl FromStr for InvalidBlockHookType {
@@ -313,6 +337,7 @@ impl FromStr for InvalidBlockHookType {
             "witness" => Self::Witness,
             "prestate" => Self::PreState,
             "opcode" => Self::Opcode,
+            "webhook" => Self::Webhook,
             _ => return Err(ParseError::VariantNotFound),
         })
     }
@@ -421,4 +446,25 @@ mod tests {
         .args;
         assert_eq!(args, expected_args);
     }
+
+    #[test]
+    fn test_parse_invalid_block_webhook_args() {
+        let expected_args = DebugArgs {
+            invalid_block_hook: Some(InvalidBlockSelection::from([
+                InvalidBlockHookType::Witness,
+                InvalidBlockHookType::Webhook,
+            ])),
+            invalid_block_webhook_url: Some("http://localhost:8080/invalid".to_string()),
+            ..Default::default()
+        };
+        let args = CommandParser::<DebugArgs>::parse_from([
+            "reth",
+            "--debug.invalid-block-hook",
+            "witness,webhook",
+            "--debug.invalid-block-webhook-url",
+            "http://localhost:8080/invalid",
+        ])
+        .args;
+        assert_eq!(args, expected_args);
+    }
 }
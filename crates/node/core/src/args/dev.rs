@@ -29,6 +29,20 @@ pub struct DevArgs {
     )]
     pub block_max_transactions: Option<usize>,
 
+    /// Maximum time to wait for `--dev.block-max-transactions` transactions, before the
+    /// transactions received so far are mined.
+    ///
+    /// Parses strings using [`humantime::parse_duration`]
+    /// --dev.block-max-wait 2s
+    #[arg(
+        long = "dev.block-max-wait",
+        help_heading = "Dev testnet",
+        requires = "block_max_transactions",
+        value_parser = parse_duration,
+        verbatim_doc_comment
+    )]
+    pub block_max_wait: Option<Duration>,
+
     /// Interval between blocks.
     ///
     /// Parses strings using [`humantime::parse_duration`]
@@ -42,6 +56,14 @@ pub struct DevArgs {
     )]
     pub block_time: Option<Duration>,
 
+    /// Only mine blocks on request with the `reth_mine` RPC method.
+    #[arg(
+        long = "dev.manual-mining",
+        help_heading = "Dev testnet",
+        conflicts_with_all = ["block_time", "block_max_transactions"]
+    )]
+    pub manual_mining: bool,
+
     /// Derive dev accounts from a fixed mnemonic instead of random ones.
     #[arg(
         long = "dev.mnemonic",
@@ -59,7 +81,9 @@ impl Default for DevArgs {
         Self {
             dev: false,
             block_max_transactions: None,
+            block_max_wait: None,
             block_time: None,
+            manual_mining: false,
             dev_mnemonic: DEFAULT_MNEMONIC.to_string(),
         }
     }
@@ -85,7 +109,9 @@ mod tests {
             DevArgs {
                 dev: false,
                 block_max_transactions: None,
+                block_max_wait: None,
                 block_time: None,
+                manual_mining: false,
                 dev_mnemonic: DEFAULT_MNEMONIC.to_string(),
             }
         );
@@ -96,7 +122,9 @@ mod tests {
             DevArgs {
                 dev: true,
                 block_max_transactions: None,
+                block_max_wait: None,
                 block_time: None,
+                manual_mining: false,
                 dev_mnemonic: DEFAULT_MNEMONIC.to_string(),
             }
         );
@@ -107,7 +135,9 @@ mod tests {
             DevArgs {
                 dev: true,
                 block_max_transactions: None,
+                block_max_wait: None,
                 block_time: None,
+                manual_mining: false,
                 dev_mnemonic: DEFAULT_MNEMONIC.to_string(),
             }
         );
@@ -124,7 +154,9 @@ mod tests {
             DevArgs {
                 dev: true,
                 block_max_transactions: Some(2),
+                block_max_wait: None,
                 block_time: None,
+                manual_mining: false,
                 dev_mnemonic: DEFAULT_MNEMONIC.to_string(),
             }
         );
@@ -136,12 +168,50 @@ mod tests {
             DevArgs {
                 dev: true,
                 block_max_transactions: None,
+                block_max_wait: None,
                 block_time: Some(std::time::Duration::from_secs(1)),
+                manual_mining: false,
                 dev_mnemonic: DEFAULT_MNEMONIC.to_string(),
             }
         );
     }
 
+    #[test]
+    fn test_parse_dev_mining_modes() {
+        let args = CommandParser::<DevArgs>::parse_from([
+            "reth",
+            "--dev",
+            "--dev.block-max-transactions",
+            "10",
+            "--dev.block-max-wait",
+            "2s",
+        ])
+        .args;
+        assert_eq!(args.block_max_transactions, Some(10));
+        assert_eq!(args.block_max_wait, Some(Duration::from_secs(2)));
+
+        let args =
+            CommandParser::<DevArgs>::parse_from(["reth", "--dev", "--dev.manual-mining"]).args;
+        assert!(args.manual_mining);
+
+        // the max wait only applies to a max number of transactions
+        assert!(CommandParser::<DevArgs>::try_parse_from([
+            "reth",
+            "--dev",
+            "--dev.block-max-wait",
+            "2s"
+        ])
+        .is_err());
+        assert!(CommandParser::<DevArgs>::try_parse_from([
+            "reth",
+            "--dev",
+            "--dev.manual-mining",
+            "--dev.block-time",
+            "1s"
+        ])
+        .is_err());
+    }
+
     #[test]
     fn test_parse_dev_args_conflicts() {
         let args = CommandParser::<DevArgs>::try_parse_from([
@@ -1,12 +1,16 @@
 //! clap [Args](clap::Args) for engine purposes
 
 use clap::{builder::Resettable, Args};
-use reth_engine_primitives::{TreeConfig, DEFAULT_MULTIPROOF_TASK_CHUNK_SIZE};
-use std::sync::OnceLock;
+use reth_engine_primitives::{
+    HeadWatchdogConfig, TreeConfig, DEFAULT_BLOCK_BUFFER_LIMIT,
+    DEFAULT_HEAD_WATCHDOG_ESCALATION_INTERVAL, DEFAULT_HEAD_WATCHDOG_MIN_PEER_LEAD,
+    DEFAULT_HEAD_WATCHDOG_STALL_THRESHOLD, DEFAULT_MULTIPROOF_TASK_CHUNK_SIZE,
+};
+use std::{sync::OnceLock, time::Duration};
 
 use crate::node_config::{
-    DEFAULT_CROSS_BLOCK_CACHE_SIZE_MB, DEFAULT_MEMORY_BLOCK_BUFFER_TARGET,
-    DEFAULT_PERSISTENCE_THRESHOLD, DEFAULT_RESERVED_CPU_CORES,
+    DEFAULT_CROSS_BLOCK_CACHE_SIZE_MB, DEFAULT_EXECUTION_OUTPUT_COMPACTION_DEPTH,
+    DEFAULT_MEMORY_BLOCK_BUFFER_TARGET, DEFAULT_PERSISTENCE_THRESHOLD, DEFAULT_RESERVED_CPU_CORES,
 };
 
 /// Global static engine defaults
@@ -22,6 +26,7 @@ pub struct DefaultEngineValues {
     legacy_state_root_task_enabled: bool,
     state_cache_disabled: bool,
     prewarming_disabled: bool,
+    access_list_prefetch_disabled: bool,
     state_provider_metrics: bool,
     cross_block_cache_size: usize,
     state_root_task_compare_updates: bool,
@@ -37,6 +42,12 @@ pub struct DefaultEngineValues {
     account_worker_count: Option<usize>,
     disable_proof_v2: bool,
     cache_metrics_disabled: bool,
+    execution_output_compaction_depth: u64,
+    max_buffered_blocks: u32,
+    head_watchdog_enabled: bool,
+    head_watchdog_stall_threshold: u64,
+    head_watchdog_escalation_interval: u64,
+    head_watchdog_min_peer_lead: u64,
 }
 
 impl DefaultEngineValues {
@@ -80,6 +91,12 @@ impl DefaultEngineValues {
         self
     }
 
+    /// Set whether to disable access list prefetching by default
+    pub const fn with_access_list_prefetch_disabled(mut self, v: bool) -> Self {
+        self.access_list_prefetch_disabled = v;
+        self
+    }
+
     /// Set whether to enable state provider metrics by default
     pub const fn with_state_provider_metrics(mut self, v: bool) -> Self {
         self.state_provider_metrics = v;
@@ -172,6 +189,42 @@ impl DefaultEngineValues {
         self.cache_metrics_disabled = v;
         self
     }
+
+    /// Set the default execution output compaction depth
+    pub const fn with_execution_output_compaction_depth(mut self, v: u64) -> Self {
+        self.execution_output_compaction_depth = v;
+        self
+    }
+
+    /// Set the default maximum number of buffered blocks
+    pub const fn with_max_buffered_blocks(mut self, v: u32) -> Self {
+        self.max_buffered_blocks = v;
+        self
+    }
+
+    /// Set whether to enable the head watchdog by default
+    pub const fn with_head_watchdog_enabled(mut self, v: bool) -> Self {
+        self.head_watchdog_enabled = v;
+        self
+    }
+
+    /// Set the default head watchdog stall threshold in seconds
+    pub const fn with_head_watchdog_stall_threshold(mut self, v: u64) -> Self {
+        self.head_watchdog_stall_threshold = v;
+        self
+    }
+
+    /// Set the default head watchdog escalation interval in seconds
+    pub const fn with_head_watchdog_escalation_interval(mut self, v: u64) -> Self {
+        self.head_watchdog_escalation_interval = v;
+        self
+    }
+
+    /// Set the default number of blocks peers must be ahead for the head watchdog
+    pub const fn with_head_watchdog_min_peer_lead(mut self, v: u64) -> Self {
+        self.head_watchdog_min_peer_lead = v;
+        self
+    }
 }
 
 impl Default for DefaultEngineValues {
@@ -182,6 +235,7 @@ impl Default for DefaultEngineValues {
             legacy_state_root_task_enabled: false,
             state_cache_disabled: false,
             prewarming_disabled: false,
+            access_list_prefetch_disabled: false,
             state_provider_metrics: false,
             cross_block_cache_size: DEFAULT_CROSS_BLOCK_CACHE_SIZE_MB,
             state_root_task_compare_updates: false,
@@ -197,6 +251,12 @@ impl Default for DefaultEngineValues {
             account_worker_count: None,
             disable_proof_v2: false,
             cache_metrics_disabled: false,
+            execution_output_compaction_depth: DEFAULT_EXECUTION_OUTPUT_COMPACTION_DEPTH,
+            max_buffered_blocks: DEFAULT_BLOCK_BUFFER_LIMIT,
+            head_watchdog_enabled: false,
+            head_watchdog_stall_threshold: DEFAULT_HEAD_WATCHDOG_STALL_THRESHOLD.as_secs(),
+            head_watchdog_escalation_interval: DEFAULT_HEAD_WATCHDOG_ESCALATION_INTERVAL.as_secs(),
+            head_watchdog_min_peer_lead: DEFAULT_HEAD_WATCHDOG_MIN_PEER_LEAD,
         }
     }
 }
@@ -236,6 +296,11 @@ pub struct EngineArgs {
     #[arg(long = "engine.disable-prewarming", alias = "engine.disable-caching-and-prewarming", default_value_t = DefaultEngineValues::get_global().prewarming_disabled)]
     pub prewarming_disabled: bool,
 
+    /// Disable prefetching the accounts, storage slots and trie nodes touched by the access lists
+    /// and recipients of transactions ahead of execution
+    #[arg(long = "engine.disable-access-list-prefetch", default_value_t = DefaultEngineValues::get_global().access_list_prefetch_disabled)]
+    pub access_list_prefetch_disabled: bool,
+
     /// CAUTION: This CLI flag has no effect anymore. The parallel sparse trie is always enabled.
     #[deprecated]
     #[arg(long = "engine.parallel-sparse-trie", default_value = "true", hide = true)]
@@ -324,6 +389,39 @@ pub struct EngineArgs {
     /// Disable cache metrics recording, which can take up to 50ms with large cached state.
     #[arg(long = "engine.disable-cache-metrics", default_value_t = DefaultEngineValues::get_global().cache_metrics_disabled)]
     pub cache_metrics_disabled: bool,
+
+    /// Configure how many blocks below the canonical head the execution outputs of in-memory
+    /// blocks are compacted. Compacted outputs hold the same values, but use less memory.
+    #[arg(long = "engine.execution-output-compaction-depth", default_value_t = DefaultEngineValues::get_global().execution_output_compaction_depth)]
+    pub execution_output_compaction_depth: u64,
+
+    /// Configure the maximum number of blocks with unknown parents that are buffered until they
+    /// can be connected. If the buffer is full, the blocks with the lowest numbers are evicted
+    /// first, except for the head of the latest forkchoice update.
+    #[arg(long = "engine.max-buffered-blocks", default_value_t = DefaultEngineValues::get_global().max_buffered_blocks)]
+    pub max_buffered_blocks: u32,
+
+    /// Enable the watchdog that recovers a stalled canonical head.
+    ///
+    /// If the canonical head doesn't advance while peers advertise higher blocks, the watchdog
+    /// requests the forkchoice sync target again, then restarts the sync of the engine and
+    /// finally triggers a backfill sync, until the head advances.
+    #[arg(long = "engine.head-watchdog", default_value_t = DefaultEngineValues::get_global().head_watchdog_enabled)]
+    pub head_watchdog_enabled: bool,
+
+    /// Configure the number of seconds without canonical head progress after which the head
+    /// watchdog considers the head stalled.
+    #[arg(long = "engine.head-watchdog.stall-threshold", value_name = "SECONDS", default_value_t = DefaultEngineValues::get_global().head_watchdog_stall_threshold)]
+    pub head_watchdog_stall_threshold: u64,
+
+    /// Configure the number of seconds between escalating recovery actions of the head watchdog.
+    #[arg(long = "engine.head-watchdog.escalation-interval", value_name = "SECONDS", default_value_t = DefaultEngineValues::get_global().head_watchdog_escalation_interval)]
+    pub head_watchdog_escalation_interval: u64,
+
+    /// Configure the number of blocks peers must advertise ahead of the canonical head for the
+    /// head watchdog to consider the head stalled.
+    #[arg(long = "engine.head-watchdog.min-peer-lead", value_name = "BLOCKS", default_value_t = DefaultEngineValues::get_global().head_watchdog_min_peer_lead)]
+    pub head_watchdog_min_peer_lead: u64,
 }
 
 #[allow(deprecated)]
@@ -335,6 +433,7 @@ impl Default for EngineArgs {
             legacy_state_root_task_enabled,
             state_cache_disabled,
             prewarming_disabled,
+            access_list_prefetch_disabled,
             state_provider_metrics,
             cross_block_cache_size,
             state_root_task_compare_updates,
@@ -350,6 +449,12 @@ impl Default for EngineArgs {
             account_worker_count,
             disable_proof_v2,
             cache_metrics_disabled,
+            execution_output_compaction_depth,
+            max_buffered_blocks,
+            head_watchdog_enabled,
+            head_watchdog_stall_threshold,
+            head_watchdog_escalation_interval,
+            head_watchdog_min_peer_lead,
         } = DefaultEngineValues::get_global().clone();
         Self {
             persistence_threshold,
@@ -359,6 +464,7 @@ impl Default for EngineArgs {
             caching_and_prewarming_enabled: true,
             state_cache_disabled,
             prewarming_disabled,
+            access_list_prefetch_disabled,
             parallel_sparse_trie_enabled: true,
             parallel_sparse_trie_disabled: false,
             state_provider_metrics,
@@ -376,6 +482,12 @@ impl Default for EngineArgs {
             account_worker_count,
             disable_proof_v2,
             cache_metrics_disabled,
+            execution_output_compaction_depth,
+            max_buffered_blocks,
+            head_watchdog_enabled,
+            head_watchdog_stall_threshold,
+            head_watchdog_escalation_interval,
+            head_watchdog_min_peer_lead,
         }
     }
 }
@@ -389,6 +501,7 @@ impl EngineArgs {
             .with_legacy_state_root(self.legacy_state_root_task_enabled)
             .without_state_cache(self.state_cache_disabled)
             .without_prewarming(self.prewarming_disabled)
+            .without_access_list_prefetch(self.access_list_prefetch_disabled)
             .with_state_provider_metrics(self.state_provider_metrics)
             .with_always_compare_trie_updates(self.state_root_task_compare_updates)
             .with_cross_block_cache_size(self.cross_block_cache_size * 1024 * 1024)
@@ -412,9 +525,26 @@ impl EngineArgs {
 
         config = config.with_disable_proof_v2(self.disable_proof_v2);
         config = config.without_cache_metrics(self.cache_metrics_disabled);
+        config =
+            config.with_execution_output_compaction_depth(self.execution_output_compaction_depth);
+        config = config.with_block_buffer_limit(self.max_buffered_blocks);
 
         config
     }
+
+    /// Creates a [`HeadWatchdogConfig`] from the engine arguments, if the head watchdog is
+    /// enabled.
+    pub const fn head_watchdog_config(&self) -> Option<HeadWatchdogConfig> {
+        if !self.head_watchdog_enabled {
+            return None
+        }
+
+        Some(HeadWatchdogConfig {
+            stall_threshold: Duration::from_secs(self.head_watchdog_stall_threshold),
+            escalation_interval: Duration::from_secs(self.head_watchdog_escalation_interval),
+            min_peer_lead: self.head_watchdog_min_peer_lead,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -446,6 +576,7 @@ mod tests {
             caching_and_prewarming_enabled: true,
             state_cache_disabled: true,
             prewarming_disabled: true,
+            access_list_prefetch_disabled: true,
             parallel_sparse_trie_enabled: true,
             parallel_sparse_trie_disabled: false,
             state_provider_metrics: true,
@@ -464,6 +595,12 @@ mod tests {
             account_worker_count: Some(8),
             disable_proof_v2: false,
             cache_metrics_disabled: true,
+            execution_output_compaction_depth: 32,
+            max_buffered_blocks: 128,
+            head_watchdog_enabled: true,
+            head_watchdog_stall_threshold: 60,
+            head_watchdog_escalation_interval: 30,
+            head_watchdog_min_peer_lead: 4,
         };
 
         let parsed_args = CommandParser::<EngineArgs>::parse_from([
@@ -475,6 +612,7 @@ mod tests {
             "--engine.legacy-state-root",
             "--engine.disable-state-cache",
             "--engine.disable-prewarming",
+            "--engine.disable-access-list-prefetch",
             "--engine.state-provider-metrics",
             "--engine.cross-block-cache-size",
             "256",
@@ -494,6 +632,17 @@ mod tests {
             "--engine.account-worker-count",
             "8",
             "--engine.disable-cache-metrics",
+            "--engine.execution-output-compaction-depth",
+            "32",
+            "--engine.max-buffered-blocks",
+            "128",
+            "--engine.head-watchdog",
+            "--engine.head-watchdog.stall-threshold",
+            "60",
+            "--engine.head-watchdog.escalation-interval",
+            "30",
+            "--engine.head-watchdog.min-peer-lead",
+            "4",
         ])
         .args;
 
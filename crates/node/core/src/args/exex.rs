@@ -0,0 +1,38 @@
+//! clap [Args](clap::Args) for execution extensions (`ExEx`).
+
+use clap::Args;
+use std::path::PathBuf;
+
+/// Parameters for execution extensions (`ExEx`).
+#[derive(Clone, Debug, Default, PartialEq, Eq, Args)]
+pub struct ExExArgs {
+    /// The path to a directory of WASM `ExEx` modules that are installed on startup.
+    ///
+    /// Every `.wasm` file in the directory is installed as an `ExEx`, with the file name as its
+    /// id. Requires a build with the `wasm-exex` feature.
+    #[arg(long = "exex.wasm-dir", value_name = "PATH", verbatim_doc_comment)]
+    pub wasm_dir: Option<PathBuf>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn test_parse_exex_args() {
+        let args = CommandParser::<ExExArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, ExExArgs::default());
+
+        let args =
+            CommandParser::<ExExArgs>::parse_from(["reth", "--exex.wasm-dir", "/tmp/exex"]).args;
+        assert_eq!(args.wasm_dir, Some(PathBuf::from("/tmp/exex")));
+    }
+}
@@ -89,6 +89,13 @@ pub struct LogArgs {
     )]
     pub tracy_filter: String,
 
+    /// Serve the task instrumentation of the tokio runtime to `tokio-console`.
+    ///
+    /// Requires the `tokio-console` feature and building with `RUSTFLAGS="--cfg tokio_unstable"`.
+    /// The server listens on `TOKIO_CONSOLE_BIND`, `127.0.0.1:6669` by default.
+    #[arg(long = "debug.tokio-console", global = true)]
+    pub tokio_console: bool,
+
     /// Sets whether or not the formatter emits ANSI terminal escape codes for colors and other
     /// text formatting.
     #[arg(
@@ -168,6 +175,15 @@ impl LogArgs {
             tracer = tracer.with_tracy(config);
         }
 
+        if self.tokio_console {
+            #[cfg(feature = "tokio-console")]
+            {
+                tracer = tracer.with_tokio_console();
+            }
+            #[cfg(not(feature = "tokio-console"))]
+            eyre::bail!("--debug.tokio-console requires the `tokio-console` feature");
+        }
+
         let guard = tracer.init_with_layers(layers)?;
         Ok(guard)
     }
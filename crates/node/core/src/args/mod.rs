@@ -54,7 +54,7 @@ pub use dev::DevArgs;
 
 /// PruneArgs for configuring the pruning and full node
 mod pruning;
-pub use pruning::{DefaultPruningValues, PruningArgs};
+pub use pruning::{DefaultPruningValues, PruningArgs, DEFAULT_REORG_HISTORY_LIMIT};
 
 /// DatadirArgs for configuring data storage paths
 mod datadir_args;
@@ -76,6 +76,10 @@ pub use ress_args::RessArgs;
 mod era;
 pub use era::{DefaultEraHost, EraArgs, EraSourceArgs};
 
+/// `ExExArgs` for configuring execution extensions.
+mod exex;
+pub use exex::ExExArgs;
+
 /// `StaticFilesArgs` for configuring static files.
 mod static_files;
 pub use static_files::{StaticFilesArgs, MINIMAL_BLOCKS_PER_FILE};
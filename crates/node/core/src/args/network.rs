@@ -1,11 +1,12 @@
 //! clap [Args](clap::Args) for network related arguments.
 
 use alloy_eips::BlockNumHash;
-use alloy_primitives::B256;
+use alloy_primitives::{Bytes, B256};
+use alloy_rlp::Encodable;
 use std::{
     net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
     ops::Not,
-    path::PathBuf,
+    path::{Path, PathBuf},
 };
 
 use crate::version::version_metadata;
@@ -43,6 +44,9 @@ use secp256k1::SecretKey;
 use std::str::FromStr;
 use tracing::error;
 
+/// The default file name of the persistent peer reputations file.
+pub const DEFAULT_REPUTATIONS_FILE_NAME: &str = "peer-reputations.json";
+
 /// Parameters for configuring the network more granularity via CLI
 #[derive(Debug, Clone, Args, PartialEq, Eq)]
 #[command(next_help_heading = "Networking")]
@@ -77,6 +81,22 @@ pub struct NetworkArgs {
     #[arg(long, value_name = "FILE", verbatim_doc_comment, conflicts_with = "no_persist_peers")]
     pub peers_file: Option<PathBuf>,
 
+    /// The path to the peer reputations file. Peer reputations and bans are dumped to this file
+    /// on node shutdown, and read on startup. Defaults to `peer-reputations.json` next to the
+    /// known peers file. Cannot be used with `--no-persist-peers`.
+    #[arg(long, value_name = "FILE", verbatim_doc_comment, conflicts_with = "no_persist_peers")]
+    pub reputations_file: Option<PathBuf>,
+
+    /// Import banned peers from the given file on startup.
+    ///
+    /// The file is expected to be in the format written by `--banlist-export`, and must exist.
+    #[arg(long = "banlist-import", value_name = "FILE", value_parser = parse_existing_file)]
+    pub banlist_import: Option<PathBuf>,
+
+    /// Export all banned peers to the given file on node shutdown.
+    #[arg(long = "banlist-export", value_name = "FILE")]
+    pub banlist_export: Option<PathBuf>,
+
     /// Custom node identity
     #[arg(long, value_name = "IDENTITY", default_value = version_metadata().p2p_client_version.as_ref())]
     pub identity: String,
@@ -227,6 +247,12 @@ pub struct NetworkArgs {
     /// Example: --netrestrict "192.168.0.0/16,10.0.0.0/8"
     #[arg(long, value_name = "NETRESTRICT")]
     pub netrestrict: Option<String>,
+
+    /// Additional kv-pairs of the local ENR, with rlp encoded values.
+    ///
+    /// These can't be set from the command line, see [`Self::add_enr_kv_pair`].
+    #[arg(skip)]
+    pub enr_kv_pairs: Vec<(&'static [u8], Bytes)>,
 }
 
 impl NetworkArgs {
@@ -324,13 +350,23 @@ impl NetworkArgs {
             .resolved_bootnodes()
             .unwrap_or_else(|| chain_spec.bootnodes().unwrap_or_else(mainnet_nodes));
         let peers_file = self.peers_file.clone().unwrap_or(default_peers_file);
+        let reputations_file = self.persistent_reputations_file(&peers_file);
 
         // Configure peer connections
         let ip_filter = self.ip_filter().unwrap_or_default();
-        let peers_config = config
-            .peers_config_with_basic_nodes_from_file(
-                self.persistent_peers_file(peers_file).as_deref(),
-            )
+        let peers_config = config.peers_config_with_basic_nodes_from_file(
+            self.persistent_peers_file(peers_file).as_deref(),
+        );
+        let peers_config = peers_config
+            .clone()
+            .with_persisted_reputations_from_file(reputations_file.as_deref())
+            .and_then(|config| {
+                config.with_persisted_reputations_from_file(self.banlist_import.as_deref())
+            })
+            .unwrap_or_else(|err| {
+                error!(target: "reth::cli", %err, "Failed to load persisted peer reputations");
+                peers_config
+            })
             .with_max_inbound_opt(self.resolved_max_inbound_peers())
             .with_max_outbound_opt(self.resolved_max_outbound_peers())
             .with_ip_filter(ip_filter);
@@ -370,6 +406,18 @@ impl NetworkArgs {
             .disable_tx_gossip(self.disable_tx_gossip)
             .required_block_hashes(self.required_block_hashes.clone())
             .network_id(self.network_id)
+            .apply(|mut builder| {
+                for (key, rlp) in &self.enr_kv_pairs {
+                    builder = builder.add_enr_rlp_pair(*key, rlp.clone());
+                }
+                builder
+            })
+    }
+
+    /// Adds an additional kv-pair to the local ENR, e.g. to advertise features to peers of
+    /// networks that use ENR based feature discovery.
+    pub fn add_enr_kv_pair(&mut self, key: &'static [u8], value: impl Encodable) {
+        self.enr_kv_pairs.push((key, alloy_rlp::encode(value).into()));
     }
 
     /// If `no_persist_peers` is false then this returns the path to the persistent peers file path.
@@ -377,6 +425,18 @@ impl NetworkArgs {
         self.no_persist_peers.not().then_some(peers_file)
     }
 
+    /// If `no_persist_peers` is false then this returns the path to the persistent peer
+    /// reputations file.
+    ///
+    /// Unless configured explicitly, the file is located next to the given known `peers_file`.
+    pub fn persistent_reputations_file(&self, peers_file: &Path) -> Option<PathBuf> {
+        self.no_persist_peers.not().then(|| {
+            self.reputations_file
+                .clone()
+                .unwrap_or_else(|| peers_file.with_file_name(DEFAULT_REPUTATIONS_FILE_NAME))
+        })
+    }
+
     /// Configures the [`DiscoveryArgs`].
     pub const fn with_discovery(mut self, discovery: DiscoveryArgs) -> Self {
         self.discovery = discovery;
@@ -465,6 +525,9 @@ impl Default for NetworkArgs {
             bootnodes: None,
             dns_retries: 0,
             peers_file: None,
+            reputations_file: None,
+            banlist_import: None,
+            banlist_export: None,
             identity: version_metadata().p2p_client_version.to_string(),
             p2p_secret_key: None,
             p2p_secret_key_hex: None,
@@ -491,6 +554,7 @@ impl Default for NetworkArgs {
             required_block_hashes: vec![],
             network_id: None,
             netrestrict: None,
+            enr_kv_pairs: Vec::new(),
         }
     }
 }
@@ -563,6 +627,19 @@ pub struct DiscoveryArgs {
     #[arg(id = "discovery.v5.bootstrap.lookup-countdown", long = "discovery.v5.bootstrap.lookup-countdown", value_name = "DISCOVERY_V5_BOOTSTRAP_LOOKUP_COUNTDOWN",
         default_value_t = DEFAULT_COUNT_BOOTSTRAP_LOOKUPS)]
     pub discv5_bootstrap_lookup_countdown: u64,
+
+    /// Comma separated topics to advertise in the discv5 node record, scoped to the current fork.
+    ///
+    /// Discovered peers that don't advertise at least one of the topics are dropped, and lookup
+    /// queries only look for peers that do. Helps nodes of isolated testnets find each other.
+    /// Enables discv5.
+    #[arg(
+        id = "discovery.topics",
+        long = "discovery.topics",
+        value_name = "TOPICS",
+        value_delimiter = ','
+    )]
+    pub discv5_topics: Vec<String>,
 }
 
 impl DiscoveryArgs {
@@ -611,6 +688,7 @@ impl DiscoveryArgs {
             discv5_lookup_interval,
             discv5_bootstrap_lookup_interval,
             discv5_bootstrap_lookup_countdown,
+            discv5_topics,
             ..
         } = self;
 
@@ -636,6 +714,7 @@ impl DiscoveryArgs {
             .lookup_interval(*discv5_lookup_interval)
             .bootstrap_lookup_interval(*discv5_bootstrap_lookup_interval)
             .bootstrap_lookup_countdown(*discv5_bootstrap_lookup_countdown)
+            .topics(discv5_topics.iter().cloned())
     }
 
     /// Returns true if discv5 discovery should be configured
@@ -646,7 +725,8 @@ impl DiscoveryArgs {
 
         self.enable_discv5_discovery ||
             self.discv5_addr.is_some() ||
-            self.discv5_addr_ipv6.is_some()
+            self.discv5_addr_ipv6.is_some() ||
+            !self.discv5_topics.is_empty()
     }
 
     /// Set the discovery port to zero, to allow the OS to assign a random unused port when
@@ -692,10 +772,20 @@ impl Default for DiscoveryArgs {
             discv5_lookup_interval: DEFAULT_SECONDS_LOOKUP_INTERVAL,
             discv5_bootstrap_lookup_interval: DEFAULT_SECONDS_BOOTSTRAP_LOOKUP_INTERVAL,
             discv5_bootstrap_lookup_countdown: DEFAULT_COUNT_BOOTSTRAP_LOOKUPS,
+            discv5_topics: Vec::new(),
         }
     }
 }
 
+/// Parse a path to a file that must exist
+fn parse_existing_file(s: &str) -> Result<PathBuf, String> {
+    let path = PathBuf::from(s);
+    if !path.is_file() {
+        return Err(format!("File not found: {}", path.display()))
+    }
+    Ok(path)
+}
+
 /// Parse a block number=hash pair or just a hash into `BlockNumHash`
 fn parse_block_num_hash(s: &str) -> Result<BlockNumHash, String> {
     if let Some((num_str, hash_str)) = s.split_once('=') {
@@ -928,6 +1018,30 @@ mod tests {
         assert!(args.required_block_hashes.is_empty());
     }
 
+    #[test]
+    fn parse_discovery_topics() {
+        let args =
+            CommandParser::<NetworkArgs>::parse_from(["reth", "--discovery.topics", "devnet,ops"])
+                .args;
+        assert_eq!(args.discovery.discv5_topics, vec!["devnet".to_string(), "ops".to_string()]);
+        assert!(args.discovery.should_enable_discv5());
+    }
+
+    #[test]
+    fn parse_banlist_import_requires_existing_file() {
+        let unique = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos();
+        let banlist = std::env::temp_dir().join(format!("reth_banlist_test_{unique}.json"));
+
+        let args = ["reth", "--banlist-import", banlist.to_str().unwrap()];
+        assert!(CommandParser::<NetworkArgs>::try_parse_from(args).is_err());
+
+        fs::write(&banlist, "[]").expect("write banlist file");
+        let parsed = CommandParser::<NetworkArgs>::try_parse_from(args).unwrap().args;
+        assert_eq!(parsed.banlist_import, Some(banlist.clone()));
+
+        let _ = fs::remove_file(&banlist);
+    }
+
     #[test]
     fn test_parse_block_num_hash() {
         // Test hash only format
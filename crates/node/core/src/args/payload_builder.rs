@@ -1,5 +1,6 @@
 use crate::{cli::config::PayloadBuilderConfig, version::default_extra_data};
 use alloy_consensus::constants::MAXIMUM_EXTRA_DATA_SIZE;
+use alloy_primitives::Address;
 use clap::{
     builder::{RangedU64ValueParser, TypedValueParser},
     Arg, Args, Command,
@@ -8,7 +9,7 @@ use reth_cli_util::{
     parse_duration_from_secs, parse_duration_from_secs_or_ms,
     parsers::format_duration_as_secs_or_ms,
 };
-use std::{borrow::Cow, ffi::OsStr, sync::OnceLock, time::Duration};
+use std::{borrow::Cow, ffi::OsStr, ops::Range, sync::OnceLock, time::Duration};
 
 /// Global static payload builder defaults
 static PAYLOAD_BUILDER_DEFAULTS: OnceLock<DefaultPayloadBuilderValues> = OnceLock::new();
@@ -24,6 +25,8 @@ pub struct DefaultPayloadBuilderValues {
     interval: String,
     /// Default deadline for payload builds in seconds
     deadline: String,
+    /// Default time to wait for an interrupted payload build when the payload is resolved
+    interrupt_timeout: String,
     /// Default maximum number of concurrent payload building tasks
     max_payload_tasks: usize,
 }
@@ -57,6 +60,12 @@ impl DefaultPayloadBuilderValues {
         self
     }
 
+    /// Set the default time to wait for an interrupted payload build
+    pub fn with_interrupt_timeout(mut self, v: Duration) -> Self {
+        self.interrupt_timeout = format_duration_as_secs_or_ms(v);
+        self
+    }
+
     /// Set the default maximum payload tasks
     pub const fn with_max_payload_tasks(mut self, v: usize) -> Self {
         self.max_payload_tasks = v;
@@ -70,6 +79,7 @@ impl Default for DefaultPayloadBuilderValues {
             extra_data: default_extra_data(),
             interval: "1".to_string(),
             deadline: "12".to_string(),
+            interrupt_timeout: "0".to_string(),
             max_payload_tasks: 3,
         }
     }
@@ -88,7 +98,15 @@ pub struct PayloadBuilderArgs {
     pub extra_data: String,
 
     /// Target gas limit for built blocks.
-    #[arg(long = "builder.gaslimit", alias = "miner.gaslimit", value_name = "GAS_LIMIT")]
+    ///
+    /// The gas limit of built blocks moves towards the target by at most the change allowed by the
+    /// protocol per block, 1/1024 of the parent gas limit.
+    #[arg(
+        long = "builder.gaslimit",
+        alias = "miner.gaslimit",
+        visible_alias = "builder.gaslimit-target",
+        value_name = "GAS_LIMIT"
+    )]
     pub gas_limit: Option<u64>,
 
     /// The interval at which the job should build a new payload after the last.
@@ -113,6 +131,22 @@ pub struct PayloadBuilderArgs {
     )]
     pub deadline: Duration,
 
+    /// The maximum time to wait for the payload build that is in progress when the payload is
+    /// requested.
+    ///
+    /// The build is interrupted, it stops adding transactions and seals the payload, which is
+    /// returned if it pays more fees than the best payload built so far. If it isn't sealed in
+    /// time, the best payload is returned. By default the best payload is returned right away.
+    ///
+    /// Timeout is specified in seconds or in milliseconds if the value ends with `ms`.
+    #[arg(
+        long = "builder.interrupt-timeout",
+        value_parser = parse_duration_from_secs_or_ms,
+        default_value = DefaultPayloadBuilderValues::get_global().interrupt_timeout.as_str(),
+        value_name = "DURATION"
+    )]
+    pub interrupt_timeout: Duration,
+
     /// Maximum number of tasks to spawn for building a payload.
     #[arg(
         long = "builder.max-tasks",
@@ -124,6 +158,29 @@ pub struct PayloadBuilderArgs {
     /// Maximum number of blobs to include per block.
     #[arg(long = "builder.max-blobs", value_name = "COUNT")]
     pub max_blobs_per_block: Option<u64>,
+
+    /// Overrides the fee recipient suggested by the CL in the payload attributes, e.g. of a
+    /// proposer, with another fee recipient.
+    ///
+    /// Specified as `<SUGGESTED>=<RECIPIENT>`, can be repeated.
+    #[arg(
+        long = "builder.fee-recipient-override",
+        value_parser = parse_fee_recipient_override,
+        value_name = "SUGGESTED=RECIPIENT"
+    )]
+    pub fee_recipient_overrides: Vec<(Address, Address)>,
+
+    /// Sets the fee recipient of the blocks with a timestamp in the given window, regardless of
+    /// the fee recipient suggested by the CL.
+    ///
+    /// Specified as `<START>..<END>=<RECIPIENT>`, with the window given as unix timestamps in
+    /// seconds, end exclusive. Can be repeated, windows must not overlap.
+    #[arg(
+        long = "builder.fee-recipient-window",
+        value_parser = parse_fee_recipient_window,
+        value_name = "START..END=RECIPIENT"
+    )]
+    pub fee_recipient_windows: Vec<(Range<u64>, Address)>,
 }
 
 impl Default for PayloadBuilderArgs {
@@ -134,8 +191,12 @@ impl Default for PayloadBuilderArgs {
             interval: parse_duration_from_secs_or_ms(defaults.interval.as_str()).unwrap(),
             gas_limit: None,
             deadline: Duration::from_secs(defaults.deadline.parse().unwrap()),
+            interrupt_timeout: parse_duration_from_secs_or_ms(defaults.interrupt_timeout.as_str())
+                .unwrap(),
             max_payload_tasks: defaults.max_payload_tasks,
             max_blobs_per_block: None,
+            fee_recipient_overrides: Vec::new(),
+            fee_recipient_windows: Vec::new(),
         }
     }
 }
@@ -164,6 +225,38 @@ impl PayloadBuilderConfig for PayloadBuilderArgs {
     fn max_blobs_per_block(&self) -> Option<u64> {
         self.max_blobs_per_block
     }
+
+    fn fee_recipient_overrides(&self) -> &[(Address, Address)] {
+        &self.fee_recipient_overrides
+    }
+
+    fn fee_recipient_windows(&self) -> &[(Range<u64>, Address)] {
+        &self.fee_recipient_windows
+    }
+}
+
+/// Parses a fee recipient override specified as `<SUGGESTED>=<RECIPIENT>`.
+fn parse_fee_recipient_override(value: &str) -> Result<(Address, Address), String> {
+    let (suggested, recipient) =
+        value.split_once('=').ok_or_else(|| format!("expected SUGGESTED=RECIPIENT: {value}"))?;
+    let suggested =
+        suggested.parse().map_err(|err| format!("invalid address {suggested}: {err}"))?;
+    let recipient =
+        recipient.parse().map_err(|err| format!("invalid address {recipient}: {err}"))?;
+    Ok((suggested, recipient))
+}
+
+/// Parses a fee recipient window specified as `<START>..<END>=<RECIPIENT>`.
+fn parse_fee_recipient_window(value: &str) -> Result<(Range<u64>, Address), String> {
+    let (window, recipient) =
+        value.split_once('=').ok_or_else(|| format!("expected START..END=RECIPIENT: {value}"))?;
+    let (start, end) =
+        window.split_once("..").ok_or_else(|| format!("expected START..END: {window}"))?;
+    let start = start.parse().map_err(|err| format!("invalid timestamp {start}: {err}"))?;
+    let end = end.parse().map_err(|err| format!("invalid timestamp {end}: {err}"))?;
+    let recipient =
+        recipient.parse().map_err(|err| format!("invalid address {recipient}: {err}"))?;
+    Ok((start..end, recipient))
 }
 
 #[derive(Clone, Debug, Default)]
@@ -223,6 +316,42 @@ mod tests {
         .is_err());
     }
 
+    #[test]
+    fn test_gas_limit_aliases() {
+        for flag in ["--builder.gaslimit", "--builder.gaslimit-target", "--miner.gaslimit"] {
+            let args =
+                CommandParser::<PayloadBuilderArgs>::parse_from(["reth", flag, "60000000"]).args;
+            assert_eq!(args.gas_limit, Some(60_000_000));
+        }
+    }
+
+    #[test]
+    fn test_fee_recipient_overrides() {
+        let args = CommandParser::<PayloadBuilderArgs>::parse_from([
+            "reth",
+            "--builder.fee-recipient-override",
+            "0x0000000000000000000000000000000000000001=0x0000000000000000000000000000000000000002",
+            "--builder.fee-recipient-window",
+            "1700000000..1700003600=0x0000000000000000000000000000000000000003",
+        ])
+        .args;
+        assert_eq!(
+            args.fee_recipient_overrides,
+            [(Address::with_last_byte(1), Address::with_last_byte(2))]
+        );
+        assert_eq!(
+            args.fee_recipient_windows,
+            [(1_700_000_000..1_700_003_600, Address::with_last_byte(3))]
+        );
+
+        assert!(CommandParser::<PayloadBuilderArgs>::try_parse_from([
+            "reth",
+            "--builder.fee-recipient-window",
+            "1700000000=0x0000000000000000000000000000000000000003",
+        ])
+        .is_err());
+    }
+
     #[test]
     fn test_default_extra_data() {
         let extra_data = default_extra_data();
@@ -268,4 +397,18 @@ mod tests {
                 .args;
         assert_eq!(args.interval, Duration::from_millis(50));
     }
+
+    #[test]
+    fn test_args_with_interrupt_timeout() {
+        let args = CommandParser::<PayloadBuilderArgs>::parse_from(["reth"]).args;
+        assert_eq!(args.interrupt_timeout, Duration::ZERO);
+
+        let args = CommandParser::<PayloadBuilderArgs>::parse_from([
+            "reth",
+            "--builder.interrupt-timeout",
+            "50ms",
+        ])
+        .args;
+        assert_eq!(args.interrupt_timeout, Duration::from_millis(50));
+    }
 }
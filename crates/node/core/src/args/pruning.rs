@@ -3,10 +3,14 @@
 use crate::{args::error::ReceiptsLogError, primitives::EthereumHardfork};
 use alloy_primitives::{Address, BlockNumber};
 use clap::{builder::RangedU64ValueParser, Args};
+use humantime::parse_duration;
 use reth_chainspec::EthereumHardforks;
 use reth_config::config::PruneConfig;
 use reth_prune_types::{PruneMode, PruneModes, ReceiptsLogPruneConfig, MINIMUM_PRUNING_DISTANCE};
-use std::{collections::BTreeMap, ops::Not, sync::OnceLock};
+use std::{collections::BTreeMap, ops::Not, sync::OnceLock, time::Duration};
+
+/// Default number of reorgs kept in the reorg history.
+pub const DEFAULT_REORG_HISTORY_LIMIT: usize = 1_000;
 
 /// Global static pruning defaults
 static PRUNING_DEFAULTS: OnceLock<DefaultPruningValues> = OnceLock::new();
@@ -138,22 +142,26 @@ pub struct PruningArgs {
 
     // Receipts
     /// Prunes all receipt data.
-    #[arg(long = "prune.receipts.full", conflicts_with_all = &["receipts_pre_merge", "receipts_distance", "receipts_before"])]
+    #[arg(long = "prune.receipts.full", conflicts_with_all = &["receipts_pre_merge", "receipts_distance", "receipts_before", "receipts_older_than"])]
     pub receipts_full: bool,
     /// Prune receipts before the merge block.
-    #[arg(long = "prune.receipts.pre-merge", conflicts_with_all = &["receipts_full", "receipts_distance", "receipts_before"])]
+    #[arg(long = "prune.receipts.pre-merge", conflicts_with_all = &["receipts_full", "receipts_distance", "receipts_before", "receipts_older_than"])]
     pub receipts_pre_merge: bool,
     /// Prune receipts before the `head-N` block number. In other words, keep last N + 1 blocks.
-    #[arg(long = "prune.receipts.distance", value_name = "BLOCKS", conflicts_with_all = &["receipts_full", "receipts_pre_merge", "receipts_before"])]
+    #[arg(long = "prune.receipts.distance", value_name = "BLOCKS", conflicts_with_all = &["receipts_full", "receipts_pre_merge", "receipts_before", "receipts_older_than"])]
     pub receipts_distance: Option<u64>,
     /// Prune receipts before the specified block number. The specified block number is not pruned.
-    #[arg(long = "prune.receipts.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["receipts_full", "receipts_pre_merge", "receipts_distance"])]
+    #[arg(long = "prune.receipts.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["receipts_full", "receipts_pre_merge", "receipts_distance", "receipts_older_than"])]
     pub receipts_before: Option<BlockNumber>,
+    /// Prune receipts of blocks older than the specified duration, relative to the tip block
+    /// timestamp, e.g. `365days`.
+    #[arg(long = "prune.receipts.older-than", value_name = "DURATION", value_parser = parse_duration, conflicts_with_all = &["receipts_full", "receipts_pre_merge", "receipts_distance", "receipts_before"])]
+    pub receipts_older_than: Option<Duration>,
     // Receipts Log Filter
     /// Configure receipts log filter. Format:
     /// <`address`>:<`prune_mode`>... where <`prune_mode`> can be 'full', 'distance:<`blocks`>', or
     /// 'before:<`block_number`>'
-    #[arg(long = "prune.receiptslogfilter", value_name = "FILTER_CONFIG", conflicts_with_all = &["receipts_full", "receipts_pre_merge", "receipts_distance",  "receipts_before"], value_parser = parse_receipts_log_filter)]
+    #[arg(long = "prune.receiptslogfilter", value_name = "FILTER_CONFIG", conflicts_with_all = &["receipts_full", "receipts_pre_merge", "receipts_distance",  "receipts_before", "receipts_older_than"], value_parser = parse_receipts_log_filter)]
     pub receipts_log_filter: Option<ReceiptsLogPruneConfig>,
 
     // Account History
@@ -183,19 +191,37 @@ pub struct PruningArgs {
 
     // Bodies
     /// Prune bodies before the merge block.
-    #[arg(long = "prune.bodies.pre-merge", value_name = "BLOCKS", conflicts_with_all = &["bodies_distance", "bodies_before"])]
+    #[arg(long = "prune.bodies.pre-merge", value_name = "BLOCKS", conflicts_with_all = &["bodies_distance", "bodies_before", "bodies_older_than"])]
     pub bodies_pre_merge: bool,
     /// Prune bodies before the `head-N` block number. In other words, keep last N + 1
     /// blocks.
-    #[arg(long = "prune.bodies.distance", value_name = "BLOCKS", conflicts_with_all = &["bodies_pre_merge", "bodies_before"])]
+    #[arg(long = "prune.bodies.distance", value_name = "BLOCKS", conflicts_with_all = &["bodies_pre_merge", "bodies_before", "bodies_older_than"])]
     pub bodies_distance: Option<u64>,
     /// Prune storage history before the specified block number. The specified block number is not
     /// pruned.
-    #[arg(long = "prune.bodies.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["bodies_distance", "bodies_pre_merge"])]
+    #[arg(long = "prune.bodies.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["bodies_distance", "bodies_pre_merge", "bodies_older_than"])]
     pub bodies_before: Option<BlockNumber>,
+    /// Prune bodies of blocks older than the specified duration, relative to the tip block
+    /// timestamp, e.g. `365days`.
+    #[arg(long = "prune.bodies.older-than", value_name = "DURATION", value_parser = parse_duration, conflicts_with_all = &["bodies_distance", "bodies_pre_merge", "bodies_before"])]
+    pub bodies_older_than: Option<Duration>,
+
+    // Reorg history
+    /// Maximum number of reorgs kept in the reorg history. Older entries are pruned.
+    ///
+    /// Set to 0 to disable recording reorgs. Defaults to 1000 entries.
+    #[arg(long = "prune.reorg-history.limit", value_name = "ENTRIES")]
+    pub reorg_history_limit: Option<usize>,
 }
 
 impl PruningArgs {
+    /// Returns the maximum number of reorgs kept in the reorg history, or [`None`] if reorgs
+    /// should not be recorded.
+    pub fn reorg_history_limit(&self) -> Option<usize> {
+        let limit = self.reorg_history_limit.unwrap_or(DEFAULT_REORG_HISTORY_LIMIT);
+        (limit > 0).then_some(limit)
+    }
+
     /// Returns pruning configuration.
     ///
     /// Returns [`None`] if no parameters are specified and default pruning configuration should be
@@ -273,6 +299,8 @@ impl PruningArgs {
                 .map(PruneMode::Before)
         } else if let Some(distance) = self.bodies_distance {
             Some(PruneMode::Distance(distance))
+        } else if let Some(duration) = self.bodies_older_than {
+            Some(PruneMode::OlderThan(duration.as_secs()))
         } else {
             self.bodies_before.map(PruneMode::Before)
         }
@@ -315,6 +343,8 @@ impl PruningArgs {
             Some(PruneMode::Full)
         } else if let Some(distance) = self.receipts_distance {
             Some(PruneMode::Distance(distance))
+        } else if let Some(duration) = self.receipts_older_than {
+            Some(PruneMode::OlderThan(duration.as_secs()))
         } else {
             self.receipts_before.map(PruneMode::Before)
         }
@@ -393,6 +423,7 @@ mod tests {
     use super::*;
     use alloy_primitives::address;
     use clap::Parser;
+    use reth_chainspec::MAINNET;
 
     /// A helper type to parse Args more easily
     #[derive(Parser)]
@@ -417,6 +448,36 @@ mod tests {
         assert_eq!(args.receipts_log_filter, Some(config));
     }
 
+    #[test]
+    fn parse_older_than() {
+        let args = CommandParser::<PruningArgs>::parse_from([
+            "reth",
+            "--prune.receipts.older-than",
+            "365days",
+            "--prune.bodies.older-than",
+            "1h",
+        ])
+        .args;
+        assert_eq!(args.receipts_prune_mode(&*MAINNET), Some(PruneMode::OlderThan(365 * 86400)));
+        assert_eq!(args.bodies_prune_mode(&*MAINNET), Some(PruneMode::OlderThan(3600)));
+    }
+
+    #[test]
+    fn parse_reorg_history_limit() {
+        let args = CommandParser::<PruningArgs>::parse_from(["reth"]).args;
+        assert_eq!(args.reorg_history_limit(), Some(DEFAULT_REORG_HISTORY_LIMIT));
+
+        let args =
+            CommandParser::<PruningArgs>::parse_from(["reth", "--prune.reorg-history.limit", "10"])
+                .args;
+        assert_eq!(args.reorg_history_limit(), Some(10));
+
+        let args =
+            CommandParser::<PruningArgs>::parse_from(["reth", "--prune.reorg-history.limit", "0"])
+                .args;
+        assert_eq!(args.reorg_history_limit(), None);
+    }
+
     #[test]
     fn parse_receiptslogfilter() {
         let default_args = PruningArgs::default();
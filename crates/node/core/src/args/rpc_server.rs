@@ -12,6 +12,7 @@ use clap::{
 };
 use rand::Rng;
 use reth_cli_util::{parse_duration_from_secs_or_ms, parse_ether_value};
+use reth_config::RpcRateLimitConfig;
 use reth_rpc_eth_types::builder::config::PendingBlockKind;
 use reth_rpc_server_types::{constants, RethRpcModule, RpcModuleSelection};
 use std::{
@@ -57,17 +58,21 @@ pub struct DefaultRpcServerArgs {
     http_disable_compression: bool,
     http_api: Option<RpcModuleSelection>,
     http_corsdomain: Option<String>,
+    graphql: bool,
     ws: bool,
     ws_addr: IpAddr,
     ws_port: u16,
     ws_allowed_origins: Option<String>,
     ws_api: Option<RpcModuleSelection>,
+    ws_max_request_size: Option<MaxU32>,
+    ws_max_response_size: Option<MaxU32>,
     ipcdisable: bool,
     ipcpath: String,
     ipc_socket_permissions: Option<String>,
     auth_addr: IpAddr,
     auth_port: u16,
     auth_jwtsecret: Option<PathBuf>,
+    auth_allowlist: Option<PathBuf>,
     auth_ipc: bool,
     auth_ipc_path: String,
     disable_auth_server: bool,
@@ -78,6 +83,9 @@ pub struct DefaultRpcServerArgs {
     rpc_max_connections: MaxU32,
     rpc_max_tracing_requests: usize,
     rpc_max_blocking_io_requests: usize,
+    rpc_debug_workers: usize,
+    rpc_trace_workers: usize,
+    rpc_worker_niceness: i32,
     rpc_max_trace_filter_blocks: u64,
     rpc_max_blocks_per_filter: ZeroAsNoneU64,
     rpc_max_logs_per_response: ZeroAsNoneU64,
@@ -89,6 +97,7 @@ pub struct DefaultRpcServerArgs {
     rpc_proof_permits: usize,
     rpc_pending_block: PendingBlockKind,
     rpc_forwarder: Option<Url>,
+    rpc_served_block: bool,
     builder_disallow: Option<HashSet<Address>>,
     rpc_state_cache: RpcStateCacheArgs,
     gas_price_oracle: GasPriceOracleArgs,
@@ -142,6 +151,12 @@ impl DefaultRpcServerArgs {
         self
     }
 
+    /// Set whether to enable the GraphQL server by default
+    pub const fn with_graphql(mut self, v: bool) -> Self {
+        self.graphql = v;
+        self
+    }
+
     /// Set the default WS enabled state
     pub const fn with_ws(mut self, v: bool) -> Self {
         self.ws = v;
@@ -172,6 +187,18 @@ impl DefaultRpcServerArgs {
         self
     }
 
+    /// Set the default maximum WS request message size
+    pub const fn with_ws_max_request_size(mut self, v: Option<MaxU32>) -> Self {
+        self.ws_max_request_size = v;
+        self
+    }
+
+    /// Set the default maximum WS response message size
+    pub const fn with_ws_max_response_size(mut self, v: Option<MaxU32>) -> Self {
+        self.ws_max_response_size = v;
+        self
+    }
+
     /// Set whether to disable IPC by default
     pub const fn with_ipcdisable(mut self, v: bool) -> Self {
         self.ipcdisable = v;
@@ -208,6 +235,12 @@ impl DefaultRpcServerArgs {
         self
     }
 
+    /// Set the default auth server client allowlist path
+    pub fn with_auth_allowlist(mut self, v: Option<PathBuf>) -> Self {
+        self.auth_allowlist = v;
+        self
+    }
+
     /// Set the default auth IPC enabled state
     pub const fn with_auth_ipc(mut self, v: bool) -> Self {
         self.auth_ipc = v;
@@ -268,6 +301,24 @@ impl DefaultRpcServerArgs {
         self
     }
 
+    /// Set the default number of `debug` namespace workers
+    pub const fn with_rpc_debug_workers(mut self, v: usize) -> Self {
+        self.rpc_debug_workers = v;
+        self
+    }
+
+    /// Set the default number of `trace` namespace workers
+    pub const fn with_rpc_trace_workers(mut self, v: usize) -> Self {
+        self.rpc_trace_workers = v;
+        self
+    }
+
+    /// Set the default niceness of the namespace workers
+    pub const fn with_rpc_worker_niceness(mut self, v: i32) -> Self {
+        self.rpc_worker_niceness = v;
+        self
+    }
+
     /// Set the default max trace filter blocks
     pub const fn with_rpc_max_trace_filter_blocks(mut self, v: u64) -> Self {
         self.rpc_max_trace_filter_blocks = v;
@@ -334,6 +385,12 @@ impl DefaultRpcServerArgs {
         self
     }
 
+    /// Set whether to report the block requests were served against by default
+    pub const fn with_rpc_served_block(mut self, v: bool) -> Self {
+        self.rpc_served_block = v;
+        self
+    }
+
     /// Set the default builder disallow addresses
     pub fn with_builder_disallow(mut self, v: Option<HashSet<Address>>) -> Self {
         self.builder_disallow = v;
@@ -368,17 +425,21 @@ impl Default for DefaultRpcServerArgs {
             http_disable_compression: false,
             http_api: None,
             http_corsdomain: None,
+            graphql: false,
             ws: false,
             ws_addr: Ipv4Addr::LOCALHOST.into(),
             ws_port: constants::DEFAULT_WS_RPC_PORT,
             ws_allowed_origins: None,
             ws_api: None,
+            ws_max_request_size: None,
+            ws_max_response_size: None,
             ipcdisable: false,
             ipcpath: constants::DEFAULT_IPC_ENDPOINT.to_string(),
             ipc_socket_permissions: None,
             auth_addr: Ipv4Addr::LOCALHOST.into(),
             auth_port: constants::DEFAULT_AUTH_PORT,
             auth_jwtsecret: None,
+            auth_allowlist: None,
             auth_ipc: false,
             auth_ipc_path: constants::DEFAULT_ENGINE_API_IPC_ENDPOINT.to_string(),
             disable_auth_server: false,
@@ -389,6 +450,9 @@ impl Default for DefaultRpcServerArgs {
             rpc_max_connections: RPC_DEFAULT_MAX_CONNECTIONS.into(),
             rpc_max_tracing_requests: constants::default_max_tracing_requests(),
             rpc_max_blocking_io_requests: constants::DEFAULT_MAX_BLOCKING_IO_REQUEST,
+            rpc_debug_workers: constants::default_namespace_workers(),
+            rpc_trace_workers: constants::default_namespace_workers(),
+            rpc_worker_niceness: constants::DEFAULT_RPC_WORKER_NICENESS,
             rpc_max_trace_filter_blocks: constants::DEFAULT_MAX_TRACE_FILTER_BLOCKS,
             rpc_max_blocks_per_filter: constants::DEFAULT_MAX_BLOCKS_PER_FILTER.into(),
             rpc_max_logs_per_response: (constants::DEFAULT_MAX_LOGS_PER_RESPONSE as u64).into(),
@@ -400,6 +464,7 @@ impl Default for DefaultRpcServerArgs {
             rpc_proof_permits: constants::DEFAULT_PROOF_PERMITS,
             rpc_pending_block: PendingBlockKind::Full,
             rpc_forwarder: None,
+            rpc_served_block: false,
             builder_disallow: None,
             rpc_state_cache: RpcStateCacheArgs::default(),
             gas_price_oracle: GasPriceOracleArgs::default(),
@@ -437,6 +502,10 @@ pub struct RpcServerArgs {
     #[arg(long = "http.corsdomain", default_value = Resettable::from(DefaultRpcServerArgs::get_global().http_corsdomain.as_ref().map(|v| v.to_string().into())))]
     pub http_corsdomain: Option<String>,
 
+    /// Enable the GraphQL server on the `/graphql` path of the HTTP-RPC server
+    #[arg(long, requires = "http", default_value_t = DefaultRpcServerArgs::get_global().graphql)]
+    pub graphql: bool,
+
     /// Enable the WS-RPC server
     #[arg(long, default_value_t = DefaultRpcServerArgs::get_global().ws)]
     pub ws: bool,
@@ -457,6 +526,20 @@ pub struct RpcServerArgs {
     #[arg(long = "ws.api", value_parser = RpcModuleSelectionValueParser::default(), default_value = Resettable::from(DefaultRpcServerArgs::get_global().ws_api.as_ref().map(|v| v.to_string().into())))]
     pub ws_api: Option<RpcModuleSelection>,
 
+    /// Set the maximum WS request message size in megabytes, overriding `--rpc.max-request-size`.
+    ///
+    /// Only applies if the WS server doesn't share its port with the HTTP server.
+    #[arg(long = "ws.max-request-size", value_name = "MB", default_value = Resettable::from(DefaultRpcServerArgs::get_global().ws_max_request_size.as_ref().map(|v| v.to_string().into())))]
+    pub ws_max_request_size: Option<MaxU32>,
+
+    /// Set the maximum WS response message size in megabytes, overriding
+    /// `--rpc.max-response-size`.
+    ///
+    /// Large subscription notifications, e.g. full blocks, are rejected if they exceed this.
+    /// Only applies if the WS server doesn't share its port with the HTTP server.
+    #[arg(long = "ws.max-response-size", value_name = "MB", default_value = Resettable::from(DefaultRpcServerArgs::get_global().ws_max_response_size.as_ref().map(|v| v.to_string().into())))]
+    pub ws_max_response_size: Option<MaxU32>,
+
     /// Disable the IPC-RPC server
     #[arg(long, default_value_t = DefaultRpcServerArgs::get_global().ipcdisable)]
     pub ipcdisable: bool,
@@ -488,6 +571,14 @@ pub struct RpcServerArgs {
     #[arg(long = "authrpc.jwtsecret", value_name = "PATH", global = true, required = false, default_value = Resettable::from(DefaultRpcServerArgs::get_global().auth_jwtsecret.as_ref().map(|v| v.to_string_lossy().into())))]
     pub auth_jwtsecret: Option<PathBuf>,
 
+    /// Path to a file with the IP addresses or CIDR networks that are allowed to connect to the
+    /// authenticated engine-API RPC server, one per line.
+    ///
+    /// Connections from other addresses are rejected before any request is handled, even if they
+    /// present a valid JWT. The file is reloaded when it changes.
+    #[arg(long = "authrpc.allowlist", value_name = "PATH", default_value = Resettable::from(DefaultRpcServerArgs::get_global().auth_allowlist.as_ref().map(|v| v.to_string_lossy().into())))]
+    pub auth_allowlist: Option<PathBuf>,
+
     /// Enable auth engine API over IPC
     #[arg(long, default_value_t = DefaultRpcServerArgs::get_global().auth_ipc)]
     pub auth_ipc: bool,
@@ -544,6 +635,26 @@ pub struct RpcServerArgs {
     #[arg(long = "rpc.max-blocking-io-requests", alias = "rpc-max-blocking-io-requests", value_name = "COUNT", default_value_t = DefaultRpcServerArgs::get_global().rpc_max_blocking_io_requests)]
     pub rpc_max_blocking_io_requests: usize,
 
+    /// Number of worker threads executing the blocking work of `debug_` calls. (0 = shared
+    /// blocking pool)
+    ///
+    /// `debug_` calls run on their own bounded pool, so heavy tracing can't starve other
+    /// requests, like the `eth_` calls of wallets.
+    #[arg(long = "rpc.debug-workers", value_name = "COUNT", default_value_t = DefaultRpcServerArgs::get_global().rpc_debug_workers)]
+    pub rpc_debug_workers: usize,
+
+    /// Number of worker threads executing the blocking work of `trace_` calls. (0 = shared
+    /// blocking pool)
+    #[arg(long = "rpc.trace-workers", value_name = "COUNT", default_value_t = DefaultRpcServerArgs::get_global().rpc_trace_workers)]
+    pub rpc_trace_workers: usize,
+
+    /// Niceness of the `debug_` and `trace_` worker threads.
+    ///
+    /// Higher values give the workers a lower scheduling priority than the threads serving other
+    /// requests. Only supported on Linux.
+    #[arg(long = "rpc.worker-niceness", value_name = "NICENESS", allow_negative_numbers = true, default_value_t = DefaultRpcServerArgs::get_global().rpc_worker_niceness)]
+    pub rpc_worker_niceness: i32,
+
     /// Maximum number of blocks for `trace_filter` requests.
     #[arg(long = "rpc.max-trace-filter-blocks", alias = "rpc-max-trace-filter-blocks", value_name = "COUNT", default_value_t = DefaultRpcServerArgs::get_global().rpc_max_trace_filter_blocks)]
     pub rpc_max_trace_filter_blocks: u64,
@@ -619,6 +730,17 @@ pub struct RpcServerArgs {
     #[arg(long = "rpc.forwarder", alias = "rpc-forwarder", value_name = "FORWARDER")]
     pub rpc_forwarder: Option<Url>,
 
+    /// Report the block and state requests were served against.
+    ///
+    /// The block is resolved from the block parameter of a call, calls without one are served
+    /// against the canonical head. On every transport, calls of a method prefixed with
+    /// `servedBlock_`, e.g. `servedBlock_eth_getBalance`, return an envelope with the `result` and
+    /// the `servedBlock`. HTTP responses also have the `reth-block-number`, `reth-block-hash`,
+    /// `reth-state-root` and `reth-unpersisted` headers. A block is unpersisted if it's only in
+    /// memory and can still be reorged away.
+    #[arg(long = "rpc.served-block", default_value_t = DefaultRpcServerArgs::get_global().rpc_served_block)]
+    pub rpc_served_block: bool,
+
     /// Path to file containing disallowed addresses, json-encoded list of strings. Block
     /// validation API will reject blocks containing transactions from these addresses.
     #[arg(long = "builder.disallow", value_name = "PATH", value_parser = reth_cli_util::parsers::read_json_from_file::<HashSet<Address>>, default_value = Resettable::from(DefaultRpcServerArgs::get_global().builder_disallow.as_ref().map(|v| format!("{:?}", v).into())))]
@@ -647,6 +769,12 @@ pub struct RpcServerArgs {
     /// transactions from the same sender will also be skipped.
     #[arg(long = "testing.skip-invalid-transactions", default_value_t = false)]
     pub testing_skip_invalid_transactions: bool,
+
+    /// Rate limits and method filters of the http and ws servers.
+    ///
+    /// These are loaded from the `[rpc.rate_limit]` section of the `reth.toml` config.
+    #[arg(skip)]
+    pub rate_limit: RpcRateLimitConfig,
 }
 
 impl RpcServerArgs {
@@ -779,17 +907,21 @@ impl Default for RpcServerArgs {
             http_disable_compression,
             http_api,
             http_corsdomain,
+            graphql,
             ws,
             ws_addr,
             ws_port,
             ws_allowed_origins,
             ws_api,
+            ws_max_request_size,
+            ws_max_response_size,
             ipcdisable,
             ipcpath,
             ipc_socket_permissions,
             auth_addr,
             auth_port,
             auth_jwtsecret,
+            auth_allowlist,
             auth_ipc,
             auth_ipc_path,
             disable_auth_server,
@@ -800,6 +932,9 @@ impl Default for RpcServerArgs {
             rpc_max_connections,
             rpc_max_tracing_requests,
             rpc_max_blocking_io_requests,
+            rpc_debug_workers,
+            rpc_trace_workers,
+            rpc_worker_niceness,
             rpc_max_trace_filter_blocks,
             rpc_max_blocks_per_filter,
             rpc_max_logs_per_response,
@@ -811,6 +946,7 @@ impl Default for RpcServerArgs {
             rpc_proof_permits,
             rpc_pending_block,
             rpc_forwarder,
+            rpc_served_block,
             builder_disallow,
             rpc_state_cache,
             gas_price_oracle,
@@ -823,17 +959,21 @@ impl Default for RpcServerArgs {
             http_disable_compression,
             http_api,
             http_corsdomain,
+            graphql,
             ws,
             ws_addr,
             ws_port,
             ws_allowed_origins,
             ws_api,
+            ws_max_request_size,
+            ws_max_response_size,
             ipcdisable,
             ipcpath,
             ipc_socket_permissions,
             auth_addr,
             auth_port,
             auth_jwtsecret,
+            auth_allowlist,
             auth_ipc,
             auth_ipc_path,
             disable_auth_server,
@@ -844,6 +984,9 @@ impl Default for RpcServerArgs {
             rpc_max_connections,
             rpc_max_tracing_requests,
             rpc_max_blocking_io_requests,
+            rpc_debug_workers,
+            rpc_trace_workers,
+            rpc_worker_niceness,
             rpc_max_trace_filter_blocks,
             rpc_max_blocks_per_filter,
             rpc_max_logs_per_response,
@@ -855,11 +998,13 @@ impl Default for RpcServerArgs {
             rpc_proof_permits,
             rpc_pending_block,
             rpc_forwarder,
+            rpc_served_block,
             builder_disallow,
             rpc_state_cache,
             gas_price_oracle,
             rpc_send_raw_transaction_sync_timeout,
             testing_skip_invalid_transactions: false,
+            rate_limit: Default::default(),
         }
     }
 }
@@ -982,17 +1127,21 @@ mod tests {
             http_disable_compression: false,
             http_api: Some(RpcModuleSelection::try_from_selection(["eth", "admin"]).unwrap()),
             http_corsdomain: Some("*".to_string()),
+            graphql: false,
             ws: true,
             ws_addr: "127.0.0.1".parse().unwrap(),
             ws_port: 8546,
             ws_allowed_origins: Some("*".to_string()),
             ws_api: Some(RpcModuleSelection::try_from_selection(["eth", "admin"]).unwrap()),
+            ws_max_request_size: Some(10u32.into()),
+            ws_max_response_size: Some(320u32.into()),
             ipcdisable: false,
             ipcpath: "reth.ipc".to_string(),
             ipc_socket_permissions: Some("0o666".to_string()),
             auth_addr: "127.0.0.1".parse().unwrap(),
             auth_port: 8551,
             auth_jwtsecret: Some(std::path::PathBuf::from("/tmp/jwt.hex")),
+            auth_allowlist: Some(std::path::PathBuf::from("/tmp/allowlist")),
             auth_ipc: false,
             auth_ipc_path: "engine.ipc".to_string(),
             disable_auth_server: false,
@@ -1008,6 +1157,9 @@ mod tests {
             rpc_max_connections: 500u32.into(),
             rpc_max_tracing_requests: 16,
             rpc_max_blocking_io_requests: 256,
+            rpc_debug_workers: 2,
+            rpc_trace_workers: 2,
+            rpc_worker_niceness: 10,
             rpc_max_trace_filter_blocks: 4000,
             rpc_max_blocks_per_filter: 1000u64.into(),
             rpc_max_logs_per_response: 10000u64.into(),
@@ -1019,6 +1171,7 @@ mod tests {
             rpc_proof_permits: 16,
             rpc_pending_block: PendingBlockKind::Full,
             rpc_forwarder: Some("http://localhost:8545".parse().unwrap()),
+            rpc_served_block: false,
             builder_disallow: None,
             rpc_state_cache: RpcStateCacheArgs {
                 max_blocks: 5000,
@@ -1036,6 +1189,7 @@ mod tests {
             },
             rpc_send_raw_transaction_sync_timeout: std::time::Duration::from_secs(30),
             testing_skip_invalid_transactions: true,
+            rate_limit: Default::default(),
         };
 
         let parsed_args = CommandParser::<RpcServerArgs>::parse_from([
@@ -1058,6 +1212,10 @@ mod tests {
             "*",
             "--ws.api",
             "eth,admin",
+            "--ws.max-request-size",
+            "10",
+            "--ws.max-response-size",
+            "320",
             "--ipcpath",
             "reth.ipc",
             "--ipc.permissions",
@@ -1068,6 +1226,8 @@ mod tests {
             "8551",
             "--authrpc.jwtsecret",
             "/tmp/jwt.hex",
+            "--authrpc.allowlist",
+            "/tmp/allowlist",
             "--auth-ipc.path",
             "engine.ipc",
             "--rpc.jwtsecret",
@@ -1084,6 +1244,12 @@ mod tests {
             "16",
             "--rpc.max-blocking-io-requests",
             "256",
+            "--rpc.debug-workers",
+            "2",
+            "--rpc.trace-workers",
+            "2",
+            "--rpc.worker-niceness",
+            "10",
             "--rpc.max-trace-filter-blocks",
             "4000",
             "--rpc.max-blocks-per-filter",
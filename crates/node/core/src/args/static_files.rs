@@ -54,6 +54,15 @@ pub struct StaticFilesArgs {
     #[arg(long = "static-files.receipts", default_value_t = default_static_file_flag(), action = clap::ArgAction::Set)]
     pub receipts: bool,
 
+    /// Keep receipts of the most recent blocks in the database when receipts are stored in
+    /// static files.
+    ///
+    /// Receipts of the given number of most recent blocks are written to the database, which keeps
+    /// random access and reorg handling cheap. Older finalized receipts are moved to static files.
+    /// Only applies if `--static-files.receipts` is enabled.
+    #[arg(long = "static-files.receipts.hot-blocks", value_name = "BLOCKS")]
+    pub receipts_hot_blocks: Option<u64>,
+
     /// Store transaction senders in static files instead of the database.
     ///
     /// When enabled, transaction senders will be written to static files on disk instead of the
@@ -120,6 +129,7 @@ impl StaticFilesArgs {
                     .or(minimal_blocks_per_file)
                     .or(config.blocks_per_file.storage_change_sets),
             },
+            shared_dir: config.shared_dir,
         }
     }
 
@@ -131,6 +141,7 @@ impl StaticFilesArgs {
         let base = StorageSettings::legacy();
 
         base.with_receipts_in_static_files(self.receipts)
+            .with_receipts_hot_blocks(self.receipts_hot_blocks)
             .with_transaction_senders_in_static_files(self.transaction_senders)
             .with_account_changesets_in_static_files(self.account_changesets)
             .with_storage_changesets_in_static_files(self.storage_changesets)
@@ -147,6 +158,7 @@ impl Default for StaticFilesArgs {
             blocks_per_file_account_change_sets: None,
             blocks_per_file_storage_change_sets: None,
             receipts: default_static_file_flag(),
+            receipts_hot_blocks: None,
             transaction_senders: default_static_file_flag(),
             account_changesets: default_static_file_flag(),
             storage_changesets: default_static_file_flag(),
@@ -41,7 +41,9 @@ pub struct DefaultTxPoolValues {
     minimum_priority_fee: Option<u128>,
     enforced_gas_limit: u64,
     max_tx_gas_limit: Option<u64>,
+    validate_request_fees: bool,
     blob_transaction_price_bump: u128,
+    max_replacements_per_sender: Option<usize>,
     max_tx_input_bytes: usize,
     max_cached_entries: u32,
     no_locals: bool,
@@ -164,12 +166,24 @@ impl DefaultTxPoolValues {
         self
     }
 
+    /// Set whether to validate EIP-7002 and EIP-7251 request fees by default
+    pub const fn with_validate_request_fees(mut self, v: bool) -> Self {
+        self.validate_request_fees = v;
+        self
+    }
+
     /// Set the default blob transaction price bump
     pub const fn with_blob_transaction_price_bump(mut self, v: u128) -> Self {
         self.blob_transaction_price_bump = v;
         self
     }
 
+    /// Set the default max number of replacements per sender per block
+    pub const fn with_max_replacements_per_sender(mut self, v: Option<usize>) -> Self {
+        self.max_replacements_per_sender = v;
+        self
+    }
+
     /// Set the default max transaction input bytes
     pub const fn with_max_tx_input_bytes(mut self, v: usize) -> Self {
         self.max_tx_input_bytes = v;
@@ -268,7 +282,9 @@ impl Default for DefaultTxPoolValues {
             minimum_priority_fee: None,
             enforced_gas_limit: ETHEREUM_BLOCK_GAS_LIMIT_30M,
             max_tx_gas_limit: None,
+            validate_request_fees: false,
             blob_transaction_price_bump: REPLACE_BLOB_PRICE_BUMP,
+            max_replacements_per_sender: None,
             max_tx_input_bytes: DEFAULT_MAX_TX_INPUT_BYTES,
             max_cached_entries: DEFAULT_MAX_CACHED_BLOBS,
             no_locals: false,
@@ -352,10 +368,20 @@ pub struct TxPoolArgs {
     #[arg(long = "txpool.max-tx-gas", default_value = Resettable::from(DefaultTxPoolValues::get_global().max_tx_gas_limit.map(|v| v.to_string().into())))]
     pub max_tx_gas_limit: Option<u64>,
 
+    /// Reject transactions to the EIP-7002 withdrawal and EIP-7251 consolidation request
+    /// contracts that would revert, because their value doesn't cover the current request fee or
+    /// their input is malformed.
+    #[arg(long = "txpool.validate-request-fees", default_value_t = DefaultTxPoolValues::get_global().validate_request_fees)]
+    pub validate_request_fees: bool,
+
     /// Price bump percentage to replace an already existing blob transaction
     #[arg(long = "blobpool.pricebump", default_value_t = DefaultTxPoolValues::get_global().blob_transaction_price_bump)]
     pub blob_transaction_price_bump: u128,
 
+    /// Max number of transactions a non-local sender can replace per block, unlimited by default
+    #[arg(long = "txpool.max-replacements-per-sender", default_value = Resettable::from(DefaultTxPoolValues::get_global().max_replacements_per_sender.map(|v| v.to_string().into())))]
+    pub max_replacements_per_sender: Option<usize>,
+
     /// Max size in bytes of a single transaction allowed to enter the pool
     #[arg(long = "txpool.max-tx-input-bytes", alias = "txpool.max_tx_input_bytes", default_value_t = DefaultTxPoolValues::get_global().max_tx_input_bytes)]
     pub max_tx_input_bytes: usize,
@@ -450,7 +476,9 @@ impl Default for TxPoolArgs {
             minimum_priority_fee,
             enforced_gas_limit,
             max_tx_gas_limit,
+            validate_request_fees,
             blob_transaction_price_bump,
+            max_replacements_per_sender,
             max_tx_input_bytes,
             max_cached_entries,
             no_locals,
@@ -482,7 +510,9 @@ impl Default for TxPoolArgs {
             minimum_priority_fee,
             enforced_gas_limit,
             max_tx_gas_limit,
+            validate_request_fees,
             blob_transaction_price_bump,
+            max_replacements_per_sender,
             max_tx_input_bytes,
             max_cached_entries,
             no_locals,
@@ -532,6 +562,7 @@ impl RethTransactionPoolConfig for TxPoolArgs {
                 default_price_bump: self.price_bump,
                 replace_blob_tx_price_bump: self.blob_transaction_price_bump,
             },
+            max_replacements_per_sender: self.max_replacements_per_sender,
             minimal_protocol_basefee: self.minimal_protocol_basefee,
             minimum_priority_fee: self.minimum_priority_fee,
             gas_limit: self.enforced_gas_limit,
@@ -608,7 +639,9 @@ mod tests {
             minimum_priority_fee: Some(2000000000),
             enforced_gas_limit: 40000000,
             max_tx_gas_limit: Some(50000000),
+            validate_request_fees: true,
             blob_transaction_price_bump: 25,
+            max_replacements_per_sender: Some(3),
             max_tx_input_bytes: 131072,
             max_cached_entries: 200,
             no_locals: true,
@@ -659,8 +692,11 @@ mod tests {
             "40000000",
             "--txpool.max-tx-gas",
             "50000000",
+            "--txpool.validate-request-fees",
             "--blobpool.pricebump",
             "25",
+            "--txpool.max-replacements-per-sender",
+            "3",
             "--txpool.max-tx-input-bytes",
             "131072",
             "--txpool.max-cached-entries",
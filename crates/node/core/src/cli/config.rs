@@ -1,11 +1,11 @@
 //! Config traits for various node components.
 
 use alloy_eips::eip1559::ETHEREUM_BLOCK_GAS_LIMIT_36M;
-use alloy_primitives::Bytes;
+use alloy_primitives::{Address, Bytes};
 use reth_chainspec::{Chain, ChainKind, NamedChain};
 use reth_network::{protocol::IntoRlpxSubProtocol, NetworkPrimitives};
 use reth_transaction_pool::PoolConfig;
-use std::{borrow::Cow, time::Duration};
+use std::{borrow::Cow, ops::Range, time::Duration};
 
 /// 60M gas limit
 const ETHEREUM_BLOCK_GAS_LIMIT_60M: u64 = 60_000_000;
@@ -40,6 +40,17 @@ pub trait PayloadBuilderConfig {
     /// If `None`, defaults to the protocol maximum.
     fn max_blobs_per_block(&self) -> Option<u64>;
 
+    /// Overrides of the fee recipient suggested in the payload attributes, as pairs of suggested
+    /// fee recipient and override.
+    fn fee_recipient_overrides(&self) -> &[(Address, Address)] {
+        &[]
+    }
+
+    /// Fee recipients of the blocks with a timestamp in the given windows.
+    fn fee_recipient_windows(&self) -> &[(Range<u64>, Address)] {
+        &[]
+    }
+
     /// Returns the configured gas limit if set, or a chain-specific default.
     fn gas_limit_for(&self, chain: Chain) -> u64 {
         if let Some(limit) = self.gas_limit() {
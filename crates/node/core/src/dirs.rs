@@ -368,6 +368,13 @@ impl<D> ChainPath<D> {
         self.data_dir().join("jwt.hex")
     }
 
+    /// Returns the path to the secret of the remote database server for this chain.
+    ///
+    /// `<DIR>/<CHAIN_ID>/db-serve.hex`
+    pub fn remote_db_secret(&self) -> PathBuf {
+        self.data_dir().join("db-serve.hex")
+    }
+
     /// Returns the path to the invalid block hooks directory for this chain.
     ///
     /// `<DIR>/<CHAIN_ID>/invalid_block_hooks`
@@ -33,9 +33,10 @@ use std::{
 };
 use tracing::*;
 
-use crate::args::{EraArgs, MetricArgs};
+use crate::args::{EraArgs, ExExArgs, MetricArgs};
 pub use reth_engine_primitives::{
-    DEFAULT_MEMORY_BLOCK_BUFFER_TARGET, DEFAULT_PERSISTENCE_THRESHOLD, DEFAULT_RESERVED_CPU_CORES,
+    DEFAULT_EXECUTION_OUTPUT_COMPACTION_DEPTH, DEFAULT_MEMORY_BLOCK_BUFFER_TARGET,
+    DEFAULT_PERSISTENCE_THRESHOLD, DEFAULT_RESERVED_CPU_CORES,
 };
 
 /// Default size of cross-block cache in megabytes.
@@ -149,6 +150,9 @@ pub struct NodeConfig<ChainSpec> {
     /// All ERA import related arguments with --era prefix
     pub era: EraArgs,
 
+    /// All `ExEx` related arguments with --exex prefix
+    pub exex: ExExArgs,
+
     /// All static files related arguments
     pub static_files: StaticFilesArgs,
 
@@ -184,6 +188,7 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
             datadir: DatadirArgs::default(),
             engine: EngineArgs::default(),
             era: EraArgs::default(),
+            exex: ExExArgs::default(),
             static_files: StaticFilesArgs::default(),
             rocksdb: RocksDbArgs::default(),
         }
@@ -259,6 +264,7 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
             pruning,
             engine,
             era,
+            exex,
             static_files,
             rocksdb,
             ..
@@ -279,6 +285,7 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
             pruning,
             engine,
             era,
+            exex,
             static_files,
             rocksdb,
         }
@@ -361,6 +368,7 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
     pub const fn storage_settings(&self) -> StorageSettings {
         StorageSettings::base()
             .with_receipts_in_static_files(self.static_files.receipts)
+            .with_receipts_hot_blocks(self.static_files.receipts_hot_blocks)
             .with_transaction_senders_in_static_files(self.static_files.transaction_senders)
             .with_account_changesets_in_static_files(self.static_files.account_changesets)
             .with_storage_changesets_in_static_files(self.static_files.storage_changesets)
@@ -562,6 +570,7 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
             pruning: self.pruning,
             engine: self.engine,
             era: self.era,
+            exex: self.exex,
             static_files: self.static_files,
             rocksdb: self.rocksdb,
         }
@@ -572,10 +581,16 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
     where
         Pool: TransactionPool + Unpin,
     {
-        if let Some(interval) = self.dev.block_time {
+        if self.dev.manual_mining {
+            MiningMode::manual()
+        } else if let Some(interval) = self.dev.block_time {
             MiningMode::interval(interval)
         } else {
-            MiningMode::instant(pool, self.dev.block_max_transactions)
+            let mode = MiningMode::instant(pool, self.dev.block_max_transactions);
+            match self.dev.block_max_wait {
+                Some(max_wait) => mode.with_max_wait(max_wait),
+                None => mode,
+            }
         }
     }
 }
@@ -604,6 +619,7 @@ impl<ChainSpec> Clone for NodeConfig<ChainSpec> {
             datadir: self.datadir.clone(),
             engine: self.engine.clone(),
             era: self.era.clone(),
+            exex: self.exex.clone(),
             static_files: self.static_files,
             rocksdb: self.rocksdb,
         }
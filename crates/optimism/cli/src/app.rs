@@ -105,6 +105,7 @@ where
                 runner.run_command_until_exit(|ctx| command.execute::<OpNode, _>(ctx, components))
             }
             Commands::P2P(command) => runner.run_until_ctrl_c(command.execute::<OpNode>()),
+            Commands::Backfill(command) => runner.run_until_ctrl_c(command.execute::<OpNode>()),
             Commands::Config(command) => runner.run_until_ctrl_c(command.execute()),
             Commands::Prune(command) => runner.run_until_ctrl_c(command.execute::<OpNode>()),
             #[cfg(feature = "dev")]
@@ -112,6 +113,9 @@ where
             Commands::ReExecute(command) => {
                 runner.run_until_ctrl_c(command.execute::<OpNode>(components))
             }
+            Commands::StaticFiles(command) => {
+                runner.run_blocking_until_ctrl_c(command.execute::<OpNode>())
+            }
         }
     }
 
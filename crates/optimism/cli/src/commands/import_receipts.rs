@@ -4,30 +4,24 @@
 use crate::receipt_file_codec::OpGethReceiptFileCodec;
 use clap::Parser;
 use reth_cli::chainspec::ChainSpecParser;
-use reth_cli_commands::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
-use reth_db_api::tables;
-use reth_downloaders::{
-    file_client::{ChunkedFileReader, DEFAULT_BYTE_LEN_CHUNK_CHAIN_FILE},
-    receipt_file_client::ReceiptFileClient,
+use reth_cli_commands::{
+    common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs},
+    import_receipts,
 };
-use reth_execution_types::ExecutionOutcome;
+use reth_downloaders::file_client::{ChunkedFileReader, DEFAULT_BYTE_LEN_CHUNK_CHAIN_FILE};
 use reth_node_builder::ReceiptTy;
 use reth_node_core::version::version_metadata;
 use reth_optimism_chainspec::OpChainSpec;
 use reth_optimism_primitives::{bedrock::is_dup_tx, OpPrimitives, OpReceipt};
 use reth_primitives_traits::NodePrimitives;
-use reth_provider::{
-    providers::ProviderNodeTypes, DBProvider, DatabaseProviderFactory, OriginalValuesKnown,
-    ProviderFactory, StageCheckpointReader, StageCheckpointWriter, StateWriteConfig, StateWriter,
-    StaticFileProviderFactory, StatsReader,
-};
-use reth_stages::{StageCheckpoint, StageId};
-use reth_static_file_types::StaticFileSegment;
+use reth_provider::{providers::ProviderNodeTypes, ProviderFactory};
 use std::{
     path::{Path, PathBuf},
     sync::Arc,
 };
-use tracing::{debug, info, trace, warn};
+use tracing::{debug, info};
+
+pub use reth_cli_commands::import_receipts::ImportReceiptsResult;
 
 /// Initializes the database with the genesis block.
 #[derive(Debug, Parser)]
@@ -100,20 +94,17 @@ where
     P: AsRef<Path>,
     F: FnMut(u64, &mut Vec<Vec<OpReceipt>>) -> usize,
 {
-    for stage in StageId::ALL {
-        let checkpoint = provider_factory.database_provider_ro()?.get_stage_checkpoint(stage)?;
-        trace!(target: "reth::cli",
-            ?stage,
-            ?checkpoint,
-            "Read stage checkpoints from db"
-        );
-    }
-
-    // open file
-    let reader = ChunkedFileReader::new(&path, chunk_len).await?;
-
-    // import receipts
-    let _ = import_receipts_from_reader(&provider_factory, reader, filter).await?;
+    // receipts roots of pre-Bedrock blocks don't match the exported receipts, hence they are not
+    // verified
+    let _ =
+        import_receipts::import_receipts_from_file::<N, OpGethReceiptFileCodec<OpReceipt>, P, F>(
+            provider_factory,
+            path,
+            chunk_len,
+            false,
+            filter,
+        )
+        .await?;
 
     info!(target: "reth::cli",
         "Receipt file imported"
@@ -125,150 +116,23 @@ where
 /// Imports receipts to static files. Takes a filter callback as parameter, that returns the total
 /// number of filtered out receipts.
 ///
-/// Caution! Filter callback must replace completely filtered out receipts for a block, with empty
-/// vectors, rather than `vec!(None)`. This is since the code for writing to static files, expects
-/// indices in the receipts list, to map to sequential block numbers.
+/// See [`import_receipts::import_receipts_from_reader`].
 pub async fn import_receipts_from_reader<N, F>(
     provider_factory: &ProviderFactory<N>,
-    mut reader: ChunkedFileReader,
-    mut filter: F,
+    reader: ChunkedFileReader,
+    filter: F,
 ) -> eyre::Result<ImportReceiptsResult>
 where
     N: ProviderNodeTypes<Primitives: NodePrimitives<Receipt = OpReceipt>>,
     F: FnMut(u64, &mut Vec<Vec<ReceiptTy<N>>>) -> usize,
 {
-    let static_file_provider = provider_factory.static_file_provider();
-
-    // Ensure that receipts hasn't been initialized apart from `init_genesis`.
-    if let Some(num_receipts) =
-        static_file_provider.get_highest_static_file_tx(StaticFileSegment::Receipts) &&
-        num_receipts > 0
-    {
-        eyre::bail!("Expected no receipts in storage, but found {num_receipts}.");
-    }
-    match static_file_provider.get_highest_static_file_block(StaticFileSegment::Receipts) {
-        Some(receipts_block) => {
-            if receipts_block > 0 {
-                eyre::bail!("Expected highest receipt block to be 0, but found {receipts_block}.");
-            }
-        }
-        None => {
-            eyre::bail!(
-                "Receipts was not initialized. Please import blocks and transactions before calling this command."
-            );
-        }
-    }
-
-    let provider = provider_factory.database_provider_rw()?;
-    let mut total_decoded_receipts = 0;
-    let mut total_receipts = 0;
-    let mut total_filtered_out_dup_txns = 0;
-    let mut highest_block_receipts = 0;
-
-    let highest_block_transactions = static_file_provider
-        .get_highest_static_file_block(StaticFileSegment::Transactions)
-        .expect("transaction static files must exist before importing receipts");
-
-    while let Some(file_client) =
-        reader.next_receipts_chunk::<ReceiptFileClient<OpGethReceiptFileCodec<OpReceipt>>>().await?
-    {
-        if highest_block_receipts == highest_block_transactions {
-            warn!(target: "reth::cli",  highest_block_receipts, highest_block_transactions, "Ignoring all other blocks in the file since we have reached the desired height");
-            break
-        }
-
-        // create a new file client from chunk read from file
-        let ReceiptFileClient {
-            mut receipts,
-            mut first_block,
-            total_receipts: total_receipts_chunk,
-            ..
-        } = file_client;
-
-        // mark these as decoded
-        total_decoded_receipts += total_receipts_chunk;
-
-        total_filtered_out_dup_txns += filter(first_block, &mut receipts);
-
-        info!(target: "reth::cli",
-            first_receipts_block=?first_block,
-            total_receipts_chunk,
-            "Importing receipt file chunk"
-        );
-
-        // It is possible for the first receipt returned by the file client to be the genesis
-        // block. In this case, we just prepend empty receipts to the current list of receipts.
-        // When initially writing to static files, the provider expects the first block to be block
-        // one. So, if the first block returned by the file client is the genesis block, we remove
-        // those receipts.
-        if first_block == 0 {
-            // remove the first empty receipts
-            let genesis_receipts = receipts.remove(0);
-            debug_assert!(genesis_receipts.is_empty());
-            // this ensures the execution outcome and static file producer start at block 1
-            first_block = 1;
-        }
-        highest_block_receipts = first_block + receipts.len() as u64 - 1;
-
-        // RLP file may have too many blocks. We ignore the excess, but warn the user.
-        if highest_block_receipts > highest_block_transactions {
-            let excess = highest_block_receipts - highest_block_transactions;
-            highest_block_receipts -= excess;
-
-            // Remove the last `excess` blocks
-            receipts.truncate(receipts.len() - excess as usize);
-
-            warn!(target: "reth::cli", highest_block_receipts, "Too many decoded blocks, ignoring the last {excess}.");
-        }
-
-        // Update total_receipts after all filtering
-        total_receipts += receipts.iter().map(|v| v.len()).sum::<usize>();
-
-        let execution_outcome =
-            ExecutionOutcome::new(Default::default(), receipts, first_block, Default::default());
-
-        // finally, write the receipts
-        provider.write_state(
-            &execution_outcome,
-            OriginalValuesKnown::Yes,
-            StateWriteConfig::default(),
-        )?;
-    }
-
-    // Only commit if we have imported as many receipts as the number of transactions.
-    let total_imported_txns = static_file_provider
-        .count_entries::<tables::Transactions>()
-        .expect("transaction static files must exist before importing receipts");
-
-    if total_receipts != total_imported_txns {
-        eyre::bail!(
-            "Number of receipts ({total_receipts}) inconsistent with transactions {total_imported_txns}"
-        )
-    }
-
-    // Only commit if the receipt block height matches the one from transactions.
-    if highest_block_receipts != highest_block_transactions {
-        eyre::bail!(
-            "Receipt block height ({highest_block_receipts}) inconsistent with transactions' {highest_block_transactions}"
-        )
-    }
-
-    // Required or any access-write provider factory will attempt to unwind to 0.
-    provider
-        .save_stage_checkpoint(StageId::Execution, StageCheckpoint::new(highest_block_receipts))?;
-
-    provider.commit()?;
-
-    Ok(ImportReceiptsResult { total_decoded_receipts, total_filtered_out_dup_txns })
-}
-
-/// Result of importing receipts in chunks.
-#[derive(Debug)]
-pub struct ImportReceiptsResult {
-    /// Total decoded receipts.
-    pub total_decoded_receipts: usize,
-    /// Total filtered out receipts.
-    pub total_filtered_out_dup_txns: usize,
+    import_receipts::import_receipts_from_reader::<N, OpGethReceiptFileCodec<OpReceipt>, F>(
+        provider_factory,
+        reader,
+        false,
+        filter,
+    )
+    .await
 }
 
 #[cfg(test)]
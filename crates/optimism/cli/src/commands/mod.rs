@@ -5,9 +5,9 @@ use import_receipts::ImportReceiptsOpCommand;
 use reth_chainspec::{EthChainSpec, EthereumHardforks, Hardforks};
 use reth_cli::chainspec::ChainSpecParser;
 use reth_cli_commands::{
-    config_cmd, db, dump_genesis, init_cmd,
+    backfill, config_cmd, db, dump_genesis, init_cmd,
     node::{self, NoArgs},
-    p2p, prune, re_execute, stage,
+    p2p, prune, re_execute, stage, static_files,
 };
 use std::{fmt, sync::Arc};
 
@@ -48,6 +48,9 @@ pub enum Commands<Spec: ChainSpecParser = OpChainSpecParser, Ext: clap::Args + f
     /// P2P Debugging utilities
     #[command(name = "p2p")]
     P2P(Box<p2p::Command<Spec>>),
+    /// Backfill data, like pruned receipts, from the network
+    #[command(name = "backfill")]
+    Backfill(Box<backfill::Command<Spec>>),
     /// Write config to stdout
     #[command(name = "config")]
     Config(config_cmd::Command),
@@ -61,6 +64,9 @@ pub enum Commands<Spec: ChainSpecParser = OpChainSpecParser, Ext: clap::Args + f
     /// Re-execute blocks in parallel to verify historical sync correctness.
     #[command(name = "re-execute")]
     ReExecute(re_execute::Command<Spec>),
+    /// Static file utilities
+    #[command(name = "static-files")]
+    StaticFiles(static_files::Command<Spec>),
 }
 
 impl<
@@ -78,6 +84,7 @@ impl<
             Self::Db(cmd) => cmd.chain_spec(),
             Self::Stage(cmd) => cmd.chain_spec(),
             Self::P2P(cmd) => cmd.chain_spec(),
+            Self::Backfill(cmd) => cmd.chain_spec(),
             Self::Config(_) => None,
             Self::Prune(cmd) => cmd.chain_spec(),
             Self::ImportOp(cmd) => cmd.chain_spec(),
@@ -85,6 +92,7 @@ impl<
             #[cfg(feature = "dev")]
             Self::TestVectors(_) => None,
             Self::ReExecute(cmd) => cmd.chain_spec(),
+            Self::StaticFiles(cmd) => cmd.chain_spec(),
         }
     }
 }
@@ -20,7 +20,8 @@ use op_alloy_consensus::EIP1559ParamError;
 use op_revm::{OpSpecId, OpTransaction};
 use reth_chainspec::EthChainSpec;
 use reth_evm::{
-    eth::NextEvmEnvAttributes, precompiles::PrecompilesMap, ConfigureEvm, EvmEnv, TransactionEnv,
+    eth::NextEvmEnvAttributes, precompiles::PrecompilesMap, ConfigureEvm, ConfigureEvmPrecompiles,
+    EvmEnv, PrecompilesHook, TransactionEnv, WithPrecompiles,
 };
 use reth_optimism_chainspec::OpChainSpec;
 use reth_optimism_forks::OpHardforks;
@@ -206,8 +207,50 @@ where
     }
 }
 
+impl<ChainSpec, N, R, EvmF> ConfigureEvmPrecompiles for OpEvmConfig<ChainSpec, N, R, EvmF>
+where
+    ChainSpec: EthChainSpec<Header = Header> + OpHardforks,
+    N: NodePrimitives<
+        Receipt = R::Receipt,
+        SignedTx = R::Transaction,
+        BlockHeader = Header,
+        BlockBody = alloy_consensus::BlockBody<R::Transaction>,
+        Block = alloy_consensus::Block<R::Transaction>,
+    >,
+    OpTransaction<TxEnv>: FromRecoveredTx<N::SignedTx> + FromTxWithEncoded<N::SignedTx>,
+    R: OpReceiptBuilder<Receipt: DepositReceipt, Transaction: SignedTransaction> + Clone,
+    EvmF: EvmFactory<
+            Tx: FromRecoveredTx<R::Transaction>
+                    + FromTxWithEncoded<R::Transaction>
+                    + TransactionEnv
+                    + OpTxEnv,
+            Precompiles = PrecompilesMap,
+            Spec = OpSpecId,
+            BlockEnv = BlockEnv,
+        > + Clone
+        + Debug,
+    Self: Send + Sync + Unpin + Clone + 'static,
+    OpEvmConfig<ChainSpec, N, R, WithPrecompiles<EvmF>>: Send + Sync + Unpin + Clone + 'static,
+{
+    type WithPrecompiles = OpEvmConfig<ChainSpec, N, R, WithPrecompiles<EvmF>>;
+
+    fn with_precompiles(self, hook: PrecompilesHook<OpSpecId>) -> Self::WithPrecompiles {
+        let Self { executor_factory, block_assembler, _pd } = self;
+        OpEvmConfig {
+            executor_factory: OpBlockExecutorFactory::new(
+                executor_factory.receipt_builder().clone(),
+                executor_factory.spec().clone(),
+                WithPrecompiles::new(executor_factory.evm_factory().clone(), hook),
+            ),
+            block_assembler,
+            _pd,
+        }
+    }
+}
+
 #[cfg(feature = "std")]
-impl<ChainSpec, N, R> ConfigureEngineEvm<OpExecutionData> for OpEvmConfig<ChainSpec, N, R>
+impl<ChainSpec, N, R, EvmF> ConfigureEngineEvm<OpExecutionData>
+    for OpEvmConfig<ChainSpec, N, R, EvmF>
 where
     ChainSpec: EthChainSpec<Header = Header> + OpHardforks,
     N: NodePrimitives<
@@ -219,6 +262,15 @@ where
     >,
     OpTransaction<TxEnv>: FromRecoveredTx<N::SignedTx> + FromTxWithEncoded<N::SignedTx>,
     R: OpReceiptBuilder<Receipt: DepositReceipt, Transaction: SignedTransaction>,
+    EvmF: EvmFactory<
+            Tx: FromRecoveredTx<R::Transaction>
+                    + FromTxWithEncoded<R::Transaction>
+                    + TransactionEnv
+                    + OpTxEnv,
+            Precompiles = PrecompilesMap,
+            Spec = OpSpecId,
+            BlockEnv = BlockEnv,
+        > + Debug,
     Self: Send + Sync + Unpin + Clone + 'static,
 {
     fn evm_env_for_payload(
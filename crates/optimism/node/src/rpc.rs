@@ -102,9 +102,19 @@ use reth_payload_builder::PayloadStore;
 use reth_rpc_engine_api::{EngineApi, EngineCapabilities};
 
 /// Builder for basic [`OpEngineApi`] implementation.
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Clone)]
 pub struct OpEngineApiBuilder<EV> {
     engine_validator_builder: EV,
+    capabilities: EngineCapabilities,
+}
+
+impl<EV: Default> Default for OpEngineApiBuilder<EV> {
+    fn default() -> Self {
+        Self {
+            engine_validator_builder: EV::default(),
+            capabilities: EngineCapabilities::new(OP_ENGINE_CAPABILITIES.iter().copied()),
+        }
+    }
 }
 
 impl<N, EV> EngineApiBuilder<N> for OpEngineApiBuilder<EV>
@@ -127,7 +137,7 @@ where
     >;
 
     async fn build_engine_api(self, ctx: &AddOnsContext<'_, N>) -> eyre::Result<Self::EngineApi> {
-        let Self { engine_validator_builder } = self;
+        let Self { engine_validator_builder, capabilities } = self;
 
         let engine_validator = engine_validator_builder.build(ctx).await?;
         let client = ClientVersionV1 {
@@ -144,7 +154,7 @@ where
             ctx.node.pool().clone(),
             Box::new(ctx.node.task_executor().clone()),
             client,
-            EngineCapabilities::new(OP_ENGINE_CAPABILITIES.iter().copied()),
+            capabilities,
             engine_validator,
             ctx.config.engine.accept_execution_requests_hash,
             ctx.node.network().clone(),
@@ -152,4 +162,8 @@ where
 
         Ok(OpEngineApi::new(inner))
     }
+
+    fn capabilities(&self) -> Option<EngineCapabilities> {
+        Some(self.capabilities.clone())
+    }
 }
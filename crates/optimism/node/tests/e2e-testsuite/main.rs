@@ -1,6 +1,8 @@
 #![allow(missing_docs)]
 
 mod p2p;
+mod payload;
+mod rpc;
 mod testsuite;
 
 const fn main() {}
@@ -0,0 +1,48 @@
+use reth_basic_payload_builder::{BuildArguments, PayloadBuilder, PayloadConfig};
+use reth_e2e_test_utils::transaction::TransactionTestContext;
+use reth_optimism_node::utils::{optimism_payload_attributes, setup};
+use reth_optimism_payload_builder::OpPayloadBuilder;
+use reth_optimism_primitives::OpTransactionSigned;
+use reth_provider::HeaderProvider;
+use reth_revm::cancelled::ManualCancel;
+use std::sync::Arc;
+
+#[tokio::test]
+async fn payload_build_stops_adding_transactions_on_interrupt() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+
+    let (mut nodes, _tasks, wallet) = setup(1).await?;
+    let node = nodes.pop().unwrap();
+
+    let raw_tx =
+        TransactionTestContext::optimism_l1_block_info_tx(wallet.chain_id, wallet.inner, 0).await;
+    node.rpc.inject_tx(raw_tx).await?;
+
+    let parent = Arc::new(node.inner.provider.sealed_header(0)?.expect("genesis header"));
+    let builder = OpPayloadBuilder::new(
+        node.inner.pool.clone(),
+        node.inner.provider.clone(),
+        node.inner.evm_config.clone(),
+    );
+    let build = |interrupt: ManualCancel| {
+        let config = PayloadConfig::new(
+            parent.clone(),
+            optimism_payload_attributes::<OpTransactionSigned>(parent.timestamp + 2),
+        );
+        let args = BuildArguments::new(Default::default(), config, Default::default(), None)
+            .with_interrupt(interrupt);
+        builder.try_build(args).map(|outcome| outcome.into_payload().expect("built payload"))
+    };
+
+    // the pool transaction is included by a regular build
+    let payload = build(ManualCancel::default())?;
+    assert_eq!(payload.block().body().transactions.len(), 1);
+
+    // an interrupted build seals the payload without adding more transactions
+    let interrupt = ManualCancel::default();
+    interrupt.clone().cancel();
+    let payload = build(interrupt)?;
+    assert!(payload.block().body().transactions.is_empty());
+
+    Ok(())
+}
@@ -0,0 +1,102 @@
+use alloy_eips::eip2718::Encodable2718;
+use alloy_primitives::{Address, TxKind, B256, U256};
+use alloy_rpc_types_trace::{
+    geth::{GethDebugBuiltInTracerType, GethDebugTracerType, GethDebugTracingOptions, GethTrace},
+    parity::TraceType,
+};
+use op_alloy_consensus::TxDeposit;
+use reth_basic_payload_builder::{BuildArguments, PayloadBuilder, PayloadConfig};
+use reth_optimism_node::utils::{optimism_payload_attributes, setup};
+use reth_optimism_payload_builder::OpPayloadBuilder;
+use reth_optimism_primitives::OpTransactionSigned;
+use reth_primitives_traits::WithEncoded;
+use reth_provider::HeaderProvider;
+use std::{collections::HashSet, sync::Arc};
+
+#[tokio::test]
+async fn trace_deposit_transaction() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+
+    let (mut nodes, _tasks, _wallet) = setup(1).await?;
+    let node = nodes.pop().unwrap();
+
+    // a user deposit that mints the value it transfers
+    let depositor = Address::with_last_byte(0xde);
+    let recipient = Address::with_last_byte(0xaa);
+    let deposit: OpTransactionSigned = TxDeposit {
+        source_hash: B256::with_last_byte(1),
+        from: depositor,
+        to: TxKind::Call(recipient),
+        mint: 10u128.pow(18),
+        value: U256::from(10u128.pow(18)),
+        gas_limit: 100_000,
+        ..Default::default()
+    }
+    .into();
+    let deposit_hash = *deposit.tx_hash();
+    let raw_deposit = deposit.encoded_2718();
+
+    let parent = Arc::new(node.inner.provider.sealed_header(0)?.expect("genesis header"));
+    let builder = OpPayloadBuilder::new(
+        node.inner.pool.clone(),
+        node.inner.provider.clone(),
+        node.inner.evm_config.clone(),
+    );
+    let mut attributes = optimism_payload_attributes::<OpTransactionSigned>(parent.timestamp + 2);
+    attributes.transactions = vec![WithEncoded::new(raw_deposit.clone().into(), deposit)];
+    attributes.no_tx_pool = true;
+    let payload = builder
+        .try_build(BuildArguments::new(
+            Default::default(),
+            PayloadConfig::new(parent.clone(), attributes),
+            Default::default(),
+            None,
+        ))?
+        .into_payload()
+        .expect("built payload");
+    assert_eq!(payload.block().body().transactions.len(), 1);
+
+    let block_hash = node.submit_payload(payload).await?;
+    node.update_forkchoice(parent.hash(), block_hash).await?;
+    node.wait_block(1, block_hash, false).await?;
+
+    let debug_api = node.rpc.inner.debug_api();
+
+    let GethTrace::Default(frame) =
+        debug_api.debug_trace_transaction(deposit_hash, Default::default()).await?
+    else {
+        panic!("expected a struct log trace")
+    };
+    assert!(!frame.failed);
+
+    let opts = GethDebugTracingOptions {
+        tracer: Some(GethDebugTracerType::BuiltInTracer(GethDebugBuiltInTracerType::CallTracer)),
+        ..Default::default()
+    };
+    let GethTrace::CallTracer(frame) =
+        debug_api.debug_trace_transaction(deposit_hash, opts).await?
+    else {
+        panic!("expected a call trace")
+    };
+    assert_eq!(frame.from, depositor);
+    assert_eq!(frame.to, Some(recipient));
+    assert_eq!(frame.value, Some(U256::from(10u128.pow(18))));
+    assert!(frame.error.is_none());
+
+    // deposits are never pooled, but can still be traced as raw transactions
+    let results = node
+        .rpc
+        .inner
+        .trace_api()
+        .trace_raw_transaction(
+            raw_deposit.into(),
+            HashSet::from([TraceType::Trace]),
+            Some(block_hash.into()),
+        )
+        .await?;
+    let trace = results.trace.expect("call traces");
+    assert_eq!(trace.len(), 1);
+    assert!(trace[0].error.is_none());
+
+    Ok(())
+}
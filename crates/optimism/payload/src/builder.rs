@@ -33,7 +33,9 @@ use reth_primitives_traits::{
     HeaderTy, NodePrimitives, SealedHeader, SealedHeaderFor, SignedTransaction, TxTy,
 };
 use reth_revm::{
-    cancelled::CancelOnDrop, database::StateProviderDatabase, db::State,
+    cancelled::{CancelOnDrop, ManualCancel},
+    database::StateProviderDatabase,
+    db::State,
     witness::ExecutionWitnessRecord,
 };
 use reth_storage_api::{errors::ProviderError, StateProvider, StateProviderFactory};
@@ -180,7 +182,7 @@ where
         Txs:
             PayloadTransactions<Transaction: PoolTransaction<Consensus = N::SignedTx> + OpPooledTx>,
     {
-        let BuildArguments { mut cached_reads, config, cancel, best_payload } = args;
+        let BuildArguments { mut cached_reads, config, cancel, interrupt, best_payload } = args;
 
         let ctx = OpPayloadBuilderCtx {
             evm_config: self.evm_config.clone(),
@@ -188,6 +190,7 @@ where
             chain_spec: self.client.chain_spec(),
             config,
             cancel,
+            interrupt,
             best_payload,
         };
 
@@ -224,6 +227,7 @@ where
             chain_spec: self.client.chain_spec(),
             config,
             cancel: Default::default(),
+            interrupt: Default::default(),
             best_payload: Default::default(),
         };
 
@@ -278,6 +282,7 @@ where
             config,
             cached_reads: Default::default(),
             cancel: Default::default(),
+            interrupt: Default::default(),
             best_payload: None,
         };
         self.build_payload(args, |_| NoopPayloadTransactions::<Pool::Transaction>::default())?
@@ -552,6 +557,9 @@ pub struct OpPayloadBuilderCtx<
     pub config: PayloadConfig<Attrs, HeaderTy<Evm::Primitives>>,
     /// Marker to check whether the job has been cancelled.
     pub cancel: CancelOnDrop,
+    /// Marker to check whether the payload was requested, in which case no more transactions
+    /// are added to it.
+    pub interrupt: ManualCancel,
     /// The currently best payload.
     pub best_payload: Option<OpBuiltPayload<Evm::Primitives>>,
 }
@@ -736,6 +744,12 @@ where
                 return Ok(Some(()))
             }
 
+            // check if the payload was requested, if so it's sealed with the transactions so far
+            if self.interrupt.is_cancelled() {
+                debug!(target: "payload_builder", id=%self.payload_id(), "payload build interrupted");
+                break
+            }
+
             let gas_used = match builder.execute_transaction(tx.clone()) {
                 Ok(gas_used) => gas_used,
                 Err(BlockExecutionError::Validation(BlockValidationError::InvalidTx {
@@ -9,6 +9,7 @@
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
 use crate::metrics::PayloadBuilderMetrics;
+use alloy_consensus::BlockHeader;
 use alloy_eips::merge::SLOT_DURATION;
 use alloy_primitives::{B256, U256};
 use futures_core::ready;
@@ -18,7 +19,10 @@ use reth_payload_builder::{KeepPayloadJobAlive, PayloadId, PayloadJob, PayloadJo
 use reth_payload_builder_primitives::PayloadBuilderError;
 use reth_payload_primitives::{BuiltPayload, PayloadBuilderAttributes, PayloadKind};
 use reth_primitives_traits::{HeaderTy, NodePrimitives, SealedHeader};
-use reth_revm::{cached::CachedReads, cancelled::CancelOnDrop};
+use reth_revm::{
+    cached::CachedReads,
+    cancelled::{CancelOnDrop, ManualCancel},
+};
 use reth_storage_api::{BlockReaderIdExt, StateProviderFactory};
 use reth_tasks::TaskSpawner;
 use std::{
@@ -171,10 +175,12 @@ where
             deadline,
             // ticks immediately
             interval: tokio::time::interval(self.config.interval),
+            interrupt_timeout: self.config.interrupt_timeout,
             best_payload: PayloadState::Missing,
             pending_block: None,
             cached_reads,
             payload_task_guard: self.payload_task_guard.clone(),
+            stats: Default::default(),
             metrics: Default::default(),
             builder: self.builder.clone(),
         };
@@ -246,6 +252,12 @@ pub struct BasicPayloadJobGeneratorConfig {
     ///
     /// By default this is [`SLOT_DURATION`]: 12s
     deadline: Duration,
+    /// The maximum time to wait for the build job that is in progress when the payload is
+    /// resolved, after it was interrupted.
+    ///
+    /// By default this is zero: the best payload is returned right away if the interrupted job
+    /// hasn't finished yet.
+    interrupt_timeout: Duration,
     /// Maximum number of tasks to spawn for building a payload.
     max_payload_tasks: usize,
 }
@@ -265,6 +277,12 @@ impl BasicPayloadJobGeneratorConfig {
         self
     }
 
+    /// Sets the maximum time to wait for the interrupted build job when the payload is resolved.
+    pub const fn interrupt_timeout(mut self, interrupt_timeout: Duration) -> Self {
+        self.interrupt_timeout = interrupt_timeout;
+        self
+    }
+
     /// Sets the maximum number of tasks to spawn for building a payload(s).
     ///
     /// # Panics
@@ -283,6 +301,7 @@ impl Default for BasicPayloadJobGeneratorConfig {
             interval: Duration::from_secs(1),
             // 12s slot time
             deadline: SLOT_DURATION,
+            interrupt_timeout: Duration::ZERO,
             max_payload_tasks: 3,
         }
     }
@@ -298,6 +317,11 @@ impl Default for BasicPayloadJobGeneratorConfig {
 /// [`BuildOutcome::Freeze`]. Once a frozen payload is returned, no additional payloads will be
 /// built and this future will wait to be resolved: [`PayloadJob::resolve`] or terminated if the
 /// deadline is reached.
+///
+/// When the job is resolved while a payload is being built, that build is interrupted unless the
+/// job is resolved with [`PayloadKind::WaitForPending`]: it stops adding transactions and seals the
+/// payload, which is returned instead of the best payload if it pays more fees and is sealed
+/// within the configured interrupt timeout.
 #[derive(Debug)]
 pub struct BasicPayloadJob<Tasks, Builder>
 where
@@ -311,6 +335,8 @@ where
     deadline: Pin<Box<Sleep>>,
     /// The interval at which the job should build a new payload after the last.
     interval: Interval,
+    /// The maximum time to wait for the interrupted build job when the job is resolved.
+    interrupt_timeout: Duration,
     /// The best payload so far and its state.
     best_payload: PayloadState<Builder::BuiltPayload>,
     /// Receiver for the block that is currently being built.
@@ -322,6 +348,8 @@ where
     /// This is used to avoid reading the same state over and over again when new attempts are
     /// triggered, because during the building process we'll repeatedly execute the transactions.
     cached_reads: Option<CachedReads>,
+    /// Statistics of the payloads built by this job.
+    stats: PayloadJobStats,
     /// metrics for this type
     metrics: PayloadBuilderMetrics,
     /// The type responsible for building payloads.
@@ -343,6 +371,7 @@ where
         let (tx, rx) = oneshot::channel();
         let cancel = CancelOnDrop::default();
         let _cancel = cancel.clone();
+        let interrupt = ManualCancel::default();
         let guard = self.payload_task_guard.clone();
         let payload_config = self.config.clone();
         let best_payload = self.best_payload.payload().cloned();
@@ -352,13 +381,23 @@ where
         self.executor.spawn_blocking(Box::pin(async move {
             // acquire the permit for executing the task
             let _permit = guard.acquire().await;
-            let args =
-                BuildArguments { cached_reads, config: payload_config, cancel, best_payload };
+            let args = BuildArguments {
+                cached_reads,
+                config: payload_config,
+                cancel,
+                interrupt: interrupt.clone(),
+                best_payload,
+            };
             let result = builder.try_build(args);
             let _ = tx.send(result);
         }));
 
-        self.pending_block = Some(PendingPayload { _cancel, payload: rx });
+        self.pending_block = Some(PendingPayload { _cancel, interrupt, payload: rx });
+    }
+
+    /// Returns the statistics of the payloads built by this job so far.
+    pub const fn stats(&self) -> &PayloadJobStats {
+        &self.stats
     }
 }
 
@@ -396,10 +435,12 @@ where
                     BuildOutcome::Better { payload, cached_reads } => {
                         this.cached_reads = Some(cached_reads);
                         debug!(target: "payload_builder", value = %payload.fees(), "built better payload");
+                        this.stats.on_payload_built(&payload);
                         this.best_payload = PayloadState::Best(payload);
                     }
                     BuildOutcome::Freeze(payload) => {
                         debug!(target: "payload_builder", "payload frozen, no further building will occur");
+                        this.stats.on_payload_built(&payload);
                         this.best_payload = PayloadState::Frozen(payload);
                     }
                     BuildOutcome::Aborted { fees, cached_reads } => {
@@ -472,6 +513,28 @@ where
         let maybe_better = self.pending_block.take();
         let mut empty_payload = None;
 
+        // when waiting for the pending payload, e.g. in dev mode, the build job must include all
+        // transactions, so it's only interrupted otherwise
+        let interrupt = kind != PayloadKind::WaitForPending;
+        if let Some(pending) = maybe_better.as_ref().filter(|_| interrupt) {
+            // stop adding transactions to the payload that's being built, so it's sealed with the
+            // transactions included so far
+            debug!(target: "payload_builder", id=%self.config.payload_id(), "interrupting in progress payload build job");
+            pending.interrupt();
+        }
+
+        self.stats.interrupted = interrupt && maybe_better.is_some();
+        self.metrics.record_resolved_job(&self.stats);
+        debug!(
+            target: "payload_builder",
+            id=%self.config.payload_id(),
+            payloads_built = self.stats.payloads_built,
+            cumulative_gas_used = self.stats.cumulative_gas_used,
+            revenue = %self.stats.revenue,
+            interrupted = self.stats.interrupted,
+            "resolving payload job"
+        );
+
         if best_payload.is_none() {
             debug!(target: "payload_builder", id=%self.config.payload_id(), "no best payload yet to resolve, building empty payload");
 
@@ -479,6 +542,7 @@ where
                 cached_reads: self.cached_reads.take().unwrap_or_default(),
                 config: self.config.clone(),
                 cancel: CancelOnDrop::default(),
+                interrupt: ManualCancel::default(),
                 best_payload: None,
             };
 
@@ -514,9 +578,16 @@ where
             };
         }
 
+        // only wait for the interrupted build job for a limited time if there's a payload to fall
+        // back to, waiting is opt-in so the best payload is returned right away by default
+        let interrupt_deadline =
+            (self.stats.interrupted && best_payload.is_some() && !self.interrupt_timeout.is_zero())
+                .then(|| Box::pin(tokio::time::sleep(self.interrupt_timeout)));
+
         let fut = ResolveBestPayload {
             best_payload,
             maybe_better,
+            interrupt_deadline,
             empty_payload: empty_payload.filter(|_| kind != PayloadKind::WaitForPending),
         };
 
@@ -524,6 +595,28 @@ where
     }
 }
 
+/// Statistics of the payloads built by a [`BasicPayloadJob`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PayloadJobStats {
+    /// The number of payloads built that were better than the previous best payload.
+    pub payloads_built: u64,
+    /// The total gas used by the built payloads.
+    pub cumulative_gas_used: u64,
+    /// The fees paid to the fee recipient by the best payload, in wei.
+    pub revenue: U256,
+    /// Whether a build job was in progress and interrupted when the job was resolved.
+    pub interrupted: bool,
+}
+
+impl PayloadJobStats {
+    /// Records a payload built by the job, which is the new best payload.
+    fn on_payload_built<P: BuiltPayload>(&mut self, payload: &P) {
+        self.payloads_built += 1;
+        self.cumulative_gas_used += payload.block().header().gas_used();
+        self.revenue = payload.fees();
+    }
+}
+
 /// Represents the current state of a payload being built.
 #[derive(Debug, Clone)]
 pub enum PayloadState<P> {
@@ -561,12 +654,20 @@ impl<P> PayloadState<P> {
 ///
 /// If no payload has been built so far, it will either return an empty payload or the result of the
 /// in progress build job, whatever finishes first.
+///
+/// The in progress build job is expected to be interrupted, so it seals its payload shortly. If
+/// there's a best payload, the job is awaited until the interrupt deadline at most.
 #[derive(Debug)]
 pub struct ResolveBestPayload<Payload> {
     /// Best payload so far.
     pub best_payload: Option<Payload>,
     /// Regular payload job that's currently running that might produce a better payload.
     pub maybe_better: Option<PendingPayload<Payload>>,
+    /// The deadline until which the interrupted `maybe_better` job is awaited before the best
+    /// payload is returned, if any.
+    ///
+    /// If `None`, the best payload is returned right away if `maybe_better` isn't ready.
+    pub interrupt_deadline: Option<Pin<Box<Sleep>>>,
     /// The empty payload building job in progress, if any.
     pub empty_payload: Option<oneshot::Receiver<Result<Payload, PayloadBuilderError>>>,
 }
@@ -599,6 +700,15 @@ where
             }
         }
 
+        // give the interrupted job time to seal its payload before falling back to the best payload
+        if this.best_payload.is_some() &&
+            this.maybe_better.is_some() &&
+            let Some(deadline) = this.interrupt_deadline.as_mut() &&
+            deadline.as_mut().poll(cx).is_pending()
+        {
+            return Poll::Pending
+        }
+
         if let Some(best) = this.best_payload.take() {
             debug!(target: "payload_builder", "resolving best payload");
             return Poll::Ready(Ok(best))
@@ -634,17 +744,32 @@ where
 pub struct PendingPayload<P> {
     /// The marker to cancel the job on drop
     _cancel: CancelOnDrop,
+    /// The marker to interrupt the job.
+    interrupt: ManualCancel,
     /// The channel to send the result to.
     payload: oneshot::Receiver<Result<BuildOutcome<P>, PayloadBuilderError>>,
 }
 
 impl<P> PendingPayload<P> {
     /// Constructs a `PendingPayload` future.
-    pub const fn new(
+    pub fn new(
         cancel: CancelOnDrop,
         payload: oneshot::Receiver<Result<BuildOutcome<P>, PayloadBuilderError>>,
     ) -> Self {
-        Self { _cancel: cancel, payload }
+        Self { _cancel: cancel, interrupt: ManualCancel::default(), payload }
+    }
+
+    /// Sets the marker to interrupt the job, that is passed to the job in
+    /// [`BuildArguments::interrupt`].
+    pub fn with_interrupt(mut self, interrupt: ManualCancel) -> Self {
+        self.interrupt = interrupt;
+        self
+    }
+
+    /// Interrupts the job, which then stops adding transactions and seals the payload with the
+    /// transactions included so far.
+    pub fn interrupt(&self) {
+        self.interrupt.clone().cancel();
     }
 }
 
@@ -802,19 +927,30 @@ pub struct BuildArguments<Attributes, Payload: BuiltPayload> {
     pub config: PayloadConfig<Attributes, HeaderTy<Payload::Primitives>>,
     /// A marker that can be used to cancel the job.
     pub cancel: CancelOnDrop,
+    /// A marker that is set when the payload is requested while it's being built.
+    ///
+    /// Once set, the builder should stop adding transactions and seal the payload with the
+    /// transactions included so far, instead of discarding it.
+    pub interrupt: ManualCancel,
     /// The best payload achieved so far.
     pub best_payload: Option<Payload>,
 }
 
 impl<Attributes, Payload: BuiltPayload> BuildArguments<Attributes, Payload> {
     /// Create new build arguments.
-    pub const fn new(
+    pub fn new(
         cached_reads: CachedReads,
         config: PayloadConfig<Attributes, HeaderTy<Payload::Primitives>>,
         cancel: CancelOnDrop,
         best_payload: Option<Payload>,
     ) -> Self {
-        Self { cached_reads, config, cancel, best_payload }
+        Self { cached_reads, config, cancel, interrupt: ManualCancel::default(), best_payload }
+    }
+
+    /// Sets the marker to interrupt the job.
+    pub fn with_interrupt(mut self, interrupt: ManualCancel) -> Self {
+        self.interrupt = interrupt;
+        self
     }
 }
 
@@ -912,3 +1048,76 @@ fn duration_until(unix_timestamp_secs: u64) -> Duration {
     let timestamp = Duration::from_secs(unix_timestamp_secs);
     timestamp.saturating_sub(unix_now)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    type PendingResult = Result<BuildOutcome<u64>, PayloadBuilderError>;
+
+    fn pending_payload() -> (PendingPayload<u64>, oneshot::Sender<PendingResult>, ManualCancel) {
+        let (tx, rx) = oneshot::channel();
+        let interrupt = ManualCancel::default();
+        let pending =
+            PendingPayload::new(CancelOnDrop::default(), rx).with_interrupt(interrupt.clone());
+        (pending, tx, interrupt)
+    }
+
+    #[tokio::test]
+    async fn resolve_returns_best_payload_without_interrupt_deadline() {
+        let (pending, _tx, _) = pending_payload();
+        let fut = ResolveBestPayload {
+            best_payload: Some(1),
+            maybe_better: Some(pending),
+            interrupt_deadline: None,
+            empty_payload: None,
+        };
+
+        let payload = tokio::time::timeout(Duration::from_secs(1), fut).await.unwrap().unwrap();
+        assert_eq!(payload, 1);
+    }
+
+    #[tokio::test]
+    async fn resolve_waits_for_interrupted_payload_until_deadline() {
+        let (pending, _tx, _) = pending_payload();
+        let timeout = Duration::from_millis(50);
+        let fut = ResolveBestPayload {
+            best_payload: Some(1),
+            maybe_better: Some(pending),
+            interrupt_deadline: Some(Box::pin(tokio::time::sleep(timeout))),
+            empty_payload: None,
+        };
+
+        // the pending job never finishes, so the best payload is returned at the deadline
+        let start = Instant::now();
+        let payload = tokio::time::timeout(Duration::from_secs(5), fut).await.unwrap().unwrap();
+        assert_eq!(payload, 1);
+        assert!(start.elapsed() >= timeout);
+    }
+
+    #[tokio::test]
+    async fn resolve_returns_interrupted_payload() {
+        let (pending, tx, interrupt) = pending_payload();
+
+        // the job seals its payload once it's interrupted
+        std::thread::spawn(move || {
+            while !interrupt.is_cancelled() {
+                std::thread::sleep(Duration::from_millis(1));
+            }
+            let _ =
+                tx.send(Ok(BuildOutcome::Better { payload: 2, cached_reads: Default::default() }));
+        });
+
+        pending.interrupt();
+        let fut = ResolveBestPayload {
+            best_payload: Some(1),
+            maybe_better: Some(pending),
+            interrupt_deadline: Some(Box::pin(tokio::time::sleep(Duration::from_secs(60)))),
+            empty_payload: None,
+        };
+
+        let payload = tokio::time::timeout(Duration::from_secs(5), fut).await.unwrap().unwrap();
+        assert_eq!(payload, 2);
+    }
+}
@@ -1,6 +1,10 @@
 //! Metrics for the payload builder impl
 
-use reth_metrics::{metrics::Counter, Metrics};
+use crate::PayloadJobStats;
+use reth_metrics::{
+    metrics::{Counter, Histogram},
+    Metrics,
+};
 
 /// Payload builder metrics
 #[derive(Metrics)]
@@ -12,6 +16,15 @@ pub(crate) struct PayloadBuilderMetrics {
     pub(crate) initiated_payload_builds: Counter,
     /// Total number of failed payload build attempts.
     pub(crate) failed_payload_builds: Counter,
+    /// Total number of payload build attempts that were interrupted because the payload was
+    /// requested.
+    pub(crate) interrupted_payload_builds: Counter,
+    /// Number of better payloads built by a job until it was resolved.
+    pub(crate) job_payloads_built: Histogram,
+    /// Total gas used by the payloads built by a job until it was resolved.
+    pub(crate) job_cumulative_gas_used: Histogram,
+    /// Coinbase revenue of the best payload built by a job until it was resolved.
+    pub(crate) job_revenue: Histogram,
 }
 
 impl PayloadBuilderMetrics {
@@ -26,4 +39,13 @@ impl PayloadBuilderMetrics {
     pub(crate) fn inc_failed_payload_builds(&self) {
         self.failed_payload_builds.increment(1);
     }
+
+    pub(crate) fn record_resolved_job(&self, stats: &PayloadJobStats) {
+        if stats.interrupted {
+            self.interrupted_payload_builds.increment(1);
+        }
+        self.job_payloads_built.record(stats.payloads_built as f64);
+        self.job_cumulative_gas_used.record(stats.cumulative_gas_used as f64);
+        self.job_revenue.record(f64::from(stats.revenue));
+    }
 }
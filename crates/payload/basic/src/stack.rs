@@ -194,7 +194,7 @@ where
         &self,
         args: BuildArguments<Self::Attributes, Self::BuiltPayload>,
     ) -> Result<BuildOutcome<Self::BuiltPayload>, PayloadBuilderError> {
-        let BuildArguments { cached_reads, config, cancel, best_payload } = args;
+        let BuildArguments { cached_reads, config, cancel, interrupt, best_payload } = args;
         let PayloadConfig { parent_header, attributes } = config;
 
         match attributes {
@@ -203,6 +203,7 @@ where
                     cached_reads,
                     config: PayloadConfig { parent_header, attributes: left_attr },
                     cancel,
+                    interrupt,
                     best_payload: best_payload.and_then(|payload| {
                         if let Either::Left(p) = payload {
                             Some(p)
@@ -218,6 +219,7 @@ where
                     cached_reads,
                     config: PayloadConfig { parent_header, attributes: right_attr },
                     cancel,
+                    interrupt,
                     best_payload: best_payload.and_then(|payload| {
                         if let Either::Right(p) = payload {
                             Some(p)
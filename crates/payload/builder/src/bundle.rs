@@ -0,0 +1,258 @@
+//! External bundles that are merged into built payloads.
+//!
+//! A [`PayloadBundle`] is an ordered list of transactions, e.g. submitted by a local searcher,
+//! that must be included atomically at the top of a payload. Bundles are provided to payload
+//! builders by a [`PayloadBundleSource`].
+
+use alloy_primitives::{
+    map::{B256HashSet, HashSet},
+    Address, BlockNumber, TxHash, U256,
+};
+use parking_lot::RwLock;
+use std::{fmt, sync::Arc};
+
+/// An ordered list of transactions that is either included in full or not at all.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PayloadBundle<T> {
+    /// The transactions of the bundle, in execution order.
+    pub transactions: Vec<T>,
+    /// Hashes of the transactions that are allowed to revert.
+    ///
+    /// If any other transaction of the bundle reverts, the whole bundle is discarded.
+    pub reverting_tx_hashes: B256HashSet,
+    /// The block number the bundle targets, or `None` if it is valid for any block.
+    pub block_number: Option<BlockNumber>,
+}
+
+impl<T> PayloadBundle<T> {
+    /// Creates a new bundle that is valid for any block and whose transactions must not revert.
+    pub fn new(transactions: Vec<T>) -> Self {
+        Self { transactions, reverting_tx_hashes: Default::default(), block_number: None }
+    }
+
+    /// Sets the hashes of the transactions that are allowed to revert.
+    pub fn with_reverting_tx_hashes(
+        mut self,
+        reverting_tx_hashes: impl IntoIterator<Item = TxHash>,
+    ) -> Self {
+        self.reverting_tx_hashes = reverting_tx_hashes.into_iter().collect();
+        self
+    }
+
+    /// Sets the block number the bundle targets.
+    pub const fn with_block_number(mut self, block_number: BlockNumber) -> Self {
+        self.block_number = Some(block_number);
+        self
+    }
+
+    /// Returns `true` if the transaction with the given hash is allowed to revert.
+    pub fn can_revert(&self, tx_hash: &TxHash) -> bool {
+        self.reverting_tx_hashes.contains(tx_hash)
+    }
+
+    /// Returns `true` if the bundle can be included in the block with the given number.
+    pub fn is_valid_for(&self, block_number: BlockNumber) -> bool {
+        self.block_number.is_none_or(|number| number == block_number)
+    }
+}
+
+/// Provides the bundles to merge into a payload.
+pub trait PayloadBundleSource<T>: fmt::Debug + Send + Sync {
+    /// Returns the bundles that should be merged into the payload for the given block, in order
+    /// of priority.
+    fn bundles(&self, block_number: BlockNumber) -> Vec<PayloadBundle<T>>;
+}
+
+impl<T, S: PayloadBundleSource<T> + ?Sized> PayloadBundleSource<T> for Arc<S> {
+    fn bundles(&self, block_number: BlockNumber) -> Vec<PayloadBundle<T>> {
+        (**self).bundles(block_number)
+    }
+}
+
+/// A [`PayloadBundleSource`] that never provides any bundles.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct NoopBundleSource;
+
+impl<T> PayloadBundleSource<T> for NoopBundleSource {
+    fn bundles(&self, _block_number: BlockNumber) -> Vec<PayloadBundle<T>> {
+        Vec::new()
+    }
+}
+
+/// A shared in-memory [`PayloadBundleSource`] that bundles can be submitted to.
+///
+/// Bundles are returned in submission order until they are pruned.
+#[derive(Debug)]
+pub struct PayloadBundlePool<T> {
+    bundles: Arc<RwLock<Vec<PayloadBundle<T>>>>,
+}
+
+impl<T> PayloadBundlePool<T> {
+    /// Creates a new, empty bundle pool.
+    pub fn new() -> Self {
+        Self { bundles: Default::default() }
+    }
+
+    /// Submits a bundle to the pool.
+    pub fn add_bundle(&self, bundle: PayloadBundle<T>) {
+        self.bundles.write().push(bundle);
+    }
+
+    /// Removes all bundles that target a block below the given block number.
+    pub fn prune(&self, block_number: BlockNumber) {
+        self.bundles
+            .write()
+            .retain(|bundle| bundle.block_number.is_none_or(|number| number >= block_number));
+    }
+
+    /// Removes all bundles from the pool.
+    pub fn clear(&self) {
+        self.bundles.write().clear();
+    }
+
+    /// Returns the number of bundles in the pool.
+    pub fn len(&self) -> usize {
+        self.bundles.read().len()
+    }
+
+    /// Returns `true` if the pool contains no bundles.
+    pub fn is_empty(&self) -> bool {
+        self.bundles.read().is_empty()
+    }
+}
+
+impl<T> Clone for PayloadBundlePool<T> {
+    fn clone(&self) -> Self {
+        Self { bundles: self.bundles.clone() }
+    }
+}
+
+impl<T> Default for PayloadBundlePool<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone + fmt::Debug + Send + Sync> PayloadBundleSource<T> for PayloadBundlePool<T> {
+    fn bundles(&self, block_number: BlockNumber) -> Vec<PayloadBundle<T>> {
+        self.bundles
+            .read()
+            .iter()
+            .filter(|bundle| bundle.is_valid_for(block_number))
+            .cloned()
+            .collect()
+    }
+}
+
+/// The accounts and storage slots read and written by a bundle.
+///
+/// Bundles are merged into a payload independently of each other, so a bundle that writes state
+/// read or written by an already merged bundle, or reads state written by one, conflicts with it
+/// and must not be merged.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StateAccess {
+    /// Accounts whose info was read.
+    pub accounts_read: HashSet<Address>,
+    /// Accounts whose info was changed.
+    pub accounts_written: HashSet<Address>,
+    /// Storage slots that were read.
+    pub slots_read: HashSet<(Address, U256)>,
+    /// Storage slots that were changed.
+    pub slots_written: HashSet<(Address, U256)>,
+}
+
+impl StateAccess {
+    /// Records a read of the account info.
+    pub fn read_account(&mut self, address: Address) {
+        self.accounts_read.insert(address);
+    }
+
+    /// Records a change of the account info.
+    pub fn write_account(&mut self, address: Address) {
+        self.accounts_written.insert(address);
+    }
+
+    /// Records a read of the storage slot.
+    pub fn read_slot(&mut self, address: Address, slot: U256) {
+        self.slots_read.insert((address, slot));
+    }
+
+    /// Records a change of the storage slot.
+    pub fn write_slot(&mut self, address: Address, slot: U256) {
+        self.slots_written.insert((address, slot));
+    }
+
+    /// Returns `true` if either access writes state that the other one reads or writes.
+    pub fn conflicts_with(&self, other: &Self) -> bool {
+        fn overlaps<T: Eq + std::hash::Hash>(a: &HashSet<T>, b: &HashSet<T>) -> bool {
+            let (small, large) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+            small.iter().any(|item| large.contains(item))
+        }
+
+        overlaps(&self.accounts_written, &other.accounts_written) ||
+            overlaps(&self.accounts_written, &other.accounts_read) ||
+            overlaps(&self.accounts_read, &other.accounts_written) ||
+            overlaps(&self.slots_written, &other.slots_written) ||
+            overlaps(&self.slots_written, &other.slots_read) ||
+            overlaps(&self.slots_read, &other.slots_written)
+    }
+
+    /// Merges the other access into this one.
+    pub fn extend(&mut self, other: Self) {
+        self.accounts_read.extend(other.accounts_read);
+        self.accounts_written.extend(other.accounts_written);
+        self.slots_read.extend(other.slots_read);
+        self.slots_written.extend(other.slots_written);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundle_pool_prune() {
+        let pool = PayloadBundlePool::default();
+        pool.add_bundle(PayloadBundle::new(vec![1]).with_block_number(1));
+        pool.add_bundle(PayloadBundle::new(vec![2]).with_block_number(2));
+        pool.add_bundle(PayloadBundle::new(vec![3]));
+
+        let bundles = pool.bundles(2);
+        assert_eq!(bundles.len(), 2);
+        assert_eq!(bundles[0].transactions, vec![2]);
+        assert_eq!(bundles[1].transactions, vec![3]);
+
+        pool.prune(2);
+        assert_eq!(pool.len(), 2);
+        pool.prune(3);
+        assert_eq!(pool.len(), 1);
+        assert_eq!(pool.bundles(10)[0].transactions, vec![3]);
+    }
+
+    #[test]
+    fn state_access_conflicts() {
+        let address = Address::with_last_byte(1);
+        let slot = U256::from(1);
+
+        let mut reader = StateAccess::default();
+        reader.read_account(address);
+        reader.read_slot(address, slot);
+
+        // concurrent reads don't conflict
+        assert!(!reader.conflicts_with(&reader.clone()));
+
+        let mut writer = StateAccess::default();
+        writer.write_slot(address, slot);
+        assert!(reader.conflicts_with(&writer));
+        assert!(writer.conflicts_with(&reader));
+
+        let mut other = StateAccess::default();
+        other.write_slot(address, U256::from(2));
+        other.write_account(Address::with_last_byte(2));
+        assert!(!writer.conflicts_with(&other));
+
+        writer.extend(other.clone());
+        assert!(writer.conflicts_with(&other));
+    }
+}
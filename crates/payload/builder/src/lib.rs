@@ -107,6 +107,7 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
+mod bundle;
 mod metrics;
 mod service;
 mod traits;
@@ -117,6 +118,9 @@ pub mod noop;
 pub mod test_utils;
 
 pub use alloy_rpc_types::engine::PayloadId;
+pub use bundle::{
+    NoopBundleSource, PayloadBundle, PayloadBundlePool, PayloadBundleSource, StateAccess,
+};
 pub use reth_payload_builder_primitives::PayloadBuilderError;
 pub use reth_payload_primitives::PayloadKind;
 pub use service::{
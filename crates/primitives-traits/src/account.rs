@@ -1,11 +1,9 @@
 use crate::InMemorySize;
 use alloy_consensus::constants::KECCAK_EMPTY;
-use alloy_genesis::GenesisAccount;
-use alloy_primitives::{keccak256, Bytes, B256, U256};
+use alloy_primitives::{Bytes, B256, U256};
 use alloy_trie::TrieAccount;
 use derive_more::Deref;
 use revm_bytecode::{Bytecode as RevmBytecode, BytecodeDecodeError};
-use revm_state::AccountInfo;
 
 #[cfg(any(test, feature = "reth-codec"))]
 /// Identifiers used in [`Compact`](reth_codecs::Compact) encoding of [`Bytecode`].
@@ -70,6 +68,7 @@ impl Account {
     }
 
     /// Extracts the account information from a [`revm_state::Account`]
+    #[cfg(feature = "revm")]
     pub fn from_revm_account(revm_account: &revm_state::Account) -> Self {
         Self {
             balance: revm_account.info.balance,
@@ -83,6 +82,7 @@ impl Account {
     }
 }
 
+#[cfg(feature = "revm")]
 impl From<revm_state::Account> for Account {
     fn from(value: revm_state::Account) -> Self {
         Self::from_revm_account(&value)
@@ -201,18 +201,20 @@ impl reth_codecs::Compact for Bytecode {
     }
 }
 
-impl From<&GenesisAccount> for Account {
-    fn from(value: &GenesisAccount) -> Self {
+#[cfg(feature = "genesis")]
+impl From<&alloy_genesis::GenesisAccount> for Account {
+    fn from(value: &alloy_genesis::GenesisAccount) -> Self {
         Self {
             nonce: value.nonce.unwrap_or_default(),
             balance: value.balance,
-            bytecode_hash: value.code.as_ref().map(keccak256),
+            bytecode_hash: value.code.as_ref().map(alloy_primitives::keccak256),
         }
     }
 }
 
-impl From<AccountInfo> for Account {
-    fn from(revm_acc: AccountInfo) -> Self {
+#[cfg(feature = "revm")]
+impl From<revm_state::AccountInfo> for Account {
+    fn from(revm_acc: revm_state::AccountInfo) -> Self {
         Self {
             balance: revm_acc.balance,
             nonce: revm_acc.nonce,
@@ -221,8 +223,9 @@ impl From<AccountInfo> for Account {
     }
 }
 
-impl From<&AccountInfo> for Account {
-    fn from(revm_acc: &AccountInfo) -> Self {
+#[cfg(feature = "revm")]
+impl From<&revm_state::AccountInfo> for Account {
+    fn from(revm_acc: &revm_state::AccountInfo) -> Self {
         Self {
             balance: revm_acc.balance,
             nonce: revm_acc.nonce,
@@ -231,7 +234,8 @@ impl From<&AccountInfo> for Account {
     }
 }
 
-impl From<Account> for AccountInfo {
+#[cfg(feature = "revm")]
+impl From<Account> for revm_state::AccountInfo {
     fn from(reth_acc: Account) -> Self {
         Self {
             balance: reth_acc.balance,
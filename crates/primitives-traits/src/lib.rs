@@ -17,6 +17,9 @@
 //! ## Feature Flags
 //!
 //! - `arbitrary`: Adds `proptest` and `arbitrary` support for primitive types.
+//! - `genesis`: Adds conversions from `alloy-genesis` accounts. Enabled by default.
+//! - `revm`: Adds conversions between [`Account`] and the `revm-state` account types. Enabled by
+//!   default.
 //! - `op`: Implements the traits for various [op-alloy](https://github.com/alloy-rs/op-alloy)
 //!   types.
 //! - `reth-codec`: Enables db codec support for reth types including zstd compression for certain
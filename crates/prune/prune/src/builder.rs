@@ -1,8 +1,8 @@
-use crate::{segments::SegmentSet, Pruner};
+use crate::{pruner::PruneConfigUpdates, segments::SegmentSet, Pruner};
 use alloy_eips::eip2718::Encodable2718;
 use reth_config::PruneConfig;
 use reth_db_api::{table::Value, transaction::DbTxMut};
-use reth_exex_types::FinishedExExHeight;
+use reth_exex_types::FinishedExExHeightReceiver;
 use reth_primitives_traits::NodePrimitives;
 use reth_provider::{
     providers::StaticFileProvider, BlockReader, ChainStateBlockReader, DBProvider,
@@ -26,7 +26,9 @@ pub struct PrunerBuilder {
     /// Time a pruner job can run before timing out.
     timeout: Option<Duration>,
     /// The finished height of all `ExEx`'s.
-    finished_exex_height: watch::Receiver<FinishedExExHeight>,
+    finished_exex_height: FinishedExExHeightReceiver,
+    /// Updates of the pruning configuration.
+    config_updates: Option<watch::Receiver<PruneConfig>>,
 }
 
 impl PrunerBuilder {
@@ -65,14 +67,26 @@ impl PrunerBuilder {
     }
 
     /// Sets the receiver for the finished height of all `ExEx`'s.
+    ///
+    /// The pruner will not prune any data above the reported height. See
+    /// [`FinishedExExHeightReceiver`] for details.
     pub fn finished_exex_height(
         mut self,
-        finished_exex_height: watch::Receiver<FinishedExExHeight>,
+        finished_exex_height: FinishedExExHeightReceiver,
     ) -> Self {
         self.finished_exex_height = finished_exex_height;
         self
     }
 
+    /// Sets the receiver of pruning configuration updates.
+    ///
+    /// Before every run, the pruner rebuilds its segments and updates its minimum pruning interval
+    /// if the configuration was updated, without having to restart the node.
+    pub fn config_updates(mut self, config_updates: watch::Receiver<PruneConfig>) -> Self {
+        self.config_updates = Some(config_updates);
+        self
+    }
+
     /// Builds a [Pruner] from the current configuration with the given provider factory.
     pub fn build_with_provider_factory<PF>(self, provider_factory: PF) -> Pruner<PF::ProviderRW, PF>
     where
@@ -92,17 +106,27 @@ impl PrunerBuilder {
                 Primitives = <PF::ProviderRW as NodePrimitivesProvider>::Primitives,
             >,
     {
-        let segments =
-            SegmentSet::from_components(provider_factory.static_file_provider(), self.segments);
+        let static_file_provider = provider_factory.static_file_provider();
+        let segments = SegmentSet::from_components(static_file_provider.clone(), self.segments);
 
-        Pruner::new_with_factory(
+        let pruner = Pruner::new_with_factory(
             provider_factory,
             segments.into_vec(),
             self.block_interval,
             self.delete_limit,
             self.timeout,
             self.finished_exex_height,
-        )
+        );
+
+        match self.config_updates {
+            Some(receiver) => pruner.with_config_updates(PruneConfigUpdates::new(
+                receiver,
+                Box::new(move |modes| {
+                    SegmentSet::from_components(static_file_provider.clone(), modes).into_vec()
+                }),
+            )),
+            None => pruner,
+        }
     }
 
     /// Builds a [Pruner] from the current configuration with the given static file provider.
@@ -123,15 +147,27 @@ impl PrunerBuilder {
             + ChangeSetReader
             + StorageChangeSetReader,
     {
-        let segments = SegmentSet::<Provider>::from_components(static_file_provider, self.segments);
+        let segments =
+            SegmentSet::<Provider>::from_components(static_file_provider.clone(), self.segments);
 
-        Pruner::new(
+        let pruner = Pruner::new(
             segments.into_vec(),
             self.block_interval,
             self.delete_limit,
             self.timeout,
             self.finished_exex_height,
-        )
+        );
+
+        match self.config_updates {
+            Some(receiver) => pruner.with_config_updates(PruneConfigUpdates::new(
+                receiver,
+                Box::new(move |modes| {
+                    SegmentSet::<Provider>::from_components(static_file_provider.clone(), modes)
+                        .into_vec()
+                }),
+            )),
+            None => pruner,
+        }
     }
 }
 
@@ -142,7 +178,8 @@ impl Default for PrunerBuilder {
             segments: PruneModes::default(),
             delete_limit: usize::MAX,
             timeout: None,
-            finished_exex_height: watch::channel(FinishedExExHeight::NoExExs).1,
+            finished_exex_height: FinishedExExHeightReceiver::no_exexs(),
+            config_updates: None,
         }
     }
 }
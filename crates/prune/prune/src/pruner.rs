@@ -4,18 +4,25 @@ use crate::{
     segments::{PruneInput, Segment},
     Metrics, PruneLimiter, PrunerError, PrunerEvent,
 };
+use alloy_consensus::BlockHeader;
 use alloy_primitives::BlockNumber;
-use reth_exex_types::FinishedExExHeight;
+use reth_config::PruneConfig;
+use reth_exex_types::{FinishedExExHeight, FinishedExExHeightReceiver};
 use reth_provider::{
-    DBProvider, DatabaseProviderFactory, PruneCheckpointReader, PruneCheckpointWriter,
-    StageCheckpointReader,
+    DBProvider, DatabaseProviderFactory, HeaderProvider, PruneCheckpointReader,
+    PruneCheckpointWriter, StageCheckpointReader,
+};
+use reth_prune_types::{
+    PruneMode, PruneModes, PruneProgress, PruneSegment, PrunedSegmentInfo, PrunerOutput,
 };
-use reth_prune_types::{PruneProgress, PrunedSegmentInfo, PrunerOutput};
 use reth_stages_types::StageId;
 use reth_tokio_util::{EventSender, EventStream};
-use std::time::{Duration, Instant};
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
 use tokio::sync::watch;
-use tracing::debug;
+use tracing::{debug, info};
 
 /// Result of [`Pruner::run`] execution.
 pub type PrunerResult = Result<PrunerOutput, PrunerError>;
@@ -26,6 +33,10 @@ pub type PrunerWithResult<S, DB> = (Pruner<S, DB>, PrunerResult);
 /// Pruner with preset provider factory.
 pub type PrunerWithFactory<PF> = Pruner<<PF as DatabaseProviderFactory>::ProviderRW, PF>;
 
+/// Function building the prune segments for the given prune modes.
+pub(crate) type SegmentsFn<Provider> =
+    Box<dyn Fn(PruneModes) -> Vec<Box<dyn Segment<Provider>>> + Send + Sync>;
+
 /// Pruning routine. Main pruning logic happens in [`Pruner::run`].
 #[derive(Debug)]
 pub struct Pruner<Provider, PF> {
@@ -44,12 +55,39 @@ pub struct Pruner<Provider, PF> {
     /// Maximum time for one pruner run.
     timeout: Option<Duration>,
     /// The finished height of all `ExEx`'s.
-    finished_exex_height: watch::Receiver<FinishedExExHeight>,
+    finished_exex_height: FinishedExExHeightReceiver,
+    /// Updates of the pruning configuration, applied before the next run.
+    config_updates: Option<PruneConfigUpdates<Provider>>,
     #[doc(hidden)]
     metrics: Metrics,
     event_sender: EventSender<PrunerEvent>,
 }
 
+/// Receiver of pruning configuration updates, with the function rebuilding the prune segments for
+/// the updated configuration.
+pub(crate) struct PruneConfigUpdates<Provider> {
+    receiver: watch::Receiver<PruneConfig>,
+    segments: SegmentsFn<Provider>,
+}
+
+impl<Provider> PruneConfigUpdates<Provider> {
+    /// Creates a new instance with the given receiver and segments function.
+    pub(crate) fn new(
+        receiver: watch::Receiver<PruneConfig>,
+        segments: SegmentsFn<Provider>,
+    ) -> Self {
+        Self { receiver, segments }
+    }
+}
+
+impl<Provider> fmt::Debug for PruneConfigUpdates<Provider> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PruneConfigUpdates")
+            .field("receiver", &self.receiver)
+            .finish_non_exhaustive()
+    }
+}
+
 impl<Provider> Pruner<Provider, ()> {
     /// Creates a new [Pruner] without a provider factory.
     pub fn new(
@@ -57,7 +95,7 @@ impl<Provider> Pruner<Provider, ()> {
         min_block_interval: usize,
         delete_limit: usize,
         timeout: Option<Duration>,
-        finished_exex_height: watch::Receiver<FinishedExExHeight>,
+        finished_exex_height: FinishedExExHeightReceiver,
     ) -> Self {
         Self {
             provider_factory: (),
@@ -67,6 +105,7 @@ impl<Provider> Pruner<Provider, ()> {
             delete_limit,
             timeout,
             finished_exex_height,
+            config_updates: None,
             metrics: Metrics::default(),
             event_sender: Default::default(),
         }
@@ -84,7 +123,7 @@ where
         min_block_interval: usize,
         delete_limit: usize,
         timeout: Option<Duration>,
-        finished_exex_height: watch::Receiver<FinishedExExHeight>,
+        finished_exex_height: FinishedExExHeightReceiver,
     ) -> Self {
         Self {
             provider_factory,
@@ -94,6 +133,7 @@ where
             delete_limit,
             timeout,
             finished_exex_height,
+            config_updates: None,
             metrics: Metrics::default(),
             event_sender: Default::default(),
         }
@@ -102,13 +142,45 @@ where
 
 impl<Provider, S> Pruner<Provider, S>
 where
-    Provider: PruneCheckpointReader + PruneCheckpointWriter + StageCheckpointReader,
+    Provider:
+        PruneCheckpointReader + PruneCheckpointWriter + StageCheckpointReader + HeaderProvider,
 {
     /// Listen for events on the pruner.
     pub fn events(&self) -> EventStream<PrunerEvent> {
         self.event_sender.new_listener()
     }
 
+    /// Sets the receiver of pruning configuration updates.
+    pub(crate) fn with_config_updates(
+        mut self,
+        config_updates: PruneConfigUpdates<Provider>,
+    ) -> Self {
+        self.config_updates = Some(config_updates);
+        self
+    }
+
+    /// Rebuilds the prune segments and updates the minimum pruning interval if the pruning
+    /// configuration was updated since the last run.
+    fn apply_config_updates(&mut self) {
+        let Some(updates) = &mut self.config_updates else { return };
+        if !updates.receiver.has_changed().unwrap_or_default() {
+            return
+        }
+
+        let config = updates.receiver.borrow_and_update().clone();
+        info!(target: "pruner", ?config, "Pruning configuration updated");
+        self.segments = (updates.segments)(config.segments);
+        self.min_block_interval = config.block_interval;
+    }
+
+    /// Returns the minimum pruning interval, taking the latest pruning configuration update into
+    /// account.
+    fn min_block_interval(&self) -> usize {
+        self.config_updates
+            .as_ref()
+            .map_or(self.min_block_interval, |updates| updates.receiver.borrow().block_interval)
+    }
+
     /// Run the pruner with the given provider. This will only prune data up to the highest finished
     /// `ExEx` height, if there are no `ExExes`.
     ///
@@ -124,6 +196,7 @@ where
         else {
             return Ok(PruneProgress::Finished.into())
         };
+        self.apply_config_updates();
         if tip_block_number == 0 {
             self.previous_tip_block_number = Some(tip_block_number);
 
@@ -196,6 +269,8 @@ where
 
             if let Some((to_block, prune_mode)) = segment
                 .mode()
+                .map(|mode| resolve_prune_mode(provider, mode, segment.segment(), tip_block_number))
+                .transpose()?
                 .map(|mode| {
                     mode.prune_target_block(tip_block_number, segment.segment(), segment.purpose())
                 })
@@ -292,7 +367,7 @@ where
         // current block number might be less than the previous tip block number.
         // If that's the case, no pruning is needed as outdated data is also reverted.
         if tip_block_number.saturating_sub(self.previous_tip_block_number.unwrap_or_default()) >=
-            self.min_block_interval as u64
+            self.min_block_interval() as u64
         {
             debug!(
                 target: "pruner",
@@ -317,7 +392,7 @@ where
         &self,
         tip_block_number: BlockNumber,
     ) -> Option<BlockNumber> {
-        match *self.finished_exex_height.borrow() {
+        match self.finished_exex_height.current() {
             FinishedExExHeight::NoExExs => Some(tip_block_number),
             FinishedExExHeight::NotReady => {
                 debug!(target: "pruner", %tip_block_number, "Not all ExExs have emitted a `FinishedHeight` event yet, can't prune");
@@ -334,7 +409,10 @@ where
 impl<PF> Pruner<PF::ProviderRW, PF>
 where
     PF: DatabaseProviderFactory<
-        ProviderRW: PruneCheckpointWriter + PruneCheckpointReader + StageCheckpointReader,
+        ProviderRW: PruneCheckpointWriter
+                        + PruneCheckpointReader
+                        + StageCheckpointReader
+                        + HeaderProvider,
     >,
 {
     /// Run the pruner. This will only prune data up to the highest finished ExEx height, if there
@@ -350,6 +428,33 @@ where
     }
 }
 
+/// Resolves time based prune modes into block based ones using the header timestamps.
+fn resolve_prune_mode<Provider: HeaderProvider>(
+    provider: &Provider,
+    mode: PruneMode,
+    segment: PruneSegment,
+    tip_block_number: BlockNumber,
+) -> Result<PruneMode, PrunerError> {
+    if !mode.is_time_based() {
+        return Ok(mode)
+    }
+
+    let resolved = mode.resolve_time_based(tip_block_number, segment, |block_number| {
+        Ok::<_, PrunerError>(
+            provider.header_by_number(block_number)?.map(|header| header.timestamp()),
+        )
+    })?;
+    debug!(
+        target: "pruner",
+        ?mode,
+        ?resolved,
+        %tip_block_number,
+        "Resolved time based prune mode"
+    );
+
+    Ok(resolved)
+}
+
 /// Checks if the given stage has caught up with the `Finish` stage.
 ///
 /// Returns `true` if the stage checkpoint is >= the Finish stage checkpoint.
@@ -365,9 +470,11 @@ fn is_stage_finished<Provider: StageCheckpointReader>(
 
 #[cfg(test)]
 mod tests {
-    use crate::Pruner;
-    use reth_exex_types::FinishedExExHeight;
+    use crate::{Pruner, PrunerBuilder};
+    use reth_config::PruneConfig;
+    use reth_exex_types::{FinishedExExHeight, FinishedExExHeightReceiver};
     use reth_provider::test_utils::create_test_provider_factory;
+    use reth_prune_types::{PruneMode, PruneModes, PruneSegment};
 
     #[test]
     fn is_pruning_needed() {
@@ -376,8 +483,14 @@ mod tests {
         let (finished_exex_height_tx, finished_exex_height_rx) =
             tokio::sync::watch::channel(FinishedExExHeight::NoExExs);
 
-        let mut pruner =
-            Pruner::new_with_factory(provider_factory, vec![], 5, 0, None, finished_exex_height_rx);
+        let mut pruner = Pruner::new_with_factory(
+            provider_factory,
+            vec![],
+            5,
+            0,
+            None,
+            finished_exex_height_rx.into(),
+        );
 
         // No last pruned block number was set before
         let first_block_number = 1;
@@ -408,4 +521,42 @@ mod tests {
         finished_exex_height_tx.send(FinishedExExHeight::Height(third_block_number)).unwrap();
         assert!(pruner.is_pruning_needed(third_block_number));
     }
+
+    #[test]
+    fn config_updates() {
+        let config = PruneConfig {
+            block_interval: 5,
+            segments: PruneModes { sender_recovery: Some(PruneMode::Full), ..Default::default() },
+        };
+        let (config_tx, config_rx) = tokio::sync::watch::channel(config.clone());
+
+        let mut pruner = PrunerBuilder::new(config)
+            .config_updates(config_rx)
+            .build_with_provider_factory(create_test_provider_factory());
+        pruner.previous_tip_block_number = Some(1);
+        assert!(pruner.is_pruning_needed(6));
+
+        config_tx
+            .send(PruneConfig {
+                block_interval: 10,
+                segments: PruneModes {
+                    sender_recovery: Some(PruneMode::Full),
+                    transaction_lookup: Some(PruneMode::Distance(100)),
+                    ..Default::default()
+                },
+            })
+            .unwrap();
+
+        // The updated minimum pruning interval is used right away
+        assert!(!pruner.is_pruning_needed(6));
+        assert!(pruner.is_pruning_needed(11));
+
+        // The segments are rebuilt before the next run
+        pruner.apply_config_updates();
+        assert_eq!(pruner.min_block_interval, 10);
+        assert_eq!(
+            pruner.segments.iter().map(|segment| segment.segment()).collect::<Vec<_>>(),
+            [PruneSegment::TransactionLookup, PruneSegment::SenderRecovery]
+        );
+    }
 }
@@ -47,7 +47,7 @@ mod tests {
     use super::*;
     use crate::Pruner;
     use alloy_primitives::BlockNumber;
-    use reth_exex_types::FinishedExExHeight;
+    use reth_exex_types::FinishedExExHeightReceiver;
     use reth_provider::{
         test_utils::{create_test_provider_factory, MockNodeTypesWithDB},
         ProviderFactory, StaticFileWriter,
@@ -101,7 +101,7 @@ mod tests {
 
     fn run_prune_test(
         factory: &ProviderFactory<MockNodeTypesWithDB>,
-        finished_exex_height_rx: &tokio::sync::watch::Receiver<FinishedExExHeight>,
+        finished_exex_height_rx: &FinishedExExHeightReceiver,
         test_case: PruneTestCase,
         tip: BlockNumber,
     ) {
@@ -143,7 +143,7 @@ mod tests {
         let tip = 2_499_999;
         setup_static_file_jars(&factory, tip);
 
-        let (_, finished_exex_height_rx) = tokio::sync::watch::channel(FinishedExExHeight::NoExExs);
+        let finished_exex_height_rx = FinishedExExHeightReceiver::no_exexs();
 
         let test_cases = vec![
             // Test 1: PruneMode::Before(750_000) → deletes jar 1 (0-499_999)
@@ -15,6 +15,19 @@ pub enum PruneMode {
     Distance(u64),
     /// Prune blocks before the specified block number. The specified block number is not pruned.
     Before(BlockNumber),
+    /// Prune blocks with a timestamp before the specified unix timestamp in seconds.
+    ///
+    /// Needs to be resolved into a block number with [`PruneMode::resolve_time_based`] before
+    /// pruning.
+    #[cfg_attr(any(test, feature = "serde"), serde(rename = "before_timestamp"))]
+    BeforeTimestamp(u64),
+    /// Prune blocks that are older than the specified number of seconds, relative to the timestamp
+    /// of the tip block. In other words, keep blocks of the last N seconds.
+    ///
+    /// Needs to be resolved into a block number with [`PruneMode::resolve_time_based`] before
+    /// pruning.
+    #[cfg_attr(any(test, feature = "serde"), serde(rename = "older_than"))]
+    OlderThan(u64),
 }
 
 #[cfg(any(test, feature = "test-utils"))]
@@ -33,8 +46,57 @@ impl PruneMode {
         Self::Before(block_number + 1)
     }
 
+    /// Resolves a time based prune mode into a [`PruneMode::Before`] using the provided tip.
+    ///
+    /// `timestamp_at` returns the timestamp of the given block, or `None` if the block is not
+    /// known. The resolved block is the first block with a timestamp at or after the cutoff, so
+    /// all blocks before the cutoff are pruned. Block based modes are returned unchanged, as are
+    /// time based modes if the tip timestamp is not known.
+    ///
+    /// Time based modes can't be validated against the minimum number of blocks of the segment
+    /// when they are configured, so the resolved block is capped to keep at least
+    /// [`PruneSegment::min_blocks`] blocks.
+    pub fn resolve_time_based<E>(
+        &self,
+        tip: BlockNumber,
+        segment: PruneSegment,
+        mut timestamp_at: impl FnMut(BlockNumber) -> Result<Option<u64>, E>,
+    ) -> Result<Self, E> {
+        let cutoff = match self {
+            Self::BeforeTimestamp(timestamp) => *timestamp,
+            Self::OlderThan(seconds) => {
+                let Some(tip_timestamp) = timestamp_at(tip)? else { return Ok(*self) };
+                tip_timestamp.saturating_sub(*seconds)
+            }
+            _ => return Ok(*self),
+        };
+
+        // Binary search for the first block with a timestamp at or after the cutoff. Block
+        // timestamps are strictly increasing, so the search is well-defined.
+        let (mut low, mut high) = (0, tip + 1);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            match timestamp_at(mid)? {
+                Some(timestamp) if timestamp < cutoff => low = mid + 1,
+                Some(_) => high = mid,
+                // Block is not known, nothing to resolve against
+                None => return Ok(*self),
+            }
+        }
+
+        let min_blocks = segment.min_blocks();
+        if min_blocks > 0 {
+            low = low.min(tip.saturating_sub(min_blocks));
+        }
+
+        Ok(Self::Before(low))
+    }
+
     /// Returns block up to which variant pruning needs to be done, inclusive, according to the
     /// provided tip.
+    ///
+    /// Time based modes that were not resolved with [`PruneMode::resolve_time_based`] have nothing
+    /// to prune.
     pub fn prune_target_block(
         &self,
         tip: BlockNumber,
@@ -52,12 +114,15 @@ impl PruneMode {
             Self::Before(n) => {
                 (tip - n >= segment.min_blocks()).then(|| ((*n).saturating_sub(1), *self))
             }
+            Self::BeforeTimestamp(_) | Self::OlderThan(_) => None,
             _ => return Err(PruneSegmentError::Configuration(segment)),
         };
         Ok(result)
     }
 
     /// Check if target block should be pruned according to the provided prune mode and tip.
+    ///
+    /// Time based modes never prune, because they can't be evaluated without block timestamps.
     pub const fn should_prune(&self, block: BlockNumber, tip: BlockNumber) -> bool {
         match self {
             Self::Full => true,
@@ -68,6 +133,7 @@ impl PruneMode {
                 block < tip - *distance
             }
             Self::Before(n) => *n > block,
+            Self::BeforeTimestamp(_) | Self::OlderThan(_) => false,
         }
     }
 
@@ -80,6 +146,12 @@ impl PruneMode {
     pub const fn is_distance(&self) -> bool {
         matches!(self, Self::Distance(_))
     }
+
+    /// Returns true if the prune mode is [`PruneMode::BeforeTimestamp`] or
+    /// [`PruneMode::OlderThan`].
+    pub const fn is_time_based(&self) -> bool {
+        matches!(self, Self::BeforeTimestamp(_) | Self::OlderThan(_))
+    }
 }
 
 #[cfg(test)]
@@ -157,6 +229,8 @@ mod tests {
             ),
             (PruneMode::Before(tip + 1), 1, should_prune),
             (PruneMode::Before(tip + 1), tip + 1, !should_prune),
+            (PruneMode::OlderThan(0), 1, !should_prune),
+            (PruneMode::BeforeTimestamp(u64::MAX), 1, !should_prune),
         ];
 
         for (index, (mode, block, expected_result)) in tests.into_iter().enumerate() {
@@ -164,6 +238,97 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_resolve_time_based() {
+        // block `n` has timestamp `1000 + n * 12`
+        let tip = 100;
+        let timestamp_at = |block| Ok::<_, ()>((block <= tip).then_some(1000 + block * 12));
+
+        // keep the last 120 seconds, i.e. blocks with timestamp >= 2080
+        assert_eq!(
+            PruneMode::OlderThan(120).resolve_time_based(tip, PruneSegment::Receipts, timestamp_at),
+            Ok(PruneMode::Before(90))
+        );
+        // cutoff between two blocks
+        assert_eq!(
+            PruneMode::BeforeTimestamp(1013).resolve_time_based(
+                tip,
+                PruneSegment::Receipts,
+                timestamp_at
+            ),
+            Ok(PruneMode::Before(2))
+        );
+        // cutoff before genesis, nothing to prune
+        assert_eq!(
+            PruneMode::BeforeTimestamp(0).resolve_time_based(
+                tip,
+                PruneSegment::Receipts,
+                timestamp_at
+            ),
+            Ok(PruneMode::Before(0))
+        );
+        // cutoff after tip, everything is pruned
+        assert_eq!(
+            PruneMode::BeforeTimestamp(u64::MAX).resolve_time_based(
+                tip,
+                PruneSegment::Receipts,
+                timestamp_at
+            ),
+            Ok(PruneMode::Before(tip + 1))
+        );
+        // retention longer than the chain
+        assert_eq!(
+            PruneMode::OlderThan(u64::MAX).resolve_time_based(
+                tip,
+                PruneSegment::Receipts,
+                timestamp_at
+            ),
+            Ok(PruneMode::Before(0))
+        );
+        // block based modes are unchanged
+        assert_eq!(
+            PruneMode::Distance(10).resolve_time_based(tip, PruneSegment::Receipts, timestamp_at),
+            Ok(PruneMode::Distance(10))
+        );
+        // unknown tip
+        assert_eq!(
+            PruneMode::OlderThan(120).resolve_time_based(
+                tip + 1,
+                PruneSegment::Receipts,
+                timestamp_at
+            ),
+            Ok(PruneMode::OlderThan(120))
+        );
+
+        // the minimum number of blocks of the segment is kept
+        assert_eq!(
+            PruneMode::BeforeTimestamp(u64::MAX).resolve_time_based(
+                MINIMUM_PRUNING_DISTANCE + tip,
+                PruneSegment::AccountHistory,
+                |block| Ok::<_, ()>(Some(1000 + block * 12))
+            ),
+            Ok(PruneMode::Before(tip))
+        );
+        assert_eq!(
+            PruneMode::BeforeTimestamp(u64::MAX).resolve_time_based(
+                tip,
+                PruneSegment::AccountHistory,
+                timestamp_at
+            ),
+            Ok(PruneMode::Before(0))
+        );
+
+        // unresolved time based modes have nothing to prune
+        assert_eq!(
+            PruneMode::OlderThan(120).prune_target_block(
+                tip,
+                PruneSegment::Receipts,
+                PrunePurpose::User
+            ),
+            Ok(None)
+        );
+    }
+
     #[test]
     fn prune_mode_deserialize() {
         #[derive(Debug, Deserialize)]
@@ -172,12 +337,16 @@ mod tests {
             b: Option<PruneMode>,
             c: Option<PruneMode>,
             d: Option<PruneMode>,
+            e: Option<PruneMode>,
+            f: Option<PruneMode>,
         }
 
         let toml_str = r#"
         a = "full"
         b = { distance = 10 }
         c = { before = 20 }
+        e = { before_timestamp = 1700000000 }
+        f = { older_than = 31536000 }
     "#;
 
         assert_matches!(
@@ -186,7 +355,9 @@ mod tests {
                 a: Some(PruneMode::Full),
                 b: Some(PruneMode::Distance(10)),
                 c: Some(PruneMode::Before(20)),
-                d: None
+                d: None,
+                e: Some(PruneMode::BeforeTimestamp(1700000000)),
+                f: Some(PruneMode::OlderThan(31536000)),
             })
         );
     }
@@ -0,0 +1,272 @@
+use alloy_primitives::{map::HashMap, Address, BlockHash};
+use revm::{
+    bytecode::opcode::{self, OpCode},
+    context_interface::{ContextTr, JournalTr},
+    inspector::Inspector,
+    interpreter::{
+        interpreter::EthInterpreter, interpreter_types::Jumps, CallInputs, CallOutcome,
+        CreateInputs, CreateOutcome, Interpreter,
+    },
+};
+use schnellru::{ByLength, LruMap};
+use std::sync::{Arc, LazyLock};
+
+/// The number of recently executed blocks whose stats are kept in the [`ExecutionStatsStore`].
+pub const EXECUTION_STATS_STORE_CAPACITY: u32 = 256;
+
+/// The number of executions and the gas spent by an opcode or precompile.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Usage {
+    /// The number of executions.
+    pub count: u64,
+    /// The gas spent.
+    pub gas_used: u64,
+}
+
+impl Usage {
+    /// Adds the other usage to this one.
+    pub const fn add(&mut self, other: Self) {
+        self.count += other.count;
+        self.gas_used += other.gas_used;
+    }
+}
+
+/// The aggregated opcode and precompile usage of executed transactions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecutionStats {
+    /// The usage of every opcode, indexed by opcode.
+    opcodes: Box<[Usage; 256]>,
+    /// The usage of every called precompile.
+    precompiles: HashMap<Address, Usage>,
+}
+
+impl ExecutionStats {
+    /// Returns the usage of the given opcode.
+    pub fn opcode(&self, opcode: u8) -> Usage {
+        self.opcodes[opcode as usize]
+    }
+
+    /// Returns a mutable reference to the usage of the given opcode.
+    pub fn opcode_mut(&mut self, opcode: u8) -> &mut Usage {
+        &mut self.opcodes[opcode as usize]
+    }
+
+    /// Returns the usage of the precompile at the given address.
+    pub fn precompile(&self, address: &Address) -> Usage {
+        self.precompiles.get(address).copied().unwrap_or_default()
+    }
+
+    /// Returns a mutable reference to the usage of the precompile at the given address.
+    pub fn precompile_mut(&mut self, address: Address) -> &mut Usage {
+        self.precompiles.entry(address).or_default()
+    }
+
+    /// Returns an iterator over all opcodes that were executed at least once.
+    pub fn opcodes(&self) -> impl Iterator<Item = (u8, Usage)> + '_ {
+        (0..=u8::MAX).map(|op| (op, self.opcode(op))).filter(|(_, usage)| usage.count > 0)
+    }
+
+    /// Returns an iterator over all precompiles that were called at least once.
+    pub fn precompiles(&self) -> impl Iterator<Item = (Address, Usage)> + '_ {
+        self.precompiles.iter().map(|(address, usage)| (*address, *usage))
+    }
+
+    /// Merges the other stats into these.
+    pub fn merge(&mut self, other: &Self) {
+        for (usage, other) in self.opcodes.iter_mut().zip(other.opcodes.iter()) {
+            usage.add(*other);
+        }
+        for (address, other) in &other.precompiles {
+            self.precompile_mut(*address).add(*other);
+        }
+    }
+}
+
+impl Default for ExecutionStats {
+    fn default() -> Self {
+        Self { opcodes: Box::new([Usage::default(); 256]), precompiles: Default::default() }
+    }
+}
+
+/// Returns the name of the opcode, or its hex encoded byte if it is unknown.
+pub fn opcode_name(op: u8) -> String {
+    OpCode::new(op).map(|op| op.as_str().to_string()).unwrap_or_else(|| format!("0x{op:02x}"))
+}
+
+/// An [`Inspector`] that collects the [`ExecutionStats`] of executed transactions.
+///
+/// The gas of call and create opcodes does not include the gas spent by the called frame, so
+/// that the gas of all opcodes and precompiles adds up to the execution gas of the transactions.
+#[derive(Debug, Clone, Default)]
+pub struct ExecutionStatsInspector {
+    /// The collected stats.
+    stats: ExecutionStats,
+    /// The opcode of the current step and the gas remaining before it was executed.
+    step: Option<(u8, u64)>,
+    /// The call or create opcode executed by the last step, if any.
+    last_call: Option<u8>,
+    /// The opcodes that created the currently open frames, `None` for the transaction's frame.
+    frames: Vec<Option<u8>>,
+}
+
+impl ExecutionStatsInspector {
+    /// Returns the collected stats.
+    pub const fn stats(&self) -> &ExecutionStats {
+        &self.stats
+    }
+
+    /// Consumes the inspector and returns the collected stats.
+    pub fn into_stats(self) -> ExecutionStats {
+        self.stats
+    }
+
+    /// Called when a new frame is entered.
+    fn enter_frame(&mut self) {
+        self.frames.push(self.last_call.take());
+    }
+
+    /// Called when a frame with the given gas limit is exited.
+    ///
+    /// The gas forwarded to the frame was charged to the opcode that created it, so it's removed
+    /// again.
+    fn exit_frame(&mut self, gas_limit: u64) {
+        if let Some(Some(op)) = self.frames.pop() {
+            let usage = self.stats.opcode_mut(op);
+            usage.gas_used = usage.gas_used.saturating_sub(gas_limit);
+        }
+    }
+}
+
+impl<CTX> Inspector<CTX, EthInterpreter> for ExecutionStatsInspector
+where
+    CTX: ContextTr,
+{
+    fn step(&mut self, interp: &mut Interpreter<EthInterpreter>, _context: &mut CTX) {
+        self.step = Some((interp.bytecode.opcode(), interp.gas.remaining()));
+    }
+
+    fn step_end(&mut self, interp: &mut Interpreter<EthInterpreter>, _context: &mut CTX) {
+        let Some((op, gas_before)) = self.step.take() else { return };
+
+        let usage = self.stats.opcode_mut(op);
+        usage.count += 1;
+        usage.gas_used += gas_before.saturating_sub(interp.gas.remaining());
+
+        self.last_call = matches!(
+            op,
+            opcode::CALL |
+                opcode::CALLCODE |
+                opcode::DELEGATECALL |
+                opcode::STATICCALL |
+                opcode::CREATE |
+                opcode::CREATE2
+        )
+        .then_some(op);
+    }
+
+    fn call(&mut self, _context: &mut CTX, _inputs: &mut CallInputs) -> Option<CallOutcome> {
+        self.enter_frame();
+        None
+    }
+
+    fn call_end(&mut self, context: &mut CTX, inputs: &CallInputs, outcome: &mut CallOutcome) {
+        if context.journal().precompile_addresses().contains(&inputs.bytecode_address) {
+            let usage = self.stats.precompile_mut(inputs.bytecode_address);
+            usage.count += 1;
+            usage.gas_used += outcome.result.gas.spent();
+        }
+        self.exit_frame(outcome.result.gas.limit());
+    }
+
+    fn create(&mut self, _context: &mut CTX, _inputs: &mut CreateInputs) -> Option<CreateOutcome> {
+        self.enter_frame();
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut CTX,
+        _inputs: &CreateInputs,
+        outcome: &mut CreateOutcome,
+    ) {
+        self.exit_frame(outcome.result.gas.limit());
+    }
+}
+
+/// The [`ExecutionStats`] of the most recently executed blocks, keyed by block hash.
+///
+/// The engine inserts the stats it collected while executing a block, so that they can be served
+/// without executing the block again.
+pub struct ExecutionStatsStore {
+    stats: parking_lot::Mutex<LruMap<BlockHash, Arc<ExecutionStats>, ByLength>>,
+}
+
+impl ExecutionStatsStore {
+    /// Creates a new store that keeps the stats of at most `capacity` blocks.
+    pub fn new(capacity: u32) -> Self {
+        Self { stats: parking_lot::Mutex::new(LruMap::new(ByLength::new(capacity))) }
+    }
+
+    /// Returns the store shared by the engine and the RPC.
+    pub fn global() -> &'static Self {
+        static STORE: LazyLock<ExecutionStatsStore> =
+            LazyLock::new(|| ExecutionStatsStore::new(EXECUTION_STATS_STORE_CAPACITY));
+        &STORE
+    }
+
+    /// Stores the stats of the executed block, evicting the least recently used ones if full.
+    pub fn insert(&self, block_hash: BlockHash, stats: ExecutionStats) {
+        self.stats.lock().insert(block_hash, Arc::new(stats));
+    }
+
+    /// Returns the stats of the block, if it was executed recently.
+    pub fn get(&self, block_hash: &BlockHash) -> Option<Arc<ExecutionStats>> {
+        self.stats.lock().get(block_hash).cloned()
+    }
+}
+
+impl core::fmt::Debug for ExecutionStatsStore {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ExecutionStatsStore").field("len", &self.stats.lock().len()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_execution_stats() {
+        let precompile = Address::with_last_byte(1);
+
+        let mut a = ExecutionStats::default();
+        *a.opcode_mut(opcode::SLOAD) = Usage { count: 1, gas_used: 2100 };
+        *a.precompile_mut(precompile) = Usage { count: 1, gas_used: 3000 };
+
+        let mut b = ExecutionStats::default();
+        *b.opcode_mut(opcode::SLOAD) = Usage { count: 2, gas_used: 200 };
+        *b.opcode_mut(opcode::ADD) = Usage { count: 1, gas_used: 3 };
+
+        a.merge(&b);
+        assert_eq!(a.opcode(opcode::SLOAD), Usage { count: 3, gas_used: 2300 });
+        assert_eq!(a.opcode(opcode::ADD), Usage { count: 1, gas_used: 3 });
+        assert_eq!(a.precompile(&precompile), Usage { count: 1, gas_used: 3000 });
+        assert_eq!(
+            a.opcodes().map(|(op, _)| opcode_name(op)).collect::<Vec<_>>(),
+            ["ADD", "SLOAD"]
+        );
+    }
+
+    #[test]
+    fn store_evicts_least_recently_used() {
+        let store = ExecutionStatsStore::new(2);
+        let mut stats = ExecutionStats::default();
+        for n in 1..=3 {
+            stats.opcode_mut(opcode::ADD).count = n;
+            store.insert(BlockHash::with_last_byte(n as u8), stats.clone());
+        }
+
+        assert!(store.get(&BlockHash::with_last_byte(1)).is_none());
+        assert_eq!(store.get(&BlockHash::with_last_byte(3)).unwrap().opcode(opcode::ADD).count, 3);
+    }
+}
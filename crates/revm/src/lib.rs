@@ -33,3 +33,7 @@ pub use revm::{self, database::State, *};
 /// Helper types for execution witness generation.
 #[cfg(feature = "witness")]
 pub mod witness;
+
+/// Opcode and precompile usage statistics of executed blocks.
+#[cfg(feature = "execution-stats")]
+pub mod execution_stats;
@@ -1,6 +1,9 @@
 use alloy_rpc_types_admin::{NodeInfo, PeerInfo};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
-use reth_network_peers::{AnyNode, NodeRecord};
+use reth_network_peers::{AnyNode, NodeRecord, PeerId};
+use reth_rpc_server_types::RpcConnectionInfo;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, net::SocketAddr};
 
 /// Admin namespace rpc interface that gives access to several non-standard RPC methods.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "admin"))]
@@ -34,6 +37,16 @@ pub trait AdminApi {
     #[method(name = "peers")]
     async fn peers(&self) -> RpcResult<Vec<PeerInfo>>;
 
+    /// Returns the number of connected peers grouped by client name, eth protocol version and
+    /// fork id.
+    #[method(name = "peerCensus")]
+    async fn peer_census(&self) -> RpcResult<PeerCensus>;
+
+    /// Returns the traffic exchanged with every connected peer since its session was established,
+    /// by message type, sorted by the total number of bytes received and sent in descending order.
+    #[method(name = "peerStats")]
+    async fn peer_stats(&self) -> RpcResult<Vec<PeerStats>>;
+
     /// Creates an RPC subscription which serves events received from the network.
     #[subscription(
         name = "peerEvents",
@@ -50,4 +63,69 @@ pub trait AdminApi {
     /// Returns the number of transactions that were removed from the pool.
     #[method(name = "clearTxpool")]
     async fn clear_txpool(&self) -> RpcResult<u64>;
+
+    /// Returns the open connections of the RPC servers, with their transport, remote address,
+    /// number of open subscriptions and inflight requests, and age.
+    #[method(name = "rpcConnections")]
+    fn rpc_connections(&self) -> RpcResult<Vec<RpcConnectionInfo>>;
+
+    /// Terminates the RPC connection with the given id by closing its socket.
+    ///
+    /// Returns false if there's no open connection with the given id.
+    #[method(name = "terminateRpcConnection")]
+    fn terminate_rpc_connection(&self, id: u64) -> RpcResult<bool>;
+}
+
+/// Connected peers aggregated by client, eth version and fork id, returned by
+/// `admin_peerCensus`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeerCensus {
+    /// The total number of connected peers.
+    pub total: usize,
+    /// The number of peers by client name, e.g. `Geth`, or `other` for unknown clients.
+    pub clients: BTreeMap<String, usize>,
+    /// The number of peers by negotiated eth protocol version.
+    pub eth_versions: BTreeMap<String, usize>,
+    /// The number of peers by fork id, formatted as `<hash>/<next>`.
+    pub fork_ids: BTreeMap<String, usize>,
+}
+
+/// The traffic exchanged with a connected peer, returned by `admin_peerStats`.
+///
+/// The size of a message is the size of its message id and its RLP encoded payload, before
+/// `snappy` compression.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeerStats {
+    /// The id of the peer.
+    pub id: PeerId,
+    /// The enode of the peer.
+    pub enode: String,
+    /// The client version of the peer.
+    pub name: String,
+    /// The address of the peer.
+    pub remote_address: SocketAddr,
+    /// Whether the session was initiated by the peer.
+    pub inbound: bool,
+    /// The number of seconds since the session was established.
+    pub connected_secs: u64,
+    /// The total number of bytes received from the peer.
+    pub ingress_bytes: u64,
+    /// The total number of bytes sent to the peer.
+    pub egress_bytes: u64,
+    /// The messages received from the peer, by message type, e.g. `BlockBodies`.
+    pub ingress: BTreeMap<String, MessageStats>,
+    /// The messages sent to the peer, by message type.
+    pub egress: BTreeMap<String, MessageStats>,
+}
+
+/// The number of messages of a message type and their total size, returned by `admin_peerStats`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MessageStats {
+    /// The number of messages.
+    pub messages: u64,
+    /// The total size of the messages in bytes.
+    pub bytes: u64,
 }
@@ -31,7 +31,20 @@ mod txpool;
 mod validation;
 mod web3;
 
+pub use admin::{MessageStats, PeerCensus, PeerStats};
+pub use reth::{
+    AccountDestructionEntry, AccountInfoChange, AccountProofsUpdate, AccountState,
+    AccountStateChanges, BlockExecutionStats, BlockFieldSelection, BlockFields, BlockStateChanges,
+    BlockWithdrawals, ChainStatsReport, ContractCreationEntry, EpochTimingReport,
+    InclusionEstimate, InclusionEstimateRequest, InclusionProbability, OpcodeStats,
+    PrecompileStats, ReorgEntry, StageSyncStatus, StatsPeriod, StorageSlotChange, SyncStatusMode,
+    SyncStatusReport, WatchedAccount,
+};
 pub use testing::{TestingBuildBlockRequestV1, TESTING_BUILD_BLOCK_V1};
+pub use txpool::{
+    RethSubscriptionKind, TxpoolContentPage, TxpoolContentPageParams, TxpoolContentWithLocals,
+    TxpoolDelta,
+};
 
 /// re-export of all server traits
 pub use servers::*;
@@ -46,11 +59,13 @@ pub mod servers {
         miner::MinerApiServer,
         net::NetApiServer,
         otterscan::OtterscanServer,
-        reth::RethApiServer,
+        reth::{
+            RethApiServer, RethExecutionStatsApiServer, RethInclusionApiServer, RethMinerApiServer,
+        },
         rpc::RpcApiServer,
         testing::TestingApiServer,
         trace::TraceApiServer,
-        txpool::TxPoolApiServer,
+        txpool::{RethTxPoolApiServer, TxPoolApiServer},
         validation::BlockSubmissionValidationApiServer,
         web3::Web3ApiServer,
     };
@@ -77,11 +92,13 @@ pub mod clients {
         miner::MinerApiClient,
         net::NetApiClient,
         otterscan::OtterscanClient,
-        reth::RethApiClient,
+        reth::{
+            RethApiClient, RethExecutionStatsApiClient, RethInclusionApiClient, RethMinerApiClient,
+        },
         rpc::RpcApiServer,
         testing::TestingApiClient,
         trace::TraceApiClient,
-        txpool::TxPoolApiClient,
+        txpool::{RethTxPoolApiClient, TxPoolApiClient},
         validation::BlockSubmissionValidationApiClient,
         web3::Web3ApiClient,
     };
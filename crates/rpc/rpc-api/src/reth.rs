@@ -1,6 +1,9 @@
-use alloy_eips::BlockId;
-use alloy_primitives::{Address, U256};
+use alloy_eips::{eip4895::Withdrawal, BlockId};
+use alloy_primitives::{Address, BlockNumber, B256, U256, U64};
+use alloy_rpc_types_eth::EIP1186AccountProofResponse;
+use alloy_serde::JsonStorageKey;
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 // Required for the subscription attribute below
@@ -17,6 +20,97 @@ pub trait RethApi {
         block_id: BlockId,
     ) -> RpcResult<HashMap<Address, U256>>;
 
+    /// Returns the selected fields of all blocks in the given inclusive block range.
+    ///
+    /// This is a bulk variant of `eth_getBlockByNumber` for polling clients that only need a few
+    /// fields per block. Only the fields enabled in the [`BlockFieldSelection`] are read and
+    /// returned, the block number is always included.
+    #[method(name = "getBlocksByRange")]
+    async fn reth_get_blocks_by_range(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+        fields: BlockFieldSelection,
+    ) -> RpcResult<Vec<BlockFields>>;
+
+    /// Returns the account and storage changes of all blocks in the given inclusive block range.
+    ///
+    /// The changes are read from the changesets written during execution, so blocks don't have
+    /// to be re-executed. Only blocks whose history hasn't been pruned can be queried.
+    #[method(name = "getStateChanges")]
+    async fn reth_get_state_changes(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+    ) -> RpcResult<Vec<BlockStateChanges>>;
+
+    /// Returns the withdrawals of all blocks in the given inclusive block range.
+    ///
+    /// Only blocks that contain withdrawals are returned. The withdrawals are read without
+    /// reading the full blocks.
+    #[method(name = "getWithdrawals")]
+    async fn reth_get_withdrawals(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+    ) -> RpcResult<Vec<BlockWithdrawals>>;
+
+    /// Returns the most recent reorgs performed by this node, newest first.
+    ///
+    /// At most `limit` entries are returned, defaults to 100.
+    #[method(name = "reorgHistory")]
+    async fn reth_reorg_history(&self, limit: Option<usize>) -> RpcResult<Vec<ReorgEntry>>;
+
+    /// Returns the most recent creation of the contract at the given address.
+    ///
+    /// Only available if the optional contract creations index is enabled, returns `null` for
+    /// contracts that weren't created in the blocks executed since it was enabled.
+    #[method(name = "getContractCreation")]
+    async fn reth_get_contract_creation(
+        &self,
+        address: Address,
+    ) -> RpcResult<Option<ContractCreationEntry>>;
+
+    /// Returns the removals of the account at the given address from the state, oldest first.
+    ///
+    /// Only available if the optional account destructions index is enabled, returns an empty list
+    /// for accounts that weren't removed in the blocks executed since it was enabled.
+    #[method(name = "getAccountDestructions")]
+    async fn reth_get_account_destructions(
+        &self,
+        address: Address,
+    ) -> RpcResult<Vec<AccountDestructionEntry>>;
+
+    /// Returns per-epoch summaries of when canonical blocks were received and executed relative
+    /// to the attestation deadline, a third into the slot of the chain, newest first.
+    ///
+    /// At most `epochs` epochs are returned, defaults to 4. Only blocks received by this node
+    /// since it was started are included.
+    #[method(name = "timingReport")]
+    async fn reth_timing_report(&self, epochs: Option<usize>) -> RpcResult<Vec<EpochTimingReport>>;
+
+    /// Returns aggregated statistics of the canonical chain per day or per epoch, newest first.
+    ///
+    /// The statistics are maintained incrementally as blocks become canonical, so they don't
+    /// require scanning headers or receipts. They're persisted across restarts, but only blocks
+    /// that became canonical through the engine are included, blocks synced by the pipeline are
+    /// not. At most `limit` periods are returned, defaults to 30.
+    #[method(name = "chainStats")]
+    async fn reth_chain_stats(
+        &self,
+        period: StatsPeriod,
+        limit: Option<usize>,
+    ) -> RpcResult<Vec<ChainStatsReport>>;
+
+    /// Returns the sync progress of the node: whether the chain is followed live or backfilled by
+    /// the pipeline, and the checkpoint, entity progress and estimated remaining time of each
+    /// pipeline stage.
+    ///
+    /// Progress reported by the pipeline since the node was started takes precedence over the
+    /// stored stage checkpoints.
+    #[method(name = "syncStatus")]
+    async fn reth_sync_status(&self) -> RpcResult<SyncStatusReport>;
+
     /// Subscribe to json `ChainNotifications`
     #[subscription(
         name = "subscribeChainNotifications",
@@ -34,4 +128,579 @@ pub trait RethApi {
         item = alloy_eips::BlockNumHash
     )]
     async fn reth_subscribe_persisted_block(&self) -> jsonrpsee::core::SubscriptionResult;
+
+    /// Streams the RLP encoded blocks of the given inclusive block range.
+    ///
+    /// Emits one notification per block, in ascending order, and closes the subscription once
+    /// the last block of the range was sent. Intended for bulk exports, where serving
+    /// `debug_getRawBlock` for every block would be too slow. If a block of the range expired or
+    /// can't be read, the subscription is closed with an error notification instead.
+    #[subscription(
+        name = "subscribeRawBlockRange",
+        unsubscribe = "unsubscribeRawBlockRange",
+        item = alloy_primitives::Bytes
+    )]
+    async fn reth_subscribe_raw_block_range(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+    ) -> jsonrpsee::core::SubscriptionResult;
+
+    /// Streams the EIP-2718 encoded receipts of the given inclusive block range.
+    ///
+    /// Emits one notification per block containing all receipts of the block, in ascending
+    /// order, and closes the subscription once the last block of the range was sent. If the
+    /// receipts of a block were pruned or can't be read, the subscription is closed with an error
+    /// notification instead.
+    #[subscription(
+        name = "subscribeRawReceiptsRange",
+        unsubscribe = "unsubscribeRawReceiptsRange",
+        item = Vec<alloy_primitives::Bytes>
+    )]
+    async fn reth_subscribe_raw_receipts_range(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+    ) -> jsonrpsee::core::SubscriptionResult;
+
+    /// Subscribe to the Merkle proofs of the given accounts and storage slots.
+    ///
+    /// Whenever the canonical chain changes, including reorgs, emits the proofs of all watched
+    /// accounts whose account info or watched storage slots changed, at the new canonical tip, so
+    /// clients don't have to poll `eth_getProof` for every block.
+    #[subscription(
+        name = "subscribeAccountProofs",
+        unsubscribe = "unsubscribeAccountProofs",
+        item = AccountProofsUpdate
+    )]
+    async fn reth_subscribe_account_proofs(
+        &self,
+        accounts: Vec<WatchedAccount>,
+    ) -> jsonrpsee::core::SubscriptionResult;
+}
+
+/// Reth API namespace for execution statistics of blocks.
+///
+/// Statistics are collected by re-executing the block, so this is served separately from
+/// [`RethApi`] and only available if the `execution-stats` feature of the implementation is
+/// enabled.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "reth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "reth"))]
+pub trait RethExecutionStatsApi {
+    /// Returns the aggregated opcode and precompile usage of all transactions in the block.
+    ///
+    /// Returns `None` if the block is not found.
+    #[method(name = "blockExecutionStats")]
+    async fn reth_block_execution_stats(
+        &self,
+        block_id: BlockId,
+    ) -> RpcResult<Option<BlockExecutionStats>>;
+}
+
+/// Reth API namespace for transaction inclusion estimates.
+///
+/// The estimates are based on the transaction pool and the fee history of the node, so this is
+/// served separately from [`RethApi`].
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "reth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "reth"))]
+pub trait RethInclusionApi {
+    /// Estimates how many blocks it takes until a transaction with the given fees and blob count
+    /// is included.
+    ///
+    /// The estimate combines the position the transaction would take among the pending
+    /// transactions of the pool with the base fee and blob base fee trajectories of recent
+    /// blocks.
+    #[method(name = "estimateInclusion")]
+    async fn reth_estimate_inclusion(
+        &self,
+        request: InclusionEstimateRequest,
+    ) -> RpcResult<InclusionEstimate>;
+}
+
+/// Reth API namespace for mining blocks on request in dev mode.
+///
+/// Mining is driven by the local miner of the dev mode, so this is served separately from
+/// [`RethApi`] and only available if the node runs with `--dev`.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "reth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "reth"))]
+pub trait RethMinerApi {
+    /// Mines the given number of blocks, defaults to 1 and at most 1024, and returns their hashes.
+    ///
+    /// Blocks are mined regardless of the configured mining mode, the pending transactions of
+    /// the pool are included like in blocks mined by the mode itself.
+    #[method(name = "mine")]
+    async fn reth_mine(&self, blocks: Option<U64>) -> RpcResult<Vec<B256>>;
+}
+
+/// Selects the fields returned by `reth_getBlocksByRange`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct BlockFieldSelection {
+    /// Include the block hash.
+    pub hash: bool,
+    /// Include the parent block hash.
+    pub parent_hash: bool,
+    /// Include the block timestamp.
+    pub timestamp: bool,
+    /// Include the gas used by the block.
+    pub gas_used: bool,
+    /// Include the gas limit of the block.
+    pub gas_limit: bool,
+    /// Include the base fee of the block.
+    pub base_fee_per_gas: bool,
+    /// Include the beneficiary of the block.
+    pub miner: bool,
+    /// Include the hashes of all transactions in the block.
+    pub transactions: bool,
+}
+
+impl BlockFieldSelection {
+    /// Returns `true` if any header field is selected.
+    pub const fn requires_header(&self) -> bool {
+        self.hash ||
+            self.parent_hash ||
+            self.timestamp ||
+            self.gas_used ||
+            self.gas_limit ||
+            self.base_fee_per_gas ||
+            self.miner
+    }
+}
+
+/// The selected fields of a block returned by `reth_getBlocksByRange`.
+///
+/// Fields that were not selected are omitted from the response.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockFields {
+    /// The block number.
+    #[serde(with = "alloy_serde::quantity")]
+    pub number: BlockNumber,
+    /// The block hash.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash: Option<B256>,
+    /// The parent block hash.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_hash: Option<B256>,
+    /// The block timestamp.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub timestamp: Option<u64>,
+    /// The gas used by the block.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub gas_used: Option<u64>,
+    /// The gas limit of the block.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub gas_limit: Option<u64>,
+    /// The base fee of the block, if the block has one and it was selected.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub base_fee_per_gas: Option<u64>,
+    /// The beneficiary of the block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub miner: Option<Address>,
+    /// The hashes of all transactions in the block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transactions: Option<Vec<B256>>,
+}
+
+/// The withdrawals of a block returned by `reth_getWithdrawals`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockWithdrawals {
+    /// The block number.
+    #[serde(with = "alloy_serde::quantity")]
+    pub block_number: BlockNumber,
+    /// The withdrawals of the block.
+    pub withdrawals: Vec<Withdrawal>,
+}
+
+/// An account watched by `reth_subscribeAccountProofs`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchedAccount {
+    /// The address of the account.
+    pub address: Address,
+    /// The storage keys to include proofs for.
+    #[serde(default)]
+    pub storage_keys: Vec<JsonStorageKey>,
+}
+
+/// The proofs of the watched accounts that changed, emitted by `reth_subscribeAccountProofs`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountProofsUpdate {
+    /// The number of the canonical tip the proofs were computed at.
+    #[serde(with = "alloy_serde::quantity")]
+    pub block_number: BlockNumber,
+    /// The hash of the canonical tip the proofs were computed at.
+    pub block_hash: B256,
+    /// The proofs of the changed accounts.
+    pub proofs: Vec<EIP1186AccountProofResponse>,
+}
+
+/// A reorg of the canonical chain returned by `reth_reorgHistory`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReorgEntry {
+    /// Unix timestamp in seconds at which the node performed the reorg.
+    #[serde(with = "alloy_serde::quantity")]
+    pub timestamp: u64,
+    /// The canonical head before the reorg.
+    pub old_head: B256,
+    /// The number of the canonical head before the reorg.
+    #[serde(with = "alloy_serde::quantity")]
+    pub old_head_number: BlockNumber,
+    /// The timestamp of the canonical head before the reorg.
+    #[serde(with = "alloy_serde::quantity")]
+    pub old_head_timestamp: u64,
+    /// The canonical head after the reorg.
+    pub new_head: B256,
+    /// The number of the canonical head after the reorg.
+    #[serde(with = "alloy_serde::quantity")]
+    pub new_head_number: BlockNumber,
+    /// The timestamp of the canonical head after the reorg.
+    #[serde(with = "alloy_serde::quantity")]
+    pub new_head_timestamp: u64,
+    /// The number of blocks removed from the canonical chain.
+    #[serde(with = "alloy_serde::quantity")]
+    pub depth: u64,
+    /// The number of transactions in the removed blocks.
+    #[serde(with = "alloy_serde::quantity")]
+    pub reverted_transactions: u64,
+    /// The number of transactions in the blocks that became canonical.
+    #[serde(with = "alloy_serde::quantity")]
+    pub applied_transactions: u64,
+}
+
+/// The creation of a contract returned by `reth_getContractCreation`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContractCreationEntry {
+    /// The number of the block containing the creation transaction.
+    #[serde(with = "alloy_serde::quantity")]
+    pub block_number: BlockNumber,
+    /// The hash of the creation transaction.
+    pub transaction_hash: B256,
+    /// The account that executed the `CREATE` or `CREATE2`, either the sender of the transaction
+    /// or a factory contract.
+    pub creator: Address,
+    /// The hash of the deployed code.
+    pub code_hash: B256,
+    /// The salt of the `CREATE2`, `null` for `CREATE`.
+    pub salt: Option<B256>,
+}
+
+/// The removal of an account returned by `reth_getAccountDestructions`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountDestructionEntry {
+    /// The number of the block containing the removing transaction.
+    #[serde(with = "alloy_serde::quantity")]
+    pub block_number: BlockNumber,
+    /// The hash of the removing transaction.
+    pub transaction_hash: B256,
+    /// Whether the account executed a `SELFDESTRUCT`, otherwise it was removed because it was
+    /// touched while empty (EIP-161).
+    pub self_destruct: bool,
+    /// The account the balance was sent to by the `SELFDESTRUCT`, `null` for state clearing.
+    pub beneficiary: Option<Address>,
+}
+
+/// The block timings of an epoch returned by `reth_timingReport`.
+///
+/// All delays are measured in milliseconds from the start of the block's slot.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EpochTimingReport {
+    /// The epoch number.
+    #[serde(with = "alloy_serde::quantity")]
+    pub epoch: u64,
+    /// The number of blocks with recorded timings.
+    #[serde(with = "alloy_serde::quantity")]
+    pub blocks: u64,
+    /// The average delay until the payload was received.
+    #[serde(with = "alloy_serde::quantity")]
+    pub avg_received_delay_ms: u64,
+    /// The maximum delay until the payload was received.
+    #[serde(with = "alloy_serde::quantity")]
+    pub max_received_delay_ms: u64,
+    /// The average delay until the payload finished executing.
+    #[serde(with = "alloy_serde::quantity")]
+    pub avg_executed_delay_ms: u64,
+    /// The maximum delay until the payload finished executing.
+    #[serde(with = "alloy_serde::quantity")]
+    pub max_executed_delay_ms: u64,
+    /// The smallest time left until the attestation deadline, negative if it was missed.
+    pub min_deadline_margin_ms: i64,
+    /// The number of blocks that finished executing after the attestation deadline.
+    #[serde(with = "alloy_serde::quantity")]
+    pub missed_deadlines: u64,
+}
+
+/// The period `reth_chainStats` aggregates over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum StatsPeriod {
+    /// A UTC day.
+    Day,
+    /// A beacon chain epoch.
+    Epoch,
+}
+
+/// The aggregated statistics of a day or an epoch returned by `reth_chainStats`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChainStatsReport {
+    /// The number of the day since the unix epoch, or the epoch number.
+    #[serde(with = "alloy_serde::quantity")]
+    pub number: u64,
+    /// The timestamp at which the period starts.
+    #[serde(with = "alloy_serde::quantity")]
+    pub start_timestamp: u64,
+    /// The number of canonical blocks.
+    #[serde(with = "alloy_serde::quantity")]
+    pub blocks: u64,
+    /// The number of transactions.
+    #[serde(with = "alloy_serde::quantity")]
+    pub transactions: u64,
+    /// The gas used by all transactions.
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas_used: u64,
+    /// The sum of the block gas limits.
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas_limit: u64,
+    /// The average base fee per gas.
+    #[serde(with = "alloy_serde::quantity")]
+    pub avg_base_fee_per_gas: u64,
+    /// The blob gas used.
+    #[serde(with = "alloy_serde::quantity")]
+    pub blob_gas_used: u64,
+    /// Created accounts minus destroyed accounts.
+    pub new_accounts: i64,
+    /// Storage slots set to a non-zero value minus storage slots cleared.
+    pub new_storage_slots: i64,
+}
+
+/// How the node advances its chain, returned by `reth_syncStatus`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SyncStatusMode {
+    /// The engine executes the payloads received from the consensus layer.
+    Live,
+    /// The pipeline backfills a range of blocks stage by stage.
+    Backfill,
+}
+
+/// The sync progress of the node returned by `reth_syncStatus`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncStatusReport {
+    /// How the node currently advances its chain.
+    pub mode: SyncStatusMode,
+    /// The best block of the node.
+    #[serde(with = "alloy_serde::quantity")]
+    pub current_block: BlockNumber,
+    /// The block the pipeline is syncing to, if it ran since the node was started.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub target_block: Option<BlockNumber>,
+    /// The stage that reported its progress last.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub current_stage: Option<String>,
+    /// The progress of all pipeline stages.
+    pub stages: Vec<StageSyncStatus>,
+}
+
+/// The progress of a single pipeline stage returned by `reth_syncStatus`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StageSyncStatus {
+    /// The name of the stage.
+    pub name: String,
+    /// The block the stage has processed up to.
+    #[serde(with = "alloy_serde::quantity")]
+    pub checkpoint: BlockNumber,
+    /// The block the stage is running or unwinding to, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub target: Option<BlockNumber>,
+    /// The number of entities the stage has processed, if the stage reports it.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub entities_processed: Option<u64>,
+    /// The total number of entities the stage has to process, if the stage reports it.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub entities_total: Option<u64>,
+    /// The estimated number of seconds until the stage reaches its target.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub eta_seconds: Option<u64>,
+}
+
+/// The transaction parameters of a `reth_estimateInclusion` request.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InclusionEstimateRequest {
+    /// The max fee per gas of the transaction, or the gas price of a legacy transaction.
+    #[serde(with = "alloy_serde::quantity")]
+    pub max_fee_per_gas: u128,
+    /// The max priority fee per gas of the transaction, defaults to the max fee per gas.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub max_priority_fee_per_gas: Option<u128>,
+    /// The gas limit of the transaction, defaults to 21000.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub gas: Option<u64>,
+    /// The number of blobs of the transaction.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub blob_count: Option<u64>,
+    /// The max fee per blob gas of the transaction, required if it has blobs.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub max_fee_per_blob_gas: Option<u128>,
+}
+
+/// The inclusion estimate of a transaction returned by `reth_estimateInclusion`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InclusionEstimate {
+    /// The number of the latest block the estimate is based on.
+    #[serde(with = "alloy_serde::quantity")]
+    pub block_number: BlockNumber,
+    /// The expected number of blocks until inclusion, the smallest number of blocks the
+    /// transaction is included within with a probability of at least 50%.
+    ///
+    /// `None` if the transaction is unlikely to be included within the estimated horizon.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub expected_blocks: Option<u64>,
+    /// The probabilities of inclusion within increasing numbers of blocks.
+    pub probabilities: Vec<InclusionProbability>,
+    /// The base fee of the next block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub base_fee_per_gas: u128,
+    /// The blob base fee of the next block, if the chain supports blobs.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub blob_base_fee: Option<u128>,
+    /// The gas of the pending pool transactions that pay a higher tip than the transaction.
+    #[serde(with = "alloy_serde::quantity")]
+    pub pending_gas_ahead: u64,
+    /// The blobs of the pending pool transactions that pay a higher tip than the transaction
+    /// and can afford the blob base fee.
+    #[serde(with = "alloy_serde::quantity")]
+    pub pending_blobs_ahead: u64,
+}
+
+/// The probability of inclusion within a number of blocks.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InclusionProbability {
+    /// The number of blocks, starting with the next block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub blocks: u64,
+    /// The probability of inclusion within the number of blocks, between 0 and 1.
+    pub probability: f64,
+}
+
+/// The state changes of a block returned by `reth_getStateChanges`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockStateChanges {
+    /// The block number.
+    #[serde(with = "alloy_serde::quantity")]
+    pub block_number: BlockNumber,
+    /// The changed accounts, ordered by address.
+    pub accounts: Vec<AccountStateChanges>,
+}
+
+/// The changes of a single account in a block.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountStateChanges {
+    /// The address of the account.
+    pub address: Address,
+    /// The change of the account info, if it was changed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub info: Option<AccountInfoChange>,
+    /// The changed storage slots, ordered by key.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub storage: Vec<StorageSlotChange>,
+}
+
+/// The account info before and after a block.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountInfoChange {
+    /// The account info before the block, `None` if the account didn't exist.
+    pub before: Option<AccountState>,
+    /// The account info after the block, `None` if the account doesn't exist anymore.
+    pub after: Option<AccountState>,
+}
+
+/// The info of an account.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountState {
+    /// The nonce of the account.
+    #[serde(with = "alloy_serde::quantity")]
+    pub nonce: u64,
+    /// The balance of the account.
+    pub balance: U256,
+    /// The hash of the account's bytecode.
+    pub code_hash: B256,
+}
+
+/// The value of a storage slot before and after a block.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageSlotChange {
+    /// The storage key.
+    pub key: B256,
+    /// The value before the block.
+    pub before: U256,
+    /// The value after the block.
+    pub after: U256,
+}
+
+/// The aggregated opcode and precompile usage of a block returned by
+/// `reth_blockExecutionStats`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockExecutionStats {
+    /// The block hash.
+    pub block_hash: B256,
+    /// The block number.
+    #[serde(with = "alloy_serde::quantity")]
+    pub block_number: BlockNumber,
+    /// The gas used by the block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas_used: u64,
+    /// The usage of all opcodes that were executed at least once, ordered by opcode.
+    pub opcodes: Vec<OpcodeStats>,
+    /// The usage of all precompiles that were called at least once, ordered by address.
+    pub precompiles: Vec<PrecompileStats>,
+}
+
+/// The usage of a single opcode in a block.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpcodeStats {
+    /// The name of the opcode, or its hex encoded byte if it is unknown.
+    pub opcode: String,
+    /// The number of times the opcode was executed.
+    #[serde(with = "alloy_serde::quantity")]
+    pub count: u64,
+    /// The gas spent by the opcode, excluding the gas spent by calls it made.
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas_used: u64,
+    /// The share of the gas used by the block that was spent by the opcode.
+    pub gas_share: f64,
+}
+
+/// The usage of a single precompile in a block.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrecompileStats {
+    /// The address of the precompile.
+    pub address: Address,
+    /// The number of times the precompile was called.
+    #[serde(with = "alloy_serde::quantity")]
+    pub count: u64,
+    /// The gas spent by the precompile.
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas_used: u64,
+    /// The share of the gas used by the block that was spent by the precompile.
+    pub gas_share: f64,
 }
@@ -1,7 +1,9 @@
 use alloy_json_rpc::RpcObject;
-use alloy_primitives::Address;
+use alloy_primitives::{Address, TxHash, B256};
 use alloy_rpc_types_txpool::{TxpoolContent, TxpoolContentFrom, TxpoolInspect, TxpoolStatus};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
 
 /// Txpool rpc interface.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "txpool"))]
@@ -24,14 +26,175 @@ pub trait TxPoolApi<T: RpcObject> {
     /// Retrieves the transactions contained within the txpool, returning pending as well as queued
     /// transactions of this address, grouped by nonce.
     ///
+    /// If a `limit` is provided, at most `limit` pending and `limit` queued transactions with the
+    /// lowest nonces are returned.
+    ///
     /// See [here](https://geth.ethereum.org/docs/rpc/ns-txpool#txpool_contentFrom) for more details
     #[method(name = "contentFrom")]
-    async fn txpool_content_from(&self, from: Address) -> RpcResult<TxpoolContentFrom<T>>;
+    async fn txpool_content_from(
+        &self,
+        from: Address,
+        limit: Option<usize>,
+    ) -> RpcResult<TxpoolContentFrom<T>>;
 
     /// Returns the details of all transactions currently pending for inclusion in the next
     /// block(s), as well as the ones that are being scheduled for future execution only.
     ///
+    /// The hashes of the transactions that were submitted locally are returned in addition to
+    /// geth's content, see [`TxpoolContentWithLocals`].
+    ///
     /// See [here](https://geth.ethereum.org/docs/rpc/ns-txpool#txpool_content) for more details
     #[method(name = "content")]
-    async fn txpool_content(&self) -> RpcResult<TxpoolContent<T>>;
+    async fn txpool_content(&self) -> RpcResult<TxpoolContentWithLocals<T>>;
+
+    /// Returns the hashes of all transactions in the pool that were submitted locally, e.g. via
+    /// `eth_sendRawTransaction`, rather than received from peers.
+    ///
+    /// Local transactions are exempt from some eviction rules, unless disabled with
+    /// `--txpool.nolocals`, and are persisted across restarts.
+    #[method(name = "locals")]
+    async fn txpool_locals(&self) -> RpcResult<Vec<TxHash>>;
+
+    /// Returns a page of the pool content, grouped by sender and ordered by sender address.
+    ///
+    /// Transactions of a single sender are never split across pages. The returned
+    /// [`TxpoolContentPage::next`] cursor can be passed as [`TxpoolContentPageParams::after`] to
+    /// fetch the next page.
+    #[method(name = "contentPaged")]
+    async fn txpool_content_paged(
+        &self,
+        params: TxpoolContentPageParams,
+    ) -> RpcResult<TxpoolContentPage<T>>;
+}
+
+/// Reth API namespace for subscriptions to the transaction pool.
+///
+/// Served by the `txpool` module, next to the [`TxPoolApi`].
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "reth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "reth"))]
+pub trait RethTxPoolApi {
+    /// Subscribes to notifications of the given kind.
+    ///
+    /// `txpoolDelta` emits a [`TxpoolDelta`] for every transaction that enters, moves within or
+    /// leaves the pool.
+    #[subscription(
+        name = "subscribe" => "subscription",
+        unsubscribe = "unsubscribe",
+        item = TxpoolDelta
+    )]
+    async fn reth_subscribe(
+        &self,
+        kind: RethSubscriptionKind,
+    ) -> jsonrpsee::core::SubscriptionResult;
+}
+
+/// The kind of a `reth_subscribe` subscription.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RethSubscriptionKind {
+    /// Mutations of the transaction pool, see [`TxpoolDelta`].
+    TxpoolDelta,
+}
+
+/// The pool content returned by `txpool_content`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxpoolContentWithLocals<T> {
+    /// Pending and queued transactions, grouped by sender and nonce.
+    #[serde(flatten)]
+    pub content: TxpoolContent<T>,
+    /// The hashes of the transactions that were submitted locally.
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub locals: BTreeSet<TxHash>,
+}
+
+impl<T> Default for TxpoolContentWithLocals<T> {
+    fn default() -> Self {
+        Self { content: Default::default(), locals: Default::default() }
+    }
+}
+
+/// Parameters for `txpool_contentPaged`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxpoolContentPageParams {
+    /// Only include transactions of these senders.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub senders: Option<Vec<Address>>,
+    /// Only include senders strictly greater than this address.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after: Option<Address>,
+    /// The maximum number of transactions returned in the page.
+    ///
+    /// The page always contains at least one sender, even if the sender has more transactions
+    /// than the limit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+}
+
+/// A page of the pool content returned by `txpool_contentPaged`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxpoolContentPage<T> {
+    /// Pending transactions in the page, grouped by sender and nonce.
+    pub pending: BTreeMap<Address, BTreeMap<String, T>>,
+    /// Queued transactions in the page, grouped by sender and nonce.
+    pub queued: BTreeMap<Address, BTreeMap<String, T>>,
+    /// The hashes of the transactions in the page that were submitted locally.
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub locals: BTreeSet<TxHash>,
+    /// The last sender of this page, if there are more senders after it.
+    pub next: Option<Address>,
+}
+
+impl<T> Default for TxpoolContentPage<T> {
+    fn default() -> Self {
+        Self {
+            pending: Default::default(),
+            queued: Default::default(),
+            locals: Default::default(),
+            next: None,
+        }
+    }
+}
+
+/// A mutation of the transaction pool emitted by `reth_subscribe("txpoolDelta")`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum TxpoolDelta {
+    /// Transaction has been added to the pending pool.
+    Pending {
+        /// The hash of the transaction.
+        hash: TxHash,
+    },
+    /// Transaction has been added to the queued pool.
+    Queued {
+        /// The hash of the transaction.
+        hash: TxHash,
+    },
+    /// Transaction has been included in a block and removed from the pool.
+    #[serde(rename_all = "camelCase")]
+    Mined {
+        /// The hash of the transaction.
+        hash: TxHash,
+        /// The hash of the block that contains the transaction.
+        block_hash: B256,
+    },
+    /// Transaction has been replaced by another transaction with the same sender and nonce.
+    #[serde(rename_all = "camelCase")]
+    Replaced {
+        /// The hash of the replaced transaction.
+        hash: TxHash,
+        /// The hash of the replacement transaction.
+        replaced_by: TxHash,
+    },
+    /// Transaction was dropped due to pool limits.
+    Discarded {
+        /// The hash of the transaction.
+        hash: TxHash,
+    },
+    /// Transaction became invalid and was removed from the pool.
+    Invalid {
+        /// The hash of the transaction.
+        hash: TxHash,
+    },
 }
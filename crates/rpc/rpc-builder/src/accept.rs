@@ -0,0 +1,146 @@
+//! Accept loop of the http and ws RPC servers.
+//!
+//! [`jsonrpsee`] servers don't expose the remote address of a connection to their middleware, so
+//! the http and ws servers accept connections themselves, like the auth server does for its
+//! allowlist, and insert the [`PeerAddr`] and the [`RpcConnection`] of the connection into the
+//! extensions of every http request. [`jsonrpsee`] passes the extensions of the http request on to
+//! the rpc calls it contains, and to all calls of a ws connection upgraded from it.
+//!
+//! Every connection is served with its own [`StopHandle`], which is stopped when the server is
+//! stopped or the connection is terminated, so that terminating a connection also closes a ws
+//! connection upgraded from it.
+
+use bytes::Bytes;
+use jsonrpsee::server::{serve_with_graceful_shutdown, stop_channel, ServerHandle, StopHandle};
+use reth_rpc_server_types::{RpcConnection, RpcConnections};
+use std::{
+    error::Error,
+    net::SocketAddr,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tokio::net::TcpListener;
+use tower::Service;
+use tracing::debug;
+
+type BoxError = Box<dyn Error + Send + Sync>;
+
+/// The remote address of the connection a request was received on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PeerAddr(pub SocketAddr);
+
+/// Serves the connections accepted on the listener until the returned handle is stopped.
+///
+/// Every connection is registered in the [`RpcConnections`], as a ws connection if `websocket` is
+/// set. The service of every connection is created by `make_service` and receives the
+/// [`PeerAddr`] and the [`RpcConnection`] of the connection in the extensions of its requests.
+pub(crate) fn serve<S, B>(
+    listener: TcpListener,
+    connections: RpcConnections,
+    websocket: bool,
+    make_service: impl Fn(StopHandle) -> S + Send + 'static,
+) -> ServerHandle
+where
+    S: Service<http::Request<hyper::body::Incoming>, Response = http::Response<B>>
+        + Clone
+        + Send
+        + 'static,
+    S::Future: Send,
+    S::Response: Send,
+    S::Error: Into<BoxError>,
+    B: http_body::Body<Data = Bytes> + Send + 'static,
+    B::Error: Into<BoxError>,
+{
+    let (stop_handle, handle) = stop_channel();
+    tokio::spawn(async move {
+        loop {
+            let (stream, remote_addr) = tokio::select! {
+                res = listener.accept() => match res {
+                    Ok(conn) => conn,
+                    Err(err) => {
+                        debug!(target: "rpc", %err, "Failed to accept connection");
+                        continue
+                    }
+                },
+                _ = stop_handle.clone().shutdown() => break,
+            };
+
+            // like the accept loop of jsonrpsee servers
+            let _ = stream.set_nodelay(true);
+
+            let connection = connections.register(remote_addr, websocket);
+            let (connection_stop_handle, connection_handle) = stop_channel();
+            let service = ConnectionService {
+                inner: make_service(connection_stop_handle),
+                peer: remote_addr,
+                connection: connection.clone(),
+            };
+            let server_stop_handle = stop_handle.clone();
+            tokio::spawn(async move {
+                tokio::select! {
+                    _ = serve_with_graceful_shutdown(
+                        stream,
+                        service,
+                        server_stop_handle.clone().shutdown(),
+                    ) => {}
+                    // dropping the connection closes its socket
+                    _ = connection.terminated() => {
+                        let _ = connection_handle.stop();
+                        return
+                    }
+                }
+
+                // a ws connection upgraded from the connection is served by its own task until
+                // it's closed, which drops the last stop handle of the connection
+                tokio::select! {
+                    _ = connection_handle.clone().stopped() => {}
+                    _ = server_stop_handle.shutdown() => {
+                        let _ = connection_handle.stop();
+                    }
+                    _ = connection.terminated() => {
+                        let _ = connection_handle.stop();
+                    }
+                }
+            });
+        }
+    });
+    handle
+}
+
+/// Service that inserts the [`PeerAddr`] and the [`RpcConnection`] of its connection into every
+/// request.
+#[derive(Debug, Clone)]
+struct ConnectionService<S> {
+    inner: S,
+    peer: SocketAddr,
+    connection: Arc<RpcConnection>,
+}
+
+impl<S, B> Service<http::Request<B>> for ConnectionService<S>
+where
+    S: Service<http::Request<B>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: http::Request<B>) -> Self::Future {
+        if is_upgrade_request(&req) {
+            self.connection.upgrade_to_websocket();
+        }
+        req.extensions_mut().insert(PeerAddr(self.peer));
+        req.extensions_mut().insert(self.connection.clone());
+        self.inner.call(req)
+    }
+}
+
+/// Returns `true` if the request asks to upgrade the connection to a ws connection.
+fn is_upgrade_request<B>(req: &http::Request<B>) -> bool {
+    req.headers()
+        .get(http::header::UPGRADE)
+        .is_some_and(|upgrade| upgrade.as_bytes().eq_ignore_ascii_case(b"websocket"))
+}
@@ -0,0 +1,200 @@
+use ipnet::IpNet;
+use std::{
+    io,
+    net::IpAddr,
+    path::{Path, PathBuf},
+    sync::{Arc, RwLock},
+    time::{Duration, SystemTime},
+};
+use tracing::{debug, info, warn};
+
+/// How often the allowlist file is checked for changes.
+const RELOAD_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Allowlist of client IP addresses that are allowed to connect to the auth server.
+///
+/// The allowlist is loaded from a file with one IP address or CIDR network per line, empty lines
+/// and lines starting with `#` are ignored. The file is watched for changes, see
+/// [`AuthAllowlist::spawn_reload_task`], so that clients can be added or removed without
+/// restarting the node.
+#[derive(Debug, Clone)]
+pub struct AuthAllowlist {
+    /// The file the allowlist was loaded from.
+    path: PathBuf,
+    /// The currently allowed networks.
+    networks: Arc<RwLock<Vec<IpNet>>>,
+}
+
+impl AuthAllowlist {
+    /// Loads the allowlist from the given file.
+    pub fn from_file(path: impl Into<PathBuf>) -> Result<Self, AuthAllowlistError> {
+        let path = path.into();
+        let networks = read_allowlist(&path)?;
+        Ok(Self { path, networks: Arc::new(RwLock::new(networks)) })
+    }
+
+    /// Returns the file the allowlist is loaded from.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the currently allowed networks.
+    pub fn networks(&self) -> Vec<IpNet> {
+        self.networks.read().unwrap().clone()
+    }
+
+    /// Returns `true` if the given address is allowed to connect.
+    pub fn is_allowed(&self, addr: &IpAddr) -> bool {
+        let addr = addr.to_canonical();
+        self.networks.read().unwrap().iter().any(|net| net.contains(&addr))
+    }
+
+    /// Reloads the allowlist from its file.
+    ///
+    /// If the file can't be read or is invalid, the current allowlist is kept.
+    pub fn reload(&self) -> Result<(), AuthAllowlistError> {
+        let networks = read_allowlist(&self.path)?;
+        *self.networks.write().unwrap() = networks;
+        Ok(())
+    }
+
+    /// Spawns a task that reloads the allowlist whenever its file is modified.
+    ///
+    /// The task exits once all other handles to this allowlist are dropped.
+    pub fn spawn_reload_task(&self) {
+        let path = self.path.clone();
+        let networks = Arc::downgrade(&self.networks);
+        tokio::spawn(async move {
+            let mut last_modified = modified(&path);
+            let mut interval = tokio::time::interval(RELOAD_INTERVAL);
+            loop {
+                interval.tick().await;
+                let Some(networks) = networks.upgrade() else { break };
+
+                let current_modified = modified(&path);
+                if current_modified == last_modified {
+                    continue
+                }
+                last_modified = current_modified;
+
+                match read_allowlist(&path) {
+                    Ok(new_networks) => {
+                        info!(target: "rpc::auth", ?path, networks = ?new_networks, "Reloaded auth server allowlist");
+                        *networks.write().unwrap() = new_networks;
+                    }
+                    Err(err) => {
+                        warn!(target: "rpc::auth", %err, "Failed to reload auth server allowlist, keeping the current one");
+                    }
+                }
+            }
+            debug!(target: "rpc::auth", ?path, "Auth server allowlist reload task finished");
+        });
+    }
+}
+
+/// Returns the modification time of the file, if available.
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
+
+/// Reads and parses the allowlist file.
+fn read_allowlist(path: &Path) -> Result<Vec<IpNet>, AuthAllowlistError> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|source| AuthAllowlistError::Io { path: path.to_path_buf(), source })?;
+    let networks = parse_allowlist(&contents)?;
+    if networks.is_empty() {
+        return Err(AuthAllowlistError::Empty(path.to_path_buf()))
+    }
+    Ok(networks)
+}
+
+/// Parses the allowlist, one IP address or CIDR network per line.
+fn parse_allowlist(contents: &str) -> Result<Vec<IpNet>, AuthAllowlistError> {
+    contents
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| (idx + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(line, entry)| {
+            entry
+                .parse::<IpNet>()
+                .or_else(|_| entry.parse::<IpAddr>().map(IpNet::from))
+                .map_err(|_| AuthAllowlistError::InvalidEntry { line, entry: entry.to_string() })
+        })
+        .collect()
+}
+
+/// Errors that can occur when loading an [`AuthAllowlist`].
+#[derive(Debug, thiserror::Error)]
+pub enum AuthAllowlistError {
+    /// The allowlist file couldn't be read.
+    #[error("failed to read auth server allowlist {path:?}: {source}")]
+    Io {
+        /// The allowlist file.
+        path: PathBuf,
+        /// The underlying error.
+        source: io::Error,
+    },
+    /// An entry is neither an IP address nor a CIDR network.
+    #[error("invalid auth server allowlist entry on line {line}: {entry}")]
+    InvalidEntry {
+        /// The line of the entry, starting at 1.
+        line: usize,
+        /// The invalid entry.
+        entry: String,
+    },
+    /// The allowlist doesn't contain any entries, which would reject all clients.
+    #[error("auth server allowlist {0:?} is empty")]
+    Empty(PathBuf),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_entries() {
+        let networks = parse_allowlist(
+            "
+            # consensus client
+            10.0.0.5
+            192.168.1.0/24
+
+            ::1
+            ",
+        )
+        .unwrap();
+        assert_eq!(
+            networks,
+            [
+                "10.0.0.5/32".parse::<IpNet>().unwrap(),
+                "192.168.1.0/24".parse().unwrap(),
+                "::1/128".parse().unwrap(),
+            ]
+        );
+
+        let err = parse_allowlist("10.0.0.5\nlocalhost").unwrap_err();
+        assert!(matches!(err, AuthAllowlistError::InvalidEntry { line: 2, .. }));
+    }
+
+    #[test]
+    fn reload_keeps_allowlist_on_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("allowlist");
+        std::fs::write(&path, "10.0.0.0/8").unwrap();
+
+        let allowlist = AuthAllowlist::from_file(&path).unwrap();
+        assert!(allowlist.is_allowed(&"10.1.2.3".parse().unwrap()));
+        assert!(allowlist.is_allowed(&"::ffff:10.1.2.3".parse().unwrap()));
+        assert!(!allowlist.is_allowed(&"192.168.1.1".parse().unwrap()));
+
+        std::fs::write(&path, "# nothing").unwrap();
+        assert!(matches!(allowlist.reload(), Err(AuthAllowlistError::Empty(_))));
+        assert!(allowlist.is_allowed(&"10.1.2.3".parse().unwrap()));
+
+        std::fs::write(&path, "192.168.1.1").unwrap();
+        allowlist.reload().unwrap();
+        assert!(!allowlist.is_allowed(&"10.1.2.3".parse().unwrap()));
+        assert!(allowlist.is_allowed(&"192.168.1.1".parse().unwrap()));
+    }
+}
@@ -1,12 +1,13 @@
 use crate::{
     error::{RpcError, ServerKind},
     middleware::RethRpcMiddleware,
+    AuthAllowlist,
 };
 use http::header::AUTHORIZATION;
 use jsonrpsee::{
     core::{client::SubscriptionClientT, RegisterMethodError},
     http_client::HeaderMap,
-    server::{AlreadyStoppedError, RpcModule},
+    server::{serve_with_graceful_shutdown, stop_channel, AlreadyStoppedError, RpcModule},
     ws_client::RpcServiceBuilder,
     Methods,
 };
@@ -17,7 +18,9 @@ use reth_rpc_layer::{
 };
 use reth_rpc_server_types::constants;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use tokio::net::TcpListener;
 use tower::layer::util::Identity;
+use tracing::{debug, warn};
 
 pub use jsonrpsee::server::ServerBuilder;
 use jsonrpsee::server::{ServerConfig, ServerConfigBuilder};
@@ -38,6 +41,8 @@ pub struct AuthServerConfig<RpcMiddleware = Identity> {
     pub(crate) ipc_endpoint: Option<String>,
     /// Configurable RPC middleware
     pub(crate) rpc_middleware: RpcMiddleware,
+    /// Client addresses allowed to connect, if restricted.
+    pub(crate) allowlist: Option<AuthAllowlist>,
 }
 
 // === impl AuthServerConfig ===
@@ -56,7 +61,15 @@ impl<RpcMiddleware> AuthServerConfig<RpcMiddleware> {
 
     /// Configures the rpc middleware.
     pub fn with_rpc_middleware<T>(self, rpc_middleware: T) -> AuthServerConfig<T> {
-        let Self { socket_addr, secret, server_config, ipc_server_config, ipc_endpoint, .. } = self;
+        let Self {
+            socket_addr,
+            secret,
+            server_config,
+            ipc_server_config,
+            ipc_endpoint,
+            allowlist,
+            ..
+        } = self;
         AuthServerConfig {
             socket_addr,
             secret,
@@ -64,6 +77,7 @@ impl<RpcMiddleware> AuthServerConfig<RpcMiddleware> {
             ipc_server_config,
             ipc_endpoint,
             rpc_middleware,
+            allowlist,
         }
     }
 
@@ -79,6 +93,7 @@ impl<RpcMiddleware> AuthServerConfig<RpcMiddleware> {
             ipc_server_config,
             ipc_endpoint,
             rpc_middleware,
+            allowlist,
         } = self;
 
         // Create auth middleware.
@@ -91,16 +106,64 @@ impl<RpcMiddleware> AuthServerConfig<RpcMiddleware> {
         let server = ServerBuilder::new()
             .set_config(server_config.build())
             .set_http_middleware(middleware)
-            .set_rpc_middleware(rpc_middleware)
-            .build(socket_addr)
-            .await
-            .map_err(|err| RpcError::server_error(err, ServerKind::Auth(socket_addr)))?;
+            .set_rpc_middleware(rpc_middleware);
+
+        let (handle, local_addr) = if let Some(allowlist) = allowlist {
+            // Connections are accepted manually, so that clients that aren't allowlisted are
+            // rejected before any request is handled, regardless of the JWT they present.
+            let listener = TcpListener::bind(socket_addr)
+                .await
+                .map_err(|err| RpcError::server_error(err, ServerKind::Auth(socket_addr)))?;
+            let local_addr = listener
+                .local_addr()
+                .map_err(|err| RpcError::server_error(err, ServerKind::Auth(socket_addr)))?;
+
+            allowlist.spawn_reload_task();
+
+            let (stop_handle, handle) = stop_channel();
+            let service_builder = server.to_service_builder();
+            let methods = Methods::from(module.inner.clone());
+            tokio::spawn(async move {
+                loop {
+                    let (stream, remote_addr) = tokio::select! {
+                        res = listener.accept() => match res {
+                            Ok(conn) => conn,
+                            Err(err) => {
+                                debug!(target: "rpc::auth", %err, "Failed to accept connection");
+                                continue
+                            }
+                        },
+                        _ = stop_handle.clone().shutdown() => break,
+                    };
+
+                    if !allowlist.is_allowed(&remote_addr.ip()) {
+                        warn!(target: "rpc::auth", %remote_addr, allowlist = ?allowlist.path(), "Rejected connection from address not in the auth server allowlist");
+                        continue
+                    }
+
+                    let service =
+                        service_builder.clone().build(methods.clone(), stop_handle.clone());
+                    tokio::spawn(serve_with_graceful_shutdown(
+                        stream,
+                        service,
+                        stop_handle.clone().shutdown(),
+                    ));
+                }
+            });
+
+            (handle, local_addr)
+        } else {
+            let server = server
+                .build(socket_addr)
+                .await
+                .map_err(|err| RpcError::server_error(err, ServerKind::Auth(socket_addr)))?;
 
-        let local_addr = server
-            .local_addr()
-            .map_err(|err| RpcError::server_error(err, ServerKind::Auth(socket_addr)))?;
+            let local_addr = server
+                .local_addr()
+                .map_err(|err| RpcError::server_error(err, ServerKind::Auth(socket_addr)))?;
 
-        let handle = server.start(module.inner.clone());
+            (server.start(module.inner.clone()), local_addr)
+        };
 
         let ipc_handle = if let Some(ipc_server_config) = ipc_server_config {
             let ipc_endpoint_str = ipc_endpoint
@@ -126,6 +189,7 @@ pub struct AuthServerConfigBuilder<RpcMiddleware = Identity> {
     ipc_server_config: Option<IpcServerBuilder<Identity, Identity>>,
     ipc_endpoint: Option<String>,
     rpc_middleware: RpcMiddleware,
+    allowlist: Option<AuthAllowlist>,
 }
 
 // === impl AuthServerConfigBuilder ===
@@ -140,6 +204,7 @@ impl AuthServerConfigBuilder {
             ipc_server_config: None,
             ipc_endpoint: None,
             rpc_middleware: Identity::new(),
+            allowlist: None,
         }
     }
 }
@@ -147,7 +212,15 @@ impl AuthServerConfigBuilder {
 impl<RpcMiddleware> AuthServerConfigBuilder<RpcMiddleware> {
     /// Configures the rpc middleware.
     pub fn with_rpc_middleware<T>(self, rpc_middleware: T) -> AuthServerConfigBuilder<T> {
-        let Self { socket_addr, secret, server_config, ipc_server_config, ipc_endpoint, .. } = self;
+        let Self {
+            socket_addr,
+            secret,
+            server_config,
+            ipc_server_config,
+            ipc_endpoint,
+            allowlist,
+            ..
+        } = self;
         AuthServerConfigBuilder {
             socket_addr,
             secret,
@@ -155,6 +228,7 @@ impl<RpcMiddleware> AuthServerConfigBuilder<RpcMiddleware> {
             ipc_server_config,
             ipc_endpoint,
             rpc_middleware,
+            allowlist,
         }
     }
 
@@ -176,6 +250,14 @@ impl<RpcMiddleware> AuthServerConfigBuilder<RpcMiddleware> {
         self
     }
 
+    /// Restricts the clients that can connect to the server to the given allowlist.
+    ///
+    /// Connections from other addresses are closed right after they're accepted.
+    pub fn allowlist(mut self, allowlist: AuthAllowlist) -> Self {
+        self.allowlist = Some(allowlist);
+        self
+    }
+
     /// Configures the JSON-RPC server
     ///
     /// Note: this always configures an [`EthSubscriptionIdProvider`]
@@ -233,6 +315,7 @@ impl<RpcMiddleware> AuthServerConfigBuilder<RpcMiddleware> {
             }),
             ipc_endpoint: self.ipc_endpoint,
             rpc_middleware: self.rpc_middleware,
+            allowlist: self.allowlist,
         }
     }
 }
@@ -254,6 +337,11 @@ impl AuthRpcModule {
         &mut self.inner
     }
 
+    /// Returns the names of all configured authenticated methods.
+    pub fn method_names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.inner.method_names()
+    }
+
     /// Merge the given [Methods] in the configured authenticated methods.
     ///
     /// Fails if any of the methods in other is present already.
@@ -3,14 +3,14 @@ use reth_node_core::{args::RpcServerArgs, utils::get_or_create_jwt_secret_from_p
 use reth_rpc::ValidationApiConfig;
 use reth_rpc_eth_types::{EthConfig, EthStateCacheConfig, GasPriceOracleConfig};
 use reth_rpc_layer::{JwtError, JwtSecret};
-use reth_rpc_server_types::RpcModuleSelection;
+use reth_rpc_server_types::{RethRpcModule, RpcModuleSelection};
 use std::{net::SocketAddr, path::PathBuf};
 use tower::layer::util::Identity;
 use tracing::{debug, warn};
 
 use crate::{
-    auth::AuthServerConfig, error::RpcError, IpcServerBuilder, RpcModuleConfig, RpcServerConfig,
-    TransportRpcModuleConfig,
+    auth::AuthServerConfig, error::RpcError, AuthAllowlist, GraphQlLimits, IpcServerBuilder,
+    RpcModuleConfig, RpcNamespacePools, RpcServerConfig, TransportRpcModuleConfig,
 };
 
 /// A trait that provides a configured RPC server.
@@ -39,6 +39,20 @@ pub trait RethRpcServerConfig {
     /// Returns the max response size in bytes.
     fn rpc_max_response_size_bytes(&self) -> u32;
 
+    /// Returns the max WS request message size in bytes.
+    ///
+    /// Defaults to [`Self::rpc_max_request_size_bytes`].
+    fn ws_max_request_size_bytes(&self) -> u32 {
+        self.rpc_max_request_size_bytes()
+    }
+
+    /// Returns the max WS response message size in bytes.
+    ///
+    /// Defaults to [`Self::rpc_max_response_size_bytes`].
+    fn ws_max_response_size_bytes(&self) -> u32 {
+        self.rpc_max_response_size_bytes()
+    }
+
     /// Extracts the gas price oracle config from the args.
     fn gas_price_oracle_config(&self) -> GasPriceOracleConfig;
 
@@ -51,6 +65,13 @@ pub trait RethRpcServerConfig {
     /// Returns the default server config for http/ws
     fn http_ws_server_builder(&self) -> ServerConfigBuilder;
 
+    /// Returns the server config for a ws server that doesn't share its port with http.
+    fn ws_server_builder(&self) -> ServerConfigBuilder {
+        self.http_ws_server_builder()
+            .max_request_body_size(self.ws_max_request_size_bytes())
+            .max_response_body_size(self.ws_max_response_size_bytes())
+    }
+
     /// Returns the default ipc server builder
     fn ipc_server_builder(&self) -> IpcServerBuilder<Identity, Identity>;
 
@@ -134,6 +155,20 @@ impl RethRpcServerConfig for RpcServerArgs {
         self.rpc_max_response_size.get().saturating_mul(1024 * 1024)
     }
 
+    fn ws_max_request_size_bytes(&self) -> u32 {
+        self.ws_max_request_size
+            .unwrap_or(self.rpc_max_request_size)
+            .get()
+            .saturating_mul(1024 * 1024)
+    }
+
+    fn ws_max_response_size_bytes(&self) -> u32 {
+        self.ws_max_response_size
+            .unwrap_or(self.rpc_max_response_size)
+            .get()
+            .saturating_mul(1024 * 1024)
+    }
+
     fn gas_price_oracle_config(&self) -> GasPriceOracleConfig {
         self.gas_price_oracle.gas_price_oracle_config()
     }
@@ -205,18 +240,51 @@ impl RethRpcServerConfig for RpcServerArgs {
                 .with_http_address(socket_address)
                 .with_http(self.http_ws_server_builder())
                 .with_http_cors(self.http_corsdomain.clone())
-                .with_http_disable_compression(self.http_disable_compression);
+                .with_http_disable_compression(self.http_disable_compression)
+                .with_http_graphql(self.graphql)
+                .with_http_graphql_limits(GraphQlLimits {
+                    max_body_size: self.rpc_max_request_size_bytes() as usize,
+                    ..Default::default()
+                });
         }
 
         if self.ws {
             let socket_address = SocketAddr::new(self.ws_addr, self.ws_port);
+            if self.http &&
+                socket_address == SocketAddr::new(self.http_addr, self.http_port) &&
+                (self.ws_max_request_size.is_some() || self.ws_max_response_size.is_some())
+            {
+                warn!(
+                    target: "reth::cli",
+                    "WS shares its port with HTTP, --ws.max-request-size and --ws.max-response-size are ignored."
+                );
+            }
+
             // Ensure WS CORS is applied regardless of HTTP being enabled
             config = config
                 .with_ws_address(socket_address)
-                .with_ws(self.http_ws_server_builder())
+                .with_ws(self.ws_server_builder())
                 .with_ws_cors(self.ws_allowed_origins.clone());
         }
 
+        if self.http || self.ws {
+            let namespace_pools = RpcNamespacePools::new(
+                [
+                    (RethRpcModule::Debug, self.rpc_debug_workers),
+                    (RethRpcModule::Trace, self.rpc_trace_workers),
+                ],
+                self.rpc_worker_niceness,
+            )
+            .expect("failed to build rpc namespace worker pools");
+            if !namespace_pools.is_empty() {
+                config = config.with_namespace_pools(Some(namespace_pools));
+            }
+        }
+
+        if !self.rate_limit.is_empty() {
+            config = config.with_rate_limit(Some(self.rate_limit.clone()));
+        }
+
         if self.is_ipc_enabled() {
             config =
                 config.with_ipc(self.ipc_server_builder()).with_ipc_endpoint(self.ipcpath.clone());
@@ -229,6 +297,9 @@ impl RethRpcServerConfig for RpcServerArgs {
         let address = SocketAddr::new(self.auth_addr, self.auth_port);
 
         let mut builder = AuthServerConfig::builder(jwt_secret).socket_addr(address);
+        if let Some(path) = &self.auth_allowlist {
+            builder = builder.allowlist(AuthAllowlist::from_file(path)?);
+        }
         if self.auth_ipc {
             builder = builder
                 .ipc_endpoint(self.auth_ipc_path.clone())
@@ -0,0 +1,160 @@
+//! [`jsonrpsee`] middleware tracking the requests and subscriptions of the connections of the http
+//! and ws RPC servers in the [`RpcConnections`].
+//!
+//! The connections are registered by the accept loop of the servers, which passes the
+//! [`RpcConnection`] of a connection to the middleware in the extensions of its requests.
+//!
+//! [`RpcConnections`]: reth_rpc_server_types::RpcConnections
+
+use jsonrpsee::{
+    core::middleware::{Batch, Notification},
+    server::middleware::rpc::RpcServiceT,
+    types::Request,
+    MethodResponse, RpcModule,
+};
+use reth_rpc_server_types::RpcConnection;
+use serde::Deserialize;
+use std::{
+    collections::{HashMap, HashSet},
+    future::Future,
+    sync::Arc,
+};
+use tower::Layer;
+
+/// Layer tracking the requests and subscriptions of every connection of a server.
+#[derive(Debug, Clone)]
+pub(crate) struct RpcConnectionsLayer {
+    methods: Arc<SubscriptionMethods>,
+}
+
+impl RpcConnectionsLayer {
+    /// Creates a new instance of the layer for the server of the given module.
+    pub(crate) fn new(module: Option<&RpcModule<()>>) -> Self {
+        Self { methods: Arc::new(module.map(SubscriptionMethods::new).unwrap_or_default()) }
+    }
+}
+
+impl<S> Layer<S> for RpcConnectionsLayer {
+    type Service = RpcConnectionsService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcConnectionsService { inner, methods: self.methods.clone() }
+    }
+}
+
+/// A [`RpcServiceT`] middleware that tracks the requests and subscriptions of a connection.
+#[derive(Debug, Clone)]
+pub(crate) struct RpcConnectionsService<S> {
+    inner: S,
+    methods: Arc<SubscriptionMethods>,
+}
+
+impl<S> RpcServiceT for RpcConnectionsService<S>
+where
+    S: RpcServiceT<MethodResponse = MethodResponse> + Send + Sync + Clone + 'static,
+{
+    type MethodResponse = S::MethodResponse;
+    type NotificationResponse = S::NotificationResponse;
+    type BatchResponse = S::BatchResponse;
+
+    fn call<'a>(&self, req: Request<'a>) -> impl Future<Output = Self::MethodResponse> + Send + 'a {
+        let inner = self.inner.clone();
+        let connection = req.extensions().get::<Arc<RpcConnection>>().cloned();
+        let is_subscribe = self.methods.subscribe.contains(req.method_name());
+        let unsubscribed_id = self
+            .methods
+            .unsubscribe
+            .contains(req.method_name())
+            .then(|| first_param(&req))
+            .flatten();
+
+        async move {
+            let Some(connection) = connection else { return inner.call(req).await };
+
+            let _guard = connection.start_request();
+            let response = inner.call(req).await;
+
+            if response.is_success() {
+                if is_subscribe {
+                    if let Some(id) = subscription_id(&response) {
+                        connection.add_subscription(id);
+                    }
+                } else if let Some(id) = unsubscribed_id {
+                    connection.remove_subscription(&id);
+                }
+            }
+            response
+        }
+    }
+
+    fn batch<'a>(&self, req: Batch<'a>) -> impl Future<Output = Self::BatchResponse> + Send + 'a {
+        let inner = self.inner.clone();
+        let connection = req
+            .iter()
+            .flatten()
+            .find_map(|entry| entry.extensions().get::<Arc<RpcConnection>>().cloned());
+
+        async move {
+            let _guard = connection.as_ref().map(|connection| connection.start_request());
+            inner.batch(req).await
+        }
+    }
+
+    fn notification<'a>(
+        &self,
+        n: Notification<'a>,
+    ) -> impl Future<Output = Self::NotificationResponse> + Send + 'a {
+        self.inner.notification(n)
+    }
+}
+
+/// The subscribe and unsubscribe methods of a module.
+#[derive(Debug, Default)]
+struct SubscriptionMethods {
+    subscribe: HashSet<&'static str>,
+    unsubscribe: HashSet<&'static str>,
+}
+
+impl SubscriptionMethods {
+    /// Returns the subscribe and unsubscribe methods of the module.
+    ///
+    /// Unsubscribe methods are either named after their subscribe method, like
+    /// `admin_peerEvents_unsubscribe`, or after its namespace, like `eth_unsubscribe`.
+    fn new(module: &RpcModule<()>) -> Self {
+        let methods = module.method_names().collect::<Vec<_>>();
+        let find = |name: &str| methods.iter().copied().find(|method| *method == name);
+
+        let pairs = methods
+            .iter()
+            .filter_map(|unsubscribe| {
+                let subscribe = unsubscribe
+                    .strip_suffix("_unsubscribe")
+                    .and_then(find)
+                    .or_else(|| find(&unsubscribe.replace("_unsubscribe", "_subscribe")))?;
+                (subscribe != *unsubscribe).then_some((subscribe, *unsubscribe))
+            })
+            .collect::<HashMap<_, _>>();
+
+        Self {
+            subscribe: pairs.keys().copied().collect(),
+            unsubscribe: pairs.values().copied().collect(),
+        }
+    }
+}
+
+/// Returns the JSON encoded first param of the request.
+fn first_param(req: &Request<'_>) -> Option<String> {
+    let params: Vec<serde_json::Value> = serde_json::from_str(req.params().as_str()?).ok()?;
+    params.first().map(ToString::to_string)
+}
+
+/// Returns the JSON encoded subscription id of a successful subscription response.
+fn subscription_id(response: &MethodResponse) -> Option<String> {
+    #[derive(Deserialize)]
+    struct SubscriptionResponse {
+        result: serde_json::Value,
+    }
+
+    let response: SubscriptionResponse = serde_json::from_str(response.as_json().get()).ok()?;
+    Some(response.result.to_string())
+}
@@ -0,0 +1,581 @@
+//! Reports the block and state a request was served against.
+//!
+//! Load-balanced replicas of a node can be at different heads, and a replica can serve blocks
+//! that only exist in memory and may still be reorged away, before they are persisted. The
+//! [`RpcConsistencyLayer`] resolves the block a call is served against from its block parameter,
+//! calls without one are served against the canonical head, and reports it as a [`ServedBlock`]:
+//!
+//! - On every transport, calls of a method prefixed with [`SERVED_BLOCK_PREFIX`], e.g.
+//!   `servedBlock_eth_getBalance`, return an envelope with the `result` of the method and the
+//!   `servedBlock`.
+//! - Over http, every response has the [`BLOCK_NUMBER_HEADER`], [`BLOCK_HASH_HEADER`],
+//!   [`STATE_ROOT_HEADER`] and [`UNPERSISTED_HEADER`] headers, see [`RpcConsistencyHeadersLayer`].
+//!   Batches only have them if all their calls were served against the same block.
+//!
+//! Calls in batches can't be prefixed with [`SERVED_BLOCK_PREFIX`].
+//!
+//! Nothing is reported for failed calls, `pending` blocks, and calls served against a block tag
+//! like `latest` that moved to another block while the call was served.
+
+use alloy_consensus::BlockHeader;
+use alloy_eips::{BlockId, BlockNumHash, BlockNumberOrTag};
+use alloy_primitives::B256;
+use http::{HeaderMap, HeaderValue};
+use jsonrpsee::{
+    core::middleware::{Batch, BatchEntry, Notification},
+    server::{middleware::rpc::RpcServiceT, HttpRequest, HttpResponse},
+    types::{Id, Request},
+    MethodResponse, ResponsePayload,
+};
+use reth_chain_state::PersistedBlockSubscriptions;
+use reth_storage_api::BlockReaderIdExt;
+use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
+use std::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tokio::sync::watch;
+use tower::{Layer, Service};
+
+/// Method prefix of calls that return the [`ServedBlock`] in the envelope of their result.
+pub const SERVED_BLOCK_PREFIX: &str = "servedBlock_";
+
+/// Header with the number of the block a request was served against.
+pub const BLOCK_NUMBER_HEADER: &str = "reth-block-number";
+
+/// Header with the hash of the block a request was served against.
+pub const BLOCK_HASH_HEADER: &str = "reth-block-hash";
+
+/// Header with the state root of the block a request was served against.
+pub const STATE_ROOT_HEADER: &str = "reth-state-root";
+
+/// Header that is `true` if the block a request was served against wasn't persisted yet.
+pub const UNPERSISTED_HEADER: &str = "reth-unpersisted";
+
+/// The block a request was served against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServedBlock {
+    /// The number of the block.
+    pub number: u64,
+    /// The hash of the block.
+    pub hash: B256,
+    /// The state root of the block.
+    pub state_root: B256,
+    /// Whether the block wasn't persisted yet, so the response may include in-memory state that
+    /// can still be reorged away.
+    pub unpersisted: bool,
+}
+
+/// Resolves the [`ServedBlock`] of a block id.
+pub trait ServedBlockProvider: Send + Sync {
+    /// Returns the block the given block id currently resolves to, if known.
+    fn served_block(&self, block_id: BlockId) -> Option<ServedBlock>;
+}
+
+/// [`ServedBlockProvider`] of a blockchain provider.
+struct ProviderServedBlocks<P> {
+    provider: P,
+    /// The last persisted block, `None` until the first blocks were persisted.
+    persisted: watch::Receiver<Option<BlockNumHash>>,
+}
+
+impl<P> ServedBlockProvider for ProviderServedBlocks<P>
+where
+    P: BlockReaderIdExt + Send + Sync,
+{
+    fn served_block(&self, block_id: BlockId) -> Option<ServedBlock> {
+        if matches!(block_id, BlockId::Number(BlockNumberOrTag::Pending)) {
+            return None
+        }
+
+        let header = self.provider.sealed_header_by_id(block_id).ok()??;
+        let persisted = match *self.persisted.borrow() {
+            Some(persisted) => persisted.number,
+            // nothing was persisted since the node started, the database is the source of truth
+            None => self.provider.last_block_number().ok()?,
+        };
+        Some(ServedBlock {
+            number: header.number(),
+            hash: header.hash(),
+            state_root: header.state_root(),
+            unpersisted: header.number() > persisted,
+        })
+    }
+}
+
+/// Layer that reports the block calls were served against.
+///
+/// See the [module docs](self) for how the block is reported.
+#[derive(Clone)]
+pub struct RpcConsistencyLayer {
+    blocks: Arc<dyn ServedBlockProvider>,
+}
+
+impl RpcConsistencyLayer {
+    /// Creates a new layer that resolves the served blocks with the given provider.
+    pub fn new<P>(provider: P) -> Self
+    where
+        P: BlockReaderIdExt + PersistedBlockSubscriptions + 'static,
+    {
+        let persisted = provider.subscribe_persisted_block().0;
+        Self::with_blocks(ProviderServedBlocks { provider, persisted })
+    }
+
+    /// Creates a new layer that resolves the served blocks with the given provider.
+    pub fn with_blocks(blocks: impl ServedBlockProvider + 'static) -> Self {
+        Self { blocks: Arc::new(blocks) }
+    }
+}
+
+impl fmt::Debug for RpcConsistencyLayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RpcConsistencyLayer").finish_non_exhaustive()
+    }
+}
+
+impl<S> Layer<S> for RpcConsistencyLayer {
+    type Service = RpcConsistencyService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcConsistencyService { blocks: self.blocks.clone(), inner }
+    }
+}
+
+/// A [`RpcServiceT`] middleware that reports the block calls were served against.
+///
+/// Created by [`RpcConsistencyLayer`].
+#[derive(Clone)]
+pub struct RpcConsistencyService<S> {
+    blocks: Arc<dyn ServedBlockProvider>,
+    inner: S,
+}
+
+impl<S> RpcConsistencyService<S> {
+    /// Resolves the block of every call before and after it was served, and returns the block if
+    /// all calls were served against the same one.
+    fn served_block(
+        &self,
+        block_ids: Vec<BlockId>,
+    ) -> impl FnOnce() -> Option<ServedBlock> + Send + 'static {
+        let blocks = self.blocks.clone();
+        let before = same_block(block_ids.iter().map(|block_id| blocks.served_block(*block_id)));
+        move || {
+            let before = before?;
+            let after = same_block(block_ids.iter().map(|block_id| blocks.served_block(*block_id)));
+            // a block tag may have moved to another block while the calls were served
+            (after?.hash == before.hash).then_some(before)
+        }
+    }
+}
+
+impl<S> fmt::Debug for RpcConsistencyService<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RpcConsistencyService").finish_non_exhaustive()
+    }
+}
+
+impl<S> RpcServiceT for RpcConsistencyService<S>
+where
+    S: RpcServiceT<MethodResponse = MethodResponse, BatchResponse = MethodResponse>
+        + Send
+        + Sync
+        + Clone
+        + 'static,
+{
+    type MethodResponse = S::MethodResponse;
+    type NotificationResponse = S::NotificationResponse;
+    type BatchResponse = S::BatchResponse;
+
+    fn call<'a>(
+        &self,
+        mut req: Request<'a>,
+    ) -> impl Future<Output = Self::MethodResponse> + Send + 'a {
+        let envelope = match req.method.strip_prefix(SERVED_BLOCK_PREFIX) {
+            Some(method) => {
+                req.method = method.to_string().into();
+                true
+            }
+            None => false,
+        };
+        let id = req.id().into_owned();
+        let served_block = self.served_block(vec![block_id(&req.method, req.params.as_deref())]);
+        let fut = self.inner.call(req);
+
+        async move {
+            let mut response = fut.await;
+            if !response.is_success() || response.is_subscription() {
+                return response
+            }
+
+            let served_block = served_block();
+            if envelope {
+                response = with_envelope(response, id, served_block);
+            }
+            if let Some(served_block) = served_block {
+                response.extensions_mut().insert(served_block);
+            }
+            response
+        }
+    }
+
+    fn batch<'a>(&self, req: Batch<'a>) -> impl Future<Output = Self::BatchResponse> + Send + 'a {
+        let block_ids = req
+            .iter()
+            .flatten()
+            .filter_map(|entry| match entry {
+                BatchEntry::Call(req) => Some(block_id(&req.method, req.params.as_deref())),
+                BatchEntry::Notification(_) => None,
+            })
+            .collect();
+        let served_block = self.served_block(block_ids);
+        let fut = self.inner.batch(req);
+
+        async move {
+            let mut response = fut.await;
+            if let Some(served_block) = served_block() {
+                response.extensions_mut().insert(served_block);
+            }
+            response
+        }
+    }
+
+    fn notification<'a>(
+        &self,
+        n: Notification<'a>,
+    ) -> impl Future<Output = Self::NotificationResponse> + Send + 'a {
+        self.inner.notification(n)
+    }
+}
+
+/// Result of a successful JSON-RPC response.
+#[derive(Deserialize)]
+struct SuccessResponse<'a> {
+    #[serde(borrow)]
+    result: &'a RawValue,
+}
+
+/// Envelope of the result of calls of methods prefixed with [`SERVED_BLOCK_PREFIX`].
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ServedBlockEnvelope {
+    result: Box<RawValue>,
+    served_block: Option<ServedBlock>,
+}
+
+/// Wraps the result of the response in a [`ServedBlockEnvelope`].
+fn with_envelope(
+    response: MethodResponse,
+    id: Id<'static>,
+    served_block: Option<ServedBlock>,
+) -> MethodResponse {
+    let result = match serde_json::from_str::<SuccessResponse<'_>>(response.as_json().get()) {
+        Ok(SuccessResponse { result }) => result.to_owned(),
+        Err(_) => return response,
+    };
+    MethodResponse::response(
+        id,
+        ResponsePayload::success(ServedBlockEnvelope { result, served_block }),
+        usize::MAX,
+    )
+    .with_extensions(response.extensions().clone())
+}
+
+/// Returns the block, if all blocks are known and the same.
+fn same_block(mut blocks: impl Iterator<Item = Option<ServedBlock>>) -> Option<ServedBlock> {
+    let first = blocks.next()??;
+    blocks.all(|block| block.is_some_and(|block| block.hash == first.hash)).then_some(first)
+}
+
+/// Returns the block id of the block parameter of the method, `latest` if it has none.
+fn block_id(method: &str, params: Option<&RawValue>) -> BlockId {
+    block_param_position(method)
+        .and_then(|position| {
+            let params: Vec<&RawValue> = serde_json::from_str(params?.get()).ok()?;
+            serde_json::from_str(params.get(position)?.get()).ok()
+        })
+        .unwrap_or(BlockId::Number(BlockNumberOrTag::Latest))
+}
+
+/// Returns the position of the block parameter of the method, if it has one.
+fn block_param_position(method: &str) -> Option<usize> {
+    Some(match method {
+        "eth_getBlockByNumber" |
+        "eth_getBlockByHash" |
+        "eth_getBlockReceipts" |
+        "eth_getBlockTransactionCountByNumber" |
+        "eth_getBlockTransactionCountByHash" |
+        "eth_getUncleCountByBlockNumber" |
+        "eth_getUncleCountByBlockHash" |
+        "eth_getTransactionByBlockNumberAndIndex" |
+        "eth_getTransactionByBlockHashAndIndex" |
+        "eth_getRawTransactionByBlockNumberAndIndex" |
+        "eth_getRawTransactionByBlockHashAndIndex" |
+        "eth_getHeaderByNumber" |
+        "eth_getHeaderByHash" |
+        "debug_traceBlockByNumber" |
+        "debug_traceBlockByHash" |
+        "trace_block" => 0,
+        "eth_getBalance" |
+        "eth_getCode" |
+        "eth_getTransactionCount" |
+        "eth_getAccount" |
+        "eth_getAccountInfo" |
+        "eth_call" |
+        "eth_estimateGas" |
+        "eth_createAccessList" |
+        "debug_traceCall" => 1,
+        "eth_getStorageAt" | "eth_getProof" | "trace_call" => 2,
+        _ => return None,
+    })
+}
+
+/// Layer that adds the headers of the [`ServedBlock`] a request was served against to the
+/// responses of the http server.
+///
+/// The block is reported by the [`RpcConsistencyLayer`] of the rpc middleware.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RpcConsistencyHeadersLayer;
+
+impl<S> Layer<S> for RpcConsistencyHeadersLayer {
+    type Service = RpcConsistencyHeadersService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcConsistencyHeadersService { inner }
+    }
+}
+
+/// Service that adds the headers of the [`ServedBlock`] a request was served against to the
+/// responses.
+///
+/// Created by [`RpcConsistencyHeadersLayer`].
+#[derive(Debug, Clone)]
+pub struct RpcConsistencyHeadersService<S> {
+    inner: S,
+}
+
+impl<S> Service<HttpRequest> for RpcConsistencyHeadersService<S>
+where
+    S: Service<HttpRequest, Response = HttpResponse> + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = HttpResponse;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: HttpRequest) -> Self::Future {
+        let fut = self.inner.call(req);
+        Box::pin(async move {
+            let mut response = fut.await?;
+            if let Some(served_block) = response.extensions().get::<ServedBlock>().copied() {
+                insert_headers(response.headers_mut(), served_block);
+            }
+            Ok(response)
+        })
+    }
+}
+
+/// Inserts the headers of the block a request was served against.
+fn insert_headers(headers: &mut HeaderMap, served_block: ServedBlock) {
+    headers.insert(BLOCK_NUMBER_HEADER, HeaderValue::from(served_block.number));
+    headers.insert(BLOCK_HASH_HEADER, header_value(served_block.hash));
+    headers.insert(STATE_ROOT_HEADER, header_value(served_block.state_root));
+    headers.insert(
+        UNPERSISTED_HEADER,
+        HeaderValue::from_static(if served_block.unpersisted { "true" } else { "false" }),
+    );
+}
+
+fn header_value(hash: B256) -> HeaderValue {
+    HeaderValue::try_from(hash.to_string()).expect("hex is a valid header value")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    fn block(number: u64, unpersisted: bool) -> ServedBlock {
+        ServedBlock {
+            number,
+            hash: B256::with_last_byte(number as u8),
+            state_root: B256::with_last_byte(0xff),
+            unpersisted,
+        }
+    }
+
+    /// Resolves `latest` to the head and numbers to blocks, blocks above 10 are unpersisted.
+    struct TestBlocks {
+        head: Mutex<u64>,
+    }
+
+    impl ServedBlockProvider for TestBlocks {
+        fn served_block(&self, block_id: BlockId) -> Option<ServedBlock> {
+            let number = match block_id {
+                BlockId::Number(BlockNumberOrTag::Latest) => *self.head.lock().unwrap(),
+                BlockId::Number(BlockNumberOrTag::Number(number)) => number,
+                _ => return None,
+            };
+            Some(block(number, number > 10))
+        }
+    }
+
+    #[test]
+    fn block_params() {
+        let params = |params: &str| RawValue::from_string(params.to_string()).unwrap();
+
+        assert_eq!(
+            block_id(
+                "eth_getBalance",
+                Some(&params(r#"["0x0000000000000000000000000000000000000001","0x5"]"#))
+            ),
+            BlockId::number(5)
+        );
+        assert_eq!(
+            block_id(
+                "eth_getStorageAt",
+                Some(&params(r#"["0x0000000000000000000000000000000000000001","0x0","safe"]"#))
+            ),
+            BlockId::Number(BlockNumberOrTag::Safe)
+        );
+        let hash = B256::with_last_byte(1);
+        assert_eq!(
+            block_id("eth_getBlockByHash", Some(&params(&format!(r#"["{hash}",false]"#)))),
+            BlockId::from(hash)
+        );
+        // the optional block parameter defaults to `latest`
+        assert_eq!(
+            block_id(
+                "eth_getBalance",
+                Some(&params(r#"["0x0000000000000000000000000000000000000001"]"#))
+            ),
+            BlockId::latest()
+        );
+        assert_eq!(block_id("eth_chainId", None), BlockId::latest());
+    }
+
+    /// Responds to every call with its method name.
+    #[derive(Clone)]
+    struct MethodName;
+
+    impl RpcServiceT for MethodName {
+        type MethodResponse = MethodResponse;
+        type NotificationResponse = MethodResponse;
+        type BatchResponse = MethodResponse;
+
+        fn call<'a>(&self, req: Request<'a>) -> impl Future<Output = MethodResponse> + Send + 'a {
+            let method = req.method.to_string();
+            async move { MethodResponse::response(req.id, ResponsePayload::success(method), usize::MAX) }
+        }
+
+        fn batch<'a>(&self, _: Batch<'a>) -> impl Future<Output = MethodResponse> + Send + 'a {
+            async { MethodResponse::notification() }
+        }
+
+        fn notification<'a>(
+            &self,
+            _: Notification<'a>,
+        ) -> impl Future<Output = MethodResponse> + Send + 'a {
+            async { MethodResponse::notification() }
+        }
+    }
+
+    #[tokio::test]
+    async fn reports_served_block() {
+        let service =
+            RpcConsistencyLayer::with_blocks(TestBlocks { head: Mutex::new(12) }).layer(MethodName);
+        let call = |method: &str, params: &str| {
+            Request::owned(
+                method.to_string(),
+                Some(RawValue::from_string(params.to_string()).unwrap()),
+                Id::Number(1),
+            )
+        };
+        let json = |response: &MethodResponse| {
+            serde_json::from_str::<serde_json::Value>(response.as_json().get()).unwrap()
+        };
+
+        // unprefixed calls only report the block in the extensions
+        let response = service.call(call("eth_blockNumber", "[]")).await;
+        assert_eq!(response.extensions().get::<ServedBlock>(), Some(&block(12, true)));
+        assert_eq!(json(&response)["result"], "eth_blockNumber");
+
+        // prefixed calls are served by the method and wrapped in the envelope
+        let response = service
+            .call(call(
+                "servedBlock_eth_getBalance",
+                r#"["0x0000000000000000000000000000000000000001","0x5"]"#,
+            ))
+            .await;
+        assert_eq!(response.extensions().get::<ServedBlock>(), Some(&block(5, false)));
+        assert_eq!(json(&response)["result"]["result"], "eth_getBalance");
+        assert_eq!(json(&response)["result"]["servedBlock"]["number"], 5);
+        assert_eq!(json(&response)["result"]["servedBlock"]["unpersisted"], false);
+
+        // nothing is reported for pending blocks
+        let response = service
+            .call(call(
+                "servedBlock_eth_getBalance",
+                r#"["0x0000000000000000000000000000000000000001","pending"]"#,
+            ))
+            .await;
+        assert!(response.extensions().get::<ServedBlock>().is_none());
+        assert!(json(&response)["result"]["servedBlock"].is_null());
+    }
+
+    #[test]
+    fn envelope() {
+        let response = MethodResponse::response(
+            Id::Number(1),
+            ResponsePayload::success(serde_json::json!({"balance": "0x1"})),
+            usize::MAX,
+        );
+        let response = with_envelope(response, Id::Number(1), Some(block(12, true)));
+        let json: serde_json::Value = serde_json::from_str(response.as_json().get()).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "result": {
+                    "result": {"balance": "0x1"},
+                    "servedBlock": {
+                        "number": 12,
+                        "hash": B256::with_last_byte(12),
+                        "stateRoot": B256::with_last_byte(0xff),
+                        "unpersisted": true,
+                    },
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn served_block_headers() {
+        let mut headers = HeaderMap::new();
+        insert_headers(&mut headers, block(12, true));
+        assert_eq!(headers[BLOCK_NUMBER_HEADER], "12");
+        assert_eq!(headers[BLOCK_HASH_HEADER], B256::with_last_byte(12).to_string());
+        assert_eq!(headers[STATE_ROOT_HEADER], B256::with_last_byte(0xff).to_string());
+        assert_eq!(headers[UNPERSISTED_HEADER], "true");
+    }
+
+    #[test]
+    fn served_blocks_must_match() {
+        let blocks = TestBlocks { head: Mutex::new(12) };
+        let served = |ids: &[BlockId]| same_block(ids.iter().map(|id| blocks.served_block(*id)));
+
+        assert_eq!(served(&[BlockId::latest(), BlockId::number(12)]), Some(block(12, true)));
+        assert_eq!(served(&[BlockId::number(5)]), Some(block(5, false)));
+        assert_eq!(served(&[BlockId::latest(), BlockId::number(5)]), None);
+        assert_eq!(served(&[BlockId::Number(BlockNumberOrTag::Pending)]), None);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{cors::CorsDomainError, RethRpcModule};
+use crate::{cors::CorsDomainError, AuthAllowlistError, RethRpcModule};
 use reth_ipc::server::IpcServerStartError;
 use std::{
     collections::HashSet,
@@ -70,6 +70,9 @@ pub enum RpcError {
     /// Thrown when IPC server fails to start.
     #[error(transparent)]
     IpcServerError(#[from] IpcServerStartError),
+    /// Auth server allowlist couldn't be loaded.
+    #[error(transparent)]
+    AuthAllowlist(#[from] AuthAllowlistError),
     /// Custom error.
     #[error("{0}")]
     Custom(String),
@@ -0,0 +1,373 @@
+//! GraphQL server implementing the Ethereum GraphQL schema of
+//! [EIP-1767](https://eips.ethereum.org/EIPS/eip-1767).
+//!
+//! The GraphQL server isn't a separate server, but a [`GraphQlLayer`] of the http server that
+//! answers the requests to [`GRAPHQL_PATH`]. Queries are resolved with JSON-RPC calls that are sent
+//! through the rest of the http server, so they are subject to the same rpc middleware, like the
+//! [`RpcRequestRateLimiter`](crate::rate_limiter::RpcRequestRateLimiter) and the request metrics,
+//! and are answered by the same `eth` handlers and caches as regular JSON-RPC requests.
+//!
+//! Queries exceeding the [`GraphQlLimits`] are rejected before they're executed.
+
+use async_graphql::{http::parse_query_string, ServerError};
+use http::{header::CONTENT_TYPE, HeaderValue, Method, StatusCode};
+use http_body_util::{BodyExt, LengthLimitError, Limited};
+use jsonrpsee::{
+    server::{HttpBody, HttpRequest, HttpResponse},
+    types::ErrorObjectOwned,
+};
+use schema::GraphQlSchema;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex, PoisonError},
+    task::{Context, Poll},
+};
+use tower::{util::BoxCloneService, Layer, Service, ServiceExt};
+
+mod schema;
+
+/// The path of the http server on which GraphQL queries are served.
+pub const GRAPHQL_PATH: &str = "/graphql";
+
+/// The default maximum nesting depth of a GraphQL query.
+pub const DEFAULT_GRAPHQL_MAX_DEPTH: usize = 16;
+
+/// The default maximum complexity of a GraphQL query.
+pub const DEFAULT_GRAPHQL_MAX_COMPLEXITY: usize = 10_000;
+
+/// The default maximum size of a GraphQL request in bytes.
+pub const DEFAULT_GRAPHQL_MAX_BODY_SIZE: usize = 15 * 1024 * 1024;
+
+/// Limits of the GraphQL queries served by the [`GraphQlLayer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GraphQlLimits {
+    /// The maximum nesting depth of the fields of a query.
+    pub max_depth: usize,
+    /// The maximum complexity of a query, that is the number of selected fields, where the fields
+    /// of every block returned by the `blocks` query count separately.
+    pub max_complexity: usize,
+    /// The maximum size of the body of a `POST` request or the query string of a `GET` request in
+    /// bytes.
+    pub max_body_size: usize,
+}
+
+impl Default for GraphQlLimits {
+    fn default() -> Self {
+        Self {
+            max_depth: DEFAULT_GRAPHQL_MAX_DEPTH,
+            max_complexity: DEFAULT_GRAPHQL_MAX_COMPLEXITY,
+            max_body_size: DEFAULT_GRAPHQL_MAX_BODY_SIZE,
+        }
+    }
+}
+
+/// Layer that serves GraphQL queries on [`GRAPHQL_PATH`] and forwards all other requests to the
+/// wrapped http service.
+#[derive(Clone)]
+pub struct GraphQlLayer {
+    schema: GraphQlSchema,
+    max_body_size: usize,
+}
+
+impl GraphQlLayer {
+    /// Creates a new GraphQL layer with the default [`GraphQlLimits`].
+    pub fn new() -> Self {
+        Self::with_limits(GraphQlLimits::default())
+    }
+
+    /// Creates a new GraphQL layer that rejects queries exceeding the given limits.
+    pub fn with_limits(limits: GraphQlLimits) -> Self {
+        Self { schema: schema::build_schema(&limits), max_body_size: limits.max_body_size }
+    }
+}
+
+impl Default for GraphQlLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for GraphQlLayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GraphQlLayer")
+            .field("max_body_size", &self.max_body_size)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S> Layer<S> for GraphQlLayer {
+    type Service = GraphQlService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        GraphQlService { schema: self.schema.clone(), max_body_size: self.max_body_size, inner }
+    }
+}
+
+/// Service that answers GraphQL queries with the JSON-RPC methods of the wrapped http service.
+///
+/// Created by [`GraphQlLayer`].
+#[derive(Clone)]
+pub struct GraphQlService<S> {
+    schema: GraphQlSchema,
+    max_body_size: usize,
+    inner: S,
+}
+
+impl<S> fmt::Debug for GraphQlService<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GraphQlService").finish_non_exhaustive()
+    }
+}
+
+impl<S> Service<HttpRequest> for GraphQlService<S>
+where
+    S: Service<HttpRequest, Response = HttpResponse> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+    S::Error: fmt::Display + Send + 'static,
+{
+    type Response = HttpResponse;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: HttpRequest) -> Self::Future {
+        if req.uri().path() != GRAPHQL_PATH {
+            return Box::pin(self.inner.call(req))
+        }
+
+        let schema = self.schema.clone();
+        let max_body_size = self.max_body_size;
+        let client = RpcClient::new(self.inner.clone());
+        Box::pin(async move { Ok(serve_graphql(&schema, max_body_size, client, req).await) })
+    }
+}
+
+/// Executes the GraphQL query of the given http request.
+///
+/// Queries are accepted as the JSON body of `POST` requests, or as the query string of `GET`
+/// requests, of at most `max_body_size` bytes.
+async fn serve_graphql(
+    schema: &GraphQlSchema,
+    max_body_size: usize,
+    client: RpcClient,
+    req: HttpRequest,
+) -> HttpResponse {
+    let too_large = || {
+        error_response(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            format!("request exceeds the maximum size of {max_body_size} bytes"),
+        )
+    };
+
+    let method = req.method().clone();
+    let request = if method == Method::GET {
+        let query = req.uri().query().unwrap_or_default();
+        if query.len() > max_body_size {
+            return too_large()
+        }
+        parse_query_string(query).map_err(|err| err.to_string())
+    } else if method == Method::POST {
+        match Limited::new(req.into_body(), max_body_size).collect().await {
+            Ok(body) => serde_json::from_slice::<async_graphql::Request>(&body.to_bytes())
+                .map_err(|err| err.to_string()),
+            Err(err) if err.is::<LengthLimitError>() => return too_large(),
+            Err(err) => Err(err.to_string()),
+        }
+    } else {
+        return error_response(
+            StatusCode::METHOD_NOT_ALLOWED,
+            format!("unsupported method {method}"),
+        )
+    };
+
+    match request {
+        Ok(request) => json_response(StatusCode::OK, &schema.execute(request.data(client)).await),
+        Err(err) => error_response(StatusCode::BAD_REQUEST, err),
+    }
+}
+
+/// Returns a GraphQL response with the given error.
+fn error_response(status: StatusCode, message: String) -> HttpResponse {
+    json_response(
+        status,
+        &async_graphql::Response::from_errors(vec![ServerError::new(message, None)]),
+    )
+}
+
+/// Returns a response with the given JSON body.
+fn json_response(status: StatusCode, body: &async_graphql::Response) -> HttpResponse {
+    let body = serde_json::to_string(body).unwrap_or_default();
+    let mut response = HttpResponse::new(HttpBody::from(body));
+    *response.status_mut() = status;
+    response.headers_mut().insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    response
+}
+
+/// Client sending JSON-RPC calls through the http service of the RPC server.
+#[derive(Clone)]
+pub(crate) struct RpcClient {
+    service: Arc<Mutex<BoxCloneService<HttpRequest, HttpResponse, String>>>,
+}
+
+impl RpcClient {
+    /// Creates a new client for the given http service.
+    fn new<S>(service: S) -> Self
+    where
+        S: Service<HttpRequest, Response = HttpResponse> + Clone + Send + 'static,
+        S::Future: Send + 'static,
+        S::Error: fmt::Display,
+    {
+        let service = BoxCloneService::new(service.map_err(|err: S::Error| err.to_string()));
+        Self { service: Arc::new(Mutex::new(service)) }
+    }
+
+    /// Calls the given JSON-RPC method, failing if the result is `null`.
+    pub(crate) async fn call<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: impl Serialize,
+    ) -> async_graphql::Result<T> {
+        self.call_optional(method, params)
+            .await?
+            .ok_or_else(|| format!("{method} returned no result").into())
+    }
+
+    /// Calls the given JSON-RPC method, returning [`None`] if the result is `null`.
+    pub(crate) async fn call_optional<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: impl Serialize,
+    ) -> async_graphql::Result<Option<T>> {
+        let body = serde_json::to_string(&RpcRequest { jsonrpc: "2.0", id: 0, method, params })?;
+        let mut request = HttpRequest::new(HttpBody::from(body));
+        *request.method_mut() = Method::POST;
+        request.headers_mut().insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+
+        let service = self.service.lock().unwrap_or_else(PoisonError::into_inner).clone();
+        let response = service.oneshot(request).await?;
+        let body = response.into_body().collect().await?.to_bytes();
+
+        let response: RpcResponse<T> = serde_json::from_slice(&body)?;
+        match response.error {
+            Some(err) => Err(async_graphql::Error::new(err.message())),
+            None => Ok(response.result),
+        }
+    }
+}
+
+impl fmt::Debug for RpcClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RpcClient").finish_non_exhaustive()
+    }
+}
+
+/// A JSON-RPC request.
+#[derive(Serialize)]
+struct RpcRequest<'a, P> {
+    jsonrpc: &'static str,
+    id: u64,
+    method: &'a str,
+    params: P,
+}
+
+/// A JSON-RPC response.
+#[derive(Deserialize)]
+struct RpcResponse<T> {
+    result: Option<T>,
+    error: Option<ErrorObjectOwned>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonrpsee::{
+        server::{stop_channel, Server},
+        RpcModule,
+    };
+
+    /// Sends the query to a GraphQL service with the given limits, returns the status and the
+    /// JSON body of the response.
+    async fn query(limits: GraphQlLimits, query: &str) -> (StatusCode, serde_json::Value) {
+        let mut module = RpcModule::new(());
+        module.register_method("eth_chainId", |_, _, _| "0x1").unwrap();
+        module.register_method("eth_gasPrice", |_, _, _| "0x3b9aca00").unwrap();
+        module.register_method("eth_blockNumber", |_, _, _| "0x0").unwrap();
+        let (stop_handle, _server_handle) = stop_channel();
+        let service = GraphQlLayer::with_limits(limits)
+            .layer(Server::builder().to_service_builder().build(module, stop_handle));
+
+        let body = serde_json::json!({ "query": query }).to_string();
+        let mut request = HttpRequest::new(HttpBody::from(body));
+        *request.method_mut() = Method::POST;
+        *request.uri_mut() = GRAPHQL_PATH.parse().unwrap();
+        request.headers_mut().insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+
+        let response = service.oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    /// Returns the message of the first error of the response.
+    fn error_message(response: &serde_json::Value) -> &str {
+        response["errors"][0]["message"].as_str().unwrap()
+    }
+
+    #[tokio::test]
+    async fn graphql_query() {
+        let (status, response) = query(GraphQlLimits::default(), "{ chainID gasPrice }").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            response,
+            serde_json::json!({ "data": { "chainID": "0x1", "gasPrice": "0x3b9aca00" } })
+        );
+    }
+
+    #[tokio::test]
+    async fn graphql_depth_limit() {
+        let nested = "{ block { transactions { from { balance } } } }";
+        let limits = GraphQlLimits { max_depth: 3, ..Default::default() };
+
+        let (status, response) = query(limits, nested).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(response.get("data").is_none_or(|data| data.is_null()));
+        assert_eq!(error_message(&response), "Query is nested too deep.");
+
+        let (_, response) = query(limits, "{ chainID }").await;
+        assert_eq!(response, serde_json::json!({ "data": { "chainID": "0x1" } }));
+    }
+
+    #[tokio::test]
+    async fn graphql_complexity_limit() {
+        let limits = GraphQlLimits { max_complexity: 100, ..Default::default() };
+
+        // the fields of every block of the range count towards the complexity
+        let (_, response) = query(limits, "{ blocks(from: 0, to: 99) { hash number } }").await;
+        assert_eq!(error_message(&response), "Query is too complex.");
+
+        // without `to`, the maximum range is assumed
+        let (_, response) = query(limits, "{ blocks(from: 0) { hash } }").await;
+        assert_eq!(error_message(&response), "Query is too complex.");
+
+        let (_, response) = query(limits, "{ chainID gasPrice }").await;
+        assert!(response.get("errors").is_none());
+    }
+
+    #[tokio::test]
+    async fn graphql_body_limit() {
+        let limits = GraphQlLimits { max_body_size: 64, ..Default::default() };
+
+        let (status, response) = query(limits, &format!("{{ chainID {} }}", " ".repeat(64))).await;
+        assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(error_message(&response), "request exceeds the maximum size of 64 bytes");
+
+        let (status, _) = query(limits, "{ chainID }").await;
+        assert_eq!(status, StatusCode::OK);
+    }
+}
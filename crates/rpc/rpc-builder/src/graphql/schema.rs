@@ -0,0 +1,818 @@
+//! Types and resolvers of the Ethereum GraphQL schema.
+//!
+//! Every resolver is backed by the `eth` JSON-RPC method answering the same question. The `call`
+//! field of blocks and the `pending` query aren't supported.
+
+use super::{GraphQlLimits, RpcClient};
+use alloy_consensus::{BlockHeader, Transaction as ConsensusTransaction};
+use alloy_eips::{BlockId, BlockNumberOrTag};
+use alloy_network::{
+    AnyRpcBlock, AnyRpcTransaction, AnyTransactionReceipt, ReceiptResponse, TransactionResponse,
+};
+use alloy_primitives::{TxKind, B256, U256, U64};
+use alloy_rpc_types_eth::{Filter, SyncStatus, TransactionInput, TransactionRequest};
+use async_graphql::{
+    Context, EmptySubscription, InputObject, InputValueError, InputValueResult, Object, Result,
+    Scalar, ScalarType, Schema, SimpleObject, Value,
+};
+use tokio::sync::OnceCell;
+
+/// The GraphQL schema served by the [`GraphQlLayer`](super::GraphQlLayer).
+pub(crate) type GraphQlSchema = Schema<Query, Mutation, EmptySubscription>;
+
+/// Maximum number of blocks returned by the `blocks` query.
+const MAX_BLOCKS_PER_QUERY: u64 = 1_000;
+
+/// Maximum number of topics of a log filter.
+const MAX_TOPICS: usize = 4;
+
+/// Params of the JSON-RPC methods without params.
+const NO_PARAMS: [u8; 0] = [];
+
+/// Builds the GraphQL schema, which rejects queries exceeding the given limits.
+pub(crate) fn build_schema(limits: &GraphQlLimits) -> GraphQlSchema {
+    Schema::build(Query, Mutation, EmptySubscription)
+        .limit_depth(limits.max_depth)
+        .limit_complexity(limits.max_complexity)
+        .finish()
+}
+
+/// Returns the number of blocks returned by the `blocks` query for the given range, assuming the
+/// maximum if `to` isn't given.
+fn block_range_len(from: u64, to: Option<u64>) -> usize {
+    let len = to.map_or(MAX_BLOCKS_PER_QUERY, |to| to.saturating_sub(from).saturating_add(1));
+    len.min(MAX_BLOCKS_PER_QUERY) as usize
+}
+
+/// Returns the client of the JSON-RPC methods resolving the query.
+fn rpc<'a>(ctx: &Context<'a>) -> &'a RpcClient {
+    ctx.data_unchecked::<RpcClient>()
+}
+
+/// 32 byte binary string, represented as 0x-prefixed hexadecimal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Bytes32(B256);
+
+#[Scalar]
+impl ScalarType for Bytes32 {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        parse_string(value).map(Self)
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.0.to_string())
+    }
+}
+
+/// 20 byte Ethereum address, represented as 0x-prefixed hexadecimal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Address(alloy_primitives::Address);
+
+#[Scalar]
+impl ScalarType for Address {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        parse_string(value).map(Self)
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.0.to_string())
+    }
+}
+
+/// Arbitrary length binary string, represented as 0x-prefixed hexadecimal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Bytes(alloy_primitives::Bytes);
+
+#[Scalar]
+impl ScalarType for Bytes {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        parse_string(value).map(Self)
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.0.to_string())
+    }
+}
+
+/// Large integer, input as a JSON string in decimal or 0x-prefixed hexadecimal, and output as
+/// 0x-prefixed hexadecimal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct BigInt(U256);
+
+#[Scalar]
+impl ScalarType for BigInt {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match &value {
+            Value::Number(number) => number.as_u64().map(|number| Self(U256::from(number))),
+            Value::String(s) => s.parse().ok().map(Self),
+            _ => None,
+        }
+        .ok_or_else(|| InputValueError::expected_type(value))
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(format!("{:#x}", self.0))
+    }
+}
+
+/// 64 bit unsigned integer, input as a JSON number or as a string in decimal or 0x-prefixed
+/// hexadecimal, and output as 0x-prefixed hexadecimal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Long(u64);
+
+#[Scalar]
+impl ScalarType for Long {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match &value {
+            Value::Number(number) => number.as_u64(),
+            Value::String(s) => match s.strip_prefix("0x") {
+                Some(hex) => u64::from_str_radix(hex, 16).ok(),
+                None => s.parse().ok(),
+            },
+            _ => None,
+        }
+        .map(Self)
+        .ok_or_else(|| InputValueError::expected_type(value))
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(format!("{:#x}", self.0))
+    }
+}
+
+/// Parses a scalar represented as a string.
+fn parse_string<T: std::str::FromStr>(value: Value) -> InputValueResult<T> {
+    match &value {
+        Value::String(s) => s.parse().map_err(|_| InputValueError::expected_type(value)),
+        _ => Err(InputValueError::expected_type(value)),
+    }
+}
+
+/// The root of the queries.
+#[derive(Debug)]
+pub(crate) struct Query;
+
+#[Object]
+impl Query {
+    /// Fetches a block by number or by hash, or the latest block if neither is given.
+    async fn block(
+        &self,
+        ctx: &Context<'_>,
+        number: Option<Long>,
+        hash: Option<Bytes32>,
+    ) -> Result<Option<Block>> {
+        match (number, hash) {
+            (_, Some(hash)) => Block::by_hash(ctx, hash.0).await,
+            (Some(number), None) => Block::by_number(ctx, number.0.into()).await,
+            (None, None) => Block::by_number(ctx, BlockNumberOrTag::Latest).await,
+        }
+    }
+
+    /// Fetches the blocks in the given inclusive range, up to the latest block if `to` isn't
+    /// given.
+    #[graphql(
+        complexity = "block_range_len(from.0, to.map(|to| to.0)).saturating_mul(child_complexity)"
+    )]
+    async fn blocks(&self, ctx: &Context<'_>, from: Long, to: Option<Long>) -> Result<Vec<Block>> {
+        let to = match to {
+            Some(to) => to.0,
+            None => rpc(ctx).call::<U64>("eth_blockNumber", NO_PARAMS).await?.to(),
+        };
+        if to.saturating_sub(from.0) >= MAX_BLOCKS_PER_QUERY {
+            return Err(format!("block range exceeds the maximum of {MAX_BLOCKS_PER_QUERY}").into())
+        }
+
+        let mut blocks = Vec::new();
+        for number in from.0..=to {
+            match Block::by_number(ctx, number.into()).await? {
+                Some(block) => blocks.push(block),
+                None => break,
+            }
+        }
+        Ok(blocks)
+    }
+
+    /// Fetches a transaction by hash.
+    async fn transaction(&self, ctx: &Context<'_>, hash: Bytes32) -> Result<Option<Transaction>> {
+        let tx = rpc(ctx).call_optional("eth_getTransactionByHash", (hash.0,)).await?;
+        Ok(tx.map(Transaction::new))
+    }
+
+    /// Returns the logs matching the given filter.
+    async fn logs(&self, ctx: &Context<'_>, filter: FilterCriteria) -> Result<Vec<Log>> {
+        let mut log_filter = log_filter(filter.addresses, filter.topics)?;
+        if let Some(from_block) = filter.from_block {
+            log_filter = log_filter.from_block(from_block.0);
+        }
+        if let Some(to_block) = filter.to_block {
+            log_filter = log_filter.to_block(to_block.0);
+        }
+        logs(ctx, log_filter).await
+    }
+
+    /// Returns the current gas price of the network, in wei.
+    async fn gas_price(&self, ctx: &Context<'_>) -> Result<BigInt> {
+        rpc(ctx).call("eth_gasPrice", NO_PARAMS).await.map(BigInt)
+    }
+
+    /// Returns the current suggested priority fee per gas, in wei.
+    async fn max_priority_fee_per_gas(&self, ctx: &Context<'_>) -> Result<BigInt> {
+        rpc(ctx).call("eth_maxPriorityFeePerGas", NO_PARAMS).await.map(BigInt)
+    }
+
+    /// Returns the sync state of the node, or `null` if the node is synced.
+    async fn syncing(&self, ctx: &Context<'_>) -> Result<Option<SyncState>> {
+        Ok(match rpc(ctx).call("eth_syncing", NO_PARAMS).await? {
+            SyncStatus::Info(info) => Some(SyncState {
+                starting_block: Long(info.starting_block.saturating_to()),
+                current_block: Long(info.current_block.saturating_to()),
+                highest_block: Long(info.highest_block.saturating_to()),
+            }),
+            SyncStatus::None => None,
+        })
+    }
+
+    /// Returns the chain id used to sign transactions.
+    #[graphql(name = "chainID")]
+    async fn chain_id(&self, ctx: &Context<'_>) -> Result<BigInt> {
+        rpc(ctx).call("eth_chainId", NO_PARAMS).await.map(BigInt)
+    }
+}
+
+/// The root of the mutations.
+#[derive(Debug)]
+pub(crate) struct Mutation;
+
+#[Object]
+impl Mutation {
+    /// Sends an RLP encoded signed transaction to the network and returns its hash.
+    async fn send_raw_transaction(&self, ctx: &Context<'_>, data: Bytes) -> Result<Bytes32> {
+        rpc(ctx).call("eth_sendRawTransaction", (data.0,)).await.map(Bytes32)
+    }
+}
+
+/// An Ethereum block.
+#[derive(Debug)]
+pub(crate) struct Block(Box<AnyRpcBlock>);
+
+impl Block {
+    /// Fetches the block with the given number, including its transactions.
+    async fn by_number(ctx: &Context<'_>, number: BlockNumberOrTag) -> Result<Option<Self>> {
+        let block = rpc(ctx).call_optional("eth_getBlockByNumber", (number, true)).await?;
+        Ok(block.map(|block| Self(Box::new(block))))
+    }
+
+    /// Fetches the block with the given hash, including its transactions.
+    async fn by_hash(ctx: &Context<'_>, hash: B256) -> Result<Option<Self>> {
+        let block = rpc(ctx).call_optional("eth_getBlockByHash", (hash, true)).await?;
+        Ok(block.map(|block| Self(Box::new(block))))
+    }
+
+    /// Returns the [`BlockId`] of the state after this block, or of the given block number.
+    fn state_block(&self, block: Option<Long>) -> BlockId {
+        block.map_or_else(|| BlockId::from(self.0.header.hash), |block| BlockId::number(block.0))
+    }
+}
+
+#[Object]
+impl Block {
+    /// The number of the block.
+    async fn number(&self) -> Long {
+        Long(self.0.header.number())
+    }
+
+    /// The hash of the block.
+    async fn hash(&self) -> Bytes32 {
+        Bytes32(self.0.header.hash)
+    }
+
+    /// The parent of the block, or `null` for the genesis block.
+    async fn parent(&self, ctx: &Context<'_>) -> Result<Option<Block>> {
+        if self.0.header.number() == 0 {
+            return Ok(None)
+        }
+        Block::by_hash(ctx, self.0.header.parent_hash()).await
+    }
+
+    /// The proof-of-work nonce of the block.
+    async fn nonce(&self) -> Bytes {
+        let nonce = self.0.header.nonce().unwrap_or_default();
+        Bytes(alloy_primitives::Bytes::copy_from_slice(nonce.as_slice()))
+    }
+
+    /// The root of the transactions trie of the block.
+    async fn transactions_root(&self) -> Bytes32 {
+        Bytes32(self.0.header.transactions_root())
+    }
+
+    /// The number of transactions in the block.
+    async fn transaction_count(&self) -> Long {
+        Long(self.0.transactions.len() as u64)
+    }
+
+    /// The root of the state trie after the block.
+    async fn state_root(&self) -> Bytes32 {
+        Bytes32(self.0.header.state_root())
+    }
+
+    /// The root of the receipts trie of the block.
+    async fn receipts_root(&self) -> Bytes32 {
+        Bytes32(self.0.header.receipts_root())
+    }
+
+    /// The account that mined the block, at the state after this block unless another block is
+    /// given.
+    async fn miner(&self, block: Option<Long>) -> Account {
+        Account { address: self.0.header.beneficiary(), block: self.state_block(block) }
+    }
+
+    /// The extra data of the block.
+    async fn extra_data(&self) -> Bytes {
+        Bytes(self.0.header.extra_data().clone())
+    }
+
+    /// The maximum amount of gas the block can use.
+    async fn gas_limit(&self) -> Long {
+        Long(self.0.header.gas_limit())
+    }
+
+    /// The amount of gas used by the transactions of the block.
+    async fn gas_used(&self) -> Long {
+        Long(self.0.header.gas_used())
+    }
+
+    /// The base fee per gas of the block, if it's an EIP-1559 block.
+    async fn base_fee_per_gas(&self) -> Option<BigInt> {
+        self.0.header.base_fee_per_gas().map(|base_fee| BigInt(U256::from(base_fee)))
+    }
+
+    /// The unix timestamp of the block.
+    async fn timestamp(&self) -> Long {
+        Long(self.0.header.timestamp())
+    }
+
+    /// The bloom filter of the logs of the block.
+    async fn logs_bloom(&self) -> Bytes {
+        Bytes(alloy_primitives::Bytes::copy_from_slice(self.0.header.logs_bloom().as_slice()))
+    }
+
+    /// The mix hash of the block.
+    async fn mix_hash(&self) -> Bytes32 {
+        Bytes32(self.0.header.mix_hash().unwrap_or_default())
+    }
+
+    /// The proof-of-work difficulty of the block.
+    async fn difficulty(&self) -> BigInt {
+        BigInt(self.0.header.difficulty())
+    }
+
+    /// The number of ommers of the block.
+    async fn ommer_count(&self) -> Long {
+        Long(self.0.uncles.len() as u64)
+    }
+
+    /// The ommers of the block, without their transactions.
+    async fn ommers(&self, ctx: &Context<'_>) -> Result<Vec<Block>> {
+        let mut ommers = Vec::with_capacity(self.0.uncles.len());
+        for index in 0..self.0.uncles.len() {
+            let ommer = rpc(ctx)
+                .call("eth_getUncleByBlockHashAndIndex", (self.0.header.hash, U64::from(index)))
+                .await?;
+            ommers.push(Block(Box::new(ommer)));
+        }
+        Ok(ommers)
+    }
+
+    /// The ommer at the given index.
+    async fn ommer_at(&self, ctx: &Context<'_>, index: Long) -> Result<Option<Block>> {
+        let ommer = rpc(ctx)
+            .call_optional(
+                "eth_getUncleByBlockHashAndIndex",
+                (self.0.header.hash, U64::from(index.0)),
+            )
+            .await?;
+        Ok(ommer.map(|ommer| Block(Box::new(ommer))))
+    }
+
+    /// The hash of the ommers of the block.
+    async fn ommer_hash(&self) -> Bytes32 {
+        Bytes32(self.0.header.ommers_hash())
+    }
+
+    /// The transactions of the block, or `null` for ommers.
+    async fn transactions(&self) -> Option<Vec<Transaction>> {
+        self.0
+            .transactions
+            .as_transactions()
+            .map(|transactions| transactions.iter().cloned().map(Transaction::new).collect())
+    }
+
+    /// The transaction at the given index.
+    async fn transaction_at(&self, index: Long) -> Option<Transaction> {
+        let transactions = self.0.transactions.as_transactions()?;
+        transactions.get(index.0 as usize).cloned().map(Transaction::new)
+    }
+
+    /// The logs of the block matching the given filter.
+    async fn logs(&self, ctx: &Context<'_>, filter: BlockFilterCriteria) -> Result<Vec<Log>> {
+        let log_filter =
+            log_filter(filter.addresses, filter.topics)?.at_block_hash(self.0.header.hash);
+        logs(ctx, log_filter).await
+    }
+
+    /// The account with the given address, at the state after this block.
+    async fn account(&self, address: Address) -> Account {
+        Account { address: address.0, block: self.state_block(None) }
+    }
+
+    /// Estimates the amount of gas the given call would use on top of this block.
+    async fn estimate_gas(&self, ctx: &Context<'_>, data: CallData) -> Result<Long> {
+        let estimate: U64 = rpc(ctx)
+            .call("eth_estimateGas", (TransactionRequest::from(data), self.state_block(None)))
+            .await?;
+        Ok(Long(estimate.to()))
+    }
+
+    /// The root of the withdrawals trie of the block, if it's a Shanghai block.
+    async fn withdrawals_root(&self) -> Option<Bytes32> {
+        self.0.header.withdrawals_root().map(Bytes32)
+    }
+
+    /// The withdrawals of the block, if it's a Shanghai block.
+    async fn withdrawals(&self) -> Option<Vec<Withdrawal>> {
+        let withdrawals = self.0.withdrawals.as_ref()?;
+        Some(
+            withdrawals
+                .iter()
+                .map(|withdrawal| Withdrawal {
+                    index: Long(withdrawal.index),
+                    validator: Long(withdrawal.validator_index),
+                    address: Address(withdrawal.address),
+                    amount: Long(withdrawal.amount),
+                })
+                .collect(),
+        )
+    }
+
+    /// The amount of blob gas used by the transactions of the block, if it's a Cancun block.
+    async fn blob_gas_used(&self) -> Option<Long> {
+        self.0.header.blob_gas_used().map(Long)
+    }
+
+    /// The excess blob gas of the block, if it's a Cancun block.
+    async fn excess_blob_gas(&self) -> Option<Long> {
+        self.0.header.excess_blob_gas().map(Long)
+    }
+}
+
+/// An Ethereum account at a given block.
+#[derive(Debug)]
+pub(crate) struct Account {
+    address: alloy_primitives::Address,
+    block: BlockId,
+}
+
+#[Object]
+impl Account {
+    /// The address of the account.
+    async fn address(&self) -> Address {
+        Address(self.address)
+    }
+
+    /// The balance of the account, in wei.
+    async fn balance(&self, ctx: &Context<'_>) -> Result<BigInt> {
+        rpc(ctx).call("eth_getBalance", (self.address, self.block)).await.map(BigInt)
+    }
+
+    /// The number of transactions sent from the account.
+    async fn transaction_count(&self, ctx: &Context<'_>) -> Result<Long> {
+        let count: U64 =
+            rpc(ctx).call("eth_getTransactionCount", (self.address, self.block)).await?;
+        Ok(Long(count.to()))
+    }
+
+    /// The code of the contract at the account, empty for externally owned accounts.
+    async fn code(&self, ctx: &Context<'_>) -> Result<Bytes> {
+        rpc(ctx).call("eth_getCode", (self.address, self.block)).await.map(Bytes)
+    }
+
+    /// The value of the given storage slot of the account.
+    async fn storage(&self, ctx: &Context<'_>, slot: Bytes32) -> Result<Bytes32> {
+        rpc(ctx).call("eth_getStorageAt", (self.address, slot.0, self.block)).await.map(Bytes32)
+    }
+}
+
+/// An Ethereum transaction.
+#[derive(Debug)]
+pub(crate) struct Transaction {
+    tx: Box<AnyRpcTransaction>,
+    /// The receipt of the transaction, fetched on first use.
+    receipt: OnceCell<Option<AnyTransactionReceipt>>,
+}
+
+impl Transaction {
+    fn new(tx: AnyRpcTransaction) -> Self {
+        Self { tx: Box::new(tx), receipt: OnceCell::new() }
+    }
+
+    /// Returns the receipt of the transaction, or [`None`] if it's pending.
+    async fn receipt(&self, ctx: &Context<'_>) -> Result<Option<&AnyTransactionReceipt>> {
+        let receipt = self
+            .receipt
+            .get_or_try_init(|| {
+                rpc(ctx).call_optional("eth_getTransactionReceipt", (self.tx.tx_hash(),))
+            })
+            .await?;
+        Ok(receipt.as_ref())
+    }
+
+    /// Returns the [`BlockId`] of the state after the block of the transaction, or of the given
+    /// block number.
+    fn state_block(&self, block: Option<Long>) -> BlockId {
+        match block {
+            Some(block) => BlockId::number(block.0),
+            None => self.tx.block_hash().map_or_else(BlockId::latest, BlockId::from),
+        }
+    }
+}
+
+#[Object]
+impl Transaction {
+    /// The hash of the transaction.
+    async fn hash(&self) -> Bytes32 {
+        Bytes32(self.tx.tx_hash())
+    }
+
+    /// The nonce of the transaction.
+    async fn nonce(&self) -> Long {
+        Long(ConsensusTransaction::nonce(&*self.tx))
+    }
+
+    /// The index of the transaction in its block, or `null` if it's pending.
+    async fn index(&self) -> Option<Long> {
+        self.tx.transaction_index().map(Long)
+    }
+
+    /// The sender of the transaction, at the state after its block unless another block is given.
+    async fn from(&self, block: Option<Long>) -> Account {
+        Account { address: self.tx.from(), block: self.state_block(block) }
+    }
+
+    /// The recipient of the transaction, or `null` for contract creations.
+    async fn to(&self, block: Option<Long>) -> Option<Account> {
+        let address = ConsensusTransaction::to(&*self.tx)?;
+        Some(Account { address, block: self.state_block(block) })
+    }
+
+    /// The value transferred by the transaction, in wei.
+    async fn value(&self) -> BigInt {
+        BigInt(ConsensusTransaction::value(&*self.tx))
+    }
+
+    /// The price per gas paid by the transaction, in wei.
+    async fn gas_price(&self) -> BigInt {
+        let gas_price = self.tx.effective_gas_price.unwrap_or_else(|| {
+            ConsensusTransaction::gas_price(&*self.tx)
+                .unwrap_or_else(|| ConsensusTransaction::max_fee_per_gas(&*self.tx))
+        });
+        BigInt(U256::from(gas_price))
+    }
+
+    /// The maximum fee per gas of the transaction, if it's an EIP-1559 transaction.
+    async fn max_fee_per_gas(&self) -> Option<BigInt> {
+        ConsensusTransaction::is_dynamic_fee(&*self.tx)
+            .then(|| BigInt(U256::from(ConsensusTransaction::max_fee_per_gas(&*self.tx))))
+    }
+
+    /// The maximum priority fee per gas of the transaction, if it's an EIP-1559 transaction.
+    async fn max_priority_fee_per_gas(&self) -> Option<BigInt> {
+        ConsensusTransaction::max_priority_fee_per_gas(&*self.tx).map(|fee| BigInt(U256::from(fee)))
+    }
+
+    /// The maximum fee per blob gas of the transaction, if it's an EIP-4844 transaction.
+    async fn max_fee_per_blob_gas(&self) -> Option<BigInt> {
+        ConsensusTransaction::max_fee_per_blob_gas(&*self.tx).map(|fee| BigInt(U256::from(fee)))
+    }
+
+    /// The versioned hashes of the blobs of the transaction, if it's an EIP-4844 transaction.
+    async fn blob_versioned_hashes(&self) -> Option<Vec<Bytes32>> {
+        let hashes = ConsensusTransaction::blob_versioned_hashes(&*self.tx)?;
+        Some(hashes.iter().copied().map(Bytes32).collect())
+    }
+
+    /// The maximum amount of gas the transaction can use.
+    async fn gas(&self) -> Long {
+        Long(ConsensusTransaction::gas_limit(&*self.tx))
+    }
+
+    /// The input data of the transaction.
+    async fn input_data(&self) -> Bytes {
+        Bytes(ConsensusTransaction::input(&*self.tx).clone())
+    }
+
+    /// The block of the transaction, or `null` if it's pending.
+    async fn block(&self, ctx: &Context<'_>) -> Result<Option<Block>> {
+        match self.tx.block_hash() {
+            Some(hash) => Block::by_hash(ctx, hash).await,
+            None => Ok(None),
+        }
+    }
+
+    /// The status of the transaction, 1 on success and 0 on failure, or `null` if it's pending.
+    async fn status(&self, ctx: &Context<'_>) -> Result<Option<Long>> {
+        Ok(self.receipt(ctx).await?.map(|receipt| Long(receipt.status() as u64)))
+    }
+
+    /// The amount of gas used by the transaction, or `null` if it's pending.
+    async fn gas_used(&self, ctx: &Context<'_>) -> Result<Option<Long>> {
+        Ok(self.receipt(ctx).await?.map(|receipt| Long(receipt.gas_used())))
+    }
+
+    /// The amount of gas used by the block up to and including the transaction, or `null` if
+    /// it's pending.
+    async fn cumulative_gas_used(&self, ctx: &Context<'_>) -> Result<Option<Long>> {
+        Ok(self.receipt(ctx).await?.map(|receipt| Long(receipt.cumulative_gas_used())))
+    }
+
+    /// The price per gas paid by the transaction, in wei, or `null` if it's pending.
+    async fn effective_gas_price(&self, ctx: &Context<'_>) -> Result<Option<BigInt>> {
+        Ok(self
+            .receipt(ctx)
+            .await?
+            .map(|receipt| BigInt(U256::from(receipt.effective_gas_price()))))
+    }
+
+    /// The contract created by the transaction, or `null` if it isn't a contract creation or is
+    /// pending.
+    async fn created_contract(
+        &self,
+        ctx: &Context<'_>,
+        block: Option<Long>,
+    ) -> Result<Option<Account>> {
+        let address = self.receipt(ctx).await?.and_then(|receipt| receipt.contract_address());
+        Ok(address.map(|address| Account { address, block: self.state_block(block) }))
+    }
+
+    /// The logs emitted by the transaction, or `null` if it's pending.
+    async fn logs(&self, ctx: &Context<'_>) -> Result<Option<Vec<Log>>> {
+        Ok(self
+            .receipt(ctx)
+            .await?
+            .map(|receipt| receipt.inner.inner.logs().iter().cloned().map(Log).collect()))
+    }
+
+    /// The EIP-2718 type of the transaction.
+    async fn r#type(&self) -> Long {
+        Long(ConsensusTransaction::ty(&*self.tx) as u64)
+    }
+}
+
+/// A log emitted by a transaction.
+#[derive(Debug)]
+pub(crate) struct Log(alloy_rpc_types_eth::Log);
+
+#[Object]
+impl Log {
+    /// The index of the log in its block.
+    async fn index(&self) -> Long {
+        Long(self.0.log_index.unwrap_or_default())
+    }
+
+    /// The account that emitted the log, at the state after its block unless another block is
+    /// given.
+    async fn account(&self, block: Option<Long>) -> Account {
+        let block = match block {
+            Some(block) => BlockId::number(block.0),
+            None => self.0.block_hash.map_or_else(BlockId::latest, BlockId::from),
+        };
+        Account { address: self.0.address(), block }
+    }
+
+    /// The topics of the log.
+    async fn topics(&self) -> Vec<Bytes32> {
+        self.0.topics().iter().copied().map(Bytes32).collect()
+    }
+
+    /// The data of the log.
+    async fn data(&self) -> Bytes {
+        Bytes(self.0.data().data.clone())
+    }
+
+    /// The transaction that emitted the log.
+    async fn transaction(&self, ctx: &Context<'_>) -> Result<Transaction> {
+        let hash = self.0.transaction_hash.ok_or("log without transaction hash")?;
+        rpc(ctx).call("eth_getTransactionByHash", (hash,)).await.map(Transaction::new)
+    }
+}
+
+/// A withdrawal from the beacon chain.
+#[derive(Debug, SimpleObject)]
+pub(crate) struct Withdrawal {
+    /// The index of the withdrawal.
+    index: Long,
+    /// The index of the validator that withdrew.
+    validator: Long,
+    /// The recipient of the withdrawal.
+    address: Address,
+    /// The amount of the withdrawal, in gwei.
+    amount: Long,
+}
+
+/// The sync state of the node.
+#[derive(Debug, SimpleObject)]
+pub(crate) struct SyncState {
+    /// The block the sync started at.
+    starting_block: Long,
+    /// The current block of the sync.
+    current_block: Long,
+    /// The highest block known to the node.
+    highest_block: Long,
+}
+
+/// A filter of the logs in a block range.
+#[derive(Debug, InputObject)]
+pub(crate) struct FilterCriteria {
+    /// The first block of the range, the latest block if not given.
+    from_block: Option<Long>,
+    /// The last block of the range, the latest block if not given.
+    to_block: Option<Long>,
+    /// The addresses that may have emitted the logs, any address if not given.
+    addresses: Option<Vec<Address>>,
+    /// The topics the logs must match by position, any topic for an empty position.
+    topics: Option<Vec<Vec<Bytes32>>>,
+}
+
+/// A filter of the logs in a block.
+#[derive(Debug, InputObject)]
+pub(crate) struct BlockFilterCriteria {
+    /// The addresses that may have emitted the logs, any address if not given.
+    addresses: Option<Vec<Address>>,
+    /// The topics the logs must match by position, any topic for an empty position.
+    topics: Option<Vec<Vec<Bytes32>>>,
+}
+
+/// The arguments of a call.
+#[derive(Debug, InputObject)]
+pub(crate) struct CallData {
+    /// The sender of the call.
+    from: Option<Address>,
+    /// The recipient of the call, a contract creation if not given.
+    to: Option<Address>,
+    /// The maximum amount of gas the call can use.
+    gas: Option<Long>,
+    /// The price per gas of a legacy call, in wei.
+    gas_price: Option<BigInt>,
+    /// The maximum fee per gas of an EIP-1559 call, in wei.
+    max_fee_per_gas: Option<BigInt>,
+    /// The maximum priority fee per gas of an EIP-1559 call, in wei.
+    max_priority_fee_per_gas: Option<BigInt>,
+    /// The value transferred by the call, in wei.
+    value: Option<BigInt>,
+    /// The input data of the call.
+    data: Option<Bytes>,
+}
+
+impl From<CallData> for TransactionRequest {
+    fn from(data: CallData) -> Self {
+        Self {
+            from: data.from.map(|from| from.0),
+            to: data.to.map(|to| TxKind::Call(to.0)),
+            gas: data.gas.map(|gas| gas.0),
+            gas_price: data.gas_price.map(|price| price.0.saturating_to()),
+            max_fee_per_gas: data.max_fee_per_gas.map(|fee| fee.0.saturating_to()),
+            max_priority_fee_per_gas: data
+                .max_priority_fee_per_gas
+                .map(|fee| fee.0.saturating_to()),
+            value: data.value.map(|value| value.0),
+            input: TransactionInput::maybe_input(data.data.map(|data| data.0)),
+            ..Default::default()
+        }
+    }
+}
+
+/// Returns the log filter matching the given addresses and topics.
+fn log_filter(
+    addresses: Option<Vec<Address>>,
+    topics: Option<Vec<Vec<Bytes32>>>,
+) -> Result<Filter> {
+    let mut filter = Filter::new();
+    if let Some(addresses) = addresses {
+        filter = filter.address(addresses.into_iter().map(|address| address.0).collect::<Vec<_>>());
+    }
+
+    let topics = topics.unwrap_or_default();
+    if topics.len() > MAX_TOPICS {
+        return Err(format!("filter exceeds the maximum of {MAX_TOPICS} topics").into())
+    }
+    for (position, topic) in topics.into_iter().enumerate() {
+        filter.topics[position] = topic.into_iter().map(|topic| topic.0).collect::<Vec<_>>().into();
+    }
+
+    Ok(filter)
+}
+
+/// Returns the logs matching the given filter.
+async fn logs(ctx: &Context<'_>, filter: Filter) -> Result<Vec<Log>> {
+    let logs: Vec<alloy_rpc_types_eth::Log> = rpc(ctx).call("eth_getLogs", (filter,)).await?;
+    Ok(logs.into_iter().map(Log).collect())
+}
@@ -31,6 +31,7 @@ use jsonrpsee::{
     Methods, RpcModule,
 };
 use reth_chainspec::{ChainSpecProvider, EthereumHardforks};
+use reth_config::RpcRateLimitConfig;
 use reth_consensus::FullConsensus;
 use reth_engine_primitives::ConsensusEngineEvent;
 use reth_evm::ConfigureEvm;
@@ -54,8 +55,10 @@ use reth_rpc_eth_types::{receipt::EthReceiptConverter, EthConfig, EthSubscriptio
 use reth_rpc_layer::{AuthLayer, Claims, CompressionLayer, JwtAuthValidator, JwtSecret};
 pub use reth_rpc_server_types::RethRpcModule;
 use reth_storage_api::{
-    AccountReader, BlockReader, ChangeSetReader, FullRpcProvider, NodePrimitivesProvider,
-    StateProviderFactory,
+    AccountDestructionsReader, AccountReader, AddressTransactionsReader, BlockReader,
+    ChangeSetReader, ContractCreationsReader, FullRpcProvider, LogsBloomIndexReader,
+    NodePrimitivesProvider, PruneCheckpointReader, ReorgHistoryReader, StateProviderFactory,
+    StorageChangeSetReader, WithdrawalsProvider,
 };
 use reth_tasks::{pool::BlockingTaskGuard, TaskSpawner, TokioTaskExecutor};
 use reth_tokio_util::EventSender;
@@ -65,8 +68,10 @@ use std::{
     collections::HashMap,
     fmt::Debug,
     net::{Ipv4Addr, SocketAddr, SocketAddrV4},
+    sync::Arc,
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
+use tokio::net::TcpListener;
 use tower_http::cors::CorsLayer;
 
 pub use cors::CorsDomainError;
@@ -77,12 +82,16 @@ use jsonrpsee::server::ServerConfigBuilder;
 pub use reth_ipc::server::{
     Builder as IpcServerBuilder, RpcServiceBuilder as IpcRpcServiceBuilder,
 };
-pub use reth_rpc_server_types::{constants, RpcModuleSelection};
+pub use reth_rpc_server_types::{constants, RpcConnections, RpcModuleSelection};
 pub use tower::layer::util::{Identity, Stack};
 
 /// Auth server utilities.
 pub mod auth;
 
+/// Auth server client allowlist.
+mod allowlist;
+pub use allowlist::{AuthAllowlist, AuthAllowlistError};
+
 /// RPC server utilities.
 pub mod config;
 
@@ -101,14 +110,40 @@ pub use eth::EthHandlers;
 
 // Rpc server metrics
 mod metrics;
-use crate::middleware::RethRpcMiddleware;
+
+// Rpc server connections
+mod connections;
+use crate::{connections::RpcConnectionsLayer, middleware::RethRpcMiddleware};
 pub use metrics::{MeteredBatchRequestsFuture, MeteredRequestFuture, RpcRequestMetricsService};
-use reth_chain_state::{CanonStateSubscriptions, PersistedBlockSubscriptions};
+use reth_chain_state::{
+    BlockTimingsProvider, CanonStateSubscriptions, ChainStatsProvider, PersistedBlockSubscriptions,
+    SyncProgressProvider,
+};
 use reth_rpc::eth::sim_bundle::EthSimBundle;
 
 // Rpc rate limiter
 pub mod rate_limiter;
 
+// Rpc rate limits and method filters of the node config
+mod quotas;
+pub use quotas::{ClientIp, RpcClientIpLayer, RpcClientIpService, RpcQuotaLayer, RpcQuotaService};
+
+// Accept loop of the http and ws servers
+mod accept;
+pub use accept::PeerAddr;
+
+// Dedicated worker pools of expensive rpc namespaces
+mod namespace_pools;
+pub use namespace_pools::{RpcNamespacePools, RpcNamespacePoolsService};
+
+/// GraphQL server
+pub mod graphql;
+pub use graphql::{GraphQlLayer, GraphQlLimits, GRAPHQL_PATH};
+
+/// Reports the block and state a request was served against
+pub mod consistency;
+pub use consistency::{RpcConsistencyHeadersLayer, RpcConsistencyLayer};
+
 /// A builder type to configure the RPC module: See [`RpcModule`]
 ///
 /// This is the main entrypoint and the easiest way to configure an RPC server.
@@ -312,8 +347,19 @@ where
     Provider: FullRpcProvider<Block = N::Block, Receipt = N::Receipt, Header = N::BlockHeader>
         + CanonStateSubscriptions<Primitives = N>
         + PersistedBlockSubscriptions
+        + BlockTimingsProvider
+        + ChainStatsProvider
+        + SyncProgressProvider
         + AccountReader
-        + ChangeSetReader,
+        + ChangeSetReader
+        + StorageChangeSetReader
+        + PruneCheckpointReader
+        + WithdrawalsProvider
+        + ReorgHistoryReader
+        + AddressTransactionsReader
+        + LogsBloomIndexReader
+        + ContractCreationsReader
+        + AccountDestructionsReader,
     Pool: TransactionPool + Clone + 'static,
     Network: NetworkInfo + Peers + Clone + 'static,
     EvmConfig: ConfigureEvm<Primitives = N> + 'static,
@@ -362,7 +408,7 @@ where
         EthApi: FullEthApiServer<Provider = Provider, Pool = Pool>,
     {
         let Self { provider, pool, network, executor, consensus, evm_config, .. } = self;
-        RpcRegistryInner::new(
+        let mut registry = RpcRegistryInner::new(
             provider,
             pool,
             network,
@@ -372,7 +418,10 @@ where
             evm_config,
             eth,
             engine_events,
-        )
+        );
+        registry.eth.filter =
+            registry.eth.filter.clone().with_logs_bloom_index(Arc::new(registry.provider.clone()));
+        registry
     }
 
     /// Configures all [`RpcModule`]s specific to the given [`TransportRpcModuleConfig`] which can
@@ -397,6 +446,7 @@ where
             modules.http = registry.maybe_module(http.as_ref());
             modules.ws = registry.maybe_module(ws.as_ref());
             modules.ipc = registry.maybe_module(ipc.as_ref());
+            modules.connections = registry.rpc_connections.clone();
         }
 
         modules
@@ -497,6 +547,8 @@ pub struct RpcRegistryInner<Provider, Pool, Network, EthApi: EthApiTypes, EvmCon
     /// Notification channel for engine API events
     engine_events:
         EventSender<ConsensusEngineEvent<<EthApi::RpcConvert as RpcConvert>::Primitives>>,
+    /// The open connections of the RPC servers
+    rpc_connections: RpcConnections,
 }
 
 // === impl RpcRegistryInner ===
@@ -550,6 +602,7 @@ where
             eth_config: config.eth,
             evm_config,
             engine_events,
+            rpc_connections: Default::default(),
         }
     }
 }
@@ -569,6 +622,11 @@ where
         &self.eth
     }
 
+    /// Returns a reference to the registry of the open connections of the RPC servers.
+    pub const fn rpc_connections(&self) -> &RpcConnections {
+        &self.rpc_connections
+    }
+
     /// Returns a reference to the pool
     pub const fn pool(&self) -> &Pool {
         &self.pool
@@ -618,7 +676,12 @@ where
         Network: Peers,
         Pool: TransactionPool + Clone + 'static,
     {
-        AdminApi::new(self.network.clone(), self.provider.chain_spec(), self.pool.clone())
+        AdminApi::new(
+            self.network.clone(),
+            self.provider.chain_spec(),
+            self.pool.clone(),
+            self.rpc_connections.clone(),
+        )
     }
 
     /// Instantiates `Web3Api`
@@ -656,8 +719,19 @@ where
             Transaction = N::SignedTx,
         > + AccountReader
         + ChangeSetReader
+        + StorageChangeSetReader
+        + PruneCheckpointReader
+        + WithdrawalsProvider
+        + ReorgHistoryReader
+        + AddressTransactionsReader
+        + LogsBloomIndexReader
+        + ContractCreationsReader
+        + AccountDestructionsReader
         + CanonStateSubscriptions
-        + PersistedBlockSubscriptions,
+        + PersistedBlockSubscriptions
+        + BlockTimingsProvider
+        + ChainStatsProvider
+        + SyncProgressProvider,
     Network: NetworkInfo + Peers + Clone + 'static,
     EthApi: EthApiServer<
             RpcTxReq<EthApi::NetworkTypes>,
@@ -758,7 +832,7 @@ where
     /// If called outside of the tokio runtime. See also [`Self::eth_api`]
     pub fn otterscan_api(&self) -> OtterscanApi<EthApi> {
         let eth_api = self.eth_api().clone();
-        OtterscanApi::new(eth_api)
+        OtterscanApi::new(eth_api).with_contract_creations(Arc::new(self.provider.clone()))
     }
 }
 
@@ -772,7 +846,15 @@ where
             Transaction = N::SignedTx,
             Receipt = N::Receipt,
         > + AccountReader
-        + ChangeSetReader,
+        + ChangeSetReader
+        + StorageChangeSetReader
+        + PruneCheckpointReader
+        + WithdrawalsProvider
+        + ReorgHistoryReader
+        + AddressTransactionsReader
+        + LogsBloomIndexReader
+        + ContractCreationsReader
+        + AccountDestructionsReader,
     Network: NetworkInfo + Peers + Clone + 'static,
     EthApi: EthApiTypes,
     EvmConfig: ConfigureEvm<Primitives = N>,
@@ -788,6 +870,7 @@ where
             self.blocking_pool_guard.clone(),
             self.eth_config.clone(),
         )
+        .with_address_transactions(Arc::new(self.provider.clone()))
     }
 
     /// Instantiates [`EthBundle`] Api
@@ -843,11 +926,22 @@ impl<N, Provider, Pool, Network, EthApi, EvmConfig, Consensus>
     RpcRegistryInner<Provider, Pool, Network, EthApi, EvmConfig, Consensus>
 where
     N: NodePrimitives,
-    Provider: FullRpcProvider<Block = N::Block>
+    Provider: FullRpcProvider<Block = N::Block, Receipt = N::Receipt>
         + CanonStateSubscriptions<Primitives = N>
         + PersistedBlockSubscriptions
+        + BlockTimingsProvider
+        + ChainStatsProvider
+        + SyncProgressProvider
         + AccountReader
-        + ChangeSetReader,
+        + ChangeSetReader
+        + StorageChangeSetReader
+        + PruneCheckpointReader
+        + WithdrawalsProvider
+        + ReorgHistoryReader
+        + AddressTransactionsReader
+        + LogsBloomIndexReader
+        + ContractCreationsReader
+        + AccountDestructionsReader,
     Pool: TransactionPool + Clone + 'static,
     Network: NetworkInfo + Peers + Clone + 'static,
     EthApi: FullEthApiServer,
@@ -892,6 +986,7 @@ where
         modules.http = http;
         modules.ws = ws;
         modules.ipc = ipc;
+        modules.connections = self.rpc_connections.clone();
         modules
     }
 
@@ -933,6 +1028,7 @@ where
                             self.network.clone(),
                             self.provider.chain_spec(),
                             self.pool.clone(),
+                            self.rpc_connections.clone(),
                         )
                         .into_rpc()
                         .into(),
@@ -969,15 +1065,22 @@ where
                             self.blocking_pool_guard.clone(),
                             self.eth_config.clone(),
                         )
+                        .with_address_transactions(Arc::new(self.provider.clone()))
                         .into_rpc()
                         .into(),
                         RethRpcModule::Web3 => Web3Api::new(self.network.clone()).into_rpc().into(),
-                        RethRpcModule::Txpool => TxPoolApi::new(
-                            self.eth.api.pool().clone(),
-                            dyn_clone::clone(self.eth.api.converter()),
-                        )
-                        .into_rpc()
-                        .into(),
+                        RethRpcModule::Txpool => {
+                            let txpool = TxPoolApi::new(
+                                self.eth.api.pool().clone(),
+                                dyn_clone::clone(self.eth.api.converter()),
+                            )
+                            .with_subscription_task_spawner(self.executor.clone());
+                            let mut module = TxPoolApiServer::into_rpc(txpool.clone());
+                            module
+                                .merge(RethTxPoolApiServer::into_rpc(txpool))
+                                .expect("No conflicts");
+                            module.into()
+                        }
                         RethRpcModule::Rpc => RPCApi::new(
                             namespaces
                                 .iter()
@@ -986,11 +1089,28 @@ where
                         )
                         .into_rpc()
                         .into(),
-                        RethRpcModule::Ots => OtterscanApi::new(eth_api.clone()).into_rpc().into(),
+                        RethRpcModule::Ots => OtterscanApi::new(eth_api.clone())
+                            .with_contract_creations(Arc::new(self.provider.clone()))
+                            .into_rpc()
+                            .into(),
                         RethRpcModule::Reth => {
-                            RethApi::new(self.provider.clone(), self.executor.clone())
-                                .into_rpc()
-                                .into()
+                            let mut module =
+                                RethApi::new(self.provider.clone(), self.executor.clone())
+                                    .into_rpc();
+                            module
+                                .merge(reth_rpc::RethInclusion::new(eth_api.clone()).into_rpc())
+                                .expect("No conflicts");
+                            #[cfg(feature = "execution-stats")]
+                            module
+                                .merge(
+                                    reth_rpc::RethExecutionStats::new(
+                                        eth_api.clone(),
+                                        self.blocking_pool_guard.clone(),
+                                    )
+                                    .into_rpc(),
+                                )
+                                .expect("No conflicts");
+                            module.into()
                         }
                         RethRpcModule::Miner => MinerApi::default().into_rpc().into(),
                         RethRpcModule::Mev => {
@@ -1059,6 +1179,16 @@ pub struct RpcServerConfig<RpcMiddleware = Identity> {
     http_addr: Option<SocketAddr>,
     /// Control whether http responses should be compressed
     http_disable_compression: bool,
+    /// Control whether the GraphQL server is served by the http server
+    http_graphql: bool,
+    /// Limits of the queries served by the GraphQL server
+    http_graphql_limits: GraphQlLimits,
+    /// Reports the block requests were served against
+    consistency: Option<RpcConsistencyLayer>,
+    /// Rate limits and method filters of the http and ws servers
+    rate_limit: Option<RpcRateLimitConfig>,
+    /// Dedicated worker pools of expensive namespaces of the http and ws servers
+    namespace_pools: Option<RpcNamespacePools>,
     /// Configs for WS server
     ws_server_config: Option<ServerConfigBuilder>,
     /// Allowed CORS Domains for ws.
@@ -1085,6 +1215,11 @@ impl Default for RpcServerConfig<Identity> {
             http_cors_domains: None,
             http_addr: None,
             http_disable_compression: false,
+            http_graphql: false,
+            http_graphql_limits: GraphQlLimits::default(),
+            consistency: None,
+            rate_limit: None,
+            namespace_pools: None,
             ws_server_config: None,
             ws_cors_domains: None,
             ws_addr: None,
@@ -1149,6 +1284,11 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
             http_cors_domains: self.http_cors_domains,
             http_addr: self.http_addr,
             http_disable_compression: self.http_disable_compression,
+            http_graphql: self.http_graphql,
+            http_graphql_limits: self.http_graphql_limits,
+            consistency: self.consistency,
+            rate_limit: self.rate_limit,
+            namespace_pools: self.namespace_pools,
             ws_server_config: self.ws_server_config,
             ws_cors_domains: self.ws_cors_domains,
             ws_addr: self.ws_addr,
@@ -1176,6 +1316,25 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
         self
     }
 
+    /// Configure whether the GraphQL server is served on the [`GRAPHQL_PATH`] of the http server
+    pub const fn with_http_graphql(mut self, http_graphql: bool) -> Self {
+        self.http_graphql = http_graphql;
+        self
+    }
+
+    /// Configure the limits of the queries served by the GraphQL server
+    pub const fn with_http_graphql_limits(mut self, limits: GraphQlLimits) -> Self {
+        self.http_graphql_limits = limits;
+        self
+    }
+
+    /// Configure the [`RpcConsistencyLayer`] that reports the block requests were served against
+    /// on all transports
+    pub fn with_consistency(mut self, consistency: Option<RpcConsistencyLayer>) -> Self {
+        self.consistency = consistency;
+        self
+    }
+
     /// Configure the cors domains for HTTP
     pub fn with_http_cors(mut self, cors_domain: Option<String>) -> Self {
         self.http_cors_domains = cors_domain;
@@ -1253,6 +1412,18 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
 
     /// Returns true if any server is configured.
     ///
+    /// Configure the rate limits and method filters of the http and ws servers
+    pub fn with_rate_limit(mut self, rate_limit: Option<RpcRateLimitConfig>) -> Self {
+        self.rate_limit = rate_limit;
+        self
+    }
+
+    /// Configure the dedicated worker pools of expensive namespaces of the http and ws servers
+    pub fn with_namespace_pools(mut self, namespace_pools: Option<RpcNamespacePools>) -> Self {
+        self.namespace_pools = namespace_pools;
+        self
+    }
+
     /// If no server is configured, no server will be launched on [`RpcServerConfig::start`].
     pub const fn has_server(&self) -> bool {
         self.http_server_config.is_some() ||
@@ -1295,6 +1466,35 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
         }
     }
 
+    /// Returns a [`GraphQlLayer`] that serves GraphQL queries on [`GRAPHQL_PATH`], if enabled
+    fn maybe_graphql_layer(graphql: bool, limits: GraphQlLimits) -> Option<GraphQlLayer> {
+        graphql.then(|| GraphQlLayer::with_limits(limits))
+    }
+
+    /// Returns the [`RpcQuotaLayer`] and the [`RpcClientIpLayer`] of the configured rate limits.
+    ///
+    /// The layers are shared by the http and ws servers, so are their limits.
+    fn quota_layers(
+        rate_limit: Option<&RpcRateLimitConfig>,
+    ) -> (Option<RpcQuotaLayer>, Option<RpcClientIpLayer>) {
+        let Some(rate_limit) = rate_limit else { return (None, None) };
+        (
+            Some(RpcQuotaLayer::new(rate_limit)),
+            Some(RpcClientIpLayer::new(&rate_limit.trusted_proxies)),
+        )
+    }
+
+    /// Binds the listener of a http or ws server.
+    async fn bind(
+        addr: SocketAddr,
+        kind: ServerKind,
+    ) -> Result<(TcpListener, SocketAddr), RpcError> {
+        let listener =
+            TcpListener::bind(addr).await.map_err(|err| RpcError::server_error(err, kind))?;
+        let local_addr = listener.local_addr().map_err(|err| RpcError::server_error(err, kind))?;
+        Ok((listener, local_addr))
+    }
+
     /// Builds and starts the configured server(s): http, ws, ipc.
     ///
     /// If both http and ws are on the same port, they are combined into one server.
@@ -1318,13 +1518,19 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
             constants::DEFAULT_WS_RPC_PORT,
         )));
 
+        let (quota_layer, client_ip_layer) = Self::quota_layers(self.rate_limit.as_ref());
+
         let metrics = modules.ipc.as_ref().map(RpcRequestMetrics::ipc).unwrap_or_default();
         let ipc_path =
             self.ipc_endpoint.clone().unwrap_or_else(|| constants::DEFAULT_IPC_ENDPOINT.into());
 
         if let Some(builder) = self.ipc_server_config {
             let ipc = builder
-                .set_rpc_middleware(IpcRpcServiceBuilder::new().layer(metrics))
+                .set_rpc_middleware(
+                    IpcRpcServiceBuilder::new()
+                        .option_layer(self.consistency.clone())
+                        .layer(metrics),
+                )
                 .build(ipc_path);
             ipc_handle = Some(ipc.start(modules.ipc.clone().expect("ipc server error")).await?);
         }
@@ -1356,14 +1562,23 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
                 let server = ServerBuilder::new()
                     .set_http_middleware(
                         tower::ServiceBuilder::new()
+                            .option_layer(client_ip_layer.clone())
                             .option_layer(Self::maybe_cors_layer(cors)?)
                             .option_layer(Self::maybe_jwt_layer(self.jwt_secret))
                             .option_layer(Self::maybe_compression_layer(
                                 self.http_disable_compression,
+                            ))
+                            .option_layer(
+                                self.consistency.as_ref().map(|_| RpcConsistencyHeadersLayer),
+                            )
+                            .option_layer(Self::maybe_graphql_layer(
+                                self.http_graphql,
+                                self.http_graphql_limits,
                             )),
                     )
                     .set_rpc_middleware(
                         RpcServiceBuilder::default()
+                            .option_layer(self.consistency.clone())
                             .layer(
                                 modules
                                     .http
@@ -1372,19 +1587,25 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
                                     .map(RpcRequestMetrics::same_port)
                                     .unwrap_or_default(),
                             )
+                            .layer(RpcConnectionsLayer::new(
+                                modules.http.as_ref().or(modules.ws.as_ref()),
+                            ))
+                            .option_layer(quota_layer.clone())
+                            .option_layer(self.namespace_pools.clone())
                             .layer(self.rpc_middleware.clone()),
                     )
                     .set_config(config.build())
-                    .build(http_socket_addr)
-                    .await
-                    .map_err(|err| {
-                        RpcError::server_error(err, ServerKind::WsHttp(http_socket_addr))
-                    })?;
-                let addr = server.local_addr().map_err(|err| {
-                    RpcError::server_error(err, ServerKind::WsHttp(http_socket_addr))
-                })?;
+                    .to_service_builder();
+                let (listener, addr) =
+                    Self::bind(http_socket_addr, ServerKind::WsHttp(http_socket_addr)).await?;
                 if let Some(module) = modules.http.as_ref().or(modules.ws.as_ref()) {
-                    let handle = server.start(module.clone());
+                    let methods = Methods::from(module.clone());
+                    let handle = accept::serve(
+                        listener,
+                        modules.connections.clone(),
+                        false,
+                        move |stop_handle| server.clone().build(methods.clone(), stop_handle),
+                    );
                     http_handle = Some(handle.clone());
                     ws_handle = Some(handle);
                 }
@@ -1410,24 +1631,25 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
                 .set_config(config.ws_only().build())
                 .set_http_middleware(
                     tower::ServiceBuilder::new()
+                        .option_layer(client_ip_layer.clone())
                         .option_layer(Self::maybe_cors_layer(self.ws_cors_domains.clone())?)
                         .option_layer(Self::maybe_jwt_layer(self.jwt_secret)),
                 )
                 .set_rpc_middleware(
                     RpcServiceBuilder::default()
+                        .option_layer(self.consistency.clone())
                         .layer(modules.ws.as_ref().map(RpcRequestMetrics::ws).unwrap_or_default())
+                        .layer(RpcConnectionsLayer::new(modules.ws.as_ref()))
+                        .option_layer(quota_layer.clone())
+                        .option_layer(self.namespace_pools.clone())
                         .layer(self.rpc_middleware.clone()),
                 )
-                .build(ws_socket_addr)
-                .await
-                .map_err(|err| RpcError::server_error(err, ServerKind::WS(ws_socket_addr)))?;
-
-            let addr = server
-                .local_addr()
-                .map_err(|err| RpcError::server_error(err, ServerKind::WS(ws_socket_addr)))?;
+                .to_service_builder();
+            let (listener, addr) =
+                Self::bind(ws_socket_addr, ServerKind::WS(ws_socket_addr)).await?;
 
             ws_local_addr = Some(addr);
-            ws_server = Some(server);
+            ws_server = Some((listener, server));
         }
 
         if let Some(config) = self.http_server_config {
@@ -1435,31 +1657,46 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
                 .set_config(config.http_only().build())
                 .set_http_middleware(
                     tower::ServiceBuilder::new()
+                        .option_layer(client_ip_layer.clone())
                         .option_layer(Self::maybe_cors_layer(self.http_cors_domains.clone())?)
                         .option_layer(Self::maybe_jwt_layer(self.jwt_secret))
-                        .option_layer(Self::maybe_compression_layer(self.http_disable_compression)),
+                        .option_layer(Self::maybe_compression_layer(self.http_disable_compression))
+                        .option_layer(self.consistency.as_ref().map(|_| RpcConsistencyHeadersLayer))
+                        .option_layer(Self::maybe_graphql_layer(
+                            self.http_graphql,
+                            self.http_graphql_limits,
+                        )),
                 )
                 .set_rpc_middleware(
                     RpcServiceBuilder::default()
+                        .option_layer(self.consistency.clone())
                         .layer(
                             modules.http.as_ref().map(RpcRequestMetrics::http).unwrap_or_default(),
                         )
+                        .layer(RpcConnectionsLayer::new(modules.http.as_ref()))
+                        .option_layer(quota_layer.clone())
+                        .option_layer(self.namespace_pools.clone())
                         .layer(self.rpc_middleware.clone()),
                 )
-                .build(http_socket_addr)
-                .await
-                .map_err(|err| RpcError::server_error(err, ServerKind::Http(http_socket_addr)))?;
-            let local_addr = server
-                .local_addr()
-                .map_err(|err| RpcError::server_error(err, ServerKind::Http(http_socket_addr)))?;
+                .to_service_builder();
+            let (listener, local_addr) =
+                Self::bind(http_socket_addr, ServerKind::Http(http_socket_addr)).await?;
             http_local_addr = Some(local_addr);
-            http_server = Some(server);
+            http_server = Some((listener, server));
         }
 
-        http_handle = http_server
-            .map(|http_server| http_server.start(modules.http.clone().expect("http server error")));
-        ws_handle = ws_server
-            .map(|ws_server| ws_server.start(modules.ws.clone().expect("ws server error")));
+        http_handle = http_server.map(|(listener, server)| {
+            let methods = Methods::from(modules.http.clone().expect("http server error"));
+            accept::serve(listener, modules.connections.clone(), false, move |stop_handle| {
+                server.clone().build(methods.clone(), stop_handle)
+            })
+        });
+        ws_handle = ws_server.map(|(listener, server)| {
+            let methods = Methods::from(modules.ws.clone().expect("ws server error"));
+            accept::serve(listener, modules.connections.clone(), true, move |stop_handle| {
+                server.clone().build(methods.clone(), stop_handle)
+            })
+        });
         Ok(RpcServerHandle {
             http_local_addr,
             ws_local_addr,
@@ -1637,6 +1874,8 @@ pub struct TransportRpcModules<Context = ()> {
     ws: Option<RpcModule<Context>>,
     /// rpcs module for ipc
     ipc: Option<RpcModule<Context>>,
+    /// The open connections of the servers of the modules
+    connections: RpcConnections,
 }
 
 // === impl TransportRpcModules ===
@@ -1675,6 +1914,11 @@ impl TransportRpcModules {
         &self.config
     }
 
+    /// Returns the registry of the open connections of the http and ws servers of the modules.
+    pub const fn connections(&self) -> &RpcConnections {
+        &self.connections
+    }
+
     /// Merge the given [`Methods`] in all configured transport modules if the given
     /// [`RethRpcModule`] is configured for the transport.
     ///
@@ -2654,7 +2898,7 @@ mod tests {
         let config = TransportRpcModuleConfig::default().with_http([RethRpcModule::Eth]);
 
         let mut modules =
-            TransportRpcModules { config, http: Some(RpcModule::new(())), ws: None, ipc: None };
+            TransportRpcModules { config, http: Some(RpcModule::new(())), ..Default::default() };
 
         // Track whether closure was called
         let mut closure_called = false;
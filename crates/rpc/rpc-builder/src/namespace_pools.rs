@@ -0,0 +1,164 @@
+//! Dedicated worker pools for the blocking work of expensive RPC namespaces.
+//!
+//! Calls of namespaces like `debug_` and `trace_` spawn CPU heavy blocking tasks. By default these
+//! run on the same tokio blocking pool as the `eth_` calls, so a burst of tracing requests can
+//! delay latency sensitive wallet queries. The [`RpcNamespacePools`] layer runs the calls of the
+//! configured namespaces inside the [`BlockingTaskPool::scope`] of a bounded pool of the
+//! namespace, whose threads run with a lower scheduling priority.
+
+use jsonrpsee::{
+    core::middleware::{Batch, Notification},
+    server::middleware::rpc::RpcServiceT,
+    types::Request,
+};
+use reth_rpc_server_types::RethRpcModule;
+use reth_tasks::pool::BlockingTaskPool;
+use std::{future::Future, sync::Arc};
+use tower::Layer;
+
+/// Layer that executes the blocking tasks of the calls of certain namespaces on dedicated
+/// [`BlockingTaskPool`]s.
+///
+/// Batches are executed on the pool of a namespace only if all their calls belong to it.
+#[derive(Debug, Clone, Default)]
+pub struct RpcNamespacePools {
+    /// The method prefix of every namespace, e.g. `debug_`, and its pool.
+    pools: Arc<Vec<(String, BlockingTaskPool)>>,
+}
+
+impl RpcNamespacePools {
+    /// Creates a pool with the given number of worker threads for every namespace.
+    ///
+    /// Namespaces with zero workers keep using the shared blocking pool. On Linux, the worker
+    /// threads run with the given niceness, see `setpriority(2)`.
+    pub fn new(
+        workers: impl IntoIterator<Item = (RethRpcModule, usize)>,
+        niceness: i32,
+    ) -> Result<Self, rayon::ThreadPoolBuildError> {
+        let mut pools = Vec::new();
+        for (module, workers) in workers {
+            if workers == 0 {
+                continue
+            }
+
+            let name = module.as_str().to_string();
+            let pool = BlockingTaskPool::builder()
+                .num_threads(workers)
+                .thread_name(move |idx| format!("rpc-{name}-{idx}"))
+                .start_handler(move |_| lower_thread_priority(niceness))
+                .build()?;
+            pools.push((format!("{}_", module.as_str()), BlockingTaskPool::new(pool)));
+        }
+
+        Ok(Self { pools: Arc::new(pools) })
+    }
+
+    /// Returns `true` if no namespace has a dedicated pool.
+    pub fn is_empty(&self) -> bool {
+        self.pools.is_empty()
+    }
+
+    /// Returns the pool of the namespace of the method, if any.
+    fn pool(&self, method: &str) -> Option<&BlockingTaskPool> {
+        self.pools.iter().find(|(prefix, _)| method.starts_with(prefix)).map(|(_, pool)| pool)
+    }
+}
+
+impl<S> Layer<S> for RpcNamespacePools {
+    type Service = RpcNamespacePoolsService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcNamespacePoolsService { inner, pools: self.clone() }
+    }
+}
+
+/// A [`RpcServiceT`] middleware that executes calls inside the scope of their namespace pool.
+///
+/// Created by [`RpcNamespacePools`].
+#[derive(Debug, Clone)]
+pub struct RpcNamespacePoolsService<S> {
+    inner: S,
+    pools: RpcNamespacePools,
+}
+
+impl<S> RpcServiceT for RpcNamespacePoolsService<S>
+where
+    S: RpcServiceT + Send + Sync + Clone + 'static,
+{
+    type MethodResponse = S::MethodResponse;
+    type NotificationResponse = S::NotificationResponse;
+    type BatchResponse = S::BatchResponse;
+
+    fn call<'a>(&self, req: Request<'a>) -> impl Future<Output = Self::MethodResponse> + Send + 'a {
+        let pool = self.pools.pool(req.method_name()).cloned();
+        let fut = self.inner.call(req);
+
+        async move {
+            match pool {
+                Some(pool) => pool.scope(fut).await,
+                None => fut.await,
+            }
+        }
+    }
+
+    fn batch<'a>(&self, req: Batch<'a>) -> impl Future<Output = Self::BatchResponse> + Send + 'a {
+        let mut pools = req.iter().flatten().map(|entry| self.pools.pool(entry.method_name()));
+        let first = pools.next().flatten();
+        let pool = first
+            .filter(|first| pools.all(|pool| pool.is_some_and(|pool| pool.ptr_eq(first))))
+            .cloned();
+        let fut = self.inner.batch(req);
+
+        async move {
+            match pool {
+                Some(pool) => pool.scope(fut).await,
+                None => fut.await,
+            }
+        }
+    }
+
+    fn notification<'a>(
+        &self,
+        n: Notification<'a>,
+    ) -> impl Future<Output = Self::NotificationResponse> + Send + 'a {
+        self.inner.notification(n)
+    }
+}
+
+/// Lowers the scheduling priority of the current thread to the given niceness.
+#[cfg(target_os = "linux")]
+fn lower_thread_priority(niceness: i32) {
+    // SAFETY: `setpriority` has no memory safety requirements, `0` refers to the calling thread.
+    if unsafe { libc::setpriority(libc::PRIO_PROCESS, 0, niceness) } != 0 {
+        tracing::warn!(target: "rpc", niceness, err = %std::io::Error::last_os_error(), "Failed to lower priority of rpc worker thread");
+    }
+}
+
+/// Lowers the scheduling priority of the current thread to the given niceness.
+///
+/// Thread priorities are only supported on Linux.
+#[cfg(not(target_os = "linux"))]
+fn lower_thread_priority(niceness: i32) {
+    let _ = niceness;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn namespace_pools() {
+        let pools = RpcNamespacePools::new(
+            [(RethRpcModule::Debug, 1), (RethRpcModule::Trace, 1), (RethRpcModule::Admin, 0)],
+            0,
+        )
+        .unwrap();
+
+        let debug = pools.pool("debug_traceTransaction").unwrap();
+        assert!(pools.pool("debug_traceCall").is_some_and(|pool| pool.ptr_eq(debug)));
+        assert!(pools.pool("trace_block").is_some_and(|pool| !pool.ptr_eq(debug)));
+        assert!(pools.pool("admin_peers").is_none());
+        assert!(pools.pool("eth_call").is_none());
+        assert!(pools.pool("debugger_x").is_none());
+    }
+}
@@ -0,0 +1,608 @@
+//! Configurable rate limits and method filters of the http and ws RPC servers.
+//!
+//! The limits are configured with the [`RpcRateLimitConfig`] of the node config, and are enforced
+//! by two layers:
+//!
+//! - [`RpcClientIpLayer`]: an http middleware that resolves the [`ClientIp`] of every request.
+//! - [`RpcQuotaLayer`]: a [`jsonrpsee`] rpc middleware that rejects methods that are not allowed
+//!   and enforces the per-method token buckets, which are shared by all clients, and the per-IP
+//!   token buckets.
+//!
+//! Clients are identified by the [`PeerAddr`] of their connection. The `X-Forwarded-For` and
+//! `X-Real-IP` headers are only used if the peer is one of the configured trusted proxies. The
+//! [`ClientIp`] of the http request is passed on to every call of the request, and to every call
+//! of a ws connection, so ws messages are subject to the same quotas as http requests.
+
+use crate::accept::PeerAddr;
+use http::HeaderMap;
+use jsonrpsee::{
+    core::middleware::{Batch, Notification},
+    server::{middleware::rpc::RpcServiceT, HttpRequest},
+    types::{ErrorObject, Id, Request},
+    MethodResponse,
+};
+use reth_config::{RpcRateLimitConfig, TokenBucketConfig};
+use std::{
+    collections::{BTreeMap, HashMap},
+    future::Future,
+    hash::{BuildHasher, RandomState},
+    net::{IpAddr, Ipv6Addr},
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+use tower::{Layer, Service};
+
+/// Error code of the calls of methods that are not allowed.
+const METHOD_NOT_ALLOWED_CODE: i32 = -32601;
+
+/// Error code of the calls rejected by a rate limit.
+const RATE_LIMITED_CODE: i32 = -32005;
+
+/// Maximum number of tracked client IP addresses, before idle buckets are evicted.
+const MAX_TRACKED_IPS: usize = 100_000;
+
+/// Number of independently locked shards of the per-IP buckets.
+const IP_SHARDS: usize = 64;
+
+/// Minimum time between two evictions of the idle buckets of a shard.
+const IP_EVICTION_INTERVAL: Duration = Duration::from_secs(1);
+
+/// A token bucket that refills at a constant rate up to its burst size.
+#[derive(Debug)]
+struct TokenBucket {
+    config: TokenBucketConfig,
+    tokens: f64,
+    updated_at: Instant,
+}
+
+impl TokenBucket {
+    /// Creates a full bucket.
+    fn new(config: TokenBucketConfig, now: Instant) -> Self {
+        Self { config, tokens: config.burst as f64, updated_at: now }
+    }
+
+    /// Refills the bucket and returns `true` if it holds at least `count` tokens.
+    fn refill(&mut self, now: Instant, count: usize) -> bool {
+        let elapsed = now.saturating_duration_since(self.updated_at).as_secs_f64();
+        self.tokens =
+            (self.tokens + elapsed * self.config.per_second as f64).min(self.config.burst as f64);
+        self.updated_at = now;
+        self.tokens >= count as f64
+    }
+
+    /// Takes `count` tokens, which must have been checked with [`Self::refill`].
+    fn take(&mut self, count: usize) {
+        self.tokens -= count as f64;
+    }
+
+    /// Refills the bucket and takes a token, returns `false` if the bucket is empty.
+    #[cfg(test)]
+    fn try_acquire(&mut self, now: Instant) -> bool {
+        let available = self.refill(now, 1);
+        if available {
+            self.take(1);
+        }
+        available
+    }
+
+    /// Returns `true` if the bucket has been full for at least the given duration.
+    fn is_idle(&self, now: Instant, idle: Duration) -> bool {
+        let elapsed = now.saturating_duration_since(self.updated_at);
+        if elapsed < idle {
+            return false
+        }
+        let refilled = self.tokens + elapsed.as_secs_f64() * self.config.per_second as f64;
+        refilled >= self.config.burst as f64
+    }
+}
+
+/// Returns `true` if the method name matches the pattern.
+///
+/// Patterns ending with `*` match every method with the given prefix.
+fn matches(pattern: &str, method: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => method.starts_with(prefix),
+        None => pattern == method,
+    }
+}
+
+/// The reason a call is rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Rejection {
+    /// The method is not allowed.
+    NotAllowed,
+    /// The rate limit of the method or the client is exceeded.
+    RateLimited,
+}
+
+impl Rejection {
+    /// Returns the error response of a rejected request.
+    fn response(self, id: Id<'_>) -> MethodResponse {
+        let error = match self {
+            Self::NotAllowed => {
+                ErrorObject::owned(METHOD_NOT_ALLOWED_CODE, "method not allowed", None::<()>)
+            }
+            Self::RateLimited => {
+                ErrorObject::owned(RATE_LIMITED_CODE, "rate limit exceeded", None::<()>)
+            }
+        };
+        MethodResponse::error(id, error)
+    }
+}
+
+/// A shard of the per-IP buckets.
+#[derive(Debug)]
+struct IpShard {
+    buckets: HashMap<IpAddr, TokenBucket>,
+    evicted_at: Option<Instant>,
+}
+
+/// The per-IP buckets, sharded by client so clients don't contend on a single lock and evicting
+/// idle clients only walks a single shard.
+#[derive(Debug)]
+struct IpQuotas {
+    config: TokenBucketConfig,
+    hasher: RandomState,
+    shards: Box<[Mutex<IpShard>]>,
+}
+
+impl IpQuotas {
+    fn new(config: TokenBucketConfig) -> Self {
+        Self {
+            config,
+            hasher: RandomState::new(),
+            shards: (0..IP_SHARDS)
+                .map(|_| Mutex::new(IpShard { buckets: HashMap::new(), evicted_at: None }))
+                .collect(),
+        }
+    }
+
+    /// Locks the shard of the client.
+    fn shard(&self, ip: IpAddr) -> MutexGuard<'_, IpShard> {
+        let index = self.hasher.hash_one(ip) as usize % self.shards.len();
+        self.shards[index].lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns the bucket of the client, or `None` if its shard is full of active clients.
+    fn bucket<'a>(
+        &self,
+        shard: &'a mut IpShard,
+        ip: IpAddr,
+        now: Instant,
+    ) -> Option<&'a mut TokenBucket> {
+        let capacity = MAX_TRACKED_IPS / IP_SHARDS;
+        if shard.buckets.len() >= capacity && !shard.buckets.contains_key(&ip) {
+            let evict = shard
+                .evicted_at
+                .is_none_or(|at| now.saturating_duration_since(at) >= IP_EVICTION_INTERVAL);
+            if evict {
+                // forget the clients whose bucket refilled, they start again with a full bucket
+                shard.buckets.retain(|_, bucket| !bucket.is_idle(now, Duration::from_secs(1)));
+                shard.evicted_at = Some(now);
+            }
+            if shard.buckets.len() >= capacity {
+                return None
+            }
+        }
+        Some(shard.buckets.entry(ip).or_insert_with(|| TokenBucket::new(self.config, now)))
+    }
+}
+
+/// Shared state of the [`RpcQuotaLayer`].
+#[derive(Debug)]
+struct Quotas {
+    allow_methods: Vec<String>,
+    deny_methods: Vec<String>,
+    /// The patterns of the per-method buckets, sorted by precedence: exact names first, then
+    /// prefixes from the longest to the shortest.
+    patterns: Vec<String>,
+    /// The bucket of every pattern, in the same order as `patterns`.
+    buckets: Vec<Mutex<TokenBucket>>,
+    /// The per-IP buckets, if configured.
+    per_ip: Option<IpQuotas>,
+}
+
+impl Quotas {
+    fn new(config: &RpcRateLimitConfig) -> Self {
+        let now = Instant::now();
+        let mut methods = config.methods.iter().collect::<Vec<_>>();
+        methods.sort_by_key(|(pattern, _)| {
+            let is_prefix = pattern.ends_with('*');
+            (is_prefix, std::cmp::Reverse(pattern.len()))
+        });
+
+        Self {
+            allow_methods: config.allow_methods.clone(),
+            deny_methods: config.deny_methods.clone(),
+            patterns: methods.iter().map(|(pattern, _)| (*pattern).clone()).collect(),
+            buckets: methods
+                .iter()
+                .map(|(_, bucket)| Mutex::new(TokenBucket::new(**bucket, now)))
+                .collect(),
+            per_ip: config.per_ip.map(IpQuotas::new),
+        }
+    }
+
+    /// Returns `true` if the method can be called.
+    fn is_allowed(&self, method: &str) -> bool {
+        (self.allow_methods.is_empty() ||
+            self.allow_methods.iter().any(|pattern| matches(pattern, method))) &&
+            !self.deny_methods.iter().any(|pattern| matches(pattern, method))
+    }
+
+    /// Returns the index of the bucket of the method, if it is rate limited.
+    fn bucket_index(&self, method: &str) -> Option<usize> {
+        self.patterns.iter().position(|pattern| matches(pattern, method))
+    }
+
+    /// Checks whether a call of the method by the client is accepted, taking a token of the
+    /// buckets of the method and the client.
+    #[cfg(test)]
+    fn check(&self, client: Option<ClientIp>, method: &str) -> Result<(), Rejection> {
+        self.check_all(client, [method])
+    }
+
+    /// Checks whether all calls of the methods by the client are accepted.
+    ///
+    /// Tokens are only taken if all calls are accepted, so a rejected batch doesn't consume the
+    /// quota of its accepted calls.
+    fn check_all<'a>(
+        &self,
+        client: Option<ClientIp>,
+        methods: impl IntoIterator<Item = &'a str>,
+    ) -> Result<(), Rejection> {
+        let mut calls = 0;
+        // the number of tokens taken from every method bucket, ordered by bucket index so the
+        // buckets are always locked in the same order
+        let mut counts = BTreeMap::<usize, usize>::new();
+        for method in methods {
+            if !self.is_allowed(method) {
+                return Err(Rejection::NotAllowed)
+            }
+            calls += 1;
+            if let Some(index) = self.bucket_index(method) {
+                *counts.entry(index).or_default() += 1;
+            }
+        }
+
+        let now = Instant::now();
+        let mut buckets = Vec::with_capacity(counts.len());
+        for (index, count) in counts {
+            let mut bucket = self.buckets[index].lock().unwrap_or_else(PoisonError::into_inner);
+            if !bucket.refill(now, count) {
+                return Err(Rejection::RateLimited)
+            }
+            buckets.push((bucket, count));
+        }
+
+        if let (Some(per_ip), Some(ClientIp(ip))) = (&self.per_ip, client) {
+            let mut shard = per_ip.shard(ip);
+            let bucket = per_ip.bucket(&mut shard, ip, now).ok_or(Rejection::RateLimited)?;
+            if !bucket.refill(now, calls) {
+                return Err(Rejection::RateLimited)
+            }
+            bucket.take(calls);
+        }
+
+        for (mut bucket, count) in buckets {
+            bucket.take(count);
+        }
+        Ok(())
+    }
+}
+
+/// Layer that rejects the calls of methods that are not allowed, or that exceeded the rate limit
+/// of their method or client.
+///
+/// The buckets are shared by all connections of the servers the layer is installed on. Clients
+/// are identified by the [`ClientIp`] of their requests, see [`RpcClientIpLayer`].
+#[derive(Debug, Clone)]
+pub struct RpcQuotaLayer {
+    quotas: Arc<Quotas>,
+}
+
+impl RpcQuotaLayer {
+    /// Creates a new layer enforcing the method filters, per-method and per-IP limits of the
+    /// config.
+    pub fn new(config: &RpcRateLimitConfig) -> Self {
+        Self { quotas: Arc::new(Quotas::new(config)) }
+    }
+}
+
+impl<S> Layer<S> for RpcQuotaLayer {
+    type Service = RpcQuotaService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcQuotaService { inner, quotas: self.quotas.clone() }
+    }
+}
+
+/// A [`RpcServiceT`] middleware that enforces the method filters and rate limits.
+///
+/// Created by [`RpcQuotaLayer`].
+#[derive(Debug, Clone)]
+pub struct RpcQuotaService<S> {
+    inner: S,
+    quotas: Arc<Quotas>,
+}
+
+impl<S> RpcServiceT for RpcQuotaService<S>
+where
+    S: RpcServiceT<MethodResponse = MethodResponse, BatchResponse = MethodResponse>
+        + Send
+        + Sync
+        + Clone
+        + 'static,
+{
+    type MethodResponse = S::MethodResponse;
+    type NotificationResponse = S::NotificationResponse;
+    type BatchResponse = S::BatchResponse;
+
+    fn call<'a>(&self, req: Request<'a>) -> impl Future<Output = Self::MethodResponse> + Send + 'a {
+        let inner = self.inner.clone();
+        let client = req.extensions().get::<ClientIp>().copied();
+        let rejection = self.quotas.check_all(client, [req.method_name()]).err();
+
+        async move {
+            if let Some(rejection) = rejection {
+                return rejection.response(req.id)
+            }
+            inner.call(req).await
+        }
+    }
+
+    fn batch<'a>(&self, req: Batch<'a>) -> impl Future<Output = Self::BatchResponse> + Send + 'a {
+        let inner = self.inner.clone();
+        let client =
+            req.iter().flatten().find_map(|entry| entry.extensions().get::<ClientIp>().copied());
+        // the whole batch is rejected if any of its calls is rejected
+        let rejection = self
+            .quotas
+            .check_all(client, req.iter().flatten().map(|entry| entry.method_name()))
+            .err();
+
+        async move {
+            if let Some(rejection) = rejection {
+                return rejection.response(Id::Null)
+            }
+            inner.batch(req).await
+        }
+    }
+
+    fn notification<'a>(
+        &self,
+        n: Notification<'a>,
+    ) -> impl Future<Output = Self::NotificationResponse> + Send + 'a {
+        self.inner.notification(n)
+    }
+}
+
+/// The IP address of the client a request is attributed to by the rate limits.
+///
+/// Inserted into the extensions of every request by the [`RpcClientIpLayer`]. IPv6 clients are
+/// identified by their /64 prefix, which is usually assigned to a single host.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ClientIp(pub IpAddr);
+
+impl ClientIp {
+    fn new(ip: IpAddr) -> Self {
+        match ip.to_canonical() {
+            IpAddr::V6(ip) => {
+                let prefix = u128::from(ip) & !(u128::MAX >> 64);
+                Self(IpAddr::V6(Ipv6Addr::from(prefix)))
+            }
+            ip => Self(ip),
+        }
+    }
+}
+
+/// Returns the IP address of the client of a request received from the peer.
+///
+/// The forwarding headers are only used if the peer is a trusted proxy. The `X-Forwarded-For`
+/// header is read from the right, skipping trusted proxies, because only the entries appended by
+/// trusted proxies can't be set by the client.
+fn client_ip(peer: IpAddr, headers: &HeaderMap, trusted_proxies: &[IpAddr]) -> IpAddr {
+    let is_trusted = |ip: &IpAddr| trusted_proxies.contains(&ip.to_canonical());
+    if !is_trusted(&peer) {
+        return peer
+    }
+
+    let parse = |value: &str| value.trim().parse::<IpAddr>().ok();
+    let forwarded_for = headers
+        .get_all("x-forwarded-for")
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .collect::<Vec<_>>()
+        .join(",");
+    if !forwarded_for.is_empty() {
+        let mut client = peer;
+        for entry in forwarded_for.split(',').rev() {
+            match parse(entry) {
+                Some(ip) if is_trusted(&ip) => client = ip,
+                Some(ip) => return ip,
+                // entries left of an unparsable one can't be trusted
+                None => break,
+            }
+        }
+        return client
+    }
+
+    headers.get("x-real-ip").and_then(|value| value.to_str().ok()).and_then(parse).unwrap_or(peer)
+}
+
+/// Layer that resolves the [`ClientIp`] of every http request, including ws upgrades.
+///
+/// The client is the [`PeerAddr`] of the connection, or the client reported by the forwarding
+/// headers if the peer is one of the trusted proxies.
+#[derive(Debug, Clone)]
+pub struct RpcClientIpLayer {
+    trusted_proxies: Arc<[IpAddr]>,
+}
+
+impl RpcClientIpLayer {
+    /// Creates a new layer trusting the forwarding headers of the given proxies.
+    pub fn new(trusted_proxies: &[IpAddr]) -> Self {
+        Self { trusted_proxies: trusted_proxies.iter().map(IpAddr::to_canonical).collect() }
+    }
+}
+
+impl<S> Layer<S> for RpcClientIpLayer {
+    type Service = RpcClientIpService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcClientIpService { trusted_proxies: self.trusted_proxies.clone(), inner }
+    }
+}
+
+/// Service that inserts the [`ClientIp`] into every request.
+///
+/// Created by [`RpcClientIpLayer`].
+#[derive(Debug, Clone)]
+pub struct RpcClientIpService<S> {
+    trusted_proxies: Arc<[IpAddr]>,
+    inner: S,
+}
+
+impl<S> Service<HttpRequest> for RpcClientIpService<S>
+where
+    S: Service<HttpRequest>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: HttpRequest) -> Self::Future {
+        if let Some(PeerAddr(peer)) = req.extensions().get::<PeerAddr>().copied() {
+            let ip = client_ip(peer.ip().to_canonical(), req.headers(), &self.trusted_proxies);
+            req.extensions_mut().insert(ClientIp::new(ip));
+        }
+        self.inner.call(req)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::HeaderValue;
+
+    const fn bucket(per_second: u32, burst: u32) -> TokenBucketConfig {
+        TokenBucketConfig { per_second, burst }
+    }
+
+    #[test]
+    fn token_bucket_refills_up_to_burst() {
+        let now = Instant::now();
+        let mut bucket = TokenBucket::new(bucket(2, 2), now);
+        assert!(bucket.try_acquire(now));
+        assert!(bucket.try_acquire(now));
+        assert!(!bucket.try_acquire(now));
+
+        let later = now + Duration::from_millis(500);
+        assert!(bucket.try_acquire(later));
+        assert!(!bucket.try_acquire(later));
+
+        let much_later = later + Duration::from_secs(10);
+        assert!(bucket.is_idle(much_later, Duration::from_secs(1)));
+        assert!(bucket.try_acquire(much_later));
+        assert!(bucket.try_acquire(much_later));
+        assert!(!bucket.try_acquire(much_later));
+    }
+
+    #[test]
+    fn method_quotas() {
+        let config = RpcRateLimitConfig {
+            allow_methods: vec!["eth_*".to_string(), "debug_*".to_string()],
+            deny_methods: vec!["debug_setHead".to_string()],
+            methods: BTreeMap::from([
+                ("debug_*".to_string(), bucket(0, 1)),
+                ("debug_trace*".to_string(), bucket(0, 2)),
+                ("debug_traceCall".to_string(), bucket(0, 0)),
+            ]),
+            ..Default::default()
+        };
+        let quotas = Quotas::new(&config);
+
+        assert_eq!(quotas.check(None, "admin_peers"), Err(Rejection::NotAllowed));
+        assert_eq!(quotas.check(None, "debug_setHead"), Err(Rejection::NotAllowed));
+        assert_eq!(quotas.check(None, "eth_call"), Ok(()));
+
+        // exact name before the longest prefix
+        assert_eq!(quotas.check(None, "debug_traceCall"), Err(Rejection::RateLimited));
+        assert_eq!(quotas.check(None, "debug_traceTransaction"), Ok(()));
+        assert_eq!(quotas.check(None, "debug_traceBlock"), Ok(()));
+        assert_eq!(quotas.check(None, "debug_traceBlock"), Err(Rejection::RateLimited));
+        assert_eq!(quotas.check(None, "debug_getRawBlock"), Ok(()));
+        assert_eq!(quotas.check(None, "debug_getRawBlock"), Err(Rejection::RateLimited));
+    }
+
+    #[test]
+    fn ip_quotas() {
+        let config = RpcRateLimitConfig { per_ip: Some(bucket(0, 2)), ..Default::default() };
+        let quotas = Quotas::new(&config);
+        let first = Some(ClientIp::new("10.0.0.1".parse().unwrap()));
+        let second = Some(ClientIp::new("10.0.0.2".parse().unwrap()));
+
+        assert_eq!(quotas.check(first, "eth_call"), Ok(()));
+        assert_eq!(quotas.check(first, "eth_call"), Ok(()));
+        assert_eq!(quotas.check(first, "eth_call"), Err(Rejection::RateLimited));
+        assert_eq!(quotas.check(second, "eth_call"), Ok(()));
+
+        // all addresses of an IPv6 /64 share a bucket
+        let third = Some(ClientIp::new("2001:db8::1".parse().unwrap()));
+        let fourth = Some(ClientIp::new("2001:db8::2".parse().unwrap()));
+        assert_eq!(third, fourth);
+        assert_eq!(quotas.check(third, "eth_call"), Ok(()));
+        assert_eq!(quotas.check(fourth, "eth_call"), Ok(()));
+        assert_eq!(quotas.check(third, "eth_call"), Err(Rejection::RateLimited));
+    }
+
+    #[test]
+    fn rejected_batches_take_no_tokens() {
+        let config = RpcRateLimitConfig {
+            methods: BTreeMap::from([("eth_call".to_string(), bucket(0, 2))]),
+            per_ip: Some(bucket(0, 3)),
+            ..Default::default()
+        };
+        let quotas = Quotas::new(&config);
+        let client = Some(ClientIp::new("10.0.0.1".parse().unwrap()));
+
+        // the batch exceeds the bucket of eth_call, so no token of the client is taken
+        assert_eq!(
+            quotas.check_all(client, ["eth_call", "eth_call", "eth_call"]),
+            Err(Rejection::RateLimited)
+        );
+        assert_eq!(quotas.check_all(client, ["eth_call", "eth_call", "eth_chainId"]), Ok(()));
+        assert_eq!(quotas.check(client, "eth_chainId"), Err(Rejection::RateLimited));
+    }
+
+    #[test]
+    fn client_ip_from_peer() {
+        let peer: IpAddr = "10.0.0.9".parse().unwrap();
+        let proxy: IpAddr = "10.0.0.100".parse().unwrap();
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-real-ip", HeaderValue::from_static("10.0.0.2"));
+        headers.insert("x-forwarded-for", HeaderValue::from_static("10.0.0.1, 10.0.0.3"));
+
+        // headers of untrusted peers are ignored
+        assert_eq!(client_ip(peer, &headers, &[]), peer);
+        assert_eq!(client_ip(peer, &headers, &[proxy]), peer);
+
+        // the rightmost address that is not a trusted proxy is the client
+        assert_eq!(client_ip(proxy, &headers, &[proxy]), "10.0.0.3".parse::<IpAddr>().unwrap());
+        headers.insert("x-forwarded-for", HeaderValue::from_static("10.0.0.1, 10.0.0.100"));
+        assert_eq!(client_ip(proxy, &headers, &[proxy]), "10.0.0.1".parse::<IpAddr>().unwrap());
+        headers.insert("x-forwarded-for", HeaderValue::from_static("garbage, 10.0.0.100"));
+        assert_eq!(client_ip(proxy, &headers, &[proxy]), proxy);
+
+        headers.remove("x-forwarded-for");
+        assert_eq!(client_ip(proxy, &headers, &[proxy]), "10.0.0.2".parse::<IpAddr>().unwrap());
+        headers.remove("x-real-ip");
+        assert_eq!(client_ip(proxy, &headers, &[proxy]), proxy);
+    }
+}
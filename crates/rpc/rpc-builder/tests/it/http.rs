@@ -1,7 +1,7 @@
 #![allow(unreachable_pub)]
 //! Standalone http tests
 
-use crate::utils::{launch_http, launch_http_ws, launch_ws};
+use crate::utils::{launch_http, launch_http_ws, launch_http_ws_same_port, launch_ws};
 use alloy_eips::{eip1898::LenientBlockNumberOrTag, BlockId, BlockNumberOrTag};
 use alloy_primitives::{hex_literal::hex, Address, Bytes, TxHash, B256, B64, U256, U64};
 use alloy_rpc_types_eth::{
@@ -28,7 +28,7 @@ use reth_rpc_api::{
 use reth_rpc_server_types::RethRpcModule;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashSet;
+use std::{collections::HashSet, time::Duration};
 
 fn is_unimplemented(err: jsonrpsee::core::client::Error) -> bool {
     match err {
@@ -642,6 +642,25 @@ async fn test_call_admin_functions_http_and_ws() {
     test_basic_admin_calls(&client).await;
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_terminate_ws_rpc_connection_same_port() {
+    reth_tracing::init_test_tracing();
+
+    let handle = launch_http_ws_same_port(vec![RethRpcModule::Admin]).await;
+    let ws_client = handle.ws_client().await.unwrap();
+    let http_client = handle.http_client().unwrap();
+
+    // the ws connection is upgraded from an http connection of the same server
+    let connections = AdminApiClient::rpc_connections(&http_client).await.unwrap();
+    let ws = connections.iter().find(|connection| connection.transport == "ws").unwrap();
+    assert!(ws.remote_addr.ip().is_loopback());
+    assert!(connections.iter().any(|connection| connection.transport == "http"));
+
+    assert!(AdminApiClient::terminate_rpc_connection(&http_client, ws.id).await.unwrap());
+    tokio::time::timeout(Duration::from_secs(5), ws_client.on_disconnect()).await.unwrap();
+    assert!(!AdminApiClient::terminate_rpc_connection(&http_client, u64::MAX).await.unwrap());
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_call_eth_functions_http() {
     reth_tracing::init_test_tracing();
@@ -1,6 +1,7 @@
 //! Engine API capabilities.
 
-use std::collections::HashSet;
+use parking_lot::RwLock;
+use std::{collections::HashSet, sync::Arc};
 use tracing::warn;
 
 /// Critical Engine API method prefixes that warrant warnings on capability mismatches.
@@ -34,26 +35,64 @@ pub const CAPABILITIES: &[&str] = &[
     "engine_getBlobsV3",
 ];
 
-/// Engine API capabilities set.
+/// Method excluded from the advertised capabilities.
+///
+/// The spec requires that `engine_exchangeCapabilities` is not part of the response.
+const EXCHANGE_CAPABILITIES_METHOD: &str = "engine_exchangeCapabilities";
+
+/// Engine API capabilities registry.
+///
+/// Clones share the same set, so capabilities registered after the engine API was created, e.g.
+/// experimental `engine_` methods that custom engine types merge into the auth server, are
+/// advertised by `engine_exchangeCapabilities` as well.
 #[derive(Debug, Clone)]
 pub struct EngineCapabilities {
-    inner: HashSet<String>,
+    inner: Arc<RwLock<HashSet<String>>>,
 }
 
 impl EngineCapabilities {
     /// Creates from an iterator of capability strings.
     pub fn new(capabilities: impl IntoIterator<Item = impl Into<String>>) -> Self {
-        Self { inner: capabilities.into_iter().map(Into::into).collect() }
+        Self { inner: Arc::new(RwLock::new(capabilities.into_iter().map(Into::into).collect())) }
+    }
+
+    /// Registers a capability, returns `false` if it was already registered.
+    pub fn register(&self, capability: impl Into<String>) -> bool {
+        self.inner.write().insert(capability.into())
+    }
+
+    /// Registers all `engine_` methods of the given method names as capabilities.
+    ///
+    /// Non-engine methods and `engine_exchangeCapabilities` are ignored.
+    pub fn register_engine_methods<'a>(&self, methods: impl IntoIterator<Item = &'a str>) {
+        let mut inner = self.inner.write();
+        for method in methods {
+            if method.starts_with("engine_") && method != EXCHANGE_CAPABILITIES_METHOD {
+                inner.insert(method.to_string());
+            }
+        }
     }
 
-    /// Returns the capabilities as a list of strings.
+    /// Removes a capability, returns `false` if it wasn't registered.
+    pub fn unregister(&self, capability: &str) -> bool {
+        self.inner.write().remove(capability)
+    }
+
+    /// Returns `true` if the capability is registered.
+    pub fn contains(&self, capability: &str) -> bool {
+        self.inner.read().contains(capability)
+    }
+
+    /// Returns the capabilities as a sorted list of strings.
     pub fn list(&self) -> Vec<String> {
-        self.inner.iter().cloned().collect()
+        let mut list: Vec<_> = self.inner.read().iter().cloned().collect();
+        list.sort_unstable();
+        list
     }
 
-    /// Returns a reference to the inner set.
-    pub const fn as_set(&self) -> &HashSet<String> {
-        &self.inner
+    /// Returns a snapshot of the registered capabilities.
+    pub fn as_set(&self) -> HashSet<String> {
+        self.inner.read().clone()
     }
 
     /// Compares CL capabilities with this EL's capabilities and returns any mismatches.
@@ -63,17 +102,16 @@ impl EngineCapabilities {
     pub fn get_capability_mismatches(&self, cl_capabilities: &[String]) -> CapabilityMismatches {
         let cl_set: HashSet<&str> = cl_capabilities.iter().map(String::as_str).collect();
 
+        let inner = self.inner.read();
+
         // CL has methods EL doesn't support
-        let mut missing_in_el: Vec<_> = cl_capabilities
-            .iter()
-            .filter(|cap| !self.inner.contains(cap.as_str()))
-            .cloned()
-            .collect();
+        let mut missing_in_el: Vec<_> =
+            cl_capabilities.iter().filter(|cap| !inner.contains(cap.as_str())).cloned().collect();
         missing_in_el.sort();
 
         // EL has methods CL doesn't support
         let mut missing_in_cl: Vec<_> =
-            self.inner.iter().filter(|cap| !cl_set.contains(cap.as_str())).cloned().collect();
+            inner.iter().filter(|cap| !cl_set.contains(cap.as_str())).cloned().collect();
         missing_in_cl.sort();
 
         CapabilityMismatches { missing_in_el, missing_in_cl }
@@ -202,6 +240,27 @@ mod tests {
         assert_eq!(result.missing_in_cl, vec!["a_method", "z_method"]);
     }
 
+    #[test]
+    fn test_register_engine_methods() {
+        let el = EngineCapabilities::new(["engine_newPayloadV1"]);
+        let shared = el.clone();
+
+        shared.register_engine_methods([
+            "engine_newPayloadV1",
+            "engine_customMethodV1",
+            "engine_exchangeCapabilities",
+            "eth_chainId",
+        ]);
+        assert_eq!(el.list(), vec!["engine_customMethodV1", "engine_newPayloadV1"]);
+
+        assert!(el.register("engine_otherMethodV1"));
+        assert!(!el.register("engine_otherMethodV1"));
+        assert!(shared.contains("engine_otherMethodV1"));
+
+        assert!(shared.unregister("engine_customMethodV1"));
+        assert!(!el.contains("engine_customMethodV1"));
+    }
+
     #[test]
     fn test_is_critical_method() {
         assert!(is_critical_method("engine_forkchoiceUpdatedV1"));
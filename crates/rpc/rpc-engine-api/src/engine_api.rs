@@ -1380,6 +1380,24 @@ mod tests {
         assert_matches!(handle.from_api.recv().await, Some(BeaconEngineMessage::NewPayload { .. }));
     }
 
+    #[tokio::test]
+    async fn new_payload_v4_rejects_requests_hash() {
+        let (_, api) = setup_engine_api();
+
+        let payload = ExecutionPayloadV3::from_block_slow(&Block::default());
+        let res = EngineApiServer::new_payload_v4(
+            &api,
+            payload,
+            Vec::new(),
+            B256::ZERO,
+            RequestsOrHash::Hash(B256::ZERO),
+        )
+        .await;
+        let expected: jsonrpsee_types::ErrorObject<'static> =
+            EngineApiError::UnexpectedRequestsHash.into();
+        assert_eq!(res.unwrap_err(), expected);
+    }
+
     #[derive(Clone)]
     struct TestNetworkInfo {
         syncing: bool,
@@ -9,7 +9,9 @@ use reth_rpc_convert::{transaction::ConvertReceiptInput, RpcConvert};
 use reth_rpc_eth_types::{
     error::FromEthApiError, utils::calculate_gas_used_and_next_log_index, EthApiError,
 };
-use reth_storage_api::{ProviderReceipt, ProviderTx};
+use reth_storage_api::{
+    ProviderReceipt, ProviderTx, ReceiptBlockOffsets, ReceiptProvider, TransactionsProvider,
+};
 
 /// Assembles transaction receipt data w.r.t to network.
 ///
@@ -25,17 +27,34 @@ pub trait LoadReceipt:
         receipt: ProviderReceipt<Self::Provider>,
     ) -> impl Future<Output = Result<RpcReceipt<Self::NetworkTypes>, Self::Error>> + Send {
         async move {
-            let hash = meta.block_hash;
-            // get all receipts for the block
-            let all_receipts = self
-                .cache()
-                .get_receipts(hash)
-                .await
-                .map_err(Self::Error::from_eth_err)?
-                .ok_or(EthApiError::HeaderNotFound(hash.into()))?;
+            let offsets = if meta.index == 0 {
+                Some(ReceiptBlockOffsets::default())
+            } else {
+                // receipts in finished static files have their offsets within the block indexed
+                let provider = self.provider();
+                match provider.transaction_id(meta.tx_hash).map_err(Self::Error::from_eth_err)? {
+                    Some(id) => {
+                        provider.receipt_block_offsets(id).map_err(Self::Error::from_eth_err)?
+                    }
+                    None => None,
+                }
+            };
+
+            let (gas_used, next_log_index) = match offsets {
+                Some(offsets) => (offsets.gas_used, offsets.log_index as usize),
+                None => {
+                    let hash = meta.block_hash;
+                    // get all receipts for the block
+                    let all_receipts = self
+                        .cache()
+                        .get_receipts(hash)
+                        .await
+                        .map_err(Self::Error::from_eth_err)?
+                        .ok_or(EthApiError::HeaderNotFound(hash.into()))?;
 
-            let (gas_used, next_log_index) =
-                calculate_gas_used_and_next_log_index(meta.index, &all_receipts);
+                    calculate_gas_used_and_next_log_index(meta.index, &all_receipts)
+                }
+            };
 
             Ok(self
                 .converter()
@@ -3,6 +3,7 @@
 use alloy_primitives::{U256, U64};
 use alloy_rpc_types_eth::{Stage, SyncInfo, SyncStatus};
 use futures::Future;
+use reth_chain_state::SyncProgressProvider;
 use reth_chainspec::ChainInfo;
 use reth_errors::{RethError, RethResult};
 use reth_network_api::NetworkInfo;
@@ -45,8 +46,13 @@ pub trait EthApiSpec: RpcNodeCore + EthApiTypes {
     /// Returns the [`SyncStatus`] of the network
     fn sync_status(&self) -> RethResult<SyncStatus> {
         let status = if self.is_syncing() {
-            let current_block = U256::from(
-                self.provider().chain_info().map(|info| info.best_number).unwrap_or_default(),
+            let best_number =
+                self.provider().chain_info().map(|info| info.best_number).unwrap_or_default();
+            let current_block = U256::from(best_number);
+            // the pipeline target is the best known estimate of the highest block while
+            // backfilling
+            let highest_block = U256::from(
+                self.provider().sync_progress().target().unwrap_or_default().max(best_number),
             );
 
             let stages = self
@@ -60,7 +66,7 @@ pub trait EthApiSpec: RpcNodeCore + EthApiTypes {
             SyncStatus::Info(Box::new(SyncInfo {
                 starting_block: self.starting_block(),
                 current_block,
-                highest_block: current_block,
+                highest_block,
                 warp_chunks_amount: None,
                 warp_chunks_processed: None,
                 stages: Some(stages),
@@ -18,7 +18,8 @@ use reth_rpc_server_types::result::{
 };
 use reth_transaction_pool::error::{
     Eip4844PoolTransactionError, Eip7702PoolTransactionError, InvalidPoolTransactionError,
-    PoolError, PoolErrorKind, PoolTransactionError,
+    PoolError, PoolErrorKind, PoolTransactionError, ReplacementUnderpricedError,
+    SystemRequestPoolTransactionError,
 };
 use revm::{
     context_interface::result::{
@@ -159,6 +160,10 @@ pub enum EthApiError {
     /// Error thrown when a (tracing) call exceeds the configured timeout
     #[error("execution aborted (timeout = {0:?})")]
     ExecutionTimedOut(Duration),
+    /// Error thrown when the database read transaction serving the request has been timed out for
+    /// being open for too long. The request can be retried.
+    #[error("database read transaction timed out, retry the request")]
+    ReadTransactionTimeout,
     /// Internal Error thrown by the javascript tracer
     #[error("{0}")]
     InternalJsTracerError(String),
@@ -324,6 +329,10 @@ impl From<EthApiError> for jsonrpsee_types::error::ErrorObject<'static> {
                 jsonrpsee_types::error::CALL_EXECUTION_FAILED_CODE,
                 err.to_string(),
             ),
+            // EIP-1474 "Resource unavailable"
+            err @ EthApiError::ReadTransactionTimeout => {
+                rpc_error_with_code(-32002, err.to_string())
+            }
             err @ (EthApiError::InternalBlockingTaskError | EthApiError::InternalEthError) => {
                 internal_rpc_err(err.to_string())
             }
@@ -508,6 +517,7 @@ impl From<reth_errors::ProviderError> for EthApiError {
             ProviderError::FinalizedBlockNotFound => Self::HeaderNotFound(BlockId::finalized()),
             ProviderError::SafeBlockNotFound => Self::HeaderNotFound(BlockId::safe()),
             ProviderError::BlockExpired { .. } => Self::PrunedHistoryUnavailable,
+            err if err.is_read_transaction_timeout() => Self::ReadTransactionTimeout,
             err => Self::Internal(err.into()),
         }
     }
@@ -957,8 +967,17 @@ pub enum RpcPoolError {
     #[error("txpool is full")]
     TxPoolOverflow,
     /// When the replacement transaction is underpriced
-    #[error("replacement transaction underpriced")]
-    ReplaceUnderpriced,
+    ///
+    /// The message starts with the same `replacement transaction underpriced` as geth's, followed
+    /// by the fee that isn't bumped enough.
+    #[error("replacement transaction underpriced: {0}")]
+    ReplaceUnderpriced(ReplacementUnderpricedError),
+    /// When the sender exceeded the limit of replacements per block
+    #[error("replacement limit of {limit} per block exceeded")]
+    ReplacementLimitExceeded {
+        /// The configured max number of replacements per sender per block.
+        limit: usize,
+    },
     /// When the transaction exceeds the block gas limit
     #[error("exceeds block gas limit")]
     ExceedsGasLimit,
@@ -1000,6 +1019,9 @@ pub enum RpcPoolError {
     /// EIP-7702 related error
     #[error(transparent)]
     Eip7702(#[from] Eip7702PoolTransactionError),
+    /// EIP-7002 and EIP-7251 request related error
+    #[error(transparent)]
+    SystemRequest(#[from] SystemRequestPoolTransactionError),
     /// Thrown if a conflicting transaction type is already in the pool
     ///
     /// In other words, thrown if a transaction with the same sender that violates the exclusivity
@@ -1015,13 +1037,13 @@ impl From<RpcPoolError> for jsonrpsee_types::error::ErrorObject<'static> {
     fn from(error: RpcPoolError) -> Self {
         match error {
             RpcPoolError::Invalid(err) => err.into(),
-            RpcPoolError::TxPoolOverflow => {
+            RpcPoolError::TxPoolOverflow | RpcPoolError::ReplacementLimitExceeded { .. } => {
                 rpc_error_with_code(EthRpcErrorCode::TransactionRejected.code(), error.to_string())
             }
             RpcPoolError::AlreadyKnown |
             RpcPoolError::InvalidSender |
             RpcPoolError::Underpriced |
-            RpcPoolError::ReplaceUnderpriced |
+            RpcPoolError::ReplaceUnderpriced(_) |
             RpcPoolError::ExceedsGasLimit |
             RpcPoolError::MaxTxGasLimitExceeded |
             RpcPoolError::ExceedsFeeCap { .. } |
@@ -1031,6 +1053,7 @@ impl From<RpcPoolError> for jsonrpsee_types::error::ErrorObject<'static> {
             RpcPoolError::PoolTransactionError(_) |
             RpcPoolError::Eip4844(_) |
             RpcPoolError::Eip7702(_) |
+            RpcPoolError::SystemRequest(_) |
             RpcPoolError::AddressAlreadyReserved => {
                 rpc_error_with_code(EthRpcErrorCode::InvalidInput.code(), error.to_string())
             }
@@ -1042,7 +1065,10 @@ impl From<RpcPoolError> for jsonrpsee_types::error::ErrorObject<'static> {
 impl From<PoolError> for RpcPoolError {
     fn from(err: PoolError) -> Self {
         match err.kind {
-            PoolErrorKind::ReplacementUnderpriced => Self::ReplaceUnderpriced,
+            PoolErrorKind::ReplacementUnderpriced(reason) => Self::ReplaceUnderpriced(reason),
+            PoolErrorKind::ReplacementLimitExceeded(_, limit) => {
+                Self::ReplacementLimitExceeded { limit }
+            }
             PoolErrorKind::FeeCapBelowMinimumProtocolFeeCap(_) => Self::Underpriced,
             PoolErrorKind::SpammerExceededCapacity(_) | PoolErrorKind::DiscardedOnInsert => {
                 Self::TxPoolOverflow
@@ -1080,6 +1106,7 @@ impl From<InvalidPoolTransactionError> for RpcPoolError {
             InvalidPoolTransactionError::Other(err) => Self::PoolTransactionError(err),
             InvalidPoolTransactionError::Eip4844(err) => Self::Eip4844(err),
             InvalidPoolTransactionError::Eip7702(err) => Self::Eip7702(err),
+            InvalidPoolTransactionError::SystemRequest(err) => Self::SystemRequest(err),
             InvalidPoolTransactionError::Overdraft { cost, balance } => {
                 Self::Invalid(RpcInvalidTransactionError::InsufficientFunds { cost, balance })
             }
@@ -1122,6 +1149,7 @@ pub enum SignError {
 mod tests {
     use super::*;
     use alloy_sol_types::{Revert, SolError};
+    use reth_storage_api::errors::db::DatabaseErrorInfo;
     use revm::primitives::b256;
 
     #[test]
@@ -1130,6 +1158,29 @@ mod tests {
         assert_eq!(err.to_string(), "execution aborted (timeout = 10s)");
     }
 
+    #[test]
+    fn read_transaction_timeout_error() {
+        let err = reth_errors::ProviderError::Database(reth_errors::DatabaseError::Read(
+            DatabaseErrorInfo {
+                message: "read transaction has been timed out".into(),
+                code: DatabaseErrorInfo::READ_TRANSACTION_TIMEOUT_CODE,
+            },
+        ));
+        let err = EthApiError::from(err);
+        assert!(matches!(err, EthApiError::ReadTransactionTimeout));
+
+        let err: jsonrpsee_types::error::ErrorObject<'static> = err.into();
+        assert_eq!(err.code(), -32002);
+
+        let err = reth_errors::ProviderError::Database(reth_errors::DatabaseError::Read(
+            DatabaseErrorInfo {
+                message: "read transaction is open for too long, new reads are denied".into(),
+                code: DatabaseErrorInfo::READ_TRANSACTION_DENIED_CODE,
+            },
+        ));
+        assert!(matches!(EthApiError::from(err), EthApiError::ReadTransactionTimeout));
+    }
+
     #[test]
     fn header_not_found_message() {
         let err: jsonrpsee_types::error::ErrorObject<'static> =
@@ -61,14 +61,22 @@ where
         self.config().resolution
     }
 
-    /// Returns all blocks that are missing in the cache in the [`lower_bound`, `upper_bound`]
-    /// range.
+    /// Returns all blocks that are missing in the cache in the configured window of
+    /// [`FeeHistoryCacheConfig::max_blocks`] ending at the `upper_bound`, newest first.
     ///
     /// This function is used to populate the cache with missing blocks, which can happen if the
-    /// node switched to stage sync node.
+    /// node switched to stage sync node, or if blocks were committed before the cache was
+    /// created.
     async fn missing_consecutive_blocks(&self) -> VecDeque<u64> {
+        self.missing_blocks_until(self.upper_bound()).await
+    }
+
+    /// Returns all blocks that are missing in the cache in the configured window of
+    /// [`FeeHistoryCacheConfig::max_blocks`] ending at the given block (inclusive), newest first.
+    async fn missing_blocks_until(&self, upper_bound: u64) -> VecDeque<u64> {
         let entries = self.inner.entries.read().await;
-        (self.lower_bound()..self.upper_bound())
+        let lower_bound = (upper_bound + 1).saturating_sub(self.inner.config.max_blocks);
+        (lower_bound..=upper_bound)
             .rev()
             .filter(|&block_number| !entries.contains_key(&block_number))
             .collect()
@@ -231,6 +239,12 @@ pub async fn fee_history_cache_new_blocks_task<St, Provider, N>(
     // We're listening for new blocks emitted when the node is in live sync.
     // If the node transitions to stage sync, we need to fetch the missing blocks
     let mut missing_blocks = VecDeque::new();
+
+    // Backfill the most recent blocks, so that `eth_feeHistory` requests are served from the
+    // precomputed reward percentiles instead of recomputing them on every request.
+    if let Ok(best_block) = provider.best_block_number() {
+        missing_blocks = fee_history_cache.missing_blocks_until(best_block).await;
+    }
     let mut fetch_missing_block = Fuse::terminated();
 
     loop {
@@ -413,3 +427,40 @@ where
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_chainspec::MAINNET;
+    use reth_ethereum_primitives::Receipt;
+
+    fn block(number: u64) -> SealedBlock<reth_ethereum_primitives::Block> {
+        SealedBlock::seal_slow(reth_ethereum_primitives::Block {
+            header: Header { number, gas_limit: 30_000_000, ..Default::default() },
+            body: Default::default(),
+        })
+    }
+
+    #[tokio::test]
+    async fn backfills_configured_window() {
+        let cache =
+            FeeHistoryCache::<Header>::new(FeeHistoryCacheConfig { max_blocks: 5, resolution: 4 });
+        assert_eq!(cache.missing_blocks_until(10).await, [10, 9, 8, 7, 6]);
+
+        let receipts: &[Receipt] = &[];
+        let head = block(10);
+        cache.insert_blocks([(&head, receipts)], &*MAINNET).await;
+        assert_eq!(cache.missing_consecutive_blocks().await, [9, 8, 7, 6]);
+        assert!(cache.get_history(6, 10).await.is_none());
+
+        let blocks = (6..10).rev().map(block).collect::<Vec<_>>();
+        for block in &blocks {
+            cache.insert_blocks([(block, receipts)], &*MAINNET).await;
+        }
+        assert!(cache.missing_consecutive_blocks().await.is_empty());
+
+        let history = cache.get_history(6, 10).await.unwrap();
+        assert_eq!(history.len(), 5);
+        assert!(history.iter().all(|entry| entry.rewards.len() == 401));
+    }
+}
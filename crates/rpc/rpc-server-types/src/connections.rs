@@ -0,0 +1,262 @@
+//! Registry of the open connections of the RPC servers.
+
+use dashmap::DashMap;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashSet,
+    fmt,
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        Arc, Weak,
+    },
+    time::Instant,
+};
+use tokio::sync::Notify;
+
+/// Info about an open connection of the RPC servers, returned by `admin_rpcConnections`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcConnectionInfo {
+    /// The id of the connection, used to terminate it.
+    pub id: u64,
+    /// The transport of the connection: `http` or `ws`.
+    pub transport: String,
+    /// The remote address of the connection.
+    pub remote_addr: SocketAddr,
+    /// The number of open subscriptions of the connection.
+    pub subscriptions: usize,
+    /// The number of requests of the connection that are being processed.
+    pub inflight_requests: usize,
+    /// The total number of requests received on the connection.
+    pub requests: u64,
+    /// The number of seconds since the connection was opened.
+    pub age_secs: u64,
+}
+
+/// Registry of the open connections of the RPC servers.
+///
+/// Connections are registered by the accept loop of the http and ws servers and stay in the
+/// registry until they are closed.
+#[derive(Debug, Clone, Default)]
+pub struct RpcConnections {
+    inner: Arc<RpcConnectionsInner>,
+}
+
+#[derive(Debug, Default)]
+struct RpcConnectionsInner {
+    /// The id of the next connection.
+    next_id: AtomicU64,
+    /// The open connections by id.
+    connections: DashMap<u64, Weak<RpcConnection>>,
+}
+
+impl RpcConnections {
+    /// Registers a new connection from the given remote address.
+    ///
+    /// The connection is removed from the registry once the returned [`RpcConnection`] is dropped.
+    pub fn register(&self, remote_addr: SocketAddr, websocket: bool) -> Arc<RpcConnection> {
+        let id = self.inner.next_id.fetch_add(1, Ordering::Relaxed);
+        let connection = Arc::new(RpcConnection {
+            id,
+            remote_addr,
+            websocket: AtomicBool::new(websocket),
+            opened_at: Instant::now(),
+            inflight_requests: AtomicUsize::new(0),
+            requests: AtomicU64::new(0),
+            subscriptions: Mutex::new(HashSet::new()),
+            terminated: AtomicBool::new(false),
+            terminate: Notify::new(),
+            registry: Arc::downgrade(&self.inner),
+        });
+        self.inner.connections.insert(id, Arc::downgrade(&connection));
+        connection
+    }
+
+    /// Returns the info of all open connections, ordered by id.
+    pub fn connections(&self) -> Vec<RpcConnectionInfo> {
+        // collected first, because dropping the last reference to a connection removes it from
+        // the registry
+        let connections = self
+            .inner
+            .connections
+            .iter()
+            .filter_map(|entry| entry.value().upgrade())
+            .collect::<Vec<_>>();
+        let mut infos = connections.iter().map(|connection| connection.info()).collect::<Vec<_>>();
+        infos.sort_unstable_by_key(|info| info.id);
+        infos
+    }
+
+    /// Terminates the connection with the given id, which closes its socket.
+    ///
+    /// Returns `false` if there's no open connection with the given id.
+    pub fn terminate(&self, id: u64) -> bool {
+        let connection = self.inner.connections.get(&id).and_then(|entry| entry.value().upgrade());
+        let Some(connection) = connection else { return false };
+        connection.terminate();
+        true
+    }
+}
+
+/// An open connection of the RPC servers, registered in the [`RpcConnections`].
+pub struct RpcConnection {
+    id: u64,
+    remote_addr: SocketAddr,
+    /// Whether the connection is a ws connection, or was upgraded to one.
+    websocket: AtomicBool,
+    opened_at: Instant,
+    inflight_requests: AtomicUsize,
+    requests: AtomicU64,
+    /// The JSON encoded ids of the open subscriptions.
+    subscriptions: Mutex<HashSet<String>>,
+    terminated: AtomicBool,
+    /// Notifies the accept loop that the connection was terminated.
+    terminate: Notify,
+    registry: Weak<RpcConnectionsInner>,
+}
+
+impl RpcConnection {
+    /// Returns the id of the connection.
+    pub const fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Returns `true` if the connection was terminated.
+    pub fn is_terminated(&self) -> bool {
+        self.terminated.load(Ordering::SeqCst)
+    }
+
+    /// Records that the connection was upgraded to a ws connection.
+    pub fn upgrade_to_websocket(&self) {
+        self.websocket.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns once the connection is terminated.
+    pub async fn terminated(&self) {
+        // created before checking the flag, so that a termination in between isn't missed
+        let notified = self.terminate.notified();
+        if self.is_terminated() {
+            return
+        }
+        notified.await
+    }
+
+    /// Records the start of a request, returning a guard that records its end when dropped.
+    pub fn start_request(self: &Arc<Self>) -> RpcRequestGuard {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+        self.inflight_requests.fetch_add(1, Ordering::Relaxed);
+        RpcRequestGuard { connection: self.clone() }
+    }
+
+    /// Records a subscription opened by the connection.
+    pub fn add_subscription(&self, id: String) {
+        self.subscriptions.lock().insert(id);
+    }
+
+    /// Records a subscription closed by the connection.
+    pub fn remove_subscription(&self, id: &str) {
+        self.subscriptions.lock().remove(id);
+    }
+
+    /// Returns the info of the connection.
+    pub fn info(&self) -> RpcConnectionInfo {
+        let transport = if self.websocket.load(Ordering::Relaxed) { "ws" } else { "http" };
+        RpcConnectionInfo {
+            id: self.id,
+            transport: transport.to_string(),
+            remote_addr: self.remote_addr,
+            subscriptions: self.subscriptions.lock().len(),
+            inflight_requests: self.inflight_requests.load(Ordering::Relaxed),
+            requests: self.requests.load(Ordering::Relaxed),
+            age_secs: self.opened_at.elapsed().as_secs(),
+        }
+    }
+
+    /// Marks the connection as terminated and wakes the accept loop, which closes its socket.
+    fn terminate(&self) {
+        if !self.terminated.swap(true, Ordering::SeqCst) {
+            self.terminate.notify_waiters();
+        }
+    }
+}
+
+impl fmt::Debug for RpcConnection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RpcConnection")
+            .field("id", &self.id)
+            .field("remote_addr", &self.remote_addr)
+            .field("websocket", &self.websocket)
+            .field("opened_at", &self.opened_at)
+            .field("terminated", &self.terminated)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Drop for RpcConnection {
+    fn drop(&mut self) {
+        if let Some(registry) = self.registry.upgrade() {
+            registry.connections.remove(&self.id);
+        }
+    }
+}
+
+/// Guard of a request of a [`RpcConnection`], which records the end of the request when dropped.
+#[derive(Debug)]
+pub struct RpcRequestGuard {
+    connection: Arc<RpcConnection>,
+}
+
+impl Drop for RpcRequestGuard {
+    fn drop(&mut self) {
+        self.connection.inflight_requests.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::{Ipv4Addr, SocketAddrV4};
+
+    #[tokio::test]
+    async fn register_and_terminate() {
+        let connections = RpcConnections::default();
+        let remote_addr = SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::LOCALHOST, 40000));
+
+        let connection = connections.register(remote_addr, false);
+        let other = connections.register(remote_addr, true);
+
+        let request = connection.start_request();
+        connection.upgrade_to_websocket();
+        connection.add_subscription("\"0x1\"".to_string());
+        connection.add_subscription("\"0x2\"".to_string());
+        connection.remove_subscription("\"0x2\"");
+
+        let infos = connections.connections();
+        assert_eq!(infos.len(), 2);
+        assert_eq!(infos[0].id, connection.id());
+        assert_eq!(infos[0].transport, "ws");
+        assert_eq!(infos[0].remote_addr, remote_addr);
+        assert_eq!(infos[0].subscriptions, 1);
+        assert_eq!(infos[0].inflight_requests, 1);
+        assert_eq!(infos[0].requests, 1);
+        drop(request);
+        assert_eq!(connection.info().inflight_requests, 0);
+
+        let terminated = connection.terminated();
+        assert!(connections.terminate(connection.id()));
+        assert!(connection.is_terminated());
+        terminated.await;
+        // a connection terminated before waiting for it is terminated right away
+        connection.terminated().await;
+
+        // closed connections are removed from the registry
+        drop(connection);
+        assert_eq!(
+            connections.connections().iter().map(|info| info.id).collect::<Vec<_>>(),
+            [other.id()]
+        );
+        assert!(!connections.terminate(u64::MAX));
+    }
+}
@@ -32,6 +32,19 @@ pub const DEFAULT_MAX_TRACE_FILTER_BLOCKS: u64 = 100;
 /// value.
 pub const DEFAULT_MAX_BLOCKING_IO_REQUEST: usize = 256;
 
+/// The default number of worker threads of the dedicated pools of the `debug` and `trace`
+/// namespaces.
+///
+/// Each namespace gets half of the available cores, so that tracing requests of one namespace
+/// can't occupy all cores.
+pub fn default_namespace_workers() -> usize {
+    std::thread::available_parallelism().map_or(4, |cpus| max(cpus.get() / 2, 1))
+}
+
+/// The default niceness of the worker threads of the dedicated namespace pools, which gives them a
+/// lower scheduling priority than the threads serving other requests.
+pub const DEFAULT_RPC_WORKER_NICENESS: i32 = 10;
+
 /// The default maximum number tracing requests we're allowing concurrently.
 /// Tracing is mostly CPU bound so we're limiting the number of concurrent requests to something
 /// lower that the number of cores, in order to minimize the impact on the rest of the system.
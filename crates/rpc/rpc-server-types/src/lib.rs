@@ -12,6 +12,9 @@
 pub mod constants;
 pub mod result;
 
+mod connections;
+pub use connections::{RpcConnection, RpcConnectionInfo, RpcConnections, RpcRequestGuard};
+
 mod module;
 pub use module::{
     DefaultRpcModuleValidator, LenientRpcModuleValidator, RethRpcModule, RpcModuleSelection,
@@ -8,13 +8,18 @@ use alloy_rpc_types_admin::{
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
 use reth_chainspec::{EthChainSpec, EthereumHardfork, EthereumHardforks, ForkCondition};
-use reth_network_api::{NetworkInfo, Peers};
+use reth_network_api::{
+    bandwidth::MessageTraffic,
+    census::{client_name, fork_id_label},
+    NetworkInfo, Peers,
+};
 use reth_network_peers::{id2pk, AnyNode, NodeRecord};
 use reth_network_types::PeerKind;
-use reth_rpc_api::AdminApiServer;
-use reth_rpc_server_types::ToRpcResult;
+use reth_rpc_api::{AdminApiServer, MessageStats, PeerCensus, PeerStats};
+use reth_rpc_server_types::{RpcConnectionInfo, RpcConnections, ToRpcResult};
 use reth_transaction_pool::TransactionPool;
 use revm_primitives::keccak256;
+use std::collections::BTreeMap;
 
 /// `admin` API implementation.
 ///
@@ -26,12 +31,19 @@ pub struct AdminApi<N, ChainSpec, Pool> {
     chain_spec: Arc<ChainSpec>,
     /// The transaction pool
     pool: Pool,
+    /// The open connections of the RPC servers
+    rpc_connections: RpcConnections,
 }
 
 impl<N, ChainSpec, Pool> AdminApi<N, ChainSpec, Pool> {
     /// Creates a new instance of `AdminApi`.
-    pub const fn new(network: N, chain_spec: Arc<ChainSpec>, pool: Pool) -> Self {
-        Self { network, chain_spec, pool }
+    pub const fn new(
+        network: N,
+        chain_spec: Arc<ChainSpec>,
+        pool: Pool,
+        rpc_connections: RpcConnections,
+    ) -> Self {
+        Self { network, chain_spec, pool, rpc_connections }
     }
 }
 
@@ -99,6 +111,57 @@ where
         Ok(infos)
     }
 
+    /// Handler for `admin_peerCensus`
+    async fn peer_census(&self) -> RpcResult<PeerCensus> {
+        let peers = self.network.get_all_peers().await.to_rpc_result()?;
+        let mut census = PeerCensus { total: peers.len(), ..Default::default() };
+
+        for peer in peers {
+            *census.clients.entry(client_name(&peer.client_version).to_string()).or_default() += 1;
+            *census.eth_versions.entry((peer.status.version as u8).to_string()).or_default() += 1;
+            *census.fork_ids.entry(fork_id_label(peer.status.forkid)).or_default() += 1;
+        }
+
+        Ok(census)
+    }
+
+    /// Handler for `admin_peerStats`
+    async fn peer_stats(&self) -> RpcResult<Vec<PeerStats>> {
+        let peers = self.network.get_all_peers().await.to_rpc_result()?;
+
+        let message_stats = |traffic: &BTreeMap<&'static str, MessageTraffic>| {
+            traffic
+                .iter()
+                .map(|(message, traffic)| {
+                    (
+                        message.to_string(),
+                        MessageStats { messages: traffic.messages, bytes: traffic.bytes },
+                    )
+                })
+                .collect()
+        };
+        let mut stats = peers
+            .into_iter()
+            .map(|peer| PeerStats {
+                id: peer.remote_id,
+                enode: peer.enode,
+                name: peer.client_version.to_string(),
+                remote_address: peer.remote_addr,
+                inbound: peer.direction.is_incoming(),
+                connected_secs: peer.session_established.elapsed().as_secs(),
+                ingress_bytes: peer.bandwidth.ingress_bytes(),
+                egress_bytes: peer.bandwidth.egress_bytes(),
+                ingress: message_stats(&peer.bandwidth.ingress),
+                egress: message_stats(&peer.bandwidth.egress),
+            })
+            .collect::<Vec<_>>();
+        stats.sort_unstable_by_key(|peer| {
+            std::cmp::Reverse(peer.ingress_bytes.saturating_add(peer.egress_bytes))
+        });
+
+        Ok(stats)
+    }
+
     /// Handler for `admin_nodeInfo`
     async fn node_info(&self) -> RpcResult<NodeInfo> {
         let enode = self.network.local_node_record();
@@ -193,6 +256,16 @@ where
         let _ = self.pool.remove_transactions(all_hashes);
         Ok(count)
     }
+
+    /// Handler for `admin_rpcConnections`
+    fn rpc_connections(&self) -> RpcResult<Vec<RpcConnectionInfo>> {
+        Ok(self.rpc_connections.connections())
+    }
+
+    /// Handler for `admin_terminateRpcConnection`
+    fn terminate_rpc_connection(&self, id: u64) -> RpcResult<bool> {
+        Ok(self.rpc_connections.terminate(id))
+    }
 }
 
 impl<N, ChainSpec, Pool> std::fmt::Debug for AdminApi<N, ChainSpec, Pool> {
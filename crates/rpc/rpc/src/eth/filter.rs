@@ -28,8 +28,8 @@ use reth_rpc_eth_types::{
 };
 use reth_rpc_server_types::{result::rpc_error_with_code, ToRpcResult};
 use reth_storage_api::{
-    BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, HeaderProvider, ProviderBlock,
-    ProviderReceipt, ReceiptProvider,
+    BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, HeaderProvider,
+    LogsBloomIndexReader, ProviderBlock, ProviderReceipt, ReceiptProvider,
 };
 use reth_tasks::TaskSpawner;
 use reth_transaction_pool::{NewSubpoolTransactionStream, PoolTransaction, TransactionPool};
@@ -94,6 +94,9 @@ const DEFAULT_PARALLEL_CONCURRENCY: usize = 4;
 pub struct EthFilter<Eth: EthApiTypes> {
     /// All nested fields bundled together
     inner: Arc<EthFilterInner<Eth>>,
+    /// Optional index of the logs blooms aggregated per static file, used to skip static files
+    /// without logs of the filtered addresses.
+    logs_bloom_index: Option<Arc<dyn LogsBloomIndexReader>>,
 }
 
 impl<Eth> Clone for EthFilter<Eth>
@@ -101,7 +104,7 @@ where
     Eth: EthApiTypes,
 {
     fn clone(&self) -> Self {
-        Self { inner: self.inner.clone() }
+        Self { inner: self.inner.clone(), logs_bloom_index: self.logs_bloom_index.clone() }
     }
 }
 
@@ -149,7 +152,7 @@ where
             query_limits: QueryLimits { max_blocks_per_filter, max_logs_per_response },
         };
 
-        let eth_filter = Self { inner: Arc::new(inner) };
+        let eth_filter = Self { inner: Arc::new(inner), logs_bloom_index: None };
 
         let this = eth_filter.clone();
         eth_filter.inner.task_spawner.spawn_critical(
@@ -162,6 +165,18 @@ where
         eth_filter
     }
 
+    /// Configures the logs bloom index used by log queries with address filters.
+    ///
+    /// For the blocks covered by the index, the headers of a static file are only read if its
+    /// aggregated logs bloom contains any of the filtered addresses.
+    pub fn with_logs_bloom_index(
+        mut self,
+        logs_bloom_index: Arc<dyn LogsBloomIndexReader>,
+    ) -> Self {
+        self.logs_bloom_index = Some(logs_bloom_index);
+        self
+    }
+
     /// Returns all currently active filters
     pub fn active_filters(&self) -> &ActiveFilters<RpcTransaction<Eth::NetworkTypes>> {
         &self.inner.active_filters
@@ -286,6 +301,7 @@ where
                         from_block_number,
                         to_block_number,
                         self.inner.query_limits,
+                        self.logs_bloom_index.clone(),
                     )
                     .await?;
                 Ok(FilterChanges::Logs(logs))
@@ -321,7 +337,7 @@ where
         filter: Filter,
         limits: QueryLimits,
     ) -> Result<Vec<Log>, EthFilterError> {
-        self.inner.clone().logs_for_filter(filter, limits).await
+        self.inner.clone().logs_for_filter(filter, limits, self.logs_bloom_index.clone()).await
     }
 }
 
@@ -464,6 +480,7 @@ where
         self: Arc<Self>,
         filter: Filter,
         limits: QueryLimits,
+        logs_bloom_index: Option<Arc<dyn LogsBloomIndexReader>>,
     ) -> Result<Vec<Log>, EthFilterError> {
         match filter.block_option {
             FilterBlockOption::AtBlockHash(block_hash) => {
@@ -577,8 +594,14 @@ where
                     return Err(EthApiError::PrunedHistoryUnavailable.into());
                 }
 
-                self.get_logs_in_block_range(filter, from_block_number, to_block_number, limits)
-                    .await
+                self.get_logs_in_block_range(
+                    filter,
+                    from_block_number,
+                    to_block_number,
+                    limits,
+                    logs_bloom_index,
+                )
+                .await
             }
         }
     }
@@ -618,6 +641,7 @@ where
         from_block: u64,
         to_block: u64,
         limits: QueryLimits,
+        logs_bloom_index: Option<Arc<dyn LogsBloomIndexReader>>,
     ) -> Result<Vec<Log>, EthFilterError> {
         trace!(target: "rpc::eth::filter", from=from_block, to=to_block, ?filter, "finding logs in range");
 
@@ -635,8 +659,15 @@ where
         let (tx, rx) = oneshot::channel();
         let this = self.clone();
         self.task_spawner.spawn_blocking(Box::pin(async move {
-            let res =
-                this.get_logs_in_block_range_inner(&filter, from_block, to_block, limits).await;
+            let res = this
+                .get_logs_in_block_range_inner(
+                    &filter,
+                    from_block,
+                    to_block,
+                    limits,
+                    logs_bloom_index.as_deref(),
+                )
+                .await;
             let _ = tx.send(res);
         }));
 
@@ -657,6 +688,7 @@ where
         from_block: u64,
         to_block: u64,
         limits: QueryLimits,
+        logs_bloom_index: Option<&dyn LogsBloomIndexReader>,
     ) -> Result<Vec<Log>, EthFilterError> {
         let mut all_logs = Vec::new();
         let mut matching_headers = Vec::new();
@@ -664,9 +696,19 @@ where
         // get current chain tip to determine processing mode
         let chain_tip = self.provider().best_block_number()?;
 
+        // skip the static files whose aggregated logs bloom contains none of the addresses
+        let block_ranges = match logs_bloom_index {
+            Some(index) if !filter.address.is_empty() => {
+                let addresses = filter.address.iter().copied().collect::<Vec<_>>();
+                index.block_ranges_with_log_addresses(&addresses, from_block..=to_block)?
+            }
+            _ => vec![from_block..=to_block],
+        };
+
         // first collect all headers that match the bloom filter for cached mode decision
-        for (from, to) in
-            BlockRangeInclusiveIter::new(from_block..=to_block, self.max_headers_range)
+        for (from, to) in block_ranges
+            .into_iter()
+            .flat_map(|range| BlockRangeInclusiveIter::new(range, self.max_headers_range))
         {
             let headers = self.provider().headers_range(from..=to)?;
 
@@ -1831,7 +1873,7 @@ mod tests {
                 parent_hash: prev_hash,
                 // Set bloom to match filter only for blocks 100 and 102
                 logs_bloom: if i == 100 || i == 102 {
-                    alloy_primitives::Bloom::from([1u8; 256])
+                    alloy_primitives::Bloom::repeat_byte(0xff)
                 } else {
                     alloy_primitives::Bloom::default()
                 },
@@ -1895,7 +1937,7 @@ mod tests {
         let logs = eth_filter
             .inner
             .clone()
-            .get_logs_in_block_range(filter, 100, 103, QueryLimits::default())
+            .get_logs_in_block_range(filter, 100, 103, QueryLimits::default(), None)
             .await
             .expect("should succeed");
 
@@ -1908,5 +1950,36 @@ mod tests {
         // Each block hash should be the hash of its own header, not derived from any other header
         assert_eq!(logs[0].block_hash, Some(expected_hashes[0])); // block 100
         assert_eq!(logs[1].block_hash, Some(expected_hashes[2])); // block 102
+
+        // Blocks outside of the ranges returned by the logs bloom index are skipped
+        let filter = Filter::default().address(alloy_primitives::Address::ZERO);
+        let logs = eth_filter
+            .inner
+            .clone()
+            .get_logs_in_block_range(
+                filter,
+                100,
+                103,
+                QueryLimits::default(),
+                Some(Arc::new(FixedLogsBloomIndex(101..=103))),
+            )
+            .await
+            .expect("should succeed");
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].block_number, Some(102));
+        assert_eq!(logs[0].block_hash, Some(expected_hashes[2]));
+    }
+
+    /// Logs bloom index that returns the same block range for any addresses.
+    struct FixedLogsBloomIndex(RangeInclusive<u64>);
+
+    impl LogsBloomIndexReader for FixedLogsBloomIndex {
+        fn block_ranges_with_log_addresses(
+            &self,
+            _addresses: &[alloy_primitives::Address],
+            _block_range: RangeInclusive<u64>,
+        ) -> reth_errors::ProviderResult<Vec<RangeInclusive<u64>>> {
+            Ok(vec![self.0.clone()])
+        }
     }
 }
@@ -12,3 +12,81 @@ where
     Rpc: RpcConvert<Primitives = N::Primitives, Error = EthApiError>,
 {
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::EthApi;
+    use alloy_consensus::{
+        transaction::{TransactionMeta, TxHashRef},
+        Header, TxLegacy,
+    };
+    use alloy_primitives::{Log, Signature, B256};
+    use reth_db_api::models::StoredBlockBodyIndices;
+    use reth_ethereum_primitives::{Block, BlockBody, Receipt, Transaction, TransactionSigned};
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_network_api::noop::NoopNetwork;
+    use reth_provider::{test_utils::MockEthProvider, ChainSpecProvider};
+    use reth_rpc_eth_api::helpers::LoadReceipt;
+    use reth_transaction_pool::test_utils::testing_pool;
+
+    #[tokio::test]
+    async fn build_transaction_receipt_from_indexed_offsets() {
+        let provider = MockEthProvider::default();
+        let transactions = (0..2)
+            .map(|nonce| {
+                TransactionSigned::new_unhashed(
+                    Transaction::Legacy(TxLegacy { nonce, ..Default::default() }),
+                    Signature::test_signature(),
+                )
+            })
+            .collect::<Vec<_>>();
+        let receipts = vec![
+            Receipt {
+                cumulative_gas_used: 21_000,
+                logs: vec![Log::default(), Log::default()],
+                ..Default::default()
+            },
+            Receipt {
+                cumulative_gas_used: 50_000,
+                logs: vec![Log::default()],
+                ..Default::default()
+            },
+        ];
+
+        // the header of the block is unknown, so its receipts can't be loaded by its hash and the
+        // receipt has to be built from the offsets of the index
+        let block_hash = B256::repeat_byte(1);
+        let header = Header { number: 1, ..Default::default() };
+        provider.blocks.lock().insert(
+            block_hash,
+            Block {
+                header,
+                body: BlockBody { transactions: transactions.clone(), ..Default::default() },
+            },
+        );
+        provider.add_block_body_indices(1, StoredBlockBodyIndices { first_tx_num: 0, tx_count: 2 });
+        provider.add_receipts(1, receipts.clone());
+
+        let eth_api = EthApi::builder(
+            provider.clone(),
+            testing_pool(),
+            NoopNetwork::default(),
+            EthEvmConfig::new(provider.chain_spec()),
+        )
+        .build();
+
+        let tx = transactions[1].clone();
+        let meta = TransactionMeta {
+            tx_hash: *tx.tx_hash(),
+            index: 1,
+            block_hash,
+            block_number: 1,
+            ..Default::default()
+        };
+        let receipt =
+            eth_api.build_transaction_receipt(tx, meta, receipts[1].clone()).await.unwrap();
+
+        assert_eq!(receipt.gas_used, 29_000);
+        assert_eq!(receipt.inner.logs()[0].log_index, Some(2));
+    }
+}
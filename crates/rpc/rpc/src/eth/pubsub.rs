@@ -9,7 +9,8 @@ use alloy_rpc_types_eth::{
 };
 use futures::StreamExt;
 use jsonrpsee::{
-    server::SubscriptionMessage, types::ErrorObject, PendingSubscriptionSink, SubscriptionSink,
+    core::SubscriptionError, server::SubscriptionMessage, types::ErrorObject,
+    PendingSubscriptionSink, SubscriptionSink,
 };
 use reth_chain_state::CanonStateSubscriptions;
 use reth_network_api::NetworkInfo;
@@ -23,8 +24,9 @@ use reth_storage_api::BlockNumReader;
 use reth_tasks::{TaskSpawner, TokioTaskExecutor};
 use reth_transaction_pool::{NewTransactionEvent, TransactionPool};
 use serde::Serialize;
+use tokio::sync::oneshot;
 use tokio_stream::{
-    wrappers::{BroadcastStream, ReceiverStream},
+    wrappers::{errors::BroadcastStreamRecvError, BroadcastStream, ReceiverStream},
     Stream,
 };
 use tracing::error;
@@ -82,7 +84,13 @@ where
     }
 
     /// Returns a stream that yields all logs that match the given filter.
-    pub fn log_stream(&self, filter: Filter) -> impl Stream<Item = Log> {
+    ///
+    /// Yields an error if the stream lagged behind the canonical state notifications, in which
+    /// case logs were missed.
+    pub fn log_stream(
+        &self,
+        filter: Filter,
+    ) -> impl Stream<Item = Result<Log, BroadcastStreamRecvError>> {
         self.inner.log_stream(filter)
     }
 
@@ -107,7 +115,16 @@ where
                     }
                     _ => Default::default(),
                 };
-                pipe_from_stream(accepted_sink, self.log_stream(filter)).await
+                // a lagging subscription missed logs, so it's closed with an error instead of
+                // silently skipping them
+                let logs = self.log_stream(filter).map(|log| {
+                    log.map_err(|err| {
+                        internal_rpc_err(format!(
+                            "logs subscription lagged behind the canonical chain: {err}"
+                        ))
+                    })
+                });
+                pipe_from_fallible_stream(accepted_sink, logs).await
             }
             SubscriptionKind::NewPendingTransactions => {
                 if let Some(params) = params {
@@ -214,10 +231,16 @@ where
     ) -> jsonrpsee::core::SubscriptionResult {
         let sink = pending.accept().await?;
         let pubsub = self.clone();
+        let (tx, rx) = oneshot::channel();
         self.inner.subscription_task_spawner.spawn(Box::pin(async move {
-            let _ = pubsub.handle_accepted(sink, kind, params).await;
+            let _ = tx.send(pubsub.handle_accepted(sink, kind, params).await);
         }));
 
+        // a subscription that failed is closed with an error notification
+        if let Ok(Err(err)) = rx.await {
+            return Err(SubscriptionError::from_json(serde_json::value::to_raw_value(&err)?))
+        }
+
         Ok(())
     }
 }
@@ -242,11 +265,23 @@ impl From<SubscriptionSerializeError> for ErrorObject<'static> {
 /// Pipes all stream items to the subscription sink.
 async fn pipe_from_stream<T, St>(
     sink: SubscriptionSink,
-    mut stream: St,
+    stream: St,
 ) -> Result<(), ErrorObject<'static>>
 where
     St: Stream<Item = T> + Unpin,
     T: Serialize,
+{
+    pipe_from_fallible_stream(sink, stream.map(Ok)).await
+}
+
+/// Pipes all stream items to the subscription sink, until the stream yields an error.
+async fn pipe_from_fallible_stream<T, St>(
+    sink: SubscriptionSink,
+    mut stream: St,
+) -> Result<(), ErrorObject<'static>>
+where
+    St: Stream<Item = Result<T, ErrorObject<'static>>> + Unpin,
+    T: Serialize,
 {
     loop {
         tokio::select! {
@@ -256,7 +291,7 @@ where
             },
             maybe_item = stream.next() => {
                 let item = match maybe_item {
-                    Some(item) => item,
+                    Some(item) => item?,
                     None => {
                         // stream ended
                         break  Ok(())
@@ -362,21 +397,43 @@ where
     }
 
     /// Returns a stream that yields all logs that match the given filter.
-    fn log_stream(&self, filter: Filter) -> impl Stream<Item = Log> {
-        BroadcastStream::new(self.eth_api.provider().subscribe_to_canonical_state())
-            .map(move |canon_state| {
-                canon_state.expect("new block subscription never ends").block_receipts()
-            })
-            .flat_map(futures::stream::iter)
-            .flat_map(move |(block_receipts, removed)| {
-                let all_logs = logs_utils::matching_block_logs_with_tx_hashes(
-                    &filter,
-                    block_receipts.block,
-                    block_receipts.timestamp,
-                    block_receipts.tx_receipts.iter().map(|(tx, receipt)| (*tx, receipt)),
-                    removed,
-                );
-                futures::stream::iter(all_logs)
-            })
+    ///
+    /// On reorgs, the logs of the reverted blocks are emitted with `removed: true` in reverse
+    /// order, before the logs of the newly committed blocks.
+    ///
+    /// Yields an error if the stream lagged behind the canonical state notifications.
+    fn log_stream(
+        &self,
+        filter: Filter,
+    ) -> impl Stream<Item = Result<Log, BroadcastStreamRecvError>> {
+        BroadcastStream::new(self.eth_api.provider().subscribe_to_canonical_state()).flat_map(
+            move |canon_state| {
+                let logs = match canon_state {
+                    Ok(canon_state) => canon_state
+                        .block_receipts()
+                        .into_iter()
+                        .flat_map(|(block_receipts, removed)| {
+                            let mut all_logs = logs_utils::matching_block_logs_with_tx_hashes(
+                                &filter,
+                                block_receipts.block,
+                                block_receipts.timestamp,
+                                block_receipts
+                                    .tx_receipts
+                                    .iter()
+                                    .map(|(tx, receipt)| (*tx, receipt)),
+                                removed,
+                            );
+                            if removed {
+                                all_logs.reverse();
+                            }
+                            all_logs
+                        })
+                        .map(Ok)
+                        .collect::<Vec<_>>(),
+                    Err(err) => vec![Err(err)],
+                };
+                futures::stream::iter(logs)
+            },
+        )
     }
 }
@@ -0,0 +1,170 @@
+//! Opcode and precompile usage statistics of executed blocks.
+
+use alloy_consensus::BlockHeader as _;
+use alloy_eips::BlockId;
+use alloy_primitives::{BlockHash, BlockNumber};
+use async_trait::async_trait;
+use jsonrpsee::core::RpcResult;
+use reth_revm::execution_stats::{
+    opcode_name, ExecutionStats, ExecutionStatsInspector, ExecutionStatsStore,
+};
+use reth_rpc_api::{
+    BlockExecutionStats, OpcodeStats, PrecompileStats, RethExecutionStatsApiServer,
+};
+use reth_rpc_eth_api::helpers::{LoadBlock, Trace};
+use reth_tasks::pool::BlockingTaskGuard;
+use std::sync::Arc;
+
+/// Converts the stats into the RPC response for the given block.
+fn into_block_stats(
+    stats: &ExecutionStats,
+    block_hash: BlockHash,
+    block_number: BlockNumber,
+    gas_used: u64,
+) -> BlockExecutionStats {
+    let gas_share = |gas: u64| if gas_used == 0 { 0.0 } else { gas as f64 / gas_used as f64 };
+
+    let opcodes = stats
+        .opcodes()
+        .map(|(op, usage)| OpcodeStats {
+            opcode: opcode_name(op),
+            count: usage.count,
+            gas_used: usage.gas_used,
+            gas_share: gas_share(usage.gas_used),
+        })
+        .collect();
+
+    let mut precompiles = stats
+        .precompiles()
+        .map(|(address, usage)| PrecompileStats {
+            address,
+            count: usage.count,
+            gas_used: usage.gas_used,
+            gas_share: gas_share(usage.gas_used),
+        })
+        .collect::<Vec<_>>();
+    precompiles.sort_unstable_by_key(|stats| stats.address);
+
+    BlockExecutionStats { block_hash, block_number, gas_used, opcodes, precompiles }
+}
+
+/// `reth_blockExecutionStats` implementation.
+///
+/// This serves the stats the engine collected while executing the block from the
+/// [`ExecutionStatsStore`], and only re-executes the block with an [`ExecutionStatsInspector`] if
+/// they are not stored anymore.
+pub struct RethExecutionStats<Eth> {
+    inner: Arc<RethExecutionStatsInner<Eth>>,
+}
+
+impl<Eth> RethExecutionStats<Eth> {
+    /// Create a new instance of the [`RethExecutionStats`]
+    pub fn new(eth_api: Eth, blocking_task_guard: BlockingTaskGuard) -> Self {
+        let inner = Arc::new(RethExecutionStatsInner { eth_api, blocking_task_guard });
+        Self { inner }
+    }
+
+    /// Access the underlying `Eth` API.
+    pub fn eth_api(&self) -> &Eth {
+        &self.inner.eth_api
+    }
+}
+
+impl<Eth> RethExecutionStats<Eth>
+where
+    Eth: Trace + LoadBlock + 'static,
+{
+    /// Returns the aggregated opcode and precompile usage of all transactions in the block.
+    pub async fn block_execution_stats(
+        &self,
+        block_id: BlockId,
+    ) -> Result<Option<BlockExecutionStats>, Eth::Error> {
+        let Some(block) = self.eth_api().recovered_block(block_id).await? else { return Ok(None) };
+
+        if let Some(stats) = ExecutionStatsStore::global().get(&block.hash()) {
+            return Ok(Some(into_block_stats(
+                &stats,
+                block.hash(),
+                block.number(),
+                block.gas_used(),
+            )))
+        }
+
+        let _permit = self.inner.blocking_task_guard.clone().acquire_owned().await;
+        let Some(transactions) = self
+            .eth_api()
+            .trace_block_inspector(
+                block_id,
+                Some(block.clone()),
+                ExecutionStatsInspector::default,
+                |_, ctx| Ok(ctx.take_inspector().into_stats()),
+            )
+            .await?
+        else {
+            return Ok(None)
+        };
+
+        let mut stats = ExecutionStats::default();
+        for tx_stats in &transactions {
+            stats.merge(tx_stats);
+        }
+
+        Ok(Some(into_block_stats(&stats, block.hash(), block.number(), block.gas_used())))
+    }
+}
+
+#[async_trait]
+impl<Eth> RethExecutionStatsApiServer for RethExecutionStats<Eth>
+where
+    Eth: Trace + LoadBlock + 'static,
+{
+    /// Handler for `reth_blockExecutionStats`
+    async fn reth_block_execution_stats(
+        &self,
+        block_id: BlockId,
+    ) -> RpcResult<Option<BlockExecutionStats>> {
+        Ok(Self::block_execution_stats(self, block_id).await.map_err(Into::into)?)
+    }
+}
+
+impl<Eth> std::fmt::Debug for RethExecutionStats<Eth> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RethExecutionStats").finish_non_exhaustive()
+    }
+}
+
+impl<Eth> Clone for RethExecutionStats<Eth> {
+    fn clone(&self) -> Self {
+        Self { inner: Arc::clone(&self.inner) }
+    }
+}
+
+struct RethExecutionStatsInner<Eth> {
+    /// Access to commonly used code of the `eth` namespace
+    eth_api: Eth,
+    // restrict the number of concurrent block executions
+    blocking_task_guard: BlockingTaskGuard,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::Address;
+    use reth_revm::{bytecode::opcode, execution_stats::Usage};
+
+    #[test]
+    fn block_execution_stats() {
+        let precompile = Address::with_last_byte(1);
+
+        let mut stats = ExecutionStats::default();
+        *stats.opcode_mut(opcode::SLOAD) = Usage { count: 3, gas_used: 2300 };
+        *stats.opcode_mut(opcode::ADD) = Usage { count: 1, gas_used: 3 };
+        *stats.precompile_mut(precompile) = Usage { count: 1, gas_used: 3000 };
+
+        let stats = into_block_stats(&stats, BlockHash::ZERO, 1, 10_606);
+        assert_eq!(stats.opcodes.len(), 2);
+        assert_eq!(stats.opcodes[0].opcode, "ADD");
+        assert_eq!(stats.opcodes[1].opcode, "SLOAD");
+        assert_eq!(stats.precompiles[0].gas_share, 3000.0 / 10_606.0);
+    }
+}
@@ -0,0 +1,273 @@
+//! Inclusion estimates of transactions based on the pool and recent fee trajectories.
+
+use alloy_consensus::{BlockHeader, Transaction};
+use alloy_eips::{BlockId, BlockNumberOrTag};
+use async_trait::async_trait;
+use jsonrpsee::core::RpcResult;
+use reth_chainspec::{ChainSpecProvider, EthChainSpec};
+use reth_rpc_api::{
+    InclusionEstimate, InclusionEstimateRequest, InclusionProbability, RethInclusionApiServer,
+};
+use reth_rpc_eth_api::{helpers::EthFees, FromEthApiError, RpcNodeCore};
+use reth_rpc_eth_types::EthApiError;
+use reth_storage_api::BlockReaderIdExt;
+use reth_transaction_pool::TransactionPool;
+
+/// The number of recent blocks whose fee trajectories are sampled.
+const TRAJECTORY_BLOCKS: u64 = 128;
+
+/// The maximum number of blocks an inclusion is estimated for.
+const MAX_INCLUSION_HORIZON: u64 = 25;
+
+/// The numbers of blocks whose inclusion probabilities are returned.
+const INCLUSION_HORIZONS: [u64; 6] = [1, 2, 3, 5, 10, MAX_INCLUSION_HORIZON];
+
+/// The gas limit of a transaction if the request doesn't specify it.
+const DEFAULT_GAS: u64 = 21_000;
+
+/// `reth_estimateInclusion` implementation.
+///
+/// The pending transactions of the pool that pay a higher tip than the estimated transaction
+/// determine the first block it fits into. The probability of inclusion within a number of blocks
+/// is the share of windows of the recent fee history in which the base fee and blob base fee,
+/// scaled to the current fees, dropped to the fees of the transaction in time.
+pub struct RethInclusion<Eth> {
+    /// Access to commonly used code of the `eth` namespace
+    eth_api: Eth,
+}
+
+impl<Eth> RethInclusion<Eth> {
+    /// Create a new instance of the [`RethInclusion`]
+    pub const fn new(eth_api: Eth) -> Self {
+        Self { eth_api }
+    }
+
+    /// Access the underlying `Eth` API.
+    pub const fn eth_api(&self) -> &Eth {
+        &self.eth_api
+    }
+}
+
+impl<Eth> RethInclusion<Eth>
+where
+    Eth: EthFees + RpcNodeCore + 'static,
+{
+    /// Estimates how many blocks it takes until a transaction with the given fees and blob count
+    /// is included.
+    pub async fn estimate_inclusion(
+        &self,
+        request: InclusionEstimateRequest,
+    ) -> Result<InclusionEstimate, Eth::Error> {
+        let provider = self.eth_api().provider();
+        let latest = provider
+            .latest_header()
+            .map_err(Eth::Error::from_eth_err)?
+            .ok_or(EthApiError::HeaderNotFound(BlockId::latest()))?;
+
+        let gas = request.gas.unwrap_or(DEFAULT_GAS);
+        let blob_count = request.blob_count.unwrap_or_default();
+        let max_priority_fee_per_gas =
+            request.max_priority_fee_per_gas.unwrap_or(request.max_fee_per_gas);
+        if max_priority_fee_per_gas > request.max_fee_per_gas {
+            return Err(EthApiError::InvalidParams(
+                "max priority fee per gas higher than max fee per gas".to_string(),
+            )
+            .into())
+        }
+        if gas > latest.gas_limit() {
+            return Err(EthApiError::InvalidParams(format!(
+                "gas {gas} exceeds the block gas limit {}",
+                latest.gas_limit()
+            ))
+            .into())
+        }
+
+        let blob_params = provider.chain_spec().blob_params_at_timestamp(latest.timestamp());
+        let blob_base_fee = latest.maybe_next_block_blob_fee(blob_params);
+        let blob_fee_limit = if blob_count > 0 {
+            let (Some(blob_params), Some(blob_base_fee)) = (blob_params, blob_base_fee) else {
+                return Err(EthApiError::InvalidParams(
+                    "blob transactions are not supported".to_string(),
+                )
+                .into())
+            };
+            if blob_count > blob_params.max_blobs_per_tx {
+                return Err(EthApiError::InvalidParams(format!(
+                    "blob count {blob_count} exceeds the maximum of {} blobs per transaction",
+                    blob_params.max_blobs_per_tx
+                ))
+                .into())
+            }
+            let Some(max_fee_per_blob_gas) = request.max_fee_per_blob_gas else {
+                return Err(EthApiError::InvalidParams(
+                    "max fee per blob gas is required for blob transactions".to_string(),
+                )
+                .into())
+            };
+            Some((blob_params.max_blob_count, max_fee_per_blob_gas))
+        } else {
+            None
+        };
+
+        let history = self
+            .eth_api()
+            .fee_history(TRAJECTORY_BLOCKS, BlockNumberOrTag::Number(latest.number()), None)
+            .await?;
+        let base_fee = history.base_fee_per_gas.last().copied().unwrap_or_default();
+
+        // Pending transactions are ordered by their effective tip at the next base fee, if the
+        // transaction can't afford it yet, everything that can is ahead of it.
+        let tip = max_priority_fee_per_gas.min(request.max_fee_per_gas.saturating_sub(base_fee));
+        let mut pending_gas_ahead = 0u64;
+        let mut pending_blobs_ahead = 0u64;
+        for tx in self.eth_api().pool().pending_transactions() {
+            if tx.effective_tip_per_gas(base_fee as u64).is_none_or(|tx_tip| tx_tip < tip) {
+                continue
+            }
+            pending_gas_ahead = pending_gas_ahead.saturating_add(tx.gas_limit());
+            if let Some(blob_base_fee) = blob_base_fee &&
+                tx.max_fee_per_blob_gas().is_some_and(|max_fee| max_fee >= blob_base_fee)
+            {
+                let blobs = tx.transaction.blob_versioned_hashes().map_or(0, |hashes| hashes.len());
+                pending_blobs_ahead += blobs as u64;
+            }
+        }
+
+        let mut first_block =
+            pending_gas_ahead.saturating_add(gas).div_ceil(latest.gas_limit().max(1)).max(1);
+        let mut limits = vec![FeeLimit {
+            trajectory: &history.base_fee_per_gas,
+            max_fee: request.max_fee_per_gas,
+        }];
+        if let Some((max_blob_count, max_fee_per_blob_gas)) = blob_fee_limit {
+            first_block =
+                first_block.max((pending_blobs_ahead + blob_count).div_ceil(max_blob_count.max(1)));
+            limits.push(FeeLimit {
+                trajectory: &history.base_fee_per_blob_gas,
+                max_fee: max_fee_per_blob_gas,
+            });
+        }
+
+        let probabilities = inclusion_probabilities(&limits, first_block, MAX_INCLUSION_HORIZON);
+        let expected_blocks =
+            (1..=MAX_INCLUSION_HORIZON).find(|blocks| probabilities[*blocks as usize - 1] >= 0.5);
+
+        Ok(InclusionEstimate {
+            block_number: latest.number(),
+            expected_blocks,
+            probabilities: INCLUSION_HORIZONS
+                .into_iter()
+                .map(|blocks| InclusionProbability {
+                    blocks,
+                    probability: probabilities[blocks as usize - 1],
+                })
+                .collect(),
+            base_fee_per_gas: base_fee,
+            blob_base_fee,
+            pending_gas_ahead,
+            pending_blobs_ahead,
+        })
+    }
+}
+
+#[async_trait]
+impl<Eth> RethInclusionApiServer for RethInclusion<Eth>
+where
+    Eth: EthFees + RpcNodeCore + 'static,
+{
+    /// Handler for `reth_estimateInclusion`
+    async fn reth_estimate_inclusion(
+        &self,
+        request: InclusionEstimateRequest,
+    ) -> RpcResult<InclusionEstimate> {
+        Ok(Self::estimate_inclusion(self, request).await.map_err(Into::into)?)
+    }
+}
+
+impl<Eth> std::fmt::Debug for RethInclusion<Eth> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RethInclusion").finish_non_exhaustive()
+    }
+}
+
+impl<Eth: Clone> Clone for RethInclusion<Eth> {
+    fn clone(&self) -> Self {
+        Self { eth_api: self.eth_api.clone() }
+    }
+}
+
+/// A fee a transaction pays at most, and the recent trajectory of the fee it has to cover.
+#[derive(Debug)]
+struct FeeLimit<'a> {
+    /// The fees of recent blocks, oldest first, ending with the fee of the next block.
+    trajectory: &'a [u128],
+    /// The maximum fee of the transaction.
+    max_fee: u128,
+}
+
+impl FeeLimit<'_> {
+    /// Returns `true` if the transaction can afford the fee `offset` blocks after the next block,
+    /// if the fee changes like it did from block `start` onwards.
+    fn is_affordable(&self, start: usize, offset: usize) -> bool {
+        let last = self.trajectory.len() - 1;
+        let current = self.trajectory[last];
+        let (from, to) = (self.trajectory[start], self.trajectory[(start + offset).min(last)]);
+        let projected =
+            if from == 0 { current as f64 } else { current as f64 * to as f64 / from as f64 };
+        projected <= self.max_fee as f64
+    }
+}
+
+/// Returns the probabilities of inclusion within `1..=horizon` blocks of a transaction that
+/// fits into the pending transactions from the `first_block` after the next block onwards.
+///
+/// Every window of the fee trajectories is a sample of how the fees could evolve from the
+/// current fees.
+fn inclusion_probabilities(limits: &[FeeLimit<'_>], first_block: u64, horizon: u64) -> Vec<f64> {
+    let len = limits.iter().map(|limit| limit.trajectory.len()).min().unwrap_or_default();
+    (1..=horizon)
+        .map(|blocks| {
+            if len == 0 || first_block > blocks {
+                return 0.0
+            }
+
+            let samples = len.saturating_sub(blocks as usize) + 1;
+            let included = (0..samples)
+                .filter(|&start| {
+                    (first_block as usize - 1..blocks as usize)
+                        .any(|offset| limits.iter().all(|limit| limit.is_affordable(start, offset)))
+                })
+                .count();
+            included as f64 / samples as f64
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inclusion_probabilities_follow_fee_trajectory() {
+        let falling = [200, 180, 160, 140, 120, 100];
+
+        // the current fee is affordable right away
+        let limits = [FeeLimit { trajectory: &falling, max_fee: 100 }];
+        assert_eq!(inclusion_probabilities(&limits, 1, 3), vec![1.0, 1.0, 1.0]);
+
+        // the fee has to drop by 10%, which it did in every window
+        let limits = [FeeLimit { trajectory: &falling, max_fee: 90 }];
+        assert_eq!(inclusion_probabilities(&limits, 1, 3), vec![0.0, 1.0, 1.0]);
+
+        // the pending transactions ahead fill the next two blocks
+        assert_eq!(inclusion_probabilities(&limits, 3, 3), vec![0.0, 0.0, 1.0]);
+
+        // the blob fee only dropped in the most recent windows
+        let blob_fees = [100, 100, 100, 100, 90, 80];
+        let limits = [
+            FeeLimit { trajectory: &falling, max_fee: 90 },
+            FeeLimit { trajectory: &blob_fees, max_fee: 72 },
+        ];
+        assert_eq!(inclusion_probabilities(&limits, 1, 2), vec![0.0, 2.0 / 5.0]);
+    }
+}
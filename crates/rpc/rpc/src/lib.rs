@@ -37,6 +37,10 @@ mod aliases;
 mod debug;
 mod engine;
 pub mod eth;
+#[cfg(feature = "execution-stats")]
+pub mod execution_stats;
+mod inclusion;
+mod local_miner;
 mod miner;
 mod net;
 mod otterscan;
@@ -53,6 +57,10 @@ pub use aliases::*;
 pub use debug::DebugApi;
 pub use engine::{EngineApi, EngineEthApi};
 pub use eth::{helpers::SyncListener, EthApi, EthApiBuilder, EthBundle, EthFilter, EthPubSub};
+#[cfg(feature = "execution-stats")]
+pub use execution_stats::RethExecutionStats;
+pub use inclusion::RethInclusion;
+pub use local_miner::RethMiner;
 pub use miner::MinerApi;
 pub use net::NetApi;
 pub use otterscan::OtterscanApi;
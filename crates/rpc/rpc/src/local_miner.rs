@@ -0,0 +1,39 @@
+//! `reth_mine` implementation for the dev mode.
+
+use alloy_primitives::{B256, U64};
+use async_trait::async_trait;
+use jsonrpsee::core::RpcResult;
+use reth_engine_local::{LocalMinerHandle, MAX_MINE_BLOCKS};
+use reth_rpc_api::RethMinerApiServer;
+use reth_rpc_server_types::result::{internal_rpc_err, invalid_params_rpc_err};
+
+/// `reth_mine` implementation.
+///
+/// Mines blocks with the local miner of the dev mode that is attached to the
+/// [`LocalMinerHandle`].
+#[derive(Debug, Clone)]
+pub struct RethMiner {
+    /// Handle to the local miner.
+    miner: LocalMinerHandle,
+}
+
+impl RethMiner {
+    /// Create a new instance of the [`RethMiner`]
+    pub const fn new(miner: LocalMinerHandle) -> Self {
+        Self { miner }
+    }
+}
+
+#[async_trait]
+impl RethMinerApiServer for RethMiner {
+    /// Handler for `reth_mine`
+    async fn reth_mine(&self, blocks: Option<U64>) -> RpcResult<Vec<B256>> {
+        let blocks = blocks.map_or(1, |blocks| blocks.saturating_to());
+        if blocks > MAX_MINE_BLOCKS {
+            return Err(invalid_params_rpc_err(format!(
+                "can't mine more than {MAX_MINE_BLOCKS} blocks at once, requested {blocks}"
+            )))
+        }
+        self.miner.mine(blocks).await.map_err(|err| internal_rpc_err(err.to_string()))
+    }
+}
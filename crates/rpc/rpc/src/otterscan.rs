@@ -16,29 +16,53 @@ use reth_primitives_traits::TxTy;
 use reth_rpc_api::{EthApiServer, OtterscanServer};
 use reth_rpc_convert::RpcTxReq;
 use reth_rpc_eth_api::{
-    helpers::{EthTransactions, TraceExt},
-    FullEthApiTypes, RpcBlock, RpcHeader, RpcReceipt, RpcTransaction,
+    helpers::{EthTransactions, SpawnBlocking, TraceExt},
+    FromEthApiError, FullEthApiTypes, RpcBlock, RpcHeader, RpcReceipt, RpcTransaction,
 };
 use reth_rpc_eth_types::{utils::binary_search, EthApiError};
 use reth_rpc_server_types::result::internal_rpc_err;
+use reth_storage_api::ContractCreationsReader;
 use revm::context_interface::result::ExecutionResult;
 use revm_inspectors::{
     tracing::{types::CallTraceNode, TracingInspectorConfig},
     transfer::{TransferInspector, TransferKind},
 };
+use std::sync::Arc;
 
 const API_LEVEL: u64 = 8;
 
 /// Otterscan API.
-#[derive(Debug)]
 pub struct OtterscanApi<Eth> {
     eth: Eth,
+    /// Optional index of the contract creations, used to serve `ots_getContractCreator` without
+    /// tracing.
+    contract_creations: Option<Arc<dyn ContractCreationsReader>>,
 }
 
 impl<Eth> OtterscanApi<Eth> {
     /// Creates a new instance of `Otterscan`.
     pub const fn new(eth: Eth) -> Self {
-        Self { eth }
+        Self { eth, contract_creations: None }
+    }
+
+    /// Configures the contract creations index used by `ots_getContractCreator`.
+    ///
+    /// Contracts that aren't in the index are still found by tracing.
+    pub fn with_contract_creations(
+        mut self,
+        contract_creations: Arc<dyn ContractCreationsReader>,
+    ) -> Self {
+        self.contract_creations = Some(contract_creations);
+        self
+    }
+}
+
+impl<Eth> std::fmt::Debug for OtterscanApi<Eth>
+where
+    Eth: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OtterscanApi").field("eth", &self.eth).finish_non_exhaustive()
     }
 }
 
@@ -323,6 +347,22 @@ where
             return Ok(None);
         }
 
+        if let Some(index) = self.contract_creations.clone() {
+            let creation = self
+                .eth
+                .spawn_blocking_io(move |_| {
+                    index.contract_creation(address).map_err(Eth::Error::from_eth_err)
+                })
+                .await
+                .map_err(Into::into)?;
+            if let Some(creation) = creation {
+                return Ok(Some(ContractCreator {
+                    hash: creation.transaction_hash,
+                    creator: creation.creator,
+                }))
+            }
+        }
+
         let num = binary_search::<_, _, ErrorObjectOwned>(
             1,
             self.eth.block_number()?.saturating_to(),
@@ -1,18 +1,84 @@
-use std::{collections::HashMap, future::Future, sync::Arc};
+use std::{
+    collections::{BTreeMap, HashMap},
+    future::Future,
+    ops::RangeInclusive,
+    sync::Arc,
+};
 
-use alloy_eips::BlockId;
-use alloy_primitives::{Address, U256};
+use alloy_consensus::{transaction::TxHashRef, BlockHeader};
+use alloy_eips::{eip2718::Encodable2718, BlockId};
+use alloy_primitives::{Address, BlockNumber, Bytes, B256, U256};
+use alloy_rpc_types_eth::EIP1186AccountProofResponse;
+use alloy_serde::JsonStorageKey;
 use async_trait::async_trait;
 use futures::{Stream, StreamExt};
-use jsonrpsee::{core::RpcResult, PendingSubscriptionSink, SubscriptionMessage, SubscriptionSink};
-use reth_chain_state::{CanonStateSubscriptions, PersistedBlockSubscriptions};
+use jsonrpsee::{
+    core::{RpcResult, SubscriptionError},
+    types::ErrorObject,
+    PendingSubscriptionSink, SubscriptionMessage, SubscriptionSink,
+};
+use reth_chain_state::{
+    BlockTimingsProvider, CanonStateNotifications, CanonStateSubscriptions, ChainStatsEntry,
+    ChainStatsPeriod, ChainStatsProvider, EpochTimingSummary, PersistedBlockSubscriptions,
+    StageProgress, SyncMode, SyncProgressProvider,
+};
 use reth_errors::RethResult;
-use reth_rpc_api::RethApiServer;
+use reth_primitives_traits::{
+    Account, Block, BlockBody, NodePrimitives, Receipt, ReceiptWithBloom,
+};
+use reth_prune_types::PruneSegment;
+use reth_revm::db::BundleState;
+use reth_rpc_api::{
+    AccountDestructionEntry, AccountInfoChange, AccountProofsUpdate, AccountState,
+    AccountStateChanges, BlockFieldSelection, BlockFields, BlockStateChanges, BlockWithdrawals,
+    ChainStatsReport, ContractCreationEntry, EpochTimingReport, ReorgEntry, RethApiServer,
+    StageSyncStatus, StatsPeriod, StorageSlotChange, SyncStatusMode, SyncStatusReport,
+    WatchedAccount,
+};
 use reth_rpc_eth_types::{EthApiError, EthResult};
-use reth_storage_api::{BlockReaderIdExt, ChangeSetReader, StateProviderFactory};
+use reth_storage_api::{
+    AccountDestructionsReader, BlockNumReader, BlockReaderIdExt, ChangeSetReader,
+    ContractCreationsReader, PruneCheckpointReader, ReorgHistoryReader, StageCheckpointReader,
+    StateProofProvider, StateProviderFactory, StorageChangeSetReader, WithdrawalsProvider,
+};
 use reth_tasks::TaskSpawner;
 use serde::Serialize;
-use tokio::sync::oneshot;
+use tokio::sync::{broadcast::error::RecvError, oneshot, Semaphore};
+
+/// The number of blocks read at once when streaming raw block ranges.
+const RAW_RANGE_CHUNK_SIZE: u64 = 100;
+
+/// The maximum number of blocks returned by a single `reth_getBlocksByRange` call.
+const MAX_BLOCKS_BY_RANGE: u64 = 1_000;
+
+/// The maximum number of blocks returned by a single `reth_getStateChanges` call.
+const MAX_STATE_CHANGES_RANGE: u64 = 100;
+
+/// The maximum number of blocks that can be queried by a single `reth_getWithdrawals` call.
+const MAX_WITHDRAWALS_RANGE: u64 = 10_000;
+
+/// The default number of entries returned by `reth_reorgHistory`.
+const DEFAULT_REORG_HISTORY_LIMIT: usize = 100;
+
+/// The maximum number of entries returned by a single `reth_reorgHistory` call.
+const MAX_REORG_HISTORY_LIMIT: usize = 1_000;
+
+/// The default number of epochs returned by `reth_timingReport`.
+const DEFAULT_TIMING_REPORT_EPOCHS: usize = 4;
+
+/// The default number of periods returned by `reth_chainStats`.
+const DEFAULT_CHAIN_STATS_LIMIT: usize = 30;
+
+/// The maximum number of accounts watched by a single `reth_subscribeAccountProofs`
+/// subscription.
+const MAX_WATCHED_ACCOUNTS: usize = 100;
+
+/// The maximum number of storage keys watched by a single `reth_subscribeAccountProofs`
+/// subscription, across all accounts.
+const MAX_WATCHED_STORAGE_KEYS: usize = 1_000;
+
+/// The maximum number of concurrent `reth_subscribeAccountProofs` subscriptions.
+const MAX_ACCOUNT_PROOF_SUBSCRIPTIONS: usize = 100;
 
 /// `reth` API implementation.
 ///
@@ -31,14 +97,27 @@ impl<Provider> RethApi<Provider> {
 
     /// Create a new instance of the [`RethApi`]
     pub fn new(provider: Provider, task_spawner: Box<dyn TaskSpawner>) -> Self {
-        let inner = Arc::new(RethApiInner { provider, task_spawner });
+        let inner = Arc::new(RethApiInner {
+            provider,
+            task_spawner,
+            account_proof_subscriptions: Arc::new(Semaphore::new(MAX_ACCOUNT_PROOF_SUBSCRIPTIONS)),
+        });
         Self { inner }
     }
 }
 
 impl<Provider> RethApi<Provider>
 where
-    Provider: BlockReaderIdExt + ChangeSetReader + StateProviderFactory + 'static,
+    Provider: BlockReaderIdExt
+        + ChangeSetReader
+        + StorageChangeSetReader
+        + PruneCheckpointReader
+        + StateProviderFactory
+        + WithdrawalsProvider
+        + ReorgHistoryReader
+        + ContractCreationsReader
+        + AccountDestructionsReader
+        + 'static,
 {
     /// Executes the future on a new blocking task.
     async fn on_blocking_task<C, F, R>(&self, c: C) -> EthResult<R>
@@ -86,6 +165,409 @@ where
         )?;
         Ok(hash_map)
     }
+
+    /// Returns the selected fields of all blocks in the given range.
+    pub async fn blocks_by_range(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+        fields: BlockFieldSelection,
+    ) -> EthResult<Vec<BlockFields>> {
+        self.on_blocking_task(|this| async move { this.try_blocks_by_range(from, to, fields) })
+            .await
+    }
+
+    fn try_blocks_by_range(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+        fields: BlockFieldSelection,
+    ) -> EthResult<Vec<BlockFields>> {
+        let range = self.raw_range(from, to)?;
+        if to - from >= MAX_BLOCKS_BY_RANGE {
+            return Err(EthApiError::InvalidParams(format!(
+                "block range too large, max {MAX_BLOCKS_BY_RANGE} blocks"
+            )))
+        }
+
+        let mut blocks = range
+            .clone()
+            .map(|number| BlockFields { number, ..Default::default() })
+            .collect::<Vec<_>>();
+
+        // Headers and transactions are only read if any of their fields were selected, headers
+        // are served directly from static files. Entries are paired with their block by number,
+        // so that a block missing from the read range doesn't shift the entries of the following
+        // blocks.
+        let position = |blocks: &[BlockFields], number| {
+            blocks.binary_search_by_key(&number, |block: &BlockFields| block.number).ok()
+        };
+        if fields.requires_header() {
+            let headers = self.provider().sealed_headers_range(range.clone())?;
+            for header in headers {
+                let Some(index) = position(&blocks, header.number()) else { continue };
+                let block = &mut blocks[index];
+                block.hash = fields.hash.then(|| header.hash());
+                block.parent_hash = fields.parent_hash.then(|| header.parent_hash());
+                block.timestamp = fields.timestamp.then(|| header.timestamp());
+                block.gas_used = fields.gas_used.then(|| header.gas_used());
+                block.gas_limit = fields.gas_limit.then(|| header.gas_limit());
+                block.base_fee_per_gas =
+                    fields.base_fee_per_gas.then(|| header.base_fee_per_gas()).flatten();
+                block.miner = fields.miner.then(|| header.beneficiary());
+            }
+        }
+
+        if fields.transactions {
+            for body in self.provider().block_range(range)? {
+                let Some(index) = position(&blocks, body.header().number()) else { continue };
+                blocks[index].transactions =
+                    Some(body.body().transactions().iter().map(|tx| *tx.tx_hash()).collect());
+            }
+        }
+
+        Ok(blocks)
+    }
+
+    /// Returns the account and storage changes of all blocks in the given range.
+    pub async fn state_changes(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+    ) -> EthResult<Vec<BlockStateChanges>> {
+        self.on_blocking_task(|this| async move { this.try_state_changes(from, to) }).await
+    }
+
+    fn try_state_changes(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+    ) -> EthResult<Vec<BlockStateChanges>> {
+        let range = self.raw_range(from, to)?;
+        if to - from >= MAX_STATE_CHANGES_RANGE {
+            return Err(EthApiError::InvalidParams(format!(
+                "block range too large, max {MAX_STATE_CHANGES_RANGE} blocks"
+            )))
+        }
+
+        // The changesets of pruned blocks were deleted, so their changes can't be served.
+        for segment in [PruneSegment::AccountHistory, PruneSegment::StorageHistory] {
+            if let Some(checkpoint) = self.provider().get_prune_checkpoint(segment)? &&
+                checkpoint.block_number.is_some_and(|pruned| from <= pruned)
+            {
+                return Err(EthApiError::PrunedHistoryUnavailable)
+            }
+        }
+
+        // Changesets only contain the values from before each block, the values after a block
+        // are the values before the next block that changed them, or the values at the end of
+        // the range.
+        let state = self.provider().state_by_block_id(to.into())?;
+
+        let mut blocks = range
+            .clone()
+            .map(|number| (number, BTreeMap::<Address, AccountStateChanges>::new()))
+            .collect::<BTreeMap<_, _>>();
+        for (number, account) in self.provider().account_changesets_range(range.clone())? {
+            if let Some(accounts) = blocks.get_mut(&number) {
+                accounts.entry(account.address).or_default().info = Some(AccountInfoChange {
+                    before: account.info.map(account_state),
+                    after: None,
+                });
+            }
+        }
+        for (block_address, entry) in self.provider().storage_changesets_range(range)? {
+            if let Some(accounts) = blocks.get_mut(&block_address.block_number()) {
+                accounts.entry(block_address.address()).or_default().storage.push(
+                    StorageSlotChange { key: entry.key, before: entry.value, after: U256::ZERO },
+                );
+            }
+        }
+
+        let mut accounts_after = HashMap::<Address, Option<AccountState>>::default();
+        let mut storage_after = HashMap::<(Address, B256), U256>::default();
+        let mut changes = Vec::with_capacity(blocks.len());
+        for (block_number, accounts) in blocks.into_iter().rev() {
+            let mut block = BlockStateChanges { block_number, accounts: Vec::new() };
+            for (address, mut account) in accounts {
+                account.address = address;
+                if let Some(info) = &mut account.info {
+                    info.after = match accounts_after.get(&address) {
+                        Some(after) => *after,
+                        None => state.basic_account(&address)?.map(account_state),
+                    };
+                    accounts_after.insert(address, info.before);
+                }
+
+                account.storage.sort_unstable_by_key(|slot| slot.key);
+                for slot in &mut account.storage {
+                    slot.after = match storage_after.get(&(address, slot.key)) {
+                        Some(after) => *after,
+                        None => state.storage(address, slot.key)?.unwrap_or_default(),
+                    };
+                    storage_after.insert((address, slot.key), slot.before);
+                }
+
+                block.accounts.push(account);
+            }
+            changes.push(block);
+        }
+        changes.reverse();
+
+        Ok(changes)
+    }
+
+    /// Returns the withdrawals of all blocks in the given range that contain withdrawals.
+    pub async fn withdrawals(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+    ) -> EthResult<Vec<BlockWithdrawals>> {
+        self.on_blocking_task(|this| async move { this.try_withdrawals(from, to) }).await
+    }
+
+    fn try_withdrawals(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+    ) -> EthResult<Vec<BlockWithdrawals>> {
+        let range = self.raw_range(from, to)?;
+        if to - from >= MAX_WITHDRAWALS_RANGE {
+            return Err(EthApiError::InvalidParams(format!(
+                "block range too large, max {MAX_WITHDRAWALS_RANGE} blocks"
+            )))
+        }
+
+        let withdrawals = self.provider().withdrawals_by_block_range(range.clone())?;
+        Ok(range
+            .zip(withdrawals)
+            .filter(|(_, withdrawals)| !withdrawals.is_empty())
+            .map(|(block_number, withdrawals)| BlockWithdrawals {
+                block_number,
+                withdrawals: withdrawals.into_inner(),
+            })
+            .collect())
+    }
+
+    /// Returns the most recent reorgs performed by the node, newest first.
+    pub async fn reorg_history(&self, limit: Option<usize>) -> EthResult<Vec<ReorgEntry>> {
+        self.on_blocking_task(|this| async move { this.try_reorg_history(limit) }).await
+    }
+
+    fn try_reorg_history(&self, limit: Option<usize>) -> EthResult<Vec<ReorgEntry>> {
+        let limit = limit.unwrap_or(DEFAULT_REORG_HISTORY_LIMIT);
+        if limit > MAX_REORG_HISTORY_LIMIT {
+            return Err(EthApiError::InvalidParams(format!(
+                "limit too large, max {MAX_REORG_HISTORY_LIMIT} entries"
+            )))
+        }
+
+        Ok(self
+            .provider()
+            .reorg_history(limit)?
+            .into_iter()
+            .map(|record| ReorgEntry {
+                timestamp: record.timestamp,
+                old_head: record.old_head,
+                old_head_number: record.old_head_number,
+                old_head_timestamp: record.old_head_timestamp,
+                new_head: record.new_head,
+                new_head_number: record.new_head_number,
+                new_head_timestamp: record.new_head_timestamp,
+                depth: record.depth,
+                reverted_transactions: record.reverted_transactions,
+                applied_transactions: record.applied_transactions,
+            })
+            .collect())
+    }
+
+    /// Returns the most recent creation of the contract at the given address, if it's indexed.
+    pub async fn contract_creation(
+        &self,
+        address: Address,
+    ) -> EthResult<Option<ContractCreationEntry>> {
+        self.on_blocking_task(|this| async move {
+            Ok(this.provider().contract_creation(address)?.map(|creation| ContractCreationEntry {
+                block_number: creation.block_number,
+                transaction_hash: creation.transaction_hash,
+                creator: creation.creator,
+                code_hash: creation.code_hash,
+                salt: creation.salt,
+            }))
+        })
+        .await
+    }
+
+    /// Returns the indexed removals of the account at the given address, oldest first.
+    pub async fn account_destructions(
+        &self,
+        address: Address,
+    ) -> EthResult<Vec<AccountDestructionEntry>> {
+        self.on_blocking_task(|this| async move {
+            Ok(this
+                .provider()
+                .account_destructions(address)?
+                .into_iter()
+                .map(|destruction| AccountDestructionEntry {
+                    block_number: destruction.block_number,
+                    transaction_hash: destruction.transaction_hash,
+                    self_destruct: destruction.is_self_destruct(),
+                    beneficiary: destruction.beneficiary,
+                })
+                .collect())
+        })
+        .await
+    }
+
+    /// Validates the requested raw range against the best block.
+    fn raw_range(&self, from: BlockNumber, to: BlockNumber) -> EthResult<RangeInclusive<u64>> {
+        if from > to {
+            return Err(EthApiError::InvalidBlockRange)
+        }
+        let best_block = self.provider().best_block_number()?;
+        if to > best_block {
+            return Err(EthApiError::HeaderNotFound(to.into()))
+        }
+        Ok(from..=to)
+    }
+
+    /// Reads the range in chunks on blocking tasks with `read` and pipes all items to the
+    /// subscription sink.
+    ///
+    /// Blocks that expired from the static files, or that are missing, fail the read instead of
+    /// being skipped. A failed read closes the subscription with an error notification, so that
+    /// clients can tell a failed export apart from a complete one.
+    async fn pipe_range<T, F>(
+        self,
+        sink: SubscriptionSink,
+        range: RangeInclusive<u64>,
+        read: F,
+    ) -> jsonrpsee::core::SubscriptionResult
+    where
+        T: Serialize + Send + 'static,
+        F: Fn(&Provider, RangeInclusive<BlockNumber>) -> EthResult<Vec<T>> + Clone + Send + 'static,
+    {
+        let (start, end) = range.into_inner();
+        let mut chunk_start = start;
+        while chunk_start <= end {
+            let chunk_end = chunk_start.saturating_add(RAW_RANGE_CHUNK_SIZE - 1).min(end);
+            let chunk = chunk_start..=chunk_end;
+
+            let read = read.clone();
+            let items = match self
+                .on_blocking_task(|this| async move {
+                    let provider = this.provider();
+                    if chunk_start < provider.earliest_block_number()? {
+                        return Err(EthApiError::PrunedHistoryUnavailable)
+                    }
+                    let items = read(provider, chunk)?;
+                    if items.len() as u64 != chunk_end - chunk_start + 1 {
+                        let missing = chunk_start + items.len() as u64;
+                        return Err(EthApiError::HeaderNotFound(missing.into()))
+                    }
+                    Ok(items)
+                })
+                .await
+            {
+                Ok(items) => items,
+                Err(err) => {
+                    tracing::debug!(target: "rpc::reth", %err, "Failed to read raw range");
+                    let err = ErrorObject::from(err);
+                    return Err(SubscriptionError::from_json(serde_json::value::to_raw_value(&err)?))
+                }
+            };
+
+            for item in items {
+                let msg =
+                    SubscriptionMessage::new(sink.method_name(), sink.subscription_id(), &item)?;
+                if sink.send(msg).await.is_err() {
+                    return Ok(())
+                }
+            }
+
+            if chunk_end == u64::MAX {
+                break
+            }
+            chunk_start = chunk_end + 1;
+        }
+        Ok(())
+    }
+
+    /// Computes the proofs of the given accounts at the block with the given hash.
+    fn account_proofs(
+        &self,
+        block_hash: B256,
+        accounts: Vec<WatchedAccount>,
+    ) -> EthResult<Vec<EIP1186AccountProofResponse>> {
+        let state = self.provider().state_by_block_hash(block_hash)?;
+        accounts
+            .into_iter()
+            .map(|WatchedAccount { address, storage_keys }| {
+                let keys = storage_keys.iter().map(JsonStorageKey::as_b256).collect::<Vec<_>>();
+                let proof = state.proof(Default::default(), address, &keys)?;
+                Ok(proof.into_eip1186_response(storage_keys))
+            })
+            .collect()
+    }
+
+    /// Sends the proofs of the watched accounts whose account info or watched storage slots
+    /// changed in a canonical state notification to the subscription sink.
+    ///
+    /// If notifications were skipped because the subscription lags behind, the proofs of all
+    /// watched accounts are sent with the next notification. Returns an error if the proofs can't
+    /// be sent, which closes the subscription with an error notification.
+    async fn pipe_account_proofs<N: NodePrimitives>(
+        self,
+        sink: SubscriptionSink,
+        mut notifications: CanonStateNotifications<N>,
+        accounts: Vec<WatchedAccount>,
+    ) -> jsonrpsee::core::SubscriptionResult {
+        let mut lagged = false;
+        loop {
+            let notification = tokio::select! {
+                _ = sink.closed() => break,
+                notification = notifications.recv() => match notification {
+                    Ok(notification) => notification,
+                    Err(RecvError::Lagged(skipped)) => {
+                        tracing::debug!(target: "rpc::reth", skipped, "Account proofs subscription lagged, sending proofs of all watched accounts");
+                        lagged = true;
+                        continue
+                    }
+                    Err(RecvError::Closed) => break,
+                }
+            };
+
+            let changed = if std::mem::take(&mut lagged) {
+                accounts.clone()
+            } else {
+                // accounts changed by reverted blocks differ at the new tip as well
+                let chains = std::iter::once(notification.committed())
+                    .chain(notification.reverted())
+                    .collect::<Vec<_>>();
+                let bundles = chains
+                    .iter()
+                    .map(|chain| &chain.execution_outcome().bundle)
+                    .collect::<Vec<_>>();
+                changed_watched_accounts(&accounts, &bundles)
+            };
+            if changed.is_empty() {
+                continue
+            }
+
+            let tip = notification.tip().num_hash();
+            let proofs = self
+                .on_blocking_task(|this| async move { this.account_proofs(tip.hash, changed) })
+                .await?;
+
+            let update =
+                AccountProofsUpdate { block_number: tip.number, block_hash: tip.hash, proofs };
+            let msg =
+                SubscriptionMessage::new(sink.method_name(), sink.subscription_id(), &update)?;
+            if sink.send(msg).await.is_err() {
+                break
+            }
+        }
+    }
 }
 
 #[async_trait]
@@ -93,10 +575,21 @@ impl<Provider> RethApiServer for RethApi<Provider>
 where
     Provider: BlockReaderIdExt
         + ChangeSetReader
+        + StorageChangeSetReader
+        + PruneCheckpointReader
         + StateProviderFactory
+        + WithdrawalsProvider
+        + ReorgHistoryReader
+        + ContractCreationsReader
+        + AccountDestructionsReader
         + CanonStateSubscriptions
         + PersistedBlockSubscriptions
+        + BlockTimingsProvider
+        + ChainStatsProvider
+        + StageCheckpointReader
+        + SyncProgressProvider
         + 'static,
+    Provider::Receipt: Receipt,
 {
     /// Handler for `reth_getBalanceChangesInBlock`
     async fn reth_get_balance_changes_in_block(
@@ -106,6 +599,127 @@ where
         Ok(Self::balance_changes_in_block(self, block_id).await?)
     }
 
+    /// Handler for `reth_getBlocksByRange`
+    async fn reth_get_blocks_by_range(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+        fields: BlockFieldSelection,
+    ) -> RpcResult<Vec<BlockFields>> {
+        Ok(Self::blocks_by_range(self, from, to, fields).await?)
+    }
+
+    /// Handler for `reth_getStateChanges`
+    async fn reth_get_state_changes(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+    ) -> RpcResult<Vec<BlockStateChanges>> {
+        Ok(Self::state_changes(self, from, to).await?)
+    }
+
+    /// Handler for `reth_getWithdrawals`
+    async fn reth_get_withdrawals(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+    ) -> RpcResult<Vec<BlockWithdrawals>> {
+        Ok(Self::withdrawals(self, from, to).await?)
+    }
+
+    /// Handler for `reth_reorgHistory`
+    async fn reth_reorg_history(&self, limit: Option<usize>) -> RpcResult<Vec<ReorgEntry>> {
+        Ok(Self::reorg_history(self, limit).await?)
+    }
+
+    /// Handler for `reth_getContractCreation`
+    async fn reth_get_contract_creation(
+        &self,
+        address: Address,
+    ) -> RpcResult<Option<ContractCreationEntry>> {
+        Ok(Self::contract_creation(self, address).await?)
+    }
+
+    /// Handler for `reth_getAccountDestructions`
+    async fn reth_get_account_destructions(
+        &self,
+        address: Address,
+    ) -> RpcResult<Vec<AccountDestructionEntry>> {
+        Ok(Self::account_destructions(self, address).await?)
+    }
+
+    /// Handler for `reth_timingReport`
+    async fn reth_timing_report(&self, epochs: Option<usize>) -> RpcResult<Vec<EpochTimingReport>> {
+        let epochs = epochs.unwrap_or(DEFAULT_TIMING_REPORT_EPOCHS);
+        Ok(self
+            .provider()
+            .block_timings()
+            .epoch_summaries(epochs)
+            .into_iter()
+            .map(epoch_timing_report)
+            .collect())
+    }
+
+    /// Handler for `reth_chainStats`
+    async fn reth_chain_stats(
+        &self,
+        period: StatsPeriod,
+        limit: Option<usize>,
+    ) -> RpcResult<Vec<ChainStatsReport>> {
+        let period = match period {
+            StatsPeriod::Day => ChainStatsPeriod::Day,
+            StatsPeriod::Epoch => ChainStatsPeriod::Epoch,
+        };
+        let stats = self.provider().chain_stats();
+        Ok(stats
+            .entries(period, limit.unwrap_or(DEFAULT_CHAIN_STATS_LIMIT))
+            .into_iter()
+            .map(|(number, entry)| {
+                chain_stats_report(number, stats.period_start(period, number), entry)
+            })
+            .collect())
+    }
+
+    /// Handler for `reth_syncStatus`
+    async fn reth_sync_status(&self) -> RpcResult<SyncStatusReport> {
+        let current_block = self.provider().best_block_number().map_err(EthApiError::from)?;
+        let checkpoints = self.provider().get_all_checkpoints().map_err(EthApiError::from)?;
+        let sync_progress = self.provider().sync_progress();
+
+        // the stored checkpoints only lag behind the progress reported by the pipeline
+        let mut stages = checkpoints
+            .into_iter()
+            .map(|(name, checkpoint)| {
+                let entities = checkpoint.entities();
+                StageSyncStatus {
+                    name,
+                    checkpoint: checkpoint.block_number,
+                    target: None,
+                    entities_processed: entities.map(|entities| entities.processed),
+                    entities_total: entities.map(|entities| entities.total),
+                    eta_seconds: None,
+                }
+            })
+            .collect::<Vec<_>>();
+        for stage in sync_progress.stages().into_iter().map(stage_sync_status) {
+            match stages.iter_mut().find(|stored| stored.name == stage.name) {
+                Some(stored) => *stored = stage,
+                None => stages.push(stage),
+            }
+        }
+
+        Ok(SyncStatusReport {
+            mode: match sync_progress.mode() {
+                SyncMode::Live => SyncStatusMode::Live,
+                SyncMode::Backfill => SyncStatusMode::Backfill,
+            },
+            current_block,
+            target_block: sync_progress.target(),
+            current_stage: sync_progress.current_stage().map(|stage_id| stage_id.to_string()),
+            stages,
+        })
+    }
+
     /// Handler for `reth_subscribeChainNotifications`
     async fn reth_subscribe_chain_notifications(
         &self,
@@ -129,6 +743,178 @@ where
 
         Ok(())
     }
+
+    /// Handler for `reth_subscribeRawBlockRange`
+    async fn reth_subscribe_raw_block_range(
+        &self,
+        pending: PendingSubscriptionSink,
+        from: BlockNumber,
+        to: BlockNumber,
+    ) -> jsonrpsee::core::SubscriptionResult {
+        let range = self.raw_range(from, to)?;
+        let sink = pending.accept().await?;
+        self.clone()
+            .pipe_range(sink, range, |provider, chunk| {
+                Ok(provider
+                    .block_range(chunk)?
+                    .into_iter()
+                    .map(|block| Bytes::from(alloy_rlp::encode(block)))
+                    .collect())
+            })
+            .await
+    }
+
+    /// Handler for `reth_subscribeRawReceiptsRange`
+    async fn reth_subscribe_raw_receipts_range(
+        &self,
+        pending: PendingSubscriptionSink,
+        from: BlockNumber,
+        to: BlockNumber,
+    ) -> jsonrpsee::core::SubscriptionResult {
+        let range = self.raw_range(from, to)?;
+        let sink = pending.accept().await?;
+        self.clone()
+            .pipe_range(sink, range, |provider, chunk| {
+                for segment in [PruneSegment::Receipts, PruneSegment::ContractLogs] {
+                    if let Some(checkpoint) = provider.get_prune_checkpoint(segment)? &&
+                        checkpoint.block_number.is_some_and(|pruned| *chunk.start() <= pruned)
+                    {
+                        return Err(EthApiError::PrunedHistoryUnavailable)
+                    }
+                }
+                Ok(provider
+                    .receipts_by_block_range(chunk)?
+                    .into_iter()
+                    .map(|receipts| {
+                        receipts
+                            .into_iter()
+                            .map(|receipt| {
+                                Bytes::from(ReceiptWithBloom::from(receipt).encoded_2718())
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                    .collect())
+            })
+            .await
+    }
+
+    /// Handler for `reth_subscribeAccountProofs`
+    async fn reth_subscribe_account_proofs(
+        &self,
+        pending: PendingSubscriptionSink,
+        accounts: Vec<WatchedAccount>,
+    ) -> jsonrpsee::core::SubscriptionResult {
+        validate_watched_accounts(&accounts)?;
+        let Ok(_permit) = self.inner.account_proof_subscriptions.clone().try_acquire_owned() else {
+            return Err(format!(
+                "too many account proof subscriptions, max {MAX_ACCOUNT_PROOF_SUBSCRIPTIONS} subscriptions"
+            )
+            .into())
+        };
+
+        let sink = pending.accept().await?;
+        let notifications = self.provider().subscribe_to_canonical_state();
+        self.clone().pipe_account_proofs(sink, notifications, accounts).await
+    }
+}
+
+/// Converts the epoch timing summary into its RPC representation.
+const fn epoch_timing_report(summary: EpochTimingSummary) -> EpochTimingReport {
+    EpochTimingReport {
+        epoch: summary.epoch,
+        blocks: summary.blocks,
+        avg_received_delay_ms: summary.avg_received_delay_ms,
+        max_received_delay_ms: summary.max_received_delay_ms,
+        avg_executed_delay_ms: summary.avg_executed_delay_ms,
+        max_executed_delay_ms: summary.max_executed_delay_ms,
+        min_deadline_margin_ms: summary.min_deadline_margin_ms,
+        missed_deadlines: summary.missed_deadlines,
+    }
+}
+
+/// Converts the progress reported by a pipeline stage into its RPC representation.
+fn stage_sync_status(stage: StageProgress) -> StageSyncStatus {
+    let entities = stage.entities();
+    StageSyncStatus {
+        name: stage.stage_id.to_string(),
+        checkpoint: stage.checkpoint.block_number,
+        target: stage.target,
+        entities_processed: entities.map(|entities| entities.processed),
+        entities_total: entities.map(|entities| entities.total),
+        eta_seconds: stage.eta.map(|eta| eta.as_secs()),
+    }
+}
+
+/// Converts the statistics of a day or an epoch into their RPC representation.
+const fn chain_stats_report(
+    number: u64,
+    start_timestamp: u64,
+    entry: ChainStatsEntry,
+) -> ChainStatsReport {
+    ChainStatsReport {
+        number,
+        start_timestamp,
+        blocks: entry.blocks,
+        transactions: entry.transactions,
+        gas_used: entry.gas_used,
+        gas_limit: entry.gas_limit,
+        avg_base_fee_per_gas: entry.avg_base_fee_per_gas(),
+        blob_gas_used: entry.blob_gas_used,
+        new_accounts: entry.state_growth.accounts,
+        new_storage_slots: entry.state_growth.storage_slots,
+    }
+}
+
+/// Validates the accounts watched by a `reth_subscribeAccountProofs` subscription.
+fn validate_watched_accounts(accounts: &[WatchedAccount]) -> EthResult<()> {
+    if accounts.is_empty() {
+        return Err(EthApiError::InvalidParams("no accounts to watch".to_string()))
+    }
+    if accounts.len() > MAX_WATCHED_ACCOUNTS {
+        return Err(EthApiError::InvalidParams(format!(
+            "too many accounts, max {MAX_WATCHED_ACCOUNTS} accounts"
+        )))
+    }
+    let storage_keys = accounts.iter().map(|account| account.storage_keys.len()).sum::<usize>();
+    if storage_keys > MAX_WATCHED_STORAGE_KEYS {
+        return Err(EthApiError::InvalidParams(format!(
+            "too many storage keys, max {MAX_WATCHED_STORAGE_KEYS} keys"
+        )))
+    }
+    Ok(())
+}
+
+/// Returns the watched accounts whose account info or watched storage slots changed in any of the
+/// bundles.
+fn changed_watched_accounts(
+    accounts: &[WatchedAccount],
+    bundles: &[&BundleState],
+) -> Vec<WatchedAccount> {
+    accounts
+        .iter()
+        .filter(|watched| {
+            bundles.iter().filter_map(|bundle| bundle.account(&watched.address)).any(|account| {
+                account.is_info_changed() ||
+                    account.was_destroyed() ||
+                    watched.storage_keys.iter().any(|key| {
+                        account
+                            .storage
+                            .get(&U256::from_be_bytes(key.as_b256().0))
+                            .is_some_and(|slot| slot.is_changed())
+                    })
+            })
+        })
+        .cloned()
+        .collect()
+}
+
+/// Converts the account into its RPC representation.
+fn account_state(account: Account) -> AccountState {
+    AccountState {
+        nonce: account.nonce,
+        balance: account.balance,
+        code_hash: account.get_bytecode_hash(),
+    }
 }
 
 /// Pipes all stream items to the subscription sink.
@@ -178,4 +964,284 @@ struct RethApiInner<Provider> {
     provider: Provider,
     /// The type that can spawn tasks which would otherwise block.
     task_spawner: Box<dyn TaskSpawner>,
+    /// Permits for `reth_subscribeAccountProofs` subscriptions.
+    account_proof_subscriptions: Arc<Semaphore>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{Header, SignableTransaction, TxLegacy};
+    use alloy_eips::eip4895::{Withdrawal, Withdrawals};
+    use alloy_primitives::Signature;
+    use reth_ethereum_primitives::{Block, BlockBody, TransactionSigned};
+    use reth_provider::{
+        providers::{BlockchainProvider, ProviderFactory},
+        test_utils::{
+            create_test_provider_factory, BlockchainTestData, MockEthProvider, MockNodeTypesWithDB,
+        },
+        BlockWriter, ExecutionOutcome, OriginalValuesKnown, PruneCheckpointWriter,
+        StageCheckpointWriter, StateWriteConfig, StateWriter,
+    };
+    use reth_prune_types::{PruneCheckpoint, PruneMode};
+    use reth_revm::{
+        db::{states::StorageSlot, AccountStatus, BundleAccount},
+        state::AccountInfo,
+    };
+    use reth_stages_types::{StageCheckpoint, StageId};
+    use reth_tasks::TokioTaskExecutor;
+
+    fn watched(address: Address, storage_keys: &[u64]) -> WatchedAccount {
+        WatchedAccount {
+            address,
+            storage_keys: storage_keys
+                .iter()
+                .map(|key| JsonStorageKey::from(B256::from(U256::from(*key))))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn changed_watched_accounts_only_matches_watched_keys() {
+        let info = AccountInfo { nonce: 1, ..Default::default() };
+        let account = |storage: &[(u64, u64, u64)]| {
+            BundleAccount::new(
+                Some(info.clone()),
+                Some(info.clone()),
+                storage
+                    .iter()
+                    .map(|(key, original, present)| {
+                        (
+                            U256::from(*key),
+                            StorageSlot::new_changed(U256::from(*original), U256::from(*present)),
+                        )
+                    })
+                    .collect(),
+                AccountStatus::Changed,
+            )
+        };
+        let (storage_changed, info_changed, untouched) =
+            (Address::with_last_byte(1), Address::with_last_byte(2), Address::with_last_byte(3));
+
+        let mut bundle = BundleState::default();
+        bundle.state.insert(storage_changed, account(&[(1, 0, 1), (2, 5, 5)]));
+        let mut changed_info = account(&[]);
+        changed_info.info = Some(AccountInfo { nonce: 2, ..Default::default() });
+        bundle.state.insert(info_changed, changed_info);
+
+        let changed = |accounts: &[WatchedAccount]| {
+            changed_watched_accounts(accounts, &[&bundle])
+                .into_iter()
+                .map(|account| account.address)
+                .collect::<Vec<_>>()
+        };
+
+        // only changes of watched storage slots count
+        assert_eq!(changed(&[watched(storage_changed, &[1])]), vec![storage_changed]);
+        assert!(changed(&[watched(storage_changed, &[2, 3])]).is_empty());
+        assert!(changed(&[watched(storage_changed, &[])]).is_empty());
+
+        // account info changes always count
+        assert_eq!(changed(&[watched(info_changed, &[])]), vec![info_changed]);
+        assert!(changed(&[watched(untouched, &[1])]).is_empty());
+    }
+
+    #[test]
+    fn validate_watched_accounts_limits() {
+        assert!(validate_watched_accounts(&[]).is_err());
+        assert!(validate_watched_accounts(&[watched(Address::ZERO, &[1, 2])]).is_ok());
+
+        let accounts = (0..=MAX_WATCHED_ACCOUNTS as u64)
+            .map(|i| watched(Address::with_last_byte(i as u8), &[]))
+            .collect::<Vec<_>>();
+        assert!(validate_watched_accounts(&accounts).is_err());
+
+        let keys = (0..=MAX_WATCHED_STORAGE_KEYS as u64).collect::<Vec<_>>();
+        assert!(validate_watched_accounts(&[watched(Address::ZERO, &keys)]).is_err());
+    }
+
+    /// Returns a provider factory with the genesis and the first two blocks of the
+    /// [`BlockchainTestData`] persisted.
+    fn factory_with_blocks() -> (ProviderFactory<MockNodeTypesWithDB>, BlockchainTestData) {
+        let factory = create_test_provider_factory();
+        let data = BlockchainTestData::default();
+
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw.insert_block(&data.genesis.clone().try_recover().unwrap()).unwrap();
+        provider_rw
+            .write_state(
+                &ExecutionOutcome { first_block: 0, receipts: vec![vec![]], ..Default::default() },
+                OriginalValuesKnown::No,
+                StateWriteConfig::default(),
+            )
+            .unwrap();
+        for (block, outcome) in &data.blocks[..2] {
+            provider_rw.insert_block(block).unwrap();
+            provider_rw
+                .write_state(outcome, OriginalValuesKnown::No, StateWriteConfig::default())
+                .unwrap();
+        }
+        provider_rw.save_stage_checkpoint(StageId::Finish, StageCheckpoint::new(2)).unwrap();
+        provider_rw.commit().unwrap();
+        (factory, data)
+    }
+
+    #[test]
+    fn state_changes_of_range() {
+        let (factory, _) = factory_with_blocks();
+        let api = RethApi::new(
+            BlockchainProvider::new(factory.clone()).unwrap(),
+            Box::<TokioTaskExecutor>::default(),
+        );
+        let account = Address::repeat_byte(0x60);
+        let state = |nonce: u64, balance: u64| {
+            account_state(Account { nonce, balance: U256::from(balance), bytecode_hash: None })
+        };
+
+        let changes = api.try_state_changes(1, 2).unwrap();
+        assert_eq!(changes.iter().map(|block| block.block_number).collect::<Vec<_>>(), [1, 2]);
+        let changed = |block: &BlockStateChanges| {
+            block.accounts.iter().find(|changes| changes.address == account).cloned().unwrap()
+        };
+
+        // the values after block 1 are the values before block 2
+        let first = changed(&changes[0]);
+        assert_eq!(first.info, Some(AccountInfoChange { before: None, after: Some(state(1, 10)) }));
+
+        // the values after the last block are the values of its state
+        let second = changed(&changes[1]);
+        assert_eq!(
+            second.info,
+            Some(AccountInfoChange { before: Some(state(1, 10)), after: Some(state(3, 20)) })
+        );
+        assert_eq!(
+            second.storage,
+            [StorageSlotChange {
+                key: B256::from(U256::from(5)),
+                before: U256::from(10),
+                after: U256::from(15)
+            }]
+        );
+
+        // changes of pruned blocks are not served as empty
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw
+            .save_prune_checkpoint(
+                PruneSegment::StorageHistory,
+                PruneCheckpoint {
+                    block_number: Some(1),
+                    tx_number: None,
+                    prune_mode: PruneMode::Before(2),
+                },
+            )
+            .unwrap();
+        provider_rw.commit().unwrap();
+
+        assert!(matches!(api.try_state_changes(1, 2), Err(EthApiError::PrunedHistoryUnavailable)));
+        assert_eq!(api.try_state_changes(2, 2).unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn subscribe_raw_ranges() {
+        let (factory, data) = factory_with_blocks();
+        let module = RethApiServer::into_rpc(RethApi::new(
+            BlockchainProvider::new(factory.clone()).unwrap(),
+            Box::<TokioTaskExecutor>::default(),
+        ));
+
+        // headers and transactions are read from the static files
+        let mut blocks =
+            module.subscribe_unbounded("reth_subscribeRawBlockRange", (1, 2)).await.unwrap();
+        for (block, _) in &data.blocks[..2] {
+            let (raw, _) = blocks.next::<Bytes>().await.unwrap().unwrap();
+            assert_eq!(raw, Bytes::from(alloy_rlp::encode(block.clone_block())));
+        }
+
+        let mut receipts =
+            module.subscribe_unbounded("reth_subscribeRawReceiptsRange", (1, 2)).await.unwrap();
+        for (_, outcome) in &data.blocks[..2] {
+            let (raw, _) = receipts.next::<Vec<Bytes>>().await.unwrap().unwrap();
+            let expected = outcome.receipts[0]
+                .iter()
+                .map(|receipt| Bytes::from(ReceiptWithBloom::from(receipt.clone()).encoded_2718()))
+                .collect::<Vec<_>>();
+            assert_eq!(raw, expected);
+        }
+
+        // ranges beyond the tip are rejected right away
+        assert!(module.subscribe_unbounded("reth_subscribeRawBlockRange", (1, 3)).await.is_err());
+
+        // pruned receipts close the subscription with an error instead of being skipped
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw
+            .save_prune_checkpoint(
+                PruneSegment::Receipts,
+                PruneCheckpoint {
+                    block_number: Some(1),
+                    tx_number: None,
+                    prune_mode: PruneMode::Before(2),
+                },
+            )
+            .unwrap();
+        provider_rw.commit().unwrap();
+
+        let request =
+            r#"{"jsonrpc":"2.0","id":1,"method":"reth_subscribeRawReceiptsRange","params":[1,2]}"#;
+        let (_, mut notifications) = module.raw_json_request(request, 1).await.unwrap();
+        let notification: serde_json::Value =
+            serde_json::from_str(notifications.recv().await.unwrap().get()).unwrap();
+        assert_eq!(notification["params"]["error"]["code"], 4444);
+    }
+
+    #[test]
+    fn blocks_of_range_are_paired_by_number() {
+        let provider = MockEthProvider::default();
+        let transaction: TransactionSigned =
+            TxLegacy::default().into_signed(Signature::test_signature()).into();
+        provider.extend_chain([0, 1, 3].into_iter().map(|number| Block {
+            header: Header { number, timestamp: number * 10, ..Default::default() },
+            body: BlockBody {
+                transactions: if number == 3 { vec![transaction.clone()] } else { Vec::new() },
+                ..Default::default()
+            },
+        }));
+        let api = RethApi::new(provider, Box::<TokioTaskExecutor>::default());
+
+        // the entries of the blocks after the missing block 2 are not shifted onto it
+        let fields =
+            BlockFieldSelection { timestamp: true, transactions: true, ..Default::default() };
+        let blocks = api.try_blocks_by_range(0, 3, fields).unwrap();
+        assert_eq!(
+            blocks.iter().map(|block| (block.number, block.timestamp)).collect::<Vec<_>>(),
+            [(0, Some(0)), (1, Some(10)), (2, None), (3, Some(30))]
+        );
+        assert_eq!(blocks[1].transactions, Some(Vec::new()));
+        assert_eq!(blocks[2].transactions, None);
+        assert_eq!(blocks[3].transactions, Some(vec![*transaction.tx_hash()]));
+    }
+
+    #[test]
+    fn withdrawals_of_range() {
+        let provider = MockEthProvider::default();
+        let withdrawal = |index| Withdrawal { index, ..Default::default() };
+        provider.extend_chain((0..4).map(|number| Block {
+            header: Header { number, ..Default::default() },
+            body: BlockBody {
+                withdrawals: (number % 2 == 1).then(|| Withdrawals::new(vec![withdrawal(number)])),
+                ..Default::default()
+            },
+        }));
+        let api = RethApi::new(provider, Box::<TokioTaskExecutor>::default());
+
+        // blocks without withdrawals are skipped
+        assert_eq!(
+            api.try_withdrawals(0, 3).unwrap(),
+            [
+                BlockWithdrawals { block_number: 1, withdrawals: vec![withdrawal(1)] },
+                BlockWithdrawals { block_number: 3, withdrawals: vec![withdrawal(3)] },
+            ]
+        );
+        assert!(api.try_withdrawals(0, 0).unwrap().is_empty());
+        assert!(matches!(api.try_withdrawals(0, 4), Err(EthApiError::HeaderNotFound(_))));
+    }
 }
@@ -19,7 +19,7 @@ use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
 use reth_chainspec::{ChainSpecProvider, EthereumHardforks};
 use reth_evm::ConfigureEvm;
-use reth_primitives_traits::{BlockBody, BlockHeader};
+use reth_primitives_traits::{BlockBody, BlockHeader, SignedTransaction};
 use reth_rpc_api::TraceApiServer;
 use reth_rpc_convert::RpcTxReq;
 use reth_rpc_eth_api::{
@@ -27,7 +27,7 @@ use reth_rpc_eth_api::{
     FromEthApiError, RpcNodeCore,
 };
 use reth_rpc_eth_types::{error::EthApiError, utils::recover_raw_transaction, EthConfig};
-use reth_storage_api::{BlockNumReader, BlockReader};
+use reth_storage_api::{AddressTransactionsReader, BlockNumReader, BlockReader, ProviderTx};
 use reth_tasks::pool::BlockingTaskGuard;
 use reth_transaction_pool::{PoolPooledTx, PoolTransaction, TransactionPool};
 use revm::DatabaseCommit;
@@ -45,6 +45,9 @@ use tokio::sync::{AcquireError, OwnedSemaphorePermit};
 /// This type provides the functionality for handling `trace` related requests.
 pub struct TraceApi<Eth> {
     inner: Arc<TraceApiInner<Eth>>,
+    /// Optional index of the transactions sent from or to each address, used to skip tracing
+    /// unrelated transactions in `trace_filter`.
+    address_transactions: Option<Arc<dyn AddressTransactionsReader>>,
 }
 
 // === impl TraceApi ===
@@ -57,7 +60,21 @@ impl<Eth> TraceApi<Eth> {
         eth_config: EthConfig,
     ) -> Self {
         let inner = Arc::new(TraceApiInner { eth_api, blocking_task_guard, eth_config });
-        Self { inner }
+        Self { inner, address_transactions: None }
+    }
+
+    /// Configures the address transactions index used by `trace_filter`.
+    ///
+    /// The index is transaction level: it only records the sender and the recipient of each
+    /// transaction. For the blocks covered by the index, `trace_filter` with address filters only
+    /// traces the transactions sent from or to the filtered addresses, so calls to or from these
+    /// addresses made internally by other transactions are not returned.
+    pub fn with_address_transactions(
+        mut self,
+        address_transactions: Arc<dyn AddressTransactionsReader>,
+    ) -> Self {
+        self.address_transactions = Some(address_transactions);
+        self
     }
 
     /// Acquires a permit to execute a tracing call.
@@ -118,8 +135,15 @@ where
         trace_types: HashSet<TraceType>,
         block_id: Option<BlockId>,
     ) -> Result<TraceResults, Eth::Error> {
-        let tx = recover_raw_transaction::<PoolPooledTx<Eth::Pool>>(&tx)?
-            .map(<Eth::Pool as TransactionPool>::Transaction::pooled_into_consensus);
+        let tx = match recover_raw_transaction::<PoolPooledTx<Eth::Pool>>(&tx) {
+            Ok(tx) => tx.map(<Eth::Pool as TransactionPool>::Transaction::pooled_into_consensus),
+            // system transactions, like OP deposit transactions, can't be pooled and are only
+            // decodable as consensus transactions
+            Err(err) => match recover_raw_transaction::<ProviderTx<Eth::Provider>>(&tx) {
+                Ok(tx) if tx.is_system_tx() => tx,
+                _ => return Err(err.into()),
+            },
+        };
 
         let (evm_env, at) = self.eth_api().evm_env_at(block_id.unwrap_or_default()).await?;
         let tx_env = self.eth_api().evm_config().tx_env(tx);
@@ -344,7 +368,9 @@ where
     ) -> Result<Vec<LocalizedTransactionTrace>, Eth::Error> {
         // We'll reuse the matcher across multiple blocks that are traced in parallel
         let matcher = Arc::new(filter.matcher());
-        let TraceFilter { from_block, to_block, mut after, count, .. } = filter;
+        let TraceFilter {
+            from_block, to_block, from_address, to_address, mut after, count, ..
+        } = filter;
         let start = from_block.unwrap_or(0);
 
         let latest_block = self.provider().best_block_number().map_err(Eth::Error::from_eth_err)?;
@@ -370,6 +396,23 @@ where
             .into())
         }
 
+        // Look up the transactions sent from or to the filtered addresses, if the range is indexed.
+        // Only these transactions are traced, other transactions are executed without building
+        // their traces.
+        let addresses = from_address.into_iter().chain(to_address).collect::<Vec<_>>();
+        let indexed_transactions = match self.address_transactions.clone() {
+            Some(index) if !addresses.is_empty() => self
+                .eth_api()
+                .spawn_blocking_io(move |_| {
+                    index
+                        .address_transactions(&addresses, start..=end)
+                        .map_err(Eth::Error::from_eth_err)
+                })
+                .await?
+                .map(Arc::new),
+            _ => None,
+        };
+
         let mut all_traces = Vec::new();
         let mut block_traces = Vec::with_capacity(self.inner.eth_config.max_tracing_requests);
         for chunk_start in (start..=end).step_by(self.inner.eth_config.max_tracing_requests) {
@@ -378,29 +421,60 @@ where
                 end,
             );
 
-            // fetch all blocks in that chunk
+            // fetch all blocks in that chunk, with the indices of their transactions to trace if
+            // the range is indexed
+            let indexed_transactions = indexed_transactions.clone();
             let blocks = self
                 .eth_api()
                 .spawn_blocking_io(move |this| {
-                    Ok(this
-                        .provider()
+                    let provider = this.provider();
+                    provider
                         .recovered_block_range(chunk_start..=chunk_end)
                         .map_err(Eth::Error::from_eth_err)?
                         .into_iter()
-                        .map(Arc::new)
-                        .collect::<Vec<_>>())
+                        .map(|block| {
+                            let candidates = match &indexed_transactions {
+                                Some(transactions) => provider
+                                    .block_body_indices(block.number())
+                                    .map_err(Eth::Error::from_eth_err)?
+                                    .map(|indices| {
+                                        let from = transactions
+                                            .partition_point(|tx| *tx < indices.first_tx_num());
+                                        let to = transactions
+                                            .partition_point(|tx| *tx < indices.next_tx_num());
+                                        transactions[from..to]
+                                            .iter()
+                                            .map(|tx| tx - indices.first_tx_num())
+                                            .collect::<HashSet<_>>()
+                                    }),
+                                None => None,
+                            };
+                            Ok((Arc::new(block), candidates))
+                        })
+                        .collect::<Result<Vec<_>, _>>()
                 })
                 .await?;
 
             // trace all blocks
-            for block in &blocks {
+            for (block, candidates) in &blocks {
+                // skip blocks without any transactions of the filtered addresses
+                if candidates.as_ref().is_some_and(HashSet::is_empty) {
+                    continue
+                }
+                let highest_index = candidates.as_ref().and_then(|c| c.iter().max().copied());
+                let candidates = candidates.clone();
                 let matcher = matcher.clone();
                 let traces = self.eth_api().trace_block_until(
                     block.hash().into(),
                     Some(block.clone()),
-                    None,
+                    highest_index,
                     TracingInspectorConfig::default_parity(),
                     move |tx_info, mut ctx| {
+                        if let Some(candidates) = &candidates &&
+                            tx_info.index.is_none_or(|index| !candidates.contains(&index))
+                        {
+                            return Ok(None)
+                        }
                         let mut traces = ctx
                             .take_inspector()
                             .into_parity_builder()
@@ -419,7 +493,7 @@ where
             }));
 
             // add reward traces for all blocks
-            for block in &blocks {
+            for (block, _) in &blocks {
                 if let Some(base_block_reward) = self.calculate_base_block_reward(block.header())? {
                     all_traces.extend(
                         self.extract_reward_traces(
@@ -741,7 +815,10 @@ impl<Eth> std::fmt::Debug for TraceApi<Eth> {
 }
 impl<Eth> Clone for TraceApi<Eth> {
     fn clone(&self) -> Self {
-        Self { inner: Arc::clone(&self.inner) }
+        Self {
+            inner: Arc::clone(&self.inner),
+            address_transactions: self.address_transactions.clone(),
+        }
     }
 }
 
@@ -1,19 +1,25 @@
 use core::fmt;
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, ops::Bound, sync::Arc};
 
 use alloy_consensus::Transaction;
-use alloy_primitives::Address;
+use alloy_primitives::{Address, TxHash};
 use alloy_rpc_types_txpool::{
     TxpoolContent, TxpoolContentFrom, TxpoolInspect, TxpoolInspectSummary, TxpoolStatus,
 };
 use async_trait::async_trait;
-use jsonrpsee::core::RpcResult;
+use futures::StreamExt;
+use jsonrpsee::{core::RpcResult, PendingSubscriptionSink, SubscriptionMessage};
 use reth_primitives_traits::NodePrimitives;
-use reth_rpc_api::TxPoolApiServer;
+use reth_rpc_api::{
+    RethSubscriptionKind, RethTxPoolApiServer, TxPoolApiServer, TxpoolContentPage,
+    TxpoolContentPageParams, TxpoolContentWithLocals, TxpoolDelta,
+};
 use reth_rpc_convert::{RpcConvert, RpcTypes};
 use reth_rpc_eth_api::RpcTransaction;
+use reth_tasks::{TaskSpawner, TokioTaskExecutor};
 use reth_transaction_pool::{
-    AllPoolTransactions, PoolConsensusTx, PoolTransaction, TransactionPool,
+    AllPoolTransactions, FullTransactionEvent, PoolConsensusTx, PoolTransaction, TransactionPool,
+    ValidPoolTransaction,
 };
 use tracing::trace;
 
@@ -25,12 +31,26 @@ pub struct TxPoolApi<Pool, Eth> {
     /// An interface to interact with the pool
     pool: Pool,
     converter: Eth,
+    /// The type that's used to spawn subscription tasks.
+    subscription_task_spawner: Box<dyn TaskSpawner>,
 }
 
 impl<Pool, Eth> TxPoolApi<Pool, Eth> {
     /// Creates a new instance of `TxpoolApi`.
-    pub const fn new(pool: Pool, converter: Eth) -> Self {
-        Self { pool, converter }
+    ///
+    /// Subscription tasks are spawned on the current tokio runtime, see also
+    /// [`TxPoolApi::with_subscription_task_spawner`].
+    pub fn new(pool: Pool, converter: Eth) -> Self {
+        Self { pool, converter, subscription_task_spawner: Box::<TokioTaskExecutor>::default() }
+    }
+
+    /// Sets the type that's used to spawn subscription tasks.
+    pub fn with_subscription_task_spawner(
+        mut self,
+        subscription_task_spawner: Box<dyn TaskSpawner>,
+    ) -> Self {
+        self.subscription_task_spawner = subscription_task_spawner;
+        self
     }
 }
 
@@ -39,7 +59,7 @@ where
     Pool: TransactionPool<Transaction: PoolTransaction<Consensus: Transaction>> + 'static,
     Eth: RpcConvert<Primitives: NodePrimitives<SignedTx = PoolConsensusTx<Pool>>>,
 {
-    fn content(&self) -> Result<TxpoolContent<RpcTransaction<Eth::Network>>, Eth::Error> {
+    fn content(&self) -> Result<TxpoolContentWithLocals<RpcTransaction<Eth::Network>>, Eth::Error> {
         #[inline]
         fn insert<Tx, RpcTxB>(
             tx: &Tx,
@@ -63,16 +83,112 @@ where
 
         let AllPoolTransactions { pending, queued } = self.pool.all_transactions();
 
-        let mut content = TxpoolContent::default();
+        let mut content = TxpoolContentWithLocals {
+            content: TxpoolContent::default(),
+            locals: pending
+                .iter()
+                .chain(&queued)
+                .filter(|tx| tx.is_local())
+                .map(|tx| *tx.hash())
+                .collect(),
+        };
         for pending in pending {
-            insert::<_, Eth>(&pending.transaction, &mut content.pending, &self.converter)?;
+            insert::<_, Eth>(&pending.transaction, &mut content.content.pending, &self.converter)?;
         }
         for queued in queued {
-            insert::<_, Eth>(&queued.transaction, &mut content.queued, &self.converter)?;
+            insert::<_, Eth>(&queued.transaction, &mut content.content.queued, &self.converter)?;
         }
 
         Ok(content)
     }
+
+    /// Converts the given transactions and groups them by nonce.
+    fn group_by_nonce<'a>(
+        &self,
+        txs: impl IntoIterator<Item = &'a Arc<ValidPoolTransaction<Pool::Transaction>>>,
+    ) -> Result<BTreeMap<String, RpcTransaction<Eth::Network>>, Eth::Error> {
+        txs.into_iter()
+            .map(|tx| {
+                Ok((
+                    tx.nonce().to_string(),
+                    self.converter.fill_pending(tx.transaction.clone_into_consensus())?,
+                ))
+            })
+            .collect()
+    }
+
+    fn content_from(
+        &self,
+        from: Address,
+        limit: Option<usize>,
+    ) -> Result<TxpoolContentFrom<RpcTransaction<Eth::Network>>, Eth::Error> {
+        let limit = limit.unwrap_or(usize::MAX);
+        let mut pending = self.pool.get_pending_transactions_by_sender(from);
+        let mut queued = self.pool.get_queued_transactions_by_sender(from);
+        pending.sort_unstable_by_key(|tx| tx.nonce());
+        queued.sort_unstable_by_key(|tx| tx.nonce());
+
+        Ok(TxpoolContentFrom {
+            pending: self.group_by_nonce(pending.iter().take(limit))?,
+            queued: self.group_by_nonce(queued.iter().take(limit))?,
+        })
+    }
+
+    fn content_paged(
+        &self,
+        params: TxpoolContentPageParams,
+    ) -> Result<TxpoolContentPage<RpcTransaction<Eth::Network>>, Eth::Error> {
+        let mut by_sender: BTreeMap<Address, (Vec<_>, Vec<_>)> = BTreeMap::new();
+
+        // group without converting, so that only transactions of the page are converted
+        let AllPoolTransactions { pending, queued } = self.pool.all_transactions();
+        for tx in pending {
+            by_sender.entry(tx.sender()).or_default().0.push(tx);
+        }
+        for tx in queued {
+            by_sender.entry(tx.sender()).or_default().1.push(tx);
+        }
+
+        if let Some(senders) = &params.senders {
+            by_sender.retain(|sender, _| senders.contains(sender));
+        }
+
+        let limit = params.limit.unwrap_or(usize::MAX);
+        let mut page = TxpoolContentPage::default();
+        let mut remaining = limit;
+        let lower = params.after.map_or(Bound::Unbounded, Bound::Excluded);
+        let mut senders = by_sender.range((lower, Bound::Unbounded)).peekable();
+
+        while let Some((sender, (pending, queued))) = senders.next() {
+            let count = pending.len() + queued.len();
+            let is_first = page.pending.is_empty() && page.queued.is_empty();
+            if !is_first && count > remaining {
+                break
+            }
+            remaining = remaining.saturating_sub(count);
+
+            page.locals.extend(
+                pending.iter().chain(queued).filter(|tx| tx.is_local()).map(|tx| *tx.hash()),
+            );
+            if !pending.is_empty() {
+                page.pending.insert(*sender, self.group_by_nonce(pending)?);
+            }
+            if !queued.is_empty() {
+                page.queued.insert(*sender, self.group_by_nonce(queued)?);
+            }
+
+            if remaining == 0 {
+                break
+            }
+        }
+
+        // the page is cut off if there are senders left
+        if senders.peek().is_some() {
+            page.next = page.pending.keys().chain(page.queued.keys()).max().copied();
+        }
+
+        Ok(page)
+    }
 }
 
 #[async_trait]
@@ -133,9 +249,10 @@ where
     async fn txpool_content_from(
         &self,
         from: Address,
+        limit: Option<usize>,
     ) -> RpcResult<TxpoolContentFrom<RpcTransaction<Eth::Network>>> {
-        trace!(target: "rpc::eth", ?from, "Serving txpool_contentFrom");
-        Ok(self.content().map_err(Into::into)?.remove_from(&from))
+        trace!(target: "rpc::eth", ?from, ?limit, "Serving txpool_contentFrom");
+        Ok(self.content_from(from, limit).map_err(Into::into)?)
     }
 
     /// Returns the details of all transactions currently pending for inclusion in the next
@@ -143,10 +260,95 @@ where
     ///
     /// See [here](https://geth.ethereum.org/docs/rpc/ns-txpool#txpool_content) for more details
     /// Handler for `txpool_content`
-    async fn txpool_content(&self) -> RpcResult<TxpoolContent<RpcTransaction<Eth::Network>>> {
+    async fn txpool_content(
+        &self,
+    ) -> RpcResult<TxpoolContentWithLocals<RpcTransaction<Eth::Network>>> {
         trace!(target: "rpc::eth", "Serving txpool_content");
         Ok(self.content().map_err(Into::into)?)
     }
+
+    /// Handler for `txpool_locals`
+    async fn txpool_locals(&self) -> RpcResult<Vec<TxHash>> {
+        trace!(target: "rpc::eth", "Serving txpool_locals");
+        Ok(self.pool.get_local_transactions().iter().map(|tx| *tx.hash()).collect())
+    }
+
+    /// Handler for `txpool_contentPaged`
+    async fn txpool_content_paged(
+        &self,
+        params: TxpoolContentPageParams,
+    ) -> RpcResult<TxpoolContentPage<RpcTransaction<Eth::Network>>> {
+        trace!(target: "rpc::eth", ?params, "Serving txpool_contentPaged");
+        Ok(self.content_paged(params).map_err(Into::into)?)
+    }
+}
+
+#[async_trait]
+impl<Pool, Eth> RethTxPoolApiServer for TxPoolApi<Pool, Eth>
+where
+    Pool: TransactionPool + 'static,
+    Eth: Send + Sync + 'static,
+{
+    /// Handler for `reth_subscribe`
+    async fn reth_subscribe(
+        &self,
+        pending: PendingSubscriptionSink,
+        kind: RethSubscriptionKind,
+    ) -> jsonrpsee::core::SubscriptionResult {
+        match kind {
+            RethSubscriptionKind::TxpoolDelta => {
+                // listen before accepting, so that no events are missed
+                let mut events = self.pool.all_transactions_event_listener();
+                let sink = pending.accept().await?;
+
+                self.subscription_task_spawner.spawn(Box::pin(async move {
+                    loop {
+                        tokio::select! {
+                            _ = sink.closed() => break,
+                            event = events.next() => {
+                                let Some(event) = event else { break };
+                                let Some(delta) = delta_from_event(event) else { continue };
+                                let msg = match SubscriptionMessage::new(
+                                    sink.method_name(),
+                                    sink.subscription_id(),
+                                    &delta,
+                                ) {
+                                    Ok(msg) => msg,
+                                    Err(err) => {
+                                        tracing::error!(target: "rpc::txpool", %err, "Failed to serialize subscription message");
+                                        break
+                                    }
+                                };
+                                if sink.send(msg).await.is_err() {
+                                    break
+                                }
+                            }
+                        }
+                    }
+                }));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Converts a pool event into a [`TxpoolDelta`], if it changes the pool content.
+fn delta_from_event<T: PoolTransaction>(event: FullTransactionEvent<T>) -> Option<TxpoolDelta> {
+    let delta = match event {
+        FullTransactionEvent::Pending(hash) => TxpoolDelta::Pending { hash },
+        FullTransactionEvent::Queued(hash, _) => TxpoolDelta::Queued { hash },
+        FullTransactionEvent::Mined { tx_hash, block_hash } => {
+            TxpoolDelta::Mined { hash: tx_hash, block_hash }
+        }
+        FullTransactionEvent::Replaced { transaction, replaced_by } => {
+            TxpoolDelta::Replaced { hash: *transaction.hash(), replaced_by }
+        }
+        FullTransactionEvent::Discarded(hash) => TxpoolDelta::Discarded { hash },
+        FullTransactionEvent::Invalid(hash) => TxpoolDelta::Invalid { hash },
+        FullTransactionEvent::Propagated(_) => return None,
+    };
+    Some(delta)
 }
 
 impl<Pool, Eth> fmt::Debug for TxPoolApi<Pool, Eth> {
@@ -154,3 +356,87 @@ impl<Pool, Eth> fmt::Debug for TxPoolApi<Pool, Eth> {
         f.debug_struct("TxpoolApi").finish_non_exhaustive()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eth::helpers::types::EthRpcConverter;
+    use alloy_primitives::B256;
+    use reth_chainspec::MAINNET;
+    use reth_rpc_eth_types::receipt::EthReceiptConverter;
+    use reth_transaction_pool::{
+        test_utils::{testing_pool, MockTransaction},
+        TransactionOrigin,
+    };
+    use std::collections::BTreeSet;
+
+    #[tokio::test]
+    async fn content_includes_local_transactions() {
+        let pool = testing_pool();
+        let local = MockTransaction::eip1559();
+        let external = MockTransaction::eip1559();
+        pool.add_transaction(TransactionOrigin::Local, local.clone()).await.unwrap();
+        pool.add_transaction(TransactionOrigin::External, external.clone()).await.unwrap();
+
+        let api =
+            TxPoolApi::new(pool, EthRpcConverter::new(EthReceiptConverter::new(MAINNET.clone())));
+
+        let content = api.txpool_content().await.unwrap();
+        assert_eq!(content.content.pending.len(), 2);
+        assert!(content.content.queued.is_empty());
+        assert_eq!(content.locals, BTreeSet::from([*local.hash()]));
+
+        // the locals are returned next to geth's content
+        let json = serde_json::to_value(&content).unwrap();
+        assert_eq!(json["locals"], serde_json::json!([local.hash()]));
+        assert_eq!(json["pending"].as_object().unwrap().len(), 2);
+
+        let page = api
+            .txpool_content_paged(TxpoolContentPageParams {
+                senders: Some(vec![external.sender()]),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert!(page.locals.is_empty());
+        assert!(serde_json::to_value(&page).unwrap().get("locals").is_none());
+
+        assert_eq!(api.txpool_locals().await.unwrap(), [*local.hash()]);
+    }
+
+    #[tokio::test]
+    async fn reth_subscribe_txpool_delta() {
+        let pool = testing_pool();
+        let api = TxPoolApi::new(
+            pool.clone(),
+            EthRpcConverter::new(EthReceiptConverter::new(MAINNET.clone())),
+        );
+        let module = RethTxPoolApiServer::into_rpc(api);
+
+        let mut subscription =
+            module.subscribe_unbounded("reth_subscribe", ["txpoolDelta"]).await.unwrap();
+        let tx = MockTransaction::eip1559();
+        pool.add_transaction(TransactionOrigin::External, tx.clone()).await.unwrap();
+
+        let (delta, _) = subscription.next::<TxpoolDelta>().await.unwrap().unwrap();
+        assert_eq!(delta, TxpoolDelta::Pending { hash: *tx.hash() });
+
+        // unknown kinds are rejected
+        assert!(module.subscribe_unbounded("reth_subscribe", ["newHeads"]).await.is_err());
+    }
+
+    #[test]
+    fn delta_from_pool_events() {
+        let hash = B256::with_last_byte(1);
+        assert_eq!(
+            delta_from_event::<MockTransaction>(FullTransactionEvent::Discarded(hash)),
+            Some(TxpoolDelta::Discarded { hash })
+        );
+        assert_eq!(
+            delta_from_event::<MockTransaction>(FullTransactionEvent::Propagated(
+                Default::default()
+            )),
+            None
+        );
+    }
+}
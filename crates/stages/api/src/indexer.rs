@@ -0,0 +1,22 @@
+use crate::StageError;
+use reth_primitives_traits::{Block, RecoveredBlock};
+use std::fmt::Debug;
+
+/// An optional index that is recorded while blocks are executed.
+///
+/// The execution stage records the index while executing the blocks of the pipeline. Blocks that
+/// are executed by the engine are passed to the indexer when they're persisted.
+pub trait BlockIndexer<Provider, B: Block>: Debug + Send + Sync {
+    /// Indexes the given blocks, in order of increasing block number, and writes the index.
+    ///
+    /// The blocks are executed on top of the latest state of the provider, so this must be called
+    /// before the blocks are written.
+    fn index_blocks(
+        &self,
+        provider: &Provider,
+        blocks: &[&RecoveredBlock<B>],
+    ) -> Result<(), StageError>;
+}
+
+/// A boxed [`BlockIndexer`].
+pub type BoxedBlockIndexer<Provider, B> = Box<dyn BlockIndexer<Provider, B>>;
@@ -13,6 +13,7 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 
 mod error;
+mod indexer;
 mod metrics;
 mod pipeline;
 mod stage;
@@ -22,6 +23,7 @@ mod util;
 
 pub use crate::metrics::*;
 pub use error::*;
+pub use indexer::*;
 pub use pipeline::*;
 pub use stage::*;
 
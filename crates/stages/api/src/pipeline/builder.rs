@@ -83,6 +83,7 @@ impl<Provider> PipelineBuilder<Provider> {
             stages,
             max_block,
             static_file_producer,
+            static_file_producer_task: None,
             tip_tx,
             event_sender: Default::default(),
             progress: Default::default(),
@@ -5,17 +5,20 @@ use crate::{PipelineTarget, StageCheckpoint, StageId};
 use alloy_primitives::{BlockNumber, B256};
 pub use event::*;
 use futures_util::Future;
+use reth_db_api::write_audit::{self, WriteAuditContext};
 use reth_primitives_traits::constants::BEACON_CONSENSUS_REORG_UNWIND_DEPTH;
 use reth_provider::{
     providers::ProviderNodeTypes, BlockHashReader, BlockNumReader, ChainStateBlockReader,
-    ChainStateBlockWriter, DBProvider, DatabaseProviderFactory, ProviderFactory,
-    PruneCheckpointReader, StageCheckpointReader, StageCheckpointWriter,
+    ChainStateBlockWriter, DBProvider, DatabaseProviderFactory, ProviderError, ProviderFactory,
+    ProviderResult, PruneCheckpointReader, StageCheckpointReader, StageCheckpointWriter,
 };
 use reth_prune::PrunerBuilder;
 use reth_static_file::StaticFileProducer;
+use reth_static_file_types::HighestStaticFiles;
 use reth_tokio_util::{EventSender, EventStream};
 use std::{
     pin::Pin,
+    thread::JoinHandle,
     time::{Duration, Instant},
 };
 use tokio::sync::watch;
@@ -74,6 +77,9 @@ pub struct Pipeline<N: ProviderNodeTypes> {
     /// The maximum block number to sync to.
     max_block: Option<BlockNumber>,
     static_file_producer: StaticFileProducer<ProviderFactory<N>>,
+    /// Run of the static file producer copying the data of the finished stages to static files in
+    /// the background, while the following stages run.
+    static_file_producer_task: Option<JoinHandle<ProviderResult<HighestStaticFiles>>>,
     /// Sender for events the pipeline emits.
     event_sender: EventSender<PipelineEvent>,
     /// Keeps track of the progress of the pipeline.
@@ -220,9 +226,23 @@ impl<N: ProviderNodeTypes> Pipeline<N> {
     /// This will be [`ControlFlow::Continue`] or [`ControlFlow::NoProgress`] of the _last_ stage in
     /// the pipeline (for example the `Finish` stage). Or [`ControlFlow::Unwind`] of the stage
     /// that caused the unwind.
+    ///
+    /// The static file producer run started in the background after the execution stage is
+    /// finished before this returns, so that no static files are written once the pipeline hands
+    /// over control, e.g. to the engine.
     pub async fn run_loop(&mut self) -> Result<ControlFlow, PipelineError> {
         self.move_to_static_files()?;
 
+        let result = self.run_stages().await;
+        let static_file_producer_result = self.wait_for_static_file_producer();
+        let next = result?;
+        static_file_producer_result?;
+
+        Ok(next)
+    }
+
+    /// Executes all stages once, see [`Self::run_loop`].
+    async fn run_stages(&mut self) -> Result<ControlFlow, PipelineError> {
         let mut previous_stage = None;
         for stage_index in 0..self.stages.len() {
             let stage = &self.stages[stage_index];
@@ -233,6 +253,12 @@ impl<N: ProviderNodeTypes> Pipeline<N> {
 
             trace!(target: "sync::pipeline", stage = %stage_id, ?next, "Completed stage");
 
+            // The receipts of the executed blocks are copied to static files while the following
+            // stages run
+            if stage_id == StageId::Execution && !next.is_unwind() {
+                self.spawn_static_file_producer()?;
+            }
+
             match next {
                 ControlFlow::NoProgress { block_number } => {
                     if let Some(block_number) = block_number {
@@ -269,9 +295,14 @@ impl<N: ProviderNodeTypes> Pipeline<N> {
     /// - [`StaticFileSegment::Transactions`](reth_static_file_types::StaticFileSegment::Transactions)
     ///   -> [`StageId::Bodies`]
     ///
+    /// Waits for the static file producer run started by [`Self::spawn_static_file_producer`]
+    /// first, if any.
+    ///
     /// CAUTION: This method locks the static file producer Mutex, hence can block the thread if the
     /// lock is occupied.
-    pub fn move_to_static_files(&self) -> RethResult<()> {
+    pub fn move_to_static_files(&mut self) -> RethResult<()> {
+        self.wait_for_static_file_producer()?;
+
         // Copies data from database to static files
         let lowest_static_file_height =
             self.static_file_producer.lock().copy_to_static_files()?.min_block_num();
@@ -290,6 +321,34 @@ impl<N: ProviderNodeTypes> Pipeline<N> {
         Ok(())
     }
 
+    /// Starts a run of the static file producer in the background, copying the data of the
+    /// finished stages to static files while the following stages run.
+    ///
+    /// The data is only deleted from the database by [`Self::move_to_static_files`], which waits
+    /// for the run to finish first.
+    fn spawn_static_file_producer(&mut self) -> RethResult<()> {
+        self.wait_for_static_file_producer()?;
+
+        let static_file_producer = self.static_file_producer.clone();
+        let task = std::thread::Builder::new()
+            .name("static-file-producer".to_string())
+            .spawn(move || static_file_producer.lock().copy_to_static_files())
+            .map_err(ProviderError::other)?;
+        self.static_file_producer_task = Some(task);
+
+        Ok(())
+    }
+
+    /// Waits for the static file producer run started by [`Self::spawn_static_file_producer`] to
+    /// finish, if any.
+    fn wait_for_static_file_producer(&mut self) -> ProviderResult<()> {
+        let Some(task) = self.static_file_producer_task.take() else { return Ok(()) };
+        match task.join() {
+            Ok(result) => result.map(drop),
+            Err(panic) => std::panic::resume_unwind(panic),
+        }
+    }
+
     /// Unwind the stages to the target block (exclusive).
     ///
     /// If the unwind is due to a bad block the number of that block should be specified.
@@ -298,6 +357,8 @@ impl<N: ProviderNodeTypes> Pipeline<N> {
         to: BlockNumber,
         bad_block: Option<BlockNumber>,
     ) -> Result<(), PipelineError> {
+        self.wait_for_static_file_producer()?;
+
         // Add validation before starting unwind
         let provider = self.provider_factory.provider()?;
         let latest_block = provider.last_block_number()?;
@@ -348,6 +409,7 @@ impl<N: ProviderNodeTypes> Pipeline<N> {
                 let input = UnwindInput { checkpoint, unwind_to: to, bad_block };
                 self.event_sender.notify(PipelineEvent::Unwind { stage_id, input });
 
+                let _write_audit_context = WriteAuditContext::enter(stage_id.as_str());
                 let output = stage.unwind(&provider_rw, input);
                 match output {
                     Ok(unwind_output) => {
@@ -400,6 +462,7 @@ impl<N: ProviderNodeTypes> Pipeline<N> {
                     }
                     Err(err) => {
                         self.event_sender.notify(PipelineEvent::Error { stage_id });
+                        log_write_audit(stage_id);
 
                         return Err(PipelineError::Stage(StageError::Fatal(Box::new(err))))
                     }
@@ -476,6 +539,7 @@ impl<N: ProviderNodeTypes> Pipeline<N> {
                 target,
             });
 
+            let _write_audit_context = WriteAuditContext::enter(stage_id.as_str());
             match self.stage(stage_index).execute(&provider_rw, exec_input) {
                 Ok(out @ ExecOutput { checkpoint, done }) => {
                     // Update stage checkpoint.
@@ -519,6 +583,7 @@ impl<N: ProviderNodeTypes> Pipeline<N> {
                 Err(err) => {
                     drop(provider_rw);
                     self.event_sender.notify(PipelineEvent::Error { stage_id });
+                    log_write_audit(stage_id);
 
                     if let Some(ctrl) = self.on_stage_error(stage_id, prev_checkpoint, err)? {
                         return Ok(ctrl)
@@ -653,6 +718,20 @@ impl<N: ProviderNodeTypes> std::fmt::Debug for Pipeline<N> {
     }
 }
 
+/// Logs and clears the writes recorded in the write audit log, which is only populated if the
+/// database records its writes, e.g. `reth-db` with the `write-audit` feature.
+fn log_write_audit(stage_id: StageId) {
+    let writes = write_audit::take();
+    if writes.is_empty() {
+        return
+    }
+
+    error!(target: "sync::pipeline", stage = %stage_id, writes = writes.len(), "Stage failed, dumping recent database writes");
+    for write in writes {
+        error!(target: "sync::pipeline::write_audit", %write);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::atomic::Ordering;
@@ -663,7 +742,7 @@ mod tests {
     use reth_consensus::ConsensusError;
     use reth_errors::ProviderError;
     use reth_provider::test_utils::{create_test_provider_factory, MockNodeTypesWithDB};
-    use reth_prune::PruneModes;
+    use reth_prune::{PruneMode, PruneModes};
     use reth_testing_utils::generators::{self, random_block_with_parent};
     use tokio_stream::StreamExt;
 
@@ -694,6 +773,35 @@ mod tests {
         assert_eq!(progress.next_ctrl(), ControlFlow::Continue { block_number: 1 });
     }
 
+    /// Runs the static file producer in the background after the execution stage.
+    #[tokio::test]
+    async fn run_static_file_producer_after_execution() {
+        let provider_factory = create_test_provider_factory();
+
+        let mut pipeline = Pipeline::<MockNodeTypesWithDB>::builder()
+            .add_stage(
+                TestStage::new(StageId::Execution)
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(10), done: true })),
+            )
+            .add_stage(
+                TestStage::new(StageId::Other("B"))
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(10), done: true })),
+            )
+            .with_max_block(10)
+            .build(
+                provider_factory.clone(),
+                StaticFileProducer::new(
+                    provider_factory.clone(),
+                    PruneModes { receipts: Some(PruneMode::Full), ..Default::default() },
+                ),
+            );
+
+        // The background run is finished before the run returns
+        let result = pipeline.run_loop().await;
+        assert_matches!(result, Ok(ControlFlow::Continue { block_number: 10 }));
+        assert!(pipeline.static_file_producer_task.is_none());
+    }
+
     /// Runs a simple pipeline.
     #[tokio::test]
     async fn run_pipeline() {
@@ -38,10 +38,11 @@
 //! ```
 use crate::{
     stages::{
-        AccountHashingStage, BodyStage, EraImportSource, EraStage, ExecutionStage, FinishStage,
-        HeaderStage, IndexAccountHistoryStage, IndexStorageHistoryStage, MerkleStage,
+        AccountHashingStage, BodyStage, ContractsIndexer, EraImportSource, EraStage,
+        ExecutionStage, FinishStage, HeaderStage, IndexAccountHistoryStage,
+        IndexStorageHistoryStage, IndexTransactionAddressesStage, MerkleStage,
         PruneSenderRecoveryStage, PruneStage, SenderRecoveryStage, StorageHashingStage,
-        TransactionLookupStage,
+        TransactionLookupStage, VerifyReceiptsStage,
     },
     StageSet, StageSetBuilder,
 };
@@ -254,7 +255,10 @@ where
         HeaderStage<P, H>: Stage<Provider>,
     {
         StageSetBuilder::default()
-            .add_stage(HeaderStage::new(provider, header_downloader, tip, stages_config.etl))
+            .add_stage(
+                HeaderStage::new(provider, header_downloader, tip, stages_config.etl)
+                    .with_download_checkpoint_dir(stages_config.headers.download_checkpoint_dir),
+            )
             .add_stage(bodies)
     }
 }
@@ -278,12 +282,17 @@ where
         }
 
         builder
-            .add_stage(HeaderStage::new(
-                self.provider,
-                self.header_downloader,
-                self.tip,
-                self.stages_config.etl.clone(),
-            ))
+            .add_stage(
+                HeaderStage::new(
+                    self.provider,
+                    self.header_downloader,
+                    self.tip,
+                    self.stages_config.etl.clone(),
+                )
+                .with_download_checkpoint_dir(
+                    self.stages_config.headers.download_checkpoint_dir.clone(),
+                ),
+            )
             .add_stage(BodyStage::new(self.body_downloader))
     }
 }
@@ -293,6 +302,7 @@ where
 /// A combination of (in order)
 ///
 /// - [`ExecutionStages`]
+/// - [`VerifyReceiptsStage`], if enabled
 /// - [`PruneSenderRecoveryStage`]
 /// - [`HashingStages`]
 /// - [`HistoryIndexingStages`]
@@ -326,6 +336,7 @@ impl<E, Provider> StageSet<Provider> for OfflineStages<E>
 where
     E: ConfigureEvm,
     ExecutionStages<E>: StageSet<Provider>,
+    VerifyReceiptsStage: Stage<Provider>,
     PruneSenderRecoveryStage: Stage<Provider>,
     HashingStages: StageSet<Provider>,
     HistoryIndexingStages: StageSet<Provider>,
@@ -334,6 +345,13 @@ where
     fn builder(self) -> StageSetBuilder<Provider> {
         ExecutionStages::new(self.evm_config, self.consensus, self.stages_config.clone())
             .builder()
+            // Only verify the receipts against the headers if enabled
+            .add_stage_opt(self.stages_config.verify_receipts.enabled.then(|| {
+                VerifyReceiptsStage::new(
+                    self.stages_config.verify_receipts,
+                    self.prune_modes.clone(),
+                )
+            }))
             // If sender recovery prune mode is set, add the prune sender recovery stage.
             .add_stage_opt(self.prune_modes.sender_recovery.map(|prune_mode| {
                 PruneSenderRecoveryStage::new(prune_mode, self.stages_config.prune.commit_threshold)
@@ -384,12 +402,18 @@ where
     fn builder(self) -> StageSetBuilder<Provider> {
         StageSetBuilder::default()
             .add_stage(SenderRecoveryStage::new(self.stages_config.sender_recovery))
-            .add_stage(ExecutionStage::from_config(
-                self.evm_config,
-                self.consensus,
-                self.stages_config.execution,
-                self.stages_config.execution_external_clean_threshold(),
-            ))
+            .add_stage(
+                ExecutionStage::from_config(
+                    self.evm_config.clone(),
+                    self.consensus,
+                    self.stages_config.execution,
+                    self.stages_config.execution_external_clean_threshold(),
+                )
+                .with_contracts_indexer(ContractsIndexer::from_config(
+                    self.evm_config,
+                    &self.stages_config,
+                )),
+            )
     }
 }
 
@@ -446,6 +470,7 @@ where
     TransactionLookupStage: Stage<Provider>,
     IndexStorageHistoryStage: Stage<Provider>,
     IndexAccountHistoryStage: Stage<Provider>,
+    IndexTransactionAddressesStage: Stage<Provider>,
 {
     fn builder(self) -> StageSetBuilder<Provider> {
         StageSetBuilder::default()
@@ -464,5 +489,9 @@ where
                 self.stages_config.etl.clone(),
                 self.prune_modes.account_history,
             ))
+            // Only index transaction addresses if enabled
+            .add_stage_opt(self.stages_config.index_transaction_addresses.enabled.then(|| {
+                IndexTransactionAddressesStage::new(self.stages_config.index_transaction_addresses)
+            }))
     }
 }
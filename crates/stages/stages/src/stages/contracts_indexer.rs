@@ -0,0 +1,477 @@
+use alloy_consensus::{transaction::TxHashRef, BlockHeader};
+use alloy_primitives::{keccak256, Address, BlockNumber, B256, U256};
+use reth_config::config::StageConfig;
+use reth_db_api::models::{AccountDestruction, ContractCreation};
+use reth_evm::{
+    block::StateChangeSource,
+    execute::{BlockExecutionError, BlockExecutor},
+    ConfigureEvm, Database, Evm,
+};
+use reth_execution_types::BlockExecutionResult;
+use reth_primitives_traits::{NodePrimitives, RecoveredBlock};
+use reth_provider::{
+    BlockHashReader, DBProvider, LatestStateProviderRef, ProviderResult, StageCheckpointReader,
+    StageCheckpointWriter,
+};
+use reth_revm::{
+    context_interface::ContextTr,
+    database::StateProviderDatabase,
+    db::{states::bundle_state::BundleRetention, State},
+    inspector::Inspector,
+    interpreter::{
+        interpreter::EthInterpreter, CallInputs, CallOutcome, CreateInputs, CreateOutcome,
+        CreateScheme,
+    },
+    state::EvmState,
+};
+use reth_stages_api::{BlockErrorKind, BlockIndexer, StageCheckpoint, StageError, StageId};
+use reth_storage_api::{AccountDestructionsWriter, ContractCreationsWriter};
+use std::{
+    mem,
+    sync::{Arc, Mutex},
+};
+
+/// Records the optional contract creations and account destructions indices while blocks are
+/// executed.
+///
+/// The [`ExecutionStage`](super::ExecutionStage) executes the blocks with the inspector of the
+/// indexer, and the engine passes the blocks it persists to [`BlockIndexer::index_blocks`], which
+/// executes them again on top of the state they're written on. Each index only covers a contiguous
+/// range of blocks, starting at the first block executed after it was enabled, which is tracked by
+/// the [`StageId::IndexContractCreations`] and [`StageId::IndexAccountDestructions`] checkpoints.
+///
+/// For more information take a look at [`tables::ContractCreations`] and
+/// [`tables::AccountDestructions`].
+///
+/// [`tables::ContractCreations`]: reth_db_api::tables::ContractCreations
+/// [`tables::AccountDestructions`]: reth_db_api::tables::AccountDestructions
+#[derive(Debug, Clone)]
+pub struct ContractsIndexer<E> {
+    /// The EVM configuration used to execute the blocks.
+    evm_config: E,
+    /// The enabled indices.
+    indices: ContractIndices,
+}
+
+impl<E> ContractsIndexer<E> {
+    /// Create new instance of [`ContractsIndexer`] recording the given indices.
+    pub const fn new(evm_config: E, indices: ContractIndices) -> Self {
+        Self { evm_config, indices }
+    }
+
+    /// Returns the indexer if any of the indices is enabled in the configuration.
+    pub fn from_config(evm_config: E, config: &StageConfig) -> Option<Self> {
+        let indices = ContractIndices {
+            creations: config.index_contract_creations.enabled,
+            destructions: config.index_account_destructions.enabled,
+        };
+        indices.any().then(|| Self::new(evm_config, indices))
+    }
+
+    /// Returns the enabled indices that continue at the given block, i.e. the blocks before it are
+    /// indexed, or nothing is indexed yet.
+    ///
+    /// Blocks are not recorded in the indices they don't continue, since the index would have a
+    /// gap.
+    pub fn indices_at<Provider: StageCheckpointReader>(
+        &self,
+        provider: &Provider,
+        block: BlockNumber,
+    ) -> ProviderResult<ContractIndices> {
+        let continues_at = |stage_id| -> ProviderResult<bool> {
+            Ok(provider
+                .get_stage_checkpoint(stage_id)?
+                .is_none_or(|checkpoint| checkpoint.block_number + 1 == block))
+        };
+
+        Ok(ContractIndices {
+            creations: self.indices.creations && continues_at(StageId::IndexContractCreations)?,
+            destructions: self.indices.destructions &&
+                continues_at(StageId::IndexAccountDestructions)?,
+        })
+    }
+
+    /// Writes the entries recorded while executing the blocks up to and including `last_block`.
+    pub fn write_index<Provider>(
+        &self,
+        provider: &Provider,
+        indices: ContractIndices,
+        last_block: BlockNumber,
+        index: ContractsIndex,
+    ) -> ProviderResult<()>
+    where
+        Provider: StageCheckpointWriter + ContractCreationsWriter + AccountDestructionsWriter,
+    {
+        if indices.creations {
+            provider.insert_contract_creations(index.creations)?;
+            provider.save_stage_checkpoint(
+                StageId::IndexContractCreations,
+                StageCheckpoint::new(last_block),
+            )?;
+        }
+        if indices.destructions {
+            provider.insert_account_destructions(index.destructions)?;
+            provider.save_stage_checkpoint(
+                StageId::IndexAccountDestructions,
+                StageCheckpoint::new(last_block),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl<E: ConfigureEvm> ContractsIndexer<E> {
+    /// Executes the block on top of the given state, like
+    /// [`Executor::execute_one`](reth_evm::execute::Executor::execute_one), and appends the entries
+    /// of the given indices to `index`.
+    pub fn execute_block<DB: Database>(
+        &self,
+        db: &mut State<DB>,
+        block: &RecoveredBlock<<E::Primitives as NodePrimitives>::Block>,
+        indices: ContractIndices,
+        index: &mut ContractsIndex,
+    ) -> Result<BlockExecutionResult<<E::Primitives as NodePrimitives>::Receipt>, BlockExecutionError>
+    {
+        let evm_env =
+            self.evm_config.evm_env(block.header()).map_err(BlockExecutionError::other)?;
+        let evm = self.evm_config.evm_with_env_and_inspector(
+            &mut *db,
+            evm_env,
+            ContractsInspector::default(),
+        );
+        let ctx = self.evm_config.context_for_block(block).map_err(BlockExecutionError::other)?;
+        let mut executor = self.evm_config.create_executor(evm, ctx);
+
+        // The accounts a transaction may have removed, reported by the state hook before the
+        // changes of the transaction are committed.
+        let candidates = Arc::new(Mutex::new(Vec::new()));
+        if indices.destructions {
+            let hook_candidates = candidates.clone();
+            executor.set_state_hook(Some(Box::new(
+                move |source: StateChangeSource, state: &EvmState| {
+                    if let StateChangeSource::Transaction(_) = source {
+                        hook_candidates.lock().unwrap().extend(state.iter().filter_map(
+                            |(address, account)| {
+                                let removed = account.is_selfdestructed() ||
+                                    (account.is_touched() &&
+                                        account.is_empty() &&
+                                        !account.is_loaded_as_not_existing());
+                                removed.then_some((*address, account.is_selfdestructed()))
+                            },
+                        ));
+                    }
+                },
+            )));
+        }
+
+        executor.apply_pre_execution_changes()?;
+        for tx in block.transactions_recovered() {
+            let transaction_hash = *tx.tx_hash();
+            executor.execute_transaction(tx)?;
+
+            let (created, self_destructs) = executor.evm_mut().inspector_mut().take();
+            if indices.creations {
+                index.creations.extend(created.into_iter().map(|created| {
+                    (
+                        created.address,
+                        ContractCreation {
+                            block_number: block.header().number(),
+                            transaction_hash,
+                            creator: created.creator,
+                            code_hash: created.code_hash,
+                            salt: created.salt,
+                        },
+                    )
+                }));
+            }
+
+            let candidates = mem::take(&mut *candidates.lock().unwrap());
+            for (address, self_destructed) in candidates {
+                // Touched empty accounts are only removed after EIP-161 is activated.
+                let removed = executor
+                    .evm_mut()
+                    .db_mut()
+                    .cache
+                    .accounts
+                    .get(&address)
+                    .is_some_and(|cached| cached.account.is_none());
+                if !removed {
+                    continue
+                }
+
+                let beneficiary = self_destructed
+                    .then(|| {
+                        self_destructs
+                            .iter()
+                            .rev()
+                            .find(|self_destruct| self_destruct.contract == address)
+                            .map(|self_destruct| self_destruct.beneficiary)
+                    })
+                    .flatten();
+                index.destructions.push((
+                    address,
+                    AccountDestruction {
+                        block_number: block.header().number(),
+                        transaction_hash,
+                        beneficiary,
+                    },
+                ));
+            }
+        }
+        let (_, result) = executor.finish()?;
+
+        db.merge_transitions(BundleRetention::Reverts);
+
+        Ok(result)
+    }
+}
+
+impl<E, Provider> BlockIndexer<Provider, <E::Primitives as NodePrimitives>::Block>
+    for ContractsIndexer<E>
+where
+    E: ConfigureEvm,
+    Provider: DBProvider
+        + BlockHashReader
+        + StageCheckpointReader
+        + StageCheckpointWriter
+        + ContractCreationsWriter
+        + AccountDestructionsWriter,
+{
+    fn index_blocks(
+        &self,
+        provider: &Provider,
+        blocks: &[&RecoveredBlock<<E::Primitives as NodePrimitives>::Block>],
+    ) -> Result<(), StageError> {
+        let (Some(first), Some(last)) = (blocks.first(), blocks.last()) else { return Ok(()) };
+        let indices = self.indices_at(provider, first.header().number())?;
+        if !indices.any() {
+            return Ok(())
+        }
+
+        // The blocks are executed on top of the state before the first block, so this has to be
+        // called before the blocks are written.
+        let mut db = State::builder()
+            .with_database(StateProviderDatabase::new(LatestStateProviderRef::new(provider)))
+            .with_bundle_update()
+            .without_state_clear()
+            .build();
+
+        let mut index = ContractsIndex::default();
+        for block in blocks {
+            self.execute_block(&mut db, block, indices, &mut index).map_err(|error| {
+                StageError::Block {
+                    block: Box::new(block.block_with_parent()),
+                    error: BlockErrorKind::Execution(error),
+                }
+            })?;
+        }
+
+        self.write_index(provider, indices, last.header().number(), index)?;
+
+        Ok(())
+    }
+}
+
+/// The optional indices recorded by the [`ContractsIndexer`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ContractIndices {
+    /// Whether the contract creations are indexed.
+    pub creations: bool,
+    /// Whether the account destructions are indexed.
+    pub destructions: bool,
+}
+
+impl ContractIndices {
+    /// Returns whether any of the indices is recorded.
+    pub const fn any(&self) -> bool {
+        self.creations || self.destructions
+    }
+}
+
+/// The index entries recorded while executing blocks.
+#[derive(Debug, Default)]
+pub struct ContractsIndex {
+    /// The contracts created by the blocks, by their address.
+    pub creations: Vec<(Address, ContractCreation)>,
+    /// The accounts removed by the blocks, by their address.
+    pub destructions: Vec<(Address, AccountDestruction)>,
+}
+
+/// A contract created by a transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct CreatedContract {
+    /// Address of the created contract.
+    address: Address,
+    /// Address of the account that executed the `CREATE` or `CREATE2`.
+    creator: Address,
+    /// Hash of the deployed code.
+    code_hash: B256,
+    /// Salt of the `CREATE2`.
+    salt: Option<B256>,
+}
+
+/// A `SELFDESTRUCT` executed by a transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct SelfDestruct {
+    /// Address of the contract that executed the `SELFDESTRUCT`.
+    contract: Address,
+    /// Address the balance of the contract was sent to.
+    beneficiary: Address,
+}
+
+/// Inspector recording the contracts created and the `SELFDESTRUCT`s executed by a transaction.
+///
+/// The creations and `SELFDESTRUCT`s of a frame that is reverted are discarded, including the ones
+/// of the frames it called.
+#[derive(Debug, Default)]
+struct ContractsInspector {
+    /// The contracts created by the current transaction, in order of creation.
+    created: Vec<CreatedContract>,
+    /// The `SELFDESTRUCT`s executed by the current transaction, in order of execution.
+    self_destructs: Vec<SelfDestruct>,
+    /// The number of created contracts and `SELFDESTRUCT`s at the start of every open frame.
+    frames: Vec<(usize, usize)>,
+}
+
+impl ContractsInspector {
+    /// Returns the contracts created and the `SELFDESTRUCT`s executed by the current transaction
+    /// and resets the inspector for the next one.
+    fn take(&mut self) -> (Vec<CreatedContract>, Vec<SelfDestruct>) {
+        self.frames.clear();
+        (mem::take(&mut self.created), mem::take(&mut self.self_destructs))
+    }
+
+    /// Called when a new frame is entered.
+    fn enter_frame(&mut self) {
+        self.frames.push((self.created.len(), self.self_destructs.len()));
+    }
+
+    /// Called when a frame is exited, discarding its creations and `SELFDESTRUCT`s if it failed.
+    fn exit_frame(&mut self, success: bool) {
+        let (created, self_destructs) = self.frames.pop().unwrap_or_default();
+        if !success {
+            self.created.truncate(created);
+            self.self_destructs.truncate(self_destructs);
+        }
+    }
+}
+
+impl<CTX> Inspector<CTX, EthInterpreter> for ContractsInspector
+where
+    CTX: ContextTr,
+{
+    fn call(&mut self, _context: &mut CTX, _inputs: &mut CallInputs) -> Option<CallOutcome> {
+        self.enter_frame();
+        None
+    }
+
+    fn call_end(&mut self, _context: &mut CTX, _inputs: &CallInputs, outcome: &mut CallOutcome) {
+        self.exit_frame(outcome.result.is_ok());
+    }
+
+    fn create(&mut self, _context: &mut CTX, _inputs: &mut CreateInputs) -> Option<CreateOutcome> {
+        self.enter_frame();
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut CTX,
+        inputs: &CreateInputs,
+        outcome: &mut CreateOutcome,
+    ) {
+        let success = outcome.result.is_ok();
+        self.exit_frame(success);
+
+        // the creation belongs to the calling frame, so it's discarded if that frame reverts
+        if success && let Some(address) = outcome.address {
+            self.created.push(CreatedContract {
+                address,
+                creator: inputs.caller(),
+                code_hash: keccak256(&outcome.result.output),
+                salt: match inputs.scheme() {
+                    CreateScheme::Create2 { salt } => Some(salt.into()),
+                    _ => None,
+                },
+            });
+        }
+    }
+
+    fn selfdestruct(&mut self, contract: Address, target: Address, _value: U256) {
+        self.self_destructs.push(SelfDestruct { contract, beneficiary: target });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn created(byte: u8) -> CreatedContract {
+        CreatedContract {
+            address: Address::repeat_byte(byte),
+            creator: Address::ZERO,
+            code_hash: B256::ZERO,
+            salt: None,
+        }
+    }
+
+    fn self_destruct(byte: u8) -> SelfDestruct {
+        SelfDestruct { contract: Address::repeat_byte(byte), beneficiary: Address::ZERO }
+    }
+
+    #[test]
+    fn reverted_frames_discard_creations() {
+        let mut inspector = ContractsInspector::default();
+
+        // transaction calling a factory that creates a contract, then calls another contract that
+        // creates a contract and reverts
+        inspector.enter_frame();
+        inspector.enter_frame();
+        inspector.exit_frame(true);
+        inspector.created.push(created(1));
+        inspector.enter_frame();
+        inspector.enter_frame();
+        inspector.exit_frame(true);
+        inspector.created.push(created(2));
+        inspector.exit_frame(false);
+        inspector.exit_frame(true);
+        assert_eq!(inspector.take(), (vec![created(1)], vec![]));
+
+        // reverted transaction
+        inspector.enter_frame();
+        inspector.enter_frame();
+        inspector.exit_frame(true);
+        inspector.created.push(created(3));
+        inspector.exit_frame(false);
+        assert_eq!(inspector.take(), (vec![], vec![]));
+        assert!(inspector.frames.is_empty());
+    }
+
+    #[test]
+    fn reverted_frames_discard_self_destructs() {
+        let mut inspector = ContractsInspector::default();
+
+        // transaction calling a contract that self-destructs, then calls another contract that
+        // self-destructs and reverts
+        inspector.enter_frame();
+        inspector.enter_frame();
+        inspector.self_destructs.push(self_destruct(1));
+        inspector.exit_frame(true);
+        inspector.enter_frame();
+        inspector.enter_frame();
+        inspector.self_destructs.push(self_destruct(2));
+        inspector.exit_frame(true);
+        inspector.exit_frame(false);
+        inspector.exit_frame(true);
+        assert_eq!(inspector.take(), (vec![], vec![self_destruct(1)]));
+
+        // reverted transaction
+        inspector.enter_frame();
+        inspector.enter_frame();
+        inspector.self_destructs.push(self_destruct(3));
+        inspector.exit_frame(true);
+        inspector.exit_frame(false);
+        assert_eq!(inspector.take(), (vec![], vec![]));
+        assert!(inspector.frames.is_empty());
+    }
+}
@@ -1,27 +1,36 @@
-use crate::stages::MERKLE_STAGE_DEFAULT_INCREMENTAL_THRESHOLD;
+use crate::stages::{ContractsIndex, ContractsIndexer, MERKLE_STAGE_DEFAULT_INCREMENTAL_THRESHOLD};
 use alloy_consensus::BlockHeader;
 use alloy_primitives::BlockNumber;
 use num_traits::Zero;
 use reth_config::config::ExecutionConfig;
 use reth_consensus::FullConsensus;
 use reth_db::{static_file::HeaderMask, tables};
-use reth_evm::{execute::Executor, metrics::ExecutorMetrics, ConfigureEvm};
-use reth_execution_types::Chain;
+use reth_evm::{
+    execute::{BlockExecutionError, BlockExecutor},
+    metrics::ExecutorMetrics,
+    ConfigureEvm,
+};
+use reth_execution_types::{BlockExecutionResult, Chain};
 use reth_exex::{ExExManagerHandle, ExExNotification, ExExNotificationSource};
-use reth_primitives_traits::{format_gas_throughput, BlockBody, NodePrimitives};
+use reth_primitives_traits::{format_gas_throughput, BlockBody, NodePrimitives, RecoveredBlock};
 use reth_provider::{
     providers::{StaticFileProvider, StaticFileWriter},
     BlockHashReader, BlockReader, DBProvider, EitherWriter, ExecutionOutcome, HeaderProvider,
-    LatestStateProviderRef, OriginalValuesKnown, ProviderError, StateWriteConfig, StateWriter,
-    StaticFileProviderFactory, StatsReader, StorageSettingsCache, TransactionVariant,
+    LatestStateProviderRef, OriginalValuesKnown, ProviderError, StageCheckpointReader,
+    StageCheckpointWriter, StateWriteConfig, StateWriter, StaticFileProviderFactory, StatsReader,
+    StorageSettingsCache, TransactionVariant,
+};
+use reth_revm::{
+    database::StateProviderDatabase,
+    db::{states::bundle_state::BundleRetention, State},
 };
-use reth_revm::database::StateProviderDatabase;
 use reth_stages_api::{
     BlockErrorKind, CheckpointBlockRange, EntitiesCheckpoint, ExecInput, ExecOutput,
     ExecutionCheckpoint, ExecutionStageThresholds, Stage, StageCheckpoint, StageError, StageId,
     UnwindInput, UnwindOutput,
 };
 use reth_static_file_types::StaticFileSegment;
+use reth_storage_api::{AccountDestructionsWriter, ContractCreationsWriter};
 use std::{
     cmp::{max, Ordering},
     collections::BTreeMap,
@@ -55,6 +64,8 @@ use super::missing_static_data_error;
 /// - [`tables::Bytecodes`]
 /// - [`tables::AccountChangeSets`]
 /// - [`tables::StorageChangeSets`]
+/// - [`tables::ContractCreations`] and [`tables::AccountDestructions`], if the optional indices are
+///   enabled, see [`ContractsIndexer`]
 ///
 /// For unwinds we are accessing:
 /// - [`tables::BlockBodyIndices`] get tx index to know what needs to be unwinded
@@ -90,6 +101,8 @@ where
     exex_manager_handle: ExExManagerHandle<E::Primitives>,
     /// Executor metrics.
     metrics: ExecutorMetrics,
+    /// Records the optional contract indices while executing the blocks, if enabled.
+    contracts_indexer: Option<ContractsIndexer<E>>,
 }
 
 impl<E> ExecutionStage<E>
@@ -113,9 +126,19 @@ where
             post_unwind_commit_input: None,
             exex_manager_handle,
             metrics: ExecutorMetrics::default(),
+            contracts_indexer: None,
         }
     }
 
+    /// Set the indexer recording the optional contract indices while executing the blocks.
+    pub fn with_contracts_indexer(
+        mut self,
+        contracts_indexer: Option<ContractsIndexer<E>>,
+    ) -> Self {
+        self.contracts_indexer = contracts_indexer;
+        self
+    }
+
     /// Create an execution stage with the provided executor.
     ///
     /// The commit threshold will be set to [`MERKLE_STAGE_DEFAULT_INCREMENTAL_THRESHOLD`].
@@ -148,6 +171,25 @@ where
         )
     }
 
+    /// Executes the block on top of the given state, like
+    /// [`reth_evm::execute::Executor::execute_one`].
+    fn execute_block<DB: reth_evm::Database>(
+        &self,
+        db: &mut State<DB>,
+        block: &RecoveredBlock<<E::Primitives as NodePrimitives>::Block>,
+    ) -> Result<BlockExecutionResult<<E::Primitives as NodePrimitives>::Receipt>, BlockExecutionError>
+    {
+        let result = self
+            .evm_config
+            .executor_for_block(db, block)
+            .map_err(BlockExecutionError::other)?
+            .execute_block(block.transactions_recovered())?;
+
+        db.merge_transitions(BundleRetention::Reverts);
+
+        Ok(result)
+    }
+
     /// Returns whether we can perform pruning of [`tables::AccountChangeSets`] and
     /// [`tables::StorageChangeSets`].
     ///
@@ -267,7 +309,11 @@ where
         > + StatsReader
         + BlockHashReader
         + StateWriter<Receipt = <E::Primitives as NodePrimitives>::Receipt>
-        + StorageSettingsCache,
+        + StorageSettingsCache
+        + StageCheckpointReader
+        + StageCheckpointWriter
+        + ContractCreationsWriter
+        + AccountDestructionsWriter,
 {
     /// Return the id of the stage
     fn id(&self) -> StageId {
@@ -296,8 +342,18 @@ where
 
         self.ensure_consistency(provider, input.checkpoint().block_number, None)?;
 
-        let db = StateProviderDatabase(LatestStateProviderRef::new(provider));
-        let mut executor = self.evm_config.batch_executor(db);
+        let mut db = State::builder()
+            .with_database(StateProviderDatabase(LatestStateProviderRef::new(provider)))
+            .with_bundle_update()
+            .without_state_clear()
+            .build();
+
+        // The optional contract indices are only recorded if they continue at the first block.
+        let contract_indices = match &self.contracts_indexer {
+            Some(indexer) => indexer.indices_at(provider, start_block)?,
+            None => Default::default(),
+        };
+        let mut contracts_index = ContractsIndex::default();
 
         // Progress tracking
         let mut stage_progress = start_block;
@@ -345,7 +401,16 @@ where
             let execute_start = Instant::now();
 
             let result = self.metrics.metered_one(&block, |input| {
-                executor.execute_one(input).map_err(|error| StageError::Block {
+                match self.contracts_indexer.as_ref().filter(|_| contract_indices.any()) {
+                    Some(indexer) => indexer.execute_block(
+                        &mut db,
+                        input,
+                        contract_indices,
+                        &mut contracts_index,
+                    ),
+                    None => self.execute_block(&mut db, input),
+                }
+                .map_err(|error| StageError::Block {
                     block: Box::new(block.block_with_parent()),
                     error: BlockErrorKind::Execution(error),
                 })
@@ -388,7 +453,7 @@ where
             // Check if we should commit now
             if self.thresholds.is_end_of_batch(
                 block_number - start_block,
-                executor.size_hint() as u64,
+                db.bundle_state.size_hint() as u64,
                 cumulative_gas,
                 batch_start.elapsed(),
             ) {
@@ -398,11 +463,7 @@ where
 
         // prepare execution output for writing
         let time = Instant::now();
-        let mut state = ExecutionOutcome::from_blocks(
-            start_block,
-            executor.into_state().take_bundle(),
-            results,
-        );
+        let mut state = ExecutionOutcome::from_blocks(start_block, db.take_bundle(), results);
         let write_preparation_duration = time.elapsed();
 
         // log the gas per second for the range we just executed
@@ -463,6 +524,9 @@ where
 
         // write output
         provider.write_state(&state, OriginalValuesKnown::Yes, StateWriteConfig::default())?;
+        if let Some(indexer) = self.contracts_indexer.as_ref().filter(|_| contract_indices.any()) {
+            indexer.write_index(provider, contract_indices, stage_progress, contracts_index)?;
+        }
 
         let db_write_duration = time.elapsed();
         debug!(
@@ -680,6 +744,7 @@ mod tests {
     use alloy_rlp::Decodable;
     use assert_matches::assert_matches;
     use reth_chainspec::ChainSpecBuilder;
+    use reth_consensus::ConsensusError;
     use reth_db_api::{
         models::{metadata::StorageSettings, AccountBeforeTx},
         transaction::{DbTx, DbTxMut},
@@ -1000,6 +1065,78 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn invalid_receipts_root_is_not_written() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+        let input = ExecInput { target: Some(1), checkpoint: None };
+        let mut genesis_rlp = hex!("f901faf901f5a00000000000000000000000000000000000000000000000000000000000000000a01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347942adc25665018aa1fe0e6bc666dac8fc2697ff9baa045571b40ae66ca7480791bbb2887286e4e4c4b1b298b191c889d6959023a32eda056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421a056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421b901000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000083020000808502540be400808000a00000000000000000000000000000000000000000000000000000000000000000880000000000000000c0c0").as_slice();
+        let genesis = SealedBlock::<Block>::decode(&mut genesis_rlp).unwrap();
+        let mut block_rlp = hex!("f90262f901f9a075c371ba45999d87f4542326910a11af515897aebce5265d3f6acd1f1161f82fa01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347942adc25665018aa1fe0e6bc666dac8fc2697ff9baa098f2dcd87c8ae4083e7017a05456c14eea4b1db2032126e27b3b1563d57d7cc0a08151d548273f6683169524b66ca9fe338b9ce42bc3540046c828fd939ae23bcba03f4e5c2ec5b2170b711d97ee755c160457bb58d8daa338e835ec02ae6860bbabb901000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000083020000018502540be40082a8798203e800a00000000000000000000000000000000000000000000000000000000000000000880000000000000000f863f861800a8405f5e10094100000000000000000000000000000000000000080801ba07e09e26678ed4fac08a249ebe8ed680bf9051a5e14ad223e4b2b9d26e0208f37a05f6e3f188e3e6eab7d7d3b6568f5eac7d687b08d307d3154ccd8c87b4630509bc0").as_slice();
+        let mut block = SealedBlock::<Block>::decode(&mut block_rlp).unwrap().into_block();
+        block.header.receipts_root = B256::random();
+        let block = SealedBlock::seal_slow(block);
+        provider.insert_block(&genesis.try_recover().unwrap()).unwrap();
+        provider.insert_block(&block.clone().try_recover().unwrap()).unwrap();
+        provider
+            .static_file_provider()
+            .latest_writer(StaticFileSegment::Headers)
+            .unwrap()
+            .commit()
+            .unwrap();
+        {
+            let static_file_provider = provider.static_file_provider();
+            let mut receipts_writer =
+                static_file_provider.latest_writer(StaticFileSegment::Receipts).unwrap();
+            receipts_writer.increment_block(0).unwrap();
+            receipts_writer.commit().unwrap();
+        }
+        provider.commit().unwrap();
+
+        // insert pre state
+        let provider = factory.provider_rw().unwrap();
+        let db_tx = provider.tx_ref();
+        let acc1 = address!("0x1000000000000000000000000000000000000000");
+        let acc2 = address!("0xa94f5374fce5edbc8e2a8697c15331677e6ebf0b");
+        let code = hex!("5a465a905090036002900360015500");
+        let balance = U256::from(0x3635c9adc5dea00000u128);
+        let code_hash = keccak256(code);
+        db_tx
+            .put::<tables::PlainAccountState>(
+                acc1,
+                Account { nonce: 0, balance: U256::ZERO, bytecode_hash: Some(code_hash) },
+            )
+            .unwrap();
+        db_tx
+            .put::<tables::PlainAccountState>(
+                acc2,
+                Account { nonce: 0, balance, bytecode_hash: None },
+            )
+            .unwrap();
+        db_tx.put::<tables::Bytecodes>(code_hash, Bytecode::new_raw(code.to_vec().into())).unwrap();
+        provider.commit().unwrap();
+
+        // the receipts root is validated right after executing the block, before the state is
+        // written
+        let provider = factory.database_provider_rw().unwrap();
+        let result = stage().execute(&provider, input);
+        assert_matches!(
+            result,
+            Err(StageError::Block {
+                error: BlockErrorKind::Validation(ConsensusError::BodyReceiptRootDiff(_)),
+                ..
+            })
+        );
+
+        // no state or changesets were written
+        assert_eq!(
+            provider.basic_account(&acc2).unwrap(),
+            Some(Account { nonce: 0, balance, bytecode_hash: None })
+        );
+        assert_eq!(provider.count_entries::<tables::PlainStorageState>().unwrap(), 0);
+        assert_eq!(provider.count_entries::<tables::AccountChangeSets>().unwrap(), 0);
+    }
+
     #[tokio::test]
     async fn sanity_execute_unwind() {
         let factory = create_test_provider_factory();
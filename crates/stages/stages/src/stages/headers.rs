@@ -26,8 +26,12 @@ use reth_stages_api::{
     StageCheckpoint, StageError, StageId, UnwindInput, UnwindOutput,
 };
 use reth_static_file_types::StaticFileSegment;
-use std::task::{ready, Context, Poll};
+use std::{
+    path::PathBuf,
+    task::{ready, Context, Poll},
+};
 
+use super::headers_checkpoint::HeadersDownloadCheckpoint;
 use tokio::sync::watch;
 use tracing::*;
 
@@ -41,7 +45,8 @@ use tracing::*;
 ///
 /// NOTE: This stage downloads headers in reverse and pushes them to the ETL [`Collector`]. It then
 /// proceeds to push them sequentially to static files. The stage checkpoint is not updated until
-/// this stage is done.
+/// this stage is done. If a download checkpoint directory is set, the downloaded headers are also
+/// persisted there, so the download resumes from the lowest downloaded header after a restart.
 #[derive(Debug)]
 pub struct HeaderStage<Provider, Downloader: HeaderDownloader> {
     /// Database handle.
@@ -60,6 +65,8 @@ pub struct HeaderStage<Provider, Downloader: HeaderDownloader> {
     header_collector: Collector<BlockNumber, Bytes>,
     /// Returns true if the ETL collector has all necessary headers to fill the gap.
     is_etl_ready: bool,
+    /// Persisted headers of the current download.
+    download_checkpoint: Option<HeadersDownloadCheckpoint>,
 }
 
 // === impl HeaderStage ===
@@ -83,9 +90,17 @@ where
             hash_collector: Collector::new(etl_config.file_size / 2, etl_config.dir.clone()),
             header_collector: Collector::new(etl_config.file_size / 2, etl_config.dir),
             is_etl_ready: false,
+            download_checkpoint: None,
         }
     }
 
+    /// Persists the downloaded headers in the given directory, so an interrupted download is
+    /// resumed from the lowest downloaded header instead of the sync target.
+    pub fn with_download_checkpoint_dir(mut self, dir: Option<PathBuf>) -> Self {
+        self.download_checkpoint = dir.map(HeadersDownloadCheckpoint::new);
+        self
+    }
+
     /// Clear all ETL state. Called on error paths to prevent buffer pollution on retry.
     fn clear_etl_state(&mut self) {
         self.sync_gap = None;
@@ -94,6 +109,71 @@ where
         self.is_etl_ready = false;
     }
 
+    /// Removes the download checkpoint, called if the downloaded headers must not be reused.
+    fn remove_download_checkpoint(&mut self) {
+        if let Some(checkpoint) = &mut self.download_checkpoint &&
+            let Err(error) = checkpoint.remove()
+        {
+            warn!(target: "sync::stages::headers", %error, "Failed to remove headers download checkpoint");
+        }
+    }
+
+    /// Inserts a downloaded header into the ETL collectors.
+    fn collect_header(
+        &mut self,
+        header: &SealedHeader<Downloader::Header>,
+    ) -> Result<(), StageError>
+    where
+        Downloader::Header: FullBlockHeader,
+    {
+        self.hash_collector.insert(header.hash(), header.number())?;
+        self.header_collector.insert(
+            header.number(),
+            Bytes::from(
+                bincode::serialize(&serde_bincode_compat::SealedHeader::from(header))
+                    .map_err(|err| StageError::Fatal(Box::new(err)))?,
+            ),
+        )?;
+        Ok(())
+    }
+
+    /// Restores the headers of an interrupted download from the local head into the ETL
+    /// collectors and returns the lowest restored header.
+    ///
+    /// Starts a new download checkpoint if there is nothing to restore.
+    fn restore_download_checkpoint(
+        &mut self,
+        local_head: &SealedHeader<Downloader::Header>,
+    ) -> Option<SealedHeader<Downloader::Header>>
+    where
+        Downloader::Header: FullBlockHeader,
+    {
+        let mut checkpoint = self.download_checkpoint.take()?;
+
+        // Restored headers would be mixed up with the headers already in the ETL collectors
+        let restored = if self.header_collector.is_empty() {
+            checkpoint.open(local_head.hash(), |header| self.collect_header(&header))
+        } else {
+            checkpoint.start(local_head.hash()).map(|()| None).map_err(StageError::from)
+        };
+
+        let lowest = restored.unwrap_or_else(|error| {
+            warn!(target: "sync::stages::headers", %error, "Failed to restore headers download checkpoint");
+            self.hash_collector.clear();
+            self.header_collector.clear();
+            if let Err(error) = checkpoint.start(local_head.hash()) {
+                warn!(target: "sync::stages::headers", %error, "Failed to start headers download checkpoint");
+            }
+            None
+        });
+        self.download_checkpoint = Some(checkpoint);
+
+        if let Some(lowest) = &lowest {
+            info!(target: "sync::stages::headers", total = self.header_collector.len(), lowest = lowest.number(), "Resuming headers download from checkpoint");
+        }
+        lowest
+    }
+
     /// Write downloaded headers to storage from ETL.
     ///
     /// Writes to static files ( `Header | HeaderTD | HeaderHash` ) and [`tables::HeaderNumbers`]
@@ -225,16 +305,24 @@ where
             );
             self.is_etl_ready = true;
             self.sync_gap = Some(gap);
+            self.remove_download_checkpoint();
             return Poll::Ready(Ok(()))
         }
 
         debug!(target: "sync::stages::headers", ?tip, head = ?gap.local_head.hash(), "Commencing sync");
         let local_head_number = gap.local_head.number();
 
-        // let the downloader know what to sync
+        // let the downloader know what to sync, continuing an interrupted download if possible
         if self.sync_gap != Some(gap.clone()) {
             self.sync_gap = Some(gap.clone());
-            self.downloader.update_sync_gap(gap.local_head, gap.target);
+            match self.restore_download_checkpoint(&gap.local_head) {
+                Some(lowest) if lowest.number() == local_head_number + 1 => {
+                    self.is_etl_ready = true;
+                    return Poll::Ready(Ok(()))
+                }
+                Some(lowest) => self.downloader.resume_sync_gap(gap.local_head, lowest),
+                None => self.downloader.update_sync_gap(gap.local_head, gap.target),
+            }
         }
 
         // We only want to stop once we have all the headers on ETL filespace (disk).
@@ -242,23 +330,18 @@ where
             match ready!(self.downloader.poll_next_unpin(cx)) {
                 Some(Ok(headers)) => {
                     info!(target: "sync::stages::headers", total = headers.len(), from_block = headers.first().map(|h| h.number()), to_block = headers.last().map(|h| h.number()), "Received headers");
+                    if let Some(checkpoint) = &mut self.download_checkpoint &&
+                        let Err(error) = checkpoint.append(&headers)
+                    {
+                        warn!(target: "sync::stages::headers", %error, "Failed to checkpoint downloaded headers");
+                    }
+
                     for header in headers {
-                        let header_number = header.number();
-
-                        self.hash_collector.insert(header.hash(), header_number)?;
-                        self.header_collector.insert(
-                            header_number,
-                            Bytes::from(
-                                bincode::serialize(&serde_bincode_compat::SealedHeader::from(
-                                    &header,
-                                ))
-                                .map_err(|err| StageError::Fatal(Box::new(err)))?,
-                            ),
-                        )?;
+                        self.collect_header(&header)?;
 
                         // Headers are downloaded in reverse, so if we reach here, we know we have
                         // filled the gap.
-                        if header_number == local_head_number + 1 {
+                        if header.number() == local_head_number + 1 {
                             self.is_etl_ready = true;
                             return Poll::Ready(Ok(()))
                         }
@@ -267,6 +350,7 @@ where
                 Some(Err(HeadersDownloaderError::DetachedHead { local_head, header, error })) => {
                     error!(target: "sync::stages::headers", %error, "Cannot attach header to head");
                     self.clear_etl_state();
+                    self.remove_download_checkpoint();
                     return Poll::Ready(Err(StageError::DetachedHead {
                         local_head: Box::new(local_head.block_with_parent()),
                         header: Box::new(header.block_with_parent()),
@@ -333,6 +417,8 @@ where
         input: UnwindInput,
     ) -> Result<UnwindOutput, StageError> {
         self.clear_etl_state();
+        // The unwound headers may be invalid, so they must not be restored
+        self.remove_download_checkpoint();
 
         // First unwind the db tables, until the unwind_to block number. use the walker to unwind
         // HeaderNumbers based on the index in CanonicalHeaders
@@ -0,0 +1,238 @@
+use alloy_consensus::BlockHeader;
+use alloy_primitives::B256;
+use reth_primitives_traits::{serde_bincode_compat, FullBlockHeader, SealedHeader};
+use std::{
+    fs::{File, OpenOptions},
+    io::{self, BufReader, BufWriter, Read, Seek, SeekFrom, Write},
+    path::PathBuf,
+};
+use tracing::*;
+
+/// Name of the checkpoint file inside the checkpoint directory.
+const CHECKPOINT_FILE_NAME: &str = "headers";
+
+/// Upper bound for the encoded size of a single header, larger lengths are treated as corrupted.
+const MAX_HEADER_SIZE: usize = 1024 * 1024;
+
+/// Persists the headers downloaded by the [`HeaderStage`](super::HeaderStage), so an interrupted
+/// download can be resumed from the lowest downloaded header instead of the sync target.
+///
+/// The checkpoint file starts with the hash of the local head the download started from, followed
+/// by the downloaded headers with falling block numbers. Every header is stored as a bincode
+/// encoded [`SealedHeader`] prefixed with its length as a big-endian `u32`.
+#[derive(Debug)]
+pub(crate) struct HeadersDownloadCheckpoint {
+    path: PathBuf,
+    /// Writer of the current checkpoint, `None` if checkpointing failed.
+    writer: Option<BufWriter<File>>,
+}
+
+impl HeadersDownloadCheckpoint {
+    /// Creates a new checkpoint stored in the given directory.
+    pub(crate) fn new(dir: PathBuf) -> Self {
+        Self { path: dir.join(CHECKPOINT_FILE_NAME), writer: None }
+    }
+
+    /// Opens the checkpoint of a download that started from the given local head, calling `f` with
+    /// every checkpointed header and returning the lowest one.
+    ///
+    /// If there is no checkpoint for the local head, a new one is started. Headers that were
+    /// partially written or don't attach to the previous header are discarded.
+    pub(crate) fn open<H, E>(
+        &mut self,
+        local_head: B256,
+        mut f: impl FnMut(SealedHeader<H>) -> Result<(), E>,
+    ) -> Result<Option<SealedHeader<H>>, E>
+    where
+        H: FullBlockHeader,
+        E: From<io::Error>,
+    {
+        self.writer = None;
+
+        let mut file = match OpenOptions::new().read(true).write(true).open(&self.path) {
+            Ok(file) => file,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                self.start(local_head)?;
+                return Ok(None)
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        let mut reader = BufReader::new(&mut file);
+        let mut head = [0; 32];
+        if !read_exact_or_eof(&mut reader, &mut head)? || B256::from(head) != local_head {
+            debug!(target: "sync::stages::headers", path = ?self.path, "Discarding stale headers download checkpoint");
+            self.start(local_head)?;
+            return Ok(None)
+        }
+
+        let mut valid_len = head.len() as u64;
+        let mut lowest: Option<SealedHeader<H>> = None;
+        while let Some(header) = read_header::<H>(&mut reader)? {
+            if lowest.as_ref().is_some_and(|lowest| lowest.parent_hash() != header.hash()) {
+                break
+            }
+            valid_len = reader.stream_position()?;
+            f(header.clone())?;
+            lowest = Some(header);
+        }
+
+        // Continue after the last valid header
+        file.set_len(valid_len)?;
+        file.seek(SeekFrom::End(0))?;
+        self.writer = Some(BufWriter::new(file));
+
+        Ok(lowest)
+    }
+
+    /// Starts a new checkpoint for a download from the given local head, discarding the previous
+    /// checkpoint.
+    pub(crate) fn start(&mut self, local_head: B256) -> io::Result<()> {
+        self.writer = None;
+        if let Some(dir) = self.path.parent() {
+            reth_fs_util::create_dir_all(dir).map_err(io::Error::other)?;
+        }
+
+        let mut writer = BufWriter::new(File::create(&self.path)?);
+        writer.write_all(local_head.as_slice())?;
+        writer.flush()?;
+        self.writer = Some(writer);
+        Ok(())
+    }
+
+    /// Appends the downloaded headers, which have to continue the checkpointed headers with
+    /// falling block numbers, and syncs them to disk.
+    ///
+    /// If this fails, checkpointing is stopped until the next [`Self::start`].
+    pub(crate) fn append<H: FullBlockHeader>(
+        &mut self,
+        headers: &[SealedHeader<H>],
+    ) -> io::Result<()> {
+        let Some(writer) = self.writer.as_mut() else { return Ok(()) };
+
+        let result = headers
+            .iter()
+            .try_for_each(|header| {
+                let bytes = bincode::serialize(&serde_bincode_compat::SealedHeader::from(header))
+                    .map_err(io::Error::other)?;
+                let len = u32::try_from(bytes.len()).map_err(io::Error::other)?;
+                writer.write_all(&len.to_be_bytes())?;
+                writer.write_all(&bytes)
+            })
+            .and_then(|()| writer.flush())
+            .and_then(|()| writer.get_ref().sync_data());
+
+        if result.is_err() {
+            self.writer = None;
+        }
+        result
+    }
+
+    /// Removes the checkpoint.
+    pub(crate) fn remove(&mut self) -> io::Result<()> {
+        self.writer = None;
+        match std::fs::remove_file(&self.path) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Reads the next checkpointed header, returns `None` if there is no complete header left.
+///
+/// The hash of the header is recomputed, so corrupted headers fail to attach to their child.
+fn read_header<H: FullBlockHeader>(reader: &mut impl Read) -> io::Result<Option<SealedHeader<H>>> {
+    let mut len = [0; 4];
+    if !read_exact_or_eof(reader, &mut len)? {
+        return Ok(None)
+    }
+
+    let len = u32::from_be_bytes(len) as usize;
+    if len > MAX_HEADER_SIZE {
+        return Ok(None)
+    }
+
+    let mut bytes = vec![0; len];
+    if !read_exact_or_eof(reader, &mut bytes)? {
+        return Ok(None)
+    }
+
+    let Ok(header) = bincode::deserialize::<serde_bincode_compat::SealedHeader<'_, H>>(&bytes)
+    else {
+        return Ok(None)
+    };
+    Ok(Some(SealedHeader::seal_slow(SealedHeader::<H>::from(header).unseal())))
+}
+
+/// Fills the buffer, returns `false` if the reader ended before.
+fn read_exact_or_eof(reader: &mut impl Read, buf: &mut [u8]) -> io::Result<bool> {
+    match reader.read_exact(buf) {
+        Ok(()) => Ok(true),
+        Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => Ok(false),
+        Err(err) => Err(err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Header;
+    use reth_testing_utils::generators::{self, random_header_range};
+
+    fn open(
+        checkpoint: &mut HeadersDownloadCheckpoint,
+        local_head: B256,
+    ) -> (Vec<SealedHeader<Header>>, Option<SealedHeader<Header>>) {
+        let mut restored = Vec::new();
+        let lowest = checkpoint
+            .open(local_head, |header| {
+                restored.push(header);
+                Ok::<_, io::Error>(())
+            })
+            .unwrap();
+        (restored, lowest)
+    }
+
+    #[test]
+    fn resumes_checkpointed_headers() {
+        let mut rng = generators::rng();
+        let dir = tempfile::tempdir().unwrap();
+        let mut headers = random_header_range(&mut rng, 0..10, B256::ZERO);
+        let local_head = headers.remove(0);
+        headers.reverse();
+
+        let mut checkpoint = HeadersDownloadCheckpoint::new(dir.path().to_path_buf());
+        assert_eq!(open(&mut checkpoint, local_head.hash()), (Vec::new(), None));
+        checkpoint.append(&headers[..4]).unwrap();
+        checkpoint.append(&headers[4..6]).unwrap();
+
+        // Simulate an interrupted write of the next batch
+        drop(checkpoint);
+        let path = dir.path().join(CHECKPOINT_FILE_NAME);
+        let len = std::fs::metadata(&path).unwrap().len();
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(&[0, 0, 1]).unwrap();
+        drop(file);
+
+        let mut checkpoint = HeadersDownloadCheckpoint::new(dir.path().to_path_buf());
+        let (restored, lowest) = open(&mut checkpoint, local_head.hash());
+        assert_eq!(restored, headers[..6]);
+        assert_eq!(lowest.as_ref(), Some(&headers[5]));
+        assert_eq!(std::fs::metadata(&path).unwrap().len(), len);
+
+        // The resumed checkpoint continues after the lowest header
+        checkpoint.append(&headers[6..]).unwrap();
+        let (restored, _) = open(&mut checkpoint, local_head.hash());
+        assert_eq!(restored, headers);
+
+        // A checkpoint of another local head is discarded
+        assert_eq!(open(&mut checkpoint, B256::random()), (Vec::new(), None));
+        assert_eq!(
+            checkpoint.open::<Header, io::Error>(local_head.hash(), |_| unreachable!()).ok(),
+            Some(None)
+        );
+
+        checkpoint.remove().unwrap();
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,176 @@
+use alloy_primitives::{BlockNumber, TxNumber};
+use reth_config::config::IndexTransactionAddressesConfig;
+use reth_db_api::transaction::DbTxMut;
+use reth_provider::{BlockBodyIndicesProvider, DBProvider, ProviderError};
+use reth_stages_api::{
+    BlockRangeOutput, ExecInput, ExecOutput, Stage, StageCheckpoint, StageError, StageId,
+    UnwindInput, UnwindOutput,
+};
+use reth_storage_api::AddressTransactionsWriter;
+use std::ops::{Range, RangeInclusive};
+use tracing::info;
+
+/// Maximum number of transactions whose addresses are collected in memory at once.
+const TRANSACTIONS_BATCH_SIZE: u64 = 500_000;
+
+/// Optional stage indexing the transactions sent from or to each address, which allows
+/// `trace_filter` to only trace the transactions of the filtered addresses. For more information
+/// on index sharding take a look at [`tables::AddressTransactions`].
+///
+/// [`tables::AddressTransactions`]: reth_db_api::tables::AddressTransactions
+#[derive(Debug)]
+pub struct IndexTransactionAddressesStage {
+    /// Number of blocks after which the control
+    /// flow will be returned to the pipeline for commit.
+    pub commit_threshold: u64,
+}
+
+impl IndexTransactionAddressesStage {
+    /// Create new instance of [`IndexTransactionAddressesStage`].
+    pub const fn new(config: IndexTransactionAddressesConfig) -> Self {
+        Self { commit_threshold: config.commit_threshold }
+    }
+}
+
+impl Default for IndexTransactionAddressesStage {
+    fn default() -> Self {
+        Self { commit_threshold: 100_000 }
+    }
+}
+
+impl<Provider> Stage<Provider> for IndexTransactionAddressesStage
+where
+    Provider: DBProvider<Tx: DbTxMut> + BlockBodyIndicesProvider + AddressTransactionsWriter,
+{
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        StageId::IndexTransactionAddresses
+    }
+
+    /// Execute the stage.
+    fn execute(&mut self, provider: &Provider, input: ExecInput) -> Result<ExecOutput, StageError> {
+        if input.target_reached() {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+
+        let BlockRangeOutput { block_range, is_final_range } =
+            input.next_block_range_with_threshold(self.commit_threshold);
+        let tx_range = tx_range(provider, block_range.clone())?;
+
+        info!(target: "sync::stages::index_transaction_addresses", ?block_range, ?tx_range, "Indexing transaction addresses");
+
+        for batch_start in tx_range.clone().step_by(TRANSACTIONS_BATCH_SIZE as usize) {
+            let batch_end = (batch_start + TRANSACTIONS_BATCH_SIZE).min(tx_range.end);
+            provider.index_address_transactions(batch_start..batch_end)?;
+        }
+
+        Ok(ExecOutput {
+            checkpoint: StageCheckpoint::new(*block_range.end()),
+            done: is_final_range,
+        })
+    }
+
+    /// Unwind the stage.
+    fn unwind(
+        &mut self,
+        provider: &Provider,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        let (range, unwind_to, _) = input.unwind_block_range_with_threshold(self.commit_threshold);
+
+        provider.unwind_address_transactions(tx_range(provider, range)?)?;
+
+        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(unwind_to) })
+    }
+}
+
+/// Returns the range of transactions in the given block range.
+fn tx_range<Provider: BlockBodyIndicesProvider>(
+    provider: &Provider,
+    block_range: RangeInclusive<BlockNumber>,
+) -> Result<Range<TxNumber>, StageError> {
+    let first_block = provider
+        .block_body_indices(*block_range.start())?
+        .ok_or(ProviderError::BlockBodyIndicesNotFound(*block_range.start()))?;
+    let last_block = provider
+        .block_body_indices(*block_range.end())?
+        .ok_or(ProviderError::BlockBodyIndicesNotFound(*block_range.end()))?;
+
+    Ok(first_block.first_tx_num()..last_block.next_tx_num())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{StorageKind, TestStageDB};
+    use alloy_primitives::B256;
+    use reth_db_api::{cursor::DbCursorRO, tables, transaction::DbTx};
+    use reth_primitives_traits::SignerRecoverable;
+    use reth_provider::{DatabaseProviderFactory, StageCheckpointWriter};
+    use reth_storage_api::AddressTransactionsReader;
+    use reth_testing_utils::generators::{self, random_block_range, BlockRangeParams};
+
+    #[test]
+    fn execute_and_unwind() {
+        let mut rng = generators::rng();
+        let db = TestStageDB::default();
+
+        let blocks = random_block_range(
+            &mut rng,
+            0..=10,
+            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 1..3, ..Default::default() },
+        );
+        db.insert_blocks(blocks.iter(), StorageKind::Static).unwrap();
+
+        let senders = blocks
+            .iter()
+            .flat_map(|block| block.body().transactions.iter())
+            .map(|tx| tx.recover_signer().unwrap())
+            .collect::<Vec<_>>();
+        let first_unwound_tx =
+            blocks[..6].iter().map(|block| block.body().transactions.len() as u64).sum::<u64>();
+
+        let provider = db.factory.database_provider_rw().unwrap();
+        let mut stage = IndexTransactionAddressesStage::default();
+
+        let output =
+            stage.execute(&provider, ExecInput { target: Some(10), checkpoint: None }).unwrap();
+        assert_eq!(output, ExecOutput { checkpoint: StageCheckpoint::new(10), done: true });
+        provider
+            .save_stage_checkpoint(StageId::IndexTransactionAddresses, output.checkpoint)
+            .unwrap();
+
+        // every transaction is indexed for its sender
+        for (tx_num, sender) in senders.iter().enumerate() {
+            let transactions = provider.address_transactions(&[*sender], 0..=10).unwrap().unwrap();
+            assert!(transactions.contains(&(tx_num as u64)));
+        }
+        assert_eq!(
+            provider.address_transactions(&senders, 0..=10).unwrap(),
+            Some((0..senders.len() as u64).collect())
+        );
+        // the range above the checkpoint isn't covered by the index
+        assert_eq!(provider.address_transactions(&senders, 0..=11).unwrap(), None);
+
+        let output = stage
+            .unwind(
+                &provider,
+                UnwindInput { checkpoint: StageCheckpoint::new(10), unwind_to: 5, bad_block: None },
+            )
+            .unwrap();
+        assert_eq!(output, UnwindOutput { checkpoint: StageCheckpoint::new(5) });
+        provider
+            .save_stage_checkpoint(StageId::IndexTransactionAddresses, output.checkpoint)
+            .unwrap();
+
+        assert_eq!(
+            provider.address_transactions(&senders, 0..=5).unwrap(),
+            Some((0..first_unwound_tx).collect())
+        );
+        let mut cursor = provider.tx_ref().cursor_read::<tables::AddressTransactions>().unwrap();
+        for entry in cursor.walk(None).unwrap() {
+            let (_, list) = entry.unwrap();
+            assert!(list.iter().all(|tx_num| tx_num < first_unwound_tx));
+        }
+    }
+}
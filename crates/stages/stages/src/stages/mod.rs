@@ -1,5 +1,7 @@
 /// The bodies stage.
 mod bodies;
+/// Optional contract indices recorded during execution
+mod contracts_indexer;
 mod era;
 /// The execution stage that generates state diff.
 mod execution;
@@ -11,19 +13,28 @@ mod hashing_account;
 mod hashing_storage;
 /// The headers stage.
 mod headers;
+/// Persisted progress of the headers download.
+mod headers_checkpoint;
 /// Index history of account changes
 mod index_account_history;
 /// Index history of storage changes
 mod index_storage_history;
+/// Index transactions by their sender and recipient addresses
+mod index_transaction_addresses;
 /// Stage for computing state root.
 mod merkle;
+/// Parallel re-execution of historical block batches.
+mod parallel_execution;
 mod prune;
 /// The sender recovery stage.
 mod sender_recovery;
 /// The transaction lookup stage
 mod tx_lookup;
+/// Verify imported receipts against the headers
+mod verify_receipts;
 
 pub use bodies::*;
+pub use contracts_indexer::*;
 pub use era::*;
 pub use execution::*;
 pub use finish::*;
@@ -32,10 +43,13 @@ pub use hashing_storage::*;
 pub use headers::*;
 pub use index_account_history::*;
 pub use index_storage_history::*;
+pub use index_transaction_addresses::*;
 pub use merkle::*;
+pub use parallel_execution::*;
 pub use prune::*;
 pub use sender_recovery::*;
 pub use tx_lookup::*;
+pub use verify_receipts::*;
 
 mod utils;
 use utils::*;
@@ -0,0 +1,263 @@
+use alloy_primitives::BlockNumber;
+use rayon::prelude::*;
+use reth_evm::{
+    execute::{BlockExecutionError, ExecutionOutcome, Executor},
+    ConfigureEvm,
+};
+use reth_primitives_traits::NodePrimitives;
+use reth_provider::{BlockReader, StateProviderFactory};
+use reth_revm::database::StateProviderDatabase;
+use reth_storage_errors::provider::ProviderError;
+use std::{ops::RangeInclusive, time::Instant};
+use tracing::*;
+
+/// The default number of blocks executed sequentially by a single worker.
+pub const DEFAULT_PARALLEL_EXECUTION_BATCH_SIZE: u64 = 1_000;
+
+/// Errors that can occur during [`ParallelHistoricalExecutor`] runs.
+#[derive(Debug, thiserror::Error)]
+pub enum ParallelExecutionError {
+    /// Failed to read blocks or historical state for a batch.
+    #[error("batch {batch:?}: {error}")]
+    Provider {
+        /// The block range of the batch that failed.
+        batch: RangeInclusive<BlockNumber>,
+        /// The underlying provider error.
+        #[source]
+        error: ProviderError,
+    },
+    /// A block in the batch failed to execute.
+    #[error("batch {batch:?}: {error}")]
+    Execution {
+        /// The block range of the batch that failed.
+        batch: RangeInclusive<BlockNumber>,
+        /// The underlying execution error.
+        #[source]
+        error: BlockExecutionError,
+    },
+    /// The requested range starts at genesis, which has no pre-state to execute against.
+    #[error("cannot re-execute genesis block")]
+    Genesis,
+}
+
+/// Executes historical block ranges by splitting them into independent batches.
+///
+/// Every batch is executed on its own worker against the historical state at the block preceding
+/// the batch, which is reconstructed from the changesets already present in the database. This
+/// makes batches independent of each other, so they can run concurrently even though the blocks
+/// within a batch are still executed sequentially.
+///
+/// Outcomes of all batches are merged in block order, producing the same [`ExecutionOutcome`] a
+/// sequential execution of the whole range would.
+///
+/// This is only applicable to blocks that were already executed and whose changesets are
+/// available, e.g. on archive nodes re-executing ranges for trace backfills or verification.
+#[derive(Debug, Clone)]
+pub struct ParallelHistoricalExecutor<P, E> {
+    /// Provider used to read blocks and historical state.
+    provider: P,
+    /// The EVM configuration used to execute blocks.
+    evm_config: E,
+    /// The number of blocks executed sequentially by a single worker.
+    batch_size: u64,
+}
+
+impl<P, E> ParallelHistoricalExecutor<P, E> {
+    /// Creates a new executor with the [`DEFAULT_PARALLEL_EXECUTION_BATCH_SIZE`].
+    pub const fn new(provider: P, evm_config: E) -> Self {
+        Self { provider, evm_config, batch_size: DEFAULT_PARALLEL_EXECUTION_BATCH_SIZE }
+    }
+
+    /// Sets the number of blocks executed sequentially by a single worker.
+    ///
+    /// Smaller batches yield more parallelism, at the cost of more historical state lookups.
+    pub const fn with_batch_size(mut self, batch_size: u64) -> Self {
+        self.batch_size = if batch_size == 0 { 1 } else { batch_size };
+        self
+    }
+
+    /// Returns the number of blocks executed sequentially by a single worker.
+    pub const fn batch_size(&self) -> u64 {
+        self.batch_size
+    }
+
+    /// Splits the given range into the batches that will be executed concurrently.
+    pub fn batches(&self, range: RangeInclusive<BlockNumber>) -> Vec<RangeInclusive<BlockNumber>> {
+        split_range(range, self.batch_size)
+    }
+}
+
+impl<P, E, N> ParallelHistoricalExecutor<P, E>
+where
+    P: StateProviderFactory + BlockReader<Block = N::Block> + Send + Sync,
+    E: ConfigureEvm<Primitives = N> + Send + Sync,
+    N: NodePrimitives,
+{
+    /// Executes the given range of blocks and returns the merged [`ExecutionOutcome`].
+    ///
+    /// Batches are executed on the global rayon thread pool.
+    pub fn execute_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> Result<ExecutionOutcome<N::Receipt>, ParallelExecutionError> {
+        if *range.start() == 0 {
+            return Err(ParallelExecutionError::Genesis)
+        }
+
+        let start = Instant::now();
+        let batches = self.batches(range.clone());
+        debug!(target: "sync::stages::execution::parallel", ?range, batches = batches.len(), "Executing historical blocks in parallel");
+
+        let outcomes = batches
+            .into_par_iter()
+            .map(|batch| self.execute_batch(batch))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut outcomes = outcomes.into_iter();
+        let mut merged = outcomes.next().unwrap_or_default();
+        for outcome in outcomes {
+            merged.extend(outcome);
+        }
+
+        debug!(target: "sync::stages::execution::parallel", ?range, elapsed = ?start.elapsed(), "Finished executing historical blocks in parallel");
+
+        Ok(merged)
+    }
+
+    /// Executes a single batch against the state at the block preceding it.
+    fn execute_batch(
+        &self,
+        batch: RangeInclusive<BlockNumber>,
+    ) -> Result<ExecutionOutcome<N::Receipt>, ParallelExecutionError> {
+        let provider_err = |error| ParallelExecutionError::Provider { batch: batch.clone(), error };
+
+        let state =
+            self.provider.history_by_block_number(*batch.start() - 1).map_err(provider_err)?;
+        let blocks = self.provider.recovered_block_range(batch.clone()).map_err(provider_err)?;
+
+        trace!(target: "sync::stages::execution::parallel", ?batch, blocks = blocks.len(), "Executing batch");
+
+        self.evm_config
+            .batch_executor(StateProviderDatabase::new(state))
+            .execute_batch(&blocks)
+            .map_err(|error| ParallelExecutionError::Execution { batch, error })
+    }
+}
+
+/// Splits the range into consecutive chunks of at most `batch_size` blocks.
+fn split_range(
+    range: RangeInclusive<BlockNumber>,
+    batch_size: u64,
+) -> Vec<RangeInclusive<BlockNumber>> {
+    let (start, end) = range.into_inner();
+    let mut batches = Vec::new();
+    let mut batch_start = start;
+    while batch_start <= end {
+        let batch_end = batch_start.saturating_add(batch_size - 1).min(end);
+        batches.push(batch_start..=batch_end);
+        if batch_end == u64::MAX {
+            break
+        }
+        batch_start = batch_end + 1;
+    }
+    batches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Header;
+    use alloy_primitives::{address, U256};
+    use reth_chainspec::{ChainSpecBuilder, MAINNET};
+    use reth_db_common::init::init_genesis;
+    use reth_ethereum_primitives::{Block, BlockBody};
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_primitives_traits::{Block as _, RecoveredBlock};
+    use reth_provider::{
+        providers::BlockchainProvider, test_utils::create_test_provider_factory_with_chain_spec,
+        BlockWriter, LatestStateProvider,
+    };
+    use std::sync::Arc;
+
+    #[test]
+    fn parallel_execution_matches_sequential_execution() {
+        // pre-merge blocks reward the same beneficiary, so every batch depends on the state
+        // written by the previous ones
+        let mut genesis = MAINNET.genesis.clone();
+        genesis.alloc.clear();
+        let chain_spec = Arc::new(
+            ChainSpecBuilder::default()
+                .chain(MAINNET.chain)
+                .genesis(genesis)
+                .berlin_activated()
+                .build(),
+        );
+        let evm_config = EthEvmConfig::ethereum(chain_spec.clone());
+        let factory = create_test_provider_factory_with_chain_spec(chain_spec.clone());
+        let genesis_hash = init_genesis(&factory).unwrap();
+
+        let beneficiary = address!("0x000000000000000000000000000000000000c014");
+        let mut parent_hash = genesis_hash;
+        let mut blocks = Vec::new();
+        for number in 1..=7 {
+            let block: RecoveredBlock<Block> = Block {
+                header: Header {
+                    parent_hash,
+                    beneficiary,
+                    number,
+                    timestamp: number * 12,
+                    difficulty: U256::from(1),
+                    gas_limit: 30_000_000,
+                    ..Default::default()
+                },
+                body: BlockBody::default(),
+            }
+            .try_into_recovered()
+            .unwrap();
+            parent_hash = block.hash();
+            blocks.push(block);
+        }
+
+        // execute the blocks sequentially and write their changesets
+        let mut sequential = evm_config
+            .batch_executor(StateProviderDatabase::new(LatestStateProvider::new(
+                factory.provider().unwrap(),
+            )))
+            .execute_batch(&blocks)
+            .unwrap();
+        sequential.bundle.reverts.sort();
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw
+            .append_blocks_with_state(blocks.clone(), &sequential, Default::default())
+            .unwrap();
+        provider_rw.commit().unwrap();
+
+        // re-execute them in batches against the historical state
+        let provider = BlockchainProvider::new(factory).unwrap();
+        let executor = ParallelHistoricalExecutor::new(provider, evm_config).with_batch_size(3);
+        assert_eq!(executor.batches(1..=7), vec![1..=3, 4..=6, 7..=7]);
+        let mut parallel = executor.execute_range(1..=7).unwrap();
+        parallel.bundle.reverts.sort();
+
+        assert_eq!(parallel.first_block, 1);
+        assert_eq!(parallel.receipts, sequential.receipts);
+        assert_eq!(parallel.bundle.reverts, sequential.bundle.reverts);
+        let account = parallel.bundle.account(&beneficiary).unwrap();
+        assert_eq!(account.original_info, None);
+        assert_eq!(account.info, sequential.bundle.account(&beneficiary).unwrap().info);
+        assert!(account.info.as_ref().unwrap().balance > U256::ZERO);
+
+        assert!(matches!(executor.execute_range(0..=1), Err(ParallelExecutionError::Genesis)));
+    }
+
+    #[test]
+    fn split_range_into_batches() {
+        assert_eq!(split_range(1..=10, 4), vec![1..=4, 5..=8, 9..=10]);
+        assert_eq!(split_range(1..=8, 4), vec![1..=4, 5..=8]);
+        assert_eq!(split_range(5..=5, 4), vec![5..=5]);
+        assert_eq!(split_range(1..=3, 1), vec![1..=1, 2..=2, 3..=3]);
+        #[expect(clippy::reversed_empty_ranges)]
+        let empty = split_range(5..=4, 4);
+        assert!(empty.is_empty());
+    }
+}
@@ -0,0 +1,286 @@
+use alloy_consensus::{proofs::calculate_receipt_root, BlockHeader, TxReceipt};
+use alloy_primitives::{BlockNumber, Bloom};
+use rayon::prelude::*;
+use reth_chainspec::{ChainSpecProvider, EthereumHardfork, EthereumHardforks};
+use reth_config::config::VerifyReceiptsConfig;
+use reth_consensus::ConsensusError;
+use reth_primitives_traits::{GotExpected, Receipt};
+use reth_provider::{
+    DBProvider, HeaderProvider, ProviderError, PruneCheckpointReader, ReceiptProvider,
+};
+use reth_prune_types::{PruneModes, PrunePurpose, PruneSegment};
+use reth_stages_api::{
+    BlockErrorKind, BlockRangeOutput, ExecInput, ExecOutput, Stage, StageCheckpoint, StageError,
+    StageId, UnwindInput, UnwindOutput,
+};
+use std::ops::RangeInclusive;
+use tracing::{info, warn};
+
+/// Number of blocks whose receipts are loaded and verified at once.
+const VERIFY_BATCH_SIZE: u64 = 64;
+
+/// Optional stage verifying the stored receipts against the receipts roots and logs blooms of the
+/// headers.
+///
+/// Receipts that are imported from external sources, e.g. with `reth import-receipts` or with
+/// downloaded static files, are not produced by the [`ExecutionStage`](super::ExecutionStage), so
+/// they are never validated against the headers. This stage recomputes the receipts root and logs
+/// bloom of every block, in parallel batches of [`VERIFY_BATCH_SIZE`] blocks, so a corrupted
+/// archive is caught at import time instead of when its receipts are served.
+///
+/// Blocks whose receipts are pruned, or not stored because of the configured [`PruneModes`], are
+/// skipped. So are the blocks before Byzantium, whose receipts commit to intermediate state roots
+/// that are not stored, like the consensus only checks receipts from Byzantium on.
+#[derive(Debug)]
+pub struct VerifyReceiptsStage {
+    /// Number of blocks after which the control
+    /// flow will be returned to the pipeline for commit.
+    pub commit_threshold: u64,
+    /// Prune configuration, to skip the blocks whose receipts are not stored.
+    prune_modes: PruneModes,
+}
+
+impl VerifyReceiptsStage {
+    /// Create new instance of [`VerifyReceiptsStage`].
+    pub const fn new(config: VerifyReceiptsConfig, prune_modes: PruneModes) -> Self {
+        Self { commit_threshold: config.commit_threshold, prune_modes }
+    }
+
+    /// Returns the highest block whose receipts are not stored, if any.
+    fn highest_pruned_block<Provider: PruneCheckpointReader>(
+        &self,
+        provider: &Provider,
+        target: BlockNumber,
+    ) -> Result<Option<BlockNumber>, StageError> {
+        let mut pruned = None;
+        for segment in [PruneSegment::Receipts, PruneSegment::ContractLogs] {
+            let checkpoint = provider.get_prune_checkpoint(segment)?;
+            pruned = pruned.max(checkpoint.and_then(|checkpoint| checkpoint.block_number));
+        }
+
+        // the receipts that are pruned right away may not be written by the execution stage
+        if let Some((block, _)) = self
+            .prune_modes
+            .receipts
+            .and_then(|mode| {
+                mode.prune_target_block(target, PruneSegment::Receipts, PrunePurpose::User).ok()
+            })
+            .flatten()
+        {
+            pruned = pruned.max(Some(block));
+        }
+
+        Ok(pruned)
+    }
+}
+
+impl Default for VerifyReceiptsStage {
+    fn default() -> Self {
+        Self { commit_threshold: 100_000, prune_modes: PruneModes::default() }
+    }
+}
+
+impl<Provider> Stage<Provider> for VerifyReceiptsStage
+where
+    Provider: DBProvider
+        + HeaderProvider
+        + ReceiptProvider<Receipt: Receipt>
+        + PruneCheckpointReader
+        + ChainSpecProvider<ChainSpec: EthereumHardforks>,
+{
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        StageId::VerifyReceipts
+    }
+
+    /// Execute the stage.
+    fn execute(&mut self, provider: &Provider, input: ExecInput) -> Result<ExecOutput, StageError> {
+        if input.target_reached() {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+
+        let BlockRangeOutput { block_range, is_final_range } =
+            input.next_block_range_with_threshold(self.commit_threshold);
+        let checkpoint = StageCheckpoint::new(*block_range.end());
+
+        if !self.prune_modes.receipts_log_filter.is_empty() {
+            // only the receipts matching the filter are stored, so the roots can't be recomputed
+            warn!(target: "sync::stages::verify_receipts", "Receipts are pruned by a contract logs filter, skipping verification");
+            return Ok(ExecOutput { checkpoint, done: is_final_range })
+        }
+
+        let start = match self.highest_pruned_block(provider, input.target())? {
+            Some(pruned) if pruned >= *block_range.end() => {
+                return Ok(ExecOutput { checkpoint, done: is_final_range })
+            }
+            Some(pruned) => (*block_range.start()).max(pruned + 1),
+            None => *block_range.start(),
+        };
+
+        let byzantium = provider.chain_spec().ethereum_fork_activation(EthereumHardfork::Byzantium);
+        if !byzantium.active_at_block(*block_range.end()) {
+            return Ok(ExecOutput { checkpoint, done: is_final_range })
+        }
+        let start = start.max(byzantium.block_number().unwrap_or_default());
+        let end = *block_range.end();
+
+        info!(target: "sync::stages::verify_receipts", block_range = ?start..=end, "Verifying receipts");
+
+        for batch_start in (start..=end).step_by(VERIFY_BATCH_SIZE as usize) {
+            let batch_end = (batch_start + VERIFY_BATCH_SIZE - 1).min(end);
+            verify_block_range(provider, batch_start..=batch_end)?;
+        }
+
+        Ok(ExecOutput { checkpoint, done: is_final_range })
+    }
+
+    /// Unwind the stage.
+    fn unwind(
+        &mut self,
+        _provider: &Provider,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(input.unwind_to) })
+    }
+}
+
+/// Verifies the receipts of all blocks of the range in parallel.
+fn verify_block_range<Provider>(
+    provider: &Provider,
+    block_range: RangeInclusive<BlockNumber>,
+) -> Result<(), StageError>
+where
+    Provider: HeaderProvider + ReceiptProvider<Receipt: Receipt>,
+{
+    let start = *block_range.start();
+    let headers = provider.headers_range(block_range.clone())?;
+    let receipts = provider.receipts_by_block_range(block_range)?;
+    if headers.len() != receipts.len() {
+        let missing = start + headers.len().min(receipts.len()) as u64;
+        return Err(ProviderError::BlockBodyIndicesNotFound(missing).into())
+    }
+
+    let invalid = headers.par_iter().zip(&receipts).enumerate().find_map_first(
+        |(index, (header, receipts))| {
+            verify_receipts(header, receipts).err().map(|err| (start + index as u64, err))
+        },
+    );
+
+    if let Some((block_number, err)) = invalid {
+        let header = provider
+            .sealed_header(block_number)?
+            .ok_or_else(|| ProviderError::HeaderNotFound(block_number.into()))?;
+        return Err(StageError::Block {
+            block: Box::new(header.block_with_parent()),
+            error: BlockErrorKind::Validation(err),
+        })
+    }
+
+    Ok(())
+}
+
+/// Compares the receipts root and logs bloom of the receipts with the ones of the header.
+fn verify_receipts<H: BlockHeader, R: Receipt>(
+    header: &H,
+    receipts: &[R],
+) -> Result<(), ConsensusError> {
+    let receipts_with_bloom = receipts.iter().map(TxReceipt::with_bloom_ref).collect::<Vec<_>>();
+
+    let receipts_root = calculate_receipt_root(&receipts_with_bloom);
+    if receipts_root != header.receipts_root() {
+        return Err(ConsensusError::BodyReceiptRootDiff(
+            GotExpected { got: receipts_root, expected: header.receipts_root() }.into(),
+        ))
+    }
+
+    let logs_bloom = receipts_with_bloom.iter().fold(Bloom::ZERO, |bloom, r| bloom | r.bloom_ref());
+    if logs_bloom != header.logs_bloom() {
+        return Err(ConsensusError::BodyBloomLogDiff(
+            GotExpected { got: logs_bloom, expected: header.logs_bloom() }.into(),
+        ))
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{StorageKind, TestStageDB};
+    use alloy_primitives::B256;
+    use assert_matches::assert_matches;
+    use reth_chainspec::{ChainSpecBuilder, ForkCondition};
+    use reth_primitives_traits::Block as _;
+    use reth_provider::DatabaseProviderFactory;
+    use reth_testing_utils::generators::{
+        self, random_block_range, random_receipt, BlockRangeParams,
+    };
+    use std::sync::Arc;
+
+    #[test]
+    fn detects_corrupted_receipts() {
+        let mut rng = generators::rng();
+        let chain_spec = ChainSpecBuilder::mainnet()
+            .with_fork(EthereumHardfork::Byzantium, ForkCondition::Block(3))
+            .build();
+        let db = TestStageDB::with_chain_spec(Arc::new(chain_spec));
+
+        let mut blocks = Vec::new();
+        let mut receipts = Vec::new();
+        let mut tx_num = 0u64;
+        for block in random_block_range(
+            &mut rng,
+            0..=10,
+            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 1..3, ..Default::default() },
+        ) {
+            let block_receipts = block
+                .body()
+                .transactions
+                .iter()
+                .map(|tx| random_receipt(&mut rng, tx, Some(1), None))
+                .collect::<Vec<_>>();
+
+            // seal the block with the roots of its receipts
+            let mut block = block.unseal();
+            let receipts_with_bloom =
+                block_receipts.iter().map(TxReceipt::with_bloom_ref).collect::<Vec<_>>();
+            block.header.receipts_root = calculate_receipt_root(&receipts_with_bloom);
+            block.header.logs_bloom =
+                receipts_with_bloom.iter().fold(Bloom::ZERO, |bloom, r| bloom | r.bloom_ref());
+
+            let first_tx_num = tx_num;
+            tx_num += block_receipts.len() as u64;
+            receipts.push((
+                block.header.number,
+                (first_tx_num..tx_num).zip(block_receipts).collect::<Vec<_>>(),
+            ));
+            blocks.push(block.seal_slow());
+        }
+
+        // corrupt a receipt of block 6, and of block 1 which is before Byzantium and not verified
+        receipts[6].1[0].1.cumulative_gas_used += 1;
+        receipts[1].1[0].1.cumulative_gas_used += 1;
+
+        db.insert_blocks(blocks.iter(), StorageKind::Static).unwrap();
+        db.insert_receipts_by_block(receipts, StorageKind::Static).unwrap();
+
+        let provider = db.factory.database_provider_rw().unwrap();
+        let mut stage = VerifyReceiptsStage::default();
+
+        let output =
+            stage.execute(&provider, ExecInput { target: Some(5), checkpoint: None }).unwrap();
+        assert_eq!(output, ExecOutput { checkpoint: StageCheckpoint::new(5), done: true });
+
+        let result = stage.execute(
+            &provider,
+            ExecInput { target: Some(10), checkpoint: Some(StageCheckpoint::new(5)) },
+        );
+        assert_matches!(
+            result,
+            Err(StageError::Block {
+                block,
+                error: BlockErrorKind::Validation(ConsensusError::BodyReceiptRootDiff(_)),
+            }) if block.block.number == 6
+        );
+    }
+}
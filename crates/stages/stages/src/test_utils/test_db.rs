@@ -1,5 +1,5 @@
 use alloy_primitives::{keccak256, Address, BlockNumber, TxHash, TxNumber, B256};
-use reth_chainspec::MAINNET;
+use reth_chainspec::{ChainSpec, MAINNET};
 use reth_db::{
     test_utils::{
         create_test_rocksdb_dir, create_test_rw_db, create_test_rw_db_with_path,
@@ -30,7 +30,7 @@ use reth_provider::{
 use reth_static_file_types::StaticFileSegment;
 use reth_storage_errors::provider::ProviderResult;
 use reth_testing_utils::generators::ChangeSet;
-use std::{collections::BTreeMap, fmt::Debug, path::Path};
+use std::{collections::BTreeMap, fmt::Debug, path::Path, sync::Arc};
 use tempfile::TempDir;
 
 /// Test database that is used for testing stage implementations.
@@ -44,6 +44,13 @@ pub struct TestStageDB {
 impl Default for TestStageDB {
     /// Create a new instance of [`TestStageDB`]
     fn default() -> Self {
+        Self::with_chain_spec(MAINNET.clone())
+    }
+}
+
+impl TestStageDB {
+    /// Create a new instance of [`TestStageDB`] for the given chain spec
+    pub fn with_chain_spec(chain_spec: Arc<ChainSpec>) -> Self {
         let (static_dir, static_dir_path) = create_test_static_files_dir();
         let (rocksdb_dir, rocksdb_dir_path) = create_test_rocksdb_dir();
         Self {
@@ -51,16 +58,14 @@ impl Default for TestStageDB {
             temp_rocksdb_dir: rocksdb_dir,
             factory: ProviderFactory::new(
                 create_test_rw_db(),
-                MAINNET.clone(),
+                chain_spec,
                 StaticFileProvider::read_write(static_dir_path).unwrap(),
                 RocksDBProvider::builder(rocksdb_dir_path).with_default_tables().build().unwrap(),
             )
             .expect("failed to create test provider factory"),
         }
     }
-}
 
-impl TestStageDB {
     pub fn new(path: &Path) -> Self {
         let (static_dir, static_dir_path) = create_test_static_files_dir();
         let (rocksdb_dir, rocksdb_dir_path) = create_test_rocksdb_dir();
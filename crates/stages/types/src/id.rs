@@ -21,6 +21,9 @@ pub enum StageId {
     Bodies,
     SenderRecovery,
     Execution,
+    /// Optional stage verifying the stored receipts against the headers, only run if enabled.
+    /// It's not part of [`StageId::ALL`].
+    VerifyReceipts,
     PruneSenderRecovery,
     MerkleUnwind,
     AccountHashing,
@@ -29,6 +32,15 @@ pub enum StageId {
     TransactionLookup,
     IndexStorageHistory,
     IndexAccountHistory,
+    /// Optional stage indexing the transactions of each address, only run if enabled. It's not
+    /// part of [`StageId::ALL`].
+    IndexTransactionAddresses,
+    /// Checkpoint of the optional contract creations index, recorded while executing the blocks if
+    /// enabled. It's not a stage of the pipeline and not part of [`StageId::ALL`].
+    IndexContractCreations,
+    /// Checkpoint of the optional account destructions index, recorded while executing the blocks
+    /// if enabled. It's not a stage of the pipeline and not part of [`StageId::ALL`].
+    IndexAccountDestructions,
     Prune,
     Finish,
     /// Other custom stage with a provided string identifier.
@@ -86,6 +98,7 @@ impl StageId {
             Self::Bodies => "Bodies",
             Self::SenderRecovery => "SenderRecovery",
             Self::Execution => "Execution",
+            Self::VerifyReceipts => "VerifyReceipts",
             Self::PruneSenderRecovery => "PruneSenderRecovery",
             Self::MerkleUnwind => "MerkleUnwind",
             Self::AccountHashing => "AccountHashing",
@@ -94,6 +107,9 @@ impl StageId {
             Self::TransactionLookup => "TransactionLookup",
             Self::IndexAccountHistory => "IndexAccountHistory",
             Self::IndexStorageHistory => "IndexStorageHistory",
+            Self::IndexTransactionAddresses => "IndexTransactionAddresses",
+            Self::IndexContractCreations => "IndexContractCreations",
+            Self::IndexAccountDestructions => "IndexAccountDestructions",
             Self::Prune => "Prune",
             Self::Finish => "Finish",
             Self::Other(s) => s,
@@ -152,6 +168,7 @@ mod tests {
         assert_eq!(StageId::Bodies.to_string(), "Bodies");
         assert_eq!(StageId::SenderRecovery.to_string(), "SenderRecovery");
         assert_eq!(StageId::Execution.to_string(), "Execution");
+        assert_eq!(StageId::VerifyReceipts.to_string(), "VerifyReceipts");
         assert_eq!(StageId::MerkleUnwind.to_string(), "MerkleUnwind");
         assert_eq!(StageId::AccountHashing.to_string(), "AccountHashing");
         assert_eq!(StageId::StorageHashing.to_string(), "StorageHashing");
@@ -159,6 +176,9 @@ mod tests {
         assert_eq!(StageId::IndexAccountHistory.to_string(), "IndexAccountHistory");
         assert_eq!(StageId::IndexStorageHistory.to_string(), "IndexStorageHistory");
         assert_eq!(StageId::TransactionLookup.to_string(), "TransactionLookup");
+        assert_eq!(StageId::IndexTransactionAddresses.to_string(), "IndexTransactionAddresses");
+        assert_eq!(StageId::IndexContractCreations.to_string(), "IndexContractCreations");
+        assert_eq!(StageId::IndexAccountDestructions.to_string(), "IndexAccountDestructions");
         assert_eq!(StageId::Finish.to_string(), "Finish");
 
         assert_eq!(StageId::Other("Foo").to_string(), "Foo");
@@ -6,14 +6,14 @@
 //!
 //! # Key Components
 //!
-//! * `WitnessDatabase`: An implementation of [`reth_revm::Database`] that uses a
-//!   [`reth_trie_sparse::SparseStateTrie`] populated from witness data, along with provided
-//!   bytecode and ancestor block hashes, to serve state reads during execution.
+//! * `StatelessSparseTrie`: A [`StatelessTrie`] backed by a [`reth_trie_sparse::SparseStateTrie`]
+//!   populated from witness data, used to serve state reads during execution and to compute the
+//!   post-state root.
 //! * `stateless_validation`: The core function that orchestrates the stateless validation process.
 //!   It takes a block, its execution witness, ancestor headers, and chain specification, then
 //!   performs:
 //!     1. Witness verification against the parent block's state root.
-//!     2. Block execution using the `WitnessDatabase`.
+//!     2. Block execution using the [`reth_evm::stateless::StatelessBlockExecutor`].
 //!     3. Post-execution consensus checks.
 //!     4. Post-state root calculation and comparison against the block header.
 //!
@@ -51,7 +51,6 @@ pub use validation::stateless_validation_with_trie;
 
 /// Implementation of stateless validation
 pub mod validation;
-pub(crate) mod witness_db;
 
 #[doc(inline)]
 pub use alloy_rpc_types_debug::ExecutionWitness;
@@ -6,6 +6,7 @@ use alloy_rpc_types_debug::ExecutionWitness;
 use alloy_trie::{TrieAccount, EMPTY_ROOT_HASH};
 use itertools::Itertools;
 use reth_errors::ProviderError;
+use reth_evm::stateless::StatelessState;
 use reth_revm::state::Bytecode;
 use reth_trie_common::{HashedPostState, Nibbles, TRIE_ACCOUNT_RLP_MAX_SIZE};
 use reth_trie_sparse::{
@@ -153,6 +154,27 @@ impl StatelessTrie for StatelessSparseTrie {
     }
 }
 
+/// Adapter exposing a [`StatelessTrie`] as a [`StatelessState`] for the
+/// [`StatelessBlockExecutor`](reth_evm::stateless::StatelessBlockExecutor).
+#[derive(Debug)]
+pub(crate) struct StatelessTrieState<T>(pub(crate) T);
+
+impl<T: StatelessTrie> StatelessState for StatelessTrieState<T> {
+    type Error = StatelessValidationError;
+
+    fn account(&self, address: Address) -> Result<Option<TrieAccount>, ProviderError> {
+        self.0.account(address)
+    }
+
+    fn storage(&self, address: Address, slot: U256) -> Result<U256, ProviderError> {
+        self.0.storage(address, slot)
+    }
+
+    fn state_root(&mut self, state: HashedPostState) -> Result<B256, Self::Error> {
+        self.0.calculate_state_root(state)
+    }
+}
+
 /// Verifies execution witness [`ExecutionWitness`] against an expected pre-state root.
 ///
 /// This function takes the RLP-encoded values provided in [`ExecutionWitness`]
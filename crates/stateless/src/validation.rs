@@ -1,7 +1,6 @@
 use crate::{
     recover_block::{recover_block_with_public_keys, UncompressedPublicKey},
-    trie::{StatelessSparseTrie, StatelessTrie},
-    witness_db::WitnessDatabase,
+    trie::{StatelessSparseTrie, StatelessTrie, StatelessTrieState},
     ExecutionWitness,
 };
 use alloc::{
@@ -19,11 +18,11 @@ use reth_errors::ConsensusError;
 use reth_ethereum_consensus::{validate_block_post_execution, EthBeaconConsensus};
 use reth_ethereum_primitives::{Block, EthPrimitives, EthereumReceipt};
 use reth_evm::{
-    execute::{BlockExecutionOutput, Executor},
+    execute::BlockExecutionOutput,
+    stateless::{StatelessBlockExecutor, StatelessExecutionError},
     ConfigureEvm,
 };
 use reth_primitives_traits::{RecoveredBlock, SealedHeader};
-use reth_trie_common::{HashedPostState, KeccakKeyHasher};
 
 /// BLOCKHASH ancestor lookup window limit per EVM (number of most recent blocks accessible).
 const BLOCKHASH_ANCESTOR_LIMIT: usize = 256;
@@ -219,16 +218,17 @@ where
     validate_block_consensus(chain_spec.clone(), &current_block, parent)?;
 
     // First verify that the pre-state reads are correct
-    let (mut trie, bytecode) = T::new(&witness, parent.state_root)?;
+    let (trie, bytecode) = T::new(&witness, parent.state_root)?;
 
-    // Create an in-memory database that will use the reads to validate the block
-    let db = WitnessDatabase::new(&trie, bytecode, ancestor_hashes);
-
-    // Execute the block
-    let executor = evm_config.executor(db);
-    let output = executor
-        .execute(&current_block)
-        .map_err(|e| StatelessValidationError::StatelessExecutionFailed(e.to_string()))?;
+    // Execute the block against the verified witness data
+    let mut executor = StatelessBlockExecutor::new(
+        evm_config,
+        StatelessTrieState(trie),
+        bytecode,
+        ancestor_hashes,
+    );
+    let output =
+        executor.execute_without_state_root(&current_block).map_err(stateless_execution_error)?;
 
     // Post validation checks
     validate_block_post_execution(
@@ -241,19 +241,27 @@ where
     .map_err(StatelessValidationError::ConsensusValidationFailed)?;
 
     // Compute and check the post state root
-    let hashed_state = HashedPostState::from_bundle_state::<KeccakKeyHasher>(&output.state.state);
-    let state_root = trie.calculate_state_root(hashed_state)?;
-    if state_root != current_block.state_root {
-        return Err(StatelessValidationError::PostStateRootMismatch {
-            got: state_root,
-            expected: current_block.state_root,
-        });
-    }
+    executor.validate_state_root(&current_block, &output).map_err(stateless_execution_error)?;
 
     // Return block hash
     Ok((current_block.hash_slow(), output))
 }
 
+/// Converts an error of the [`StatelessBlockExecutor`] into a [`StatelessValidationError`].
+fn stateless_execution_error(
+    err: StatelessExecutionError<StatelessValidationError>,
+) -> StatelessValidationError {
+    match err {
+        StatelessExecutionError::Execution(err) => {
+            StatelessValidationError::StatelessExecutionFailed(err.to_string())
+        }
+        StatelessExecutionError::StateRoot(err) => err,
+        StatelessExecutionError::StateRootMismatch { got, expected } => {
+            StatelessValidationError::PostStateRootMismatch { got, expected }
+        }
+    }
+}
+
 /// Performs consensus validation checks on a block without execution or state validation.
 ///
 /// This function validates a block against Ethereum consensus rules by:
@@ -0,0 +1,132 @@
+//! Journal of static file producer runs.
+//!
+//! The journal is written to the static files directory before a run starts and updated every
+//! time a segment commits a chunk of blocks to static files. It's removed once the run finishes,
+//! so an existing journal means that the previous run was interrupted. See
+//! [`StaticFileProducerInner::resume`](crate::StaticFileProducerInner::resume).
+
+use alloy_primitives::BlockNumber;
+use reth_static_file_types::{StaticFileSegment, StaticFileTargets};
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
+use serde::{Deserialize, Serialize};
+use std::{
+    io::Write,
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+};
+
+/// File name of the [`ProducerJournal`] in the static files directory.
+pub const PRODUCER_JOURNAL_FILE_NAME: &str = "static_file_producer.journal";
+
+/// Progress of a single segment in a static file producer run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SegmentProgress {
+    /// The segment being produced.
+    pub segment: StaticFileSegment,
+    /// Targeted block range of the run.
+    pub target: RangeInclusive<BlockNumber>,
+    /// Highest block of the target that was committed to static files, if any.
+    pub produced: Option<BlockNumber>,
+}
+
+impl SegmentProgress {
+    /// Creates the progress of a segment that hasn't committed any blocks of the target yet.
+    pub const fn new(segment: StaticFileSegment, target: RangeInclusive<BlockNumber>) -> Self {
+        Self { segment, target, produced: None }
+    }
+
+    /// Returns `true` if all blocks of the target were committed.
+    pub fn is_finished(&self) -> bool {
+        self.produced.is_some_and(|produced| produced >= *self.target.end())
+    }
+
+    /// Returns the blocks of the target that weren't committed yet.
+    pub fn remaining(&self) -> Option<RangeInclusive<BlockNumber>> {
+        let start = self.produced.map_or(*self.target.start(), |produced| produced + 1);
+        let range = start..=*self.target.end();
+        (!range.is_empty()).then_some(range)
+    }
+}
+
+/// Returns the progress of all [Some] targets in [`StaticFileTargets`].
+pub fn targets_progress(targets: &StaticFileTargets) -> Vec<SegmentProgress> {
+    targets
+        .receipts
+        .clone()
+        .map(|range| SegmentProgress::new(StaticFileSegment::Receipts, range))
+        .into_iter()
+        .collect()
+}
+
+/// Journal of the in-progress static file producer run, stored at
+/// [`PRODUCER_JOURNAL_FILE_NAME`] in the static files directory.
+#[derive(Debug, Clone)]
+pub struct ProducerJournal {
+    path: PathBuf,
+}
+
+impl ProducerJournal {
+    /// Creates a new [`ProducerJournal`] in the given static files directory.
+    pub fn new(static_files_dir: impl AsRef<Path>) -> Self {
+        Self { path: static_files_dir.as_ref().join(PRODUCER_JOURNAL_FILE_NAME) }
+    }
+
+    /// Returns the path of the journal file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the progress of the interrupted run, or [`None`] if there's no journal.
+    pub fn read(&self) -> ProviderResult<Option<Vec<SegmentProgress>>> {
+        if !self.path.exists() {
+            return Ok(None)
+        }
+        reth_fs_util::read_json_file(&self.path).map(Some).map_err(ProviderError::other)
+    }
+
+    /// Atomically replaces the journal with the given progress.
+    pub fn write(&self, progress: &[SegmentProgress]) -> ProviderResult<()> {
+        reth_fs_util::atomic_write_file(&self.path, |file| {
+            serde_json::to_writer(&mut *file, progress)?;
+            file.flush()
+        })
+        .map_err(ProviderError::other)
+    }
+
+    /// Removes the journal, marking the run as finished.
+    pub fn clear(&self) -> ProviderResult<()> {
+        if self.path.exists() {
+            reth_fs_util::remove_file(&self.path).map_err(ProviderError::other)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn journal_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let journal = ProducerJournal::new(dir.path());
+        assert_eq!(journal.read().unwrap(), None);
+
+        let mut progress = targets_progress(&StaticFileTargets { receipts: Some(10..=20) });
+        assert_eq!(progress[0].remaining(), Some(10..=20));
+
+        progress[0].produced = Some(15);
+        journal.write(&progress).unwrap();
+        let read = journal.read().unwrap().unwrap();
+        assert_eq!(read, progress);
+        assert!(!read[0].is_finished());
+        assert_eq!(read[0].remaining(), Some(16..=20));
+
+        progress[0].produced = Some(20);
+        assert!(progress[0].is_finished());
+        assert_eq!(progress[0].remaining(), None);
+
+        journal.clear().unwrap();
+        assert_eq!(journal.read().unwrap(), None);
+    }
+}
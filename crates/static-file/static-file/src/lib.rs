@@ -8,12 +8,14 @@
 #![cfg_attr(docsrs, feature(doc_cfg))]
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 
+pub mod journal;
 pub mod segments;
 mod static_file_producer;
 
+pub use journal::ProducerJournal;
 pub use static_file_producer::{
     StaticFileProducer, StaticFileProducerInner, StaticFileProducerResult,
-    StaticFileProducerWithResult,
+    StaticFileProducerWithResult, JOURNAL_COMMIT_BLOCKS,
 };
 
 // Re-export for convenience.
@@ -1,6 +1,11 @@
 //! Support for producing static files.
 
-use crate::{segments, segments::Segment, StaticFileProducerEvent};
+use crate::{
+    journal::{targets_progress, ProducerJournal},
+    segments,
+    segments::Segment,
+    StaticFileProducerEvent,
+};
 use alloy_primitives::BlockNumber;
 use parking_lot::Mutex;
 use rayon::prelude::*;
@@ -10,10 +15,11 @@ use reth_primitives_traits::NodePrimitives;
 use reth_provider::{
     providers::StaticFileWriter, BlockReader, ChainStateBlockReader, DBProvider,
     DatabaseProviderFactory, StageCheckpointReader, StaticFileProviderFactory,
+    StorageSettingsCache,
 };
 use reth_prune_types::PruneModes;
 use reth_stages_types::StageId;
-use reth_static_file_types::{HighestStaticFiles, StaticFileTargets};
+use reth_static_file_types::{HighestStaticFiles, StaticFileSegment, StaticFileTargets};
 use reth_storage_errors::provider::ProviderResult;
 use reth_tokio_util::{EventSender, EventStream};
 use std::{
@@ -21,7 +27,11 @@ use std::{
     sync::Arc,
     time::Instant,
 };
-use tracing::{debug, trace};
+use tracing::{debug, info, trace, warn};
+
+/// Number of blocks a segment copies to static files before committing them and recording the
+/// progress in the [`ProducerJournal`].
+pub const JOURNAL_COMMIT_BLOCKS: u64 = 100_000;
 
 /// Result of [`StaticFileProducerInner::run`] execution.
 pub type StaticFileProducerResult = ProviderResult<StaticFileTargets>;
@@ -73,6 +83,11 @@ impl<Provider> StaticFileProducerInner<Provider> {
     fn new(provider: Provider, prune_modes: PruneModes) -> Self {
         Self { provider, prune_modes, event_sender: Default::default() }
     }
+
+    /// Updates the pruning configuration, which is used from the next run on.
+    pub fn set_prune_modes(&mut self, prune_modes: PruneModes) {
+        self.prune_modes = prune_modes;
+    }
 }
 
 impl<Provider> StaticFileProducerInner<Provider>
@@ -97,6 +112,7 @@ where
                 >,
             > + StageCheckpointReader
                           + BlockReader
+                          + StorageSettingsCache
                           + reth_provider::ChangeSetReader,
         >,
 {
@@ -105,6 +121,52 @@ where
         self.event_sender.new_listener()
     }
 
+    /// Returns the [`ProducerJournal`] of the static files directory.
+    pub fn journal(&self) -> ProducerJournal {
+        ProducerJournal::new(self.provider.static_file_provider().directory())
+    }
+
+    /// Resumes the run that was interrupted, according to the [`ProducerJournal`].
+    ///
+    /// Every unfinished segment continues right after the highest block in its static files, which
+    /// is the last block committed before the interruption. Returns [`None`] if there was no
+    /// interrupted run.
+    pub fn resume(&self) -> ProviderResult<Option<StaticFileTargets>> {
+        let journal = self.journal();
+        let Some(progress) = journal.read()? else { return Ok(None) };
+
+        let static_file_provider = self.provider.static_file_provider();
+        let mut targets = StaticFileTargets { receipts: None };
+        for segment in progress.into_iter().filter(|segment| !segment.is_finished()) {
+            let highest_block = static_file_provider.get_highest_static_file_block(segment.segment);
+            if highest_block < segment.produced {
+                warn!(
+                    target: "static_file",
+                    segment = %segment.segment,
+                    ?highest_block,
+                    produced = ?segment.produced,
+                    "Static files are behind the journaled progress, resuming from static files"
+                );
+            }
+
+            let start = highest_block.map_or(0, |block| block + 1);
+            let range = start..=*segment.target.end();
+            if range.is_empty() {
+                continue
+            }
+            match segment.segment {
+                StaticFileSegment::Receipts => targets.receipts = Some(range),
+                segment => warn!(target: "static_file", %segment, "Unsupported journaled segment"),
+            }
+        }
+
+        info!(target: "static_file", ?targets, "Resuming interrupted StaticFileProducer run");
+        let targets = self.run(targets)?;
+        journal.clear()?;
+
+        Ok(Some(targets))
+    }
+
     /// Run the `static_file_producer`.
     ///
     /// For each [Some] target in [`StaticFileTargets`], initializes a corresponding [Segment] and
@@ -112,6 +174,9 @@ where
     /// and a read-only database transaction from [`DatabaseProviderFactory`]. All segments are run
     /// in parallel.
     ///
+    /// Segments commit every [`JOURNAL_COMMIT_BLOCKS`] blocks and record their progress in the
+    /// [`ProducerJournal`], so that an interrupted run can be resumed with [`Self::resume`].
+    ///
     /// NOTE: it doesn't delete the data from database, and the actual deleting (aka pruning) logic
     /// lives in the `prune` crate.
     pub fn run(&self, targets: StaticFileTargets) -> StaticFileProducerResult {
@@ -136,14 +201,33 @@ where
             segments.push((Box::new(segments::Receipts), block_range));
         }
 
+        let journal = self.journal();
+        let progress = Mutex::new(targets_progress(&targets));
+        journal.write(&progress.lock())?;
+
         segments.par_iter().try_for_each(|(segment, block_range)| -> ProviderResult<()> {
             debug!(target: "static_file", segment = %segment.segment(), ?block_range, "StaticFileProducer segment");
             let start = Instant::now();
 
-            // Create a new database transaction on every segment to prevent long-lived read-only
-            // transactions
-            let provider = self.provider.database_provider_ro()?.disable_long_read_transaction_safety();
-            segment.copy_to_static_files(provider,  block_range.clone())?;
+            for chunk_start in block_range.clone().step_by(JOURNAL_COMMIT_BLOCKS as usize) {
+                let chunk_end =
+                    (chunk_start + JOURNAL_COMMIT_BLOCKS - 1).min(*block_range.end());
+
+                // Create a new database transaction on every chunk to prevent long-lived read-only
+                // transactions
+                let provider =
+                    self.provider.database_provider_ro()?.disable_long_read_transaction_safety();
+                segment.copy_to_static_files(provider, chunk_start..=chunk_end)?;
+                self.provider.static_file_provider().latest_writer(segment.segment())?.commit()?;
+
+                let mut journaled = progress.lock();
+                if let Some(segment_progress) =
+                    journaled.iter_mut().find(|progress| progress.segment == segment.segment())
+                {
+                    segment_progress.produced = Some(chunk_end);
+                }
+                journal.write(&journaled)?;
+            }
 
             let elapsed = start.elapsed(); // TODO(alexey): track in metrics
             debug!(target: "static_file", segment = %segment.segment(), ?block_range, ?elapsed, "Finished StaticFileProducer segment");
@@ -158,6 +242,15 @@ where
                 .update_index(segment.segment(), Some(*block_range.end()))?;
         }
 
+        if let Some(block_range) = &targets.receipts &&
+            let Err(err) = self.index_receipts_static_files(block_range)
+        {
+            // The index only speeds up reading single receipts, so it can be written later
+            warn!(target: "static_file", %err, "Failed to index receipts static files");
+        }
+
+        journal.clear()?;
+
         let elapsed = start.elapsed(); // TODO(alexey): track in metrics
         debug!(target: "static_file", ?targets, ?elapsed, "StaticFileProducer finished");
 
@@ -167,11 +260,39 @@ where
         Ok(targets)
     }
 
+    /// Writes the receipts index of the finished receipts static files that intersect the block
+    /// range and are not indexed yet.
+    ///
+    /// The static file writer indexes the receipts it appends itself, so this only covers static
+    /// files that were partially written before the writer indexed them.
+    fn index_receipts_static_files(
+        &self,
+        block_range: &RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<()> {
+        let provider = self.provider.database_provider_ro()?;
+        let static_file_provider = provider.static_file_provider();
+        for (expected_block_range, indexed) in
+            static_file_provider.finished_receipts_static_files()?
+        {
+            if !indexed &&
+                expected_block_range.start() <= *block_range.end() &&
+                expected_block_range.end() >= *block_range.start()
+            {
+                static_file_provider.write_receipts_index(&provider, expected_block_range)?;
+            }
+        }
+        Ok(())
+    }
+
     /// Copies data from database to static files according to
     /// [stage checkpoints](reth_stages_types::StageCheckpoint).
     ///
+    /// Resumes the interrupted run first, if any. See [`Self::resume`].
+    ///
     /// Returns highest block numbers for all static file segments.
     pub fn copy_to_static_files(&self) -> ProviderResult<HighestStaticFiles> {
+        self.resume()?;
+
         let provider = self.provider.database_provider_ro()?;
         let stages_checkpoints = std::iter::once(StageId::Execution)
             .map(|stage| provider.get_stage_checkpoint(stage).map(|c| c.map(|c| c.block_number)))
@@ -192,11 +313,16 @@ where
         finalized_block_numbers: HighestStaticFiles,
     ) -> ProviderResult<StaticFileTargets> {
         let highest_static_files = self.provider.static_file_provider().get_highest_static_files();
+        // Nodes keeping the most recent receipts in the database move older receipts to static
+        // files on their own when persisting blocks.
+        let receipts_hot_blocks =
+            self.provider.database_provider_ro()?.cached_storage_settings().receipts_hot_blocks();
 
         let targets = StaticFileTargets {
             // StaticFile receipts only if they're not pruned according to the user configuration
             receipts: if self.prune_modes.receipts.is_none() &&
-                self.prune_modes.receipts_log_filter.is_empty()
+                self.prune_modes.receipts_log_filter.is_empty() &&
+                receipts_hot_blocks.is_none()
             {
                 finalized_block_numbers.receipts.and_then(|finalized_block_number| {
                     self.get_static_file_target(
@@ -233,8 +359,9 @@ where
 
 #[cfg(test)]
 mod tests {
-    use crate::static_file_producer::{
-        StaticFileProducer, StaticFileProducerInner, StaticFileTargets,
+    use crate::{
+        journal::SegmentProgress,
+        static_file_producer::{StaticFileProducer, StaticFileProducerInner, StaticFileTargets},
     };
     use alloy_primitives::B256;
     use assert_matches::assert_matches;
@@ -329,6 +456,43 @@ mod tests {
         );
     }
 
+    #[test]
+    fn resume() {
+        let (provider_factory, _temp_static_files_dir) = setup();
+
+        let static_file_producer =
+            StaticFileProducerInner::new(provider_factory.clone(), PruneModes::default());
+        let journal = static_file_producer.journal();
+
+        // Nothing to resume without a journal
+        assert_matches!(static_file_producer.resume(), Ok(None));
+
+        assert_matches!(
+            static_file_producer.run(StaticFileTargets { receipts: Some(0..=1) }),
+            Ok(_)
+        );
+        assert_matches!(journal.read(), Ok(None));
+
+        // Simulate a run that was interrupted after committing the first two blocks
+        journal
+            .write(&[SegmentProgress {
+                segment: StaticFileSegment::Receipts,
+                target: 0..=3,
+                produced: Some(1),
+            }])
+            .unwrap();
+
+        assert_matches!(
+            static_file_producer.resume(),
+            Ok(Some(targets)) if targets == StaticFileTargets { receipts: Some(2..=3) }
+        );
+        assert_eq!(
+            provider_factory.static_file_provider().get_highest_static_files(),
+            HighestStaticFiles { receipts: Some(3) }
+        );
+        assert_matches!(journal.read(), Ok(None));
+    }
+
     /// Tests that a cloneable [`StaticFileProducer`] type is not susceptible to any race condition.
     #[test]
     fn only_one() {
@@ -82,6 +82,8 @@ pub use tables::*;
 /// Transaction database traits.
 pub mod transaction;
 
+pub mod write_audit;
+
 /// Re-exports
 pub use reth_storage_errors::db::{DatabaseError, DatabaseWriteOperation};
 
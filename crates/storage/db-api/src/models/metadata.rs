@@ -34,6 +34,14 @@ pub struct StorageSettings {
     /// Whether this node should read and write storage changesets from static files.
     #[serde(default)]
     pub storage_changesets_in_static_files: bool,
+    /// Number of most recent blocks whose receipts are kept in the database when receipts are
+    /// stored in static files.
+    ///
+    /// Receipts of these blocks are written to the database, which keeps random access and reorg
+    /// handling cheap. Once a block falls out of this window and is finalized, its receipts are
+    /// moved to static files. Only applies if `receipts_in_static_files` is set.
+    #[serde(default)]
+    pub receipts_hot_blocks: Option<u64>,
 }
 
 impl StorageSettings {
@@ -66,6 +74,7 @@ impl StorageSettings {
             storages_history_in_rocksdb: true,
             transaction_hash_numbers_in_rocksdb: true,
             account_history_in_rocksdb: true,
+            receipts_hot_blocks: None,
         }
     }
 
@@ -83,6 +92,7 @@ impl StorageSettings {
             account_history_in_rocksdb: false,
             account_changesets_in_static_files: false,
             storage_changesets_in_static_files: false,
+            receipts_hot_blocks: None,
         }
     }
 
@@ -128,6 +138,22 @@ impl StorageSettings {
         self
     }
 
+    /// Sets the `receipts_hot_blocks` value to the provided value.
+    pub const fn with_receipts_hot_blocks(mut self, value: Option<u64>) -> Self {
+        self.receipts_hot_blocks = value;
+        self
+    }
+
+    /// Returns the number of most recent blocks whose receipts are kept in the database, if
+    /// receipts are stored in static files with a hot window in the database.
+    pub const fn receipts_hot_blocks(&self) -> Option<u64> {
+        if self.receipts_in_static_files {
+            self.receipts_hot_blocks
+        } else {
+            None
+        }
+    }
+
     /// Returns `true` if any tables are configured to be stored in `RocksDB`.
     pub const fn any_in_rocksdb(&self) -> bool {
         self.transaction_hash_numbers_in_rocksdb ||
@@ -27,8 +27,9 @@ pub use blocks::*;
 pub use integer_list::IntegerList;
 pub use metadata::*;
 pub use reth_db_models::{
-    AccountBeforeTx, ClientVersion, StaticFileBlockWithdrawals, StorageBeforeTx,
-    StoredBlockBodyIndices, StoredBlockWithdrawals,
+    AccountBeforeTx, AccountDestruction, ChainStatsRecord, ClientVersion, ContractCreation,
+    ReorgRecord, StaticFileBlockWithdrawals, StorageBeforeTx, StoredBlockBodyIndices,
+    StoredBlockWithdrawals,
 };
 pub use sharded_key::ShardedKey;
 
@@ -233,6 +234,10 @@ impl_compression_for_compact!(
     StageCheckpoint,
     PruneCheckpoint,
     ClientVersion,
+    ReorgRecord,
+    ChainStatsRecord,
+    ContractCreation,
+    AccountDestruction,
     // Non-DB
     GenesisAccount
 );
@@ -332,6 +337,7 @@ mod tests {
         };
         assert_eq!(Account::bitflag_encoded_bytes(), 2);
         assert_eq!(AccountHashingCheckpoint::bitflag_encoded_bytes(), 1);
+        assert_eq!(ChainStatsRecord::bitflag_encoded_bytes(), 5);
         assert_eq!(CheckpointBlockRange::bitflag_encoded_bytes(), 1);
         assert_eq!(CompactClientVersion::bitflag_encoded_bytes(), 0);
         assert_eq!(CompactU256::bitflag_encoded_bytes(), 1);
@@ -344,6 +350,7 @@ mod tests {
         assert_eq!(PruneMode::bitflag_encoded_bytes(), 1);
         assert_eq!(PruneSegment::bitflag_encoded_bytes(), 1);
         assert_eq!(Receipt::bitflag_encoded_bytes(), 1);
+        assert_eq!(ReorgRecord::bitflag_encoded_bytes(), 4);
         assert_eq!(StageCheckpoint::bitflag_encoded_bytes(), 1);
         assert_eq!(StageUnitCheckpoint::bitflag_encoded_bytes(), 1);
         assert_eq!(StoredBlockBodyIndices::bitflag_encoded_bytes(), 1);
@@ -352,6 +359,7 @@ mod tests {
 
         validate_bitflag_backwards_compat!(Account, UnusedBits::NotZero);
         validate_bitflag_backwards_compat!(AccountHashingCheckpoint, UnusedBits::NotZero);
+        validate_bitflag_backwards_compat!(ChainStatsRecord, UnusedBits::NotZero);
         validate_bitflag_backwards_compat!(CheckpointBlockRange, UnusedBits::Zero);
         validate_bitflag_backwards_compat!(CompactClientVersion, UnusedBits::Zero);
         validate_bitflag_backwards_compat!(CompactU256, UnusedBits::NotZero);
@@ -364,6 +372,7 @@ mod tests {
         validate_bitflag_backwards_compat!(PruneMode, UnusedBits::Zero);
         validate_bitflag_backwards_compat!(PruneSegment, UnusedBits::Zero);
         validate_bitflag_backwards_compat!(Receipt, UnusedBits::Zero);
+        validate_bitflag_backwards_compat!(ReorgRecord, UnusedBits::Zero);
         validate_bitflag_backwards_compat!(StageCheckpoint, UnusedBits::NotZero);
         validate_bitflag_backwards_compat!(StageUnitCheckpoint, UnusedBits::Zero);
         validate_bitflag_backwards_compat!(StoredBlockBodyIndices, UnusedBits::Zero);
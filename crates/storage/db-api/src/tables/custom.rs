@@ -0,0 +1,242 @@
+//! Custom tables declared outside of reth.
+//!
+//! Downstream crates, like `ExEx`es or custom stages, can declare their own tables with
+//! [`custom_tables!`](crate::custom_tables) and store them in the node's database next to the
+//! [`Tables`](crate::Tables) of reth. The names of custom tables are prefixed with a namespace, so
+//! they can't collide with the tables of reth or of other crates.
+
+/// Separator between the namespace and the name of a custom table.
+pub const CUSTOM_TABLE_NAMESPACE_SEPARATOR: &str = ".";
+
+/// Declares custom tables with the same codec machinery as the [`Tables`](crate::Tables) of reth.
+///
+/// Every table is a marker type implementing [`Table`](crate::table::Table), named
+/// `<namespace>.<table>` in the database. The macro also generates an enum of all declared tables
+/// that implements [`TableSet`](crate::TableSet), which is used to create the tables in the
+/// node's database, e.g. with `DatabaseEnv::create_tables_for`.
+///
+/// # Example
+///
+/// ```
+/// use alloy_primitives::{Address, BlockNumber, B256};
+/// use reth_db_api::{custom_tables, table::Table, TableSet};
+/// use reth_primitives_traits::StorageEntry;
+///
+/// custom_tables! {
+///     /// Tables of my `ExEx`.
+///     pub enum MyExExTables in "my_exex" {
+///         /// Stores the last block processed for each address.
+///         table LastProcessedBlocks {
+///             type Key = Address;
+///             type Value = BlockNumber;
+///         }
+///
+///         /// Stores the storage slots watched for each address.
+///         table WatchedStorages {
+///             type Key = Address;
+///             type Value = StorageEntry;
+///             type SubKey = B256;
+///         }
+///     }
+/// }
+///
+/// assert_eq!(LastProcessedBlocks::NAME, "my_exex.LastProcessedBlocks");
+/// assert!(WatchedStorages::DUPSORT);
+/// assert_eq!(MyExExTables::tables().count(), 2);
+/// ```
+#[macro_export]
+macro_rules! custom_tables {
+    (
+        $(#[$enum_attr:meta])*
+        $vis:vis enum $enum_name:ident in $namespace:literal {
+            $($(#[$attr:meta])* table $name:ident { type Key = $key:ty; type Value = $value:ty; $(type SubKey = $subkey:ty;)? } )*
+        }
+    ) => {
+        // Table marker types.
+        $(
+            $(#[$attr])*
+            ///
+            #[doc = concat!("Marker type representing a custom database table mapping [`", stringify!($key), "`] to [`", stringify!($value), "`].")]
+            $(
+                #[doc = concat!("\n\nThis table's `DUPSORT` subkey is [`", stringify!($subkey), "`].")]
+            )?
+            $vis struct $name {
+                _private: (),
+            }
+
+            impl ::core::fmt::Debug for $name {
+                fn fmt(&self, _: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    unreachable!("this type cannot be instantiated")
+                }
+            }
+
+            impl $crate::table::Table for $name {
+                const NAME: &'static str = concat!($namespace, ".", stringify!($name));
+                const DUPSORT: bool = $crate::tables!(@bool $($subkey)?);
+
+                type Key = $key;
+                type Value = $value;
+            }
+
+            $(
+                impl $crate::table::DupSort for $name {
+                    type SubKey = $subkey;
+                }
+            )?
+        )*
+
+        $(#[$enum_attr])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        $vis enum $enum_name {
+            $(
+                #[doc = concat!("The [`", stringify!($name), "`] custom database table.")]
+                $name,
+            )*
+        }
+
+        impl $enum_name {
+            /// Namespace of the tables, prefixed to their names in the database.
+            pub const NAMESPACE: &'static str = $namespace;
+
+            /// All the tables of the namespace.
+            pub const ALL: &'static [Self] = &[$(Self::$name,)*];
+
+            /// Returns the name of the table in the database.
+            pub const fn name(&self) -> &'static str {
+                match self {
+                    $(
+                        Self::$name => <$name as $crate::table::Table>::NAME,
+                    )*
+                }
+            }
+
+            /// Returns `true` if the table is a `DUPSORT` table.
+            pub const fn is_dupsort(&self) -> bool {
+                match self {
+                    $(
+                        Self::$name => <$name as $crate::table::Table>::DUPSORT,
+                    )*
+                }
+            }
+
+            /// Allows to operate on specific table type
+            pub fn view<T, R>(&self, visitor: &T) -> Result<R, T::Error>
+            where
+                T: ?Sized + $crate::TableViewer<R>,
+            {
+                match self {
+                    $(
+                        Self::$name => $crate::tables!(@view $name visitor $($subkey)?),
+                    )*
+                }
+            }
+        }
+
+        impl ::core::fmt::Display for $enum_name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.write_str(self.name())
+            }
+        }
+
+        impl ::core::str::FromStr for $enum_name {
+            type Err = String;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                Self::ALL
+                    .iter()
+                    .find(|table| table.name() == s)
+                    .copied()
+                    .ok_or_else(|| format!("unknown table: {s:?}"))
+            }
+        }
+
+        impl $crate::table::TableInfo for $enum_name {
+            fn name(&self) -> &'static str {
+                self.name()
+            }
+
+            fn is_dupsort(&self) -> bool {
+                self.is_dupsort()
+            }
+        }
+
+        impl $crate::TableSet for $enum_name {
+            fn tables() -> Box<dyn Iterator<Item = Box<dyn $crate::table::TableInfo>>> {
+                Box::new(
+                    Self::ALL
+                        .iter()
+                        .map(|table| Box::new(*table) as Box<dyn $crate::table::TableInfo>),
+                )
+            }
+        }
+    };
+}
+
+/// Returns the namespace of the given table name, or [`None`] if it isn't a custom table.
+pub fn custom_table_namespace(name: &str) -> Option<&str> {
+    name.split_once(CUSTOM_TABLE_NAMESPACE_SEPARATOR).map(|(namespace, _)| namespace)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        table::{DupSort, Table},
+        TableSet, TableViewer, Tables,
+    };
+    use alloy_primitives::{Address, BlockNumber, B256};
+    use reth_primitives_traits::StorageEntry;
+
+    crate::custom_tables! {
+        /// Tables of a test `ExEx`.
+        pub enum TestTables in "test" {
+            /// Last processed block of each address.
+            table LastProcessedBlocks {
+                type Key = Address;
+                type Value = BlockNumber;
+            }
+
+            /// Watched storage slots of each address.
+            table WatchedStorages {
+                type Key = Address;
+                type Value = StorageEntry;
+                type SubKey = B256;
+            }
+        }
+    }
+
+    struct NameViewer;
+
+    impl TableViewer<&'static str> for NameViewer {
+        type Error = ();
+
+        fn view<T: Table>(&self) -> Result<&'static str, Self::Error> {
+            Ok(T::NAME)
+        }
+
+        fn view_dupsort<T: DupSort>(&self) -> Result<&'static str, Self::Error> {
+            Ok(T::NAME)
+        }
+    }
+
+    #[test]
+    fn custom_tables() {
+        assert_eq!(TestTables::NAMESPACE, "test");
+        assert_eq!(LastProcessedBlocks::NAME, "test.LastProcessedBlocks");
+        assert!(!LastProcessedBlocks::DUPSORT);
+        assert!(WatchedStorages::DUPSORT);
+
+        let names = TestTables::tables().map(|table| table.name()).collect::<Vec<_>>();
+        assert_eq!(names, ["test.LastProcessedBlocks", "test.WatchedStorages"]);
+
+        for table in TestTables::ALL {
+            assert_eq!(table.to_string().parse::<TestTables>(), Ok(*table));
+            assert_eq!(table.view(&NameViewer), Ok(table.name()));
+            assert_eq!(custom_table_namespace(table.name()), Some("test"));
+        }
+        assert!("LastProcessedBlocks".parse::<TestTables>().is_err());
+
+        // tables of reth are never namespaced
+        assert!(Tables::ALL.iter().all(|table| custom_table_namespace(table.name()).is_none()));
+    }
+}
@@ -16,13 +16,17 @@ pub mod codecs;
 mod raw;
 pub use raw::{RawDupSort, RawKey, RawTable, RawValue, TableRawRow};
 
+mod custom;
+pub use custom::{custom_table_namespace, CUSTOM_TABLE_NAMESPACE_SEPARATOR};
+
 use crate::{
     models::{
         accounts::BlockNumberAddress,
         blocks::{HeaderHash, StoredBlockOmmers},
         storage_sharded_key::StorageShardedKey,
-        AccountBeforeTx, ClientVersion, CompactU256, IntegerList, ShardedKey,
-        StoredBlockBodyIndices, StoredBlockWithdrawals,
+        AccountBeforeTx, AccountDestruction, ChainStatsRecord, ClientVersion, CompactU256,
+        ContractCreation, IntegerList, ReorgRecord, ShardedKey, StoredBlockBodyIndices,
+        StoredBlockWithdrawals,
     },
     table::{Decode, DupSort, Encode, Table, TableInfo},
 };
@@ -418,6 +422,57 @@ tables! {
         type Value = BlockNumberList;
     }
 
+    /// Stores pointers to the transactions sent from or to each address, as lists of
+    /// [`TxNumber`]s sharded the same way as [`AccountsHistory`].
+    ///
+    /// Only populated if the optional `IndexTransactionAddresses` stage is enabled.
+    table AddressTransactions {
+        type Key = ShardedKey<Address>;
+        type Value = BlockNumberList;
+    }
+
+    /// Stores every creation of each contract, with its creator, creation transaction, code hash
+    /// and `CREATE2` salt.
+    ///
+    /// Only populated if the optional contract creations index is enabled, by the execution stage
+    /// and when the engine persists blocks.
+    table ContractCreations {
+        type Key = Address;
+        type Value = ContractCreation;
+        type SubKey = BlockNumber;
+    }
+
+    /// Stores the contracts created in every block with the creating transaction, which allows
+    /// [`ContractCreations`] to be unwound.
+    ///
+    /// Contracts that are created and removed in the same block don't have an account changeset
+    /// entry, so the changesets can't be used for this.
+    table BlockContractCreations {
+        type Key = BlockNumberAddress;
+        type Value = TxHash;
+    }
+
+    /// Stores every removal of an account from the state, by a `SELFDESTRUCT` or because it was
+    /// touched while empty, with the block and transaction it was removed in.
+    ///
+    /// Only populated if the optional account destructions index is enabled, by the execution stage
+    /// and when the engine persists blocks.
+    table AccountDestructions {
+        type Key = Address;
+        type Value = AccountDestruction;
+        type SubKey = BlockNumber;
+    }
+
+    /// Stores the accounts removed in every block with the removing transaction, which allows
+    /// [`AccountDestructions`] to be unwound.
+    ///
+    /// Accounts that are created and removed in the same block don't have an account changeset
+    /// entry, so the changesets can't be used for this.
+    table BlockAccountDestructions {
+        type Key = BlockNumberAddress;
+        type Value = TxHash;
+    }
+
     /// Stores pointers to block number changeset with changes for each storage key.
     ///
     /// Last shard key of the storage will contain `u64::MAX` `BlockNumber`,
@@ -522,6 +577,27 @@ tables! {
         type Value = ClientVersion;
     }
 
+    /// Stores the reorgs of the canonical chain performed by this node, keyed by an increasing
+    /// sequence number.
+    table ReorgHistory {
+        type Key = u64;
+        type Value = ReorgRecord;
+    }
+
+    /// Stores the aggregated statistics of canonical blocks per UTC day, keyed by the number of
+    /// the day since the unix epoch.
+    table ChainStatsDays {
+        type Key = u64;
+        type Value = ChainStatsRecord;
+    }
+
+    /// Stores the aggregated statistics of canonical blocks per beacon chain epoch, keyed by the
+    /// epoch number.
+    table ChainStatsEpochs {
+        type Key = u64;
+        type Value = ChainStatsRecord;
+    }
+
     /// Stores generic chain state info, like the last finalized block.
     table ChainState {
         type Key = ChainStateKey;
@@ -543,6 +619,8 @@ pub enum ChainStateKey {
     LastFinalizedBlock,
     /// Last safe block key
     LastSafeBlock,
+    /// Number of the last block included in the chain statistics
+    ChainStatsHead,
 }
 
 impl Encode for ChainStateKey {
@@ -552,6 +630,7 @@ impl Encode for ChainStateKey {
         match self {
             Self::LastFinalizedBlock => [0],
             Self::LastSafeBlock => [1],
+            Self::ChainStatsHead => [2],
         }
     }
 }
@@ -561,6 +640,7 @@ impl Decode for ChainStateKey {
         match value {
             [0] => Ok(Self::LastFinalizedBlock),
             [1] => Ok(Self::LastSafeBlock),
+            [2] => Ok(Self::ChainStatsHead),
             _ => Err(crate::DatabaseError::Decode),
         }
     }
@@ -0,0 +1,172 @@
+//! Audit log of the most recent database writes.
+//!
+//! Database implementations record their writes with [`record`] if write auditing is enabled for
+//! them, e.g. `reth-db` with its `write-audit` feature. Writes are tagged with the context of the
+//! writing thread, which the pipeline sets to the id of the running stage, so a write that
+//! corrupted the state can be traced back to the stage that made it. The log is a ring buffer that
+//! keeps the last [`DEFAULT_WRITE_AUDIT_CAPACITY`] writes.
+
+use alloy_primitives::hex;
+use std::{
+    cell::RefCell,
+    collections::VecDeque,
+    fmt,
+    sync::{Arc, Mutex, PoisonError},
+};
+
+/// The default number of writes kept in the audit log.
+pub const DEFAULT_WRITE_AUDIT_CAPACITY: usize = 100_000;
+
+static LOG: Mutex<WriteAuditLog> = Mutex::new(WriteAuditLog::new(DEFAULT_WRITE_AUDIT_CAPACITY));
+
+thread_local! {
+    static CONTEXT: RefCell<Option<Arc<str>>> = const { RefCell::new(None) };
+}
+
+/// The kind of an audited write.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WriteOperation {
+    /// [`DbTxMut::put`](crate::transaction::DbTxMut::put)
+    PutUpsert,
+    /// [`DbTxMut::append`](crate::transaction::DbTxMut::append)
+    PutAppend,
+    /// [`DbTxMut::delete`](crate::transaction::DbTxMut::delete)
+    Delete,
+    /// [`DbTxMut::clear`](crate::transaction::DbTxMut::clear)
+    Clear,
+    /// [`DbCursorRW::upsert`](crate::cursor::DbCursorRW::upsert)
+    CursorUpsert,
+    /// [`DbCursorRW::insert`](crate::cursor::DbCursorRW::insert)
+    CursorInsert,
+    /// [`DbCursorRW::append`](crate::cursor::DbCursorRW::append)
+    CursorAppend,
+    /// [`DbCursorRW::delete_current`](crate::cursor::DbCursorRW::delete_current)
+    CursorDeleteCurrent,
+    /// [`DbDupCursorRW::append_dup`](crate::cursor::DbDupCursorRW::append_dup)
+    CursorAppendDup,
+    /// [`DbDupCursorRW::delete_current_duplicates`](crate::cursor::DbDupCursorRW::delete_current_duplicates)
+    CursorDeleteCurrentDuplicates,
+}
+
+/// A single audited write.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditedWrite {
+    /// The sequence number of the write since the start of the process.
+    pub sequence: u64,
+    /// The context of the writing thread, see [`WriteAuditContext`].
+    pub context: Option<Arc<str>>,
+    /// The table written to.
+    pub table: &'static str,
+    /// The kind of the write.
+    pub operation: WriteOperation,
+    /// The encoded key, empty for [`WriteOperation::Clear`].
+    pub key: Vec<u8>,
+}
+
+impl fmt::Display for AuditedWrite {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "#{} [{}] {:?} {} 0x{}",
+            self.sequence,
+            self.context.as_deref().unwrap_or("-"),
+            self.operation,
+            self.table,
+            hex::encode(&self.key)
+        )
+    }
+}
+
+#[derive(Debug)]
+struct WriteAuditLog {
+    writes: VecDeque<AuditedWrite>,
+    capacity: usize,
+    next_sequence: u64,
+}
+
+impl WriteAuditLog {
+    const fn new(capacity: usize) -> Self {
+        Self { writes: VecDeque::new(), capacity, next_sequence: 0 }
+    }
+}
+
+/// Sets the context of the writes of the current thread until the guard is dropped.
+///
+/// The previous context is restored when the guard is dropped, so contexts can be nested.
+#[derive(Debug)]
+#[must_use = "the context is reset when the guard is dropped"]
+pub struct WriteAuditContext {
+    previous: Option<Arc<str>>,
+}
+
+impl WriteAuditContext {
+    /// Sets the context of the writes of the current thread.
+    pub fn enter(context: &str) -> Self {
+        let previous = CONTEXT.with(|current| current.replace(Some(context.into())));
+        Self { previous }
+    }
+}
+
+impl Drop for WriteAuditContext {
+    fn drop(&mut self) {
+        CONTEXT.with(|current| *current.borrow_mut() = self.previous.take());
+    }
+}
+
+/// Records a write, evicting the oldest write if the log is full.
+pub fn record(table: &'static str, operation: WriteOperation, key: &[u8]) {
+    let context = CONTEXT.with(|current| current.borrow().clone());
+    let mut log = LOG.lock().unwrap_or_else(PoisonError::into_inner);
+    if log.capacity == 0 {
+        return
+    }
+    if log.writes.len() >= log.capacity {
+        log.writes.pop_front();
+    }
+    let sequence = log.next_sequence;
+    log.next_sequence += 1;
+    log.writes.push_back(AuditedWrite { sequence, context, table, operation, key: key.to_vec() });
+}
+
+/// Sets the number of writes kept in the audit log, evicting the oldest writes if necessary.
+pub fn set_capacity(capacity: usize) {
+    let mut log = LOG.lock().unwrap_or_else(PoisonError::into_inner);
+    log.capacity = capacity;
+    let excess = log.writes.len().saturating_sub(capacity);
+    log.writes.drain(..excess);
+}
+
+/// Removes and returns all writes of the audit log, oldest first.
+pub fn take() -> Vec<AuditedWrite> {
+    LOG.lock().unwrap_or_else(PoisonError::into_inner).writes.drain(..).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_writes_with_context() {
+        take();
+        record("Headers", WriteOperation::PutUpsert, &[1]);
+        {
+            let _outer = WriteAuditContext::enter("Execution");
+            record("PlainAccountState", WriteOperation::CursorUpsert, &[2]);
+            {
+                let _inner = WriteAuditContext::enter("MerkleExecute");
+                record("AccountsTrie", WriteOperation::Delete, &[3]);
+            }
+            record("PlainStorageState", WriteOperation::CursorAppendDup, &[4]);
+        }
+
+        let writes = take();
+        let contexts = writes.iter().map(|write| write.context.as_deref()).collect::<Vec<_>>();
+        assert_eq!(contexts, [None, Some("Execution"), Some("MerkleExecute"), Some("Execution")]);
+        assert!(writes.windows(2).all(|pair| pair[0].sequence + 1 == pair[1].sequence));
+        assert_eq!(
+            writes[1].to_string(),
+            format!("#{} [Execution] CursorUpsert PlainAccountState 0x02", writes[1].sequence)
+        );
+        assert!(take().is_empty());
+    }
+}
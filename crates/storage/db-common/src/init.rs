@@ -3,13 +3,17 @@
 use alloy_consensus::BlockHeader;
 use alloy_genesis::GenesisAccount;
 use alloy_primitives::{keccak256, map::HashMap, Address, B256, U256};
+use alloy_rlp::Encodable;
+use rayon::prelude::*;
 use reth_chainspec::EthChainSpec;
 use reth_codecs::Compact;
 use reth_config::config::EtlConfig;
 use reth_db_api::{
+    cursor::{DbCursorRO, DbCursorRW, DbDupCursorRO, DbDupCursorRW},
     models::{storage_sharded_key::StorageShardedKey, ShardedKey},
+    table::Decompress,
     tables,
-    transaction::DbTxMut,
+    transaction::{DbTx, DbTxMut},
     BlockNumberList, DatabaseError,
 };
 use reth_etl::Collector;
@@ -28,10 +32,9 @@ use reth_provider::{
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_static_file_types::StaticFileSegment;
 use reth_trie::{
-    prefix_set::{TriePrefixSets, TriePrefixSetsMut},
-    IntermediateStateRootState, Nibbles, StateRoot as StateRootComputer, StateRootProgress,
+    updates::{StorageTrieUpdates, TrieUpdates},
+    HashBuilder, Nibbles,
 };
-use reth_trie_db::DatabaseStateRoot;
 use serde::{Deserialize, Serialize};
 use std::io::BufRead;
 use tracing::{debug, error, info, trace, warn};
@@ -53,6 +56,13 @@ pub const AVERAGE_COUNT_ACCOUNTS_PER_GB_STATE_DUMP: usize = 285_228;
 /// Soft limit for the number of flushed updates after which to log progress summary.
 const SOFT_LIMIT_COUNT_FLUSHED_UPDATES: usize = 1_000_000;
 
+/// Number of lines of the state dump file that are parsed in parallel.
+const STATE_DUMP_PARSE_BATCH_LEN: usize = 10_000;
+
+/// Number of accounts whose storage tries are built in parallel, and after which the trie updates
+/// are flushed, when building the state trie from scratch.
+const STATE_TRIE_CHUNK_LEN: usize = 10_000;
+
 /// Storage initialization error type.
 #[derive(Debug, thiserror::Error, Clone)]
 pub enum InitStorageError {
@@ -211,7 +221,9 @@ where
     // Behaviour reserved only for new nodes should be set in the storage settings.
     provider_rw.write_storage_settings(genesis_storage_settings)?;
 
-    insert_genesis_hashes(&provider_rw, alloc.iter())?;
+    // hash the alloc once, it's used for both the hashed state and the trie
+    let hashed_alloc = hash_alloc(alloc.iter());
+    write_hashed_alloc(&provider_rw, &hashed_alloc)?;
     insert_genesis_history(&provider_rw, alloc.iter())?;
 
     // Insert header
@@ -219,8 +231,14 @@ where
 
     insert_genesis_state(&provider_rw, alloc.iter())?;
 
-    // compute state root to populate trie tables
-    compute_state_root(&provider_rw, None)?;
+    // the database is empty, so the trie can be built from the alloc instead of the hashed tables
+    let mut trie = StateTrieBuilder::new(&provider_rw);
+    for accounts in hashed_alloc.chunks(STATE_TRIE_CHUNK_LEN) {
+        trie.add_accounts(accounts)?;
+    }
+    drop(hashed_alloc);
+    let state_root = trie.root()?;
+    trace!(target: "reth::storage", %state_root, "Inserted genesis trie");
 
     // set stage checkpoint to genesis block number for all stages
     let checkpoint = StageCheckpoint::new(genesis_block_number);
@@ -370,6 +388,8 @@ where
 }
 
 /// Inserts hashes for the genesis state.
+///
+/// The accounts and storage slots are hashed in parallel.
 pub fn insert_genesis_hashes<'a, 'b, Provider>(
     provider: &Provider,
     alloc: impl Iterator<Item = (&'a Address, &'b GenesisAccount)> + Clone,
@@ -377,25 +397,223 @@ pub fn insert_genesis_hashes<'a, 'b, Provider>(
 where
     Provider: DBProvider<Tx: DbTxMut> + HashingWriter,
 {
-    // insert and hash accounts to hashing table
-    let alloc_accounts = alloc.clone().map(|(addr, account)| (*addr, Some(Account::from(account))));
-    provider.insert_account_for_hashing(alloc_accounts)?;
+    write_hashed_alloc(provider, &hash_alloc(alloc))
+}
 
-    trace!(target: "reth::cli", "Inserted account hashes");
+/// A genesis account with its hashed address and hashed storage.
+#[derive(Debug)]
+struct HashedGenesisAccount {
+    hashed_address: B256,
+    account: Account,
+    /// The non-zero storage slots, sorted by the hashed slot.
+    storage: Vec<(B256, U256)>,
+}
 
-    let alloc_storage = alloc.filter_map(|(addr, account)| {
-        // only return Some if there is storage
-        account.storage.as_ref().map(|storage| {
-            (*addr, storage.iter().map(|(&key, &value)| StorageEntry { key, value: value.into() }))
+/// Hashes the accounts and storage slots of the alloc in parallel, returns the accounts sorted by
+/// the hashed address.
+fn hash_alloc<'a, 'b>(
+    alloc: impl Iterator<Item = (&'a Address, &'b GenesisAccount)>,
+) -> Vec<HashedGenesisAccount> {
+    let alloc = alloc.collect::<Vec<_>>();
+    let mut hashed = alloc
+        .into_par_iter()
+        .map(|(address, account)| {
+            let mut storage = account
+                .storage
+                .iter()
+                .flatten()
+                .map(|(key, value)| (keccak256(key), U256::from_be_bytes(value.0)))
+                .filter(|(_, value)| !value.is_zero())
+                .collect::<Vec<_>>();
+            storage.sort_unstable_by_key(|(hashed_slot, _)| *hashed_slot);
+
+            HashedGenesisAccount {
+                hashed_address: keccak256(address),
+                account: Account::from(account),
+                storage,
+            }
         })
-    });
-    provider.insert_storage_for_hashing(alloc_storage)?;
+        .collect::<Vec<_>>();
+    hashed.par_sort_unstable_by_key(|account| account.hashed_address);
+    hashed
+}
+
+/// Writes the hashed alloc to the hashed state tables.
+fn write_hashed_alloc<Provider>(
+    provider: &Provider,
+    alloc: &[HashedGenesisAccount],
+) -> ProviderResult<()>
+where
+    Provider: DBProvider<Tx: DbTxMut>,
+{
+    let mut hashed_accounts_cursor = provider.tx_ref().cursor_write::<tables::HashedAccounts>()?;
+    for account in alloc {
+        hashed_accounts_cursor.upsert(account.hashed_address, &account.account)?;
+    }
+
+    trace!(target: "reth::cli", "Inserted account hashes");
+
+    let mut hashed_storage_cursor =
+        provider.tx_ref().cursor_dup_write::<tables::HashedStorages>()?;
+    for account in alloc {
+        for (hashed_slot, value) in &account.storage {
+            if hashed_storage_cursor
+                .seek_by_key_subkey(account.hashed_address, *hashed_slot)?
+                .is_some_and(|entry| entry.key == *hashed_slot)
+            {
+                hashed_storage_cursor.delete_current()?;
+            }
+            hashed_storage_cursor
+                .upsert(account.hashed_address, &StorageEntry::new(*hashed_slot, *value))?;
+        }
+    }
 
     trace!(target: "reth::cli", "Inserted storage hashes");
 
     Ok(())
 }
 
+/// Builds the state trie from scratch out of accounts sorted by hashed address.
+///
+/// The storage tries of every chunk of accounts are built in parallel, and the trie updates are
+/// flushed to the database after every chunk, so the updates of the entire trie are never held in
+/// memory. This is only equivalent to [`reth_trie::StateRoot`] if the accounts are the entire
+/// state.
+struct StateTrieBuilder<'a, Provider> {
+    provider: &'a Provider,
+    hash_builder: HashBuilder,
+    account_rlp: Vec<u8>,
+    total_flushed_updates: usize,
+}
+
+impl<'a, Provider> StateTrieBuilder<'a, Provider>
+where
+    Provider: DBProvider<Tx: DbTxMut> + TrieWriter,
+{
+    fn new(provider: &'a Provider) -> Self {
+        Self {
+            provider,
+            hash_builder: HashBuilder::default().with_updates(true),
+            account_rlp: Vec::new(),
+            total_flushed_updates: 0,
+        }
+    }
+
+    /// Adds the next chunk of accounts to the trie and flushes the trie updates produced so far.
+    fn add_accounts(&mut self, accounts: &[HashedGenesisAccount]) -> ProviderResult<()> {
+        let storage_tries = accounts
+            .par_iter()
+            .map(|account| {
+                let mut hash_builder = HashBuilder::default().with_updates(true);
+                for (hashed_slot, value) in &account.storage {
+                    hash_builder.add_leaf(
+                        Nibbles::unpack(hashed_slot),
+                        alloy_rlp::encode_fixed_size(value).as_ref(),
+                    );
+                }
+                let storage_root = hash_builder.root();
+
+                let mut updates = StorageTrieUpdates::default();
+                updates.finalize(hash_builder, Default::default());
+                (storage_root, updates)
+            })
+            .collect::<Vec<_>>();
+
+        let mut trie_updates = TrieUpdates::default();
+        for (account, (storage_root, storage_updates)) in accounts.iter().zip(storage_tries) {
+            self.account_rlp.clear();
+            account.account.into_trie_account(storage_root).encode(&mut self.account_rlp);
+            self.hash_builder.add_leaf(Nibbles::unpack(account.hashed_address), &self.account_rlp);
+            trie_updates.insert_storage_updates(account.hashed_address, storage_updates);
+        }
+
+        // branch nodes are final once they're emitted by the hash builder
+        let (mut hash_builder, account_nodes) = std::mem::take(&mut self.hash_builder).split();
+        hash_builder.set_updates(true);
+        self.hash_builder = hash_builder;
+        trie_updates.account_nodes.extend(account_nodes);
+
+        self.flush(trie_updates)
+    }
+
+    /// Computes the state root and flushes the remaining trie updates.
+    fn root(mut self) -> ProviderResult<B256> {
+        let root = self.hash_builder.root();
+
+        let mut trie_updates = TrieUpdates::default();
+        trie_updates.finalize(
+            std::mem::take(&mut self.hash_builder),
+            Default::default(),
+            Default::default(),
+        );
+        self.flush(trie_updates)?;
+
+        trace!(target: "reth::cli",
+            %root,
+            total_flushed_updates = self.total_flushed_updates,
+            "State root has been computed"
+        );
+        Ok(root)
+    }
+
+    fn flush(&mut self, trie_updates: TrieUpdates) -> ProviderResult<()> {
+        let updated_len = self.provider.write_trie_updates(trie_updates)?;
+        let previous_total = self.total_flushed_updates;
+        self.total_flushed_updates += updated_len;
+
+        trace!(target: "reth::cli",
+            updated_len,
+            total_flushed_updates = self.total_flushed_updates,
+            "Flushing trie updates"
+        );
+        if previous_total / SOFT_LIMIT_COUNT_FLUSHED_UPDATES !=
+            self.total_flushed_updates / SOFT_LIMIT_COUNT_FLUSHED_UPDATES
+        {
+            info!(target: "reth::cli",
+                total_flushed_updates = self.total_flushed_updates,
+                "Flushing trie updates"
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Rebuilds the state trie from the hashed state tables, returns the state root.
+///
+/// The trie tables are cleared first, see [`StateTrieBuilder`].
+fn rebuild_state_trie<Provider>(provider: &Provider) -> ProviderResult<B256>
+where
+    Provider: DBProvider<Tx: DbTxMut> + TrieWriter,
+{
+    trace!(target: "reth::cli", "Computing state root");
+
+    let tx = provider.tx_ref();
+    tx.clear::<tables::AccountsTrie>()?;
+    tx.clear::<tables::StoragesTrie>()?;
+
+    let mut hashed_accounts_cursor = tx.cursor_read::<tables::HashedAccounts>()?;
+    let mut hashed_storage_cursor = tx.cursor_dup_read::<tables::HashedStorages>()?;
+
+    let mut trie = StateTrieBuilder::new(provider);
+    let mut accounts = Vec::with_capacity(STATE_TRIE_CHUNK_LEN);
+    for entry in hashed_accounts_cursor.walk(None)? {
+        let (hashed_address, account) = entry?;
+        let storage = hashed_storage_cursor
+            .walk_dup(Some(hashed_address), None)?
+            .map(|entry| entry.map(|(_, entry)| (entry.key, entry.value)))
+            .collect::<Result<Vec<_>, _>>()?;
+        accounts.push(HashedGenesisAccount { hashed_address, account, storage });
+
+        if accounts.len() == STATE_TRIE_CHUNK_LEN {
+            trie.add_accounts(&accounts)?;
+            accounts.clear();
+        }
+    }
+    trie.add_accounts(&accounts)?;
+
+    trie.root()
+}
+
 /// Inserts history indices for genesis accounts and storage.
 ///
 /// Writes to either MDBX or `RocksDB` based on storage settings configuration,
@@ -576,14 +794,13 @@ where
     // remaining lines are accounts
     let collector = parse_accounts(&mut reader, etl_config)?;
 
-    // write state to db and collect prefix sets
-    let mut prefix_sets = TriePrefixSetsMut::default();
-    dump_state(collector, provider_rw, block, &mut prefix_sets)?;
+    // write state to db
+    dump_state(collector, provider_rw, block)?;
 
     info!(target: "reth::cli", "All accounts written to database, starting state root computation (may take some time)");
 
-    // compute and compare state root. this advances the stage checkpoints.
-    let computed_state_root = compute_state_root(provider_rw, Some(prefix_sets.freeze()))?;
+    // the dump is the entire state, so the trie is rebuilt from the hashed state
+    let computed_state_root = rebuild_state_trie(provider_rw)?;
     if computed_state_root == expected_state_root {
         info!(target: "reth::cli",
             ?computed_state_root,
@@ -624,43 +841,68 @@ fn parse_state_root(reader: &mut impl BufRead) -> eyre::Result<B256> {
     Ok(expected_state_root)
 }
 
-/// Parses accounts and pushes them to a [`Collector`].
+/// Parses the addresses of the accounts and pushes the accounts to a [`Collector`], sorted by
+/// address.
+///
+/// Only the address of an account is parsed here, the account is kept as it is in the file and
+/// parsed once it's written to the database, see [`dump_state`]. The file is streamed in batches of
+/// lines that are parsed in parallel.
 fn parse_accounts(
     mut reader: impl BufRead,
     etl_config: EtlConfig,
-) -> Result<Collector<Address, GenesisAccount>, eyre::Error> {
-    let mut line = String::new();
+) -> Result<Collector<Address, Vec<u8>>, eyre::Error> {
+    let mut lines = Vec::with_capacity(STATE_DUMP_PARSE_BATCH_LEN);
     let mut collector = Collector::new(etl_config.file_size, etl_config.dir);
 
     loop {
+        let mut line = String::new();
         let n = reader.read_line(&mut line)?;
-        if n == 0 {
-            break
+        if n > 0 {
+            lines.push(line.into_bytes());
+        }
+        if lines.len() < STATE_DUMP_PARSE_BATCH_LEN && n > 0 {
+            continue
         }
 
-        let GenesisAccountWithAddress { genesis_account, address } = serde_json::from_str(&line)?;
-        collector.insert(address, genesis_account)?;
+        let addresses = lines
+            .par_iter()
+            .map(|line| serde_json::from_slice::<AccountAddress>(line))
+            .collect::<Result<Vec<_>, _>>()?;
 
-        if !collector.is_empty() &&
-            collector.len().is_multiple_of(AVERAGE_COUNT_ACCOUNTS_PER_GB_STATE_DUMP)
-        {
-            info!(target: "reth::cli",
-                parsed_new_accounts=collector.len(),
-            );
+        for (AccountAddress { address }, line) in addresses.into_iter().zip(lines.drain(..)) {
+            collector.insert(address, line)?;
+
+            if collector.len().is_multiple_of(AVERAGE_COUNT_ACCOUNTS_PER_GB_STATE_DUMP) {
+                info!(target: "reth::cli",
+                    parsed_new_accounts=collector.len(),
+                );
+            }
         }
 
-        line.clear();
+        if n == 0 {
+            break
+        }
     }
 
     Ok(collector)
 }
 
+/// Parses the given lines of the state dump file in parallel.
+fn parse_account_lines(lines: &[Vec<u8>]) -> serde_json::Result<Vec<(Address, GenesisAccount)>> {
+    lines
+        .par_iter()
+        .map(|line| {
+            serde_json::from_slice::<GenesisAccountWithAddress>(line)
+                .map(|account| (account.address, account.genesis_account))
+        })
+        .collect()
+}
+
 /// Takes a [`Collector`] and processes all accounts.
 fn dump_state<Provider>(
-    mut collector: Collector<Address, GenesisAccount>,
+    mut collector: Collector<Address, Vec<u8>>,
     provider_rw: &Provider,
     block: u64,
-    prefix_sets: &mut TriePrefixSetsMut,
 ) -> Result<(), eyre::Error>
 where
     Provider: StaticFileProviderFactory
@@ -675,35 +917,18 @@ where
         + AsRef<Provider>,
 {
     let accounts_len = collector.len();
-    let mut accounts = Vec::with_capacity(AVERAGE_COUNT_ACCOUNTS_PER_GB_STATE_DUMP);
+    let mut lines = Vec::with_capacity(AVERAGE_COUNT_ACCOUNTS_PER_GB_STATE_DUMP);
     let mut total_inserted_accounts = 0;
 
     for (index, entry) in collector.iter()?.enumerate() {
-        let (address, account) = entry?;
-        let (address, _) = Address::from_compact(address.as_slice(), address.len());
-        let (account, _) = GenesisAccount::from_compact(account.as_slice(), account.len());
-
-        // Add to prefix sets
-        let hashed_address = keccak256(address);
-        prefix_sets.account_prefix_set.insert(Nibbles::unpack(hashed_address));
-
-        // Add storage keys to prefix sets if storage exists
-        if let Some(ref storage) = account.storage {
-            for key in storage.keys() {
-                let hashed_key = keccak256(key);
-                prefix_sets
-                    .storage_prefix_sets
-                    .entry(hashed_address)
-                    .or_default()
-                    .insert(Nibbles::unpack(hashed_key));
-            }
-        }
-
-        accounts.push((address, account));
+        let (_, line) = entry?;
+        lines.push(Vec::<u8>::decompress_owned(line)?);
 
         if (index > 0 && index.is_multiple_of(AVERAGE_COUNT_ACCOUNTS_PER_GB_STATE_DUMP)) ||
             index == accounts_len - 1
         {
+            let accounts = parse_account_lines(&lines)?;
+            lines.clear();
             total_inserted_accounts += accounts.len();
 
             info!(target: "reth::cli",
@@ -729,80 +954,24 @@ where
                 accounts.iter().map(|(address, account)| (address, account)),
                 block,
             )?;
-
-            accounts.clear();
         }
     }
     Ok(())
 }
 
-/// Computes the state root (from scratch) based on the accounts and storages present in the
-/// database.
-fn compute_state_root<Provider>(
-    provider: &Provider,
-    prefix_sets: Option<TriePrefixSets>,
-) -> Result<B256, InitStorageError>
-where
-    Provider: DBProvider<Tx: DbTxMut> + TrieWriter,
-{
-    trace!(target: "reth::cli", "Computing state root");
-
-    let tx = provider.tx_ref();
-    let mut intermediate_state: Option<IntermediateStateRootState> = None;
-    let mut total_flushed_updates = 0;
-
-    loop {
-        let mut state_root =
-            StateRootComputer::from_tx(tx).with_intermediate_state(intermediate_state);
-
-        if let Some(sets) = prefix_sets.clone() {
-            state_root = state_root.with_prefix_sets(sets);
-        }
-
-        match state_root.root_with_progress()? {
-            StateRootProgress::Progress(state, _, updates) => {
-                let updated_len = provider.write_trie_updates(updates)?;
-                total_flushed_updates += updated_len;
-
-                trace!(target: "reth::cli",
-                    last_account_key = %state.account_root_state.last_hashed_key,
-                    updated_len,
-                    total_flushed_updates,
-                    "Flushing trie updates"
-                );
-
-                intermediate_state = Some(*state);
-
-                if total_flushed_updates.is_multiple_of(SOFT_LIMIT_COUNT_FLUSHED_UPDATES) {
-                    info!(target: "reth::cli",
-                        total_flushed_updates,
-                        "Flushing trie updates"
-                    );
-                }
-            }
-            StateRootProgress::Complete(root, _, updates) => {
-                let updated_len = provider.write_trie_updates(updates)?;
-                total_flushed_updates += updated_len;
-
-                trace!(target: "reth::cli",
-                    %root,
-                    updated_len,
-                    total_flushed_updates,
-                    "State root has been computed"
-                );
-
-                return Ok(root)
-            }
-        }
-    }
-}
-
 /// Type to deserialize state root from state dump file.
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
 struct StateRoot {
     root: B256,
 }
 
+/// The address of an account in the state dump file, the rest of the account is skipped.
+#[derive(Debug, Deserialize)]
+struct AccountAddress {
+    /// The account's address.
+    address: Address,
+}
+
 /// An account as in the state dump file. This contains a [`GenesisAccount`] and the account's
 /// address.
 #[derive(Debug, Serialize, Deserialize)]
@@ -831,9 +1000,14 @@ mod tests {
         Database,
     };
     use reth_provider::{
-        test_utils::{create_test_provider_factory_with_chain_spec, MockNodeTypesWithDB},
+        test_utils::{
+            create_test_provider_factory, create_test_provider_factory_with_chain_spec,
+            MockNodeTypesWithDB,
+        },
         ProviderFactory, RocksDBProviderFactory,
     };
+    use reth_trie::StateRoot as StateRootComputer;
+    use reth_trie_db::DatabaseStateRoot;
     use std::{collections::BTreeMap, sync::Arc};
 
     fn collect_table_entries<DB, T>(
@@ -987,6 +1161,110 @@ mod tests {
         }
     }
 
+    fn genesis_alloc(len: u64) -> BTreeMap<Address, GenesisAccount> {
+        (0..len)
+            .map(|i| {
+                let storage = (0..i % 20).map(|_| (B256::random(), B256::random())).collect();
+                (
+                    Address::random(),
+                    GenesisAccount {
+                        balance: U256::from(i),
+                        storage: Some(storage),
+                        ..Default::default()
+                    },
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn init_genesis_trie() {
+        let alloc = genesis_alloc(100);
+        let expected_state_root = reth_trie::root::state_root_ref_unhashed(&alloc);
+
+        let chain_spec = Arc::new(ChainSpec {
+            chain: Chain::from_id(1),
+            genesis: Genesis { alloc, ..Default::default() },
+            ..Default::default()
+        });
+        let factory = create_test_provider_factory_with_chain_spec(chain_spec);
+        init_genesis(&factory).unwrap();
+
+        // the root is computed from the trie nodes written on genesis
+        let provider = factory.provider_rw().unwrap();
+        assert_eq!(
+            StateRootComputer::from_tx(provider.tx_ref()).root().unwrap(),
+            expected_state_root
+        );
+        assert!(!collect_table_entries::<Arc<DatabaseEnv>, tables::AccountsTrie>(
+            provider.tx_ref()
+        )
+        .unwrap()
+        .is_empty());
+    }
+
+    #[test]
+    fn state_trie_flushed_in_chunks() {
+        let alloc = genesis_alloc(100);
+        let expected_state_root = reth_trie::root::state_root_ref_unhashed(&alloc);
+
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+        let hashed_alloc = hash_alloc(alloc.iter());
+        write_hashed_alloc(&provider, &hashed_alloc).unwrap();
+
+        let mut trie = StateTrieBuilder::new(&provider);
+        for accounts in hashed_alloc.chunks(7) {
+            trie.add_accounts(accounts).unwrap();
+        }
+        // the updates are flushed with every chunk, before the root is computed
+        assert!(trie.total_flushed_updates > 0);
+        assert_eq!(trie.root().unwrap(), expected_state_root);
+        assert_eq!(
+            StateRootComputer::from_tx(provider.tx_ref()).root().unwrap(),
+            expected_state_root
+        );
+
+        // rebuilding the trie from the hashed state yields the same trie
+        let account_nodes =
+            collect_table_entries::<Arc<DatabaseEnv>, tables::AccountsTrie>(provider.tx_ref())
+                .unwrap();
+        assert_eq!(rebuild_state_trie(&provider).unwrap(), expected_state_root);
+        assert_eq!(
+            collect_table_entries::<Arc<DatabaseEnv>, tables::AccountsTrie>(provider.tx_ref())
+                .unwrap(),
+            account_nodes
+        );
+    }
+
+    #[test]
+    fn parse_state_dump_accounts() {
+        let alloc = genesis_alloc(10);
+        let dump = alloc
+            .iter()
+            .rev()
+            .map(|(address, account)| {
+                serde_json::to_string(&GenesisAccountWithAddress {
+                    genesis_account: account.clone(),
+                    address: *address,
+                })
+                .unwrap() +
+                    "\n"
+            })
+            .collect::<String>();
+
+        let mut collector = parse_accounts(dump.as_bytes(), EtlConfig::default()).unwrap();
+        assert_eq!(collector.len(), alloc.len());
+
+        // the accounts are sorted by address and parsed once they're written
+        let lines = collector
+            .iter()
+            .unwrap()
+            .map(|entry| Vec::<u8>::decompress_owned(entry.unwrap().1).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(parse_account_lines(&lines).unwrap(), alloc.into_iter().collect::<Vec<_>>());
+    }
+
     #[test]
     fn warn_storage_settings_mismatch() {
         let factory = create_test_provider_factory_with_chain_spec(MAINNET.clone());
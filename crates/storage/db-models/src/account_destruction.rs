@@ -0,0 +1,77 @@
+//! Account destruction model.
+
+use alloy_primitives::{Address, BlockNumber, TxHash};
+use reth_primitives_traits::ValueWithSubKey;
+
+/// The removal of an account from the state, as recorded during execution if the optional account
+/// destructions index is enabled.
+///
+/// An account is removed if it executes a `SELFDESTRUCT`, which since EIP-6780 only removes
+/// contracts created in the same transaction, or if it's touched while empty since EIP-161.
+///
+/// [`BlockNumber`] is the subkey.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "reth-codec"), reth_codecs::add_arbitrary_tests(compact))]
+pub struct AccountDestruction {
+    /// Number of the block the account was removed in. Acts as `DupSort::SubKey`.
+    pub block_number: BlockNumber,
+    /// Hash of the transaction that removed the account.
+    pub transaction_hash: TxHash,
+    /// Beneficiary of the `SELFDESTRUCT`, [`None`] if the account was removed because it was
+    /// touched while empty.
+    pub beneficiary: Option<Address>,
+}
+
+impl AccountDestruction {
+    /// Returns `true` if the account was removed by a `SELFDESTRUCT`.
+    pub const fn is_self_destruct(&self) -> bool {
+        self.beneficiary.is_some()
+    }
+}
+
+impl ValueWithSubKey for AccountDestruction {
+    type SubKey = BlockNumber;
+
+    fn get_subkey(&self) -> Self::SubKey {
+        self.block_number
+    }
+}
+
+// NOTE: The block number is encoded as big endian bytes in front of the value, so the
+// destructions of an account are sorted by block number and can be fetched with
+// seek_by_key_subkey
+#[cfg(any(test, feature = "reth-codec"))]
+impl reth_codecs::Compact for AccountDestruction {
+    fn to_compact<B>(&self, buf: &mut B) -> usize
+    where
+        B: bytes::BufMut + AsMut<[u8]>,
+    {
+        buf.put_slice(&self.block_number.to_be_bytes());
+        buf.put_slice(self.transaction_hash.as_slice());
+
+        let beneficiary_len = if let Some(beneficiary) = self.beneficiary {
+            buf.put_slice(beneficiary.as_slice());
+            20
+        } else {
+            0
+        };
+        beneficiary_len + 40
+    }
+
+    fn from_compact(mut buf: &[u8], len: usize) -> (Self, &[u8]) {
+        use bytes::Buf;
+        let block_number = buf.get_u64();
+        let transaction_hash = TxHash::from_slice(&buf[..32]);
+        buf.advance(32);
+
+        let beneficiary = (len - 40 > 0).then(|| {
+            let beneficiary = Address::from_slice(&buf[..20]);
+            buf.advance(20);
+            beneficiary
+        });
+
+        (Self { block_number, transaction_hash, beneficiary }, buf)
+    }
+}
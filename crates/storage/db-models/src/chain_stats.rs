@@ -0,0 +1,26 @@
+//! Chain statistics record model.
+
+/// The aggregated statistics of the canonical blocks of a day or an epoch.
+#[derive(Debug, Default, Eq, PartialEq, Clone, Copy)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[cfg_attr(any(test, feature = "reth-codec"), derive(reth_codecs::Compact))]
+#[cfg_attr(any(test, feature = "reth-codec"), reth_codecs::add_arbitrary_tests(compact))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChainStatsRecord {
+    /// Number of canonical blocks.
+    pub blocks: u64,
+    /// Number of transactions.
+    pub transactions: u64,
+    /// Gas used by all transactions.
+    pub gas_used: u64,
+    /// Sum of the gas limits.
+    pub gas_limit: u64,
+    /// Sum of the base fees per gas.
+    pub base_fee_per_gas_total: u128,
+    /// Blob gas used.
+    pub blob_gas_used: u64,
+    /// Net number of created accounts, as the two's complement of the signed value.
+    pub accounts_growth: u64,
+    /// Net number of non-zero storage slots, as the two's complement of the signed value.
+    pub storage_slots_growth: u64,
+}
@@ -0,0 +1,79 @@
+//! Contract creation model.
+
+use alloy_primitives::{Address, BlockNumber, TxHash, B256};
+use reth_primitives_traits::ValueWithSubKey;
+
+/// The creation of a contract, as recorded during execution if the optional contract creations
+/// index is enabled.
+///
+/// A contract is created more than once if it's re-created at the same address with `CREATE2`
+/// after it was removed by a `SELFDESTRUCT`.
+///
+/// [`BlockNumber`] is the subkey.
+#[derive(Debug, Default, Eq, PartialEq, Clone, Copy)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[cfg_attr(any(test, feature = "reth-codec"), reth_codecs::add_arbitrary_tests(compact))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ContractCreation {
+    /// Number of the block the contract was created in. Acts as `DupSort::SubKey`.
+    pub block_number: BlockNumber,
+    /// Hash of the transaction that created the contract.
+    pub transaction_hash: TxHash,
+    /// Address of the account that executed the `CREATE` or `CREATE2`, which is the sender of
+    /// the transaction for contracts created by a transaction without recipient.
+    pub creator: Address,
+    /// Hash of the deployed code.
+    pub code_hash: B256,
+    /// Salt of the `CREATE2`, [`None`] if the contract was created with `CREATE`.
+    pub salt: Option<B256>,
+}
+
+impl ValueWithSubKey for ContractCreation {
+    type SubKey = BlockNumber;
+
+    fn get_subkey(&self) -> Self::SubKey {
+        self.block_number
+    }
+}
+
+// NOTE: The block number is encoded as big endian bytes in front of the value, so the creations
+// of a contract are sorted by block number and can be fetched with seek_by_key_subkey
+#[cfg(any(test, feature = "reth-codec"))]
+impl reth_codecs::Compact for ContractCreation {
+    fn to_compact<B>(&self, buf: &mut B) -> usize
+    where
+        B: bytes::BufMut + AsMut<[u8]>,
+    {
+        buf.put_slice(&self.block_number.to_be_bytes());
+        buf.put_slice(self.transaction_hash.as_slice());
+        buf.put_slice(self.creator.as_slice());
+        buf.put_slice(self.code_hash.as_slice());
+
+        let salt_len = if let Some(salt) = self.salt {
+            buf.put_slice(salt.as_slice());
+            32
+        } else {
+            0
+        };
+        salt_len + 92
+    }
+
+    fn from_compact(mut buf: &[u8], len: usize) -> (Self, &[u8]) {
+        use bytes::Buf;
+        let block_number = buf.get_u64();
+        let transaction_hash = TxHash::from_slice(&buf[..32]);
+        buf.advance(32);
+        let creator = Address::from_slice(&buf[..20]);
+        buf.advance(20);
+        let code_hash = B256::from_slice(&buf[..32]);
+        buf.advance(32);
+
+        let salt = (len > 92).then(|| {
+            let salt = B256::from_slice(&buf[..32]);
+            buf.advance(32);
+            salt
+        });
+
+        (Self { block_number, transaction_hash, creator, code_hash, salt }, buf)
+    }
+}
@@ -26,3 +26,19 @@ pub use storage::StorageBeforeTx;
 /// Client Version
 pub mod client_version;
 pub use client_version::ClientVersion;
+
+/// Reorgs
+pub mod reorg;
+pub use reorg::ReorgRecord;
+
+/// Chain statistics
+pub mod chain_stats;
+pub use chain_stats::ChainStatsRecord;
+
+/// Contract creations
+pub mod contract_creation;
+pub use contract_creation::ContractCreation;
+
+/// Account destructions
+pub mod account_destruction;
+pub use account_destruction::AccountDestruction;
@@ -0,0 +1,32 @@
+//! Reorg record model.
+
+use alloy_primitives::{BlockHash, BlockNumber};
+
+/// A reorg of the canonical chain performed by the node.
+#[derive(Debug, Default, Eq, PartialEq, Clone, Copy)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[cfg_attr(any(test, feature = "reth-codec"), derive(reth_codecs::Compact))]
+#[cfg_attr(any(test, feature = "reth-codec"), reth_codecs::add_arbitrary_tests(compact))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReorgRecord {
+    /// Unix timestamp in seconds at which the node performed the reorg.
+    pub timestamp: u64,
+    /// Hash of the canonical head before the reorg.
+    pub old_head: BlockHash,
+    /// Number of the canonical head before the reorg.
+    pub old_head_number: BlockNumber,
+    /// Timestamp of the canonical head before the reorg.
+    pub old_head_timestamp: u64,
+    /// Hash of the canonical head after the reorg.
+    pub new_head: BlockHash,
+    /// Number of the canonical head after the reorg.
+    pub new_head_number: BlockNumber,
+    /// Timestamp of the canonical head after the reorg.
+    pub new_head_timestamp: u64,
+    /// Number of blocks that were removed from the canonical chain.
+    pub depth: u64,
+    /// Number of transactions in the removed blocks.
+    pub reverted_transactions: u64,
+    /// Number of transactions in the blocks that became canonical.
+    pub applied_transactions: u64,
+}
@@ -0,0 +1,415 @@
+use crate::{
+    protocol::{
+        read_message, write_message, Request, Response, MAX_REQUEST_SIZE, MAX_RESPONSE_SIZE,
+    },
+    tls::{ServerName, Stream},
+    RemoteCursor, DEFAULT_MAX_BATCH_SIZE,
+};
+use parking_lot::Mutex;
+use reth_db_api::{
+    database_metrics::DatabaseMetrics,
+    table::{Decompress, DupSort, Encode, Table, TableImporter},
+    transaction::{DbTx, DbTxMut},
+    Database, DatabaseError,
+};
+use rustls::ClientConfig;
+use std::{
+    io::BufReader,
+    net::{SocketAddr, TcpStream},
+    sync::Arc,
+};
+
+/// A [`Database`] served by a [`RemoteDatabaseServer`](crate::RemoteDatabaseServer).
+///
+/// Every transaction opens its own connection to the server and authenticates it with the shared
+/// secret. The database is read-only, so [`Database::tx_mut`] always returns an error.
+///
+/// Connections are only encrypted if TLS is configured with [`Self::with_tls`].
+#[derive(Clone)]
+pub struct RemoteDatabase {
+    addr: SocketAddr,
+    secret: Arc<[u8]>,
+    max_batch_size: usize,
+    tls: Option<(Arc<ClientConfig>, ServerName<'static>)>,
+}
+
+impl std::fmt::Debug for RemoteDatabase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RemoteDatabase")
+            .field("addr", &self.addr)
+            .field("max_batch_size", &self.max_batch_size)
+            .field("tls", &self.tls.as_ref().map(|(_, server_name)| server_name))
+            .finish_non_exhaustive()
+    }
+}
+
+impl RemoteDatabase {
+    /// Creates a new client for the server listening on the given address, which authenticates
+    /// with the secret of the server.
+    pub fn new(addr: SocketAddr, secret: impl AsRef<[u8]>) -> Self {
+        Self {
+            addr,
+            secret: secret.as_ref().into(),
+            max_batch_size: DEFAULT_MAX_BATCH_SIZE,
+            tls: None,
+        }
+    }
+
+    /// Sets the maximum number of entries requested by a single cursor request.
+    pub const fn with_max_batch_size(mut self, max_batch_size: usize) -> Self {
+        self.max_batch_size = max_batch_size;
+        self
+    }
+
+    /// Encrypts all connections with TLS and only accepts servers with a certificate for the
+    /// given name that is trusted by the configuration, see
+    /// [`client_config`](crate::tls::client_config).
+    pub fn with_tls(mut self, config: Arc<ClientConfig>, server_name: ServerName<'static>) -> Self {
+        self.tls = Some((config, server_name));
+        self
+    }
+
+    /// Returns the address of the server.
+    pub const fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+}
+
+impl Database for RemoteDatabase {
+    type TX = RemoteTx;
+    type TXMut = RemoteTx;
+
+    fn tx(&self) -> Result<Self::TX, DatabaseError> {
+        let tx = RemoteTx {
+            connection: Arc::new(Mutex::new(Connection::connect(
+                self.addr,
+                &self.secret,
+                self.tls.as_ref(),
+            )?)),
+            max_batch_size: self.max_batch_size,
+        };
+        tx.request(&Request::BeginRead)?;
+        Ok(tx)
+    }
+
+    fn tx_mut(&self) -> Result<Self::TXMut, DatabaseError> {
+        Err(read_only())
+    }
+}
+
+impl DatabaseMetrics for RemoteDatabase {}
+
+/// A connection to a [`RemoteDatabaseServer`](crate::RemoteDatabaseServer).
+#[derive(Debug)]
+pub(crate) struct Connection {
+    stream: BufReader<Stream>,
+}
+
+impl Connection {
+    fn connect(
+        addr: SocketAddr,
+        secret: &[u8],
+        tls: Option<&(Arc<ClientConfig>, ServerName<'static>)>,
+    ) -> Result<Self, DatabaseError> {
+        let connect = || {
+            let stream = TcpStream::connect(addr)?;
+            stream.set_nodelay(true)?;
+            Ok(Self { stream: BufReader::new(Stream::connect(stream, tls)?) })
+        };
+        let mut connection = connect().map_err(|err: std::io::Error| {
+            DatabaseError::Other(format!("failed to connect to remote database at {addr}: {err}"))
+        })?;
+        connection.request(&Request::Authenticate { secret: secret.to_vec() })?;
+        Ok(connection)
+    }
+
+    /// Sends the request and waits for its response.
+    pub(crate) fn request(&mut self, request: &Request) -> Result<Response, DatabaseError> {
+        let io_error =
+            |err: std::io::Error| DatabaseError::Other(format!("remote database error: {err}"));
+        write_message(self.stream.get_mut(), request, MAX_REQUEST_SIZE).map_err(io_error)?;
+        match read_message(&mut self.stream, MAX_RESPONSE_SIZE).map_err(io_error)? {
+            Some(Response::Error(err)) => Err(DatabaseError::Other(err)),
+            Some(response) => Ok(response),
+            None => Err(DatabaseError::Other("remote database closed the connection".to_string())),
+        }
+    }
+}
+
+/// A read-only transaction of a [`RemoteDatabase`].
+///
+/// Dropping the transaction closes its connection, which aborts the read transaction on the server.
+#[derive(Debug)]
+pub struct RemoteTx {
+    connection: Arc<Mutex<Connection>>,
+    max_batch_size: usize,
+}
+
+impl RemoteTx {
+    fn request(&self, request: &Request) -> Result<Response, DatabaseError> {
+        self.connection.lock().request(request)
+    }
+
+    fn new_cursor<T: Table>(&self) -> Result<RemoteCursor<T>, DatabaseError> {
+        match self.request(&Request::OpenCursor { table: T::NAME.to_string() })? {
+            Response::Cursor(id) => {
+                Ok(RemoteCursor::new(self.connection.clone(), id, self.max_batch_size))
+            }
+            response => Err(response.unexpected()),
+        }
+    }
+}
+
+impl DbTx for RemoteTx {
+    type Cursor<T: Table> = RemoteCursor<T>;
+    type DupCursor<T: DupSort> = RemoteCursor<T>;
+
+    fn get<T: Table>(&self, key: T::Key) -> Result<Option<T::Value>, DatabaseError> {
+        self.get_by_encoded_key::<T>(&key.encode())
+    }
+
+    fn get_by_encoded_key<T: Table>(
+        &self,
+        key: &<T::Key as Encode>::Encoded,
+    ) -> Result<Option<T::Value>, DatabaseError> {
+        let request = Request::Get { table: T::NAME.to_string(), key: key.as_ref().to_vec() };
+        match self.request(&request)? {
+            Response::Value(value) => value.map(|value| T::Value::decompress(&value)).transpose(),
+            response => Err(response.unexpected()),
+        }
+    }
+
+    fn commit(self) -> Result<(), DatabaseError> {
+        self.request(&Request::Commit)?;
+        Ok(())
+    }
+
+    fn abort(self) {
+        let _ = self.request(&Request::Abort);
+    }
+
+    fn cursor_read<T: Table>(&self) -> Result<Self::Cursor<T>, DatabaseError> {
+        self.new_cursor()
+    }
+
+    fn cursor_dup_read<T: DupSort>(&self) -> Result<Self::DupCursor<T>, DatabaseError> {
+        self.new_cursor()
+    }
+
+    fn entries<T: Table>(&self) -> Result<usize, DatabaseError> {
+        match self.request(&Request::Entries { table: T::NAME.to_string() })? {
+            Response::Entries(entries) => Ok(entries),
+            response => Err(response.unexpected()),
+        }
+    }
+
+    /// Does nothing, the long read transaction safety of the server always applies to remote
+    /// transactions so clients can't pin a read transaction on the server.
+    fn disable_long_read_transaction_safety(&mut self) {}
+}
+
+impl DbTxMut for RemoteTx {
+    type CursorMut<T: Table> = RemoteCursor<T>;
+    type DupCursorMut<T: DupSort> = RemoteCursor<T>;
+
+    fn put<T: Table>(&self, _key: T::Key, _value: T::Value) -> Result<(), DatabaseError> {
+        Err(read_only())
+    }
+
+    fn delete<T: Table>(
+        &self,
+        _key: T::Key,
+        _value: Option<T::Value>,
+    ) -> Result<bool, DatabaseError> {
+        Err(read_only())
+    }
+
+    fn clear<T: Table>(&self) -> Result<(), DatabaseError> {
+        Err(read_only())
+    }
+
+    fn cursor_write<T: Table>(&self) -> Result<Self::CursorMut<T>, DatabaseError> {
+        Err(read_only())
+    }
+
+    fn cursor_dup_write<T: DupSort>(&self) -> Result<Self::DupCursorMut<T>, DatabaseError> {
+        Err(read_only())
+    }
+}
+
+impl TableImporter for RemoteTx {}
+
+/// Returns the error of write operations.
+pub(crate) fn read_only() -> DatabaseError {
+    DatabaseError::Other("remote database is read-only".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tls, RemoteDatabaseServer};
+    use alloy_primitives::{Address, B256, U256};
+    use reth_db::test_utils::create_test_rw_db;
+    use reth_db_api::{
+        cursor::{DbCursorRO, DbDupCursorRO},
+        tables,
+    };
+    use reth_primitives_traits::StorageEntry;
+    use rustls::ServerConfig;
+    use std::{net::TcpListener, path::PathBuf};
+
+    const SECRET: &[u8] = b"secret";
+
+    fn serve_test_db() -> RemoteDatabase {
+        serve_test_db_with_tls(None)
+    }
+
+    fn serve_test_db_with_tls(tls: Option<Arc<ServerConfig>>) -> RemoteDatabase {
+        let db = create_test_rw_db();
+        db.update(|tx| {
+            for number in 0..100u64 {
+                tx.put::<tables::CanonicalHeaders>(number, B256::with_last_byte(number as u8))?;
+            }
+            for byte in 1..=3u8 {
+                for slot in 0..10u8 {
+                    tx.put::<tables::PlainStorageState>(
+                        Address::repeat_byte(byte),
+                        StorageEntry::new(B256::with_last_byte(slot), U256::from(slot)),
+                    )?;
+                }
+            }
+            Ok::<_, DatabaseError>(())
+        })
+        .unwrap()
+        .unwrap();
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let mut server = RemoteDatabaseServer::new(db, SECRET).with_max_connections(2);
+        if let Some(config) = tls {
+            server = server.with_tls(config);
+        }
+        std::thread::spawn(move || server.serve(listener));
+
+        RemoteDatabase::new(addr, SECRET).with_max_batch_size(8)
+    }
+
+    #[test]
+    fn remote_reads() {
+        let db = serve_test_db();
+        let tx = db.tx().unwrap();
+
+        assert_eq!(tx.get::<tables::CanonicalHeaders>(42).unwrap(), Some(B256::with_last_byte(42)));
+        assert_eq!(tx.get::<tables::CanonicalHeaders>(100).unwrap(), None);
+        assert_eq!(tx.entries::<tables::CanonicalHeaders>().unwrap(), 100);
+
+        let mut cursor = tx.cursor_read::<tables::CanonicalHeaders>().unwrap();
+        let numbers =
+            cursor.walk(Some(10)).unwrap().map(|entry| entry.unwrap().0).collect::<Vec<_>>();
+        assert_eq!(numbers, (10..100).collect::<Vec<_>>());
+
+        let numbers =
+            cursor.walk_range(20..25).unwrap().map(|entry| entry.unwrap().0).collect::<Vec<_>>();
+        assert_eq!(numbers, (20..25).collect::<Vec<_>>());
+
+        // Stepping back while the next batch is buffered continues from the last returned entry
+        assert_eq!(cursor.seek(50).unwrap().unwrap().0, 50);
+        for number in 51..55 {
+            assert_eq!(cursor.next().unwrap().unwrap().0, number);
+        }
+        assert_eq!(cursor.prev().unwrap().unwrap().0, 53);
+        assert_eq!(cursor.current().unwrap().unwrap().0, 53);
+
+        let numbers =
+            cursor.walk_back(Some(5)).unwrap().map(|entry| entry.unwrap().0).collect::<Vec<_>>();
+        assert_eq!(numbers, (0..=5).rev().collect::<Vec<_>>());
+
+        tx.commit().unwrap();
+    }
+
+    #[test]
+    fn remote_dupsort_reads() {
+        let db = serve_test_db();
+        let tx = db.tx().unwrap();
+        let mut cursor = tx.cursor_dup_read::<tables::PlainStorageState>().unwrap();
+
+        let slots = cursor
+            .walk_dup(Some(Address::repeat_byte(2)), Some(B256::with_last_byte(4)))
+            .unwrap()
+            .map(|entry| entry.unwrap().1.value)
+            .collect::<Vec<_>>();
+        assert_eq!(slots, (4..10).map(U256::from).collect::<Vec<_>>());
+
+        let entry = cursor
+            .seek_by_key_subkey(Address::repeat_byte(3), B256::with_last_byte(7))
+            .unwrap()
+            .unwrap();
+        assert_eq!(entry.value, U256::from(7));
+        assert_eq!(cursor.next_dup().unwrap().unwrap().1.value, U256::from(8));
+        assert_eq!(cursor.last_dup().unwrap().unwrap().value, U256::from(9));
+
+        assert_eq!(cursor.first().unwrap().unwrap().0, Address::repeat_byte(1));
+        assert_eq!(cursor.next_no_dup().unwrap().unwrap().0, Address::repeat_byte(2));
+        assert_eq!(cursor.next_no_dup().unwrap().unwrap().0, Address::repeat_byte(3));
+        assert_eq!(cursor.next_no_dup().unwrap(), None);
+    }
+
+    #[test]
+    fn remote_database_requires_secret() {
+        let db = serve_test_db();
+        assert!(RemoteDatabase::new(db.addr(), b"wrong").tx().is_err());
+        assert!(RemoteDatabase::new(db.addr(), b"").tx().is_err());
+        assert!(db.tx().is_ok());
+    }
+
+    #[test]
+    fn remote_database_limits_connections() {
+        let db = serve_test_db();
+        let first = db.tx().unwrap();
+        let second = db.tx().unwrap();
+        assert!(db.tx().is_err());
+
+        drop(first);
+        drop(second);
+        // Connection slots are released once the server notices the closed connections
+        let reconnected = (0..100).any(|_| {
+            std::thread::sleep(std::time::Duration::from_millis(10));
+            db.tx().is_ok()
+        });
+        assert!(reconnected);
+    }
+
+    #[test]
+    fn remote_reads_over_tls() {
+        // A CA and a certificate for `localhost` issued by it, valid until 2126
+        let testdata = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("testdata");
+        let server_config =
+            tls::server_config(&testdata.join("server.pem"), &testdata.join("server.key")).unwrap();
+        let client_config = tls::client_config(&testdata.join("ca.pem")).unwrap();
+
+        let db = serve_test_db_with_tls(Some(server_config));
+        let tls_db = db.clone().with_tls(client_config.clone(), "localhost".try_into().unwrap());
+        let tx = tls_db.tx().unwrap();
+        assert_eq!(tx.get::<tables::CanonicalHeaders>(42).unwrap(), Some(B256::with_last_byte(42)));
+        let numbers = tx
+            .cursor_read::<tables::CanonicalHeaders>()
+            .unwrap()
+            .walk(None)
+            .unwrap()
+            .map(|entry| entry.unwrap().0)
+            .collect::<Vec<_>>();
+        assert_eq!(numbers, (0..100).collect::<Vec<_>>());
+        tx.commit().unwrap();
+
+        // Plain text clients and clients expecting another server are rejected
+        assert!(db.tx().is_err());
+        assert!(db.with_tls(client_config, "example.com".try_into().unwrap()).tx().is_err());
+    }
+
+    #[test]
+    fn remote_database_is_read_only() {
+        let db = serve_test_db();
+        assert!(db.tx_mut().is_err());
+        assert!(db.tx().unwrap().put::<tables::CanonicalHeaders>(0, B256::ZERO).is_err());
+    }
+}
@@ -0,0 +1,266 @@
+use crate::{
+    client::{read_only, Connection},
+    protocol::{CursorOp, RawEntry, Request, Response},
+};
+use parking_lot::Mutex;
+use reth_db_api::{
+    common::{PairResult, ValueOnlyResult},
+    cursor::{
+        DbCursorRO, DbCursorRW, DbDupCursorRO, DbDupCursorRW, DupWalker, RangeWalker,
+        ReverseWalker, Walker,
+    },
+    table::{Decode, Decompress, DupSort, Encode, IntoVec, Table},
+    DatabaseError,
+};
+use std::{
+    collections::VecDeque,
+    marker::PhantomData,
+    ops::{Bound, RangeBounds},
+    sync::Arc,
+};
+
+/// A cursor of a [`RemoteTx`](crate::RemoteTx).
+///
+/// The position of the cursor is kept on the server. Consecutive steps in the same direction
+/// request batches of entries, which start with a single entry and double in size up to the
+/// maximum batch size, so short scans don't transfer entries that are never read.
+#[derive(Debug)]
+pub struct RemoteCursor<T> {
+    connection: Arc<Mutex<Connection>>,
+    id: u64,
+    max_batch_size: usize,
+    /// The size of the next batch of [`Self::batched_op`].
+    batch_size: usize,
+    /// The operation that returned the entries of [`Self::batch`].
+    batched_op: Option<CursorOp>,
+    /// Entries of the last batch that weren't returned yet.
+    batch: VecDeque<RawEntry>,
+    /// The entry last returned by the cursor.
+    current: Option<RawEntry>,
+    _table: PhantomData<T>,
+}
+
+impl<T: Table> RemoteCursor<T> {
+    pub(crate) const fn new(
+        connection: Arc<Mutex<Connection>>,
+        id: u64,
+        max_batch_size: usize,
+    ) -> Self {
+        Self {
+            connection,
+            id,
+            max_batch_size,
+            batch_size: 1,
+            batched_op: None,
+            batch: VecDeque::new(),
+            current: None,
+            _table: PhantomData,
+        }
+    }
+
+    /// Executes the cursor operation, returning buffered entries of batched operations first.
+    fn execute(&mut self, op: CursorOp) -> Result<Option<RawEntry>, DatabaseError> {
+        let batched = op.is_batched();
+        if batched && self.batched_op.as_ref() == Some(&op) {
+            if let Some(entry) = self.batch.pop_front() {
+                self.current = Some(entry.clone());
+                return Ok(Some(entry))
+            }
+            self.batch_size = (self.batch_size * 2).min(self.max_batch_size);
+        } else {
+            self.batch_size = 1;
+        }
+
+        // The server is ahead of us if we didn't return all entries of the last batch
+        let reposition = if self.batch.is_empty() { None } else { self.current.clone() };
+        self.batch.clear();
+        self.batched_op = batched.then(|| op.clone());
+
+        let request = Request::Cursor { cursor: self.id, op, count: self.batch_size, reposition };
+        let rows = match self.connection.lock().request(&request)? {
+            Response::Rows(rows) => rows,
+            response => return Err(response.unexpected()),
+        };
+
+        let mut rows = VecDeque::from(rows);
+        let entry = rows.pop_front();
+        if entry.is_some() || !batched {
+            self.current = entry.clone();
+        }
+        self.batch = rows;
+
+        Ok(entry)
+    }
+
+    fn pair(&mut self, op: CursorOp) -> PairResult<T> {
+        self.execute(op)?
+            .map(|(key, value)| Ok((T::Key::decode(&key)?, T::Value::decompress(&value)?)))
+            .transpose()
+    }
+
+    fn value(&mut self, op: CursorOp) -> ValueOnlyResult<T> {
+        self.execute(op)?.map(|(_, value)| T::Value::decompress(&value)).transpose()
+    }
+}
+
+impl<T> Drop for RemoteCursor<T> {
+    fn drop(&mut self) {
+        let _ = self.connection.lock().request(&Request::CloseCursor { cursor: self.id });
+    }
+}
+
+impl<T: Table> DbCursorRO<T> for RemoteCursor<T> {
+    fn first(&mut self) -> PairResult<T> {
+        self.pair(CursorOp::First)
+    }
+
+    fn seek_exact(&mut self, key: T::Key) -> PairResult<T> {
+        self.pair(CursorOp::SeekExact(key.encode().into_vec()))
+    }
+
+    fn seek(&mut self, key: T::Key) -> PairResult<T> {
+        self.pair(CursorOp::Seek(key.encode().into_vec()))
+    }
+
+    fn next(&mut self) -> PairResult<T> {
+        self.pair(CursorOp::Next)
+    }
+
+    fn prev(&mut self) -> PairResult<T> {
+        self.pair(CursorOp::Prev)
+    }
+
+    fn last(&mut self) -> PairResult<T> {
+        self.pair(CursorOp::Last)
+    }
+
+    fn current(&mut self) -> PairResult<T> {
+        self.pair(CursorOp::Current)
+    }
+
+    fn walk(&mut self, start_key: Option<T::Key>) -> Result<Walker<'_, T, Self>, DatabaseError> {
+        let start = match start_key {
+            Some(key) => self.seek(key),
+            None => self.first(),
+        }
+        .transpose();
+
+        Ok(Walker::new(self, start))
+    }
+
+    fn walk_range(
+        &mut self,
+        range: impl RangeBounds<T::Key>,
+    ) -> Result<RangeWalker<'_, T, Self>, DatabaseError> {
+        let start = match range.start_bound().cloned() {
+            Bound::Included(key) => self.seek(key),
+            Bound::Excluded(_key) => {
+                unreachable!("Rust doesn't allow for Bound::Excluded in starting bounds");
+            }
+            Bound::Unbounded => self.first(),
+        }
+        .transpose();
+
+        Ok(RangeWalker::new(self, start, range.end_bound().cloned()))
+    }
+
+    fn walk_back(
+        &mut self,
+        start_key: Option<T::Key>,
+    ) -> Result<ReverseWalker<'_, T, Self>, DatabaseError> {
+        let start = match start_key {
+            Some(key) => self.seek(key),
+            None => self.last(),
+        }
+        .transpose();
+
+        Ok(ReverseWalker::new(self, start))
+    }
+}
+
+impl<T: DupSort> DbDupCursorRO<T> for RemoteCursor<T> {
+    fn prev_dup(&mut self) -> PairResult<T> {
+        self.pair(CursorOp::PrevDup)
+    }
+
+    fn next_dup(&mut self) -> PairResult<T> {
+        self.pair(CursorOp::NextDup)
+    }
+
+    fn last_dup(&mut self) -> ValueOnlyResult<T> {
+        self.value(CursorOp::LastDup)
+    }
+
+    fn next_no_dup(&mut self) -> PairResult<T> {
+        self.pair(CursorOp::NextNoDup)
+    }
+
+    fn next_dup_val(&mut self) -> ValueOnlyResult<T> {
+        self.value(CursorOp::NextDup)
+    }
+
+    fn seek_by_key_subkey(&mut self, key: T::Key, subkey: T::SubKey) -> ValueOnlyResult<T> {
+        self.value(CursorOp::SeekBySubKey {
+            key: key.encode().into_vec(),
+            subkey: subkey.encode().into_vec(),
+        })
+    }
+
+    /// Depending on its arguments, returns an iterator starting at:
+    /// - Some(key), Some(subkey): a `key` item whose data is >= than `subkey`
+    /// - Some(key), None: first item of a specified `key`
+    /// - None, Some(subkey): like first case, but in the first key
+    /// - None, None: first item in the table of a DUPSORT table.
+    fn walk_dup(
+        &mut self,
+        key: Option<T::Key>,
+        subkey: Option<T::SubKey>,
+    ) -> Result<DupWalker<'_, T, Self>, DatabaseError> {
+        let start = match (key, subkey) {
+            (Some(key), Some(subkey)) => self.pair(CursorOp::SeekBySubKey {
+                key: key.encode().into_vec(),
+                subkey: subkey.encode().into_vec(),
+            }),
+            (Some(key), None) => self.seek_exact(key),
+            (None, Some(subkey)) => match self.first()? {
+                Some((key, _)) => self.pair(CursorOp::SeekBySubKey {
+                    key: key.encode().into_vec(),
+                    subkey: subkey.encode().into_vec(),
+                }),
+                None => Ok(None),
+            },
+            (None, None) => self.first(),
+        }
+        .transpose();
+
+        Ok(DupWalker { cursor: self, start })
+    }
+}
+
+impl<T: Table> DbCursorRW<T> for RemoteCursor<T> {
+    fn upsert(&mut self, _key: T::Key, _value: &T::Value) -> Result<(), DatabaseError> {
+        Err(read_only())
+    }
+
+    fn insert(&mut self, _key: T::Key, _value: &T::Value) -> Result<(), DatabaseError> {
+        Err(read_only())
+    }
+
+    fn append(&mut self, _key: T::Key, _value: &T::Value) -> Result<(), DatabaseError> {
+        Err(read_only())
+    }
+
+    fn delete_current(&mut self) -> Result<(), DatabaseError> {
+        Err(read_only())
+    }
+}
+
+impl<T: DupSort> DbDupCursorRW<T> for RemoteCursor<T> {
+    fn delete_current_duplicates(&mut self) -> Result<(), DatabaseError> {
+        Err(read_only())
+    }
+
+    fn append_dup(&mut self, _key: T::Key, _value: T::Value) -> Result<(), DatabaseError> {
+        Err(read_only())
+    }
+}
@@ -0,0 +1,63 @@
+//! Read-only access to a reth database over the network.
+//!
+//! Analysis tools and read-heavy RPC replicas often only need to read from a database that lives
+//! on another machine. Instead of copying the datadir, a [`RemoteDatabaseServer`] exposes the read
+//! interface of any [`Database`](reth_db_api::Database) over TCP and a [`RemoteDatabase`]
+//! implements [`Database`](reth_db_api::Database) on top of it, so it can be used wherever a local
+//! database is expected.
+//!
+//! Every read-only transaction of the client is backed by its own connection, which holds a read
+//! transaction on the server for as long as the client transaction is alive, so all reads observe
+//! the same snapshot. Cursor positions are kept on the server and consecutive cursor steps in the
+//! same direction are fetched in batches of growing size, which keeps the number of round trips of
+//! walkers over large tables low.
+//!
+//! The remote database is read-only, opening a read-write transaction returns an error.
+//!
+//! # Security
+//!
+//! Every connection has to authenticate with a secret shared between server and clients before it
+//! can read anything. The server serves a bounded number of connections at a time and bounds the
+//! size of every message it reads. The long read transaction safety of the served database always
+//! applies, clients can't disable it.
+//!
+//! Connections are encrypted with TLS if server and client are configured with certificates, see
+//! [`RemoteDatabaseServer::with_tls`] and [`RemoteDatabase::with_tls`]. Without TLS the secret and
+//! all data are sent in plain text, so the server should only listen on a loopback interface, which
+//! is the default of `reth db serve`.
+//!
+//! # Protocol
+//!
+//! Messages are length-prefixed bincode over TCP or TLS instead of gRPC or JSON-RPC. The database
+//! traits are synchronous and every call maps to exactly one request, so there is no need for
+//! multiplexing, streaming or an async runtime, and keys and values are already encoded by the
+//! tables, so they are sent as raw bytes without another schema layer. This keeps the crate free of
+//! protobuf code generation and server frameworks. The protocol is internal to this crate and only
+//! has to match between client and server of the same version.
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+
+mod client;
+pub use client::{RemoteDatabase, RemoteTx};
+
+mod cursor;
+pub use cursor::RemoteCursor;
+
+mod protocol;
+
+mod server;
+pub use server::RemoteDatabaseServer;
+
+pub mod tls;
+
+/// The default maximum number of entries returned by a single cursor request.
+pub const DEFAULT_MAX_BATCH_SIZE: usize = 1_024;
+
+/// The default maximum number of connections served at the same time.
+pub const DEFAULT_MAX_CONNECTIONS: usize = 64;
@@ -0,0 +1,207 @@
+//! The wire protocol between [`RemoteDatabase`](crate::RemoteDatabase) and
+//! [`RemoteDatabaseServer`](crate::RemoteDatabaseServer).
+//!
+//! Every message is a bincode-encoded [`Request`] or [`Response`] prefixed with its length as a
+//! big-endian `u32`. The client sends one request at a time and waits for its response. The first
+//! request of every connection must be a [`Request::Authenticate`], the server closes connections
+//! that send anything else or the wrong secret.
+
+use reth_db_api::DatabaseError;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::io::{self, Read, Write};
+
+/// The maximum size of a message before the connection is authenticated.
+pub(crate) const MAX_AUTH_MESSAGE_SIZE: usize = 1024;
+
+/// The maximum size of a request.
+///
+/// Requests only carry table names, encoded keys and cursor positions, so this is far below
+/// [`MAX_RESPONSE_SIZE`].
+pub(crate) const MAX_REQUEST_SIZE: usize = 1024 * 1024;
+
+/// The maximum size of a response.
+pub(crate) const MAX_RESPONSE_SIZE: usize = 256 * 1024 * 1024;
+
+/// An encoded `(key, value)` pair.
+pub(crate) type RawEntry = (Vec<u8>, Vec<u8>);
+
+/// A request sent by the client.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) enum Request {
+    /// Authenticates the connection with the shared secret of the server.
+    Authenticate { secret: Vec<u8> },
+    /// Opens the read transaction of the connection.
+    BeginRead,
+    /// Returns the value of the encoded key.
+    Get { table: String, key: Vec<u8> },
+    /// Returns the number of entries in the table.
+    Entries { table: String },
+    /// Opens a cursor over the table.
+    OpenCursor { table: String },
+    /// Closes the cursor.
+    CloseCursor { cursor: u64 },
+    /// Executes a cursor operation.
+    Cursor {
+        /// The cursor to operate on.
+        cursor: u64,
+        /// The operation.
+        op: CursorOp,
+        /// The maximum number of entries to return, only used by batched operations.
+        count: usize,
+        /// The entry to move the cursor to before executing the operation.
+        ///
+        /// Set if the client didn't consume all entries of the previous batch, so the cursor on
+        /// the server is ahead of the cursor of the client.
+        reposition: Option<RawEntry>,
+    },
+    /// Commits the read transaction.
+    Commit,
+    /// Aborts the read transaction.
+    Abort,
+}
+
+/// A response sent by the server.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) enum Response {
+    /// The request succeeded without a result.
+    Ok,
+    /// The encoded value of a [`Request::Get`].
+    Value(Option<Vec<u8>>),
+    /// The number of entries of a [`Request::Entries`].
+    Entries(usize),
+    /// The id of a cursor opened by [`Request::OpenCursor`].
+    Cursor(u64),
+    /// The entries returned by a [`Request::Cursor`], in the order they were visited.
+    Rows(Vec<RawEntry>),
+    /// The request failed.
+    Error(String),
+}
+
+impl Response {
+    /// Returns an error for a response that doesn't match the request.
+    pub(crate) fn unexpected(self) -> DatabaseError {
+        DatabaseError::Other(format!("unexpected remote database response: {self:?}"))
+    }
+}
+
+/// A cursor operation, see [`DbCursorRO`](reth_db_api::cursor::DbCursorRO) and
+/// [`DbDupCursorRO`](reth_db_api::cursor::DbDupCursorRO).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) enum CursorOp {
+    First,
+    Last,
+    Current,
+    Seek(Vec<u8>),
+    SeekExact(Vec<u8>),
+    Next,
+    Prev,
+    NextDup,
+    PrevDup,
+    NextNoDup,
+    LastDup,
+    SeekBySubKey { key: Vec<u8>, subkey: Vec<u8> },
+}
+
+impl CursorOp {
+    /// Returns `true` if the operation steps from the current position and can be repeated to
+    /// return a batch of entries.
+    pub(crate) const fn is_batched(&self) -> bool {
+        matches!(self, Self::Next | Self::Prev | Self::NextDup | Self::PrevDup | Self::NextNoDup)
+    }
+}
+
+/// Writes a length-prefixed message of at most `max_size` bytes.
+///
+/// The length prefix and the message are written at once, so the writer doesn't need to be
+/// buffered to avoid sending the prefix in its own packet.
+pub(crate) fn write_message<W: Write>(
+    writer: &mut W,
+    message: &impl Serialize,
+    max_size: usize,
+) -> io::Result<()> {
+    let mut frame = vec![0; 4];
+    bincode::serialize_into(&mut frame, message).map_err(io::Error::other)?;
+    let len = u32::try_from(frame.len() - 4)
+        .ok()
+        .filter(|len| *len as usize <= max_size)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "message too large"))?;
+    frame[..4].copy_from_slice(&len.to_be_bytes());
+    writer.write_all(&frame)?;
+    writer.flush()
+}
+
+/// Reads a length-prefixed message of at most `max_size` bytes.
+///
+/// The buffer grows with the bytes actually received instead of being allocated up front from the
+/// untrusted length prefix.
+///
+/// Returns `None` if the connection was closed before the message started.
+pub(crate) fn read_message<R: Read, M: DeserializeOwned>(
+    reader: &mut R,
+    max_size: usize,
+) -> io::Result<Option<M>> {
+    let mut len = [0; 4];
+    match reader.read_exact(&mut len) {
+        Ok(()) => {}
+        Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(err) => return Err(err),
+    }
+
+    let len = u32::from_be_bytes(len) as usize;
+    if len > max_size {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "message too large"))
+    }
+
+    let mut bytes = Vec::new();
+    reader.take(len as u64).read_to_end(&mut bytes)?;
+    if bytes.len() != len {
+        return Err(io::ErrorKind::UnexpectedEof.into())
+    }
+    bincode::deserialize(&bytes)
+        .map(Some)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn message_roundtrip() {
+        let request = Request::Cursor {
+            cursor: 1,
+            op: CursorOp::SeekBySubKey { key: vec![1, 2], subkey: vec![3] },
+            count: 16,
+            reposition: Some((vec![1], vec![2])),
+        };
+
+        let mut buf = Vec::new();
+        write_message(&mut buf, &request, MAX_REQUEST_SIZE).unwrap();
+        write_message(&mut buf, &Request::Commit, MAX_REQUEST_SIZE).unwrap();
+
+        let mut reader = buf.as_slice();
+        assert_eq!(
+            read_message::<_, Request>(&mut reader, MAX_REQUEST_SIZE).unwrap(),
+            Some(request)
+        );
+        assert_eq!(
+            read_message::<_, Request>(&mut reader, MAX_REQUEST_SIZE).unwrap(),
+            Some(Request::Commit)
+        );
+        assert_eq!(read_message::<_, Request>(&mut reader, MAX_REQUEST_SIZE).unwrap(), None);
+    }
+
+    #[test]
+    fn rejects_oversized_messages() {
+        let request = Request::Get { table: "Headers".to_string(), key: vec![0; 2048] };
+        let mut buf = Vec::new();
+        write_message(&mut buf, &request, MAX_REQUEST_SIZE).unwrap();
+        assert!(read_message::<_, Request>(&mut buf.as_slice(), MAX_AUTH_MESSAGE_SIZE).is_err());
+
+        // A length prefix without the promised bytes fails without allocating them
+        let mut reader = (MAX_REQUEST_SIZE as u32).to_be_bytes().to_vec();
+        reader.extend([0; 16]);
+        let err = read_message::<_, Request>(&mut reader.as_slice(), MAX_REQUEST_SIZE).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+}
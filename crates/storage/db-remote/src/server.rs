@@ -0,0 +1,400 @@
+use crate::{
+    protocol::{
+        read_message, write_message, CursorOp, RawEntry, Request, Response, MAX_AUTH_MESSAGE_SIZE,
+        MAX_REQUEST_SIZE, MAX_RESPONSE_SIZE,
+    },
+    tls::Stream,
+    DEFAULT_MAX_BATCH_SIZE, DEFAULT_MAX_CONNECTIONS,
+};
+use reth_db_api::{
+    cursor::{DbCursorRO, DbDupCursorRO},
+    table::{DupSort, Key, Table, Value},
+    transaction::DbTx,
+    Database, DatabaseError, RawDupSort, RawKey, RawTable, RawValue, TableViewer, Tables,
+};
+use rustls::ServerConfig;
+use std::{
+    collections::HashMap,
+    io::{self, BufReader},
+    net::{TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tracing::{debug, trace, warn};
+
+/// Serves the read interface of a [`Database`] to [`RemoteDatabase`](crate::RemoteDatabase)
+/// clients.
+///
+/// Every connection is handled on its own thread and holds at most one read transaction. Clients
+/// have to authenticate with the shared secret of the server before sending any other request, and
+/// connections beyond the configured maximum are closed right after they are accepted.
+///
+/// Connections are only encrypted if TLS is configured with [`Self::with_tls`].
+#[derive(Debug, Clone)]
+pub struct RemoteDatabaseServer<DB> {
+    db: DB,
+    secret: Arc<[u8]>,
+    max_batch_size: usize,
+    max_connections: usize,
+    tls: Option<Arc<ServerConfig>>,
+}
+
+impl<DB> RemoteDatabaseServer<DB>
+where
+    DB: Database + Clone + 'static,
+{
+    /// Creates a new server for the database that only accepts clients that know the secret.
+    pub fn new(db: DB, secret: impl AsRef<[u8]>) -> Self {
+        Self {
+            db,
+            secret: secret.as_ref().into(),
+            max_batch_size: DEFAULT_MAX_BATCH_SIZE,
+            max_connections: DEFAULT_MAX_CONNECTIONS,
+            tls: None,
+        }
+    }
+
+    /// Sets the maximum number of entries returned by a single cursor request.
+    pub const fn with_max_batch_size(mut self, max_batch_size: usize) -> Self {
+        self.max_batch_size = max_batch_size;
+        self
+    }
+
+    /// Sets the maximum number of connections that are served at the same time.
+    pub const fn with_max_connections(mut self, max_connections: usize) -> Self {
+        self.max_connections = max_connections;
+        self
+    }
+
+    /// Encrypts all connections with TLS, see [`server_config`](crate::tls::server_config).
+    ///
+    /// Clients have to be configured with
+    /// [`RemoteDatabase::with_tls`](crate::RemoteDatabase::with_tls).
+    pub fn with_tls(mut self, config: Arc<ServerConfig>) -> Self {
+        self.tls = Some(config);
+        self
+    }
+
+    /// Accepts connections on the listener until accepting fails.
+    pub fn serve(self, listener: TcpListener) -> io::Result<()> {
+        let active = Arc::new(AtomicUsize::new(0));
+        for stream in listener.incoming() {
+            let stream = stream?;
+            let peer = stream.peer_addr()?;
+
+            if active.fetch_add(1, Ordering::AcqRel) >= self.max_connections {
+                active.fetch_sub(1, Ordering::AcqRel);
+                warn!(target: "db::remote", %peer, max = self.max_connections, "Too many connections, closing");
+                continue
+            }
+            let guard = ConnectionGuard(active.clone());
+
+            let session = Session::new(self.db.clone(), self.max_batch_size);
+            let secret = self.secret.clone();
+            let tls = self.tls.clone();
+            std::thread::Builder::new().name(format!("remote-db-{peer}")).spawn(move || {
+                let _guard = guard;
+                debug!(target: "db::remote", %peer, "Accepted connection");
+                if let Err(err) = session.run(stream, &secret, tls.as_ref()) {
+                    warn!(target: "db::remote", %peer, %err, "Connection failed");
+                }
+                debug!(target: "db::remote", %peer, "Closed connection");
+            })?;
+        }
+        Ok(())
+    }
+}
+
+/// The time a client has to complete the TLS handshake and authenticate after connecting.
+const AUTH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Releases the slot of a connection when it's closed.
+#[derive(Debug)]
+struct ConnectionGuard(Arc<AtomicUsize>);
+
+impl Drop for ConnectionGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// A cursor opened by a client.
+///
+/// The cursor of the database is reopened for every request and moved to the stored position, so
+/// sessions don't need to keep cursors borrowing from their transaction.
+#[derive(Debug)]
+struct SessionCursor {
+    table: Tables,
+    position: Option<RawEntry>,
+}
+
+/// The state of a single connection.
+#[derive(Debug)]
+struct Session<DB: Database> {
+    db: DB,
+    max_batch_size: usize,
+    tx: Option<DB::TX>,
+    cursors: HashMap<u64, SessionCursor>,
+    next_cursor_id: u64,
+}
+
+impl<DB: Database> Session<DB> {
+    fn new(db: DB, max_batch_size: usize) -> Self {
+        Self { db, max_batch_size, tx: None, cursors: HashMap::default(), next_cursor_id: 0 }
+    }
+
+    /// Authenticates the client and handles its requests until it closes the connection.
+    /// Dropping the session aborts its read transaction.
+    fn run(
+        mut self,
+        stream: TcpStream,
+        secret: &[u8],
+        tls: Option<&Arc<ServerConfig>>,
+    ) -> io::Result<()> {
+        stream.set_nodelay(true)?;
+        stream.set_read_timeout(Some(AUTH_TIMEOUT))?;
+        let mut stream = BufReader::new(Stream::accept(stream, tls)?);
+
+        match read_message::<_, Request>(&mut stream, MAX_AUTH_MESSAGE_SIZE)? {
+            Some(Request::Authenticate { secret: provided })
+                if constant_time_eq(&provided, secret) =>
+            {
+                write_message(stream.get_mut(), &Response::Ok, MAX_RESPONSE_SIZE)?;
+            }
+            Some(_) => {
+                let _ = write_message(
+                    stream.get_mut(),
+                    &Response::Error("remote database authentication failed".to_string()),
+                    MAX_RESPONSE_SIZE,
+                );
+                return Err(io::Error::new(io::ErrorKind::PermissionDenied, "authentication failed"))
+            }
+            None => return Ok(()),
+        }
+        stream.get_ref().socket().set_read_timeout(None)?;
+
+        while let Some(request) = read_message::<_, Request>(&mut stream, MAX_REQUEST_SIZE)? {
+            trace!(target: "db::remote", ?request, "Received request");
+            let response =
+                self.handle(request).unwrap_or_else(|err| Response::Error(err.to_string()));
+            write_message(stream.get_mut(), &response, MAX_RESPONSE_SIZE)?;
+        }
+        Ok(())
+    }
+
+    fn handle(&mut self, request: Request) -> Result<Response, DatabaseError> {
+        match request {
+            Request::Authenticate { .. } => {
+                Err(DatabaseError::Other("connection already authenticated".to_string()))
+            }
+            Request::BeginRead => {
+                if self.tx.is_some() {
+                    return Err(DatabaseError::Other("read transaction already open".to_string()))
+                }
+                self.tx = Some(self.db.tx()?);
+                Ok(Response::Ok)
+            }
+            Request::Get { table, key } => {
+                let value = parse_table(&table)?.view(&GetViewer { tx: self.tx()?, key })?;
+                Ok(Response::Value(value))
+            }
+            Request::Entries { table } => {
+                let entries = parse_table(&table)?.view(&EntriesViewer { tx: self.tx()? })?;
+                Ok(Response::Entries(entries))
+            }
+            Request::OpenCursor { table } => {
+                let table = parse_table(&table)?;
+                self.tx()?;
+                let id = self.next_cursor_id;
+                self.next_cursor_id += 1;
+                self.cursors.insert(id, SessionCursor { table, position: None });
+                Ok(Response::Cursor(id))
+            }
+            Request::CloseCursor { cursor } => {
+                self.cursors.remove(&cursor);
+                Ok(Response::Ok)
+            }
+            Request::Cursor { cursor, op, count, reposition } => {
+                let tx = self.tx.as_ref().ok_or_else(no_transaction)?;
+                let cursor = self.cursors.get_mut(&cursor).ok_or_else(|| {
+                    DatabaseError::Other(format!("unknown remote database cursor {cursor}"))
+                })?;
+                if reposition.is_some() {
+                    cursor.position = reposition;
+                }
+
+                let rows = cursor.table.view(&CursorViewer {
+                    tx,
+                    op: &op,
+                    count: count.clamp(1, self.max_batch_size),
+                    position: cursor.position.as_ref(),
+                })?;
+
+                // Batched operations that reach the end of the table keep their position, like
+                // the cursor of the database does
+                if let Some(last) = rows.last() {
+                    cursor.position = Some(last.clone());
+                } else if !op.is_batched() {
+                    cursor.position = None;
+                }
+                Ok(Response::Rows(rows))
+            }
+            Request::Commit => {
+                self.cursors.clear();
+                self.tx.take().ok_or_else(no_transaction)?.commit()?;
+                Ok(Response::Ok)
+            }
+            Request::Abort => {
+                self.cursors.clear();
+                if let Some(tx) = self.tx.take() {
+                    tx.abort();
+                }
+                Ok(Response::Ok)
+            }
+        }
+    }
+
+    fn tx(&self) -> Result<&DB::TX, DatabaseError> {
+        self.tx.as_ref().ok_or_else(no_transaction)
+    }
+}
+
+/// Compares the secrets in time independent of the position of the first mismatch.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (a, b)| acc | (a ^ b)) == 0
+}
+
+fn no_transaction() -> DatabaseError {
+    DatabaseError::Other("no read transaction open".to_string())
+}
+
+fn parse_table(table: &str) -> Result<Tables, DatabaseError> {
+    table.parse().map_err(DatabaseError::Other)
+}
+
+fn raw<K: Key, V: Value>((key, value): (RawKey<K>, RawValue<V>)) -> RawEntry {
+    (key.into_key(), value.into_value())
+}
+
+struct GetViewer<'a, TX> {
+    tx: &'a TX,
+    key: Vec<u8>,
+}
+
+impl<TX: DbTx> TableViewer<Option<Vec<u8>>> for GetViewer<'_, TX> {
+    type Error = DatabaseError;
+
+    fn view<T: Table>(&self) -> Result<Option<Vec<u8>>, Self::Error> {
+        Ok(self.tx.get_by_encoded_key::<RawTable<T>>(&self.key)?.map(|value| value.into_value()))
+    }
+}
+
+struct EntriesViewer<'a, TX> {
+    tx: &'a TX,
+}
+
+impl<TX: DbTx> TableViewer<usize> for EntriesViewer<'_, TX> {
+    type Error = DatabaseError;
+
+    fn view<T: Table>(&self) -> Result<usize, Self::Error> {
+        self.tx.entries::<T>()
+    }
+}
+
+struct CursorViewer<'a, TX> {
+    tx: &'a TX,
+    op: &'a CursorOp,
+    count: usize,
+    position: Option<&'a RawEntry>,
+}
+
+impl<TX: DbTx> CursorViewer<'_, TX> {
+    /// Executes the operations that are supported by all tables, returns `None` for operations
+    /// that require a `DUPSORT` table.
+    fn execute<T, K, V, C>(&self, cursor: &mut C) -> Result<Option<Vec<RawEntry>>, DatabaseError>
+    where
+        T: Table<Key = RawKey<K>, Value = RawValue<V>>,
+        K: Key,
+        V: Value,
+        C: DbCursorRO<T>,
+    {
+        let entry = match self.op {
+            CursorOp::First => cursor.first()?,
+            CursorOp::Last => cursor.last()?,
+            CursorOp::Current => cursor.current()?,
+            CursorOp::Seek(key) => cursor.seek(RawKey::from_vec(key.clone()))?,
+            CursorOp::SeekExact(key) => cursor.seek_exact(RawKey::from_vec(key.clone()))?,
+            CursorOp::Next => return collect(self.count, || Ok(cursor.next()?.map(raw))).map(Some),
+            CursorOp::Prev => return collect(self.count, || Ok(cursor.prev()?.map(raw))).map(Some),
+            _ => return Ok(None),
+        };
+        Ok(Some(entry.map(raw).into_iter().collect()))
+    }
+}
+
+impl<TX: DbTx> TableViewer<Vec<RawEntry>> for CursorViewer<'_, TX> {
+    type Error = DatabaseError;
+
+    fn view<T: Table>(&self) -> Result<Vec<RawEntry>, Self::Error> {
+        let mut cursor = self.tx.cursor_read::<RawTable<T>>()?;
+        if let Some((key, _)) = self.position {
+            cursor.seek_exact(RawKey::from_vec(key.clone()))?;
+        }
+
+        self.execute(&mut cursor)?
+            .ok_or_else(|| DatabaseError::Other(format!("{} is not a DUPSORT table", T::NAME)))
+    }
+
+    fn view_dupsort<T: DupSort>(&self) -> Result<Vec<RawEntry>, Self::Error> {
+        let mut cursor = self.tx.cursor_dup_read::<RawDupSort<T>>()?;
+        if let Some((key, value)) = self.position {
+            // The values of `DUPSORT` tables start with their subkey, so seeking the full value
+            // finds the exact duplicate
+            cursor.seek_by_key_subkey(
+                RawKey::from_vec(key.clone()),
+                RawKey::from_vec(value.clone()),
+            )?;
+        }
+
+        if let Some(rows) = self.execute(&mut cursor)? {
+            return Ok(rows)
+        }
+
+        match self.op {
+            CursorOp::NextDup => collect(self.count, || Ok(cursor.next_dup()?.map(raw))),
+            CursorOp::PrevDup => collect(self.count, || Ok(cursor.prev_dup()?.map(raw))),
+            CursorOp::NextNoDup => collect(self.count, || Ok(cursor.next_no_dup()?.map(raw))),
+            CursorOp::LastDup => {
+                if cursor.last_dup()?.is_none() {
+                    return Ok(Vec::new())
+                }
+                Ok(cursor.current()?.map(raw).into_iter().collect())
+            }
+            CursorOp::SeekBySubKey { key, subkey } => Ok(cursor
+                .seek_by_key_subkey(
+                    RawKey::from_vec(key.clone()),
+                    RawKey::from_vec(subkey.clone()),
+                )?
+                .map(|value| (key.clone(), value.into_value()))
+                .into_iter()
+                .collect()),
+            _ => unreachable!("handled by execute"),
+        }
+    }
+}
+
+/// Repeats the step until it returns `None` or `count` entries were collected.
+fn collect(
+    count: usize,
+    mut step: impl FnMut() -> Result<Option<RawEntry>, DatabaseError>,
+) -> Result<Vec<RawEntry>, DatabaseError> {
+    let mut rows = Vec::new();
+    while rows.len() < count {
+        let Some(entry) = step()? else { break };
+        rows.push(entry);
+    }
+    Ok(rows)
+}
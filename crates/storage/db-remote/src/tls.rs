@@ -0,0 +1,123 @@
+//! TLS encryption of remote database connections.
+
+use rustls::{
+    crypto::ring::default_provider,
+    pki_types::{pem::PemObject, CertificateDer, PrivateKeyDer},
+    ClientConfig, ClientConnection, RootCertStore, ServerConfig, ServerConnection, StreamOwned,
+};
+use std::{
+    io::{self, Read, Write},
+    net::TcpStream,
+    path::Path,
+    sync::Arc,
+};
+
+pub use rustls::pki_types::ServerName;
+
+/// Loads the TLS configuration of a [`RemoteDatabaseServer`](crate::RemoteDatabaseServer) from a
+/// PEM file with the certificate chain and a PEM file with the private key of the server.
+pub fn server_config(cert: &Path, key: &Path) -> io::Result<Arc<ServerConfig>> {
+    let certs = CertificateDer::pem_file_iter(cert)
+        .and_then(|certs| certs.collect::<Result<Vec<_>, _>>())
+        .map_err(|err| pem_error(cert, err))?;
+    let key = PrivateKeyDer::from_pem_file(key).map_err(|err| pem_error(key, err))?;
+
+    let config = ServerConfig::builder_with_provider(Arc::new(default_provider()))
+        .with_safe_default_protocol_versions()
+        .map_err(io::Error::other)?
+        .with_no_client_auth()
+        .with_single_cert(certs, key)
+        .map_err(io::Error::other)?;
+    Ok(Arc::new(config))
+}
+
+/// Loads the TLS configuration of a [`RemoteDatabase`](crate::RemoteDatabase) that trusts the
+/// certificates of the given PEM file, usually the certificate authority that issued the
+/// certificate of the server.
+pub fn client_config(ca: &Path) -> io::Result<Arc<ClientConfig>> {
+    let mut roots = RootCertStore::empty();
+    for cert in CertificateDer::pem_file_iter(ca).map_err(|err| pem_error(ca, err))? {
+        roots.add(cert.map_err(|err| pem_error(ca, err))?).map_err(io::Error::other)?;
+    }
+
+    let config = ClientConfig::builder_with_provider(Arc::new(default_provider()))
+        .with_safe_default_protocol_versions()
+        .map_err(io::Error::other)?
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+    Ok(Arc::new(config))
+}
+
+fn pem_error(path: &Path, err: rustls::pki_types::pem::Error) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("invalid PEM file {}: {err}", path.display()),
+    )
+}
+
+/// A connection between client and server, encrypted if TLS is configured.
+///
+/// The TLS handshake runs on the first read or write, so it's bound by the timeouts of the socket.
+#[derive(Debug)]
+pub(crate) enum Stream {
+    Plain(TcpStream),
+    Client(Box<StreamOwned<ClientConnection, TcpStream>>),
+    Server(Box<StreamOwned<ServerConnection, TcpStream>>),
+}
+
+impl Stream {
+    /// Wraps a stream connected to a server.
+    pub(crate) fn connect(
+        stream: TcpStream,
+        tls: Option<&(Arc<ClientConfig>, ServerName<'static>)>,
+    ) -> io::Result<Self> {
+        let Some((config, server_name)) = tls else { return Ok(Self::Plain(stream)) };
+        let connection =
+            ClientConnection::new(config.clone(), server_name.clone()).map_err(io::Error::other)?;
+        Ok(Self::Client(Box::new(StreamOwned::new(connection, stream))))
+    }
+
+    /// Wraps a stream accepted by the server.
+    pub(crate) fn accept(stream: TcpStream, tls: Option<&Arc<ServerConfig>>) -> io::Result<Self> {
+        let Some(config) = tls else { return Ok(Self::Plain(stream)) };
+        let connection = ServerConnection::new(config.clone()).map_err(io::Error::other)?;
+        Ok(Self::Server(Box::new(StreamOwned::new(connection, stream))))
+    }
+
+    /// Returns the underlying socket.
+    pub(crate) fn socket(&self) -> &TcpStream {
+        match self {
+            Self::Plain(stream) => stream,
+            Self::Client(stream) => stream.get_ref(),
+            Self::Server(stream) => stream.get_ref(),
+        }
+    }
+}
+
+impl Read for Stream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Self::Plain(stream) => stream.read(buf),
+            Self::Client(stream) => stream.read(buf),
+            Self::Server(stream) => stream.read(buf),
+        }
+    }
+}
+
+impl Write for Stream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Self::Plain(stream) => stream.write(buf),
+            Self::Client(stream) => stream.write(buf),
+            Self::Server(stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Self::Plain(stream) => stream.flush(),
+            Self::Client(stream) => stream.flush(),
+            Self::Server(stream) => stream.flush(),
+        }
+    }
+}
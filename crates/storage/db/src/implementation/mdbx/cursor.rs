@@ -5,6 +5,8 @@ use crate::{
     metrics::{DatabaseEnvMetrics, Operation},
     DatabaseError,
 };
+#[cfg(feature = "write-audit")]
+use reth_db_api::write_audit::{self, WriteOperation};
 use reth_db_api::{
     common::{PairResult, ValueOnlyResult},
     cursor::{
@@ -246,6 +248,16 @@ impl<K: TransactionKind, T: DupSort> DbDupCursorRO<T> for Cursor<K, T> {
     }
 }
 
+#[cfg(feature = "write-audit")]
+impl<T: Table> Cursor<RW, T> {
+    /// Records a write at the current position of the cursor in the write audit log.
+    fn audit_current(&mut self, operation: WriteOperation) {
+        if let Ok(Some((key, _))) = self.inner.get_current::<Cow<'_, [u8]>, Cow<'_, [u8]>>() {
+            write_audit::record(T::NAME, operation, &key);
+        }
+    }
+}
+
 impl<T: Table> DbCursorRW<T> for Cursor<RW, T> {
     /// Database operation that will update an existing row if a specified value already
     /// exists in a table, and insert a new row if the specified value doesn't already exist
@@ -256,6 +268,8 @@ impl<T: Table> DbCursorRW<T> for Cursor<RW, T> {
     /// found, before calling `upsert`.
     fn upsert(&mut self, key: T::Key, value: &T::Value) -> Result<(), DatabaseError> {
         let key = key.encode();
+        #[cfg(feature = "write-audit")]
+        write_audit::record(T::NAME, WriteOperation::CursorUpsert, key.as_ref());
         let value = compress_to_buf_or_ref!(self, value);
         self.execute_with_operation_metric(
             Operation::CursorUpsert,
@@ -278,6 +292,8 @@ impl<T: Table> DbCursorRW<T> for Cursor<RW, T> {
 
     fn insert(&mut self, key: T::Key, value: &T::Value) -> Result<(), DatabaseError> {
         let key = key.encode();
+        #[cfg(feature = "write-audit")]
+        write_audit::record(T::NAME, WriteOperation::CursorInsert, key.as_ref());
         let value = compress_to_buf_or_ref!(self, value);
         self.execute_with_operation_metric(
             Operation::CursorInsert,
@@ -302,6 +318,8 @@ impl<T: Table> DbCursorRW<T> for Cursor<RW, T> {
     /// will fail if the inserted key is less than the last table key
     fn append(&mut self, key: T::Key, value: &T::Value) -> Result<(), DatabaseError> {
         let key = key.encode();
+        #[cfg(feature = "write-audit")]
+        write_audit::record(T::NAME, WriteOperation::CursorAppend, key.as_ref());
         let value = compress_to_buf_or_ref!(self, value);
         self.execute_with_operation_metric(
             Operation::CursorAppend,
@@ -323,6 +341,8 @@ impl<T: Table> DbCursorRW<T> for Cursor<RW, T> {
     }
 
     fn delete_current(&mut self) -> Result<(), DatabaseError> {
+        #[cfg(feature = "write-audit")]
+        self.audit_current(WriteOperation::CursorDeleteCurrent);
         self.execute_with_operation_metric(Operation::CursorDeleteCurrent, None, |this| {
             this.inner.del(WriteFlags::CURRENT).map_err(|e| DatabaseError::Delete(e.into()))
         })
@@ -331,6 +351,8 @@ impl<T: Table> DbCursorRW<T> for Cursor<RW, T> {
 
 impl<T: DupSort> DbDupCursorRW<T> for Cursor<RW, T> {
     fn delete_current_duplicates(&mut self) -> Result<(), DatabaseError> {
+        #[cfg(feature = "write-audit")]
+        self.audit_current(WriteOperation::CursorDeleteCurrentDuplicates);
         self.execute_with_operation_metric(Operation::CursorDeleteCurrentDuplicates, None, |this| {
             this.inner.del(WriteFlags::NO_DUP_DATA).map_err(|e| DatabaseError::Delete(e.into()))
         })
@@ -338,6 +360,8 @@ impl<T: DupSort> DbDupCursorRW<T> for Cursor<RW, T> {
 
     fn append_dup(&mut self, key: T::Key, value: T::Value) -> Result<(), DatabaseError> {
         let key = key.encode();
+        #[cfg(feature = "write-audit")]
+        write_audit::record(T::NAME, WriteOperation::CursorAppendDup, key.as_ref());
         let value = compress_to_buf_or_ref!(self, value);
         self.execute_with_operation_metric(
             Operation::CursorAppendDup,
@@ -9,6 +9,7 @@ use crate::{
 };
 use eyre::Context;
 use metrics::{gauge, Label};
+use parking_lot::Mutex;
 use reth_db_api::{
     cursor::{DbCursorRO, DbCursorRW},
     database::Database,
@@ -18,21 +19,24 @@ use reth_db_api::{
 };
 use reth_libmdbx::{
     ffi, DatabaseFlags, Environment, EnvironmentFlags, Geometry, HandleSlowReadersReturnCode,
-    MaxReadTransactionDuration, Mode, PageSize, SyncMode, RO, RW,
+    MaxReadTransactionDuration, Mode, PageSize, ReadTransactionTimeoutPolicy, SyncMode, RO, RW,
 };
 use reth_storage_errors::db::LogLevel;
-use reth_tracing::tracing::error;
+use reth_tracing::tracing::{error, warn};
 use std::{
+    borrow::Cow,
     collections::HashMap,
     ops::{Deref, Range},
     path::Path,
     sync::Arc,
-    time::{SystemTime, UNIX_EPOCH},
+    time::{Instant, SystemTime, UNIX_EPOCH},
 };
 use tx::Tx;
+use utilization::{DatabaseGrowthTracker, DatabaseUtilization, TablePayload, TableUtilization};
 
 pub mod cursor;
 pub mod tx;
+pub mod utilization;
 
 mod utils;
 
@@ -79,6 +83,9 @@ pub struct DatabaseArguments {
     log_level: Option<LogLevel>,
     /// Maximum duration of a read transaction. If [None], the default value is used.
     max_read_transaction_duration: Option<MaxReadTransactionDuration>,
+    /// Policy consulted before timing out a long-lived read transaction. If [None], read
+    /// transactions are always timed out after the maximum duration.
+    read_transaction_timeout_policy: Option<Arc<dyn ReadTransactionTimeoutPolicy>>,
     /// Open environment in exclusive/monopolistic mode. If [None], the default value is used.
     ///
     /// This can be used as a replacement for `MDB_NOLOCK`, which don't supported by MDBX. In this
@@ -140,6 +147,7 @@ impl DatabaseArguments {
             },
             log_level: None,
             max_read_transaction_duration: None,
+            read_transaction_timeout_policy: None,
             exclusive: None,
             max_readers: None,
             sync_mode: SyncMode::Durable,
@@ -203,6 +211,16 @@ impl DatabaseArguments {
         self
     }
 
+    /// Set the policy consulted before timing out a read transaction that has been open for
+    /// longer than the maximum duration.
+    pub fn with_read_transaction_timeout_policy(
+        mut self,
+        policy: Option<Arc<dyn ReadTransactionTimeoutPolicy>>,
+    ) -> Self {
+        self.read_transaction_timeout_policy = policy;
+        self
+    }
+
     /// Set the mdbx exclusive flag.
     pub const fn with_exclusive(mut self, exclusive: Option<bool>) -> Self {
         self.exclusive = exclusive;
@@ -236,6 +254,8 @@ pub struct DatabaseEnv {
     metrics: Option<Arc<DatabaseEnvMetrics>>,
     /// Write lock for when dealing with a read-write environment.
     _lock_file: Option<StorageLock>,
+    /// Tracks utilization samples taken when reporting metrics to forecast growth.
+    growth_tracker: Mutex<DatabaseGrowthTracker>,
 }
 
 impl Database for DatabaseEnv {
@@ -271,64 +291,88 @@ impl DatabaseMetrics for DatabaseEnv {
     fn gauge_metrics(&self) -> Vec<(&'static str, f64, Vec<Label>)> {
         let mut metrics = Vec::new();
 
-        let _ = self
+        // Tables that can't be read are skipped, so that they don't drop the metrics of the others.
+        let tables = self
             .view(|tx| {
-                for table in Tables::ALL.iter().map(Tables::name) {
-                    let table_db =
-                        tx.inner().open_db(Some(table)).wrap_err("Could not open db.")?;
-
-                    let stats = tx
-                        .inner()
-                        .db_stat(table_db.dbi())
-                        .wrap_err(format!("Could not find table: {table}"))?;
-
-                    let page_size = stats.page_size() as usize;
-                    let leaf_pages = stats.leaf_pages();
-                    let branch_pages = stats.branch_pages();
-                    let overflow_pages = stats.overflow_pages();
-                    let num_pages = leaf_pages + branch_pages + overflow_pages;
-                    let table_size = page_size * num_pages;
-                    let entries = stats.entries();
-
-                    metrics.push((
-                        "db.table_size",
-                        table_size as f64,
-                        vec![Label::new("table", table)],
-                    ));
-                    metrics.push((
-                        "db.table_pages",
-                        leaf_pages as f64,
-                        vec![Label::new("table", table), Label::new("type", "leaf")],
-                    ));
-                    metrics.push((
-                        "db.table_pages",
-                        branch_pages as f64,
-                        vec![Label::new("table", table), Label::new("type", "branch")],
-                    ));
-                    metrics.push((
-                        "db.table_pages",
-                        overflow_pages as f64,
-                        vec![Label::new("table", table), Label::new("type", "overflow")],
-                    ));
-                    metrics.push((
-                        "db.table_entries",
-                        entries as f64,
-                        vec![Label::new("table", table)],
-                    ));
-                }
-
-                Ok::<(), eyre::Report>(())
+                Tables::ALL
+                    .iter()
+                    .filter_map(|table| {
+                        table_utilization(tx, table.name())
+                            .map_err(|error| error!(%error, "Failed to read db table stats"))
+                            .ok()
+                    })
+                    .collect::<Vec<_>>()
             })
-            .map_err(|error| error!(%error, "Failed to read db table stats"));
+            .map_err(|error| error!(%error, "Failed to read db table stats"))
+            .unwrap_or_default();
+
+        for table in &tables {
+            let name = table.table;
+            metrics.push(("db.table_size", table.size() as f64, vec![Label::new("table", name)]));
+            metrics.push((
+                "db.table_pages",
+                table.leaf_pages as f64,
+                vec![Label::new("table", name), Label::new("type", "leaf")],
+            ));
+            metrics.push((
+                "db.table_pages",
+                table.branch_pages as f64,
+                vec![Label::new("table", name), Label::new("type", "branch")],
+            ));
+            metrics.push((
+                "db.table_pages",
+                table.overflow_pages as f64,
+                vec![Label::new("table", name), Label::new("type", "overflow")],
+            ));
+            metrics.push((
+                "db.table_entries",
+                table.entries as f64,
+                vec![Label::new("table", name)],
+            ));
+            metrics.push((
+                "db.table_entries_per_leaf_page",
+                table.entries_per_leaf_page(),
+                vec![Label::new("table", name)],
+            ));
+            metrics.push((
+                "db.table_overflow_ratio",
+                table.overflow_ratio(),
+                vec![Label::new("table", name)],
+            ));
+        }
 
         if let Ok(freelist) =
             self.freelist().map_err(|error| error!(%error, "Failed to read db.freelist"))
         {
             metrics.push(("db.freelist", freelist as f64, vec![]));
-        }
 
-        if let Ok(stat) = self.stat().map_err(|error| error!(%error, "Failed to read db.stat")) {
-            metrics.push(("db.page_size", stat.page_size() as f64, vec![]));
+            if let Ok(utilization) = self
+                .environment_utilization(tables, freelist)
+                .map_err(|error| error!(%error, "Failed to read db utilization"))
+            {
+                metrics.push(("db.freelist_ratio", utilization.freelist_ratio(), vec![]));
+                metrics.push(("db.page_size", utilization.page_size as f64, vec![]));
+                metrics.push(("db.file_size", utilization.file_size as f64, vec![]));
+                metrics.push(("db.used_size", utilization.used_size() as f64, vec![]));
+
+                if utilization.is_freelist_pathological() {
+                    warn!(
+                        target: "storage::db::mdbx",
+                        freelist_size = utilization.freelist_size(),
+                        freelist_ratio = utilization.freelist_ratio(),
+                        "Database freelist is unusually large, consider compacting the database"
+                    );
+                }
+
+                if let Some(growth) =
+                    self.growth_tracker.lock().record(&utilization, Instant::now())
+                {
+                    metrics.push(("db.growth_rate", growth.bytes_per_second, vec![]));
+                    if let Some(time_to_full) = growth.time_to_full {
+                        metrics.push(("db.time_to_full", time_to_full.as_secs_f64(), vec![]));
+                    }
+                }
+            }
         }
 
         metrics.push((
@@ -336,12 +380,87 @@ impl DatabaseMetrics for DatabaseEnv {
             self.timed_out_not_aborted_transactions() as f64,
             vec![],
         ));
+        metrics.push(("db.timed_out_transactions", self.timed_out_transactions() as f64, vec![]));
+        metrics.push((
+            "db.active_read_transactions",
+            self.active_read_transactions() as f64,
+            vec![],
+        ));
+        metrics.push((
+            "db.oldest_read_transaction_age",
+            self.oldest_read_transaction_duration().unwrap_or_default().as_secs_f64(),
+            vec![],
+        ));
 
         metrics
     }
 }
 
 impl DatabaseEnv {
+    /// Returns a snapshot of the page utilization of all tables and the freelist.
+    pub fn utilization(&self) -> eyre::Result<DatabaseUtilization> {
+        let tables = self.view(|tx| {
+            Tables::ALL
+                .iter()
+                .map(|table| table_utilization(tx, table.name()))
+                .collect::<eyre::Result<Vec<_>>>()
+        })??;
+
+        self.environment_utilization(tables, self.freelist()?)
+    }
+
+    /// Returns the utilization of the environment with the given table utilizations and number of
+    /// freelist pages.
+    fn environment_utilization(
+        &self,
+        tables: Vec<TableUtilization>,
+        freelist_pages: usize,
+    ) -> eyre::Result<DatabaseUtilization> {
+        let stat = self.stat()?;
+        let info = self.info()?;
+
+        Ok(DatabaseUtilization {
+            page_size: stat.page_size() as usize,
+            tables,
+            freelist_pages,
+            last_page: info.last_pgno(),
+            file_size: info.geometry().current(),
+            max_size: info.geometry().max(),
+        })
+    }
+
+    /// Walks all entries of the table to measure the payload stored on its pages.
+    ///
+    /// This reads the whole table and can take a long time for large tables.
+    pub fn table_payload(&self, table: &str) -> eyre::Result<TablePayload> {
+        self.view(|tx| {
+            // We may be walking a large table
+            tx.disable_long_read_transaction_safety();
+
+            let table_db = tx.inner().open_db(Some(table)).wrap_err("Could not open db.")?;
+            let page_size = tx.inner().db_stat(table_db.dbi())?.page_size() as usize;
+            let dupsort = tx.inner().db_flags(table_db.dbi())?.contains(DatabaseFlags::DUP_SORT);
+            let mut cursor = tx.inner().cursor_with_dbi(table_db.dbi())?;
+
+            let mut payload = TablePayload::default();
+            let mut last_key = Vec::new();
+            let mut entry = cursor.first()?;
+            while let Some((key, value)) = entry {
+                let (key, value): (Cow<'_, [u8]>, Cow<'_, [u8]>) = (key, value);
+                // duplicate values of a key share a single copy of the key
+                let key_len = if dupsort && *key == *last_key { 0 } else { key.len() };
+                payload.add_entry(key_len, value.len(), page_size);
+                if dupsort {
+                    last_key.clear();
+                    last_key.extend_from_slice(&key);
+                }
+                entry = cursor.next()?;
+            }
+
+            Ok(payload)
+        })?
+    }
+
     /// Opens the database at the specified path with the given `EnvKind`.
     ///
     /// It does not create the tables, for that call [`DatabaseEnv::create_tables`].
@@ -488,11 +607,16 @@ impl DatabaseEnv {
             inner_env.set_max_read_transaction_duration(max_read_transaction_duration);
         }
 
+        if let Some(policy) = args.read_transaction_timeout_policy {
+            inner_env.set_read_transaction_timeout_policy(policy);
+        }
+
         let env = Self {
             inner: inner_env.open(path).map_err(|e| DatabaseError::Open(e.into()))?,
             dbis: Arc::default(),
             metrics: None,
             _lock_file,
+            growth_tracker: Mutex::default(),
         };
 
         Ok(env)
@@ -590,6 +714,27 @@ impl DatabaseEnv {
         }
     }
 
+    /// Returns the names of all tables in the database, sorted.
+    ///
+    /// Unlike [`Tables::ALL`], this includes the custom tables declared by downstream crates with
+    /// [`custom_tables!`](reth_db_api::custom_tables) and created with [`Self::create_tables_for`].
+    pub fn table_names(&self) -> Result<Vec<String>, DatabaseError> {
+        let tx = self.inner.begin_ro_txn().map_err(|e| DatabaseError::InitTx(e.into()))?;
+        // The unnamed database stores the names of all named databases as keys.
+        let main_db = tx.open_db(None).map_err(|e| DatabaseError::Open(e.into()))?;
+        let mut cursor =
+            tx.cursor_with_dbi(main_db.dbi()).map_err(|e| DatabaseError::InitCursor(e.into()))?;
+
+        cursor
+            .iter_start::<Vec<u8>, ()>()
+            .map(|entry| {
+                entry
+                    .map(|(name, _)| String::from_utf8_lossy(&name).into_owned())
+                    .map_err(|e| DatabaseError::Read(e.into()))
+            })
+            .collect()
+    }
+
     /// Records version that accesses the database with write privileges.
     pub fn record_client_version(&self, version: ClientVersion) -> Result<(), DatabaseError> {
         if version.is_empty() {
@@ -620,6 +765,23 @@ impl Deref for DatabaseEnv {
     }
 }
 
+/// Returns the page utilization of the table.
+fn table_utilization(tx: &Tx<RO>, table: &'static str) -> eyre::Result<TableUtilization> {
+    let table_db = tx.inner().open_db(Some(table)).wrap_err("Could not open db.")?;
+    let stats =
+        tx.inner().db_stat(table_db.dbi()).wrap_err(format!("Could not find table: {table}"))?;
+
+    Ok(TableUtilization {
+        table,
+        entries: stats.entries(),
+        depth: stats.depth(),
+        branch_pages: stats.branch_pages(),
+        leaf_pages: stats.leaf_pages(),
+        overflow_pages: stats.overflow_pages(),
+        page_size: stats.page_size() as usize,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -716,6 +878,32 @@ mod tests {
         assert!(!result.unwrap(), "drop_orphan_table should return false for non-existent table");
     }
 
+    #[test]
+    fn db_custom_tables() {
+        reth_db_api::custom_tables! {
+            enum TestTables in "test" {
+                table Counters {
+                    type Key = Address;
+                    type Value = u64;
+                }
+            }
+        }
+
+        let mut env = create_test_db(DatabaseEnvKind::RW);
+        env.create_tables_for::<TestTables>().expect(ERROR_TABLE_CREATION);
+
+        let table_names = env.table_names().unwrap();
+        assert_eq!(table_names.len(), Tables::COUNT + 1);
+        assert!(table_names.iter().any(|name| name == "test.Counters"));
+
+        let tx = env.tx_mut().expect(ERROR_INIT_TX);
+        tx.put::<Counters>(Address::ZERO, 1).expect(ERROR_PUT);
+        tx.commit().expect(ERROR_COMMIT);
+
+        let tx = env.tx().expect(ERROR_INIT_TX);
+        assert_eq!(tx.get::<Counters>(Address::ZERO).expect(ERROR_GET), Some(1));
+    }
+
     #[test]
     fn db_manual_put_get() {
         let env = create_test_db(DatabaseEnvKind::RW);
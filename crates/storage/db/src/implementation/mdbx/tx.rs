@@ -5,6 +5,8 @@ use crate::{
     metrics::{DatabaseEnvMetrics, Operation, TransactionMode, TransactionOutcome},
     DatabaseError,
 };
+#[cfg(feature = "write-audit")]
+use reth_db_api::write_audit::{self, WriteOperation};
 use reth_db_api::{
     table::{Compress, DupSort, Encode, IntoVec, Table, TableImporter},
     transaction::{DbTx, DbTxMut},
@@ -362,6 +364,14 @@ enum PutKind {
 }
 
 impl PutKind {
+    #[cfg(feature = "write-audit")]
+    const fn audit_operation(self) -> WriteOperation {
+        match self {
+            Self::Upsert => WriteOperation::PutUpsert,
+            Self::Append => WriteOperation::PutAppend,
+        }
+    }
+
     const fn into_operation_and_flags(self) -> (Operation, DatabaseWriteOperation, WriteFlags) {
         match self {
             Self::Upsert => {
@@ -385,6 +395,8 @@ impl Tx<RW> {
     ) -> Result<(), DatabaseError> {
         let key = key.encode();
         let value = value.compress();
+        #[cfg(feature = "write-audit")]
+        write_audit::record(T::NAME, kind.audit_operation(), key.as_ref());
         let (operation, write_operation, flags) = kind.into_operation_and_flags();
         self.execute_with_operation_metric::<T, _>(operation, Some(value.as_ref().len()), |tx| {
             tx.put(self.get_dbi::<T>()?, key.as_ref(), value, flags).map_err(|e| {
@@ -424,13 +436,19 @@ impl DbTxMut for Tx<RW> {
             data = Some(value.as_ref());
         };
 
+        let key = key.encode();
+        #[cfg(feature = "write-audit")]
+        write_audit::record(T::NAME, WriteOperation::Delete, key.as_ref());
+
         self.execute_with_operation_metric::<T, _>(Operation::Delete, None, |tx| {
-            tx.del(self.get_dbi::<T>()?, key.encode(), data)
-                .map_err(|e| DatabaseError::Delete(e.into()))
+            tx.del(self.get_dbi::<T>()?, key, data).map_err(|e| DatabaseError::Delete(e.into()))
         })
     }
 
     fn clear<T: Table>(&self) -> Result<(), DatabaseError> {
+        #[cfg(feature = "write-audit")]
+        write_audit::record(T::NAME, WriteOperation::Clear, &[]);
+
         self.inner.clear_db(self.get_dbi::<T>()?).map_err(|e| DatabaseError::Delete(e.into()))?;
 
         Ok(())
@@ -496,8 +514,10 @@ mod tests {
         sleep(MAX_DURATION + Duration::from_millis(100));
 
         // Transaction has timed out.
+        let err = tx.get::<tables::Transactions>(0).unwrap_err();
+        assert!(err.is_read_transaction_timeout());
         assert!(matches!(
-            tx.get::<tables::Transactions>(0).unwrap_err(),
+            err,
             DatabaseError::Open(err) if err == reth_libmdbx::Error::ReadTransactionTimeout.into()));
         // Backtrace is recorded.
         assert!(tx.metrics_handler.unwrap().backtrace_recorded.load(Ordering::Relaxed));
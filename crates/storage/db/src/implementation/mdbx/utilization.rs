@@ -0,0 +1,334 @@
+//! Page utilization and growth tracking of the MDBX environment.
+
+use std::time::{Duration, Instant};
+
+/// The share of the database file occupied by the freelist above which the freelist is considered
+/// pathologically large.
+pub const FREELIST_WARN_RATIO: f64 = 0.25;
+
+/// The minimum freelist size in bytes before [`FREELIST_WARN_RATIO`] is taken into account.
+///
+/// Small databases naturally have a high freelist ratio, which is not worth warning about.
+pub const FREELIST_WARN_MIN_SIZE: usize = 16 * 1024 * 1024 * 1024;
+
+/// The minimum table size in bytes before compaction is recommended for it.
+pub const COMPACTION_MIN_SIZE: usize = 1024 * 1024 * 1024;
+
+/// The fill factor of leaf or overflow pages below which space is considered wasted.
+pub const LOW_FILL_FACTOR: f64 = 0.5;
+
+/// The size of the header of every page in bytes.
+const PAGE_HEADER_SIZE: usize = 20;
+
+/// The overhead of every entry on a leaf page in bytes, the node header and its offset in the
+/// page index.
+const LEAF_NODE_OVERHEAD: usize = 10;
+
+/// The size of the page number stored on the leaf page in place of a value that was moved to
+/// overflow pages.
+const OVERFLOW_REFERENCE_SIZE: usize = 8;
+
+/// Page utilization of a single table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableUtilization {
+    /// The name of the table.
+    pub table: &'static str,
+    /// The number of entries in the table.
+    pub entries: usize,
+    /// The depth of the B-tree of the table.
+    pub depth: u32,
+    /// The number of branch pages.
+    pub branch_pages: usize,
+    /// The number of leaf pages.
+    pub leaf_pages: usize,
+    /// The number of overflow pages.
+    pub overflow_pages: usize,
+    /// The page size in bytes.
+    pub page_size: usize,
+}
+
+impl TableUtilization {
+    /// Returns the total number of pages used by the table.
+    pub const fn pages(&self) -> usize {
+        self.branch_pages + self.leaf_pages + self.overflow_pages
+    }
+
+    /// Returns the total size of the table in bytes.
+    pub const fn size(&self) -> usize {
+        self.pages() * self.page_size
+    }
+
+    /// Returns the average number of entries stored per leaf page.
+    ///
+    /// Low values for tables with small values indicate poorly filled pages, e.g. after many
+    /// random deletions.
+    pub fn entries_per_leaf_page(&self) -> f64 {
+        if self.leaf_pages == 0 {
+            return 0.0
+        }
+        self.entries as f64 / self.leaf_pages as f64
+    }
+
+    /// Returns the share of pages of the table that are overflow pages.
+    pub fn overflow_ratio(&self) -> f64 {
+        let pages = self.pages();
+        if pages == 0 {
+            return 0.0
+        }
+        self.overflow_pages as f64 / pages as f64
+    }
+
+    /// Returns the estimated share of the leaf pages that is occupied by entries.
+    pub fn leaf_fill_factor(&self, payload: &TablePayload) -> f64 {
+        let capacity = self.leaf_pages * self.page_size.saturating_sub(PAGE_HEADER_SIZE);
+        if capacity == 0 {
+            return 0.0
+        }
+        (payload.leaf_bytes as f64 / capacity as f64).min(1.0)
+    }
+
+    /// Returns the share of the overflow pages that is occupied by values.
+    ///
+    /// Every value stored in overflow pages occupies whole pages, so values slightly larger than
+    /// a page waste most of their last page.
+    pub fn overflow_fill_factor(&self, payload: &TablePayload) -> f64 {
+        let capacity = self.overflow_pages * self.page_size;
+        if capacity == 0 {
+            return 0.0
+        }
+        (payload.overflow_bytes as f64 / capacity as f64).min(1.0)
+    }
+
+    /// Returns a recommendation for the table if a significant share of its pages is wasted.
+    pub fn recommendation(&self, payload: &TablePayload) -> Option<&'static str> {
+        if self.size() < COMPACTION_MIN_SIZE {
+            return None
+        }
+
+        if self.leaf_pages > 0 && self.leaf_fill_factor(payload) < LOW_FILL_FACTOR {
+            Some("leaf pages are less than half full, compact the database to reclaim space")
+        } else if self.overflow_ratio() > LOW_FILL_FACTOR &&
+            self.overflow_fill_factor(payload) < LOW_FILL_FACTOR
+        {
+            Some("large values waste most of their overflow pages, a smaller page size would help")
+        } else {
+            None
+        }
+    }
+}
+
+/// The payload stored in a table, measured by walking all of its entries.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TablePayload {
+    /// The bytes occupied by entries on leaf pages, including the per-entry overhead.
+    pub leaf_bytes: usize,
+    /// The number of values that were too large for a leaf page and moved to overflow pages.
+    pub overflow_values: usize,
+    /// The bytes of all values stored in overflow pages.
+    pub overflow_bytes: usize,
+}
+
+impl TablePayload {
+    /// Accounts for an entry of a table with the given page size.
+    ///
+    /// For duplicate values of the same key in `DUP_SORT` tables, the key is only stored once and
+    /// `key_len` should be zero.
+    pub const fn add_entry(&mut self, key_len: usize, value_len: usize, page_size: usize) {
+        // a node may take up at most half of a leaf page, larger values are moved to overflow
+        // pages
+        let max_node_size = page_size.saturating_sub(PAGE_HEADER_SIZE) / 2;
+        let node_size = LEAF_NODE_OVERHEAD + key_len + value_len;
+        if node_size > max_node_size {
+            self.leaf_bytes += LEAF_NODE_OVERHEAD + key_len + OVERFLOW_REFERENCE_SIZE;
+            self.overflow_values += 1;
+            self.overflow_bytes += value_len;
+        } else {
+            self.leaf_bytes += node_size;
+        }
+    }
+}
+
+/// A snapshot of the page utilization of the whole environment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DatabaseUtilization {
+    /// The page size in bytes.
+    pub page_size: usize,
+    /// Utilization of every table.
+    pub tables: Vec<TableUtilization>,
+    /// The number of pages in the freelist, which can be reused without growing the file.
+    pub freelist_pages: usize,
+    /// The number of the last page used in the file.
+    pub last_page: usize,
+    /// The current size of the database file in bytes.
+    pub file_size: u64,
+    /// The maximum size the database file is allowed to grow to in bytes.
+    pub max_size: u64,
+}
+
+impl DatabaseUtilization {
+    /// Returns the total size of all tables in bytes.
+    pub fn tables_size(&self) -> usize {
+        self.tables.iter().map(TableUtilization::size).sum()
+    }
+
+    /// Returns the size of the freelist in bytes.
+    pub const fn freelist_size(&self) -> usize {
+        self.freelist_pages * self.page_size
+    }
+
+    /// Returns the number of bytes up to the last used page.
+    pub const fn used_size(&self) -> usize {
+        (self.last_page + 1) * self.page_size
+    }
+
+    /// Returns the share of the used part of the file occupied by the freelist.
+    pub fn freelist_ratio(&self) -> f64 {
+        let used = self.used_size();
+        if used == 0 {
+            return 0.0
+        }
+        self.freelist_size() as f64 / used as f64
+    }
+
+    /// Returns `true` if the freelist is both large in absolute terms and occupies more than
+    /// [`FREELIST_WARN_RATIO`] of the used file.
+    pub fn is_freelist_pathological(&self) -> bool {
+        self.freelist_size() >= FREELIST_WARN_MIN_SIZE &&
+            self.freelist_ratio() > FREELIST_WARN_RATIO
+    }
+}
+
+/// Database growth derived from two consecutive [`DatabaseUtilization`] samples.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DatabaseGrowth {
+    /// The growth of the data stored in the database, excluding reusable freelist pages, in
+    /// bytes per second. Negative if the database shrunk.
+    pub bytes_per_second: f64,
+    /// The estimated time until the database reaches its maximum size, if it is growing.
+    pub time_to_full: Option<Duration>,
+}
+
+/// Tracks [`DatabaseUtilization`] samples over time to forecast database growth.
+#[derive(Debug, Default)]
+pub struct DatabaseGrowthTracker {
+    /// The last sample and the time it was taken.
+    last: Option<(Instant, usize)>,
+}
+
+impl DatabaseGrowthTracker {
+    /// Records a new sample taken at `now` and returns the growth since the previous sample.
+    ///
+    /// Returns `None` for the first sample.
+    pub fn record(
+        &mut self,
+        utilization: &DatabaseUtilization,
+        now: Instant,
+    ) -> Option<DatabaseGrowth> {
+        // Freelist pages are reused before the file grows, so they don't count as used.
+        let data_size = utilization.used_size().saturating_sub(utilization.freelist_size());
+        let (last_time, last_size) = self.last.replace((now, data_size))?;
+
+        let elapsed = now.checked_duration_since(last_time)?.as_secs_f64();
+        if elapsed == 0.0 {
+            return None
+        }
+
+        let bytes_per_second = (data_size as f64 - last_size as f64) / elapsed;
+        let time_to_full = (bytes_per_second > 0.0).then(|| {
+            let remaining = (utilization.max_size as f64 - data_size as f64).max(0.0);
+            Duration::from_secs_f64(remaining / bytes_per_second)
+        });
+
+        Some(DatabaseGrowth { bytes_per_second, time_to_full })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE_SIZE: usize = 4096;
+
+    fn utilization(last_page: usize, freelist_pages: usize) -> DatabaseUtilization {
+        DatabaseUtilization {
+            page_size: PAGE_SIZE,
+            tables: Vec::new(),
+            freelist_pages,
+            last_page,
+            file_size: ((last_page + 1) * PAGE_SIZE) as u64,
+            max_size: (10_000 * PAGE_SIZE) as u64,
+        }
+    }
+
+    #[test]
+    fn freelist_ratio() {
+        let utilization = utilization(999, 250);
+        assert_eq!(utilization.used_size(), 1000 * PAGE_SIZE);
+        assert_eq!(utilization.freelist_ratio(), 0.25);
+        // too small to be considered pathological
+        assert!(!utilization.is_freelist_pathological());
+
+        let utilization = DatabaseUtilization {
+            freelist_pages: FREELIST_WARN_MIN_SIZE / PAGE_SIZE,
+            last_page: 2 * FREELIST_WARN_MIN_SIZE / PAGE_SIZE,
+            ..utilization
+        };
+        assert!(utilization.is_freelist_pathological());
+    }
+
+    #[test]
+    fn page_fill() {
+        let table = TableUtilization {
+            table: "Test",
+            entries: 1000,
+            depth: 2,
+            branch_pages: 0,
+            leaf_pages: 200_000,
+            overflow_pages: 200_000,
+            page_size: PAGE_SIZE,
+        };
+
+        let mut payload = TablePayload::default();
+        for _ in 0..1000 {
+            // fits on a leaf page
+            payload.add_entry(32, 100, PAGE_SIZE);
+            // moved to an overflow page
+            payload.add_entry(32, PAGE_SIZE, PAGE_SIZE);
+        }
+        assert_eq!(payload.leaf_bytes, 1000 * (10 + 32 + 100) + 1000 * (10 + 32 + 8));
+        assert_eq!(payload.overflow_values, 1000);
+        assert_eq!(payload.overflow_bytes, 1000 * PAGE_SIZE);
+
+        assert_eq!(table.overflow_ratio(), 0.5);
+        assert!(table.leaf_fill_factor(&payload) < LOW_FILL_FACTOR);
+        assert_eq!(table.overflow_fill_factor(&payload), 0.005);
+        assert_eq!(
+            table.recommendation(&payload),
+            Some("leaf pages are less than half full, compact the database to reclaim space")
+        );
+
+        // small tables are never recommended for compaction
+        let table = TableUtilization { leaf_pages: 10, overflow_pages: 10, ..table };
+        assert_eq!(table.recommendation(&payload), None);
+    }
+
+    #[test]
+    fn growth_forecast() {
+        let mut tracker = DatabaseGrowthTracker::default();
+        let start = Instant::now();
+
+        assert_eq!(tracker.record(&utilization(999, 0), start), None);
+
+        // 1000 pages in 10 seconds, 8000 pages left until the max size
+        let growth =
+            tracker.record(&utilization(1999, 0), start + Duration::from_secs(10)).unwrap();
+        assert_eq!(growth.bytes_per_second, (100 * PAGE_SIZE) as f64);
+        assert_eq!(growth.time_to_full, Some(Duration::from_secs(80)));
+
+        // the file grew, but only by pages that are now in the freelist
+        let growth =
+            tracker.record(&utilization(2999, 1000), start + Duration::from_secs(20)).unwrap();
+        assert_eq!(growth.bytes_per_second, 0.0);
+        assert_eq!(growth.time_to_full, None);
+    }
+}
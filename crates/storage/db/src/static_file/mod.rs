@@ -4,7 +4,7 @@ use reth_nippy_jar::{NippyJar, NippyJarError};
 use reth_static_file_types::{
     SegmentHeader, SegmentRangeInclusive, StaticFileMap, StaticFileSegment,
 };
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 mod cursor;
 pub use cursor::StaticFileCursor;
@@ -22,18 +22,50 @@ type SortedStaticFiles = StaticFileMap<Vec<(SegmentRangeInclusive, SegmentHeader
 /// organized by [`StaticFileSegment`]. Each segment has a sorted list of block ranges and
 /// segment headers as presented in the file configuration.
 pub fn iter_static_files(path: &Path) -> Result<SortedStaticFiles, NippyJarError> {
+    iter_static_files_in_dirs(path, &StaticFileMap::default())
+}
+
+/// Same as [`iter_static_files`], but the static files of the segments in `segment_dirs` are
+/// read from their own directory instead of the `static_files` directory.
+///
+/// Static files of these segments that are located in the `static_files` directory are skipped,
+/// callers have to make sure there are none.
+pub fn iter_static_files_in_dirs(
+    path: &Path,
+    segment_dirs: &StaticFileMap<PathBuf>,
+) -> Result<SortedStaticFiles, NippyJarError> {
+    let mut static_files = SortedStaticFiles::default();
+    read_static_files(path, |segment| !segment_dirs.contains_key(segment), &mut static_files)?;
+    for (segment, dir) in segment_dirs.iter() {
+        read_static_files(dir, |other| other == segment, &mut static_files)?;
+    }
+
+    // Sort by block end range.
+    for range_list in static_files.values_mut() {
+        range_list.sort_by_key(|(block_range, _)| block_range.end());
+    }
+
+    Ok(static_files)
+}
+
+/// Adds the static files in `path` of the segments accepted by `filter` to `static_files`.
+fn read_static_files(
+    path: &Path,
+    filter: impl Fn(StaticFileSegment) -> bool,
+    static_files: &mut SortedStaticFiles,
+) -> Result<(), NippyJarError> {
     if !path.exists() {
         reth_fs_util::create_dir_all(path).map_err(|err| NippyJarError::Custom(err.to_string()))?;
     }
 
-    let mut static_files = SortedStaticFiles::default();
     let entries = reth_fs_util::read_dir(path)
         .map_err(|err| NippyJarError::Custom(err.to_string()))?
         .filter_map(Result::ok);
     for entry in entries {
         if entry.metadata().is_ok_and(|metadata| metadata.is_file()) &&
             let Some((segment, _)) =
-                StaticFileSegment::parse_filename(&entry.file_name().to_string_lossy())
+                StaticFileSegment::parse_filename(&entry.file_name().to_string_lossy()) &&
+            filter(segment)
         {
             let jar = NippyJar::<SegmentHeader>::load(&entry.path())?;
 
@@ -46,10 +78,5 @@ pub fn iter_static_files(path: &Path) -> Result<SortedStaticFiles, NippyJarError
         }
     }
 
-    // Sort by block end range.
-    for range_list in static_files.values_mut() {
-        range_list.sort_by_key(|(block_range, _)| block_range.end());
-    }
-
-    Ok(static_files)
+    Ok(())
 }
@@ -55,6 +55,28 @@ pub enum DatabaseError {
     Custom(#[from] Arc<dyn Error + Send + Sync>),
 }
 
+impl DatabaseError {
+    /// Returns `true` if the error was caused by using a read transaction that has been timed out
+    /// for being open for too long, or by opening a read transaction while they're denied because
+    /// of another one that has been open for too long.
+    ///
+    /// Such errors are transient, and the operation can be retried with a new transaction.
+    pub const fn is_read_transaction_timeout(&self) -> bool {
+        match self {
+            Self::Open(info) |
+            Self::CreateTable(info) |
+            Self::Read(info) |
+            Self::Delete(info) |
+            Self::Commit(info) |
+            Self::InitTx(info) |
+            Self::InitCursor(info) |
+            Self::Stats(info) => info.is_read_transaction_timeout(),
+            // Writes are only performed by read-write transactions, which never time out.
+            _ => false,
+        }
+    }
+}
+
 /// Common error struct to propagate implementation-specific error information.
 #[derive(Debug, Clone, PartialEq, Eq, derive_more::Display)]
 #[display("{message} ({code})")]
@@ -65,6 +87,24 @@ pub struct DatabaseErrorInfo {
     pub code: i32,
 }
 
+impl DatabaseErrorInfo {
+    /// Error code of a read transaction that has been timed out, matches the code of
+    /// `reth_libmdbx::Error::ReadTransactionTimeout`.
+    pub const READ_TRANSACTION_TIMEOUT_CODE: i32 = -96000;
+
+    /// Error code of a read of a transaction that is kept open past its timeout, matches the code
+    /// of `reth_libmdbx::Error::ReadTransactionDenied`.
+    pub const READ_TRANSACTION_DENIED_CODE: i32 = -96002;
+
+    /// Returns `true` if the error was caused by a timed out or denied read transaction.
+    pub const fn is_read_transaction_timeout(&self) -> bool {
+        matches!(
+            self.code,
+            Self::READ_TRANSACTION_TIMEOUT_CODE | Self::READ_TRANSACTION_DENIED_CODE
+        )
+    }
+}
+
 impl<E> From<E> for DatabaseErrorInfo
 where
     E: Display + Into<i32>,
@@ -125,6 +125,11 @@ pub enum ProviderError {
     #[cfg(feature = "std")]
     #[error("not able to find static file at {_0:?}")]
     MissingStaticFilePath(std::path::PathBuf),
+    /// Static files of a segment are located outside of the directory the segment is stored in,
+    /// so they would be ignored.
+    #[cfg(feature = "std")]
+    #[error("found {_0} static files at {_1:?}, but the segment is stored at {_2:?}")]
+    MisplacedStaticFiles(StaticFileSegment, std::path::PathBuf, std::path::PathBuf),
     /// Highest block is not found for static file block.
     #[error("highest block is not found for {_0} static file")]
     MissingHighestStaticFileBlock(StaticFileSegment),
@@ -184,6 +189,13 @@ impl ProviderError {
         Self::Other(AnyError::new(error))
     }
 
+    /// Returns `true` if the error was caused by a timed out database read transaction.
+    ///
+    /// See [`DatabaseError::is_read_transaction_timeout`].
+    pub const fn is_read_transaction_timeout(&self) -> bool {
+        matches!(self, Self::Database(err) if err.is_read_transaction_timeout())
+    }
+
     /// Returns the arbitrary error if it is [`ProviderError::Other`]
     pub fn as_other(&self) -> Option<&(dyn core::error::Error + Send + Sync + 'static)> {
         match self {
@@ -55,6 +55,8 @@ impl Environment {
             handle_slow_readers: None,
             #[cfg(feature = "read-tx-timeouts")]
             max_read_transaction_duration: None,
+            #[cfg(feature = "read-tx-timeouts")]
+            read_transaction_timeout_policy: None,
         }
     }
 
@@ -94,6 +96,28 @@ impl Environment {
         self.inner.txn_manager.timed_out_not_aborted_read_transactions().unwrap_or(0)
     }
 
+    /// Returns the total number of read transactions that were timed out.
+    #[cfg(feature = "read-tx-timeouts")]
+    pub fn timed_out_transactions(&self) -> usize {
+        self.inner.txn_manager.timed_out_read_transactions().unwrap_or(0)
+    }
+
+    /// Returns the number of currently open read transactions.
+    ///
+    /// Read transactions are only tracked if the maximum read transaction duration is set.
+    #[cfg(feature = "read-tx-timeouts")]
+    pub fn active_read_transactions(&self) -> usize {
+        self.inner.txn_manager.active_read_transactions().unwrap_or(0)
+    }
+
+    /// Returns for how long the oldest currently open read transaction has been open.
+    ///
+    /// Read transactions are only tracked if the maximum read transaction duration is set.
+    #[cfg(feature = "read-tx-timeouts")]
+    pub fn oldest_read_transaction_duration(&self) -> Option<std::time::Duration> {
+        self.inner.txn_manager.oldest_active_read_transaction()
+    }
+
     /// Create a read-only transaction for use with the environment.
     #[inline]
     pub fn begin_ro_txn(&self) -> Result<Transaction<RO>> {
@@ -362,6 +386,18 @@ impl GeometryInfo {
     pub const fn min(&self) -> u64 {
         self.0.lower
     }
+
+    /// Upper limit for the datafile size.
+    #[inline]
+    pub const fn max(&self) -> u64 {
+        self.0.upper
+    }
+
+    /// Current datafile size.
+    #[inline]
+    pub const fn current(&self) -> u64 {
+        self.0.current
+    }
 }
 
 /// Environment information.
@@ -602,6 +638,11 @@ pub struct EnvironmentBuilder {
     /// The maximum duration of a read transaction. If [None], but the `read-tx-timeout` feature is
     /// enabled, the default value of [`DEFAULT_MAX_READ_TRANSACTION_DURATION`] is used.
     max_read_transaction_duration: Option<read_transactions::MaxReadTransactionDuration>,
+    #[cfg(feature = "read-tx-timeouts")]
+    /// The policy consulted before timing out a read transaction. If [None], read transactions
+    /// are always timed out after the maximum duration.
+    read_transaction_timeout_policy:
+        Option<std::sync::Arc<dyn read_transactions::ReadTransactionTimeoutPolicy>>,
 }
 
 impl EnvironmentBuilder {
@@ -744,7 +785,11 @@ impl EnvironmentBuilder {
                     DEFAULT_MAX_READ_TRANSACTION_DURATION,
                 ))
             {
-                TxnManager::new_with_max_read_transaction_duration(env_ptr, duration)
+                TxnManager::new_with_max_read_transaction_duration(
+                    env_ptr,
+                    duration,
+                    self.read_transaction_timeout_policy.clone(),
+                )
             } else {
                 TxnManager::new(env_ptr)
             }
@@ -878,7 +923,7 @@ impl EnvironmentBuilder {
 #[cfg(feature = "read-tx-timeouts")]
 pub(crate) mod read_transactions {
     use crate::EnvironmentBuilder;
-    use std::time::Duration;
+    use std::{fmt::Debug, str::FromStr, sync::Arc, time::Duration};
 
     /// The maximum duration of a read transaction.
     #[derive(Debug, Clone, Copy)]
@@ -900,6 +945,52 @@ pub(crate) mod read_transactions {
         }
     }
 
+    /// Action to take for a read transaction that has been open for longer than the
+    /// [`MaxReadTransactionDuration`].
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ReadTransactionTimeoutAction {
+        /// Time out the transaction. Any further use of it fails with
+        /// [`Error::ReadTransactionTimeout`](crate::Error::ReadTransactionTimeout).
+        Abort,
+        /// Keep the transaction open. The policy is consulted again on the next check.
+        Keep,
+        /// Keep the transaction open, but deny its new reads with
+        /// [`Error::ReadTransactionDenied`](crate::Error::ReadTransactionDenied). Cursors that are
+        /// already open can still be used, so that iterations in progress can finish, and other
+        /// transactions are not affected. The policy is consulted again on the next check.
+        Deny,
+    }
+
+    /// Always takes the same action, regardless of how long the transaction has been open.
+    impl ReadTransactionTimeoutPolicy for ReadTransactionTimeoutAction {
+        fn on_timeout(&self, _open_duration: Duration) -> ReadTransactionTimeoutAction {
+            *self
+        }
+    }
+
+    impl FromStr for ReadTransactionTimeoutAction {
+        type Err = String;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            let val = s.trim().to_ascii_lowercase();
+            match val.as_str() {
+                "abort" => Ok(Self::Abort),
+                "keep" => Ok(Self::Keep),
+                "deny" => Ok(Self::Deny),
+                _ => Err(format!(
+                    "invalid value '{s}' for read transaction timeout action. valid values: abort, keep, deny"
+                )),
+            }
+        }
+    }
+
+    /// Policy consulted before timing out a long-lived read transaction.
+    pub trait ReadTransactionTimeoutPolicy: Debug + Send + Sync {
+        /// Returns the action to take for a read transaction that has been open for
+        /// `open_duration`, which is longer than the [`MaxReadTransactionDuration`].
+        fn on_timeout(&self, open_duration: Duration) -> ReadTransactionTimeoutAction;
+    }
+
     impl EnvironmentBuilder {
         /// Set the maximum time a read-only transaction can be open.
         pub const fn set_max_read_transaction_duration(
@@ -909,6 +1000,16 @@ pub(crate) mod read_transactions {
             self.max_read_transaction_duration = Some(max_read_transaction_duration);
             self
         }
+
+        /// Set the policy consulted before timing out a read-only transaction that has been open
+        /// for longer than the maximum duration.
+        pub fn set_read_transaction_timeout_policy(
+            &mut self,
+            policy: Arc<dyn ReadTransactionTimeoutPolicy>,
+        ) -> &mut Self {
+            self.read_transaction_timeout_policy = Some(policy);
+            self
+        }
     }
 }
 
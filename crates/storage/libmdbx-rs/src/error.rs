@@ -123,6 +123,9 @@ pub enum Error {
     /// Read transaction has been timed out.
     #[error("read transaction has been timed out")]
     ReadTransactionTimeout,
+    /// New reads of a read transaction that is kept open past its timeout are denied.
+    #[error("read transaction is open for too long, new reads are denied")]
+    ReadTransactionDenied,
     /// The transaction commit was aborted due to previous errors.
     ///
     /// This can happen in exceptionally rare cases and it signals the problem coming from inside
@@ -211,6 +214,7 @@ impl Error {
             Self::NestedTransactionsUnsupportedWithWriteMap => ffi::MDBX_EACCESS,
             Self::ReadTransactionTimeout => -96000, // Custom non-MDBX error code
             Self::BotchedTransaction => -96001,
+            Self::ReadTransactionDenied => -96002,
             Self::Permission => ffi::MDBX_EPERM,
             Self::Other(err_code) => *err_code,
         }
@@ -25,7 +25,9 @@ pub use crate::{
 };
 
 #[cfg(feature = "read-tx-timeouts")]
-pub use crate::environment::read_transactions::MaxReadTransactionDuration;
+pub use crate::environment::read_transactions::{
+    MaxReadTransactionDuration, ReadTransactionTimeoutAction, ReadTransactionTimeoutPolicy,
+};
 
 mod codec;
 mod cursor;
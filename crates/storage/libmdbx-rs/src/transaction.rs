@@ -158,6 +158,7 @@ where
             ffi::MDBX_val { iov_len: key.len(), iov_base: key.as_ptr() as *mut c_void };
         let mut data_val: ffi::MDBX_val = ffi::MDBX_val { iov_len: 0, iov_base: ptr::null_mut() };
 
+        self.inner.txn.ensure_not_denied()?;
         self.txn_execute(|txn| unsafe {
             match ffi::mdbx_get(txn, dbi, &key_val, &mut data_val) {
                 ffi::MDBX_SUCCESS => Key::decode_val::<K>(txn, data_val).map(Some),
@@ -254,11 +255,13 @@ where
 
     /// Open a new cursor on the given database.
     pub fn cursor(&self, dbi: ffi::MDBX_dbi) -> Result<Cursor<K>> {
+        self.inner.txn.ensure_not_denied()?;
         Cursor::new(self.clone(), dbi)
     }
 
     /// Open a new cursor on the given dbi.
     pub fn cursor_with_dbi(&self, dbi: ffi::MDBX_dbi) -> Result<Cursor<K>> {
+        self.inner.txn.ensure_not_denied()?;
         Cursor::new(self.clone(), dbi)
     }
 
@@ -542,6 +545,9 @@ pub(crate) struct TransactionPtr {
     txn: *mut ffi::MDBX_txn,
     #[cfg(feature = "read-tx-timeouts")]
     timed_out: Arc<AtomicBool>,
+    /// Whether new reads of the transaction are denied, because it's kept open past its deadline.
+    #[cfg(feature = "read-tx-timeouts")]
+    denied: Arc<AtomicBool>,
     lock: Arc<Mutex<()>>,
 }
 
@@ -551,6 +557,8 @@ impl TransactionPtr {
             txn,
             #[cfg(feature = "read-tx-timeouts")]
             timed_out: Arc::new(AtomicBool::new(false)),
+            #[cfg(feature = "read-tx-timeouts")]
+            denied: Arc::new(AtomicBool::new(false)),
             lock: Arc::new(Mutex::new(())),
         }
     }
@@ -573,6 +581,25 @@ impl TransactionPtr {
         self.timed_out.store(true, std::sync::atomic::Ordering::SeqCst);
     }
 
+    /// Sets whether new reads of the transaction are denied, returning the previous value.
+    ///
+    /// Denied transactions stay usable for their open cursors, but opening new cursors or reading
+    /// values directly fails with [`Error::ReadTransactionDenied`].
+    #[cfg(feature = "read-tx-timeouts")]
+    pub(crate) fn set_denied(&self, denied: bool) -> bool {
+        self.denied.swap(denied, std::sync::atomic::Ordering::SeqCst)
+    }
+
+    /// Returns [`Error::ReadTransactionDenied`] if new reads of the transaction are denied.
+    #[inline]
+    fn ensure_not_denied(&self) -> Result<()> {
+        #[cfg(feature = "read-tx-timeouts")]
+        if self.denied.load(std::sync::atomic::Ordering::SeqCst) {
+            return Err(Error::ReadTransactionDenied)
+        }
+        Ok(())
+    }
+
     /// Acquires the inner transaction lock to guarantee exclusive access to the transaction
     /// pointer.
     fn lock(&self) -> MutexGuard<'_, ()> {
@@ -108,13 +108,22 @@ impl TxnManager {
 #[cfg(feature = "read-tx-timeouts")]
 mod read_transactions {
     use crate::{
-        environment::EnvPtr, error::mdbx_result, transaction::TransactionPtr,
+        environment::{
+            read_transactions::{ReadTransactionTimeoutAction, ReadTransactionTimeoutPolicy},
+            EnvPtr,
+        },
+        error::mdbx_result,
+        transaction::TransactionPtr,
         txn_manager::TxnManager,
     };
     use dashmap::{DashMap, DashSet};
     use std::{
         backtrace::Backtrace,
-        sync::{mpsc::sync_channel, Arc},
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            mpsc::sync_channel,
+            Arc,
+        },
         time::{Duration, Instant},
     };
     use tracing::{error, trace, warn};
@@ -124,11 +133,14 @@ mod read_transactions {
     impl TxnManager {
         /// Returns a new instance for which the maximum duration that a read transaction can be
         /// open is set.
+        ///
+        /// If `policy` is set, it's consulted before timing out a read transaction.
         pub(crate) fn new_with_max_read_transaction_duration(
             env: EnvPtr,
             duration: Duration,
+            policy: Option<Arc<dyn ReadTransactionTimeoutPolicy>>,
         ) -> Self {
-            let read_transactions = Arc::new(ReadTransactions::new(duration));
+            let read_transactions = Arc::new(ReadTransactions::new(duration, policy));
             read_transactions.clone().start_monitor();
 
             let (tx, rx) = sync_channel(0);
@@ -164,6 +176,23 @@ mod read_transactions {
                 .as_ref()
                 .map(|read_transactions| read_transactions.timed_out_not_aborted())
         }
+
+        /// Returns the number of currently active read transactions.
+        pub(crate) fn active_read_transactions(&self) -> Option<usize> {
+            self.read_transactions.as_ref().map(|read_transactions| read_transactions.active.len())
+        }
+
+        /// Returns the duration of the longest currently active read transaction.
+        pub(crate) fn oldest_active_read_transaction(&self) -> Option<Duration> {
+            self.read_transactions.as_ref().and_then(|read_transactions| read_transactions.oldest())
+        }
+
+        /// Returns the total number of read transactions that were timed out.
+        pub(crate) fn timed_out_read_transactions(&self) -> Option<usize> {
+            self.read_transactions
+                .as_ref()
+                .map(|read_transactions| read_transactions.timed_out.load(Ordering::Relaxed))
+        }
     }
 
     #[derive(Debug, Default)]
@@ -182,11 +211,19 @@ mod read_transactions {
         /// List of timed out transactions that were not aborted by the user yet, hence have a
         /// dangling read transaction pointer.
         timed_out_not_aborted: DashSet<usize>,
+        /// Total number of timed out transactions.
+        timed_out: AtomicUsize,
+        /// Policy consulted before timing out a transaction. If [None], transactions are always
+        /// timed out.
+        policy: Option<Arc<dyn ReadTransactionTimeoutPolicy>>,
     }
 
     impl ReadTransactions {
-        pub(super) fn new(max_duration: Duration) -> Self {
-            Self { max_duration, ..Default::default() }
+        pub(super) fn new(
+            max_duration: Duration,
+            policy: Option<Arc<dyn ReadTransactionTimeoutPolicy>>,
+        ) -> Self {
+            Self { max_duration, policy, ..Default::default() }
         }
 
         /// Adds a new transaction to the list of active read transactions.
@@ -212,6 +249,21 @@ mod read_transactions {
             self.timed_out_not_aborted.len()
         }
 
+        /// Returns the duration of the longest currently active read transaction.
+        pub(super) fn oldest(&self) -> Option<Duration> {
+            self.active.iter().map(|entry| entry.value().1.elapsed()).max()
+        }
+
+        /// Returns the action to take for the transaction that has been open for `duration`, or
+        /// [None] if it's not past its deadline yet.
+        fn timeout_action(&self, duration: Duration) -> Option<ReadTransactionTimeoutAction> {
+            (duration > self.max_duration).then(|| {
+                self.policy.as_ref().map_or(ReadTransactionTimeoutAction::Abort, |policy| {
+                    policy.on_timeout(duration)
+                })
+            })
+        }
+
         /// Spawns a new [`std::thread`] that monitors the list of active read transactions and
         /// timeouts those that are open for longer than `ReadTransactions.max_duration`.
         pub(super) fn start_monitor(self: Arc<Self>) {
@@ -228,44 +280,59 @@ mod read_transactions {
                         let (tx, start, backtrace) = entry.value();
                         let duration = now - *start;
 
-                        if duration > self.max_duration {
-                            let result = tx.txn_execute_fail_on_timeout(|txn_ptr| {
-                                // Time out the transaction.
-                                //
-                                // We use `mdbx_txn_reset` instead of `mdbx_txn_abort` here to
-                                // prevent MDBX from reusing the pointer of the aborted
-                                // transaction for new read-only transactions. This is
-                                // important because we store the pointer in the `active` list
-                                // and assume that it is unique.
-                                //
-                                // See https://libmdbx.dqdkfa.ru/group__c__transactions.html#gae9f34737fe60b0ba538d5a09b6a25c8d for more info.
-                                let result = mdbx_result(unsafe { ffi::mdbx_txn_reset(txn_ptr) });
-                                if result.is_ok() {
-                                    tx.set_timed_out();
+                        match self.timeout_action(duration) {
+                            Some(ReadTransactionTimeoutAction::Abort) => {
+                                let result = tx.txn_execute_fail_on_timeout(|txn_ptr| {
+                                    // Time out the transaction.
+                                    //
+                                    // We use `mdbx_txn_reset` instead of `mdbx_txn_abort` here to
+                                    // prevent MDBX from reusing the pointer of the aborted
+                                    // transaction for new read-only transactions. This is
+                                    // important because we store the pointer in the `active` list
+                                    // and assume that it is unique.
+                                    //
+                                    // See https://libmdbx.dqdkfa.ru/group__c__transactions.html#gae9f34737fe60b0ba538d5a09b6a25c8d for more info.
+                                    let result =
+                                        mdbx_result(unsafe { ffi::mdbx_txn_reset(txn_ptr) });
+                                    if result.is_ok() {
+                                        tx.set_timed_out();
+                                    }
+                                    (txn_ptr, duration, result)
+                                });
+
+                                match result {
+                                    Ok((txn_ptr, duration, error)) => {
+                                        // Add the transaction to `timed_out_active`. We can't
+                                        // remove it instantly from the list of active
+                                        // transactions, because we iterate through it.
+                                        timed_out_active.push((
+                                            txn_ptr,
+                                            duration,
+                                            backtrace.clone(),
+                                            error,
+                                        ));
+                                    }
+                                    Err(err) => {
+                                        error!(target: "libmdbx", %err, ?backtrace, "Failed to abort the long-lived read transaction")
+                                    }
                                 }
-                                (txn_ptr, duration, result)
-                            });
-
-                            match result {
-                                Ok((txn_ptr, duration, error)) => {
-                                    // Add the transaction to `timed_out_active`. We can't remove it
-                                    // instantly from the list of active transactions, because we
-                                    // iterate through it.
-                                    timed_out_active.push((
-                                        txn_ptr,
-                                        duration,
-                                        backtrace.clone(),
-                                        error,
-                                    ));
-                                }
-                                Err(err) => {
-                                    error!(target: "libmdbx", %err, ?backtrace, "Failed to abort the long-lived read transaction")
+                            }
+                            Some(ReadTransactionTimeoutAction::Deny) => {
+                                if !tx.set_denied(true) {
+                                    warn!(target: "libmdbx", open_duration = ?duration, ?backtrace, "Long-lived read transaction is kept open, denying its new reads");
                                 }
                             }
-                        } else {
-                            max_active_transaction_duration = Some(
-                                duration.max(max_active_transaction_duration.unwrap_or_default()),
-                            );
+                            Some(ReadTransactionTimeoutAction::Keep) => {
+                                tx.set_denied(false);
+                            }
+                            None => {
+                                // Only transactions within their deadline shorten the sleep
+                                // below, those kept open by the policy are past it already.
+                                max_active_transaction_duration = Some(
+                                    duration
+                                        .max(max_active_transaction_duration.unwrap_or_default()),
+                                );
+                            }
                         }
                     }
 
@@ -283,6 +350,7 @@ mod read_transactions {
                         } else {
                             // Happy path, the transaction has been timed out by us with no errors.
                             warn!(target: "libmdbx", ?open_duration, ?backtrace, "Long-lived read transaction has been timed out");
+                            self.timed_out.fetch_add(1, Ordering::Relaxed);
                             // Add transaction to the list of timed out transactions that were not
                             // aborted by the user yet.
                             self.timed_out_not_aborted.insert(ptr as usize);
@@ -325,9 +393,10 @@ mod read_transactions {
     mod tests {
         use crate::{
             txn_manager::read_transactions::READ_TRANSACTIONS_CHECK_INTERVAL, Environment, Error,
-            MaxReadTransactionDuration,
+            MaxReadTransactionDuration, ReadTransactionTimeoutAction, ReadTransactionTimeoutPolicy,
+            WriteFlags,
         };
-        use std::{thread::sleep, time::Duration};
+        use std::{sync::Arc, thread::sleep, time::Duration};
         use tempfile::tempdir;
 
         #[test]
@@ -418,5 +487,87 @@ mod read_transactions {
             sleep(READ_TRANSACTIONS_CHECK_INTERVAL);
             assert!(tx.commit().is_ok())
         }
+
+        #[test]
+        fn txn_manager_read_transactions_timeout_policy_keep() {
+            const MAX_DURATION: Duration = Duration::from_secs(1);
+
+            #[derive(Debug)]
+            struct KeepPolicy;
+
+            impl ReadTransactionTimeoutPolicy for KeepPolicy {
+                fn on_timeout(&self, _open_duration: Duration) -> ReadTransactionTimeoutAction {
+                    ReadTransactionTimeoutAction::Keep
+                }
+            }
+
+            let dir = tempdir().unwrap();
+            let env = Environment::builder()
+                .set_max_read_transaction_duration(MaxReadTransactionDuration::Set(MAX_DURATION))
+                .set_read_transaction_timeout_policy(Arc::new(KeepPolicy))
+                .open(dir.path())
+                .unwrap();
+
+            let tx = env.begin_ro_txn().unwrap();
+            sleep(MAX_DURATION + READ_TRANSACTIONS_CHECK_INTERVAL);
+
+            // The policy kept the transaction open past its deadline.
+            assert!(tx.open_db(None).is_ok());
+            assert_eq!(env.active_read_transactions(), 1);
+            assert!(env.oldest_read_transaction_duration().unwrap() > MAX_DURATION);
+            assert_eq!(env.timed_out_transactions(), 0);
+
+            assert!(tx.commit().is_ok());
+            assert_eq!(env.active_read_transactions(), 0);
+        }
+
+        #[test]
+        fn txn_manager_read_transactions_timeout_policy_deny() {
+            const MAX_DURATION: Duration = Duration::from_secs(1);
+
+            let dir = tempdir().unwrap();
+            let env = Environment::builder()
+                .set_max_read_transaction_duration(MaxReadTransactionDuration::Set(MAX_DURATION))
+                .set_read_transaction_timeout_policy(Arc::new(ReadTransactionTimeoutAction::Deny))
+                .open(dir.path())
+                .unwrap();
+
+            {
+                let tx = env.begin_rw_txn().unwrap();
+                let dbi = tx.open_db(None).unwrap().dbi();
+                tx.put(dbi, b"key1", b"val1", WriteFlags::empty()).unwrap();
+                tx.put(dbi, b"key2", b"val2", WriteFlags::empty()).unwrap();
+                tx.commit().unwrap();
+            }
+
+            let tx = env.begin_ro_txn().unwrap();
+            let dbi = tx.open_db(None).unwrap().dbi();
+            let mut cursor = tx.cursor(dbi).unwrap();
+            assert_eq!(cursor.first().unwrap(), Some((*b"key1", *b"val1")));
+            sleep(MAX_DURATION + READ_TRANSACTIONS_CHECK_INTERVAL);
+
+            // The transaction is kept open past its deadline, its open cursor can still be used,
+            // but its new reads are denied.
+            assert_eq!(cursor.next().unwrap(), Some((*b"key2", *b"val2")));
+            assert_eq!(tx.cursor(dbi).err(), Some(Error::ReadTransactionDenied));
+            assert_eq!(tx.get::<()>(dbi, b"key1").err(), Some(Error::ReadTransactionDenied));
+            assert_eq!(env.timed_out_transactions(), 0);
+
+            // Other transactions are not affected.
+            let other = env.begin_ro_txn().unwrap();
+            assert_eq!(other.get(dbi, b"key1").unwrap(), Some(*b"val1"));
+            assert!(env.begin_rw_txn().is_ok());
+
+            drop(cursor);
+            assert!(tx.commit().is_ok());
+        }
+
+        #[test]
+        fn read_transaction_timeout_action_from_str() {
+            assert_eq!("abort".parse(), Ok(ReadTransactionTimeoutAction::Abort));
+            assert_eq!("Keep".parse(), Ok(ReadTransactionTimeoutAction::Keep));
+            assert_eq!(" deny ".parse(), Ok(ReadTransactionTimeoutAction::Deny));
+            assert!("drop".parse::<ReadTransactionTimeoutAction>().is_err());
+        }
     }
 }
@@ -2,6 +2,7 @@ use crate::NippyJarError;
 use serde::{Deserialize, Serialize};
 
 mod zstd;
+pub(crate) use self::zstd::ZstdColumnCompressors;
 pub use self::zstd::{DecoderDictionary, Decompressor, Zstd, ZstdState};
 mod lz4;
 pub use self::lz4::Lz4;
@@ -30,7 +31,6 @@ pub trait Compression: Serialize + for<'a> Deserialize<'a> {
         true
     }
 
-    #[cfg(test)]
     /// If required, prepares compression algorithm with an early pass on the data.
     fn prepare_compression(
         &mut self,
@@ -98,7 +98,6 @@ impl Compression for Compressors {
         }
     }
 
-    #[cfg(test)]
     fn prepare_compression(
         &mut self,
         columns: Vec<impl IntoIterator<Item = Vec<u8>>>,
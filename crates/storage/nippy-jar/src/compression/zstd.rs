@@ -71,7 +71,7 @@ impl Zstd {
     }
 
     /// If using dictionaries, creates a list of [`Compressor`].
-    pub fn compressors(&self) -> Result<Option<Vec<Compressor<'_>>>, NippyJarError> {
+    pub fn compressors(&self) -> Result<Option<Vec<Compressor<'static>>>, NippyJarError> {
         match self.state {
             ZstdState::PendingDictionary => Err(NippyJarError::CompressorNotReady),
             ZstdState::Ready => {
@@ -81,13 +81,26 @@ impl Zstd {
 
                 if let Some(dictionaries) = &self.dictionaries {
                     debug!(target: "nippy-jar", count=?dictionaries.len(), "Generating ZSTD compressor dictionaries.");
-                    return Ok(Some(dictionaries.compressors()?))
+                    return Ok(Some(dictionaries.compressors(self.level)?))
                 }
                 Ok(None)
             }
         }
     }
 
+    /// Creates a [`ZstdColumnCompressors`] if dictionaries have been prepared for compression.
+    ///
+    /// Dictionaries loaded from disk can only be used for decompression, in which case `None` is
+    /// returned.
+    pub(crate) fn column_compressors(
+        &self,
+    ) -> Result<Option<ZstdColumnCompressors>, NippyJarError> {
+        if self.dictionaries.as_ref().is_some_and(|dictionaries| dictionaries.is_raw()) {
+            return Ok(self.compressors()?.map(ZstdColumnCompressors))
+        }
+        Ok(None)
+    }
+
     /// Compresses a value using a dictionary. Reserves additional capacity for `buffer` if
     /// necessary.
     pub fn compress_with_dictionary(
@@ -189,7 +202,6 @@ impl Compression for Zstd {
         matches!(self.state, ZstdState::Ready)
     }
 
-    #[cfg(test)]
     /// If using it with dictionaries, prepares a dictionary for each column.
     fn prepare_compression(
         &mut self,
@@ -265,6 +277,26 @@ mod dictionaries_serde {
     }
 }
 
+/// One dictionary [`Compressor`] per column, used when appending data to a jar.
+pub(crate) struct ZstdColumnCompressors(Vec<Compressor<'static>>);
+
+impl ZstdColumnCompressors {
+    /// Compresses `value` with the dictionary of `column`.
+    pub(crate) fn compress(
+        &mut self,
+        column: usize,
+        value: &[u8],
+    ) -> Result<Vec<u8>, NippyJarError> {
+        Ok(self.0[column].compress(value)?)
+    }
+}
+
+impl std::fmt::Debug for ZstdColumnCompressors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ZstdColumnCompressors").field("num", &self.0.len()).finish_non_exhaustive()
+    }
+}
+
 /// List of [`ZstdDictionary`]
 #[cfg_attr(test, derive(PartialEq))]
 #[derive(Serialize, Deserialize, Deref)]
@@ -277,7 +309,6 @@ impl std::fmt::Debug for ZstdDictionaries<'_> {
 }
 
 impl ZstdDictionaries<'_> {
-    #[cfg(test)]
     /// Creates [`ZstdDictionaries`].
     pub(crate) fn new(raw: Vec<RawDictionary>) -> Self {
         Self(raw.into_iter().map(ZstdDictionary::Raw).collect())
@@ -304,14 +335,24 @@ impl ZstdDictionaries<'_> {
             .collect::<Result<Vec<_>, _>>()?)
     }
 
-    /// Creates a list of compressors from a list of [`ZstdDictionary::Raw`].
-    pub(crate) fn compressors(&self) -> Result<Vec<Compressor<'_>>, NippyJarError> {
+    /// Returns `true` if every dictionary is a [`ZstdDictionary::Raw`], meaning it can be used
+    /// for compression.
+    pub(crate) fn is_raw(&self) -> bool {
+        self.iter().all(|dict| dict.raw().is_some())
+    }
+
+    /// Creates a list of compressors with the given level from a list of
+    /// [`ZstdDictionary::Raw`].
+    pub(crate) fn compressors(
+        &self,
+        level: i32,
+    ) -> Result<Vec<Compressor<'static>>, NippyJarError> {
         Ok(self
             .iter()
             .flat_map(|dict| {
                 dict.raw()
                     .ok_or(NippyJarError::CompressorNotAllowed)
-                    .map(|dict| Compressor::with_dictionary(0, dict))
+                    .map(|dict| Compressor::with_dictionary(level, dict))
             })
             .collect::<Result<Vec<_>, _>>()?)
     }
@@ -320,7 +361,6 @@ impl ZstdDictionaries<'_> {
 /// A Zstd dictionary. It's created and serialized with [`ZstdDictionary::Raw`], and deserialized as
 /// [`ZstdDictionary::Loaded`].
 pub(crate) enum ZstdDictionary<'a> {
-    #[cfg_attr(not(test), expect(dead_code))]
     Raw(RawDictionary),
     Loaded(DecoderDictionary<'a>),
 }
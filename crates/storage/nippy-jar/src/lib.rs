@@ -25,9 +25,7 @@ use tracing::*;
 
 /// Compression algorithms supported by `NippyJar`.
 pub mod compression;
-#[cfg(test)]
-use compression::Compression;
-use compression::Compressors;
+use compression::{Compression, Compressors};
 
 /// empty enum for backwards compatibility
 #[derive(Debug, Serialize, Deserialize)]
@@ -54,9 +52,9 @@ pub use consistency::NippyJarChecker;
 /// The version number of the Nippy Jar format.
 const NIPPY_JAR_VERSION: usize = 1;
 /// The file extension used for index files.
-const INDEX_FILE_EXTENSION: &str = "idx";
+pub const INDEX_FILE_EXTENSION: &str = "idx";
 /// The file extension used for offsets files.
-const OFFSETS_FILE_EXTENSION: &str = "off";
+pub const OFFSETS_FILE_EXTENSION: &str = "off";
 /// The file extension used for configuration files.
 pub const CONFIG_FILE_EXTENSION: &str = "conf";
 
@@ -162,6 +160,16 @@ impl<H: NippyJarHeader> NippyJar<H> {
         self
     }
 
+    /// Sets the compression level of a previously added [`compression::Zstd`] compression.
+    ///
+    /// Has no effect if the jar does not use [`compression::Zstd`].
+    pub const fn with_zstd_level(mut self, level: i32) -> Self {
+        if let Some(Compressors::Zstd(zstd)) = &mut self.compressor {
+            zstd.level = level;
+        }
+        self
+    }
+
     /// Adds [`compression::Lz4`] compression.
     pub fn with_lz4(mut self) -> Self {
         self.compressor = Some(Compressors::Lz4(compression::Lz4::default()));
@@ -261,15 +269,10 @@ impl<H: NippyJarHeader> NippyJar<H> {
         DataReader::new(self.data_path())
     }
 
-    /// Writes all necessary configuration to file.
-    fn freeze_config(&self) -> Result<(), NippyJarError> {
-        Ok(reth_fs_util::atomic_write_file(&self.config_path(), |file| self.save_to_writer(file))?)
-    }
-}
-
-#[cfg(test)]
-impl<H: NippyJarHeader> NippyJar<H> {
     /// If required, prepares any compression algorithm to an early pass of the data.
+    ///
+    /// For [`compression::Zstd`] with dictionaries, this trains one dictionary per column, which
+    /// is then used by [`NippyJarWriter`] when appending data.
     pub fn prepare_compression(
         &mut self,
         columns: Vec<impl IntoIterator<Item = Vec<u8>>>,
@@ -282,6 +285,14 @@ impl<H: NippyJarHeader> NippyJar<H> {
         Ok(())
     }
 
+    /// Writes all necessary configuration to file.
+    fn freeze_config(&self) -> Result<(), NippyJarError> {
+        Ok(reth_fs_util::atomic_write_file(&self.config_path(), |file| self.save_to_writer(file))?)
+    }
+}
+
+#[cfg(test)]
+impl<H: NippyJarHeader> NippyJar<H> {
     /// Writes all data and configuration to a file and the offset index to another.
     pub fn freeze(
         self,
@@ -640,6 +651,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_zstd_level() {
+        let (col1, col2) = test_data(None);
+        let num_rows = col1.len() as u64;
+        let num_columns = 2;
+        let file_path = tempfile::NamedTempFile::new().unwrap();
+
+        let mut nippy = NippyJar::new_without_header(num_columns, file_path.path())
+            .with_zstd(true, 5000)
+            .with_zstd_level(19);
+        nippy.prepare_compression(vec![col1.clone(), col2.clone()]).unwrap();
+        nippy.freeze(vec![clone_with_result(&col1), clone_with_result(&col2)], num_rows).unwrap();
+
+        let loaded_nippy = NippyJar::load_without_header(file_path.path()).unwrap();
+        let Some(Compressors::Zstd(zstd)) = loaded_nippy.compressor() else {
+            panic!("Expected Zstd compressor")
+        };
+        assert_eq!(zstd.level, 19);
+
+        let mut cursor = NippyJarCursor::new(&loaded_nippy).unwrap();
+        let mut row_index = 0usize;
+        while let Some(row) = cursor.next_row().unwrap() {
+            assert_eq!((row[0], row[1]), (col1[row_index].as_slice(), col2[row_index].as_slice()));
+            row_index += 1;
+        }
+        assert_eq!(row_index, num_rows as usize);
+    }
+
     /// Tests `NippyJar` with everything enabled.
     #[test]
     fn test_full_nippy_jar() {
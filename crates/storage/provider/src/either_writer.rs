@@ -10,7 +10,9 @@ use std::{
 #[cfg(all(unix, feature = "rocksdb"))]
 use crate::providers::rocksdb::RocksDBBatch;
 use crate::{
-    providers::{history_info, HistoryInfo, StaticFileProvider, StaticFileProviderRWRefMut},
+    providers::{
+        history_blocks, history_info, HistoryInfo, StaticFileProvider, StaticFileProviderRWRefMut,
+    },
     StaticFileProviderFactory,
 };
 use alloy_primitives::{map::HashMap, Address, BlockNumber, TxHash, TxNumber, B256};
@@ -202,16 +204,21 @@ impl<'a> EitherWriter<'a, (), ()> {
     ///   enabled, write to the database.
     /// - If the node should always write receipts to static files, but receipt log filter pruning
     ///   is enabled, write to the database.
+    /// - If the node keeps receipts of the most recent blocks in the database, write to the
+    ///   database. Older receipts are moved to static files afterwards, see
+    ///   [`DatabaseProvider::move_cold_receipts_to_static_files`](crate::DatabaseProvider::move_cold_receipts_to_static_files).
     /// - Otherwise, write to static files.
     pub fn receipts_destination<P: DBProvider + StorageSettingsCache>(
         provider: &P,
     ) -> EitherWriterDestination {
-        let receipts_in_static_files = provider.cached_storage_settings().receipts_in_static_files;
+        let settings = provider.cached_storage_settings();
+        let receipts_in_static_files = settings.receipts_in_static_files;
         let prune_modes = provider.prune_modes_ref();
 
         if !receipts_in_static_files && prune_modes.has_receipts_pruning() ||
             // TODO: support writing receipts to static files with log filter pruning enabled
-            receipts_in_static_files && !prune_modes.receipts_log_filter.is_empty()
+            receipts_in_static_files && !prune_modes.receipts_log_filter.is_empty() ||
+            settings.receipts_hot_blocks().is_some()
         {
             EitherWriterDestination::Database
         } else {
@@ -935,6 +942,26 @@ where
             ),
         }
     }
+
+    /// Returns the blocks in the given range that changed the storage slot, in ascending order.
+    pub fn storage_history_blocks(
+        &mut self,
+        address: Address,
+        storage_key: B256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        match self {
+            Self::Database(cursor, _) => {
+                let key = StorageShardedKey::new(address, storage_key, *range.start());
+                history_blocks::<tables::StoragesHistory, _, _>(cursor, key, range, |k| {
+                    k.address == address && k.sharded_key.key == storage_key
+                })
+            }
+            Self::StaticFile(_, _) => Err(ProviderError::UnsupportedProvider),
+            #[cfg(all(unix, feature = "rocksdb"))]
+            Self::RocksDB(tx) => tx.storage_history_blocks(address, storage_key, range),
+        }
+    }
 }
 
 impl<CURSOR, N: NodePrimitives> EitherReader<'_, CURSOR, N>
@@ -979,6 +1006,25 @@ where
             }
         }
     }
+
+    /// Returns the blocks in the given range that changed the account, in ascending order.
+    pub fn account_history_blocks(
+        &mut self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        match self {
+            Self::Database(cursor, _) => {
+                let key = ShardedKey::new(address, *range.start());
+                history_blocks::<tables::AccountsHistory, _, _>(cursor, key, range, |k| {
+                    k.key == address
+                })
+            }
+            Self::StaticFile(_, _) => Err(ProviderError::UnsupportedProvider),
+            #[cfg(all(unix, feature = "rocksdb"))]
+            Self::RocksDB(tx) => tx.account_history_blocks(address, range),
+        }
+    }
 }
 
 impl<CURSOR, N: NodePrimitives> EitherReader<'_, CURSOR, N>
@@ -37,7 +37,7 @@ pub use either_writer::*;
 
 pub use reth_chain_state::{
     CanonStateNotification, CanonStateNotificationSender, CanonStateNotificationStream,
-    CanonStateNotifications, CanonStateSubscriptions,
+    CanonStateNotifications, CanonStateSubscriptions, SyncProgress, SyncProgressProvider,
 };
 pub use reth_execution_types::*;
 /// Re-export `OriginalValuesKnown`
@@ -45,7 +45,7 @@ pub use revm_database::states::OriginalValuesKnown;
 // reexport traits to avoid breaking changes
 pub use reth_static_file_types as static_file;
 pub use reth_storage_api::{
-    HistoryWriter, MetadataProvider, MetadataWriter, StateWriteConfig, StatsReader,
+    HistoryReader, HistoryWriter, MetadataProvider, MetadataWriter, StateWriteConfig, StatsReader,
     StorageSettings, StorageSettingsCache,
 };
 /// Re-export provider error.
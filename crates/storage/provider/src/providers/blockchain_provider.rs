@@ -12,22 +12,33 @@ use crate::{
     TransactionVariant, TransactionsProvider,
 };
 use alloy_consensus::transaction::TransactionMeta;
-use alloy_eips::{BlockHashOrNumber, BlockId, BlockNumHash, BlockNumberOrTag};
-use alloy_primitives::{Address, BlockHash, BlockNumber, TxHash, TxNumber, B256};
+use alloy_eips::{
+    eip4895::Withdrawals, BlockHashOrNumber, BlockId, BlockNumHash, BlockNumberOrTag,
+};
+use alloy_primitives::{Address, BlockHash, BlockNumber, TxHash, TxNumber, B256, U256};
 use alloy_rpc_types_engine::ForkchoiceState;
 use reth_chain_state::{
-    BlockState, CanonicalInMemoryState, ForkChoiceNotifications, ForkChoiceSubscriptions,
+    beacon_genesis_time, BlockState, BlockTimings, BlockTimingsProvider, CanonicalInMemoryState,
+    ChainStats, ChainStatsProvider, ForkChoiceNotifications, ForkChoiceSubscriptions,
     MemoryOverlayStateProvider, PersistedBlockNotifications, PersistedBlockSubscriptions,
+    SyncProgress, SyncProgressProvider,
+};
+use reth_chainspec::{ChainInfo, EthChainSpec};
+use reth_db_api::models::{
+    AccountBeforeTx, AccountDestruction, BlockNumberAddress, ContractCreation, ReorgRecord,
+    StoredBlockBodyIndices,
 };
-use reth_chainspec::ChainInfo;
-use reth_db_api::models::{AccountBeforeTx, BlockNumberAddress, StoredBlockBodyIndices};
 use reth_execution_types::ExecutionOutcome;
 use reth_node_types::{BlockTy, HeaderTy, NodeTypesWithDB, ReceiptTy, TxTy};
 use reth_primitives_traits::{Account, RecoveredBlock, SealedHeader, StorageEntry};
 use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_static_file_types::StaticFileSegment;
-use reth_storage_api::{BlockBodyIndicesProvider, NodePrimitivesProvider, StorageChangeSetReader};
+use reth_storage_api::{
+    AccountDestructionsReader, AddressTransactionsReader, BlockBodyIndicesProvider,
+    ContractCreationsReader, HistoryReader, LogsBloomIndexReader, NodePrimitivesProvider,
+    ReceiptBlockOffsets, ReorgHistoryReader, StorageChangeSetReader, WithdrawalsProvider,
+};
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::{HashedPostState, KeccakKeyHasher};
 use revm_database::BundleState;
@@ -101,14 +112,22 @@ impl<N: ProviderNodeTypes> BlockchainProvider<N> {
             .map(|num| provider.sealed_header(num))
             .transpose()?
             .flatten();
-        Ok(Self {
-            database: storage,
-            canonical_in_memory_state: CanonicalInMemoryState::with_head(
-                latest,
-                finalized_header,
-                safe_header,
-            ),
-        })
+        let canonical_in_memory_state =
+            CanonicalInMemoryState::with_head(latest, finalized_header, safe_header);
+
+        // networks without a well-known beacon genesis usually launched post-merge, where both
+        // genesis times match
+        let chain_spec = storage.chain_spec();
+        let genesis_time =
+            beacon_genesis_time(chain_spec.chain()).unwrap_or(chain_spec.genesis().timestamp);
+        canonical_in_memory_state.block_timings().set_genesis_time(genesis_time);
+        canonical_in_memory_state.block_timings().set_slot_duration(chain_spec.slot_duration());
+        canonical_in_memory_state.chain_stats().set_genesis_time(genesis_time);
+        canonical_in_memory_state
+            .chain_stats()
+            .set_slot_timing(chain_spec.slot_duration(), chain_spec.epoch_slots());
+
+        Ok(Self { database: storage, canonical_in_memory_state })
     }
 
     /// Gets a clone of `canonical_in_memory_state`.
@@ -423,6 +442,10 @@ impl<N: ProviderNodeTypes> ReceiptProvider for BlockchainProvider<N> {
         self.consistent_provider()?.receipt(id)
     }
 
+    fn receipt_block_offsets(&self, id: TxNumber) -> ProviderResult<Option<ReceiptBlockOffsets>> {
+        self.consistent_provider()?.receipt_block_offsets(id)
+    }
+
     fn receipt_by_hash(&self, hash: TxHash) -> ProviderResult<Option<Self::Receipt>> {
         self.consistent_provider()?.receipt_by_hash(hash)
     }
@@ -471,6 +494,15 @@ impl<N: ProviderNodeTypes> BlockBodyIndicesProvider for BlockchainProvider<N> {
     }
 }
 
+impl<N: ProviderNodeTypes> WithdrawalsProvider for BlockchainProvider<N> {
+    fn withdrawals_by_block_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<Withdrawals>> {
+        self.consistent_provider()?.withdrawals_by_block_range(range)
+    }
+}
+
 impl<N: ProviderNodeTypes> StageCheckpointReader for BlockchainProvider<N> {
     fn get_stage_checkpoint(&self, id: StageId) -> ProviderResult<Option<StageCheckpoint>> {
         self.consistent_provider()?.get_stage_checkpoint(id)
@@ -498,6 +530,63 @@ impl<N: ProviderNodeTypes> PruneCheckpointReader for BlockchainProvider<N> {
     }
 }
 
+impl<N: ProviderNodeTypes> ReorgHistoryReader for BlockchainProvider<N> {
+    fn reorg_history(&self, limit: usize) -> ProviderResult<Vec<ReorgRecord>> {
+        self.database.reorg_history(limit)
+    }
+}
+
+impl<N: ProviderNodeTypes> HistoryReader for BlockchainProvider<N> {
+    fn account_history_range(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, Option<Account>)>> {
+        self.database.account_history_range(address, range)
+    }
+
+    fn storage_history_range(
+        &self,
+        address: Address,
+        storage_key: B256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, U256)>> {
+        self.database.storage_history_range(address, storage_key, range)
+    }
+}
+
+impl<N: ProviderNodeTypes> AddressTransactionsReader for BlockchainProvider<N> {
+    fn address_transactions(
+        &self,
+        addresses: &[Address],
+        block_range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Option<Vec<TxNumber>>> {
+        self.database.address_transactions(addresses, block_range)
+    }
+}
+
+impl<N: ProviderNodeTypes> LogsBloomIndexReader for BlockchainProvider<N> {
+    fn block_ranges_with_log_addresses(
+        &self,
+        addresses: &[Address],
+        block_range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<RangeInclusive<BlockNumber>>> {
+        self.database.block_ranges_with_log_addresses(addresses, block_range)
+    }
+}
+
+impl<N: ProviderNodeTypes> ContractCreationsReader for BlockchainProvider<N> {
+    fn contract_creation(&self, address: Address) -> ProviderResult<Option<ContractCreation>> {
+        self.database.contract_creation(address)
+    }
+}
+
+impl<N: ProviderNodeTypes> AccountDestructionsReader for BlockchainProvider<N> {
+    fn account_destructions(&self, address: Address) -> ProviderResult<Vec<AccountDestruction>> {
+        self.database.account_destructions(address)
+    }
+}
+
 impl<N: NodeTypesWithDB> ChainSpecProvider for BlockchainProvider<N> {
     type ChainSpec = N::ChainSpec;
 
@@ -709,6 +798,24 @@ impl<N: ProviderNodeTypes> PersistedBlockSubscriptions for BlockchainProvider<N>
     }
 }
 
+impl<N: ProviderNodeTypes> BlockTimingsProvider for BlockchainProvider<N> {
+    fn block_timings(&self) -> BlockTimings {
+        self.canonical_in_memory_state.block_timings().clone()
+    }
+}
+
+impl<N: ProviderNodeTypes> ChainStatsProvider for BlockchainProvider<N> {
+    fn chain_stats(&self) -> ChainStats {
+        self.canonical_in_memory_state.chain_stats().clone()
+    }
+}
+
+impl<N: ProviderNodeTypes> SyncProgressProvider for BlockchainProvider<N> {
+    fn sync_progress(&self) -> SyncProgress {
+        self.canonical_in_memory_state.sync_progress().clone()
+    }
+}
+
 impl<N: ProviderNodeTypes> StorageChangeSetReader for BlockchainProvider<N> {
     fn storage_changeset(
         &self,
@@ -1616,6 +1723,27 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_receipts_by_block_range() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+        let (provider, database_blocks, in_memory_blocks, receipts) = provider_with_random_blocks(
+            &mut rng,
+            TEST_BLOCKS_COUNT,
+            TEST_BLOCKS_COUNT,
+            BlockRangeParams { tx_count: 1..3, ..Default::default() },
+        )?;
+
+        // the range spans both the persisted and the in-memory blocks
+        let range =
+            database_blocks.first().unwrap().number..=in_memory_blocks.last().unwrap().number;
+        assert_eq!(
+            provider.receipts_by_block_range(range.clone())?,
+            receipts[*range.start() as usize..=*range.end() as usize].to_vec()
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn test_receipt_provider_id_ext_receipts_by_block_number_or_tag() -> eyre::Result<()> {
         let mut rng = generators::rng();
@@ -2199,6 +2327,11 @@ mod tests {
                 .body()
                 .transactions
                 .clone()),
+            (withdrawals_by_block_range, |block: &SealedBlock<Block>| block
+                .body()
+                .withdrawals
+                .clone()
+                .unwrap_or_default()),
         ]);
 
         Ok(())
@@ -9,8 +9,8 @@ use crate::{
 };
 use alloy_consensus::{transaction::TransactionMeta, BlockHeader};
 use alloy_eips::{
-    eip2718::Encodable2718, BlockHashOrNumber, BlockId, BlockNumHash, BlockNumberOrTag,
-    HashOrNumber,
+    eip2718::Encodable2718, eip4895::Withdrawals, BlockHashOrNumber, BlockId, BlockNumHash,
+    BlockNumberOrTag, HashOrNumber,
 };
 use alloy_primitives::{
     map::{hash_map, HashMap},
@@ -26,8 +26,9 @@ use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_static_file_types::StaticFileSegment;
 use reth_storage_api::{
-    BlockBodyIndicesProvider, DatabaseProviderFactory, NodePrimitivesProvider, StateProvider,
-    StateProviderBox, StorageChangeSetReader, TryIntoHistoricalStateProvider,
+    BlockBodyIndicesProvider, DatabaseProviderFactory, NodePrimitivesProvider, ReceiptBlockOffsets,
+    StateProvider, StateProviderBox, StorageChangeSetReader, TryIntoHistoricalStateProvider,
+    WithdrawalsProvider,
 };
 use reth_storage_errors::provider::ProviderResult;
 use revm_database::states::PlainStorageRevert;
@@ -120,14 +121,15 @@ impl<N: ProviderNodeTypes> ConsistentProvider<N> {
     ) -> ProviderResult<Box<dyn StateProvider + 'a>> {
         trace!(target: "providers::blockchain", ?block_hash, "Getting history by block hash");
 
-        self.get_in_memory_or_storage_by_block(
-            block_hash.into(),
-            |_| self.storage_provider.history_by_block_hash(block_hash),
-            |block_state| {
-                let state_provider = self.block_state_provider_ref(block_state)?;
-                Ok(Box::new(state_provider))
-            },
-        )
+        if let Some(Some(block_state)) =
+            self.head_block.as_ref().map(|b| b.block_on_chain(block_hash.into()))
+        {
+            return Ok(Box::new(self.block_state_provider_ref(block_state)?))
+        }
+        if self.is_reorged_out_block(block_hash)? {
+            return Err(ProviderError::BlockHashNotFound(block_hash))
+        }
+        self.storage_provider.history_by_block_hash(block_hash)
     }
 
     /// Returns a state provider indexed by the given block number or tag.
@@ -568,7 +570,10 @@ impl<N: ProviderNodeTypes> ConsistentProvider<N> {
         }
 
         // Not found in-memory, so check database.
-        if let HashOrNumber::Hash(_) = id {
+        if let HashOrNumber::Hash(tx_hash) = id {
+            if self.is_reorged_out_transaction(tx_hash)? {
+                return Ok(None)
+            }
             return fetch_from_db(provider)
         }
 
@@ -585,18 +590,65 @@ impl<N: ProviderNodeTypes> ConsistentProvider<N> {
     where
         S: FnOnce(&DatabaseProviderRO<N::DB, N>) -> ProviderResult<R>,
         M: Fn(&BlockState<N::Primitives>) -> ProviderResult<R>,
+        R: Default,
     {
         if let Some(Some(block_state)) = self.head_block.as_ref().map(|b| b.block_on_chain(id)) {
             return fetch_from_block_state(block_state)
         }
+        if let BlockHashOrNumber::Hash(hash) = id &&
+            self.is_reorged_out_block(hash)?
+        {
+            return Ok(R::default())
+        }
         fetch_from_db(&self.storage_provider)
     }
 
+    /// Returns `true` if the given block number is covered by the in-memory canonical chain.
+    ///
+    /// The canonical blocks of this range are always served from memory. A block of this range
+    /// that is only found in storage was reorged out, and is only kept in the database until it is
+    /// reconciled with the in-memory chain on the next persistence flush.
+    fn is_overlapped_by_memory(&self, number: BlockNumber) -> bool {
+        self.head_block
+            .as_ref()
+            .is_some_and(|head| head.anchor().number < number && number <= head.number())
+    }
+
+    /// Returns `true` if the block with the given hash, which is not part of the in-memory chain,
+    /// is stored in the database but was reorged out.
+    fn is_reorged_out_block(&self, hash: BlockHash) -> ProviderResult<bool> {
+        if self.head_block.is_none() {
+            return Ok(false)
+        }
+        Ok(self
+            .storage_provider
+            .block_number(hash)?
+            .is_some_and(|number| self.is_overlapped_by_memory(number)))
+    }
+
+    /// Returns `true` if the transaction with the given hash, which is not part of the in-memory
+    /// chain, is stored in the database as part of a block that was reorged out.
+    fn is_reorged_out_transaction(&self, hash: TxHash) -> ProviderResult<bool> {
+        if self.head_block.is_none() {
+            return Ok(false)
+        }
+        let Some(tx_number) = self.storage_provider.transaction_id(hash)? else { return Ok(false) };
+        Ok(self
+            .storage_provider
+            .block_by_transaction_id(tx_number)?
+            .is_some_and(|number| self.is_overlapped_by_memory(number)))
+    }
+
     /// Consumes the provider and returns a state provider for the specific block hash.
     pub(crate) fn into_state_provider_at_block_hash(
         self,
         block_hash: BlockHash,
     ) -> ProviderResult<StateProviderBox> {
+        if self.head_block.as_ref().is_some_and(|b| b.block_on_chain(block_hash.into()).is_none()) &&
+            self.is_reorged_out_block(block_hash)?
+        {
+            return Err(ProviderError::BlockHashNotFound(block_hash))
+        }
         let Self { storage_provider, head_block, .. } = self;
         let into_history_at_block_hash = |block_hash| -> ProviderResult<StateProviderBox> {
             let block_number = storage_provider
@@ -956,6 +1008,9 @@ impl<N: ProviderNodeTypes> TransactionsProvider for ConsistentProvider<N> {
         if let Some(tx) = self.head_block.as_ref().and_then(|b| b.transaction_on_chain(hash)) {
             return Ok(Some(tx))
         }
+        if self.is_reorged_out_transaction(hash)? {
+            return Ok(None)
+        }
 
         self.storage_provider.transaction_by_hash(hash)
     }
@@ -970,7 +1025,10 @@ impl<N: ProviderNodeTypes> TransactionsProvider for ConsistentProvider<N> {
             return Ok(Some((tx, meta)))
         }
 
-        self.storage_provider.transaction_by_hash_with_meta(tx_hash)
+        Ok(self
+            .storage_provider
+            .transaction_by_hash_with_meta(tx_hash)?
+            .filter(|(_, meta)| !self.is_overlapped_by_memory(meta.block_number)))
     }
 
     fn transactions_by_block(
@@ -1051,6 +1109,15 @@ impl<N: ProviderNodeTypes> ReceiptProvider for ConsistentProvider<N> {
         )
     }
 
+    fn receipt_block_offsets(&self, id: TxNumber) -> ProviderResult<Option<ReceiptBlockOffsets>> {
+        // receipts of in-memory blocks are not indexed
+        self.get_in_memory_or_storage_by_tx(
+            id.into(),
+            |provider| provider.receipt_block_offsets(id),
+            |_, _, _| Ok(None),
+        )
+    }
+
     fn receipt_by_hash(&self, hash: TxHash) -> ProviderResult<Option<Self::Receipt>> {
         for block_state in self.head_block.iter().flat_map(|b| b.chain()) {
             let executed_block = block_state.block_ref();
@@ -1071,6 +1138,9 @@ impl<N: ProviderNodeTypes> ReceiptProvider for ConsistentProvider<N> {
                 return Ok(receipts.get(tx_index).cloned());
             }
         }
+        if self.is_reorged_out_transaction(hash)? {
+            return Ok(None)
+        }
 
         self.storage_provider.receipt_by_hash(hash)
     }
@@ -1103,7 +1173,12 @@ impl<N: ProviderNodeTypes> ReceiptProvider for ConsistentProvider<N> {
         &self,
         block_range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<Vec<Vec<Self::Receipt>>> {
-        self.storage_provider.receipts_by_block_range(block_range)
+        self.get_in_memory_or_storage_by_block_range_while(
+            block_range,
+            |db_provider, range, _| db_provider.receipts_by_block_range(range),
+            |block_state, _| Some(block_state.executed_block_receipts()),
+            |_| true,
+        )
     }
 }
 
@@ -1184,6 +1259,30 @@ impl<N: ProviderNodeTypes> BlockBodyIndicesProvider for ConsistentProvider<N> {
     }
 }
 
+impl<N: ProviderNodeTypes> WithdrawalsProvider for ConsistentProvider<N> {
+    fn withdrawals_by_block_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<Withdrawals>> {
+        self.get_in_memory_or_storage_by_block_range_while(
+            range,
+            |db_provider, range, _| db_provider.withdrawals_by_block_range(range),
+            |block_state, _| {
+                Some(
+                    block_state
+                        .block_ref()
+                        .recovered_block()
+                        .body()
+                        .withdrawals()
+                        .cloned()
+                        .unwrap_or_default(),
+                )
+            },
+            |_| true,
+        )
+    }
+}
+
 impl<N: ProviderNodeTypes> StageCheckpointReader for ConsistentProvider<N> {
     fn get_stage_checkpoint(&self, id: StageId) -> ProviderResult<Option<StageCheckpoint>> {
         self.storage_provider.get_stage_checkpoint(id)
@@ -1306,10 +1405,7 @@ impl<N: ProviderNodeTypes> StorageChangeSetReader for ConsistentProvider<N> {
         {
             let changesets = state
                 .block()
-                .execution_output
-                .state
-                .reverts
-                .clone()
+                .execution_reverts()
                 .to_plain_state_reverts()
                 .storage
                 .into_iter()
@@ -1359,10 +1455,7 @@ impl<N: ProviderNodeTypes> StorageChangeSetReader for ConsistentProvider<N> {
         {
             let changeset = state
                 .block_ref()
-                .execution_output
-                .state
-                .reverts
-                .clone()
+                .execution_reverts()
                 .to_plain_state_reverts()
                 .storage
                 .into_iter()
@@ -1411,10 +1504,7 @@ impl<N: ProviderNodeTypes> StorageChangeSetReader for ConsistentProvider<N> {
             for state in chain {
                 let block_changesets = state
                     .block_ref()
-                    .execution_output
-                    .state
-                    .reverts
-                    .clone()
+                    .execution_reverts()
                     .to_plain_state_reverts()
                     .storage
                     .into_iter()
@@ -1462,10 +1552,7 @@ impl<N: ProviderNodeTypes> StorageChangeSetReader for ConsistentProvider<N> {
             for state in head_block.chain() {
                 count += state
                     .block_ref()
-                    .execution_output
-                    .state
-                    .reverts
-                    .clone()
+                    .execution_reverts()
                     .to_plain_state_reverts()
                     .storage
                     .into_iter()
@@ -1491,10 +1578,7 @@ impl<N: ProviderNodeTypes> ChangeSetReader for ConsistentProvider<N> {
         {
             let changesets = state
                 .block_ref()
-                .execution_output
-                .state
-                .reverts
-                .clone()
+                .execution_reverts()
                 .to_plain_state_reverts()
                 .accounts
                 .into_iter()
@@ -1537,10 +1621,7 @@ impl<N: ProviderNodeTypes> ChangeSetReader for ConsistentProvider<N> {
             // Search in-memory state for the account changeset
             let changeset = state
                 .block_ref()
-                .execution_output
-                .state
-                .reverts
-                .clone()
+                .execution_reverts()
                 .to_plain_state_reverts()
                 .accounts
                 .into_iter()
@@ -1591,10 +1672,7 @@ impl<N: ProviderNodeTypes> ChangeSetReader for ConsistentProvider<N> {
                 // found block in memory, collect its changesets
                 let block_changesets = state
                     .block_ref()
-                    .execution_output
-                    .state
-                    .reverts
-                    .clone()
+                    .execution_reverts()
                     .to_plain_state_reverts()
                     .accounts
                     .into_iter()
@@ -1637,15 +1715,8 @@ impl<N: ProviderNodeTypes> ChangeSetReader for ConsistentProvider<N> {
         let mut count = 0;
         if let Some(head_block) = &self.head_block {
             for state in head_block.chain() {
-                count += state
-                    .block_ref()
-                    .execution_output
-                    .state
-                    .reverts
-                    .clone()
-                    .to_plain_state_reverts()
-                    .accounts
-                    .len();
+                count +=
+                    state.block_ref().execution_reverts().to_plain_state_reverts().accounts.len();
             }
         }
 
@@ -1682,7 +1753,7 @@ impl<N: ProviderNodeTypes> StateReader for ConsistentProvider<N> {
         block: BlockNumber,
     ) -> ProviderResult<Option<ExecutionOutcome<Self::Receipt>>> {
         if let Some(state) = self.head_block.as_ref().and_then(|b| b.block_on_chain(block.into())) {
-            let state = state.block_ref().execution_outcome().clone();
+            let state = state.block_ref().rehydrated_execution_outcome().into_owned();
             Ok(Some(ExecutionOutcome::from((state, block))))
         } else {
             Self::get_state(self, block..=block)
@@ -1694,7 +1765,8 @@ impl<N: ProviderNodeTypes> StateReader for ConsistentProvider<N> {
 mod tests {
     use crate::{
         providers::blockchain_provider::BlockchainProvider,
-        test_utils::create_test_provider_factory, BlockWriter,
+        test_utils::create_test_provider_factory, BlockNumReader, BlockWriter, HeaderProvider,
+        TransactionsProvider,
     };
     use alloy_eips::BlockHashOrNumber;
     use alloy_primitives::B256;
@@ -1707,7 +1779,8 @@ mod tests {
     use reth_primitives_traits::{RecoveredBlock, SealedBlock};
     use reth_storage_api::{BlockReader, BlockSource, ChangeSetReader};
     use reth_testing_utils::generators::{
-        self, random_block_range, random_changeset_range, random_eoa_accounts, BlockRangeParams,
+        self, random_block, random_block_range, random_changeset_range, random_eoa_accounts,
+        BlockParams, BlockRangeParams,
     };
     use revm_database::BundleState;
     use std::{
@@ -1861,6 +1934,72 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_reorged_out_database_blocks_are_not_served() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+        let factory = create_test_provider_factory();
+
+        let database_blocks = random_block_range(
+            &mut rng,
+            0..=5,
+            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 1..2, ..Default::default() },
+        );
+        let provider_rw = factory.provider_rw()?;
+        for block in &database_blocks {
+            provider_rw.insert_block(&block.clone().try_recover()?)?;
+        }
+        provider_rw.commit()?;
+
+        let provider = BlockchainProvider::new(factory)?;
+
+        // the in-memory canonical chain replaced the last persisted block with a sibling, and the
+        // database is not unwound yet
+        let reorged_out = database_blocks.last().unwrap();
+        let sibling = random_block(
+            &mut rng,
+            reorged_out.number,
+            BlockParams {
+                parent: Some(reorged_out.parent_hash),
+                tx_count: Some(1),
+                ..Default::default()
+            },
+        );
+        provider.canonical_in_memory_state.update_chain(NewCanonicalChain::Commit {
+            new: vec![ExecutedBlock {
+                recovered_block: Arc::new(sibling.clone().try_recover()?),
+                ..Default::default()
+            }],
+        });
+        let consistent_provider = provider.consistent_provider()?;
+
+        // lookups by number are served by the in-memory chain
+        assert_eq!(consistent_provider.block_hash(reorged_out.number)?, Some(sibling.hash()));
+        assert_eq!(
+            consistent_provider.header_by_number(reorged_out.number)?,
+            Some(sibling.header().clone())
+        );
+
+        // the reorged out block and its transactions are not served by hash
+        assert_eq!(consistent_provider.header(reorged_out.hash())?, None);
+        assert_eq!(consistent_provider.block_number(reorged_out.hash())?, None);
+        assert_eq!(
+            consistent_provider.find_block_by_hash(reorged_out.hash(), BlockSource::Canonical)?,
+            None
+        );
+        let reorged_out_tx = *reorged_out.body().transactions[0].tx_hash();
+        assert_eq!(consistent_provider.transaction_id(reorged_out_tx)?, None);
+        assert_eq!(consistent_provider.transaction_by_hash(reorged_out_tx)?, None);
+        assert_eq!(consistent_provider.transaction_by_hash_with_meta(reorged_out_tx)?, None);
+        assert!(consistent_provider.history_by_block_hash_ref(reorged_out.hash()).is_err());
+
+        // the persisted fork point is still served from the database
+        let fork_point = &database_blocks[database_blocks.len() - 2];
+        assert_eq!(consistent_provider.block_number(fork_point.hash())?, Some(fork_point.number));
+        assert!(consistent_provider.history_by_block_hash_ref(fork_point.hash()).is_ok());
+
+        Ok(())
+    }
+
     #[test]
     fn test_block_reader_block() -> eyre::Result<()> {
         // Initialize random number generator and provider factory
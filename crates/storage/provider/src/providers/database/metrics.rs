@@ -42,6 +42,9 @@ pub(crate) enum Action {
     InsertTransactionBlocks,
     InsertTransactionSenders,
     InsertTransactionHashNumbers,
+    InsertAccountChangeSets,
+    InsertStorageChangeSets,
+    InsertReceipts,
 }
 
 /// Database provider metrics
@@ -68,6 +71,12 @@ pub(crate) struct DatabaseProviderMetrics {
     insert_transaction_senders: Histogram,
     /// Duration of insert transaction hash numbers
     insert_transaction_hash_numbers: Histogram,
+    /// Duration of insert account changesets
+    insert_account_changesets: Histogram,
+    /// Duration of insert storage changesets
+    insert_storage_changesets: Histogram,
+    /// Duration of insert receipts
+    insert_receipts: Histogram,
     /// Duration of `save_blocks`
     save_blocks_total: Histogram,
     /// Duration of MDBX work in `save_blocks`
@@ -138,6 +147,9 @@ impl DatabaseProviderMetrics {
             Action::InsertTransactionHashNumbers => {
                 self.insert_transaction_hash_numbers.record(duration)
             }
+            Action::InsertAccountChangeSets => self.insert_account_changesets.record(duration),
+            Action::InsertStorageChangeSets => self.insert_storage_changesets.record(duration),
+            Action::InsertReceipts => self.insert_receipts.record(duration),
         }
     }
 
@@ -1,7 +1,7 @@
 use crate::{
     providers::{
-        state::latest::LatestStateProvider, NodeTypesForProvider, RocksDBProvider,
-        StaticFileProvider, StaticFileProviderRWRefMut,
+        state::latest::LatestStateProvider, HistoricalStateCache, NodeTypesForProvider,
+        RocksDBProvider, StaticFileProvider, StaticFileProviderRWRefMut,
     },
     to_range,
     traits::{BlockSource, ReceiptProvider},
@@ -12,24 +12,29 @@ use crate::{
     TransactionVariant, TransactionsProvider,
 };
 use alloy_consensus::transaction::TransactionMeta;
-use alloy_eips::BlockHashOrNumber;
-use alloy_primitives::{Address, BlockHash, BlockNumber, TxHash, TxNumber, B256};
+use alloy_eips::{eip4895::Withdrawals, BlockHashOrNumber};
+use alloy_primitives::{Address, BlockHash, BlockNumber, TxHash, TxNumber, B256, U256};
 use core::fmt;
 use parking_lot::RwLock;
 use reth_chainspec::ChainInfo;
 use reth_db::{init_db, mdbx::DatabaseArguments, DatabaseEnv};
-use reth_db_api::{database::Database, models::StoredBlockBodyIndices};
+use reth_db_api::{
+    database::Database,
+    models::{AccountDestruction, ContractCreation, ReorgRecord, StoredBlockBodyIndices},
+};
 use reth_errors::{RethError, RethResult};
 use reth_node_types::{
     BlockTy, HeaderTy, NodeTypesWithDB, NodeTypesWithDBAdapter, ReceiptTy, TxTy,
 };
-use reth_primitives_traits::{RecoveredBlock, SealedHeader};
+use reth_primitives_traits::{Account, RecoveredBlock, SealedHeader};
 use reth_prune_types::{PruneCheckpoint, PruneModes, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_static_file_types::StaticFileSegment;
 use reth_storage_api::{
-    BlockBodyIndicesProvider, NodePrimitivesProvider, StorageSettings, StorageSettingsCache,
-    TryIntoHistoricalStateProvider,
+    AccountDestructionsReader, AddressTransactionsReader, BlockBodyIndicesProvider,
+    ContractCreationsReader, HistoryReader, LogsBloomIndexReader, NodePrimitivesProvider,
+    ReceiptBlockOffsets, ReorgHistoryReader, StorageSettings, StorageSettingsCache,
+    TryIntoHistoricalStateProvider, WithdrawalsProvider,
 };
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::HashedPostState;
@@ -59,6 +64,9 @@ mod metrics;
 mod chain;
 pub use chain::*;
 
+mod write_batch;
+pub use write_batch::BlockWriteBatch;
+
 /// A common provider that fetches data from a database or static file.
 ///
 /// This provider implements most provider or provider factory traits.
@@ -69,8 +77,8 @@ pub struct ProviderFactory<N: NodeTypesWithDB> {
     chain_spec: Arc<N::ChainSpec>,
     /// Static File Provider
     static_file_provider: StaticFileProvider<N::Primitives>,
-    /// Optional pruning configuration
-    prune_modes: PruneModes,
+    /// Optional pruning configuration, shared by all clones of the factory
+    prune_modes: Arc<RwLock<PruneModes>>,
     /// The node storage handler.
     storage: Arc<N::Storage>,
     /// Storage configuration settings for this node
@@ -79,6 +87,8 @@ pub struct ProviderFactory<N: NodeTypesWithDB> {
     rocksdb_provider: RocksDBProvider,
     /// Changeset cache for trie unwinding
     changeset_cache: ChangesetCache,
+    /// Cache shared by the historical state providers
+    historical_state_cache: HistoricalStateCache,
 }
 
 impl<N: NodeTypesForProvider> ProviderFactory<NodeTypesWithDBAdapter<N, Arc<DatabaseEnv>>> {
@@ -118,28 +128,54 @@ impl<N: ProviderNodeTypes> ProviderFactory<N> {
             db,
             chain_spec,
             static_file_provider,
-            prune_modes: PruneModes::default(),
+            prune_modes: Default::default(),
             storage: Default::default(),
             storage_settings: Arc::new(RwLock::new(storage_settings)),
             rocksdb_provider,
             changeset_cache: ChangesetCache::new(),
+            historical_state_cache: HistoricalStateCache::default(),
         })
     }
 }
 
 impl<N: NodeTypesWithDB> ProviderFactory<N> {
     /// Sets the pruning configuration for an existing [`ProviderFactory`].
+    ///
+    /// Unlike [`Self::set_prune_modes`], this doesn't affect other clones of the factory.
     pub fn with_prune_modes(mut self, prune_modes: PruneModes) -> Self {
-        self.prune_modes = prune_modes;
+        self.prune_modes = Arc::new(RwLock::new(prune_modes));
         self
     }
 
+    /// Returns the pruning configuration of the providers created by the factory.
+    pub fn prune_modes(&self) -> PruneModes {
+        self.prune_modes.read().clone()
+    }
+
+    /// Updates the pruning configuration of this factory and all of its clones.
+    ///
+    /// Providers created afterwards use the new configuration, e.g. to decide which data is
+    /// written to static files.
+    pub fn set_prune_modes(&self, prune_modes: PruneModes) {
+        *self.prune_modes.write() = prune_modes;
+    }
+
     /// Sets the changeset cache for an existing [`ProviderFactory`].
     pub fn with_changeset_cache(mut self, changeset_cache: ChangesetCache) -> Self {
         self.changeset_cache = changeset_cache;
         self
     }
 
+    /// Sets the cache shared by the historical state providers for an existing
+    /// [`ProviderFactory`].
+    pub fn with_historical_state_cache(
+        mut self,
+        historical_state_cache: HistoricalStateCache,
+    ) -> Self {
+        self.historical_state_cache = historical_state_cache;
+        self
+    }
+
     /// Returns reference to the underlying database.
     pub const fn db_ref(&self) -> &N::DB {
         &self.db
@@ -211,12 +247,13 @@ impl<N: ProviderNodeTypes> ProviderFactory<N> {
             self.db.tx()?,
             self.chain_spec.clone(),
             self.static_file_provider.clone(),
-            self.prune_modes.clone(),
+            self.prune_modes(),
             self.storage.clone(),
             self.storage_settings.clone(),
             self.rocksdb_provider.clone(),
             self.changeset_cache.clone(),
-        ))
+        )
+        .with_historical_state_cache(self.historical_state_cache.clone()))
     }
 
     /// Returns a provider with a created `DbTxMut` inside, which allows fetching and updating
@@ -229,7 +266,7 @@ impl<N: ProviderNodeTypes> ProviderFactory<N> {
             self.db.tx_mut()?,
             self.chain_spec.clone(),
             self.static_file_provider.clone(),
-            self.prune_modes.clone(),
+            self.prune_modes(),
             self.storage.clone(),
             self.storage_settings.clone(),
             self.rocksdb_provider.clone(),
@@ -248,7 +285,7 @@ impl<N: ProviderNodeTypes> ProviderFactory<N> {
             self.db.tx_mut()?,
             self.chain_spec.clone(),
             self.static_file_provider.clone(),
-            self.prune_modes.clone(),
+            self.prune_modes(),
             self.storage.clone(),
             self.storage_settings.clone(),
             self.rocksdb_provider.clone(),
@@ -555,6 +592,10 @@ impl<N: ProviderNodeTypes> ReceiptProvider for ProviderFactory<N> {
         )
     }
 
+    fn receipt_block_offsets(&self, id: TxNumber) -> ProviderResult<Option<ReceiptBlockOffsets>> {
+        self.static_file_provider.receipt_block_offsets(id)
+    }
+
     fn receipt_by_hash(&self, hash: TxHash) -> ProviderResult<Option<Self::Receipt>> {
         self.provider()?.receipt_by_hash(hash)
     }
@@ -603,6 +644,15 @@ impl<N: ProviderNodeTypes> BlockBodyIndicesProvider for ProviderFactory<N> {
     }
 }
 
+impl<N: ProviderNodeTypes> WithdrawalsProvider for ProviderFactory<N> {
+    fn withdrawals_by_block_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<Withdrawals>> {
+        self.provider()?.withdrawals_by_block_range(range)
+    }
+}
+
 impl<N: ProviderNodeTypes> StageCheckpointReader for ProviderFactory<N> {
     fn get_stage_checkpoint(&self, id: StageId) -> ProviderResult<Option<StageCheckpoint>> {
         self.provider()?.get_stage_checkpoint(id)
@@ -637,6 +687,63 @@ impl<N: ProviderNodeTypes> PruneCheckpointReader for ProviderFactory<N> {
     }
 }
 
+impl<N: ProviderNodeTypes> ReorgHistoryReader for ProviderFactory<N> {
+    fn reorg_history(&self, limit: usize) -> ProviderResult<Vec<ReorgRecord>> {
+        self.provider()?.reorg_history(limit)
+    }
+}
+
+impl<N: ProviderNodeTypes> HistoryReader for ProviderFactory<N> {
+    fn account_history_range(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, Option<Account>)>> {
+        self.provider()?.account_history_range(address, range)
+    }
+
+    fn storage_history_range(
+        &self,
+        address: Address,
+        storage_key: B256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, U256)>> {
+        self.provider()?.storage_history_range(address, storage_key, range)
+    }
+}
+
+impl<N: ProviderNodeTypes> AddressTransactionsReader for ProviderFactory<N> {
+    fn address_transactions(
+        &self,
+        addresses: &[Address],
+        block_range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Option<Vec<TxNumber>>> {
+        self.provider()?.address_transactions(addresses, block_range)
+    }
+}
+
+impl<N: ProviderNodeTypes> LogsBloomIndexReader for ProviderFactory<N> {
+    fn block_ranges_with_log_addresses(
+        &self,
+        addresses: &[Address],
+        block_range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<RangeInclusive<BlockNumber>>> {
+        self.static_file_provider.block_ranges_with_log_addresses(addresses, block_range)
+    }
+}
+
+impl<N: ProviderNodeTypes> ContractCreationsReader for ProviderFactory<N> {
+    fn contract_creation(&self, address: Address) -> ProviderResult<Option<ContractCreation>> {
+        self.provider()?.contract_creation(address)
+    }
+}
+
+impl<N: ProviderNodeTypes> AccountDestructionsReader for ProviderFactory<N> {
+    fn account_destructions(&self, address: Address) -> ProviderResult<Vec<AccountDestruction>> {
+        self.provider()?.account_destructions(address)
+    }
+}
+
 impl<N: ProviderNodeTypes> HashedPostStateProvider for ProviderFactory<N> {
     fn hashed_post_state(&self, bundle_state: &BundleState) -> HashedPostState {
         HashedPostState::from_bundle_state::<KeccakKeyHasher>(bundle_state.state())
@@ -663,16 +770,18 @@ where
             storage_settings,
             rocksdb_provider,
             changeset_cache,
+            historical_state_cache,
         } = self;
         f.debug_struct("ProviderFactory")
             .field("db", &db)
             .field("chain_spec", &chain_spec)
             .field("static_file_provider", &static_file_provider)
-            .field("prune_modes", &prune_modes)
+            .field("prune_modes", &*prune_modes.read())
             .field("storage", &storage)
             .field("storage_settings", &*storage_settings.read())
             .field("rocksdb_provider", &rocksdb_provider)
             .field("changeset_cache", &changeset_cache)
+            .field("historical_state_cache", &historical_state_cache)
             .finish()
     }
 }
@@ -688,6 +797,7 @@ impl<N: NodeTypesWithDB> Clone for ProviderFactory<N> {
             storage_settings: self.storage_settings.clone(),
             rocksdb_provider: self.rocksdb_provider.clone(),
             changeset_cache: self.changeset_cache.clone(),
+            historical_state_cache: self.historical_state_cache.clone(),
         }
     }
 }
@@ -796,6 +906,26 @@ mod tests {
         }
     }
 
+    #[test]
+    fn set_prune_modes_updates_clones() {
+        let block = TEST_BLOCK.clone();
+        let prune_modes =
+            PruneModes { sender_recovery: Some(PruneMode::Full), ..PruneModes::default() };
+
+        let factory = create_test_provider_factory();
+        factory.clone().set_prune_modes(prune_modes.clone());
+        assert_eq!(factory.prune_modes(), prune_modes);
+
+        let provider = factory.provider_rw().unwrap();
+        assert_matches!(provider.insert_block(&block.clone().try_recover().unwrap()), Ok(_));
+        assert_matches!(provider.transaction_sender(0), Ok(None));
+
+        // a factory with its own prune modes isn't affected
+        let other = factory.clone().with_prune_modes(PruneModes::default());
+        factory.set_prune_modes(PruneModes::all());
+        assert_eq!(other.prune_modes(), PruneModes::default());
+    }
+
     #[test]
     fn take_block_transaction_range_recover_senders() {
         let mut rng = generators::rng();
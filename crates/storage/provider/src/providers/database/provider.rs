@@ -1,10 +1,10 @@
 use crate::{
     changesets_utils::StorageRevertsIter,
     providers::{
-        database::{chain::ChainStorage, metrics},
+        database::{chain::ChainStorage, metrics, BlockWriteBatch},
         rocksdb::{PendingRocksDBBatches, RocksDBProvider, RocksDBWriteCtx},
         static_file::{StaticFileWriteCtx, StaticFileWriter},
-        NodeTypesForProvider, StaticFileProvider,
+        HistoricalStateCache, NodeTypesForProvider, StaticFileProvider,
     },
     to_range,
     traits::{
@@ -22,13 +22,13 @@ use crate::{
 };
 use alloy_consensus::{
     transaction::{SignerRecoverable, TransactionMeta, TxHashRef},
-    BlockHeader, TxReceipt,
+    BlockHeader, Transaction as _, TxReceipt,
 };
-use alloy_eips::BlockHashOrNumber;
+use alloy_eips::{eip4895::Withdrawals, BlockHashOrNumber, BlockNumHash};
 use alloy_primitives::{
     keccak256,
     map::{hash_map, HashMap, HashSet},
-    Address, BlockHash, BlockNumber, TxHash, TxNumber, B256,
+    Address, BlockHash, BlockNumber, TxHash, TxKind, TxNumber, B256, U256,
 };
 use itertools::Itertools;
 use parking_lot::RwLock;
@@ -39,8 +39,9 @@ use reth_db_api::{
     cursor::{DbCursorRO, DbCursorRW, DbDupCursorRO, DbDupCursorRW},
     database::Database,
     models::{
-        sharded_key, storage_sharded_key::StorageShardedKey, AccountBeforeTx, BlockNumberAddress,
-        ShardedKey, StorageBeforeTx, StorageSettings, StoredBlockBodyIndices,
+        sharded_key, storage_sharded_key::StorageShardedKey, AccountBeforeTx, AccountDestruction,
+        BlockNumberAddress, ChainStatsRecord, ContractCreation, ReorgRecord, ShardedKey,
+        StorageBeforeTx, StorageSettings, StoredBlockBodyIndices,
     },
     table::Table,
     tables,
@@ -58,9 +59,13 @@ use reth_prune_types::{
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_static_file_types::StaticFileSegment;
 use reth_storage_api::{
-    BlockBodyIndicesProvider, BlockBodyReader, MetadataProvider, MetadataWriter,
-    NodePrimitivesProvider, StateProvider, StateWriteConfig, StorageChangeSetReader,
-    StorageSettingsCache, TryIntoHistoricalStateProvider, WriteStateInput,
+    AccountDestructionsReader, AccountDestructionsWriter, AddressTransactionsReader,
+    AddressTransactionsWriter, BlockBodyIndicesProvider, BlockBodyReader, ChainStatsStorageReader,
+    ChainStatsStorageWriter, ChainStatsTable, ContractCreationsReader, ContractCreationsWriter,
+    HistoryReader, MetadataProvider, MetadataWriter, NodePrimitivesProvider, ReceiptBlockOffsets,
+    ReorgHistoryReader, ReorgHistoryWriter, StateProvider, StateWriteConfig,
+    StorageChangeSetReader, StorageSettingsCache, TryIntoHistoricalStateProvider,
+    WithdrawalsProvider, WriteStateInput,
 };
 use reth_storage_errors::provider::{ProviderResult, StaticFileWriterError};
 use reth_trie::{
@@ -82,6 +87,10 @@ use std::{
 };
 use tracing::{debug, instrument, trace};
 
+/// Maximum number of blocks whose receipts are moved to static files by a single call to
+/// [`DatabaseProvider::move_cold_receipts_to_static_files`].
+const MAX_COLD_RECEIPTS_BLOCKS: u64 = 10_000;
+
 /// Determines the commit order for database operations.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum CommitOrder {
@@ -199,6 +208,8 @@ pub struct DatabaseProvider<TX, N: NodeTypes> {
     rocksdb_provider: RocksDBProvider,
     /// Changeset cache for trie unwinding
     changeset_cache: ChangesetCache,
+    /// Cache shared by the historical state providers created from this provider
+    historical_state_cache: Option<HistoricalStateCache>,
     /// Pending `RocksDB` batches to be committed at provider commit time.
     #[cfg_attr(not(all(unix, feature = "rocksdb")), allow(dead_code))]
     pending_rocksdb_batches: PendingRocksDBBatches,
@@ -221,6 +232,7 @@ impl<TX: Debug, N: NodeTypes> Debug for DatabaseProvider<TX, N> {
             .field("storage_settings", &self.storage_settings)
             .field("rocksdb_provider", &self.rocksdb_provider)
             .field("changeset_cache", &self.changeset_cache)
+            .field("historical_state_cache", &self.historical_state_cache)
             .field("pending_rocksdb_batches", &"<pending batches>")
             .field("commit_order", &self.commit_order)
             .field("minimum_pruning_distance", &self.minimum_pruning_distance)
@@ -233,6 +245,13 @@ impl<TX, N: NodeTypes> DatabaseProvider<TX, N> {
     pub const fn prune_modes_ref(&self) -> &PruneModes {
         &self.prune_modes
     }
+
+    /// Sets the cache that is shared by the historical state providers created from this
+    /// provider, see [`HistoricalStateCache`].
+    pub fn with_historical_state_cache(mut self, cache: HistoricalStateCache) -> Self {
+        self.historical_state_cache = Some(cache);
+        self
+    }
 }
 
 impl<TX: DbTx + 'static, N: NodeTypes> DatabaseProvider<TX, N> {
@@ -290,6 +309,22 @@ impl<TX: DbTx + 'static, N: NodeTypes> DatabaseProvider<TX, N> {
     pub fn set_prune_modes(&mut self, prune_modes: PruneModes) {
         self.prune_modes = prune_modes;
     }
+
+    /// Returns an error if the history of the given segment was pruned for the start of the
+    /// range, in which case the changes of the range can't be served completely.
+    fn ensure_history_available(
+        &self,
+        segment: PruneSegment,
+        range: &RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<()> {
+        if let Some(pruned) =
+            self.get_prune_checkpoint(segment)?.and_then(|checkpoint| checkpoint.block_number) &&
+            *range.start() <= pruned
+        {
+            return Err(ProviderError::StateAtBlockPruned(*range.start()))
+        }
+        Ok(())
+    }
 }
 
 impl<TX, N: NodeTypes> NodePrimitivesProvider for DatabaseProvider<TX, N> {
@@ -365,6 +400,7 @@ impl<TX: DbTxMut, N: NodeTypes> DatabaseProvider<TX, N> {
             storage_settings,
             rocksdb_provider,
             changeset_cache,
+            historical_state_cache: None,
             pending_rocksdb_batches: Default::default(),
             commit_order,
             minimum_pruning_distance: MINIMUM_PRUNING_DISTANCE,
@@ -503,11 +539,27 @@ impl<TX: DbTx + DbTxMut + 'static, N: NodeTypesForProvider> DatabaseProvider<TX,
     ///
     /// Use [`SaveBlocksMode::Full`] for production (includes receipts, state, trie).
     /// Use [`SaveBlocksMode::BlocksOnly`] for block structure only (used by `insert_block`).
-    #[instrument(level = "debug", target = "providers::db", skip_all, fields(block_count = blocks.len()))]
+    ///
+    /// See [`Self::save_blocks_with_batch`] to reuse the buffers of the MDBX writes across calls.
     pub fn save_blocks(
         &self,
         blocks: Vec<ExecutedBlock<N::Primitives>>,
         save_mode: SaveBlocksMode,
+    ) -> ProviderResult<()> {
+        self.save_blocks_with_batch(blocks, save_mode, &mut BlockWriteBatch::default())
+    }
+
+    /// Writes executed blocks and state to storage, see [`Self::save_blocks`].
+    ///
+    /// Block indices, senders, changesets and receipts destined for MDBX are accumulated in the
+    /// given [`BlockWriteBatch`] and written once per table for all blocks, instead of block by
+    /// block.
+    #[instrument(level = "debug", target = "providers::db", skip_all, fields(block_count = blocks.len()))]
+    pub fn save_blocks_with_batch(
+        &self,
+        blocks: Vec<ExecutedBlock<N::Primitives>>,
+        save_mode: SaveBlocksMode,
+        batch: &mut BlockWriteBatch<ReceiptTy<N>>,
     ) -> ProviderResult<()> {
         if blocks.is_empty() {
             debug!(target: "providers::db", "Attempted to write empty block range");
@@ -521,6 +573,9 @@ impl<TX: DbTx + DbTxMut + 'static, N: NodeTypesForProvider> DatabaseProvider<TX,
 
         debug!(target: "providers::db", block_count, "Writing blocks and execution data to storage");
 
+        // Changesets are written from the reverts, which compaction of in-memory blocks drops.
+        let blocks = blocks.into_iter().map(ExecutedBlock::into_rehydrated).collect::<Vec<_>>();
+
         // Compute tx_nums upfront (both threads need these)
         let first_tx_num = self
             .tx
@@ -604,24 +659,39 @@ impl<TX: DbTx + DbTxMut + 'static, N: NodeTypesForProvider> DatabaseProvider<TX,
                 );
             }
 
-            for (i, block) in blocks.iter().enumerate() {
-                let recovered_block = block.recovered_block();
-
-                let start = Instant::now();
-                self.insert_block_mdbx_only(recovered_block, tx_nums[i])?;
-                timings.insert_block += start.elapsed();
+            // Start from a clean batch, in case a previous call failed before flushing it.
+            batch.clear();
 
-                if save_mode.with_state() {
-                    let execution_output = block.execution_outcome();
+            let start = Instant::now();
+            for (i, block) in blocks.iter().enumerate() {
+                self.insert_block_mdbx_only(block.recovered_block(), tx_nums[i], batch);
+            }
+            // Write the buffered block data in a single pass per table. This has to happen before
+            // the state is written, because of the receipt lookup.
+            batch.flush(&self.tx, &self.metrics)?;
+
+            // MDBX: Ommers/Withdrawals
+            self.storage.writer().write_block_bodies(
+                self,
+                blocks
+                    .iter()
+                    .map(|block| {
+                        let block = block.recovered_block();
+                        (block.number(), Some(block.body()))
+                    })
+                    .collect(),
+            )?;
+            timings.insert_block += start.elapsed();
 
-                    // Write state and changesets to the database.
-                    // Must be written after blocks because of the receipt lookup.
+            if save_mode.with_state() {
+                for block in &blocks {
+                    // Write state to the database and buffer changesets and receipts.
                     // Skip receipts/account changesets if they're being written to static files.
                     let start = Instant::now();
-                    self.write_state(
+                    self.write_state_with_batch(
                         WriteStateInput::Single {
-                            outcome: execution_output,
-                            block: recovered_block.number(),
+                            outcome: block.execution_outcome(),
+                            block: block.recovered_block().number(),
                         },
                         OriginalValuesKnown::No,
                         StateWriteConfig {
@@ -629,9 +699,17 @@ impl<TX: DbTx + DbTxMut + 'static, N: NodeTypesForProvider> DatabaseProvider<TX,
                             write_account_changesets: !sf_ctx.write_account_changesets,
                             write_storage_changesets: !sf_ctx.write_storage_changesets,
                         },
+                        Some(&mut *batch),
                     )?;
                     timings.write_state += start.elapsed();
                 }
+
+                // Write the buffered changesets and receipts in a single pass per table. This has
+                // to happen before the history indices are updated, because they are built from
+                // the changesets.
+                let start = Instant::now();
+                batch.flush(&self.tx, &self.metrics)?;
+                timings.write_state += start.elapsed();
             }
 
             // Write all hashed state and trie updates in single batches.
@@ -660,6 +738,7 @@ impl<TX: DbTx + DbTxMut + 'static, N: NodeTypesForProvider> DatabaseProvider<TX,
             if save_mode.with_state() {
                 let start = Instant::now();
                 self.update_history_indices(first_number..=last_block_number)?;
+                self.index_address_transactions_of_blocks(&blocks, &tx_nums)?;
                 timings.update_history_indices = start.elapsed();
             }
 
@@ -690,67 +769,56 @@ impl<TX: DbTx + DbTxMut + 'static, N: NodeTypesForProvider> DatabaseProvider<TX,
         })
     }
 
-    /// Writes MDBX-only data for a block (indices, lookups, and senders if configured for MDBX).
+    /// Buffers MDBX-only data for a block (indices, lookups, and senders if configured for MDBX)
+    /// in the given [`BlockWriteBatch`].
     ///
-    /// SF data (headers, transactions, senders if SF, receipts if SF) must be written separately.
+    /// SF data (headers, transactions, senders if SF, receipts if SF) and block bodies must be
+    /// written separately.
     #[instrument(level = "debug", target = "providers::db", skip_all)]
     fn insert_block_mdbx_only(
         &self,
         block: &RecoveredBlock<BlockTy<N>>,
         first_tx_num: TxNumber,
-    ) -> ProviderResult<StoredBlockBodyIndices> {
+        batch: &mut BlockWriteBatch<ReceiptTy<N>>,
+    ) {
         if self.prune_modes.sender_recovery.is_none_or(|m| !m.is_full()) &&
             EitherWriterDestination::senders(self).is_database()
         {
-            let start = Instant::now();
-            let tx_nums_iter = std::iter::successors(Some(first_tx_num), |n| Some(n + 1));
-            let mut cursor = self.tx.cursor_write::<tables::TransactionSenders>()?;
-            for (tx_num, sender) in tx_nums_iter.zip(block.senders_iter().copied()) {
-                cursor.append(tx_num, &sender)?;
-            }
-            self.metrics
-                .record_duration(metrics::Action::InsertTransactionSenders, start.elapsed());
+            batch.push_transaction_senders(first_tx_num, block.senders_iter().copied());
         }
 
         let block_number = block.number();
         let tx_count = block.body().transaction_count() as u64;
 
-        let start = Instant::now();
-        self.tx.put::<tables::HeaderNumbers>(block.hash(), block_number)?;
-        self.metrics.record_duration(metrics::Action::InsertHeaderNumbers, start.elapsed());
-
-        self.write_block_body_indices(block_number, block.body(), first_tx_num, tx_count)?;
-
-        Ok(StoredBlockBodyIndices { first_tx_num, tx_count })
+        batch.push_header_number(block.hash(), block_number);
+        batch.push_block_body_indices(
+            block_number,
+            StoredBlockBodyIndices { first_tx_num, tx_count },
+        );
     }
 
-    /// Writes MDBX block body indices (`BlockBodyIndices`, `TransactionBlocks`,
-    /// `Ommers`/`Withdrawals`).
-    fn write_block_body_indices(
-        &self,
-        block_number: BlockNumber,
-        body: &BodyTy<N>,
-        first_tx_num: TxNumber,
-        tx_count: u64,
-    ) -> ProviderResult<()> {
-        // MDBX: BlockBodyIndices
-        let start = Instant::now();
-        self.tx
-            .cursor_write::<tables::BlockBodyIndices>()?
-            .append(block_number, &StoredBlockBodyIndices { first_tx_num, tx_count })?;
-        self.metrics.record_duration(metrics::Action::InsertBlockBodyIndices, start.elapsed());
-
-        // MDBX: TransactionBlocks (last tx -> block mapping)
-        if tx_count > 0 {
-            let start = Instant::now();
-            self.tx
-                .cursor_write::<tables::TransactionBlocks>()?
-                .append(first_tx_num + tx_count - 1, &block_number)?;
-            self.metrics.record_duration(metrics::Action::InsertTransactionBlocks, start.elapsed());
+    /// Unwinds the optional indices recorded while executing the blocks, the contract creations
+    /// and account destructions, above the given block.
+    fn unwind_execution_indices_above(&self, block: BlockNumber) -> ProviderResult<()> {
+        if let Some(checkpoint) = self.get_stage_checkpoint(StageId::IndexContractCreations)? &&
+            checkpoint.block_number > block
+        {
+            self.unwind_contract_creations(block + 1..=checkpoint.block_number)?;
+            self.save_stage_checkpoint(
+                StageId::IndexContractCreations,
+                StageCheckpoint::new(block),
+            )?;
         }
 
-        // MDBX: Ommers/Withdrawals
-        self.storage.writer().write_block_bodies(self, vec![(block_number, Some(body))])?;
+        if let Some(checkpoint) = self.get_stage_checkpoint(StageId::IndexAccountDestructions)? &&
+            checkpoint.block_number > block
+        {
+            self.unwind_account_destructions(block + 1..=checkpoint.block_number)?;
+            self.save_stage_checkpoint(
+                StageId::IndexAccountDestructions,
+                StageCheckpoint::new(block),
+            )?;
+        }
 
         Ok(())
     }
@@ -811,7 +879,11 @@ impl<TX: DbTx + DbTxMut + 'static, N: NodeTypesForProvider> DatabaseProvider<TX,
         // iterate over block body and remove receipts
         self.remove::<tables::Receipts<ReceiptTy<N>>>(from_tx..)?;
 
-        if EitherWriter::receipts_destination(self).is_static_file() {
+        // Nodes keeping only the most recent receipts in the database may have older receipts in
+        // static files, which need to be unwound as well.
+        if EitherWriter::receipts_destination(self).is_static_file() ||
+            self.cached_storage_settings().receipts_hot_blocks().is_some()
+        {
             let static_file_receipt_num =
                 self.static_file_provider.get_highest_static_file_tx(StaticFileSegment::Receipts);
 
@@ -826,6 +898,84 @@ impl<TX: DbTx + DbTxMut + 'static, N: NodeTypesForProvider> DatabaseProvider<TX,
 
         Ok(())
     }
+
+    /// Moves receipts that fell out of the hot window configured by
+    /// [`StorageSettings::receipts_hot_blocks`] from the database to static files.
+    ///
+    /// Receipts are moved for blocks up to `tip - receipts_hot_blocks`, but never beyond the last
+    /// finalized block, so that blocks which can still be reorged always have their receipts in
+    /// the database. The receipts of at most 10,000 blocks are moved at once to keep the write
+    /// transaction small, the rest is moved by the following calls. Moved receipts are removed from
+    /// the database in the same transaction, receipts below the static files, e.g. written by
+    /// `reth backfill receipts`, are kept.
+    ///
+    /// Does nothing if receipts are not stored in static files, no hot window is configured, or
+    /// receipts are pruned.
+    pub fn move_cold_receipts_to_static_files(&self, tip: BlockNumber) -> ProviderResult<()> {
+        self.move_cold_receipts_to_static_files_with_limit(tip, MAX_COLD_RECEIPTS_BLOCKS)
+    }
+
+    /// Moves the receipts of at most `max_blocks` blocks, see
+    /// [`Self::move_cold_receipts_to_static_files`].
+    fn move_cold_receipts_to_static_files_with_limit(
+        &self,
+        tip: BlockNumber,
+        max_blocks: u64,
+    ) -> ProviderResult<()> {
+        let Some(hot_blocks) = self.cached_storage_settings().receipts_hot_blocks() else {
+            return Ok(())
+        };
+        if self.prune_modes.has_receipts_pruning() {
+            return Ok(())
+        }
+
+        let Some(mut to_block) = tip.checked_sub(hot_blocks) else { return Ok(()) };
+        if let Some(finalized) = self.last_finalized_block_number()? {
+            to_block = to_block.min(finalized);
+        }
+
+        let from_block = self
+            .static_file_provider
+            .get_highest_static_file_block(StaticFileSegment::Receipts)
+            .map_or(0, |block| block + 1);
+        let to_block = to_block.min(from_block.saturating_add(max_blocks.saturating_sub(1)));
+
+        if from_block > to_block {
+            return Ok(())
+        }
+
+        let start = Instant::now();
+        let mut writer = self.get_static_file_writer(from_block, StaticFileSegment::Receipts)?;
+        let mut receipts_cursor = self.tx.cursor_read::<tables::Receipts<ReceiptTy<N>>>()?;
+
+        let mut moved_txs: Option<Range<TxNumber>> = None;
+        for block in from_block..=to_block {
+            writer.increment_block(block)?;
+
+            let tx_num_range = self
+                .block_body_indices(block)?
+                .ok_or(ProviderError::BlockBodyIndicesNotFound(block))?
+                .tx_num_range();
+            let first_moved_tx = moved_txs.map_or(tx_num_range.start, |txs| txs.start);
+            moved_txs = Some(first_moved_tx..tx_num_range.end);
+
+            writer.append_receipts(
+                receipts_cursor
+                    .walk_range(tx_num_range)?
+                    .map(|result| result.map_err(ProviderError::from)),
+            )?;
+        }
+
+        // Static files are committed before the database, so the moved receipts are never missing
+        // from both tiers
+        if let Some(moved_txs) = moved_txs {
+            self.remove::<tables::Receipts<ReceiptTy<N>>>(moved_txs)?;
+        }
+
+        debug!(target: "providers::db", range = ?from_block..=to_block, elapsed = ?start.elapsed(), "Moved cold receipts to static files");
+
+        Ok(())
+    }
 }
 
 impl<TX: DbTx + 'static, N: NodeTypes> TryIntoHistoricalStateProvider for DatabaseProvider<TX, N> {
@@ -839,6 +989,13 @@ impl<TX: DbTx + 'static, N: NodeTypes> TryIntoHistoricalStateProvider for Databa
             return Ok(Box::new(LatestStateProvider::new(self)))
         }
 
+        let cached_reads = match &self.historical_state_cache {
+            Some(cache) => self
+                .block_hash(block_number)?
+                .and_then(|hash| cache.reads(BlockNumHash::new(block_number, hash))),
+            None => None,
+        };
+
         // +1 as the changeset that we want is the one that was applied after this block.
         block_number += 1;
 
@@ -848,6 +1005,9 @@ impl<TX: DbTx + 'static, N: NodeTypes> TryIntoHistoricalStateProvider for Databa
             self.get_prune_checkpoint(PruneSegment::StorageHistory)?;
 
         let mut state_provider = HistoricalStateProvider::new(self, block_number);
+        if let Some(cached_reads) = cached_reads {
+            state_provider = state_provider.with_cached_reads(cached_reads);
+        }
 
         // If we pruned account or storage history, we can't return state on every historical block.
         // Instead, we should cap it at the latest prune checkpoint for corresponding prune segment.
@@ -955,6 +1115,7 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
             storage_settings,
             rocksdb_provider,
             changeset_cache,
+            historical_state_cache: None,
             pending_rocksdb_batches: Default::default(),
             commit_order: CommitOrder::Normal,
             minimum_pruning_distance: MINIMUM_PRUNING_DISTANCE,
@@ -981,6 +1142,22 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
     pub fn chain_spec(&self) -> &N::ChainSpec {
         &self.chain_spec
     }
+
+    /// Returns the first transaction number stored in receipt static files, if the lowest receipt
+    /// static file doesn't start at genesis.
+    ///
+    /// Receipts below it were pruned from static files, and may have been backfilled into the
+    /// database since.
+    fn lowest_static_file_receipt(&self) -> ProviderResult<Option<TxNumber>> {
+        let Some(block) = self
+            .static_file_provider
+            .get_lowest_range_start(StaticFileSegment::Receipts)
+            .filter(|block| *block > 0)
+        else {
+            return Ok(None)
+        };
+        Ok(self.block_body_indices(block)?.map(|indices| indices.first_tx_num()))
+    }
 }
 
 impl<TX: DbTx + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
@@ -1471,6 +1648,57 @@ impl<TX: DbTx, N: NodeTypes> ChangeSetReader for DatabaseProvider<TX, N> {
     }
 }
 
+impl<TX: DbTx + 'static, N: NodeTypes> HistoryReader for DatabaseProvider<TX, N> {
+    fn account_history_range(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, Option<Account>)>> {
+        self.ensure_history_available(PruneSegment::AccountHistory, &range)?;
+        let blocks = self.with_rocksdb_tx(|tx_ref| {
+            let mut reader = EitherReader::new_accounts_history(self, tx_ref)?;
+            reader.account_history_blocks(address, range)
+        })?;
+
+        blocks
+            .into_iter()
+            .map(|block_number| {
+                let account_before = self
+                    .get_account_before_block(block_number, address)?
+                    .ok_or(ProviderError::AccountChangesetNotFound { block_number, address })?;
+                Ok((block_number, account_before.info))
+            })
+            .collect()
+    }
+
+    fn storage_history_range(
+        &self,
+        address: Address,
+        storage_key: B256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, U256)>> {
+        self.ensure_history_available(PruneSegment::StorageHistory, &range)?;
+        let blocks = self.with_rocksdb_tx(|tx_ref| {
+            let mut reader = EitherReader::new_storages_history(self, tx_ref)?;
+            reader.storage_history_blocks(address, storage_key, range)
+        })?;
+
+        blocks
+            .into_iter()
+            .map(|block_number| {
+                let entry = self
+                    .get_storage_before_block(block_number, address, storage_key)?
+                    .ok_or_else(|| ProviderError::StorageChangesetNotFound {
+                        block_number,
+                        address,
+                        storage_key: Box::new(storage_key),
+                    })?;
+                Ok((block_number, entry.value))
+            })
+            .collect()
+    }
+}
+
 impl<TX: DbTx + 'static, N: NodeTypesForProvider> HeaderSyncGapProvider
     for DatabaseProvider<TX, N>
 {
@@ -1896,6 +2124,9 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> ReceiptProvider for DatabasePr
     type Receipt = ReceiptTy<N>;
 
     fn receipt(&self, id: TxNumber) -> ProviderResult<Option<Self::Receipt>> {
+        if self.lowest_static_file_receipt()?.is_some_and(|lowest| id < lowest) {
+            return Ok(self.tx.get::<tables::Receipts<Self::Receipt>>(id)?)
+        }
         self.static_file_provider.get_with_static_file_or_database(
             StaticFileSegment::Receipts,
             id,
@@ -1904,6 +2135,10 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> ReceiptProvider for DatabasePr
         )
     }
 
+    fn receipt_block_offsets(&self, id: TxNumber) -> ProviderResult<Option<ReceiptBlockOffsets>> {
+        self.static_file_provider.receipt_block_offsets(id)
+    }
+
     fn receipt_by_hash(&self, hash: TxHash) -> ProviderResult<Option<Self::Receipt>> {
         if let Some(id) = self.transaction_id(hash)? {
             self.receipt(id)
@@ -1933,13 +2168,29 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> ReceiptProvider for DatabasePr
         &self,
         range: impl RangeBounds<TxNumber>,
     ) -> ProviderResult<Vec<Self::Receipt>> {
-        self.static_file_provider.get_range_with_static_file_or_database(
+        let mut range = to_range(range);
+        let mut receipts = Vec::new();
+        if let Some(lowest) = self.lowest_static_file_receipt()? &&
+            range.start < lowest
+        {
+            let end = range.end.min(lowest);
+            receipts.extend(
+                self.cursor_read_collect::<tables::Receipts<Self::Receipt>>(range.start..end)?,
+            );
+            range.start = end;
+        }
+        if range.is_empty() {
+            return Ok(receipts)
+        }
+
+        receipts.extend(self.static_file_provider.get_range_with_static_file_or_database(
             StaticFileSegment::Receipts,
-            to_range(range),
+            range,
             |static_file, range, _| static_file.receipts_by_tx_range(range),
             |range, _| self.cursor_read_collect::<tables::Receipts<Self::Receipt>>(range),
             |_| true,
-        )
+        )?);
+        Ok(receipts)
     }
 
     fn receipts_by_block_range(
@@ -2014,6 +2265,25 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> BlockBodyIndicesProvider
     }
 }
 
+impl<TX: DbTx + 'static, N: NodeTypesForProvider> WithdrawalsProvider for DatabaseProvider<TX, N> {
+    fn withdrawals_by_block_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<Withdrawals>> {
+        // The bodies are read without their transactions, the chain storage only reads the rest
+        // of the body, e.g. the withdrawals.
+        let headers = self.headers_range(range)?;
+        let inputs = headers.iter().map(|header| (header, Vec::new())).collect();
+        Ok(self
+            .storage
+            .reader()
+            .read_block_bodies(self, inputs)?
+            .into_iter()
+            .map(|body| body.withdrawals().cloned().unwrap_or_default())
+            .collect())
+    }
+}
+
 impl<TX: DbTx, N: NodeTypes> StageCheckpointReader for DatabaseProvider<TX, N> {
     fn get_stage_checkpoint(&self, id: StageId) -> ProviderResult<Option<StageCheckpoint>> {
         Ok(if let Some(encoded) = id.get_pre_encoded() {
@@ -2171,17 +2441,17 @@ impl<TX: DbTx + 'static, N: NodeTypes> StorageReader for DatabaseProvider<TX, N>
     }
 }
 
-impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
-    for DatabaseProvider<TX, N>
-{
-    type Receipt = ReceiptTy<N>;
-
-    #[instrument(level = "debug", target = "providers::db", skip_all)]
-    fn write_state<'a>(
+impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
+    /// Writes the state of an execution outcome, see [`StateWriter::write_state`].
+    ///
+    /// If a [`BlockWriteBatch`] is given, changesets and receipts destined for MDBX are buffered in
+    /// it instead of being written, and have to be flushed by the caller.
+    fn write_state_with_batch<'a>(
         &self,
-        execution_outcome: impl Into<WriteStateInput<'a, Self::Receipt>>,
+        execution_outcome: impl Into<WriteStateInput<'a, ReceiptTy<N>>>,
         is_value_known: OriginalValuesKnown,
         config: StateWriteConfig,
+        mut batch: Option<&mut BlockWriteBatch<ReceiptTy<N>>>,
     ) -> ProviderResult<()> {
         let execution_outcome = execution_outcome.into();
         let first_block = execution_outcome.first_block();
@@ -2189,7 +2459,7 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
         let (plain_state, reverts) =
             execution_outcome.state().to_plain_state_and_reverts(is_value_known);
 
-        self.write_state_reverts(reverts, first_block, config)?;
+        self.write_state_reverts_with_batch(reverts, first_block, config, batch.as_deref_mut())?;
         self.write_state_changes(plain_state)?;
 
         if !config.write_receipts {
@@ -2229,7 +2499,8 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
         // Database, OR if receipts_in_static_files is enabled but no receipts exist in static
         // files yet. Once receipts exist in static files, we must continue writing to maintain
         // continuity and have no gaps.
-        let prunable_receipts = (EitherWriter::receipts_destination(self).is_database() ||
+        let receipts_in_database = EitherWriter::receipts_destination(self).is_database();
+        let prunable_receipts = (receipts_in_database ||
             self.static_file_provider()
                 .get_highest_static_file_tx(StaticFileSegment::Receipts)
                 .is_none()) &&
@@ -2274,18 +2545,29 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
                     continue
                 }
 
-                receipts_writer.append_receipt(receipt_idx, receipt)?;
+                if let Some(batch) = batch.as_deref_mut() &&
+                    receipts_in_database
+                {
+                    batch.push_receipt(receipt_idx, receipt.clone());
+                } else {
+                    receipts_writer.append_receipt(receipt_idx, receipt)?;
+                }
             }
         }
 
         Ok(())
     }
 
-    fn write_state_reverts(
+    /// Writes the changesets of the state reverts, see [`StateWriter::write_state_reverts`].
+    ///
+    /// If a [`BlockWriteBatch`] is given, changesets destined for MDBX are buffered in it instead
+    /// of being written.
+    fn write_state_reverts_with_batch(
         &self,
         reverts: PlainStateReverts,
         first_block: BlockNumber,
         config: StateWriteConfig,
+        mut batch: Option<&mut BlockWriteBatch<ReceiptTy<N>>>,
     ) -> ProviderResult<()> {
         // Write storage changes
         if config.write_storage_changesets {
@@ -2333,6 +2615,12 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
                     }
                 }
 
+                if let Some(batch) = batch.as_deref_mut() &&
+                    EitherWriter::storage_changesets_destination(self).is_database()
+                {
+                    batch.push_storage_changeset(block_number, changeset);
+                    continue
+                }
                 let mut storage_changesets_writer =
                     EitherWriter::new_storage_changesets(self, block_number)?;
                 storage_changesets_writer.append_storage_changeset(block_number, changeset)?;
@@ -2351,6 +2639,12 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
                 .into_iter()
                 .map(|(address, info)| AccountBeforeTx { address, info: info.map(Into::into) })
                 .collect::<Vec<_>>();
+            if let Some(batch) = batch.as_deref_mut() &&
+                EitherWriter::account_changesets_destination(self).is_database()
+            {
+                batch.push_account_changeset(block_number, changeset);
+                continue
+            }
             let mut account_changesets_writer =
                 EitherWriter::new_account_changesets(self, block_number)?;
 
@@ -2359,6 +2653,31 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
 
         Ok(())
     }
+}
+
+impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
+    for DatabaseProvider<TX, N>
+{
+    type Receipt = ReceiptTy<N>;
+
+    #[instrument(level = "debug", target = "providers::db", skip_all)]
+    fn write_state<'a>(
+        &self,
+        execution_outcome: impl Into<WriteStateInput<'a, Self::Receipt>>,
+        is_value_known: OriginalValuesKnown,
+        config: StateWriteConfig,
+    ) -> ProviderResult<()> {
+        self.write_state_with_batch(execution_outcome, is_value_known, config, None)
+    }
+
+    fn write_state_reverts(
+        &self,
+        reverts: PlainStateReverts,
+        first_block: BlockNumber,
+        config: StateWriteConfig,
+    ) -> ProviderResult<()> {
+        self.write_state_reverts_with_batch(reverts, first_block, config, None)
+    }
 
     fn write_state_changes(&self, mut changes: StateChangeset) -> ProviderResult<()> {
         // sort all entries so they can be written to database in more performant way.
@@ -2483,6 +2802,8 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
     ///     2. Take the new value from the local state
     ///     3. Set the local state to the value in the changeset
     fn remove_state_above(&self, block: BlockNumber) -> ProviderResult<()> {
+        self.unwind_execution_indices_above(block)?;
+
         let range = block + 1..=self.last_block_number()?;
 
         if range.is_empty() {
@@ -2587,6 +2908,8 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
         &self,
         block: BlockNumber,
     ) -> ProviderResult<ExecutionOutcome<Self::Receipt>> {
+        self.unwind_execution_indices_above(block)?;
+
         let range = block + 1..=self.last_block_number()?;
 
         if range.is_empty() {
@@ -3275,6 +3598,19 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> BlockWriter
             .1
             .last_tx_num();
 
+        // Unwind the optional address transactions index while the transactions still exist.
+        if let Some(checkpoint) = self.get_stage_checkpoint(StageId::IndexTransactionAddresses)? &&
+            checkpoint.block_number > block
+        {
+            if unwind_tx_from <= unwind_tx_to {
+                self.unwind_address_transactions(unwind_tx_from..(unwind_tx_to + 1))?;
+            }
+            self.save_stage_checkpoint(
+                StageId::IndexTransactionAddresses,
+                StageCheckpoint::new(block),
+            )?;
+        }
+
         if unwind_tx_from <= unwind_tx_to {
             let hashes = self.transaction_hashes_by_range(unwind_tx_from..(unwind_tx_to + 1))?;
             self.with_rocksdb_batch(|batch| {
@@ -3579,6 +3915,432 @@ impl<TX: DbTxMut, N: NodeTypes> MetadataWriter for DatabaseProvider<TX, N> {
     }
 }
 
+impl<TX: DbTx, N: NodeTypes> ReorgHistoryReader for DatabaseProvider<TX, N> {
+    fn reorg_history(&self, limit: usize) -> ProviderResult<Vec<ReorgRecord>> {
+        self.tx
+            .cursor_read::<tables::ReorgHistory>()?
+            .walk_back(None)?
+            .take(limit)
+            .map(|entry| entry.map(|(_, record)| record).map_err(Into::into))
+            .collect()
+    }
+}
+
+impl<TX: DbTxMut + DbTx, N: NodeTypes> ReorgHistoryWriter for DatabaseProvider<TX, N> {
+    fn insert_reorg_record(&self, record: ReorgRecord) -> ProviderResult<()> {
+        let mut cursor = self.tx.cursor_write::<tables::ReorgHistory>()?;
+        let next = cursor.last()?.map_or(0, |(sequence, _)| sequence + 1);
+        Ok(cursor.append(next, &record)?)
+    }
+
+    fn prune_reorg_history(&self, keep: usize) -> ProviderResult<usize> {
+        let to_remove = self.tx.entries::<tables::ReorgHistory>()?.saturating_sub(keep);
+        let mut cursor = self.tx.cursor_write::<tables::ReorgHistory>()?;
+        let mut walker = cursor.walk(None)?;
+        for _ in 0..to_remove {
+            if walker.next().transpose()?.is_none() {
+                break
+            }
+            walker.delete_current()?;
+        }
+
+        Ok(to_remove)
+    }
+}
+
+impl<TX: DbTx, N: NodeTypes> ChainStatsStorageReader for DatabaseProvider<TX, N> {
+    fn chain_stats_head(&self) -> ProviderResult<Option<BlockNumber>> {
+        Ok(self.tx.get::<tables::ChainState>(tables::ChainStateKey::ChainStatsHead)?)
+    }
+
+    fn chain_stats(
+        &self,
+        table: ChainStatsTable,
+        limit: usize,
+    ) -> ProviderResult<Vec<(u64, ChainStatsRecord)>> {
+        fn read<T: Table<Key = u64, Value = ChainStatsRecord>>(
+            tx: &impl DbTx,
+            limit: usize,
+        ) -> ProviderResult<Vec<(u64, ChainStatsRecord)>> {
+            tx.cursor_read::<T>()?
+                .walk_back(None)?
+                .take(limit)
+                .map(|entry| entry.map_err(Into::into))
+                .collect()
+        }
+
+        match table {
+            ChainStatsTable::Days => read::<tables::ChainStatsDays>(&self.tx, limit),
+            ChainStatsTable::Epochs => read::<tables::ChainStatsEpochs>(&self.tx, limit),
+        }
+    }
+}
+
+impl<TX: DbTxMut + DbTx, N: NodeTypes> ChainStatsStorageWriter for DatabaseProvider<TX, N> {
+    fn save_chain_stats_head(&self, head: Option<BlockNumber>) -> ProviderResult<()> {
+        let key = tables::ChainStateKey::ChainStatsHead;
+        match head {
+            Some(head) => self.tx.put::<tables::ChainState>(key, head)?,
+            None => {
+                self.tx.delete::<tables::ChainState>(key, None)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn save_chain_stats(
+        &self,
+        table: ChainStatsTable,
+        period: u64,
+        record: Option<ChainStatsRecord>,
+    ) -> ProviderResult<()> {
+        fn write<T: Table<Key = u64, Value = ChainStatsRecord>>(
+            tx: &impl DbTxMut,
+            period: u64,
+            record: Option<ChainStatsRecord>,
+        ) -> ProviderResult<()> {
+            match record {
+                Some(record) => tx.put::<T>(period, record)?,
+                None => {
+                    tx.delete::<T>(period, None)?;
+                }
+            }
+            Ok(())
+        }
+
+        match table {
+            ChainStatsTable::Days => write::<tables::ChainStatsDays>(&self.tx, period, record),
+            ChainStatsTable::Epochs => write::<tables::ChainStatsEpochs>(&self.tx, period, record),
+        }
+    }
+
+    fn prune_chain_stats(&self, table: ChainStatsTable, keep: usize) -> ProviderResult<usize> {
+        fn prune<T: Table<Key = u64, Value = ChainStatsRecord>>(
+            tx: &(impl DbTx + DbTxMut),
+            keep: usize,
+        ) -> ProviderResult<usize> {
+            let to_remove = tx.entries::<T>()?.saturating_sub(keep);
+            let mut cursor = tx.cursor_write::<T>()?;
+            let mut walker = cursor.walk(None)?;
+            for _ in 0..to_remove {
+                if walker.next().transpose()?.is_none() {
+                    break
+                }
+                walker.delete_current()?;
+            }
+            Ok(to_remove)
+        }
+
+        match table {
+            ChainStatsTable::Days => prune::<tables::ChainStatsDays>(&self.tx, keep),
+            ChainStatsTable::Epochs => prune::<tables::ChainStatsEpochs>(&self.tx, keep),
+        }
+    }
+}
+
+impl<TX: DbTx + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
+    /// Returns the sender and recipient of each transaction in the given range, paired with the
+    /// transaction number.
+    ///
+    /// The recipient of a contract creation is the address of the created contract. Senders that
+    /// aren't stored are recovered from the transaction.
+    fn transaction_addresses_by_tx_range(
+        &self,
+        tx_range: Range<TxNumber>,
+    ) -> ProviderResult<Vec<(Address, TxNumber)>> {
+        let transactions = self.transactions_by_tx_range(tx_range.clone())?;
+        let known_senders: HashMap<TxNumber, Address> =
+            EitherReader::new_senders(self)?.senders_by_tx_range(tx_range.clone())?;
+
+        let mut addresses = Vec::with_capacity(transactions.len() * 2);
+        for (tx_num, tx) in tx_range.zip(transactions) {
+            let sender = match known_senders.get(&tx_num) {
+                Some(sender) => *sender,
+                None => tx.recover_signer_unchecked()?,
+            };
+            let (sender, recipient) = transaction_addresses(sender, &tx);
+            addresses.push((sender, tx_num));
+            addresses.extend(recipient.map(|recipient| (recipient, tx_num)));
+        }
+
+        Ok(addresses)
+    }
+}
+
+/// Returns the addresses a transaction is indexed under in [`tables::AddressTransactions`]: its
+/// sender and, if it's a different address, its recipient. The recipient of a contract creation is
+/// the address of the created contract.
+fn transaction_addresses<T: alloy_consensus::Transaction>(
+    sender: Address,
+    tx: &T,
+) -> (Address, Option<Address>) {
+    let recipient = match tx.kind() {
+        TxKind::Call(to) => to,
+        TxKind::Create => sender.create(tx.nonce()),
+    };
+    (sender, (recipient != sender).then_some(recipient))
+}
+
+impl<TX: DbTx + 'static, N: NodeTypesForProvider> AddressTransactionsReader
+    for DatabaseProvider<TX, N>
+{
+    fn address_transactions(
+        &self,
+        addresses: &[Address],
+        block_range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Option<Vec<TxNumber>>> {
+        let indexed_block = self
+            .get_stage_checkpoint(StageId::IndexTransactionAddresses)?
+            .map(|checkpoint| checkpoint.block_number);
+        if indexed_block.is_none_or(|indexed_block| indexed_block < *block_range.end()) {
+            return Ok(None)
+        }
+
+        let (Some(first_block), Some(last_block)) = (
+            self.block_body_indices(*block_range.start())?,
+            self.block_body_indices(*block_range.end())?,
+        ) else {
+            return Ok(None)
+        };
+        let tx_range = first_block.first_tx_num()..last_block.next_tx_num();
+
+        let mut cursor = self.tx.cursor_read::<tables::AddressTransactions>()?;
+        let mut transactions = Vec::new();
+        for &address in addresses {
+            let mut shard = cursor.seek(ShardedKey::new(address, tx_range.start))?;
+            while let Some((sharded_key, list)) = shard {
+                if sharded_key.key != address {
+                    break
+                }
+
+                transactions.extend(
+                    list.iter()
+                        .skip_while(|tx_num| *tx_num < tx_range.start)
+                        .take_while(|tx_num| *tx_num < tx_range.end),
+                );
+                if sharded_key.highest_block_number >= tx_range.end {
+                    break
+                }
+                shard = cursor.next()?;
+            }
+        }
+        transactions.sort_unstable();
+        transactions.dedup();
+
+        Ok(Some(transactions))
+    }
+}
+
+impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
+    /// Appends the given transaction numbers to the indices of their addresses in
+    /// [`tables::AddressTransactions`].
+    fn append_address_transactions(
+        &self,
+        addresses: impl IntoIterator<Item = (Address, TxNumber)>,
+    ) -> ProviderResult<()> {
+        let mut index_updates = BTreeMap::<Address, Vec<TxNumber>>::new();
+        for (address, tx_num) in addresses {
+            index_updates.entry(address).or_default().push(tx_num);
+        }
+
+        self.append_history_index::<_, tables::AddressTransactions>(index_updates, ShardedKey::new)
+    }
+
+    /// Extends the optional address transactions index with the given blocks, if it's enabled and
+    /// indexed up to the block before them.
+    ///
+    /// This keeps the index that is built by the `IndexTransactionAddresses` stage up to date with
+    /// the blocks persisted by the engine.
+    fn index_address_transactions_of_blocks(
+        &self,
+        blocks: &[ExecutedBlock<N::Primitives>],
+        tx_nums: &[TxNumber],
+    ) -> ProviderResult<()> {
+        let (Some(first), Some(last)) = (blocks.first(), blocks.last()) else { return Ok(()) };
+        let first_number = first.recovered_block().number();
+        let last_number = last.recovered_block().number();
+
+        if self
+            .get_stage_checkpoint(StageId::IndexTransactionAddresses)?
+            .is_none_or(|checkpoint| checkpoint.block_number + 1 != first_number)
+        {
+            return Ok(())
+        }
+
+        let mut addresses = Vec::new();
+        for (block, first_tx_num) in blocks.iter().zip(tx_nums) {
+            for ((sender, tx), tx_num) in
+                block.recovered_block().transactions_with_sender().zip(*first_tx_num..)
+            {
+                let (sender, recipient) = transaction_addresses(*sender, tx);
+                addresses.push((sender, tx_num));
+                addresses.extend(recipient.map(|recipient| (recipient, tx_num)));
+            }
+        }
+        self.append_address_transactions(addresses)?;
+
+        self.save_stage_checkpoint(
+            StageId::IndexTransactionAddresses,
+            StageCheckpoint::new(last_number),
+        )
+    }
+}
+
+impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> AddressTransactionsWriter
+    for DatabaseProvider<TX, N>
+{
+    fn index_address_transactions(&self, tx_range: Range<TxNumber>) -> ProviderResult<usize> {
+        self.append_address_transactions(
+            self.transaction_addresses_by_tx_range(tx_range.clone())?,
+        )?;
+        Ok(tx_range.end.saturating_sub(tx_range.start) as usize)
+    }
+
+    fn unwind_address_transactions(&self, tx_range: Range<TxNumber>) -> ProviderResult<usize> {
+        let addresses = self
+            .transaction_addresses_by_tx_range(tx_range.clone())?
+            .into_iter()
+            .map(|(address, _)| address)
+            .collect::<BTreeSet<_>>();
+
+        let mut cursor = self.tx.cursor_write::<tables::AddressTransactions>()?;
+        for address in addresses {
+            let partial_shard = unwind_history_shards::<_, tables::AddressTransactions, _>(
+                &mut cursor,
+                ShardedKey::last(address),
+                tx_range.start,
+                |sharded_key| sharded_key.key == address,
+            )?;
+
+            // Check the last returned partial shard.
+            // If it's not empty, the shard needs to be reinserted.
+            if !partial_shard.is_empty() {
+                cursor.insert(
+                    ShardedKey::last(address),
+                    &BlockNumberList::new_pre_sorted(partial_shard),
+                )?;
+            }
+        }
+
+        Ok(tx_range.end.saturating_sub(tx_range.start) as usize)
+    }
+}
+
+impl<TX: DbTx + 'static, N: NodeTypesForProvider> ContractCreationsReader
+    for DatabaseProvider<TX, N>
+{
+    fn contract_creation(&self, address: Address) -> ProviderResult<Option<ContractCreation>> {
+        let mut cursor = self.tx.cursor_dup_read::<tables::ContractCreations>()?;
+        if cursor.seek_exact(address)?.is_none() {
+            return Ok(None)
+        }
+        Ok(cursor.last_dup()?)
+    }
+}
+
+impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> ContractCreationsWriter
+    for DatabaseProvider<TX, N>
+{
+    fn insert_contract_creations(
+        &self,
+        creations: Vec<(Address, ContractCreation)>,
+    ) -> ProviderResult<usize> {
+        let mut cursor = self.tx.cursor_dup_write::<tables::ContractCreations>()?;
+        let mut blocks_cursor = self.tx.cursor_write::<tables::BlockContractCreations>()?;
+        for (address, creation) in &creations {
+            cursor.upsert(*address, creation)?;
+            blocks_cursor.upsert(
+                BlockNumberAddress((creation.block_number, *address)),
+                &creation.transaction_hash,
+            )?;
+        }
+
+        Ok(creations.len())
+    }
+
+    fn unwind_contract_creations(
+        &self,
+        block_range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<usize> {
+        let addresses = self
+            .take::<tables::BlockContractCreations>(BlockNumberAddress::range(block_range.clone()))?
+            .into_iter()
+            .map(|(BlockNumberAddress((_, address)), _)| address)
+            .collect::<BTreeSet<_>>();
+
+        // Only the creations in the range are removed, so the previous creation of a contract
+        // that was re-created in the range becomes the most recent one again.
+        let mut cursor = self.tx.cursor_dup_write::<tables::ContractCreations>()?;
+        let mut removed = 0;
+        for address in addresses {
+            while cursor.seek_by_key_subkey(address, *block_range.start())?.is_some() {
+                cursor.delete_current()?;
+                removed += 1;
+            }
+        }
+
+        Ok(removed)
+    }
+}
+
+impl<TX: DbTx + 'static, N: NodeTypesForProvider> AccountDestructionsReader
+    for DatabaseProvider<TX, N>
+{
+    fn account_destructions(&self, address: Address) -> ProviderResult<Vec<AccountDestruction>> {
+        Ok(self
+            .tx
+            .cursor_dup_read::<tables::AccountDestructions>()?
+            .walk_dup(Some(address), None)?
+            .map(|entry| entry.map(|(_, destruction)| destruction))
+            .collect::<Result<_, _>>()?)
+    }
+}
+
+impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> AccountDestructionsWriter
+    for DatabaseProvider<TX, N>
+{
+    fn insert_account_destructions(
+        &self,
+        destructions: Vec<(Address, AccountDestruction)>,
+    ) -> ProviderResult<usize> {
+        let mut cursor = self.tx.cursor_dup_write::<tables::AccountDestructions>()?;
+        let mut blocks_cursor = self.tx.cursor_write::<tables::BlockAccountDestructions>()?;
+        for (address, destruction) in &destructions {
+            cursor.upsert(*address, destruction)?;
+            blocks_cursor.upsert(
+                BlockNumberAddress((destruction.block_number, *address)),
+                &destruction.transaction_hash,
+            )?;
+        }
+
+        Ok(destructions.len())
+    }
+
+    fn unwind_account_destructions(
+        &self,
+        block_range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<usize> {
+        let addresses = self
+            .take::<tables::BlockAccountDestructions>(BlockNumberAddress::range(
+                block_range.clone(),
+            ))?
+            .into_iter()
+            .map(|(BlockNumberAddress((_, address)), _)| address)
+            .collect::<BTreeSet<_>>();
+
+        let mut cursor = self.tx.cursor_dup_write::<tables::AccountDestructions>()?;
+        let mut removed = 0;
+        for address in addresses {
+            while cursor.seek_by_key_subkey(address, *block_range.start())?.is_some() {
+                cursor.delete_current()?;
+                removed += 1;
+            }
+        }
+
+        Ok(removed)
+    }
+}
+
 impl<TX: Send, N: NodeTypes> StorageSettingsCache for DatabaseProvider<TX, N> {
     fn cached_storage_settings(&self) -> StorageSettings {
         *self.storage_settings.read()
@@ -3593,14 +4355,193 @@ impl<TX: Send, N: NodeTypes> StorageSettingsCache for DatabaseProvider<TX, N> {
 mod tests {
     use super::*;
     use crate::{
-        test_utils::{blocks::BlockchainTestData, create_test_provider_factory},
-        BlockWriter,
+        providers::{RocksDBBuilder, StaticFileProviderBuilder},
+        test_utils::{
+            blocks::BlockchainTestData, create_test_provider_factory, MockNodeTypesWithDB,
+        },
+        BlockWriter, ProviderFactory,
     };
     use alloy_primitives::map::B256Map;
+    use reth_chain_state::test_utils::TestBlockBuilder;
+    use reth_chainspec::MAINNET;
+    use reth_db::test_utils::{
+        create_test_rocksdb_dir, create_test_rw_db, create_test_static_files_dir,
+    };
     use reth_ethereum_primitives::Receipt;
     use reth_testing_utils::generators::{self, random_block, BlockParams};
     use reth_trie::{Nibbles, StoredNibblesSubKey};
 
+    #[test]
+    fn test_reorg_history() {
+        let factory = create_test_provider_factory();
+
+        let provider_rw = factory.provider_rw().unwrap();
+        for depth in 1..=5 {
+            provider_rw.insert_reorg_record(ReorgRecord { depth, ..Default::default() }).unwrap();
+        }
+        assert_eq!(provider_rw.prune_reorg_history(3).unwrap(), 2);
+        provider_rw.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        let depths = |limit| {
+            provider.reorg_history(limit).unwrap().into_iter().map(|r| r.depth).collect::<Vec<_>>()
+        };
+        assert_eq!(depths(10), vec![5, 4, 3]);
+        assert_eq!(depths(2), vec![5, 4]);
+    }
+
+    #[test]
+    fn test_unwind_contract_creations_restores_previous_creation() {
+        let factory = create_test_provider_factory();
+        let address = Address::with_last_byte(1);
+        let creation = |block_number| ContractCreation {
+            block_number,
+            transaction_hash: B256::with_last_byte(block_number as u8),
+            salt: Some(B256::ZERO),
+            ..Default::default()
+        };
+
+        // contract re-created with `CREATE2` after it self-destructed
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw
+            .insert_contract_creations(vec![(address, creation(5)), (address, creation(10))])
+            .unwrap();
+        assert_eq!(provider_rw.contract_creation(address).unwrap(), Some(creation(10)));
+
+        assert_eq!(provider_rw.unwind_contract_creations(8..=12).unwrap(), 1);
+        assert_eq!(provider_rw.contract_creation(address).unwrap(), Some(creation(5)));
+
+        assert_eq!(provider_rw.unwind_contract_creations(5..=7).unwrap(), 1);
+        assert_eq!(provider_rw.contract_creation(address).unwrap(), None);
+    }
+
+    #[test]
+    fn test_save_blocks_extends_address_transactions_index() {
+        let factory = create_test_provider_factory();
+        let mut test_block_builder = TestBlockBuilder::eth();
+        let blocks: Vec<_> = test_block_builder.get_executed_blocks(0..3).collect();
+        let tx_count = |block: &ExecutedBlock| block.recovered_block().body().transaction_count();
+
+        // blocks persisted before the index is enabled aren't indexed
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw.save_blocks(blocks[..1].to_vec(), SaveBlocksMode::Full).unwrap();
+        provider_rw
+            .save_stage_checkpoint(StageId::IndexTransactionAddresses, StageCheckpoint::new(0))
+            .unwrap();
+        provider_rw.save_blocks(blocks[1..].to_vec(), SaveBlocksMode::Full).unwrap();
+        provider_rw.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        assert_eq!(
+            provider.get_stage_checkpoint(StageId::IndexTransactionAddresses).unwrap(),
+            Some(StageCheckpoint::new(2))
+        );
+        let first_tx = tx_count(&blocks[0]) as u64;
+        let last_tx = blocks.iter().map(tx_count).sum::<usize>() as u64;
+        assert_eq!(
+            provider.address_transactions(&[test_block_builder.signer], 1..=2).unwrap(),
+            Some((first_tx..last_tx).collect())
+        );
+    }
+
+    #[test]
+    fn test_save_blocks_batches_changesets_and_receipts() {
+        let factory = create_test_provider_factory();
+        factory.set_storage_settings_cache(
+            StorageSettings::legacy().with_receipts_hot_blocks(Some(64)),
+        );
+        let address = Address::with_last_byte(1);
+        let slot = U256::from(1);
+        let blocks: Vec<_> = TestBlockBuilder::eth()
+            .get_executed_blocks(0..3)
+            .map(|block| {
+                let recovered_block = block.recovered_block.clone();
+                let number = recovered_block.number();
+                let receipt = Receipt { cumulative_gas_used: number, ..Default::default() };
+                let state = revm_database::BundleState::builder(number..=number)
+                    .state_present_account_info(
+                        address,
+                        revm_state::AccountInfo { nonce: number + 1, ..Default::default() },
+                    )
+                    .state_storage(
+                        address,
+                        [(slot, (U256::from(number), U256::from(number + 1)))]
+                            .into_iter()
+                            .collect(),
+                    )
+                    .revert_account_info(
+                        number,
+                        address,
+                        Some((number > 0).then(|| revm_state::AccountInfo {
+                            nonce: number,
+                            ..Default::default()
+                        })),
+                    )
+                    .revert_storage(number, address, vec![(slot, U256::from(number))])
+                    .build();
+                let receipts = vec![receipt; recovered_block.body().transaction_count()];
+                ExecutedBlock::new(
+                    recovered_block,
+                    Arc::new(BlockExecutionOutput {
+                        result: BlockExecutionResult {
+                            receipts,
+                            requests: Default::default(),
+                            gas_used: 0,
+                            blob_gas_used: 0,
+                        },
+                        state,
+                    }),
+                    ComputedTrieData::default(),
+                )
+            })
+            .collect();
+        let tx_counts = blocks
+            .iter()
+            .map(|b| b.recovered_block().body().transaction_count())
+            .collect::<Vec<_>>();
+
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw.save_blocks(blocks, SaveBlocksMode::Full).unwrap();
+        provider_rw.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        for (number, tx_count) in (0..).zip(tx_counts) {
+            let changeset = provider.account_block_changeset(number).unwrap();
+            assert_eq!(changeset.len(), 1);
+            assert_eq!(changeset[0].info.map(|info| info.nonce), (number > 0).then_some(number));
+
+            let storage = provider.storage_changeset(number).unwrap();
+            assert_eq!(storage.len(), 1);
+            assert_eq!(storage[0].1.value, U256::from(number));
+
+            let receipts = provider.receipts_by_block(number.into()).unwrap().unwrap();
+            assert_eq!(receipts.len(), tx_count);
+            assert!(receipts.iter().all(|receipt| receipt.cumulative_gas_used == number));
+        }
+    }
+
+    #[test]
+    fn test_chain_stats() {
+        let factory = create_test_provider_factory();
+
+        let provider_rw = factory.provider_rw().unwrap();
+        for day in 0..5 {
+            let record = ChainStatsRecord { blocks: day + 1, ..Default::default() };
+            provider_rw.save_chain_stats(ChainStatsTable::Days, day, Some(record)).unwrap();
+        }
+        provider_rw.save_chain_stats(ChainStatsTable::Days, 4, None).unwrap();
+        assert_eq!(provider_rw.prune_chain_stats(ChainStatsTable::Days, 3).unwrap(), 1);
+        provider_rw.save_chain_stats_head(Some(42)).unwrap();
+        provider_rw.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        let days = provider.chain_stats(ChainStatsTable::Days, 10).unwrap();
+        assert_eq!(days.iter().map(|(day, _)| *day).collect::<Vec<_>>(), vec![3, 2, 1]);
+        assert_eq!(days[0].1.blocks, 4);
+        assert!(provider.chain_stats(ChainStatsTable::Epochs, 10).unwrap().is_empty());
+        assert_eq!(provider.chain_stats_head().unwrap(), Some(42));
+    }
+
     #[test]
     fn test_receipts_by_block_range_empty_range() {
         let factory = create_test_provider_factory();
@@ -4202,4 +5143,205 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_move_cold_receipts_to_static_files() {
+        let factory = create_test_provider_factory();
+        factory.set_storage_settings_cache(
+            StorageSettings::legacy()
+                .with_receipts_in_static_files(true)
+                .with_receipts_hot_blocks(Some(5)),
+        );
+
+        let tip_block = 10u64;
+        let mut rng = generators::rng();
+        let provider_rw = factory.provider_rw().unwrap();
+        for block_num in 0..=tip_block {
+            let block = random_block(
+                &mut rng,
+                block_num,
+                BlockParams { tx_count: Some(1), ..Default::default() },
+            );
+            provider_rw.insert_block(&block.try_recover().unwrap()).unwrap();
+        }
+
+        // Receipts of all blocks are written to the database first
+        assert!(EitherWriter::receipts_destination(&provider_rw).is_database());
+        let outcome = ExecutionOutcome {
+            first_block: 0,
+            receipts: (0..=tip_block)
+                .map(|block| {
+                    vec![Receipt {
+                        tx_type: Default::default(),
+                        success: true,
+                        cumulative_gas_used: block, // identifier to assert against
+                        logs: vec![],
+                    }]
+                })
+                .collect(),
+            ..Default::default()
+        };
+        provider_rw
+            .write_state(&outcome, crate::OriginalValuesKnown::No, StateWriteConfig::default())
+            .unwrap();
+        provider_rw.commit().unwrap();
+
+        let move_cold_receipts = |finalized: BlockNumber, max_blocks: u64| {
+            let provider_rw = factory.provider_rw().unwrap();
+            provider_rw.save_finalized_block_number(finalized).unwrap();
+            provider_rw
+                .move_cold_receipts_to_static_files_with_limit(tip_block, max_blocks)
+                .unwrap();
+            provider_rw.commit().unwrap();
+        };
+
+        // Receipts are moved in batches
+        move_cold_receipts(3, 2);
+        assert_eq!(
+            factory
+                .static_file_provider()
+                .get_highest_static_file_block(StaticFileSegment::Receipts),
+            Some(1)
+        );
+
+        // Receipts are not moved beyond the finalized block
+        move_cold_receipts(3, MAX_COLD_RECEIPTS_BLOCKS);
+        assert_eq!(
+            factory
+                .static_file_provider()
+                .get_highest_static_file_block(StaticFileSegment::Receipts),
+            Some(3)
+        );
+
+        // Receipts of the hot blocks stay in the database, even if they are finalized. Moved
+        // receipts are removed from the database.
+        move_cold_receipts(8, MAX_COLD_RECEIPTS_BLOCKS);
+        assert_eq!(
+            factory
+                .static_file_provider()
+                .get_highest_static_file_block(StaticFileSegment::Receipts),
+            Some(5)
+        );
+
+        let provider = factory.provider().unwrap();
+        let db_receipts = provider
+            .tx_ref()
+            .cursor_read::<tables::Receipts<Receipt>>()
+            .unwrap()
+            .walk(None)
+            .unwrap()
+            .map(|entry| entry.unwrap().0)
+            .collect::<Vec<_>>();
+        assert_eq!(db_receipts, (6..=tip_block).collect::<Vec<_>>());
+
+        // Receipts are read from both tiers transparently
+        for block in 0..=tip_block {
+            let receipts = provider.receipts_by_block(block.into()).unwrap().unwrap();
+            assert_eq!(receipts.len(), 1);
+            assert_eq!(receipts[0].cumulative_gas_used, block);
+            assert!(provider
+                .receipt(block)
+                .unwrap()
+                .is_some_and(|r| r.cumulative_gas_used == block));
+        }
+    }
+
+    #[test]
+    fn test_move_cold_receipts_keeps_backfilled_receipts() {
+        let (static_dir, _) = create_test_static_files_dir();
+        let (rocksdb_dir, _) = create_test_rocksdb_dir();
+        let factory = ProviderFactory::<MockNodeTypesWithDB>::new(
+            create_test_rw_db(),
+            MAINNET.clone(),
+            StaticFileProviderBuilder::read_write(&static_dir)
+                .with_blocks_per_file(4)
+                .build()
+                .unwrap(),
+            RocksDBBuilder::new(&rocksdb_dir).with_default_tables().build().unwrap(),
+        )
+        .unwrap();
+        factory.set_storage_settings_cache(
+            StorageSettings::legacy()
+                .with_receipts_in_static_files(true)
+                .with_receipts_hot_blocks(Some(5)),
+        );
+
+        let receipt = |block: BlockNumber| Receipt {
+            tx_type: Default::default(),
+            success: true,
+            cumulative_gas_used: block, // identifier to assert against
+            logs: vec![],
+        };
+
+        let tip_block = 15u64;
+        let mut rng = generators::rng();
+        let provider_rw = factory.provider_rw().unwrap();
+        for block_num in 0..=tip_block {
+            let block = random_block(
+                &mut rng,
+                block_num,
+                BlockParams { tx_count: Some(1), ..Default::default() },
+            );
+            provider_rw.insert_block(&block.try_recover().unwrap()).unwrap();
+        }
+        let outcome = ExecutionOutcome {
+            first_block: 0,
+            receipts: (0..=tip_block).map(|block| vec![receipt(block)]).collect(),
+            ..Default::default()
+        };
+        provider_rw
+            .write_state(&outcome, crate::OriginalValuesKnown::No, StateWriteConfig::default())
+            .unwrap();
+        provider_rw.commit().unwrap();
+
+        let move_cold_receipts = |finalized: BlockNumber| {
+            let provider_rw = factory.provider_rw().unwrap();
+            provider_rw.save_finalized_block_number(finalized).unwrap();
+            provider_rw.move_cold_receipts_to_static_files(tip_block).unwrap();
+            provider_rw.commit().unwrap();
+        };
+
+        // Receipts of blocks 0..=7 are moved, then the static file of blocks 0..=3 is removed
+        move_cold_receipts(7);
+        factory
+            .static_file_provider()
+            .delete_segment_below_block(StaticFileSegment::Receipts, 4)
+            .unwrap();
+        assert_eq!(
+            factory.static_file_provider().get_lowest_range_start(StaticFileSegment::Receipts),
+            Some(4)
+        );
+
+        // Receipts of blocks 0..=3 are backfilled to the database below the static files
+        let provider_rw = factory.provider_rw().unwrap();
+        for block in 0..=3 {
+            provider_rw.tx_ref().put::<tables::Receipts<Receipt>>(block, receipt(block)).unwrap();
+        }
+        provider_rw.commit().unwrap();
+
+        // Moving the next cold receipts only removes the moved receipts from the database
+        move_cold_receipts(10);
+        assert_eq!(
+            factory
+                .static_file_provider()
+                .get_highest_static_file_block(StaticFileSegment::Receipts),
+            Some(10)
+        );
+
+        let provider = factory.provider().unwrap();
+        let db_receipts = provider
+            .tx_ref()
+            .cursor_read::<tables::Receipts<Receipt>>()
+            .unwrap()
+            .walk(None)
+            .unwrap()
+            .map(|entry| entry.unwrap().0)
+            .collect::<Vec<_>>();
+        assert_eq!(db_receipts, (0..=3).chain(11..=tip_block).collect::<Vec<_>>());
+
+        for block in 0..=tip_block {
+            let receipts = provider.receipts_by_block(block.into()).unwrap().unwrap();
+            assert_eq!(receipts, vec![receipt(block)]);
+        }
+    }
 }
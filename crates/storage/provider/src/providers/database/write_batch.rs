@@ -0,0 +1,359 @@
+//! Batched MDBX writes of canonical blocks.
+
+use super::metrics::{Action, DatabaseProviderMetrics};
+use alloy_primitives::{Address, BlockHash, BlockNumber, TxNumber};
+use rayon::slice::ParallelSliceMut;
+use reth_db_api::{
+    cursor::{DbCursorRW, DbDupCursorRW},
+    models::{AccountBeforeTx, BlockNumberAddress, StorageBeforeTx, StoredBlockBodyIndices},
+    table::Value,
+    tables,
+    transaction::DbTxMut,
+};
+use reth_primitives_traits::StorageEntry;
+use reth_storage_errors::provider::ProviderResult;
+use std::time::Instant;
+
+/// Accumulates the MDBX writes of a range of canonical blocks and writes them in one go.
+///
+/// Writing block by block opens a cursor per table and block and interleaves writes to many
+/// tables. Instead, entries are buffered per table, sorted by key on [`BlockWriteBatch::flush`]
+/// and written with `APPEND` mode cursors, so each table is only touched once per batch and MDBX
+/// can fill pages sequentially.
+///
+/// Block indices and senders are buffered while the blocks are inserted, changesets and receipts
+/// while their state is written, see
+/// [`DatabaseProvider::save_blocks_with_batch`](crate::DatabaseProvider::save_blocks_with_batch).
+/// Only entries destined for MDBX are buffered, static file writes are unaffected.
+///
+/// The buffers keep their capacity after a flush, so a long-lived batch can be reused across
+/// transactions without reallocating.
+///
+/// All appendable tables require the buffered keys to be greater than the last key stored in the
+/// table, which holds for canonical blocks written on top of the current tip.
+#[derive(Debug)]
+pub struct BlockWriteBatch<R> {
+    /// Buffered [`tables::HeaderNumbers`] entries.
+    header_numbers: Vec<(BlockHash, BlockNumber)>,
+    /// Buffered [`tables::BlockBodyIndices`] entries.
+    block_body_indices: Vec<(BlockNumber, StoredBlockBodyIndices)>,
+    /// Buffered [`tables::TransactionBlocks`] entries.
+    transaction_blocks: Vec<(TxNumber, BlockNumber)>,
+    /// Buffered [`tables::TransactionSenders`] entries.
+    transaction_senders: Vec<(TxNumber, Address)>,
+    /// Buffered [`tables::AccountChangeSets`] entries.
+    account_changesets: Vec<(BlockNumber, AccountBeforeTx)>,
+    /// Buffered [`tables::StorageChangeSets`] entries.
+    storage_changesets: Vec<(BlockNumberAddress, StorageEntry)>,
+    /// Buffered [`tables::Receipts`] entries.
+    receipts: Vec<(TxNumber, R)>,
+}
+
+impl<R> Default for BlockWriteBatch<R> {
+    fn default() -> Self {
+        Self {
+            header_numbers: Vec::new(),
+            block_body_indices: Vec::new(),
+            transaction_blocks: Vec::new(),
+            transaction_senders: Vec::new(),
+            account_changesets: Vec::new(),
+            storage_changesets: Vec::new(),
+            receipts: Vec::new(),
+        }
+    }
+}
+
+impl<R> BlockWriteBatch<R> {
+    /// Buffers the hash to number mapping of a block.
+    pub fn push_header_number(&mut self, hash: BlockHash, number: BlockNumber) {
+        self.header_numbers.push((hash, number));
+    }
+
+    /// Buffers the body indices of a block, including the mapping of its last transaction to the
+    /// block.
+    pub fn push_block_body_indices(
+        &mut self,
+        number: BlockNumber,
+        indices: StoredBlockBodyIndices,
+    ) {
+        if !indices.is_empty() {
+            self.transaction_blocks.push((indices.last_tx_num(), number));
+        }
+        self.block_body_indices.push((number, indices));
+    }
+
+    /// Buffers the senders of consecutive transactions, starting at `first_tx_num`.
+    pub fn push_transaction_senders(
+        &mut self,
+        first_tx_num: TxNumber,
+        senders: impl IntoIterator<Item = Address>,
+    ) {
+        self.transaction_senders.extend((first_tx_num..).zip(senders));
+    }
+
+    /// Buffers the account changeset of a block.
+    pub fn push_account_changeset(
+        &mut self,
+        number: BlockNumber,
+        changeset: impl IntoIterator<Item = AccountBeforeTx>,
+    ) {
+        self.account_changesets.extend(changeset.into_iter().map(|change| (number, change)));
+    }
+
+    /// Buffers the storage changeset of a block.
+    pub fn push_storage_changeset(
+        &mut self,
+        number: BlockNumber,
+        changeset: impl IntoIterator<Item = StorageBeforeTx>,
+    ) {
+        self.storage_changesets.extend(changeset.into_iter().map(|change| {
+            (
+                BlockNumberAddress((number, change.address)),
+                StorageEntry { key: change.key, value: change.value },
+            )
+        }));
+    }
+
+    /// Buffers the receipt of a transaction.
+    pub fn push_receipt(&mut self, tx_num: TxNumber, receipt: R) {
+        self.receipts.push((tx_num, receipt));
+    }
+
+    /// Returns the total number of buffered entries.
+    pub fn len(&self) -> usize {
+        self.header_numbers.len() +
+            self.block_body_indices.len() +
+            self.transaction_blocks.len() +
+            self.transaction_senders.len() +
+            self.account_changesets.len() +
+            self.storage_changesets.len() +
+            self.receipts.len()
+    }
+
+    /// Returns `true` if no entries are buffered.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Discards all buffered entries, keeping the allocated capacity.
+    pub fn clear(&mut self) {
+        self.header_numbers.clear();
+        self.block_body_indices.clear();
+        self.transaction_blocks.clear();
+        self.transaction_senders.clear();
+        self.account_changesets.clear();
+        self.storage_changesets.clear();
+        self.receipts.clear();
+    }
+}
+
+impl<R: Value> BlockWriteBatch<R> {
+    /// Sorts all buffered entries by key and writes them to the given transaction, recording the
+    /// duration of the writes to every table.
+    ///
+    /// The batch is empty afterwards, even if writing fails.
+    pub(crate) fn flush<TX: DbTxMut>(
+        &mut self,
+        tx: &TX,
+        metrics: &DatabaseProviderMetrics,
+    ) -> ProviderResult<()> {
+        let result = self.write(tx, metrics);
+        self.clear();
+        result
+    }
+
+    fn write<TX: DbTxMut>(
+        &mut self,
+        tx: &TX,
+        metrics: &DatabaseProviderMetrics,
+    ) -> ProviderResult<()> {
+        if !self.transaction_senders.is_empty() {
+            let start = Instant::now();
+            self.transaction_senders.par_sort_unstable_by_key(|(tx_num, _)| *tx_num);
+            let mut cursor = tx.cursor_write::<tables::TransactionSenders>()?;
+            for (tx_num, sender) in self.transaction_senders.drain(..) {
+                cursor.append(tx_num, &sender)?;
+            }
+            metrics.record_duration(Action::InsertTransactionSenders, start.elapsed());
+        }
+
+        // Block hashes are random, so the entries can't be appended. Sorting them still turns
+        // the inserts into a single ordered pass over the table.
+        if !self.header_numbers.is_empty() {
+            let start = Instant::now();
+            self.header_numbers.par_sort_unstable_by_key(|(hash, _)| *hash);
+            let mut cursor = tx.cursor_write::<tables::HeaderNumbers>()?;
+            for (hash, number) in self.header_numbers.drain(..) {
+                cursor.upsert(hash, &number)?;
+            }
+            metrics.record_duration(Action::InsertHeaderNumbers, start.elapsed());
+        }
+
+        if !self.block_body_indices.is_empty() {
+            let start = Instant::now();
+            self.block_body_indices.sort_unstable_by_key(|(number, _)| *number);
+            let mut cursor = tx.cursor_write::<tables::BlockBodyIndices>()?;
+            for (number, indices) in self.block_body_indices.drain(..) {
+                cursor.append(number, &indices)?;
+            }
+            metrics.record_duration(Action::InsertBlockBodyIndices, start.elapsed());
+        }
+
+        if !self.transaction_blocks.is_empty() {
+            let start = Instant::now();
+            self.transaction_blocks.sort_unstable_by_key(|(tx_num, _)| *tx_num);
+            let mut cursor = tx.cursor_write::<tables::TransactionBlocks>()?;
+            for (tx_num, number) in self.transaction_blocks.drain(..) {
+                cursor.append(tx_num, &number)?;
+            }
+            metrics.record_duration(Action::InsertTransactionBlocks, start.elapsed());
+        }
+
+        if !self.account_changesets.is_empty() {
+            let start = Instant::now();
+            self.account_changesets
+                .par_sort_unstable_by_key(|(number, change)| (*number, change.address));
+            let mut cursor = tx.cursor_dup_write::<tables::AccountChangeSets>()?;
+            for (number, change) in self.account_changesets.drain(..) {
+                cursor.append_dup(number, change)?;
+            }
+            metrics.record_duration(Action::InsertAccountChangeSets, start.elapsed());
+        }
+
+        if !self.storage_changesets.is_empty() {
+            let start = Instant::now();
+            self.storage_changesets.par_sort_unstable_by_key(|(id, entry)| (*id, entry.key));
+            let mut cursor = tx.cursor_dup_write::<tables::StorageChangeSets>()?;
+            for (id, entry) in self.storage_changesets.drain(..) {
+                cursor.append_dup(id, entry)?;
+            }
+            metrics.record_duration(Action::InsertStorageChangeSets, start.elapsed());
+        }
+
+        if !self.receipts.is_empty() {
+            let start = Instant::now();
+            self.receipts.sort_unstable_by_key(|(tx_num, _)| *tx_num);
+            let mut cursor = tx.cursor_write::<tables::Receipts<R>>()?;
+            for (tx_num, receipt) in self.receipts.drain(..) {
+                cursor.append(tx_num, &receipt)?;
+            }
+            metrics.record_duration(Action::InsertReceipts, start.elapsed());
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_utils::create_test_provider_factory, DBProvider};
+    use alloy_primitives::{B256, U256};
+    use reth_db_api::{
+        cursor::{DbCursorRO, DbDupCursorRO},
+        transaction::DbTx,
+    };
+    use reth_ethereum_primitives::Receipt;
+
+    #[test]
+    fn flush_sorts_and_appends() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+        let mut batch = BlockWriteBatch::<Receipt>::default();
+
+        // buffer two blocks out of order
+        let (hash_1, hash_2) = (B256::repeat_byte(0xff), B256::repeat_byte(0x01));
+        batch.push_header_number(hash_2, 2);
+        batch.push_block_body_indices(2, StoredBlockBodyIndices { first_tx_num: 2, tx_count: 1 });
+        batch.push_transaction_senders(2, [Address::with_last_byte(3)]);
+        batch.push_receipt(2, Receipt::default());
+        batch.push_storage_changeset(
+            2,
+            [
+                StorageBeforeTx {
+                    address: Address::with_last_byte(2),
+                    key: B256::with_last_byte(2),
+                    value: U256::from(2),
+                },
+                StorageBeforeTx {
+                    address: Address::with_last_byte(2),
+                    key: B256::with_last_byte(1),
+                    value: U256::from(1),
+                },
+            ],
+        );
+
+        batch.push_header_number(hash_1, 1);
+        batch.push_block_body_indices(1, StoredBlockBodyIndices { first_tx_num: 0, tx_count: 2 });
+        batch.push_transaction_senders(0, [Address::with_last_byte(1), Address::with_last_byte(2)]);
+        batch.push_receipt(1, Receipt::default());
+        batch.push_receipt(0, Receipt::default());
+        batch.push_account_changeset(
+            1,
+            [
+                AccountBeforeTx { address: Address::with_last_byte(2), info: None },
+                AccountBeforeTx { address: Address::with_last_byte(1), info: None },
+            ],
+        );
+        // a block without transactions doesn't map any transaction to the block
+        batch.push_block_body_indices(3, StoredBlockBodyIndices { first_tx_num: 3, tx_count: 0 });
+
+        assert_eq!(batch.len(), 17);
+        batch.flush(provider.tx_ref(), &DatabaseProviderMetrics::default()).unwrap();
+        assert!(batch.is_empty());
+
+        let tx = provider.tx_ref();
+        assert_eq!(tx.get::<tables::HeaderNumbers>(hash_1).unwrap(), Some(1));
+        assert_eq!(tx.get::<tables::HeaderNumbers>(hash_2).unwrap(), Some(2));
+        assert_eq!(
+            tx.cursor_read::<tables::BlockBodyIndices>()
+                .unwrap()
+                .walk(None)
+                .unwrap()
+                .map(|entry| entry.unwrap().0)
+                .collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+        assert_eq!(
+            tx.cursor_read::<tables::TransactionBlocks>()
+                .unwrap()
+                .walk(None)
+                .unwrap()
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap(),
+            vec![(1, 1), (2, 2)]
+        );
+        assert_eq!(
+            tx.cursor_read::<tables::TransactionSenders>()
+                .unwrap()
+                .walk(None)
+                .unwrap()
+                .map(|entry| entry.unwrap().1)
+                .collect::<Vec<_>>(),
+            vec![
+                Address::with_last_byte(1),
+                Address::with_last_byte(2),
+                Address::with_last_byte(3),
+            ]
+        );
+        assert_eq!(tx.entries::<tables::Receipts<Receipt>>().unwrap(), 3);
+        assert_eq!(
+            tx.cursor_dup_read::<tables::AccountChangeSets>()
+                .unwrap()
+                .walk_dup(Some(1), None)
+                .unwrap()
+                .map(|entry| entry.unwrap().1.address)
+                .collect::<Vec<_>>(),
+            vec![Address::with_last_byte(1), Address::with_last_byte(2)]
+        );
+        assert_eq!(
+            tx.cursor_dup_read::<tables::StorageChangeSets>()
+                .unwrap()
+                .walk_dup(Some(BlockNumberAddress((2, Address::with_last_byte(2)))), None)
+                .unwrap()
+                .map(|entry| entry.unwrap().1.key)
+                .collect::<Vec<_>>(),
+            vec![B256::with_last_byte(1), B256::with_last_byte(2)]
+        );
+    }
+}
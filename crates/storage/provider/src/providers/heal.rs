@@ -0,0 +1,153 @@
+//! Healing plans produced by the storage consistency checks.
+
+use alloy_primitives::BlockNumber;
+use reth_static_file_types::StaticFileSegment;
+use std::fmt;
+
+/// A single action required to bring the storage layers back into a consistent state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HealAction {
+    /// The latest static file of the segment has uncommitted data and is truncated back to its
+    /// last committed configuration.
+    TruncateStaticFile {
+        /// Static file segment.
+        segment: StaticFileSegment,
+    },
+    /// Static file rows above the stage checkpoint are pruned.
+    PruneStaticFile {
+        /// Static file segment.
+        segment: StaticFileSegment,
+        /// Highest block of the segment before pruning.
+        from: BlockNumber,
+        /// Stage checkpoint the segment is pruned to.
+        to: BlockNumber,
+    },
+    /// `RocksDB` index entries above the stage checkpoint are removed, so the index can be
+    /// rebuilt by its stage.
+    RebuildIndex {
+        /// Name of the index table.
+        table: &'static str,
+        /// First block whose index entries are removed.
+        from: BlockNumber,
+        /// Last block whose index entries are removed.
+        to: BlockNumber,
+    },
+    /// The pipeline is unwound to the given block.
+    Unwind {
+        /// Unwind target.
+        target: BlockNumber,
+    },
+}
+
+impl fmt::Display for HealAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TruncateStaticFile { segment } => {
+                write!(f, "truncate {segment} static file to its last commit")
+            }
+            Self::PruneStaticFile { segment, from, to } => {
+                write!(f, "prune {segment} static files from block {from} to {to}")
+            }
+            Self::RebuildIndex { table, from, to } => {
+                write!(f, "rebuild {table} index for blocks {from}..={to}")
+            }
+            Self::Unwind { target } => write!(f, "unwind pipeline to block {target}"),
+        }
+    }
+}
+
+/// The set of [`HealAction`]s computed by a consistency check.
+///
+/// Depending on how the check was run, the actions have either already been executed (except for
+/// [`HealAction::Unwind`], which is always left to the caller) or only describe what healing
+/// would do.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HealPlan {
+    actions: Vec<HealAction>,
+}
+
+impl HealPlan {
+    /// Returns `true` if no healing is required.
+    pub const fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+    }
+
+    /// Returns the actions of the plan, in the order they were recorded.
+    pub fn actions(&self) -> &[HealAction] {
+        &self.actions
+    }
+
+    /// Records a new action.
+    pub fn push(&mut self, action: HealAction) {
+        self.actions.push(action);
+    }
+
+    /// Records an unwind to `target`, keeping only the lowest unwind target of the plan.
+    pub fn push_unwind(&mut self, target: BlockNumber) {
+        for action in &mut self.actions {
+            if let HealAction::Unwind { target: existing } = action {
+                *existing = (*existing).min(target);
+                return
+            }
+        }
+        self.actions.push(HealAction::Unwind { target });
+    }
+
+    /// Appends all actions of `other` to this plan.
+    pub fn extend(&mut self, other: Self) {
+        for action in other.actions {
+            match action {
+                HealAction::Unwind { target } => self.push_unwind(target),
+                action => self.push(action),
+            }
+        }
+    }
+
+    /// Returns the pipeline unwind target of the plan, if any.
+    pub fn unwind_target(&self) -> Option<BlockNumber> {
+        self.actions.iter().find_map(|action| match action {
+            HealAction::Unwind { target } => Some(*target),
+            _ => None,
+        })
+    }
+}
+
+impl fmt::Display for HealPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.actions.is_empty() {
+            return f.write_str("none")
+        }
+
+        for (i, action) in self.actions.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{action}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_lowest_unwind_target() {
+        let mut plan = HealPlan::default();
+        plan.push_unwind(100);
+        plan.push(HealAction::TruncateStaticFile { segment: StaticFileSegment::Headers });
+
+        let mut other = HealPlan::default();
+        other.push_unwind(50);
+        other.push_unwind(70);
+        plan.extend(other);
+
+        assert_eq!(plan.unwind_target(), Some(50));
+        assert_eq!(plan.actions().len(), 2);
+        assert_eq!(
+            plan.to_string(),
+            "unwind pipeline to block 50; truncate Headers static file to its last commit"
+        );
+    }
+}
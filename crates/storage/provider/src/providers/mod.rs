@@ -9,20 +9,26 @@ pub use database::*;
 
 mod static_file;
 pub use static_file::{
-    StaticFileAccess, StaticFileJarProvider, StaticFileProvider, StaticFileProviderBuilder,
-    StaticFileProviderRW, StaticFileProviderRWRefMut, StaticFileWriteCtx, StaticFileWriter,
+    replace_static_file, replacement_path, SharedStaticFiles, StaticFileAccess,
+    StaticFileJarProvider, StaticFileProvider, StaticFileProviderBuilder, StaticFileProviderRW,
+    StaticFileProviderRWRefMut, StaticFileWriteCtx, StaticFileWriter, REPLACEMENTS_DIR,
 };
 
 mod state;
 pub use state::{
     historical::{
-        compute_history_rank, history_info, needs_prev_shard_check, HistoricalStateProvider,
-        HistoricalStateProviderRef, HistoryInfo, LowestAvailableBlocks,
+        collect_history_blocks, compute_history_rank, history_blocks, history_info,
+        needs_prev_shard_check, HistoricalStateCache, HistoricalStateProvider,
+        HistoricalStateProviderRef, HistoricalStateReads, HistoryInfo, LowestAvailableBlocks,
+        DEFAULT_HISTORICAL_STATE_CACHE_SIZE,
     },
     latest::{LatestStateProvider, LatestStateProviderRef},
     overlay::{OverlayStateProvider, OverlayStateProviderFactory},
 };
 
+mod heal;
+pub use heal::{HealAction, HealPlan};
+
 mod consistent_view;
 pub use consistent_view::{ConsistentDbView, ConsistentViewError};
 
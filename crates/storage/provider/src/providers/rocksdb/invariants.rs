@@ -5,11 +5,14 @@
 //! inconsistencies between `RocksDB` data and MDBX checkpoints.
 
 use super::RocksDBProvider;
-use crate::StaticFileProviderFactory;
+use crate::{
+    providers::{HealAction, HealPlan},
+    StaticFileProviderFactory,
+};
 use alloy_eips::eip2718::Encodable2718;
 use alloy_primitives::BlockNumber;
 use rayon::prelude::*;
-use reth_db_api::tables;
+use reth_db_api::{table::Table, tables};
 use reth_stages_types::StageId;
 use reth_static_file_types::StaticFileSegment;
 use reth_storage_api::{
@@ -59,30 +62,54 @@ impl RocksDBProvider {
             + ChangeSetReader
             + TransactionsProvider<Transaction: Encodable2718>,
     {
-        let mut unwind_target: Option<BlockNumber> = None;
+        Ok(self.consistency_plan(provider, true)?.unwind_target())
+    }
+
+    /// Runs the same checks as [`Self::check_consistency`], returning every action required to
+    /// restore consistency as a [`HealPlan`].
+    ///
+    /// If `heal` is `true`, stale index entries are removed while checking, and only the pipeline
+    /// unwind is left to the caller. Otherwise, `RocksDB` is not modified and the plan only
+    /// describes what healing would do.
+    pub fn consistency_plan<Provider>(
+        &self,
+        provider: &Provider,
+        heal: bool,
+    ) -> ProviderResult<HealPlan>
+    where
+        Provider: DBProvider
+            + StageCheckpointReader
+            + StorageSettingsCache
+            + StaticFileProviderFactory
+            + BlockBodyIndicesProvider
+            + StorageChangeSetReader
+            + ChangeSetReader
+            + TransactionsProvider<Transaction: Encodable2718>,
+    {
+        let mut plan = HealPlan::default();
 
         // Heal TransactionHashNumbers if stored in RocksDB
         if provider.cached_storage_settings().transaction_hash_numbers_in_rocksdb &&
-            let Some(target) = self.heal_transaction_hash_numbers(provider)?
+            let Some(target) = self.heal_transaction_hash_numbers(provider, heal, &mut plan)?
         {
-            unwind_target = Some(unwind_target.map_or(target, |t| t.min(target)));
+            plan.push_unwind(target);
         }
 
         // Heal StoragesHistory if stored in RocksDB
         if provider.cached_storage_settings().storages_history_in_rocksdb &&
-            let Some(target) = self.heal_storages_history(provider)?
+            let Some(target) = self.heal_storages_history(provider, heal, &mut plan)?
         {
-            unwind_target = Some(unwind_target.map_or(target, |t| t.min(target)));
+            plan.push_unwind(target);
         }
 
         // Heal AccountsHistory if stored in RocksDB
         if provider.cached_storage_settings().account_history_in_rocksdb &&
-            let Some(target) = self.heal_accounts_history(provider)?
+            let Some(target) = self.heal_accounts_history(provider, heal, &mut plan)?
         {
-            unwind_target = Some(unwind_target.map_or(target, |t| t.min(target)));
+            plan.push_unwind(target);
         }
 
-        Ok(unwind_target)
+        Ok(plan)
     }
 
     /// Heals the `TransactionHashNumbers` table.
@@ -94,6 +121,8 @@ impl RocksDBProvider {
     fn heal_transaction_hash_numbers<Provider>(
         &self,
         provider: &Provider,
+        heal: bool,
+        plan: &mut HealPlan,
     ) -> ProviderResult<Option<BlockNumber>>
     where
         Provider: DBProvider
@@ -114,6 +143,15 @@ impl RocksDBProvider {
 
         // Fast path: if checkpoint is 0 and RocksDB has data, clear everything.
         if checkpoint == 0 && self.first::<tables::TransactionHashNumbers>()?.is_some() {
+            plan.push(HealAction::RebuildIndex {
+                table: tables::TransactionHashNumbers::NAME,
+                from: 0,
+                to: sf_tip,
+            });
+            if !heal {
+                return Ok(None);
+            }
+
             tracing::info!(
                 target: "reth::providers::rocksdb",
                 "TransactionHashNumbers has data but checkpoint is 0, clearing all"
@@ -167,6 +205,15 @@ impl RocksDBProvider {
             return Ok(Some(sf_tip));
         }
 
+        plan.push(HealAction::RebuildIndex {
+            table: tables::TransactionHashNumbers::NAME,
+            from: checkpoint + 1,
+            to: sf_tip,
+        });
+        if !heal {
+            return Ok(None);
+        }
+
         tracing::info!(
             target: "reth::providers::rocksdb",
             checkpoint,
@@ -254,6 +301,8 @@ impl RocksDBProvider {
     fn heal_storages_history<Provider>(
         &self,
         provider: &Provider,
+        heal: bool,
+        plan: &mut HealPlan,
     ) -> ProviderResult<Option<BlockNumber>>
     where
         Provider:
@@ -264,8 +313,22 @@ impl RocksDBProvider {
             .map(|cp| cp.block_number)
             .unwrap_or(0);
 
+        let sf_tip = provider
+            .static_file_provider()
+            .get_highest_static_file_block(StaticFileSegment::StorageChangeSets)
+            .unwrap_or(0);
+
         // Fast path: if checkpoint is 0 and RocksDB has data, clear everything.
         if checkpoint == 0 && self.first::<tables::StoragesHistory>()?.is_some() {
+            plan.push(HealAction::RebuildIndex {
+                table: tables::StoragesHistory::NAME,
+                from: 0,
+                to: sf_tip,
+            });
+            if !heal {
+                return Ok(None);
+            }
+
             tracing::info!(
                 target: "reth::providers::rocksdb",
                 "StoragesHistory has data but checkpoint is 0, clearing all"
@@ -274,11 +337,6 @@ impl RocksDBProvider {
             return Ok(None);
         }
 
-        let sf_tip = provider
-            .static_file_provider()
-            .get_highest_static_file_block(StaticFileSegment::StorageChangeSets)
-            .unwrap_or(0);
-
         if sf_tip < checkpoint {
             // This should never happen in normal operation - static files are always
             // committed before RocksDB. If we get here, something is seriously wrong.
@@ -296,6 +354,15 @@ impl RocksDBProvider {
             return Ok(None);
         }
 
+        plan.push(HealAction::RebuildIndex {
+            table: tables::StoragesHistory::NAME,
+            from: checkpoint + 1,
+            to: sf_tip,
+        });
+        if !heal {
+            return Ok(None);
+        }
+
         let total_blocks = sf_tip - checkpoint;
         tracing::info!(
             target: "reth::providers::rocksdb",
@@ -349,6 +416,8 @@ impl RocksDBProvider {
     fn heal_accounts_history<Provider>(
         &self,
         provider: &Provider,
+        heal: bool,
+        plan: &mut HealPlan,
     ) -> ProviderResult<Option<BlockNumber>>
     where
         Provider: DBProvider + StageCheckpointReader + StaticFileProviderFactory + ChangeSetReader,
@@ -358,8 +427,22 @@ impl RocksDBProvider {
             .map(|cp| cp.block_number)
             .unwrap_or(0);
 
+        let sf_tip = provider
+            .static_file_provider()
+            .get_highest_static_file_block(StaticFileSegment::AccountChangeSets)
+            .unwrap_or(0);
+
         // Fast path: if checkpoint is 0 and RocksDB has data, clear everything.
         if checkpoint == 0 && self.first::<tables::AccountsHistory>()?.is_some() {
+            plan.push(HealAction::RebuildIndex {
+                table: tables::AccountsHistory::NAME,
+                from: 0,
+                to: sf_tip,
+            });
+            if !heal {
+                return Ok(None);
+            }
+
             tracing::info!(
                 target: "reth::providers::rocksdb",
                 "AccountsHistory has data but checkpoint is 0, clearing all"
@@ -368,11 +451,6 @@ impl RocksDBProvider {
             return Ok(None);
         }
 
-        let sf_tip = provider
-            .static_file_provider()
-            .get_highest_static_file_block(StaticFileSegment::AccountChangeSets)
-            .unwrap_or(0);
-
         if sf_tip < checkpoint {
             // This should never happen in normal operation - static files are always
             // committed before RocksDB. If we get here, something is seriously wrong.
@@ -390,6 +468,15 @@ impl RocksDBProvider {
             return Ok(None);
         }
 
+        plan.push(HealAction::RebuildIndex {
+            table: tables::AccountsHistory::NAME,
+            from: checkpoint + 1,
+            to: sf_tip,
+        });
+        if !heal {
+            return Ok(None);
+        }
+
         let total_blocks = sf_tip - checkpoint;
         tracing::info!(
             target: "reth::providers::rocksdb",
@@ -614,6 +701,44 @@ mod tests {
         }
     }
 
+    /// Tests that a consistency plan computed without healing leaves `RocksDB` untouched.
+    #[test]
+    fn test_consistency_plan_without_heal_keeps_rocksdb_data() {
+        let temp_dir = TempDir::new().unwrap();
+        let rocksdb = RocksDBBuilder::new(temp_dir.path())
+            .with_table::<tables::TransactionHashNumbers>()
+            .build()
+            .unwrap();
+
+        let factory = create_test_provider_factory();
+        factory.set_storage_settings_cache(
+            StorageSettings::legacy().with_transaction_hash_numbers_in_rocksdb(true),
+        );
+
+        // No checkpoint, but RocksDB has data.
+        let hash = B256::random();
+        rocksdb.put::<tables::TransactionHashNumbers>(hash, &0).unwrap();
+
+        let provider = factory.database_provider_ro().unwrap();
+
+        let plan = rocksdb.consistency_plan(&provider, false).unwrap();
+        assert_eq!(
+            plan.actions(),
+            &[HealAction::RebuildIndex {
+                table: tables::TransactionHashNumbers::NAME,
+                from: 0,
+                to: 0
+            }]
+        );
+        assert_eq!(plan.unwind_target(), None);
+        assert!(rocksdb.get::<tables::TransactionHashNumbers>(hash).unwrap().is_some());
+
+        // Healing executes the same plan.
+        let plan = rocksdb.consistency_plan(&provider, true).unwrap();
+        assert_eq!(plan.actions().len(), 1);
+        assert!(rocksdb.get::<tables::TransactionHashNumbers>(hash).unwrap().is_none());
+    }
+
     #[test]
     fn test_check_consistency_storages_history_empty_with_checkpoint_is_first_run() {
         let temp_dir = TempDir::new().unwrap();
@@ -1,5 +1,7 @@
 use super::metrics::{RocksDBMetrics, RocksDBOperation, ROCKSDB_TABLES};
-use crate::providers::{compute_history_rank, needs_prev_shard_check, HistoryInfo};
+use crate::providers::{
+    collect_history_blocks, compute_history_rank, needs_prev_shard_check, HistoryInfo,
+};
 use alloy_consensus::transaction::TxHashRef;
 use alloy_primitives::{Address, BlockNumber, TxNumber, B256};
 use itertools::Itertools;
@@ -30,6 +32,7 @@ use rocksdb::{
 use std::{
     collections::{BTreeMap, HashMap},
     fmt,
+    ops::RangeInclusive,
     path::{Path, PathBuf},
     sync::Arc,
     thread,
@@ -1897,6 +1900,70 @@ impl<'db> RocksTx<'db> {
         )
     }
 
+    /// Returns the blocks in the given range that changed the account, in ascending order.
+    pub fn account_history_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        let key = ShardedKey::new(address, *range.start());
+        self.history_blocks::<tables::AccountsHistory>(key.encode().as_ref(), range, |key_bytes| {
+            Ok(<ShardedKey<Address> as Decode>::decode(key_bytes)?.key == address)
+        })
+    }
+
+    /// Returns the blocks in the given range that changed the storage slot, in ascending order.
+    pub fn storage_history_blocks(
+        &self,
+        address: Address,
+        storage_key: B256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        let key = StorageShardedKey::new(address, storage_key, *range.start());
+        self.history_blocks::<tables::StoragesHistory>(key.encode().as_ref(), range, |key_bytes| {
+            let k = <StorageShardedKey as Decode>::decode(key_bytes)?;
+            Ok(k.address == address && k.sharded_key.key == storage_key)
+        })
+    }
+
+    /// Generic history range lookup for sharded history tables.
+    ///
+    /// Walks the shards starting at the one containing the start of `range` while the key
+    /// matches via `key_matches`, and returns the blocks within `range` in ascending order.
+    fn history_blocks<T>(
+        &self,
+        encoded_key: &[u8],
+        range: RangeInclusive<BlockNumber>,
+        key_matches: impl Fn(&[u8]) -> Result<bool, reth_db_api::DatabaseError>,
+    ) -> ProviderResult<Vec<BlockNumber>>
+    where
+        T: Table<Value = BlockNumberList>,
+    {
+        let cf = self.provider.0.cf_handle_rw(T::NAME)?;
+        let mut iter: DBRawIteratorWithThreadMode<'_, Transaction<'_, OptimisticTransactionDB>> =
+            self.inner.raw_iterator_cf(&cf);
+
+        let mut blocks = Vec::new();
+        iter.seek(encoded_key);
+        Self::raw_iter_status_ok(&iter)?;
+        while iter.valid() {
+            let (Some(key_bytes), Some(value_bytes)) = (iter.key(), iter.value()) else { break };
+            if !key_matches(key_bytes)? {
+                break
+            }
+
+            let chunk = BlockNumberList::decompress(value_bytes)?;
+            if !collect_history_blocks(&chunk, &range, &mut blocks) {
+                break
+            }
+
+            iter.next();
+            Self::raw_iter_status_ok(&iter)?;
+        }
+
+        Ok(blocks)
+    }
+
     /// Generic history lookup for sharded history tables.
     ///
     /// Seeks to the shard containing `block_number`, checks if the key matches via `key_matches`,
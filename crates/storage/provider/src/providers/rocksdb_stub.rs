@@ -4,6 +4,7 @@
 //! available (either on non-Unix platforms or when the `rocksdb` feature is not enabled).
 //! All method calls are cfg-guarded in the calling code, so only type definitions are needed here.
 
+use crate::providers::HealPlan;
 use alloy_primitives::BlockNumber;
 use metrics::Label;
 use parking_lot::Mutex;
@@ -84,6 +85,18 @@ impl RocksDBProvider {
         Ok(None)
     }
 
+    /// Computes the consistency healing plan of `RocksDB` tables (stub implementation).
+    ///
+    /// Returns an empty plan since there is no `RocksDB` data to check when the feature is
+    /// disabled.
+    pub fn consistency_plan<Provider>(
+        &self,
+        _provider: &Provider,
+        _heal: bool,
+    ) -> ProviderResult<HealPlan> {
+        Ok(HealPlan::default())
+    }
+
     /// Returns statistics for all column families in the database (stub implementation).
     ///
     /// Returns an empty vector since there is no `RocksDB` when the feature is disabled.
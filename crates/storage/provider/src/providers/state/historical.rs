@@ -2,7 +2,7 @@ use crate::{
     AccountReader, BlockHashReader, ChangeSetReader, EitherReader, HashedPostStateProvider,
     ProviderError, RocksDBProviderFactory, StateProvider, StateRootProvider,
 };
-use alloy_eips::merge::EPOCH_SLOTS;
+use alloy_eips::{merge::EPOCH_SLOTS, BlockNumHash};
 use alloy_primitives::{Address, BlockNumber, Bytes, StorageKey, StorageValue, B256};
 use reth_db_api::{
     cursor::{DbCursorRO, DbDupCursorRO},
@@ -30,7 +30,16 @@ use reth_trie_db::{
     DatabaseStateRoot, DatabaseStorageProof, DatabaseStorageRoot, DatabaseTrieWitness,
 };
 
-use std::fmt::Debug;
+use dashmap::DashMap;
+use parking_lot::Mutex;
+use std::{collections::VecDeque, fmt::Debug, ops::RangeInclusive, sync::Arc};
+
+/// The default number of historical blocks whose state reads are kept by the
+/// [`HistoricalStateCache`].
+pub const DEFAULT_HISTORICAL_STATE_CACHE_SIZE: usize = 16;
+
+/// The maximum number of accounts and of storage slots that are cached per historical block.
+const MAX_CACHED_READS_PER_BLOCK: usize = 100_000;
 
 /// Result of a history lookup for an account or storage slot.
 ///
@@ -107,6 +116,8 @@ pub struct HistoricalStateProviderRef<'b, Provider> {
     block_number: BlockNumber,
     /// Lowest blocks at which different parts of the state are available.
     lowest_available_blocks: LowestAvailableBlocks,
+    /// State reads shared with other providers for the same block.
+    cached_reads: Option<&'b HistoricalStateReads>,
 }
 
 impl<'b, Provider: DBProvider + ChangeSetReader + StorageChangeSetReader + BlockNumReader>
@@ -114,7 +125,12 @@ impl<'b, Provider: DBProvider + ChangeSetReader + StorageChangeSetReader + Block
 {
     /// Create new `StateProvider` for historical block number
     pub fn new(provider: &'b Provider, block_number: BlockNumber) -> Self {
-        Self { provider, block_number, lowest_available_blocks: Default::default() }
+        Self {
+            provider,
+            block_number,
+            lowest_available_blocks: Default::default(),
+            cached_reads: None,
+        }
     }
 
     /// Create new `StateProvider` for historical block number and lowest block numbers at which
@@ -124,7 +140,17 @@ impl<'b, Provider: DBProvider + ChangeSetReader + StorageChangeSetReader + Block
         block_number: BlockNumber,
         lowest_available_blocks: LowestAvailableBlocks,
     ) -> Self {
-        Self { provider, block_number, lowest_available_blocks }
+        Self { provider, block_number, lowest_available_blocks, cached_reads: None }
+    }
+
+    /// Shares the accounts and storage slots read from this state with other providers for the
+    /// same block, see [`HistoricalStateCache`].
+    pub const fn with_cached_reads(
+        mut self,
+        cached_reads: Option<&'b HistoricalStateReads>,
+    ) -> Self {
+        self.cached_reads = cached_reads;
+        self
     }
 
     /// Lookup an account in the `AccountsHistory` table using `EitherReader`.
@@ -250,6 +276,28 @@ impl<
 {
     /// Get basic account information.
     fn basic_account(&self, address: &Address) -> ProviderResult<Option<Account>> {
+        let Some(cached_reads) = self.cached_reads else { return self.read_account(address) };
+
+        // cached values may have been read before the history was pruned
+        if !self.lowest_available_blocks.is_account_history_available(self.block_number) {
+            return Err(ProviderError::StateAtBlockPruned(self.block_number))
+        }
+        cached_reads.account_or_try_insert_with(*address, || self.read_account(address))
+    }
+}
+
+impl<
+        Provider: DBProvider
+            + BlockNumReader
+            + ChangeSetReader
+            + StorageChangeSetReader
+            + StorageSettingsCache
+            + RocksDBProviderFactory
+            + NodePrimitivesProvider,
+    > HistoricalStateProviderRef<'_, Provider>
+{
+    /// Reads the account from the history.
+    fn read_account(&self, address: &Address) -> ProviderResult<Option<Account>> {
         match self.account_history_lookup(*address)? {
             HistoryInfo::NotYetWritten => Ok(None),
             HistoryInfo::InChangeset(changeset_block_number) => {
@@ -417,6 +465,36 @@ impl<
         &self,
         address: Address,
         storage_key: StorageKey,
+    ) -> ProviderResult<Option<StorageValue>> {
+        let Some(cached_reads) = self.cached_reads else {
+            return self.read_storage(address, storage_key)
+        };
+
+        // cached values may have been read before the history was pruned
+        if !self.lowest_available_blocks.is_storage_history_available(self.block_number) {
+            return Err(ProviderError::StateAtBlockPruned(self.block_number))
+        }
+        cached_reads.storage_or_try_insert_with(address, storage_key, || {
+            self.read_storage(address, storage_key)
+        })
+    }
+}
+
+impl<
+        Provider: DBProvider
+            + BlockNumReader
+            + ChangeSetReader
+            + StorageChangeSetReader
+            + StorageSettingsCache
+            + RocksDBProviderFactory
+            + NodePrimitivesProvider,
+    > HistoricalStateProviderRef<'_, Provider>
+{
+    /// Reads the storage slot from the history.
+    fn read_storage(
+        &self,
+        address: Address,
+        storage_key: StorageKey,
     ) -> ProviderResult<Option<StorageValue>> {
         match self.storage_history_lookup(address, storage_key)? {
             HistoryInfo::NotYetWritten => Ok(None),
@@ -460,6 +538,8 @@ pub struct HistoricalStateProvider<Provider> {
     block_number: BlockNumber,
     /// Lowest blocks at which different parts of the state are available.
     lowest_available_blocks: LowestAvailableBlocks,
+    /// State reads shared with other providers for the same block.
+    cached_reads: Option<Arc<HistoricalStateReads>>,
 }
 
 impl<Provider: DBProvider + ChangeSetReader + StorageChangeSetReader + BlockNumReader>
@@ -467,7 +547,19 @@ impl<Provider: DBProvider + ChangeSetReader + StorageChangeSetReader + BlockNumR
 {
     /// Create new `StateProvider` for historical block number
     pub fn new(provider: Provider, block_number: BlockNumber) -> Self {
-        Self { provider, block_number, lowest_available_blocks: Default::default() }
+        Self {
+            provider,
+            block_number,
+            lowest_available_blocks: Default::default(),
+            cached_reads: None,
+        }
+    }
+
+    /// Shares the accounts and storage slots read from this state with other providers for the
+    /// same block, see [`HistoricalStateCache`].
+    pub fn with_cached_reads(mut self, cached_reads: Arc<HistoricalStateReads>) -> Self {
+        self.cached_reads = Some(cached_reads);
+        self
     }
 
     /// Set the lowest block number at which the account history is available.
@@ -490,12 +582,13 @@ impl<Provider: DBProvider + ChangeSetReader + StorageChangeSetReader + BlockNumR
 
     /// Returns a new provider that takes the `TX` as reference
     #[inline(always)]
-    const fn as_ref(&self) -> HistoricalStateProviderRef<'_, Provider> {
+    fn as_ref(&self) -> HistoricalStateProviderRef<'_, Provider> {
         HistoricalStateProviderRef::new_with_lowest_available_blocks(
             &self.provider,
             self.block_number,
             self.lowest_available_blocks,
         )
+        .with_cached_reads(self.cached_reads.as_deref())
     }
 }
 
@@ -530,6 +623,133 @@ impl LowestAvailableBlocks {
     }
 }
 
+/// A cache of the accounts and storage slots read from recently queried historical states.
+///
+/// Reading an account or storage slot of a historical state requires lookups in the history and
+/// changeset tables, which is a lot more expensive than a read of the plain state. Concurrent
+/// requests against the same historical block, e.g. `eth_call`s, mostly read the same accounts
+/// and slots, so the values read by any provider are shared with all providers for that block.
+///
+/// The state at a block only depends on that block and its ancestors, so the reads are keyed by
+/// the block alone and stay valid until they're evicted. The cache keeps the reads of the most
+/// recently used blocks up to its capacity.
+#[derive(Debug, Clone)]
+pub struct HistoricalStateCache {
+    inner: Arc<Mutex<HistoricalStateCacheInner>>,
+}
+
+impl Default for HistoricalStateCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_HISTORICAL_STATE_CACHE_SIZE)
+    }
+}
+
+impl HistoricalStateCache {
+    /// Creates a new cache that keeps the reads of up to `capacity` historical blocks.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(HistoricalStateCacheInner {
+                capacity,
+                entries: VecDeque::with_capacity(capacity),
+            })),
+        }
+    }
+
+    /// Returns the number of cached blocks.
+    pub fn len(&self) -> usize {
+        self.inner.lock().entries.len()
+    }
+
+    /// Returns `true` if the cache is empty.
+    pub fn is_empty(&self) -> bool {
+        self.inner.lock().entries.is_empty()
+    }
+
+    /// Removes all entries.
+    pub fn clear(&self) {
+        self.inner.lock().entries.clear();
+    }
+
+    /// Returns the reads of the state after the given block, inserting empty reads if there are
+    /// none.
+    ///
+    /// Returns `None` if the cache has no capacity.
+    pub fn reads(&self, block: BlockNumHash) -> Option<Arc<HistoricalStateReads>> {
+        let mut inner = self.inner.lock();
+        if inner.capacity == 0 {
+            return None
+        }
+
+        let reads = match inner.entries.iter().position(|(entry, _)| *entry == block) {
+            Some(idx) => inner.entries.remove(idx).expect("index is in bounds").1,
+            None => {
+                if inner.entries.len() >= inner.capacity {
+                    inner.entries.pop_front();
+                }
+                Default::default()
+            }
+        };
+        inner.entries.push_back((block, Arc::clone(&reads)));
+        Some(reads)
+    }
+}
+
+#[derive(Debug)]
+struct HistoricalStateCacheInner {
+    /// The maximum number of blocks.
+    capacity: usize,
+    /// The reads by block, least recently used first.
+    entries: VecDeque<(BlockNumHash, Arc<HistoricalStateReads>)>,
+}
+
+/// The accounts and storage slots read from a historical state, see [`HistoricalStateCache`].
+#[derive(Debug, Default)]
+pub struct HistoricalStateReads {
+    /// Accounts by address.
+    accounts: DashMap<Address, Option<Account>>,
+    /// Storage values by address and slot.
+    storages: DashMap<(Address, StorageKey), Option<StorageValue>>,
+}
+
+impl HistoricalStateReads {
+    /// Returns the cached account, or reads and caches it.
+    ///
+    /// Errors are not cached.
+    fn account_or_try_insert_with<E>(
+        &self,
+        address: Address,
+        read: impl FnOnce() -> Result<Option<Account>, E>,
+    ) -> Result<Option<Account>, E> {
+        if let Some(account) = self.accounts.get(&address) {
+            return Ok(*account)
+        }
+        let account = read()?;
+        if self.accounts.len() < MAX_CACHED_READS_PER_BLOCK {
+            self.accounts.insert(address, account);
+        }
+        Ok(account)
+    }
+
+    /// Returns the cached storage value, or reads and caches it.
+    ///
+    /// Errors are not cached.
+    fn storage_or_try_insert_with<E>(
+        &self,
+        address: Address,
+        storage_key: StorageKey,
+        read: impl FnOnce() -> Result<Option<StorageValue>, E>,
+    ) -> Result<Option<StorageValue>, E> {
+        if let Some(value) = self.storages.get(&(address, storage_key)) {
+            return Ok(*value)
+        }
+        let value = read()?;
+        if self.storages.len() < MAX_CACHED_READS_PER_BLOCK {
+            self.storages.insert((address, storage_key), value);
+        }
+        Ok(value)
+    }
+}
+
 /// Computes the rank and finds the next modification block in a history shard.
 ///
 /// Given a `block_number`, this function returns:
@@ -569,6 +789,51 @@ pub fn needs_prev_shard_check(
     rank == 0 && found_block != Some(block_number)
 }
 
+/// Appends the blocks of a history shard that are within `range` to `blocks`.
+///
+/// Returns `true` if the following shards of the same key may still contain blocks within
+/// `range`.
+///
+/// This logic is shared between MDBX cursor-based and `RocksDB` iterator-based range lookups.
+#[inline]
+pub fn collect_history_blocks(
+    chunk: &BlockNumberList,
+    range: &RangeInclusive<BlockNumber>,
+    blocks: &mut Vec<BlockNumber>,
+) -> bool {
+    blocks.extend(
+        chunk
+            .iter()
+            .skip_while(|block| block < range.start())
+            .take_while(|block| block <= range.end()),
+    );
+    chunk.max().is_some_and(|max| max < *range.end())
+}
+
+/// Generic history range lookup for sharded history tables.
+///
+/// Walks the shards starting at the one containing the start of `range` while the key matches
+/// `key_filter`, and returns the blocks within `range` in ascending order.
+pub fn history_blocks<T, K, C>(
+    cursor: &mut C,
+    key: K,
+    range: RangeInclusive<BlockNumber>,
+    key_filter: impl Fn(&K) -> bool,
+) -> ProviderResult<Vec<BlockNumber>>
+where
+    T: Table<Key = K, Value = BlockNumberList>,
+    C: DbCursorRO<T>,
+{
+    let mut blocks = Vec::new();
+    for entry in cursor.walk(Some(key))? {
+        let (key, chunk) = entry?;
+        if !key_filter(&key) || !collect_history_blocks(&chunk, &range, &mut blocks) {
+            break
+        }
+    }
+    Ok(blocks)
+}
+
 /// Generic history lookup for sharded history tables.
 ///
 /// Seeks to the shard containing `block_number`, verifies the key via `key_filter`,
@@ -616,13 +881,14 @@ where
 
 #[cfg(test)]
 mod tests {
-    use super::needs_prev_shard_check;
+    use super::{needs_prev_shard_check, HistoricalStateCache, HistoricalStateReads};
     use crate::{
         providers::state::historical::{HistoryInfo, LowestAvailableBlocks},
         test_utils::create_test_provider_factory,
         AccountReader, HistoricalStateProvider, HistoricalStateProviderRef, RocksDBProviderFactory,
         StateProvider,
     };
+    use alloy_eips::BlockNumHash;
     use alloy_primitives::{address, b256, Address, B256, U256};
     use reth_db_api::{
         models::{storage_sharded_key::StorageShardedKey, AccountBeforeTx, ShardedKey},
@@ -631,11 +897,14 @@ mod tests {
         BlockNumberList,
     };
     use reth_primitives_traits::{Account, StorageEntry};
+    use reth_prune_types::{PruneCheckpoint, PruneMode, PruneSegment};
     use reth_storage_api::{
         BlockHashReader, BlockNumReader, ChangeSetReader, DBProvider, DatabaseProviderFactory,
-        NodePrimitivesProvider, StorageChangeSetReader, StorageSettingsCache,
+        HistoryReader, NodePrimitivesProvider, PruneCheckpointWriter, StorageChangeSetReader,
+        StorageSettingsCache,
     };
     use reth_storage_errors::provider::ProviderError;
+    use std::sync::Arc;
 
     const ADDRESS: Address = address!("0x0000000000000000000000000000000000000001");
     const HIGHER_ADDRESS: Address = address!("0x0000000000000000000000000000000000000005");
@@ -865,6 +1134,113 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn history_range() {
+        let factory = create_test_provider_factory();
+        let tx = factory.provider_rw().unwrap().into_tx();
+
+        tx.put::<tables::AccountsHistory>(
+            ShardedKey { key: ADDRESS, highest_block_number: 7 },
+            BlockNumberList::new([1, 3, 7]).unwrap(),
+        )
+        .unwrap();
+        tx.put::<tables::AccountsHistory>(
+            ShardedKey { key: ADDRESS, highest_block_number: u64::MAX },
+            BlockNumberList::new([10, 15]).unwrap(),
+        )
+        .unwrap();
+        tx.put::<tables::AccountsHistory>(
+            ShardedKey { key: HIGHER_ADDRESS, highest_block_number: u64::MAX },
+            BlockNumberList::new([4]).unwrap(),
+        )
+        .unwrap();
+        for block in [1, 3, 7, 10, 15] {
+            let info = (block > 1).then_some(Account {
+                nonce: block,
+                balance: U256::ZERO,
+                bytecode_hash: None,
+            });
+            tx.put::<tables::AccountChangeSets>(block, AccountBeforeTx { address: ADDRESS, info })
+                .unwrap();
+        }
+
+        tx.put::<tables::StoragesHistory>(
+            StorageShardedKey { address: ADDRESS, sharded_key: ShardedKey::new(STORAGE, u64::MAX) },
+            BlockNumberList::new([3, 10]).unwrap(),
+        )
+        .unwrap();
+        for block in [3, 10] {
+            tx.put::<tables::StorageChangeSets>(
+                (block, ADDRESS).into(),
+                StorageEntry { key: STORAGE, value: U256::from(block) },
+            )
+            .unwrap();
+        }
+        tx.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        let nonces = |range| {
+            provider
+                .account_history_range(ADDRESS, range)
+                .unwrap()
+                .into_iter()
+                .map(|(block, info)| (block, info.map(|acc| acc.nonce)))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            nonces(0..=u64::MAX),
+            [(1, None), (3, Some(3)), (7, Some(7)), (10, Some(10)), (15, Some(15))]
+        );
+        assert_eq!(nonces(3..=10), [(3, Some(3)), (7, Some(7)), (10, Some(10))]);
+        assert!(nonces(8..=9).is_empty());
+        assert!(provider.account_history_range(HIGHER_ADDRESS, 5..=10).unwrap().is_empty());
+
+        assert_eq!(
+            provider.storage_history_range(ADDRESS, STORAGE, 4..=20).unwrap(),
+            [(10, U256::from(10))]
+        );
+        drop(provider);
+
+        // ranges starting at pruned history are rejected instead of served incompletely
+        let provider_rw = factory.provider_rw().unwrap();
+        for segment in [PruneSegment::AccountHistory, PruneSegment::StorageHistory] {
+            provider_rw
+                .save_prune_checkpoint(
+                    segment,
+                    PruneCheckpoint {
+                        block_number: Some(3),
+                        tx_number: None,
+                        prune_mode: PruneMode::Before(4),
+                    },
+                )
+                .unwrap();
+        }
+        provider_rw.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        assert!(matches!(
+            provider.account_history_range(ADDRESS, 3..=10),
+            Err(ProviderError::StateAtBlockPruned(3))
+        ));
+        assert_eq!(
+            provider
+                .account_history_range(ADDRESS, 4..=10)
+                .unwrap()
+                .into_iter()
+                .map(|(block, _)| block)
+                .collect::<Vec<_>>(),
+            [7, 10]
+        );
+        assert!(matches!(
+            provider.storage_history_range(ADDRESS, STORAGE, 0..=20),
+            Err(ProviderError::StateAtBlockPruned(0))
+        ));
+        assert_eq!(
+            provider.storage_history_range(ADDRESS, STORAGE, 4..=20).unwrap(),
+            [(10, U256::from(10))]
+        );
+    }
+
     #[test]
     fn history_provider_unavailable() {
         let factory = create_test_provider_factory();
@@ -951,4 +1327,93 @@ mod tests {
         assert!(!needs_prev_shard_check(0, Some(5), 5)); // found_block == block_number
         assert!(!needs_prev_shard_check(1, Some(10), 5)); // rank > 0
     }
+
+    #[test]
+    fn historical_state_cache_evicts_blocks() {
+        let cache = HistoricalStateCache::new(2);
+        let block = |number| BlockNumHash::new(number, B256::with_last_byte(number as u8));
+
+        let first = cache.reads(block(1)).unwrap();
+        assert!(Arc::ptr_eq(&first, &cache.reads(block(1)).unwrap()));
+
+        // a different block with the same number has its own reads
+        let reorged = BlockNumHash::new(1, B256::with_last_byte(100));
+        assert!(!Arc::ptr_eq(&first, &cache.reads(reorged).unwrap()));
+
+        // the least recently used block is evicted
+        cache.reads(block(2)).unwrap();
+        assert_eq!(cache.len(), 2);
+        assert!(!Arc::ptr_eq(&first, &cache.reads(block(1)).unwrap()));
+
+        assert!(HistoricalStateCache::new(0).reads(block(1)).is_none());
+    }
+
+    #[test]
+    fn history_provider_shares_cached_reads() {
+        let factory = create_test_provider_factory();
+        let tx = factory.provider_rw().unwrap().into_tx();
+        let account = Account { nonce: 3, balance: U256::ZERO, bytecode_hash: None };
+        tx.put::<tables::AccountsHistory>(
+            ShardedKey { key: ADDRESS, highest_block_number: u64::MAX },
+            BlockNumberList::new([3]).unwrap(),
+        )
+        .unwrap();
+        tx.put::<tables::AccountChangeSets>(
+            3,
+            AccountBeforeTx { address: ADDRESS, info: Some(account) },
+        )
+        .unwrap();
+        tx.put::<tables::StoragesHistory>(
+            StorageShardedKey {
+                address: ADDRESS,
+                sharded_key: ShardedKey { key: STORAGE, highest_block_number: u64::MAX },
+            },
+            BlockNumberList::new([3]).unwrap(),
+        )
+        .unwrap();
+        tx.put::<tables::StorageChangeSets>(
+            (3, ADDRESS).into(),
+            StorageEntry { key: STORAGE, value: U256::from(3) },
+        )
+        .unwrap();
+        tx.commit().unwrap();
+
+        let reads = Arc::new(HistoricalStateReads::default());
+        let db = factory.provider().unwrap();
+        let provider = HistoricalStateProviderRef::new(&db, 2).with_cached_reads(Some(&reads));
+        assert_eq!(provider.basic_account(&ADDRESS).unwrap(), Some(account));
+        assert_eq!(provider.storage(ADDRESS, STORAGE).unwrap(), Some(U256::from(3)));
+        drop(db);
+
+        // other providers for the block read the values from the cache
+        let tx = factory.provider_rw().unwrap().into_tx();
+        tx.clear::<tables::AccountChangeSets>().unwrap();
+        tx.clear::<tables::StorageChangeSets>().unwrap();
+        tx.commit().unwrap();
+
+        let db = factory.provider().unwrap();
+        let provider = HistoricalStateProviderRef::new(&db, 2).with_cached_reads(Some(&reads));
+        assert_eq!(provider.basic_account(&ADDRESS).unwrap(), Some(account));
+        assert_eq!(provider.storage(ADDRESS, STORAGE).unwrap(), Some(U256::from(3)));
+        assert!(HistoricalStateProviderRef::new(&db, 2).basic_account(&ADDRESS).is_err());
+
+        // unless the history was pruned in the meantime
+        let provider = HistoricalStateProviderRef::new_with_lowest_available_blocks(
+            &db,
+            2,
+            LowestAvailableBlocks {
+                account_history_block_number: Some(3),
+                storage_history_block_number: Some(3),
+            },
+        )
+        .with_cached_reads(Some(&reads));
+        assert!(matches!(
+            provider.basic_account(&ADDRESS),
+            Err(ProviderError::StateAtBlockPruned(2))
+        ));
+        assert!(matches!(
+            provider.storage(ADDRESS, STORAGE),
+            Err(ProviderError::StateAtBlockPruned(2))
+        ));
+    }
 }
@@ -1,5 +1,6 @@
 use super::{
     metrics::{StaticFileProviderMetrics, StaticFileProviderOperation},
+    receipts_index::read_receipt_block_offsets,
     LoadedJarRef,
 };
 use crate::{
@@ -17,7 +18,7 @@ use reth_db::static_file::{
 use reth_db_api::table::{Decompress, Value};
 use reth_node_types::NodePrimitives;
 use reth_primitives_traits::{SealedHeader, SignedTransaction};
-use reth_storage_api::range_size_hint;
+use reth_storage_api::{range_size_hint, ReceiptBlockOffsets};
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
 use std::{
     fmt::Debug,
@@ -301,6 +302,15 @@ impl<N: NodePrimitives<SignedTx: Decompress + SignedTransaction, Receipt: Decomp
         self.cursor()?.get_one::<ReceiptMask<Self::Receipt>>(num.into())
     }
 
+    fn receipt_block_offsets(&self, num: TxNumber) -> ProviderResult<Option<ReceiptBlockOffsets>> {
+        let Some(tx_range) = self.user_header().tx_range().filter(|range| range.contains(num))
+        else {
+            return Ok(None)
+        };
+        read_receipt_block_offsets(self.data_path(), self.rows() as u64, num - tx_range.start())
+            .map_err(ProviderError::other)
+    }
+
     fn receipt_by_hash(&self, hash: TxHash) -> ProviderResult<Option<Self::Receipt>> {
         if let Some(tx_static_file) = &self.auxiliary_jar &&
             let Some(num) = tx_static_file.transaction_id(hash)?
@@ -1,16 +1,23 @@
 use super::{
-    metrics::StaticFileProviderMetrics, writer::StaticFileWriters, LoadedJar,
-    StaticFileJarProvider, StaticFileProviderRW, StaticFileProviderRWRefMut,
+    metrics::StaticFileProviderMetrics,
+    receipts_index::{has_receipts_index, remove_receipts_index, ReceiptsIndexWriter},
+    replace::recover_replacements,
+    writer::StaticFileWriters,
+    LoadedJar, SharedStaticFiles, StaticFileJarProvider, StaticFileProviderRW,
+    StaticFileProviderRWRefMut,
 };
 use crate::{
     changeset_walker::{StaticFileAccountChangesetWalker, StaticFileStorageChangesetWalker},
+    providers::{HealAction, HealPlan},
     to_range, BlockHashReader, BlockNumReader, BlockReader, BlockSource, EitherWriter,
     EitherWriterDestination, HeaderProvider, ReceiptProvider, StageCheckpointReader, StatsReader,
     TransactionVariant, TransactionsProvider, TransactionsProviderExt,
 };
-use alloy_consensus::{transaction::TransactionMeta, Header};
+use alloy_consensus::{transaction::TransactionMeta, Header, TxReceipt};
 use alloy_eips::{eip2718::Encodable2718, BlockHashOrNumber};
-use alloy_primitives::{b256, keccak256, Address, BlockHash, BlockNumber, TxHash, TxNumber, B256};
+use alloy_primitives::{
+    b256, keccak256, Address, BlockHash, BlockNumber, Bloom, BloomInput, TxHash, TxNumber, B256,
+};
 use dashmap::DashMap;
 use notify::{RecommendedWatcher, RecursiveMode, Watcher};
 use parking_lot::RwLock;
@@ -19,8 +26,9 @@ use reth_chainspec::{ChainInfo, ChainSpecProvider, EthChainSpec, NamedChain};
 use reth_db::{
     lockfile::StorageLock,
     static_file::{
-        iter_static_files, BlockHashMask, HeaderMask, HeaderWithHashMask, ReceiptMask,
-        StaticFileCursor, StorageChangesetMask, TransactionMask, TransactionSenderMask,
+        iter_static_files, iter_static_files_in_dirs, BlockHashMask, HeaderMask,
+        HeaderWithHashMask, ReceiptMask, StaticFileCursor, StorageChangesetMask, TransactionMask,
+        TransactionSenderMask,
     },
 };
 use reth_db_api::{
@@ -43,12 +51,12 @@ use reth_static_file_types::{
     StaticFileSegment, DEFAULT_BLOCKS_PER_STATIC_FILE,
 };
 use reth_storage_api::{
-    BlockBodyIndicesProvider, ChangeSetReader, DBProvider, StorageChangeSetReader,
-    StorageSettingsCache,
+    BlockBodyIndicesProvider, ChangeSetReader, DBProvider, LogsBloomIndexReader,
+    ReceiptBlockOffsets, StorageChangeSetReader, StorageSettingsCache,
 };
 use reth_storage_errors::provider::{ProviderError, ProviderResult, StaticFileWriterError};
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, BTreeSet},
     fmt::Debug,
     ops::{Bound, Deref, Range, RangeBounds, RangeInclusive},
     path::{Path, PathBuf},
@@ -61,6 +69,15 @@ use tracing::{debug, info, instrument, trace, warn};
 /// represent either a block or a transaction number end of a static file range.
 type SegmentRanges = BTreeMap<u64, SegmentRangeInclusive>;
 
+/// Logs bloom aggregated over the headers of a static file.
+#[derive(Debug, Clone, Copy)]
+struct AggregatedBloom {
+    /// Highest block whose logs bloom is included.
+    last_block: BlockNumber,
+    /// Union of the logs blooms of all included blocks.
+    bloom: Bloom,
+}
+
 /// Access mode on a static file provider. RO/RW.
 #[derive(Debug, Default, PartialEq, Eq)]
 pub enum StaticFileAccess {
@@ -129,6 +146,8 @@ pub struct StaticFileProviderBuilder<P> {
     blocks_per_file: StaticFileMap<u64>,
     path: P,
     genesis_block_number: u64,
+    shared_dir: Option<PathBuf>,
+    segment_dirs: StaticFileMap<PathBuf>,
 }
 
 impl<P: AsRef<Path>> StaticFileProviderBuilder<P> {
@@ -140,6 +159,8 @@ impl<P: AsRef<Path>> StaticFileProviderBuilder<P> {
             blocks_per_file: Default::default(),
             use_metrics: false,
             genesis_block_number: 0,
+            shared_dir: None,
+            segment_dirs: Default::default(),
         }
     }
 
@@ -151,6 +172,8 @@ impl<P: AsRef<Path>> StaticFileProviderBuilder<P> {
             blocks_per_file: Default::default(),
             use_metrics: false,
             genesis_block_number: 0,
+            shared_dir: None,
+            segment_dirs: Default::default(),
         }
     }
 
@@ -216,6 +239,39 @@ impl<P: AsRef<Path>> StaticFileProviderBuilder<P> {
         self
     }
 
+    /// Sets the directory where finished static files are shared with other nodes, see
+    /// [`SharedStaticFiles`].
+    ///
+    /// Only applies to providers with read-write access. Static files that were finished before
+    /// are shared on [`Self::build`].
+    pub fn with_shared_dir(mut self, shared_dir: Option<PathBuf>) -> Self {
+        self.shared_dir = shared_dir;
+        self
+    }
+
+    /// Sets the directories of the segments whose static files are not stored in the static files
+    /// directory, e.g. to keep receipts on a different volume than headers.
+    ///
+    /// [`Self::build`] fails if static files of these segments are still located in the static
+    /// files directory, they have to be moved to the directory of their segment first.
+    pub fn with_segment_dirs(
+        mut self,
+        segment_dirs: &<StaticFileMap<PathBuf> as Deref>::Target,
+    ) -> Self {
+        for (segment, dir) in segment_dirs {
+            self.segment_dirs.insert(segment, dir.clone());
+        }
+        self
+    }
+
+    /// Sets the directory of the static files of a specific segment.
+    ///
+    /// See [`Self::with_segment_dirs`].
+    pub fn with_segment_dir(mut self, segment: StaticFileSegment, dir: PathBuf) -> Self {
+        self.segment_dirs.insert(segment, dir);
+        self
+    }
+
     /// Builds the final [`StaticFileProvider`] and initializes the index.
     pub fn build<N: NodePrimitives>(self) -> ProviderResult<StaticFileProvider<N>> {
         let mut provider = StaticFileProviderInner::new(self.path, self.access)?;
@@ -227,9 +283,16 @@ impl<P: AsRef<Path>> StaticFileProviderBuilder<P> {
             provider.blocks_per_file.insert(segment, blocks_per_file);
         }
         provider.genesis_block_number = self.genesis_block_number;
+        provider.set_segment_dirs(self.segment_dirs)?;
+
+        if let Some(shared_dir) = self.shared_dir.filter(|_| provider.access.is_read_write()) {
+            provider.shared =
+                Some(SharedStaticFiles::open(shared_dir).map_err(ProviderError::other)?);
+        }
 
         let provider = StaticFileProvider(Arc::new(provider));
         provider.initialize_index()?;
+        provider.share_finished_static_files()?;
         Ok(provider)
     }
 }
@@ -283,9 +346,9 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
             )
             .expect("failed to create watcher");
 
-            watcher
-                .watch(&provider.path, RecursiveMode::NonRecursive)
-                .expect("failed to watch path");
+            for dir in provider.directories() {
+                watcher.watch(dir, RecursiveMode::NonRecursive).expect("failed to watch path");
+            }
 
             // Some backends send repeated modified events
             let mut last_event_timestamp = None;
@@ -382,6 +445,8 @@ pub struct StaticFileProviderInner<N> {
     earliest_history_height: AtomicU64,
     /// Directory where `static_files` are located
     path: PathBuf,
+    /// Directories of the segments whose static files are not located in [`Self::path`].
+    segment_dirs: StaticFileMap<PathBuf>,
     /// Maintains a writer set of [`StaticFileSegment`].
     writers: StaticFileWriters<N>,
     /// Metrics for the static files.
@@ -392,15 +457,23 @@ pub struct StaticFileProviderInner<N> {
     blocks_per_file: StaticFileMap<u64>,
     /// Write lock for when access is [`StaticFileAccess::RW`].
     _lock_file: Option<StorageLock>,
+    /// Write locks of the segment directories for when access is [`StaticFileAccess::RW`].
+    _segment_dir_lock_files: Vec<StorageLock>,
     /// Genesis block number, default is 0;
     genesis_block_number: u64,
+    /// Store that finished static files are shared with, if any.
+    shared: Option<SharedStaticFiles>,
+    /// Aggregated logs blooms of the headers static files, keyed by the end of their block range.
+    headers_blooms: DashMap<BlockNumber, AggregatedBloom>,
 }
 
 impl<N: NodePrimitives> StaticFileProviderInner<N> {
     /// Creates a new [`StaticFileProviderInner`].
     fn new(path: impl AsRef<Path>, access: StaticFileAccess) -> ProviderResult<Self> {
         let _lock_file = if access.is_read_write() {
-            StorageLock::try_acquire(path.as_ref()).map_err(ProviderError::other)?.into()
+            let lock = StorageLock::try_acquire(path.as_ref()).map_err(ProviderError::other)?;
+            recover_replacements(path.as_ref())?;
+            lock.into()
         } else {
             None
         };
@@ -416,16 +489,58 @@ impl<N: NodePrimitives> StaticFileProviderInner<N> {
             writers: Default::default(),
             earliest_history_height: Default::default(),
             path: path.as_ref().to_path_buf(),
+            segment_dirs: Default::default(),
             metrics: None,
             access,
             blocks_per_file,
             _lock_file,
+            _segment_dir_lock_files: Vec::new(),
             genesis_block_number: 0,
+            shared: None,
+            headers_blooms: Default::default(),
         };
 
         Ok(provider)
     }
 
+    /// Sets the directories of the segments whose static files are not located in [`Self::path`],
+    /// and locks them like [`Self::path`] if the access is read-write. Interrupted replacements of
+    /// static files in them are completed as well, see [`super::replace_static_file`].
+    ///
+    /// Fails if static files of these segments are located in [`Self::path`], since they would be
+    /// ignored.
+    fn set_segment_dirs(&mut self, segment_dirs: StaticFileMap<PathBuf>) -> ProviderResult<()> {
+        if segment_dirs.values().all(|dir| *dir == self.path) {
+            return Ok(())
+        }
+
+        let static_files = iter_static_files(&self.path).map_err(ProviderError::other)?;
+        for (segment, dir) in segment_dirs.iter() {
+            if *dir != self.path && static_files.get(segment).is_some_and(|files| !files.is_empty())
+            {
+                return Err(ProviderError::MisplacedStaticFiles(
+                    segment,
+                    self.path.clone(),
+                    dir.clone(),
+                ))
+            }
+        }
+
+        if self.access.is_read_write() {
+            let dirs =
+                segment_dirs.values().filter(|dir| **dir != self.path).collect::<BTreeSet<_>>();
+            for dir in dirs {
+                std::fs::create_dir_all(dir).map_err(ProviderError::other)?;
+                self._segment_dir_lock_files
+                    .push(StorageLock::try_acquire(dir).map_err(ProviderError::other)?);
+                recover_replacements(dir)?;
+            }
+        }
+
+        self.segment_dirs = segment_dirs;
+        Ok(())
+    }
+
     pub const fn is_read_only(&self) -> bool {
         self.access.is_read_only()
     }
@@ -508,7 +623,7 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
     pub fn report_metrics(&self) -> ProviderResult<()> {
         let Some(metrics) = &self.metrics else { return Ok(()) };
 
-        let static_files = iter_static_files(&self.path).map_err(ProviderError::other)?;
+        let static_files = self.static_files()?;
         for (segment, headers) in &*static_files {
             let mut entries = 0;
             let mut size = 0;
@@ -964,7 +1079,7 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         let jar = if let Some((_, jar)) = self.map.remove(&key) {
             jar.jar
         } else {
-            let file = self.path.join(segment.filename(&fixed_block_range));
+            let file = self.segment_directory(segment).join(segment.filename(&fixed_block_range));
             debug!(
                 target: "provider::static_file",
                 ?file,
@@ -976,8 +1091,15 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         };
 
         let header = jar.user_header().clone();
+        if segment == StaticFileSegment::Receipts {
+            remove_receipts_index(jar.data_path()).map_err(ProviderError::other)?;
+        }
         jar.delete().map_err(ProviderError::other)?;
 
+        if let Some(shared) = &self.shared {
+            shared.remove_unreferenced().map_err(ProviderError::other)?;
+        }
+
         // SAFETY: this is currently necessary to ensure that certain indexes like
         // `static_files_min_block` have the correct values after pruning.
         self.initialize_index()?;
@@ -985,6 +1107,72 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         Ok(header)
     }
 
+    /// Returns the block ranges of the headers static files within `range` that may contain logs
+    /// of the given address, one range per static file.
+    ///
+    /// The logs blooms of all headers of a static file are aggregated into a single bloom, which
+    /// is cached, so log queries can skip entire static files without checking the logs bloom of
+    /// every block. Blocks that are not in static files are never part of the result.
+    pub fn segments_containing_address(
+        &self,
+        address: &Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<RangeInclusive<BlockNumber>>> {
+        let segment = StaticFileSegment::Headers;
+        let Some(highest_block) = self.get_highest_static_file_block(segment) else {
+            return Ok(Vec::new())
+        };
+
+        let end = (*range.end()).min(highest_block);
+        let mut ranges = Vec::new();
+        let mut start = *range.start();
+        while start <= end {
+            let fixed_block_range = self.find_fixed_range(segment, start);
+            let bloom = self.aggregated_headers_bloom(
+                &fixed_block_range,
+                fixed_block_range.end().min(highest_block),
+            )?;
+            if bloom.contains_input(BloomInput::Raw(address.as_slice())) {
+                ranges.push(start..=fixed_block_range.end().min(end));
+            }
+
+            if fixed_block_range.end() >= end {
+                break
+            }
+            start = fixed_block_range.end() + 1;
+        }
+
+        Ok(ranges)
+    }
+
+    /// Returns the logs bloom aggregated over the headers of the static file with the given block
+    /// range up to `last_block`, extending the cached bloom if necessary.
+    fn aggregated_headers_bloom(
+        &self,
+        fixed_block_range: &SegmentRangeInclusive,
+        last_block: BlockNumber,
+    ) -> ProviderResult<Bloom> {
+        let cached = self.headers_blooms.get(&fixed_block_range.end()).map(|entry| *entry);
+        let (first_block, mut bloom) = match cached {
+            Some(cached) if cached.last_block >= last_block => return Ok(cached.bloom),
+            Some(cached) => (cached.last_block + 1, cached.bloom),
+            None => (fixed_block_range.start(), Bloom::ZERO),
+        };
+
+        for header in self.fetch_range_iter(
+            StaticFileSegment::Headers,
+            first_block..last_block + 1,
+            |cursor, number| cursor.get_one::<HeaderMask<N::BlockHeader>>(number.into()),
+        )? {
+            if let Some(header) = header? {
+                bloom.accrue_bloom(&header.logs_bloom());
+            }
+        }
+
+        self.headers_blooms.insert(fixed_block_range.end(), AggregatedBloom { last_block, bloom });
+        Ok(bloom)
+    }
+
     /// Given a segment and block range it returns a cached
     /// [`StaticFileJarProvider`]. TODO(joshie): we should check the size and pop N if there's too
     /// many.
@@ -1002,7 +1190,7 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
             jar.into()
         } else {
             trace!(target: "provider::static_file", ?segment, ?fixed_block_range, "Creating jar from scratch");
-            let path = self.path.join(segment.filename(fixed_block_range));
+            let path = self.segment_directory(segment).join(segment.filename(fixed_block_range));
             let jar = NippyJar::load(&path).map_err(ProviderError::other)?;
             self.map.entry(key).insert(LoadedJar::new(jar)?).downgrade().into()
         };
@@ -1083,6 +1271,13 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         );
         let mut indexes = self.indexes.write();
 
+        if segment.is_headers() {
+            // Aggregated blooms that include unwound blocks have to be recomputed.
+            self.headers_blooms.retain(|_, aggregated| {
+                segment_max_block.is_some_and(|max_block| aggregated.last_block <= max_block)
+            });
+        }
+
         match segment_max_block {
             Some(segment_max_block) => {
                 let fixed_range = self.find_fixed_range_with_block_index(
@@ -1092,7 +1287,7 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
                 );
 
                 let jar = NippyJar::<SegmentHeader>::load(
-                    &self.path.join(segment.filename(&fixed_range)),
+                    &self.segment_directory(segment).join(segment.filename(&fixed_range)),
                 )
                 .map_err(ProviderError::other)?;
 
@@ -1209,8 +1404,9 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
     pub fn initialize_index(&self) -> ProviderResult<()> {
         let mut indexes = self.indexes.write();
         indexes.clear();
+        self.headers_blooms.clear();
 
-        for (segment, headers) in &*iter_static_files(&self.path).map_err(ProviderError::other)? {
+        for (segment, headers) in &*self.static_files()? {
             // Update first and last block for each segment
             //
             // It's safe to call `expect` here, because every segment has at least one header
@@ -1289,6 +1485,31 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         &self,
         provider: &Provider,
     ) -> ProviderResult<Option<PipelineTarget>>
+    where
+        Provider: DBProvider
+            + BlockReader
+            + StageCheckpointReader
+            + ChainSpecProvider
+            + StorageSettingsCache,
+        N: NodePrimitives<Receipt: Value, BlockHeader: Value, SignedTx: Value>,
+    {
+        Ok(self.consistency_plan(provider, true)?.unwind_target().map(PipelineTarget::Unwind))
+    }
+
+    /// Runs the same checks as [`Self::check_consistency`], returning every action required to
+    /// restore consistency as a [`HealPlan`].
+    ///
+    /// If `heal` is `true`, static file truncations and prunes are executed while checking, and
+    /// only the pipeline unwind is left to the caller. Otherwise, no file is modified and the plan
+    /// only describes what healing would do.
+    ///
+    /// WARNING: No static file writer should be held before calling this function, otherwise it
+    /// will deadlock.
+    pub fn consistency_plan<Provider>(
+        &self,
+        provider: &Provider,
+        heal: bool,
+    ) -> ProviderResult<HealPlan>
     where
         Provider: DBProvider
             + BlockReader
@@ -1313,26 +1534,20 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
                 info!(target: "reth::cli",
                     "Skipping storage verification for OP mainnet, expected inconsistency in OVM chain"
                 );
-                return Ok(None);
+                return Ok(HealPlan::default());
             }
         }
 
         info!(target: "reth::cli", "Verifying storage consistency.");
 
-        let mut unwind_target: Option<BlockNumber> = None;
-        let mut update_unwind_target = |new_target: BlockNumber| {
-            if let Some(target) = unwind_target.as_mut() {
-                *target = (*target).min(new_target);
-            } else {
-                unwind_target = Some(new_target);
-            }
-        };
+        let mut plan = HealPlan::default();
 
         for segment in self.segments_to_check(provider) {
             debug!(target: "reth::providers::static_file", ?segment, "Checking consistency for segment");
 
             // Heal file-level inconsistencies and get before/after highest block
-            let (initial_highest_block, mut highest_block) = self.maybe_heal_segment(segment)?;
+            let (initial_highest_block, mut highest_block) =
+                self.maybe_heal_segment(segment, heal, &mut plan)?;
 
             // Only applies to block-based static files. (Headers)
             //
@@ -1346,7 +1561,7 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
                     ?segment,
                     "Setting unwind target."
                 );
-                update_unwind_target(highest_block.unwrap_or_default());
+                plan.push_unwind(highest_block.unwrap_or_default());
             }
 
             // Only applies to transaction-based static files. (Receipts & Transactions)
@@ -1386,7 +1601,7 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
                         "Setting unwind target."
                     );
                     highest_block = Some(last_block);
-                    update_unwind_target(last_block);
+                    plan.push_unwind(last_block);
                 }
             }
 
@@ -1398,6 +1613,8 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
                         segment,
                         highest_block,
                         highest_block,
+                        heal,
+                        &mut plan,
                     )?,
                 StaticFileSegment::Transactions => self
                     .ensure_invariants::<_, tables::Transactions<N::SignedTx>>(
@@ -1405,6 +1622,8 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
                         segment,
                         highest_tx,
                         highest_block,
+                        heal,
+                        &mut plan,
                     )?,
                 StaticFileSegment::Receipts => self
                     .ensure_invariants::<_, tables::Receipts<N::Receipt>>(
@@ -1412,6 +1631,8 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
                         segment,
                         highest_tx,
                         highest_block,
+                        heal,
+                        &mut plan,
                     )?,
                 StaticFileSegment::TransactionSenders => self
                     .ensure_invariants::<_, tables::TransactionSenders>(
@@ -1419,6 +1640,8 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
                         segment,
                         highest_tx,
                         highest_block,
+                        heal,
+                        &mut plan,
                     )?,
                 StaticFileSegment::AccountChangeSets => self
                     .ensure_invariants::<_, tables::AccountChangeSets>(
@@ -1426,6 +1649,8 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
                         segment,
                         highest_tx,
                         highest_block,
+                        heal,
+                        &mut plan,
                     )?,
                 StaticFileSegment::StorageChangeSets => self
                     .ensure_changeset_invariants_by_block::<_, tables::StorageChangeSets, _>(
@@ -1433,16 +1658,18 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
                         segment,
                         highest_block,
                         |key| key.block_number(),
+                        heal,
+                        &mut plan,
                     )?,
             } {
                 debug!(target: "reth::providers::static_file", ?segment, unwind_target=unwind, "Invariants check returned unwind target");
-                update_unwind_target(unwind);
+                plan.push_unwind(unwind);
             } else {
                 debug!(target: "reth::providers::static_file", ?segment, "Invariants check completed, no unwind needed");
             }
         }
 
-        Ok(unwind_target.map(PipelineTarget::Unwind))
+        Ok(plan)
     }
 
     /// Heals file-level (`NippyJar`) inconsistencies for eligible static file segments.
@@ -1454,13 +1681,33 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
     where
         Provider: DBProvider + ChainSpecProvider + StorageSettingsCache,
     {
-        info!(target: "reth::cli", "Healing static file inconsistencies.");
+        self.file_consistency_plan(provider, true).map(|_| ())
+    }
 
+    /// Runs the same checks as [`Self::check_file_consistency`], returning the static file
+    /// truncations that are required as a [`HealPlan`].
+    ///
+    /// If `heal` is `false`, no file is modified.
+    pub fn file_consistency_plan<Provider>(
+        &self,
+        provider: &Provider,
+        heal: bool,
+    ) -> ProviderResult<HealPlan>
+    where
+        Provider: DBProvider + ChainSpecProvider + StorageSettingsCache,
+    {
+        if heal {
+            info!(target: "reth::cli", "Healing static file inconsistencies.");
+        } else {
+            info!(target: "reth::cli", "Checking static file inconsistencies.");
+        }
+
+        let mut plan = HealPlan::default();
         for segment in self.segments_to_check(provider) {
-            let _ = self.maybe_heal_segment(segment)?;
+            let _ = self.maybe_heal_segment(segment, heal, &mut plan)?;
         }
 
-        Ok(())
+        Ok(plan)
     }
 
     /// Returns the static file segments that should be checked/healed for this provider.
@@ -1531,7 +1778,7 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         debug!(target: "reth::providers::static_file", ?segment, "Checking segment consistency");
         if let Some(latest_block) = self.get_highest_static_file_block(segment) {
             let file_path = self
-                .directory()
+                .segment_directory(segment)
                 .join(segment.filename(&self.find_fixed_range(segment, latest_block)));
             debug!(target: "reth::providers::static_file", ?segment, ?file_path, latest_block, "Loading NippyJar for consistency check");
 
@@ -1560,10 +1807,13 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
     ///   expected to still have. We need to check the Database and unwind everything accordingly.
     ///
     /// **Note:** In read-only mode, this will return an error if a consistency issue is detected,
-    /// since healing requires write access.
+    /// since healing requires write access. If `heal` is `false`, the required truncation is only
+    /// recorded in `plan`.
     fn maybe_heal_segment(
         &self,
         segment: StaticFileSegment,
+        heal: bool,
+        plan: &mut HealPlan,
     ) -> ProviderResult<(Option<BlockNumber>, Option<BlockNumber>)> {
         let initial_highest_block = self.get_highest_static_file_block(segment);
         debug!(target: "reth::providers::static_file", ?segment, ?initial_highest_block, "Initial highest block for segment");
@@ -1574,10 +1824,17 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
             debug!(target: "reth::providers::static_file", ?segment, "Checking segment consistency (read-only)");
             self.check_segment_consistency(segment)?;
         } else {
-            // Writable mode: fetching the writer will automatically heal any file-level
-            // inconsistency by truncating data to match the last committed config.
-            debug!(target: "reth::providers::static_file", ?segment, "Fetching latest writer which might heal any potential inconsistency");
-            self.latest_writer(segment)?;
+            if let Err(err) = self.check_segment_consistency(segment) {
+                info!(target: "reth::providers::static_file", ?segment, %err, heal, "Static file segment is inconsistent");
+                plan.push(HealAction::TruncateStaticFile { segment });
+            }
+
+            if heal {
+                // Writable mode: fetching the writer will automatically heal any file-level
+                // inconsistency by truncating data to match the last committed config.
+                debug!(target: "reth::providers::static_file", ?segment, "Fetching latest writer which might heal any potential inconsistency");
+                self.latest_writer(segment)?;
+            }
         }
 
         // The updated `highest_block` may have decreased if we healed from a pruning
@@ -1597,7 +1854,8 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
     ///     block. This is expressed by returning [`Some`] with the requested pipeline unwind
     ///     target.
     ///   * If the checkpoint block is lower, then heal by removing rows from the static file. In
-    ///     this case, the rows will be removed and [`None`] will be returned.
+    ///     this case, the prune is recorded in `plan`, the rows will be removed if `heal` is set
+    ///     and [`None`] will be returned.
     ///
     /// * If the database tables overlap with static files and have contiguous keys, or the
     ///   checkpoint block matches the highest static files block, then [`None`] will be returned.
@@ -1607,6 +1865,8 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         segment: StaticFileSegment,
         highest_static_file_entry: Option<u64>,
         highest_static_file_block: Option<BlockNumber>,
+        heal: bool,
+        plan: &mut HealPlan,
     ) -> ProviderResult<Option<BlockNumber>>
     where
         Provider: DBProvider + BlockReader + StageCheckpointReader,
@@ -1679,6 +1939,15 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         // static files on executing a stage, or the reverse on unwinding a stage.
         // All we need to do is to prune the extra static file rows.
         if checkpoint_block_number < highest_static_file_block {
+            plan.push(HealAction::PruneStaticFile {
+                segment,
+                from: highest_static_file_block,
+                to: checkpoint_block_number,
+            });
+            if !heal {
+                return Ok(None)
+            }
+
             info!(
                 target: "reth::providers",
                 ?segment,
@@ -1743,6 +2012,8 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         segment: StaticFileSegment,
         highest_static_file_block: Option<BlockNumber>,
         block_from_key: F,
+        heal: bool,
+        plan: &mut HealPlan,
     ) -> ProviderResult<Option<BlockNumber>>
     where
         Provider: DBProvider + BlockReader + StageCheckpointReader,
@@ -1813,6 +2084,15 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         }
 
         if checkpoint_block_number < highest_static_file_block {
+            plan.push(HealAction::PruneStaticFile {
+                segment,
+                from: highest_static_file_block,
+                to: checkpoint_block_number,
+            });
+            if !heal {
+                return Ok(None)
+            }
+
             info!(
                 target: "reth::providers",
                 ?segment,
@@ -2061,10 +2341,70 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
     }
 
     /// Returns directory where `static_files` are located.
+    ///
+    /// Static files of segments with their own directory are located in
+    /// [`Self::segment_directory`] instead.
     pub fn directory(&self) -> &Path {
         &self.path
     }
 
+    /// Returns the directory where the static files of the segment are located.
+    pub fn segment_directory(&self, segment: StaticFileSegment) -> &Path {
+        self.segment_dirs.get(segment).unwrap_or(&self.path)
+    }
+
+    /// Returns the static files directory and all segment directories that differ from it.
+    pub fn directories(&self) -> impl Iterator<Item = &Path> {
+        std::iter::once(self.path.as_path()).chain(
+            self.segment_dirs
+                .values()
+                .map(PathBuf::as_path)
+                .filter(|dir| *dir != self.path.as_path()),
+        )
+    }
+
+    /// Returns all existing static files, organized by segment and sorted by block range, see
+    /// [`iter_static_files_in_dirs`].
+    pub fn static_files(
+        &self,
+    ) -> ProviderResult<StaticFileMap<Vec<(SegmentRangeInclusive, SegmentHeader)>>> {
+        iter_static_files_in_dirs(&self.path, &self.segment_dirs).map_err(ProviderError::other)
+    }
+
+    /// Shares the finished static file at `data_path` with other nodes, if a
+    /// [`SharedStaticFiles`] store is configured.
+    ///
+    /// Failing to share a static file is not fatal, the static file is kept private instead.
+    pub(crate) fn share_static_file(&self, data_path: &Path) {
+        if let Some(shared) = &self.shared &&
+            let Err(err) = shared.share(data_path)
+        {
+            warn!(target: "provider::static_file", ?data_path, %err, "Failed to share static file");
+        }
+    }
+
+    /// Shares all finished static files, i.e. all but the highest static file of every segment, if
+    /// a [`SharedStaticFiles`] store is configured.
+    ///
+    /// Also removes shared files that aren't referenced anymore, e.g. because static files were
+    /// deleted while the store wasn't configured.
+    fn share_finished_static_files(&self) -> ProviderResult<()> {
+        let Some(shared) = &self.shared else { return Ok(()) };
+
+        for (segment, headers) in &*self.static_files()? {
+            for (_, header) in headers.iter().rev().skip(1) {
+                self.share_static_file(
+                    &self
+                        .segment_directory(segment)
+                        .join(segment.filename(&header.expected_block_range())),
+                );
+            }
+        }
+        shared.remove_unreferenced().map_err(ProviderError::other)?;
+
+        Ok(())
+    }
+
     /// Retrieves data from the database or static file, wherever it's available.
     ///
     /// # Arguments
@@ -2389,7 +2729,7 @@ impl<N: NodePrimitives> ChangeSetReader for StaticFileProvider<N> {
         let mut count = 0;
 
         // iterate through static files and sum changeset metadata via each static file header
-        let static_files = iter_static_files(&self.path).map_err(ProviderError::other)?;
+        let static_files = self.static_files()?;
         if let Some(changeset_segments) = static_files.get(StaticFileSegment::AccountChangeSets) {
             for (_, header) in changeset_segments {
                 if let Some(changeset_offsets) = header.changeset_offsets() {
@@ -2508,7 +2848,7 @@ impl<N: NodePrimitives> StorageChangeSetReader for StaticFileProvider<N> {
     fn storage_changeset_count(&self) -> ProviderResult<usize> {
         let mut count = 0;
 
-        let static_files = iter_static_files(&self.path).map_err(ProviderError::other)?;
+        let static_files = self.static_files()?;
         if let Some(changeset_segments) = static_files.get(StaticFileSegment::StorageChangeSets) {
             for (_, header) in changeset_segments {
                 if let Some(changeset_offsets) = header.changeset_offsets() {
@@ -2523,6 +2863,46 @@ impl<N: NodePrimitives> StorageChangeSetReader for StaticFileProvider<N> {
     }
 }
 
+impl<N: NodePrimitives> LogsBloomIndexReader for StaticFileProvider<N> {
+    fn block_ranges_with_log_addresses(
+        &self,
+        addresses: &[Address],
+        block_range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<RangeInclusive<BlockNumber>>> {
+        if addresses.is_empty() {
+            return Ok(vec![block_range])
+        }
+
+        let mut ranges = Vec::new();
+        for address in addresses {
+            ranges.extend(self.segments_containing_address(address, block_range.clone())?);
+        }
+
+        // blocks above the static files aren't covered by the aggregated blooms
+        let first_uncovered = self
+            .get_highest_static_file_block(StaticFileSegment::Headers)
+            .map_or(*block_range.start(), |highest| (highest + 1).max(*block_range.start()));
+        if first_uncovered <= *block_range.end() {
+            ranges.push(first_uncovered..=*block_range.end());
+        }
+        ranges.sort_unstable_by_key(|range| *range.start());
+
+        // merge the ranges of adjacent static files and of static files containing several of the
+        // addresses
+        let mut merged: Vec<RangeInclusive<BlockNumber>> = Vec::with_capacity(ranges.len());
+        for range in ranges {
+            match merged.last_mut() {
+                Some(last) if *range.start() <= last.end() + 1 => {
+                    *last = *last.start()..=(*last.end()).max(*range.end());
+                }
+                _ => merged.push(range),
+            }
+        }
+
+        Ok(merged)
+    }
+}
+
 impl<N: NodePrimitives> StaticFileProvider<N> {
     /// Creates an iterator for walking through account changesets in the specified block range.
     ///
@@ -2549,6 +2929,88 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
     }
 }
 
+impl<N: NodePrimitives<Receipt: Value>> StaticFileProvider<N> {
+    /// Returns the expected block ranges of the finished receipts static files, and whether they
+    /// have a receipts index.
+    ///
+    /// The highest receipts static file is still written to and is therefore never indexed.
+    pub fn finished_receipts_static_files(
+        &self,
+    ) -> ProviderResult<Vec<(SegmentRangeInclusive, bool)>> {
+        let segment = StaticFileSegment::Receipts;
+        let Some(headers) = self.static_files()?.get(segment).cloned() else {
+            return Ok(Vec::new())
+        };
+
+        headers
+            .into_iter()
+            .filter(|(_, header)| header.block_end() == Some(header.expected_block_end()))
+            .filter_map(|(expected_block_range, header)| {
+                let tx_len = header.tx_len()?;
+                let data_path =
+                    self.segment_directory(segment).join(segment.filename(&expected_block_range));
+                Some(
+                    has_receipts_index(&data_path, tx_len)
+                        .map(|indexed| (expected_block_range, indexed))
+                        .map_err(ProviderError::other),
+                )
+            })
+            .collect()
+    }
+
+    /// Writes the receipts index of the finished receipts static file with the given expected
+    /// block range, replacing an existing index.
+    ///
+    /// The index holds the [`ReceiptBlockOffsets`] of every receipt, which are computed from the
+    /// block body indices of the given provider. Returns `false` if the static file isn't finished
+    /// or has no transactions, in which case no index is written.
+    pub fn write_receipts_index<P: BlockBodyIndicesProvider>(
+        &self,
+        provider: &P,
+        expected_block_range: SegmentRangeInclusive,
+    ) -> ProviderResult<bool> {
+        let segment = StaticFileSegment::Receipts;
+        let jar_provider =
+            self.get_segment_provider_for_block(segment, expected_block_range.start(), None)?;
+        let header = jar_provider.user_header();
+        let (Some(block_range), Some(tx_range)) = (header.block_range(), header.tx_range()) else {
+            return Ok(false)
+        };
+        if block_range.end() != header.expected_block_end() {
+            return Ok(false)
+        }
+
+        let mut cursor = jar_provider.cursor()?;
+        let mut writer =
+            ReceiptsIndexWriter::new(jar_provider.data_path()).map_err(ProviderError::other)?;
+        let mut next_tx = tx_range.start();
+        for indices in provider.block_body_indices_range(block_range.start()..=block_range.end())? {
+            // every row of the static file needs an entry, so the blocks can't skip transactions
+            if indices.first_tx_num() != next_tx && indices.tx_count() > 0 {
+                return Err(ProviderError::MissingStaticFileTx(segment, next_tx))
+            }
+
+            let mut offsets = ReceiptBlockOffsets::default();
+            for tx in indices.tx_num_range() {
+                let receipt = cursor
+                    .get_one::<ReceiptMask<N::Receipt>>(tx.into())?
+                    .ok_or(ProviderError::ReceiptNotFound(tx.into()))?;
+                writer.append(offsets).map_err(ProviderError::other)?;
+
+                offsets.gas_used = receipt.cumulative_gas_used();
+                offsets.log_index += receipt.logs().len() as u64;
+                next_tx = tx + 1;
+            }
+        }
+        if next_tx != tx_range.end() + 1 {
+            return Err(ProviderError::MissingStaticFileTx(segment, next_tx))
+        }
+        writer.finish().map_err(ProviderError::other)?;
+
+        Ok(true)
+    }
+}
+
 impl<N: NodePrimitives<BlockHeader: Value>> HeaderProvider for StaticFileProvider<N> {
     type Header = N::BlockHeader;
 
@@ -2667,6 +3129,18 @@ impl<N: NodePrimitives<SignedTx: Value + SignedTransaction, Receipt: Value>> Rec
             })
     }
 
+    fn receipt_block_offsets(&self, num: TxNumber) -> ProviderResult<Option<ReceiptBlockOffsets>> {
+        self.get_segment_provider_for_transaction(StaticFileSegment::Receipts, num, None)
+            .and_then(|provider| provider.receipt_block_offsets(num))
+            .or_else(|err| {
+                if let ProviderError::MissingStaticFileTx(_, _) = err {
+                    Ok(None)
+                } else {
+                    Err(err)
+                }
+            })
+    }
+
     fn receipt_by_hash(&self, hash: TxHash) -> ProviderResult<Option<Self::Receipt>> {
         if let Some(num) = self.transaction_id(hash)? {
             return self.receipt(num);
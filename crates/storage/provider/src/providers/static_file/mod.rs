@@ -11,6 +11,15 @@ mod writer;
 pub use writer::{StaticFileProviderRW, StaticFileProviderRWRefMut};
 
 mod metrics;
+
+mod shared;
+pub use shared::SharedStaticFiles;
+
+mod receipts_index;
+
+mod replace;
+pub use replace::{replace_static_file, replacement_path, REPLACEMENTS_DIR};
+
 use reth_nippy_jar::NippyJar;
 use reth_static_file_types::{SegmentHeader, StaticFileSegment};
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
@@ -64,23 +73,31 @@ mod tests {
     use super::*;
     use crate::{
         providers::static_file::manager::StaticFileProviderBuilder,
-        test_utils::create_test_provider_factory, HeaderProvider, StaticFileProviderFactory,
+        test_utils::{create_test_provider_factory, MockEthProvider},
+        HeaderProvider, StaticFileProviderFactory,
     };
     use alloy_consensus::{Header, SignableTransaction, Transaction, TxLegacy};
-    use alloy_primitives::{Address, BlockHash, Signature, TxNumber, B256, U160, U256};
+    use alloy_primitives::{
+        Address, BlockHash, Bloom, BloomInput, Signature, TxNumber, B256, U160, U256,
+    };
+    use assert_matches::assert_matches;
     use rand::seq::SliceRandom;
     use reth_db::{
         models::{AccountBeforeTx, StorageBeforeTx},
         test_utils::create_test_static_files_dir,
     };
-    use reth_db_api::{transaction::DbTxMut, CanonicalHeaders, HeaderNumbers, Headers};
+    use reth_db_api::{
+        models::StoredBlockBodyIndices, transaction::DbTxMut, CanonicalHeaders, HeaderNumbers,
+        Headers,
+    };
     use reth_ethereum_primitives::{EthPrimitives, Receipt, TransactionSigned};
     use reth_primitives_traits::Account;
     use reth_static_file_types::{
         find_fixed_range, SegmentRangeInclusive, DEFAULT_BLOCKS_PER_STATIC_FILE,
     };
     use reth_storage_api::{
-        ChangeSetReader, ReceiptProvider, StorageChangeSetReader, TransactionsProvider,
+        ChangeSetReader, LogsBloomIndexReader, ReceiptBlockOffsets, ReceiptProvider,
+        StorageChangeSetReader, TransactionsProvider,
     };
     use reth_testing_utils::generators::{self, random_header_range};
     use std::{collections::BTreeMap, fmt::Debug, fs, ops::Range, path::Path};
@@ -601,6 +618,143 @@ mod tests {
         Ok(count)
     }
 
+    /// Receipts of blocks `0..=5`, where every block has `block % 3` receipts, with increasing gas
+    /// used and numbers of logs.
+    fn receipts_index_blocks() -> Vec<Vec<Receipt>> {
+        (0..6u64)
+            .map(|block| {
+                (0..block % 3)
+                    .map(|i| Receipt {
+                        cumulative_gas_used: 21_000 * (i + 1),
+                        logs: vec![Default::default(); i as usize + 1],
+                        ..Default::default()
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Returns the offsets of all receipts of the blocks within their block.
+    fn receipt_block_offsets(blocks: &[Vec<Receipt>]) -> Vec<ReceiptBlockOffsets> {
+        blocks
+            .iter()
+            .flat_map(|receipts| {
+                receipts.iter().scan(ReceiptBlockOffsets::default(), |offsets, receipt| {
+                    let receipt_offsets = *offsets;
+                    offsets.gas_used = receipt.cumulative_gas_used;
+                    offsets.log_index += receipt.logs.len() as u64;
+                    Some(receipt_offsets)
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_receipts_index() -> eyre::Result<()> {
+        let (static_dir, _) = create_test_static_files_dir();
+        let sf_rw = StaticFileProviderBuilder::read_write(&static_dir)
+            .with_blocks_per_file(4)
+            .build::<EthPrimitives>()?;
+
+        let blocks = receipts_index_blocks();
+        let offsets = receipt_block_offsets(&blocks);
+        let append_blocks = |block_range: Range<usize>| -> ProviderResult<()> {
+            let mut writer = sf_rw.latest_writer(StaticFileSegment::Receipts)?;
+            let mut tx_num = sf_rw
+                .get_highest_static_file_tx(StaticFileSegment::Receipts)
+                .map_or(0, |tx_num| tx_num + 1);
+            for block in block_range {
+                writer.increment_block(block as u64)?;
+                for receipt in &blocks[block] {
+                    writer.append_receipt(tx_num, receipt)?;
+                    tx_num += 1;
+                }
+            }
+            writer.commit()
+        };
+        let assert_offsets = |indexed: Range<u64>| -> ProviderResult<()> {
+            for (tx_num, expected) in offsets.iter().enumerate() {
+                let tx_num = tx_num as u64;
+                assert_eq!(
+                    sf_rw.receipt_block_offsets(tx_num)?,
+                    indexed.contains(&tx_num).then_some(*expected),
+                    "tx {tx_num}"
+                );
+            }
+            Ok(())
+        };
+
+        // only the receipts of the finished static file of blocks 0..=3 are indexed
+        append_blocks(0..6)?;
+        assert_offsets(0..3)?;
+
+        // unwinding into the finished static file removes its index
+        {
+            let mut writer = sf_rw.latest_writer(StaticFileSegment::Receipts)?;
+            writer.prune_receipts(5, 1)?;
+            writer.commit()?;
+        }
+        assert_offsets(0..0)?;
+
+        // the entries of the remaining receipts are kept and the index is written again once the
+        // static file is finished
+        append_blocks(2..6)?;
+        assert_offsets(0..3)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_receipts_index() -> eyre::Result<()> {
+        let (static_dir, _) = create_test_static_files_dir();
+        let sf_rw = StaticFileProviderBuilder::read_write(&static_dir)
+            .with_blocks_per_file(4)
+            .build::<EthPrimitives>()?;
+
+        let blocks = receipts_index_blocks();
+        let offsets = receipt_block_offsets(&blocks);
+        let provider = MockEthProvider::default();
+        {
+            let mut writer = sf_rw.latest_writer(StaticFileSegment::Receipts)?;
+            let mut tx_num = 0;
+            for (block, receipts) in blocks.iter().enumerate() {
+                provider.add_block_body_indices(
+                    block as u64,
+                    StoredBlockBodyIndices {
+                        first_tx_num: tx_num,
+                        tx_count: receipts.len() as u64,
+                    },
+                );
+                writer.increment_block(block as u64)?;
+                for receipt in receipts {
+                    writer.append_receipt(tx_num, receipt)?;
+                    tx_num += 1;
+                }
+            }
+            writer.commit()?;
+        }
+
+        // static files that were written without an index are indexed from the block body indices
+        let finished = SegmentRangeInclusive::new(0, 3);
+        fs::remove_file(receipts_index::receipts_index_path(
+            &static_dir.as_ref().join(StaticFileSegment::Receipts.filename(&finished)),
+        ))?;
+        assert_eq!(sf_rw.finished_receipts_static_files()?, vec![(finished, false)]);
+        assert_eq!(sf_rw.receipt_block_offsets(2)?, None);
+
+        assert!(sf_rw.write_receipts_index(&provider, finished)?);
+        assert_eq!(sf_rw.finished_receipts_static_files()?, vec![(finished, true)]);
+        for (tx_num, expected) in offsets.iter().enumerate().take(3) {
+            assert_eq!(sf_rw.receipt_block_offsets(tx_num as u64)?, Some(*expected));
+        }
+
+        // the static file that is still written to isn't indexed
+        assert!(!sf_rw.write_receipts_index(&provider, SegmentRangeInclusive::new(4, 7))?);
+        assert_eq!(sf_rw.receipt_block_offsets(3)?, None);
+
+        Ok(())
+    }
+
     #[test]
     fn test_dynamic_size() -> eyre::Result<()> {
         let (static_dir, _) = create_test_static_files_dir();
@@ -684,6 +838,118 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_segment_directory() -> eyre::Result<()> {
+        let (static_dir, _) = create_test_static_files_dir();
+        let headers_dir = tempfile::tempdir()?;
+
+        let build = || {
+            StaticFileProviderBuilder::read_write(&static_dir)
+                .with_blocks_per_file(10)
+                .with_segment_dir(StaticFileSegment::Headers, headers_dir.path().to_path_buf())
+                .build::<EthPrimitives>()
+        };
+
+        {
+            let sf_rw = build()?;
+            let mut header_writer = sf_rw.latest_writer(StaticFileSegment::Headers)?;
+
+            let mut header = Header::default();
+            for num in 0..=15 {
+                header.number = num;
+                header_writer.append_header(&header, &BlockHash::default()).unwrap();
+            }
+            header_writer.commit().unwrap();
+        }
+
+        // the headers are only written to their own directory
+        assert_eq!(count_files_without_lockfile(&static_dir)?, 0);
+        assert!(count_files_without_lockfile(headers_dir.path())? > 0);
+
+        let sf_rw = build()?;
+        assert_eq!(sf_rw.get_highest_static_file_block(StaticFileSegment::Headers), Some(15));
+        assert_eq!(sf_rw.headers_range(0..=15)?.len(), 16);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_segment_directory_misplaced_static_files() -> eyre::Result<()> {
+        let (static_dir, _) = create_test_static_files_dir();
+        let headers_dir = tempfile::tempdir()?;
+
+        {
+            let sf_rw = StaticFileProviderBuilder::read_write(&static_dir)
+                .with_blocks_per_file(10)
+                .build::<EthPrimitives>()?;
+            let mut header_writer = sf_rw.latest_writer(StaticFileSegment::Headers)?;
+            header_writer.append_header(&Header::default(), &BlockHash::default()).unwrap();
+            header_writer.commit().unwrap();
+        }
+
+        // the headers written to the static files directory would be ignored
+        let result = StaticFileProviderBuilder::read_write(&static_dir)
+            .with_blocks_per_file(10)
+            .with_segment_dir(StaticFileSegment::Headers, headers_dir.path().to_path_buf())
+            .build::<EthPrimitives>();
+        assert_matches!(
+            result,
+            Err(ProviderError::MisplacedStaticFiles(StaticFileSegment::Headers, _, _))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_recover_interrupted_replacement() -> eyre::Result<()> {
+        let (static_dir, _) = create_test_static_files_dir();
+        let headers = (0..=10).map(|number| Header { number, ..Default::default() });
+
+        {
+            let sf_rw = StaticFileProviderBuilder::read_write(&static_dir)
+                .with_blocks_per_file(10)
+                .build::<EthPrimitives>()?;
+            let mut header_writer = sf_rw.latest_writer(StaticFileSegment::Headers)?;
+            for header in headers.clone() {
+                header_writer.append_header(&header, &BlockHash::default()).unwrap();
+            }
+            header_writer.commit().unwrap();
+        }
+
+        // write an lz4 compressed replacement of the finished static file
+        let path = static_dir
+            .as_ref()
+            .join(StaticFileSegment::Headers.filename(&SegmentRangeInclusive::new(0, 9)));
+        let jar = NippyJar::<SegmentHeader>::load(&path)?;
+        let replacement = replacement_path(&path);
+        fs::create_dir_all(replacement.parent().unwrap())?;
+        let mut writer = reth_nippy_jar::NippyJarWriter::new(
+            NippyJar::new(jar.columns(), &replacement, jar.user_header().clone()).with_lz4(),
+        )?;
+        let mut cursor = reth_nippy_jar::NippyJarCursor::new(&jar)?;
+        while let Some(row) = cursor.next_row()? {
+            for column in row {
+                writer.append_column(Some(Ok(column)))?;
+            }
+        }
+        writer.commit()?;
+
+        // the replacement was interrupted after moving the data file
+        fs::write(replacement.with_extension("replace"), [])?;
+        fs::rename(&replacement, &path)?;
+
+        let sf_rw = StaticFileProviderBuilder::read_write(&static_dir)
+            .with_blocks_per_file(10)
+            .build::<EthPrimitives>()?;
+        assert!(fs::read_dir(replacement.parent().unwrap())?.next().is_none());
+        assert!(NippyJar::<SegmentHeader>::load(&path)?.compressor().is_some());
+        for header in headers {
+            assert_eq!(sf_rw.header_by_number(header.number)?, Some(header));
+        }
+
+        Ok(())
+    }
+
     #[test]
     fn test_account_changeset_static_files() {
         let (static_dir, _) = create_test_static_files_dir();
@@ -1351,4 +1617,65 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_segments_containing_address() {
+        let (static_dir, _) = create_test_static_files_dir();
+        let sf_rw: StaticFileProvider<EthPrimitives> =
+            StaticFileProviderBuilder::read_write(&static_dir)
+                .with_blocks_per_file(10)
+                .build()
+                .expect("Failed to build static file provider");
+
+        let address = Address::with_last_byte(1);
+        let mut bloom = Bloom::ZERO;
+        bloom.accrue(BloomInput::Raw(address.as_slice()));
+
+        let append_headers = |range: Range<u64>| {
+            let mut header_writer = sf_rw.latest_writer(StaticFileSegment::Headers).unwrap();
+            for number in range {
+                let logs_bloom = if number == 13 || number == 24 { bloom } else { Bloom::ZERO };
+                let header = Header { number, logs_bloom, ..Default::default() };
+                header_writer.append_header(&header, &BlockHash::default()).unwrap();
+            }
+            header_writer.commit().unwrap();
+        };
+        append_headers(0..25);
+
+        assert_eq!(
+            sf_rw.segments_containing_address(&address, 0..=100).unwrap(),
+            vec![10..=19, 20..=24]
+        );
+        assert_eq!(sf_rw.segments_containing_address(&address, 5..=15).unwrap(), vec![10..=15]);
+
+        // Unwound blocks are removed from the aggregated bloom
+        let mut header_writer = sf_rw.latest_writer(StaticFileSegment::Headers).unwrap();
+        header_writer.prune_headers(2).unwrap();
+        header_writer.commit().unwrap();
+        drop(header_writer);
+        assert_eq!(sf_rw.segments_containing_address(&address, 0..=100).unwrap(), vec![10..=19]);
+
+        // Appended blocks are added to the aggregated bloom
+        append_headers(23..25);
+        assert_eq!(
+            sf_rw.segments_containing_address(&address, 0..=100).unwrap(),
+            vec![10..=19, 20..=24]
+        );
+
+        // Adjacent ranges are merged and blocks above the static files are always included
+        let other = Address::with_last_byte(2);
+        assert_eq!(
+            sf_rw.block_ranges_with_log_addresses(&[address, other], 0..=100).unwrap(),
+            vec![10..=100]
+        );
+        assert_eq!(
+            sf_rw.block_ranges_with_log_addresses(&[address], 5..=15).unwrap(),
+            vec![10..=15]
+        );
+        assert_eq!(
+            sf_rw.block_ranges_with_log_addresses(&[other], 0..=100).unwrap(),
+            vec![25..=100]
+        );
+        assert_eq!(sf_rw.block_ranges_with_log_addresses(&[other], 0..=20).unwrap(), vec![]);
+    }
 }
@@ -0,0 +1,239 @@
+//! Index of the offsets of the receipts of a finished receipts static file within their blocks.
+//!
+//! The rows of a static file can already be read in constant time, but the gas used and the log
+//! index of a receipt depend on the receipts that precede it in its block. The index stores these
+//! [`ReceiptBlockOffsets`] for every row, so a single receipt can be returned without reading all
+//! receipts of its block.
+//!
+//! The index is a file next to the static file with the [`RECEIPTS_INDEX_FILE_EXTENSION`], that
+//! contains one entry of [`ENTRY_SIZE`] bytes per row. While a static file is written, its index
+//! is built in a partial file, which is moved into place once the static file is finished, and
+//! truncated together with the static file. An index whose number of entries doesn't match the
+//! rows of its static file is ignored.
+
+use reth_storage_api::ReceiptBlockOffsets;
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, BufWriter, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+};
+
+/// File extension of the receipts index of a receipts static file.
+pub(crate) const RECEIPTS_INDEX_FILE_EXTENSION: &str = "ridx";
+
+/// File extension of the temporary file a receipts index is written to.
+const TMP_EXTENSION: &str = "ridx.tmp";
+
+/// File extension of the receipts index of a static file that is still being written.
+const PARTIAL_EXTENSION: &str = "ridx.partial";
+
+/// Size of an entry of the index: the gas used and the log index as little endian `u64`s.
+const ENTRY_SIZE: u64 = 16;
+
+/// Returns the path of the receipts index of the static file at `data_path`.
+pub(crate) fn receipts_index_path(data_path: &Path) -> PathBuf {
+    data_path.with_extension(RECEIPTS_INDEX_FILE_EXTENSION)
+}
+
+/// Returns `true` if the static file at `data_path`, which has `rows` rows, has a receipts index
+/// with an entry for every row.
+pub(crate) fn has_receipts_index(data_path: &Path, rows: u64) -> io::Result<bool> {
+    match fs::metadata(receipts_index_path(data_path)) {
+        Ok(metadata) => Ok(metadata.len() == rows * ENTRY_SIZE),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(false),
+        Err(err) => Err(err),
+    }
+}
+
+/// Reads the offsets of the receipt at `row` from the receipts index of the static file at
+/// `data_path`, which has `rows` rows.
+///
+/// Returns `None` if there's no index, or if it doesn't match the number of rows.
+pub(crate) fn read_receipt_block_offsets(
+    data_path: &Path,
+    rows: u64,
+    row: u64,
+) -> io::Result<Option<ReceiptBlockOffsets>> {
+    let mut file = match File::open(receipts_index_path(data_path)) {
+        Ok(file) => file,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err),
+    };
+    if row >= rows || file.metadata()?.len() != rows * ENTRY_SIZE {
+        return Ok(None)
+    }
+
+    let mut entry = [0; ENTRY_SIZE as usize];
+    file.seek(SeekFrom::Start(row * ENTRY_SIZE))?;
+    file.read_exact(&mut entry)?;
+
+    let (gas_used, log_index) = entry.split_at(8);
+    Ok(Some(ReceiptBlockOffsets {
+        gas_used: u64::from_le_bytes(gas_used.try_into().expect("8 bytes")),
+        log_index: u64::from_le_bytes(log_index.try_into().expect("8 bytes")),
+    }))
+}
+
+/// Removes the receipts index of the static file at `data_path`, if it exists.
+pub(crate) fn remove_receipts_index(data_path: &Path) -> io::Result<()> {
+    match fs::remove_file(receipts_index_path(data_path)) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+        _ => Ok(()),
+    }
+}
+
+/// Writes the receipts index of a static file.
+///
+/// Entries are written to a temporary file, which replaces the index on [`Self::finish`], so
+/// readers never see a partially written index.
+///
+/// The index of a static file that is being written is built in a separate partial file, see
+/// [`Self::create_partial`], so it isn't replaced by an index written with [`Self::new`] at the
+/// same time.
+#[derive(Debug)]
+pub(crate) struct ReceiptsIndexWriter {
+    /// The temporary file the entries are written to.
+    file: BufWriter<File>,
+    /// Path of the temporary file.
+    tmp_path: PathBuf,
+    /// Path of the index.
+    path: PathBuf,
+    /// Number of written entries.
+    entries: u64,
+}
+
+impl ReceiptsIndexWriter {
+    /// Creates a writer for the receipts index of the static file at `data_path`.
+    pub(crate) fn new(data_path: &Path) -> io::Result<Self> {
+        Self::create(data_path, TMP_EXTENSION)
+    }
+
+    /// Creates a writer for the receipts index of the static file at `data_path`, which has no
+    /// rows yet, that is written along with the static file.
+    pub(crate) fn create_partial(data_path: &Path) -> io::Result<Self> {
+        Self::create(data_path, PARTIAL_EXTENSION)
+    }
+
+    /// Continues the partial receipts index of the static file at `data_path`, which has `rows`
+    /// rows.
+    ///
+    /// Returns `None` if there's no partial index with an entry for every row.
+    pub(crate) fn resume_partial(data_path: &Path, rows: u64) -> io::Result<Option<Self>> {
+        let path = receipts_index_path(data_path);
+        let tmp_path = data_path.with_extension(PARTIAL_EXTENSION);
+        let file = match OpenOptions::new().append(true).open(&tmp_path) {
+            Ok(file) => file,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err),
+        };
+        if file.metadata()?.len() != rows * ENTRY_SIZE {
+            drop(file);
+            fs::remove_file(&tmp_path)?;
+            return Ok(None)
+        }
+
+        Ok(Some(Self { file: BufWriter::new(file), tmp_path, path, entries: rows }))
+    }
+
+    /// Like [`Self::resume_partial`], but turns the finished index of the static file back into a
+    /// partial one first, because the static file is modified again.
+    pub(crate) fn reopen_partial(data_path: &Path, rows: u64) -> io::Result<Option<Self>> {
+        let tmp_path = data_path.with_extension(PARTIAL_EXTENSION);
+        if tmp_path.exists() {
+            remove_receipts_index(data_path)?;
+        } else {
+            match fs::rename(receipts_index_path(data_path), &tmp_path) {
+                Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+                result => result?,
+            }
+        }
+        Self::resume_partial(data_path, rows)
+    }
+
+    /// Creates a writer whose entries are written to the file with the given extension.
+    fn create(data_path: &Path, tmp_extension: &str) -> io::Result<Self> {
+        let path = receipts_index_path(data_path);
+        let tmp_path = data_path.with_extension(tmp_extension);
+        let file = BufWriter::new(File::create(&tmp_path)?);
+        Ok(Self { file, tmp_path, path, entries: 0 })
+    }
+
+    /// Returns the number of written entries.
+    pub(crate) const fn entries(&self) -> u64 {
+        self.entries
+    }
+
+    /// Appends the offsets of the next row.
+    pub(crate) fn append(&mut self, offsets: ReceiptBlockOffsets) -> io::Result<()> {
+        self.file.write_all(&offsets.gas_used.to_le_bytes())?;
+        self.file.write_all(&offsets.log_index.to_le_bytes())?;
+        self.entries += 1;
+        Ok(())
+    }
+
+    /// Writes the buffered entries to the temporary file.
+    pub(crate) fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+
+    /// Removes all entries after the first `rows`.
+    pub(crate) fn truncate(&mut self, rows: u64) -> io::Result<()> {
+        self.file.flush()?;
+        self.file.get_ref().set_len(rows * ENTRY_SIZE)?;
+        self.entries = rows;
+        Ok(())
+    }
+
+    /// Syncs the written entries to disk and moves them into place.
+    pub(crate) fn finish(self) -> io::Result<()> {
+        let file = self.file.into_inner().map_err(io::IntoInnerError::into_error)?;
+        file.sync_all()?;
+        fs::rename(&self.tmp_path, &self.path)
+    }
+
+    /// Removes the written entries without moving them into place.
+    pub(crate) fn discard(self) -> io::Result<()> {
+        drop(self.file);
+        fs::remove_file(&self.tmp_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn receipts_index_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let data_path = dir.path().join("static_file_receipts_0_499999");
+
+        let entries = [
+            ReceiptBlockOffsets { gas_used: 0, log_index: 0 },
+            ReceiptBlockOffsets { gas_used: 21_000, log_index: 2 },
+            ReceiptBlockOffsets { gas_used: 0, log_index: 0 },
+        ];
+        let mut writer = ReceiptsIndexWriter::new(&data_path).unwrap();
+        for entry in entries {
+            writer.append(entry).unwrap();
+        }
+        writer.finish().unwrap();
+        assert!(has_receipts_index(&data_path, 3).unwrap());
+        assert!(!has_receipts_index(&data_path, 4).unwrap());
+
+        for (row, entry) in entries.iter().enumerate() {
+            assert_eq!(
+                read_receipt_block_offsets(&data_path, 3, row as u64).unwrap(),
+                Some(*entry)
+            );
+        }
+        assert_eq!(read_receipt_block_offsets(&data_path, 3, 3).unwrap(), None);
+
+        // the static file has more rows than the index, e.g. after it was unwound and appended to
+        assert_eq!(read_receipt_block_offsets(&data_path, 4, 0).unwrap(), None);
+
+        remove_receipts_index(&data_path).unwrap();
+        assert!(!has_receipts_index(&data_path, 3).unwrap());
+        assert_eq!(read_receipt_block_offsets(&data_path, 3, 0).unwrap(), None);
+        remove_receipts_index(&data_path).unwrap();
+    }
+}
@@ -0,0 +1,121 @@
+//! Replacement of the files of a finished static file, e.g. with a compacted copy of it.
+//!
+//! The files of a static file can't be replaced at once. The replacement is written into
+//! [`REPLACEMENTS_DIR`] next to the original first. Before its files are moved over the original
+//! ones, a marker is written next to them, which is only removed once all files were moved. If the
+//! replacement is interrupted, the marker is found by a provider with read-write access on startup,
+//! which moves the remaining files, so data, offsets and configuration of a static file always
+//! belong together.
+
+use reth_nippy_jar::{CONFIG_FILE_EXTENSION, INDEX_FILE_EXTENSION, OFFSETS_FILE_EXTENSION};
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
+use std::{
+    fs::{self, File},
+    io,
+    path::{Path, PathBuf},
+};
+use tracing::info;
+
+/// Name of the directory inside the directory of a segment where replacements of its static files
+/// are written.
+pub const REPLACEMENTS_DIR: &str = "compact";
+
+/// File extension of the marker of a replacement that is in progress.
+const MARKER_EXTENSION: &str = "replace";
+
+/// Returns the path the replacement of the static file at `path` has to be written to, before it
+/// can be moved into place with [`replace_static_file`].
+pub fn replacement_path(path: &Path) -> PathBuf {
+    let file_name = path.file_name().expect("static file has a file name");
+    path.with_file_name(REPLACEMENTS_DIR).join(file_name)
+}
+
+/// Replaces the files of the static file at `path` with the files of its replacement at
+/// [`replacement_path`].
+///
+/// The replacement must be fully written. If the replacement is interrupted, it's completed the
+/// next time a [`StaticFileProvider`](super::StaticFileProvider) with read-write access is created
+/// on the directory.
+pub fn replace_static_file(path: &Path) -> ProviderResult<()> {
+    let replacement = replacement_path(path);
+    if !replacement.with_extension(CONFIG_FILE_EXTENSION).exists() {
+        return Err(ProviderError::other(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("replacement of static file {} not found", path.display()),
+        )))
+    }
+
+    let marker = replacement.with_extension(MARKER_EXTENSION);
+    File::create(&marker).and_then(|file| file.sync_all()).map_err(ProviderError::other)?;
+    sync_dir(&replacement).map_err(ProviderError::other)?;
+
+    complete_replacement(path, &marker).map_err(ProviderError::other)
+}
+
+/// Completes the replacements of static files in `dir` that were interrupted.
+pub(crate) fn recover_replacements(dir: &Path) -> ProviderResult<()> {
+    let entries = match fs::read_dir(dir.join(REPLACEMENTS_DIR)) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(ProviderError::other(err)),
+    };
+
+    for entry in entries {
+        let marker = entry.map_err(ProviderError::other)?.path();
+        if marker.extension().is_none_or(|extension| extension != MARKER_EXTENSION) {
+            continue
+        }
+
+        let Some(file_name) = marker.file_stem() else { continue };
+        let path = dir.join(file_name);
+        info!(target: "provider::static_file", ?path, "Completing interrupted replacement of static file");
+        complete_replacement(&path, &marker).map_err(ProviderError::other)?;
+    }
+
+    Ok(())
+}
+
+/// Moves the files of the replacement of the static file at `path` that weren't moved yet over the
+/// original ones, and removes the `marker` of the replacement.
+///
+/// The configuration file is moved last, so that it always describes the data and offsets that
+/// were written with it.
+fn complete_replacement(path: &Path, marker: &Path) -> io::Result<()> {
+    // Index files are no longer written, but may exist for older static files.
+    match fs::remove_file(path.with_extension(INDEX_FILE_EXTENSION)) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err),
+        _ => {}
+    }
+
+    let replacement = replacement_path(path);
+    let files = [
+        (replacement.clone(), path.to_path_buf()),
+        (
+            replacement.with_extension(OFFSETS_FILE_EXTENSION),
+            path.with_extension(OFFSETS_FILE_EXTENSION),
+        ),
+        (
+            replacement.with_extension(CONFIG_FILE_EXTENSION),
+            path.with_extension(CONFIG_FILE_EXTENSION),
+        ),
+    ];
+    for (from, to) in files {
+        if from.exists() {
+            fs::rename(from, to)?;
+        }
+    }
+    sync_dir(path)?;
+
+    fs::remove_file(marker)
+}
+
+/// Syncs the directory of the file at `path`, so that renames and new files in it are durable.
+fn sync_dir(path: &Path) -> io::Result<()> {
+    // Directories can't be opened as files on windows.
+    if cfg!(unix) &&
+        let Some(dir) = path.parent()
+    {
+        File::open(dir)?.sync_all()?;
+    }
+    Ok(())
+}
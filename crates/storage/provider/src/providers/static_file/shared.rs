@@ -0,0 +1,214 @@
+//! Static files shared between the datadirs of multiple nodes.
+
+use alloy_primitives::{hex, Keccak256};
+use reth_nippy_jar::{CONFIG_FILE_EXTENSION, INDEX_FILE_EXTENSION, OFFSETS_FILE_EXTENSION};
+use std::{
+    ffi::OsString,
+    fs::{self, File},
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+use tracing::debug;
+
+/// Directory inside the shared directory that contains the content-addressed files.
+const OBJECTS_DIR: &str = "objects";
+
+/// Content-addressed store of finished static files, shared between the datadirs of multiple nodes
+/// of the same chain on one host.
+///
+/// Every file of a finished static file is moved into the store under the hash of its content and
+/// replaced by a hard link in the datadir, so identical files of different datadirs only use disk
+/// space once. The link count of a file in the store is its reference count: files that are not
+/// linked from any datadir anymore are removed by [`Self::remove_unreferenced`].
+///
+/// Static files that are modified again, e.g. on unwind, are copied back into the datadir first,
+/// see [`ensure_unshared`].
+#[derive(Debug, Clone)]
+pub struct SharedStaticFiles {
+    /// Directory of the content-addressed files.
+    objects: PathBuf,
+}
+
+impl SharedStaticFiles {
+    /// Opens the shared directory, creating it if it doesn't exist.
+    ///
+    /// The directory must be on the same filesystem as the datadirs sharing it.
+    pub fn open(dir: impl AsRef<Path>) -> io::Result<Self> {
+        if !cfg!(unix) {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "shared static files are only supported on unix",
+            ))
+        }
+
+        let objects = dir.as_ref().join(OBJECTS_DIR);
+        fs::create_dir_all(&objects)?;
+        Ok(Self { objects })
+    }
+
+    /// Replaces the files of the finished static file at `data_path` with hard links into the
+    /// shared directory, adding them to it if no other datadir has shared the same content yet.
+    pub fn share(&self, data_path: &Path) -> io::Result<()> {
+        for path in static_file_paths(data_path) {
+            if !path.exists() || is_shared(&path)? {
+                continue
+            }
+
+            let object = self.objects.join(hex::encode(hash_file(&path)?));
+            let tmp = tmp_path(&object);
+            if object.exists() {
+                // The same file was already shared by another datadir, so link it instead of ours
+                remove_if_exists(&tmp)?;
+                fs::hard_link(&object, &tmp)?;
+                fs::rename(&tmp, &path)?;
+            } else {
+                // Links are renamed into place, so other datadirs never see partially added files
+                remove_if_exists(&tmp)?;
+                fs::hard_link(&path, &tmp)?;
+                fs::rename(&tmp, &object)?;
+            }
+            debug!(target: "provider::static_file", ?path, ?object, "Shared static file");
+        }
+
+        Ok(())
+    }
+
+    /// Removes all files from the shared directory that aren't linked from any datadir anymore,
+    /// returning the number of removed files.
+    pub fn remove_unreferenced(&self) -> io::Result<usize> {
+        let mut removed = 0;
+        for entry in fs::read_dir(&self.objects)? {
+            let entry = entry?;
+            let metadata = entry.metadata()?;
+            if metadata.is_file() && link_count(&metadata) == 1 {
+                remove_if_exists(&entry.path())?;
+                removed += 1;
+            }
+        }
+
+        if removed > 0 {
+            debug!(target: "provider::static_file", removed, "Removed unreferenced shared static files");
+        }
+        Ok(removed)
+    }
+}
+
+/// Replaces the files of the static file at `data_path` that are hard links, e.g. into
+/// [`SharedStaticFiles`], with private copies, so the static file can be modified.
+pub(crate) fn ensure_unshared(data_path: &Path) -> io::Result<()> {
+    for path in static_file_paths(data_path) {
+        if !path.exists() || !is_shared(&path)? {
+            continue
+        }
+
+        let tmp = tmp_path(&path);
+        fs::copy(&path, &tmp)?;
+        File::open(&tmp)?.sync_all()?;
+        fs::rename(&tmp, &path)?;
+        debug!(target: "provider::static_file", ?path, "Unshared static file");
+    }
+
+    Ok(())
+}
+
+/// Returns the paths of all files of the static file at `data_path`.
+fn static_file_paths(data_path: &Path) -> [PathBuf; 4] {
+    [
+        data_path.to_path_buf(),
+        data_path.with_extension(INDEX_FILE_EXTENSION),
+        data_path.with_extension(OFFSETS_FILE_EXTENSION),
+        data_path.with_extension(CONFIG_FILE_EXTENSION),
+    ]
+}
+
+/// Returns a temporary path next to the given one, which isn't picked up as a static file.
+fn tmp_path(path: &Path) -> PathBuf {
+    let mut file_name = OsString::from(".");
+    file_name.push(path.file_name().unwrap_or_default());
+    file_name.push(format!(".{}.tmp", std::process::id()));
+    path.with_file_name(file_name)
+}
+
+/// Returns `true` if the file has more than one hard link.
+fn is_shared(path: &Path) -> io::Result<bool> {
+    Ok(link_count(&fs::metadata(path)?) > 1)
+}
+
+/// Returns the number of hard links of a file.
+#[cfg(unix)]
+fn link_count(metadata: &fs::Metadata) -> u64 {
+    std::os::unix::fs::MetadataExt::nlink(metadata)
+}
+
+/// Returns the number of hard links of a file.
+#[cfg(not(unix))]
+const fn link_count(_metadata: &fs::Metadata) -> u64 {
+    1
+}
+
+/// Computes the hash of the file content.
+fn hash_file(path: &Path) -> io::Result<[u8; 32]> {
+    let mut file = File::open(path)?;
+    let mut hasher = Keccak256::new();
+    let mut buf = vec![0; 1024 * 1024];
+    loop {
+        let read = file.read(&mut buf)?;
+        if read == 0 {
+            return Ok(hasher.finalize().0)
+        }
+        hasher.update(&buf[..read]);
+    }
+}
+
+fn remove_if_exists(path: &Path) -> io::Result<()> {
+    match fs::remove_file(path) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn write_static_file(dir: &Path, data: &[u8]) -> PathBuf {
+        let data_path = dir.join("static_file_headers_0_499");
+        fs::write(&data_path, data).unwrap();
+        fs::write(data_path.with_extension("off"), b"offsets").unwrap();
+        fs::write(data_path.with_extension("conf"), b"config").unwrap();
+        data_path
+    }
+
+    #[test]
+    fn shares_identical_static_files() {
+        let shared_dir = tempfile::tempdir().unwrap();
+        let first = tempfile::tempdir().unwrap();
+        let second = tempfile::tempdir().unwrap();
+        let shared = SharedStaticFiles::open(shared_dir.path()).unwrap();
+
+        let first_path = write_static_file(first.path(), b"headers");
+        let second_path = write_static_file(second.path(), b"headers");
+        shared.share(&first_path).unwrap();
+        shared.share(&second_path).unwrap();
+        // Sharing is idempotent
+        shared.share(&first_path).unwrap();
+
+        assert_eq!(fs::read_dir(&shared.objects).unwrap().count(), 3);
+        for path in [&first_path, &second_path] {
+            assert_eq!(link_count(&fs::metadata(path).unwrap()), 3);
+            assert_eq!(fs::read(path).unwrap(), b"headers");
+        }
+
+        // Modifying a static file doesn't affect the shared one
+        ensure_unshared(&first_path).unwrap();
+        assert!(!is_shared(&first_path).unwrap());
+        fs::write(&first_path, b"unwound").unwrap();
+        assert_eq!(fs::read(&second_path).unwrap(), b"headers");
+        assert_eq!(shared.remove_unreferenced().unwrap(), 0);
+
+        // Files are removed from the shared directory once no datadir references them
+        fs::remove_dir_all(second.path()).unwrap();
+        assert_eq!(shared.remove_unreferenced().unwrap(), 3);
+        assert_eq!(fs::read_dir(&shared.objects).unwrap().count(), 0);
+    }
+}
@@ -1,8 +1,12 @@
 use super::{
-    manager::StaticFileProviderInner, metrics::StaticFileProviderMetrics, StaticFileProvider,
+    manager::StaticFileProviderInner,
+    metrics::StaticFileProviderMetrics,
+    receipts_index::{remove_receipts_index, ReceiptsIndexWriter},
+    shared::ensure_unshared,
+    StaticFileProvider,
 };
 use crate::providers::static_file::metrics::StaticFileProviderOperation;
-use alloy_consensus::BlockHeader;
+use alloy_consensus::{BlockHeader, TxReceipt};
 use alloy_primitives::{BlockHash, BlockNumber, TxNumber, U256};
 use parking_lot::{lock_api::RwLockWriteGuard, RawRwLock, RwLock};
 use reth_codecs::Compact;
@@ -11,6 +15,7 @@ use reth_db_api::models::CompactU256;
 use reth_nippy_jar::{NippyJar, NippyJarError, NippyJarWriter};
 use reth_node_types::NodePrimitives;
 use reth_static_file_types::{SegmentHeader, SegmentRangeInclusive, StaticFileSegment};
+use reth_storage_api::ReceiptBlockOffsets;
 use reth_storage_errors::provider::{ProviderError, ProviderResult, StaticFileWriterError};
 use std::{
     borrow::Borrow,
@@ -219,6 +224,11 @@ pub struct StaticFileProviderRW<N> {
     prune_on_commit: Option<PruneStrategy>,
     /// Whether `sync_all()` has been called. Used by `finalize()` to avoid redundant syncs.
     synced: bool,
+    /// Writer of the receipts index of the current receipts static file, if all its rows were
+    /// appended with an index entry.
+    receipts_index: Option<ReceiptsIndexWriter>,
+    /// Offsets of the next receipt within its block.
+    receipt_offsets: ReceiptBlockOffsets,
 }
 
 impl<N: NodePrimitives> StaticFileProviderRW<N> {
@@ -241,9 +251,12 @@ impl<N: NodePrimitives> StaticFileProviderRW<N> {
             metrics,
             prune_on_commit: None,
             synced: false,
+            receipts_index: None,
+            receipt_offsets: ReceiptBlockOffsets::default(),
         };
 
         writer.ensure_end_range_consistency()?;
+        writer.open_receipts_index()?;
 
         Ok(writer)
     }
@@ -264,12 +277,23 @@ impl<N: NodePrimitives> StaticFileProviderRW<N> {
             block_range.start(),
             None,
         ) {
-            Ok(provider) => (
-                NippyJar::load(provider.data_path()).map_err(ProviderError::other)?,
-                provider.data_path().into(),
-            ),
+            Ok(provider) => {
+                // Shared static files must not be modified in place
+                ensure_unshared(provider.data_path()).map_err(ProviderError::other)?;
+                (
+                    NippyJar::load(provider.data_path()).map_err(ProviderError::other)?,
+                    provider.data_path().into(),
+                )
+            }
             Err(ProviderError::MissingStaticFileBlock(_, _)) => {
-                let path = static_file_provider.directory().join(segment.filename(&block_range));
+                let path = static_file_provider
+                    .segment_directory(segment)
+                    .join(segment.filename(&block_range));
+                // A receipts index left behind by a previously deleted static file doesn't match
+                // the new one
+                if segment == StaticFileSegment::Receipts {
+                    remove_receipts_index(&path).map_err(ProviderError::other)?;
+                }
                 (create_jar(segment, &path, block_range), path)
             }
             Err(err) => return Err(err),
@@ -368,6 +392,7 @@ impl<N: NodePrimitives> StaticFileProviderRW<N> {
             }
 
             self.writer.finalize().map_err(ProviderError::other)?;
+            self.flush_receipts_index()?;
             self.update_index()?;
         }
         self.synced = false;
@@ -414,6 +439,7 @@ impl<N: NodePrimitives> StaticFileProviderRW<N> {
 
             // Commits offsets and new user_header to disk
             self.writer.commit().map_err(ProviderError::other)?;
+            self.flush_receipts_index()?;
 
             if let Some(metrics) = &self.metrics {
                 metrics.record_segment_operation(
@@ -452,6 +478,7 @@ impl<N: NodePrimitives> StaticFileProviderRW<N> {
 
         // Commits offsets and new user_header to disk
         self.writer.commit_without_sync_all().map_err(ProviderError::other)?;
+        self.flush_receipts_index()?;
 
         if let Some(metrics) = &self.metrics {
             metrics.record_segment_operation(
@@ -543,6 +570,8 @@ impl<N: NodePrimitives> StaticFileProviderRW<N> {
             if last_block == self.writer.user_header().expected_block_end() {
                 // Commits offsets and new user_header to disk
                 self.commit()?;
+                self.finish_receipts_index()?;
+                self.reader().share_static_file(&self.data_path);
 
                 // Opens the new static file
                 let (writer, data_path) =
@@ -556,8 +585,10 @@ impl<N: NodePrimitives> StaticFileProviderRW<N> {
                     None,
                     segment,
                 );
+                self.open_receipts_index()?;
             }
         }
+        self.receipt_offsets = ReceiptBlockOffsets::default();
 
         self.writer.user_header_mut().increment_block();
         if let Some(metrics) = &self.metrics {
@@ -724,7 +755,7 @@ impl<N: NodePrimitives> StaticFileProviderRW<N> {
                 } else {
                     // Update `SegmentHeader`
                     self.writer.user_header_mut().prune(len);
-                    self.writer.prune_rows(len as usize).map_err(ProviderError::other)?;
+                    self.prune_rows(len)?;
                     break
                 }
 
@@ -734,7 +765,7 @@ impl<N: NodePrimitives> StaticFileProviderRW<N> {
                 self.writer.user_header_mut().prune(remaining_rows);
 
                 // Truncate data
-                self.writer.prune_rows(remaining_rows as usize).map_err(ProviderError::other)?;
+                self.prune_rows(remaining_rows)?;
                 remaining_rows = 0;
             }
         }
@@ -774,13 +805,94 @@ impl<N: NodePrimitives> StaticFileProviderRW<N> {
         self.writer = previous_writer;
         self.writer.set_dirty();
         self.data_path = data_path;
+        if let Some(receipts_index) = self.receipts_index.take() {
+            receipts_index.discard().map_err(ProviderError::other)?;
+        }
+        remove_receipts_index(&current_path).map_err(ProviderError::other)?;
         NippyJar::<SegmentHeader>::load(&current_path)
             .map_err(ProviderError::other)?
             .delete()
             .map_err(ProviderError::other)?;
+        self.open_receipts_index()?;
+        Ok(())
+    }
+
+    /// Prunes the last `num_rows` rows of the current static file, together with their receipts
+    /// index entries.
+    fn prune_rows(&mut self, num_rows: u64) -> ProviderResult<()> {
+        if self.writer.user_header().segment() == StaticFileSegment::Receipts {
+            // The index has an entry for every row, so it has to be removed before rows are
+            // pruned. A finished index is continued, since the static file is modified again.
+            if self.receipts_index.is_none() {
+                self.receipts_index =
+                    ReceiptsIndexWriter::reopen_partial(&self.data_path, self.writer.rows() as u64)
+                        .map_err(ProviderError::other)?;
+            }
+            remove_receipts_index(&self.data_path).map_err(ProviderError::other)?;
+        }
+
+        self.writer.prune_rows(num_rows as usize).map_err(ProviderError::other)?;
+
+        if let Some(receipts_index) = &mut self.receipts_index {
+            receipts_index.truncate(self.writer.rows() as u64).map_err(ProviderError::other)?;
+        }
+        Ok(())
+    }
+
+    /// Opens the writer of the receipts index of the current static file, if it's a receipts
+    /// static file.
+    ///
+    /// The index of a static file that already has rows is only continued if a previous run of the
+    /// node wrote an entry for every row. Otherwise the static file isn't indexed until the static
+    /// file producer indexes it, or `reth static-files index` is run.
+    fn open_receipts_index(&mut self) -> ProviderResult<()> {
+        if self.writer.user_header().segment() != StaticFileSegment::Receipts {
+            return Ok(())
+        }
+
+        let rows = self.writer.rows() as u64;
+        self.receipts_index = if rows == 0 {
+            Some(
+                ReceiptsIndexWriter::create_partial(&self.data_path)
+                    .map_err(ProviderError::other)?,
+            )
+        } else {
+            ReceiptsIndexWriter::resume_partial(&self.data_path, rows)
+                .map_err(ProviderError::other)?
+        };
+        Ok(())
+    }
+
+    /// Appends the index entry of a receipt that was appended to the current static file.
+    fn append_receipt_index(&mut self, receipt: &N::Receipt) -> ProviderResult<()> {
+        if let Some(receipts_index) = &mut self.receipts_index {
+            receipts_index.append(self.receipt_offsets).map_err(ProviderError::other)?;
+        }
+        self.receipt_offsets.gas_used = receipt.cumulative_gas_used();
+        self.receipt_offsets.log_index += receipt.logs().len() as u64;
         Ok(())
     }
 
+    /// Writes the buffered entries of the receipts index, so they match the committed rows.
+    fn flush_receipts_index(&mut self) -> ProviderResult<()> {
+        if let Some(receipts_index) = &mut self.receipts_index {
+            receipts_index.flush().map_err(ProviderError::other)?;
+        }
+        Ok(())
+    }
+
+    /// Moves the receipts index of the finished current static file into place, if it has an
+    /// entry for every row.
+    fn finish_receipts_index(&mut self) -> ProviderResult<()> {
+        let Some(receipts_index) = self.receipts_index.take() else { return Ok(()) };
+        if receipts_index.entries() == self.writer.rows() as u64 {
+            receipts_index.finish()
+        } else {
+            receipts_index.discard()
+        }
+        .map_err(ProviderError::other)
+    }
+
     /// Appends column to static file.
     fn append_column<T: Compact>(&mut self, column: T) -> ProviderResult<()> {
         self.buf.clear();
@@ -940,6 +1052,7 @@ impl<N: NodePrimitives> StaticFileProviderRW<N> {
 
         debug_assert!(self.writer.user_header().segment() == StaticFileSegment::Receipts);
         self.append_with_tx_number(tx_num, receipt)?;
+        self.append_receipt_index(receipt)?;
 
         if let Some(metrics) = &self.metrics {
             metrics.record_segment_operation(
@@ -976,6 +1089,7 @@ impl<N: NodePrimitives> StaticFileProviderRW<N> {
         for receipt_result in receipts_iter {
             let (tx_num, receipt) = receipt_result?;
             self.append_with_tx_number(tx_num, receipt.borrow())?;
+            self.append_receipt_index(receipt.borrow())?;
             count += 1;
         }
 
@@ -8,20 +8,25 @@ use crate::{
 use alloy_consensus::{
     constants::EMPTY_ROOT_HASH,
     transaction::{TransactionMeta, TxHashRef},
-    BlockHeader,
+    BlockHeader, TxReceipt,
 };
-use alloy_eips::{BlockHashOrNumber, BlockId, BlockNumberOrTag};
+use alloy_eips::{eip4895::Withdrawals, BlockHashOrNumber, BlockId, BlockNumberOrTag};
 use alloy_primitives::{
-    keccak256, map::HashMap, Address, BlockHash, BlockNumber, Bytes, StorageKey, StorageValue,
-    TxHash, TxNumber, B256, U256,
+    keccak256, map::HashMap, Address, BlockHash, BlockNumber, Bytes, Sealable, StorageKey,
+    StorageValue, TxHash, TxNumber, B256, U256,
 };
 use parking_lot::Mutex;
-use reth_chain_state::{CanonStateNotifications, CanonStateSubscriptions};
+use reth_chain_state::{
+    CanonStateNotification, CanonStateNotificationSender, CanonStateNotifications,
+    CanonStateSubscriptions, SyncProgress, SyncProgressProvider,
+};
 use reth_chainspec::{ChainInfo, EthChainSpec};
 use reth_db::transaction::DbTx;
 use reth_db_api::{
     mock::{DatabaseMock, TxMock},
-    models::{AccountBeforeTx, StoredBlockBodyIndices},
+    models::{
+        AccountBeforeTx, AccountDestruction, ContractCreation, ReorgRecord, StoredBlockBodyIndices,
+    },
 };
 use reth_ethereum_primitives::EthPrimitives;
 use reth_execution_types::ExecutionOutcome;
@@ -32,9 +37,11 @@ use reth_primitives_traits::{
 use reth_prune_types::{PruneCheckpoint, PruneModes, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_storage_api::{
-    BlockBodyIndicesProvider, BytecodeReader, DBProvider, DatabaseProviderFactory,
-    HashedPostStateProvider, NodePrimitivesProvider, StageCheckpointReader, StateProofProvider,
-    StorageChangeSetReader, StorageRootProvider,
+    AccountDestructionsReader, AddressTransactionsReader, BlockBodyIndicesProvider, BytecodeReader,
+    ContractCreationsReader, DBProvider, DatabaseProviderFactory, HashedPostStateProvider,
+    LogsBloomIndexReader, NodePrimitivesProvider, ReceiptBlockOffsets, ReorgHistoryReader,
+    StageCheckpointReader, StateProofProvider, StorageChangeSetReader, StorageRootProvider,
+    WithdrawalsProvider,
 };
 use reth_storage_errors::provider::{ConsistentViewError, ProviderError, ProviderResult};
 use reth_trie::{
@@ -49,6 +56,9 @@ use std::{
 };
 use tokio::sync::broadcast;
 
+/// Capacity of the canonical state notification channel of the [`MockEthProvider`].
+const DEFAULT_CANON_STATE_NOTIFICATION_CHANNEL_SIZE: usize = 256;
+
 /// A mock implementation for Provider interfaces.
 #[derive(Debug)]
 pub struct MockEthProvider<T: NodePrimitives = EthPrimitives, ChainSpec = reth_chainspec::ChainSpec>
@@ -67,6 +77,8 @@ pub struct MockEthProvider<T: NodePrimitives = EthPrimitives, ChainSpec = reth_c
     pub state_roots: Arc<Mutex<Vec<B256>>>,
     /// Local block body indices store
     pub block_body_indices: Arc<Mutex<HashMap<BlockNumber, StoredBlockBodyIndices>>>,
+    /// Sender for canonical state notifications, see [`Self::notify_canonical_state`].
+    canon_state_notification_sender: CanonStateNotificationSender<T>,
     tx: TxMock,
     prune_modes: Arc<PruneModes>,
 }
@@ -84,6 +96,7 @@ where
             chain_spec: self.chain_spec.clone(),
             state_roots: self.state_roots.clone(),
             block_body_indices: self.block_body_indices.clone(),
+            canon_state_notification_sender: self.canon_state_notification_sender.clone(),
             tx: self.tx.clone(),
             prune_modes: self.prune_modes.clone(),
         }
@@ -101,6 +114,10 @@ impl<T: NodePrimitives> MockEthProvider<T, reth_chainspec::ChainSpec> {
             chain_spec: Arc::new(reth_chainspec::ChainSpecBuilder::mainnet().build()),
             state_roots: Default::default(),
             block_body_indices: Default::default(),
+            canon_state_notification_sender: broadcast::channel(
+                DEFAULT_CANON_STATE_NOTIFICATION_CHANNEL_SIZE,
+            )
+            .0,
             tx: Default::default(),
             prune_modes: Default::default(),
         }
@@ -121,6 +138,23 @@ impl<T: NodePrimitives, ChainSpec> MockEthProvider<T, ChainSpec> {
         }
     }
 
+    /// Adds a chain of blocks to the local block store.
+    ///
+    /// The blocks are keyed by the hash of their header.
+    pub fn extend_chain(&self, chain: impl IntoIterator<Item = T::Block>) {
+        for block in chain {
+            self.add_block(block.header().hash_slow(), block)
+        }
+    }
+
+    /// Sends the given notification to all subscribers of
+    /// [`CanonStateSubscriptions::subscribe_to_canonical_state`].
+    ///
+    /// This can be used to script canonical chain commits and reorgs.
+    pub fn notify_canonical_state(&self, notification: CanonStateNotification<T>) {
+        let _ = self.canon_state_notification_sender.send(notification);
+    }
+
     /// Add header to local header store
     pub fn add_header(&self, hash: B256, header: <T::Block as Block>::Header) {
         self.headers.lock().insert(hash, header);
@@ -184,6 +218,7 @@ impl<T: NodePrimitives, ChainSpec> MockEthProvider<T, ChainSpec> {
             chain_spec: Arc::new(chain_spec),
             state_roots: self.state_roots,
             block_body_indices: self.block_body_indices,
+            canon_state_notification_sender: self.canon_state_notification_sender,
             tx: self.tx,
             prune_modes: self.prune_modes,
         }
@@ -495,6 +530,32 @@ where
         Ok(None)
     }
 
+    fn receipt_block_offsets(&self, id: TxNumber) -> ProviderResult<Option<ReceiptBlockOffsets>> {
+        // like an index of the receipts, the offsets are computed from the body indices and the
+        // receipts of the block, without looking up its header
+        let Some((block_number, first_tx_num)) = self
+            .block_body_indices
+            .lock()
+            .iter()
+            .find(|(_, indices)| indices.contains_tx(id))
+            .map(|(block_number, indices)| (*block_number, indices.first_tx_num()))
+        else {
+            return Ok(None)
+        };
+        let receipts = self.receipts.lock();
+        let Some(preceding) = receipts
+            .get(&block_number)
+            .and_then(|receipts| receipts.get(..(id - first_tx_num) as usize))
+        else {
+            return Ok(None)
+        };
+
+        Ok(Some(ReceiptBlockOffsets {
+            gas_used: preceding.last().map(|receipt| receipt.cumulative_gas_used()).unwrap_or(0),
+            log_index: preceding.iter().map(|receipt| receipt.logs().len() as u64).sum(),
+        }))
+    }
+
     fn receipts_by_block(
         &self,
         block: BlockHashOrNumber,
@@ -970,12 +1031,81 @@ impl<T: NodePrimitives, ChainSpec: Send + Sync> BlockBodyIndicesProvider
     }
     fn block_body_indices_range(
         &self,
-        _range: RangeInclusive<BlockNumber>,
+        range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<Vec<StoredBlockBodyIndices>> {
+        let block_body_indices = self.block_body_indices.lock();
+        Ok(range.map_while(|num| block_body_indices.get(&num).copied()).collect())
+    }
+}
+
+impl<T: NodePrimitives, ChainSpec: Send + Sync> WithdrawalsProvider
+    for MockEthProvider<T, ChainSpec>
+{
+    fn withdrawals_by_block_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<Withdrawals>> {
+        let lock = self.blocks.lock();
+
+        let mut blocks: Vec<_> =
+            lock.values().filter(|block| range.contains(&block.header().number())).collect();
+        blocks.sort_by_key(|block| block.header().number());
+
+        Ok(blocks
+            .into_iter()
+            .map(|block| block.body().withdrawals().cloned().unwrap_or_default())
+            .collect())
+    }
+}
+
+impl<T: NodePrimitives, ChainSpec: Send + Sync> ReorgHistoryReader
+    for MockEthProvider<T, ChainSpec>
+{
+    fn reorg_history(&self, _limit: usize) -> ProviderResult<Vec<ReorgRecord>> {
         Ok(vec![])
     }
 }
 
+impl<T: NodePrimitives, ChainSpec: Send + Sync> AddressTransactionsReader
+    for MockEthProvider<T, ChainSpec>
+{
+    fn address_transactions(
+        &self,
+        _addresses: &[Address],
+        _block_range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Option<Vec<TxNumber>>> {
+        Ok(None)
+    }
+}
+
+impl<T: NodePrimitives, ChainSpec: Send + Sync> LogsBloomIndexReader
+    for MockEthProvider<T, ChainSpec>
+{
+    fn block_ranges_with_log_addresses(
+        &self,
+        _addresses: &[Address],
+        block_range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<RangeInclusive<BlockNumber>>> {
+        Ok(vec![block_range])
+    }
+}
+
+impl<T: NodePrimitives, ChainSpec: Send + Sync> ContractCreationsReader
+    for MockEthProvider<T, ChainSpec>
+{
+    fn contract_creation(&self, _address: Address) -> ProviderResult<Option<ContractCreation>> {
+        Ok(None)
+    }
+}
+
+impl<T: NodePrimitives, ChainSpec: Send + Sync> AccountDestructionsReader
+    for MockEthProvider<T, ChainSpec>
+{
+    fn account_destructions(&self, _address: Address) -> ProviderResult<Vec<AccountDestruction>> {
+        Ok(Vec::new())
+    }
+}
+
 impl<T: NodePrimitives, ChainSpec: Send + Sync> ChangeSetReader for MockEthProvider<T, ChainSpec> {
     fn account_block_changeset(
         &self,
@@ -1050,7 +1180,15 @@ impl<T: NodePrimitives, ChainSpec: Send + Sync> CanonStateSubscriptions
     for MockEthProvider<T, ChainSpec>
 {
     fn subscribe_to_canonical_state(&self) -> CanonStateNotifications<T> {
-        broadcast::channel(1).1
+        self.canon_state_notification_sender.subscribe()
+    }
+}
+
+impl<T: NodePrimitives, ChainSpec: Send + Sync> SyncProgressProvider
+    for MockEthProvider<T, ChainSpec>
+{
+    fn sync_progress(&self) -> SyncProgress {
+        SyncProgress::default()
     }
 }
 
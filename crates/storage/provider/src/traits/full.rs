@@ -7,10 +7,15 @@ use crate::{
     StaticFileProviderFactory,
 };
 use reth_chain_state::{
-    CanonStateSubscriptions, ForkChoiceSubscriptions, PersistedBlockSubscriptions,
+    BlockTimingsProvider, CanonStateSubscriptions, ChainStatsProvider, ForkChoiceSubscriptions,
+    PersistedBlockSubscriptions, SyncProgressProvider,
 };
 use reth_node_types::{BlockTy, HeaderTy, NodeTypesWithDB, ReceiptTy, TxTy};
-use reth_storage_api::{NodePrimitivesProvider, StorageChangeSetReader};
+use reth_storage_api::{
+    AccountDestructionsReader, AddressTransactionsReader, ContractCreationsReader,
+    LogsBloomIndexReader, NodePrimitivesProvider, ReorgHistoryReader, StorageChangeSetReader,
+    WithdrawalsProvider,
+};
 use std::fmt::Debug;
 
 /// Helper trait to unify all provider traits for simplicity.
@@ -37,10 +42,20 @@ pub trait FullProvider<N: NodeTypesWithDB>:
     + ChainSpecProvider<ChainSpec = N::ChainSpec>
     + ChangeSetReader
     + StorageChangeSetReader
+    + WithdrawalsProvider
+    + ReorgHistoryReader
+    + AddressTransactionsReader
+    + LogsBloomIndexReader
+    + ContractCreationsReader
+    + AccountDestructionsReader
     + CanonStateSubscriptions
     + ForkChoiceSubscriptions<Header = HeaderTy<N>>
     + PersistedBlockSubscriptions
+    + BlockTimingsProvider
+    + ChainStatsProvider
+    + SyncProgressProvider
     + StageCheckpointReader
+    + PruneCheckpointReader
     + Clone
     + Debug
     + Unpin
@@ -71,10 +86,20 @@ impl<T, N: NodeTypesWithDB> FullProvider<N> for T where
         + ChainSpecProvider<ChainSpec = N::ChainSpec>
         + ChangeSetReader
         + StorageChangeSetReader
+        + WithdrawalsProvider
+        + ReorgHistoryReader
+        + AddressTransactionsReader
+        + LogsBloomIndexReader
+        + ContractCreationsReader
+        + AccountDestructionsReader
         + CanonStateSubscriptions
         + ForkChoiceSubscriptions<Header = HeaderTy<N>>
         + PersistedBlockSubscriptions
+        + BlockTimingsProvider
+        + ChainStatsProvider
+        + SyncProgressProvider
         + StageCheckpointReader
+        + PruneCheckpointReader
         + Clone
         + Debug
         + Unpin
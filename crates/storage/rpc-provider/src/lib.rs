@@ -50,7 +50,7 @@ use reth_provider::{
     ChainStateBlockReader, ChainStateBlockWriter, ChangeSetReader, DatabaseProviderFactory,
     HeaderProvider, PruneCheckpointReader, ReceiptProvider, StageCheckpointReader, StateProvider,
     StateProviderBox, StateProviderFactory, StateReader, StateRootProvider, StorageReader,
-    TransactionVariant, TransactionsProvider,
+    SyncProgress, SyncProgressProvider, TransactionVariant, TransactionsProvider,
 };
 use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_rpc_convert::{TryFromBlockResponse, TryFromReceiptResponse, TryFromTransactionResponse};
@@ -875,6 +875,17 @@ where
     }
 }
 
+impl<P, Node, N> SyncProgressProvider for RpcBlockchainProvider<P, Node, N>
+where
+    P: Send + Sync,
+    N: Send + Sync,
+    Node: NodeTypes,
+{
+    fn sync_progress(&self) -> SyncProgress {
+        SyncProgress::default()
+    }
+}
+
 impl<P, Node, N> ChainSpecProvider for RpcBlockchainProvider<P, Node, N>
 where
     P: Send + Sync,
@@ -0,0 +1,34 @@
+use alloc::vec::Vec;
+use alloy_primitives::{Address, BlockNumber};
+use core::ops::RangeInclusive;
+use reth_db_models::AccountDestruction;
+use reth_storage_errors::provider::ProviderResult;
+
+/// The trait for looking up the removals of an account from the state, using the optional index
+/// recorded while executing the blocks.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait AccountDestructionsReader: Send + Sync {
+    /// Returns the removals of the account at the given address in the blocks covered by the
+    /// index, in ascending order of block number.
+    fn account_destructions(&self, address: Address) -> ProviderResult<Vec<AccountDestruction>>;
+}
+
+/// The trait for maintaining the index of account destructions.
+#[auto_impl::auto_impl(&)]
+pub trait AccountDestructionsWriter {
+    /// Inserts the given account destructions.
+    ///
+    /// Returns the number of inserted destructions.
+    fn insert_account_destructions(
+        &self,
+        destructions: Vec<(Address, AccountDestruction)>,
+    ) -> ProviderResult<usize>;
+
+    /// Removes the destructions of the accounts removed in the given block range.
+    ///
+    /// Returns the number of removed destructions.
+    fn unwind_account_destructions(
+        &self,
+        block_range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<usize>;
+}
@@ -0,0 +1,36 @@
+use alloc::vec::Vec;
+use alloy_primitives::{Address, BlockNumber, TxNumber};
+use core::ops::{Range, RangeInclusive};
+use reth_storage_errors::provider::ProviderResult;
+
+/// The trait for looking up the transactions sent from or to an address, using the index built by
+/// the optional `IndexTransactionAddresses` stage.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait AddressTransactionsReader: Send + Sync {
+    /// Returns the numbers of the transactions in the given block range that were sent from or to
+    /// any of the given addresses, in ascending order.
+    ///
+    /// Returns [`None`] if the block range isn't fully covered by the index.
+    fn address_transactions(
+        &self,
+        addresses: &[Address],
+        block_range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Option<Vec<TxNumber>>>;
+}
+
+/// The trait for maintaining the index of the transactions sent from or to each address.
+#[auto_impl::auto_impl(&)]
+pub trait AddressTransactionsWriter {
+    /// Indexes the senders and recipients of the transactions in the given range.
+    ///
+    /// The range must start above all transactions that are already indexed.
+    ///
+    /// Returns the number of indexed transactions.
+    fn index_address_transactions(&self, tx_range: Range<TxNumber>) -> ProviderResult<usize>;
+
+    /// Removes the transactions in the given range from the indices of their senders and
+    /// recipients, including all indexed transactions above it.
+    ///
+    /// Returns the number of unwound transactions.
+    fn unwind_address_transactions(&self, tx_range: Range<TxNumber>) -> ProviderResult<usize>;
+}
@@ -0,0 +1,48 @@
+use alloc::vec::Vec;
+use alloy_primitives::BlockNumber;
+use reth_db_models::ChainStatsRecord;
+use reth_storage_errors::provider::ProviderResult;
+
+/// The period the persisted chain statistics are aggregated over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChainStatsTable {
+    /// The statistics per UTC day.
+    Days,
+    /// The statistics per beacon chain epoch.
+    Epochs,
+}
+
+/// The trait for fetching the persisted chain statistics.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait ChainStatsStorageReader: Send {
+    /// Returns the number of the last block included in the persisted statistics.
+    fn chain_stats_head(&self) -> ProviderResult<Option<BlockNumber>>;
+
+    /// Returns the statistics of at most `limit` of the most recent periods, newest first.
+    fn chain_stats(
+        &self,
+        table: ChainStatsTable,
+        limit: usize,
+    ) -> ProviderResult<Vec<(u64, ChainStatsRecord)>>;
+}
+
+/// The trait for persisting the chain statistics.
+#[auto_impl::auto_impl(&)]
+pub trait ChainStatsStorageWriter {
+    /// Saves the number of the last block included in the persisted statistics, or removes it if
+    /// `None`.
+    fn save_chain_stats_head(&self, head: Option<BlockNumber>) -> ProviderResult<()>;
+
+    /// Saves the statistics of a period, or removes them if `None`.
+    fn save_chain_stats(
+        &self,
+        table: ChainStatsTable,
+        period: u64,
+        record: Option<ChainStatsRecord>,
+    ) -> ProviderResult<()>;
+
+    /// Removes the oldest periods so that at most `keep` entries remain.
+    ///
+    /// Returns the number of removed entries.
+    fn prune_chain_stats(&self, table: ChainStatsTable, keep: usize) -> ProviderResult<usize>;
+}
@@ -0,0 +1,36 @@
+use alloc::vec::Vec;
+use alloy_primitives::{Address, BlockNumber};
+use core::ops::RangeInclusive;
+use reth_db_models::ContractCreation;
+use reth_storage_errors::provider::ProviderResult;
+
+/// The trait for looking up the creation of a contract, using the optional contract creations
+/// index.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait ContractCreationsReader: Send + Sync {
+    /// Returns the most recent creation of the contract at the given address.
+    ///
+    /// Returns [`None`] if the contract wasn't created in the blocks covered by the index.
+    fn contract_creation(&self, address: Address) -> ProviderResult<Option<ContractCreation>>;
+}
+
+/// The trait for maintaining the index of contract creations.
+#[auto_impl::auto_impl(&)]
+pub trait ContractCreationsWriter {
+    /// Inserts the given contract creations. Previous creations of the same addresses are kept, so
+    /// they're restored if the new ones are unwound.
+    ///
+    /// Returns the number of inserted creations.
+    fn insert_contract_creations(
+        &self,
+        creations: Vec<(Address, ContractCreation)>,
+    ) -> ProviderResult<usize>;
+
+    /// Removes the creations of the contracts created in the given block range.
+    ///
+    /// Returns the number of removed creations.
+    fn unwind_contract_creations(
+        &self,
+        block_range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<usize>;
+}
@@ -1,11 +1,48 @@
-use alloy_primitives::{Address, BlockNumber, B256};
+use alloc::vec::Vec;
+use alloy_primitives::{Address, BlockNumber, B256, U256};
 use auto_impl::auto_impl;
 use core::ops::{RangeBounds, RangeInclusive};
 use reth_db_api::models::BlockNumberAddress;
 use reth_db_models::AccountBeforeTx;
-use reth_primitives_traits::StorageEntry;
+use reth_primitives_traits::{Account, StorageEntry};
 use reth_storage_errors::provider::ProviderResult;
 
+/// History Reader
+///
+/// Looks up the history of accounts and storage slots using the history indices and the
+/// changesets.
+#[auto_impl(&, Arc, Box)]
+pub trait HistoryReader: Send {
+    /// Returns the blocks in the given range that changed the account, in ascending order,
+    /// together with the account info from before the block.
+    ///
+    /// The account info is `None` if the account didn't exist before the block.
+    ///
+    /// Returns [`ProviderError::StateAtBlockPruned`] if the account history of the start of the
+    /// range was pruned.
+    ///
+    /// [`ProviderError::StateAtBlockPruned`]: reth_storage_errors::provider::ProviderError::StateAtBlockPruned
+    fn account_history_range(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, Option<Account>)>>;
+
+    /// Returns the blocks in the given range that changed the storage slot, in ascending order,
+    /// together with the value of the slot from before the block.
+    ///
+    /// Returns [`ProviderError::StateAtBlockPruned`] if the storage history of the start of the
+    /// range was pruned.
+    ///
+    /// [`ProviderError::StateAtBlockPruned`]: reth_storage_errors::provider::ProviderError::StateAtBlockPruned
+    fn storage_history_range(
+        &self,
+        address: Address,
+        storage_key: B256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, U256)>>;
+}
+
 /// History Writer
 #[auto_impl(&, Box)]
 pub trait HistoryWriter: Send {
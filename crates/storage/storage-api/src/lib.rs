@@ -85,6 +85,27 @@ pub use primitives::*;
 mod block_indices;
 pub use block_indices::*;
 
+mod withdrawals;
+pub use withdrawals::*;
+
+mod reorg;
+pub use reorg::*;
+
+mod chain_stats;
+pub use chain_stats::*;
+
+mod address_transactions;
+pub use address_transactions::*;
+
+mod logs_bloom;
+pub use logs_bloom::*;
+
+mod contract_creations;
+pub use contract_creations::*;
+
+mod account_destructions;
+pub use account_destructions::*;
+
 #[cfg(feature = "std")]
 mod block_writer;
 #[cfg(feature = "std")]
@@ -0,0 +1,19 @@
+use alloc::vec::Vec;
+use alloy_primitives::{Address, BlockNumber};
+use core::ops::RangeInclusive;
+use reth_storage_errors::provider::ProviderResult;
+
+/// The trait for skipping blocks without logs of an address, using the logs blooms of the headers
+/// aggregated per static file.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait LogsBloomIndexReader: Send + Sync {
+    /// Returns the block ranges within the given block range that may contain logs emitted by any
+    /// of the given addresses, in ascending order and without overlaps.
+    ///
+    /// Blocks that aren't covered by the index are always part of the result.
+    fn block_ranges_with_log_addresses(
+        &self,
+        addresses: &[Address],
+        block_range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<RangeInclusive<BlockNumber>>>;
+}
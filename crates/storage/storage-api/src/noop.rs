@@ -1,19 +1,21 @@
 //! Various noop implementations for traits.
 
 use crate::{
-    AccountReader, BlockBodyIndicesProvider, BlockHashReader, BlockIdReader, BlockNumReader,
-    BlockReader, BlockReaderIdExt, BlockSource, BytecodeReader, ChangeSetReader,
-    HashedPostStateProvider, HeaderProvider, NodePrimitivesProvider, PruneCheckpointReader,
-    ReceiptProvider, ReceiptProviderIdExt, StageCheckpointReader, StateProofProvider,
-    StateProvider, StateProviderBox, StateProviderFactory, StateReader, StateRootProvider,
-    StorageRootProvider, TransactionVariant, TransactionsProvider,
+    AccountDestructionsReader, AccountReader, AddressTransactionsReader, BlockBodyIndicesProvider,
+    BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt, BlockSource,
+    BytecodeReader, ChangeSetReader, ContractCreationsReader, HashedPostStateProvider,
+    HeaderProvider, LogsBloomIndexReader, NodePrimitivesProvider, PruneCheckpointReader,
+    ReceiptProvider, ReceiptProviderIdExt, ReorgHistoryReader, StageCheckpointReader,
+    StateProofProvider, StateProvider, StateProviderBox, StateProviderFactory, StateReader,
+    StateRootProvider, StorageRootProvider, TransactionVariant, TransactionsProvider,
+    WithdrawalsProvider,
 };
 
 #[cfg(feature = "db-api")]
-use crate::{DBProvider, DatabaseProviderFactory, StorageChangeSetReader};
+use crate::{DBProvider, DatabaseProviderFactory, HistoryReader, StorageChangeSetReader};
 use alloc::{boxed::Box, string::String, sync::Arc, vec::Vec};
 use alloy_consensus::transaction::TransactionMeta;
-use alloy_eips::{BlockHashOrNumber, BlockId, BlockNumberOrTag};
+use alloy_eips::{eip4895::Withdrawals, BlockHashOrNumber, BlockId, BlockNumberOrTag};
 use alloy_primitives::{
     Address, BlockHash, BlockNumber, Bytes, StorageKey, StorageValue, TxHash, TxNumber, B256,
 };
@@ -25,7 +27,9 @@ use core::{
 use reth_chainspec::{ChainInfo, ChainSpecProvider, EthChainSpec, MAINNET};
 #[cfg(feature = "db-api")]
 use reth_db_api::mock::{DatabaseMock, TxMock};
-use reth_db_models::{AccountBeforeTx, StoredBlockBodyIndices};
+use reth_db_models::{
+    AccountBeforeTx, AccountDestruction, ContractCreation, ReorgRecord, StoredBlockBodyIndices,
+};
 use reth_ethereum_primitives::EthPrimitives;
 use reth_execution_types::ExecutionOutcome;
 use reth_primitives_traits::{Account, Bytecode, NodePrimitives, RecoveredBlock, SealedHeader};
@@ -442,6 +446,26 @@ impl<C: Send + Sync, N: NodePrimitives> StorageChangeSetReader for NoopProvider<
     }
 }
 
+#[cfg(feature = "db-api")]
+impl<C: Send + Sync, N: Send + Sync> HistoryReader for NoopProvider<C, N> {
+    fn account_history_range(
+        &self,
+        _address: Address,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, Option<Account>)>> {
+        Ok(Vec::new())
+    }
+
+    fn storage_history_range(
+        &self,
+        _address: Address,
+        _storage_key: B256,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, StorageValue)>> {
+        Ok(Vec::new())
+    }
+}
+
 impl<C: Send + Sync, N: NodePrimitives> StateRootProvider for NoopProvider<C, N> {
     fn state_root(&self, _state: HashedPostState) -> ProviderResult<B256> {
         Ok(B256::default())
@@ -652,6 +676,53 @@ impl<C: Send + Sync, N: Send + Sync> BlockBodyIndicesProvider for NoopProvider<C
     }
 }
 
+impl<C: Send + Sync, N: Send + Sync> WithdrawalsProvider for NoopProvider<C, N> {
+    fn withdrawals_by_block_range(
+        &self,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<Withdrawals>> {
+        Ok(Vec::new())
+    }
+}
+
+impl<C: Send + Sync, N: Send + Sync> ReorgHistoryReader for NoopProvider<C, N> {
+    fn reorg_history(&self, _limit: usize) -> ProviderResult<Vec<ReorgRecord>> {
+        Ok(Vec::new())
+    }
+}
+
+impl<C: Send + Sync, N: Send + Sync> AddressTransactionsReader for NoopProvider<C, N> {
+    fn address_transactions(
+        &self,
+        _addresses: &[Address],
+        _block_range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Option<Vec<TxNumber>>> {
+        Ok(None)
+    }
+}
+
+impl<C: Send + Sync, N: Send + Sync> LogsBloomIndexReader for NoopProvider<C, N> {
+    fn block_ranges_with_log_addresses(
+        &self,
+        _addresses: &[Address],
+        block_range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<RangeInclusive<BlockNumber>>> {
+        Ok(alloc::vec![block_range])
+    }
+}
+
+impl<C: Send + Sync, N: Send + Sync> ContractCreationsReader for NoopProvider<C, N> {
+    fn contract_creation(&self, _address: Address) -> ProviderResult<Option<ContractCreation>> {
+        Ok(None)
+    }
+}
+
+impl<C: Send + Sync, N: Send + Sync> AccountDestructionsReader for NoopProvider<C, N> {
+    fn account_destructions(&self, _address: Address) -> ProviderResult<Vec<AccountDestruction>> {
+        Ok(Vec::new())
+    }
+}
+
 #[cfg(feature = "db-api")]
 impl<ChainSpec: Send + Sync, N: NodePrimitives> DBProvider for NoopProvider<ChainSpec, N> {
     type Tx = TxMock;
@@ -52,6 +52,24 @@ pub trait ReceiptProvider {
         &self,
         block_range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<Vec<Vec<Self::Receipt>>>;
+
+    /// Get the [`ReceiptBlockOffsets`] of the receipt of the given transaction number.
+    ///
+    /// Returns `None` if the offsets are not indexed, in which case they have to be computed from
+    /// the receipts of the block.
+    fn receipt_block_offsets(&self, _id: TxNumber) -> ProviderResult<Option<ReceiptBlockOffsets>> {
+        Ok(None)
+    }
+}
+
+/// Offsets of a receipt within its block, i.e. the gas used and the logs emitted by the
+/// transactions that precede it in the block.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReceiptBlockOffsets {
+    /// Cumulative gas used by the preceding transactions of the block.
+    pub gas_used: u64,
+    /// Index of the first log of the receipt within the block.
+    pub log_index: u64,
 }
 
 /// Trait extension for `ReceiptProvider`, for types that implement `BlockId` conversion.
@@ -0,0 +1,22 @@
+use alloc::vec::Vec;
+use reth_db_models::ReorgRecord;
+use reth_storage_errors::provider::ProviderResult;
+
+/// The trait for fetching the reorgs recorded by the node.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait ReorgHistoryReader: Send {
+    /// Returns the most recent recorded reorgs, newest first, up to `limit` entries.
+    fn reorg_history(&self, limit: usize) -> ProviderResult<Vec<ReorgRecord>>;
+}
+
+/// The trait for recording reorgs performed by the node.
+#[auto_impl::auto_impl(&)]
+pub trait ReorgHistoryWriter {
+    /// Appends a reorg to the reorg history.
+    fn insert_reorg_record(&self, record: ReorgRecord) -> ProviderResult<()>;
+
+    /// Removes the oldest recorded reorgs so that at most `keep` entries remain.
+    ///
+    /// Returns the number of removed entries.
+    fn prune_reorg_history(&self, keep: usize) -> ProviderResult<usize>;
+}
@@ -0,0 +1,19 @@
+use alloc::vec::Vec;
+use alloy_eips::eip4895::Withdrawals;
+use alloy_primitives::BlockNumber;
+use core::ops::RangeInclusive;
+use reth_storage_errors::provider::ProviderResult;
+
+/// Client trait for fetching the withdrawals of blocks without reading their full bodies.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait WithdrawalsProvider: Send {
+    /// Returns the withdrawals of all blocks in the requested range.
+    ///
+    /// Contains one entry per block, starting at the start of the range and ending at the last
+    /// available block. Blocks without withdrawals, e.g. because they are pre-Shanghai, have an
+    /// empty entry.
+    fn withdrawals_by_block_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<Withdrawals>>;
+}
@@ -13,7 +13,10 @@
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
 use crate::{
-    metrics::{IncCounterOnDrop, TaskExecutorMetrics},
+    metrics::{
+        inc_critical_task_panics, task_poll_duration, InFlightGuard, IncCounterOnDrop,
+        PollDurationFuture, TaskExecutorMetrics,
+    },
     shutdown::{signal, GracefulShutdown, GracefulShutdownGuard, Shutdown, Signal},
 };
 use dyn_clone::DynClone;
@@ -131,6 +134,10 @@ impl TaskSpawner for TokioTaskExecutor {
     }
 
     fn spawn_blocking(&self, fut: BoxFuture<'static, ()>) -> JoinHandle<()> {
+        #[cfg(feature = "rayon")]
+        if let Some(pool) = pool::BlockingTaskPool::scoped() {
+            return pool.spawn_blocking_future(Handle::current(), fut)
+        }
         tokio::task::spawn_blocking(move || tokio::runtime::Handle::current().block_on(fut))
     }
 
@@ -363,15 +370,31 @@ impl TaskExecutor {
     }
 
     /// Spawns a future on the tokio runtime depending on the [`TaskKind`]
-    fn spawn_on_rt<F>(&self, fut: F, task_kind: TaskKind) -> JoinHandle<()>
+    ///
+    /// Records the poll durations of the task with the given name, or the number of blocking tasks
+    /// in flight for blocking tasks.
+    fn spawn_on_rt<F>(&self, name: &'static str, fut: F, task_kind: TaskKind) -> JoinHandle<()>
     where
         F: Future<Output = ()> + Send + 'static,
     {
         match task_kind {
-            TaskKind::Default => self.handle.spawn(fut),
+            TaskKind::Default => {
+                self.handle.spawn(PollDurationFuture::new(fut, task_poll_duration(name)))
+            }
             TaskKind::Blocking => {
                 let handle = self.handle.clone();
-                self.handle.spawn_blocking(move || handle.block_on(fut))
+                let in_flight = InFlightGuard::new(self.metrics.blocking_tasks_in_flight.clone());
+                #[cfg(feature = "rayon")]
+                if let Some(pool) = pool::BlockingTaskPool::scoped() {
+                    return pool.spawn_blocking_future(handle, async move {
+                        let _in_flight = in_flight;
+                        fut.await
+                    })
+                }
+                self.handle.spawn_blocking(move || {
+                    let _in_flight = in_flight;
+                    handle.block_on(fut)
+                })
             }
         }
     }
@@ -400,7 +423,7 @@ impl TaskExecutor {
         }
         .in_current_span();
 
-        self.spawn_on_rt(task, task_kind)
+        self.spawn_on_rt(REGULAR_TASK, task, task_kind)
     }
 
     /// Spawns the task onto the runtime.
@@ -438,7 +461,7 @@ impl TaskExecutor {
 
         let task = fut.in_current_span();
 
-        self.handle.spawn(task)
+        self.handle.spawn(PollDurationFuture::new(task, task_poll_duration(REGULAR_TASK)))
     }
 
     /// Spawns a critical task depending on the given [`TaskKind`]
@@ -460,6 +483,7 @@ impl TaskExecutor {
             .map_err(move |error| {
                 let task_error = PanickedTaskError::new(name, error);
                 error!("{task_error}");
+                inc_critical_task_panics(name);
                 let _ = panicked_tasks_tx.send(TaskEvent::Panic(task_error));
             })
             .in_current_span();
@@ -474,7 +498,7 @@ impl TaskExecutor {
             let _ = select(on_shutdown, task).await;
         };
 
-        self.spawn_on_rt(task, task_kind)
+        self.spawn_on_rt(name, task, task_kind)
     }
 
     /// This spawns a critical blocking task onto the runtime.
@@ -520,12 +544,13 @@ impl TaskExecutor {
             .map_err(move |error| {
                 let task_error = PanickedTaskError::new(name, error);
                 error!("{task_error}");
+                inc_critical_task_panics(name);
                 let _ = panicked_tasks_tx.send(TaskEvent::Panic(task_error));
             })
             .map(drop)
             .in_current_span();
 
-        self.handle.spawn(task)
+        self.handle.spawn(PollDurationFuture::new(task, task_poll_duration(name)))
     }
 
     /// This spawns a critical task onto the runtime.
@@ -569,12 +594,13 @@ impl TaskExecutor {
             .map_err(move |error| {
                 let task_error = PanickedTaskError::new(name, error);
                 error!("{task_error}");
+                inc_critical_task_panics(name);
                 let _ = panicked_tasks_tx.send(TaskEvent::Panic(task_error));
             })
             .map(drop)
             .in_current_span();
 
-        self.handle.spawn(task)
+        self.handle.spawn(PollDurationFuture::new(task, task_poll_duration(name)))
     }
 
     /// This spawns a regular task onto the runtime.
@@ -609,7 +635,7 @@ impl TaskExecutor {
         );
         let fut = f(on_shutdown);
 
-        self.handle.spawn(fut)
+        self.handle.spawn(PollDurationFuture::new(fut, task_poll_duration(REGULAR_TASK)))
     }
 
     /// Sends a request to the `TaskManager` to initiate a graceful shutdown.
@@ -707,6 +733,9 @@ impl TaskSpawnerExt for TaskExecutor {
     }
 }
 
+/// Metrics label of tasks spawned without a name.
+const REGULAR_TASK: &str = "regular";
+
 /// Determines how a task is spawned
 enum TaskKind {
     /// Spawn the task to the default executor [`Handle::spawn`]
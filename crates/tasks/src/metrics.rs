@@ -1,8 +1,17 @@
 //! Task Executor Metrics
 
 use core::fmt;
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Instant,
+};
 
-use reth_metrics::{metrics::Counter, Metrics};
+use reth_metrics::{
+    metrics::{self, Counter, Gauge, Histogram},
+    Metrics,
+};
 
 /// Task Executor Metrics
 #[derive(Metrics, Clone)]
@@ -20,6 +29,8 @@ pub struct TaskExecutorMetrics {
     pub(crate) regular_blocking_tasks_total: Counter,
     /// Number of finished spawned regular blocking tasks
     pub(crate) finished_regular_blocking_tasks_total: Counter,
+    /// Number of spawned blocking tasks that are queued or running on the blocking pool
+    pub(crate) blocking_tasks_in_flight: Gauge,
 }
 
 impl TaskExecutorMetrics {
@@ -39,6 +50,18 @@ impl TaskExecutorMetrics {
     }
 }
 
+/// Returns the histogram of the poll durations of the task with the given name.
+///
+/// Regular tasks are unnamed and share the `regular` label.
+pub(crate) fn task_poll_duration(task: &'static str) -> Histogram {
+    metrics::histogram!("executor.spawn.task_poll_duration_seconds", "task" => task)
+}
+
+/// Increments the counter of panics of the critical task with the given name.
+pub(crate) fn inc_critical_task_panics(task: &'static str) {
+    metrics::counter!("executor.spawn.critical_task_panics_total", "task" => task).increment(1);
+}
+
 /// Helper type for increasing counters even if a task fails
 pub struct IncCounterOnDrop(Counter);
 
@@ -61,3 +84,49 @@ impl Drop for IncCounterOnDrop {
         self.0.increment(1);
     }
 }
+
+/// Helper type that increments a gauge on creation and decrements it on drop, even if a task fails
+pub(crate) struct InFlightGuard(Gauge);
+
+impl InFlightGuard {
+    /// Increments the gauge until the returned guard is dropped.
+    pub(crate) fn new(gauge: Gauge) -> Self {
+        gauge.increment(1);
+        Self(gauge)
+    }
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        self.0.decrement(1);
+    }
+}
+
+/// A future that records the duration of every poll of the inner future.
+///
+/// Long polls block the worker thread of the runtime and delay all other tasks scheduled on it.
+#[pin_project::pin_project]
+pub(crate) struct PollDurationFuture<F> {
+    #[pin]
+    fut: F,
+    poll_duration: Histogram,
+}
+
+impl<F> PollDurationFuture<F> {
+    /// Wraps the future, recording its poll durations to the given histogram.
+    pub(crate) const fn new(fut: F, poll_duration: Histogram) -> Self {
+        Self { fut, poll_duration }
+    }
+}
+
+impl<F: Future> Future for PollDurationFuture<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let start = Instant::now();
+        let poll = this.fut.poll(cx);
+        this.poll_duration.record(start.elapsed());
+        poll
+    }
+}
@@ -8,7 +8,11 @@ use std::{
     task::{ready, Context, Poll},
     thread,
 };
-use tokio::sync::{oneshot, AcquireError, OwnedSemaphorePermit, Semaphore};
+use tokio::{
+    runtime::Handle,
+    sync::{oneshot, AcquireError, OwnedSemaphorePermit, Semaphore},
+    task::JoinHandle,
+};
 
 /// RPC Tracing call guard semaphore.
 ///
@@ -117,6 +121,44 @@ impl BlockingTaskPool {
 
         BlockingTaskHandle { rx }
     }
+
+    /// Returns `true` if both handles refer to the same threadpool.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.pool, &other.pool)
+    }
+
+    /// Runs the future with this pool as the pool of its blocking tasks.
+    ///
+    /// While the future is polled, blocking tasks spawned with
+    /// [`TaskSpawner::spawn_blocking`](crate::TaskSpawner::spawn_blocking) of the
+    /// [`TaskExecutor`](crate::TaskExecutor) or the
+    /// [`TokioTaskExecutor`](crate::TokioTaskExecutor) are executed on this pool instead of the
+    /// tokio blocking pool. This allows isolating the blocking work of certain requests, without
+    /// having to pass the pool to the code that spawns the tasks.
+    pub fn scope<F: Future>(&self, fut: F) -> impl Future<Output = F::Output> {
+        SCOPED_POOL.scope(self.clone(), fut)
+    }
+
+    /// Returns the pool of the current [`Self::scope`], if any.
+    pub(crate) fn scoped() -> Option<Self> {
+        SCOPED_POOL.try_with(Clone::clone).ok()
+    }
+
+    /// Runs the future to completion on this pool, inside the runtime of the given handle.
+    pub(crate) fn spawn_blocking_future<F>(&self, handle: Handle, fut: F) -> JoinHandle<()>
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let task = self.spawn(move || handle.block_on(fut));
+        tokio::task::spawn(async move {
+            let _ = task.await;
+        })
+    }
+}
+
+tokio::task_local! {
+    /// The pool set by [`BlockingTaskPool::scope`].
+    static SCOPED_POOL: BlockingTaskPool;
 }
 
 /// Async handle for a blocking task running in a Rayon thread pool.
@@ -154,6 +196,7 @@ pub struct TokioBlockingTaskError;
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{TaskSpawner, TokioTaskExecutor};
 
     #[tokio::test]
     async fn blocking_pool() {
@@ -163,6 +206,26 @@ mod tests {
         assert_eq!(res, 5);
     }
 
+    #[tokio::test]
+    async fn scoped_blocking_tasks() {
+        let pool = BlockingTaskPool::new(
+            BlockingTaskPool::builder()
+                .num_threads(1)
+                .thread_name(|_| "scoped-pool".to_string())
+                .build()
+                .unwrap(),
+        );
+        let (tx, rx) = oneshot::channel();
+        pool.scope(async move {
+            TokioTaskExecutor::default().spawn_blocking(Box::pin(async move {
+                let _ = tx.send(thread::current().name().map(ToString::to_string));
+            }));
+        })
+        .await;
+        assert_eq!(rx.await.unwrap().as_deref(), Some("scoped-pool"));
+        assert!(BlockingTaskPool::scoped().is_none());
+    }
+
     #[tokio::test]
     async fn blocking_pool_panic() {
         let pool = BlockingTaskPool::build().unwrap();
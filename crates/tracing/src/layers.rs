@@ -153,6 +153,21 @@ impl Layers {
         Ok(())
     }
 
+    /// Adds a layer serving the task instrumentation of the tokio runtime to `tokio-console`.
+    ///
+    /// The server address is configured via the `TOKIO_CONSOLE_BIND` environment variable and
+    /// defaults to `127.0.0.1:6669`.
+    #[cfg(feature = "tokio-console")]
+    pub(crate) fn tokio_console(&mut self) -> eyre::Result<()> {
+        self.add_layer(
+            console_subscriber::ConsoleLayer::builder()
+                .with_default_env()
+                .spawn()
+                .with_filter(EnvFilter::try_new("tokio=trace,runtime=trace")?),
+        );
+        Ok(())
+    }
+
     /// Add OTLP spans layer to the layer collection
     #[cfg(feature = "otlp")]
     pub fn with_span_layer(
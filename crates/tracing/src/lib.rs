@@ -81,6 +81,8 @@ pub struct RethTracer {
     samply: Option<LayerInfo>,
     #[cfg(feature = "tracy")]
     tracy: Option<LayerInfo>,
+    #[cfg(feature = "tokio-console")]
+    tokio_console: bool,
 }
 
 impl RethTracer {
@@ -96,6 +98,8 @@ impl RethTracer {
             samply: None,
             #[cfg(feature = "tracy")]
             tracy: None,
+            #[cfg(feature = "tokio-console")]
+            tokio_console: false,
         }
     }
 
@@ -139,6 +143,15 @@ impl RethTracer {
         self.tracy = Some(config);
         self
     }
+
+    /// Enables the tokio-console layer.
+    ///
+    /// The instrumentation is only emitted by tokio if built with `--cfg tokio_unstable`.
+    #[cfg(feature = "tokio-console")]
+    pub const fn with_tokio_console(mut self) -> Self {
+        self.tokio_console = true;
+        self
+    }
 }
 
 impl Default for RethTracer {
@@ -264,6 +277,11 @@ impl Tracer for RethTracer {
             layers.tracy(config)?;
         }
 
+        #[cfg(feature = "tokio-console")]
+        if self.tokio_console {
+            layers.tokio_console()?;
+        }
+
         // The error is returned if the global default subscriber is already set,
         // so it's safe to ignore it
         let _ = tracing_subscriber::registry().with(layers.into_inner()).try_init();
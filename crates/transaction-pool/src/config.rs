@@ -51,6 +51,10 @@ pub struct PoolConfig {
     pub max_account_slots: usize,
     /// Price bump (in %) for the transaction pool underpriced check.
     pub price_bumps: PriceBumpConfig,
+    /// Max number of transactions a sender can replace per block, unlimited if `None`.
+    ///
+    /// Local transactions are exempt from this limit.
+    pub max_replacements_per_sender: Option<usize>,
     /// Minimum base fee required by the protocol.
     pub minimal_protocol_basefee: u64,
     /// Minimum priority fee required for transaction acceptance into the pool.
@@ -100,6 +104,15 @@ impl PoolConfig {
         self
     }
 
+    /// Configures how many transactions a sender can replace per block.
+    pub const fn with_max_replacements_per_sender(
+        mut self,
+        max_replacements_per_sender: Option<usize>,
+    ) -> Self {
+        self.max_replacements_per_sender = max_replacements_per_sender;
+        self
+    }
+
     /// Returns whether the size and amount constraints in any sub-pools are exceeded.
     #[inline]
     pub const fn is_exceeded(&self, pool_size: PoolSize) -> bool {
@@ -120,6 +133,7 @@ impl Default for PoolConfig {
             blob_cache_size: None,
             max_account_slots: TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
             price_bumps: Default::default(),
+            max_replacements_per_sender: None,
             minimal_protocol_basefee: MIN_PROTOCOL_BASE_FEE,
             minimum_priority_fee: None,
             gas_limit: ETHEREUM_BLOCK_GAS_LIMIT_30M,
@@ -48,8 +48,12 @@ pub enum PoolErrorKind {
     #[error("already imported")]
     AlreadyImported,
     /// Thrown if a replacement transaction's gas price is below the already imported transaction
-    #[error("insufficient gas price to replace existing transaction")]
-    ReplacementUnderpriced,
+    #[error("insufficient gas price to replace existing transaction: {0}")]
+    ReplacementUnderpriced(ReplacementUnderpricedError),
+    /// Thrown if the sender already replaced the configured maximum number of transactions since
+    /// the last block.
+    #[error("sender {0} exceeded the limit of {1} replacements per block")]
+    ReplacementLimitExceeded(Address, usize),
     /// The fee cap of the transaction is below the minimum fee cap determined by the protocol
     #[error("transaction feeCap {0} below chain minimum")]
     FeeCapBelowMinimumProtocolFeeCap(u128),
@@ -108,10 +112,14 @@ impl PoolError {
                 // already imported but not bad
                 false
             }
-            PoolErrorKind::ReplacementUnderpriced => {
+            PoolErrorKind::ReplacementUnderpriced(_) => {
                 // already imported but not bad
                 false
             }
+            PoolErrorKind::ReplacementLimitExceeded(_, _) => {
+                // the limit is a local policy of the pool and the replacement may be valid
+                false
+            }
             PoolErrorKind::FeeCapBelowMinimumProtocolFeeCap(_) => {
                 // fee cap of the tx below the technical minimum determined by the protocol, see
                 // [MINIMUM_PROTOCOL_FEE_CAP](alloy_primitives::constants::MIN_PROTOCOL_BASE_FEE)
@@ -147,6 +155,38 @@ impl PoolError {
     }
 }
 
+/// The fee of a replacement transaction that isn't bumped enough to replace the existing
+/// transaction with the same nonce.
+///
+/// See also [`PriceBumpConfig`](crate::PriceBumpConfig).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum ReplacementUnderpricedError {
+    /// The max fee per gas (or gas price) is below the required bump.
+    #[error("max fee per gas {got} below required {required}")]
+    MaxFeePerGas {
+        /// The max fee per gas of the replacement transaction.
+        got: u128,
+        /// The minimum max fee per gas required to replace the existing transaction.
+        required: u128,
+    },
+    /// The max priority fee per gas is below the required bump.
+    #[error("max priority fee per gas {got} below required {required}")]
+    MaxPriorityFeePerGas {
+        /// The max priority fee per gas of the replacement transaction.
+        got: u128,
+        /// The minimum max priority fee per gas required to replace the existing transaction.
+        required: u128,
+    },
+    /// The max fee per blob gas of a replacement blob transaction is below the required bump.
+    #[error("max fee per blob gas {got} below required {required}")]
+    MaxFeePerBlobGas {
+        /// The max fee per blob gas of the replacement transaction.
+        got: u128,
+        /// The minimum max fee per blob gas required to replace the existing transaction.
+        required: u128,
+    },
+}
+
 /// Represents all errors that can happen when validating transactions for the pool for EIP-4844
 /// transactions
 #[derive(Debug, thiserror::Error)]
@@ -206,6 +246,31 @@ pub enum Eip7702PoolTransactionError {
     AuthorityReserved,
 }
 
+/// Represents errors that can happen when validating transactions that submit EIP-7002 withdrawal
+/// or EIP-7251 consolidation requests to the system contracts.
+#[derive(Debug, thiserror::Error)]
+pub enum SystemRequestPoolTransactionError {
+    /// Thrown if the value of the transaction doesn't cover the current request fee.
+    #[error("request fee {fee} exceeds transaction value {value}")]
+    InsufficientFee {
+        /// Current fee of the system contract.
+        fee: U256,
+        /// Value of the transaction.
+        value: U256,
+    },
+    /// Thrown if the input of the transaction is not a well-formed request.
+    #[error("invalid request input length {len}, expected {expected}")]
+    InvalidInput {
+        /// Length of the transaction input.
+        len: usize,
+        /// Length of a request.
+        expected: usize,
+    },
+    /// Thrown if the system contract doesn't accept requests yet.
+    #[error("request system contract is not active")]
+    Inactive,
+}
+
 /// Represents errors that can happen when validating transactions for the pool
 ///
 /// See [`TransactionValidator`](crate::TransactionValidator).
@@ -265,6 +330,9 @@ pub enum InvalidPoolTransactionError {
     /// EIP-7702 related errors
     #[error(transparent)]
     Eip7702(#[from] Eip7702PoolTransactionError),
+    /// EIP-7002 and EIP-7251 request related errors
+    #[error(transparent)]
+    SystemRequest(#[from] SystemRequestPoolTransactionError),
     /// Any other error that occurred while inserting/validating that is transaction specific
     #[error(transparent)]
     Other(Box<dyn PoolTransactionError>),
@@ -392,6 +460,10 @@ impl InvalidPoolTransactionError {
                 Eip7702PoolTransactionError::InflightTxLimitReached => false,
                 Eip7702PoolTransactionError::AuthorityReserved => false,
             },
+            Self::SystemRequest(_) => {
+                // the request would revert, but the transaction itself is valid
+                false
+            }
             Self::PriorityFeeBelowMinimum { .. } => false,
         }
     }
@@ -92,6 +92,8 @@
 //! - [`Underpriced`](crate::error::InvalidPoolTransactionError::Underpriced): Fee too low
 //! - [`ReplacementUnderpriced`](crate::error::PoolErrorKind::ReplacementUnderpriced): Replacement
 //!   transaction fee too low
+//! - [`ReplacementLimitExceeded`](crate::error::PoolErrorKind::ReplacementLimitExceeded): Sender
+//!   replaced too many transactions since the last block
 //! - Blob errors:
 //!   - [`MissingEip4844BlobSidecar`](crate::error::Eip4844PoolTransactionError::MissingEip4844BlobSidecar): Missing sidecar
 //!   - [`InvalidEip4844Blob`](crate::error::Eip4844PoolTransactionError::InvalidEip4844Blob):
@@ -9,7 +9,7 @@ use crate::{
     TransactionOrigin,
 };
 use alloy_consensus::{transaction::TxHashRef, BlockHeader, Typed2718};
-use alloy_eips::{BlockNumberOrTag, Decodable2718, Encodable2718};
+use alloy_eips::{eip7840::BlobParams, BlockNumberOrTag, Decodable2718, Encodable2718};
 use alloy_primitives::{Address, BlockHash, BlockNumber, Bytes};
 use alloy_rlp::Encodable;
 use futures_util::{
@@ -141,10 +141,15 @@ pub async fn maintain_transaction_pool<N, Client, P, St, Tasks>(
 {
     let metrics = MaintainPoolMetrics::default();
     let MaintainPoolConfig { max_update_depth, max_reload_accounts, .. } = config;
+
+    // keeps track of the blob parameters at the tip, to detect when they change at a fork boundary
+    let mut blob_params = None;
+
     // ensure the pool points to latest state
     if let Ok(Some(latest)) = client.header_by_number_or_tag(BlockNumberOrTag::Latest) {
         let latest = SealedHeader::seal_slow(latest);
         let chain_spec = client.chain_spec();
+        blob_params = chain_spec.blob_params_at_timestamp(latest.timestamp());
         let info = BlockInfo {
             block_gas_limit: latest.gas_limit(),
             last_seen_block_hash: latest.hash(),
@@ -317,6 +322,21 @@ pub async fn maintain_transaction_pool<N, Client, P, St, Tasks>(
 
         // handle the new block or reorg
         let Some(event) = event else { continue };
+
+        // if the blob parameters changed at a fork boundary, blob transactions that were valid
+        // before may no longer be includable
+        let tip_blob_params = client.chain_spec().blob_params_at_timestamp(event.tip().timestamp());
+        if tip_blob_params != blob_params {
+            if let Some(params) = &tip_blob_params {
+                let evicted = evict_blob_transactions(&pool, params);
+                if evicted > 0 {
+                    info!(target: "txpool", evicted, max_blobs_per_tx = params.max_blobs_per_tx, "Evicted blob transactions after blob parameters changed");
+                }
+                metrics.inc_evicted_blob_transactions(evicted);
+            }
+            blob_params = tip_blob_params;
+        }
+
         match event {
             CanonStateNotification::Reorg { old, new } => {
                 let (old_blocks, old_state) = old.inner();
@@ -594,6 +614,36 @@ pub async fn maintain_transaction_pool<N, Client, P, St, Tasks>(
     }
 }
 
+/// Removes all blob transactions from the pool that can no longer be included with the given blob
+/// parameters, because they have more blobs than permitted per transaction.
+///
+/// Returns the number of evicted transactions.
+fn evict_blob_transactions<P>(pool: &P, blob_params: &BlobParams) -> usize
+where
+    P: TransactionPoolExt,
+{
+    // `all_transactions` only returns the pending and queued transactions, but most blob
+    // transactions that can't be included are in the blob sub-pool
+    let evicted = pool
+        .get_all(pool.all_transaction_hashes())
+        .into_iter()
+        .filter(|tx| {
+            tx.transaction
+                .blob_versioned_hashes()
+                .is_some_and(|hashes| hashes.len() as u64 > blob_params.max_blobs_per_tx)
+        })
+        .map(|tx| *tx.hash())
+        .collect::<Vec<_>>();
+    if evicted.is_empty() {
+        return 0
+    }
+
+    trace!(target: "txpool", ?evicted, "evicting blob transactions exceeding the blob limit");
+    let removed = pool.remove_transactions(evicted.clone()).len();
+    pool.delete_blobs(evicted);
+    removed
+}
+
 struct FinalizedBlockTracker {
     last_finalized_block: Option<BlockNumber>,
 }
@@ -856,11 +906,13 @@ pub async fn backup_local_transactions_task<P>(
 mod tests {
     use super::*;
     use crate::{
-        blobstore::InMemoryBlobStore, validate::EthTransactionValidatorBuilder,
+        blobstore::InMemoryBlobStore,
+        test_utils::{testing_pool, MockTransaction},
+        validate::EthTransactionValidatorBuilder,
         CoinbaseTipOrdering, EthPooledTransaction, Pool, TransactionOrigin,
     };
     use alloy_eips::eip2718::Decodable2718;
-    use alloy_primitives::{hex, U256};
+    use alloy_primitives::{hex, B256, U256};
     use reth_ethereum_primitives::PooledTransactionVariant;
     use reth_evm_ethereum::EthEvmConfig;
     use reth_fs_util as fs;
@@ -878,6 +930,53 @@ mod tests {
     const EXTENSION: &str = "json";
     const FILENAME: &str = "test_transactions_backup";
 
+    #[tokio::test]
+    async fn evict_blob_transactions_over_limit() {
+        let pool = testing_pool();
+        let blob_tx = |blobs: usize| {
+            let mut tx = MockTransaction::eip4844();
+            if let MockTransaction::Eip4844 { blob_versioned_hashes, .. } = &mut tx {
+                *blob_versioned_hashes = vec![B256::random(); blobs];
+            }
+            tx
+        };
+        let small = blob_tx(2);
+        let large = blob_tx(9);
+        pool.add_transaction(TransactionOrigin::External, small.clone()).await.unwrap();
+        pool.add_transaction(TransactionOrigin::External, large.clone()).await.unwrap();
+        pool.add_transaction(TransactionOrigin::External, MockTransaction::eip1559())
+            .await
+            .unwrap();
+
+        let blob_params = BlobParams { max_blobs_per_tx: 6, ..BlobParams::cancun() };
+        assert_eq!(evict_blob_transactions(&pool, &blob_params), 1);
+        assert!(pool.contains(small.hash()));
+        assert!(!pool.contains(large.hash()));
+        assert_eq!(pool.len(), 2);
+
+        assert_eq!(evict_blob_transactions(&pool, &blob_params), 0);
+    }
+
+    #[tokio::test]
+    async fn evict_blob_transactions_from_blob_subpool() {
+        let pool = testing_pool();
+        let mut large = MockTransaction::eip4844().with_blob_fee(0);
+        if let MockTransaction::Eip4844 { blob_versioned_hashes, .. } = &mut large {
+            *blob_versioned_hashes = vec![B256::random(); 9];
+        }
+        pool.add_transaction(TransactionOrigin::External, large.clone()).await.unwrap();
+
+        // the blob fee is too low, so the transaction is neither pending nor queued
+        let AllPoolTransactions { pending, queued } = pool.all_transactions();
+        assert!(pending.is_empty() && queued.is_empty());
+        assert_eq!(pool.pool_size().blob, 1);
+
+        let blob_params = BlobParams { max_blobs_per_tx: 6, ..BlobParams::cancun() };
+        assert_eq!(evict_blob_transactions(&pool, &blob_params), 1);
+        assert!(!pool.contains(large.hash()));
+        assert_eq!(pool.len(), 0);
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn test_save_local_txs_backup() {
         let temp_dir = tempfile::tempdir().unwrap();
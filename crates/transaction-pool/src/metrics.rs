@@ -93,6 +93,9 @@ pub struct MaintainPoolMetrics {
     pub reinserted_transactions: Counter,
     /// Counter for the number of finalized blob transactions that have been removed from tracking.
     pub deleted_tracked_finalized_blobs: Counter,
+    /// Counter for the number of blob transactions evicted because they can no longer be included
+    /// after the blob parameters changed.
+    pub evicted_blob_transactions: Counter,
 }
 
 impl MaintainPoolMetrics {
@@ -114,6 +117,12 @@ impl MaintainPoolMetrics {
         self.deleted_tracked_finalized_blobs.increment(count as u64);
     }
 
+    /// Increments the count of evicted blob transactions.
+    #[inline]
+    pub fn inc_evicted_blob_transactions(&self, count: usize) {
+        self.evicted_blob_transactions.increment(count as u64);
+    }
+
     /// Increments the drift count by one.
     #[inline]
     pub fn inc_drift(&self) {
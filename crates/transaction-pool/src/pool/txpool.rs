@@ -4,7 +4,7 @@ use crate::{
     config::{LocalTransactionConfig, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER},
     error::{
         Eip4844PoolTransactionError, Eip7702PoolTransactionError, InvalidPoolTransactionError,
-        PoolError, PoolErrorKind,
+        PoolError, PoolErrorKind, ReplacementUnderpricedError,
     },
     identifier::{SenderId, TransactionId},
     metrics::{AllTransactionsMetrics, TxPoolMetrics},
@@ -784,10 +784,18 @@ impl<T: TransactionOrdering> TxPool<T> {
                 // Update invalid transactions metric
                 self.metrics.invalid_transactions.increment(1);
                 match err {
-                    InsertErr::Underpriced { existing: _, transaction } => Err(PoolError::new(
-                        *transaction.hash(),
-                        PoolErrorKind::ReplacementUnderpriced,
-                    )),
+                    InsertErr::Underpriced { existing: _, transaction, reason } => {
+                        Err(PoolError::new(
+                            *transaction.hash(),
+                            PoolErrorKind::ReplacementUnderpriced(reason),
+                        ))
+                    }
+                    InsertErr::ReplacementLimitExceeded { transaction, limit } => {
+                        Err(PoolError::new(
+                            *transaction.hash(),
+                            PoolErrorKind::ReplacementLimitExceeded(transaction.sender(), limit),
+                        ))
+                    }
                     InsertErr::FeeCapBelowMinimumProtocolFeeCap { transaction, fee_cap } => {
                         Err(PoolError::new(
                             *transaction.hash(),
@@ -1339,6 +1347,10 @@ pub(crate) struct AllTransactions<T: PoolTransaction> {
     pending_fees: PendingFees,
     /// Configured price bump settings for replacements
     price_bumps: PriceBumpConfig,
+    /// Max number of replacements per sender since the last seen block, if limited.
+    max_replacements_per_sender: Option<usize>,
+    /// Tracks the number of replacements by sender since the last seen block.
+    replacements: FxHashMap<SenderId, usize>,
     /// How to handle [`TransactionOrigin::Local`](crate::TransactionOrigin) transactions.
     local_transactions_config: LocalTransactionConfig,
     /// All accounts with a pooled authorization
@@ -1353,6 +1365,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
         Self {
             max_account_slots: config.max_account_slots,
             price_bumps: config.price_bumps,
+            max_replacements_per_sender: config.max_replacements_per_sender,
             local_transactions_config: config.local_transactions_config.clone(),
             minimal_protocol_basefee: config.minimal_protocol_basefee,
             block_gas_limit: config.gas_limit,
@@ -1414,6 +1427,10 @@ impl<T: PoolTransaction> AllTransactions<T> {
             pending_basefee,
             pending_blob_fee,
         } = block_info;
+        if self.last_seen_block_hash != last_seen_block_hash {
+            // the replacement limit applies per block
+            self.replacements.clear();
+        }
         self.last_seen_block_number = last_seen_block_number;
         self.last_seen_block_hash = last_seen_block_hash;
 
@@ -1995,12 +2012,32 @@ impl<T: PoolTransaction> AllTransactions<T> {
                 let maybe_replacement = transaction.as_ref();
 
                 // Ensure the new transaction is not underpriced
-                if existing_transaction.is_underpriced(maybe_replacement, &self.price_bumps) {
+                if let Err(reason) = existing_transaction
+                    .ensure_replacement_fees(maybe_replacement, &self.price_bumps)
+                {
                     return Err(InsertErr::Underpriced {
                         transaction: pool_tx.transaction,
                         existing: *entry.get().transaction.hash(),
+                        reason,
                     })
                 }
+
+                // Ensure the sender didn't exceed the replacement limit
+                if let Some(limit) = self.max_replacements_per_sender &&
+                    !self
+                        .local_transactions_config
+                        .is_local(maybe_replacement.origin, maybe_replacement.sender_ref())
+                {
+                    let replacements =
+                        self.replacements.entry(maybe_replacement.sender_id()).or_default();
+                    if *replacements >= limit {
+                        return Err(InsertErr::ReplacementLimitExceeded {
+                            transaction: pool_tx.transaction,
+                            limit,
+                        })
+                    }
+                    *replacements += 1;
+                }
                 let new_hash = *pool_tx.transaction.hash();
                 let new_transaction = pool_tx.transaction.clone();
                 let replaced = entry.insert(pool_tx);
@@ -2140,6 +2177,8 @@ impl<T: PoolTransaction> Default for AllTransactions<T> {
             last_seen_block_hash: Default::default(),
             pending_fees: Default::default(),
             price_bumps: Default::default(),
+            max_replacements_per_sender: None,
+            replacements: Default::default(),
             local_transactions_config: Default::default(),
             auths: Default::default(),
             metrics: Default::default(),
@@ -2173,7 +2212,12 @@ pub(crate) enum InsertErr<T: PoolTransaction> {
         transaction: Arc<ValidPoolTransaction<T>>,
         #[expect(dead_code)]
         existing: TxHash,
+        /// The fee that isn't bumped enough.
+        reason: ReplacementUnderpricedError,
     },
+    /// Attempted to replace existing transaction, but the sender exceeded the configured limit of
+    /// replacements per block.
+    ReplacementLimitExceeded { transaction: Arc<ValidPoolTransaction<T>>, limit: usize },
     /// Attempted to insert a blob transaction with a nonce gap
     BlobTxHasNonceGap { transaction: Arc<ValidPoolTransaction<T>> },
     /// Attempted to insert a transaction that would overdraft the sender's balance at the time of
@@ -3041,6 +3085,97 @@ mod tests {
         assert_eq!(pool.len(), 1);
     }
 
+    #[test]
+    fn insert_replace_blob_underpriced_priority_fee() {
+        let on_chain_balance = U256::MAX;
+        let on_chain_nonce = 0;
+        let mut f = MockTransactionFactory::default();
+        let mut pool = AllTransactions::default();
+        let mut tx = MockTransaction::eip4844().inc_limit();
+        tx.set_priority_fee(100);
+        tx.set_max_fee(100);
+        tx.set_blob_fee(100);
+        let first = f.validated(tx.clone());
+        pool.insert_tx(first.clone(), on_chain_balance, on_chain_nonce).unwrap();
+
+        // blob transactions must bump the priority fee even if the replacement sets it to zero
+        let mut replacement = f.validated(tx.rng_hash());
+        replacement.transaction.set_priority_fee(0);
+        replacement.transaction.set_max_fee(200);
+        replacement.transaction.set_blob_fee(200);
+        let err =
+            pool.insert_tx(replacement.clone(), on_chain_balance, on_chain_nonce).unwrap_err();
+        assert!(matches!(
+            err,
+            InsertErr::Underpriced {
+                reason: ReplacementUnderpricedError::MaxPriorityFeePerGas { got: 0, required: 200 },
+                ..
+            }
+        ));
+
+        replacement.transaction.set_priority_fee(200);
+        replacement.transaction.set_blob_fee(199);
+        let err =
+            pool.insert_tx(replacement.clone(), on_chain_balance, on_chain_nonce).unwrap_err();
+        assert!(matches!(
+            err,
+            InsertErr::Underpriced {
+                reason: ReplacementUnderpricedError::MaxFeePerBlobGas { got: 199, required: 200 },
+                ..
+            }
+        ));
+        assert!(pool.contains(first.hash()));
+
+        replacement.transaction.set_blob_fee(200);
+        pool.insert_tx(replacement.clone(), on_chain_balance, on_chain_nonce).unwrap();
+        assert!(pool.contains(replacement.hash()));
+        assert_eq!(pool.len(), 1);
+    }
+
+    #[test]
+    fn insert_replace_limit_per_sender() {
+        let on_chain_balance = U256::MAX;
+        let on_chain_nonce = 0;
+        let mut f = MockTransactionFactory::default();
+        let mut pool = AllTransactions::new(&PoolConfig {
+            max_replacements_per_sender: Some(1),
+            ..Default::default()
+        });
+        let mut tx = MockTransaction::eip1559().inc_limit();
+        tx.set_priority_fee(100);
+        tx.set_max_fee(100);
+        pool.insert_tx(f.validated(tx.clone()), on_chain_balance, on_chain_nonce).unwrap();
+
+        let mut replacement = f.validated(tx.rng_hash());
+        replacement.transaction.set_priority_fee(200);
+        replacement.transaction.set_max_fee(200);
+        pool.insert_tx(replacement, on_chain_balance, on_chain_nonce).unwrap();
+
+        let mut replacement = f.validated(tx.rng_hash());
+        replacement.transaction.set_priority_fee(400);
+        replacement.transaction.set_max_fee(400);
+        let err =
+            pool.insert_tx(replacement.clone(), on_chain_balance, on_chain_nonce).unwrap_err();
+        assert!(matches!(err, InsertErr::ReplacementLimitExceeded { limit: 1, .. }));
+
+        // local transactions are exempt from the limit
+        let mut local = f.validated_with_origin(TransactionOrigin::Local, tx.rng_hash());
+        local.transaction.set_priority_fee(400);
+        local.transaction.set_max_fee(400);
+        pool.insert_tx(local, on_chain_balance, on_chain_nonce).unwrap();
+
+        // the limit is reset with the next block
+        pool.set_block_info(BlockInfo {
+            last_seen_block_hash: B256::with_last_byte(1),
+            ..Default::default()
+        });
+        replacement.transaction.set_priority_fee(800);
+        replacement.transaction.set_max_fee(800);
+        pool.insert_tx(replacement.clone(), on_chain_balance, on_chain_nonce).unwrap();
+        assert!(pool.contains(replacement.hash()));
+        assert_eq!(pool.len(), 1);
+    }
+
     #[test]
     fn insert_conflicting_type_normal_to_blob() {
         let on_chain_balance = U256::from(10_000);
@@ -142,7 +142,7 @@ impl<R: Rng> MockTransactionSimulator<R> {
                         Err(e) => match e.kind {
                             // skip pool capacity/replacement errors (not relevant)
                             PoolErrorKind::SpammerExceededCapacity(_) |
-                            PoolErrorKind::ReplacementUnderpriced => return,
+                            PoolErrorKind::ReplacementUnderpriced(_) => return,
                             _ => panic!("unexpected error: {e:?}"),
                         },
                     };
@@ -185,7 +185,7 @@ impl<R: Rng> MockTransactionSimulator<R> {
                         Err(e) => match e.kind {
                             // skip pool capacity/replacement errors (not relevant)
                             PoolErrorKind::SpammerExceededCapacity(_) |
-                            PoolErrorKind::ReplacementUnderpriced => return,
+                            PoolErrorKind::ReplacementUnderpriced(_) => return,
                             _ => panic!("unexpected error: {e:?}"),
                         },
                     };
@@ -233,7 +233,7 @@ impl<R: Rng> MockTransactionSimulator<R> {
                         Err(e) => match e.kind {
                             // skip pool capacity/replacement errors (not relevant)
                             PoolErrorKind::SpammerExceededCapacity(_) |
-                            PoolErrorKind::ReplacementUnderpriced => return,
+                            PoolErrorKind::ReplacementUnderpriced(_) => return,
                             _ => panic!("unexpected error: {e:?}"),
                         },
                     };
@@ -287,7 +287,7 @@ impl<R: Rng> MockTransactionSimulator<R> {
                         Err(e) => match e.kind {
                             // skip pool capacity/replacement errors (not relevant)
                             PoolErrorKind::SpammerExceededCapacity(_) |
-                            PoolErrorKind::ReplacementUnderpriced => return,
+                            PoolErrorKind::ReplacementUnderpriced(_) => return,
                             _ => panic!("unexpected error: {e:?}"),
                         },
                     };
@@ -0,0 +1,343 @@
+//! Composable admission rules that are checked before a transaction is validated.
+
+use crate::{
+    error::{InvalidPoolTransactionError, PoolTransactionError},
+    traits::{PoolTransaction, TransactionOrigin},
+    validate::{TransactionValidationOutcome, TransactionValidator},
+};
+use alloy_primitives::Address;
+use reth_primitives_traits::SealedBlock;
+use std::{any::Any, collections::HashSet, fmt, sync::Arc};
+
+/// A rule that decides whether a transaction is admitted to the pool.
+///
+/// Admission rules are stateless checks of the transaction itself, e.g. address blocklists or
+/// policies of a bundler, that are applied on top of a [`TransactionValidator`] with a
+/// [`ValidatorChain`].
+///
+/// This is implemented for closures with the same signature as [`Self::check`].
+pub trait TransactionAdmissionRule<T: PoolTransaction>: Send + Sync {
+    /// Returns an error if the transaction must not be admitted to the pool.
+    fn check(
+        &self,
+        origin: TransactionOrigin,
+        transaction: &T,
+    ) -> Result<(), InvalidPoolTransactionError>;
+}
+
+impl<T, F> TransactionAdmissionRule<T> for F
+where
+    T: PoolTransaction,
+    F: Fn(TransactionOrigin, &T) -> Result<(), InvalidPoolTransactionError> + Send + Sync,
+{
+    fn check(
+        &self,
+        origin: TransactionOrigin,
+        transaction: &T,
+    ) -> Result<(), InvalidPoolTransactionError> {
+        self(origin, transaction)
+    }
+}
+
+/// Errors of the admission rules provided by this crate.
+#[derive(Debug, thiserror::Error)]
+pub enum AdmissionRuleError {
+    /// The sender or the recipient of the transaction is blocked.
+    #[error("address {0} is blocked")]
+    BlockedAddress(Address),
+    /// The calldata of the transaction exceeds the configured limit.
+    #[error("calldata size {size} exceeds the limit {limit}")]
+    CalldataTooLarge {
+        /// Size of the calldata of the transaction.
+        size: usize,
+        /// Configured limit that was exceeded.
+        limit: usize,
+    },
+}
+
+impl PoolTransactionError for AdmissionRuleError {
+    fn is_bad_transaction(&self) -> bool {
+        // these are local policies, the transaction is valid as far as the network is concerned
+        false
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// Rejects transactions sent from or to a blocked address.
+#[derive(Debug, Clone, Default)]
+pub struct AddressBlocklist {
+    /// The blocked addresses.
+    addresses: HashSet<Address>,
+}
+
+impl AddressBlocklist {
+    /// Creates a new blocklist of the given addresses.
+    pub fn new(addresses: impl IntoIterator<Item = Address>) -> Self {
+        Self { addresses: addresses.into_iter().collect() }
+    }
+
+    /// Returns `true` if the address is blocked.
+    pub fn contains(&self, address: &Address) -> bool {
+        self.addresses.contains(address)
+    }
+}
+
+impl<T: PoolTransaction> TransactionAdmissionRule<T> for AddressBlocklist {
+    fn check(
+        &self,
+        _origin: TransactionOrigin,
+        transaction: &T,
+    ) -> Result<(), InvalidPoolTransactionError> {
+        let blocked = std::iter::once(*transaction.sender_ref())
+            .chain(transaction.to())
+            .find(|address| self.contains(address));
+        match blocked {
+            Some(address) => {
+                Err(InvalidPoolTransactionError::other(AdmissionRuleError::BlockedAddress(address)))
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+/// Rejects transactions with calldata larger than the configured limit.
+///
+/// This is meant as a tighter policy than the
+/// [`max_tx_input_bytes`](crate::validate::EthTransactionValidator::max_tx_input_bytes) DoS
+/// protection, which applies to the entire encoded transaction.
+#[derive(Debug, Clone, Copy)]
+pub struct MaxCalldataSize {
+    /// The maximum size of the calldata in bytes.
+    limit: usize,
+    /// Whether the limit also applies to local transactions.
+    include_local: bool,
+}
+
+impl MaxCalldataSize {
+    /// Creates a new rule with the given limit, which doesn't apply to local transactions.
+    pub const fn new(limit: usize) -> Self {
+        Self { limit, include_local: false }
+    }
+
+    /// Configures whether the limit also applies to local transactions.
+    pub const fn with_local_transactions(mut self, include_local: bool) -> Self {
+        self.include_local = include_local;
+        self
+    }
+}
+
+impl<T: PoolTransaction> TransactionAdmissionRule<T> for MaxCalldataSize {
+    fn check(
+        &self,
+        origin: TransactionOrigin,
+        transaction: &T,
+    ) -> Result<(), InvalidPoolTransactionError> {
+        if origin.is_local() && !self.include_local {
+            return Ok(())
+        }
+
+        let size = transaction.input().len();
+        if size > self.limit {
+            return Err(InvalidPoolTransactionError::other(AdmissionRuleError::CalldataTooLarge {
+                size,
+                limit: self.limit,
+            }))
+        }
+        Ok(())
+    }
+}
+
+/// A [`TransactionValidator`] that checks a chain of [`TransactionAdmissionRule`]s before it
+/// delegates to the wrapped validator.
+///
+/// The rules are checked in the order they were added, the first rule that rejects a transaction
+/// determines the [`TransactionValidationOutcome::Invalid`] outcome, and only the admitted
+/// transactions are validated by the wrapped validator.
+///
+/// This can be used to extend a validator like
+/// [`EthTransactionValidator`](crate::validate::EthTransactionValidator) with custom policies
+/// without reimplementing it, e.g. via [`TransactionValidationTaskExecutor::map`]:
+///
+/// ```ignore
+/// let validator = TransactionValidationTaskExecutor::eth_builder(client, evm_config)
+///     .build_with_tasks(tasks, blob_store.clone())
+///     .map(|validator| {
+///         ValidatorChain::new(validator).with_rule(AddressBlocklist::new([blocked]))
+///     });
+/// ```
+///
+/// [`TransactionValidationTaskExecutor::map`]: crate::validate::TransactionValidationTaskExecutor::map
+pub struct ValidatorChain<V: TransactionValidator> {
+    /// The validator that validates the admitted transactions.
+    inner: V,
+    /// The rules that are checked before the inner validator.
+    rules: Vec<Arc<dyn TransactionAdmissionRule<V::Transaction>>>,
+}
+
+impl<V: TransactionValidator> ValidatorChain<V> {
+    /// Creates a new chain without any rules around the given validator.
+    pub const fn new(inner: V) -> Self {
+        Self { inner, rules: Vec::new() }
+    }
+
+    /// Appends a rule to the chain.
+    pub fn with_rule<R>(mut self, rule: R) -> Self
+    where
+        R: TransactionAdmissionRule<V::Transaction> + 'static,
+    {
+        self.rules.push(Arc::new(rule));
+        self
+    }
+
+    /// Appends the given rules to the chain.
+    pub fn with_rules(
+        mut self,
+        rules: impl IntoIterator<Item = Arc<dyn TransactionAdmissionRule<V::Transaction>>>,
+    ) -> Self {
+        self.rules.extend(rules);
+        self
+    }
+
+    /// Returns the wrapped validator.
+    pub const fn inner(&self) -> &V {
+        &self.inner
+    }
+
+    /// Returns the number of rules in the chain.
+    pub const fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    /// Returns `true` if the chain has no rules.
+    pub const fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Checks all rules against the transaction.
+    fn check(
+        &self,
+        origin: TransactionOrigin,
+        transaction: &V::Transaction,
+    ) -> Result<(), InvalidPoolTransactionError> {
+        self.rules.iter().try_for_each(|rule| rule.check(origin, transaction))
+    }
+
+    /// Checks the rules against all transactions and validates the admitted transactions with the
+    /// inner validator, the outcomes are returned in the order of the given transactions.
+    async fn validate_all(
+        &self,
+        transactions: Vec<(TransactionOrigin, V::Transaction)>,
+    ) -> Vec<TransactionValidationOutcome<V::Transaction>> {
+        let mut outcomes = Vec::with_capacity(transactions.len());
+        let mut admitted = Vec::with_capacity(transactions.len());
+        for (origin, transaction) in transactions {
+            match self.check(origin, &transaction) {
+                Ok(()) => {
+                    outcomes.push(None);
+                    admitted.push((origin, transaction));
+                }
+                Err(err) => {
+                    outcomes.push(Some(TransactionValidationOutcome::Invalid(transaction, err)))
+                }
+            }
+        }
+
+        let mut validated = self.inner.validate_transactions(admitted).await.into_iter();
+        outcomes
+            .into_iter()
+            .map(|outcome| {
+                outcome.unwrap_or_else(|| {
+                    validated.next().expect("validator returns an outcome per transaction")
+                })
+            })
+            .collect()
+    }
+}
+
+impl<V: TransactionValidator> fmt::Debug for ValidatorChain<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ValidatorChain")
+            .field("inner", &self.inner)
+            .field("rules", &self.rules.len())
+            .finish()
+    }
+}
+
+impl<V: TransactionValidator> TransactionValidator for ValidatorChain<V> {
+    type Transaction = V::Transaction;
+    type Block = V::Block;
+
+    async fn validate_transaction(
+        &self,
+        origin: TransactionOrigin,
+        transaction: Self::Transaction,
+    ) -> TransactionValidationOutcome<Self::Transaction> {
+        if let Err(err) = self.check(origin, &transaction) {
+            return TransactionValidationOutcome::Invalid(transaction, err)
+        }
+        self.inner.validate_transaction(origin, transaction).await
+    }
+
+    async fn validate_transactions(
+        &self,
+        transactions: Vec<(TransactionOrigin, Self::Transaction)>,
+    ) -> Vec<TransactionValidationOutcome<Self::Transaction>> {
+        self.validate_all(transactions).await
+    }
+
+    async fn validate_transactions_with_origin(
+        &self,
+        origin: TransactionOrigin,
+        transactions: impl IntoIterator<Item = Self::Transaction> + Send,
+    ) -> Vec<TransactionValidationOutcome<Self::Transaction>> {
+        self.validate_all(transactions.into_iter().map(|tx| (origin, tx)).collect()).await
+    }
+
+    fn on_new_head_block(&self, new_tip_block: &SealedBlock<Self::Block>) {
+        self.inner.on_new_head_block(new_tip_block)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{MockTransaction, OkValidator};
+    use alloy_primitives::{Bytes, TxKind};
+
+    #[tokio::test]
+    async fn chain_rejects_before_inner_validator() {
+        let blocked = Address::random();
+        let chain = ValidatorChain::new(OkValidator::<MockTransaction>::default())
+            .with_rule(AddressBlocklist::new([blocked]))
+            .with_rule(MaxCalldataSize::new(4));
+
+        let ok = MockTransaction::eip1559().with_input(Bytes::from_static(&[1; 4]));
+        let from_blocked = MockTransaction::eip1559().with_sender(blocked);
+        let mut to_blocked = MockTransaction::eip1559();
+        if let MockTransaction::Eip1559 { to, .. } = &mut to_blocked {
+            *to = TxKind::Call(blocked);
+        }
+        let large = MockTransaction::eip1559().with_input(Bytes::from_static(&[1; 5]));
+
+        let outcomes = chain
+            .validate_transactions_with_origin(
+                TransactionOrigin::External,
+                [ok.clone(), from_blocked, to_blocked, large.clone()],
+            )
+            .await;
+        assert!(outcomes[0].is_valid());
+        for outcome in &outcomes[1..] {
+            let TransactionValidationOutcome::Invalid(_, err) = outcome else {
+                panic!("expected invalid outcome, got {outcome:?}")
+            };
+            assert!(err.is_other::<AdmissionRuleError>());
+        }
+
+        // the calldata limit doesn't apply to local transactions
+        assert!(chain.validate_transaction(TransactionOrigin::Local, large).await.is_valid());
+        assert!(chain.validate_transaction(TransactionOrigin::External, ok).await.is_valid());
+    }
+}
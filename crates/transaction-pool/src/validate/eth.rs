@@ -1,10 +1,17 @@
 //! Ethereum transaction validator.
 
-use super::constants::DEFAULT_MAX_TX_INPUT_BYTES;
+use super::{
+    constants::DEFAULT_MAX_TX_INPUT_BYTES,
+    requests::{
+        system_request_fee, system_request_input_len, EXCESS_INHIBITOR,
+        EXCESS_REQUESTS_STORAGE_SLOT,
+    },
+};
 use crate::{
     blobstore::BlobStore,
     error::{
         Eip4844PoolTransactionError, Eip7702PoolTransactionError, InvalidPoolTransactionError,
+        SystemRequestPoolTransactionError,
     },
     metrics::TxPoolValidationMetrics,
     traits::TransactionOrigin,
@@ -31,7 +38,9 @@ use reth_primitives_traits::{
     transaction::error::InvalidTransactionError, Account, BlockTy, GotExpected, HeaderTy,
     SealedBlock,
 };
-use reth_storage_api::{AccountInfoReader, BlockReaderIdExt, BytecodeReader, StateProviderFactory};
+use reth_storage_api::{
+    AccountInfoReader, BlockReaderIdExt, BytecodeReader, StateProvider, StateProviderFactory,
+};
 use reth_tasks::TaskSpawner;
 use revm::context_interface::Cfg;
 use revm_primitives::U256;
@@ -91,6 +100,8 @@ pub struct EthTransactionValidator<Client, T, Evm> {
     max_tx_gas_limit: Option<u64>,
     /// Disable balance checks during transaction validation
     disable_balance_check: bool,
+    /// Whether to reject EIP-7002 and EIP-7251 requests that don't cover the request fee
+    validate_request_fees: bool,
     /// EVM configuration for fetching execution limits
     evm_config: Evm,
     /// Marker for the transaction type
@@ -178,6 +189,11 @@ impl<Client, Tx, Evm> EthTransactionValidator<Client, Tx, Evm> {
     pub const fn disable_balance_check(&self) -> bool {
         self.disable_balance_check
     }
+
+    /// Returns whether EIP-7002 and EIP-7251 requests are checked for the request fee
+    pub const fn validate_request_fees(&self) -> bool {
+        self.validate_request_fees
+    }
 }
 
 impl<Client, Tx, Evm> EthTransactionValidator<Client, Tx, Evm>
@@ -563,6 +579,13 @@ where
             return TransactionValidationOutcome::Invalid(transaction, err)
         }
 
+        // checks that withdrawal and consolidation requests won't revert
+        match self.validate_system_request(&transaction) {
+            Err(outcome) => return outcome,
+            Ok(Err(err)) => return TransactionValidationOutcome::Invalid(transaction, err),
+            _ => {}
+        };
+
         // heavy blob tx validation
         let maybe_blob_sidecar = match self.validate_eip4844(&mut transaction) {
             Err(err) => return TransactionValidationOutcome::Invalid(transaction, err),
@@ -659,6 +682,57 @@ where
         Ok(())
     }
 
+    /// Validates transactions that submit EIP-7002 withdrawal or EIP-7251 consolidation requests.
+    ///
+    /// The system contracts revert if the input is not a well-formed request or if the value
+    /// doesn't cover the current request fee, which grows with the number of excess requests.
+    /// Such transactions are only rejected if request fee validation is enabled.
+    ///
+    /// The fee is read from the latest state, so a request can still revert if the fee increases
+    /// before the transaction is included.
+    pub fn validate_system_request(
+        &self,
+        transaction: &Tx,
+    ) -> Result<Result<(), InvalidPoolTransactionError>, TransactionValidationOutcome<Tx>> {
+        if !self.validate_request_fees || !self.fork_tracker.is_prague_activated() {
+            return Ok(Ok(()))
+        }
+        let Some(to) = transaction.to() else { return Ok(Ok(())) };
+        let Some(expected) = system_request_input_len(to) else { return Ok(Ok(())) };
+
+        // calls without input only query the current fee
+        let len = transaction.input().len();
+        if len == 0 {
+            return Ok(Ok(()))
+        }
+        if len != expected {
+            let err = SystemRequestPoolTransactionError::InvalidInput { len, expected };
+            return Ok(Err(err.into()))
+        }
+
+        let excess = match self
+            .client
+            .latest()
+            .and_then(|state| state.storage(to, EXCESS_REQUESTS_STORAGE_SLOT))
+        {
+            Ok(excess) => excess.unwrap_or_default(),
+            Err(err) => {
+                return Err(TransactionValidationOutcome::Error(*transaction.hash(), Box::new(err)))
+            }
+        };
+        if excess == EXCESS_INHIBITOR {
+            return Ok(Err(SystemRequestPoolTransactionError::Inactive.into()))
+        }
+
+        let fee = system_request_fee(excess);
+        let value = transaction.value();
+        if value < fee {
+            let err = SystemRequestPoolTransactionError::InsufficientFee { fee, value };
+            return Ok(Err(err.into()))
+        }
+        Ok(Ok(()))
+    }
+
     /// Validates EIP-4844 blob sidecar data and returns the extracted sidecar, if any.
     pub fn validate_eip4844(
         &self,
@@ -903,6 +977,8 @@ pub struct EthTransactionValidatorBuilder<Client, Evm> {
     max_tx_gas_limit: Option<u64>,
     /// Disable balance checks during transaction validation
     disable_balance_check: bool,
+    /// Whether to reject EIP-7002 and EIP-7251 requests that don't cover the request fee
+    validate_request_fees: bool,
     /// Bitmap of custom transaction types that are allowed.
     other_tx_types: U256,
     /// Cached max initcode size from EVM config
@@ -967,6 +1043,9 @@ impl<Client, Evm> EthTransactionValidatorBuilder<Client, Evm> {
             // balance checks are enabled by default
             disable_balance_check: false,
 
+            // request fees are not validated by default
+            validate_request_fees: false,
+
             // no custom transaction types by default
             other_tx_types: U256::ZERO,
 
@@ -1113,6 +1192,14 @@ impl<Client, Evm> EthTransactionValidatorBuilder<Client, Evm> {
         self
     }
 
+    /// Sets whether transactions submitting EIP-7002 withdrawal or EIP-7251 consolidation requests
+    /// are rejected if they would revert, because of malformed input or a value below the current
+    /// request fee.
+    pub const fn with_request_fee_validation(mut self, validate_request_fees: bool) -> Self {
+        self.validate_request_fees = validate_request_fees;
+        self
+    }
+
     /// Adds a custom transaction type to the validator.
     pub const fn with_custom_tx_type(mut self, tx_type: u8) -> Self {
         self.other_tx_types.set_bit(tx_type as usize, true);
@@ -1144,6 +1231,7 @@ impl<Client, Evm> EthTransactionValidatorBuilder<Client, Evm> {
             max_tx_input_bytes,
             max_tx_gas_limit,
             disable_balance_check,
+            validate_request_fees,
             max_blob_count,
             additional_tasks: _,
             other_tx_types,
@@ -1178,6 +1266,7 @@ impl<Client, Evm> EthTransactionValidatorBuilder<Client, Evm> {
             max_tx_input_bytes,
             max_tx_gas_limit,
             disable_balance_check,
+            validate_request_fees,
             evm_config,
             _marker: Default::default(),
             validation_metrics: TxPoolValidationMetrics::default(),
@@ -1321,12 +1410,12 @@ pub fn ensure_intrinsic_gas<T: EthPoolTransaction>(
 mod tests {
     use super::*;
     use crate::{
-        blobstore::InMemoryBlobStore, error::PoolErrorKind, traits::PoolTransaction,
-        CoinbaseTipOrdering, EthPooledTransaction, Pool, TransactionPool,
+        blobstore::InMemoryBlobStore, error::PoolErrorKind, test_utils::MockTransaction,
+        traits::PoolTransaction, CoinbaseTipOrdering, EthPooledTransaction, Pool, TransactionPool,
     };
     use alloy_consensus::Transaction;
-    use alloy_eips::eip2718::Decodable2718;
-    use alloy_primitives::{hex, U256};
+    use alloy_eips::{eip2718::Decodable2718, eip7002::WITHDRAWAL_REQUEST_PREDEPLOY_ADDRESS};
+    use alloy_primitives::{hex, Bytes, U256};
     use reth_ethereum_primitives::PooledTransactionVariant;
     use reth_evm_ethereum::EthEvmConfig;
     use reth_primitives_traits::SignedTransaction;
@@ -1785,4 +1874,49 @@ mod tests {
         let outcome = validator.validate_one(TransactionOrigin::External, transaction);
         assert!(outcome.is_valid()); // Should be valid because balance check is disabled
     }
+
+    #[tokio::test]
+    async fn invalid_on_insufficient_request_fee() {
+        let provider = MockEthProvider::default().with_genesis_block();
+        // 100 excess requests put the request fee at 357 wei
+        provider.add_account(
+            WITHDRAWAL_REQUEST_PREDEPLOY_ADDRESS,
+            ExtendedAccount::new(0, U256::ZERO)
+                .extend_storage([(EXCESS_REQUESTS_STORAGE_SLOT, U256::from(100))]),
+        );
+        let validator = EthTransactionValidatorBuilder::new(provider, test_evm_config())
+            .set_prague(true)
+            .with_request_fee_validation(true)
+            .build::<MockTransaction, _>(InMemoryBlobStore::default());
+
+        let mut transaction = MockTransaction::eip1559().with_input(Bytes::from(vec![0; 56]));
+        if let MockTransaction::Eip1559 { to, .. } = &mut transaction {
+            *to = WITHDRAWAL_REQUEST_PREDEPLOY_ADDRESS.into();
+        }
+
+        let underpaid = transaction.clone().with_value(U256::from(356));
+        let err = validator.validate_system_request(&underpaid).unwrap().unwrap_err();
+        assert!(matches!(
+            err,
+            InvalidPoolTransactionError::SystemRequest(
+                SystemRequestPoolTransactionError::InsufficientFee { fee, .. }
+            ) if fee == U256::from(357)
+        ));
+
+        let paid = transaction.clone().with_value(U256::from(357));
+        assert!(validator.validate_system_request(&paid).unwrap().is_ok());
+
+        let malformed = paid.with_input(Bytes::from(vec![0; 55]));
+        let err = validator.validate_system_request(&malformed).unwrap().unwrap_err();
+        assert!(matches!(
+            err,
+            InvalidPoolTransactionError::SystemRequest(
+                SystemRequestPoolTransactionError::InvalidInput { len: 55, expected: 56 }
+            )
+        ));
+
+        // fee queries are always accepted
+        let query = transaction.with_input(Bytes::new());
+        assert!(validator.validate_system_request(&query).unwrap().is_ok());
+    }
 }
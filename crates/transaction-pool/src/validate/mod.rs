@@ -1,7 +1,7 @@
 //! Transaction validation abstractions.
 
 use crate::{
-    error::InvalidPoolTransactionError,
+    error::{InvalidPoolTransactionError, ReplacementUnderpricedError},
     identifier::{SenderId, TransactionId},
     traits::{PoolTransaction, TransactionOrigin},
     PriceBumpConfig,
@@ -12,11 +12,15 @@ use futures_util::future::Either;
 use reth_primitives_traits::{Block, Recovered, SealedBlock};
 use std::{fmt, fmt::Debug, future::Future, time::Instant};
 
+mod admission;
 mod constants;
 mod eth;
+mod requests;
 mod task;
 
+pub use admission::*;
 pub use eth::*;
+pub use requests::system_request_fee;
 
 pub use task::{TransactionValidationTaskExecutor, ValidationTask};
 
@@ -450,19 +454,40 @@ impl<T: PoolTransaction> ValidPoolTransaction<T> {
     /// Determines whether a candidate transaction (`maybe_replacement`) is underpriced compared to
     /// an existing transaction in the pool.
     ///
+    /// See [`Self::ensure_replacement_fees`] for the rules.
+    #[inline]
+    pub fn is_underpriced(&self, maybe_replacement: &Self, price_bumps: &PriceBumpConfig) -> bool {
+        self.ensure_replacement_fees(maybe_replacement, price_bumps).is_err()
+    }
+
+    /// Ensures that a candidate transaction (`maybe_replacement`) bumps the fees of this existing
+    /// transaction enough to replace it, returning the first fee that is underpriced otherwise.
+    ///
     /// A transaction is considered underpriced if it doesn't meet the required fee bump threshold.
     /// This applies to both standard gas fees and, for blob-carrying transactions (EIP-4844),
     /// the blob-specific fees.
-    #[inline]
-    pub fn is_underpriced(&self, maybe_replacement: &Self, price_bumps: &PriceBumpConfig) -> bool {
+    ///
+    /// An EIP-4844 transaction must bump all of its fees, including the max priority fee per gas,
+    /// by the blob price bump. The max priority fee per gas of other transactions is only checked
+    /// if both transactions have a non-zero priority fee.
+    pub fn ensure_replacement_fees(
+        &self,
+        maybe_replacement: &Self,
+        price_bumps: &PriceBumpConfig,
+    ) -> Result<(), ReplacementUnderpricedError> {
         // Retrieve the required price bump percentage for this type of transaction.
         //
         // The bump is different for EIP-4844 and other transactions. See `PriceBumpConfig`.
         let price_bump = price_bumps.price_bump(self.tx_type());
+        let bumped = |fee: u128| fee * (100 + price_bump) / 100;
 
         // Check if the max fee per gas is underpriced.
-        if maybe_replacement.max_fee_per_gas() < self.max_fee_per_gas() * (100 + price_bump) / 100 {
-            return true
+        let required = bumped(self.max_fee_per_gas());
+        if maybe_replacement.max_fee_per_gas() < required {
+            return Err(ReplacementUnderpricedError::MaxFeePerGas {
+                got: maybe_replacement.max_fee_per_gas(),
+                required,
+            })
         }
 
         let existing_max_priority_fee_per_gas =
@@ -470,13 +495,17 @@ impl<T: PoolTransaction> ValidPoolTransaction<T> {
         let replacement_max_priority_fee_per_gas =
             maybe_replacement.transaction.max_priority_fee_per_gas().unwrap_or_default();
 
-        // Check max priority fee per gas (relevant for EIP-1559 transactions only)
-        if existing_max_priority_fee_per_gas != 0 &&
-            replacement_max_priority_fee_per_gas != 0 &&
-            replacement_max_priority_fee_per_gas <
-                existing_max_priority_fee_per_gas * (100 + price_bump) / 100
-        {
-            return true
+        // Check max priority fee per gas (relevant for EIP-1559 transactions only), blob
+        // transactions must always bump it
+        let required = bumped(existing_max_priority_fee_per_gas);
+        let check_priority_fee = self.is_eip4844() ||
+            (existing_max_priority_fee_per_gas != 0 &&
+                replacement_max_priority_fee_per_gas != 0);
+        if check_priority_fee && replacement_max_priority_fee_per_gas < required {
+            return Err(ReplacementUnderpricedError::MaxPriorityFeePerGas {
+                got: replacement_max_priority_fee_per_gas,
+                required,
+            })
         }
 
         // Check max blob fee per gas
@@ -484,14 +513,16 @@ impl<T: PoolTransaction> ValidPoolTransaction<T> {
             // This enforces that blob txs can only be replaced by blob txs
             let replacement_max_blob_fee_per_gas =
                 maybe_replacement.transaction.max_fee_per_blob_gas().unwrap_or_default();
-            if replacement_max_blob_fee_per_gas <
-                existing_max_blob_fee_per_gas * (100 + price_bump) / 100
-            {
-                return true
+            let required = bumped(existing_max_blob_fee_per_gas);
+            if replacement_max_blob_fee_per_gas < required {
+                return Err(ReplacementUnderpricedError::MaxFeePerBlobGas {
+                    got: replacement_max_blob_fee_per_gas,
+                    required,
+                })
             }
         }
 
-        false
+        Ok(())
     }
 }
 
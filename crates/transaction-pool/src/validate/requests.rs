@@ -0,0 +1,74 @@
+//! Helpers for validating EIP-7002 withdrawal and EIP-7251 consolidation requests.
+
+use alloy_eips::{
+    eip7002::WITHDRAWAL_REQUEST_PREDEPLOY_ADDRESS, eip7251::CONSOLIDATION_REQUEST_PREDEPLOY_ADDRESS,
+};
+use alloy_primitives::{Address, B256, U256};
+
+/// Storage slot of the excess requests counter in both request system contracts.
+pub(crate) const EXCESS_REQUESTS_STORAGE_SLOT: B256 = B256::ZERO;
+
+/// Value of the excess requests counter before the system contract was first called by the
+/// protocol, in which case all requests revert.
+pub(crate) const EXCESS_INHIBITOR: U256 = U256::MAX;
+
+/// Input length of a withdrawal request: 48 byte validator pubkey and 8 byte amount.
+const WITHDRAWAL_REQUEST_INPUT_LEN: usize = 56;
+
+/// Input length of a consolidation request: 48 byte source and 48 byte target pubkey.
+const CONSOLIDATION_REQUEST_INPUT_LEN: usize = 96;
+
+/// Minimum fee of a request in wei.
+const MIN_REQUEST_FEE: U256 = U256::from_limbs([1, 0, 0, 0]);
+
+/// Controls how fast the request fee grows with the number of excess requests.
+const REQUEST_FEE_UPDATE_FRACTION: U256 = U256::from_limbs([17, 0, 0, 0]);
+
+/// Returns the expected input length of a request if `to` is one of the request system contracts.
+pub(crate) fn system_request_input_len(to: Address) -> Option<usize> {
+    if to == WITHDRAWAL_REQUEST_PREDEPLOY_ADDRESS {
+        Some(WITHDRAWAL_REQUEST_INPUT_LEN)
+    } else if to == CONSOLIDATION_REQUEST_PREDEPLOY_ADDRESS {
+        Some(CONSOLIDATION_REQUEST_INPUT_LEN)
+    } else {
+        None
+    }
+}
+
+/// Returns the fee in wei the request system contracts charge for a request, given the number of
+/// excess requests.
+///
+/// This is the `fake_exponential` approximation used by both EIP-7002 and EIP-7251 and saturates
+/// at [`U256::MAX`].
+pub fn system_request_fee(excess: U256) -> U256 {
+    let mut i = U256::from(1);
+    let mut output = U256::ZERO;
+    let mut numerator_accum = MIN_REQUEST_FEE * REQUEST_FEE_UPDATE_FRACTION;
+    while !numerator_accum.is_zero() {
+        output = output.saturating_add(numerator_accum);
+        let Some(next) = numerator_accum.checked_mul(excess) else { return U256::MAX };
+        numerator_accum = next / (REQUEST_FEE_UPDATE_FRACTION * i);
+        i += U256::from(1);
+    }
+    output / REQUEST_FEE_UPDATE_FRACTION
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn request_fee() {
+        assert_eq!(system_request_fee(U256::ZERO), U256::from(1));
+        assert_eq!(system_request_fee(U256::from(17)), U256::from(2));
+        assert_eq!(system_request_fee(U256::from(100)), U256::from(357));
+        assert_eq!(system_request_fee(U256::MAX), U256::MAX);
+    }
+
+    #[test]
+    fn request_input_len() {
+        assert_eq!(system_request_input_len(WITHDRAWAL_REQUEST_PREDEPLOY_ADDRESS), Some(56));
+        assert_eq!(system_request_input_len(CONSOLIDATION_REQUEST_PREDEPLOY_ADDRESS), Some(96));
+        assert_eq!(system_request_input_len(Address::ZERO), None);
+    }
+}
@@ -471,6 +471,11 @@ fn preprocess_help(s: &str) -> Cow<'_, str> {
                 r"(rpc.max-tracing-requests <COUNT>\n.*\n.*\n.*\n.*\n.*)\[default: \d+\]",
                 r"$1[default: <NUM CPU CORES-2>]",
             ),
+            // Remove rpc.debug-workers and rpc.trace-workers default values
+            (
+                r"(rpc\.(?:debug|trace)-workers <COUNT>\n(?:.*\n)*?\s*)\[default: \d+\]",
+                r"$1[default: <NUM CPU CORES/2>]",
+            ),
             // Handle engine.reserved-cpu-cores dynamic default
             (
                 r"(engine\.reserved-cpu-cores.*)\[default: \d+\]",
@@ -54,6 +54,10 @@ export const opRethCliSidebar: SidebarItem = {
                         {
                             text: "op-reth db checksum static-file",
                             link: "/cli/op-reth/db/checksum/static-file"
+                        },
+                        {
+                            text: "op-reth db checksum manifest",
+                            link: "/cli/op-reth/db/checksum/manifest"
                         }
                     ]
                 },
@@ -76,6 +80,18 @@ export const opRethCliSidebar: SidebarItem = {
                         }
                     ]
                 },
+                {
+                    text: "op-reth db export",
+                    link: "/cli/op-reth/db/export"
+                },
+                {
+                    text: "op-reth db import",
+                    link: "/cli/op-reth/db/import"
+                },
+                {
+                    text: "op-reth db serve",
+                    link: "/cli/op-reth/db/serve"
+                },
                 {
                     text: "op-reth db drop",
                     link: "/cli/op-reth/db/drop"
@@ -114,6 +130,10 @@ export const opRethCliSidebar: SidebarItem = {
                         }
                     ]
                 },
+                {
+                    text: "op-reth db static-file-producer",
+                    link: "/cli/op-reth/db/static-file-producer"
+                },
                 {
                     text: "op-reth db version",
                     link: "/cli/op-reth/db/version"
@@ -163,6 +183,10 @@ export const opRethCliSidebar: SidebarItem = {
                                 {
                                     text: "op-reth db settings set storage_changesets",
                                     link: "/cli/op-reth/db/settings/set/storage_changesets"
+                                },
+                                {
+                                    text: "op-reth db settings set receipts_hot_blocks",
+                                    link: "/cli/op-reth/db/settings/set/receipts_hot_blocks"
                                 }
                             ]
                         }
@@ -207,6 +231,14 @@ export const opRethCliSidebar: SidebarItem = {
                         {
                             text: "op-reth stage dump merkle",
                             link: "/cli/op-reth/stage/dump/merkle"
+                        },
+                        {
+                            text: "op-reth stage dump account-history",
+                            link: "/cli/op-reth/stage/dump/account-history"
+                        },
+                        {
+                            text: "op-reth stage dump storage-history",
+                            link: "/cli/op-reth/stage/dump/storage-history"
                         }
                     ]
                 },
@@ -258,6 +290,44 @@ export const opRethCliSidebar: SidebarItem = {
                 {
                     text: "op-reth p2p enode",
                     link: "/cli/op-reth/p2p/enode"
+                },
+                {
+                    text: "op-reth p2p identity",
+                    link: "/cli/op-reth/p2p/identity",
+                    collapsed: true,
+                    items: [
+                        {
+                            text: "op-reth p2p identity show",
+                            link: "/cli/op-reth/p2p/identity/show"
+                        },
+                        {
+                            text: "op-reth p2p identity regenerate",
+                            link: "/cli/op-reth/p2p/identity/regenerate"
+                        },
+                        {
+                            text: "op-reth p2p identity import",
+                            link: "/cli/op-reth/p2p/identity/import"
+                        },
+                        {
+                            text: "op-reth p2p identity sign",
+                            link: "/cli/op-reth/p2p/identity/sign"
+                        },
+                        {
+                            text: "op-reth p2p identity verify",
+                            link: "/cli/op-reth/p2p/identity/verify"
+                        }
+                    ]
+                }
+            ]
+        },
+        {
+            text: "op-reth backfill",
+            link: "/cli/op-reth/backfill",
+            collapsed: true,
+            items: [
+                {
+                    text: "op-reth backfill receipts",
+                    link: "/cli/op-reth/backfill/receipts"
                 }
             ]
         },
@@ -272,6 +342,21 @@ export const opRethCliSidebar: SidebarItem = {
         {
             text: "op-reth re-execute",
             link: "/cli/op-reth/re-execute"
+        },
+        {
+            text: "op-reth static-files",
+            link: "/cli/op-reth/static-files",
+            collapsed: true,
+            items: [
+                {
+                    text: "op-reth static-files compact",
+                    link: "/cli/op-reth/static-files/compact"
+                },
+                {
+                    text: "op-reth static-files index",
+                    link: "/cli/op-reth/static-files/index"
+                }
+            ]
         }
     ]
 };
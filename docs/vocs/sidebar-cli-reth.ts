@@ -25,6 +25,10 @@ export const rethCliSidebar: SidebarItem = {
             text: "reth import-era",
             link: "/cli/reth/import-era"
         },
+        {
+            text: "reth import-receipts",
+            link: "/cli/reth/import-receipts"
+        },
         {
             text: "reth export-era",
             link: "/cli/reth/export-era"
@@ -58,6 +62,10 @@ export const rethCliSidebar: SidebarItem = {
                         {
                             text: "reth db checksum static-file",
                             link: "/cli/reth/db/checksum/static-file"
+                        },
+                        {
+                            text: "reth db checksum manifest",
+                            link: "/cli/reth/db/checksum/manifest"
                         }
                     ]
                 },
@@ -80,6 +88,18 @@ export const rethCliSidebar: SidebarItem = {
                         }
                     ]
                 },
+                {
+                    text: "reth db export",
+                    link: "/cli/reth/db/export"
+                },
+                {
+                    text: "reth db import",
+                    link: "/cli/reth/db/import"
+                },
+                {
+                    text: "reth db serve",
+                    link: "/cli/reth/db/serve"
+                },
                 {
                     text: "reth db drop",
                     link: "/cli/reth/db/drop"
@@ -118,6 +138,10 @@ export const rethCliSidebar: SidebarItem = {
                         }
                     ]
                 },
+                {
+                    text: "reth db static-file-producer",
+                    link: "/cli/reth/db/static-file-producer"
+                },
                 {
                     text: "reth db version",
                     link: "/cli/reth/db/version"
@@ -167,6 +191,10 @@ export const rethCliSidebar: SidebarItem = {
                                 {
                                     text: "reth db settings set storage_changesets",
                                     link: "/cli/reth/db/settings/set/storage_changesets"
+                                },
+                                {
+                                    text: "reth db settings set receipts_hot_blocks",
+                                    link: "/cli/reth/db/settings/set/receipts_hot_blocks"
                                 }
                             ]
                         }
@@ -215,6 +243,14 @@ export const rethCliSidebar: SidebarItem = {
                         {
                             text: "reth stage dump merkle",
                             link: "/cli/reth/stage/dump/merkle"
+                        },
+                        {
+                            text: "reth stage dump account-history",
+                            link: "/cli/reth/stage/dump/account-history"
+                        },
+                        {
+                            text: "reth stage dump storage-history",
+                            link: "/cli/reth/stage/dump/storage-history"
                         }
                     ]
                 },
@@ -235,6 +271,21 @@ export const rethCliSidebar: SidebarItem = {
                 }
             ]
         },
+        {
+            text: "reth debug",
+            link: "/cli/reth/debug",
+            collapsed: true,
+            items: [
+                {
+                    text: "reth debug replay-pipeline",
+                    link: "/cli/reth/debug/replay-pipeline"
+                },
+                {
+                    text: "reth debug fork-readiness",
+                    link: "/cli/reth/debug/fork-readiness"
+                }
+            ]
+        },
         {
             text: "reth p2p",
             link: "/cli/reth/p2p",
@@ -266,6 +317,44 @@ export const rethCliSidebar: SidebarItem = {
                 {
                     text: "reth p2p enode",
                     link: "/cli/reth/p2p/enode"
+                },
+                {
+                    text: "reth p2p identity",
+                    link: "/cli/reth/p2p/identity",
+                    collapsed: true,
+                    items: [
+                        {
+                            text: "reth p2p identity show",
+                            link: "/cli/reth/p2p/identity/show"
+                        },
+                        {
+                            text: "reth p2p identity regenerate",
+                            link: "/cli/reth/p2p/identity/regenerate"
+                        },
+                        {
+                            text: "reth p2p identity import",
+                            link: "/cli/reth/p2p/identity/import"
+                        },
+                        {
+                            text: "reth p2p identity sign",
+                            link: "/cli/reth/p2p/identity/sign"
+                        },
+                        {
+                            text: "reth p2p identity verify",
+                            link: "/cli/reth/p2p/identity/verify"
+                        }
+                    ]
+                }
+            ]
+        },
+        {
+            text: "reth backfill",
+            link: "/cli/reth/backfill",
+            collapsed: true,
+            items: [
+                {
+                    text: "reth backfill receipts",
+                    link: "/cli/reth/backfill/receipts"
                 }
             ]
         },
@@ -280,6 +369,25 @@ export const rethCliSidebar: SidebarItem = {
         {
             text: "reth re-execute",
             link: "/cli/reth/re-execute"
+        },
+        {
+            text: "reth static-files",
+            link: "/cli/reth/static-files",
+            collapsed: true,
+            items: [
+                {
+                    text: "reth static-files compact",
+                    link: "/cli/reth/static-files/compact"
+                },
+                {
+                    text: "reth static-files index",
+                    link: "/cli/reth/static-files/index"
+                }
+            ]
+        },
+        {
+            text: "reth rpc-serve",
+            link: "/cli/reth/rpc-serve"
         }
     ]
 };
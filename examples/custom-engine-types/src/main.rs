@@ -13,18 +13,23 @@
 //! running payload jobs.
 //!
 //! Once traits are implemented and custom types are defined, the [EngineTypes] trait can be
-//! implemented:
+//! implemented.
+//!
+//! The custom field is threaded through the whole engine API flow: it's validated when the
+//! attributes are received, it's part of the payload id, and it's written into the extra data of
+//! the built payload. The `main` function drives this flow over the authenticated engine API.
 
 #![warn(unused_crate_dependencies)]
 
 use alloy_eips::eip4895::Withdrawals;
 use alloy_genesis::Genesis;
-use alloy_primitives::{Address, B256};
+use alloy_primitives::{keccak256, Address, Bytes, B256};
 use alloy_rpc_types::{
     engine::{
         ExecutionData, ExecutionPayloadEnvelopeV2, ExecutionPayloadEnvelopeV3,
         ExecutionPayloadEnvelopeV4, ExecutionPayloadEnvelopeV5, ExecutionPayloadEnvelopeV6,
-        ExecutionPayloadV1, PayloadAttributes as EthPayloadAttributes, PayloadId,
+        ExecutionPayloadV1, ForkchoiceState, PayloadAttributes as EthPayloadAttributes, PayloadId,
+        PayloadStatusEnum,
     },
     Withdrawal,
 };
@@ -53,7 +58,7 @@ use reth_ethereum::{
     pool::{PoolTransaction, TransactionPool},
     primitives::{Block, SealedBlock},
     provider::{EthStorage, StateProviderFactory},
-    rpc::types::engine::ExecutionPayload,
+    rpc::{api::EngineApiClient, types::engine::ExecutionPayload},
     tasks::TaskManager,
     EthPrimitives, TransactionSigned,
 };
@@ -95,9 +100,14 @@ impl PayloadAttributes for CustomPayloadAttributes {
     }
 }
 
-/// New type around the payload builder attributes type
+/// Payload builder attributes that keep the custom field for the payload builder
 #[derive(Clone, Debug, PartialEq, Eq)]
-pub struct CustomPayloadBuilderAttributes(EthPayloadBuilderAttributes);
+pub struct CustomPayloadBuilderAttributes {
+    /// The default payload builder attributes
+    pub inner: EthPayloadBuilderAttributes,
+    /// The custom field of the payload attributes
+    pub custom: u64,
+}
 
 impl PayloadBuilderAttributes for CustomPayloadBuilderAttributes {
     type RpcPayloadAttributes = CustomPayloadAttributes;
@@ -108,40 +118,46 @@ impl PayloadBuilderAttributes for CustomPayloadBuilderAttributes {
         attributes: CustomPayloadAttributes,
         _version: u8,
     ) -> Result<Self, Infallible> {
-        Ok(Self(EthPayloadBuilderAttributes::new(parent, attributes.inner)))
+        let mut inner = EthPayloadBuilderAttributes::new(parent, attributes.inner);
+
+        // the payload id must also cover the custom field, otherwise attributes that only differ
+        // in the custom field would resolve to the same payload job
+        let hash = keccak256([inner.id.0.as_slice(), &attributes.custom.to_be_bytes()].concat());
+        inner.id = PayloadId::new(hash[..8].try_into().expect("sufficient length"));
+
+        Ok(Self { inner, custom: attributes.custom })
     }
 
     fn payload_id(&self) -> PayloadId {
-        self.0.id
+        self.inner.id
     }
 
     fn parent(&self) -> B256 {
-        self.0.parent
+        self.inner.parent
     }
 
     fn timestamp(&self) -> u64 {
-        self.0.timestamp
+        self.inner.timestamp
     }
 
     fn parent_beacon_block_root(&self) -> Option<B256> {
-        self.0.parent_beacon_block_root
+        self.inner.parent_beacon_block_root
     }
 
     fn suggested_fee_recipient(&self) -> Address {
-        self.0.suggested_fee_recipient
+        self.inner.suggested_fee_recipient
     }
 
     fn prev_randao(&self) -> B256 {
-        self.0.prev_randao
+        self.inner.prev_randao
     }
 
     fn withdrawals(&self) -> &Withdrawals {
-        &self.0.withdrawals
+        &self.inner.withdrawals
     }
 }
 
-/// Custom engine types - uses a custom payload attributes RPC type, but uses the default
-/// payload builder attributes type.
+/// Custom engine types - uses custom payload attributes, but the default execution payload types.
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
 #[non_exhaustive]
 pub struct CustomEngineTypes;
@@ -353,6 +369,25 @@ pub struct CustomPayloadBuilder<Pool, Client> {
     inner: reth_ethereum_payload_builder::EthereumPayloadBuilder<Pool, Client>,
 }
 
+impl<Pool, Client> CustomPayloadBuilder<Pool, Client>
+where
+    Client: Clone,
+    Pool: Clone,
+{
+    /// Returns the default payload builder configured for the given custom attributes.
+    fn builder_for(
+        &self,
+        attributes: &CustomPayloadBuilderAttributes,
+    ) -> reth_ethereum_payload_builder::EthereumPayloadBuilder<Pool, Client> {
+        let config = self
+            .inner
+            .builder_config()
+            .clone()
+            .with_extra_data(Bytes::copy_from_slice(&attributes.custom.to_be_bytes()));
+        self.inner.clone().with_builder_config(config)
+    }
+}
+
 impl<Pool, Client> PayloadBuilder for CustomPayloadBuilder<Pool, Client>
 where
     Client: StateProviderFactory + ChainSpecProvider<ChainSpec = ChainSpec> + Clone,
@@ -365,15 +400,16 @@ where
         &self,
         args: BuildArguments<Self::Attributes, Self::BuiltPayload>,
     ) -> Result<BuildOutcome<Self::BuiltPayload>, PayloadBuilderError> {
-        let BuildArguments { cached_reads, config, cancel, best_payload } = args;
+        let BuildArguments { cached_reads, config, cancel, interrupt, best_payload } = args;
         let PayloadConfig { parent_header, attributes } = config;
 
-        // This reuses the default EthereumPayloadBuilder to build the payload
-        // but any custom logic can be implemented here
-        self.inner.try_build(BuildArguments {
+        // This reuses the default EthereumPayloadBuilder to build the payload, with the custom
+        // field written into the extra data, but any custom logic can be implemented here
+        self.builder_for(&attributes).try_build(BuildArguments {
             cached_reads,
-            config: PayloadConfig { parent_header, attributes: attributes.0 },
+            config: PayloadConfig { parent_header, attributes: attributes.inner },
             cancel,
+            interrupt,
             best_payload,
         })
     }
@@ -383,7 +419,8 @@ where
         config: PayloadConfig<Self::Attributes>,
     ) -> Result<Self::BuiltPayload, PayloadBuilderError> {
         let PayloadConfig { parent_header, attributes } = config;
-        self.inner.build_empty_payload(PayloadConfig { parent_header, attributes: attributes.0 })
+        self.builder_for(&attributes)
+            .build_empty_payload(PayloadConfig { parent_header, attributes: attributes.inner })
     }
 }
 
@@ -414,5 +451,47 @@ async fn main() -> eyre::Result<()> {
 
     println!("Node started");
 
+    // drive the custom payload attributes through the authenticated engine API
+    let engine = handle.node.engine_http_client();
+    let genesis_hash = handle.node.chain_spec().genesis_hash();
+    let state = ForkchoiceState {
+        head_block_hash: genesis_hash,
+        safe_block_hash: genesis_hash,
+        finalized_block_hash: genesis_hash,
+    };
+    let attributes = CustomPayloadAttributes {
+        inner: EthPayloadAttributes {
+            timestamp: 1,
+            prev_randao: B256::ZERO,
+            suggested_fee_recipient: Address::ZERO,
+            withdrawals: Some(Vec::new()),
+            parent_beacon_block_root: None,
+        },
+        custom: 42,
+    };
+
+    // attributes with a zero custom field are rejected by the custom validator
+    let invalid = CustomPayloadAttributes { custom: 0, ..attributes.clone() };
+    let res =
+        EngineApiClient::<CustomEngineTypes>::fork_choice_updated_v2(&engine, state, Some(invalid))
+            .await;
+    assert!(res.is_err(), "zero custom field must be rejected");
+
+    let updated = EngineApiClient::<CustomEngineTypes>::fork_choice_updated_v2(
+        &engine,
+        state,
+        Some(attributes),
+    )
+    .await?;
+    assert_eq!(updated.payload_status.status, PayloadStatusEnum::Valid);
+    let payload_id = updated.payload_id.ok_or_else(|| eyre::eyre!("no payload id returned"))?;
+
+    let envelope =
+        EngineApiClient::<CustomEngineTypes>::get_payload_v2(&engine, payload_id).await?;
+    let extra_data = &envelope.execution_payload.as_v1().extra_data;
+    assert_eq!(extra_data.as_ref(), 42u64.to_be_bytes());
+
+    println!("Built payload with custom extra data {extra_data}");
+
     handle.node_exit_future.await
 }